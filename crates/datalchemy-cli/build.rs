@@ -0,0 +1,19 @@
+//! Compiles `proto/datalchemy/v1/generation.proto` for the `grpc` feature with
+//! protox (pure Rust), so no `protoc` is needed.
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    let proto_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../proto");
+    let proto = proto_dir.join("datalchemy/v1/generation.proto");
+    println!("cargo:rerun-if-changed={}", proto.display());
+    let descriptors = protox::compile([&proto], [&proto_dir]).expect("compile generation.proto");
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_fds(descriptors)
+        .expect("generate gRPC code");
+}
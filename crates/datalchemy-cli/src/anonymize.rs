@@ -0,0 +1,59 @@
+//! `datalchemy anonymize`: a schema.json safe to attach to a bug report.
+//!
+//! Identifiers are replaced by stable pseudonyms (see
+//! [`datalchemy_core::anonymize`]); the mapping file undoes it with
+//! `--restore` and stays with the user.
+
+use std::path::PathBuf;
+
+use datalchemy_core::{IdentifierMap, anonymize_schema, restore_schema};
+
+use crate::output::Output;
+use crate::plan::read_schema;
+use crate::workspace::{HeadlessWorkspace, write_json_atomic};
+use crate::{AnonymizeArgs, CliError};
+
+const DEFAULT_OUT: &str = "schema.anonymized.json";
+const DEFAULT_MAP: &str = "schema.anonymized.map.json";
+
+pub fn run_anonymize(args: AnonymizeArgs, output: &mut Output) -> Result<(), CliError> {
+    let schema_path = match args.schema {
+        Some(path) => path,
+        None => {
+            let workspace = HeadlessWorkspace::open(args.workspace)?;
+            let run_id = workspace.run_id(args.run_id.as_deref())?;
+            workspace.schema_path(&run_id)
+        }
+    };
+    let schema = read_schema(&schema_path)?;
+    let map_path = args.map.unwrap_or_else(|| PathBuf::from(DEFAULT_MAP));
+
+    if args.restore {
+        if !map_path.exists() {
+            return Err(CliError::InvalidConfig(format!(
+                "mapping file not found: {}",
+                map_path.display()
+            )));
+        }
+        let map: IdentifierMap = serde_json::from_str(&std::fs::read_to_string(&map_path)?)?;
+        let out = args.out.unwrap_or_else(|| PathBuf::from("schema.json"));
+        write_json_atomic(&out, &restore_schema(&schema, &map))?;
+        output.field("schema_path", out.display());
+        eprintln!("restored: {} name(s).", map.names.len());
+        return Ok(());
+    }
+
+    let (anonymized, map) = anonymize_schema(&schema);
+    let out = args.out.unwrap_or_else(|| PathBuf::from(DEFAULT_OUT));
+    write_json_atomic(&out, &anonymized)?;
+    write_json_atomic(&map_path, &map)?;
+    output.field("schema_path", out.display());
+    output.field("map_path", map_path.display());
+    output.set("names", map.names.len())?;
+    eprintln!(
+        "anonymized: {} name(s); keep {} private (it restores the original names).",
+        map.names.len(),
+        map_path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! `datalchemy bench`: generation throughput on a synthetic workload.
+//!
+//! Prints one line per format and thread count (rows/s, MiB/s). `--json`
+//! saves the results; `--baseline` compares rows/s with a saved run, e.g. the
+//! previous release, so regressions show up as negative changes.
+
+use datalchemy_generate::bench::{BenchOptions, BenchReport, BenchResult};
+
+use crate::output::Output;
+use crate::{BenchArgs, CliError};
+
+/// Run the benchmark and print the results.
+pub fn run_bench(args: BenchArgs, output: &mut Output) -> Result<(), CliError> {
+    let baseline: Option<BenchReport> = match &args.baseline {
+        Some(path) => Some(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+        None => None,
+    };
+    let defaults = BenchOptions::default();
+    let report = datalchemy_generate::bench::run_bench(&BenchOptions {
+        rows: args.rows,
+        threads: args.threads,
+        formats: args.formats,
+        out_dir: args.out_dir.unwrap_or(defaults.out_dir),
+        seed: args.seed,
+        keep_output: args.keep_output,
+    })?;
+
+    output.line(format!(
+        "datalchemy-generate {} ({} events, seed {})",
+        report.version, report.rows, report.seed
+    ));
+    for result in &report.results {
+        output.line(bench_line(result, baseline.as_ref()));
+    }
+    output.set("report", &report)?;
+    if let Some(path) = &args.json {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(())
+}
+
+/// One result, with the rows/s change against the same format and thread
+/// count of `baseline`.
+pub(crate) fn bench_line(result: &BenchResult, baseline: Option<&BenchReport>) -> String {
+    let mut line = format!(
+        "{:<14} {:>2} thread(s): {} rows in {} ms, {:.0} rows/s, {:.2} MiB/s",
+        result.format.as_str(),
+        result.threads,
+        result.rows,
+        result.duration_ms,
+        result.rows_per_sec,
+        result.bytes_per_sec / (1024.0 * 1024.0)
+    );
+    let previous = baseline.and_then(|baseline| {
+        baseline
+            .results
+            .iter()
+            .find(|known| known.format == result.format && known.threads == result.threads)
+            .map(|known| (baseline.version.as_str(), known.rows_per_sec))
+    });
+    if let Some((version, rows_per_sec)) = previous
+        && rows_per_sec > 0.0
+    {
+        let change = (result.rows_per_sec / rows_per_sec - 1.0) * 100.0;
+        line.push_str(&format!(" ({change:+.1}% vs {version})"));
+    }
+    line
+}
@@ -0,0 +1,449 @@
+//! `datalchemy classify`: semantic types and PII categories of schema columns.
+//!
+//! Column names and SQL types are matched first; when the workspace enables
+//! an LLM the model may refine those labels (see [`crate::llm::classifier`]).
+//! The result is `runs/<run_id>/classification.json`, read by `/plan new`
+//! (classified text columns get the generator of their semantic type) and by
+//! eval (classified PII columns the generation report does not tag are
+//! warnings).
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use datalchemy_core::{Column, DatabaseSchema};
+use serde::{Deserialize, Serialize};
+
+use crate::llm;
+use crate::llm::classifier::refine_classification;
+use crate::llm::planner::planner_model;
+use crate::output::Output;
+use crate::plan::read_schema;
+use crate::workspace::{HeadlessWorkspace, WorkspacePaths, write_json_atomic};
+use crate::{ClassifyArgs, CliError};
+
+pub const CLASSIFICATION_FILE: &str = "classification.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SemanticType {
+    Email,
+    Phone,
+    PersonName,
+    FirstName,
+    LastName,
+    Cpf,
+    Cnpj,
+    Rg,
+    BirthDate,
+    Gender,
+    Address,
+    PostalCode,
+    City,
+    State,
+    Country,
+    IpAddress,
+    Url,
+    CompanyName,
+    Income,
+}
+
+/// How a column identifies people: on its own, combined with other columns,
+/// or as information to protect rather than an identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiCategory {
+    DirectIdentifier,
+    QuasiIdentifier,
+    Sensitive,
+}
+
+impl PiiCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DirectIdentifier => "direct_identifier",
+            Self::QuasiIdentifier => "quasi_identifier",
+            Self::Sensitive => "sensitive",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassSource {
+    Heuristic,
+    Llm,
+}
+
+impl SemanticType {
+    pub const ALL: [SemanticType; 19] = [
+        Self::Email,
+        Self::Phone,
+        Self::PersonName,
+        Self::FirstName,
+        Self::LastName,
+        Self::Cpf,
+        Self::Cnpj,
+        Self::Rg,
+        Self::BirthDate,
+        Self::Gender,
+        Self::Address,
+        Self::PostalCode,
+        Self::City,
+        Self::State,
+        Self::Country,
+        Self::IpAddress,
+        Self::Url,
+        Self::CompanyName,
+        Self::Income,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Phone => "phone",
+            Self::PersonName => "person_name",
+            Self::FirstName => "first_name",
+            Self::LastName => "last_name",
+            Self::Cpf => "cpf",
+            Self::Cnpj => "cnpj",
+            Self::Rg => "rg",
+            Self::BirthDate => "birth_date",
+            Self::Gender => "gender",
+            Self::Address => "address",
+            Self::PostalCode => "postal_code",
+            Self::City => "city",
+            Self::State => "state",
+            Self::Country => "country",
+            Self::IpAddress => "ip_address",
+            Self::Url => "url",
+            Self::CompanyName => "company_name",
+            Self::Income => "income",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    pub fn pii_category(&self) -> Option<PiiCategory> {
+        match self {
+            Self::Email
+            | Self::Phone
+            | Self::PersonName
+            | Self::FirstName
+            | Self::LastName
+            | Self::Cpf
+            | Self::Cnpj
+            | Self::Rg
+            | Self::Address
+            | Self::IpAddress => Some(PiiCategory::DirectIdentifier),
+            Self::BirthDate
+            | Self::Gender
+            | Self::PostalCode
+            | Self::City
+            | Self::State
+            | Self::Country => Some(PiiCategory::QuasiIdentifier),
+            Self::Income => Some(PiiCategory::Sensitive),
+            Self::Url | Self::CompanyName => None,
+        }
+    }
+
+    /// Tag generators record for this kind of value (`generation_report.json`).
+    pub fn pii_tag(&self) -> Option<&'static str> {
+        match self {
+            Self::Email => Some("pii.email"),
+            Self::Phone => Some("pii.phone"),
+            Self::PersonName | Self::FirstName | Self::LastName => Some("pii.name"),
+            Self::Cpf => Some("pii.cpf"),
+            Self::Cnpj => Some("pii.cnpj"),
+            Self::Rg => Some("pii.rg"),
+            Self::Address => Some("pii.address"),
+            Self::PostalCode | Self::City | Self::State => Some("pii.location"),
+            Self::IpAddress => Some("pii.network"),
+            Self::BirthDate
+            | Self::Gender
+            | Self::Country
+            | Self::Url
+            | Self::CompanyName
+            | Self::Income => None,
+        }
+    }
+
+    /// Generator `/plan new` assigns to text columns of this type.
+    pub fn generator(&self) -> Option<&'static str> {
+        match self {
+            Self::Email => Some("semantic.br.email.safe"),
+            Self::Phone => Some("semantic.br.phone"),
+            Self::PersonName => Some("semantic.br.name"),
+            Self::FirstName => Some("faker.name.raw.FirstName"),
+            Self::LastName => Some("faker.name.raw.LastName"),
+            Self::Cpf => Some("semantic.br.cpf"),
+            Self::Cnpj => Some("semantic.br.cnpj"),
+            Self::Rg => Some("semantic.br.rg"),
+            Self::Address => Some("semantic.br.address"),
+            Self::PostalCode => Some("semantic.br.cep"),
+            Self::City => Some("semantic.br.city"),
+            Self::State => Some("semantic.br.uf"),
+            Self::Country => Some("faker.address.raw.CountryName"),
+            Self::IpAddress => Some("semantic.br.ip"),
+            Self::Url => Some("semantic.br.url"),
+            Self::CompanyName => Some("semantic.br.company.name"),
+            Self::BirthDate | Self::Gender | Self::Income => None,
+        }
+    }
+}
+
+/// `classification.json` of a run: only labeled columns are listed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Classification {
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_fingerprint: Option<String>,
+    pub created_at: String,
+    /// `provider/model` that refined the heuristic labels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm: Option<String>,
+    pub columns: Vec<ColumnClass>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnClass {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub semantic_type: SemanticType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pii: Option<PiiCategory>,
+    pub source: ClassSource,
+}
+
+impl ColumnClass {
+    pub fn new(
+        schema: &str,
+        table: &str,
+        column: &str,
+        semantic_type: SemanticType,
+        source: ClassSource,
+    ) -> Self {
+        Self {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            column: column.to_string(),
+            semantic_type,
+            pii: semantic_type.pii_category(),
+            source,
+        }
+    }
+
+    pub fn path(&self) -> String {
+        format!("{}.{}.{}", self.schema, self.table, self.column)
+    }
+}
+
+impl Classification {
+    pub fn column(&self, schema: &str, table: &str, column: &str) -> Option<&ColumnClass> {
+        self.columns
+            .iter()
+            .find(|class| class.schema == schema && class.table == table && class.column == column)
+    }
+
+    /// PII tag of each tagged column, by `schema.table.column`, for the eval
+    /// PII scan.
+    pub fn pii_columns(&self) -> BTreeMap<String, String> {
+        self.columns
+            .iter()
+            .filter_map(|class| {
+                class
+                    .semantic_type
+                    .pii_tag()
+                    .map(|tag| (class.path(), tag.to_string()))
+            })
+            .collect()
+    }
+
+    /// One-line outcome for `/classify` and `classify`.
+    pub fn summary(&self) -> String {
+        let pii = self
+            .columns
+            .iter()
+            .filter(|class| class.pii.is_some())
+            .count();
+        let llm = self
+            .columns
+            .iter()
+            .filter(|class| class.source == ClassSource::Llm)
+            .count();
+        format!(
+            "{} columns labeled, {pii} PII, {llm} from {}",
+            self.columns.len(),
+            self.llm.as_deref().unwrap_or("llm (disabled)")
+        )
+    }
+}
+
+/// Label the columns of `schema` by name and type.
+pub fn heuristic_classification(run_id: &str, schema: &DatabaseSchema) -> Classification {
+    let mut columns = Vec::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            for column in &table.columns {
+                if let Some(semantic_type) = classify_column(column) {
+                    columns.push(ColumnClass::new(
+                        &db_schema.name,
+                        &table.name,
+                        &column.name,
+                        semantic_type,
+                        ClassSource::Heuristic,
+                    ));
+                }
+            }
+        }
+    }
+    Classification {
+        run_id: run_id.to_string(),
+        schema_fingerprint: schema.schema_fingerprint.clone(),
+        created_at: Utc::now().to_rfc3339(),
+        llm: None,
+        columns,
+    }
+}
+
+/// Semantic type named by a `@pii:<type>` comment annotation (`email`,
+/// `cpf`, `person_name`...), else implied by the column name (Portuguese or
+/// English) or type.
+pub fn classify_column(column: &Column) -> Option<SemanticType> {
+    if let Some(semantic_type) = column
+        .hints()
+        .pii
+        .iter()
+        .find_map(|kind| SemanticType::parse(kind))
+    {
+        return Some(semantic_type);
+    }
+    let name = column.name.to_lowercase();
+    let is = |names: &[&str]| names.contains(&name.as_str());
+    let has = |parts: &[&str]| parts.iter().any(|part| name.contains(part));
+
+    // Keys point at other rows, whatever entity they name.
+    if name == "id" || name.ends_with("_id") {
+        return None;
+    }
+    Some(if has(&["email", "e_mail"]) {
+        SemanticType::Email
+    } else if has(&["phone", "telefone", "celular"]) {
+        SemanticType::Phone
+    } else if has(&["cpf"]) {
+        SemanticType::Cpf
+    } else if has(&["cnpj"]) {
+        SemanticType::Cnpj
+    } else if is(&["rg"]) || name.starts_with("rg_") || name.ends_with("_rg") {
+        SemanticType::Rg
+    } else if has(&["empresa", "company"]) || is(&["razao_social", "nome_fantasia"]) {
+        SemanticType::CompanyName
+    } else if is(&["primeiro_nome", "first_name", "firstname"]) {
+        SemanticType::FirstName
+    } else if is(&["sobrenome", "ultimo_nome", "last_name", "lastname"]) {
+        SemanticType::LastName
+    } else if is(&["nome", "name", "nome_completo", "full_name", "fullname"]) {
+        SemanticType::PersonName
+    } else if has(&["nascimento", "birth"]) || is(&["dob"]) {
+        SemanticType::BirthDate
+    } else if is(&["sexo", "genero", "gender"]) {
+        SemanticType::Gender
+    } else if has(&["endereco", "address"]) || is(&["logradouro"]) {
+        SemanticType::Address
+    } else if is(&["cep", "zip", "zipcode", "zip_code", "codigo_postal"]) {
+        SemanticType::PostalCode
+    } else if is(&["cidade", "city", "municipio"]) {
+        SemanticType::City
+    } else if is(&["estado", "state", "uf"]) {
+        SemanticType::State
+    } else if is(&["pais", "country"]) {
+        SemanticType::Country
+    } else if column.column_type.udt_name == "inet" || is(&["ip"]) || name.ends_with("_ip") {
+        SemanticType::IpAddress
+    } else if has(&["url", "website"]) {
+        SemanticType::Url
+    } else if has(&["salario", "salary", "renda", "income"]) {
+        SemanticType::Income
+    } else {
+        return None;
+    })
+}
+
+/// `runs/<run_id>/classification.json`, when the run was classified.
+pub fn read_classification(
+    paths: &WorkspacePaths,
+    run_id: &str,
+) -> Result<Option<Classification>, CliError> {
+    let path = paths.runs_dir.join(run_id).join(CLASSIFICATION_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// Heuristic labels, refined by `llm` (client plus provider and model) when
+/// given, written to `runs/<run_id>/classification.json`.
+pub fn write_classification(
+    paths: &WorkspacePaths,
+    run_id: &str,
+    schema: &DatabaseSchema,
+    llm: Option<(&dyn llm::LlmClient, (&str, &str))>,
+) -> Result<Classification, CliError> {
+    let mut classification = heuristic_classification(run_id, schema);
+    if let Some((client, source)) = llm {
+        refine_classification(client, source, schema, &mut classification)?;
+    }
+    write_json_atomic(
+        &paths.runs_dir.join(run_id).join(CLASSIFICATION_FILE),
+        &classification,
+    )?;
+    Ok(classification)
+}
+
+pub fn run_classify(args: ClassifyArgs, output: &mut Output) -> Result<(), CliError> {
+    let workspace = HeadlessWorkspace::open(args.workspace)?;
+    let run_id = workspace.run_id(args.run_id.as_deref())?;
+    let schema = read_schema(&workspace.schema_path(&run_id))?;
+
+    let refiner = planner_model(&workspace.settings).filter(|_| !args.heuristic);
+    let classification = match refiner {
+        Some((provider, model)) => {
+            let client = llm::client_for(
+                &provider,
+                workspace.settings.llm_base_url.as_deref(),
+                &BTreeMap::new(),
+            )?;
+            write_classification(
+                &workspace.paths,
+                &run_id,
+                &schema,
+                Some((client.as_ref(), (provider.as_str(), &model))),
+            )?
+        }
+        None => write_classification(&workspace.paths, &run_id, &schema, None)?,
+    };
+
+    output.field("run_id", &run_id);
+    output.field(
+        "classification_path",
+        workspace
+            .paths
+            .runs_dir
+            .join(&run_id)
+            .join(CLASSIFICATION_FILE)
+            .display(),
+    );
+    output.set("columns", classification.columns.len())?;
+    output.set(
+        "pii_columns",
+        classification
+            .columns
+            .iter()
+            .filter(|class| class.pii.is_some())
+            .count(),
+    )?;
+    eprintln!("classification: {}.", classification.summary());
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! `datalchemy.toml`: project defaults for the subcommands.
+//!
+//! Read from `--config <path>` or, when present, `datalchemy.toml` in the
+//! current directory. A value from the file replaces the default of the
+//! matching flag; flags passed on the command line always win. Relative paths
+//! are resolved against the directory of the file.
+//!
+//! ```toml
+//! [introspect]
+//! run_dir = "runs"
+//! schemas = ["crm"]
+//! strict = false
+//!
+//! [generate]
+//! out_dir = "out"
+//! format = "sqlite"
+//! scale = 0.1
+//!
+//! [eval]
+//! strict = true
+//! junit = true
+//! thresholds = "limites.json"
+//! ```
+//!
+//! `pipeline` takes `run_dir`/`schemas` from `[introspect]`, `format`/`scale`
+//! from `[generate]` and `[eval]` as is.
+
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use datalchemy_generate::OutputFormat;
+use serde::Deserialize;
+
+use crate::{CliError, Command};
+
+pub(crate) const CONFIG_FILE: &str = "datalchemy.toml";
+
+/// Contents of `datalchemy.toml`; every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ProjectConfig {
+    introspect: IntrospectDefaults,
+    generate: GenerateDefaults,
+    eval: EvalDefaults,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct IntrospectDefaults {
+    run_dir: Option<PathBuf>,
+    schemas: Vec<String>,
+    /// Fail on foreign key cycles.
+    strict: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GenerateDefaults {
+    out_dir: Option<PathBuf>,
+    format: Option<OutputFormat>,
+    /// Multiplier of the plan's target rows.
+    scale: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct EvalDefaults {
+    /// Fail on violations (`false` is `--no-strict`).
+    strict: Option<bool>,
+    junit: Option<bool>,
+    thresholds: Option<PathBuf>,
+}
+
+impl ProjectConfig {
+    /// Load `explicit`, else `datalchemy.toml` in the current directory if it
+    /// exists.
+    pub(crate) fn load(explicit: Option<&Path>) -> Result<Option<Self>, CliError> {
+        let path = match explicit {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let path = PathBuf::from(CONFIG_FILE);
+                if !path.is_file() {
+                    return Ok(None);
+                }
+                path
+            }
+        };
+        let content = std::fs::read_to_string(&path).map_err(|err| {
+            CliError::InvalidConfig(format!("cannot read {}: {err}", path.display()))
+        })?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&content, base)
+            .map(Some)
+            .map_err(|err| CliError::InvalidConfig(format!("{}: {err}", path.display())))
+    }
+
+    /// Parse a config file whose relative paths are relative to `base`.
+    fn parse(content: &str, base: &Path) -> Result<Self, String> {
+        let mut config: Self = toml::from_str(content).map_err(|err| err.to_string())?;
+        if let Some(scale) = config.generate.scale
+            && !(scale.is_finite() && scale > 0.0)
+        {
+            return Err(format!("generate.scale must be positive, got {scale}"));
+        }
+        for path in [
+            &mut config.introspect.run_dir,
+            &mut config.generate.out_dir,
+            &mut config.eval.thresholds,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Replace the defaults of `command` with the values of the file, except
+    /// for flags present on the command line (`matches`).
+    pub(crate) fn apply(&self, command: &mut Command, matches: &ArgMatches) {
+        let Some((_, matches)) = matches.subcommand() else {
+            return;
+        };
+        let unset = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+        let introspect = &self.introspect;
+        let generate = &self.generate;
+        let eval = &self.eval;
+        match command {
+            Command::Introspect(args) => {
+                if unset("run_dir")
+                    && let Some(run_dir) = &introspect.run_dir
+                {
+                    args.run_dir = run_dir.clone();
+                }
+                if unset("schema") && !introspect.schemas.is_empty() {
+                    args.schema = introspect.schemas.clone();
+                }
+                if unset("strict")
+                    && let Some(strict) = introspect.strict
+                {
+                    args.strict = strict;
+                }
+            }
+            Command::Generate(args) => {
+                if unset("out_dir")
+                    && let Some(out_dir) = &generate.out_dir
+                {
+                    args.out_dir = out_dir.clone();
+                }
+                if unset("format")
+                    && let Some(format) = generate.format
+                {
+                    args.format = format;
+                }
+                if unset("scale")
+                    && let Some(scale) = generate.scale
+                {
+                    args.scale = scale;
+                }
+            }
+            Command::Eval(args) => {
+                if unset("no_strict")
+                    && let Some(strict) = eval.strict
+                {
+                    args.no_strict = !strict;
+                }
+                if unset("junit")
+                    && let Some(junit) = eval.junit
+                {
+                    args.junit = junit;
+                }
+                if unset("thresholds") && eval.thresholds.is_some() {
+                    args.thresholds = eval.thresholds.clone();
+                }
+            }
+            Command::Pipeline(args) => {
+                if unset("run_dir")
+                    && let Some(run_dir) = &introspect.run_dir
+                {
+                    args.run_dir = run_dir.clone();
+                }
+                if unset("schema") && !introspect.schemas.is_empty() {
+                    args.schema = introspect.schemas.clone();
+                }
+                if unset("format")
+                    && let Some(format) = generate.format
+                {
+                    args.format = format;
+                }
+                if unset("scale")
+                    && let Some(scale) = generate.scale
+                {
+                    args.scale = scale;
+                }
+                if unset("no_strict")
+                    && let Some(strict) = eval.strict
+                {
+                    args.no_strict = !strict;
+                }
+                if unset("junit")
+                    && let Some(junit) = eval.junit
+                {
+                    args.junit = junit;
+                }
+                if unset("thresholds") && eval.thresholds.is_some() {
+                    args.thresholds = eval.thresholds.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! `datalchemy diff <run_a> <run_b>`: structural changes between two schemas.
+//!
+//! Each side is a workspace run id or a path to a `schema.json`, so the schema
+//! a plan was written against can be checked against a fresh introspection.
+//! Text mode prints one line per change (`-` removed, `+` added, `~` changed)
+//! in `schema.table` order, then the enums; `--message-format json` records the
+//! [`SchemaDiff`] as `diff`. The diff itself is
+//! [`datalchemy_core::diff_schemas`].
+
+use std::path::Path;
+
+use datalchemy_core::{DatabaseSchema, SchemaDiff, constraint_signature, diff_schemas};
+
+use crate::output::Output;
+use crate::plan::read_schema;
+use crate::tui::compare::column_label;
+use crate::workspace::HeadlessWorkspace;
+use crate::{CliError, DiffArgs};
+
+pub fn run_diff(args: DiffArgs, output: &mut Output) -> Result<(), CliError> {
+    let before = load_side(&args.run_a, &args.workspace)?;
+    let after = load_side(&args.run_b, &args.workspace)?;
+    let diff = diff_schemas(&before, &after);
+
+    for line in diff_lines(&diff) {
+        output.line(line);
+    }
+    let tables = diff.changed_tables().len();
+    let enums = diff.enums_added.len() + diff.enums_removed.len() + diff.enums_modified.len();
+    output.set("diff", &diff)?;
+    output.set("tables_changed", tables)?;
+    output.set("enums_changed", enums)?;
+    if diff.is_empty() {
+        eprintln!("no structural changes: {} -> {}", args.run_a, args.run_b);
+    } else {
+        eprintln!(
+            "{} -> {}: {tables} table(s) and {enums} enum(s) changed",
+            args.run_a, args.run_b
+        );
+    }
+    Ok(())
+}
+
+/// `side` as a schema.json path when it names a file, else as a run id of the
+/// workspace.
+fn load_side(side: &str, workspace: &Path) -> Result<DatabaseSchema, CliError> {
+    let path = Path::new(side);
+    if path.is_file() {
+        return read_schema(path);
+    }
+    let workspace = HeadlessWorkspace::open(workspace.to_path_buf())?;
+    let run_id = workspace.run_id(Some(side))?;
+    read_schema(&workspace.schema_path(&run_id))
+}
+
+fn diff_lines(diff: &SchemaDiff) -> Vec<String> {
+    let mut lines = Vec::new();
+    for name in diff.changed_tables() {
+        if diff.tables_removed.iter().any(|table| table == name) {
+            lines.push(format!("- table {name}"));
+        } else if diff.tables_added.iter().any(|table| table == name) {
+            lines.push(format!("+ table {name}"));
+        } else if let Some(table) = diff.table(name) {
+            lines.push(format!("~ table {name}"));
+            for column in &table.columns_removed {
+                lines.push(format!("  - {}", column_label(column)));
+            }
+            for column in &table.columns_modified {
+                let changes: Vec<&str> = column
+                    .changes
+                    .iter()
+                    .map(|change| change.as_str())
+                    .collect();
+                lines.push(format!(
+                    "  ~ {} -> {} ({})",
+                    column_label(&column.before),
+                    column_label(&column.after),
+                    changes.join(", ")
+                ));
+            }
+            for column in &table.columns_added {
+                lines.push(format!("  + {}", column_label(column)));
+            }
+            for constraint in &table.constraints_removed {
+                lines.push(format!("  - {}", constraint_signature(constraint)));
+            }
+            for constraint in &table.constraints_added {
+                lines.push(format!("  + {}", constraint_signature(constraint)));
+            }
+        }
+    }
+    for name in &diff.enums_removed {
+        lines.push(format!("- enum {name}"));
+    }
+    for name in &diff.enums_added {
+        lines.push(format!("+ enum {name}"));
+    }
+    for en in &diff.enums_modified {
+        let labels: Vec<String> = en
+            .labels_removed
+            .iter()
+            .map(|label| format!("-{label}"))
+            .chain(en.labels_added.iter().map(|label| format!("+{label}")))
+            .collect();
+        let labels = if labels.is_empty() {
+            "reordered".to_string()
+        } else {
+            labels.join(", ")
+        };
+        lines.push(format!("~ enum {} ({labels})", en.name));
+    }
+    lines
+}
@@ -0,0 +1,237 @@
+//! `datalchemy eval`: headless evaluation of a run or dataset directory.
+//!
+//! Meant for CI: the command exits nonzero when violations are found (unless
+//! `--no-strict`), and `--junit` writes `junit.xml` (one test case per check)
+//! next to `metrics.json`, even when the evaluation fails. `--live-sample`
+//! samples the source database into `source_sample.json` (see
+//! [`crate::live_sample`]) and scores the dataset's realism against it.
+//! `--workspace <dir>` evaluates a workspace output against the schema and
+//! plan of its manifest, into `eval/<eval_id>` (with `eval_manifest.json`),
+//! and appends the result to the eval history; the PII scan also checks the
+//! columns of the run's `classification.json`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use datalchemy_core::DatabaseSchema;
+use datalchemy_eval::history::HISTORY_FILE;
+use datalchemy_eval::{
+    EvalThresholds, EvaluateOptions, EvaluationEngine, EvaluationResult, HistoryEntry,
+    append_history,
+};
+use datalchemy_plan::Plan;
+
+use crate::classify::read_classification;
+use crate::generate::load_plan;
+use crate::live_sample::sample_source;
+use crate::output::Output;
+use crate::plan::read_schema;
+use crate::workspace::{
+    ArtifactStatus, EvalManifest, HeadlessWorkspace, new_artifact_id, write_json_atomic,
+};
+use crate::{CliError, EvalArgs};
+
+/// Evaluate `--dataset` against the schema (and the plan, when given).
+pub fn run_eval(
+    args: EvalArgs,
+    runtime: &tokio::runtime::Runtime,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    if let Some(root) = &args.workspace {
+        return eval_in_workspace(&args, root, runtime, output);
+    }
+    let (Some(schema_path), Some(dataset)) = (&args.schema, &args.dataset) else {
+        return Err(CliError::InvalidConfig(
+            "--schema and --dataset are required without --workspace".to_string(),
+        ));
+    };
+    let schema: DatabaseSchema = serde_json::from_str(&std::fs::read_to_string(schema_path)?)?;
+    let plan = match &args.plan {
+        Some(plan_path) => Some(load_plan(plan_path, &schema)?),
+        None => None,
+    };
+    let out_dir = args.out_dir.as_ref().unwrap_or(dataset);
+    let result = evaluate(
+        &args,
+        runtime,
+        &schema,
+        plan.as_ref(),
+        dataset,
+        out_dir,
+        BTreeMap::new(),
+        output,
+    )?;
+    print_result(&result, output)
+}
+
+/// `--workspace`: evaluate `out/<out_id>` into `eval/<eval_id>`.
+fn eval_in_workspace(
+    args: &EvalArgs,
+    root: &Path,
+    runtime: &tokio::runtime::Runtime,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    let workspace = HeadlessWorkspace::open(root.to_path_buf())?;
+    let out_id = workspace.out_id(args.out_id.as_deref())?;
+    let out_manifest = workspace.out_manifest(&out_id)?;
+    let schema = read_schema(&workspace.schema_path(&out_manifest.schema_run_id))?;
+    let plan = load_plan(&workspace.plan_path(&out_manifest.plan_id), &schema)?;
+    let pii_columns = read_classification(&workspace.paths, &out_manifest.schema_run_id)?
+        .map(|classification| classification.pii_columns())
+        .unwrap_or_default();
+
+    let eval_id = args
+        .eval_id
+        .clone()
+        .unwrap_or_else(|| new_artifact_id("eval"));
+    let eval_dir = workspace.paths.eval_dir.join(&eval_id);
+    if eval_dir.exists() {
+        return Err(CliError::InvalidConfig(format!(
+            "eval directory already exists: {}",
+            eval_dir.display()
+        )));
+    }
+    std::fs::create_dir_all(&eval_dir)?;
+
+    let mut manifest = EvalManifest {
+        eval_id: eval_id.clone(),
+        status: ArtifactStatus::Running,
+        out_id: out_id.clone(),
+        checks_enabled: checks_enabled(args),
+        artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
+        cli_version: crate::workspace::CLI_VERSION.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        finished_at: None,
+    };
+    let manifest_path = eval_dir.join("eval_manifest.json");
+    write_json_atomic(&manifest_path, &manifest)?;
+
+    let dataset = workspace.paths.out_dir.join(&out_id);
+    output.field("eval_id", &eval_id);
+    let result = evaluate(
+        args,
+        runtime,
+        &schema,
+        Some(&plan),
+        &dataset,
+        &eval_dir,
+        pii_columns,
+        output,
+    );
+    manifest.finished_at = Some(Utc::now().to_rfc3339());
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            manifest.status = ArtifactStatus::Error;
+            write_json_atomic(&manifest_path, &manifest)?;
+            return Err(err);
+        }
+    };
+    write_json_atomic(&eval_dir.join("evaluation_report.json"), &result.metrics)?;
+    manifest.status = ArtifactStatus::Ok;
+    write_json_atomic(&manifest_path, &manifest)?;
+    let entry = HistoryEntry::new(
+        eval_id.clone(),
+        Some(out_id),
+        manifest.finished_at.clone().unwrap_or_default(),
+        result.metrics.clone(),
+    );
+    append_history(&workspace.paths.eval_dir.join(HISTORY_FILE), &entry)?;
+
+    print_result(&result, output)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn evaluate(
+    args: &EvalArgs,
+    runtime: &tokio::runtime::Runtime,
+    schema: &DatabaseSchema,
+    plan: Option<&Plan>,
+    dataset: &Path,
+    out_dir: &Path,
+    pii_columns: BTreeMap<String, String>,
+    output: &mut Output,
+) -> Result<EvaluationResult, CliError> {
+    let thresholds = match &args.thresholds {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+        None => EvalThresholds::default(),
+    };
+
+    let source_sample = if args.live_sample {
+        let conn = match &args.conn {
+            Some(conn) => conn.clone(),
+            None => std::env::var("DATABASE_URL").map_err(|_| {
+                CliError::InvalidConfig("--live-sample needs --conn or DATABASE_URL".to_string())
+            })?,
+        };
+        let sample = runtime.block_on(sample_source(&conn, schema, plan, args.live_sample_rows))?;
+        std::fs::create_dir_all(out_dir)?;
+        let path = out_dir.join("source_sample.json");
+        std::fs::write(&path, serde_json::to_vec_pretty(&sample)?)?;
+        output.field("source_sample_path", path.display());
+        Some(path)
+    } else {
+        args.source_sample.clone()
+    };
+    let engine = EvaluationEngine::new(EvaluateOptions {
+        strict: !args.no_strict,
+        thresholds,
+        write_junit: args.junit,
+        out_dir: Some(PathBuf::from(out_dir)),
+        file_patterns: args.pattern.clone(),
+        streaming: args.streaming,
+        sample_rows: args.sample_rows,
+        spill_dir: args.spill_dir.clone(),
+        top_slowest: args.top_slowest,
+        temporal_checks: !args.no_temporal_checks,
+        source_sample,
+        pii_columns,
+        ..EvaluateOptions::default()
+    });
+
+    Ok(match plan {
+        Some(plan) => engine.run(schema, plan, dataset)?,
+        None => engine.run_dataset(schema, dataset)?,
+    })
+}
+
+/// Checks recorded in `eval_manifest.json`, named as in `/eval`.
+fn checks_enabled(args: &EvalArgs) -> Vec<String> {
+    let mut checks = vec![
+        "fk_consistency".to_string(),
+        "nullability".to_string(),
+        "uniqueness".to_string(),
+        "pii_scan".to_string(),
+    ];
+    if !args.no_temporal_checks {
+        checks.push("temporal".to_string());
+    }
+    if args.thresholds.is_some() {
+        checks.push("thresholds".to_string());
+    }
+    if args.streaming {
+        checks.push("streaming".to_string());
+    }
+    if args.sample_rows.is_some() {
+        checks.push("sampled".to_string());
+    }
+    if args.live_sample || args.source_sample.is_some() {
+        checks.push("realism".to_string());
+    }
+    checks
+}
+
+fn print_result(result: &EvaluationResult, output: &mut Output) -> Result<(), CliError> {
+    output.field("metrics_path", result.metrics_path.display());
+    output.field("report_path", result.report_path.display());
+    if let Some(path) = &result.junit_path {
+        output.field("junit_path", path.display());
+    }
+    output.set("violations", &result.violations)?;
+    output.set("warnings", &result.metrics.warnings)?;
+    if !result.violations.is_empty() {
+        eprintln!("violations found: {} (not strict)", result.violations.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,751 @@
+//! `datalchemy explain [<code>]` and `/explain`: causes and remediation of
+//! the stable error codes.
+//!
+//! Errors print as `error[DL110]: <message>`; `DL1xx` are CLI errors
+//! ([`CliError::code`]) and `DL2xx` generation errors
+//! ([`datalchemy_generate::GenerationError::code`]). Explanations are in the
+//! workspace `language` (en or pt_BR) unless `--lang` is given.
+
+use crate::output::Output;
+use crate::workspace::{Language, WorkspacePaths, load_or_create_settings};
+use crate::{CliError, ExplainArgs};
+
+/// Explanation of one code in one language.
+pub struct Text {
+    pub title: &'static str,
+    pub causes: &'static [&'static str],
+    pub fixes: &'static [&'static str],
+}
+
+pub struct Explanation {
+    pub code: &'static str,
+    pub en: Text,
+    pub pt_br: Text,
+}
+
+impl Explanation {
+    pub fn text(&self, language: Language) -> &Text {
+        match language {
+            Language::En => &self.en,
+            Language::PtBr => &self.pt_br,
+        }
+    }
+
+    /// Title, causes and remediation steps as printable lines.
+    pub fn lines(&self, language: Language) -> Vec<String> {
+        let text = self.text(language);
+        let (causes, fixes) = match language {
+            Language::En => ("possible causes:", "what to do:"),
+            Language::PtBr => ("causas provaveis:", "o que fazer:"),
+        };
+        let mut lines = vec![format!("{}: {}", self.code, text.title), causes.to_string()];
+        lines.extend(text.causes.iter().map(|cause| format!("  - {cause}")));
+        lines.push(fixes.to_string());
+        lines.extend(
+            text.fixes
+                .iter()
+                .enumerate()
+                .map(|(idx, fix)| format!("  {}. {fix}", idx + 1)),
+        );
+        lines
+    }
+}
+
+/// Explanation of `code` (case-insensitive).
+pub fn explanation(code: &str) -> Option<&'static Explanation> {
+    EXPLANATIONS
+        .iter()
+        .find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
+
+pub fn run_explain(args: ExplainArgs, output: &mut Output) -> Result<(), CliError> {
+    let language = match args.lang.as_deref() {
+        Some(value) => Language::parse(value).ok_or_else(|| {
+            CliError::InvalidConfig(format!("invalid language: {value} (en|pt_BR)"))
+        })?,
+        None => workspace_language(WorkspacePaths::new(args.workspace)),
+    };
+    let Some(code) = args.code else {
+        output.set(
+            "codes",
+            EXPLANATIONS
+                .iter()
+                .map(|explanation| explanation.code)
+                .collect::<Vec<_>>(),
+        )?;
+        for explanation in EXPLANATIONS {
+            output.line(format!(
+                "{}  {}",
+                explanation.code,
+                explanation.text(language).title
+            ));
+        }
+        return Ok(());
+    };
+    let explanation = explanation(&code)
+        .ok_or_else(|| CliError::InvalidConfig(format!("unknown error code: {code}")))?;
+    let text = explanation.text(language);
+    output.set("code", explanation.code)?;
+    output.set("title", text.title)?;
+    output.set("causes", text.causes)?;
+    output.set("fixes", text.fixes)?;
+    for line in explanation.lines(language) {
+        output.line(line);
+    }
+    Ok(())
+}
+
+/// Language of an existing workspace; `en` without one (nothing is created).
+fn workspace_language(paths: WorkspacePaths) -> Language {
+    if !paths.settings_path().exists() {
+        return Language::En;
+    }
+    load_or_create_settings(&paths)
+        .map(|settings| settings.language)
+        .unwrap_or_default()
+}
+
+pub const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "DL101",
+        en: Text {
+            title: "generator registry error",
+            causes: &[
+                "the plan references a generator or transform id that is not registered",
+                "generator params do not match the declared params",
+            ],
+            fixes: &[
+                "list the registry ids in the `/plan rules` form (type `prefix?` to search)",
+                "fix the id or params in plan.json and run `datalchemy plan validate`",
+            ],
+        },
+        pt_br: Text {
+            title: "erro no registry de geradores",
+            causes: &[
+                "o plano usa um id de gerador ou transform que nao esta registrado",
+                "os params do gerador nao batem com os params declarados",
+            ],
+            fixes: &[
+                "liste os ids do registry no formulario de `/plan rules` (digite `prefixo?` para buscar)",
+                "corrija o id ou os params no plan.json e rode `datalchemy plan validate`",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL102",
+        en: Text {
+            title: "invalid schema",
+            causes: &[
+                "schema.json was edited by hand or comes from an older version",
+                "the schema has duplicated tables/columns or dangling foreign keys",
+                "a table given with `--tables` is not in the run's schema or is ambiguous",
+            ],
+            fixes: &[
+                "introspect again (`datalchemy introspect` or `/introspect`)",
+                "check the schema warnings printed by introspect and fix them in the database",
+            ],
+        },
+        pt_br: Text {
+            title: "schema invalido",
+            causes: &[
+                "o schema.json foi editado a mao ou vem de uma versao antiga",
+                "o schema tem tabelas/colunas duplicadas ou FKs apontando para o nada",
+                "uma tabela de `--tables` nao existe no schema do run ou e ambigua",
+            ],
+            fixes: &[
+                "rode o introspect de novo (`datalchemy introspect` ou `/introspect`)",
+                "confira os avisos do schema impressos pelo introspect e corrija no banco",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL103",
+        en: Text {
+            title: "database error",
+            causes: &[
+                "the connection string is wrong or the database is unreachable",
+                "the user lacks privileges to read the catalog",
+                "the connection timed out (10s)",
+            ],
+            fixes: &[
+                "test the connection with `/db test`",
+                "check host, port, database and credentials (`/db show-current`)",
+                "inspect the user privileges with `/db privileges`",
+            ],
+        },
+        pt_br: Text {
+            title: "erro de banco de dados",
+            causes: &[
+                "a connection string esta errada ou o banco nao responde",
+                "o usuario nao tem permissao para ler o catalogo",
+                "a conexao excedeu o tempo limite (10s)",
+            ],
+            fixes: &[
+                "teste a conexao com `/db test`",
+                "confira host, porta, banco e credenciais (`/db show-current`)",
+                "veja as permissoes do usuario com `/db privileges`",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL104",
+        en: Text {
+            title: "invalid JSON",
+            causes: &[
+                "a workspace artifact (plan.json, schema.json, a manifest) is malformed",
+                "a file given with a flag (--privacy, --thresholds) is not valid JSON",
+            ],
+            fixes: &[
+                "the message shows the line and column; fix the file there",
+                "recreate generated artifacts instead of editing them by hand",
+            ],
+        },
+        pt_br: Text {
+            title: "JSON invalido",
+            causes: &[
+                "um artefato do workspace (plan.json, schema.json, manifest) esta malformado",
+                "um arquivo passado por flag (--privacy, --thresholds) nao e JSON valido",
+            ],
+            fixes: &[
+                "a mensagem mostra linha e coluna; corrija o arquivo nesse ponto",
+                "recrie os artefatos gerados em vez de edita-los a mao",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL105",
+        en: Text {
+            title: "evaluation error",
+            causes: &[
+                "the dataset directory is missing or has no file matching a schema table",
+                "a CSV/Parquet file is unreadable or its header does not match the table",
+            ],
+            fixes: &[
+                "check the dataset path and the `--pattern` used to map files to tables",
+                "regenerate the output with `/generate` and evaluate again",
+            ],
+        },
+        pt_br: Text {
+            title: "erro na avaliacao",
+            causes: &[
+                "o diretorio do dataset nao existe ou nenhum arquivo casa com uma tabela do schema",
+                "um arquivo CSV/Parquet nao pode ser lido ou o cabecalho nao bate com a tabela",
+            ],
+            fixes: &[
+                "confira o caminho do dataset e o `--pattern` que liga arquivos a tabelas",
+                "gere a saida de novo com `/generate` e avalie outra vez",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL106",
+        en: Text {
+            title: "evaluation found violations",
+            causes: &[
+                "generated rows break PK, UNIQUE, FK, NOT NULL or CHECK constraints",
+                "real-looking PII, failed assertions or thresholds in strict mode",
+            ],
+            fixes: &[
+                "read evaluation_report.json / report.md for the violating paths and rows",
+                "fix the generators or rules of those columns in the plan and regenerate",
+                "compare with the previous evaluation using `/eval diff`",
+            ],
+        },
+        pt_br: Text {
+            title: "a avaliacao encontrou violacoes",
+            causes: &[
+                "linhas geradas quebram constraints PK, UNIQUE, FK, NOT NULL ou CHECK",
+                "PII com cara de real, assertions ou thresholds falhando no modo estrito",
+            ],
+            fixes: &[
+                "veja evaluation_report.json / report.md com os caminhos e linhas violados",
+                "corrija geradores ou regras dessas colunas no plano e gere de novo",
+                "compare com a avaliacao anterior usando `/eval diff`",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL107",
+        en: Text {
+            title: "invalid configuration or arguments",
+            causes: &[
+                "a required flag is missing or two flags conflict",
+                "datalchemy.toml or settings.toml has an invalid value",
+                "a referenced run, plan or output does not exist",
+            ],
+            fixes: &[
+                "run the command with `--help` to check its flags",
+                "list artifacts with `/runs list`, `/plans list` and `/out list`",
+                "run `/doctor` to diagnose the workspace",
+            ],
+        },
+        pt_br: Text {
+            title: "configuracao ou argumentos invalidos",
+            causes: &[
+                "falta uma flag obrigatoria ou duas flags conflitam",
+                "datalchemy.toml ou settings.toml tem um valor invalido",
+                "o run, plano ou saida referenciado nao existe",
+            ],
+            fixes: &[
+                "rode o comando com `--help` para conferir as flags",
+                "liste os artefatos com `/runs list`, `/plans list` e `/out list`",
+                "rode `/doctor` para diagnosticar o workspace",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL108",
+        en: Text {
+            title: "file system error",
+            causes: &[
+                "a path does not exist or is not writable",
+                "the disk is full",
+            ],
+            fixes: &[
+                "check the path and permissions in the message",
+                "free disk space or choose another output directory",
+            ],
+        },
+        pt_br: Text {
+            title: "erro de sistema de arquivos",
+            causes: &[
+                "um caminho nao existe ou nao pode ser escrito",
+                "o disco esta cheio",
+            ],
+            fixes: &[
+                "confira o caminho e as permissoes da mensagem",
+                "libere espaco ou escolha outro diretorio de saida",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL109",
+        en: Text {
+            title: "vault or signing error",
+            causes: &[
+                "wrong vault password",
+                "the vault or the signing key file is corrupted",
+            ],
+            fixes: &[
+                "unlock again with `/secrets unlock <pass>`",
+                "delete and recreate the vault (`/secrets delete`) or the key (`/workspace keygen --force`)",
+            ],
+        },
+        pt_br: Text {
+            title: "erro no cofre ou na assinatura",
+            causes: &[
+                "senha do cofre errada",
+                "o cofre ou o arquivo da chave de assinatura esta corrompido",
+            ],
+            fixes: &[
+                "desbloqueie de novo com `/secrets unlock <senha>`",
+                "apague e recrie o cofre (`/secrets delete`) ou a chave (`/workspace keygen --force`)",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL110",
+        en: Text {
+            title: "plan validation failed",
+            causes: &[
+                "plan.json does not follow the plan JSON schema",
+                "the plan references tables or columns missing from the run's schema",
+                "the schema changed after the plan was created",
+            ],
+            fixes: &[
+                "run `datalchemy plan validate` to list every error with its path",
+                "fix the listed paths with `/plan edit` or `/plan rules`",
+                "or create a fresh plan for the current run with `datalchemy plan new`",
+            ],
+        },
+        pt_br: Text {
+            title: "o plano nao passou na validacao",
+            causes: &[
+                "o plan.json nao segue o JSON schema do plano",
+                "o plano usa tabelas ou colunas que nao existem no schema do run",
+                "o schema mudou depois que o plano foi criado",
+            ],
+            fixes: &[
+                "rode `datalchemy plan validate` para listar cada erro com o caminho",
+                "corrija os caminhos listados com `/plan edit` ou `/plan rules`",
+                "ou crie um plano novo para o run atual com `datalchemy plan new`",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL111",
+        en: Text {
+            title: "runtime error",
+            causes: &["the async runtime could not start or a background task failed"],
+            fixes: &[
+                "run the command again",
+                "report it with the full message if it persists",
+            ],
+        },
+        pt_br: Text {
+            title: "erro de runtime",
+            causes: &["o runtime assincrono nao iniciou ou uma tarefa em segundo plano falhou"],
+            fixes: &[
+                "rode o comando de novo",
+                "se persistir, reporte com a mensagem completa",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL112",
+        en: Text {
+            title: "unsupported database engine",
+            causes: &[
+                "the connection string is not postgres:// or postgresql://",
+                "sqlite:// is only introspected by the TUI (`/introspect`)",
+            ],
+            fixes: &["use a PostgreSQL connection string, or the TUI for SQLite"],
+        },
+        pt_br: Text {
+            title: "engine de banco nao suportada",
+            causes: &[
+                "a connection string nao e postgres:// ou postgresql://",
+                "sqlite:// so e inspecionado pela TUI (`/introspect`)",
+            ],
+            fixes: &["use uma connection string de PostgreSQL, ou a TUI para SQLite"],
+        },
+    },
+    Explanation {
+        code: "DL113",
+        en: Text {
+            title: "workspace error",
+            causes: &[
+                "settings.toml or profiles.toml is malformed",
+                "no run or plan exists yet in the workspace",
+            ],
+            fixes: &[
+                "run `/doctor` to find the broken file",
+                "introspect and create a plan first (`/introspect`, `/plan new`)",
+            ],
+        },
+        pt_br: Text {
+            title: "erro no workspace",
+            causes: &[
+                "settings.toml ou profiles.toml esta malformado",
+                "ainda nao ha run ou plano no workspace",
+            ],
+            fixes: &[
+                "rode `/doctor` para achar o arquivo com problema",
+                "faca o introspect e crie um plano antes (`/introspect`, `/plan new`)",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL114",
+        en: Text {
+            title: "LLM provider error",
+            causes: &[
+                "the API key is missing or invalid",
+                "the model name is unknown to the provider or the endpoint is unreachable",
+                "the model answered with something that is not the expected JSON",
+            ],
+            fixes: &[
+                "set the key with `/secrets llm-key` or the provider environment variable",
+                "check provider, key and model with `/llm status` and `/llm models`",
+                "use `--heuristic` to skip the LLM",
+            ],
+        },
+        pt_br: Text {
+            title: "erro no provedor de LLM",
+            causes: &[
+                "a chave de API esta ausente ou invalida",
+                "o provedor nao conhece o modelo ou o endpoint nao responde",
+                "o modelo respondeu algo que nao e o JSON esperado",
+            ],
+            fixes: &[
+                "configure a chave com `/secrets llm-key` ou a variavel de ambiente do provedor",
+                "confira provedor, chave e modelo com `/llm status` e `/llm models`",
+                "use `--heuristic` para nao usar o LLM",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL201",
+        en: Text {
+            title: "invalid plan for generation",
+            causes: &[
+                "a rule targets a column that is missing or cannot be generated",
+                "a foreign key points to a table outside the plan targets",
+            ],
+            fixes: &[
+                "run `datalchemy plan validate` before generating",
+                "add the parent table to the targets or let parents be generated automatically",
+            ],
+        },
+        pt_br: Text {
+            title: "plano invalido para a geracao",
+            causes: &[
+                "uma regra aponta para uma coluna que nao existe ou nao pode ser gerada",
+                "uma FK aponta para uma tabela fora dos targets do plano",
+            ],
+            fixes: &[
+                "rode `datalchemy plan validate` antes de gerar",
+                "inclua a tabela pai nos targets ou deixe os pais serem gerados automaticamente",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL202",
+        en: Text {
+            title: "unsupported feature",
+            causes: &[
+                "the output format or target needs a cargo feature that is not enabled",
+                "the column type or constraint is not supported by the generator",
+            ],
+            fixes: &[
+                "rebuild with the feature named in the message (e.g. `--features kafka`)",
+                "pick another format or set a generator for the column in the plan",
+            ],
+        },
+        pt_br: Text {
+            title: "recurso nao suportado",
+            causes: &[
+                "o formato ou destino precisa de uma feature do cargo que nao foi habilitada",
+                "o tipo ou constraint da coluna nao e suportado pelo gerador",
+            ],
+            fixes: &[
+                "recompile com a feature citada na mensagem (ex.: `--features kafka`)",
+                "escolha outro formato ou defina um gerador para a coluna no plano",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL203",
+        en: Text {
+            title: "I/O error while writing the output",
+            causes: &["the output directory is not writable", "the disk is full"],
+            fixes: &[
+                "check permissions of the out directory",
+                "free disk space or lower the row counts",
+            ],
+        },
+        pt_br: Text {
+            title: "erro de I/O ao gravar a saida",
+            causes: &[
+                "o diretorio de saida nao pode ser escrito",
+                "o disco esta cheio",
+            ],
+            fixes: &[
+                "confira as permissoes do diretorio out",
+                "libere espaco ou reduza a quantidade de linhas",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL204",
+        en: Text {
+            title: "JSON error during generation",
+            causes: &["a JSON/JSONB value or generator params could not be (de)serialized"],
+            fixes: &["check the params of the JSON columns in the plan"],
+        },
+        pt_br: Text {
+            title: "erro de JSON na geracao",
+            causes: &[
+                "um valor JSON/JSONB ou os params de um gerador nao puderam ser (de)serializados",
+            ],
+            fixes: &["confira os params das colunas JSON no plano"],
+        },
+    },
+    Explanation {
+        code: "DL205",
+        en: Text {
+            title: "CSV writer error",
+            causes: &["the CSV file could not be written"],
+            fixes: &["check the output directory and free disk space"],
+        },
+        pt_br: Text {
+            title: "erro ao gravar CSV",
+            causes: &["o arquivo CSV nao pode ser gravado"],
+            fixes: &["confira o diretorio de saida e o espaco em disco"],
+        },
+    },
+    Explanation {
+        code: "DL206",
+        en: Text {
+            title: "SQLite output error",
+            causes: &["a row breaks the SQLite table definition or the database file is locked"],
+            fixes: &[
+                "close other programs using the database file",
+                "evaluate a CSV output to find the offending column",
+            ],
+        },
+        pt_br: Text {
+            title: "erro na saida SQLite",
+            causes: &["uma linha quebra a definicao da tabela SQLite ou o arquivo esta travado"],
+            fixes: &[
+                "feche outros programas usando o arquivo do banco",
+                "avalie uma saida CSV para achar a coluna com problema",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL207",
+        en: Text {
+            title: "XLSX output error",
+            causes: &["a sheet exceeds the XLSX limits (rows, sheet name length)"],
+            fixes: &["lower the row counts or use CSV for large tables"],
+        },
+        pt_br: Text {
+            title: "erro na saida XLSX",
+            causes: &["uma planilha passa dos limites do XLSX (linhas, tamanho do nome)"],
+            fixes: &["reduza as linhas ou use CSV para tabelas grandes"],
+        },
+    },
+    Explanation {
+        code: "DL208",
+        en: Text {
+            title: "Arrow output error",
+            causes: &["a column type could not be mapped to Arrow"],
+            fixes: &["use another format for this schema or report the column type"],
+        },
+        pt_br: Text {
+            title: "erro na saida Arrow",
+            causes: &["um tipo de coluna nao pode ser mapeado para Arrow"],
+            fixes: &["use outro formato para este schema ou reporte o tipo da coluna"],
+        },
+    },
+    Explanation {
+        code: "DL209",
+        en: Text {
+            title: "DuckDB output error",
+            causes: &["the DuckDB file is locked or a row breaks the table definition"],
+            fixes: &["close other DuckDB sessions and generate again"],
+        },
+        pt_br: Text {
+            title: "erro na saida DuckDB",
+            causes: &["o arquivo DuckDB esta travado ou uma linha quebra a definicao da tabela"],
+            fixes: &["feche outras sessoes do DuckDB e gere de novo"],
+        },
+    },
+    Explanation {
+        code: "DL210",
+        en: Text {
+            title: "object store upload error",
+            causes: &[
+                "missing or wrong storage credentials",
+                "the bucket does not exist or the endpoint is unreachable",
+            ],
+            fixes: &[
+                "load credentials with `/secrets import-env`",
+                "check the profile storage (`/profiles storage`), endpoint and region",
+            ],
+        },
+        pt_br: Text {
+            title: "erro no upload para o object store",
+            causes: &[
+                "credenciais de storage ausentes ou erradas",
+                "o bucket nao existe ou o endpoint nao responde",
+            ],
+            fixes: &[
+                "carregue as credenciais com `/secrets import-env`",
+                "confira o storage do perfil (`/profiles storage`), endpoint e regiao",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL211",
+        en: Text {
+            title: "Kafka error",
+            causes: &[
+                "the brokers are unreachable",
+                "the topic does not exist and cannot be created",
+            ],
+            fixes: &[
+                "check `--kafka` brokers and network access",
+                "create the topics or allow auto creation (`--kafka-partitions`)",
+            ],
+        },
+        pt_br: Text {
+            title: "erro no Kafka",
+            causes: &[
+                "os brokers nao respondem",
+                "o topico nao existe e nao pode ser criado",
+            ],
+            fixes: &[
+                "confira os brokers de `--kafka` e o acesso de rede",
+                "crie os topicos ou permita a criacao automatica (`--kafka-partitions`)",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL212",
+        en: Text {
+            title: "generator asset error",
+            causes: &["a dictionary or asset file used by a generator is missing or invalid"],
+            fixes: &["check the asset path in the generator params"],
+        },
+        pt_br: Text {
+            title: "erro em asset de gerador",
+            causes: &["um dicionario ou arquivo usado por um gerador esta ausente ou invalido"],
+            fixes: &["confira o caminho do asset nos params do gerador"],
+        },
+    },
+    Explanation {
+        code: "DL213",
+        en: Text {
+            title: "generation failed",
+            causes: &[
+                "a table could not be generated within the attempt limit",
+                "UNIQUE/CHECK constraints are too tight for the generator values",
+            ],
+            fixes: &[
+                "read generation_report.json for the failing table and constraint",
+                "use generators with more distinct values or lower the row counts",
+            ],
+        },
+        pt_br: Text {
+            title: "a geracao falhou",
+            causes: &[
+                "uma tabela nao foi gerada dentro do limite de tentativas",
+                "constraints UNIQUE/CHECK apertadas demais para os valores do gerador",
+            ],
+            fixes: &[
+                "veja no generation_report.json a tabela e a constraint que falharam",
+                "use geradores com mais valores distintos ou reduza as linhas",
+            ],
+        },
+    },
+    Explanation {
+        code: "DL214",
+        en: Text {
+            title: "generation cancelled",
+            causes: &["the run was cancelled (Esc in the TUI or client disconnect)"],
+            fixes: &["run `/generate` again; the partial run directory was removed"],
+        },
+        pt_br: Text {
+            title: "geracao cancelada",
+            causes: &["a geracao foi cancelada (Esc na TUI ou cliente desconectado)"],
+            fixes: &["rode `/generate` de novo; o diretorio parcial foi removido"],
+        },
+    },
+    Explanation {
+        code: "DL215",
+        en: Text {
+            title: "source database read failed",
+            causes: &[
+                "`datalchemy mask` could not connect to the source database",
+                "a table of the plan is missing or not readable in the source",
+            ],
+            fixes: &[
+                "check --conn and the privileges of its user",
+                "introspect the source again and align the plan targets",
+            ],
+        },
+        pt_br: Text {
+            title: "falha ao ler o banco de origem",
+            causes: &[
+                "`datalchemy mask` nao conectou ao banco de origem",
+                "uma tabela do plano nao existe ou nao pode ser lida na origem",
+            ],
+            fixes: &[
+                "confira --conn e as permissoes do usuario",
+                "introspecte a origem de novo e alinhe os targets do plano",
+            ],
+        },
+    },
+];
@@ -0,0 +1,269 @@
+//! `datalchemy generate`: headless generation from schema.json + plan.json.
+//!
+//! With `--table schema.table --stdout` a single table is streamed to stdout
+//! (no run directory), e.g. `datalchemy generate ... --stdout | psql -c "\copy t from stdin csv header"`.
+//! `--top-slowest <n>` prints the slowest tables to stderr after a run.
+//! `--workspace <dir>` generates a workspace plan into `out/<out_id>`, with
+//! `out_manifest.json`, `generation_report.json` and the checksums (signed
+//! with the workspace key, if any) as `/generate` writes them.
+//! `--profile <profile.json>` (in a workspace, the `profile.json` of the
+//! plan's run, when present) generates unruled columns from sampled values.
+
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use datalchemy_core::DatabaseSchema;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationReport, OutputFormat, ValueProfile,
+};
+use datalchemy_plan::{Plan, validate_plan};
+use serde_json::Value;
+
+use crate::output::Output;
+use crate::plan::read_schema;
+use crate::tui::utils::move_dir_contents;
+use crate::workspace::{
+    ArtifactStatus, HeadlessWorkspace, OutManifest, new_artifact_id, seal_output, write_json_atomic,
+};
+use crate::{CliError, GenerateArgs};
+
+/// Run the plan into `out_dir`, or stream one table to stdout.
+pub fn run_generate(args: GenerateArgs, output: &mut Output) -> Result<(), CliError> {
+    if let Some(root) = &args.workspace {
+        return generate_in_workspace(&args, root, output);
+    }
+    let (Some(schema_path), Some(plan_path)) = (&args.schema, &args.plan) else {
+        return Err(CliError::InvalidConfig(
+            "--schema and --plan are required without --workspace".to_string(),
+        ));
+    };
+    let schema: DatabaseSchema = serde_json::from_str(&std::fs::read_to_string(schema_path)?)?;
+    let mut plan = load_plan(plan_path, &schema)?;
+    scale_plan(&mut plan, args.scale)?;
+    let mut engine = GenerationEngine::new(GenerateOptions {
+        out_dir: args.out_dir.clone(),
+        format: args.format,
+        ..GenerateOptions::default()
+    });
+    if let Some(path) = &args.profile {
+        engine = engine.with_profile(read_profile(path)?);
+    }
+
+    if args.stdout {
+        if output.is_json() {
+            return Err(CliError::InvalidConfig(
+                "--stdout writes the table to stdout; --message-format json is not supported"
+                    .to_string(),
+            ));
+        }
+        let Some(table) = args.table.as_deref() else {
+            return Err(CliError::InvalidConfig(
+                "--stdout requires --table schema.table".to_string(),
+            ));
+        };
+        let Some((schema_name, table_name)) = table.split_once('.') else {
+            return Err(CliError::InvalidConfig(format!(
+                "--table expects schema.table, got '{table}'"
+            )));
+        };
+        let stdout = std::io::stdout();
+        let mut writer = BufWriter::new(stdout.lock());
+        engine.stream_table(&schema, &plan, schema_name, table_name, &mut writer)?;
+        writer.flush()?;
+        return Ok(());
+    }
+    if args.table.is_some() {
+        return Err(CliError::InvalidConfig(
+            "--table is only supported with --stdout".to_string(),
+        ));
+    }
+
+    let result = engine.run(&schema, &plan)?;
+    output.line(result.run_dir.display());
+    output.set("run_dir", &result.run_dir)?;
+    output.set(
+        "rows",
+        result
+            .report
+            .tables
+            .iter()
+            .map(|table| table.rows_generated)
+            .sum::<u64>(),
+    )?;
+    print_slowest(&result.report, args.top_slowest);
+    Ok(())
+}
+
+/// `--workspace`: generate the resolved plan into `out/<out_id>`.
+fn generate_in_workspace(
+    args: &GenerateArgs,
+    root: &Path,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    let workspace = HeadlessWorkspace::open(root.to_path_buf())?;
+    let generated = generate_workspace_output(
+        &workspace,
+        args.plan_id.as_deref(),
+        args.out_id.clone(),
+        args.format,
+        args.scale,
+    )?;
+    output.field("out_id", &generated.out_id);
+    output.field("out_path", generated.out_path.display());
+    output.set("rows", generated.rows)?;
+    print_slowest(&generated.report, args.top_slowest);
+    Ok(())
+}
+
+/// Output written by [`generate_workspace_output`].
+#[derive(Debug)]
+pub(crate) struct WorkspaceOutput {
+    pub(crate) out_id: String,
+    pub(crate) out_path: PathBuf,
+    /// Rows requested by the (scaled) plan.
+    pub(crate) rows: u64,
+    pub(crate) report: GenerationReport,
+}
+
+/// Generate a workspace plan (`plan_id`, else the active or latest one) into
+/// `out/<out_id>`, with `out_manifest.json`, `generation_report.json` and the
+/// checksums as `/generate` writes them.
+pub(crate) fn generate_workspace_output(
+    workspace: &HeadlessWorkspace,
+    plan_id: Option<&str>,
+    out_id: Option<String>,
+    format: OutputFormat,
+    scale: f64,
+) -> Result<WorkspaceOutput, CliError> {
+    let plan_id = workspace.plan_id(plan_id)?;
+    let run_id = workspace.plan_run_id(&plan_id)?;
+    let schema = read_schema(&workspace.schema_path(&run_id))?;
+    let profile_path = workspace
+        .schema_path(&run_id)
+        .with_file_name("profile.json");
+    let mut plan = load_plan(&workspace.plan_path(&plan_id), &schema)?;
+    scale_plan(&mut plan, scale)?;
+
+    let out_id = out_id.unwrap_or_else(|| new_artifact_id("out"));
+    let final_dir = workspace.paths.out_dir.join(&out_id);
+    if final_dir.exists() {
+        return Err(CliError::InvalidConfig(format!(
+            "output directory already exists: {}",
+            final_dir.display()
+        )));
+    }
+    std::fs::create_dir_all(&final_dir)?;
+
+    let mut manifest = OutManifest {
+        out_id: out_id.clone(),
+        status: ArtifactStatus::Running,
+        schema_run_id: run_id,
+        plan_id,
+        mode: format.as_str().to_string(),
+        seed: plan.seed,
+        scale: plan.targets.iter().map(|target| target.rows).sum(),
+        artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
+        cli_version: crate::workspace::CLI_VERSION.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        finished_at: None,
+        tags: Vec::new(),
+        notes: Vec::new(),
+    };
+    let manifest_path = final_dir.join("out_manifest.json");
+    write_json_atomic(&manifest_path, &manifest)?;
+
+    let mut engine = GenerationEngine::new(GenerateOptions {
+        out_dir: workspace.paths.out_dir.clone(),
+        format,
+        ..GenerateOptions::default()
+    });
+    if profile_path.exists() {
+        engine = engine.with_profile(read_profile(&profile_path)?);
+    }
+    let result = engine.run(&schema, &plan);
+    manifest.finished_at = Some(Utc::now().to_rfc3339());
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            manifest.status = ArtifactStatus::Error;
+            write_json_atomic(&manifest_path, &manifest)?;
+            return Err(err.into());
+        }
+    };
+    move_dir_contents(&result.run_dir, &final_dir)?;
+    write_json_atomic(&final_dir.join("generation_report.json"), &result.report)?;
+    manifest.status = ArtifactStatus::Ok;
+    write_json_atomic(&manifest_path, &manifest)?;
+    seal_output(&workspace.paths, &final_dir)?;
+
+    Ok(WorkspaceOutput {
+        out_id,
+        out_path: final_dir,
+        rows: manifest.scale,
+        report: result.report,
+    })
+}
+
+fn read_profile(path: &Path) -> Result<ValueProfile, CliError> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn print_slowest(report: &GenerationReport, count: Option<usize>) {
+    if let Some(count) = count {
+        for line in slowest_table_lines(report, count) {
+            eprintln!("{line}");
+        }
+    }
+}
+
+/// `--top-slowest` summary: one line per table, slowest first.
+pub(crate) fn slowest_table_lines(report: &GenerationReport, count: usize) -> Vec<String> {
+    report
+        .slowest_tables(count)
+        .into_iter()
+        .map(|table| {
+            format!(
+                "{}.{}: {} ms, {} rows ({:.0} rows/s), {} retries ({:.1}/s), ~{} KiB",
+                table.schema,
+                table.table,
+                table.duration_ms,
+                table.rows_generated,
+                table.rows_per_sec,
+                table.retries,
+                table.retries_per_sec,
+                table.peak_memory_bytes / 1024
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn load_plan(path: &Path, schema: &DatabaseSchema) -> Result<Plan, CliError> {
+    let plan_json: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let plan_schema = serde_json::to_value(datalchemy_plan::plan_json_schema())?;
+    let validated = validate_plan(&plan_json, &plan_schema, schema).map_err(|report| {
+        let messages: Vec<String> = report
+            .errors
+            .iter()
+            .map(|issue| format!("{}: {}", issue.path, issue.message))
+            .collect();
+        CliError::Plan(format!("plan validation failed: {}", messages.join("; ")))
+    })?;
+    Ok(validated.plan)
+}
+
+/// `--scale`: multiply the target rows, keeping at least one row per
+/// non-empty target.
+pub(crate) fn scale_plan(plan: &mut Plan, scale: f64) -> Result<(), CliError> {
+    if !(scale.is_finite() && scale > 0.0) {
+        return Err(CliError::InvalidConfig(format!(
+            "--scale must be positive, got {scale}"
+        )));
+    }
+    for target in &mut plan.targets {
+        if target.rows > 0 {
+            target.rows = ((target.rows as f64 * scale).round() as u64).max(1);
+        }
+    }
+    Ok(())
+}
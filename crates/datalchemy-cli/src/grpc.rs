@@ -0,0 +1,289 @@
+//! `datalchemy grpc`: generation sidecar over gRPC (behind the `grpc` feature).
+//!
+//! Serves `datalchemy.v1.GenerationService` from
+//! `proto/datalchemy/v1/generation.proto`, so test harnesses in other languages
+//! can request rows on demand instead of reading files. The schema and plan
+//! are loaded once at startup (a workspace plan, or `--schema`/`--plan`).
+//! `Generate` runs the plan on a blocking thread and streams each requested
+//! table in [`proto::RowBatch`]es as soon as it is generated; a client that
+//! goes away cancels the run.
+
+use std::collections::{BTreeSet, HashMap};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use datalchemy_core::{Column, DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_plan::Plan;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{Request, Response, Status};
+
+use crate::generate::{load_plan, scale_plan};
+use crate::output::Output;
+use crate::plan::read_schema;
+use crate::workspace::HeadlessWorkspace;
+use crate::{CliError, GrpcArgs};
+
+// `Value.kind` variants mirror the proto field names.
+#[allow(clippy::enum_variant_names)]
+mod proto {
+    tonic::include_proto!("datalchemy.v1");
+}
+
+use proto::generation_service_server::{GenerationService, GenerationServiceServer};
+use proto::{
+    ColumnInfo, GenerateRequest, ListTablesRequest, ListTablesResponse, Row, RowBatch, TableInfo,
+    Value, value::Kind,
+};
+
+/// Rows per batch when the request does not set `batch_size`.
+const DEFAULT_BATCH_SIZE: usize = 500;
+/// Batches buffered between the generation thread and the response stream.
+const STREAM_BUFFER: usize = 8;
+
+/// Load the plan and serve the generation service on `--addr` until interrupted.
+pub async fn run_grpc(args: GrpcArgs, output: &mut Output) -> Result<(), CliError> {
+    let (schema, plan) = match (&args.schema, &args.plan) {
+        (Some(schema_path), Some(plan_path)) => {
+            let schema = read_schema(schema_path)?;
+            let plan = load_plan(plan_path, &schema)?;
+            (schema, plan)
+        }
+        (None, None) => {
+            let workspace = HeadlessWorkspace::open(args.workspace.clone())?;
+            let plan_id = workspace.plan_id(args.plan_id.as_deref())?;
+            let run_id = workspace.plan_run_id(&plan_id)?;
+            let schema = read_schema(&workspace.schema_path(&run_id))?;
+            let plan = load_plan(&workspace.plan_path(&plan_id), &schema)?;
+            (schema, plan)
+        }
+        _ => {
+            return Err(CliError::InvalidConfig(
+                "--plan and --schema must be given together".to_string(),
+            ));
+        }
+    };
+
+    let listener = TcpListener::bind(args.addr).await?;
+    let addr: SocketAddr = listener.local_addr()?;
+    output.line(format!(
+        "serving datalchemy.v1.GenerationService on {addr} ({} targets)",
+        plan.targets.len()
+    ));
+    output.set("addr", addr.to_string())?;
+    output.flush("grpc");
+
+    let service = GenerationServer {
+        schema: Arc::new(schema),
+        plan: Arc::new(plan),
+    };
+    tonic::transport::Server::builder()
+        .add_service(GenerationServiceServer::new(service))
+        .serve_with_incoming(TcpListenerStream::new(listener))
+        .await
+        .map_err(|err| CliError::Runtime(err.to_string()))
+}
+
+struct GenerationServer {
+    schema: Arc<DatabaseSchema>,
+    plan: Arc<Plan>,
+}
+
+#[tonic::async_trait]
+impl GenerationService for GenerationServer {
+    async fn list_tables(
+        &self,
+        _request: Request<ListTablesRequest>,
+    ) -> Result<Response<ListTablesResponse>, Status> {
+        let tables = self
+            .plan
+            .targets
+            .iter()
+            .map(|target| TableInfo {
+                schema: target.schema.clone(),
+                table: target.table.clone(),
+                rows: target.rows,
+                columns: find_table(&self.schema, &target.schema, &target.table)
+                    .map(|table| {
+                        ordered_columns(table)
+                            .into_iter()
+                            .map(|column| ColumnInfo {
+                                name: column.name.clone(),
+                                data_type: column.column_type.data_type.clone(),
+                                nullable: column.is_nullable,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect();
+        Ok(Response::new(ListTablesResponse { tables }))
+    }
+
+    type GenerateStream = ReceiverStream<Result<RowBatch, Status>>;
+
+    async fn generate(
+        &self,
+        request: Request<GenerateRequest>,
+    ) -> Result<Response<Self::GenerateStream>, Status> {
+        let request = request.into_inner();
+        let tables: BTreeSet<String> = request.tables.iter().cloned().collect();
+        for name in &tables {
+            let found = name
+                .split_once('.')
+                .and_then(|(schema, table)| find_table(&self.schema, schema, table));
+            if found.is_none() {
+                return Err(Status::invalid_argument(format!(
+                    "table '{name}' not found in schema.json (expected schema.table)"
+                )));
+            }
+        }
+        let plan = request_plan(&self.plan, &request)?;
+        let batch_size = match request.batch_size {
+            0 => DEFAULT_BATCH_SIZE,
+            size => size as usize,
+        };
+
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let schema = Arc::clone(&self.schema);
+        tokio::task::spawn_blocking(move || {
+            let mut sink = ChannelSink {
+                tx: tx.clone(),
+                tables,
+                batch_size,
+                streamed: BTreeSet::new(),
+            };
+            // The engine only writes its run directory when persisting; this
+            // out_dir is never created.
+            let engine = GenerationEngine::new(GenerateOptions {
+                out_dir: std::env::temp_dir(),
+                ..GenerateOptions::default()
+            });
+            let status = match engine.generate_to_sink(&schema, &plan, &mut sink) {
+                Ok(_) => sink.tables.difference(&sink.streamed).next().map(|name| {
+                    Status::invalid_argument(format!("table '{name}' is not generated by the plan"))
+                }),
+                Err(GenerationError::Cancelled) => None,
+                Err(err) => Some(Status::internal(err.to_string())),
+            };
+            if let Some(status) = status {
+                let _ = tx.blocking_send(Err(status));
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// The loaded plan with the seed, scale and row overrides of `request`.
+fn request_plan(plan: &Plan, request: &GenerateRequest) -> Result<Plan, Status> {
+    let mut plan = plan.clone();
+    if let Some(seed) = request.seed {
+        plan.seed = seed;
+    }
+    if request.scale != 0.0 {
+        scale_plan(&mut plan, request.scale)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+    }
+    for (name, rows) in &request.rows {
+        let target = plan
+            .targets
+            .iter_mut()
+            .find(|target| format!("{}.{}", target.schema, target.table) == *name)
+            .ok_or_else(|| {
+                Status::invalid_argument(format!("rows: '{name}' is not a target of the plan"))
+            })?;
+        target.rows = *rows;
+    }
+    Ok(plan)
+}
+
+/// Sends the requested tables to the response stream in batches.
+struct ChannelSink {
+    tx: mpsc::Sender<Result<RowBatch, Status>>,
+    /// `schema.table` to stream; empty streams every table.
+    tables: BTreeSet<String>,
+    batch_size: usize,
+    streamed: BTreeSet<String>,
+}
+
+impl TableSink for ChannelSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let columns = ordered_columns(table);
+        let output = TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: columns.iter().map(|column| column.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        };
+        let name = format!("{schema_name}.{}", table.name);
+        if !self.tables.is_empty() && !self.tables.contains(&name) {
+            return Ok(output);
+        }
+        self.streamed.insert(name);
+
+        let mut offset = 0;
+        loop {
+            let chunk = &rows[offset..rows.len().min(offset + self.batch_size)];
+            let batch = RowBatch {
+                schema: schema_name.to_string(),
+                table: table.name.clone(),
+                columns: output.columns.clone(),
+                rows: chunk.iter().map(|row| proto_row(&columns, row)).collect(),
+                offset: offset as u64,
+                last: offset + chunk.len() == rows.len(),
+            };
+            if self.tx.blocking_send(Ok(batch)).is_err() {
+                // The client went away.
+                return Err(GenerationError::Cancelled);
+            }
+            offset += chunk.len();
+            if offset == rows.len() {
+                return Ok(output);
+            }
+        }
+    }
+}
+
+fn proto_row(columns: &[&Column], row: &HashMap<String, GeneratedValue>) -> Row {
+    let values = columns
+        .iter()
+        .map(|column| {
+            let kind = match row.get(&column.name.to_lowercase()) {
+                None | Some(GeneratedValue::Null) => Kind::NullValue(true),
+                Some(GeneratedValue::Bool(value)) => Kind::BoolValue(*value),
+                Some(GeneratedValue::Int(value)) => Kind::IntValue(*value),
+                Some(GeneratedValue::Float(value)) => Kind::DoubleValue(*value),
+                Some(value) => Kind::TextValue(value.to_csv(column)),
+            };
+            Value { kind: Some(kind) }
+        })
+        .collect();
+    Row { values }
+}
+
+fn find_table<'a>(schema: &'a DatabaseSchema, schema_name: &str, table: &str) -> Option<&'a Table> {
+    schema
+        .schemas
+        .iter()
+        .find(|db_schema| db_schema.name == schema_name)
+        .and_then(|db_schema| db_schema.tables.iter().find(|t| t.name == table))
+}
+
+/// Columns sorted by ordinal position, as in the CSV output.
+fn ordered_columns(table: &Table) -> Vec<&Column> {
+    let mut columns: Vec<_> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+    columns
+}
@@ -0,0 +1,366 @@
+//! Message catalog of the TUI and of the human lines of headless commands.
+//!
+//! Every [`Msg`] has an `en` and a `pt_BR` text (`settings.toml`
+//! `language`); a missing translation does not compile. `{}` placeholders
+//! are filled in order by [`Msg::fill`]. Command names, flags, ids and the
+//! machine-readable output (`key=value`, `--message-format json`) are not
+//! translated.
+
+use std::fmt::Display;
+
+use crate::workspace::Language;
+
+macro_rules! catalog {
+    ($($(#[$meta:meta])* $id:ident => $en:literal, $pt_br:literal;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Msg {
+            $($(#[$meta])* $id,)*
+        }
+
+        impl Msg {
+            pub fn text(self, language: Language) -> &'static str {
+                match (self, language) {
+                    $(
+                        (Msg::$id, Language::En) => $en,
+                        (Msg::$id, Language::PtBr) => $pt_br,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    UnknownCommand => "unknown command: {}. type /help for list.",
+        "comando desconhecido: {}. digite /help para ver a lista.";
+    WorkspaceMissing => "workspace missing. run /init first.",
+        "workspace ausente. rode /init primeiro.";
+    WorkspaceNotFound => "workspace not found. run /init.",
+        "workspace nao encontrado. rode /init.";
+    WorkspaceInitialized => "workspace initialized.", "workspace criado.";
+    NoActiveProfileSession => "no active profile. run will be labeled as session.",
+        "nenhum perfil ativo. o run sera rotulado como session.";
+    MissingActiveRun => "missing active run.", "nenhum run ativo.";
+    MissingActiveRunHint => "missing active run. use /introspect.",
+        "nenhum run ativo. use /introspect.";
+    MissingActivePlan => "missing active plan.", "nenhum plano ativo.";
+    MissingActivePlanHint => "missing active plan. use /plan new.",
+        "nenhum plano ativo. use /plan new.";
+    MissingOutId => "missing out_id. use /out to list.",
+        "out_id ausente. use /out para listar.";
+    SchemaOrPlanNotFound => "schema or plan not found", "schema ou plano nao encontrado";
+    PlanJsonNotFound => "plan.json not found.", "plan.json nao encontrado.";
+    SchemaJsonNotFound => "schema.json not found.", "schema.json nao encontrado.";
+    DatasetNotFound => "dataset not found for eval.", "dataset nao encontrado para o eval.";
+    RunNotFound => "run not found.", "run nao encontrado.";
+    NoRuns => "no runs found.", "nenhum run encontrado.";
+    NoPlans => "no plans found.", "nenhum plano encontrado.";
+    NoOutputs => "no outputs found.", "nenhuma saida encontrada.";
+    NoOutputsHint => "no outputs found. run /generate first.",
+        "nenhuma saida encontrada. rode /generate primeiro.";
+    OutputNotFound => "output not found.", "saida nao encontrada.";
+    ProfileNotFound => "profile not found.", "perfil nao encontrado.";
+    ApprovalDenied => "approval denied.", "aprovacao negada.";
+    SettingsUpdated => "settings updated.", "configuracoes atualizadas.";
+    UnknownSettingsKey => "unknown settings key", "chave de configuracao desconhecida";
+    UnknownErrorCode => "unknown error code: {}. use /explain to list.",
+        "codigo de erro desconhecido: {}. use /explain para listar.";
+    OperationIntrospection => "introspection", "introspeccao";
+    OperationGeneration => "generation", "geracao";
+    OperationEvaluation => "evaluation", "avaliacao";
+    AlreadyRunning => "{} already running (Esc to cancel).",
+        "{} ja em andamento (Esc para cancelar).";
+    Cancelling => "cancelling {}...", "cancelando {}...";
+    TaskIntrospecting => "Introspecting database... (Esc to cancel)",
+        "Inspecionando o banco... (Esc para cancelar)";
+    TaskGenerating => "Generating {} data...", "Gerando dados ({})...";
+    TaskEvaluating => "Evaluating dataset... (Esc to cancel)",
+        "Avaliando o dataset... (Esc para cancelar)";
+    IntrospectCompleted => "introspect completed in {}s.", "introspect concluido em {}s.";
+    IntrospectFailed => "introspect failed: {}", "introspect falhou: {}";
+    IntrospectCycles => "introspect failed: foreign key cycles detected.",
+        "introspect falhou: ciclos de chave estrangeira detectados.";
+    IntrospectCancelled => "introspect cancelled.", "introspect cancelado.";
+    SchemaWarnings => "schema warnings: {} (fix before planning)",
+        "avisos do schema: {} (corrija antes de planejar)";
+    GenerationStarted => "generating {} (Esc to cancel)...", "gerando {} (Esc para cancelar)...";
+    GenerationCompleted => "generation completed in {}s.", "geracao concluida em {}s.";
+    GenerationFailed => "generation failed: {}", "geracao falhou: {}";
+    GenerationCancelled => "generation cancelled.", "geracao cancelada.";
+    EvaluationCompleted => "evaluation completed in {}s.", "avaliacao concluida em {}s.";
+    EvaluationFailed => "evaluation failed: {}", "avaliacao falhou: {}";
+    EvaluationCancelled => "evaluation cancelled.", "avaliacao cancelada.";
+    HelpTitle => "COMMANDS", "COMANDOS";
+    HelpWorkspace => "workspace:", "workspace:";
+    HelpProfiles => "db + profiles:", "banco + perfis:";
+    HelpPipeline => "pipeline:", "pipeline:";
+    HelpSettings => "settings:", "configuracoes:";
+    HelpSecrets => "secrets:", "segredos:";
+    HelpNavigation => "navigation:", "navegacao:";
+    StatusTip => "Tip: / for commands · Tab autocomplete · Esc back",
+        "Dica: / para comandos · Tab completa · Esc volta";
+    StatusSetup => "Setup Mode · Esc to go back", "Configuracao · Esc para voltar";
+    PlanCreated => "plan created: {} tables, {} generator rules, {} rows total.",
+        "plano criado: {} tabelas, {} regras de gerador, {} linhas no total.";
+    WizardTables => "tables: {}", "tabelas: {}";
+    WizardChooseTables => "tables to generate (comma-separated)",
+        "tabelas a gerar (separadas por virgula)";
+    WizardRows => "rows for {}", "linhas de {}";
+    WizardGenerator => "generator for {}", "gerador de {}";
+    WizardUnknownTable => "unknown table '{}'", "tabela desconhecida '{}'";
+    WizardAmbiguousTable => "ambiguous table '{}' (use schema.table)",
+        "tabela ambigua '{}' (use schema.tabela)";
+    WizardNoTables => "choose at least one table", "escolha ao menos uma tabela";
+    WizardInvalidRows => "invalid row count '{}'", "quantidade de linhas invalida '{}'";
+    Usage => "usage: {}", "uso: {}";
+    NoValue => "none", "nenhum";
+    InvalidFlagValue => "invalid {} '{}'", "valor invalido de {} '{}'";
+    LevelWarn => "warn", "aviso";
+    LevelError => "error", "erro";
+    FileLine => "file: {}", "arquivo: {}";
+    MoreItems => "  ... {} more", "  ... mais {}";
+    StatusRoot => "Root:      {}", "Raiz:      {}";
+    StatusProfile => "Profile:   {}", "Perfil:    {}";
+    StatusMode => "Mode:      {}", "Modo:      {}";
+    StatusConnection => "Connection: {}", "Conexao:   {}";
+    StatusCounts => "Runs: {}  |  Plans: {}  |  Outputs: {}",
+        "Runs: {}  |  Planos: {}  |  Saidas: {}";
+    StatusActiveRun => "Active Run: {}", "Run ativo: {}";
+    StatusActivePlan => "Active Plan: {}", "Plano ativo: {}";
+    ConnSession => "session ({})", "sessao ({})";
+    ConnProfileMetadata => "profile metadata ({})", "metadados do perfil ({})";
+    WorkspaceExported => "exported {} files ({} bytes) to {} (without {}).",
+        "{} arquivos ({} bytes) exportados para {} (sem {}).";
+    ArchiveNotFound => "archive not found: {}", "arquivo compactado nao encontrado: {}";
+    WorkspaceImported => "imported {} files from {} (exported {} by cli {}).",
+        "{} arquivos importados de {} (exportados em {} pela cli {}).";
+    EvalHistoryMerged => "eval history merged: {} entries.",
+        "historico de eval mesclado: {} entradas.";
+    ImportKeptFiles => "kept {} existing files (use --overwrite to replace them):",
+        "{} arquivos existentes mantidos (use --overwrite para substitui-los):";
+    SigningKeyExists => "signing key already exists (use --force to replace it).",
+        "a chave de assinatura ja existe (use --force para substitui-la).";
+    SigningKeyCreated => "signing key created; public key: {}",
+        "chave de assinatura criada; chave publica: {}";
+    OutputsWillBeSigned => "new outputs will be signed.", "as novas saidas serao assinadas.";
+    OutputNotFoundNamed => "output not found: {}", "saida nao encontrada: {}";
+    VerifyLine => "{}  {}: {} files, {}", "{}  {}: {} arquivos, {}";
+    SignatureUnsigned => "unsigned", "sem assinatura";
+    SignatureUntrusted => "signed by untrusted key {}", "assinado por chave nao confiavel {}";
+    SignatureOk => "signature ok", "assinatura ok";
+    SignatureBad => "bad signature: {}", "assinatura invalida: {}";
+    VerifyModified => "modified", "modificado";
+    VerifyMissing => "missing", "ausente";
+    VerifyUnexpected => "unexpected", "inesperado";
+    VerifyFailed => "{} output(s) failed verification.",
+        "{} saida(s) falharam na verificacao.";
+    NoProfiles => "no profiles configured.", "nenhum perfil configurado.";
+    PromptProfileName => "Profile name:", "Nome do perfil:";
+    PromptConnection => "Database connection string (postgres:// or sqlite://):",
+        "String de conexao do banco (postgres:// ou sqlite://):";
+    ProfileCreated => "profile created.", "perfil criado.";
+    ActiveProfileUpdated => "active profile updated.", "perfil ativo atualizado.";
+    ProfileDeleted => "profile deleted.", "perfil removido.";
+    ProfileStorageCleared => "profile storage cleared.", "storage do perfil removido.";
+    ProfileStorageUpdated => "profile storage updated.", "storage do perfil atualizado.";
+    MissingActiveProfile => "missing active profile. use /profiles new or /profiles set.",
+        "nenhum perfil ativo. use /profiles new ou /profiles set.";
+    DbSessionPrompt => "Session connection (not saved). Paste database connection string:",
+        "Conexao de sessao (nao salva). Cole a string de conexao do banco:";
+    DbChangePrompt => "Update session connection. Paste database connection string:",
+        "Atualize a conexao de sessao. Cole a string de conexao do banco:";
+    ConnDatabase => "Database: {}", "Banco:    {}";
+    ConnUser => "User:     {}", "Usuario:  {}";
+    ConnStatusSession => "Status:   Session (ephemeral)", "Status:   Sessao (efemera)";
+    ConnStatusProfile => "Status:   Profile metadata only (use /db session or .env)",
+        "Status:   Apenas metadados do perfil (use /db session ou .env)";
+    ConnStatusNone => "Status:   No connection configured",
+        "Status:   Nenhuma conexao configurada";
+    TestingConnectivity => "Testing connectivity...", "Testando a conectividade...";
+    ConnectionSuccessful => "Connection successful!", "Conexao bem-sucedida!";
+    ConnectionFailed => "Connection failed: {}", "Falha na conexao: {}";
+    FetchingInfo => "Fetching info...", "Buscando informacoes...";
+    QueryFailed => "Query failed: {}", "Falha na consulta: {}";
+    DbInfoSqlite => "Engine: SQLite, Ver: {}", "Motor: SQLite, Versao: {}";
+    DbInfoPostgres => "User: {}, DB: {}, Ver: {}", "Usuario: {}, Banco: {}, Versao: {}";
+    PrivilegesQueryFailed => "Privileges query failed: {}",
+        "Falha na consulta de privilegios: {}";
+    PrivilegesSummary => "Tables: {}, SELECT on {}, INSERT on {}",
+        "Tabelas: {}, SELECT em {}, INSERT em {}";
+    PrivilegeNoInsert => "  {}: no INSERT", "  {}: sem INSERT";
+    PrivilegeNoSelect => "  {}: no SELECT", "  {}: sem SELECT";
+    PrivilegeNoSelectInsert => "  {}: no SELECT, no INSERT", "  {}: sem SELECT, sem INSERT";
+    RunActiveMarker => "active", "ativo";
+    ActiveRunLine => "active run: {}", "run ativo: {}";
+    ActiveRunUpdated => "active run updated.", "run ativo atualizado.";
+    RunManifestNotFound => "run_manifest.json not found.", "run_manifest.json nao encontrado.";
+    RunDeleted => "run deleted.", "run removido.";
+    ActivePlanUpdated => "active plan updated.", "plano ativo atualizado.";
+    TaskPlanningLlm => "Planning with {}...", "Planejando com {}...";
+    TaskSmartPlan => "Generating smart plan...", "Gerando plano inteligente...";
+    LlmPlannerSummary => "llm planner: {} (plans/{}/{}).", "planejador llm: {} (plans/{}/{}).";
+    TaskClassifying => "Classifying columns with {}...", "Classificando colunas com {}...";
+    ClassificationSummary => "classification: {} (runs/{}/{}).",
+        "classificacao: {} (runs/{}/{}).";
+    RunNotClassified => "run not classified. use /classify.",
+        "run nao classificado. use /classify.";
+    ClassificationOf => "classification of {}:", "classificacao de {}:";
+    PlanTargetRows => "  {}.{}: {} rows", "  {}.{}: {} linhas";
+    PlanFixedWidthRule => "  {}.{} fixed_width -> {} columns",
+        "  {}.{} fixed_width -> {} colunas";
+    PlanPartitionRule => "  {}.{} partitioned by {}", "  {}.{} particionado por {}";
+    InsertModeNotImplemented => "insert mode not implemented (planned).",
+        "modo insert nao implementado (planejado).";
+    PlanEdited => "plan edited. run /plan validate.", "plano editado. rode /plan validate.";
+    EditorFailed => "could not open editor. set $EDITOR or $VISUAL env var.",
+        "nao foi possivel abrir o editor. defina a variavel $EDITOR ou $VISUAL.";
+    PlanValidationOk => "plan validation ok.", "validacao do plano ok.";
+    IssueError => "error: {} {} ({})", "erro: {} {} ({})";
+    IssueWarning => "warning: {} {} ({})", "aviso: {} {} ({})";
+    UnsupportedFormat => "{}. supported: {}", "{}. suportados: {}";
+    InsertDenied => "permission denied: role {} has no INSERT on {} (grant it or use another --conn user)",
+        "permissao negada: o papel {} nao tem INSERT em {} (conceda-o ou use outro usuario em --conn)";
+    SignedChecksums => "signed checksums of {} files.", "checksums de {} arquivos assinados.";
+    #[cfg_attr(not(feature = "object-store"), allow(dead_code))]
+    TaskUploading => "Uploading to {}...", "Enviando para {}...";
+    #[cfg_attr(not(feature = "object-store"), allow(dead_code))]
+    Uploaded => "uploaded {} files to {}.", "{} arquivos enviados para {}.";
+    #[cfg_attr(not(feature = "object-store"), allow(dead_code))]
+    UploadFailed => "upload failed (output kept locally): {}",
+        "falha no envio (saida mantida localmente): {}";
+    #[cfg_attr(feature = "object-store", allow(dead_code))]
+    UploadSkipped => "skipping upload to {}: build datalchemy-cli with --features object-store.",
+        "envio para {} ignorado: compile a datalchemy-cli com --features object-store.";
+    CsvReadError => "  {}: error reading: {}", "  {}: erro de leitura: {}";
+    NoCsvInOutput => "no CSV files found in output.", "nenhum arquivo CSV encontrado na saida.";
+    PreviewingCsv => "previewing {} CSV file(s) of {} (first {} rows).",
+        "visualizando {} arquivo(s) CSV de {} (primeiras {} linhas).";
+    DbtSeedLine => "  {} ({} rows) -> {}", "  {} ({} linhas) -> {}";
+    DbtSeedsExported => "exported {} seeds to {}. run `dbt seed` in the project.",
+        "{} seeds exportadas para {}. rode `dbt seed` no projeto.";
+    EvalHistoryNotUpdated => "eval history not updated: {}",
+        "historico de eval nao atualizado: {}";
+    EvalHistoryTooShort => "eval history has fewer than two evaluations. run /eval first.",
+        "o historico de eval tem menos de duas avaliacoes. rode /eval primeiro.";
+    EvalNotFoundFor => "no evaluation found for '{}'. run /eval first.",
+        "nenhuma avaliacao encontrada para '{}'. rode /eval primeiro.";
+    DoctorOk => "doctor: no issues found.", "doctor: nenhum problema encontrado.";
+    LogNotFound => "log not found.", "log nao encontrado.";
+    FileNotFound => "file not found.", "arquivo nao encontrado.";
+    PromptFilePath => "File path:", "Caminho do arquivo:";
+    VaultStatus => "vault: {}", "cofre: {}";
+    EnvFileNotFound => ".env not found.", ".env nao encontrado.";
+    EnvLoaded => "env loaded into session.", "env carregado na sessao.";
+    ProviderWithoutKey => "{} does not use an API key.", "{} nao usa chave de API.";
+    LlmKeyLoaded => "{} key loaded into session (use /secrets store-session to keep it).",
+        "chave de {} carregada na sessao (use /secrets store-session para guarda-la).";
+    PromptPassphrase => "Passphrase:", "Senha:";
+    NoSessionSecrets => "no session connection to store.",
+        "nenhuma conexao de sessao para guardar.";
+    VaultStored => "vault stored (locked).", "cofre guardado (trancado).";
+    VaultUnlocked => "vault unlocked for this session.", "cofre destrancado nesta sessao.";
+    VaultDeleted => "vault deleted.", "cofre removido.";
+    TaskListingModels => "Listing {} models...", "Listando modelos de {}...";
+    NoModels => "{} lists no models.", "{} nao lista modelos.";
+    ModelsOf => "{} models:", "modelos de {}:";
+    ModelsListFailed => "cannot list models: {}", "nao foi possivel listar os modelos: {}";
+    ModelsFromConfig => "models from config/llm_models.toml:", "modelos de config/llm_models.toml:";
+    NoLlmModel => "no llm model set (use /llm set <provider> <model>).",
+        "nenhum modelo llm definido (use /llm set <provider> <model>).";
+    TaskAskingModel => "Asking {}...", "Perguntando a {}...";
+    LlmAnswered => "llm answered: {}", "llm respondeu: {}";
+    LlmTestFailed => "llm test failed: {}", "teste do llm falhou: {}";
+    LlmDisabled => "llm disabled.", "llm desativado.";
+    PromptProvider => "Provider (gemini|openai|anthropic|ollama):",
+        "Provedor (gemini|openai|anthropic|ollama):";
+    PromptModel => "Model name:", "Nome do modelo:";
+    LlmSettingsUpdated => "llm settings updated.", "configuracoes do llm atualizadas.";
+    ApprovedIntent => "approved: {} ({} paths)", "aprovado: {} ({} caminhos)";
+    Canceled => "Canceled.", "Cancelado.";
+    ResetCanceled => "Reset canceled.", "Reset cancelado.";
+    Welcome => "Welcome to Datalchemy.", "Bem-vindo ao Datalchemy.";
+    AnswerYesNo => "Please answer with y or n.", "Responda com s ou n.";
+    SetupProfileName => "Great! Please enter a name for your profile (e.g. 'dev', 'prod'):",
+        "Otimo! Digite um nome para o seu perfil (ex.: 'dev', 'prod'):";
+    SetupCanceled => "Setup canceled.", "Configuracao cancelada.";
+    ProfileNameEmpty => "Profile name cannot be empty.", "O nome do perfil nao pode ser vazio.";
+    SetupConnection => "Enter your database connection string:",
+        "Digite a string de conexao do seu banco:";
+    ConnectionEmpty => "Connection string cannot be empty.",
+        "A string de conexao nao pode ser vazia.";
+    UnsupportedDatabase => "Unsupported database.", "Banco nao suportado.";
+    UnsupportedDatabaseUrl => "Unsupported database. URL must start with postgres://, postgresql://, or sqlite://",
+        "Banco nao suportado. A URL deve comecar com postgres://, postgresql:// ou sqlite://";
+    ConnectingDatabase => "Connecting to database...", "Conectando ao banco...";
+    ConnectedFetchingSchemas => "Connected! Fetching schemas...", "Conectado! Buscando schemas...";
+    ConnectionFailedPath => "Connection failed: {}. Check the path and try again.",
+        "Falha na conexao: {}. Verifique o caminho e tente de novo.";
+    ConnectionFailedUrl => "Connection failed: {}. Check the URL and try again.",
+        "Falha na conexao: {}. Verifique a URL e tente de novo.";
+    ListSchemasFailed => "Failed to list schemas: {}", "Falha ao listar os schemas: {}";
+    SessionConnectionUpdated => "session connection updated (not saved).",
+        "conexao de sessao atualizada (nao salva).";
+    SessionConnectionUpdatedRun => "session connection updated for this run.",
+        "conexao de sessao atualizada para esta execucao.";
+    MissingConnectionString => "missing connection string. please enter it again.",
+        "string de conexao ausente. digite-a de novo.";
+    StartingIntrospection => "Starting introspection...", "Iniciando a introspeccao...";
+    IntrospectionComplete => "Introspection complete.", "Introspeccao concluida.";
+    SchemaValidationFailed => "Schema validation failed: {}", "Falha na validacao do schema: {}";
+    IntrospectionError => "Introspection error: {}", "Erro na introspeccao: {}";
+    ValueEmpty => "Value cannot be empty.", "O valor nao pode ser vazio.";
+    SetupError => "Error: {}", "Erro: {}";
+    CheckConnection => "Please check connection string and try again.",
+        "Verifique a string de conexao e tente de novo.";
+    IntrospectionSuccessful => "Introspection successful!", "Introspeccao bem-sucedida!";
+    SetupComplete => "Setup complete. Type /help to see commands.",
+        "Configuracao concluida. Digite /help para ver os comandos.";
+    EnterConnectionAgain => "Please enter connection string again:",
+        "Digite a string de conexao de novo:";
+    AnnotateManifestNotFound => "{} manifest not found: {}", "manifest de {} nao encontrado: {}";
+    TagsOf => "tags of {}: {}", "tags de {}: {}";
+    NotesCleared => "notes cleared.", "notas removidas.";
+    NoteAdded => "note added.", "nota adicionada.";
+    RunSchemaNotFound => "schema.json not found for run '{}'.",
+        "schema.json nao encontrado para o run '{}'.";
+    ComparingRuns => "comparing runs {} -> {}: {} difference(s).",
+        "comparando runs {} -> {}: {} diferenca(s).";
+    RuleFormTable => "table (schema.table):", "tabela (schema.tabela):";
+    RuleFormColumn => "column:", "coluna:";
+    RuleFormGenerator => "generator id (end with ? to search, e.g. semantic.br?):",
+        "id do gerador (termine com ? para buscar, ex.: semantic.br?):";
+    RuleFormParams => "params (JSON object, - for none):", "params (objeto JSON, - para nenhum):";
+    RuleFormTransforms => "transforms (<id> [params json]; ..., - for none):",
+        "transforms (<id> [params json]; ..., - para nenhum):";
+    TableNotFound => "table not found: {}", "tabela nao encontrada: {}";
+    ColumnNotFound => "column not found: {}", "coluna nao encontrada: {}";
+    GeneratorsMatch => "{} generators match '{}':", "{} geradores correspondem a '{}':";
+    UnknownGenerator => "unknown generator: {}. end with ? to search.",
+        "gerador desconhecido: {}. termine com ? para buscar.";
+    SuggestedGenerator => "suggested: {}", "sugerido: {}";
+    GeneratorsFor => "for {}: {}", "para {}: {}";
+    NoDeclaredParams => "{}: no declared params", "{}: nenhum param declarado";
+    GeneratorParams => "{} params: {} (* required)", "params de {}: {} (* obrigatorio)";
+    RuleSaved => "rule saved: {}", "regra salva: {}";
+    ExpectedSchemaTable => "expected schema.table, got '{}'",
+        "esperado schema.tabela, recebido '{}'";
+    NoGeneratorRule => "no generator rule for {}.{}", "nenhuma regra de gerador para {}.{}";
+    RuleRemoved => "rule removed: {}.{}", "regra removida: {}.{}";
+    PlanNotChanged => "plan.json not changed.", "plan.json nao alterado.";
+}
+
+impl Msg {
+    /// Text in `language` with each `{}` replaced by the next of `args`.
+    pub fn fill(self, language: Language, args: &[&dyn Display]) -> String {
+        let mut args = args.iter();
+        let mut parts = self.text(language).split("{}");
+        let mut text = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            if let Some(arg) = args.next() {
+                text.push_str(&arg.to_string());
+            }
+            text.push_str(part);
+        }
+        text
+    }
+}
@@ -0,0 +1,139 @@
+//! Source database sample for the realism score of `datalchemy eval`.
+//!
+//! Reads the row count of each table and, over at most `sample_rows` random
+//! rows (`TABLESAMPLE BERNOULLI`), the category frequencies of its boolean,
+//! enum and text columns. Columns whose name implies PII are never selected,
+//! text columns with more than [`MAX_CATEGORIES`] values are dropped and the
+//! connection string is stored redacted.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use datalchemy_core::ddl::quote_ident;
+use datalchemy_core::{DatabaseSchema, TableKind, redact_connection_string};
+use datalchemy_eval::compare::MAX_CATEGORIES;
+use datalchemy_eval::realism::{
+    SourceColumn, SourceSample, SourceTable, is_categorical, is_redacted,
+};
+use datalchemy_plan::Plan;
+use sqlx::Row;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+
+use crate::CliError;
+
+/// Rows sampled per table when none is given.
+pub(crate) const DEFAULT_SAMPLE_ROWS: u64 = 10_000;
+
+/// Sample the plan's target tables (every base table without a plan).
+pub(crate) async fn sample_source(
+    conn: &str,
+    schema: &DatabaseSchema,
+    plan: Option<&Plan>,
+    sample_rows: u64,
+) -> Result<SourceSample, CliError> {
+    if crate::detect_engine(conn)? != "postgres" {
+        return Err(CliError::UnsupportedEngine(conn.to_string()));
+    }
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect(conn)
+        .await?;
+
+    let mut tables = Vec::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            let sampled = match plan {
+                Some(plan) => plan
+                    .targets
+                    .iter()
+                    .any(|target| target.schema == db_schema.name && target.table == table.name),
+                None => matches!(table.kind, TableKind::Table | TableKind::PartitionedTable),
+            };
+            if sampled {
+                tables
+                    .push(sample_table(&pool, schema, &db_schema.name, table, sample_rows).await?);
+            }
+        }
+    }
+    pool.close().await;
+
+    Ok(SourceSample {
+        connection: Some(redact_connection_string(conn)),
+        sample_rows,
+        tables,
+    })
+}
+
+async fn sample_table(
+    pool: &PgPool,
+    schema: &DatabaseSchema,
+    schema_name: &str,
+    table: &datalchemy_core::Table,
+    sample_rows: u64,
+) -> Result<SourceTable, CliError> {
+    let qualified = format!("{}.{}", quote_ident(schema_name), quote_ident(&table.name));
+    let row_count = sqlx::query_scalar::<_, i64>(&format!("select count(*) from {qualified}"))
+        .fetch_one(pool)
+        .await?
+        .max(0) as u64;
+
+    let mut redacted_columns = Vec::new();
+    let mut columns = Vec::new();
+    for column in &table.columns {
+        if is_redacted(&column.name) {
+            redacted_columns.push(column.name.clone());
+        } else if is_categorical(schema, column) {
+            columns.push(column.name.as_str());
+        }
+    }
+
+    let mut sampled_rows = 0;
+    let mut frequencies = vec![BTreeMap::new(); columns.len()];
+    if !columns.is_empty() && row_count > 0 && sample_rows > 0 {
+        // Oversample a little so the LIMIT is usually reached.
+        let tablesample = if row_count > sample_rows {
+            let percent = (sample_rows as f64 * 120.0 / row_count as f64).min(100.0);
+            format!(" tablesample bernoulli ({percent:.6})")
+        } else {
+            String::new()
+        };
+        let select = columns
+            .iter()
+            .map(|column| format!("{}::text", quote_ident(column)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let rows = sqlx::query(&format!(
+            "select {select} from {qualified}{tablesample} limit {sample_rows}"
+        ))
+        .fetch_all(pool)
+        .await?;
+        sampled_rows = rows.len() as u64;
+        for row in &rows {
+            for (idx, counts) in frequencies.iter_mut().enumerate() {
+                if let Some(value) = row.try_get::<Option<String>, _>(idx)? {
+                    *counts.entry(value).or_insert(0u64) += 1;
+                }
+            }
+        }
+    }
+
+    Ok(SourceTable {
+        schema: schema_name.to_string(),
+        table: table.name.clone(),
+        row_count,
+        sampled_rows,
+        columns: columns
+            .into_iter()
+            .zip(frequencies)
+            .filter(|(_, frequencies)| {
+                !frequencies.is_empty() && frequencies.len() <= MAX_CATEGORIES
+            })
+            .map(|(column, frequencies)| SourceColumn {
+                column: column.to_string(),
+                frequencies,
+            })
+            .collect(),
+        redacted_columns,
+    })
+}
@@ -0,0 +1,83 @@
+//! Anthropic Messages API.
+
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::json;
+
+use super::{CompletionRequest, LlmClient, LlmError, missing, send_json};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.anthropic.com/v1";
+const API_VERSION: &str = "2023-06-01";
+const PROVIDER: &str = "anthropic";
+
+pub struct AnthropicClient {
+    pub http: Client,
+    pub base_url: String,
+    pub api_key: String,
+}
+
+impl AnthropicClient {
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+    }
+}
+
+impl LlmClient for AnthropicClient {
+    fn list_models(&self) -> Result<Vec<String>, LlmError> {
+        let mut models = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let mut request = self
+                .http
+                .get(format!("{}/models", self.base_url))
+                .query(&[("limit", "1000")]);
+            if let Some(id) = &after {
+                request = request.query(&[("after_id", id)]);
+            }
+            let body = send_json(PROVIDER, self.authorized(request))?;
+            let page = body["data"]
+                .as_array()
+                .ok_or_else(|| missing(PROVIDER, "data"))?;
+            models.extend(
+                page.iter()
+                    .filter_map(|model| model["id"].as_str().map(str::to_string)),
+            );
+            after = body["last_id"].as_str().map(str::to_string);
+            if !body["has_more"].as_bool().unwrap_or(false) || after.is_none() {
+                break;
+            }
+        }
+        models.sort();
+        Ok(models)
+    }
+
+    fn complete(&self, request: &CompletionRequest) -> Result<String, LlmError> {
+        let mut body = json!({
+            "model": request.model,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+            "messages": [{"role": "user", "content": request.prompt}],
+        });
+        if let Some(system) = &request.system {
+            body["system"] = json!(system);
+        }
+        let response = send_json(
+            PROVIDER,
+            self.authorized(
+                self.http
+                    .post(format!("{}/messages", self.base_url))
+                    .json(&body),
+            ),
+        )?;
+        let content = response["content"]
+            .as_array()
+            .ok_or_else(|| missing(PROVIDER, "content"))?;
+        Ok(content
+            .iter()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect::<Vec<_>>()
+            .join(""))
+    }
+}
@@ -0,0 +1,121 @@
+//! LLM refinement of `classify`.
+//!
+//! The model sees the redacted schema summary of [`schema_summary`], the
+//! heuristic labels and the semantic types, and answers with the labels to
+//! change: a type id, or `null` to drop a wrong label. Suggestions for
+//! unknown columns or types are skipped; there is a single round.
+
+use std::fmt::Write as _;
+
+use datalchemy_core::DatabaseSchema;
+use serde_json::Value;
+
+use super::planner::{candidate_rules, schema_summary};
+use super::{CompletionRequest, LlmClient};
+use crate::CliError;
+use crate::classify::{ClassSource, Classification, ColumnClass, SemanticType};
+
+const SYSTEM_PROMPT: &str = "You label database columns with semantic types to find personal \
+data. Answer with a JSON array only, without prose.";
+
+/// Apply the model's corrections to `classification`; returns how many
+/// labels changed. `source` is the provider and model.
+pub fn refine_classification(
+    client: &dyn LlmClient,
+    source: (&str, &str),
+    schema: &DatabaseSchema,
+    classification: &mut Classification,
+) -> Result<usize, CliError> {
+    let mut request = CompletionRequest::new(source.1, prompt(schema, classification));
+    request.system = Some(SYSTEM_PROMPT.to_string());
+    let response = client.complete(&request)?;
+    let suggestions = candidate_rules(&response).map_err(CliError::Plan)?;
+
+    let mut changed = 0;
+    for suggestion in &suggestions {
+        let field = |key: &str| suggestion.get(key).and_then(Value::as_str);
+        let (Some(schema_name), Some(table), Some(column)) =
+            (field("schema"), field("table"), field("column"))
+        else {
+            continue;
+        };
+        if !has_column(schema, schema_name, table, column) {
+            continue;
+        }
+        let semantic_type = match suggestion.get("semantic_type") {
+            Some(Value::Null) => None,
+            Some(Value::String(id)) => match SemanticType::parse(id) {
+                Some(semantic_type) => Some(semantic_type),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let position = classification.columns.iter().position(|class| {
+            class.schema == schema_name && class.table == table && class.column == column
+        });
+        match (position, semantic_type) {
+            (Some(idx), Some(semantic_type)) => {
+                if classification.columns[idx].semantic_type != semantic_type {
+                    classification.columns[idx] = ColumnClass::new(
+                        schema_name,
+                        table,
+                        column,
+                        semantic_type,
+                        ClassSource::Llm,
+                    );
+                    changed += 1;
+                }
+            }
+            (Some(idx), None) => {
+                classification.columns.remove(idx);
+                changed += 1;
+            }
+            (None, Some(semantic_type)) => {
+                classification.columns.push(ColumnClass::new(
+                    schema_name,
+                    table,
+                    column,
+                    semantic_type,
+                    ClassSource::Llm,
+                ));
+                changed += 1;
+            }
+            (None, None) => {}
+        }
+    }
+    classification.llm = Some(format!("{}/{}", source.0, source.1));
+    Ok(changed)
+}
+
+fn prompt(schema: &DatabaseSchema, classification: &Classification) -> String {
+    let types: Vec<&str> = SemanticType::ALL.iter().map(SemanticType::as_str).collect();
+    let mut prompt = format!("Semantic types: {}\n\n", types.join(", "));
+    let _ = writeln!(prompt, "Schema:\n{}", schema_summary(schema));
+    prompt.push_str("Current labels:\n");
+    for class in &classification.columns {
+        let _ = writeln!(
+            prompt,
+            "- {}: {}",
+            class.path(),
+            class.semantic_type.as_str()
+        );
+    }
+    prompt.push_str(
+        "\nReturn a JSON array with the labels to change, added or removed:\n\
+         {\"schema\": \"public\", \"table\": \"users\", \"column\": \"doc\", \
+         \"semantic_type\": \"cpf\"}\n\
+         Use `null` as semantic_type to remove a wrong label and `[]` when every \
+         label is right. Only use the types listed above.",
+    );
+    prompt
+}
+
+fn has_column(schema: &DatabaseSchema, schema_name: &str, table: &str, column: &str) -> bool {
+    schema
+        .schemas
+        .iter()
+        .filter(|db_schema| db_schema.name == schema_name)
+        .flat_map(|db_schema| &db_schema.tables)
+        .filter(|candidate| candidate.name == table)
+        .any(|candidate| candidate.columns.iter().any(|col| col.name == column))
+}
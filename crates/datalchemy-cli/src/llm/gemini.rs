@@ -0,0 +1,81 @@
+//! Google Gemini (`generateContent`).
+
+use reqwest::blocking::Client;
+use serde_json::json;
+
+use super::{CompletionRequest, LlmClient, LlmError, missing, send_json};
+
+pub const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+const PROVIDER: &str = "gemini";
+
+pub struct GeminiClient {
+    pub http: Client,
+    pub base_url: String,
+    pub api_key: String,
+}
+
+impl LlmClient for GeminiClient {
+    fn list_models(&self) -> Result<Vec<String>, LlmError> {
+        let mut models = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut request = self
+                .http
+                .get(format!("{}/models", self.base_url))
+                .header("x-goog-api-key", &self.api_key)
+                .query(&[("pageSize", "1000")]);
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token)]);
+            }
+            let body = send_json(PROVIDER, request)?;
+            for model in body["models"].as_array().into_iter().flatten() {
+                let generates = model["supportedGenerationMethods"]
+                    .as_array()
+                    .is_some_and(|methods| methods.iter().any(|m| m == "generateContent"));
+                if generates && let Some(name) = model["name"].as_str() {
+                    models.push(name.trim_start_matches("models/").to_string());
+                }
+            }
+            page_token = body["nextPageToken"]
+                .as_str()
+                .filter(|token| !token.is_empty())
+                .map(str::to_string);
+            if page_token.is_none() {
+                break;
+            }
+        }
+        models.sort();
+        Ok(models)
+    }
+
+    fn complete(&self, request: &CompletionRequest) -> Result<String, LlmError> {
+        let mut body = json!({
+            "contents": [{"role": "user", "parts": [{"text": request.prompt}]}],
+            "generationConfig": {
+                "temperature": request.temperature,
+                "maxOutputTokens": request.max_tokens,
+            },
+        });
+        if let Some(system) = &request.system {
+            body["systemInstruction"] = json!({"parts": [{"text": system}]});
+        }
+        let response = send_json(
+            PROVIDER,
+            self.http
+                .post(format!(
+                    "{}/models/{}:generateContent",
+                    self.base_url, request.model
+                ))
+                .header("x-goog-api-key", &self.api_key)
+                .json(&body),
+        )?;
+        let parts = response["candidates"][0]["content"]["parts"]
+            .as_array()
+            .ok_or_else(|| missing(PROVIDER, "candidates[0].content.parts"))?;
+        Ok(parts
+            .iter()
+            .filter_map(|part| part["text"].as_str())
+            .collect::<Vec<_>>()
+            .join(""))
+    }
+}
@@ -0,0 +1,199 @@
+//! LLM providers behind the [`LlmClient`] trait.
+//!
+//! Gemini, OpenAI-compatible servers (`/v1/chat/completions`), Anthropic and
+//! a local Ollama are reached over HTTP with a blocking client; none of them
+//! is called from inside the tokio runtime. API keys come from the session
+//! (the vault after `/secrets unlock`, or `/secrets llm-key`), else from the
+//! provider's environment variable; Ollama needs no key.
+
+mod anthropic;
+pub mod classifier;
+mod gemini;
+mod ollama;
+mod openai;
+pub mod planner;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::workspace::LlmProvider;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("llm is disabled (use /llm set <provider> <model>)")]
+    Disabled,
+    #[error("missing API key for {provider} (set {env} or use /secrets llm-key {provider})")]
+    MissingKey {
+        provider: &'static str,
+        env: &'static str,
+    },
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("{provider} returned {status}: {message}")]
+    Api {
+        provider: &'static str,
+        status: u16,
+        message: String,
+    },
+    #[error("unexpected {provider} response: {message}")]
+    Response {
+        provider: &'static str,
+        message: String,
+    },
+}
+
+/// One prompt/answer exchange.
+#[derive(Debug, Clone)]
+pub struct CompletionRequest {
+    pub model: String,
+    pub system: Option<String>,
+    pub prompt: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+impl CompletionRequest {
+    pub fn new(model: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            system: None,
+            prompt: prompt.into(),
+            temperature: 0.2,
+            max_tokens: 4096,
+        }
+    }
+}
+
+pub trait LlmClient {
+    /// Models the provider offers for text generation, sorted.
+    fn list_models(&self) -> Result<Vec<String>, LlmError>;
+
+    /// Text of the model answer.
+    fn complete(&self, request: &CompletionRequest) -> Result<String, LlmError>;
+}
+
+impl LlmProvider {
+    /// Environment variable holding the API key, for providers that need one.
+    pub fn key_env(&self) -> Option<&'static str> {
+        match self {
+            Self::Gemini => Some("GEMINI_API_KEY"),
+            Self::OpenAi => Some("OPENAI_API_KEY"),
+            Self::Anthropic => Some("ANTHROPIC_API_KEY"),
+            Self::Ollama | Self::Off => None,
+        }
+    }
+}
+
+/// Providers that take an API key.
+pub const KEYED_PROVIDERS: [LlmProvider; 3] = [
+    LlmProvider::Gemini,
+    LlmProvider::OpenAi,
+    LlmProvider::Anthropic,
+];
+
+/// Client for `provider`. `base_url` overrides the default endpoint (any
+/// OpenAI-compatible server, a remote Ollama); `session_keys` maps provider
+/// names to API keys.
+pub fn client_for(
+    provider: &LlmProvider,
+    base_url: Option<&str>,
+    session_keys: &BTreeMap<String, String>,
+) -> Result<Box<dyn LlmClient>, LlmError> {
+    let http = Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()?;
+    let base_url = base_url.map(|url| url.trim_end_matches('/').to_string());
+    let api_key = api_key(provider, session_keys);
+    let required_key = || {
+        api_key.clone().ok_or(LlmError::MissingKey {
+            provider: provider.as_str(),
+            env: provider.key_env().unwrap_or_default(),
+        })
+    };
+    Ok(match provider {
+        LlmProvider::Off => return Err(LlmError::Disabled),
+        LlmProvider::Gemini => Box::new(gemini::GeminiClient {
+            http,
+            base_url: base_url.unwrap_or_else(|| gemini::DEFAULT_BASE_URL.to_string()),
+            api_key: required_key()?,
+        }),
+        LlmProvider::OpenAi => {
+            // Local OpenAI-compatible servers usually run without a key.
+            let api_key = match base_url {
+                Some(_) => api_key.clone(),
+                None => Some(required_key()?),
+            };
+            Box::new(openai::OpenAiClient {
+                http,
+                base_url: base_url.unwrap_or_else(|| openai::DEFAULT_BASE_URL.to_string()),
+                api_key,
+            })
+        }
+        LlmProvider::Anthropic => Box::new(anthropic::AnthropicClient {
+            http,
+            base_url: base_url.unwrap_or_else(|| anthropic::DEFAULT_BASE_URL.to_string()),
+            api_key: required_key()?,
+        }),
+        LlmProvider::Ollama => Box::new(ollama::OllamaClient {
+            http,
+            base_url: base_url.unwrap_or_else(|| ollama::DEFAULT_BASE_URL.to_string()),
+        }),
+    })
+}
+
+/// Session key of `provider`, else its environment variable.
+fn api_key(provider: &LlmProvider, session_keys: &BTreeMap<String, String>) -> Option<String> {
+    session_keys
+        .get(provider.as_str())
+        .cloned()
+        .or_else(|| provider.key_env().and_then(|env| std::env::var(env).ok()))
+        .filter(|key| !key.trim().is_empty())
+}
+
+/// Send `request` and decode the JSON body, turning error statuses into
+/// [`LlmError::Api`] with the provider's error message.
+fn send_json(provider: &'static str, request: RequestBuilder) -> Result<Value, LlmError> {
+    let response: Response = request.send()?;
+    let status = response.status();
+    let body = response.text()?;
+    if !status.is_success() {
+        let message = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|value| error_message(&value))
+            .unwrap_or(body);
+        return Err(LlmError::Api {
+            provider,
+            status: status.as_u16(),
+            message,
+        });
+    }
+    serde_json::from_str(&body).map_err(|err| LlmError::Response {
+        provider,
+        message: err.to_string(),
+    })
+}
+
+/// `error.message` (OpenAI, Anthropic, Gemini) or `error` (Ollama).
+fn error_message(value: &Value) -> Option<String> {
+    let error = value.get("error")?;
+    error
+        .get("message")
+        .and_then(Value::as_str)
+        .or_else(|| error.as_str())
+        .map(str::to_string)
+}
+
+fn missing(provider: &'static str, field: &str) -> LlmError {
+    LlmError::Response {
+        provider,
+        message: format!("missing `{field}`"),
+    }
+}
@@ -0,0 +1,58 @@
+//! Local Ollama server (`/api/chat`); no API key.
+
+use reqwest::blocking::Client;
+use serde_json::json;
+
+use super::{CompletionRequest, LlmClient, LlmError, missing, send_json};
+
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const PROVIDER: &str = "ollama";
+
+pub struct OllamaClient {
+    pub http: Client,
+    pub base_url: String,
+}
+
+impl LlmClient for OllamaClient {
+    fn list_models(&self) -> Result<Vec<String>, LlmError> {
+        let body = send_json(
+            PROVIDER,
+            self.http.get(format!("{}/api/tags", self.base_url)),
+        )?;
+        let mut models: Vec<String> = body["models"]
+            .as_array()
+            .ok_or_else(|| missing(PROVIDER, "models"))?
+            .iter()
+            .filter_map(|model| model["name"].as_str().map(str::to_string))
+            .collect();
+        models.sort();
+        Ok(models)
+    }
+
+    fn complete(&self, request: &CompletionRequest) -> Result<String, LlmError> {
+        let mut messages = Vec::new();
+        if let Some(system) = &request.system {
+            messages.push(json!({"role": "system", "content": system}));
+        }
+        messages.push(json!({"role": "user", "content": request.prompt}));
+        let body = json!({
+            "model": request.model,
+            "messages": messages,
+            "stream": false,
+            "options": {
+                "temperature": request.temperature,
+                "num_predict": request.max_tokens,
+            },
+        });
+        let response = send_json(
+            PROVIDER,
+            self.http
+                .post(format!("{}/api/chat", self.base_url))
+                .json(&body),
+        )?;
+        response["message"]["content"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| missing(PROVIDER, "message.content"))
+    }
+}
@@ -0,0 +1,68 @@
+//! OpenAI and OpenAI-compatible servers (`/chat/completions`).
+
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::json;
+
+use super::{CompletionRequest, LlmClient, LlmError, missing, send_json};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const PROVIDER: &str = "openai";
+
+pub struct OpenAiClient {
+    pub http: Client,
+    pub base_url: String,
+    /// Optional for compatible servers configured with a base URL.
+    pub api_key: Option<String>,
+}
+
+impl OpenAiClient {
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+}
+
+impl LlmClient for OpenAiClient {
+    fn list_models(&self) -> Result<Vec<String>, LlmError> {
+        let body = send_json(
+            PROVIDER,
+            self.authorized(self.http.get(format!("{}/models", self.base_url))),
+        )?;
+        let mut models: Vec<String> = body["data"]
+            .as_array()
+            .ok_or_else(|| missing(PROVIDER, "data"))?
+            .iter()
+            .filter_map(|model| model["id"].as_str().map(str::to_string))
+            .collect();
+        models.sort();
+        Ok(models)
+    }
+
+    fn complete(&self, request: &CompletionRequest) -> Result<String, LlmError> {
+        let mut messages = Vec::new();
+        if let Some(system) = &request.system {
+            messages.push(json!({"role": "system", "content": system}));
+        }
+        messages.push(json!({"role": "user", "content": request.prompt}));
+        let body = json!({
+            "model": request.model,
+            "messages": messages,
+            "temperature": request.temperature,
+            "max_tokens": request.max_tokens,
+        });
+        let response = send_json(
+            PROVIDER,
+            self.authorized(
+                self.http
+                    .post(format!("{}/chat/completions", self.base_url))
+                    .json(&body),
+            ),
+        )?;
+        response["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| missing(PROVIDER, "choices[0].message.content"))
+    }
+}
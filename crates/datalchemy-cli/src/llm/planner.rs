@@ -0,0 +1,492 @@
+//! LLM-assisted `/plan new`.
+//!
+//! The model sees a redacted schema summary (names, types, nullability and
+//! keys; no comments, defaults, check expressions, enum labels or connection
+//! data) plus the generator registry, and answers with column generator
+//! rules. Each candidate is checked against the plan JSON Schema, the
+//! database schema and the registry; rejected ones go back to the model with
+//! the reasons, up to [`MAX_ATTEMPTS`] rounds. Columns left without an
+//! accepted rule keep the heuristic generator of [`smart_plan`].
+
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::fmt::Write as _;
+
+use chrono::Utc;
+use datalchemy_core::{Column, Constraint, DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratorRegistry;
+use datalchemy_generate::params::validate_params;
+use datalchemy_plan::{ColumnGeneratorRule, Plan, Rule};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::{CompletionRequest, LlmClient};
+use crate::CliError;
+use crate::classify::{Classification, read_classification};
+use crate::plan::{
+    check_registry, is_auto_column, smart_plan, validation_report, write_plan_artifacts,
+};
+use crate::workspace::{LlmProvider, WorkspacePaths, WorkspaceSettings, write_json_atomic};
+
+/// Rounds of suggestions: the first answer plus retries for rejected rules.
+pub const MAX_ATTEMPTS: usize = 3;
+pub const TRANSCRIPT_FILE: &str = "llm_transcript.json";
+
+const SYSTEM_PROMPT: &str = "You assign synthetic data generators to database columns for a \
+datalchemy generation plan. Answer with a JSON array only, without prose.";
+const MAX_TOKENS: u32 = 8192;
+
+/// Every exchange of an LLM planning session, written as `llm_transcript.json`.
+#[derive(Debug, Serialize)]
+pub struct PlanTranscript {
+    pub provider: String,
+    pub model: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub system: String,
+    pub schema_summary: String,
+    pub attempts: Vec<PlanAttempt>,
+    /// Rules taken from the model.
+    pub llm_rules: usize,
+    /// Columns that kept the heuristic generator.
+    pub heuristic_rules: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanAttempt {
+    pub attempt: usize,
+    pub prompt: String,
+    pub response: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub accepted: Vec<ColumnGeneratorRule>,
+    pub rejected: Vec<RejectedRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RejectedRule {
+    pub rule: Value,
+    pub reason: String,
+}
+
+impl PlanTranscript {
+    /// One-line outcome for `/plan new` and `plan new`.
+    pub fn summary(&self) -> String {
+        let unresolved = self
+            .attempts
+            .last()
+            .map(|attempt| attempt.rejected.len())
+            .unwrap_or_default();
+        format!(
+            "{} rules from {}, {} heuristic, {unresolved} rejected suggestion(s) left after {} attempt(s)",
+            self.llm_rules,
+            self.model,
+            self.heuristic_rules,
+            self.attempts.len()
+        )
+    }
+}
+
+/// Provider and model `plan new` asks, when the workspace enables an LLM.
+pub fn planner_model(settings: &WorkspaceSettings) -> Option<(LlmProvider, String)> {
+    if !settings.llm_enabled || settings.llm_provider == LlmProvider::Off {
+        return None;
+    }
+    settings
+        .llm_model
+        .clone()
+        .map(|model| (settings.llm_provider.clone(), model))
+}
+
+/// `(schema, table, column)`.
+type ColumnKey = (String, String, String);
+
+/// Every column in schema order; foreign key and database-filled columns
+/// carry the reason they take no rule.
+type Columns = Vec<(ColumnKey, Result<(), &'static str>)>;
+
+/// Ask `client` for column rules and write the plan, with the transcript, to
+/// `plans/<plan_id>`. `source` is the provider and model recorded in
+/// `plan.meta.json`.
+pub fn write_llm_plan(
+    paths: &WorkspacePaths,
+    plan_id: &str,
+    run_id: &str,
+    schema: &DatabaseSchema,
+    client: &dyn LlmClient,
+    source: (&str, &str),
+) -> Result<(Plan, PlanTranscript), CliError> {
+    let classification = read_classification(paths, run_id)?;
+    let (plan, transcript) = llm_plan(client, source, schema, classification.as_ref())?;
+    write_plan_artifacts(
+        paths,
+        plan_id,
+        run_id,
+        schema,
+        &plan,
+        source,
+        &format!("{}\n\n{}", transcript.system, transcript.attempts[0].prompt),
+    )?;
+    write_json_atomic(
+        &paths.plans_dir.join(plan_id).join(TRANSCRIPT_FILE),
+        &transcript,
+    )?;
+    Ok((plan, transcript))
+}
+
+/// Plan `schema` with the model; `classification` shapes the heuristic
+/// fallback rules. A failed request on the first round is an error; on a
+/// retry it ends the session with the rules accepted so far.
+pub fn llm_plan(
+    client: &dyn LlmClient,
+    source: (&str, &str),
+    schema: &DatabaseSchema,
+    classification: Option<&Classification>,
+) -> Result<(Plan, PlanTranscript), CliError> {
+    let mut plan = smart_plan(schema, classification);
+    let heuristic = std::mem::take(&mut plan.rules);
+    let mut probe = serde_json::to_value(&plan)?;
+    let columns = plannable_columns(schema);
+
+    let mut transcript = PlanTranscript {
+        provider: source.0.to_string(),
+        model: source.1.to_string(),
+        started_at: Utc::now().to_rfc3339(),
+        finished_at: None,
+        system: SYSTEM_PROMPT.to_string(),
+        schema_summary: schema_summary(schema),
+        attempts: Vec::new(),
+        llm_rules: 0,
+        heuristic_rules: 0,
+    };
+    let mut accepted: BTreeMap<ColumnKey, ColumnGeneratorRule> = BTreeMap::new();
+    let mut prompt = first_prompt(&transcript.schema_summary);
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request = CompletionRequest::new(source.1, std::mem::take(&mut prompt));
+        request.system = Some(SYSTEM_PROMPT.to_string());
+        request.max_tokens = MAX_TOKENS;
+        let mut record = PlanAttempt {
+            attempt,
+            prompt: request.prompt.clone(),
+            response: None,
+            error: None,
+            accepted: Vec::new(),
+            rejected: Vec::new(),
+        };
+        let response = match client.complete(&request) {
+            Ok(response) => response,
+            Err(err) if attempt == 1 => return Err(err.into()),
+            Err(err) => {
+                record.error = Some(err.to_string());
+                transcript.attempts.push(record);
+                break;
+            }
+        };
+
+        match candidate_rules(&response) {
+            Ok(candidates) => {
+                for candidate in candidates {
+                    match check_candidate(&candidate, &columns, &mut probe, schema) {
+                        Ok((key, rule)) if !record.accepted.iter().any(|r| rule_key(r) == key) => {
+                            record.accepted.push(rule.clone());
+                            accepted.insert(key, rule);
+                        }
+                        Ok(_) => record.rejected.push(RejectedRule {
+                            rule: candidate,
+                            reason: "duplicate rule for this column".to_string(),
+                        }),
+                        Err(reason) => record.rejected.push(RejectedRule {
+                            rule: candidate,
+                            reason,
+                        }),
+                    }
+                }
+            }
+            Err(reason) => record.rejected.push(RejectedRule {
+                rule: Value::String(truncate(&response, 200)),
+                reason,
+            }),
+        }
+        record.response = Some(response);
+
+        let rejected = record.rejected.clone();
+        transcript.attempts.push(record);
+        if rejected.is_empty() {
+            break;
+        }
+        prompt = retry_prompt(&rejected);
+    }
+
+    // One rule per column in schema order: the model's, else the heuristic one.
+    for rule in heuristic {
+        let Rule::ColumnGenerator(rule) = rule else {
+            continue;
+        };
+        if let Entry::Vacant(entry) = accepted.entry(rule_key(&rule)) {
+            transcript.heuristic_rules += 1;
+            entry.insert(rule);
+        }
+    }
+    transcript.llm_rules = accepted.len() - transcript.heuristic_rules;
+    plan.rules = columns
+        .iter()
+        .filter_map(|(key, _)| accepted.remove(key))
+        .map(Rule::ColumnGenerator)
+        .collect();
+    transcript.finished_at = Some(Utc::now().to_rfc3339());
+
+    let report = validation_report(&serde_json::to_value(&plan)?, schema)?;
+    if !report.is_ok() {
+        let issues: Vec<String> = report
+            .errors
+            .iter()
+            .map(|issue| format!("{} {}: {}", issue.code, issue.path, issue.message))
+            .collect();
+        return Err(CliError::Plan(format!(
+            "llm plan failed validation: {}",
+            issues.join("; ")
+        )));
+    }
+    Ok((plan, transcript))
+}
+
+/// Schema summary sent to the model: engine, tables and columns with type,
+/// nullability and keys.
+pub fn schema_summary(schema: &DatabaseSchema) -> String {
+    let mut summary = format!("engine: {}\n", schema.engine);
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            let _ = writeln!(summary, "table {}.{}", db_schema.name, table.name);
+            for column in &table.columns {
+                let _ = writeln!(summary, "  {}", column_line(table, column));
+            }
+        }
+    }
+    summary
+}
+
+fn column_line(table: &Table, column: &Column) -> String {
+    let data_type = if column.column_type.data_type == "USER-DEFINED" {
+        column.column_type.udt_name.as_str()
+    } else {
+        column.column_type.data_type.as_str()
+    };
+    let mut line = format!(
+        "{} {}{}",
+        column.name,
+        data_type,
+        if column.is_nullable { "" } else { " not null" }
+    );
+    let single = |columns: &[String]| columns.len() == 1 && columns[0] == column.name;
+    for constraint in &table.constraints {
+        match constraint {
+            Constraint::PrimaryKey(pk) if pk.columns.contains(&column.name) => {
+                line.push_str(if single(&pk.columns) {
+                    " pk"
+                } else {
+                    " pk-part"
+                });
+            }
+            Constraint::Unique(unique) if single(&unique.columns) => line.push_str(" unique"),
+            _ => {}
+        }
+    }
+    if let Some(fk) = foreign_key(table, column) {
+        let _ = write!(
+            line,
+            " fk -> {}.{} (filled from parent rows; no rule)",
+            fk.0, fk.1
+        );
+    } else if is_auto_column(column) {
+        line.push_str(" (filled by the database; no rule)");
+    }
+    line
+}
+
+fn foreign_key<'a>(table: &'a Table, column: &Column) -> Option<(&'a str, &'a str)> {
+    table
+        .constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            Constraint::ForeignKey(fk) if fk.columns.contains(&column.name) => {
+                Some((fk.referenced_schema.as_str(), fk.referenced_table.as_str()))
+            }
+            _ => None,
+        })
+}
+
+fn plannable_columns(schema: &DatabaseSchema) -> Columns {
+    let mut columns = Vec::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            for column in &table.columns {
+                let status = if foreign_key(table, column).is_some() {
+                    Err("foreign key column, filled from parent rows")
+                } else if is_auto_column(column) {
+                    Err("column filled by the database")
+                } else {
+                    Ok(())
+                };
+                columns.push((
+                    (
+                        db_schema.name.clone(),
+                        table.name.clone(),
+                        column.name.clone(),
+                    ),
+                    status,
+                ));
+            }
+        }
+    }
+    columns
+}
+
+fn first_prompt(summary: &str) -> String {
+    let registry = GeneratorRegistry::new();
+    let mut prompt = String::from("Generators (id: params, * = required):\n");
+    for id in registry.generator_ids() {
+        let params = registry
+            .generator(id)
+            .map(|generator| generator.params())
+            .unwrap_or_default();
+        let params: Vec<String> = params
+            .iter()
+            .map(|spec| {
+                format!(
+                    "{}{} {}",
+                    spec.key,
+                    if spec.required { "*" } else { "" },
+                    spec.kind.as_str()
+                )
+            })
+            .collect();
+        if params.is_empty() {
+            let _ = writeln!(prompt, "- {id}");
+        } else {
+            let _ = writeln!(prompt, "- {id}: {}", params.join(", "));
+        }
+    }
+    let _ = writeln!(
+        prompt,
+        "\nTransforms: {}\n",
+        registry.transform_ids().join(", ")
+    );
+    let _ = writeln!(prompt, "Schema:\n{summary}");
+    prompt.push_str(
+        "Return a JSON array with one object per column that should get a generator:\n\
+         {\"schema\": \"public\", \"table\": \"users\", \"column\": \"email\", \
+         \"generator\": {\"id\": \"<generator id>\", \"params\": {}}, \
+         \"transforms\": [{\"transform\": \"<transform id>\"}]}\n\
+         Use only the ids and params listed above, pick generators that fit the column \
+         type and name, and skip columns marked \"no rule\". `params` and `transforms` \
+         are optional.",
+    );
+    prompt
+}
+
+fn retry_prompt(rejected: &[RejectedRule]) -> String {
+    let mut prompt = String::from("These rules were rejected:\n");
+    for rejected in rejected {
+        let _ = writeln!(prompt, "- {}: {}", rejected.rule, rejected.reason);
+    }
+    prompt.push_str(
+        "\nReturn a JSON array with corrected rules for those columns only, in the same \
+         format. Leave out columns that should keep no generator.",
+    );
+    prompt
+}
+
+/// JSON array of the answer (code fences and surrounding prose are
+/// ignored; `{"rules": [...]}` is accepted too).
+pub(super) fn candidate_rules(response: &str) -> Result<Vec<Value>, String> {
+    let start = response
+        .find(['[', '{'])
+        .ok_or_else(|| "answer has no JSON array".to_string())?;
+    let value = serde_json::Deserializer::from_str(&response[start..])
+        .into_iter::<Value>()
+        .next()
+        .ok_or_else(|| "answer has no JSON array".to_string())?
+        .map_err(|err| format!("invalid JSON: {err}"))?;
+    match value {
+        Value::Array(rules) => Ok(rules),
+        Value::Object(mut object) => match object.remove("rules") {
+            Some(Value::Array(rules)) => Ok(rules),
+            _ => Ok(vec![Value::Object(object)]),
+        },
+        _ => Err("answer is not a JSON array of rules".to_string()),
+    }
+}
+
+/// Validate one suggested rule: a plannable column, the plan JSON Schema and
+/// database schema checks (on `probe`, the plan without rules), then the
+/// registry and param kinds.
+fn check_candidate(
+    candidate: &Value,
+    columns: &Columns,
+    probe: &mut Value,
+    schema: &DatabaseSchema,
+) -> Result<(ColumnKey, ColumnGeneratorRule), String> {
+    let Some(object) = candidate.as_object() else {
+        return Err("rule must be a JSON object".to_string());
+    };
+    let field = |key: &str| object.get(key).and_then(Value::as_str).map(str::to_string);
+    let (Some(schema_name), Some(table), Some(column)) =
+        (field("schema"), field("table"), field("column"))
+    else {
+        return Err("rule needs string `schema`, `table` and `column`".to_string());
+    };
+    let key = (schema_name, table, column);
+    match columns
+        .iter()
+        .find_map(|(column, status)| (*column == key).then_some(status))
+    {
+        None => return Err(format!("unknown column {}.{}.{}", key.0, key.1, key.2)),
+        Some(Err(reason)) => return Err((*reason).to_string()),
+        Some(Ok(())) => {}
+    }
+
+    let mut rule = object.clone();
+    rule.insert("type".to_string(), json!("column_generator"));
+    probe["rules"] = json!([rule]);
+    let report = validation_report(probe, schema).map_err(|err| err.to_string())?;
+    if !report.is_ok() {
+        let issues: Vec<String> = report
+            .errors
+            .iter()
+            .map(|issue| {
+                format!(
+                    "{}: {}",
+                    issue.path.replacen("/rules/0", "", 1),
+                    issue.message
+                )
+            })
+            .collect();
+        return Err(issues.join("; "));
+    }
+
+    rule.remove("type");
+    let rule: ColumnGeneratorRule =
+        serde_json::from_value(Value::Object(rule)).map_err(|err| err.to_string())?;
+    check_registry(&rule)?;
+    let registry = GeneratorRegistry::new();
+    if let Some(generator) = registry.generator(rule.generator_id()) {
+        let specs = generator.params();
+        if !specs.is_empty() {
+            validate_params(rule.generator_params(), specs, "params")
+                .map_err(|err| err.to_string())?;
+        }
+    }
+    Ok((key, rule))
+}
+
+fn rule_key(rule: &ColumnGeneratorRule) -> ColumnKey {
+    (rule.schema.clone(), rule.table.clone(), rule.column.clone())
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
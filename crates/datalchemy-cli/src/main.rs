@@ -1,20 +1,47 @@
+mod anonymize;
+mod bench;
+mod classify;
+mod config;
+mod diff;
+mod eval;
+mod explain;
+mod generate;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod i18n;
+mod live_sample;
+mod llm;
+mod mask;
+mod mcp;
+mod output;
+mod pipeline;
+mod plan;
+mod profile;
 mod registry;
+mod serve;
+mod subset;
 mod tui;
+mod watch;
 mod workspace;
 
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
 use datalchemy_core::{
-    Error as CoreError, SCHEMA_VERSION, redact_connection_string, validate_schema,
+    DatabaseSchema, Error as CoreError, SCHEMA_VERSION, redact_connection_string, validate_schema,
 };
 use datalchemy_eval::EvalError;
-use datalchemy_eval::collect_schema_metrics;
-use datalchemy_generate::GenerationError;
-use datalchemy_introspect::{IntrospectOptions, introspect_postgres_with_options};
-use registry::{RunContext, RunOptions, init_run_logging, start_run, write_metrics, write_schema};
-use sqlx::postgres::PgPoolOptions;
+use datalchemy_eval::{SchemaMetrics, collect_schema_metrics};
+use datalchemy_generate::{ErrorContext, GenerationError, OutputFormat};
+use datalchemy_introspect::{AdapterRegistry, IntrospectOptions, RetryPolicy, TablePattern};
+use output::{MessageFormat, Output};
+use registry::{
+    RunContext, RunOptions, RunPaths, init_run_logging, start_run, write_metrics, write_schema,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -31,7 +58,7 @@ enum CliError {
     #[error("evaluation error: {0}")]
     Evaluation(#[from] EvalError),
     #[error("generation error: {0}")]
-    Generation(#[from] GenerationError),
+    Generation(Box<GenerationError>),
     #[error("invalid configuration: {0}")]
     InvalidConfig(String),
     #[error("io error: {0}")]
@@ -46,11 +73,58 @@ enum CliError {
     UnsupportedEngine(String),
     #[error("workspace error: {0}")]
     Workspace(#[from] workspace::WorkspaceError),
+    #[error("llm error: {0}")]
+    Llm(#[from] llm::LlmError),
+}
+
+impl CliError {
+    /// Stable code of the error kind, explained by `datalchemy explain <code>`;
+    /// generation and evaluation errors keep the code of their [`GenerationError`]
+    /// or [`EvalError`].
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            Self::Registry(_) => "DL101",
+            Self::Core(_) => "DL102",
+            Self::Database(_) => "DL103",
+            Self::Json(_) => "DL104",
+            Self::Evaluation(err) => err.code(),
+            Self::Generation(err) => err.code(),
+            Self::InvalidConfig(_) => "DL107",
+            Self::Io(_) => "DL108",
+            Self::Crypto(_) => "DL109",
+            Self::Plan(_) => "DL110",
+            Self::Runtime(_) => "DL111",
+            Self::UnsupportedEngine(_) => "DL112",
+            Self::Workspace(_) => "DL113",
+            Self::Llm(_) => "DL114",
+        }
+    }
+
+    /// Table or column a generation or evaluation error was raised for.
+    pub(crate) fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Evaluation(err) => err.context(),
+            Self::Generation(err) => err.context(),
+            _ => None,
+        }
+    }
+}
+
+impl From<GenerationError> for CliError {
+    fn from(err: GenerationError) -> Self {
+        Self::Generation(Box::new(err))
+    }
 }
 
 #[derive(Parser, Debug)]
 #[command(name = "datalchemy", version, about = "Datalchemy CLI")]
 struct Cli {
+    /// Results on stdout: text, or one JSON object (ids, paths, issues) per command.
+    #[arg(long, global = true, value_enum, default_value_t = MessageFormat::Text)]
+    message_format: MessageFormat,
+    /// Project defaults (default: ./datalchemy.toml when present).
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
     #[command(subcommand)]
     command: Command,
 }
@@ -59,6 +133,466 @@ struct Cli {
 enum Command {
     Introspect(IntrospectArgs),
     Tui(TuiArgs),
+    /// Create or validate workspace plans without the TUI.
+    #[command(subcommand)]
+    Plan(PlanCommand),
+    /// Label schema columns with semantic types and PII categories.
+    Classify(ClassifyArgs),
+    /// Explain an error code (causes and what to do); lists the codes without one.
+    Explain(ExplainArgs),
+    /// Replace schema identifiers by stable pseudonyms for sharing, or restore them.
+    Anonymize(AnonymizeArgs),
+    /// Show the tables, columns, constraints and enums changed between two schemas.
+    Diff(DiffArgs),
+    /// Serve a generated output as a read-only REST API.
+    Serve(ServeArgs),
+    /// Generate data from schema.json + plan.json without the TUI.
+    Generate(GenerateArgs),
+    /// Evaluate a run or dataset directory (exits nonzero on violations).
+    Eval(EvalArgs),
+    /// Mask real rows of a source database with the transforms of a plan.
+    Mask(MaskArgs),
+    /// Extract a referentially intact slice of a source database (optionally masked).
+    Subset(SubsetArgs),
+    /// Measure generation throughput on a synthetic workload.
+    Bench(BenchArgs),
+    /// Introspect, validate, generate and evaluate in one run (for CI).
+    Pipeline(PipelineArgs),
+    /// Re-validate the workspace plan on every save and regenerate a small preview.
+    Watch(WatchArgs),
+    /// Serve introspection, plan validation and generation as MCP tools over stdio.
+    Mcp(McpArgs),
+    /// Serve generated rows over gRPC (requires the `grpc` feature).
+    Grpc(GrpcArgs),
+}
+
+impl Command {
+    /// Name reported in `--message-format json` results.
+    fn name(&self) -> &'static str {
+        match self {
+            Command::Introspect(_) => "introspect",
+            Command::Tui(_) => "tui",
+            Command::Plan(PlanCommand::New(_)) => "plan new",
+            Command::Plan(PlanCommand::Validate(_)) => "plan validate",
+            Command::Plan(PlanCommand::Wizard(_)) => "plan wizard",
+            Command::Classify(_) => "classify",
+            Command::Explain(_) => "explain",
+            Command::Anonymize(_) => "anonymize",
+            Command::Diff(_) => "diff",
+            Command::Serve(_) => "serve",
+            Command::Generate(_) => "generate",
+            Command::Eval(_) => "eval",
+            Command::Mask(_) => "mask",
+            Command::Subset(_) => "subset",
+            Command::Bench(_) => "bench",
+            Command::Pipeline(_) => "pipeline",
+            Command::Watch(_) => "watch",
+            Command::Mcp(_) => "mcp",
+            Command::Grpc(_) => "grpc",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum PlanCommand {
+    /// Write a heuristic plan for a workspace run and make it the active plan.
+    New(PlanNewArgs),
+    /// Validate a workspace plan (or --plan/--schema files); exits nonzero on errors.
+    Validate(PlanValidateArgs),
+    /// Build a plan from stdin prompts: tables, row counts and key column generators.
+    Wizard(PlanWizardArgs),
+}
+
+#[derive(Args, Debug)]
+struct PlanNewArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Introspection run to plan (default: active run, else the latest).
+    #[arg(long)]
+    run_id: Option<String>,
+    /// Id of the new plan (default: generated).
+    #[arg(long)]
+    plan_id: Option<String>,
+    /// Use the heuristic planner even when the workspace enables an LLM.
+    #[arg(long)]
+    heuristic: bool,
+    /// Plan only these tables (`schema.table` or `table`, comma-separated)
+    /// and their FK parents.
+    #[arg(long, value_delimiter = ',')]
+    tables: Vec<String>,
+    /// With --tables, also plan the tables that reference them.
+    #[arg(long, requires = "tables")]
+    with_children: bool,
+}
+
+#[derive(Args, Debug)]
+struct PlanWizardArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Introspection run to plan (default: active run, else the latest).
+    #[arg(long)]
+    run_id: Option<String>,
+    /// Id of the new plan (default: generated).
+    #[arg(long)]
+    plan_id: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct PlanValidateArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Plan to validate (default: active plan, else the latest).
+    #[arg(long, conflicts_with = "plan")]
+    plan_id: Option<String>,
+    /// Run whose schema.json is used (default: active run, else the latest).
+    #[arg(long, conflicts_with = "schema")]
+    run_id: Option<String>,
+    /// Path to plan.json, instead of a workspace plan.
+    #[arg(long, requires = "schema")]
+    plan: Option<PathBuf>,
+    /// Path to schema.json, instead of a workspace run.
+    #[arg(long, requires = "plan")]
+    schema: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct ClassifyArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Introspection run to classify (default: active run, else the latest).
+    #[arg(long)]
+    run_id: Option<String>,
+    /// Keep the name/type labels even when the workspace enables an LLM.
+    #[arg(long)]
+    heuristic: bool,
+}
+
+#[derive(Args, Debug)]
+struct ExplainArgs {
+    /// Error code, as printed in `error[DL110]: ...`.
+    code: Option<String>,
+    /// Language of the explanation: en or pt_BR (default: workspace language).
+    #[arg(long)]
+    lang: Option<String>,
+    /// Workspace root path, read for the language setting.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct AnonymizeArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Introspection run to anonymize (default: active run, else the latest).
+    #[arg(long, conflicts_with = "schema")]
+    run_id: Option<String>,
+    /// Path to schema.json, instead of a workspace run.
+    #[arg(long)]
+    schema: Option<PathBuf>,
+    /// Output schema (default: ./schema.anonymized.json; ./schema.json with --restore).
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// Mapping file (default: ./schema.anonymized.map.json).
+    #[arg(long)]
+    map: Option<PathBuf>,
+    /// Put the original names back into an anonymized --schema using --map.
+    #[arg(long, requires = "schema")]
+    restore: bool,
+}
+
+#[derive(Args, Debug)]
+struct DiffArgs {
+    /// Schema before: a workspace run id or a path to schema.json.
+    run_a: String,
+    /// Schema after: a workspace run id or a path to schema.json.
+    run_b: String,
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct EvalArgs {
+    /// Path to schema.json.
+    #[arg(long, required_unless_present = "workspace")]
+    schema: Option<PathBuf>,
+    /// Path to plan.json; omit to check an external dataset against the schema only.
+    #[arg(long)]
+    plan: Option<PathBuf>,
+    /// Run directory or dataset directory to evaluate.
+    #[arg(long, required_unless_present = "workspace")]
+    dataset: Option<PathBuf>,
+    /// Evaluate a workspace output instead (schema and plan from its manifest);
+    /// results go to <workspace>/eval/<eval_id> and the eval history.
+    #[arg(long, conflicts_with_all = ["schema", "plan", "dataset", "out_dir"])]
+    workspace: Option<PathBuf>,
+    /// Workspace output to evaluate (default: the latest successful one).
+    #[arg(long, requires = "workspace")]
+    out_id: Option<String>,
+    /// Id of the workspace evaluation (default: generated).
+    #[arg(long, requires = "workspace")]
+    eval_id: Option<String>,
+    /// Directory for metrics.json, report.md and junit.xml (default: the dataset).
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+    /// File patterns mapping tables to files, with {schema}/{table} placeholders.
+    #[arg(long, value_delimiter = ',')]
+    pattern: Vec<String>,
+    /// Thresholds file (JSON): max_fk_violation_rate and per-column limits.
+    #[arg(long)]
+    thresholds: Option<PathBuf>,
+    /// Write junit.xml with one test case per check.
+    #[arg(long, default_value_t = false)]
+    junit: bool,
+    /// Read files in chunks and spill keys to disk, for datasets larger than memory.
+    #[arg(long, default_value_t = false)]
+    streaming: bool,
+    /// Directory for temporary key files in streaming mode (default: system temp).
+    #[arg(long)]
+    spill_dir: Option<PathBuf>,
+    /// Check a sample of at most this many rows per table (quick, extrapolated).
+    #[arg(long)]
+    sample_rows: Option<usize>,
+    /// Slowest generated tables listed in report.md.
+    #[arg(long, default_value_t = 5)]
+    top_slowest: usize,
+    /// Skip the temporal checks (updated >= created, end >= start, plan windows).
+    #[arg(long, default_value_t = false)]
+    no_temporal_checks: bool,
+    /// Sample the source database (--conn or DATABASE_URL) and score realism
+    /// against it; PII-named columns are not read.
+    #[arg(long, default_value_t = false)]
+    live_sample: bool,
+    /// Source database for --live-sample.
+    #[arg(long, value_name = "CONNECTION_STRING")]
+    conn: Option<String>,
+    /// Rows sampled per source table with --live-sample.
+    #[arg(long, default_value_t = live_sample::DEFAULT_SAMPLE_ROWS)]
+    live_sample_rows: u64,
+    /// Score realism against a source_sample.json from a previous --live-sample.
+    #[arg(long, conflicts_with = "live_sample")]
+    source_sample: Option<PathBuf>,
+    /// Report violations without failing.
+    #[arg(long, default_value_t = false)]
+    no_strict: bool,
+}
+
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    /// Path to schema.json.
+    #[arg(long, required_unless_present = "workspace")]
+    schema: Option<PathBuf>,
+    /// Path to plan.json.
+    #[arg(long, required_unless_present = "workspace")]
+    plan: Option<PathBuf>,
+    /// Generate a workspace plan instead, into <workspace>/out/<out_id>.
+    #[arg(long, conflicts_with_all = ["schema", "plan", "out_dir", "stdout", "table", "profile"])]
+    workspace: Option<PathBuf>,
+    /// Workspace plan to generate (default: active plan, else the latest).
+    #[arg(long, requires = "workspace")]
+    plan_id: Option<String>,
+    /// Id of the workspace output (default: generated).
+    #[arg(long, requires = "workspace")]
+    out_id: Option<String>,
+    /// Output format (with --stdout: csv or pg_copy).
+    #[arg(long, default_value = "csv")]
+    format: OutputFormat,
+    /// Output directory for runs.
+    #[arg(long, default_value = "out")]
+    out_dir: PathBuf,
+    /// Table to stream, as schema.table (requires --stdout).
+    #[arg(long, value_name = "SCHEMA.TABLE")]
+    table: Option<String>,
+    /// Write the table to stdout instead of creating a run directory.
+    #[arg(long, default_value_t = false)]
+    stdout: bool,
+    /// Multiply the plan's target rows (e.g. 0.1 for a quick run).
+    #[arg(long, default_value_t = 1.0)]
+    scale: f64,
+    /// Print the N slowest tables (duration, rows/s, retries/s, memory) to stderr.
+    #[arg(long, value_name = "N")]
+    top_slowest: Option<usize>,
+    /// profile.json from `introspect --profile`: columns without a plan rule
+    /// follow the sampled ranges and frequencies.
+    #[arg(long)]
+    profile: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct MaskArgs {
+    /// Source database connection string (default: DATABASE_URL).
+    #[arg(long, value_name = "CONNECTION_STRING")]
+    conn: Option<String>,
+    /// Path to the source's schema.json.
+    #[arg(long)]
+    schema: PathBuf,
+    /// Path to plan.json: targets, row limits and column transforms.
+    #[arg(long)]
+    plan: PathBuf,
+    /// Output format.
+    #[arg(long, default_value = "csv")]
+    format: OutputFormat,
+    /// Output directory for runs.
+    #[arg(long, default_value = "out")]
+    out_dir: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct SubsetArgs {
+    /// Source database connection string (default: DATABASE_URL).
+    #[arg(long, value_name = "CONNECTION_STRING")]
+    conn: Option<String>,
+    /// Path to the source's schema.json.
+    #[arg(long)]
+    schema: PathBuf,
+    /// Table to start from, as schema.table (or table when unique).
+    #[arg(long, value_name = "SCHEMA.TABLE")]
+    seed: String,
+    /// SQL condition selecting the seed rows (e.g. "created_at > now() - interval '7 days'").
+    #[arg(long = "where", value_name = "CONDITION")]
+    filter: Option<String>,
+    /// Seed rows to start from.
+    #[arg(long, default_value_t = 100)]
+    seed_rows: u64,
+    /// Rows per table reached through child foreign keys (parents are never capped).
+    #[arg(long, default_value_t = 1000)]
+    max_rows: u64,
+    /// Only follow foreign keys up to the parents of the seed rows.
+    #[arg(long, default_value_t = false)]
+    no_children: bool,
+    /// Mask the slice with the column transforms of this plan.json.
+    #[arg(long)]
+    plan: Option<PathBuf>,
+    /// Output format.
+    #[arg(long, default_value = "csv")]
+    format: OutputFormat,
+    /// Output directory for runs.
+    #[arg(long, default_value = "out")]
+    out_dir: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct PipelineArgs {
+    /// Database connection string (default: DATABASE_URL).
+    #[arg(long, value_name = "CONNECTION_STRING")]
+    conn: Option<String>,
+    /// Path to plan.json, validated against the introspected schema.
+    #[arg(long)]
+    plan: PathBuf,
+    /// Output directory for runs (schema, dataset, evaluation and summary).
+    #[arg(long, default_value = "runs")]
+    run_dir: PathBuf,
+    /// Schema name(s) to include.
+    #[arg(long, value_name = "SCHEMA")]
+    schema: Vec<String>,
+    /// Output format of the generated dataset.
+    #[arg(long, default_value = "csv")]
+    format: OutputFormat,
+    /// Multiply the plan's target rows (e.g. 0.1 for a quick run).
+    #[arg(long, default_value_t = 1.0)]
+    scale: f64,
+    /// Thresholds file (JSON): max_fk_violation_rate and per-column limits.
+    #[arg(long)]
+    thresholds: Option<PathBuf>,
+    /// Write junit.xml with one test case per check.
+    #[arg(long, default_value_t = false)]
+    junit: bool,
+    /// Report violations without failing.
+    #[arg(long, default_value_t = false)]
+    no_strict: bool,
+}
+
+#[derive(Args, Debug)]
+struct WatchArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Plan to watch (default: active plan, else the latest).
+    #[arg(long)]
+    plan_id: Option<String>,
+    /// Run whose schema.json is used (default: the plan's run).
+    #[arg(long)]
+    run_id: Option<String>,
+    /// Rows per table in the preview (targets with fewer rows keep theirs).
+    #[arg(long, default_value_t = 10)]
+    rows: u64,
+    /// Directory for the preview output (default: system temp).
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+    /// How often plan.json is checked for changes, in milliseconds.
+    #[arg(long, default_value_t = 500)]
+    interval_ms: u64,
+}
+
+#[derive(Args, Debug)]
+struct McpArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct GrpcArgs {
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Workspace plan to serve (default: active plan, else the latest).
+    #[arg(long, conflicts_with = "plan")]
+    plan_id: Option<String>,
+    /// Path to plan.json, instead of a workspace plan.
+    #[arg(long, requires = "schema")]
+    plan: Option<PathBuf>,
+    /// Path to schema.json, instead of a workspace run.
+    #[arg(long, requires = "plan")]
+    schema: Option<PathBuf>,
+    /// Listen address.
+    #[arg(long, default_value = "127.0.0.1:50051")]
+    addr: SocketAddr,
+}
+
+#[derive(Args, Debug)]
+struct BenchArgs {
+    /// Events per run (plus one account per ten events).
+    #[arg(long, default_value_t = 100_000)]
+    rows: u64,
+    /// Thread counts to measure; the rows are split across that many shards.
+    #[arg(long, value_delimiter = ',', default_value = "1")]
+    threads: Vec<usize>,
+    /// Output formats to measure.
+    #[arg(long, value_delimiter = ',', default_value = "csv")]
+    formats: Vec<OutputFormat>,
+    /// Directory for the generated shards (default: system temp).
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+    /// Seed of the first shard.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// Keep the generated files.
+    #[arg(long, default_value_t = false)]
+    keep_output: bool,
+    /// Write the results as JSON, to compare with a later --baseline.
+    #[arg(long)]
+    json: Option<PathBuf>,
+    /// Previous --json results; prints the rows/s change per format and thread count.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Output id under `<workspace>/out` (CSV format).
+    out_id: String,
+    /// Workspace root path.
+    #[arg(long, default_value = "datalchemy-cli")]
+    workspace: PathBuf,
+    /// Listen address.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: SocketAddr,
 }
 
 #[derive(Args, Debug)]
@@ -85,6 +619,16 @@ struct IntrospectArgs {
     /// Schema name(s) to include.
     #[arg(long, value_name = "SCHEMA")]
     schema: Vec<String>,
+    /// Introspect only the tables matching one of these patterns: a glob
+    /// (`orders*`, or `public.orders*` to match `schema.table`) or a regex
+    /// over `schema.table` with the `re:` prefix.
+    #[arg(long, value_name = "PATTERN")]
+    include_table: Vec<TablePattern>,
+    /// Skip the tables matching one of these patterns (same syntax as
+    /// --include-table); skipped tables referenced by foreign keys are kept
+    /// in `external_tables`.
+    #[arg(long, value_name = "PATTERN")]
+    exclude_table: Vec<TablePattern>,
     /// Fail on cycles or unsupported features.
     #[arg(long, default_value_t = false)]
     strict: bool,
@@ -109,26 +653,106 @@ struct IntrospectArgs {
     /// Include comments in introspection.
     #[arg(long, default_value_t = true)]
     include_comments: bool,
+    /// Capture planner row estimates and column statistics (Postgres).
+    #[arg(long, default_value_t = false)]
+    sample_stats: bool,
+    /// Record which roles hold SELECT/INSERT on each table (Postgres).
+    #[arg(long, default_value_t = false)]
+    include_privileges: bool,
+    /// Sample column values into profile.json (min/max, top values, null
+    /// fraction; PII-named columns are not read). Postgres only.
+    #[arg(long, default_value_t = false)]
+    profile: bool,
+    /// Rows sampled per table with --profile.
+    #[arg(long, default_value_t = live_sample::DEFAULT_SAMPLE_ROWS)]
+    profile_rows: u64,
+    /// Previous schema.json of this database (same options): tables whose
+    /// catalog entries did not change are reused instead of queried again.
+    #[arg(long, value_name = "SCHEMA_JSON")]
+    incremental: Option<PathBuf>,
+    /// Tables whose columns, constraints and indexes are queried at once.
+    #[arg(long, default_value_t = IntrospectOptions::default().concurrency)]
+    concurrency: usize,
+    /// Wait for a pooled connection (raise it behind pgbouncer).
+    #[arg(long, default_value_t = 10_000)]
+    acquire_timeout_ms: u64,
+    /// Time limit of each catalog query (off by default).
+    #[arg(long)]
+    query_timeout_ms: Option<u64>,
+    /// Time limit of the whole introspection (off by default).
+    #[arg(long)]
+    deadline_ms: Option<u64>,
+    /// Extra attempts of a failed catalog query, with exponential backoff.
+    #[arg(long, default_value_t = 0)]
+    retries: u32,
+    /// Wait before the first retry; doubled on each further one.
+    #[arg(long, default_value_t = 200)]
+    retry_backoff_ms: u64,
+    /// Record tables whose details still fail in `failed_tables` (with a
+    /// warning) instead of aborting the introspection.
+    #[arg(long, default_value_t = false)]
+    partial: bool,
 }
 
-fn main() -> Result<(), CliError> {
-    let cli = Cli::parse();
+fn main() -> ExitCode {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let name = cli.command.name();
+    let mut output = Output::new(cli.message_format);
+    let result = run(cli, &matches, &mut output);
+    output.finish(name, result)
+}
+
+fn run(mut cli: Cli, matches: &ArgMatches, output: &mut Output) -> Result<(), CliError> {
+    let config = config::ProjectConfig::load(cli.config.as_deref())?;
     let runtime =
         tokio::runtime::Runtime::new().map_err(|err| CliError::Runtime(err.to_string()))?;
-
+    if let Some(config) = &config {
+        config.apply(&mut cli.command, matches);
+    }
     match cli.command {
-        Command::Introspect(args) => runtime.block_on(run_introspect(args)),
+        Command::Introspect(args) => runtime.block_on(run_introspect(args, output)),
+        Command::Tui(_) if output.is_json() => Err(CliError::InvalidConfig(
+            "the TUI is interactive; --message-format json is not supported".to_string(),
+        )),
         Command::Tui(args) => tui::run(runtime.handle().clone(), args.workspace),
+        Command::Plan(command) => plan::run_plan(command, output),
+        Command::Classify(args) => classify::run_classify(args, output),
+        Command::Explain(args) => explain::run_explain(args, output),
+        Command::Anonymize(args) => anonymize::run_anonymize(args, output),
+        Command::Diff(args) => diff::run_diff(args, output),
+        Command::Serve(args) => runtime.block_on(serve::run_serve(
+            &args.workspace,
+            &args.out_id,
+            args.addr,
+            output,
+        )),
+        Command::Generate(args) => generate::run_generate(args, output),
+        Command::Eval(args) => eval::run_eval(args, &runtime, output),
+        Command::Mask(args) => mask::run_mask(args, &runtime, output),
+        Command::Subset(args) => subset::run_subset(args, &runtime, output),
+        Command::Bench(args) => bench::run_bench(args, output),
+        Command::Pipeline(args) => pipeline::run_pipeline(args, &runtime, output),
+        Command::Watch(args) => watch::run_watch(args, output),
+        Command::Mcp(args) => mcp::run_mcp(args, &runtime, output),
+        #[cfg(feature = "grpc")]
+        Command::Grpc(args) => runtime.block_on(grpc::run_grpc(args, output)),
+        #[cfg(not(feature = "grpc"))]
+        Command::Grpc(_) => Err(CliError::InvalidConfig(
+            "the gRPC server requires datalchemy built with `--features grpc`".to_string(),
+        )),
     }
 }
 
-async fn run_introspect(args: IntrospectArgs) -> Result<(), CliError> {
+async fn run_introspect(args: IntrospectArgs, output: &mut Output) -> Result<(), CliError> {
     let IntrospectArgs {
         conn,
         conn_pos,
         run_dir,
         out,
         schema,
+        include_table,
+        exclude_table,
         strict,
         redact,
         include_system_schemas,
@@ -137,6 +761,18 @@ async fn run_introspect(args: IntrospectArgs) -> Result<(), CliError> {
         include_foreign_tables,
         include_indexes,
         include_comments,
+        sample_stats,
+        include_privileges,
+        profile,
+        profile_rows,
+        incremental,
+        concurrency,
+        acquire_timeout_ms,
+        query_timeout_ms,
+        deadline_ms,
+        retries,
+        retry_backoff_ms,
+        partial,
     } = args;
 
     if !redact {
@@ -177,55 +813,137 @@ async fn run_introspect(args: IntrospectArgs) -> Result<(), CliError> {
         } else {
             Some(schema.clone())
         },
+        include_tables: include_table,
+        exclude_tables: exclude_table,
+        sample_stats,
+        include_privileges,
+        concurrency,
+        acquire_timeout: Duration::from_millis(acquire_timeout_ms),
+        query_timeout: query_timeout_ms.map(Duration::from_millis),
+        deadline: deadline_ms.map(Duration::from_millis),
+        retry: RetryPolicy {
+            max_attempts: retries.saturating_add(1),
+            initial_backoff: Duration::from_millis(retry_backoff_ms),
+            ..RetryPolicy::default()
+        },
+        partial,
     };
 
-    let run_options = RunOptions {
-        include_system_schemas: options.include_system_schemas,
-        include_views: options.include_views,
-        include_materialized_views: options.include_materialized_views,
-        include_foreign_tables: options.include_foreign_tables,
-        include_indexes: options.include_indexes,
-        include_comments: options.include_comments,
-        schemas: options.schemas.clone(),
-    };
+    let run_ctx = run_context(&conn, engine, run_dir, out, strict, &options);
+    let run_id = run_ctx.run_id.clone();
+    let timer = Instant::now();
+    let previous = incremental.as_deref().map(plan::read_schema).transpose()?;
+    let (schema, run_paths, metrics) =
+        introspect_run(&run_ctx, &conn, options, previous.as_ref()).await?;
 
-    let run_id = Uuid::new_v4().to_string();
-    let started_at = chrono::Utc::now();
-    let connection = redact_connection_string(&conn);
-    let run_ctx = RunContext {
-        run_id: run_id.clone(),
-        started_at,
+    output.set("run_id", &run_id)?;
+    output.set("schema_path", &run_paths.schema_path)?;
+    output.set("metrics_path", &run_paths.metrics_path)?;
+    output.set("logs_path", &run_paths.logs_path)?;
+    output.set("warnings", &metrics.warnings)?;
+
+    if profile {
+        let value_profile = profile::sample_profile(&conn, &schema, profile_rows).await?;
+        let profile_path = run_paths.schema_path.with_file_name("profile.json");
+        std::fs::write(&profile_path, serde_json::to_vec_pretty(&value_profile)?)?;
+        output.set("profile_path", &profile_path)?;
+    }
+
+    if run_ctx.strict && metrics.fk_graph.has_cycle {
+        return Err(CliError::InvalidConfig(
+            "foreign key graph contains cycles".to_string(),
+        ));
+    }
+
+    let duration_ms = timer.elapsed().as_millis();
+    tracing::info!(
+        event = "run_finished",
+        status = "success",
+        duration_ms = duration_ms
+    );
+
+    Ok(())
+}
+
+/// Context of a new introspection run under `run_dir`.
+fn run_context(
+    conn: &str,
+    engine: &str,
+    run_dir: PathBuf,
+    out: Option<PathBuf>,
+    strict: bool,
+    options: &IntrospectOptions,
+) -> RunContext {
+    RunContext {
+        run_id: Uuid::new_v4().to_string(),
+        started_at: chrono::Utc::now(),
         engine: engine.to_string(),
         schema_version: SCHEMA_VERSION.to_string(),
         strict,
         run_dir,
         out,
-        options: run_options,
-        connection,
-    };
+        options: RunOptions {
+            include_system_schemas: options.include_system_schemas,
+            include_views: options.include_views,
+            include_materialized_views: options.include_materialized_views,
+            include_foreign_tables: options.include_foreign_tables,
+            include_indexes: options.include_indexes,
+            include_comments: options.include_comments,
+            schemas: options.schemas.clone(),
+            include_tables: options
+                .include_tables
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            exclude_tables: options
+                .exclude_tables
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            sample_stats: options.sample_stats,
+            include_privileges: options.include_privileges,
+        },
+        connection: redact_connection_string(conn),
+    }
+}
 
-    let run_paths = start_run(&run_ctx)?;
+/// Introspect `conn` into the run of `run_ctx` (schema.json, metrics.json and
+/// logs.ndjson, which also receives the rest of the process' logs). Schema
+/// warnings are printed to stderr and the time of each introspection phase
+/// is logged. With `previous`, unchanged tables are reused from it
+/// ([`datalchemy_introspect::introspect_incremental`]).
+async fn introspect_run(
+    run_ctx: &RunContext,
+    conn: &str,
+    options: IntrospectOptions,
+    previous: Option<&DatabaseSchema>,
+) -> Result<(DatabaseSchema, RunPaths, SchemaMetrics), CliError> {
+    let run_paths = start_run(run_ctx)?;
     init_run_logging(&run_paths.logs_path)?;
 
-    tracing::info!(event = "run_started", run_id = %run_id, engine = %engine);
-    tracing::info!(event = "engine_detected", engine = %engine);
-
-    let timer = Instant::now();
-
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .acquire_timeout(Duration::from_secs(10))
-        .connect(&conn)
-        .await?;
+    tracing::info!(event = "run_started", run_id = %run_ctx.run_id, engine = %run_ctx.engine);
+    tracing::info!(event = "engine_detected", engine = %run_ctx.engine);
 
+    let adapter = adapters().open(conn, &options).await?;
     tracing::info!(event = "introspection_started");
 
-    let schema = introspect_postgres_with_options(&pool, options).await?;
+    let (schema, timings) = adapter.introspect_timed(&options, previous).await?;
+    for timing in &timings.phases {
+        tracing::info!(
+            event = "introspection_phase",
+            phase = timing.phase,
+            duration_ms = timing.duration.as_millis() as u64
+        );
+    }
     validate_schema(&schema)?;
 
     tracing::info!(event = "introspection_finished");
 
     let metrics = collect_schema_metrics(&schema);
+    for warning in &metrics.warnings {
+        tracing::warn!(event = "schema_warning", message = %warning);
+        eprintln!("warning: {warning}");
+    }
 
     write_schema(&run_paths, &schema, run_ctx.out.as_deref())?;
     tracing::info!(event = "schema_written", path = %run_paths.schema_path.display());
@@ -233,26 +951,23 @@ async fn run_introspect(args: IntrospectArgs) -> Result<(), CliError> {
     write_metrics(&run_paths, &metrics)?;
     tracing::info!(event = "metrics_written", path = %run_paths.metrics_path.display());
 
-    if run_ctx.strict && metrics.fk_graph.has_cycle {
-        return Err(CliError::InvalidConfig(
-            "foreign key graph contains cycles".to_string(),
-        ));
-    }
-
-    let duration_ms = timer.elapsed().as_millis();
-    tracing::info!(
-        event = "run_finished",
-        status = "success",
-        duration_ms = duration_ms
-    );
+    Ok((schema, run_paths, metrics))
+}
 
-    Ok(())
+/// Adapters the CLI introspects with, by connection scheme
+/// ([`AdapterRegistry::builtin`]).
+fn adapters() -> &'static AdapterRegistry {
+    static ADAPTERS: OnceLock<AdapterRegistry> = OnceLock::new();
+    ADAPTERS.get_or_init(AdapterRegistry::builtin)
 }
 
+/// Engine of the adapter registered for the scheme of `conn`.
 fn detect_engine(conn: &str) -> Result<&'static str, CliError> {
-    if conn.starts_with("postgres://") || conn.starts_with("postgresql://") {
-        Ok("postgres")
-    } else {
-        Err(CliError::UnsupportedEngine(conn.to_string()))
-    }
+    adapters().engine_for(conn).ok_or_else(|| {
+        CliError::UnsupportedEngine(format!(
+            "{} (registered schemes: {})",
+            redact_connection_string(conn).redacted,
+            adapters().schemes().collect::<Vec<_>>().join(", ")
+        ))
+    })
 }
@@ -0,0 +1,157 @@
+//! `datalchemy mask`: real rows of a Postgres database masked with a plan.
+//!
+//! Rows are read with `row_to_json` (ordered by primary key, at most the
+//! target's `rows`) and handed to [`MaskingEngine`], which applies the
+//! transforms of the plan's column rules and writes a regular run directory.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use datalchemy_core::ddl::quote_ident;
+use datalchemy_core::{Constraint, DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationError, GenerationResult, MaskingEngine, RowSource, source_value,
+};
+use serde_json::Value;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use tokio::runtime::Runtime;
+
+use crate::generate::load_plan;
+use crate::output::Output;
+use crate::{CliError, MaskArgs};
+
+/// Mask the plan's target tables of `--conn` into `out_dir`.
+pub fn run_mask(args: MaskArgs, runtime: &Runtime, output: &mut Output) -> Result<(), CliError> {
+    let schema: DatabaseSchema = serde_json::from_str(&std::fs::read_to_string(&args.schema)?)?;
+    let plan = load_plan(&args.plan, &schema)?;
+    let mut source = PostgresRows::connect(args.conn.as_deref(), runtime)?;
+    let engine = MaskingEngine::new(GenerateOptions {
+        out_dir: args.out_dir.clone(),
+        format: args.format,
+        ..GenerateOptions::default()
+    });
+    let result = engine.run(&schema, &plan, &mut source)?;
+    write_run_output(&result, output)
+}
+
+/// Print the run directory and set `run_dir`, `rows` and `warnings`.
+pub(crate) fn write_run_output(
+    result: &GenerationResult,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    output.line(result.run_dir.display());
+    output.set("run_dir", &result.run_dir)?;
+    output.set(
+        "rows",
+        result
+            .report
+            .tables
+            .iter()
+            .map(|table| table.rows_generated)
+            .sum::<u64>(),
+    )?;
+    output.set("warnings", result.report.warnings.len())?;
+    Ok(())
+}
+
+/// Rows of a Postgres database, read on the CLI runtime.
+pub(crate) struct PostgresRows<'a> {
+    runtime: &'a Runtime,
+    pool: PgPool,
+}
+
+impl<'a> PostgresRows<'a> {
+    /// Connect to `conn` (default: `DATABASE_URL`).
+    pub(crate) fn connect(conn: Option<&str>, runtime: &'a Runtime) -> Result<Self, CliError> {
+        let conn = match conn {
+            Some(conn) => conn.to_string(),
+            None => std::env::var("DATABASE_URL").map_err(|_| {
+                CliError::InvalidConfig(
+                    "connection string is required (pass --conn or set DATABASE_URL)".to_string(),
+                )
+            })?,
+        };
+        if crate::detect_engine(&conn)? != "postgres" {
+            return Err(CliError::UnsupportedEngine(conn));
+        }
+        let pool = runtime.block_on(
+            PgPoolOptions::new()
+                .max_connections(1)
+                .acquire_timeout(Duration::from_secs(10))
+                .connect(&conn),
+        )?;
+        Ok(Self { runtime, pool })
+    }
+
+    /// Rows of `table` returned by `sql` as `row_to_json` text, with one text
+    /// array bound per entry of `binds`.
+    pub(crate) fn fetch(
+        &self,
+        table: &Table,
+        sql: &str,
+        binds: Vec<Vec<String>>,
+    ) -> Result<Vec<HashMap<String, GeneratedValue>>, GenerationError> {
+        let mut query = sqlx::query_scalar::<_, String>(sql);
+        for bind in binds {
+            query = query.bind(bind);
+        }
+        let rows = self
+            .runtime
+            .block_on(query.fetch_all(&self.pool))
+            .map_err(|err| GenerationError::Source(err.to_string()))?;
+        rows.iter()
+            .map(|row| {
+                let object: serde_json::Map<String, Value> = serde_json::from_str(row)?;
+                Ok(table
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        let value = object.get(&column.name).unwrap_or(&Value::Null);
+                        (column.name.to_lowercase(), source_value(column, value))
+                    })
+                    .collect())
+            })
+            .collect()
+    }
+}
+
+impl RowSource for PostgresRows<'_> {
+    fn read_table(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        limit: u64,
+    ) -> Result<Vec<HashMap<String, GeneratedValue>>, GenerationError> {
+        let sql = format!(
+            "select row_to_json(t)::text from {}{} limit {limit}",
+            qualified(schema, table),
+            order_by(table)
+        );
+        self.fetch(table, &sql, Vec::new())
+    }
+}
+
+/// `schema.table t`, quoted.
+pub(crate) fn qualified(schema: &str, table: &Table) -> String {
+    format!("{}.{} t", quote_ident(schema), quote_ident(&table.name))
+}
+
+/// ` order by` the primary key of `table`, if any.
+pub(crate) fn order_by(table: &Table) -> String {
+    table
+        .constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            Constraint::PrimaryKey(pk) => Some(
+                pk.columns
+                    .iter()
+                    .map(|column| quote_ident(column))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            _ => None,
+        })
+        .map(|columns| format!(" order by {columns}"))
+        .unwrap_or_default()
+}
@@ -0,0 +1,486 @@
+//! `datalchemy mcp`: Model Context Protocol server over stdio.
+//!
+//! Exposes introspection, plan validation and generation of a workspace as MCP
+//! tools, so an LLM agent can drive the pipeline. Messages are JSON-RPC 2.0,
+//! one per line on stdin/stdout; diagnostics go to stderr. Tools write the same
+//! artifacts as the TUI (`runs/<run_id>`, `plans/<plan_id>`, `out/<out_id>`)
+//! and update the active run/plan in `settings.toml`.
+//!
+//! Tools:
+//! - `introspect`: introspect `connection` (default: `DATABASE_URL`) into a new run.
+//! - `validate_plan`: validate a workspace plan, or an inline `plan`
+//!   (`save: true` stores a valid one as a new active plan).
+//! - `generate`: generate a workspace plan into a new output.
+//!
+//! A failing tool returns a result with `isError: true` and the message, so the
+//! agent can correct its arguments; unknown methods and malformed requests
+//! are JSON-RPC errors.
+
+use std::io::{BufRead, Write};
+
+use chrono::Utc;
+use datalchemy_core::{DatabaseSchema, redact_connection_string, validate_schema};
+use datalchemy_eval::collect_schema_metrics;
+use datalchemy_generate::OutputFormat;
+use datalchemy_introspect::IntrospectOptions;
+use serde_json::{Value, json};
+
+use crate::generate::generate_workspace_output;
+use crate::output::Output;
+use crate::plan::{read_schema, validation_report};
+use crate::tui::conn::is_supported_connection;
+use crate::workspace::{
+    ArtifactStatus, HeadlessWorkspace, PlanMeta, RunManifest, RunOptions, new_artifact_id,
+    write_bytes_atomic, write_json_atomic,
+};
+use crate::{CliError, McpArgs};
+
+/// Protocol revision answered when the client does not ask for one.
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve MCP requests from stdin until it is closed.
+pub fn run_mcp(
+    args: McpArgs,
+    runtime: &tokio::runtime::Runtime,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    if output.is_json() {
+        return Err(CliError::InvalidConfig(
+            "the MCP server speaks JSON-RPC on stdout; --message-format json is not supported"
+                .to_string(),
+        ));
+    }
+    let mut server = McpServer {
+        workspace: HeadlessWorkspace::open(args.workspace)?,
+        runtime,
+    };
+    eprintln!(
+        "datalchemy MCP server on stdio (workspace {})",
+        server.workspace.paths.root.display()
+    );
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle(message),
+            Err(err) => Some(error_response(Value::Null, PARSE_ERROR, err.to_string())),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+struct McpServer<'a> {
+    workspace: HeadlessWorkspace,
+    runtime: &'a tokio::runtime::Runtime,
+}
+
+impl McpServer<'_> {
+    /// Response to one JSON-RPC message; notifications get none.
+    fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return id.map(|id| error_response(id, INVALID_REQUEST, "missing method"));
+        };
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let result = match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err((METHOD_NOT_FOUND, format!("method not found: {method}"))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, message),
+        })
+    }
+
+    fn call_tool(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let Some(name) = params.get("name").and_then(Value::as_str) else {
+            return Err((INVALID_PARAMS, "missing tool name".to_string()));
+        };
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        let result = match name {
+            "introspect" => self.introspect(&arguments),
+            "validate_plan" => self.validate_plan(&arguments),
+            "generate" => self.generate(&arguments),
+            _ => return Err((INVALID_PARAMS, format!("unknown tool: {name}"))),
+        };
+        Ok(match result {
+            Ok(structured) => json!({
+                "content": [{ "type": "text", "text": structured.to_string() }],
+                "structuredContent": structured,
+                "isError": false,
+            }),
+            Err(err) => json!({
+                "content": [{ "type": "text", "text": err.to_string() }],
+                "isError": true,
+            }),
+        })
+    }
+
+    /// Introspect into `runs/<run_id>` like `/introspect` and make it the
+    /// active run.
+    fn introspect(&mut self, arguments: &Value) -> Result<Value, CliError> {
+        let conn = match string_arg(arguments, "connection")? {
+            Some(conn) => conn,
+            None => std::env::var("DATABASE_URL").map_err(|_| {
+                CliError::InvalidConfig(
+                    "connection is required (pass it or set DATABASE_URL)".to_string(),
+                )
+            })?,
+        };
+        if !is_supported_connection(&conn) {
+            return Err(CliError::UnsupportedEngine(
+                redact_connection_string(&conn).redacted,
+            ));
+        }
+        let schemas = match arguments.get("schemas") {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(serde_json::from_value::<Vec<String>>(value.clone())?),
+        };
+        let run_id = string_arg(arguments, "run_id")?.unwrap_or_else(|| new_artifact_id("run"));
+        let run_dir = self.workspace.paths.runs_dir.join(&run_id);
+        if run_dir.exists() {
+            return Err(CliError::InvalidConfig(format!(
+                "run already exists: {run_id}"
+            )));
+        }
+        std::fs::create_dir_all(&run_dir)?;
+        write_json_atomic(
+            &run_dir.join("config.redacted.json"),
+            &redact_connection_string(&conn),
+        )?;
+
+        let options = IntrospectOptions {
+            schemas,
+            ..IntrospectOptions::default()
+        };
+        let manifest_path = run_dir.join("run_manifest.json");
+        let mut manifest = RunManifest {
+            run_id: run_id.clone(),
+            status: ArtifactStatus::Running,
+            db_profile: self
+                .workspace
+                .settings
+                .active_profile
+                .clone()
+                .unwrap_or_else(|| "session".to_string()),
+            introspect_options: RunOptions {
+                include_system_schemas: options.include_system_schemas,
+                include_views: options.include_views,
+                include_materialized_views: options.include_materialized_views,
+                include_foreign_tables: options.include_foreign_tables,
+                include_indexes: options.include_indexes,
+                include_comments: options.include_comments,
+                schemas: options.schemas.clone(),
+                include_tables: options
+                    .include_tables
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                exclude_tables: options
+                    .exclude_tables
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                sample_stats: options.sample_stats,
+                include_privileges: options.include_privileges,
+            },
+            schema_fingerprint: None,
+            artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
+            cli_version: crate::workspace::CLI_VERSION.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+            tags: Vec::new(),
+            notes: Vec::new(),
+        };
+        write_json_atomic(&manifest_path, &manifest)?;
+
+        let schema = self
+            .runtime
+            .block_on(introspect_connection(&conn, options))
+            .and_then(|schema| {
+                validate_schema(&schema)?;
+                Ok(schema)
+            });
+        manifest.finished_at = Some(Utc::now().to_rfc3339());
+        let schema = match schema {
+            Ok(schema) => schema,
+            Err(err) => {
+                manifest.status = ArtifactStatus::Error;
+                write_json_atomic(&manifest_path, &manifest)?;
+                return Err(err);
+            }
+        };
+        let metrics = collect_schema_metrics(&schema);
+        let schema_path = run_dir.join("schema.json");
+        write_json_atomic(&schema_path, &schema)?;
+        write_json_atomic(&run_dir.join("metrics.json"), &metrics)?;
+        manifest.status = ArtifactStatus::Ok;
+        manifest.schema_fingerprint = schema.schema_fingerprint.clone();
+        write_json_atomic(&manifest_path, &manifest)?;
+
+        self.workspace.settings.active_run_id = Some(run_id.clone());
+        self.workspace.save_settings()?;
+        Ok(json!({
+            "run_id": run_id,
+            "schema_path": schema_path,
+            "tables": table_names(&schema),
+            "warnings": metrics.warnings,
+        }))
+    }
+
+    /// Validate `plan` (inline) or a workspace plan against the schema of a run.
+    fn validate_plan(&mut self, arguments: &Value) -> Result<Value, CliError> {
+        let inline = arguments.get("plan").filter(|plan| !plan.is_null());
+        let save = arguments
+            .get("save")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if save && inline.is_none() {
+            return Err(CliError::InvalidConfig(
+                "save requires an inline plan".to_string(),
+            ));
+        }
+        let explicit_run = string_arg(arguments, "run_id")?;
+        let (plan_id, plan_json, run_id) = match inline {
+            Some(plan) => {
+                let run_id = self.workspace.run_id(explicit_run.as_deref())?;
+                (None, plan.clone(), run_id)
+            }
+            None => {
+                let plan_id = self
+                    .workspace
+                    .plan_id(string_arg(arguments, "plan_id")?.as_deref())?;
+                let run_id = match explicit_run {
+                    Some(run_id) => run_id,
+                    None => self.workspace.plan_run_id(&plan_id)?,
+                };
+                let plan_path = self.workspace.plan_path(&plan_id);
+                let plan_json = serde_json::from_str(&std::fs::read_to_string(plan_path)?)?;
+                (Some(plan_id), plan_json, run_id)
+            }
+        };
+        let schema = read_schema(&self.workspace.schema_path(&run_id))?;
+        let report = validation_report(&plan_json, &schema)?;
+
+        let saved = if save && report.is_ok() {
+            Some(self.save_plan(&plan_json, &run_id, &schema)?)
+        } else {
+            None
+        };
+        Ok(json!({
+            "valid": report.is_ok(),
+            "plan_id": saved.or(plan_id),
+            "run_id": run_id,
+            "errors": report.errors,
+            "warnings": report.warnings,
+        }))
+    }
+
+    /// Store a validated inline plan as `plans/<plan_id>` and make it active.
+    fn save_plan(
+        &mut self,
+        plan_json: &Value,
+        run_id: &str,
+        schema: &DatabaseSchema,
+    ) -> Result<String, CliError> {
+        let plan_id = new_artifact_id("plan");
+        let plan_dir = self.workspace.paths.plans_dir.join(&plan_id);
+        std::fs::create_dir_all(&plan_dir)?;
+        write_bytes_atomic(
+            &plan_dir.join("plan.json"),
+            &serde_json::to_vec_pretty(plan_json)?,
+        )?;
+        let now = Utc::now().to_rfc3339();
+        let meta = PlanMeta {
+            plan_id: plan_id.clone(),
+            status: ArtifactStatus::Ok,
+            schema_run_id: run_id.to_string(),
+            schema_fingerprint: schema.schema_fingerprint.clone(),
+            provider: "mcp".to_string(),
+            model: "client".to_string(),
+            mock: false,
+            artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
+            cli_version: crate::workspace::CLI_VERSION.to_string(),
+            created_at: now.clone(),
+            finished_at: Some(now),
+            tags: Vec::new(),
+            notes: Vec::new(),
+        };
+        write_json_atomic(&plan_dir.join("plan.meta.json"), &meta)?;
+        self.workspace.settings.active_run_id = Some(run_id.to_string());
+        self.workspace.settings.active_plan_id = Some(plan_id.clone());
+        self.workspace.save_settings()?;
+        Ok(plan_id)
+    }
+
+    /// Generate a workspace plan into `out/<out_id>` like `/generate`.
+    fn generate(&mut self, arguments: &Value) -> Result<Value, CliError> {
+        let format = match string_arg(arguments, "format")? {
+            Some(format) => format
+                .parse::<OutputFormat>()
+                .map_err(|err| CliError::InvalidConfig(err.to_string()))?,
+            None => OutputFormat::default(),
+        };
+        let scale = match arguments.get("scale") {
+            None | Some(Value::Null) => 1.0,
+            Some(value) => value
+                .as_f64()
+                .ok_or_else(|| CliError::InvalidConfig("scale must be a number".to_string()))?,
+        };
+        let generated = generate_workspace_output(
+            &self.workspace,
+            string_arg(arguments, "plan_id")?.as_deref(),
+            string_arg(arguments, "out_id")?,
+            format,
+            scale,
+        )?;
+        let tables: Vec<Value> = generated
+            .report
+            .tables
+            .iter()
+            .map(|table| {
+                json!({
+                    "table": format!("{}.{}", table.schema, table.table),
+                    "rows": table.rows_generated,
+                })
+            })
+            .collect();
+        Ok(json!({
+            "out_id": generated.out_id,
+            "out_path": generated.out_path,
+            "format": format.as_str(),
+            "rows": generated.rows,
+            "tables": tables,
+            "warnings_by_code": generated.report.warnings_by_code,
+        }))
+    }
+}
+
+async fn introspect_connection(
+    conn: &str,
+    options: IntrospectOptions,
+) -> Result<DatabaseSchema, CliError> {
+    let adapter = crate::adapters().open(conn, &options).await?;
+    Ok(adapter.introspect(&options).await?)
+}
+
+fn initialize_result(params: &Value) -> Value {
+    let version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .unwrap_or(PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": {
+            "name": "datalchemy",
+            "version": crate::workspace::CLI_VERSION,
+        },
+        "instructions": "Introspect a database, validate a plan.json against the introspected \
+            schema (inline plans can be saved with save: true) and generate the active plan.",
+    })
+}
+
+fn tool_definitions() -> Value {
+    let formats: Vec<&str> = OutputFormat::all().iter().map(|f| f.as_str()).collect();
+    json!([
+        {
+            "name": "introspect",
+            "description": "Introspect a Postgres or SQLite database into a new workspace run \
+                (schema.json, metrics.json) and make it the active run.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "connection": {
+                        "type": "string",
+                        "description": "postgres://, postgresql:// or sqlite:// URL (default: DATABASE_URL)."
+                    },
+                    "schemas": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Schemas to include (default: all non-system schemas)."
+                    },
+                    "run_id": { "type": "string", "description": "Id of the new run (default: generated)." }
+                }
+            }
+        },
+        {
+            "name": "validate_plan",
+            "description": "Validate a plan.json against the schema of a run; returns errors and \
+                warnings with JSON paths. Pass an inline plan to check it before saving; with \
+                save: true a valid inline plan becomes the active workspace plan.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "plan": { "type": "object", "description": "Inline plan.json contents." },
+                    "save": { "type": "boolean", "description": "Store a valid inline plan as a new active plan." },
+                    "plan_id": { "type": "string", "description": "Workspace plan (default: active, else latest)." },
+                    "run_id": { "type": "string", "description": "Run whose schema.json is used (default: the plan's run, or the active run for inline plans)." }
+                }
+            }
+        },
+        {
+            "name": "generate",
+            "description": "Generate a workspace plan into a new output directory (out/<out_id>).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "plan_id": { "type": "string", "description": "Workspace plan (default: active, else latest)." },
+                    "out_id": { "type": "string", "description": "Id of the new output (default: generated)." },
+                    "format": { "type": "string", "enum": formats, "description": "Output format (default: csv)." },
+                    "scale": { "type": "number", "exclusiveMinimum": 0, "description": "Multiplier of the plan's target rows." }
+                }
+            }
+        }
+    ])
+}
+
+fn string_arg(arguments: &Value, key: &str) -> Result<Option<String>, CliError> {
+    match arguments.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(CliError::InvalidConfig(format!("{key} must be a string"))),
+    }
+}
+
+fn table_names(schema: &DatabaseSchema) -> Vec<String> {
+    schema
+        .schemas
+        .iter()
+        .flat_map(|db_schema| {
+            db_schema
+                .tables
+                .iter()
+                .map(move |table| format!("{}.{}", db_schema.name, table.name))
+        })
+        .collect()
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
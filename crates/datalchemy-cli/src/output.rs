@@ -0,0 +1,124 @@
+//! `--message-format json`: machine-readable results of the subcommands.
+//!
+//! In text mode each command prints its usual lines. In JSON mode nothing else
+//! is written to stdout: the fields a command records are printed as one
+//! object, `{"command": ..., "status": "ok" | "error", ...}`, also on failure
+//! (`error` holds the message, `code` its stable code, see
+//! `datalchemy explain`, and `location` the schema/table/column of generation
+//! and evaluation errors raised for one). Diagnostics stay on stderr in both modes.
+
+use std::fmt::Display;
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::CliError;
+
+/// Format of the results printed on stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum MessageFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Results of one command, printed according to [`MessageFormat`].
+#[derive(Debug)]
+pub(crate) struct Output {
+    format: MessageFormat,
+    fields: Map<String, Value>,
+    flushed: bool,
+}
+
+impl Output {
+    pub(crate) fn new(format: MessageFormat) -> Self {
+        Self {
+            format,
+            fields: Map::new(),
+            flushed: false,
+        }
+    }
+
+    pub(crate) fn is_json(&self) -> bool {
+        self.format == MessageFormat::Json
+    }
+
+    /// `key=value` in text mode, a string field in JSON mode.
+    pub(crate) fn field(&mut self, key: &str, value: impl Display) {
+        match self.format {
+            MessageFormat::Text => println!("{key}={value}"),
+            MessageFormat::Json => {
+                self.fields
+                    .insert(key.to_string(), Value::String(value.to_string()));
+            }
+        }
+    }
+
+    /// A field only printed in JSON mode.
+    pub(crate) fn set(&mut self, key: &str, value: impl Serialize) -> Result<(), CliError> {
+        if self.is_json() {
+            self.fields
+                .insert(key.to_string(), serde_json::to_value(value)?);
+        }
+        Ok(())
+    }
+
+    /// A line only printed in text mode.
+    pub(crate) fn line(&self, line: impl Display) {
+        if !self.is_json() {
+            println!("{line}");
+        }
+    }
+
+    /// Print the fields recorded so far, for commands that keep running
+    /// (`serve`); [`Self::finish`] then only reports errors.
+    pub(crate) fn flush(&mut self, command: &str) {
+        if self.is_json() {
+            self.print(command, "ok", None);
+            self.flushed = true;
+        }
+    }
+
+    /// Print the result of `command` and return the exit status. Errors are
+    /// printed with their code (`error[DL110]: ...` on stderr, or the JSON
+    /// object) and give status 1.
+    pub(crate) fn finish(self, command: &str, result: Result<(), CliError>) -> ExitCode {
+        match result {
+            Ok(()) => {
+                if self.is_json() && !self.flushed {
+                    self.print(command, "ok", None);
+                }
+                ExitCode::SUCCESS
+            }
+            Err(err) if self.is_json() => {
+                self.print(command, "error", Some(&err));
+                ExitCode::FAILURE
+            }
+            Err(err) => {
+                let code = err.code();
+                eprintln!("error[{code}]: {err}");
+                eprintln!("hint: run `datalchemy explain {code}` for causes and fixes");
+                ExitCode::FAILURE
+            }
+        }
+    }
+
+    fn print(&self, command: &str, status: &str, error: Option<&CliError>) {
+        let mut object = Map::new();
+        object.insert("command".to_string(), Value::String(command.to_string()));
+        object.insert("status".to_string(), Value::String(status.to_string()));
+        if let Some(error) = error {
+            object.insert("error".to_string(), Value::String(error.to_string()));
+            object.insert("code".to_string(), Value::String(error.code().to_string()));
+            if let Some(context) = error.context()
+                && let Ok(location) = serde_json::to_value(context)
+            {
+                object.insert("location".to_string(), location);
+            }
+        }
+        object.extend(self.fields.clone());
+        println!("{}", Value::Object(object));
+    }
+}
@@ -0,0 +1,220 @@
+//! `datalchemy pipeline`: introspect, validate, generate and evaluate in one
+//! invocation, for CI jobs without a persistent workspace.
+//!
+//! The steps share one run: the introspection run directory under `--run-dir`
+//! holds `schema.json`, `metrics.json` and `logs.ndjson` (with the events of
+//! every step), the dataset is generated into its `generated/` directory and
+//! evaluated in place. `pipeline_summary.json` records each step (status,
+//! duration) and the consolidated result, also when a step fails.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use datalchemy_core::DatabaseSchema;
+use datalchemy_eval::{EvalError, EvalThresholds, EvaluateOptions, EvaluationEngine, WarningItem};
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+use datalchemy_introspect::IntrospectOptions;
+use serde::Serialize;
+
+use crate::generate::{load_plan, scale_plan};
+use crate::output::Output;
+use crate::{CliError, PipelineArgs, detect_engine, introspect_run, run_context};
+
+const SUMMARY_FILE: &str = "pipeline_summary.json";
+
+/// Contents of `pipeline_summary.json`.
+#[derive(Debug, Default, Serialize)]
+struct PipelineSummary {
+    run_id: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    started_at: String,
+    finished_at: String,
+    steps: Vec<PipelineStep>,
+    schema_path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dataset_dir: Option<PathBuf>,
+    rows: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metrics_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    junit_path: Option<PathBuf>,
+    violations: usize,
+    schema_warnings: Vec<String>,
+    warnings: Vec<WarningItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct PipelineStep {
+    name: &'static str,
+    status: &'static str,
+    duration_ms: u128,
+}
+
+/// Run the four steps against `--conn` and write the summary into the run.
+pub fn run_pipeline(
+    args: PipelineArgs,
+    runtime: &tokio::runtime::Runtime,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    let conn = match &args.conn {
+        Some(conn) => conn.clone(),
+        None => std::env::var("DATABASE_URL").map_err(|_| {
+            CliError::InvalidConfig(
+                "connection string is required (pass --conn or set DATABASE_URL)".to_string(),
+            )
+        })?,
+    };
+    let engine = detect_engine(&conn)?;
+    let options = IntrospectOptions {
+        schemas: if args.schema.is_empty() {
+            None
+        } else {
+            Some(args.schema.clone())
+        },
+        ..IntrospectOptions::default()
+    };
+    let run_ctx = run_context(&conn, engine, args.run_dir.clone(), None, false, &options);
+    let mut summary = PipelineSummary {
+        run_id: run_ctx.run_id.clone(),
+        started_at: run_ctx.started_at.to_rfc3339(),
+        ..PipelineSummary::default()
+    };
+    output.field("run_id", &run_ctx.run_id);
+
+    // Nothing is written before the run directory exists.
+    let timer = Instant::now();
+    let introspected = runtime.block_on(introspect_run(&run_ctx, &conn, options, None));
+    let (schema, run_paths, schema_metrics) = match introspected {
+        Ok(run) => run,
+        Err(err) => {
+            output.set("steps", [step("introspect", &timer, false)])?;
+            return Err(err);
+        }
+    };
+    summary.steps.push(step("introspect", &timer, true));
+    summary.schema_path = run_paths.schema_path.clone();
+    summary.schema_warnings = schema_metrics.warnings;
+    let run_root = run_paths
+        .schema_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| args.run_dir.clone());
+
+    let result = run_steps(&args, &schema, &run_root, &mut summary);
+    summary.finished_at = Utc::now().to_rfc3339();
+    summary.status = if result.is_ok() { "ok" } else { "error" }.to_string();
+    summary.error = result.as_ref().err().map(ToString::to_string);
+    let summary_path = run_root.join(SUMMARY_FILE);
+    std::fs::write(&summary_path, serde_json::to_vec_pretty(&summary)?)?;
+    tracing::info!(
+        event = "pipeline_finished",
+        status = %summary.status,
+        violations = summary.violations
+    );
+
+    output.field("schema_path", summary.schema_path.display());
+    if let Some(path) = &summary.dataset_dir {
+        output.field("dataset_dir", path.display());
+    }
+    if let Some(path) = &summary.metrics_path {
+        output.field("metrics_path", path.display());
+    }
+    if let Some(path) = &summary.report_path {
+        output.field("report_path", path.display());
+    }
+    if let Some(path) = &summary.junit_path {
+        output.field("junit_path", path.display());
+    }
+    output.field("summary_path", summary_path.display());
+    output.line(format!("rows={}", summary.rows));
+    output.line(format!("violations={}", summary.violations));
+    output.set("rows", summary.rows)?;
+    output.set("violations", summary.violations)?;
+    output.set("steps", &summary.steps)?;
+    output.set("schema_warnings", &summary.schema_warnings)?;
+    output.set("warnings", &summary.warnings)?;
+    result
+}
+
+/// Validate, generate and evaluate, recording each step in `summary`.
+fn run_steps(
+    args: &PipelineArgs,
+    schema: &DatabaseSchema,
+    run_root: &Path,
+    summary: &mut PipelineSummary,
+) -> Result<(), CliError> {
+    let timer = Instant::now();
+    let plan = load_plan(&args.plan, schema);
+    summary.steps.push(step("validate", &timer, plan.is_ok()));
+    let mut plan = plan?;
+    scale_plan(&mut plan, args.scale)?;
+
+    let timer = Instant::now();
+    let generated = GenerationEngine::new(GenerateOptions {
+        out_dir: run_root.join("generated"),
+        format: args.format,
+        ..GenerateOptions::default()
+    })
+    .run(schema, &plan);
+    summary
+        .steps
+        .push(step("generate", &timer, generated.is_ok()));
+    let generated = generated?;
+    summary.rows = generated
+        .report
+        .tables
+        .iter()
+        .map(|table| table.rows_generated)
+        .sum();
+    summary.dataset_dir = Some(generated.run_dir.clone());
+
+    let timer = Instant::now();
+    let thresholds = match &args.thresholds {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+        None => EvalThresholds::default(),
+    };
+    // Strictness is applied below, once the violations are in the summary.
+    let evaluated = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        thresholds,
+        write_junit: args.junit,
+        out_dir: Some(generated.run_dir.clone()),
+        ..EvaluateOptions::default()
+    })
+    .run(schema, &plan, &generated.run_dir);
+    let violations = evaluated
+        .as_ref()
+        .map(|result| result.violations.len())
+        .unwrap_or_default();
+    let passed = evaluated.is_ok() && (args.no_strict || violations == 0);
+    summary.steps.push(step("eval", &timer, passed));
+    let evaluated = evaluated?;
+    summary.metrics_path = Some(evaluated.metrics_path);
+    summary.report_path = Some(evaluated.report_path);
+    summary.junit_path = evaluated.junit_path;
+    summary.violations = violations;
+    summary.warnings = evaluated.metrics.warnings;
+
+    if violations > 0 {
+        if !args.no_strict {
+            return Err(CliError::Evaluation(EvalError::Violations(
+                violations as u64,
+            )));
+        }
+        eprintln!("violations found: {violations} (not strict)");
+    }
+    Ok(())
+}
+
+fn step(name: &'static str, timer: &Instant, ok: bool) -> PipelineStep {
+    PipelineStep {
+        name,
+        status: if ok { "ok" } else { "error" },
+        duration_ms: timer.elapsed().as_millis(),
+    }
+}
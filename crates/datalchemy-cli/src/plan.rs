@@ -0,0 +1,909 @@
+//! `datalchemy plan new|validate|wizard`: headless plan authoring in a workspace.
+//!
+//! `plan new` writes the plan of `/plan new` (the LLM planner when the
+//! workspace enables one, else the heuristic plan) to
+//! `<workspace>/plans/<plan_id>` and makes it the active plan. `plan validate`
+//! checks a workspace plan (or `--plan`/`--schema` files) and exits nonzero
+//! on errors, printing every issue to stderr. `plan wizard` starts from the
+//! heuristic plan and asks on stdin for the tables, row counts and key column
+//! generators; prompts go to stderr, so answers can be piped from a file.
+//!
+//! A workspace plan checked against another run than the one it was written
+//! for also gets a `plan_stale` warning listing the target tables whose
+//! structure changed in between ([`datalchemy_core::diff_schemas`]).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use chrono::Utc;
+use datalchemy_core::{
+    Column, DatabaseSchema, SchemaDiff, SubsetOptions, constraint_signature, diff_schemas,
+};
+use datalchemy_generate::generators::GeneratorRegistry;
+use datalchemy_plan::{
+    ColumnGeneratorRule, GeneratorRef, PLAN_VERSION, Plan, PlanGlobal, Rule, SchemaRef, Target,
+    TransformRule, ValidationReport, unsupported_rule_candidates, validate_plan_against_schema,
+    validate_plan_json,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::classify::{Classification, read_classification};
+use crate::i18n::Msg;
+use crate::llm;
+use crate::llm::planner::{planner_model, write_llm_plan};
+use crate::output::Output;
+use crate::workspace::{
+    ArtifactStatus, HeadlessWorkspace, Language, PlanMeta, WorkspacePaths, new_artifact_id,
+    write_bytes_atomic, write_json_atomic,
+};
+use crate::{CliError, PlanCommand, PlanNewArgs, PlanValidateArgs, PlanWizardArgs};
+
+pub fn run_plan(command: PlanCommand, output: &mut Output) -> Result<(), CliError> {
+    match command {
+        PlanCommand::New(args) => run_plan_new(args, output),
+        PlanCommand::Validate(args) => run_plan_validate(args, output),
+        PlanCommand::Wizard(args) => run_plan_wizard(args, output),
+    }
+}
+
+fn run_plan_new(args: PlanNewArgs, output: &mut Output) -> Result<(), CliError> {
+    let mut workspace = HeadlessWorkspace::open(args.workspace)?;
+    let run_id = workspace.run_id(args.run_id.as_deref())?;
+    let schema = subset_schema(
+        read_schema(&workspace.schema_path(&run_id))?,
+        &args.tables,
+        args.with_children,
+    )?;
+    let plan_id = args.plan_id.unwrap_or_else(|| new_artifact_id("plan"));
+    if workspace.paths.plans_dir.join(&plan_id).exists() {
+        return Err(CliError::InvalidConfig(format!(
+            "plan already exists: {plan_id}"
+        )));
+    }
+
+    let planner = planner_model(&workspace.settings).filter(|_| !args.heuristic);
+    let plan = match planner {
+        Some((provider, model)) => {
+            let client = llm::client_for(
+                &provider,
+                workspace.settings.llm_base_url.as_deref(),
+                &BTreeMap::new(),
+            )?;
+            let (plan, transcript) = write_llm_plan(
+                &workspace.paths,
+                &plan_id,
+                &run_id,
+                &schema,
+                client.as_ref(),
+                (provider.as_str(), &model),
+            )?;
+            output.set("llm_rules", transcript.llm_rules)?;
+            output.set("heuristic_rules", transcript.heuristic_rules)?;
+            output.set("llm_attempts", transcript.attempts.len())?;
+            eprintln!("llm planner: {}.", transcript.summary());
+            plan
+        }
+        None => write_smart_plan(&workspace.paths, &plan_id, &run_id, &schema)?,
+    };
+    workspace.settings.active_run_id = Some(run_id);
+    workspace.settings.active_plan_id = Some(plan_id.clone());
+    workspace.save_settings()?;
+
+    output.field("plan_id", &plan_id);
+    output.field("plan_path", workspace.plan_path(&plan_id).display());
+    output.set("tables", plan.targets.len())?;
+    output.set("rules", plan.rules.len())?;
+    if !args.tables.is_empty() {
+        eprintln!(
+            "subset: {} table(s) for {} requested.",
+            plan.targets.len(),
+            args.tables.len()
+        );
+    }
+    eprintln!("{}", plan_created(&plan, workspace.settings.language));
+    Ok(())
+}
+
+fn run_plan_validate(args: PlanValidateArgs, output: &mut Output) -> Result<(), CliError> {
+    let (plan_path, schema_path, workspace) = match (args.plan, args.schema) {
+        (Some(plan), Some(schema)) => (plan, schema, None),
+        (None, None) => {
+            let workspace = HeadlessWorkspace::open(args.workspace)?;
+            let plan_id = workspace.plan_id(args.plan_id.as_deref())?;
+            let run_id = workspace.run_id(args.run_id.as_deref())?;
+            (
+                workspace.plan_path(&plan_id),
+                workspace.schema_path(&run_id),
+                Some((workspace.paths, plan_id, run_id)),
+            )
+        }
+        _ => {
+            return Err(CliError::InvalidConfig(
+                "--plan and --schema must be given together".to_string(),
+            ));
+        }
+    };
+
+    let plan_json: Value = serde_json::from_str(&std::fs::read_to_string(&plan_path)?)?;
+    let schema = read_schema(&schema_path)?;
+    let report = validation_report(&plan_json, &schema)?;
+    if let Some((paths, plan_id, run_id)) = &workspace
+        && let Some(staleness) = plan_staleness(paths, plan_id, run_id, &plan_json, &schema)?
+    {
+        for line in staleness.lines() {
+            eprintln!("{line}");
+        }
+        output.set("staleness", &staleness)?;
+    }
+    output.set("plan_path", &plan_path)?;
+    output.set("errors", &report.errors)?;
+    output.set("warnings", &report.warnings)?;
+    for issue in &report.errors {
+        eprintln!("error: {} {} ({})", issue.code, issue.path, issue.message);
+    }
+    for warning in &report.warnings {
+        eprintln!(
+            "warning: {} {} ({})",
+            warning.code, warning.path, warning.message
+        );
+    }
+    if !report.is_ok() {
+        return Err(CliError::Plan(format!(
+            "plan validation failed with {} error(s)",
+            report.errors.len()
+        )));
+    }
+    output.line(format!("plan validation ok: {}", plan_path.display()));
+    Ok(())
+}
+
+fn run_plan_wizard(args: PlanWizardArgs, output: &mut Output) -> Result<(), CliError> {
+    let mut workspace = HeadlessWorkspace::open(args.workspace)?;
+    let run_id = workspace.run_id(args.run_id.as_deref())?;
+    let schema = read_schema(&workspace.schema_path(&run_id))?;
+    let plan_id = args.plan_id.unwrap_or_else(|| new_artifact_id("plan"));
+    if workspace.paths.plans_dir.join(&plan_id).exists() {
+        return Err(CliError::InvalidConfig(format!(
+            "plan already exists: {plan_id}"
+        )));
+    }
+
+    let classification = read_classification(&workspace.paths, &run_id)?;
+    let mut plan = smart_plan(&schema, classification.as_ref());
+    let mut prompter = Prompter::new(
+        std::io::stdin().lock(),
+        std::io::stderr(),
+        workspace.settings.language,
+    );
+    plan_wizard(&mut prompter, &mut plan)?;
+
+    let report = validation_report(&serde_json::to_value(&plan)?, &schema)?;
+    for issue in &report.errors {
+        eprintln!("error: {} {} ({})", issue.code, issue.path, issue.message);
+    }
+    if !report.is_ok() {
+        return Err(CliError::Plan(format!(
+            "wizard plan is invalid with {} error(s)",
+            report.errors.len()
+        )));
+    }
+    write_plan_artifacts(
+        &workspace.paths,
+        &plan_id,
+        &run_id,
+        &schema,
+        &plan,
+        ("wizard", "plan_wizard"),
+        &prompter.transcript,
+    )?;
+    workspace.settings.active_run_id = Some(run_id);
+    workspace.settings.active_plan_id = Some(plan_id.clone());
+    workspace.save_settings()?;
+
+    output.field("plan_id", &plan_id);
+    output.field("plan_path", workspace.plan_path(&plan_id).display());
+    output.set("tables", plan.targets.len())?;
+    output.set("rules", plan.rules.len())?;
+    eprintln!("{}", plan_created(&plan, workspace.settings.language));
+    Ok(())
+}
+
+/// Narrow `plan` (a heuristic plan) to the chosen tables, then ask the rows
+/// of each and the generator of its key columns: the ones whose heuristic
+/// generator is not a `primitive.*`. `-` drops a column rule.
+fn plan_wizard<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    plan: &mut Plan,
+) -> Result<(), CliError> {
+    let names: Vec<String> = plan
+        .targets
+        .iter()
+        .map(|target| format!("{}.{}", target.schema, target.table))
+        .collect();
+    let language = prompter.language;
+    prompter.say(&Msg::WizardTables.fill(language, &[&names.join(", ")]))?;
+    let question = Msg::WizardChooseTables.text(language);
+    let chosen = prompter.ask_until(question, "all", |answer| {
+        select_tables(&names, answer, language)
+    })?;
+    plan.targets
+        .retain(|target| chosen.contains(&format!("{}.{}", target.schema, target.table)));
+    plan.rules.retain(|rule| match rule {
+        Rule::ColumnGenerator(rule) => chosen.contains(&format!("{}.{}", rule.schema, rule.table)),
+        _ => true,
+    });
+    plan.rules_unsupported.retain(|rule| {
+        rule.reference.as_ref().is_none_or(|reference| {
+            chosen.contains(&format!("{}.{}", reference.schema, reference.table))
+        })
+    });
+
+    for target in &mut plan.targets {
+        let table = format!("{}.{}", target.schema, target.table);
+        let question = Msg::WizardRows.fill(language, &[&table]);
+        target.rows = prompter.ask_until(&question, &target.rows.to_string(), |answer| {
+            answer
+                .parse::<u64>()
+                .ok()
+                .filter(|rows| *rows > 0)
+                .ok_or_else(|| Msg::WizardInvalidRows.fill(language, &[&answer]))
+        })?;
+    }
+
+    let mut kept = Vec::new();
+    for rule in std::mem::take(&mut plan.rules) {
+        let Rule::ColumnGenerator(mut rule) = rule else {
+            kept.push(rule);
+            continue;
+        };
+        if rule.generator_id().starts_with("primitive.") {
+            kept.push(Rule::ColumnGenerator(rule));
+            continue;
+        }
+        let column = format!("{}.{}.{}", rule.schema, rule.table, rule.column);
+        let question = Msg::WizardGenerator.fill(language, &[&column]);
+        let default = rule.generator_id().to_string();
+        let generator = prompter.ask_until(&question, &default, |answer| {
+            if answer == "-" {
+                return Ok(None);
+            }
+            let mut candidate = rule.clone();
+            candidate.generator = GeneratorRef::Id(answer.to_string());
+            check_registry(&candidate)?;
+            Ok(Some(candidate.generator))
+        })?;
+        if let Some(generator) = generator {
+            rule.generator = generator;
+            kept.push(Rule::ColumnGenerator(rule));
+        }
+    }
+    plan.rules = kept;
+    Ok(())
+}
+
+/// `all` or a comma-separated list of `schema.table` (or unambiguous table) names.
+fn select_tables(
+    names: &[String],
+    answer: &str,
+    language: Language,
+) -> Result<Vec<String>, String> {
+    if answer.eq_ignore_ascii_case("all") {
+        return Ok(names.to_vec());
+    }
+    let mut chosen = Vec::new();
+    for part in answer
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let matches: Vec<&String> = names
+            .iter()
+            .filter(|name| {
+                name.as_str() == part
+                    || name.split_once('.').is_some_and(|(_, table)| table == part)
+            })
+            .collect();
+        match matches.as_slice() {
+            [name] => chosen.push((*name).clone()),
+            [] => return Err(Msg::WizardUnknownTable.fill(language, &[&part])),
+            _ => return Err(Msg::WizardAmbiguousTable.fill(language, &[&part])),
+        }
+    }
+    if chosen.is_empty() {
+        return Err(Msg::WizardNoTables.text(language).to_string());
+    }
+    Ok(chosen)
+}
+
+/// Line prompts on `out` answered from `input`; an empty answer or the end
+/// of input keeps the default. Questions and answers are kept in
+/// `transcript` (the plan's `prompt.txt`).
+struct Prompter<R, W> {
+    input: R,
+    out: W,
+    transcript: String,
+    eof: bool,
+    language: Language,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    fn new(input: R, out: W, language: Language) -> Self {
+        Self {
+            input,
+            out,
+            transcript: String::from("plan wizard answers\n"),
+            eof: false,
+            language,
+        }
+    }
+
+    fn say(&mut self, message: &str) -> Result<(), CliError> {
+        writeln!(self.out, "{message}")?;
+        Ok(())
+    }
+
+    fn ask(&mut self, question: &str, default: &str) -> Result<String, CliError> {
+        write!(self.out, "{question} [{default}]: ")?;
+        self.out.flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            self.eof = true;
+            writeln!(self.out)?;
+        }
+        let answer = match line.trim() {
+            "" => default,
+            answer => answer,
+        };
+        let _ = writeln!(self.transcript, "{question}: {answer}");
+        Ok(answer.to_string())
+    }
+
+    /// Ask again, with the reason, until `parse` accepts the answer; fails
+    /// once the input has ended.
+    fn ask_until<T>(
+        &mut self,
+        question: &str,
+        default: &str,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> Result<T, CliError> {
+        loop {
+            let answer = self.ask(question, default)?;
+            match parse(&answer) {
+                Ok(value) => return Ok(value),
+                Err(message) if self.eof => {
+                    return Err(CliError::Plan(format!("{question}: {message}")));
+                }
+                Err(message) => self.say(&message)?,
+            }
+        }
+    }
+}
+
+/// Summary line of a new plan.
+fn plan_created(plan: &Plan, language: Language) -> String {
+    let rows: u64 = plan.targets.iter().map(|target| target.rows).sum();
+    Msg::PlanCreated.fill(language, &[&plan.targets.len(), &plan.rules.len(), &rows])
+}
+
+/// JSON schema checks of `plan_json` plus its checks against `schema`.
+pub(crate) fn validation_report(
+    plan_json: &Value,
+    schema: &DatabaseSchema,
+) -> Result<ValidationReport, CliError> {
+    let plan_schema = serde_json::to_value(datalchemy_plan::plan_json_schema())?;
+    let mut report = validate_plan_json(plan_json, &plan_schema)
+        .map_err(|err| CliError::Plan(err.to_string()))?;
+    let plan: Plan =
+        serde_json::from_value(plan_json.clone()).map_err(|err| CliError::Plan(err.to_string()))?;
+    report.merge(validate_plan_against_schema(&plan, schema));
+    Ok(report)
+}
+
+/// Write the heuristic plan of `schema` to `plans/<plan_id>` (`plan.json`,
+/// `plan.meta.json`, `prompt.txt`), using the run's `classification.json`
+/// when there is one.
+pub(crate) fn write_smart_plan(
+    paths: &WorkspacePaths,
+    plan_id: &str,
+    run_id: &str,
+    schema: &DatabaseSchema,
+) -> Result<Plan, CliError> {
+    let classification = read_classification(paths, run_id)?;
+    let plan = smart_plan(schema, classification.as_ref());
+    write_plan_artifacts(
+        paths,
+        plan_id,
+        run_id,
+        schema,
+        &plan,
+        ("heuristic", "smart_plan"),
+        "smart plan generated by heuristic engine",
+    )?;
+    Ok(plan)
+}
+
+/// Write `plan.json`, `plan.meta.json` (`source` is the provider and model)
+/// and `prompt.txt` under `plans/<plan_id>`.
+pub(crate) fn write_plan_artifacts(
+    paths: &WorkspacePaths,
+    plan_id: &str,
+    run_id: &str,
+    schema: &DatabaseSchema,
+    plan: &Plan,
+    source: (&str, &str),
+    prompt: &str,
+) -> Result<(), CliError> {
+    let plan_dir = paths.plans_dir.join(plan_id);
+    std::fs::create_dir_all(&plan_dir)?;
+    write_bytes_atomic(
+        &plan_dir.join("plan.json"),
+        &serde_json::to_vec_pretty(plan)?,
+    )?;
+
+    let meta = PlanMeta {
+        plan_id: plan_id.to_string(),
+        status: ArtifactStatus::Ok,
+        schema_run_id: run_id.to_string(),
+        schema_fingerprint: schema.schema_fingerprint.clone(),
+        provider: source.0.to_string(),
+        model: source.1.to_string(),
+        mock: false,
+        artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
+        cli_version: crate::workspace::CLI_VERSION.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        finished_at: Some(Utc::now().to_rfc3339()),
+        tags: Vec::new(),
+        notes: Vec::new(),
+    };
+    write_json_atomic(&plan_dir.join("plan.meta.json"), &meta)?;
+    write_bytes_atomic(&plan_dir.join("prompt.txt"), prompt.as_bytes())?;
+    Ok(())
+}
+
+/// Changes to the plan targets since the run a plan was written for
+/// (`plan.meta.json`), when it is checked against the schema of another run.
+#[derive(Debug, Serialize)]
+pub(crate) struct Staleness {
+    pub plan_run_id: String,
+    pub diff: SchemaDiff,
+}
+
+impl Staleness {
+    /// One warning line per changed target table.
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "warning: plan_stale plan was written for run {}; target tables changed since:",
+            self.plan_run_id
+        )];
+        for table in &self.diff.tables_removed {
+            lines.push(format!("  - {table} removed"));
+        }
+        for table in &self.diff.tables_modified {
+            let mut changes: Vec<String> = Vec::new();
+            changes.extend(
+                table
+                    .columns_added
+                    .iter()
+                    .map(|column| format!("+{}", column.name)),
+            );
+            changes.extend(
+                table
+                    .columns_removed
+                    .iter()
+                    .map(|column| format!("-{}", column.name)),
+            );
+            changes.extend(table.columns_modified.iter().map(|column| {
+                let aspects: Vec<&str> = column
+                    .changes
+                    .iter()
+                    .map(|change| change.as_str())
+                    .collect();
+                format!("~{} ({})", column.name, aspects.join(", "))
+            }));
+            changes.extend(
+                table
+                    .constraints_added
+                    .iter()
+                    .map(|constraint| format!("+{}", constraint_signature(constraint))),
+            );
+            changes.extend(
+                table
+                    .constraints_removed
+                    .iter()
+                    .map(|constraint| format!("-{}", constraint_signature(constraint))),
+            );
+            lines.push(format!("  ~ {}: {}", table.table, changes.join(", ")));
+        }
+        lines
+    }
+}
+
+/// Diff from the schema the plan was written for to `schema` (the run
+/// `run_id`), kept to the plan targets. `None` when the plan was written for
+/// `run_id`, has no `plan.meta.json`, its run is gone or no target changed.
+pub(crate) fn plan_staleness(
+    paths: &WorkspacePaths,
+    plan_id: &str,
+    run_id: &str,
+    plan_json: &Value,
+    schema: &DatabaseSchema,
+) -> Result<Option<Staleness>, CliError> {
+    let meta_path = paths.plans_dir.join(plan_id).join("plan.meta.json");
+    if !meta_path.exists() {
+        return Ok(None);
+    }
+    let meta: PlanMeta = serde_json::from_str(&std::fs::read_to_string(meta_path)?)?;
+    let plan_schema_path = paths.runs_dir.join(&meta.schema_run_id).join("schema.json");
+    if meta.schema_run_id == run_id || !plan_schema_path.exists() {
+        return Ok(None);
+    }
+
+    let targets: BTreeSet<String> = plan_json
+        .get("targets")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|target| {
+            let schema = target.get("schema")?.as_str()?;
+            let table = target.get("table")?.as_str()?;
+            Some(format!("{schema}.{table}"))
+        })
+        .collect();
+    let mut diff = diff_schemas(&read_schema(&plan_schema_path)?, schema);
+    diff.tables_added.clear();
+    diff.enums_added.clear();
+    diff.tables_removed.retain(|table| targets.contains(table));
+    diff.tables_modified
+        .retain(|table| targets.contains(&table.table));
+    if diff.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Staleness {
+        plan_run_id: meta.schema_run_id,
+        diff,
+    }))
+}
+
+/// `schema` cut down to `tables`, their FK parents and, with
+/// `with_children`, the tables referencing them; unchanged without tables.
+pub(crate) fn subset_schema(
+    schema: DatabaseSchema,
+    tables: &[String],
+    with_children: bool,
+) -> Result<DatabaseSchema, CliError> {
+    if tables.is_empty() {
+        return Ok(schema);
+    }
+    Ok(schema.subset(
+        tables,
+        SubsetOptions {
+            include_children: with_children,
+        },
+    )?)
+}
+
+pub(crate) fn read_schema(path: &Path) -> Result<DatabaseSchema, CliError> {
+    if !path.exists() {
+        return Err(CliError::InvalidConfig(format!(
+            "schema.json not found: {}",
+            path.display()
+        )));
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Generate a smart plan by analyzing column names and types from the schema.
+/// Uses heuristic matching to assign appropriate faker-rs generators.
+/// Heuristic plan of `schema`: a generator per column and, per table (views
+/// and foreign tables left out), the introspected row estimate (50 rows
+/// without one). Text columns labeled in `classification` get the generator
+/// of their semantic type; the others are guessed from name and type.
+/// Nullable columns with sampled statistics get a `transform.null_rate` with
+/// their null fraction.
+pub(crate) fn smart_plan(schema: &DatabaseSchema, classification: Option<&Classification>) -> Plan {
+    let mut targets = Vec::new();
+    let mut rules = Vec::new();
+
+    for db_schema in &schema.schemas {
+        for table in db_schema
+            .tables
+            .iter()
+            .filter(|table| table.kind.is_insertable())
+        {
+            targets.push(Target {
+                schema: db_schema.name.clone(),
+                table: table.name.clone(),
+                rows: table.row_estimate.filter(|rows| *rows > 0).unwrap_or(50),
+                strategy: None,
+            });
+
+            for column in &table.columns {
+                if is_auto_column(column) {
+                    continue;
+                }
+                let classified = classification
+                    .and_then(|classification| {
+                        classification.column(&db_schema.name, &table.name, &column.name)
+                    })
+                    .and_then(|class| class.semantic_type.generator())
+                    .filter(|_| is_text_column(column))
+                    .map(str::to_string);
+                if let Some(gen_id) =
+                    classified.or_else(|| guess_generator(&column.name, &column.column_type))
+                {
+                    rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
+                        schema: db_schema.name.clone(),
+                        table: table.name.clone(),
+                        column: column.name.clone(),
+                        generator: GeneratorRef::Id(gen_id),
+                        params: None,
+                        transforms: null_rate_transform(column).into_iter().collect(),
+                        entity: None,
+                    }));
+                }
+            }
+        }
+    }
+
+    let mut plan = Plan {
+        plan_version: PLAN_VERSION.to_string(),
+        seed: 42,
+        schema_ref: SchemaRef {
+            schema_version: schema.schema_version.clone(),
+            schema_fingerprint: schema.schema_fingerprint.clone(),
+            engine: schema.engine.clone(),
+        },
+        global: Some(PlanGlobal {
+            locale: Some("pt_BR".to_string()),
+            formats: None,
+        }),
+        targets,
+        rules,
+        rules_unsupported: Vec::new(),
+        assertions: Vec::new(),
+        options: None,
+    };
+    plan.rules_unsupported = unsupported_rule_candidates(&plan, schema);
+    plan
+}
+
+/// `transform.null_rate` reproducing the sampled null fraction of a nullable
+/// column.
+fn null_rate_transform(column: &Column) -> Option<TransformRule> {
+    let null_frac = column.stats?.null_frac;
+    (column.is_nullable && null_frac > 0.0).then(|| TransformRule {
+        transform: "transform.null_rate".to_string(),
+        params: Some(serde_json::json!({ "rate": null_frac.min(1.0) })),
+    })
+}
+
+fn is_text_column(column: &Column) -> bool {
+    let udt = column.column_type.udt_name.to_lowercase();
+    matches!(udt.as_str(), "text" | "varchar" | "bpchar" | "citext")
+}
+
+/// Identity, generated and sequence/`gen_random_uuid` default columns; the
+/// database fills them, so plans leave them without a generator.
+pub(crate) fn is_auto_column(column: &Column) -> bool {
+    if column.identity.is_some() || column.generated.is_some() {
+        return true;
+    }
+    column.default.as_ref().is_some_and(|default| {
+        let lower = default.to_lowercase();
+        lower.contains("nextval(") || lower.contains("gen_random_uuid")
+    })
+}
+
+/// Generator and transforms must exist; declared params must be known and
+/// the required ones present.
+pub(crate) fn check_registry(rule: &ColumnGeneratorRule) -> Result<(), String> {
+    let registry = GeneratorRegistry::new();
+    let id = rule.generator_id();
+    let Some(generator) = registry.generator(id) else {
+        return Err(format!("unknown generator: {id}"));
+    };
+    let specs = generator.params();
+    if !specs.is_empty() {
+        let params = rule.generator_params().and_then(Value::as_object);
+        if let Some(params) = params
+            && let Some(key) = params
+                .keys()
+                .find(|key| !specs.iter().any(|spec| spec.key == key.as_str()))
+        {
+            return Err(format!("{id} has no param '{key}'"));
+        }
+        if let Some(spec) = specs.iter().find(|spec| {
+            spec.required && params.is_none_or(|params| !params.contains_key(spec.key))
+        }) {
+            return Err(format!("{id} requires param '{}'", spec.key));
+        }
+    }
+    for transform in &rule.transforms {
+        if registry.transform(&transform.transform).is_none() {
+            return Err(format!("unknown transform: {}", transform.transform));
+        }
+    }
+    Ok(())
+}
+
+/// Heuristic generator mapping based on column name patterns and SQL types.
+pub(crate) fn guess_generator(
+    col_name: &str,
+    col_type: &datalchemy_core::ColumnType,
+) -> Option<String> {
+    let name = col_name.to_lowercase();
+    let udt = col_type.udt_name.to_lowercase();
+    let dtype = col_type.data_type.to_lowercase();
+
+    // --- UUID type ---
+    if udt == "uuid" {
+        return Some("primitive.uuid".to_string());
+    }
+
+    // --- Boolean type ---
+    if udt == "bool" || dtype.contains("boolean") {
+        return Some("primitive.bool".to_string());
+    }
+
+    // --- Name-based heuristics (check before generic type fallback) ---
+
+    // Email
+    if name.contains("email") || name.contains("e_mail") {
+        return Some("semantic.person.email".to_string());
+    }
+
+    // Phone / telefone
+    if name.contains("phone")
+        || name.contains("telefone")
+        || name.contains("celular")
+        || name.contains("fone")
+    {
+        return Some("faker.phone_number.raw.PhoneNumber".to_string());
+    }
+
+    // Person name patterns
+    if name == "nome"
+        || name == "name"
+        || name == "nome_completo"
+        || name == "full_name"
+        || name == "fullname"
+    {
+        return Some("faker.name.raw.Name".to_string());
+    }
+    if name == "primeiro_nome" || name == "first_name" || name == "firstname" {
+        return Some("faker.name.raw.FirstName".to_string());
+    }
+    if name == "sobrenome" || name == "last_name" || name == "lastname" || name == "ultimo_nome" {
+        return Some("faker.name.raw.LastName".to_string());
+    }
+
+    // Company
+    if name.contains("empresa")
+        || name.contains("company")
+        || name == "razao_social"
+        || name == "nome_fantasia"
+    {
+        return Some("faker.company.raw.CompanyName".to_string());
+    }
+
+    // Address
+    if name.contains("endereco") || name.contains("address") || name == "logradouro" {
+        return Some("faker.address.raw.StreetName".to_string());
+    }
+    if name == "cidade" || name == "city" {
+        return Some("faker.address.raw.CityName".to_string());
+    }
+    if name == "estado" || name == "state" || name == "uf" {
+        return Some("faker.address.raw.StateName".to_string());
+    }
+    if name == "cep"
+        || name == "zip"
+        || name == "zipcode"
+        || name == "zip_code"
+        || name == "codigo_postal"
+    {
+        return Some("faker.address.raw.ZipCode".to_string());
+    }
+    if name == "pais" || name == "country" {
+        return Some("faker.address.raw.CountryName".to_string());
+    }
+
+    // URL / website
+    if name.contains("url") || name.contains("website") || name.contains("site") {
+        return Some("faker.internet.raw.DomainSuffix".to_string());
+    }
+
+    // Description / text
+    if name.contains("descricao")
+        || name.contains("description")
+        || name.contains("observacao")
+        || name.contains("obs")
+        || name.contains("notas")
+        || name.contains("notes")
+        || name.contains("comentario")
+    {
+        return Some("faker.lorem.raw.Sentence".to_string());
+    }
+
+    // Title / titulo
+    if name == "titulo" || name == "title" || name == "assunto" || name == "subject" {
+        return Some("faker.lorem.raw.Words".to_string());
+    }
+
+    // Monetary / valor
+    if name.contains("valor")
+        || name.contains("preco")
+        || name.contains("price")
+        || name.contains("amount")
+        || name.contains("custo")
+        || name.contains("cost")
+        || name.contains("salario")
+        || name.contains("salary")
+    {
+        return Some("primitive.float".to_string());
+    }
+
+    // Quantity / count
+    if name.contains("quantidade")
+        || name.contains("qty")
+        || name.contains("quantity")
+        || name.contains("count")
+        || name.contains("total")
+    {
+        return Some("primitive.int".to_string());
+    }
+
+    // Percentage
+    if name.contains("percentual") || name.contains("percent") || name.contains("taxa") {
+        return Some("primitive.float".to_string());
+    }
+
+    // Status / tipo / category (enum-like text)
+    if name == "status"
+        || name == "tipo"
+        || name == "type"
+        || name == "categoria"
+        || name == "category"
+    {
+        // For enum-like, let the generator engine use fallback
+        return None;
+    }
+
+    // --- Type-based fallback ---
+
+    // Timestamps
+    if udt.contains("timestamp") || dtype.contains("timestamp") {
+        return Some("primitive.timestamp".to_string());
+    }
+    if udt == "date" || dtype == "date" {
+        return Some("primitive.date".to_string());
+    }
+    if udt == "time" || dtype.contains("time without") {
+        return Some("primitive.time".to_string());
+    }
+
+    // Numeric types
+    if udt == "int2" || udt == "int4" || udt == "int8" || udt == "serial" || udt == "bigserial" {
+        return Some("primitive.int".to_string());
+    }
+    if udt == "float4" || udt == "float8" || udt == "numeric" || udt == "decimal" || udt == "money"
+    {
+        return Some("primitive.float".to_string());
+    }
+
+    // JSON
+    if udt == "json" || udt == "jsonb" {
+        return Some("primitive.json".to_string());
+    }
+
+    // Text: only if it's a non-trivial text column
+    if udt == "text" || udt == "varchar" || udt.starts_with("varchar") || udt == "bpchar" {
+        // Generic text columns get a lorem generator
+        return Some("faker.lorem.raw.Word".to_string());
+    }
+
+    None
+}
@@ -0,0 +1,117 @@
+//! Value profile of the source database for `datalchemy introspect --profile`.
+//!
+//! Reads at most `sample_rows` random rows of each base table
+//! (`TABLESAMPLE BERNOULLI`) as `row_to_json` and keeps, per column, the null
+//! fraction, min/max and the most frequent values. Columns whose name implies
+//! PII, or whose comment tags it (`@pii:<kind>`), are never selected and the
+//! connection string is stored redacted.
+
+use std::time::Duration;
+
+use datalchemy_core::ddl::quote_ident;
+use datalchemy_core::{DatabaseSchema, Table, TableKind, redact_connection_string};
+use datalchemy_generate::profile::{
+    ColumnProfile, DEFAULT_TOP_VALUES, TableProfile, ValueProfile, is_redacted,
+};
+use serde_json::Value;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+
+use crate::CliError;
+
+/// Sample the base tables of `schema`.
+pub(crate) async fn sample_profile(
+    conn: &str,
+    schema: &DatabaseSchema,
+    sample_rows: u64,
+) -> Result<ValueProfile, CliError> {
+    if crate::detect_engine(conn)? != "postgres" {
+        return Err(CliError::UnsupportedEngine(conn.to_string()));
+    }
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect(conn)
+        .await?;
+
+    let mut tables = Vec::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            if matches!(table.kind, TableKind::Table | TableKind::PartitionedTable) {
+                tables.push(profile_table(&pool, &db_schema.name, table, sample_rows).await?);
+            }
+        }
+    }
+    pool.close().await;
+
+    Ok(ValueProfile {
+        connection: Some(redact_connection_string(conn)),
+        sample_rows,
+        tables,
+    })
+}
+
+async fn profile_table(
+    pool: &PgPool,
+    schema_name: &str,
+    table: &Table,
+    sample_rows: u64,
+) -> Result<TableProfile, CliError> {
+    let qualified = format!("{}.{}", quote_ident(schema_name), quote_ident(&table.name));
+    let (redacted, columns): (Vec<_>, Vec<_>) = table
+        .columns
+        .iter()
+        .partition(|column| is_redacted(&column.name) || !column.hints().pii.is_empty());
+
+    let mut rows = Vec::new();
+    if !columns.is_empty() && sample_rows > 0 {
+        let row_count = sqlx::query_scalar::<_, i64>(&format!("select count(*) from {qualified}"))
+            .fetch_one(pool)
+            .await?
+            .max(0) as u64;
+        // Oversample a little so the LIMIT is usually reached.
+        let tablesample = if row_count > sample_rows {
+            let percent = (sample_rows as f64 * 120.0 / row_count as f64).min(100.0);
+            format!(" tablesample bernoulli ({percent:.6})")
+        } else {
+            String::new()
+        };
+        let select = columns
+            .iter()
+            .map(|column| quote_ident(&column.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        rows = sqlx::query_scalar::<_, String>(&format!(
+            "select row_to_json(sample)::text from \
+             (select {select} from {qualified}{tablesample} limit {sample_rows}) sample"
+        ))
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(|row| serde_json::from_str::<Value>(row))
+        .collect::<Result<Vec<_>, _>>()?;
+    }
+
+    Ok(TableProfile {
+        schema: schema_name.to_string(),
+        table: table.name.clone(),
+        sampled_rows: rows.len() as u64,
+        columns: if rows.is_empty() {
+            Vec::new()
+        } else {
+            columns
+                .into_iter()
+                .map(|column| {
+                    let values: Vec<Value> = rows
+                        .iter()
+                        .map(|row| row.get(&column.name).cloned().unwrap_or(Value::Null))
+                        .collect();
+                    ColumnProfile::from_values(column, &values, DEFAULT_TOP_VALUES)
+                })
+                .collect()
+        },
+        redacted_columns: redacted
+            .into_iter()
+            .map(|column| column.name.clone())
+            .collect(),
+    })
+}
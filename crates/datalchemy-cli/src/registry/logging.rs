@@ -39,7 +39,7 @@ impl Write for SharedWriter {
         let mut file = self
             .file
             .lock()
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "failed to lock log file"))?;
+            .map_err(|_| io::Error::other("failed to lock log file"))?;
         file.write(buf)
     }
 
@@ -47,7 +47,7 @@ impl Write for SharedWriter {
         let mut file = self
             .file
             .lock()
-            .map_err(|_| io::Error::new(io::ErrorKind::Other, "failed to lock log file"))?;
+            .map_err(|_| io::Error::other("failed to lock log file"))?;
         file.flush()
     }
 }
@@ -21,6 +21,10 @@ pub struct RunOptions {
     pub include_indexes: bool,
     pub include_comments: bool,
     pub schemas: Option<Vec<String>>,
+    pub include_tables: Vec<String>,
+    pub exclude_tables: Vec<String>,
+    pub sample_stats: bool,
+    pub include_privileges: bool,
 }
 
 /// Metadata captured at run start.
@@ -109,10 +113,10 @@ pub fn write_schema(
     write_json(&paths.schema_path, schema)?;
 
     if let Some(out_path) = out_path {
-        if let Some(parent) = out_path.parent() {
-            if !parent.as_os_str().is_empty() {
-                create_dir_all(parent)?;
-            }
+        if let Some(parent) = out_path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            create_dir_all(parent)?;
         }
         write_json(out_path, schema)?;
     }
@@ -0,0 +1,136 @@
+//! `datalchemy serve`: read-only HTTP server over a generated output.
+//!
+//! Minimal HTTP/1.1 (one request per connection) answering `GET`/`HEAD` with
+//! JSON from [`MockDataset`]; CORS is open so browser apps on another port can
+//! call it.
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use datalchemy_generate::GenerationReport;
+use datalchemy_generate::mock_api::{MockDataset, MockResponse};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::CliError;
+use crate::output::Output;
+use crate::workspace::{OutManifest, WorkspacePaths};
+
+const MAX_HEADER_LINES: usize = 100;
+
+/// Load `out/<out_id>` of the workspace and serve it on `addr` until interrupted.
+pub async fn run_serve(
+    workspace: &Path,
+    out_id: &str,
+    addr: SocketAddr,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    let paths = WorkspacePaths::new(workspace.to_path_buf());
+    let out_dir = paths.out_dir.join(out_id);
+    let manifest_path = out_dir.join("out_manifest.json");
+    if !manifest_path.exists() {
+        return Err(CliError::InvalidConfig(format!(
+            "output not found: {}",
+            out_dir.display()
+        )));
+    }
+    let manifest: OutManifest = serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)?;
+    let schema = serde_json::from_str(&std::fs::read_to_string(
+        paths
+            .runs_dir
+            .join(&manifest.schema_run_id)
+            .join("schema.json"),
+    )?)?;
+    let report: GenerationReport = serde_json::from_str(&std::fs::read_to_string(
+        out_dir.join("generation_report.json"),
+    )?)?;
+    let dataset = Arc::new(MockDataset::load(&out_dir, &schema, &report)?);
+
+    let listener = TcpListener::bind(addr).await?;
+    let url = format!("http://{}/", listener.local_addr()?);
+    output.line(format!("serving {out_id} on {url}"));
+    output.set("out_id", out_id)?;
+    output.set("url", &url)?;
+    output.flush("serve");
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let dataset = Arc::clone(&dataset);
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, &dataset).await {
+                tracing::debug!(error = %err, "serve connection failed");
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, dataset: &MockDataset) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    // Headers are not needed; drain them up to the blank line.
+    for _ in 0..MAX_HEADER_LINES {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or("/");
+    let response = match method {
+        "GET" | "HEAD" => dataset.handle(target),
+        "OPTIONS" => MockResponse {
+            status: 204,
+            body: serde_json::Value::Null,
+            total: None,
+        },
+        _ => MockResponse {
+            status: 405,
+            body: serde_json::json!({ "error": "read-only API: use GET" }),
+            total: None,
+        },
+    };
+    writer
+        .write_all(&http_response(&response, method == "HEAD"))
+        .await?;
+    writer.shutdown().await
+}
+
+fn http_response(response: &MockResponse, head_only: bool) -> Vec<u8> {
+    let body = if response.status == 204 {
+        Vec::new()
+    } else {
+        serde_json::to_vec_pretty(&response.body).unwrap_or_default()
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, HEAD, OPTIONS\r\n\
+         Access-Control-Expose-Headers: X-Total-Count\r\nConnection: close\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    if let Some(total) = response.total {
+        head.push_str(&format!("X-Total-Count: {total}\r\n"));
+    }
+    head.push_str("\r\n");
+    let mut bytes = head.into_bytes();
+    if !head_only {
+        bytes.extend_from_slice(&body);
+    }
+    bytes
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Error",
+    }
+}
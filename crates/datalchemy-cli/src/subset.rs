@@ -0,0 +1,107 @@
+//! `datalchemy subset`: a referentially intact slice of a Postgres database.
+//!
+//! Seed rows (`--where`, ordered by primary key) and the rows reached through
+//! foreign keys are read by [`RowSubset::extract`] and written by
+//! [`MaskingEngine`]: masked with the transforms of `--plan`, or copied.
+
+use std::collections::HashMap;
+
+use datalchemy_core::ddl::quote_ident;
+use datalchemy_core::{DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationError, MaskingEngine, RowSubset, RowSubsetOptions, SubsetSource,
+};
+use tokio::runtime::Runtime;
+
+use crate::generate::load_plan;
+use crate::mask::{PostgresRows, order_by, qualified, write_run_output};
+use crate::output::Output;
+use crate::{CliError, SubsetArgs};
+
+/// Extract the slice seeded by `--seed` into `out_dir`.
+pub fn run_subset(
+    args: SubsetArgs,
+    runtime: &Runtime,
+    output: &mut Output,
+) -> Result<(), CliError> {
+    let schema: DatabaseSchema = serde_json::from_str(&std::fs::read_to_string(&args.schema)?)?;
+    let plan = args
+        .plan
+        .as_deref()
+        .map(|path| load_plan(path, &schema))
+        .transpose()?;
+    let mut source = PostgresRows::connect(args.conn.as_deref(), runtime)?;
+    let mut subset = RowSubset::extract(
+        &schema,
+        &RowSubsetOptions {
+            seed: args.seed.clone(),
+            filter: args.filter.clone(),
+            seed_rows: args.seed_rows,
+            max_rows_per_table: args.max_rows,
+            include_children: !args.no_children,
+        },
+        &mut source,
+    )?;
+    let plan = subset.plan(plan.as_ref());
+    let slice = subset.schema.clone();
+    let result = MaskingEngine::new(GenerateOptions {
+        out_dir: args.out_dir.clone(),
+        format: args.format,
+        ..GenerateOptions::default()
+    })
+    .run(&slice, &plan, &mut subset)?;
+    output.set("tables", plan.targets.len())?;
+    write_run_output(&result, output)
+}
+
+impl SubsetSource for PostgresRows<'_> {
+    fn seed_rows(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        filter: Option<&str>,
+        limit: u64,
+    ) -> Result<Vec<HashMap<String, GeneratedValue>>, GenerationError> {
+        let filter = filter
+            .map(|filter| format!(" where {filter}"))
+            .unwrap_or_default();
+        let sql = format!(
+            "select row_to_json(t)::text from {}{filter}{} limit {limit}",
+            qualified(schema, table),
+            order_by(table)
+        );
+        self.fetch(table, &sql, Vec::new())
+    }
+
+    fn rows_by_key(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        columns: &[String],
+        keys: &[Vec<String>],
+        limit: Option<u64>,
+    ) -> Result<Vec<HashMap<String, GeneratedValue>>, GenerationError> {
+        let left = columns
+            .iter()
+            .map(|column| format!("t.{}::text", quote_ident(column)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let arrays = (1..=columns.len())
+            .map(|index| format!("${index}::text[]"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let limit = limit
+            .map(|limit| format!(" limit {limit}"))
+            .unwrap_or_default();
+        let sql = format!(
+            "select row_to_json(t)::text from {} where ({left}) in (select * from unnest({arrays})){}{limit}",
+            qualified(schema, table),
+            order_by(table)
+        );
+        let binds = (0..columns.len())
+            .map(|index| keys.iter().map(|key| key[index].clone()).collect())
+            .collect();
+        self.fetch(table, &sql, binds)
+    }
+}
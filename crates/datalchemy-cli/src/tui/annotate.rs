@@ -0,0 +1,131 @@
+//! Tags and notes for `/runs`, `/plans` and `/out`.
+//!
+//! `<cmd> tag <id> <tag>...`, `<cmd> untag <id> <tag>...` and
+//! `<cmd> note <id> <text>` (`--clear` drops the notes) edit the manifest of
+//! the artifact; `<cmd> list --tag <tag>` keeps the entries carrying every
+//! given tag.
+
+use crate::CliError;
+use crate::i18n::Msg;
+use crate::tui::state::App;
+use crate::workspace::{
+    AnnotatedKind, Annotations, WriteIntent, add_tags, read_annotations, remove_tags, set_note,
+};
+
+/// Handle `tag`, `untag` and `note`; `args[0]` is the subcommand.
+pub fn cmd_annotate(
+    app: &mut App,
+    command: &str,
+    kind: AnnotatedKind,
+    args: &[&str],
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    let action = args[0];
+    if args.len() < 3 {
+        if args.len() < 2 {
+            app.input_set(format!("{command} {action} "));
+        } else {
+            let usage = if action == "note" {
+                "<text>|--clear"
+            } else {
+                "<tag>..."
+            };
+            app.say_with(Msg::Usage, &[&format!("{command} {action} <id> {usage}")]);
+        }
+        return Ok(());
+    }
+    let id = args[1];
+    let manifest_path = kind.manifest_path(&app.paths, id);
+    if !manifest_path.exists() {
+        app.say_with(
+            Msg::AnnotateManifestNotFound,
+            &[&kind.label(), &manifest_path.display()],
+        );
+        return Ok(());
+    }
+    if !bypass_approval && app.requires_approval() {
+        let intent = WriteIntent::new(format!("{action} {}", kind.label()), vec![manifest_path]);
+        return app.request_approval(intent, raw);
+    }
+
+    match action {
+        "tag" | "untag" => {
+            let tags = if action == "tag" {
+                add_tags(&app.paths, kind, id, &args[2..])?
+            } else {
+                remove_tags(&app.paths, kind, id, &args[2..])?
+            };
+            app.say_with(Msg::TagsOf, &[&id, &tag_list(&tags)]);
+        }
+        _ => {
+            if args[2..] == ["--clear"] {
+                set_note(&app.paths, kind, id, None)?;
+                app.say(Msg::NotesCleared);
+            } else {
+                set_note(&app.paths, kind, id, Some(&args[2..].join(" ")))?;
+                app.say(Msg::NoteAdded);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Values of every `--tag` flag in `args`.
+pub fn tag_filter(args: &[&str]) -> Vec<String> {
+    args.windows(2)
+        .filter(|pair| pair[0] == "--tag")
+        .map(|pair| pair[1].trim().to_lowercase())
+        .collect()
+}
+
+/// Entries of `ids` matching `filter`, with their annotations.
+pub fn filter_tagged(
+    app: &App,
+    kind: AnnotatedKind,
+    ids: Vec<String>,
+    filter: &[String],
+) -> Result<Vec<(String, Annotations)>, CliError> {
+    let mut entries = Vec::new();
+    for id in ids {
+        let annotations = read_annotations(&app.paths, kind, &id)?.unwrap_or_default();
+        if annotations.has_tags(filter) {
+            entries.push((id, annotations));
+        }
+    }
+    Ok(entries)
+}
+
+/// `  [tag, tag]  latest note` suffix of a list entry.
+pub fn list_suffix(annotations: &Annotations) -> String {
+    let mut suffix = String::new();
+    if !annotations.tags.is_empty() {
+        suffix.push_str(&format!("  [{}]", annotations.tags.join(", ")));
+    }
+    if let Some(note) = annotations.notes.last() {
+        suffix.push_str(&format!("  {}", note.text));
+    }
+    suffix
+}
+
+/// `tags:` and `notes:` lines of the inspect views.
+pub fn push_details(app: &mut App, annotations: &Annotations) {
+    app.push_message(format!("tags:             {}", tag_list(&annotations.tags)));
+    if annotations.notes.is_empty() {
+        let none = app.text(Msg::NoValue);
+        app.push_message(format!("notes:            {none}"));
+        return;
+    }
+    app.push_raw("notes:");
+    for note in &annotations.notes {
+        app.push_message(format!("  {}  {}", note.created_at, note.text));
+    }
+}
+
+fn tag_list(tags: &[String]) -> String {
+    if tags.is_empty() {
+        "none".to_string()
+    } else {
+        tags.join(", ")
+    }
+}
@@ -1,36 +1,63 @@
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use serde_json::Value;
 
 use datalchemy_core::{DatabaseSchema, redact_connection_string, validate_schema};
-use datalchemy_eval::{EvaluateOptions, EvaluationEngine, collect_schema_metrics};
-use datalchemy_generate::{GenerateOptions, GenerationEngine};
-use datalchemy_introspect::{
-    IntrospectOptions, introspect_postgres_with_options, introspect_sqlite_with_options,
+use datalchemy_eval::history::HISTORY_FILE;
+use datalchemy_eval::{
+    EvalError, EvaluateOptions, EvaluationEngine, EvaluationResult, HistoryEntry, MetricsReport,
+    append_history, collect_schema_metrics, read_history, render_diff,
 };
-use datalchemy_plan::{
-    ColumnGeneratorRule, GeneratorRef, PLAN_VERSION, Plan, PlanGlobal, Rule, SchemaRef, Target,
-    validate_plan, validate_plan_against_schema, validate_plan_json,
+use datalchemy_generate::dbt::export_dbt_seeds;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, GenerationReport, GenerationResult,
+    KafkaTarget, ObjectStoreTarget, OutputFormat,
 };
+use datalchemy_introspect::IntrospectOptions;
+use datalchemy_plan::{Plan, Rule, validate_plan};
 
 use crate::CliError;
-use crate::tui::secrets::{VaultMeta, decrypt_from_file, encrypt_to_file, load_env_file};
-use crate::tui::state::{App, AppEvent, PaletteEntry, PromptContext, SetupStep, UiState};
+use crate::classify::{
+    CLASSIFICATION_FILE, ClassSource, read_classification, write_classification,
+};
+use crate::explain;
+use crate::generate::slowest_table_lines;
+use crate::i18n::Msg;
+use crate::live_sample::sample_source;
+use crate::llm::planner::{TRANSCRIPT_FILE, planner_model, write_llm_plan};
+use crate::llm::{self, CompletionRequest, KEYED_PROVIDERS};
+use crate::plan::{plan_staleness, subset_schema, validation_report, write_smart_plan};
+use crate::tui::secrets::{
+    VaultMeta, decrypt_from_file, encrypt_to_file, format_env, load_env_file, parse_env,
+    storage_credentials,
+};
+use crate::tui::state::{
+    App, AppEvent, EvalTask, GenerationTask, IntrospectTask, PaletteEntry, PreviewState,
+    PromptContext, SetupStep, UiState,
+};
 use crate::tui::utils::{
     append_line, command_with_id, csv_preview, extract_flag_value, list_dirs, list_preview_files,
     move_dir_contents, open_in_editor, read_head_lines, read_tail_lines, set_private_permissions,
 };
+use crate::tui::{annotate, compare, rules};
 use crate::workspace::{
-    ApprovalPolicy, ArtifactStatus, DbProfile, DoctorLevel, LlmProvider, OutManifest, PlanMeta,
-    PrivacyMode, RunManifest, RunOptions, WorkspaceMode, WorkspaceSettings, WriteIntent,
+    AnnotatedKind, Annotations, ApprovalPolicy, ArtifactStatus, DbProfile, DoctorLevel, Language,
+    LlmProvider, OutManifest, PrivacyMode, RunManifest, RunOptions, SignatureStatus, WorkspaceMode,
+    WorkspaceSettings, WriteIntent, export_workspace, generate_signing_key, import_workspace,
     load_or_create_llm_models, load_or_create_profiles, load_or_create_settings, new_artifact_id,
-    run_doctor, save_profiles, save_settings, write_bytes_atomic, write_json_atomic,
+    run_doctor, save_profiles, save_settings, seal_output, verify_output, workspace_public_key,
+    write_json_atomic,
 };
 use sqlx::{Row, postgres::PgPoolOptions};
 
-use crate::tui::conn::{is_sqlite, is_supported_connection};
+use crate::tui::conn::is_sqlite;
+
+/// Rows loaded per CSV file by `/out preview` unless `--rows` is given.
+const PREVIEW_ROWS: usize = 200;
 
 pub fn execute_command(app: &mut App, input: &str, bypass_approval: bool) -> Result<(), CliError> {
     let mut parts = input.split_whitespace();
@@ -48,6 +75,7 @@ pub fn execute_command(app: &mut App, input: &str, bypass_approval: bool) -> Res
         "/status" => cmd_status(app),
         "/init" => cmd_init(app, bypass_approval, input),
         "/reset" => cmd_reset(app),
+        "/workspace" => cmd_workspace(app, parts.collect(), bypass_approval, input),
         "/settings" => cmd_settings(app, parts.collect(), bypass_approval, input),
         "/profiles" => cmd_profiles(app, parts.collect(), bypass_approval, input),
         "/db" => cmd_db(app, parts.collect()),
@@ -55,23 +83,25 @@ pub fn execute_command(app: &mut App, input: &str, bypass_approval: bool) -> Res
         "/runs" => cmd_runs(app, parts.collect(), bypass_approval, input),
         "/plans" => cmd_plans(app, parts.collect(), bypass_approval, input),
         "/plan" => cmd_plan(app, parts.collect(), bypass_approval, input),
+        "/classify" => cmd_classify(app, parts.collect(), bypass_approval, input),
         "/generate" => cmd_generate(app, parts.collect(), bypass_approval, input),
-        "/out" => cmd_out(app, parts.collect()),
+        "/out" => cmd_out(app, parts.collect(), bypass_approval, input),
         "/eval" => cmd_eval(app, parts.collect(), bypass_approval, input),
         "/doctor" => cmd_doctor(app),
+        "/explain" => cmd_explain(app, parts.next()),
         "/logs" => cmd_logs(app, parts.collect()),
         "/open" => cmd_open(app, parts.collect()),
         "/secrets" => cmd_secrets(app, parts.collect(), bypass_approval, input),
         "/llm" => cmd_llm(app, parts.collect(), bypass_approval, input),
         _ => {
-            app.push_message(format!("unknown command: {command}. type /help for list."));
+            app.say_with(Msg::UnknownCommand, &[&command]);
             Ok(())
         }
     }
 }
 
 /// Start an interactive multi-step prompt flow.
-fn start_prompt(app: &mut App, ctx: PromptContext) {
+pub fn start_prompt(app: &mut App, ctx: PromptContext) {
     if let Some(label) = ctx.current_prompt() {
         app.push_message(label.to_string());
     }
@@ -80,9 +110,9 @@ fn start_prompt(app: &mut App, ctx: PromptContext) {
 }
 
 pub fn cmd_help(app: &mut App) -> Result<(), CliError> {
-    app.push_raw("COMMANDS");
+    app.push_raw(app.text(Msg::HelpTitle));
     app.push_raw("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    app.push_raw("workspace:");
+    app.push_raw(app.text(Msg::HelpWorkspace));
     if app.paths.root.exists() {
         app.push_raw("  /reset                  delete workspace and re-setup");
     } else {
@@ -90,49 +120,86 @@ pub fn cmd_help(app: &mut App) -> Result<(), CliError> {
     }
     app.push_raw("  /status                 show current configuration");
     app.push_raw("  /doctor                 diagnose workspace issues");
+    app.push_raw("  /explain [<code>]       causes and fixes of an error code (DL110)");
+    app.push_raw("  /workspace export <file.tar.zst>  pack config, runs, plans, out, eval");
+    app.push_raw("  /workspace import <file.tar.zst>  merge an export [--overwrite]");
     app.push_raw("  /logs [<run_id>]        show log tail");
     app.push_raw("  /open <path>            preview a file");
     app.push_raw("");
-    app.push_raw("db + profiles:");
+    app.push_raw(app.text(Msg::HelpProfiles));
     app.push_raw("  /profiles list          list profiles");
     app.push_raw("  /profiles new <n> <url> create profile");
     app.push_raw("  /profiles set <name>    set active profile");
     app.push_raw("  /profiles delete <name> remove profile");
+    app.push_raw("  /profiles storage <name> <s3|gs|az://bucket/prefix|clear>");
+    app.push_raw("    --endpoint <url> --region <r> --allow-http");
     app.push_raw("  /db session             set ephemeral connection");
     app.push_raw("  /db change              update active connection");
     app.push_raw("  /db show-current        show connection details");
     app.push_raw("  /db test                test connectivity");
-    app.push_raw("  /db privileges          inspect user/db info");
+    app.push_raw("  /db privileges          inspect user/db info and table grants");
     app.push_raw("");
-    app.push_raw("pipeline:");
+    app.push_raw(app.text(Msg::HelpPipeline));
     app.push_raw("  /introspect             capture schema.json from DB");
     app.push_raw("  /runs list              list introspection runs");
     app.push_raw("  /runs set <id>          set active run");
     app.push_raw("  /runs inspect <id>      show run details");
+    app.push_raw("  /runs compare <a> <b>   schema diff and metrics of two runs, side by side");
     app.push_raw("  /runs delete <id>       delete run");
+    app.push_raw("  /classify               label columns with semantic types / PII");
+    app.push_raw("    --heuristic           name/type labels only, without llm");
+    app.push_raw("  /classify show          list column labels of the active run");
     app.push_raw("  /plan new               create plan from schema");
+    app.push_raw("    --tables <t1,t2> [--with-children]  only these tables + FK parents");
     app.push_raw("  /plan edit              edit plan.json in editor");
     app.push_raw("  /plan show              show current plan summary");
     app.push_raw("  /plan validate          validate plan vs schema");
+    app.push_raw("  /plan rules             add a column rule (form)");
+    app.push_raw("  /plan rules list        list column generator rules");
+    app.push_raw("  /plan rules add <schema.table> <col> <generator> [params|-] [transforms|-]");
+    app.push_raw("  /plan rules remove <schema.table> <col>");
     app.push_raw("  /plans list             list all plans");
     app.push_raw("  /plans set <id>         set active plan");
     app.push_raw("  /generate               generate CSV outputs");
+    app.push_raw("    --format <fmt>        csv | pg_copy | pg_copy_binary | sqlite |");
+    app.push_raw("                          duckdb | arrow_ipc | avro | xlsx |");
+    app.push_raw("                          fixed_width | flyway | liquibase");
+    app.push_raw("    --kafka <brokers>     publish rows to Kafka (one topic per table)");
+    app.push_raw("    --topic-prefix <p> --kafka-encoding json|avro");
+    app.push_raw("    --kafka-key <s.t=col> --kafka-rate <msg/s> --kafka-partitions <n>");
+    app.push_raw("    --top-slowest <n>     list the n slowest tables (ms, rows/s, retries/s)");
     app.push_raw("  /out list               list generated outputs");
-    app.push_raw("  /out preview <id>       preview CSV files");
+    app.push_raw("  /out preview [id]       browse CSV rows in a table [--rows <n>]");
+    app.push_raw("  /out dbt <dir> [id]     export CSV output as dbt seeds");
     app.push_raw("  /eval [<out_id>]        evaluate last output");
+    app.push_raw(
+        "  /eval --dataset <dir>   evaluate external CSV/Parquet files [--pattern {table}.csv]",
+    );
+    app.push_raw("    --privacy <specs.json> k-anonymity / l-diversity over quasi-identifiers");
+    app.push_raw("    --allow-domain <d,..>  e-mail domains accepted by the PII scan");
+    app.push_raw("    --no-pii-scan          skip the real-looking PII scan");
+    app.push_raw(
+        "    --no-temporal          skip updated >= created / end >= start / window checks",
+    );
+    app.push_raw("    --thresholds <file.json> fail on null rate / distinct ratio / FK rate");
+    app.push_raw("    --streaming            chunked read with keys spilled to disk (large data)");
+    app.push_raw("    --sample <rows>        quick eval of a sample per table, extrapolated");
+    app.push_raw("    --live [--live-rows n] realism score vs a sample of the source database");
+    app.push_raw("  /eval diff [<a> <b>]    compare two evaluations (default: last two)");
     app.push_raw("");
-    app.push_raw("settings:");
+    app.push_raw(app.text(Msg::HelpSettings));
     app.push_raw("  /settings show          show all settings");
     app.push_raw("  /settings set <k> <v>   update a setting");
+    app.push_raw("  /settings set language <en|pt_BR>  message language");
     app.push_raw("");
-    app.push_raw("secrets:");
+    app.push_raw(app.text(Msg::HelpSecrets));
     app.push_raw("  /secrets status         vault status");
     app.push_raw("  /secrets import-env     load .env into session");
     app.push_raw("  /secrets store-session  store session (encrypted)");
     app.push_raw("  /secrets unlock <pass>  unlock vault");
     app.push_raw("  /secrets delete         delete vault");
     app.push_raw("");
-    app.push_raw("navigation:");
+    app.push_raw(app.text(Msg::HelpNavigation));
     app.push_raw("  Tab         autocomplete command");
     app.push_raw("  Up/Down     navigate palette / schema list");
     app.push_raw("  Esc         go back / clear input");
@@ -148,12 +215,12 @@ fn cmd_status(app: &mut App) -> Result<(), CliError> {
     app.push_raw("");
     app.push_raw("WORKSPACE STATUS");
     app.push_raw("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    app.push_message(format!("Root:      {}", app.paths.root.display()));
-    app.push_message(format!(
-        "Profile:   {}",
-        app.settings.active_profile.as_deref().unwrap_or("none")
-    ));
-    app.push_message(format!("Mode:      {}", app.mode_display()));
+    let none = app.text(Msg::NoValue);
+    let root = app.paths.root.display().to_string();
+    let profile = app.settings.active_profile.clone();
+    app.say_with(Msg::StatusRoot, &[&root]);
+    app.say_with(Msg::StatusProfile, &[&profile.as_deref().unwrap_or(none)]);
+    app.say_with(Msg::StatusMode, &[&app.mode_display()]);
 
     let env_conn = std::env::var("DATABASE_URL").ok();
     let file_conn = if env_conn.is_none() {
@@ -165,7 +232,7 @@ fn cmd_status(app: &mut App) -> Result<(), CliError> {
     };
     let conn_status = if let Some(conn) = &app.session_conn {
         let safe = redact_connection_string(conn);
-        format!("session ({})", safe.redacted)
+        Msg::ConnSession.fill(app.settings.language, &[&safe.redacted])
     } else if let Some(conn) = env_conn {
         let safe = redact_connection_string(&conn);
         format!("env ({})", safe.redacted)
@@ -173,28 +240,27 @@ fn cmd_status(app: &mut App) -> Result<(), CliError> {
         let safe = redact_connection_string(&conn);
         format!(".env ({})", safe.redacted)
     } else if let Some(safe_str) = app.active_profile_redacted() {
-        format!("profile metadata ({safe_str})")
+        Msg::ConnProfileMetadata.fill(app.settings.language, &[&safe_str])
     } else {
-        "none".to_string()
+        none.to_string()
     };
-    app.push_message(format!("Connection: {conn_status}"));
+    app.say_with(Msg::StatusConnection, &[&conn_status]);
 
     let run_count = app.iter_runs().count();
     let plan_count = app.iter_plans().count();
     let out_count = list_dirs(&app.paths.out_dir)?.len();
     app.push_raw("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    app.push_message(format!(
-        "Runs: {}  |  Plans: {}  |  Outputs: {}",
-        run_count, plan_count, out_count
-    ));
-    app.push_message(format!(
-        "Active Run: {}",
-        app.settings.active_run_id.as_deref().unwrap_or("none")
-    ));
-    app.push_message(format!(
-        "Active Plan: {}",
-        app.settings.active_plan_id.as_deref().unwrap_or("none")
-    ));
+    let active_run = app.settings.active_run_id.clone();
+    let active_plan = app.settings.active_plan_id.clone();
+    app.say_with(Msg::StatusCounts, &[&run_count, &plan_count, &out_count]);
+    app.say_with(
+        Msg::StatusActiveRun,
+        &[&active_run.as_deref().unwrap_or(none)],
+    );
+    app.say_with(
+        Msg::StatusActivePlan,
+        &[&active_plan.as_deref().unwrap_or(none)],
+    );
     app.push_raw("");
     Ok(())
 }
@@ -236,13 +302,187 @@ fn cmd_init(app: &mut App, bypass_approval: bool, raw: &str) -> Result<(), CliEr
     app.settings = load_or_create_settings(&app.paths)?;
     app.profiles = load_or_create_profiles(&app.paths)?;
     app.llm_models = load_or_create_llm_models(&app.paths)?;
-    app.push_message("workspace initialized.");
+    app.say(Msg::WorkspaceInitialized);
+    Ok(())
+}
+
+/// `/workspace export|import <file.tar.zst>`: share a workspace without its
+/// secrets and logs.
+fn cmd_workspace(
+    app: &mut App,
+    args: Vec<&str>,
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    match args.as_slice() {
+        ["export", file] => {
+            let dest = PathBuf::from(file);
+            if !bypass_approval && app.requires_approval() {
+                let intent = WriteIntent::new("export workspace", vec![dest]);
+                return app.request_approval(intent, raw);
+            }
+            let manifest = export_workspace(&app.paths, &dest)?;
+            app.say_with(
+                Msg::WorkspaceExported,
+                &[
+                    &manifest.files,
+                    &manifest.bytes,
+                    &dest.display(),
+                    &manifest.excluded.join(", "),
+                ],
+            );
+        }
+        ["import", file, rest @ ..] => {
+            let overwrite = rest.contains(&"--overwrite");
+            let src = PathBuf::from(file);
+            if !src.is_file() {
+                app.say_with(Msg::ArchiveNotFound, &[&src.display()]);
+                return Ok(());
+            }
+            if !bypass_approval && app.requires_approval() {
+                let intent = WriteIntent::new("import workspace", vec![app.paths.root.clone()]);
+                return app.request_approval(intent, raw);
+            }
+            let summary = import_workspace(&app.paths, &src, overwrite)?;
+            app.settings = load_or_create_settings(&app.paths)?;
+            app.profiles = load_or_create_profiles(&app.paths)?;
+            app.llm_models = load_or_create_llm_models(&app.paths)?;
+            app.say_with(
+                Msg::WorkspaceImported,
+                &[
+                    &summary.imported,
+                    &src.display(),
+                    &summary.manifest.created_at,
+                    &summary.manifest.cli_version,
+                ],
+            );
+            if let Some(entries) = summary.history_entries {
+                app.say_with(Msg::EvalHistoryMerged, &[&entries]);
+            }
+            if !summary.skipped.is_empty() {
+                app.say_with(Msg::ImportKeptFiles, &[&summary.skipped.len()]);
+                for path in summary.skipped.iter().take(10) {
+                    app.push_message(format!("  {}", path.display()));
+                }
+                if summary.skipped.len() > 10 {
+                    app.say_with(Msg::MoreItems, &[&(summary.skipped.len() - 10)]);
+                }
+            }
+        }
+        ["keygen", rest @ ..] => {
+            let force = rest.contains(&"--force");
+            if app.paths.signing_key_path().exists() && !force {
+                app.say(Msg::SigningKeyExists);
+                return Ok(());
+            }
+            if !bypass_approval && app.requires_approval() {
+                let intent = WriteIntent::new(
+                    "create signing key",
+                    vec![
+                        app.paths.signing_key_path(),
+                        app.paths.signing_public_key_path(),
+                    ],
+                );
+                return app.request_approval(intent, raw);
+            }
+            let public_key = generate_signing_key(&app.paths, force)?;
+            app.say_with(Msg::SigningKeyCreated, &[&public_key]);
+            app.say(Msg::OutputsWillBeSigned);
+        }
+        ["verify", rest @ ..] => cmd_workspace_verify(app, rest)?,
+        _ => app.say_with(
+            Msg::Usage,
+            &[
+                &"/workspace export <file.tar.zst> | import <file.tar.zst> [--overwrite] | keygen [--force] | verify [out_id|dir] [--pubkey <hex>]",
+            ],
+        ),
+    }
+    Ok(())
+}
+
+/// `/workspace verify [out_id|dir] [--pubkey <hex>]`: check checksums and
+/// signatures; without a target, every output of the workspace.
+fn cmd_workspace_verify(app: &mut App, args: &[&str]) -> Result<(), CliError> {
+    let trusted = match extract_flag_value(args, "--pubkey") {
+        Some(key) => Some(key),
+        None => workspace_public_key(&app.paths)?,
+    };
+    let target = args
+        .iter()
+        .enumerate()
+        .find(|(idx, arg)| !arg.starts_with("--") && (*idx == 0 || args[idx - 1] != "--pubkey"))
+        .map(|(_, arg)| *arg);
+    let dirs: Vec<(String, PathBuf)> = match target {
+        Some(target) if app.paths.out_dir.join(target).is_dir() => {
+            vec![(target.to_string(), app.paths.out_dir.join(target))]
+        }
+        Some(target) if Path::new(target).is_dir() => {
+            vec![(target.to_string(), PathBuf::from(target))]
+        }
+        Some(target) => {
+            app.say_with(Msg::OutputNotFoundNamed, &[&target]);
+            return Ok(());
+        }
+        None => list_dirs(&app.paths.out_dir)?
+            .into_iter()
+            .map(|out_id| {
+                let dir = app.paths.out_dir.join(&out_id);
+                (out_id, dir)
+            })
+            .collect(),
+    };
+    if dirs.is_empty() {
+        app.say(Msg::NoOutputs);
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for (name, dir) in dirs {
+        let report = match verify_output(&dir, trusted.as_deref()) {
+            Ok(report) => report,
+            Err(err) => {
+                failed += 1;
+                app.push_message(format!("FAIL  {name}: {err}"));
+                continue;
+            }
+        };
+        let language = app.settings.language;
+        let signature = match &report.signature {
+            SignatureStatus::Unsigned => app.text(Msg::SignatureUnsigned).to_string(),
+            SignatureStatus::Valid {
+                trusted: Some(false),
+                public_key,
+            } => Msg::SignatureUntrusted.fill(language, &[public_key]),
+            SignatureStatus::Valid { .. } => app.text(Msg::SignatureOk).to_string(),
+            SignatureStatus::Invalid(reason) => Msg::SignatureBad.fill(language, &[reason]),
+        };
+        let status = if report.is_ok() { "ok" } else { "FAIL" };
+        if !report.is_ok() {
+            failed += 1;
+        }
+        app.say_with(
+            Msg::VerifyLine,
+            &[&format!("{status:<4}"), &name, &report.files, &signature],
+        );
+        for (label, files) in [
+            (Msg::VerifyModified, &report.modified),
+            (Msg::VerifyMissing, &report.missing),
+            (Msg::VerifyUnexpected, &report.unexpected),
+        ] {
+            for file in files {
+                app.push_message(format!("      {}: {file}", app.text(label)));
+            }
+        }
+    }
+    if failed > 0 {
+        app.say_with(Msg::VerifyFailed, &[&failed]);
+    }
     Ok(())
 }
 
 fn cmd_reset(app: &mut App) -> Result<(), CliError> {
     if !app.paths.root.exists() {
-        app.push_message("workspace not found. run /init.");
+        app.say(Msg::WorkspaceNotFound);
         return Ok(());
     }
     app.messages.clear();
@@ -277,6 +517,14 @@ fn cmd_settings(
             "llm_model:       {}",
             app.settings.llm_model.as_deref().unwrap_or("none")
         ));
+        app.push_message(format!(
+            "llm_base_url:    {}",
+            app.settings.llm_base_url.as_deref().unwrap_or("default")
+        ));
+        app.push_message(format!(
+            "language:        {}",
+            app.settings.language.as_str()
+        ));
         app.push_message(format!(
             "active_profile:  {}",
             app.settings.active_profile.as_deref().unwrap_or("none")
@@ -323,14 +571,22 @@ fn cmd_settings(
         "llm_model" => {
             app.settings.llm_model = Some(value.to_string());
         }
+        "llm_base_url" => {
+            app.settings.llm_base_url = (value != "default").then(|| value.to_string());
+        }
+        "language" => {
+            app.settings.language = Language::parse(value).ok_or_else(|| {
+                CliError::InvalidConfig(format!("invalid language: {value} (en|pt_BR)"))
+            })?;
+        }
         _ => {
-            app.push_message("unknown settings key");
+            app.say(Msg::UnknownSettingsKey);
             return Ok(());
         }
     }
 
     save_settings(&app.paths, &app.settings)?;
-    app.push_message("settings updated.");
+    app.say(Msg::SettingsUpdated);
     Ok(())
 }
 
@@ -347,7 +603,7 @@ fn cmd_profiles(
 
     if args[0] == "list" {
         if app.profiles.profiles.is_empty() {
-            app.push_message("no profiles configured.");
+            app.say(Msg::NoProfiles);
             return Ok(());
         }
         let mut names: Vec<String> = app.profiles.profiles.keys().cloned().collect();
@@ -372,8 +628,8 @@ fn cmd_profiles(
                     PromptContext::new(
                         "/profiles new",
                         vec![
-                            "Profile name:",
-                            "Database connection string (postgres:// or sqlite://):",
+                            app.text(Msg::PromptProfileName),
+                            app.text(Msg::PromptConnection),
                         ],
                     ),
                 );
@@ -394,7 +650,7 @@ fn cmd_profiles(
             app.session_conn = Some(conn.to_string());
             save_profiles(&app.paths, &app.profiles)?;
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("profile created.");
+            app.say(Msg::ProfileCreated);
         }
         "set" => {
             if args.len() < 2 {
@@ -408,12 +664,12 @@ fn cmd_profiles(
             }
             let name = args[1];
             if !app.profiles.profiles.contains_key(name) {
-                app.push_message("profile not found.");
+                app.say(Msg::ProfileNotFound);
                 return Ok(());
             }
             app.settings.active_profile = Some(name.to_string());
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("active profile updated.");
+            app.say(Msg::ActiveProfileUpdated);
         }
         "delete" => {
             if args.len() < 2 {
@@ -434,7 +690,42 @@ fn cmd_profiles(
             }
             save_profiles(&app.paths, &app.profiles)?;
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("profile deleted.");
+            app.say(Msg::ProfileDeleted);
+        }
+        "storage" => {
+            if args.len() < 3 {
+                app.input_set("/profiles storage ".to_string());
+                return Ok(());
+            }
+            if !bypass_approval && app.requires_approval() {
+                let intent =
+                    WriteIntent::new("set profile storage", vec![app.paths.profiles_path()]);
+                return app.request_approval(intent, raw);
+            }
+            let name = args[1];
+            let Some(profile) = app.profiles.profiles.get_mut(name) else {
+                app.say(Msg::ProfileNotFound);
+                return Ok(());
+            };
+            if args[2] == "clear" {
+                profile.object_store = None;
+                save_profiles(&app.paths, &app.profiles)?;
+                app.say(Msg::ProfileStorageCleared);
+                return Ok(());
+            }
+            let target = ObjectStoreTarget {
+                url: args[2].to_string(),
+                endpoint: extract_flag_value(&args, "--endpoint"),
+                region: extract_flag_value(&args, "--region"),
+                allow_http: args.contains(&"--allow-http"),
+            };
+            if let Err(err) = target.parts() {
+                app.push_message(err.to_string());
+                return Ok(());
+            }
+            profile.object_store = Some(target);
+            save_profiles(&app.paths, &app.profiles)?;
+            app.say(Msg::ProfileStorageUpdated);
         }
         _ => {
             app.input_set("/profiles ".to_string());
@@ -451,7 +742,7 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
 
     match args[0] {
         "session" => {
-            app.push_message("Session connection (not saved). Paste database connection string:");
+            app.say(Msg::DbSessionPrompt);
             app.ui_state = UiState::Setup(SetupStep::DbSession);
             app.input_clear();
         }
@@ -482,31 +773,31 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                     app.push_message(format!("Host:     {}", host));
                 }
                 if let Some(db) = redacted.database {
-                    app.push_message(format!("Database: {}", db));
+                    app.say_with(Msg::ConnDatabase, &[&db]);
                 }
                 if let Some(user) = redacted.user {
-                    app.push_message(format!("User:     {}", user));
+                    app.say_with(Msg::ConnUser, &[&user]);
                 }
                 if app.session_conn.is_some() {
-                    app.push_message("Status:   Session (ephemeral)");
+                    app.say(Msg::ConnStatusSession);
                 } else if env_conn.is_some() {
                     app.push_message("Status:   Env (DATABASE_URL)");
                 } else if file_conn.is_some() {
                     app.push_message("Status:   .env (DATABASE_URL)");
                 } else {
-                    app.push_message("Status:   Profile metadata only (use /db session or .env)");
+                    app.say(Msg::ConnStatusProfile);
                 }
             } else {
-                app.push_message("Status:   No connection configured");
+                app.say(Msg::ConnStatusNone);
             }
             app.push_raw("");
         }
         "change" => {
             if app.settings.active_profile.is_none() {
-                app.push_message("missing active profile. use /profiles new or /profiles set.");
+                app.say(Msg::MissingActiveProfile);
                 return Ok(());
             }
-            app.push_message("Update session connection. Paste database connection string:");
+            app.say(Msg::DbChangePrompt);
             app.ui_state = UiState::Setup(SetupStep::DbChange);
             app.input_clear();
         }
@@ -518,7 +809,8 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                     return Ok(());
                 }
             };
-            app.push_message("Testing connectivity...");
+            app.say(Msg::TestingConnectivity);
+            let language = app.settings.language;
             let tx = app.tx.clone();
             let is_sq = is_sqlite(&conn);
             app.runtime.spawn(async move {
@@ -529,11 +821,12 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                         .await
                     {
                         Ok(_) => {
-                            tx.send(AppEvent::Log("Connection successful!".into())).ok();
+                            let message = Msg::ConnectionSuccessful.text(language);
+                            tx.send(AppEvent::Log(message.into())).ok();
                         }
                         Err(e) => {
-                            tx.send(AppEvent::Log(format!("Connection failed: {}", e)))
-                                .ok();
+                            let message = Msg::ConnectionFailed.fill(language, &[&e]);
+                            tx.send(AppEvent::Log(message)).ok();
                         }
                     }
                 } else {
@@ -543,11 +836,12 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                         .await
                     {
                         Ok(_) => {
-                            tx.send(AppEvent::Log("Connection successful!".into())).ok();
+                            let message = Msg::ConnectionSuccessful.text(language);
+                            tx.send(AppEvent::Log(message.into())).ok();
                         }
                         Err(e) => {
-                            tx.send(AppEvent::Log(format!("Connection failed: {}", e)))
-                                .ok();
+                            let message = Msg::ConnectionFailed.fill(language, &[&e]);
+                            tx.send(AppEvent::Log(message)).ok();
                         }
                     };
                 }
@@ -561,7 +855,8 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                     return Ok(());
                 }
             };
-            app.push_message("Fetching info...");
+            app.say(Msg::FetchingInfo);
+            let language = app.settings.language;
             let tx = app.tx.clone();
             let is_sq = is_sqlite(&conn);
             app.runtime.spawn(async move {
@@ -576,17 +871,18 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                             match q.fetch_one(&pool).await {
                                 Ok(row) => {
                                     let ver: String = row.try_get("ver").unwrap_or_default();
-                                    tx.send(AppEvent::Log(format!("Engine: SQLite, Ver: {}", ver)))
-                                        .ok();
+                                    let message = Msg::DbInfoSqlite.fill(language, &[&ver]);
+                                    tx.send(AppEvent::Log(message)).ok();
                                 }
                                 Err(e) => {
-                                    tx.send(AppEvent::Log(format!("Query failed: {}", e))).ok();
+                                    let message = Msg::QueryFailed.fill(language, &[&e]);
+                                    tx.send(AppEvent::Log(message)).ok();
                                 }
                             }
                         }
                         Err(e) => {
-                            tx.send(AppEvent::Log(format!("Connection failed: {}", e)))
-                                .ok();
+                            let message = Msg::ConnectionFailed.fill(language, &[&e]);
+                            tx.send(AppEvent::Log(message)).ok();
                         }
                     }
                 } else {
@@ -606,20 +902,22 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
                                         row.try_get("current_database").unwrap_or_default();
                                     let ver: String = row.try_get("version").unwrap_or_default();
                                     let short_ver = ver.split_whitespace().next().unwrap_or("?");
-                                    tx.send(AppEvent::Log(format!(
-                                        "User: {}, DB: {}, Ver: {}",
-                                        user, db, short_ver
-                                    )))
-                                    .ok();
+                                    let message = Msg::DbInfoPostgres
+                                        .fill(language, &[&user, &db, &short_ver]);
+                                    tx.send(AppEvent::Log(message)).ok();
+                                    for line in table_privilege_lines(&pool, language).await {
+                                        tx.send(AppEvent::Log(line)).ok();
+                                    }
                                 }
                                 Err(e) => {
-                                    tx.send(AppEvent::Log(format!("Query failed: {}", e))).ok();
+                                    let message = Msg::QueryFailed.fill(language, &[&e]);
+                                    tx.send(AppEvent::Log(message)).ok();
                                 }
                             }
                         }
                         Err(e) => {
-                            tx.send(AppEvent::Log(format!("Connection failed: {}", e)))
-                                .ok();
+                            let message = Msg::ConnectionFailed.fill(language, &[&e]);
+                            tx.send(AppEvent::Log(message)).ok();
                         }
                     };
                 }
@@ -632,6 +930,52 @@ fn cmd_db(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
     Ok(())
 }
 
+/// SELECT/INSERT of `current_user` on the user tables, with the tables it
+/// may not read or insert into listed one per line.
+async fn table_privilege_lines(pool: &sqlx::PgPool, language: Language) -> Vec<String> {
+    let rows = sqlx::query(
+        r#"
+        select
+          n.nspname || '.' || c.relname as "table",
+          has_table_privilege(c.oid, 'SELECT') as "can_select",
+          has_table_privilege(c.oid, 'INSERT') as "can_insert"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        where c.relkind in ('r','p')
+          and n.nspname not in ('pg_catalog', 'information_schema')
+          and n.nspname !~ '^pg_toast'
+        order by 1
+        "#,
+    )
+    .fetch_all(pool)
+    .await;
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => return vec![Msg::PrivilegesQueryFailed.fill(language, &[&e])],
+    };
+
+    let mut denied = Vec::new();
+    let (mut select, mut insert) = (0, 0);
+    for row in &rows {
+        let table: String = row.try_get("table").unwrap_or_default();
+        let can_select: bool = row.try_get("can_select").unwrap_or_default();
+        let can_insert: bool = row.try_get("can_insert").unwrap_or_default();
+        select += usize::from(can_select);
+        insert += usize::from(can_insert);
+        match (can_select, can_insert) {
+            (true, true) => {}
+            (true, false) => denied.push(Msg::PrivilegeNoInsert.fill(language, &[&table])),
+            (false, true) => denied.push(Msg::PrivilegeNoSelect.fill(language, &[&table])),
+            (false, false) => {
+                denied.push(Msg::PrivilegeNoSelectInsert.fill(language, &[&table]));
+            }
+        }
+    }
+    let mut lines = vec![Msg::PrivilegesSummary.fill(language, &[&rows.len(), &select, &insert])];
+    lines.extend(denied);
+    lines
+}
+
 fn cmd_introspect(
     app: &mut App,
     args: Vec<&str>,
@@ -639,11 +983,15 @@ fn cmd_introspect(
     raw: &str,
 ) -> Result<(), CliError> {
     if !app.paths.root.exists() {
-        app.push_message("workspace missing. run /init first.");
+        app.say(Msg::WorkspaceMissing);
+        return Ok(());
+    }
+    if let Some((name, _)) = app.running_operation() {
+        app.say_with(Msg::AlreadyRunning, &[&app.text(name)]);
         return Ok(());
     }
     if app.settings.active_profile.is_none() {
-        app.push_message("no active profile. run will be labeled as session.");
+        app.say(Msg::NoActiveProfileSession);
     }
 
     let run_id = extract_flag_value(&args, "--run-id").unwrap_or_else(|| new_artifact_id("run"));
@@ -664,7 +1012,7 @@ fn cmd_introspect(
     }
 
     let options = parse_introspect_options(&args);
-    let strict = args.iter().any(|arg| *arg == "--strict");
+    let strict = args.contains(&"--strict");
     let run_dir = app.paths.runs_dir.join(&run_id);
     std::fs::create_dir_all(&run_dir)?;
 
@@ -689,43 +1037,93 @@ fn cmd_introspect(
             include_indexes: options.include_indexes,
             include_comments: options.include_comments,
             schemas: options.schemas.clone(),
+            include_tables: options
+                .include_tables
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            exclude_tables: options
+                .exclude_tables
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            sample_stats: options.sample_stats,
+            include_privileges: options.include_privileges,
         },
         schema_fingerprint: None,
         artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
         cli_version: crate::workspace::CLI_VERSION.to_string(),
         created_at: Utc::now().to_rfc3339(),
         finished_at: None,
+        tags: Vec::new(),
+        notes: Vec::new(),
     };
     write_json_atomic(&manifest_path, &manifest)?;
 
     let logs_path = run_dir.join("logs.ndjson");
     append_line(&logs_path, "{\"event\":\"run_started\"}")?;
 
-    app.start_task("Introspecting database...");
-    let is_sq = is_sqlite(&conn);
-    let result = app.runtime.block_on(async {
-        if is_sq {
-            let pool = sqlx::sqlite::SqlitePoolOptions::new()
-                .max_connections(5)
-                .acquire_timeout(Duration::from_secs(10))
-                .connect(&conn)
-                .await?;
-            let schema = introspect_sqlite_with_options(&pool, options).await?;
-            Ok::<DatabaseSchema, CliError>(schema)
-        } else {
-            let pool = sqlx::postgres::PgPoolOptions::new()
-                .max_connections(5)
-                .acquire_timeout(Duration::from_secs(10))
-                .connect(&conn)
-                .await?;
-            let schema = introspect_postgres_with_options(&pool, options).await?;
-            Ok::<DatabaseSchema, CliError>(schema)
-        }
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    let tx = app.tx.clone();
+    // Runs off the UI thread; `finish_introspect` picks up the result.
+    app.runtime.spawn(async move {
+        let result = tokio::select! {
+            schema = introspect_connection(&conn, options) => schema.map(Box::new).map(Some),
+            () = cancelled(&flag) => Ok(None),
+        };
+        tx.send(AppEvent::IntrospectDone(result)).ok();
+    });
+
+    app.start_task(app.text(Msg::TaskIntrospecting));
+    app.introspection = Some(IntrospectTask {
+        run_id,
+        run_dir,
+        manifest,
+        strict,
+        cancel,
+        started: Instant::now(),
     });
+    Ok(())
+}
+
+async fn introspect_connection(
+    conn: &str,
+    options: IntrospectOptions,
+) -> Result<DatabaseSchema, CliError> {
+    let adapter = crate::adapters().open(conn, &options).await?;
+    Ok(adapter.introspect(&options).await?)
+}
+
+/// Resolves once `cancel` is set; raced against async work with `select!`.
+async fn cancelled(cancel: &AtomicBool) {
+    while !cancel.load(Ordering::Relaxed) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Record the result of the running `/introspect` in its run directory.
+pub fn finish_introspect(
+    app: &mut App,
+    result: Result<Option<Box<DatabaseSchema>>, CliError>,
+) -> Result<(), CliError> {
     app.finish_task();
+    let Some(task) = app.introspection.take() else {
+        return Ok(());
+    };
+    let IntrospectTask {
+        run_id,
+        run_dir,
+        manifest,
+        strict,
+        started,
+        ..
+    } = task;
+    let manifest_path = run_dir.join("run_manifest.json");
+    let logs_path = run_dir.join("logs.ndjson");
 
     match result {
-        Ok(schema) => {
+        Ok(Some(schema)) => {
             validate_schema(&schema)?;
             let metrics = collect_schema_metrics(&schema);
             write_json_atomic(&run_dir.join("schema.json"), &schema)?;
@@ -736,18 +1134,18 @@ fn cmd_introspect(
                     &logs_path,
                     "{\"event\":\"run_finished\",\"status\":\"ERROR\"}",
                 )?;
-                let mut final_manifest = manifest.clone();
+                let mut final_manifest = manifest;
                 final_manifest.status = ArtifactStatus::Error;
                 final_manifest.finished_at = Some(Utc::now().to_rfc3339());
                 final_manifest.schema_fingerprint = schema.schema_fingerprint.clone();
                 write_json_atomic(&manifest_path, &final_manifest)?;
-                app.push_message("introspect failed: foreign key cycles detected.");
+                app.say(Msg::IntrospectCycles);
                 return Ok(());
             }
 
             append_line(&logs_path, "{\"event\":\"run_finished\",\"status\":\"OK\"}")?;
 
-            let mut final_manifest = manifest.clone();
+            let mut final_manifest = manifest;
             final_manifest.status = ArtifactStatus::Ok;
             final_manifest.finished_at = Some(Utc::now().to_rfc3339());
             final_manifest.schema_fingerprint = schema.schema_fingerprint.clone();
@@ -755,7 +1153,26 @@ fn cmd_introspect(
 
             app.settings.active_run_id = Some(run_id);
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("introspect completed.");
+            let elapsed = format!("{:.1}", started.elapsed().as_secs_f64());
+            app.say_with(Msg::IntrospectCompleted, &[&elapsed]);
+            if !metrics.warnings.is_empty() {
+                app.say_with(Msg::SchemaWarnings, &[&metrics.warnings.len()]);
+                for warning in &metrics.warnings {
+                    app.push_raw(format!("  - {warning}"));
+                }
+            }
+        }
+        Ok(None) => {
+            append_line(
+                &logs_path,
+                "{\"event\":\"run_finished\",\"status\":\"CANCELLED\"}",
+            )?;
+            remove_partial_artifacts(&run_dir, &["schema.json", "metrics.json"])?;
+            let mut final_manifest = manifest;
+            final_manifest.status = ArtifactStatus::Cancelled;
+            final_manifest.finished_at = Some(Utc::now().to_rfc3339());
+            write_json_atomic(&manifest_path, &final_manifest)?;
+            app.say(Msg::IntrospectCancelled);
         }
         Err(err) => {
             append_line(
@@ -766,13 +1183,24 @@ fn cmd_introspect(
             final_manifest.status = ArtifactStatus::Error;
             final_manifest.finished_at = Some(Utc::now().to_rfc3339());
             write_json_atomic(&manifest_path, &final_manifest)?;
-            app.push_message(format!("introspect failed: {err}"));
+            app.say_with(Msg::IntrospectFailed, &[&err]);
         }
     }
 
     Ok(())
 }
 
+/// Remove the files a cancelled operation may have left in `dir`.
+fn remove_partial_artifacts(dir: &Path, names: &[&str]) -> Result<(), CliError> {
+    for name in names {
+        match std::fs::remove_file(dir.join(name)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 fn cmd_runs(
     app: &mut App,
     args: Vec<&str>,
@@ -786,22 +1214,33 @@ fn cmd_runs(
 
     match args[0] {
         "list" => {
+            let filter = annotate::tag_filter(&args);
             let runs = list_dirs(&app.paths.runs_dir)?;
+            let runs = annotate::filter_tagged(app, AnnotatedKind::Run, runs, &filter)?;
             if runs.is_empty() {
-                app.push_message("no runs found.");
+                app.say(Msg::NoRuns);
                 return Ok(());
             }
             app.push_raw("RUNS");
             app.push_raw("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-            for run in runs {
+            for (run, annotations) in runs {
                 let active = app.settings.active_run_id.as_deref() == Some(run.as_str());
-                let label = if active { "active" } else { " " };
-                app.push_message(format!("{label:>6}  {run}"));
+                let label = if active {
+                    app.text(Msg::RunActiveMarker)
+                } else {
+                    " "
+                };
+                app.push_message(format!(
+                    "{label:>6}  {run}{}",
+                    annotate::list_suffix(&annotations)
+                ));
             }
-            app.push_message(format!(
-                "active run: {}",
-                app.settings.active_run_id.as_deref().unwrap_or("none")
-            ));
+            let none = app.text(Msg::NoValue);
+            let active_run = app.settings.active_run_id.clone();
+            app.say_with(
+                Msg::ActiveRunLine,
+                &[&active_run.as_deref().unwrap_or(none)],
+            );
         }
         "set" => {
             if args.len() < 2 {
@@ -815,7 +1254,7 @@ fn cmd_runs(
             let run_id = args[1].to_string();
             app.settings.active_run_id = Some(run_id);
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("active run updated.");
+            app.say(Msg::ActiveRunUpdated);
         }
         "inspect" => {
             if args.len() < 2 {
@@ -825,7 +1264,7 @@ fn cmd_runs(
             let run_id = args[1];
             let manifest_path = app.paths.runs_dir.join(run_id).join("run_manifest.json");
             if !manifest_path.exists() {
-                app.push_message("run_manifest.json not found.");
+                app.say(Msg::RunManifestNotFound);
                 return Ok(());
             }
             let manifest: RunManifest =
@@ -846,6 +1285,13 @@ fn cmd_runs(
                 "finished_at:      {}",
                 manifest.finished_at.as_deref().unwrap_or("running")
             ));
+            annotate::push_details(
+                app,
+                &Annotations {
+                    tags: manifest.tags.clone(),
+                    notes: manifest.notes.clone(),
+                },
+            );
             app.push_raw("options:");
             app.push_message(format!(
                 "  schemas: {:?}",
@@ -855,6 +1301,14 @@ fn cmd_runs(
                     .as_deref()
                     .unwrap_or(&[])
             ));
+            app.push_message(format!(
+                "  include_tables: {:?}",
+                manifest.introspect_options.include_tables
+            ));
+            app.push_message(format!(
+                "  exclude_tables: {:?}",
+                manifest.introspect_options.exclude_tables
+            ));
             app.push_message(format!(
                 "  include_system_schemas: {}",
                 manifest.introspect_options.include_system_schemas
@@ -879,6 +1333,31 @@ fn cmd_runs(
                 "  include_comments: {}",
                 manifest.introspect_options.include_comments
             ));
+            app.push_message(format!(
+                "  sample_stats: {}",
+                manifest.introspect_options.sample_stats
+            ));
+            app.push_message(format!(
+                "  include_privileges: {}",
+                manifest.introspect_options.include_privileges
+            ));
+        }
+        "compare" => {
+            if args.len() < 2 {
+                app.input_set("/runs compare ".to_string());
+                return Ok(());
+            }
+            compare::cmd_runs_compare(app, &args[1..])?;
+        }
+        "tag" | "untag" | "note" => {
+            annotate::cmd_annotate(
+                app,
+                "/runs",
+                AnnotatedKind::Run,
+                &args,
+                bypass_approval,
+                raw,
+            )?;
         }
         "delete" => {
             if args.len() < 2 {
@@ -892,7 +1371,7 @@ fn cmd_runs(
             let run_id = args[1];
             let run_dir = app.paths.runs_dir.join(run_id);
             if !run_dir.exists() {
-                app.push_message("run not found.");
+                app.say(Msg::RunNotFound);
                 return Ok(());
             }
             std::fs::remove_dir_all(&run_dir)?;
@@ -900,7 +1379,7 @@ fn cmd_runs(
                 app.settings.active_run_id = None;
                 save_settings(&app.paths, &app.settings)?;
             }
-            app.push_message("run deleted.");
+            app.say(Msg::RunDeleted);
         }
         _ => {
             app.input_set("/runs ".to_string());
@@ -921,18 +1400,36 @@ fn cmd_plans(
     }
 
     if args[0] == "list" {
+        let filter = annotate::tag_filter(&args);
         let plans = list_dirs(&app.paths.plans_dir)?;
+        let plans = annotate::filter_tagged(app, AnnotatedKind::Plan, plans, &filter)?;
         if plans.is_empty() {
-            app.push_message("no plans found.");
+            app.say(Msg::NoPlans);
             return Ok(());
         }
-        for plan in plans {
+        for (plan, annotations) in plans {
             let active = app.settings.active_plan_id.as_deref() == Some(plan.as_str());
-            app.push_message(format!("{}{}", if active { "* " } else { "  " }, plan));
+            app.push_message(format!(
+                "{}{}{}",
+                if active { "* " } else { "  " },
+                plan,
+                annotate::list_suffix(&annotations)
+            ));
         }
         return Ok(());
     }
 
+    if matches!(args[0], "tag" | "untag" | "note") {
+        return annotate::cmd_annotate(
+            app,
+            "/plans",
+            AnnotatedKind::Plan,
+            &args,
+            bypass_approval,
+            raw,
+        );
+    }
+
     if args[0] == "set" {
         if args.len() < 2 {
             app.input_set("/plans set ".to_string());
@@ -945,7 +1442,7 @@ fn cmd_plans(
         let plan_id = args[1].to_string();
         app.settings.active_plan_id = Some(plan_id);
         save_settings(&app.paths, &app.settings)?;
-        app.push_message("active plan updated.");
+        app.say(Msg::ActivePlanUpdated);
         return Ok(());
     }
 
@@ -968,6 +1465,7 @@ fn cmd_plan(
         "edit" => cmd_plan_edit(app, bypass_approval, raw),
         "show" => cmd_plan_show(app),
         "validate" => cmd_plan_validate(app),
+        "rules" => rules::cmd_plan_rules(app, &args[1..], bypass_approval, raw),
         _ => {
             app.input_set("/plan ".to_string());
             Ok(())
@@ -984,7 +1482,7 @@ fn cmd_plan_new(
     let run_id = match &app.settings.active_run_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active run. use /introspect.");
+            app.say(Msg::MissingActiveRunHint);
             return Ok(());
         }
     };
@@ -999,37 +1497,47 @@ fn cmd_plan_new(
     }
 
     let schema_path = app.paths.runs_dir.join(&run_id).join("schema.json");
-    let schema = read_schema(&schema_path)?;
-
-    let plan_dir = app.paths.plans_dir.join(&plan_id);
-    std::fs::create_dir_all(&plan_dir)?;
-
-    app.start_task("Generating smart plan...");
-    let plan = smart_plan(&schema);
-    app.finish_task();
-
-    let plan_json = serde_json::to_vec_pretty(&plan)?;
-    write_bytes_atomic(&plan_dir.join("plan.json"), &plan_json)?;
+    let tables: Vec<String> = extract_flag_value(&args, "--tables")
+        .map(|list| list.split(',').map(str::to_string).collect())
+        .unwrap_or_default();
+    let schema = subset_schema(
+        read_schema(&schema_path)?,
+        &tables,
+        args.contains(&"--with-children"),
+    )?;
 
-    let meta = PlanMeta {
-        plan_id: plan_id.clone(),
-        status: ArtifactStatus::Ok,
-        schema_run_id: run_id,
-        schema_fingerprint: schema.schema_fingerprint.clone(),
-        provider: "heuristic".to_string(),
-        model: "smart_plan".to_string(),
-        mock: false,
-        artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
-        cli_version: crate::workspace::CLI_VERSION.to_string(),
-        created_at: Utc::now().to_rfc3339(),
-        finished_at: Some(Utc::now().to_rfc3339()),
+    let planner = planner_model(&app.settings).filter(|_| !args.contains(&"--heuristic"));
+    let plan = if let Some((provider, model)) = planner {
+        app.start_task(Msg::TaskPlanningLlm.fill(app.settings.language, &[&model]));
+        let result = llm::client_for(
+            &provider,
+            app.settings.llm_base_url.as_deref(),
+            &app.session_llm_keys,
+        )
+        .map_err(CliError::from)
+        .and_then(|client| {
+            write_llm_plan(
+                &app.paths,
+                &plan_id,
+                &run_id,
+                &schema,
+                client.as_ref(),
+                (provider.as_str(), &model),
+            )
+        });
+        app.finish_task();
+        let (plan, transcript) = result?;
+        app.say_with(
+            Msg::LlmPlannerSummary,
+            &[&transcript.summary(), &plan_id, &TRANSCRIPT_FILE],
+        );
+        plan
+    } else {
+        app.start_task(app.text(Msg::TaskSmartPlan));
+        let plan = write_smart_plan(&app.paths, &plan_id, &run_id, &schema);
+        app.finish_task();
+        plan?
     };
-    write_json_atomic(&plan_dir.join("plan.meta.json"), &meta)?;
-
-    write_bytes_atomic(
-        &plan_dir.join("prompt.txt"),
-        b"smart plan generated by heuristic engine",
-    )?;
 
     // Count assigned generators for feedback
     let gen_count = plan.rules.len();
@@ -1037,10 +1545,88 @@ fn cmd_plan_new(
 
     app.settings.active_plan_id = Some(plan_id);
     save_settings(&app.paths, &app.settings)?;
-    app.push_message(format!(
-        "plan created: {table_count} tables, {gen_count} generator rules, {} rows total.",
-        plan.targets.iter().map(|t| t.rows).sum::<u64>()
-    ));
+    app.say_with(
+        Msg::PlanCreated,
+        &[
+            &table_count,
+            &gen_count,
+            &plan.targets.iter().map(|t| t.rows).sum::<u64>(),
+        ],
+    );
+    Ok(())
+}
+
+fn cmd_classify(
+    app: &mut App,
+    args: Vec<&str>,
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    let run_id = match &app.settings.active_run_id {
+        Some(id) => id.clone(),
+        None => {
+            app.say(Msg::MissingActiveRunHint);
+            return Ok(());
+        }
+    };
+    if args.first() == Some(&"show") {
+        return cmd_classify_show(app, &run_id);
+    }
+
+    let path = app.paths.runs_dir.join(&run_id).join(CLASSIFICATION_FILE);
+    if !bypass_approval && app.requires_approval() {
+        let intent = WriteIntent::new("classify schema columns", vec![path]);
+        return app.request_approval(intent, raw);
+    }
+    let schema = read_schema(&app.paths.runs_dir.join(&run_id).join("schema.json"))?;
+
+    let refiner = planner_model(&app.settings).filter(|_| !args.contains(&"--heuristic"));
+    let classification = if let Some((provider, model)) = refiner {
+        app.start_task(Msg::TaskClassifying.fill(app.settings.language, &[&model]));
+        let result = llm::client_for(
+            &provider,
+            app.settings.llm_base_url.as_deref(),
+            &app.session_llm_keys,
+        )
+        .map_err(CliError::from)
+        .and_then(|client| {
+            write_classification(
+                &app.paths,
+                &run_id,
+                &schema,
+                Some((client.as_ref(), (provider.as_str(), &model))),
+            )
+        });
+        app.finish_task();
+        result?
+    } else {
+        write_classification(&app.paths, &run_id, &schema, None)?
+    };
+    app.say_with(
+        Msg::ClassificationSummary,
+        &[&classification.summary(), &run_id, &CLASSIFICATION_FILE],
+    );
+    Ok(())
+}
+
+fn cmd_classify_show(app: &mut App, run_id: &str) -> Result<(), CliError> {
+    let Some(classification) = read_classification(&app.paths, run_id)? else {
+        app.say(Msg::RunNotClassified);
+        return Ok(());
+    };
+    app.say_with(Msg::ClassificationOf, &[&run_id]);
+    for class in &classification.columns {
+        let pii = class.pii.map(|category| category.as_str()).unwrap_or("-");
+        let source = match class.source {
+            ClassSource::Heuristic => "",
+            ClassSource::Llm => " (llm)",
+        };
+        app.push_raw(format!(
+            "  {:<40} {:<14} {pii}{source}",
+            class.path(),
+            class.semantic_type.as_str()
+        ));
+    }
     Ok(())
 }
 
@@ -1048,14 +1634,14 @@ fn cmd_plan_show(app: &mut App) -> Result<(), CliError> {
     let plan_id = match &app.settings.active_plan_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active plan. use /plan new.");
+            app.say(Msg::MissingActivePlanHint);
             return Ok(());
         }
     };
 
     let plan_path = app.paths.plans_dir.join(&plan_id).join("plan.json");
     if !plan_path.exists() {
-        app.push_message("plan.json not found.");
+        app.say(Msg::PlanJsonNotFound);
         return Ok(());
     }
 
@@ -1067,18 +1653,18 @@ fn cmd_plan_show(app: &mut App) -> Result<(), CliError> {
     app.push_message(format!("plan_id:      {}", plan_id));
     app.push_message(format!("plan_version: {}", plan.plan_version));
     app.push_message(format!("seed:         {}", plan.seed));
-    if let Some(global) = &plan.global {
-        if let Some(locale) = &global.locale {
-            app.push_message(format!("locale:       {}", locale));
-        }
+    if let Some(global) = &plan.global
+        && let Some(locale) = &global.locale
+    {
+        app.push_message(format!("locale:       {}", locale));
     }
     app.push_raw("");
     app.push_raw("TARGETS:");
     for target in &plan.targets {
-        app.push_message(format!(
-            "  {}.{}: {} rows",
-            target.schema, target.table, target.rows
-        ));
+        app.say_with(
+            Msg::PlanTargetRows,
+            &[&target.schema, &target.table, &target.rows],
+        );
     }
     app.push_raw("");
     app.push_raw(format!("RULES: {} total", plan.rules.len()));
@@ -1105,6 +1691,15 @@ fn cmd_plan_show(app: &mut App) -> Result<(), CliError> {
                     fk.schema, fk.table, fk.mode
                 ));
             }
+            Rule::FixedWidth(fw) => {
+                app.say_with(
+                    Msg::PlanFixedWidthRule,
+                    &[&fw.schema, &fw.table, &fw.columns.len()],
+                );
+            }
+            Rule::OutputPartition(op) => {
+                app.say_with(Msg::PlanPartitionRule, &[&op.schema, &op.table, &op.column]);
+            }
         }
     }
     if !plan.rules_unsupported.is_empty() {
@@ -1121,12 +1716,12 @@ fn cmd_plan_edit(app: &mut App, bypass_approval: bool, raw: &str) -> Result<(),
     let plan_id = match &app.settings.active_plan_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active plan. use /plan new.");
+            app.say(Msg::MissingActivePlanHint);
             return Ok(());
         }
     };
     if matches!(app.settings.mode, WorkspaceMode::Insert) {
-        app.push_message("insert mode not implemented (planned).");
+        app.say(Msg::InsertModeNotImplemented);
         return Ok(());
     }
 
@@ -1140,15 +1735,15 @@ fn cmd_plan_edit(app: &mut App, bypass_approval: bool, raw: &str) -> Result<(),
 
     let plan_path = app.paths.plans_dir.join(&plan_id).join("plan.json");
     if !plan_path.exists() {
-        app.push_message("plan.json not found.");
+        app.say(Msg::PlanJsonNotFound);
         return Ok(());
     }
 
     match open_in_editor(&plan_path) {
-        Ok(()) => app.push_message("plan edited. run /plan validate."),
+        Ok(()) => app.say(Msg::PlanEdited),
         Err(_) => {
-            app.push_message("could not open editor. set $EDITOR or $VISUAL env var.");
-            app.push_message(format!("file: {}", plan_path.display()));
+            app.say(Msg::EditorFailed);
+            app.say_with(Msg::FileLine, &[&plan_path.display()]);
         }
     }
     Ok(())
@@ -1158,14 +1753,14 @@ fn cmd_plan_validate(app: &mut App) -> Result<(), CliError> {
     let plan_id = match &app.settings.active_plan_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active plan.");
+            app.say(Msg::MissingActivePlan);
             return Ok(());
         }
     };
     let run_id = match &app.settings.active_run_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active run.");
+            app.say(Msg::MissingActiveRun);
             return Ok(());
         }
     };
@@ -1173,38 +1768,34 @@ fn cmd_plan_validate(app: &mut App) -> Result<(), CliError> {
     let plan_path = app.paths.plans_dir.join(&plan_id).join("plan.json");
     let schema_path = app.paths.runs_dir.join(&run_id).join("schema.json");
     if !plan_path.exists() {
-        app.push_message("plan.json not found.");
+        app.say(Msg::PlanJsonNotFound);
         return Ok(());
     }
     if !schema_path.exists() {
-        app.push_message("schema.json not found.");
+        app.say(Msg::SchemaJsonNotFound);
         return Ok(());
     }
 
     let plan_json: Value = serde_json::from_str(&std::fs::read_to_string(&plan_path)?)?;
     let schema = read_schema(&schema_path)?;
-
-    let plan_schema = serde_json::to_value(datalchemy_plan::plan_json_schema())?;
-    let mut report = validate_plan_json(&plan_json, &plan_schema)
-        .map_err(|err| CliError::Plan(err.to_string()))?;
-    let schema_report = validate_plan_against_schema(&parse_plan(&plan_json)?, &schema);
-    report.merge(schema_report);
+    let report = validation_report(&plan_json, &schema)?;
+    let staleness = plan_staleness(&app.paths, &plan_id, &run_id, &plan_json, &schema)?;
 
     if report.is_ok() {
-        app.push_message("plan validation ok.");
+        app.say(Msg::PlanValidationOk);
     } else {
         for issue in report.errors {
-            app.push_message(format!(
-                "error: {} {} ({})",
-                issue.code, issue.path, issue.message
-            ));
+            app.say_with(Msg::IssueError, &[&issue.code, &issue.path, &issue.message]);
         }
     }
     for warning in report.warnings {
-        app.push_message(format!(
-            "warning: {} {} ({})",
-            warning.code, warning.path, warning.message
-        ));
+        app.say_with(
+            Msg::IssueWarning,
+            &[&warning.code, &warning.path, &warning.message],
+        );
+    }
+    for line in staleness.iter().flat_map(|staleness| staleness.lines()) {
+        app.push_message(line);
     }
     Ok(())
 }
@@ -1215,20 +1806,48 @@ fn cmd_generate(
     bypass_approval: bool,
     raw: &str,
 ) -> Result<(), CliError> {
+    if let Some((name, _)) = app.running_operation() {
+        app.say_with(Msg::AlreadyRunning, &[&app.text(name)]);
+        return Ok(());
+    }
     let run_id = match &app.settings.active_run_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active run. use /introspect.");
+            app.say(Msg::MissingActiveRunHint);
             return Ok(());
         }
     };
     let plan_id = match &app.settings.active_plan_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active plan. use /plan new.");
+            app.say(Msg::MissingActivePlanHint);
+            return Ok(());
+        }
+    };
+
+    let format = match extract_flag_value(&args, "--format") {
+        Some(value) => match value.parse::<OutputFormat>() {
+            Ok(format) => format,
+            Err(err) => {
+                let supported: Vec<&str> = OutputFormat::all().iter().map(|f| f.as_str()).collect();
+                app.say_with(Msg::UnsupportedFormat, &[&err, &supported.join(", ")]);
+                return Ok(());
+            }
+        },
+        None => OutputFormat::default(),
+    };
+    let kafka = match kafka_target(&args) {
+        Ok(target) => target,
+        Err(message) => {
+            app.push_message(message);
             return Ok(());
         }
     };
+    let mode = if kafka.is_some() {
+        "kafka"
+    } else {
+        format.as_str()
+    };
 
     let out_id = extract_flag_value(&args, "--out-id").unwrap_or_else(|| new_artifact_id("out"));
     if !bypass_approval && app.requires_approval() {
@@ -1239,7 +1858,7 @@ fn cmd_generate(
     let schema_path = app.paths.runs_dir.join(&run_id).join("schema.json");
     let plan_path = app.paths.plans_dir.join(&plan_id).join("plan.json");
     if !schema_path.exists() || !plan_path.exists() {
-        app.push_message("schema or plan not found");
+        app.say(Msg::SchemaOrPlanNotFound);
         return Ok(());
     }
 
@@ -1250,6 +1869,21 @@ fn cmd_generate(
         .map_err(|_| CliError::Plan("plan validation failed".to_string()))?;
     let plan = validated.plan;
 
+    if matches!(app.settings.mode, WorkspaceMode::Insert) {
+        let denied = schema.tables_without_insert(
+            plan.targets
+                .iter()
+                .map(|target| (target.schema.as_str(), target.table.as_str())),
+        );
+        if !denied.is_empty() {
+            let role = schema.current_role.as_deref().unwrap_or_default();
+            for table in denied {
+                app.say_with(Msg::InsertDenied, &[&role, &table]);
+            }
+            return Ok(());
+        }
+    }
+
     let final_dir = app.paths.out_dir.join(&out_id);
     if final_dir.exists() {
         return Err(CliError::InvalidConfig(format!(
@@ -1259,33 +1893,82 @@ fn cmd_generate(
     }
     std::fs::create_dir_all(&final_dir)?;
 
-    let mut manifest = OutManifest {
+    let manifest = OutManifest {
         out_id: out_id.clone(),
         status: ArtifactStatus::Running,
         schema_run_id: run_id,
         plan_id,
-        mode: "csv".to_string(),
+        mode: mode.to_string(),
         seed: plan.seed,
         scale: plan.targets.iter().map(|t| t.rows).sum(),
         artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
         cli_version: crate::workspace::CLI_VERSION.to_string(),
         created_at: Utc::now().to_rfc3339(),
         finished_at: None,
+        tags: Vec::new(),
+        notes: Vec::new(),
     };
     let manifest_path = final_dir.join("out_manifest.json");
     write_json_atomic(&manifest_path, &manifest)?;
 
     let options = GenerateOptions {
         out_dir: app.paths.out_dir.clone(),
+        format,
         ..GenerateOptions::default()
     };
-    let engine = GenerationEngine::new(options);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let progress_tx = app.tx.clone();
+    let engine = GenerationEngine::new(options)
+        .with_progress(move |event| {
+            progress_tx.send(AppEvent::GenerationProgress(event)).ok();
+        })
+        .with_cancel(Arc::clone(&cancel));
+
+    // Runs off the UI thread; `finish_generation` picks up the result.
+    let tx = app.tx.clone();
+    app.runtime.spawn_blocking(move || {
+        let result = match kafka {
+            Some(target) => run_kafka(&engine, &schema, &plan, target),
+            None => engine.run(&schema, &plan).map_err(Box::new),
+        };
+        tx.send(AppEvent::GenerationDone(result.map(Box::new))).ok();
+    });
+
+    app.start_task(Msg::TaskGenerating.fill(app.settings.language, &[&mode]));
+    app.say_with(Msg::GenerationStarted, &[&out_id]);
+    app.generation = Some(GenerationTask {
+        out_id,
+        mode: mode.to_string(),
+        final_dir,
+        manifest,
+        top_slowest: extract_flag_value(&args, "--top-slowest"),
+        cancel,
+        started: Instant::now(),
+        tables: Vec::new(),
+    });
+    Ok(())
+}
 
-    app.start_task("Generating CSV data...");
-    let gen_result = engine.run(&schema, &plan);
+/// Record the result of the running `/generate` in its output directory.
+pub fn finish_generation(
+    app: &mut App,
+    result: Result<Box<GenerationResult>, Box<GenerationError>>,
+) -> Result<(), CliError> {
     app.finish_task();
+    let Some(task) = app.generation.take() else {
+        return Ok(());
+    };
+    let GenerationTask {
+        out_id,
+        final_dir,
+        mut manifest,
+        top_slowest,
+        started,
+        ..
+    } = task;
+    let manifest_path = final_dir.join("out_manifest.json");
 
-    match gen_result {
+    match result {
         Ok(result) => {
             move_dir_contents(&result.run_dir, &final_dir)?;
             write_json_atomic(&final_dir.join("generation_report.json"), &result.report)?;
@@ -1293,95 +1976,311 @@ fn cmd_generate(
             manifest.status = ArtifactStatus::Ok;
             manifest.finished_at = Some(Utc::now().to_rfc3339());
             write_json_atomic(&manifest_path, &manifest)?;
+            let sealed = seal_output(&app.paths, &final_dir)?;
+            let elapsed = format!("{:.1}", started.elapsed().as_secs_f64());
+            app.say_with(Msg::GenerationCompleted, &[&elapsed]);
+            if sealed.signed {
+                app.say_with(Msg::SignedChecksums, &[&sealed.files]);
+            }
+            if let Some(count) = top_slowest {
+                match count.parse::<usize>() {
+                    Ok(count) => {
+                        for line in slowest_table_lines(&result.report, count) {
+                            app.push_message(format!("  {line}"));
+                        }
+                    }
+                    Err(_) => app.say_with(Msg::InvalidFlagValue, &[&"--top-slowest", &count]),
+                }
+            }
+            upload_output(app, &final_dir, &out_id);
             app.last_out_id = Some(out_id);
-            app.push_message("generation completed.");
         }
         Err(err) => {
-            manifest.status = ArtifactStatus::Error;
+            let cancelled = matches!(*err, GenerationError::Cancelled);
+            manifest.status = if cancelled {
+                ArtifactStatus::Cancelled
+            } else {
+                ArtifactStatus::Error
+            };
             manifest.finished_at = Some(Utc::now().to_rfc3339());
             write_json_atomic(&manifest_path, &manifest)?;
-            app.push_message(format!("generation failed: {err}"));
+            if cancelled {
+                app.say(Msg::GenerationCancelled);
+            } else {
+                app.say_with(Msg::GenerationFailed, &[&err]);
+            }
         }
     }
     Ok(())
 }
 
-fn cmd_out(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
-    if args.is_empty() {
-        app.input_set("/out ".to_string());
-        return Ok(());
+/// Kafka target from `--kafka <broker[,broker]>` and its companion flags.
+fn kafka_target(args: &[&str]) -> Result<Option<KafkaTarget>, String> {
+    let Some(brokers) = extract_flag_value(args, "--kafka") else {
+        return Ok(None);
+    };
+    let mut target = KafkaTarget {
+        brokers: brokers
+            .split(',')
+            .map(str::trim)
+            .filter(|broker| !broker.is_empty())
+            .map(str::to_string)
+            .collect(),
+        topic_prefix: extract_flag_value(args, "--topic-prefix").unwrap_or_default(),
+        ..KafkaTarget::default()
+    };
+    if target.brokers.is_empty() {
+        return Err("usage: /generate --kafka <host:port[,host:port]>".to_string());
+    }
+    if let Some(encoding) = extract_flag_value(args, "--kafka-encoding") {
+        target.encoding = encoding.parse()?;
+    }
+    if let Some(rate) = extract_flag_value(args, "--kafka-rate") {
+        target.rate_per_sec = Some(
+            rate.parse()
+                .map_err(|_| format!("invalid --kafka-rate '{rate}' (messages per second)"))?,
+        );
+    }
+    if let Some(partitions) = extract_flag_value(args, "--kafka-partitions") {
+        target.partitions = partitions
+            .parse()
+            .ok()
+            .filter(|partitions: &i32| *partitions > 0)
+            .ok_or_else(|| format!("invalid --kafka-partitions '{partitions}'"))?;
+    }
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if *arg != "--kafka-key" {
+            continue;
+        }
+        let spec = iter.next().copied().unwrap_or_default();
+        let Some((table, column)) = spec.split_once('=') else {
+            return Err(format!(
+                "invalid --kafka-key '{spec}' (use schema.table=column)"
+            ));
+        };
+        target
+            .key_columns
+            .insert(table.to_string(), column.to_string());
+    }
+    Ok(Some(target))
+}
+
+#[cfg(feature = "kafka")]
+fn run_kafka(
+    engine: &GenerationEngine,
+    schema: &DatabaseSchema,
+    plan: &Plan,
+    target: KafkaTarget,
+) -> Result<GenerationResult, Box<GenerationError>> {
+    let mut sink = datalchemy_generate::KafkaSink::connect(target)?;
+    Ok(engine.run_with_sink(schema, plan, &mut sink)?)
+}
+
+#[cfg(not(feature = "kafka"))]
+fn run_kafka(
+    _engine: &GenerationEngine,
+    _schema: &DatabaseSchema,
+    _plan: &Plan,
+    _target: KafkaTarget,
+) -> Result<GenerationResult, Box<GenerationError>> {
+    Err(Box::new(GenerationError::Unsupported(
+        "kafka output requires datalchemy-cli built with the `kafka` feature".to_string(),
+    )))
+}
+
+/// Upload a finished output to the active profile's bucket, when configured.
+fn upload_output(app: &mut App, final_dir: &Path, out_id: &str) {
+    let Some(target) = app
+        .settings
+        .active_profile
+        .as_ref()
+        .and_then(|name| app.profiles.profiles.get(name))
+        .and_then(|profile| profile.object_store.clone())
+    else {
+        return;
+    };
+
+    #[cfg(feature = "object-store")]
+    {
+        app.start_task(Msg::TaskUploading.fill(app.settings.language, &[&target.url]));
+        let result = app
+            .runtime
+            .block_on(datalchemy_generate::upload::upload_dir(
+                final_dir,
+                &target,
+                &app.session_storage,
+                out_id,
+            ));
+        app.finish_task();
+        match result {
+            Ok(uploaded) => app.say_with(Msg::Uploaded, &[&uploaded.len(), &target.url]),
+            Err(err) => app.say_with(Msg::UploadFailed, &[&err]),
+        }
+    }
+    #[cfg(not(feature = "object-store"))]
+    {
+        let _ = (final_dir, out_id);
+        app.say_with(Msg::UploadSkipped, &[&target.url]);
+    }
+}
+
+fn cmd_out(
+    app: &mut App,
+    args: Vec<&str>,
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    if args.is_empty() {
+        app.input_set("/out ".to_string());
+        return Ok(());
     }
 
     if args[0] == "list" {
+        let filter = annotate::tag_filter(&args);
         let outs = list_dirs(&app.paths.out_dir)?;
+        let outs = annotate::filter_tagged(app, AnnotatedKind::Out, outs, &filter)?;
         if outs.is_empty() {
-            app.push_message("no outputs found.");
+            app.say(Msg::NoOutputs);
         }
-        for out in outs {
+        for (out, annotations) in outs {
             let active = app.last_out_id.as_deref() == Some(out.as_str());
-            app.push_message(format!("{}{}", if active { "* " } else { "  " }, out));
+            app.push_message(format!(
+                "{}{}{}",
+                if active { "* " } else { "  " },
+                out,
+                annotate::list_suffix(&annotations)
+            ));
         }
         return Ok(());
     }
 
+    if matches!(args[0], "tag" | "untag" | "note") {
+        return annotate::cmd_annotate(
+            app,
+            "/out",
+            AnnotatedKind::Out,
+            &args,
+            bypass_approval,
+            raw,
+        );
+    }
+
     if args[0] == "preview" {
-        let out_id = if args.len() > 1 {
-            args[1].to_string()
+        let rows = match extract_flag_value(&args, "--rows") {
+            Some(value) => match value.parse::<usize>() {
+                Ok(rows) if rows > 0 => rows,
+                _ => {
+                    app.say_with(Msg::InvalidFlagValue, &[&"--rows", &value]);
+                    return Ok(());
+                }
+            },
+            None => PREVIEW_ROWS,
+        };
+        let named = args[1..]
+            .iter()
+            .enumerate()
+            // `args[idx]` is the argument before `arg`.
+            .find(|(idx, arg)| !arg.starts_with("--") && args[*idx] != "--rows")
+            .map(|(_, arg)| arg.to_string());
+        let out_id = if let Some(out_id) = named {
+            out_id
         } else if let Some(last) = &app.last_out_id {
             last.clone()
         } else {
-            app.push_message("no outputs found. run /generate first.");
+            app.say(Msg::NoOutputsHint);
             return Ok(());
         };
 
         let path = app.paths.out_dir.join(&out_id);
         if !path.exists() {
-            app.push_message("output not found.");
+            app.say(Msg::OutputNotFound);
             return Ok(());
         }
 
         let entries = list_preview_files(&path)?;
-        let csv_files: Vec<&String> = entries.iter().filter(|e| e.ends_with(".csv")).collect();
+        let mut tables = Vec::new();
+        for csv_file in entries.iter().filter(|e| e.ends_with(".csv")) {
+            match csv_preview(&path.join(csv_file), rows) {
+                Ok(table) => tables.push(table),
+                Err(e) => app.say_with(Msg::CsvReadError, &[csv_file, &e]),
+            }
+        }
 
-        if csv_files.is_empty() {
-            app.push_message("no CSV files found in output.");
+        if tables.is_empty() {
+            app.say(Msg::NoCsvInOutput);
             for entry in &entries {
                 app.push_message(format!("  {entry}"));
             }
             return Ok(());
         }
 
-        app.push_raw(format!("OUTPUT PREVIEW: {out_id}"));
-        app.push_raw("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        app.say_with(Msg::PreviewingCsv, &[&tables.len(), &out_id, &rows]);
+        app.ui_state = UiState::Preview(PreviewState::new(out_id, tables));
+        return Ok(());
+    }
 
-        for csv_file in &csv_files {
-            let csv_path = path.join(csv_file);
-            app.push_raw(format!("── {} ──", csv_file));
-            match csv_preview(&csv_path, 5) {
-                Ok(lines) => {
-                    for line in lines {
-                        app.push_raw(line);
-                    }
-                }
-                Err(e) => {
-                    app.push_message(format!("  error reading: {e}"));
-                }
-            }
-            app.push_raw("");
-        }
+    if args[0] == "dbt" {
+        return cmd_out_dbt(app, &args[1..], bypass_approval, raw);
+    }
 
-        // Also list non-csv files
-        let other: Vec<&String> = entries.iter().filter(|e| !e.ends_with(".csv")).collect();
-        if !other.is_empty() {
-            app.push_raw("other files:");
-            for entry in other {
-                app.push_message(format!("  {entry}"));
-            }
+    app.input_set("/out ".to_string());
+    Ok(())
+}
+
+/// `/out dbt <project_dir> [out_id]`: copy a CSV output into a dbt project's seeds.
+fn cmd_out_dbt(
+    app: &mut App,
+    args: &[&str],
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    let Some(project_dir) = args.first().map(PathBuf::from) else {
+        app.say_with(Msg::Usage, &[&"/out dbt <dbt_project_dir> [out_id]"]);
+        return Ok(());
+    };
+    let out_id = match args
+        .get(1)
+        .map(|id| id.to_string())
+        .or(app.last_out_id.clone())
+    {
+        Some(id) => id,
+        None => {
+            app.say(Msg::NoOutputsHint);
+            return Ok(());
         }
+    };
+    let out_dir = app.paths.out_dir.join(&out_id);
+    let manifest_path = out_dir.join("out_manifest.json");
+    if !manifest_path.exists() {
+        app.say(Msg::OutputNotFound);
         return Ok(());
     }
 
-    app.input_set("/out ".to_string());
+    if !bypass_approval && app.requires_approval() {
+        let intent = WriteIntent::new("export dbt seeds", vec![project_dir.join("seeds")]);
+        return app.request_approval(intent, raw);
+    }
+
+    let manifest: OutManifest = serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)?;
+    let schema = read_schema(
+        &app.paths
+            .runs_dir
+            .join(&manifest.schema_run_id)
+            .join("schema.json"),
+    )?;
+    let report: GenerationReport = serde_json::from_str(&std::fs::read_to_string(
+        out_dir.join("generation_report.json"),
+    )?)?;
+
+    let seeds = export_dbt_seeds(&out_dir, &project_dir, &schema, &report)?;
+    for seed in &seeds {
+        app.say_with(Msg::DbtSeedLine, &[&seed.name, &seed.rows, &seed.path]);
+    }
+    app.say_with(
+        Msg::DbtSeedsExported,
+        &[&seeds.len(), &project_dir.join("seeds").display()],
+    );
     Ok(())
 }
 
@@ -1391,14 +2290,24 @@ fn cmd_eval(
     bypass_approval: bool,
     raw: &str,
 ) -> Result<(), CliError> {
+    if args.first() == Some(&"diff") {
+        return cmd_eval_diff(app, &args[1..]);
+    }
+    if let Some((name, _)) = app.running_operation() {
+        app.say_with(Msg::AlreadyRunning, &[&app.text(name)]);
+        return Ok(());
+    }
+    let dataset = extract_flag_value(&args, "--dataset").map(PathBuf::from);
     let out_id = if let Some(id) = extract_flag_value(&args, "--out-id") {
         id
-    } else if !args.is_empty() {
-        args[0].to_string()
+    } else if let Some(dir) = &dataset {
+        dir.display().to_string()
+    } else if let Some(id) = args.first().filter(|arg| !arg.starts_with("--")) {
+        id.to_string()
     } else if let Some(last) = &app.last_out_id {
         last.clone()
     } else {
-        app.push_message("missing out_id. use /out to list.");
+        app.say(Msg::MissingOutId);
         return Ok(());
     };
 
@@ -1411,49 +2320,142 @@ fn cmd_eval(
     let run_id = match &app.settings.active_run_id {
         Some(id) => id.clone(),
         None => {
-            app.push_message("missing active run.");
-            return Ok(());
-        }
-    };
-    let plan_id = match &app.settings.active_plan_id {
-        Some(id) => id.clone(),
-        None => {
-            app.push_message("missing active plan.");
+            app.say(Msg::MissingActiveRun);
             return Ok(());
         }
     };
-
     let schema_path = app.paths.runs_dir.join(&run_id).join("schema.json");
-    let plan_path = app.paths.plans_dir.join(&plan_id).join("plan.json");
-    if !schema_path.exists() || !plan_path.exists() {
-        app.push_message("schema or plan not found");
+    if !schema_path.exists() {
+        app.say(Msg::SchemaOrPlanNotFound);
         return Ok(());
     }
-
     let schema = read_schema(&schema_path)?;
-    let plan_json: Value = serde_json::from_str(&std::fs::read_to_string(&plan_path)?)?;
-    let plan = parse_plan(&plan_json)?;
+
+    // External datasets (`--dataset <dir>`) are checked against the schema only.
+    let plan = if dataset.is_some() {
+        None
+    } else {
+        let plan_id = match &app.settings.active_plan_id {
+            Some(id) => id.clone(),
+            None => {
+                app.say(Msg::MissingActivePlan);
+                return Ok(());
+            }
+        };
+        let plan_path = app.paths.plans_dir.join(&plan_id).join("plan.json");
+        if !plan_path.exists() {
+            app.say(Msg::SchemaOrPlanNotFound);
+            return Ok(());
+        }
+        let plan_json: Value = serde_json::from_str(&std::fs::read_to_string(&plan_path)?)?;
+        Some(parse_plan(&plan_json)?)
+    };
 
     let eval_dir = app.paths.eval_dir.join(&eval_id);
     std::fs::create_dir_all(&eval_dir)?;
 
-    let mut options = EvaluateOptions::default();
-    options.out_dir = Some(eval_dir.clone());
-    let engine = EvaluationEngine::new(options);
-    let dataset_dir = app.paths.out_dir.join(&out_id);
+    // `--live [--live-rows <n>]`: sample the source database (PII-named
+    // columns are not read) and score realism against it.
+    let live = if args.contains(&"--live") {
+        let conn = match app.resolve_connection_string() {
+            Ok(value) => value,
+            Err(message) => {
+                app.push_message(message);
+                return Ok(());
+            }
+        };
+        let sample_rows = extract_flag_value(&args, "--live-rows")
+            .and_then(|rows| rows.parse().ok())
+            .unwrap_or(crate::live_sample::DEFAULT_SAMPLE_ROWS);
+        Some((conn, sample_rows))
+    } else {
+        None
+    };
+
+    let options = EvaluateOptions {
+        out_dir: Some(eval_dir.clone()),
+        strict: dataset.is_none(),
+        file_patterns: extract_flag_value(&args, "--pattern")
+            .map(|patterns| {
+                patterns
+                    .split(',')
+                    .map(|pattern| pattern.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        // `--reference <out_id|dir>`: a previous output of the workspace or any directory.
+        reference_dir: extract_flag_value(&args, "--reference").map(|reference| {
+            let out_dir = app.paths.out_dir.join(&reference);
+            if out_dir.is_dir() {
+                out_dir
+            } else {
+                PathBuf::from(reference)
+            }
+        }),
+        // `--no-pii-scan` disables the scan; `--allow-domain a.com,b.com` widens it.
+        pii_scan: !args.contains(&"--no-pii-scan"),
+        temporal_checks: !args.contains(&"--no-temporal"),
+        allowed_email_domains: extract_flag_value(&args, "--allow-domain")
+            .map(|domains| {
+                domains
+                    .split(',')
+                    .map(|domain| domain.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        privacy: match extract_flag_value(&args, "--privacy") {
+            Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+            None => Vec::new(),
+        },
+        thresholds: match extract_flag_value(&args, "--thresholds") {
+            Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+            None => Default::default(),
+        },
+        // `--streaming`: constraint checks only, bounded memory.
+        streaming: args.contains(&"--streaming"),
+        // `--sample <rows>`: reservoir sample per table, failures extrapolated.
+        sample_rows: extract_flag_value(&args, "--sample").and_then(|rows| rows.parse().ok()),
+        pii_columns: read_classification(&app.paths, &run_id)?
+            .map(|classification| classification.pii_columns())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    let mut checks_enabled = vec![
+        "fk_consistency".to_string(),
+        "nullability".to_string(),
+        "uniqueness".to_string(),
+    ];
+    if !options.privacy.is_empty() {
+        checks_enabled.push("privacy".to_string());
+    }
+    if options.pii_scan {
+        checks_enabled.push("pii_scan".to_string());
+    }
+    if options.temporal_checks {
+        checks_enabled.push("temporal".to_string());
+    }
+    if !options.thresholds.is_empty() {
+        checks_enabled.push("thresholds".to_string());
+    }
+    if options.streaming {
+        checks_enabled.push("streaming".to_string());
+    }
+    if options.sample_rows.is_some() {
+        checks_enabled.push("sampled".to_string());
+    }
+    if live.is_some() {
+        checks_enabled.push("realism".to_string());
+    }
+    let dataset_dir = dataset.unwrap_or_else(|| app.paths.out_dir.join(&out_id));
     if !dataset_dir.exists() {
-        app.push_message("dataset not found for eval.");
+        app.say(Msg::DatasetNotFound);
         return Ok(());
     }
-    let mut manifest = crate::workspace::EvalManifest {
+    let manifest = crate::workspace::EvalManifest {
         eval_id: eval_id.clone(),
         status: ArtifactStatus::Running,
         out_id: out_id.clone(),
-        checks_enabled: vec![
-            "fk_consistency".to_string(),
-            "nullability".to_string(),
-            "uniqueness".to_string(),
-        ],
+        checks_enabled,
         artifact_version: crate::workspace::ARTIFACT_VERSION.to_string(),
         cli_version: crate::workspace::CLI_VERSION.to_string(),
         created_at: Utc::now().to_rfc3339(),
@@ -1462,25 +2464,204 @@ fn cmd_eval(
     let manifest_path = eval_dir.join("eval_manifest.json");
     write_json_atomic(&manifest_path, &manifest)?;
 
-    app.start_task("Evaluating dataset...");
-    let eval_result = engine.run(&schema, &plan, &dataset_dir);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    let sample_path = eval_dir.join("source_sample.json");
+    let tx = app.tx.clone();
+    // Runs off the UI thread; `finish_eval` picks up the result.
+    app.runtime.spawn(async move {
+        let mut options = options;
+        if let Some((conn, sample_rows)) = live {
+            let sampling = sample_source(&conn, &schema, plan.as_ref(), sample_rows);
+            let sample = tokio::select! {
+                sample = sampling => sample,
+                () = cancelled(&flag) => Err(EvalError::Cancelled.into()),
+            };
+            let sample = sample.and_then(|sample| {
+                write_json_atomic(&sample_path, &sample)?;
+                Ok(sample_path)
+            });
+            match sample {
+                Ok(path) => options.source_sample = Some(path),
+                Err(err) => {
+                    tx.send(AppEvent::EvalDone(Err(err))).ok();
+                    return;
+                }
+            }
+        }
+        let engine = EvaluationEngine::new(options).with_cancel(flag);
+        let result = tokio::task::spawn_blocking(move || match &plan {
+            Some(plan) => engine.run(&schema, plan, &dataset_dir),
+            None => engine.run_dataset(&schema, &dataset_dir),
+        })
+        .await
+        .map_err(|err| CliError::Runtime(err.to_string()))
+        .and_then(|result| Ok(Box::new(result?)));
+        tx.send(AppEvent::EvalDone(result)).ok();
+    });
+
+    app.start_task(app.text(Msg::TaskEvaluating));
+    app.evaluation = Some(EvalTask {
+        eval_dir,
+        manifest,
+        cancel,
+        started: Instant::now(),
+    });
+    Ok(())
+}
+
+/// Record the result of the running `/eval` in its eval directory.
+pub fn finish_eval(
+    app: &mut App,
+    result: Result<Box<EvaluationResult>, CliError>,
+) -> Result<(), CliError> {
     app.finish_task();
+    let Some(task) = app.evaluation.take() else {
+        return Ok(());
+    };
+    let EvalTask {
+        eval_dir,
+        mut manifest,
+        started,
+        ..
+    } = task;
+    let manifest_path = eval_dir.join("eval_manifest.json");
 
-    match eval_result {
+    match result {
         Ok(result) => {
             write_json_atomic(&eval_dir.join("evaluation_report.json"), &result.metrics)?;
             app.write_profile_config(&eval_dir)?;
             manifest.status = ArtifactStatus::Ok;
             manifest.finished_at = Some(Utc::now().to_rfc3339());
             write_json_atomic(&manifest_path, &manifest)?;
-            app.push_message("evaluation completed.");
+            let entry = HistoryEntry::new(
+                manifest.eval_id.clone(),
+                Some(manifest.out_id.clone()),
+                manifest.finished_at.clone().unwrap_or_default(),
+                result.metrics,
+            );
+            if let Err(err) = append_history(&app.paths.eval_dir.join(HISTORY_FILE), &entry) {
+                app.say_with(Msg::EvalHistoryNotUpdated, &[&err]);
+            }
+            let elapsed = format!("{:.1}", started.elapsed().as_secs_f64());
+            app.say_with(Msg::EvaluationCompleted, &[&elapsed]);
+        }
+        Err(CliError::Evaluation(EvalError::Cancelled)) => {
+            remove_partial_artifacts(&eval_dir, &["source_sample.json"])?;
+            manifest.status = ArtifactStatus::Cancelled;
+            manifest.finished_at = Some(Utc::now().to_rfc3339());
+            write_json_atomic(&manifest_path, &manifest)?;
+            app.say(Msg::EvaluationCancelled);
         }
         Err(err) => {
             manifest.status = ArtifactStatus::Error;
             manifest.finished_at = Some(Utc::now().to_rfc3339());
             write_json_atomic(&manifest_path, &manifest)?;
-            app.push_message(format!("evaluation failed: {err}"));
+            app.say_with(Msg::EvaluationFailed, &[&err]);
+        }
+    }
+    Ok(())
+}
+
+/// `/eval diff [<a> <b>]`: compare two evaluations (before -> after); the
+/// last two of the eval history without arguments.
+fn cmd_eval_diff(app: &mut App, args: &[&str]) -> Result<(), CliError> {
+    if args.is_empty() {
+        let history = read_history(&app.paths.eval_dir.join(HISTORY_FILE))?;
+        let [.., before, after] = history.as_slice() else {
+            app.say(Msg::EvalHistoryTooShort);
+            return Ok(());
+        };
+        app.push_message(format!("{} -> {}", before.eval_id, after.eval_id));
+        let diff = before.metrics.diff(&after.metrics);
+        for line in render_diff(&diff).lines() {
+            app.push_raw(line);
+        }
+        return Ok(());
+    }
+    let [before, after] = args else {
+        app.say_with(
+            Msg::Usage,
+            &[&"/eval diff [<out_a|eval_id> <out_b|eval_id>]"],
+        );
+        return Ok(());
+    };
+    let mut reports = Vec::new();
+    for target in [before, after] {
+        let Some(path) = find_eval_metrics(app, target)? else {
+            app.say_with(Msg::EvalNotFoundFor, &[target]);
+            return Ok(());
+        };
+        let metrics: MetricsReport = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        reports.push(metrics);
+    }
+
+    let diff = reports[0].diff(&reports[1]);
+    for line in render_diff(&diff).lines() {
+        app.push_raw(line);
+    }
+    Ok(())
+}
+
+/// `metrics.json` of an eval id, of the latest successful evaluation of an
+/// out id, or at a path.
+fn find_eval_metrics(app: &App, target: &str) -> Result<Option<PathBuf>, CliError> {
+    let eval_metrics = app.paths.eval_dir.join(target).join("metrics.json");
+    if eval_metrics.is_file() {
+        return Ok(Some(eval_metrics));
+    }
+
+    let mut latest: Option<(String, PathBuf)> = None;
+    for eval_id in list_dirs(&app.paths.eval_dir)? {
+        let eval_dir = app.paths.eval_dir.join(&eval_id);
+        let Ok(contents) = std::fs::read_to_string(eval_dir.join("eval_manifest.json")) else {
+            continue;
+        };
+        let Ok(manifest) = serde_json::from_str::<crate::workspace::EvalManifest>(&contents) else {
+            continue;
+        };
+        let metrics = eval_dir.join("metrics.json");
+        if manifest.out_id != target || !metrics.is_file() {
+            continue;
+        }
+        if latest
+            .as_ref()
+            .is_none_or(|(created_at, _)| manifest.created_at > *created_at)
+        {
+            latest = Some((manifest.created_at, metrics));
+        }
+    }
+    if let Some((_, metrics)) = latest {
+        return Ok(Some(metrics));
+    }
+
+    let path = PathBuf::from(target);
+    if path.is_dir() && path.join("metrics.json").is_file() {
+        return Ok(Some(path.join("metrics.json")));
+    }
+    Ok(path.is_file().then_some(path))
+}
+
+/// `/explain [<code>]`: the code list, or causes and fixes of one code.
+fn cmd_explain(app: &mut App, code: Option<&str>) -> Result<(), CliError> {
+    let language = app.settings.language;
+    let Some(code) = code else {
+        for explanation in explain::EXPLANATIONS {
+            app.push_raw(format!(
+                "  {}  {}",
+                explanation.code,
+                explanation.text(language).title
+            ));
+        }
+        return Ok(());
+    };
+    match explain::explanation(code) {
+        Some(explanation) => {
+            for line in explanation.lines(language) {
+                app.push_raw(line);
+            }
         }
+        None => app.say_with(Msg::UnknownErrorCode, &[&code]),
     }
     Ok(())
 }
@@ -1488,15 +2669,15 @@ fn cmd_eval(
 fn cmd_doctor(app: &mut App) -> Result<(), CliError> {
     let report = run_doctor(&app.paths, &app.settings, &app.profiles)?;
     if report.issues.is_empty() {
-        app.push_message("doctor: no issues found.");
+        app.say(Msg::DoctorOk);
         return Ok(());
     }
 
     for issue in report.issues {
-        let level = match issue.level {
-            DoctorLevel::Warning => "warn",
-            DoctorLevel::Error => "error",
-        };
+        let level = app.text(match issue.level {
+            DoctorLevel::Warning => Msg::LevelWarn,
+            DoctorLevel::Error => Msg::LevelError,
+        });
         if let Some(hint) = issue.hint {
             app.push_message(format!("{level}: {} ({hint})", issue.message));
         } else {
@@ -1513,7 +2694,7 @@ fn cmd_logs(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
         app.paths.runs_dir.join(args[0]).join("logs.ndjson")
     };
     if !path.exists() {
-        app.push_message("log not found.");
+        app.say(Msg::LogNotFound);
         return Ok(());
     }
     let lines = read_tail_lines(&path, 50)?;
@@ -1525,12 +2706,13 @@ fn cmd_logs(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
 
 fn cmd_open(app: &mut App, args: Vec<&str>) -> Result<(), CliError> {
     if args.is_empty() {
-        start_prompt(app, PromptContext::new("/open", vec!["File path:"]));
+        let prompt = app.text(Msg::PromptFilePath);
+        start_prompt(app, PromptContext::new("/open", vec![prompt]));
         return Ok(());
     }
     let path = PathBuf::from(args[0]);
     if !path.exists() {
-        app.push_message("file not found.");
+        app.say(Msg::FileNotFound);
         return Ok(());
     }
     let lines = read_head_lines(&path, 80)?;
@@ -1555,71 +2737,128 @@ fn cmd_secrets(
         "status" => {
             let meta_path = app.paths.vault_meta_path();
             if !meta_path.exists() {
-                app.push_message("vault: absent");
+                app.say_with(Msg::VaultStatus, &[&"absent"]);
                 return Ok(());
             }
             let meta: VaultMeta = serde_json::from_str(&std::fs::read_to_string(meta_path)?)?;
-            app.push_message(format!("vault: {}", meta.status));
+            app.say_with(Msg::VaultStatus, &[&meta.status]);
         }
         "import-env" => {
             let env_path = PathBuf::from(".env");
             if !env_path.exists() {
-                app.push_message(".env not found.");
+                app.say(Msg::EnvFileNotFound);
                 return Ok(());
             }
             let loaded = load_env_file(&env_path)?;
             if let Some(value) = loaded.get("DATABASE_URL") {
                 app.session_conn = Some(value.clone());
             }
-            app.push_message("env loaded into session.");
+            app.session_storage.extend(storage_credentials(&loaded));
+            for provider in KEYED_PROVIDERS {
+                if let Some(key) = provider.key_env().and_then(|env| loaded.get(env)) {
+                    app.session_llm_keys
+                        .insert(provider.as_str().to_string(), key.clone());
+                }
+            }
+            app.say(Msg::EnvLoaded);
+        }
+        "llm-key" => {
+            let [_, provider, key] = args.as_slice() else {
+                app.say_with(
+                    Msg::Usage,
+                    &[&"/secrets llm-key <gemini|openai|anthropic> <api_key>"],
+                );
+                return Ok(());
+            };
+            let provider = parse_llm_provider(provider)?;
+            if provider.key_env().is_none() {
+                app.say_with(Msg::ProviderWithoutKey, &[&provider.as_str()]);
+                return Ok(());
+            }
+            app.session_llm_keys
+                .insert(provider.as_str().to_string(), key.to_string());
+            app.say_with(Msg::LlmKeyLoaded, &[&provider.as_str()]);
         }
         "store-session" => {
             if args.len() < 2 {
+                let prompt = app.text(Msg::PromptPassphrase);
                 start_prompt(
                     app,
-                    PromptContext::new("/secrets store-session", vec!["Passphrase:"]),
+                    PromptContext::new("/secrets store-session", vec![prompt]),
                 );
                 return Ok(());
             }
             if !bypass_approval && app.requires_approval() {
                 let intent = WriteIntent::new(
                     "store session secrets",
-                    vec![app.paths.vault_db_path(), app.paths.vault_meta_path()],
+                    vec![
+                        app.paths.vault_db_path(),
+                        app.paths.vault_storage_path(),
+                        app.paths.secrets_dir.join("llm_*.enc"),
+                        app.paths.vault_meta_path(),
+                    ],
                 );
                 return app.request_approval(intent, raw);
             }
-            let Some(conn) = &app.session_conn else {
-                app.push_message("no session connection to store.");
+            if app.session_conn.is_none()
+                && app.session_storage.is_empty()
+                && app.session_llm_keys.is_empty()
+            {
+                app.say(Msg::NoSessionSecrets);
                 return Ok(());
-            };
+            }
             let passphrase = args[1];
-            encrypt_to_file(&app.paths.vault_db_path(), passphrase, conn)?;
+            if let Some(conn) = &app.session_conn {
+                encrypt_to_file(&app.paths.vault_db_path(), passphrase, conn)?;
+            }
+            if !app.session_storage.is_empty() {
+                encrypt_to_file(
+                    &app.paths.vault_storage_path(),
+                    passphrase,
+                    &format_env(&app.session_storage),
+                )?;
+            }
+            for (provider, key) in &app.session_llm_keys {
+                encrypt_to_file(&app.paths.vault_llm_path(provider), passphrase, key)?;
+            }
             let meta = VaultMeta {
                 status: "locked".to_string(),
                 created_at: Some(Utc::now().to_rfc3339()),
             };
             write_json_atomic(&app.paths.vault_meta_path(), &meta)?;
             set_private_permissions(&app.paths.vault_meta_path())?;
-            app.push_message("vault stored (locked).");
+            app.say(Msg::VaultStored);
         }
         "unlock" => {
             if args.len() < 2 {
-                start_prompt(
-                    app,
-                    PromptContext::new("/secrets unlock", vec!["Passphrase:"]),
-                );
+                let prompt = app.text(Msg::PromptPassphrase);
+                start_prompt(app, PromptContext::new("/secrets unlock", vec![prompt]));
                 return Ok(());
             }
             let passphrase = args[1];
-            let conn = decrypt_from_file(&app.paths.vault_db_path(), passphrase)?;
-            app.session_conn = Some(conn);
+            if app.paths.vault_db_path().exists() {
+                let conn = decrypt_from_file(&app.paths.vault_db_path(), passphrase)?;
+                app.session_conn = Some(conn);
+            }
+            if app.paths.vault_storage_path().exists() {
+                let storage = decrypt_from_file(&app.paths.vault_storage_path(), passphrase)?;
+                app.session_storage = parse_env(&storage);
+            }
+            for provider in KEYED_PROVIDERS {
+                let path = app.paths.vault_llm_path(provider.as_str());
+                if path.exists() {
+                    let key = decrypt_from_file(&path, passphrase)?;
+                    app.session_llm_keys
+                        .insert(provider.as_str().to_string(), key.trim().to_string());
+                }
+            }
             let meta = VaultMeta {
                 status: "unlocked".to_string(),
                 created_at: Some(Utc::now().to_rfc3339()),
             };
             write_json_atomic(&app.paths.vault_meta_path(), &meta)?;
             set_private_permissions(&app.paths.vault_meta_path())?;
-            app.push_message("vault unlocked for this session.");
+            app.say(Msg::VaultUnlocked);
         }
         "delete" => {
             if !bypass_approval && app.requires_approval() {
@@ -1627,7 +2866,8 @@ fn cmd_secrets(
                     "delete vault secrets",
                     vec![
                         app.paths.vault_db_path(),
-                        app.paths.vault_llm_path(),
+                        app.paths.secrets_dir.join("llm_*.enc"),
+                        app.paths.vault_storage_path(),
                         app.paths.vault_meta_path(),
                     ],
                 );
@@ -1636,16 +2876,24 @@ fn cmd_secrets(
             if app.paths.vault_db_path().exists() {
                 std::fs::remove_file(app.paths.vault_db_path())?;
             }
-            if app.paths.vault_llm_path().exists() {
-                std::fs::remove_file(app.paths.vault_llm_path())?;
+            if app.paths.vault_storage_path().exists() {
+                std::fs::remove_file(app.paths.vault_storage_path())?;
+            }
+            app.session_storage.clear();
+            for provider in KEYED_PROVIDERS {
+                let path = app.paths.vault_llm_path(provider.as_str());
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
             }
+            app.session_llm_keys.clear();
             let meta = VaultMeta {
                 status: "absent".to_string(),
                 created_at: Some(Utc::now().to_rfc3339()),
             };
             write_json_atomic(&app.paths.vault_meta_path(), &meta)?;
             set_private_permissions(&app.paths.vault_meta_path())?;
-            app.push_message("vault deleted.");
+            app.say(Msg::VaultDeleted);
         }
         _ => {
             app.input_set("/secrets ".to_string());
@@ -1667,17 +2915,78 @@ fn cmd_llm(
 
     match args[0] {
         "status" => {
+            let provider = &app.settings.llm_provider;
+            let key = match provider.key_env() {
+                None => "not needed",
+                Some(_) if app.session_llm_keys.contains_key(provider.as_str()) => "session",
+                Some(env) if std::env::var(env).is_ok() => env,
+                Some(_) => "missing",
+            };
             app.push_message(format!(
-                "llm: enabled={} provider={:?} model={}",
+                "llm: enabled={} provider={} model={} base_url={} key={key}",
                 app.settings.llm_enabled,
-                app.settings.llm_provider,
-                app.settings.llm_model.as_deref().unwrap_or("none")
+                provider.as_str(),
+                app.settings.llm_model.as_deref().unwrap_or("none"),
+                app.settings.llm_base_url.as_deref().unwrap_or("default"),
             ));
         }
         "models" => {
-            let models = app.llm_models.models.clone();
-            for model in models {
-                app.push_message(model);
+            let provider = match args.get(1) {
+                Some(name) => parse_llm_provider(name)?,
+                None => app.settings.llm_provider.clone(),
+            };
+            // The base URL belongs to the configured provider.
+            let base_url = (provider == app.settings.llm_provider)
+                .then(|| app.settings.llm_base_url.clone())
+                .flatten();
+            app.start_task(
+                Msg::TaskListingModels.fill(app.settings.language, &[&provider.as_str()]),
+            );
+            let listed = llm::client_for(&provider, base_url.as_deref(), &app.session_llm_keys)
+                .and_then(|client| client.list_models());
+            app.finish_task();
+            match listed {
+                Ok(models) if models.is_empty() => {
+                    app.say_with(Msg::NoModels, &[&provider.as_str()]);
+                }
+                Ok(models) => {
+                    app.say_with(Msg::ModelsOf, &[&provider.as_str()]);
+                    for model in models {
+                        let active = app.settings.llm_model.as_deref() == Some(model.as_str())
+                            && provider == app.settings.llm_provider;
+                        app.push_message(format!("{}{model}", if active { "* " } else { "  " }));
+                    }
+                }
+                Err(err) => {
+                    app.say_with(Msg::ModelsListFailed, &[&err]);
+                    app.say(Msg::ModelsFromConfig);
+                    let models = app.llm_models.models.clone();
+                    for model in models {
+                        app.push_message(format!("  {model}"));
+                    }
+                }
+            }
+        }
+        "test" => {
+            let Some(model) = app.settings.llm_model.clone() else {
+                app.say(Msg::NoLlmModel);
+                return Ok(());
+            };
+            app.start_task(Msg::TaskAskingModel.fill(app.settings.language, &[&model]));
+            let answer = llm::client_for(
+                &app.settings.llm_provider,
+                app.settings.llm_base_url.as_deref(),
+                &app.session_llm_keys,
+            )
+            .and_then(|client| {
+                let mut request = CompletionRequest::new(model, "Reply with the single word: pong");
+                request.max_tokens = 16;
+                client.complete(&request)
+            });
+            app.finish_task();
+            match answer {
+                Ok(answer) => app.say_with(Msg::LlmAnswered, &[&answer.trim()]),
+                Err(err) => app.say_with(Msg::LlmTestFailed, &[&err]),
             }
         }
         "off" => {
@@ -1688,27 +2997,33 @@ fn cmd_llm(
             app.settings.llm_enabled = false;
             app.settings.llm_provider = LlmProvider::Off;
             app.settings.llm_model = None;
+            app.settings.llm_base_url = None;
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("llm disabled.");
+            app.say(Msg::LlmDisabled);
         }
         "set" => {
             if args.len() < 3 {
                 start_prompt(
                     app,
-                    PromptContext::new("/llm set", vec!["Provider (gemini):", "Model name:"]),
+                    PromptContext::new(
+                        "/llm set",
+                        vec![app.text(Msg::PromptProvider), app.text(Msg::PromptModel)],
+                    ),
                 );
                 return Ok(());
             }
+            let provider = parse_llm_provider(args[1])?;
             if !bypass_approval && app.requires_approval() {
                 let intent =
                     WriteIntent::new("update llm settings", vec![app.paths.settings_path()]);
                 return app.request_approval(intent, raw);
             }
-            app.settings.llm_enabled = true;
-            app.settings.llm_provider = parse_llm_provider(args[1])?;
+            app.settings.llm_enabled = provider != LlmProvider::Off;
+            app.settings.llm_provider = provider;
             app.settings.llm_model = Some(args[2].to_string());
+            app.settings.llm_base_url = extract_flag_value(&args, "--base-url");
             save_settings(&app.paths, &app.settings)?;
-            app.push_message("llm settings updated.");
+            app.say(Msg::LlmSettingsUpdated);
         }
         _ => {
             app.input_set("/llm ".to_string());
@@ -1751,6 +3066,9 @@ fn parse_privacy_mode(value: &str) -> Result<PrivacyMode, CliError> {
 fn parse_llm_provider(value: &str) -> Result<LlmProvider, CliError> {
     match value {
         "gemini" => Ok(LlmProvider::Gemini),
+        "openai" => Ok(LlmProvider::OpenAi),
+        "anthropic" => Ok(LlmProvider::Anthropic),
+        "ollama" => Ok(LlmProvider::Ollama),
         "off" => Ok(LlmProvider::Off),
         _ => Err(CliError::InvalidConfig(format!(
             "invalid llm_provider: {value}"
@@ -1767,6 +3085,9 @@ pub fn parse_introspect_options(args: &[&str]) -> IntrospectOptions {
         include_indexes: true,
         include_comments: false,
         schemas: None,
+        sample_stats: false,
+        include_privileges: false,
+        ..IntrospectOptions::default()
     };
 
     let mut schemas = Vec::new();
@@ -1779,11 +3100,23 @@ pub fn parse_introspect_options(args: &[&str]) -> IntrospectOptions {
             "--include-foreign-tables" => options.include_foreign_tables = true,
             "--include-indexes" => options.include_indexes = true,
             "--include-comments" => options.include_comments = true,
+            "--sample-stats" => options.sample_stats = true,
+            "--include-privileges" => options.include_privileges = true,
             "--schema" => {
                 if let Some(schema) = iter.next() {
                     schemas.push(schema.to_string());
                 }
             }
+            "--include-table" | "--exclude-table" => {
+                let Some(pattern) = iter.next().and_then(|raw| raw.parse().ok()) else {
+                    continue;
+                };
+                if arg == "--include-table" {
+                    options.include_tables.push(pattern);
+                } else {
+                    options.exclude_tables.push(pattern);
+                }
+            }
             _ => {}
         }
     }
@@ -1804,253 +3137,6 @@ fn parse_plan(plan_json: &Value) -> Result<Plan, CliError> {
     serde_json::from_value(plan_json.clone()).map_err(|err| CliError::Plan(err.to_string()))
 }
 
-fn provider_label(settings: &WorkspaceSettings) -> String {
-    match settings.llm_provider {
-        LlmProvider::Gemini => "gemini".to_string(),
-        LlmProvider::Off => "off".to_string(),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// Smart plan generation (heuristic-based, no LLM)
-// ---------------------------------------------------------------------------
-
-/// Generate a smart plan by analyzing column names and types from the schema.
-/// Uses heuristic matching to assign appropriate faker-rs generators.
-fn smart_plan(schema: &DatabaseSchema) -> Plan {
-    let mut targets = Vec::new();
-    let mut rules = Vec::new();
-
-    for db_schema in &schema.schemas {
-        for table in &db_schema.tables {
-            targets.push(Target {
-                schema: db_schema.name.clone(),
-                table: table.name.clone(),
-                rows: 50,
-                strategy: None,
-            });
-
-            for column in &table.columns {
-                // Skip identity/generated columns
-                if column.identity.is_some() || column.generated.is_some() {
-                    continue;
-                }
-                // Skip columns with serial-like defaults (sequences)
-                if let Some(ref def) = column.default {
-                    let lower = def.to_lowercase();
-                    if lower.contains("nextval(") || lower.contains("gen_random_uuid") {
-                        continue;
-                    }
-                }
-
-                if let Some(gen_id) = guess_generator(&column.name, &column.column_type) {
-                    rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
-                        schema: db_schema.name.clone(),
-                        table: table.name.clone(),
-                        column: column.name.clone(),
-                        generator: GeneratorRef::Id(gen_id),
-                        params: None,
-                        transforms: Vec::new(),
-                    }));
-                }
-            }
-        }
-    }
-
-    Plan {
-        plan_version: PLAN_VERSION.to_string(),
-        seed: 42,
-        schema_ref: SchemaRef {
-            schema_version: schema.schema_version.clone(),
-            schema_fingerprint: schema.schema_fingerprint.clone(),
-            engine: schema.engine.clone(),
-        },
-        global: Some(PlanGlobal {
-            locale: Some("pt_BR".to_string()),
-        }),
-        targets,
-        rules,
-        rules_unsupported: Vec::new(),
-        options: None,
-    }
-}
-
-/// Heuristic generator mapping based on column name patterns and SQL types.
-fn guess_generator(col_name: &str, col_type: &datalchemy_core::ColumnType) -> Option<String> {
-    let name = col_name.to_lowercase();
-    let udt = col_type.udt_name.to_lowercase();
-    let dtype = col_type.data_type.to_lowercase();
-
-    // --- UUID type ---
-    if udt == "uuid" {
-        return Some("primitive.uuid".to_string());
-    }
-
-    // --- Boolean type ---
-    if udt == "bool" || dtype.contains("boolean") {
-        return Some("primitive.bool".to_string());
-    }
-
-    // --- Name-based heuristics (check before generic type fallback) ---
-
-    // Email
-    if name.contains("email") || name.contains("e_mail") {
-        return Some("semantic.person.email".to_string());
-    }
-
-    // Phone / telefone
-    if name.contains("phone")
-        || name.contains("telefone")
-        || name.contains("celular")
-        || name.contains("fone")
-    {
-        return Some("faker.phone_number.raw.PhoneNumber".to_string());
-    }
-
-    // Person name patterns
-    if name == "nome"
-        || name == "name"
-        || name == "nome_completo"
-        || name == "full_name"
-        || name == "fullname"
-    {
-        return Some("faker.name.raw.Name".to_string());
-    }
-    if name == "primeiro_nome" || name == "first_name" || name == "firstname" {
-        return Some("faker.name.raw.FirstName".to_string());
-    }
-    if name == "sobrenome" || name == "last_name" || name == "lastname" || name == "ultimo_nome" {
-        return Some("faker.name.raw.LastName".to_string());
-    }
-
-    // Company
-    if name.contains("empresa")
-        || name.contains("company")
-        || name == "razao_social"
-        || name == "nome_fantasia"
-    {
-        return Some("faker.company.raw.CompanyName".to_string());
-    }
-
-    // Address
-    if name.contains("endereco") || name.contains("address") || name == "logradouro" {
-        return Some("faker.address.raw.StreetName".to_string());
-    }
-    if name == "cidade" || name == "city" {
-        return Some("faker.address.raw.CityName".to_string());
-    }
-    if name == "estado" || name == "state" || name == "uf" {
-        return Some("faker.address.raw.StateName".to_string());
-    }
-    if name == "cep"
-        || name == "zip"
-        || name == "zipcode"
-        || name == "zip_code"
-        || name == "codigo_postal"
-    {
-        return Some("faker.address.raw.ZipCode".to_string());
-    }
-    if name == "pais" || name == "country" {
-        return Some("faker.address.raw.CountryName".to_string());
-    }
-
-    // URL / website
-    if name.contains("url") || name.contains("website") || name.contains("site") {
-        return Some("faker.internet.raw.DomainSuffix".to_string());
-    }
-
-    // Description / text
-    if name.contains("descricao")
-        || name.contains("description")
-        || name.contains("observacao")
-        || name.contains("obs")
-        || name.contains("notas")
-        || name.contains("notes")
-        || name.contains("comentario")
-    {
-        return Some("faker.lorem.raw.Sentence".to_string());
-    }
-
-    // Title / titulo
-    if name == "titulo" || name == "title" || name == "assunto" || name == "subject" {
-        return Some("faker.lorem.raw.Words".to_string());
-    }
-
-    // Monetary / valor
-    if name.contains("valor")
-        || name.contains("preco")
-        || name.contains("price")
-        || name.contains("amount")
-        || name.contains("custo")
-        || name.contains("cost")
-        || name.contains("salario")
-        || name.contains("salary")
-    {
-        return Some("primitive.float".to_string());
-    }
-
-    // Quantity / count
-    if name.contains("quantidade")
-        || name.contains("qty")
-        || name.contains("quantity")
-        || name.contains("count")
-        || name.contains("total")
-    {
-        return Some("primitive.int".to_string());
-    }
-
-    // Percentage
-    if name.contains("percentual") || name.contains("percent") || name.contains("taxa") {
-        return Some("primitive.float".to_string());
-    }
-
-    // Status / tipo / category (enum-like text)
-    if name == "status"
-        || name == "tipo"
-        || name == "type"
-        || name == "categoria"
-        || name == "category"
-    {
-        // For enum-like, let the generator engine use fallback
-        return None;
-    }
-
-    // --- Type-based fallback ---
-
-    // Timestamps
-    if udt.contains("timestamp") || dtype.contains("timestamp") {
-        return Some("primitive.timestamp".to_string());
-    }
-    if udt == "date" || dtype == "date" {
-        return Some("primitive.date".to_string());
-    }
-    if udt == "time" || dtype.contains("time without") {
-        return Some("primitive.time".to_string());
-    }
-
-    // Numeric types
-    if udt == "int2" || udt == "int4" || udt == "int8" || udt == "serial" || udt == "bigserial" {
-        return Some("primitive.int".to_string());
-    }
-    if udt == "float4" || udt == "float8" || udt == "numeric" || udt == "decimal" || udt == "money"
-    {
-        return Some("primitive.float".to_string());
-    }
-
-    // JSON
-    if udt == "json" || udt == "jsonb" {
-        return Some("primitive.json".to_string());
-    }
-
-    // Text: only if it's a non-trivial text column
-    if udt == "text" || udt == "varchar" || udt.starts_with("varchar") || udt == "bpchar" {
-        // Generic text columns get a lorem generator
-        return Some("faker.lorem.raw.Word".to_string());
-    }
-
-    None
-}
-
 // ---------------------------------------------------------------------------
 // Command log sanitization
 // ---------------------------------------------------------------------------
@@ -2061,25 +3147,29 @@ pub fn sanitize_command_for_log(input: &str) -> String {
         return String::new();
     }
 
-    if parts[0] == "/secrets" {
-        if let Some(sub) = parts.get(1) {
-            if *sub == "store-session" || *sub == "unlock" {
-                return format!("/secrets {} <redacted>", sub);
-            }
-        }
+    if parts[0] == "/secrets"
+        && let Some(sub) = parts.get(1)
+        && (*sub == "store-session" || *sub == "unlock")
+    {
+        return format!("/secrets {} <redacted>", sub);
     }
 
-    if parts[0] == "/profiles" {
-        if let Some(sub) = parts.get(1) {
-            if *sub == "new" {
-                if let Some(name) = parts.get(2) {
-                    if parts.len() > 3 {
-                        return format!("/profiles new {} <redacted>", name);
-                    }
-                    return format!("/profiles new {}", name);
-                }
-            }
+    if parts[0] == "/secrets"
+        && parts.get(1) == Some(&"llm-key")
+        && let Some(provider) = parts.get(2)
+    {
+        return format!("/secrets llm-key {provider} <redacted>");
+    }
+
+    if parts[0] == "/profiles"
+        && let Some(sub) = parts.get(1)
+        && *sub == "new"
+        && let Some(name) = parts.get(2)
+    {
+        if parts.len() > 3 {
+            return format!("/profiles new {} <redacted>", name);
         }
+        return format!("/profiles new {}", name);
     }
 
     let redacted: Vec<String> = parts
@@ -2131,7 +3221,7 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
         }
         return filter_entries(entries, query);
     }
-    // /runs set|inspect|delete <run_id> — show existing run IDs
+    // /runs set|inspect|compare|delete <run_id> — show existing run IDs
     if input.starts_with("/runs set ") {
         let runs: Vec<String> = app.iter_runs().collect();
         let entries: Vec<PaletteEntry> = runs
@@ -2148,6 +3238,18 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
             .collect();
         return filter_entries(entries, query);
     }
+    if let Some(rest) = input.strip_prefix("/runs compare ") {
+        // First id picked: complete the second one after it.
+        let prefix = match rest.split_once(' ') {
+            Some((first, _)) => format!("/runs compare {first} "),
+            None => "/runs compare ".to_string(),
+        };
+        let entries: Vec<PaletteEntry> = app
+            .iter_runs()
+            .map(|r| pe(&format!("{prefix}{r}"), "compare runs"))
+            .collect();
+        return filter_entries(entries, query);
+    }
     if input.starts_with("/runs delete ") {
         let runs: Vec<String> = app.iter_runs().collect();
         let entries: Vec<PaletteEntry> = runs
@@ -2174,11 +3276,40 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
             .collect();
         return filter_entries(entries, query);
     }
+    // /runs|/plans|/out tag|untag|note <id> — show existing IDs
+    for (command, kind) in [
+        ("/runs", AnnotatedKind::Run),
+        ("/plans", AnnotatedKind::Plan),
+        ("/out", AnnotatedKind::Out),
+    ] {
+        for action in ["tag", "untag", "note"] {
+            let prefix = format!("{command} {action} ");
+            if let Some(rest) = input.strip_prefix(&prefix)
+                && !rest.contains(' ')
+            {
+                let ids: Vec<String> = match kind {
+                    AnnotatedKind::Run => app.iter_runs().collect(),
+                    AnnotatedKind::Plan => app.iter_plans().collect(),
+                    AnnotatedKind::Out => list_dirs(&app.paths.out_dir).unwrap_or_default(),
+                };
+                let entries: Vec<PaletteEntry> = ids
+                    .iter()
+                    .map(|id| {
+                        pe(
+                            &format!("{prefix}{id} "),
+                            &format!("{action} {}", kind.label()),
+                        )
+                    })
+                    .collect();
+                return filter_entries(entries, query);
+            }
+        }
+    }
     // /settings set <key> <value> — show valid values for a key
     if input.starts_with("/settings set ") {
         let after = input.trim_start_matches("/settings set ").trim();
         let parts: Vec<&str> = after.split_whitespace().collect();
-        if parts.len() >= 1 && !after.is_empty() && !after.ends_with(' ') {
+        if !parts.is_empty() && !after.is_empty() && !after.ends_with(' ') {
             // User is typing a key name, show valid keys
             return filter_entries(settings_key_palette(), query);
         }
@@ -2217,6 +3348,19 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
                 pe("/profiles new", "create profile"),
                 pe("/profiles set", "set active profile"),
                 pe("/profiles delete", "remove profile"),
+                pe("/profiles storage", "set output bucket"),
+            ],
+            query,
+        );
+    }
+    if input.starts_with("/classify ") {
+        return filter_entries(
+            vec![
+                pe(
+                    "/classify --heuristic",
+                    "name/type labels only, without llm",
+                ),
+                pe("/classify show", "list column labels of the active run"),
             ],
             query,
         );
@@ -2224,10 +3368,21 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
     if input.starts_with("/plan ") {
         return filter_entries(
             vec![
-                pe("/plan new", "generate smart plan from schema"),
+                pe("/plan new", "generate plan from schema (llm if enabled)"),
+                pe(
+                    "/plan new --heuristic",
+                    "generate heuristic plan, without llm",
+                ),
+                pe(
+                    "/plan new --tables ",
+                    "plan only some tables + FK parents [--with-children]",
+                ),
                 pe("/plan edit", "edit plan.json in editor"),
                 pe("/plan show", "show current plan summary"),
                 pe("/plan validate", "validate plan against schema"),
+                pe("/plan rules", "add a column rule (form)"),
+                pe("/plan rules list", "list column generator rules"),
+                pe("/plan rules remove", "remove the rule of a column"),
             ],
             query,
         );
@@ -2238,6 +3393,10 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
                 pe("/runs list", "list runs"),
                 pe("/runs set", "set active run"),
                 pe("/runs inspect", "show run details"),
+                pe("/runs compare", "compare two runs"),
+                pe("/runs tag", "tag a run"),
+                pe("/runs untag", "remove tags from a run"),
+                pe("/runs note", "add a note to a run"),
                 pe("/runs delete", "delete run"),
             ],
             query,
@@ -2248,6 +3407,9 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
             vec![
                 pe("/plans list", "list plans"),
                 pe("/plans set", "set active plan"),
+                pe("/plans tag", "tag a plan"),
+                pe("/plans untag", "remove tags from a plan"),
+                pe("/plans note", "add a note to a plan"),
             ],
             query,
         );
@@ -2257,6 +3419,10 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
             vec![
                 pe("/out list", "list outputs"),
                 pe("/out preview", "preview CSV content"),
+                pe("/out dbt", "export CSV output as dbt seeds"),
+                pe("/out tag", "tag an output"),
+                pe("/out untag", "remove tags from an output"),
+                pe("/out note", "add a note to an output"),
             ],
             query,
         );
@@ -2275,6 +3441,7 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
             vec![
                 pe("/secrets status", "vault status"),
                 pe("/secrets import-env", "load .env into session"),
+                pe("/secrets llm-key", "load an LLM API key into session"),
                 pe("/secrets store-session", "store session secrets"),
                 pe("/secrets unlock", "unlock vault"),
                 pe("/secrets delete", "delete vault"),
@@ -2286,8 +3453,9 @@ pub fn command_palette_matches(app: &App, input: &str) -> Vec<PaletteEntry> {
         return filter_entries(
             vec![
                 pe("/llm status", "show llm configuration"),
-                pe("/llm models", "list models"),
-                pe("/llm set", "set provider/model"),
+                pe("/llm models", "list provider models"),
+                pe("/llm set", "set provider/model [--base-url <url>]"),
+                pe("/llm test", "send a test prompt"),
                 pe("/llm off", "disable llm"),
             ],
             query,
@@ -2314,8 +3482,13 @@ fn settings_key_palette() -> Vec<PaletteEntry> {
         pe("/settings set mode", "readonly_csv | insert | explore"),
         pe("/settings set privacy", "normal | paranoid"),
         pe("/settings set llm_enabled", "true | false"),
-        pe("/settings set llm_provider", "gemini | off"),
+        pe(
+            "/settings set llm_provider",
+            "gemini | openai | anthropic | ollama | off",
+        ),
         pe("/settings set llm_model", "model name"),
+        pe("/settings set llm_base_url", "endpoint url | default"),
+        pe("/settings set language", "en | pt_BR"),
     ]
 }
 
@@ -2346,8 +3519,18 @@ fn settings_value_palette(key: &str) -> Vec<PaletteEntry> {
         ],
         "llm_provider" => vec![
             pe("/settings set llm_provider gemini", "Google Gemini"),
+            pe(
+                "/settings set llm_provider openai",
+                "OpenAI or compatible server",
+            ),
+            pe("/settings set llm_provider anthropic", "Anthropic"),
+            pe("/settings set llm_provider ollama", "local Ollama"),
             pe("/settings set llm_provider off", "disable provider"),
         ],
+        "language" => vec![
+            pe("/settings set language en", "English messages"),
+            pe("/settings set language pt_BR", "mensagens em portugues"),
+        ],
         _ => Vec::new(),
     }
 }
@@ -2373,14 +3556,22 @@ pub fn command_palette_entries(app: &App) -> Vec<PaletteEntry> {
         pe("/introspect", "capture schema.json"),
         pe("/runs", "manage runs"),
         pe("/plans", "manage plans"),
-        pe("/plan new", "generate smart plan from schema"),
+        pe("/classify", "label columns with semantic types and PII"),
+        pe("/classify show", "list column labels"),
+        pe("/plan new", "generate plan from schema (llm if enabled)"),
         pe("/plan edit", "edit plan.json in editor"),
         pe("/plan show", "show plan summary"),
         pe("/plan validate", "validate plan against schema"),
+        pe("/plan rules", "add a column rule (form)"),
         pe("/generate", "generate CSV output"),
         pe("/out", "list / preview outputs"),
         pe("/eval", "evaluate last output"),
         pe("/doctor", "diagnose workspace"),
+        pe("/explain", "explain an error code"),
+        pe("/workspace export", "pack workspace into a .tar.zst"),
+        pe("/workspace import", "merge a workspace .tar.zst"),
+        pe("/workspace keygen", "create the output signing key"),
+        pe("/workspace verify", "check output checksums and signatures"),
         pe("/logs", "show logs tail"),
         pe("/open", "preview a file"),
         pe("/secrets", "vault + env helpers"),
@@ -0,0 +1,272 @@
+//! `/runs compare <a> <b>`: side-by-side view of two introspection runs.
+//!
+//! The left pane is run `a` (before), the right one run `b` (after). The
+//! schema section lists the tables only in one run and, for the tables in
+//! both, the columns and constraints added, removed or changed; the metrics
+//! section puts the `metrics.json` values of both runs next to each other with
+//! the delta. The schema section renders [`datalchemy_core::diff`]; the
+//! headless `datalchemy diff` prints the same diff.
+
+use std::path::Path;
+
+use datalchemy_core::{Column, DatabaseSchema, TableDiff, constraint_signature, diff_schemas};
+use datalchemy_eval::{SchemaMetrics, collect_schema_metrics};
+
+use crate::CliError;
+use crate::i18n::Msg;
+use crate::tui::state::{App, CompareKind, CompareLine, CompareState, UiState};
+
+pub fn cmd_runs_compare(app: &mut App, args: &[&str]) -> Result<(), CliError> {
+    let [a, b] = args else {
+        app.say_with(Msg::Usage, &[&"/runs compare <run_a> <run_b>"]);
+        return Ok(());
+    };
+    let mut runs = Vec::new();
+    for run_id in [a, b] {
+        let run_dir = app.paths.runs_dir.join(run_id);
+        let schema_path = run_dir.join("schema.json");
+        if !schema_path.is_file() {
+            app.say_with(Msg::RunSchemaNotFound, &[run_id]);
+            return Ok(());
+        }
+        let schema: DatabaseSchema = serde_json::from_str(&std::fs::read_to_string(schema_path)?)?;
+        let metrics = read_metrics(&run_dir)?.unwrap_or_else(|| collect_schema_metrics(&schema));
+        runs.push((schema, metrics));
+    }
+
+    let (before, after) = (&runs[0], &runs[1]);
+    let mut lines = vec![section("SCHEMA")];
+    let schema = schema_lines(&before.0, &after.0);
+    if schema.is_empty() {
+        lines.push(same("no structural changes", "no structural changes"));
+    }
+    lines.extend(schema);
+    lines.push(same("", ""));
+    lines.push(section("METRICS"));
+    lines.extend(metric_lines(&before.1, &after.1));
+
+    let changes = lines
+        .iter()
+        .filter(|line| !matches!(line.kind, CompareKind::Same | CompareKind::Section))
+        .count();
+    app.say_with(Msg::ComparingRuns, &[a, b, &changes]);
+    app.ui_state = UiState::Compare(CompareState::new(a.to_string(), b.to_string(), lines));
+    Ok(())
+}
+
+fn read_metrics(run_dir: &Path) -> Result<Option<SchemaMetrics>, CliError> {
+    let path = run_dir.join("metrics.json");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// Tables only in one run, then the column and constraint changes of the
+/// tables in both, in `schema.table` order, followed by the enum changes.
+fn schema_lines(before: &DatabaseSchema, after: &DatabaseSchema) -> Vec<CompareLine> {
+    let diff = diff_schemas(before, after);
+    let mut lines = Vec::new();
+    for name in diff.changed_tables() {
+        if diff.tables_removed.iter().any(|table| table == name) {
+            lines.push(removed(format!("- table {name}")));
+        } else if diff.tables_added.iter().any(|table| table == name) {
+            lines.push(added(format!("+ table {name}")));
+        } else if let Some(table) = diff.table(name) {
+            lines.push(same(&format!("table {name}"), &format!("table {name}")));
+            lines.extend(table_lines(table));
+        }
+    }
+    for name in &diff.enums_removed {
+        lines.push(removed(format!("- enum {name}")));
+    }
+    for name in &diff.enums_added {
+        lines.push(added(format!("+ enum {name}")));
+    }
+    for en in &diff.enums_modified {
+        lines.push(changed(
+            format!("~ enum {} ({})", en.name, en.before.join(", ")),
+            format!("~ enum {} ({})", en.name, en.after.join(", ")),
+        ));
+    }
+    lines
+}
+
+fn table_lines(table: &TableDiff) -> Vec<CompareLine> {
+    let mut lines = Vec::new();
+    for column in &table.columns_removed {
+        lines.push(removed(format!("  - {}", column_label(column))));
+    }
+    for column in &table.columns_modified {
+        lines.push(changed(
+            format!("  ~ {}", column_label(&column.before)),
+            format!("  ~ {}", column_label(&column.after)),
+        ));
+    }
+    for column in &table.columns_added {
+        lines.push(added(format!("  + {}", column_label(column))));
+    }
+    for constraint in &table.constraints_removed {
+        lines.push(removed(format!("  - {}", constraint_signature(constraint))));
+    }
+    for constraint in &table.constraints_added {
+        lines.push(added(format!("  + {}", constraint_signature(constraint))));
+    }
+    lines
+}
+
+/// Name, type, nullability, default and identity/generated markers of a column.
+pub(crate) fn column_label(column: &Column) -> String {
+    let mut label = format!("{} {}", column.name, column.column_type.data_type);
+    if !column.is_nullable {
+        label.push_str(" not null");
+    }
+    if let Some(default) = &column.default {
+        label.push_str(&format!(" default {default}"));
+    }
+    if column.identity.is_some() {
+        label.push_str(" identity");
+    }
+    if column.generated.is_some() {
+        label.push_str(" generated");
+    }
+    label
+}
+
+fn metric_lines(before: &SchemaMetrics, after: &SchemaMetrics) -> Vec<CompareLine> {
+    let counts = [
+        ("schemas", before.counts.schemas, after.counts.schemas),
+        ("tables", before.counts.tables, after.counts.tables),
+        ("columns", before.counts.columns, after.counts.columns),
+        (
+            "primary keys",
+            before.counts.constraints.primary_keys,
+            after.counts.constraints.primary_keys,
+        ),
+        (
+            "foreign keys",
+            before.counts.constraints.foreign_keys,
+            after.counts.constraints.foreign_keys,
+        ),
+        (
+            "unique",
+            before.counts.constraints.unique,
+            after.counts.constraints.unique,
+        ),
+        (
+            "checks",
+            before.counts.constraints.checks,
+            after.counts.constraints.checks,
+        ),
+        ("fk edges", before.fk_graph.edges, after.fk_graph.edges),
+        (
+            "tables without pk",
+            before.tables_without_pk.len(),
+            after.tables_without_pk.len(),
+        ),
+        ("warnings", before.warnings.len(), after.warnings.len()),
+    ];
+    let pcts = [
+        (
+            "tables with pk %",
+            before.coverage.tables_with_pk_pct,
+            after.coverage.tables_with_pk_pct,
+        ),
+        (
+            "tables with fk %",
+            before.coverage.tables_with_fk_pct,
+            after.coverage.tables_with_fk_pct,
+        ),
+        (
+            "columns not null %",
+            before.coverage.columns_not_null_pct,
+            after.coverage.columns_not_null_pct,
+        ),
+        (
+            "fks with index %",
+            before.coverage.fks_with_index_pct,
+            after.coverage.fks_with_index_pct,
+        ),
+        (
+            "nullable fks %",
+            before.coverage.nullable_fks_pct,
+            after.coverage.nullable_fks_pct,
+        ),
+    ];
+
+    let mut lines = Vec::new();
+    for (name, old, new) in counts {
+        let delta = new as i64 - old as i64;
+        lines.push(metric(
+            format!("{name:<20} {old}"),
+            format!("{name:<20} {new} ({delta:+})"),
+            delta != 0,
+        ));
+    }
+    for (name, old, new) in pcts {
+        let delta = new - old;
+        lines.push(metric(
+            format!("{name:<20} {old:.1}"),
+            format!("{name:<20} {new:.1} ({delta:+.1})"),
+            delta.abs() >= 0.05,
+        ));
+    }
+    lines.push(metric(
+        format!("{:<20} {}", "fk cycle", before.fk_graph.has_cycle),
+        format!("{:<20} {}", "fk cycle", after.fk_graph.has_cycle),
+        before.fk_graph.has_cycle != after.fk_graph.has_cycle,
+    ));
+    lines
+}
+
+fn section(title: &str) -> CompareLine {
+    CompareLine {
+        left: title.to_string(),
+        right: title.to_string(),
+        kind: CompareKind::Section,
+    }
+}
+
+fn same(left: &str, right: &str) -> CompareLine {
+    CompareLine {
+        left: left.to_string(),
+        right: right.to_string(),
+        kind: CompareKind::Same,
+    }
+}
+
+fn removed(left: String) -> CompareLine {
+    CompareLine {
+        left,
+        right: String::new(),
+        kind: CompareKind::Removed,
+    }
+}
+
+fn added(right: String) -> CompareLine {
+    CompareLine {
+        left: String::new(),
+        right,
+        kind: CompareKind::Added,
+    }
+}
+
+fn changed(left: String, right: String) -> CompareLine {
+    CompareLine {
+        left,
+        right,
+        kind: CompareKind::Changed,
+    }
+}
+
+fn metric(left: String, right: String, differs: bool) -> CompareLine {
+    CompareLine {
+        left,
+        right,
+        kind: if differs {
+            CompareKind::Changed
+        } else {
+            CompareKind::Same
+        },
+    }
+}
@@ -1,10 +1,8 @@
 //! Connection-string helpers shared across TUI modules.
 
-/// Returns `true` when the connection string uses a supported database engine.
+/// Returns `true` when an adapter is registered for the connection scheme.
 pub fn is_supported_connection(conn: &str) -> bool {
-    conn.starts_with("postgres://")
-        || conn.starts_with("postgresql://")
-        || conn.starts_with("sqlite://")
+    crate::adapters().resolve(conn).is_some()
 }
 
 /// Returns `true` when the connection string points to a SQLite database.
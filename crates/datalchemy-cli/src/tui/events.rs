@@ -1,15 +1,20 @@
+use std::sync::atomic::Ordering;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use sqlx::postgres::PgPoolOptions;
 
 use crate::CliError;
+use crate::i18n::Msg;
 use crate::tui::commands::{command_palette_matches, execute_command, sanitize_command_for_log};
 use crate::tui::conn::is_supported_connection;
+use crate::tui::rules;
 use crate::tui::state::{App, AppEvent, InputMode, SetupStep, UiState};
 use crate::workspace::{DbProfile, WriteIntent, save_profiles, save_settings};
 use datalchemy_core::validate_schema;
-use datalchemy_introspect::{
-    IntrospectOptions, introspect_postgres_with_options, introspect_sqlite_with_options,
-};
+use datalchemy_introspect::IntrospectOptions;
+
+/// Lines moved by PageUp/PageDown in the full-screen viewers.
+const VIEWER_PAGE: isize = 20;
 
 pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
     match app.mode.clone() {
@@ -19,6 +24,10 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
 }
 
 fn handle_command_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
+    if app.is_in_viewer() {
+        handle_viewer_key(app, key);
+        return Ok(());
+    }
     match key.code {
         // -- quit --
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -40,6 +49,9 @@ fn handle_command_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
             } else if !app.input.is_empty() {
                 app.input_clear();
                 app.palette_select = 0;
+            } else if let Some((name, cancel)) = app.running_operation() {
+                cancel.store(true, Ordering::Relaxed);
+                app.say_with(Msg::Cancelling, &[&app.text(name)]);
             }
         }
 
@@ -109,14 +121,12 @@ fn handle_command_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
         }
 
         // -- Tab: autocomplete from palette --
-        KeyCode::Tab => {
-            if app.input.starts_with('/') {
-                let matches = command_palette_matches(app, &app.input);
-                if !matches.is_empty() && app.palette_select < matches.len() {
-                    let cmd = matches[app.palette_select].command.to_string();
-                    app.input_set(cmd);
-                    app.palette_select = 0;
-                }
+        KeyCode::Tab if app.input.starts_with('/') => {
+            let matches = command_palette_matches(app, &app.input);
+            if !matches.is_empty() && app.palette_select < matches.len() {
+                let cmd = matches[app.palette_select].command.to_string();
+                app.input_set(cmd);
+                app.palette_select = 0;
             }
         }
 
@@ -148,13 +158,13 @@ fn handle_command_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
             if !input.is_empty() {
                 if app.is_in_setup() {
                     if let Err(err) = handle_setup_input(app, input) {
-                        app.push_message(format!("error: {err}"));
+                        app.push_error(&err);
                     }
                 } else {
                     let sanitized = sanitize_command_for_log(input);
                     app.record_command(&sanitized);
                     if let Err(err) = execute_command(app, input, false) {
-                        app.push_message(format!("error: {err}"));
+                        app.push_error(&err);
                     }
                 }
                 app.scroll_offset = 0;
@@ -181,6 +191,47 @@ fn handle_command_key(app: &mut App, key: KeyEvent) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Keys of the full-screen viewers: arrows (or hjkl) scroll, PageUp/PageDown
+/// jump a page, Tab/Shift+Tab switch preview files and Esc/q close them.
+fn handle_viewer_key(app: &mut App, key: KeyEvent) {
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.should_quit = true;
+        return;
+    }
+    if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+        app.ui_state = UiState::Normal;
+        return;
+    }
+    match &mut app.ui_state {
+        UiState::Preview(preview) => match key.code {
+            KeyCode::Down | KeyCode::Char('j') => preview.scroll_rows(1),
+            KeyCode::Up | KeyCode::Char('k') => preview.scroll_rows(-1),
+            KeyCode::PageDown => preview.scroll_rows(VIEWER_PAGE),
+            KeyCode::PageUp => preview.scroll_rows(-VIEWER_PAGE),
+            KeyCode::Right | KeyCode::Char('l') => preview.scroll_cols(1),
+            KeyCode::Left | KeyCode::Char('h') => preview.scroll_cols(-1),
+            KeyCode::Home => {
+                preview.row_offset = 0;
+                preview.col_offset = 0;
+            }
+            KeyCode::End => preview.scroll_rows(isize::MAX),
+            KeyCode::Tab => preview.cycle_table(true),
+            KeyCode::BackTab => preview.cycle_table(false),
+            _ => {}
+        },
+        UiState::Compare(compare) => match key.code {
+            KeyCode::Down | KeyCode::Char('j') => compare.scroll_lines(1),
+            KeyCode::Up | KeyCode::Char('k') => compare.scroll_lines(-1),
+            KeyCode::PageDown => compare.scroll_lines(VIEWER_PAGE),
+            KeyCode::PageUp => compare.scroll_lines(-VIEWER_PAGE),
+            KeyCode::Home => compare.scroll = 0,
+            KeyCode::End => compare.scroll_lines(isize::MAX),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
 fn handle_approval_key(
     app: &mut App,
     intent: WriteIntent,
@@ -190,22 +241,18 @@ fn handle_approval_key(
     match key.code {
         KeyCode::Char('y') | KeyCode::Char('Y') => {
             app.mode = InputMode::Command;
-            app.push_message(format!(
-                "approved: {} ({} paths)",
-                intent.reason,
-                intent.paths.len()
-            ));
+            app.say_with(Msg::ApprovedIntent, &[&intent.reason, &intent.paths.len()]);
             if let Err(err) = execute_command(app, &command, true) {
-                app.push_message(format!("error: {err}"));
+                app.push_error(&err);
             }
         }
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
             app.mode = InputMode::Command;
-            app.push_message("approval denied.");
+            app.say(Msg::ApprovalDenied);
         }
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.mode = InputMode::Command;
-            app.push_message("approval denied.");
+            app.say(Msg::ApprovalDenied);
         }
         _ => {}
     }
@@ -214,50 +261,47 @@ fn handle_approval_key(
 
 /// Go back one step in the setup wizard.
 fn handle_setup_back(app: &mut App) {
-    match &app.ui_state {
-        UiState::Setup(step) => {
-            let prev = match step {
-                SetupStep::Welcome => {
-                    app.should_quit = true;
-                    return;
-                }
-                SetupStep::ConfirmWorkspace => SetupStep::Welcome,
-                SetupStep::ConfirmReset => {
-                    app.ui_state = UiState::Normal;
-                    app.push_message("Reset canceled.");
-                    return;
-                }
-                SetupStep::ProfileName => SetupStep::ConfirmWorkspace,
-                SetupStep::ConnectionString => SetupStep::ProfileName,
-                SetupStep::SelectSchema => SetupStep::ConnectionString,
-                SetupStep::Introspecting => return, // can't cancel mid-introspect
-                SetupStep::DbSession | SetupStep::DbChange => {
+    if let UiState::Setup(step) = &app.ui_state {
+        let prev = match step {
+            SetupStep::Welcome => {
+                app.should_quit = true;
+                return;
+            }
+            SetupStep::ConfirmWorkspace => SetupStep::Welcome,
+            SetupStep::ConfirmReset => {
+                app.ui_state = UiState::Normal;
+                app.say(Msg::ResetCanceled);
+                return;
+            }
+            SetupStep::ProfileName => SetupStep::ConfirmWorkspace,
+            SetupStep::ConnectionString => SetupStep::ProfileName,
+            SetupStep::SelectSchema => SetupStep::ConnectionString,
+            SetupStep::Introspecting => return, // can't cancel mid-introspect
+            SetupStep::DbSession | SetupStep::DbChange => {
+                app.ui_state = UiState::Normal;
+                return;
+            }
+            SetupStep::Prompt(ctx) => {
+                if ctx.collected.is_empty() {
+                    // First step: go back to Normal
                     app.ui_state = UiState::Normal;
+                    app.say(Msg::Canceled);
                     return;
-                }
-                SetupStep::Prompt(ctx) => {
-                    if ctx.collected.is_empty() {
-                        // First step: go back to Normal
-                        app.ui_state = UiState::Normal;
-                        app.push_message("Canceled.");
-                        return;
-                    } else {
-                        // Go back one prompt step
-                        let mut new_ctx = ctx.clone();
-                        new_ctx.collected.pop();
-                        if let Some(label) = new_ctx.current_prompt() {
-                            app.push_message(label.to_string());
-                        }
-                        app.input_clear();
-                        app.ui_state = UiState::Setup(SetupStep::Prompt(new_ctx));
-                        return;
+                } else {
+                    // Go back one prompt step
+                    let mut new_ctx = ctx.clone();
+                    new_ctx.collected.pop();
+                    if let Some(label) = new_ctx.current_prompt() {
+                        app.push_message(label.to_string());
                     }
+                    app.input_clear();
+                    app.ui_state = UiState::Setup(SetupStep::Prompt(new_ctx));
+                    return;
                 }
-            };
-            app.input_clear();
-            app.ui_state = UiState::Setup(prev);
-        }
-        _ => {}
+            }
+        };
+        app.input_clear();
+        app.ui_state = UiState::Setup(prev);
     }
 }
 
@@ -266,7 +310,7 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
         UiState::Setup(SetupStep::Welcome) => {
             app.ui_state = UiState::Setup(SetupStep::ConfirmWorkspace);
             app.messages.clear();
-            app.push_message("Welcome to Datalchemy.");
+            app.say(Msg::Welcome);
             app.push_raw("");
         }
         UiState::Setup(SetupStep::ConfirmReset) => {
@@ -284,44 +328,42 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
                 app.ui_state = UiState::Setup(SetupStep::Welcome);
             } else if matches!(input, "n" | "N") {
                 app.ui_state = UiState::Normal;
-                app.push_message("Reset canceled.");
+                app.say(Msg::ResetCanceled);
             } else {
-                app.push_message("Please answer with y or n.");
+                app.say(Msg::AnswerYesNo);
             }
         }
         UiState::Setup(SetupStep::ConfirmWorkspace) => {
             if matches!(input, "s" | "S" | "y" | "Y") {
                 execute_command(app, "/init", true)?;
                 app.push_raw("");
-                app.push_message(
-                    "Great! Please enter a name for your profile (e.g. 'dev', 'prod'):",
-                );
+                app.say(Msg::SetupProfileName);
                 app.ui_state = UiState::Setup(SetupStep::ProfileName);
             } else if matches!(input, "n" | "N") {
-                app.push_message("Setup canceled.");
+                app.say(Msg::SetupCanceled);
                 app.should_quit = true;
             } else {
-                app.push_message("Please answer with y or n.");
+                app.say(Msg::AnswerYesNo);
             }
         }
         UiState::Setup(SetupStep::ProfileName) => {
             if input.trim().is_empty() {
-                app.push_message("Profile name cannot be empty.");
+                app.say(Msg::ProfileNameEmpty);
             } else {
                 app.setup_profile_name = Some(input.trim().to_string());
                 app.push_raw("");
-                app.push_message("Enter your database connection string:");
+                app.say(Msg::SetupConnection);
                 app.ui_state = UiState::Setup(SetupStep::ConnectionString);
             }
         }
         UiState::Setup(SetupStep::ConnectionString) => {
             let conn_str = input.trim();
             if conn_str.is_empty() {
-                app.push_message("Connection string cannot be empty.");
+                app.say(Msg::ConnectionEmpty);
                 return Ok(());
             }
             if !is_supported_connection(conn_str) {
-                app.push_message("Unsupported database. URL must start with postgres://, postgresql://, or sqlite://");
+                app.say(Msg::UnsupportedDatabaseUrl);
                 return Ok(());
             }
 
@@ -339,12 +381,14 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
             app.ui_state = UiState::Setup(SetupStep::Introspecting);
             app.messages.clear();
 
+            let language = app.settings.language;
             let tx = app.tx.clone();
             let conn_string = conn_str.to_string();
             let is_sqlite = conn_str.starts_with("sqlite://");
 
             app.runtime.spawn(async move {
-                tx.send(AppEvent::Log("Connecting to database...".into())).ok();
+                let message = Msg::ConnectingDatabase.text(language);
+                tx.send(AppEvent::Log(message.into())).ok();
                 if is_sqlite {
                     match sqlx::sqlite::SqlitePoolOptions::new()
                         .connect(&conn_string)
@@ -356,17 +400,15 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
                                 .ok();
                         }
                         Err(e) => {
-                            tx.send(AppEvent::SchemasLoaded(Err(format!(
-                                "Connection failed: {}. Check the path and try again.",
-                                e
-                            ))))
-                            .ok();
+                            let message = Msg::ConnectionFailedPath.fill(language, &[&e]);
+                            tx.send(AppEvent::SchemasLoaded(Err(message))).ok();
                         }
                     }
                 } else {
                     match PgPoolOptions::new().connect(&conn_string).await {
                         Ok(pool) => {
-                            tx.send(AppEvent::Log("Connected! Fetching schemas...".into())).ok();
+                            let message = Msg::ConnectedFetchingSchemas.text(language);
+                            tx.send(AppEvent::Log(message.into())).ok();
                             let schemas_result: Result<Vec<sqlx::postgres::PgRow>, sqlx::Error> =
                                 sqlx::query(
                                 "SELECT schema_name FROM information_schema.schemata
@@ -388,20 +430,14 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
                                     tx.send(AppEvent::SchemasLoaded(Ok(schemas))).ok();
                                 }
                                 Err(e) => {
-                                    tx.send(AppEvent::SchemasLoaded(Err(format!(
-                                        "Failed to list schemas: {}",
-                                        e
-                                    ))))
-                                    .ok();
+                                    let message = Msg::ListSchemasFailed.fill(language, &[&e]);
+                                    tx.send(AppEvent::SchemasLoaded(Err(message))).ok();
                                 }
                             }
                         }
                         Err(e) => {
-                            tx.send(AppEvent::SchemasLoaded(Err(format!(
-                                "Connection failed: {}. Check the URL and try again.",
-                                e
-                            ))))
-                            .ok();
+                            let message = Msg::ConnectionFailedUrl.fill(language, &[&e]);
+                            tx.send(AppEvent::SchemasLoaded(Err(message))).ok();
                         }
                     }
                 }
@@ -410,30 +446,30 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
         UiState::Setup(SetupStep::DbSession) => {
             let conn_str = input.trim();
             if conn_str.is_empty() {
-                app.push_message("Connection string cannot be empty.");
+                app.say(Msg::ConnectionEmpty);
                 return Ok(());
             }
             if !is_supported_connection(conn_str) {
-                app.push_message("Unsupported database.");
+                app.say(Msg::UnsupportedDatabase);
                 return Ok(());
             }
             app.session_conn = Some(conn_str.to_string());
             app.ui_state = UiState::Normal;
-            app.push_message("session connection updated (not saved).");
+            app.say(Msg::SessionConnectionUpdated);
         }
         UiState::Setup(SetupStep::DbChange) => {
             let conn_str = input.trim();
             if conn_str.is_empty() {
-                app.push_message("Connection string cannot be empty.");
+                app.say(Msg::ConnectionEmpty);
                 return Ok(());
             }
             if !is_supported_connection(conn_str) {
-                app.push_message("Unsupported database.");
+                app.say(Msg::UnsupportedDatabase);
                 return Ok(());
             }
             app.session_conn = Some(conn_str.to_string());
             app.ui_state = UiState::Normal;
-            app.push_message("session connection updated for this run.");
+            app.say(Msg::SessionConnectionUpdatedRun);
         }
         UiState::Setup(SetupStep::SelectSchema) => {
             let selected_schema = if app.available_schemas.is_empty() {
@@ -445,9 +481,10 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
             app.ui_state = UiState::Setup(SetupStep::Introspecting);
             app.messages.clear();
 
+            let language = app.settings.language;
             let tx = app.tx.clone();
             let Some(conn_string) = app.session_conn.clone() else {
-                app.push_message("missing connection string. please enter it again.");
+                app.say(Msg::MissingConnectionString);
                 app.ui_state = UiState::Setup(SetupStep::ConnectionString);
                 return Ok(());
             };
@@ -460,80 +497,37 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
                 include_indexes: true,
                 include_comments: true,
                 schemas: selected_schema,
+                sample_stats: false,
+                include_privileges: false,
+                ..IntrospectOptions::default()
             };
 
-            let is_sqlite = conn_string.starts_with("sqlite://");
-
             app.runtime.spawn(async move {
-                tx.send(AppEvent::Log("Starting introspection...".into()))
-                    .ok();
-                if is_sqlite {
-                    match sqlx::sqlite::SqlitePoolOptions::new()
-                        .connect(&conn_string)
-                        .await
-                    {
-                        Ok(pool) => match introspect_sqlite_with_options(&pool, options).await {
-                            Ok(schema) => {
-                                tx.send(AppEvent::Log("Introspection complete.".into()))
-                                    .ok();
-                                if let Err(e) = validate_schema(&schema) {
-                                    tx.send(AppEvent::IntrospectionDone(Err(format!(
-                                        "Schema validation failed: {}",
-                                        e
-                                    ))))
-                                    .ok();
-                                } else {
-                                    tx.send(AppEvent::IntrospectionDone(Ok(()))).ok();
-                                }
-                            }
-                            Err(e) => {
-                                tx.send(AppEvent::IntrospectionDone(Err(format!(
-                                    "Introspection error: {}",
-                                    e
-                                ))))
-                                .ok();
-                            }
-                        },
-                        Err(e) => {
-                            tx.send(AppEvent::IntrospectionDone(Err(format!(
-                                "Connection failed: {}",
-                                e
-                            ))))
-                            .ok();
-                        }
+                let message = Msg::StartingIntrospection.text(language);
+                tx.send(AppEvent::Log(message.into())).ok();
+                let adapter = match crate::adapters().open(&conn_string, &options).await {
+                    Ok(adapter) => adapter,
+                    Err(e) => {
+                        let message = Msg::ConnectionFailed.fill(language, &[&e]);
+                        tx.send(AppEvent::IntrospectionDone(Err(message))).ok();
+                        return;
                     }
-                } else {
-                    match PgPoolOptions::new().connect(&conn_string).await {
-                        Ok(pool) => match introspect_postgres_with_options(&pool, options).await {
-                            Ok(schema) => {
-                                tx.send(AppEvent::Log("Introspection complete.".into()))
-                                    .ok();
-                                if let Err(e) = validate_schema(&schema) {
-                                    tx.send(AppEvent::IntrospectionDone(Err(format!(
-                                        "Schema validation failed: {}",
-                                        e
-                                    ))))
-                                    .ok();
-                                } else {
-                                    tx.send(AppEvent::IntrospectionDone(Ok(()))).ok();
-                                }
-                            }
-                            Err(e) => {
-                                tx.send(AppEvent::IntrospectionDone(Err(format!(
-                                    "Introspection error: {}",
-                                    e
-                                ))))
-                                .ok();
-                            }
-                        },
-                        Err(e) => {
-                            tx.send(AppEvent::IntrospectionDone(Err(format!(
-                                "Connection failed: {}",
-                                e
-                            ))))
-                            .ok();
+                };
+                match adapter.introspect(&options).await {
+                    Ok(schema) => {
+                        let message = Msg::IntrospectionComplete.text(language);
+                        tx.send(AppEvent::Log(message.into())).ok();
+                        if let Err(e) = validate_schema(&schema) {
+                            let message = Msg::SchemaValidationFailed.fill(language, &[&e]);
+                            tx.send(AppEvent::IntrospectionDone(Err(message))).ok();
+                        } else {
+                            tx.send(AppEvent::IntrospectionDone(Ok(()))).ok();
                         }
                     }
+                    Err(e) => {
+                        let message = Msg::IntrospectionError.fill(language, &[&e]);
+                        tx.send(AppEvent::IntrospectionDone(Err(message))).ok();
+                    }
                 }
             });
         }
@@ -545,20 +539,26 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
 
             // Validate per-command/step
             if value.is_empty() {
-                app.push_message("Value cannot be empty.");
+                app.say(Msg::ValueEmpty);
                 return Ok(());
             }
 
             // Connection string validation for /profiles new step 2
-            if ctx.command == "/profiles new" && ctx.collected.len() == 1 {
-                if !is_supported_connection(&value) {
-                    app.push_message("Unsupported database. URL must start with postgres://, postgresql://, or sqlite://");
-                    return Ok(());
-                }
+            if ctx.command == "/profiles new"
+                && ctx.collected.len() == 1
+                && !is_supported_connection(&value)
+            {
+                app.say(Msg::UnsupportedDatabaseUrl);
+                return Ok(());
             }
 
             ctx.push(value);
 
+            if ctx.command == rules::ADD_COMMAND && !rules::form_step(app, &mut ctx)? {
+                app.ui_state = UiState::Setup(SetupStep::Prompt(ctx));
+                return Ok(());
+            }
+
             if ctx.is_complete() {
                 // Assemble and execute command
                 app.ui_state = UiState::Normal;
@@ -566,7 +566,7 @@ fn handle_setup_input(app: &mut App, input: &str) -> Result<(), CliError> {
                 let sanitized = crate::tui::commands::sanitize_command_for_log(&full_cmd);
                 app.record_command(&sanitized);
                 if let Err(err) = execute_command(app, &full_cmd, false) {
-                    app.push_message(format!("error: {err}"));
+                    app.push_error(&err);
                 }
             } else {
                 // Show next prompt
@@ -1,6 +1,9 @@
+pub mod annotate;
 pub mod commands;
+pub mod compare;
 pub mod conn;
 pub mod events;
+pub mod rules;
 pub mod secrets;
 pub mod state;
 pub mod ui;
@@ -17,6 +20,7 @@ use crossterm::{
 use ratatui::{Terminal, backend::CrosstermBackend};
 
 use crate::CliError;
+use crate::i18n::Msg;
 use events::handle_key;
 use state::App;
 use ui::draw_ui;
@@ -59,10 +63,10 @@ fn run_loop<B: ratatui::backend::Backend>(
             match event {
                 AppEvent::Log(msg) => {
                     app.push_message(msg);
-                    if let state::UiState::Setup(state::SetupStep::Introspecting) = &app.ui_state {
-                        if app.messages.len() > 10 {
-                            app.messages.remove(0);
-                        }
+                    if let state::UiState::Setup(state::SetupStep::Introspecting) = &app.ui_state
+                        && app.messages.len() > 10
+                    {
+                        app.messages.remove(0);
                     }
                 }
                 AppEvent::SchemasLoaded(res) => match res {
@@ -75,24 +79,38 @@ fn run_loop<B: ratatui::backend::Backend>(
                         app.ui_state = state::UiState::Setup(state::SetupStep::SelectSchema);
                     }
                     Err(e) => {
-                        app.push_message(format!("Error: {}", e));
-                        app.push_message("Please check connection string and try again.");
+                        app.say_with(Msg::SetupError, &[&e]);
+                        app.say(Msg::CheckConnection);
                         app.ui_state = state::UiState::Setup(state::SetupStep::ConnectionString);
                     }
                 },
                 AppEvent::IntrospectionDone(res) => match res {
                     Ok(_) => {
                         app.push_raw("");
-                        app.push_message("Introspection successful!");
-                        app.push_message("Setup complete. Type /help to see commands.");
+                        app.say(Msg::IntrospectionSuccessful);
+                        app.say(Msg::SetupComplete);
                         app.ui_state = state::UiState::Normal;
                     }
                     Err(e) => {
-                        app.push_message(format!("Error: {}", e));
-                        app.push_message("Please enter connection string again:");
+                        app.say_with(Msg::SetupError, &[&e]);
+                        app.say(Msg::EnterConnectionAgain);
                         app.ui_state = state::UiState::Setup(state::SetupStep::ConnectionString);
                     }
                 },
+                AppEvent::GenerationProgress(progress) => {
+                    if let Some(task) = &mut app.generation {
+                        task.apply(progress);
+                    }
+                }
+                AppEvent::GenerationDone(result) => {
+                    commands::finish_generation(app, result)?;
+                }
+                AppEvent::IntrospectDone(result) => {
+                    commands::finish_introspect(app, result)?;
+                }
+                AppEvent::EvalDone(result) => {
+                    commands::finish_eval(app, result)?;
+                }
                 AppEvent::AsyncTaskDone(msg) => {
                     app.finish_task();
                     if !msg.is_empty() {
@@ -110,10 +128,22 @@ fn run_loop<B: ratatui::backend::Backend>(
                 event::Event::Key(key) => handle_key(app, key)?,
                 event::Event::Mouse(mouse) => match mouse.kind {
                     event::MouseEventKind::ScrollDown => {
-                        app.scroll_offset = app.scroll_offset.saturating_sub(1);
+                        if let state::UiState::Preview(preview) = &mut app.ui_state {
+                            preview.scroll_rows(1);
+                        } else if let state::UiState::Compare(compare) = &mut app.ui_state {
+                            compare.scroll_lines(1);
+                        } else {
+                            app.scroll_offset = app.scroll_offset.saturating_sub(1);
+                        }
                     }
                     event::MouseEventKind::ScrollUp => {
-                        app.scroll_offset = app.scroll_offset.saturating_add(1);
+                        if let state::UiState::Preview(preview) = &mut app.ui_state {
+                            preview.scroll_rows(-1);
+                        } else if let state::UiState::Compare(compare) = &mut app.ui_state {
+                            compare.scroll_lines(-1);
+                        } else {
+                            app.scroll_offset = app.scroll_offset.saturating_add(1);
+                        }
                     }
                     _ => {}
                 },
@@ -121,5 +151,9 @@ fn run_loop<B: ratatui::backend::Backend>(
             }
         }
     }
+    // Leaving with an operation running: stop it at the next check.
+    if let Some((_, cancel)) = app.running_operation() {
+        cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
     Ok(())
 }
@@ -0,0 +1,515 @@
+//! `/plan rules`: form-based editor for the column generator rules of the
+//! active plan.
+//!
+//! `/plan rules` walks through table, column, generator, params and
+//! transforms, with hints from the schema and the generator registry, and then
+//! runs `/plan rules add`, which can also be typed directly:
+//!
+//! `/plan rules add <schema.table> <column> <generator> [<params json>|-] [<transform> [<params json>]; ...|-]`
+//!
+//! The new rule replaces the generator of the column; plan.json is only
+//! written back when the plan still validates against the schema.
+
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use datalchemy_core::{Column, ColumnType, DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratorRegistry;
+use datalchemy_plan::{
+    ColumnGeneratorRule, GeneratorRef, GeneratorSpec, Plan, Rule, TransformRule,
+};
+
+use crate::CliError;
+use crate::i18n::Msg;
+use crate::plan::{check_registry, guess_generator, read_schema, validation_report};
+use crate::tui::commands::start_prompt;
+use crate::tui::state::{App, PromptContext};
+use crate::workspace::{WriteIntent, write_json_atomic};
+
+/// Command run when the form is complete.
+pub const ADD_COMMAND: &str = "/plan rules add";
+
+const FORM_PROMPTS: [Msg; 5] = [
+    Msg::RuleFormTable,
+    Msg::RuleFormColumn,
+    Msg::RuleFormGenerator,
+    Msg::RuleFormParams,
+    Msg::RuleFormTransforms,
+];
+
+pub fn cmd_plan_rules(
+    app: &mut App,
+    args: &[&str],
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    match args.first().copied() {
+        None => start_form(app),
+        Some("list") => list_rules(app),
+        Some("add") => add_rule(app, bypass_approval, raw),
+        Some("remove") => remove_rule(app, &args[1..], bypass_approval, raw),
+        Some(_) => {
+            app.input_set("/plan rules ".to_string());
+            Ok(())
+        }
+    }
+}
+
+/// Plan and schema edited by `/plan rules`.
+struct ActivePlan {
+    path: PathBuf,
+    plan: Plan,
+    schema: DatabaseSchema,
+}
+
+fn load_active(app: &mut App) -> Result<Option<ActivePlan>, CliError> {
+    let Some(plan_id) = app.settings.active_plan_id.clone() else {
+        app.say(Msg::MissingActivePlanHint);
+        return Ok(None);
+    };
+    let Some(run_id) = app.settings.active_run_id.clone() else {
+        app.say(Msg::MissingActiveRunHint);
+        return Ok(None);
+    };
+    let path = app.paths.plans_dir.join(&plan_id).join("plan.json");
+    let schema_path = app.paths.runs_dir.join(&run_id).join("schema.json");
+    if !path.exists() || !schema_path.exists() {
+        app.say(Msg::SchemaOrPlanNotFound);
+        return Ok(None);
+    }
+    let plan = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|err| CliError::Plan(err.to_string()))?;
+    Ok(Some(ActivePlan {
+        path,
+        plan,
+        schema: read_schema(&schema_path)?,
+    }))
+}
+
+fn start_form(app: &mut App) -> Result<(), CliError> {
+    let Some(active) = load_active(app)? else {
+        return Ok(());
+    };
+    app.push_raw("TABLES");
+    for schema in &active.schema.schemas {
+        for table in &schema.tables {
+            app.push_raw(format!(
+                "  {}.{} ({} columns)",
+                schema.name,
+                table.name,
+                table.columns.len()
+            ));
+        }
+    }
+    let prompts = FORM_PROMPTS.map(|prompt| app.text(prompt)).to_vec();
+    start_prompt(app, PromptContext::new(ADD_COMMAND, prompts));
+    Ok(())
+}
+
+/// Check the value just entered in the form and print the hints of the next
+/// step. Returns false (after dropping the value) when it must be re-entered.
+pub fn form_step(app: &mut App, ctx: &mut PromptContext) -> Result<bool, CliError> {
+    let Some(active) = load_active(app)? else {
+        return Ok(false);
+    };
+    let Some(value) = ctx.collected.last().cloned() else {
+        return Ok(true);
+    };
+    let registry = GeneratorRegistry::new();
+    let accepted = match ctx.collected.len() {
+        1 => match find_table(&active.schema, &value) {
+            Some(table) => {
+                column_hints(app, &active.plan, &value, table);
+                true
+            }
+            None => {
+                app.say_with(Msg::TableNotFound, &[&value]);
+                false
+            }
+        },
+        2 => match find_table(&active.schema, &ctx.collected[0])
+            .and_then(|table| table.columns.iter().find(|column| column.name == value))
+        {
+            Some(column) => {
+                generator_hints(app, &registry, column);
+                true
+            }
+            None => {
+                app.say_with(Msg::ColumnNotFound, &[&value]);
+                false
+            }
+        },
+        3 => {
+            if let Some(prefix) = value.strip_suffix('?') {
+                let ids: Vec<&str> = registry
+                    .generator_ids()
+                    .into_iter()
+                    .filter(|id| id.starts_with(prefix))
+                    .collect();
+                app.say_with(Msg::GeneratorsMatch, &[&ids.len(), &prefix]);
+                for id in ids {
+                    app.push_raw(format!("  {id}"));
+                }
+                false
+            } else if registry.generator(&value).is_some() {
+                param_hints(app, &registry, &value);
+                true
+            } else {
+                app.say_with(Msg::UnknownGenerator, &[&value]);
+                false
+            }
+        }
+        4 => match parse_params(&value) {
+            Ok(_) => {
+                app.push_message(format!(
+                    "transforms: {}",
+                    registry.transform_ids().join(", ")
+                ));
+                true
+            }
+            Err(message) => {
+                app.push_message(message);
+                false
+            }
+        },
+        _ => true,
+    };
+    if !accepted {
+        ctx.collected.pop();
+        if let Some(label) = ctx.current_prompt() {
+            app.push_message(label.to_string());
+        }
+    }
+    Ok(accepted)
+}
+
+fn column_hints(app: &mut App, plan: &Plan, table_ref: &str, table: &Table) {
+    let (schema, table_name) = table_ref.split_once('.').unwrap_or(("", table_ref));
+    app.push_raw("COLUMNS");
+    for column in &table.columns {
+        let current = plan.rules.iter().find_map(|rule| match rule {
+            Rule::ColumnGenerator(cg)
+                if cg.schema == schema && cg.table == table_name && cg.column == column.name =>
+            {
+                Some(cg.generator_id())
+            }
+            _ => None,
+        });
+        app.push_raw(format!(
+            "  {} {}{}",
+            column.name,
+            column.column_type.data_type,
+            current.map(|id| format!(" -> {id}")).unwrap_or_default()
+        ));
+    }
+}
+
+fn generator_hints(app: &mut App, registry: &GeneratorRegistry, column: &Column) {
+    if let Some(id) = guess_generator(&column.name, &column.column_type) {
+        app.say_with(Msg::SuggestedGenerator, &[&id]);
+    }
+    if let Some(prefix) = primitive_prefix(&column.column_type) {
+        let ids: Vec<&str> = registry
+            .generator_ids()
+            .into_iter()
+            .filter(|id| id.starts_with(prefix))
+            .collect();
+        app.say_with(
+            Msg::GeneratorsFor,
+            &[&column.column_type.data_type, &ids.join(", ")],
+        );
+    }
+}
+
+fn param_hints(app: &mut App, registry: &GeneratorRegistry, id: &str) {
+    let specs = registry
+        .generator(id)
+        .map(|generator| generator.params())
+        .unwrap_or_default();
+    if specs.is_empty() {
+        app.say_with(Msg::NoDeclaredParams, &[&id]);
+        return;
+    }
+    let params: Vec<String> = specs
+        .iter()
+        .map(|spec| {
+            format!(
+                "{}{} ({})",
+                spec.key,
+                if spec.required { "*" } else { "" },
+                spec.kind.as_str()
+            )
+        })
+        .collect();
+    app.say_with(Msg::GeneratorParams, &[&id, &params.join(", ")]);
+}
+
+/// Primitive generators matching a column type.
+fn primitive_prefix(column_type: &ColumnType) -> Option<&'static str> {
+    let udt = column_type.udt_name.to_lowercase();
+    let dtype = column_type.data_type.to_lowercase();
+    let prefix = if udt == "uuid" {
+        "primitive.uuid"
+    } else if udt == "bool" || dtype.contains("boolean") {
+        "primitive.bool"
+    } else if dtype.contains("int") || dtype.contains("serial") {
+        "primitive.int"
+    } else if dtype.contains("numeric") || dtype.contains("decimal") {
+        "primitive.decimal"
+    } else if dtype.contains("real") || dtype.contains("double") || dtype.contains("float") {
+        "primitive.float"
+    } else if dtype.starts_with("timestamp") {
+        "primitive.timestamp"
+    } else if dtype.starts_with("date") {
+        "primitive.date"
+    } else if dtype.starts_with("time") {
+        "primitive.time"
+    } else if dtype.contains("char") || dtype.contains("text") {
+        "primitive.text"
+    } else if dtype == "user-defined" {
+        "primitive.enum"
+    } else {
+        return None;
+    };
+    Some(prefix)
+}
+
+fn find_table<'a>(schema: &'a DatabaseSchema, table_ref: &str) -> Option<&'a Table> {
+    let (schema_name, table_name) = table_ref.split_once('.')?;
+    schema
+        .schemas
+        .iter()
+        .find(|schema| schema.name == schema_name)?
+        .tables
+        .iter()
+        .find(|table| table.name == table_name)
+}
+
+fn list_rules(app: &mut App) -> Result<(), CliError> {
+    let Some(active) = load_active(app)? else {
+        return Ok(());
+    };
+    let rules: Vec<&ColumnGeneratorRule> = active
+        .plan
+        .rules
+        .iter()
+        .filter_map(|rule| match rule {
+            Rule::ColumnGenerator(cg) => Some(cg),
+            _ => None,
+        })
+        .collect();
+    app.push_raw(format!("COLUMN RULES: {}", rules.len()));
+    for rule in rules {
+        let mut line = format!(
+            "  {}.{}.{} -> {}",
+            rule.schema,
+            rule.table,
+            rule.column,
+            rule.generator_id()
+        );
+        if let Some(params) = rule.generator_params() {
+            line.push_str(&format!(" {params}"));
+        }
+        for transform in &rule.transforms {
+            line.push_str(&format!(" | {}", transform.transform));
+        }
+        app.push_raw(line);
+    }
+    Ok(())
+}
+
+fn add_rule(app: &mut App, bypass_approval: bool, raw: &str) -> Result<(), CliError> {
+    let rule = match parse_add(raw).and_then(|rule| check_registry(&rule).map(|()| rule)) {
+        Ok(rule) => rule,
+        Err(message) => {
+            app.push_message(message);
+            app.say_with(
+                Msg::Usage,
+                &[&"/plan rules add <schema.table> <column> <generator> [params|-] [transforms|-]"],
+            );
+            return Ok(());
+        }
+    };
+    let Some(mut active) = load_active(app)? else {
+        return Ok(());
+    };
+    if !bypass_approval && app.requires_approval() {
+        let intent = WriteIntent::new("edit plan.json", vec![active.path.clone()]);
+        return app.request_approval(intent, raw);
+    }
+
+    let summary = format!(
+        "{}.{}.{} -> {}",
+        rule.schema,
+        rule.table,
+        rule.column,
+        rule.generator_id()
+    );
+    remove_column_rules(&mut active.plan, &rule.schema, &rule.table, &rule.column);
+    active.plan.rules.push(Rule::ColumnGenerator(rule));
+    if write_if_valid(app, &active)? {
+        app.say_with(Msg::RuleSaved, &[&summary]);
+    }
+    Ok(())
+}
+
+fn remove_rule(
+    app: &mut App,
+    args: &[&str],
+    bypass_approval: bool,
+    raw: &str,
+) -> Result<(), CliError> {
+    let (Some(table_ref), Some(column)) = (args.first(), args.get(1)) else {
+        app.say_with(Msg::Usage, &[&"/plan rules remove <schema.table> <column>"]);
+        return Ok(());
+    };
+    let Some((schema, table)) = table_ref.split_once('.') else {
+        app.say_with(Msg::ExpectedSchemaTable, &[table_ref]);
+        return Ok(());
+    };
+    let Some(mut active) = load_active(app)? else {
+        return Ok(());
+    };
+    if !bypass_approval && app.requires_approval() {
+        let intent = WriteIntent::new("edit plan.json", vec![active.path.clone()]);
+        return app.request_approval(intent, raw);
+    }
+    if remove_column_rules(&mut active.plan, schema, table, column) == 0 {
+        app.say_with(Msg::NoGeneratorRule, &[table_ref, column]);
+        return Ok(());
+    }
+    if write_if_valid(app, &active)? {
+        app.say_with(Msg::RuleRemoved, &[table_ref, column]);
+    }
+    Ok(())
+}
+
+/// Remove the generator rules of a column; returns how many were removed.
+fn remove_column_rules(plan: &mut Plan, schema: &str, table: &str, column: &str) -> usize {
+    let before = plan.rules.len();
+    plan.rules.retain(|rule| {
+        !matches!(rule, Rule::ColumnGenerator(cg)
+            if cg.schema == schema && cg.table == table && cg.column == column)
+    });
+    before - plan.rules.len()
+}
+
+/// Validate the edited plan against the schema and write it when valid.
+fn write_if_valid(app: &mut App, active: &ActivePlan) -> Result<bool, CliError> {
+    let plan_json = serde_json::to_value(&active.plan)?;
+    let report = validation_report(&plan_json, &active.schema)?;
+    for warning in &report.warnings {
+        app.say_with(
+            Msg::IssueWarning,
+            &[&warning.code, &warning.path, &warning.message],
+        );
+    }
+    if !report.is_ok() {
+        for issue in &report.errors {
+            app.say_with(Msg::IssueError, &[&issue.code, &issue.path, &issue.message]);
+        }
+        app.say(Msg::PlanNotChanged);
+        return Ok(false);
+    }
+    write_json_atomic(&active.path, &active.plan)?;
+    Ok(true)
+}
+
+/// Parse `/plan rules add ...` (params and transforms may contain spaces).
+fn parse_add(raw: &str) -> Result<ColumnGeneratorRule, String> {
+    let mut rest = raw;
+    for _ in 0..3 {
+        rest = split_token(rest).1;
+    }
+    let (table_ref, rest) = split_token(rest);
+    let (column, rest) = split_token(rest);
+    let (generator, rest) = split_token(rest);
+    if generator.is_empty() {
+        return Err("missing table, column or generator".to_string());
+    }
+    let Some((schema, table)) = table_ref.split_once('.') else {
+        return Err(format!("expected schema.table, got '{table_ref}'"));
+    };
+
+    let rest = rest.trim_start();
+    let (params, rest) = if let Some(rest) = rest.strip_prefix('-') {
+        (None, rest)
+    } else if rest.is_empty() {
+        (None, rest)
+    } else {
+        let mut stream = serde_json::Deserializer::from_str(rest).into_iter::<Value>();
+        let value = stream
+            .next()
+            .transpose()
+            .map_err(|err| format!("invalid params: {err}"))?;
+        let params = match value {
+            Some(Value::Object(map)) => Some(Value::Object(map)),
+            Some(_) => return Err("params must be a JSON object".to_string()),
+            None => None,
+        };
+        (params, &rest[stream.byte_offset()..])
+    };
+
+    Ok(ColumnGeneratorRule {
+        schema: schema.to_string(),
+        table: table.to_string(),
+        column: column.to_string(),
+        generator: match params {
+            Some(params) => GeneratorRef::Spec(GeneratorSpec {
+                id: generator.to_string(),
+                locale: None,
+                params: Some(params),
+            }),
+            None => GeneratorRef::Id(generator.to_string()),
+        },
+        params: None,
+        transforms: parse_transforms(rest.trim())?,
+        entity: None,
+    })
+}
+
+/// `-` or a JSON object.
+fn parse_params(value: &str) -> Result<Option<Value>, String> {
+    if value.trim() == "-" {
+        return Ok(None);
+    }
+    match serde_json::from_str::<Value>(value) {
+        Ok(Value::Object(map)) => Ok(Some(Value::Object(map))),
+        Ok(_) => Err("params must be a JSON object".to_string()),
+        Err(err) => Err(format!("invalid params: {err}")),
+    }
+}
+
+/// `-` or `<id> [params json]` entries separated by `;`.
+fn parse_transforms(value: &str) -> Result<Vec<TransformRule>, String> {
+    if value.is_empty() || value == "-" {
+        return Ok(Vec::new());
+    }
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let split = entry
+                .find(|ch: char| ch.is_whitespace() || ch == '{')
+                .unwrap_or(entry.len());
+            let (id, params) = entry.split_at(split);
+            let params = if params.trim().is_empty() {
+                None
+            } else {
+                parse_params(params)?
+            };
+            Ok(TransformRule {
+                transform: id.to_string(),
+                params,
+            })
+        })
+        .collect()
+}
+
+fn split_token(input: &str) -> (&str, &str) {
+    let input = input.trim_start();
+    let end = input.find(char::is_whitespace).unwrap_or(input.len());
+    input.split_at(end)
+}
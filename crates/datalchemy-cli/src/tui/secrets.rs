@@ -15,8 +15,16 @@ pub struct VaultMeta {
     pub created_at: Option<String>,
 }
 
+/// Env prefixes kept as object store credentials (see `/profiles storage`).
+pub const STORAGE_CREDENTIAL_PREFIXES: &[&str] = &["AWS_", "GOOGLE_", "AZURE_"];
+
 pub fn load_env_file(path: &Path) -> Result<std::collections::BTreeMap<String, String>, CliError> {
     let content = std::fs::read_to_string(path)?;
+    Ok(parse_env(&content))
+}
+
+/// Parse `KEY=value` lines, skipping blanks and `#` comments.
+pub fn parse_env(content: &str) -> std::collections::BTreeMap<String, String> {
     let mut values = std::collections::BTreeMap::new();
     for line in content.lines() {
         let line = line.trim();
@@ -31,7 +39,30 @@ pub fn load_env_file(path: &Path) -> Result<std::collections::BTreeMap<String, S
         }
         values.insert(key.to_string(), value.to_string());
     }
-    Ok(values)
+    values
+}
+
+/// Object store credentials among `values` (AWS/GCS/Azure variables).
+pub fn storage_credentials(
+    values: &std::collections::BTreeMap<String, String>,
+) -> std::collections::BTreeMap<String, String> {
+    values
+        .iter()
+        .filter(|(key, _)| {
+            STORAGE_CREDENTIAL_PREFIXES
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Encode credentials back to `KEY=value` lines for the vault.
+pub fn format_env(values: &std::collections::BTreeMap<String, String>) -> String {
+    values
+        .iter()
+        .map(|(key, value)| format!("{key}={value}\n"))
+        .collect()
 }
 
 pub fn encrypt_to_file(path: &Path, passphrase: &str, plaintext: &str) -> Result<(), CliError> {
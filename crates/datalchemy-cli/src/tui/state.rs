@@ -1,14 +1,22 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
 
 use chrono::Local;
+use datalchemy_core::DatabaseSchema;
+use datalchemy_eval::EvaluationResult;
+use datalchemy_generate::{GenerationError, GenerationProgress, GenerationResult};
 
 use crate::CliError;
+use crate::i18n::Msg;
 use crate::tui::secrets::load_env_file;
 use crate::tui::utils::append_line;
 use crate::workspace::{
-    ApprovalPolicy, LlmModels, LlmProvider, PrivacyMode, WorkspaceMode, WorkspacePaths,
-    WorkspaceSettings, WriteIntent, load_or_create_llm_models, load_or_create_profiles,
-    load_or_create_settings, write_json_atomic,
+    ApprovalPolicy, EvalManifest, LlmModels, OutManifest, PrivacyMode, RunManifest, WorkspaceMode,
+    WorkspacePaths, WorkspaceSettings, WriteIntent, load_or_create_llm_models,
+    load_or_create_profiles, load_or_create_settings, write_json_atomic,
 };
 
 pub const MAX_MESSAGES: usize = 2000;
@@ -26,6 +34,10 @@ pub enum InputMode {
 pub enum UiState {
     Normal,
     Setup(SetupStep),
+    /// Full-screen table viewer opened by `/out preview`.
+    Preview(PreviewState),
+    /// Side-by-side run comparison opened by `/runs compare`.
+    Compare(CompareState),
 }
 
 #[derive(Debug, Clone)]
@@ -72,6 +84,117 @@ impl PromptContext {
     }
 }
 
+/// First rows of one CSV file of an output.
+#[derive(Debug, Clone)]
+pub struct PreviewTable {
+    pub file: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Data rows in the file, including the ones not loaded.
+    pub total_rows: usize,
+}
+
+/// Scroll position of the preview viewer.
+#[derive(Debug, Clone)]
+pub struct PreviewState {
+    pub out_id: String,
+    pub tables: Vec<PreviewTable>,
+    pub selected: usize,
+    pub row_offset: usize,
+    pub col_offset: usize,
+}
+
+impl PreviewState {
+    pub fn new(out_id: String, tables: Vec<PreviewTable>) -> Self {
+        Self {
+            out_id,
+            tables,
+            selected: 0,
+            row_offset: 0,
+            col_offset: 0,
+        }
+    }
+
+    pub fn table(&self) -> Option<&PreviewTable> {
+        self.tables.get(self.selected)
+    }
+
+    /// Switch to the next (`forward`) or previous file, from the top-left.
+    pub fn cycle_table(&mut self, forward: bool) {
+        if self.tables.is_empty() {
+            return;
+        }
+        let len = self.tables.len();
+        self.selected = if forward {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+        self.row_offset = 0;
+        self.col_offset = 0;
+    }
+
+    pub fn scroll_rows(&mut self, delta: isize) {
+        let max = self
+            .table()
+            .map(|table| table.rows.len().saturating_sub(1))
+            .unwrap_or(0);
+        self.row_offset = self.row_offset.saturating_add_signed(delta).min(max);
+    }
+
+    pub fn scroll_cols(&mut self, delta: isize) {
+        let max = self
+            .table()
+            .map(|table| table.headers.len().saturating_sub(1))
+            .unwrap_or(0);
+        self.col_offset = self.col_offset.saturating_add_signed(delta).min(max);
+    }
+}
+
+/// How a line of the run comparison is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareKind {
+    Section,
+    Same,
+    /// Only in the left (before) run.
+    Removed,
+    /// Only in the right (after) run.
+    Added,
+    Changed,
+}
+
+/// One row of the comparison: the left and right pane side by side.
+#[derive(Debug, Clone)]
+pub struct CompareLine {
+    pub left: String,
+    pub right: String,
+    pub kind: CompareKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompareState {
+    pub run_a: String,
+    pub run_b: String,
+    pub lines: Vec<CompareLine>,
+    pub scroll: usize,
+}
+
+impl CompareState {
+    pub fn new(run_a: String, run_b: String, lines: Vec<CompareLine>) -> Self {
+        Self {
+            run_a,
+            run_b,
+            lines,
+            scroll: 0,
+        }
+    }
+
+    pub fn scroll_lines(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PaletteEntry {
     pub command: String,
@@ -82,6 +205,12 @@ pub enum AppEvent {
     Log(String),
     SchemasLoaded(Result<Vec<String>, String>),
     IntrospectionDone(Result<(), String>),
+    GenerationProgress(GenerationProgress),
+    GenerationDone(Result<Box<GenerationResult>, Box<GenerationError>>),
+    /// Schema of a `/introspect`; `None` once cancelled.
+    IntrospectDone(Result<Option<Box<DatabaseSchema>>, CliError>),
+    EvalDone(Result<Box<EvaluationResult>, CliError>),
+    #[allow(dead_code)]
     AsyncTaskDone(String),
 }
 
@@ -89,9 +218,105 @@ pub enum AppEvent {
 #[derive(Debug, Clone)]
 pub struct ActiveTask {
     pub label: String,
+    #[allow(dead_code)]
     pub started_at: String,
 }
 
+/// Rows of one table of a running `/generate`.
+#[derive(Debug, Clone)]
+pub struct TableProgress {
+    /// `schema.table`.
+    pub name: String,
+    pub rows: u64,
+    pub total: u64,
+    pub finished: bool,
+}
+
+/// `/generate` running on the runtime; finished by `AppEvent::GenerationDone`.
+#[derive(Debug)]
+pub struct GenerationTask {
+    pub out_id: String,
+    pub mode: String,
+    pub final_dir: PathBuf,
+    pub manifest: OutManifest,
+    /// `--top-slowest` value, listed once the run succeeds.
+    pub top_slowest: Option<String>,
+    pub cancel: Arc<AtomicBool>,
+    pub started: Instant,
+    pub tables: Vec<TableProgress>,
+}
+
+/// `/introspect` running on the runtime; finished by `AppEvent::IntrospectDone`.
+#[derive(Debug)]
+pub struct IntrospectTask {
+    pub run_id: String,
+    pub run_dir: PathBuf,
+    pub manifest: RunManifest,
+    /// `--strict`: foreign key cycles fail the run.
+    pub strict: bool,
+    pub cancel: Arc<AtomicBool>,
+    pub started: Instant,
+}
+
+/// `/eval` running on the runtime; finished by `AppEvent::EvalDone`.
+#[derive(Debug)]
+pub struct EvalTask {
+    pub eval_dir: PathBuf,
+    pub manifest: EvalManifest,
+    pub cancel: Arc<AtomicBool>,
+    pub started: Instant,
+}
+
+impl GenerationTask {
+    pub fn apply(&mut self, event: GenerationProgress) {
+        match event {
+            GenerationProgress::Planned(planned) => {
+                self.tables = planned
+                    .into_iter()
+                    .map(|table| TableProgress {
+                        name: format!("{}.{}", table.schema, table.table),
+                        rows: 0,
+                        total: table.rows,
+                        finished: false,
+                    })
+                    .collect();
+            }
+            GenerationProgress::TableStarted { schema, table } => {
+                if let Some(progress) = self.table_mut(&schema, &table) {
+                    progress.rows = 0;
+                }
+            }
+            GenerationProgress::Rows {
+                schema,
+                table,
+                rows,
+            } => {
+                if let Some(progress) = self.table_mut(&schema, &table) {
+                    progress.rows = rows;
+                }
+            }
+            GenerationProgress::TableFinished {
+                schema,
+                table,
+                rows,
+                ..
+            } => {
+                if let Some(progress) = self.table_mut(&schema, &table) {
+                    progress.rows = rows;
+                    progress.finished = true;
+                }
+            }
+        }
+    }
+
+    fn table_mut(&mut self, schema: &str, table: &str) -> Option<&mut TableProgress> {
+        let name = format!("{schema}.{table}");
+        self.tables
+            .iter_mut()
+            .find(|progress| progress.name == name)
+    }
+}
+
 pub struct App {
     pub runtime: tokio::runtime::Handle,
     pub tx: tokio::sync::mpsc::UnboundedSender<AppEvent>,
@@ -105,6 +330,10 @@ pub struct App {
     pub mode: InputMode,
     pub should_quit: bool,
     pub session_conn: Option<String>,
+    /// Object store credentials loaded from `.env` or the vault (never persisted in clear).
+    pub session_storage: BTreeMap<String, String>,
+    /// LLM API keys by provider name, from the vault or `/secrets llm-key`.
+    pub session_llm_keys: BTreeMap<String, String>,
     pub last_out_id: Option<String>,
     pub ui_state: UiState,
     pub setup_profile_name: Option<String>,
@@ -114,6 +343,9 @@ pub struct App {
     pub available_schemas: Vec<String>,
     pub schema_picker_idx: usize,
     pub active_task: Option<ActiveTask>,
+    pub generation: Option<GenerationTask>,
+    pub introspection: Option<IntrospectTask>,
+    pub evaluation: Option<EvalTask>,
 }
 
 impl App {
@@ -154,6 +386,8 @@ impl App {
             mode: InputMode::Command,
             should_quit: false,
             session_conn: None,
+            session_storage: BTreeMap::new(),
+            session_llm_keys: BTreeMap::new(),
             last_out_id: None,
             ui_state,
             setup_profile_name: None,
@@ -163,6 +397,9 @@ impl App {
             available_schemas: Vec::new(),
             schema_picker_idx: 0,
             active_task: None,
+            generation: None,
+            introspection: None,
+            evaluation: None,
         })
     }
 
@@ -319,6 +556,11 @@ impl App {
         matches!(self.ui_state, UiState::Setup(_))
     }
 
+    /// Whether a full-screen viewer (`/out preview`, `/runs compare`) is open.
+    pub fn is_in_viewer(&self) -> bool {
+        matches!(self.ui_state, UiState::Preview(_) | UiState::Compare(_))
+    }
+
     pub fn show_header(&self) -> bool {
         matches!(self.ui_state, UiState::Normal)
     }
@@ -348,22 +590,6 @@ impl App {
         .to_string()
     }
 
-    pub fn llm_display(&self) -> String {
-        if !self.settings.llm_enabled {
-            return "OFF".to_string();
-        }
-        let provider = match self.settings.llm_provider {
-            LlmProvider::Gemini => "gemini",
-            LlmProvider::Off => "off",
-        };
-        let model = self
-            .settings
-            .llm_model
-            .clone()
-            .unwrap_or_else(|| "default".to_string());
-        format!("{provider}/{model}")
-    }
-
     pub fn write_profile_config(&self, dir: &Path) -> Result<(), CliError> {
         let Some(profile_name) = &self.settings.active_profile else {
             return Ok(());
@@ -395,12 +621,11 @@ impl App {
             return Ok(conn);
         }
         let env_path = PathBuf::from(".env");
-        if env_path.exists() {
-            if let Ok(values) = load_env_file(&env_path) {
-                if let Some(conn) = values.get("DATABASE_URL") {
-                    return Ok(conn.clone());
-                }
-            }
+        if env_path.exists()
+            && let Ok(values) = load_env_file(&env_path)
+            && let Some(conn) = values.get("DATABASE_URL")
+        {
+            return Ok(conn.clone());
         }
         Err("missing connection string. use /db session or set DATABASE_URL (or .env).".to_string())
     }
@@ -415,4 +640,38 @@ impl App {
     pub fn finish_task(&mut self) {
         self.active_task = None;
     }
+
+    /// Name and cancel flag of the running `/introspect`, `/generate` or
+    /// `/eval`; only one runs at a time.
+    pub fn running_operation(&self) -> Option<(Msg, &Arc<AtomicBool>)> {
+        if let Some(task) = &self.introspection {
+            Some((Msg::OperationIntrospection, &task.cancel))
+        } else if let Some(task) = &self.generation {
+            Some((Msg::OperationGeneration, &task.cancel))
+        } else {
+            self.evaluation
+                .as_ref()
+                .map(|task| (Msg::OperationEvaluation, &task.cancel))
+        }
+    }
+
+    /// `error[<code>]: <message>` with the `/explain` hint.
+    pub fn push_error(&mut self, err: &CliError) {
+        let code = err.code();
+        self.push_message(format!("error[{code}]: {err} (/explain {code})"));
+    }
+
+    /// `msg` in the workspace language.
+    pub fn text(&self, msg: Msg) -> &'static str {
+        msg.text(self.settings.language)
+    }
+
+    pub fn say(&mut self, msg: Msg) {
+        self.push_message(self.text(msg));
+    }
+
+    /// `msg` with its `{}` placeholders filled from `args`.
+    pub fn say_with(&mut self, msg: Msg, args: &[&dyn std::fmt::Display]) {
+        self.push_message(msg.fill(self.settings.language, args));
+    }
 }
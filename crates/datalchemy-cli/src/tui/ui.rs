@@ -1,10 +1,14 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, Table, Wrap};
 
+use crate::i18n::Msg;
 use crate::tui::commands::command_palette_matches;
-use crate::tui::state::{App, InputMode, PaletteEntry, SetupStep, UiState};
+use crate::tui::state::{
+    App, CompareKind, CompareState, GenerationTask, InputMode, PaletteEntry, PreviewState,
+    SetupStep, UiState,
+};
 use crate::tui::utils::clipped_input;
 
 pub const INPUT_HEIGHT: u16 = 3;
@@ -13,12 +17,26 @@ pub const HEADER_HEIGHT: u16 = 6;
 pub const HEADER_WIDTH: u16 = 62;
 /// Dynamic palette limit: show up to 20 entries (scrollable).
 pub const MAX_PALETTE_LINES: usize = 20;
+/// Table lines of the `/generate` progress panel; the rest scroll with the run.
+pub const MAX_PROGRESS_TABLES: usize = 8;
+const PROGRESS_BAR_WIDTH: usize = 20;
+/// Widest column of the preview table; longer values end in `…`.
+pub const MAX_PREVIEW_COL_WIDTH: usize = 30;
 
 const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 pub fn draw_ui(frame: &mut ratatui::Frame, app: &App) {
     let size = frame.size();
 
+    if let UiState::Preview(preview) = &app.ui_state {
+        render_preview(frame, preview);
+        return;
+    }
+    if let UiState::Compare(compare) = &app.ui_state {
+        render_compare(frame, compare);
+        return;
+    }
+
     // Setup mode logic
     if app.is_in_setup() {
         match app.ui_state {
@@ -179,7 +197,12 @@ pub fn draw_ui(frame: &mut ratatui::Frame, app: &App) {
             .saturating_sub(header_height + INPUT_HEIGHT + FOOTER_HEIGHT + 3) as usize,
     );
     let palette_height = palette.len().min(max_palette) as u16;
-    let bottom_reserved = INPUT_HEIGHT + FOOTER_HEIGHT + palette_height + 1;
+    let progress_height = app
+        .generation
+        .as_ref()
+        .map(|task| task.tables.len().min(MAX_PROGRESS_TABLES) as u16 + 1)
+        .unwrap_or(0);
+    let bottom_reserved = INPUT_HEIGHT + FOOTER_HEIGHT + palette_height + progress_height + 1;
 
     let body_height = size
         .height
@@ -193,6 +216,7 @@ pub fn draw_ui(frame: &mut ratatui::Frame, app: &App) {
             Constraint::Length(header_height),
             Constraint::Length(body_height),
             Constraint::Length(1), // spacer
+            Constraint::Length(progress_height),
             Constraint::Length(INPUT_HEIGHT),
             Constraint::Length(FOOTER_HEIGHT),
             Constraint::Length(palette_height),
@@ -218,15 +242,19 @@ pub fn draw_ui(frame: &mut ratatui::Frame, app: &App) {
         frame.render_widget(scrollbar, layout[2]);
     }
 
-    let (input_area, cursor) = render_input_bar(app, layout[3]);
-    frame.render_widget(input_area, layout[3]);
+    if let Some(task) = &app.generation {
+        frame.render_widget(render_progress(task, app.spinner_idx), layout[3]);
+    }
+
+    let (input_area, cursor) = render_input_bar(app, layout[4]);
+    frame.render_widget(input_area, layout[4]);
 
     let status_line = render_status_line(app);
-    frame.render_widget(status_line, layout[4]);
+    frame.render_widget(status_line, layout[5]);
 
     if palette_height > 0 {
         let palette_view = render_palette(&palette, app.palette_select, max_palette);
-        frame.render_widget(palette_view, layout[5]);
+        frame.render_widget(palette_view, layout[6]);
     }
     if let Some((x, y)) = cursor {
         frame.set_cursor(x, y);
@@ -250,6 +278,264 @@ fn render_centered_screen(frame: &mut ratatui::Frame, lines: Vec<Line<'static>>)
     frame.render_widget(p, layout[1]);
 }
 
+/// Elapsed time and one bar per table of the running `/generate`.
+fn render_progress(task: &GenerationTask, spinner_idx: usize) -> Paragraph<'static> {
+    let elapsed = task.started.elapsed().as_secs();
+    let done = task.tables.iter().filter(|table| table.finished).count();
+    let spinner = SPINNER_FRAMES[spinner_idx % SPINNER_FRAMES.len()];
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{spinner} generating {} ({}) ", task.out_id, task.mode),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            format!(
+                "· {:02}:{:02} · {done}/{} tables · Esc to cancel",
+                elapsed / 60,
+                elapsed % 60,
+                task.tables.len()
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+
+    // Keep the table being generated in view, with the last finished ones above.
+    let current = task
+        .tables
+        .iter()
+        .position(|table| !table.finished)
+        .unwrap_or(task.tables.len().saturating_sub(1));
+    let start = current
+        .saturating_sub(2)
+        .min(task.tables.len().saturating_sub(MAX_PROGRESS_TABLES));
+    for (idx, table) in task
+        .tables
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(MAX_PROGRESS_TABLES)
+    {
+        let ratio = if table.total == 0 {
+            if table.finished { 1.0 } else { 0.0 }
+        } else {
+            (table.rows as f64 / table.total as f64).min(1.0)
+        };
+        let filled = (ratio * PROGRESS_BAR_WIDTH as f64).round() as usize;
+        let (mark, color) = if table.finished {
+            ("✓", Color::Green)
+        } else if idx == current {
+            ("▸", Color::Yellow)
+        } else {
+            ("·", Color::DarkGray)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {mark} "), Style::default().fg(color)),
+            Span::raw(format!("{:<32} ", fit_cell(&table.name, 32))),
+            Span::styled("█".repeat(filled), Style::default().fg(color)),
+            Span::styled(
+                "░".repeat(PROGRESS_BAR_WIDTH - filled),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!(" {:>3.0}% {}/{}", ratio * 100.0, table.rows, table.total),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    Paragraph::new(Text::from(lines))
+}
+
+fn render_preview(frame: &mut ratatui::Frame, preview: &PreviewState) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+
+    let mut tabs = vec![Span::styled(
+        format!("OUTPUT PREVIEW: {}  ", preview.out_id),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    for (idx, table) in preview.tables.iter().enumerate() {
+        let style = if idx == preview.selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        tabs.push(Span::styled(format!(" {} ", table.file), style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(tabs)), layout[0]);
+
+    let Some(table) = preview.table() else {
+        return;
+    };
+    let area = layout[1];
+    // Borders take two lines, the header row one more.
+    let visible_rows = (area.height as usize).saturating_sub(3);
+    let row_end = (preview.row_offset + visible_rows).min(table.rows.len());
+    let rows = &table.rows[preview.row_offset.min(row_end)..row_end];
+
+    // Columns from `col_offset` that fit, each as wide as its widest value.
+    let mut columns = Vec::new();
+    let mut used = 0;
+    for col in preview.col_offset..table.headers.len() {
+        let width = std::iter::once(&table.headers[col])
+            .chain(rows.iter().filter_map(|row| row.get(col)))
+            .map(|value| value.chars().count())
+            .max()
+            .unwrap_or(0)
+            .clamp(3, MAX_PREVIEW_COL_WIDTH);
+        if !columns.is_empty() && used + width + 1 > (area.width as usize).saturating_sub(2) {
+            break;
+        }
+        used += width + 1;
+        columns.push((col, width));
+    }
+
+    let header = Row::new(
+        columns
+            .iter()
+            .map(|&(col, width)| Cell::from(fit_cell(&table.headers[col], width))),
+    )
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    let body = rows.iter().map(|row| {
+        Row::new(columns.iter().map(|&(col, width)| {
+            Cell::from(fit_cell(
+                row.get(col).map(String::as_str).unwrap_or(""),
+                width,
+            ))
+        }))
+    });
+    let widths: Vec<Constraint> = columns
+        .iter()
+        .map(|&(_, width)| Constraint::Length(width as u16))
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .border_type(BorderType::Rounded);
+    frame.render_widget(Table::new(body, widths).header(header).block(block), area);
+
+    let first_col = columns.first().map(|&(col, _)| col + 1).unwrap_or(0);
+    let last_col = columns.last().map(|&(col, _)| col + 1).unwrap_or(0);
+    let status = format!(
+        "rows {}-{} of {} loaded ({} in file) · columns {}-{} of {} · ↑↓ PgUp/PgDn rows · ←→ columns · Tab file · Esc close",
+        if rows.is_empty() {
+            0
+        } else {
+            preview.row_offset + 1
+        },
+        row_end,
+        table.rows.len(),
+        table.total_rows,
+        first_col,
+        last_col,
+        table.headers.len(),
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            status,
+            Style::default().fg(Color::DarkGray),
+        ))),
+        layout[2],
+    );
+}
+
+fn render_compare(frame: &mut ratatui::Frame, compare: &CompareState) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(layout[1]);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!("RUN COMPARE: {} -> {}", compare.run_a, compare.run_b),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))),
+        layout[0],
+    );
+
+    let visible = (panes[0].height as usize).saturating_sub(2);
+    let end = (compare.scroll + visible).min(compare.lines.len());
+    let window = &compare.lines[compare.scroll.min(end)..end];
+    for (pane, run_id, left) in [
+        (panes[0], &compare.run_a, true),
+        (panes[1], &compare.run_b, false),
+    ] {
+        let lines: Vec<Line<'static>> = window
+            .iter()
+            .map(|line| {
+                let style = match line.kind {
+                    CompareKind::Section => Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                    CompareKind::Same => Style::default().fg(Color::Gray),
+                    CompareKind::Removed => Style::default().fg(Color::Red),
+                    CompareKind::Added => Style::default().fg(Color::Green),
+                    CompareKind::Changed => Style::default().fg(Color::Yellow),
+                };
+                let text = if left { &line.left } else { &line.right };
+                Line::from(Span::styled(text.clone(), style))
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .border_type(BorderType::Rounded)
+            .title(Span::styled(
+                format!(" {run_id} "),
+                Style::default().fg(Color::Cyan),
+            ));
+        frame.render_widget(Paragraph::new(Text::from(lines)).block(block), pane);
+    }
+
+    let status = format!(
+        "lines {}-{} of {} · ↑↓ PgUp/PgDn scroll · Esc close",
+        if window.is_empty() {
+            0
+        } else {
+            compare.scroll + 1
+        },
+        end,
+        compare.lines.len(),
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            status,
+            Style::default().fg(Color::DarkGray),
+        ))),
+        layout[2],
+    );
+}
+
+/// `value` on one line, cut to `width` characters.
+fn fit_cell(value: &str, width: usize) -> String {
+    let value = value.replace(['\n', '\r'], " ");
+    if value.chars().count() <= width {
+        return value;
+    }
+    let mut cut: String = value.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
 fn render_header(app: &App) -> Paragraph<'static> {
     let profile_display = app.profile_display();
     let model_display = app
@@ -312,7 +598,7 @@ fn render_body(app: &App, height: usize) -> Paragraph<'static> {
         .iter()
         .map(|line| {
             if line.starts_with('►') {
-                let text = line.trim_start_matches(|c| c == '►' || c == ' ');
+                let text = line.trim_start_matches(['►', ' ']);
                 Line::from(vec![
                     Span::styled("●", Style::default().fg(Color::Green)),
                     Span::raw(" "),
@@ -471,9 +757,9 @@ fn render_status_line(app: &App) -> Paragraph<'static> {
     match &app.mode {
         InputMode::Command => {
             let left = if app.show_header() {
-                "Tip: / for commands · Tab autocomplete · Esc back"
+                app.text(Msg::StatusTip)
             } else {
-                "Setup Mode · Esc to go back"
+                app.text(Msg::StatusSetup)
             };
 
             let status = format!(
@@ -495,10 +781,6 @@ fn render_status_line(app: &App) -> Paragraph<'static> {
     }
 }
 
-fn render_footer(app: &App, area: Rect) -> (Paragraph<'static>, Option<(u16, u16)>) {
-    render_input_bar(app, area)
-}
-
 fn render_palette(
     entries: &[PaletteEntry],
     selected_idx: usize,
@@ -536,46 +818,46 @@ fn render_palette(
         .collect();
 
     // Show scroll indicators if there are hidden entries
-    if start_idx > 0 {
-        if let Some(first) = lines.first_mut() {
-            *first = Line::from(vec![
-                Span::styled("▲ ", Style::default().fg(Color::DarkGray)),
-                Span::styled(
-                    format!(
-                        "{:<22}  {}",
-                        entries[start_idx].command, entries[start_idx].description
-                    ),
-                    if start_idx == selected_idx {
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(Color::DarkGray)
-                    },
+    if start_idx > 0
+        && let Some(first) = lines.first_mut()
+    {
+        *first = Line::from(vec![
+            Span::styled("▲ ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(
+                    "{:<22}  {}",
+                    entries[start_idx].command, entries[start_idx].description
                 ),
-            ]);
-        }
+                if start_idx == selected_idx {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                },
+            ),
+        ]);
     }
-    if end_idx < total_cnt {
-        if let Some(last) = lines.last_mut() {
-            let last_idx = end_idx - 1;
-            *last = Line::from(vec![
-                Span::styled("▼ ", Style::default().fg(Color::DarkGray)),
-                Span::styled(
-                    format!(
-                        "{:<22}  {}",
-                        entries[last_idx].command, entries[last_idx].description
-                    ),
-                    if last_idx == selected_idx {
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(Color::DarkGray)
-                    },
+    if end_idx < total_cnt
+        && let Some(last) = lines.last_mut()
+    {
+        let last_idx = end_idx - 1;
+        *last = Line::from(vec![
+            Span::styled("▼ ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(
+                    "{:<22}  {}",
+                    entries[last_idx].command, entries[last_idx].description
                 ),
-            ]);
-        }
+                if last_idx == selected_idx {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                },
+            ),
+        ]);
     }
 
     Paragraph::new(Text::from(lines))
@@ -3,6 +3,7 @@ use std::io::{BufRead, Write};
 use std::path::Path;
 
 use crate::CliError;
+use crate::tui::state::PreviewTable;
 
 pub fn list_dirs(path: &Path) -> Result<Vec<String>, CliError> {
     if !path.exists() {
@@ -11,10 +12,10 @@ pub fn list_dirs(path: &Path) -> Result<Vec<String>, CliError> {
     let mut entries = Vec::new();
     for entry in std::fs::read_dir(path)? {
         let entry = entry?;
-        if entry.file_type()?.is_dir() {
-            if let Some(name) = entry.file_name().to_str() {
-                entries.push(name.to_string());
-            }
+        if entry.file_type()?.is_dir()
+            && let Some(name) = entry.file_name().to_str()
+        {
+            entries.push(name.to_string());
         }
     }
     entries.sort();
@@ -28,10 +29,10 @@ pub fn list_preview_files(path: &Path) -> Result<Vec<String>, CliError> {
     let mut entries = Vec::new();
     for entry in std::fs::read_dir(path)? {
         let entry = entry?;
-        if entry.file_type()?.is_file() {
-            if let Some(name) = entry.file_name().to_str() {
-                entries.push(name.to_string());
-            }
+        if entry.file_type()?.is_file()
+            && let Some(name) = entry.file_name().to_str()
+        {
+            entries.push(name.to_string());
         }
     }
     entries.sort();
@@ -74,10 +75,10 @@ pub fn read_tail_lines(path: &Path, max_lines: usize) -> Result<Vec<String>, Cli
 }
 
 pub fn append_line(path: &Path, line: &str) -> Result<(), CliError> {
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            std::fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.exists()
+    {
+        std::fs::create_dir_all(parent)?;
     }
     let mut file = OpenOptions::new().create(true).append(true).open(path)?;
     writeln!(file, "{}", line)?;
@@ -169,40 +170,39 @@ pub fn clipped_input(
     (visible, cursor_x)
 }
 
-/// Read a CSV file and return a formatted preview (first N rows with headers).
-pub fn csv_preview(path: &Path, max_rows: usize) -> Result<Vec<String>, CliError> {
-    let file = std::fs::File::open(path)?;
-    let reader = std::io::BufReader::new(file);
-    let mut lines_iter = reader.lines();
-    let mut result = Vec::new();
-
-    // Read header
-    let header = match lines_iter.next() {
-        Some(Ok(h)) => h,
-        _ => return Ok(vec!["(empty file)".to_string()]),
-    };
-
-    let columns: Vec<&str> = header.split(',').collect();
-    let col_count = columns.len();
-    result.push(format!("  {}", columns.join(" | ")));
-    result.push(format!("  {}", "-".repeat(header.len().min(120))));
-
-    let mut row_count = 0;
-    for line in lines_iter.take(max_rows) {
-        if let Ok(l) = line {
-            let fields: Vec<&str> = l.splitn(col_count, ',').collect();
-            result.push(format!("  {}", fields.join(" | ")));
-            row_count += 1;
+/// Read the header and the first `max_rows` records of a CSV file; the rest
+/// is only counted.
+pub fn csv_preview(path: &Path, max_rows: usize) -> Result<PreviewTable, CliError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .map_err(csv_error)?;
+    let headers = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(str::to_string)
+        .collect();
+    let mut rows = Vec::new();
+    let mut total_rows = 0;
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        if rows.len() < max_rows {
+            rows.push(record.iter().map(str::to_string).collect());
         }
-    }
-
-    // Count remaining
-    let total_file = std::fs::File::open(path)?;
-    let total_reader = std::io::BufReader::new(total_file);
-    let total_lines = total_reader.lines().count().saturating_sub(1); // minus header
-    if total_lines > row_count {
-        result.push(format!("  ... ({} more rows)", total_lines - row_count));
-    }
+        total_rows += 1;
+    }
+    Ok(PreviewTable {
+        file: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        headers,
+        rows,
+        total_rows,
+    })
+}
 
-    Ok(result)
+fn csv_error(err: csv::Error) -> CliError {
+    CliError::Io(err.into())
 }
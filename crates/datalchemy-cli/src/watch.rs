@@ -0,0 +1,208 @@
+//! `datalchemy watch`: edit/preview loop for plan authors.
+//!
+//! Polls the workspace plan (active plan by default) and, whenever its
+//! contents change, re-validates it against the schema of its run and prints
+//! the issues that appeared (`+`) or went away (`-`) since the previous
+//! version. A valid plan is then generated with at most `--rows` rows per
+//! target into a preview directory, replacing the previous preview. Parse and
+//! generation errors are printed and the loop waits for the next save; stop
+//! with Ctrl+C.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use datalchemy_core::DatabaseSchema;
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+use datalchemy_plan::{IssueSeverity, Plan, ValidationIssue, ValidationReport};
+use serde_json::Value;
+
+use crate::output::Output;
+use crate::plan::{read_schema, validation_report};
+use crate::workspace::HeadlessWorkspace;
+use crate::{CliError, WatchArgs};
+
+pub fn run_watch(args: WatchArgs, output: &mut Output) -> Result<(), CliError> {
+    if output.is_json() {
+        return Err(CliError::InvalidConfig(
+            "watch prints a stream of reports; --message-format json is not supported".to_string(),
+        ));
+    }
+    if args.rows == 0 {
+        return Err(CliError::InvalidConfig(
+            "--rows must be at least 1".to_string(),
+        ));
+    }
+    let workspace = HeadlessWorkspace::open(args.workspace.clone())?;
+    let plan_id = workspace.plan_id(args.plan_id.as_deref())?;
+    let run_id = match args.run_id.as_deref() {
+        Some(run_id) => run_id.to_string(),
+        None => workspace.plan_run_id(&plan_id)?,
+    };
+    let plan_path = workspace.plan_path(&plan_id);
+    let schema = read_schema(&workspace.schema_path(&run_id))?;
+    let preview_dir = args
+        .out_dir
+        .clone()
+        .unwrap_or_else(|| std::env::temp_dir().join(format!("datalchemy_watch_{plan_id}")));
+
+    eprintln!(
+        "watching {} (run {run_id}); preview: {} rows per table in {}. Ctrl+C to stop.",
+        plan_path.display(),
+        args.rows,
+        preview_dir.display()
+    );
+    let interval = Duration::from_millis(args.interval_ms.max(50));
+    let mut last_contents: Option<String> = None;
+    let mut last_issues: Option<BTreeSet<String>> = None;
+    let mut last_preview: Option<PathBuf> = None;
+    loop {
+        let contents = match std::fs::read_to_string(&plan_path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                if last_contents.take().is_some() {
+                    eprintln!("[{}] cannot read plan: {err}", timestamp());
+                }
+                std::thread::sleep(interval);
+                continue;
+            }
+        };
+        if last_contents.as_deref() == Some(contents.as_str()) {
+            std::thread::sleep(interval);
+            continue;
+        }
+        last_contents = Some(contents.clone());
+
+        println!("[{}] {} changed", timestamp(), plan_path.display());
+        let plan_json: Value = match serde_json::from_str(&contents) {
+            Ok(value) => value,
+            Err(err) => {
+                println!("  plan.json is not valid JSON: {err}");
+                continue;
+            }
+        };
+        let report = match validation_report(&plan_json, &schema) {
+            Ok(report) => report,
+            Err(err) => {
+                println!("  {err}");
+                continue;
+            }
+        };
+        let issues = issue_lines(&report);
+        for line in report_diff(last_issues.as_ref(), &issues) {
+            println!("  {line}");
+        }
+        println!(
+            "  validation: {} error(s), {} warning(s)",
+            report.errors.len(),
+            report.warnings.len()
+        );
+        last_issues = Some(issues);
+        if !report.is_ok() {
+            continue;
+        }
+
+        let mut plan: Plan = serde_json::from_value(plan_json)?;
+        cap_rows(&mut plan, args.rows);
+        match generate_preview(&schema, &plan, &preview_dir) {
+            Ok((run_dir, rows, duration)) => {
+                if let Some(previous) = last_preview.replace(run_dir.clone())
+                    && previous != run_dir
+                {
+                    let _ = std::fs::remove_dir_all(previous);
+                }
+                println!(
+                    "  preview: {} table(s), {rows} row(s) in {} ms -> {}",
+                    plan.targets.len(),
+                    duration.as_millis(),
+                    run_dir.display()
+                );
+            }
+            Err(err) => println!("  preview failed: {err}"),
+        }
+    }
+}
+
+/// Issues of `report` as `error|warning: code path (message)` lines.
+fn issue_lines(report: &ValidationReport) -> BTreeSet<String> {
+    report
+        .errors
+        .iter()
+        .chain(&report.warnings)
+        .map(issue_line)
+        .collect()
+}
+
+fn issue_line(issue: &ValidationIssue) -> String {
+    let severity = match issue.severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+    };
+    format!(
+        "{severity}: {} {} ({})",
+        issue.code, issue.path, issue.message
+    )
+}
+
+/// Issues gone since `previous` (`- `) and new ones (`+ `); every issue is
+/// new for the first version.
+fn report_diff(previous: Option<&BTreeSet<String>>, current: &BTreeSet<String>) -> Vec<String> {
+    let empty = BTreeSet::new();
+    let previous = previous.unwrap_or(&empty);
+    previous
+        .difference(current)
+        .map(|line| format!("- {line}"))
+        .chain(current.difference(previous).map(|line| format!("+ {line}")))
+        .collect()
+}
+
+/// Keep at most `rows` rows per target.
+fn cap_rows(plan: &mut Plan, rows: u64) {
+    for target in &mut plan.targets {
+        target.rows = target.rows.min(rows);
+    }
+}
+
+/// Generate `plan` under `preview_dir`; returns the run directory, the rows
+/// generated and the duration.
+fn generate_preview(
+    schema: &DatabaseSchema,
+    plan: &Plan,
+    preview_dir: &Path,
+) -> Result<(PathBuf, u64, Duration), CliError> {
+    let started = Instant::now();
+    let existing = dir_entries(preview_dir);
+    let result = GenerationEngine::new(GenerateOptions {
+        out_dir: preview_dir.to_path_buf(),
+        ..GenerateOptions::default()
+    })
+    .run(schema, plan);
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            // Drop the partial run directory of the failed preview.
+            for entry in dir_entries(preview_dir).difference(&existing) {
+                let _ = std::fs::remove_dir_all(entry);
+            }
+            return Err(err.into());
+        }
+    };
+    let rows = result
+        .report
+        .tables
+        .iter()
+        .map(|table| table.rows_generated)
+        .sum();
+    Ok((result.run_dir, rows, started.elapsed()))
+}
+
+fn dir_entries(dir: &Path) -> BTreeSet<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default()
+}
+
+fn timestamp() -> String {
+    Local::now().format("%H:%M:%S").to_string()
+}
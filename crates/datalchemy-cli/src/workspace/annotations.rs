@@ -0,0 +1,218 @@
+//! Tags and notes on runs, plans and outputs.
+//!
+//! They live in the artifact manifest (`run_manifest.json`, `plan.meta.json`,
+//! `out_manifest.json`); empty lists are not serialized, so manifests written
+//! before tags existed read as untagged.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::atomic::write_json_atomic;
+use super::manifests::{ArtifactNote, OutManifest, PlanMeta, RunManifest};
+use super::paths::WorkspacePaths;
+use super::signing::{ensure_resealable, reseal_file};
+use super::{WorkspaceError, WorkspaceResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotatedKind {
+    Run,
+    Plan,
+    Out,
+}
+
+impl AnnotatedKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Plan => "plan",
+            Self::Out => "output",
+        }
+    }
+
+    pub fn manifest_path(self, paths: &WorkspacePaths, id: &str) -> PathBuf {
+        match self {
+            Self::Run => paths.runs_dir.join(id).join("run_manifest.json"),
+            Self::Plan => paths.plans_dir.join(id).join("plan.meta.json"),
+            Self::Out => paths.out_dir.join(id).join("out_manifest.json"),
+        }
+    }
+}
+
+/// Tags and notes of one artifact.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Annotations {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: Vec<ArtifactNote>,
+}
+
+impl Annotations {
+    /// Whether every tag of `filter` is set (an empty filter matches all).
+    pub fn has_tags(&self, filter: &[String]) -> bool {
+        filter.iter().all(|tag| self.tags.contains(tag))
+    }
+}
+
+/// Annotations of `id`, or `None` when it has no manifest.
+pub fn read_annotations(
+    paths: &WorkspacePaths,
+    kind: AnnotatedKind,
+    id: &str,
+) -> WorkspaceResult<Option<Annotations>> {
+    let path = kind.manifest_path(paths, id);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// Trim and lowercase a tag; tags are single words (letters, digits, `-`,
+/// `_`, `.`, `/`, `:`).
+pub fn normalize_tag(tag: &str) -> WorkspaceResult<String> {
+    let tag = tag.trim().to_lowercase();
+    let valid = !tag.is_empty()
+        && tag
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, '-' | '_' | '.' | '/' | ':'));
+    if !valid {
+        return Err(WorkspaceError::Invalid(format!("invalid tag '{tag}'")));
+    }
+    Ok(tag)
+}
+
+/// Add `tags` to `id`; returns the resulting tag list.
+pub fn add_tags(
+    paths: &WorkspacePaths,
+    kind: AnnotatedKind,
+    id: &str,
+    tags: &[&str],
+) -> WorkspaceResult<Vec<String>> {
+    let tags = tags
+        .iter()
+        .map(|tag| normalize_tag(tag))
+        .collect::<WorkspaceResult<Vec<_>>>()?;
+    update_annotations(paths, kind, id, |annotations| {
+        for tag in tags {
+            if !annotations.tags.contains(&tag) {
+                annotations.tags.push(tag);
+            }
+        }
+        annotations.tags.sort();
+    })
+    .map(|annotations| annotations.tags)
+}
+
+/// Remove `tags` from `id`; returns the resulting tag list.
+pub fn remove_tags(
+    paths: &WorkspacePaths,
+    kind: AnnotatedKind,
+    id: &str,
+    tags: &[&str],
+) -> WorkspaceResult<Vec<String>> {
+    let tags = tags
+        .iter()
+        .map(|tag| normalize_tag(tag))
+        .collect::<WorkspaceResult<Vec<_>>>()?;
+    update_annotations(paths, kind, id, |annotations| {
+        annotations.tags.retain(|tag| !tags.contains(tag));
+    })
+    .map(|annotations| annotations.tags)
+}
+
+/// Append a note to `id`, or clear its notes when `text` is `None`.
+pub fn set_note(
+    paths: &WorkspacePaths,
+    kind: AnnotatedKind,
+    id: &str,
+    text: Option<&str>,
+) -> WorkspaceResult<()> {
+    let note = match text.map(str::trim) {
+        Some("") => {
+            return Err(WorkspaceError::Invalid("note text is empty".to_string()));
+        }
+        Some(text) => Some(ArtifactNote {
+            created_at: Utc::now().to_rfc3339(),
+            text: text.to_string(),
+        }),
+        None => None,
+    };
+    update_annotations(paths, kind, id, |annotations| match note {
+        Some(note) => annotations.notes.push(note),
+        None => annotations.notes.clear(),
+    })
+    .map(|_| ())
+}
+
+fn update_annotations(
+    paths: &WorkspacePaths,
+    kind: AnnotatedKind,
+    id: &str,
+    update: impl FnOnce(&mut Annotations),
+) -> WorkspaceResult<Annotations> {
+    let path = kind.manifest_path(paths, id);
+    if !path.exists() {
+        return Err(WorkspaceError::Invalid(format!(
+            "{} '{id}' has no {}",
+            kind.label(),
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("manifest")
+        )));
+    }
+    match kind {
+        AnnotatedKind::Run => update_manifest::<RunManifest>(&path, update),
+        AnnotatedKind::Plan => update_manifest::<PlanMeta>(&path, update),
+        AnnotatedKind::Out => {
+            // The manifest is covered by the output checksums.
+            let dir = paths.out_dir.join(id);
+            ensure_resealable(paths, &dir)?;
+            let annotations = update_manifest::<OutManifest>(&path, update)?;
+            reseal_file(paths, &dir, "out_manifest.json")?;
+            Ok(annotations)
+        }
+    }
+}
+
+/// Manifests carrying `tags` and `notes`.
+trait Annotated: Serialize + DeserializeOwned {
+    fn annotations_mut(&mut self) -> (&mut Vec<String>, &mut Vec<ArtifactNote>);
+}
+
+impl Annotated for RunManifest {
+    fn annotations_mut(&mut self) -> (&mut Vec<String>, &mut Vec<ArtifactNote>) {
+        (&mut self.tags, &mut self.notes)
+    }
+}
+
+impl Annotated for PlanMeta {
+    fn annotations_mut(&mut self) -> (&mut Vec<String>, &mut Vec<ArtifactNote>) {
+        (&mut self.tags, &mut self.notes)
+    }
+}
+
+impl Annotated for OutManifest {
+    fn annotations_mut(&mut self) -> (&mut Vec<String>, &mut Vec<ArtifactNote>) {
+        (&mut self.tags, &mut self.notes)
+    }
+}
+
+fn update_manifest<M: Annotated>(
+    path: &Path,
+    update: impl FnOnce(&mut Annotations),
+) -> WorkspaceResult<Annotations> {
+    let mut manifest: M = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let (tags, notes) = manifest.annotations_mut();
+    let mut annotations = Annotations {
+        tags: std::mem::take(tags),
+        notes: std::mem::take(notes),
+    };
+    update(&mut annotations);
+    tags.clone_from(&annotations.tags);
+    notes.clone_from(&annotations.notes);
+    write_json_atomic(path, &manifest)?;
+    Ok(annotations)
+}
@@ -0,0 +1,236 @@
+//! Workspace archives for `/workspace export|import`.
+//!
+//! An archive is a zstd-compressed tar with `config/`, `runs/`, `plans/`,
+//! `out/` and `eval/` of a workspace plus [`ARCHIVE_MANIFEST`], so a
+//! reproducible workspace can be handed to a teammate or attached to a bug
+//! report. `secrets/` (the vault) and `logs/` are never packed; profiles only
+//! hold redacted connection strings.
+//!
+//! Import merges into the current workspace: existing files are kept unless
+//! `overwrite` is set, and the eval history is merged by `eval_id`. Archives are
+//! checked before anything is written: every entry must be a regular file or
+//! directory under one of the packed directories.
+
+use std::fs::File;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use datalchemy_eval::history::{HISTORY_FILE, HistoryEntry, merge_history, read_history};
+
+use super::atomic::write_bytes_atomic;
+use super::manifests::{ARTIFACT_VERSION, CLI_VERSION};
+use super::{WorkspaceError, WorkspacePaths, WorkspaceResult};
+
+/// First entry of an archive.
+pub const ARCHIVE_MANIFEST: &str = "workspace_archive.json";
+/// Workspace directories packed into an archive.
+const ARCHIVED_DIRS: [&str; 5] = ["config", "runs", "plans", "out", "eval"];
+/// Workspace directories left out of an archive.
+const EXCLUDED_DIRS: [&str; 2] = ["secrets", "logs"];
+const ZSTD_LEVEL: i32 = 3;
+
+/// Contents of [`ARCHIVE_MANIFEST`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub artifact_version: String,
+    pub cli_version: String,
+    pub created_at: String,
+    pub files: usize,
+    pub bytes: u64,
+    pub excluded: Vec<String>,
+}
+
+/// What an import wrote into the workspace.
+#[derive(Debug, Clone)]
+pub struct ImportSummary {
+    pub manifest: ArchiveManifest,
+    pub imported: usize,
+    /// Files already in the workspace, kept as they were.
+    pub skipped: Vec<PathBuf>,
+    /// Entries of the eval history after the merge.
+    pub history_entries: Option<usize>,
+}
+
+/// Pack the workspace into `dest`.
+pub fn export_workspace(paths: &WorkspacePaths, dest: &Path) -> WorkspaceResult<ArchiveManifest> {
+    let dest_abs = std::path::absolute(dest)?;
+    for dir in ARCHIVED_DIRS {
+        if dest_abs.starts_with(std::path::absolute(paths.root.join(dir))?) {
+            return Err(WorkspaceError::Invalid(format!(
+                "archive cannot be written inside the workspace '{dir}' directory"
+            )));
+        }
+    }
+
+    let mut files = Vec::new();
+    for dir in ARCHIVED_DIRS {
+        collect_files(&paths.root, &paths.root.join(dir), &mut files)?;
+    }
+    let mut bytes = 0;
+    for (path, _) in &files {
+        bytes += std::fs::metadata(path)?.len();
+    }
+    let manifest = ArchiveManifest {
+        artifact_version: ARTIFACT_VERSION.to_string(),
+        cli_version: CLI_VERSION.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        files: files.len(),
+        bytes,
+        excluded: EXCLUDED_DIRS.iter().map(|dir| dir.to_string()).collect(),
+    };
+
+    if let Some(parent) = dest.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    let encoder = zstd::Encoder::new(File::create(dest)?, ZSTD_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, ARCHIVE_MANIFEST, manifest_bytes.as_slice())?;
+    for (path, name) in &files {
+        builder.append_path_with_name(path, name)?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(manifest)
+}
+
+/// Regular files under `dir` (recursively, sorted) with their path relative
+/// to `root`; symlinks and leftover `.tmp` files of atomic writes are skipped.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(PathBuf, PathBuf)>,
+) -> WorkspaceResult<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() && path.extension().is_none_or(|ext| ext != "tmp") {
+            let name = path
+                .strip_prefix(root)
+                .map_err(|_| {
+                    WorkspaceError::Invalid(format!("{} outside workspace", path.display()))
+                })?
+                .to_path_buf();
+            files.push((path, name));
+        }
+    }
+    Ok(())
+}
+
+/// Unpack an archive made by [`export_workspace`] into the workspace.
+pub fn import_workspace(
+    paths: &WorkspacePaths,
+    src: &Path,
+    overwrite: bool,
+) -> WorkspaceResult<ImportSummary> {
+    let manifest = check_archive(src)?;
+    paths.ensure_dirs()?;
+
+    let history_name = Path::new("eval").join(HISTORY_FILE);
+    let mut summary = ImportSummary {
+        manifest,
+        imported: 0,
+        skipped: Vec::new(),
+        history_entries: None,
+    };
+    let mut archive = tar::Archive::new(zstd::Decoder::new(File::open(src)?)?);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.into_owned();
+        if name == Path::new(ARCHIVE_MANIFEST) || !entry.header().entry_type().is_file() {
+            continue;
+        }
+        if name == history_name {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut entry, &mut content)?;
+            let theirs = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(HistoryEntry::from_line)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(history_error)?;
+            let path = paths.root.join(&history_name);
+            let ours = read_history(&path).map_err(history_error)?;
+            let merged = merge_history(ours, theirs);
+            let mut lines = String::new();
+            for entry in &merged {
+                lines.push_str(&entry.to_line().map_err(history_error)?);
+                lines.push('\n');
+            }
+            write_bytes_atomic(&path, lines.as_bytes())?;
+            summary.history_entries = Some(merged.len());
+            continue;
+        }
+
+        let dest = paths.root.join(&name);
+        if dest.exists() && !overwrite {
+            summary.skipped.push(name);
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&dest)?;
+        summary.imported += 1;
+    }
+    Ok(summary)
+}
+
+/// Read the manifest and check every entry name and type, without writing.
+fn check_archive(src: &Path) -> WorkspaceResult<ArchiveManifest> {
+    let mut archive = tar::Archive::new(zstd::Decoder::new(File::open(src)?)?);
+    let mut manifest = None;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.into_owned();
+        if name == Path::new(ARCHIVE_MANIFEST) {
+            manifest = Some(serde_json::from_reader(&mut entry)?);
+            continue;
+        }
+        let entry_type = entry.header().entry_type();
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            return Err(WorkspaceError::Invalid(format!(
+                "unsupported archive entry '{}' (only files and directories)",
+                name.display()
+            )));
+        }
+        let mut components = name.components();
+        let top = match components.next() {
+            Some(Component::Normal(top)) => top.to_string_lossy().into_owned(),
+            _ => String::new(),
+        };
+        let normal = components.all(|component| matches!(component, Component::Normal(_)));
+        if !normal || !ARCHIVED_DIRS.contains(&top.as_str()) {
+            return Err(WorkspaceError::Invalid(format!(
+                "archive entry '{}' is outside the packed directories ({})",
+                name.display(),
+                ARCHIVED_DIRS.join(", ")
+            )));
+        }
+    }
+    manifest.ok_or_else(|| {
+        WorkspaceError::Invalid(format!(
+            "{} is not a workspace archive (missing {ARCHIVE_MANIFEST})",
+            src.display()
+        ))
+    })
+}
+
+fn history_error(err: datalchemy_eval::EvalError) -> WorkspaceError {
+    WorkspaceError::Invalid(format!("eval history: {err}"))
+}
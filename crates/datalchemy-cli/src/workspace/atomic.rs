@@ -12,32 +12,55 @@ pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> WorkspaceResul
 }
 
 pub fn write_bytes_atomic(path: &Path, data: &[u8]) -> WorkspaceResult<()> {
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            create_dir_all(parent)?;
-        }
+    write_atomic(path, data, false)
+}
+
+/// Like [`write_bytes_atomic`], but the file is only readable by its owner
+/// (mode 0600 on Unix) from the moment it is created, before `data` is written.
+pub fn write_secret_atomic(path: &Path, data: &[u8]) -> WorkspaceResult<()> {
+    write_atomic(path, data, true)
+}
+
+fn write_atomic(path: &Path, data: &[u8], secret: bool) -> WorkspaceResult<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        create_dir_all(parent)?;
     }
 
     let tmp_path = temp_path(path)?;
-    let mut file = OpenOptions::new()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open(&tmp_path)?;
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if secret {
+        // A stale temp file would keep its permissions, so start from a new one.
+        match std::fs::remove_file(&tmp_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+        options.create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+    } else {
+        options.create(true).truncate(true);
+    }
+    let mut file = options.open(&tmp_path)?;
     file.write_all(data)?;
     file.sync_all()?;
 
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            sync_dir(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        sync_dir(parent)?;
     }
 
     std::fs::rename(&tmp_path, path)?;
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            sync_dir(parent)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        sync_dir(parent)?;
     }
 
     Ok(())
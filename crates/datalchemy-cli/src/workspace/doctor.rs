@@ -57,14 +57,14 @@ pub fn run_doctor(
             "active profile not set",
             Some("use /profiles set <name> or /db to configure one".to_string()),
         );
-    } else if let Some(active) = &settings.active_profile {
-        if !profiles.profiles.contains_key(active) {
-            report.push(
-                DoctorLevel::Error,
-                format!("active profile '{active}' not found in profiles.toml"),
-                Some("use /profiles to list or /db to create one".to_string()),
-            );
-        }
+    } else if let Some(active) = &settings.active_profile
+        && !profiles.profiles.contains_key(active)
+    {
+        report.push(
+            DoctorLevel::Error,
+            format!("active profile '{active}' not found in profiles.toml"),
+            Some("use /profiles to list or /db to create one".to_string()),
+        );
     }
 
     check_manifest_versions(&paths.runs_dir, "run_manifest.json", &mut report)?;
@@ -73,7 +73,11 @@ pub fn run_doctor(
     check_manifest_versions(&paths.eval_dir, "eval_manifest.json", &mut report)?;
     check_secret_permissions(&paths.vault_meta_path(), &mut report)?;
     check_secret_permissions(&paths.vault_db_path(), &mut report)?;
-    check_secret_permissions(&paths.vault_llm_path(), &mut report)?;
+    for provider in crate::llm::KEYED_PROVIDERS {
+        check_secret_permissions(&paths.vault_llm_path(provider.as_str()), &mut report)?;
+    }
+    check_secret_permissions(&paths.vault_storage_path(), &mut report)?;
+    check_secret_permissions(&paths.signing_key_path(), &mut report)?;
 
     Ok(report)
 }
@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::{
+    ArtifactStatus, OutManifest, PlanMeta, WorkspaceError, WorkspacePaths, WorkspaceResult,
+    WorkspaceSettings, load_or_create_settings, save_settings,
+};
+
+/// Workspace used by the headless subcommands (`plan`, `generate --workspace`,
+/// `eval --workspace`).
+///
+/// Artifacts are resolved like in the TUI: an explicit id, else the active one
+/// from `settings.toml`, else the most recent artifact on disk, so a CI job can
+/// chain `introspect --run-dir <ws>/runs`, `plan new`, `generate` and `eval`.
+#[derive(Debug, Clone)]
+pub struct HeadlessWorkspace {
+    pub paths: WorkspacePaths,
+    pub settings: WorkspaceSettings,
+}
+
+impl HeadlessWorkspace {
+    /// Open the workspace at `root`, creating its directories and settings
+    /// when missing.
+    pub fn open(root: PathBuf) -> WorkspaceResult<Self> {
+        let paths = WorkspacePaths::new(root);
+        paths.ensure_dirs()?;
+        let settings = load_or_create_settings(&paths)?;
+        Ok(Self { paths, settings })
+    }
+
+    pub fn save_settings(&self) -> WorkspaceResult<()> {
+        save_settings(&self.paths, &self.settings)
+    }
+
+    /// Introspection run: `explicit`, the active run or the latest run with a
+    /// `schema.json`.
+    pub fn run_id(&self, explicit: Option<&str>) -> WorkspaceResult<String> {
+        resolve(
+            explicit,
+            self.settings.active_run_id.as_deref(),
+            &self.paths.runs_dir,
+            "schema.json",
+            "run",
+        )
+    }
+
+    /// Plan: `explicit`, the active plan or the latest plan with a `plan.json`.
+    pub fn plan_id(&self, explicit: Option<&str>) -> WorkspaceResult<String> {
+        resolve(
+            explicit,
+            self.settings.active_plan_id.as_deref(),
+            &self.paths.plans_dir,
+            "plan.json",
+            "plan",
+        )
+    }
+
+    /// Generated output: `explicit` or the latest successful output.
+    pub fn out_id(&self, explicit: Option<&str>) -> WorkspaceResult<String> {
+        if let Some(id) = explicit {
+            return Ok(id.to_string());
+        }
+        let mut latest: Option<(SystemTime, String)> = None;
+        for (modified, out_id) in artifacts(&self.paths.out_dir, "out_manifest.json")? {
+            let manifest = self.out_manifest(&out_id)?;
+            if matches!(manifest.status, ArtifactStatus::Ok)
+                && latest.as_ref().is_none_or(|(time, _)| modified > *time)
+            {
+                latest = Some((modified, out_id));
+            }
+        }
+        latest.map(|(_, id)| id).ok_or_else(|| {
+            WorkspaceError::Invalid("no successful output in the workspace".to_string())
+        })
+    }
+
+    /// Run a plan was written for (`plan.meta.json`), else [`Self::run_id`].
+    pub fn plan_run_id(&self, plan_id: &str) -> WorkspaceResult<String> {
+        let meta_path = self.paths.plans_dir.join(plan_id).join("plan.meta.json");
+        if meta_path.exists() {
+            let meta: PlanMeta = serde_json::from_str(&std::fs::read_to_string(meta_path)?)?;
+            if self.schema_path(&meta.schema_run_id).exists() {
+                return Ok(meta.schema_run_id);
+            }
+        }
+        self.run_id(None)
+    }
+
+    pub fn schema_path(&self, run_id: &str) -> PathBuf {
+        self.paths.runs_dir.join(run_id).join("schema.json")
+    }
+
+    pub fn plan_path(&self, plan_id: &str) -> PathBuf {
+        self.paths.plans_dir.join(plan_id).join("plan.json")
+    }
+
+    pub fn out_manifest(&self, out_id: &str) -> WorkspaceResult<OutManifest> {
+        let path = self.paths.out_dir.join(out_id).join("out_manifest.json");
+        if !path.exists() {
+            return Err(WorkspaceError::Invalid(format!(
+                "output not found: {out_id}"
+            )));
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+fn resolve(
+    explicit: Option<&str>,
+    active: Option<&str>,
+    dir: &Path,
+    marker: &str,
+    kind: &str,
+) -> WorkspaceResult<String> {
+    if let Some(id) = explicit.or(active) {
+        if !dir.join(id).join(marker).exists() {
+            return Err(WorkspaceError::Invalid(format!("{kind} not found: {id}")));
+        }
+        return Ok(id.to_string());
+    }
+    artifacts(dir, marker)?
+        .into_iter()
+        .max()
+        .map(|(_, id)| id)
+        .ok_or_else(|| WorkspaceError::Invalid(format!("no {kind} in the workspace")))
+}
+
+/// Subdirectories of `dir` holding `marker`, with the marker's mtime.
+fn artifacts(dir: &Path, marker: &str) -> WorkspaceResult<Vec<(SystemTime, String)>> {
+    let mut found = Vec::new();
+    if !dir.exists() {
+        return Ok(found);
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path().join(marker);
+        if let (true, Some(name)) = (path.is_file(), entry.file_name().to_str()) {
+            found.push((std::fs::metadata(&path)?.modified()?, name.to_string()));
+        }
+    }
+    Ok(found)
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::atomic::write_bytes_atomic;
-use super::{WorkspaceError, WorkspacePaths, WorkspaceResult};
+use super::{WorkspacePaths, WorkspaceResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmModels {
@@ -32,5 +32,5 @@ pub fn load_or_create_llm_models(paths: &WorkspacePaths) -> WorkspaceResult<LlmM
 fn save_llm_models(paths: &WorkspacePaths, models: &LlmModels) -> WorkspaceResult<()> {
     let path = paths.llm_models_path();
     let encoded = toml::to_string_pretty(models)?;
-    write_bytes_atomic(&path, encoded.as_bytes()).map_err(WorkspaceError::from)
+    write_bytes_atomic(&path, encoded.as_bytes())
 }
@@ -23,6 +23,10 @@ pub struct RunManifest {
     pub cli_version: String,
     pub created_at: String,
     pub finished_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<ArtifactNote>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +38,14 @@ pub struct RunOptions {
     pub include_indexes: bool,
     pub include_comments: bool,
     pub schemas: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_tables: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_tables: Vec<String>,
+    #[serde(default)]
+    pub sample_stats: bool,
+    #[serde(default)]
+    pub include_privileges: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +61,10 @@ pub struct PlanMeta {
     pub cli_version: String,
     pub created_at: String,
     pub finished_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<ArtifactNote>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +80,10 @@ pub struct OutManifest {
     pub cli_version: String,
     pub created_at: String,
     pub finished_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<ArtifactNote>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,3 +97,10 @@ pub struct EvalManifest {
     pub created_at: String,
     pub finished_at: Option<String>,
 }
+
+/// Free-text note attached to a run, plan or output (`/runs note`, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactNote {
+    pub created_at: String,
+    pub text: String,
+}
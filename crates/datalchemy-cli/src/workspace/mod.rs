@@ -1,16 +1,25 @@
+mod annotations;
 mod approval;
+mod archive;
 mod atomic;
 mod doctor;
+mod headless;
 mod ids;
 mod llm_models;
 mod manifests;
 mod paths;
 mod profiles;
 mod settings;
+mod signing;
 
+pub use annotations::{
+    AnnotatedKind, Annotations, add_tags, read_annotations, remove_tags, set_note,
+};
 pub use approval::WriteIntent;
+pub use archive::{export_workspace, import_workspace};
 pub use atomic::{write_bytes_atomic, write_json_atomic};
 pub use doctor::{DoctorLevel, run_doctor};
+pub use headless::HeadlessWorkspace;
 pub use ids::new_artifact_id;
 pub use llm_models::{LlmModels, load_or_create_llm_models};
 pub use manifests::{
@@ -20,9 +29,12 @@ pub use manifests::{
 pub use paths::WorkspacePaths;
 pub use profiles::{DbProfile, ProfilesConfig, load_or_create_profiles, save_profiles};
 pub use settings::{
-    ApprovalPolicy, LlmProvider, PrivacyMode, WorkspaceMode, WorkspaceSettings,
+    ApprovalPolicy, Language, LlmProvider, PrivacyMode, WorkspaceMode, WorkspaceSettings,
     load_or_create_settings, save_settings,
 };
+pub use signing::{
+    SignatureStatus, generate_signing_key, seal_output, verify_output, workspace_public_key,
+};
 
 use std::io;
 
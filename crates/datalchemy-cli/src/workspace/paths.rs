@@ -59,8 +59,21 @@ impl WorkspacePaths {
         self.secrets_dir.join("db.enc")
     }
 
-    pub fn vault_llm_path(&self) -> PathBuf {
-        self.secrets_dir.join("llm_gemini.enc")
+    /// Vault file with the API key of an LLM provider (`gemini`, `openai`, ...).
+    pub fn vault_llm_path(&self, provider: &str) -> PathBuf {
+        self.secrets_dir.join(format!("llm_{provider}.enc"))
+    }
+
+    pub fn vault_storage_path(&self) -> PathBuf {
+        self.secrets_dir.join("storage.enc")
+    }
+
+    pub fn signing_key_path(&self) -> PathBuf {
+        self.secrets_dir.join("signing.key")
+    }
+
+    pub fn signing_public_key_path(&self) -> PathBuf {
+        self.config_dir.join("signing.pub")
     }
 
     pub fn ensure_dirs(&self) -> WorkspaceResult<()> {
@@ -3,9 +3,10 @@ use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
 use datalchemy_core::redact_connection_string;
+use datalchemy_generate::ObjectStoreTarget;
 
 use super::atomic::write_bytes_atomic;
-use super::{WorkspaceError, WorkspacePaths, WorkspaceResult};
+use super::{WorkspacePaths, WorkspaceResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DbProfile {
@@ -15,6 +16,9 @@ pub struct DbProfile {
     pub port: Option<u16>,
     pub database: Option<String>,
     pub user: Option<String>,
+    /// Bucket receiving generated outputs (credentials come from the environment).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_store: Option<ObjectStoreTarget>,
 }
 
 impl DbProfile {
@@ -27,6 +31,7 @@ impl DbProfile {
             port: redacted.port,
             database: redacted.database,
             user: redacted.user,
+            object_store: None,
         }
     }
 }
@@ -52,5 +57,5 @@ pub fn load_or_create_profiles(paths: &WorkspacePaths) -> WorkspaceResult<Profil
 pub fn save_profiles(paths: &WorkspacePaths, profiles: &ProfilesConfig) -> WorkspaceResult<()> {
     let path = paths.profiles_path();
     let encoded = toml::to_string_pretty(profiles)?;
-    write_bytes_atomic(&path, encoded.as_bytes()).map_err(WorkspaceError::from)
+    write_bytes_atomic(&path, encoded.as_bytes())
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::atomic::write_bytes_atomic;
-use super::{WorkspaceError, WorkspacePaths, WorkspaceResult};
+use super::{WorkspacePaths, WorkspaceResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -25,13 +25,59 @@ pub enum PrivacyMode {
     Paranoid,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LlmProvider {
     Gemini,
+    /// OpenAI or any OpenAI-compatible server (`llm_base_url`).
+    #[serde(rename = "openai")]
+    OpenAi,
+    Anthropic,
+    Ollama,
     Off,
 }
 
+impl LlmProvider {
+    /// Name used in settings, vault files and commands.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gemini => "gemini",
+            Self::OpenAi => "openai",
+            Self::Anthropic => "anthropic",
+            Self::Ollama => "ollama",
+            Self::Off => "off",
+        }
+    }
+}
+
+/// Language of TUI messages and of the human lines of headless commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "pt_BR")]
+    PtBr,
+}
+
+impl Language {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::PtBr => "pt_BR",
+        }
+    }
+
+    /// `en` or `pt_BR` (also `pt`, `pt-BR`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "en" => Some(Self::En),
+            "pt_BR" | "pt-BR" | "pt" => Some(Self::PtBr),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceSettings {
     pub approval_policy: ApprovalPolicy,
@@ -43,6 +89,11 @@ pub struct WorkspaceSettings {
     pub llm_enabled: bool,
     pub llm_provider: LlmProvider,
     pub llm_model: Option<String>,
+    /// Endpoint override: OpenAI-compatible server or remote Ollama.
+    #[serde(default)]
+    pub llm_base_url: Option<String>,
+    #[serde(default)]
+    pub language: Language,
 }
 
 impl Default for WorkspaceSettings {
@@ -57,6 +108,8 @@ impl Default for WorkspaceSettings {
             llm_enabled: false,
             llm_provider: LlmProvider::Off,
             llm_model: None,
+            llm_base_url: None,
+            language: Language::En,
         }
     }
 }
@@ -77,5 +130,5 @@ pub fn load_or_create_settings(paths: &WorkspacePaths) -> WorkspaceResult<Worksp
 pub fn save_settings(paths: &WorkspacePaths, settings: &WorkspaceSettings) -> WorkspaceResult<()> {
     let path = paths.settings_path();
     let encoded = toml::to_string_pretty(settings)?;
-    write_bytes_atomic(&path, encoded.as_bytes()).map_err(WorkspaceError::from)
+    write_bytes_atomic(&path, encoded.as_bytes())
 }
@@ -0,0 +1,315 @@
+//! Checksums and ed25519 signatures of output directories.
+//!
+//! When an output is written, [`seal_output`] lists the SHA-256 of every file
+//! in [`CHECKSUMS_FILE`] (`sha256sum` format, so `sha256sum -c` also works) and,
+//! if the workspace has a signing key (`/workspace keygen`), signs that file
+//! into [`SIGNATURE_FILE`]. The manifest is one of the listed files, so it is
+//! covered by the signature as well.
+//!
+//! The private key is `secrets/signing.key` (hex seed, 0600, never exported);
+//! the public key is `config/signing.pub` and is also embedded in every
+//! signature, so a consumer can check it against a key they trust.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::atomic::{write_bytes_atomic, write_secret_atomic};
+use super::{WorkspaceError, WorkspacePaths, WorkspaceResult, write_json_atomic};
+
+pub const CHECKSUMS_FILE: &str = "checksums.sha256";
+pub const SIGNATURE_FILE: &str = "signature.json";
+
+/// Contents of [`SIGNATURE_FILE`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureFile {
+    pub algorithm: String,
+    /// Hex ed25519 public key.
+    pub public_key: String,
+    /// Hex ed25519 signature of [`CHECKSUMS_FILE`].
+    pub signature: String,
+    pub signed_file: String,
+    pub signed_at: String,
+}
+
+/// Written by [`seal_output`].
+#[derive(Debug, Clone)]
+pub struct SealSummary {
+    pub files: usize,
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum SignatureStatus {
+    Unsigned,
+    /// `trusted` is `None` when no trusted key was given.
+    Valid {
+        public_key: String,
+        trusted: Option<bool>,
+    },
+    Invalid(String),
+}
+
+/// Result of [`verify_output`].
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    pub files: usize,
+    pub modified: Vec<String>,
+    pub missing: Vec<String>,
+    /// Files not listed in the checksums.
+    pub unexpected: Vec<String>,
+    pub signature: SignatureStatus,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.modified.is_empty()
+            && self.missing.is_empty()
+            && self.unexpected.is_empty()
+            && matches!(
+                self.signature,
+                SignatureStatus::Unsigned
+                    | SignatureStatus::Valid {
+                        trusted: None | Some(true),
+                        ..
+                    }
+            )
+    }
+}
+
+/// Create the workspace signing key; returns the hex public key.
+pub fn generate_signing_key(paths: &WorkspacePaths, force: bool) -> WorkspaceResult<String> {
+    let key_path = paths.signing_key_path();
+    if key_path.exists() && !force {
+        return Err(WorkspaceError::Invalid(format!(
+            "signing key already exists: {}",
+            key_path.display()
+        )));
+    }
+    let mut seed = [0u8; 32];
+    rand::rng().fill_bytes(&mut seed);
+    let key = SigningKey::from_bytes(&seed);
+    write_secret_atomic(&key_path, hex::encode(seed).as_bytes())?;
+    let public_key = hex::encode(key.verifying_key().as_bytes());
+    write_bytes_atomic(&paths.signing_public_key_path(), public_key.as_bytes())?;
+    Ok(public_key)
+}
+
+/// Hex public key of the workspace, if it has one.
+pub fn workspace_public_key(paths: &WorkspacePaths) -> WorkspaceResult<Option<String>> {
+    let path = paths.signing_public_key_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(std::fs::read_to_string(path)?.trim().to_string()))
+}
+
+fn load_signing_key(paths: &WorkspacePaths) -> WorkspaceResult<Option<SigningKey>> {
+    let path = paths.signing_key_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let seed: [u8; 32] = hex::decode(std::fs::read_to_string(&path)?.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            WorkspaceError::Invalid(format!("invalid signing key: {}", path.display()))
+        })?;
+    Ok(Some(SigningKey::from_bytes(&seed)))
+}
+
+/// Write the checksums of `dir` and, with a workspace signing key, their
+/// signature.
+pub fn seal_output(paths: &WorkspacePaths, dir: &Path) -> WorkspaceResult<SealSummary> {
+    let checksums = checksum_lines(dir)?;
+    write_checksums(paths, dir, &checksums)
+}
+
+/// Update the checksum of `file` in a sealed `dir` after it was edited (e.g.
+/// tags added to the manifest) and sign again; the other entries are kept, so
+/// files modified since sealing still fail verification.
+pub fn reseal_file(paths: &WorkspacePaths, dir: &Path, file: &str) -> WorkspaceResult<()> {
+    if !dir.join(CHECKSUMS_FILE).exists() {
+        return Ok(());
+    }
+    let mut checksums = read_checksums(dir)?;
+    let digest = hex::encode(Sha256::digest(std::fs::read(dir.join(file))?));
+    match checksums.iter_mut().find(|(name, _)| name == file) {
+        Some(entry) => entry.1 = digest,
+        None => return Ok(()),
+    }
+    write_checksums(paths, dir, &checksums)?;
+    Ok(())
+}
+
+/// Fail when `dir` is signed but the workspace has no signing key, so
+/// [`reseal_file`] would drop the signature.
+pub fn ensure_resealable(paths: &WorkspacePaths, dir: &Path) -> WorkspaceResult<()> {
+    if dir.join(SIGNATURE_FILE).exists() && !paths.signing_key_path().exists() {
+        return Err(WorkspaceError::Invalid(format!(
+            "{} is signed and the workspace has no signing key",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Write [`CHECKSUMS_FILE`] and sign it; a stale signature is removed when
+/// there is no key.
+fn write_checksums(
+    paths: &WorkspacePaths,
+    dir: &Path,
+    checksums: &[(String, String)],
+) -> WorkspaceResult<SealSummary> {
+    let contents: String = checksums
+        .iter()
+        .map(|(file, digest)| format!("{digest}  {file}\n"))
+        .collect();
+    write_bytes_atomic(&dir.join(CHECKSUMS_FILE), contents.as_bytes())?;
+
+    let signature_path = dir.join(SIGNATURE_FILE);
+    let Some(key) = load_signing_key(paths)? else {
+        if signature_path.exists() {
+            std::fs::remove_file(&signature_path)?;
+        }
+        return Ok(SealSummary {
+            files: checksums.len(),
+            signed: false,
+        });
+    };
+    let signature = SignatureFile {
+        algorithm: "ed25519".to_string(),
+        public_key: hex::encode(key.verifying_key().as_bytes()),
+        signature: hex::encode(key.sign(contents.as_bytes()).to_bytes()),
+        signed_file: CHECKSUMS_FILE.to_string(),
+        signed_at: Utc::now().to_rfc3339(),
+    };
+    write_json_atomic(&signature_path, &signature)?;
+    Ok(SealSummary {
+        files: checksums.len(),
+        signed: true,
+    })
+}
+
+/// `(file, digest)` entries of [`CHECKSUMS_FILE`].
+fn read_checksums(dir: &Path) -> WorkspaceResult<Vec<(String, String)>> {
+    let contents = std::fs::read_to_string(dir.join(CHECKSUMS_FILE))?;
+    contents
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once("  ") {
+            Some((digest, file)) => Ok((file.to_string(), digest.to_string())),
+            None => Err(WorkspaceError::Invalid(format!(
+                "invalid line in {CHECKSUMS_FILE}: {line}"
+            ))),
+        })
+        .collect()
+}
+
+/// Check the files of `dir` against its checksums and signature.
+/// `trusted_key` (hex) is the key the signature must have been made with.
+pub fn verify_output(dir: &Path, trusted_key: Option<&str>) -> WorkspaceResult<VerifyReport> {
+    let checksums_path = dir.join(CHECKSUMS_FILE);
+    if !checksums_path.exists() {
+        return Err(WorkspaceError::Invalid(format!(
+            "{CHECKSUMS_FILE} not found in {}",
+            dir.display()
+        )));
+    }
+    let contents = std::fs::read_to_string(&checksums_path)?;
+    let expected = read_checksums(dir)?;
+    let actual = checksum_lines(dir)?;
+
+    let mut report = VerifyReport {
+        files: expected.len(),
+        modified: Vec::new(),
+        missing: Vec::new(),
+        unexpected: Vec::new(),
+        signature: SignatureStatus::Unsigned,
+    };
+    for (file, digest) in &expected {
+        match actual.iter().find(|(name, _)| name == file) {
+            None => report.missing.push(file.clone()),
+            Some((_, actual_digest)) if actual_digest != digest => {
+                report.modified.push(file.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    report.unexpected = actual
+        .into_iter()
+        .map(|(file, _)| file)
+        .filter(|file| !expected.iter().any(|(name, _)| name == file))
+        .collect();
+
+    let signature_path = dir.join(SIGNATURE_FILE);
+    if signature_path.exists() {
+        let signature: SignatureFile =
+            serde_json::from_str(&std::fs::read_to_string(signature_path)?)?;
+        report.signature = match check_signature(&signature, contents.as_bytes()) {
+            Ok(()) => SignatureStatus::Valid {
+                trusted: trusted_key.map(|key| key.eq_ignore_ascii_case(&signature.public_key)),
+                public_key: signature.public_key,
+            },
+            Err(reason) => SignatureStatus::Invalid(reason),
+        };
+    } else if trusted_key.is_some() {
+        report.signature = SignatureStatus::Invalid("output is not signed".to_string());
+    }
+    Ok(report)
+}
+
+fn check_signature(signature: &SignatureFile, message: &[u8]) -> Result<(), String> {
+    if signature.algorithm != "ed25519" {
+        return Err(format!("unsupported algorithm '{}'", signature.algorithm));
+    }
+    let public_key: [u8; 32] = hex::decode(&signature.public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("invalid public key")?;
+    let public_key = VerifyingKey::from_bytes(&public_key).map_err(|err| err.to_string())?;
+    let bytes: [u8; 64] = hex::decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("invalid signature encoding")?;
+    public_key
+        .verify(message, &Signature::from_bytes(&bytes))
+        .map_err(|_| "signature does not match the checksums".to_string())
+}
+
+/// `(relative path, hex sha256)` of every file under `dir` except the
+/// checksums and signature, sorted by path.
+fn checksum_lines(dir: &Path) -> WorkspaceResult<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+    files
+        .into_iter()
+        .filter(|file| file != CHECKSUMS_FILE && file != SIGNATURE_FILE)
+        .map(|file| {
+            let digest = Sha256::digest(std::fs::read(dir.join(&file))?);
+            Ok((file, hex::encode(digest)))
+        })
+        .collect()
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> WorkspaceResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path: PathBuf = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let parts: Vec<_> = relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            files.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
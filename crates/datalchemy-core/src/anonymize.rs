@@ -0,0 +1,730 @@
+//! Identifier anonymization of a schema snapshot, for sharing it outside.
+//!
+//! [`anonymize_schema`] renames schemas, tables, columns, user types (enums,
+//! domains, composite types and their attributes), constraints, indexes,
+//! triggers, rules, trigger functions, foreign servers, roles and sequences to
+//! pseudonyms (`schema_1`, `table_3`, `column_12`, ...) numbered in the
+//! deterministic `schema.json` order, so the same schema always gets the same
+//! names. Identifiers inside check expressions, defaults, generated
+//! expressions, index definitions and view queries are rewritten too, and
+//! foreign table option values get pseudonyms of their own; string literals
+//! and enum labels are kept, comments are moved to the mapping and the
+//! database name is replaced.
+//!
+//! Every pseudonym is unique across kinds, so [`IdentifierMap`] is a flat
+//! pseudonym -> original table and [`restore_schema`] reverses the renaming
+//! (also in free text, see [`IdentifierMap::restore_text`]).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constraints::Constraint;
+use crate::schema::{DatabaseSchema, ViewDependency};
+use crate::types::ColumnType;
+
+/// Mapping from pseudonyms back to the original identifiers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentifierMap {
+    /// Pseudonym -> original name.
+    pub names: BTreeMap<String, String>,
+    /// Table or column pseudonym -> original comment.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub comments: BTreeMap<String, String>,
+    /// Original database name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+}
+
+impl IdentifierMap {
+    /// Original name of `pseudonym`.
+    pub fn original(&self, pseudonym: &str) -> Option<&str> {
+        self.names.get(pseudonym).map(String::as_str)
+    }
+
+    /// `text` (an error message, a SQL snippet) with every pseudonym
+    /// replaced by its original name.
+    pub fn restore_text(&self, text: &str) -> String {
+        rewrite_sql(text, &mut |token, _| self.names.get(token).cloned())
+    }
+
+    fn restore(&self, name: &str) -> String {
+        self.names
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+/// Copy of `schema` with pseudonymous identifiers, and the mapping to undo it.
+pub fn anonymize_schema(schema: &DatabaseSchema) -> (DatabaseSchema, IdentifierMap) {
+    let mut names = Names::default();
+    for db_schema in &schema.schemas {
+        names.schema(&db_schema.name);
+        for table in &db_schema.tables {
+            names.table(&db_schema.name, &table.name);
+            for column in &table.columns {
+                names.column(&db_schema.name, &table.name, &column.name);
+            }
+        }
+    }
+    for enum_type in &schema.enums {
+        names.schema(&enum_type.schema);
+        names.user_type("enum", &enum_type.schema, &enum_type.name);
+    }
+    for domain in &schema.domains {
+        names.schema(&domain.schema);
+        names.user_type("domain", &domain.schema, &domain.name);
+    }
+    for composite in &schema.composite_types {
+        names.schema(&composite.schema);
+        names.user_type("type", &composite.schema, &composite.name);
+        for attribute in &composite.attributes {
+            names.column(&composite.schema, &composite.name, &attribute.name);
+        }
+    }
+
+    let mut map = IdentifierMap {
+        database: schema.database.clone(),
+        ..IdentifierMap::default()
+    };
+    let mut anonymized = schema.clone();
+    anonymized.database = schema.database.as_ref().map(|_| "database".to_string());
+    for db_schema in &mut anonymized.schemas {
+        let schema_name = db_schema.name.clone();
+        db_schema.name = names.schema(&schema_name);
+        for table in &mut db_schema.tables {
+            let table_name = table.name.clone();
+            let scope = (schema_name.as_str(), table_name.as_str());
+            table.name = names.table(&schema_name, &table_name);
+            if let Some(comment) = table.comment.take() {
+                map.comments.insert(table.name.clone(), comment);
+            }
+            for column in &mut table.columns {
+                let column_name = column.name.clone();
+                column.name = names.column(&schema_name, &table_name, &column_name);
+                if let Some(comment) = column.comment.take() {
+                    map.comments.insert(column.name.clone(), comment);
+                }
+                names.column_type(&mut column.column_type);
+                if let Some(default) = &column.default {
+                    column.default = Some(names.expression(default, scope));
+                }
+                if let Some(generated) = &mut column.generated
+                    && let Some(expression) = &generated.expression
+                {
+                    generated.expression = Some(names.expression(expression, scope));
+                }
+            }
+            for constraint in &mut table.constraints {
+                match constraint {
+                    Constraint::PrimaryKey(pk) => {
+                        pk.name = pk
+                            .name
+                            .as_deref()
+                            .map(|name| names.fresh("constraint", name));
+                        names.columns(&mut pk.columns, scope);
+                    }
+                    Constraint::Unique(unique) => {
+                        unique.name = unique
+                            .name
+                            .as_deref()
+                            .map(|name| names.fresh("constraint", name));
+                        names.columns(&mut unique.columns, scope);
+                    }
+                    Constraint::Check(check) => {
+                        check.name = check
+                            .name
+                            .as_deref()
+                            .map(|name| names.fresh("constraint", name));
+                        check.expression = names.expression(&check.expression, scope);
+                    }
+                    Constraint::Exclusion(exclusion) => {
+                        exclusion.name = exclusion
+                            .name
+                            .as_deref()
+                            .map(|name| names.fresh("constraint", name));
+                        for element in &mut exclusion.elements {
+                            element.expression = names.expression(&element.expression, scope);
+                        }
+                        if let Some(predicate) = &exclusion.predicate {
+                            exclusion.predicate = Some(names.expression(predicate, scope));
+                        }
+                    }
+                    Constraint::ForeignKey(fk) => {
+                        fk.name = fk
+                            .name
+                            .as_deref()
+                            .map(|name| names.fresh("constraint", name));
+                        names.columns(&mut fk.columns, scope);
+                        let parent = (fk.referenced_schema.clone(), fk.referenced_table.clone());
+                        names.columns(&mut fk.referenced_columns, (&parent.0, &parent.1));
+                        fk.referenced_schema = names.schema(&parent.0);
+                        fk.referenced_table = names.table(&parent.0, &parent.1);
+                    }
+                }
+            }
+            for index in &mut table.indexes {
+                let original = std::mem::take(&mut index.name);
+                index.name = names.fresh("index", &original);
+                names.current_index = Some((original, index.name.clone()));
+                index.definition = names.expression(&index.definition, scope);
+                if let Some(predicate) = &index.predicate {
+                    index.predicate = Some(names.expression(predicate, scope));
+                }
+                for expression in &mut index.expressions {
+                    *expression = names.expression(expression, scope);
+                }
+                names.current_index = None;
+            }
+            for trigger in &mut table.triggers {
+                trigger.name = names.fresh("trigger", &trigger.name);
+                trigger.function = trigger
+                    .function
+                    .as_deref()
+                    .map(|function| names.function(function));
+            }
+            for rule in &mut table.rules {
+                rule.name = names.fresh("rule", &rule.name);
+            }
+            if let Some(definition) = &table.view_definition {
+                table.view_definition =
+                    Some(names.view_query(definition, scope, &table.depends_on));
+            }
+            for dependency in &mut table.depends_on {
+                dependency.name = names.table(&dependency.schema, &dependency.name);
+                dependency.schema = names.schema(&dependency.schema);
+            }
+            if let Some(source) = &mut table.foreign_table {
+                source.server = names.server(&source.server);
+                for value in source
+                    .options
+                    .values_mut()
+                    .chain(source.server_options.values_mut())
+                {
+                    *value = names.fresh("option", value);
+                }
+            }
+            if let Some(privileges) = &mut table.privileges {
+                for role in privileges.select.iter_mut().chain(&mut privileges.insert) {
+                    *role = names.role(role);
+                }
+            }
+        }
+    }
+    anonymized.current_role = schema.current_role.as_deref().map(|role| names.role(role));
+    for failed in &mut anonymized.failed_tables {
+        failed.error = names.expression(&failed.error, (&failed.schema, &failed.table));
+        failed.table = names.table(&failed.schema, &failed.table);
+        failed.schema = names.schema(&failed.schema);
+    }
+    for external in &mut anonymized.external_tables {
+        for child in &mut external.referenced_by {
+            if let Some((schema, table)) = child.split_once('.') {
+                *child = format!("{}.{}", names.schema(schema), names.table(schema, table));
+            }
+        }
+        external.table = names.table(&external.schema, &external.table);
+        external.schema = names.schema(&external.schema);
+    }
+    for enum_type in &mut anonymized.enums {
+        let schema_name = enum_type.schema.clone();
+        enum_type.name = names.user_type("enum", &schema_name, &enum_type.name);
+        enum_type.schema = names.schema(&schema_name);
+    }
+    for domain in &mut anonymized.domains {
+        let scope = (domain.schema.clone(), domain.name.clone());
+        domain.name = names.user_type("domain", &scope.0, &scope.1);
+        domain.schema = names.schema(&scope.0);
+        names.column_type(&mut domain.base_type);
+        if let Some(default) = &domain.default {
+            domain.default = Some(names.expression(default, (&scope.0, &scope.1)));
+        }
+        for check in &mut domain.checks {
+            check.name = check
+                .name
+                .as_deref()
+                .map(|name| names.fresh("constraint", name));
+            check.expression = names.expression(&check.expression, (&scope.0, &scope.1));
+        }
+    }
+    for composite in &mut anonymized.composite_types {
+        let schema_name = composite.schema.clone();
+        for attribute in &mut composite.attributes {
+            attribute.name = names.column(&schema_name, &composite.name, &attribute.name);
+            names.column_type(&mut attribute.column_type);
+        }
+        composite.name = names.user_type("type", &schema_name, &composite.name);
+        composite.schema = names.schema(&schema_name);
+    }
+    for sequence in &mut anonymized.sequences {
+        sequence.name = names.sequence(&sequence.name);
+        sequence.schema = names.schema(&sequence.schema);
+        if let Some(owner) = &mut sequence.owner {
+            owner.column = names.column(&owner.schema, &owner.table, &owner.column);
+            owner.table = names.table(&owner.schema, &owner.table);
+            owner.schema = names.schema(&owner.schema);
+        }
+    }
+
+    map.names = names.originals;
+    (anonymized, map)
+}
+
+/// Undo [`anonymize_schema`] with its mapping.
+pub fn restore_schema(schema: &DatabaseSchema, map: &IdentifierMap) -> DatabaseSchema {
+    let mut restored = schema.clone();
+    restored.database = map.database.clone();
+    for db_schema in &mut restored.schemas {
+        db_schema.name = map.restore(&db_schema.name);
+        for table in &mut db_schema.tables {
+            if let Some(comment) = map.comments.get(&table.name) {
+                table.comment = Some(comment.clone());
+            }
+            table.name = map.restore(&table.name);
+            for column in &mut table.columns {
+                if let Some(comment) = map.comments.get(&column.name) {
+                    column.comment = Some(comment.clone());
+                }
+                column.name = map.restore(&column.name);
+                restore_column_type(&mut column.column_type, map);
+                if let Some(default) = &column.default {
+                    column.default = Some(map.restore_text(default));
+                }
+                if let Some(generated) = &mut column.generated
+                    && let Some(expression) = &generated.expression
+                {
+                    generated.expression = Some(map.restore_text(expression));
+                }
+            }
+            for constraint in &mut table.constraints {
+                let restore_all = |columns: &mut Vec<String>| {
+                    for column in columns {
+                        *column = map.restore(column);
+                    }
+                };
+                match constraint {
+                    Constraint::PrimaryKey(pk) => {
+                        pk.name = pk.name.as_deref().map(|name| map.restore(name));
+                        restore_all(&mut pk.columns);
+                    }
+                    Constraint::Unique(unique) => {
+                        unique.name = unique.name.as_deref().map(|name| map.restore(name));
+                        restore_all(&mut unique.columns);
+                    }
+                    Constraint::Check(check) => {
+                        check.name = check.name.as_deref().map(|name| map.restore(name));
+                        check.expression = map.restore_text(&check.expression);
+                    }
+                    Constraint::Exclusion(exclusion) => {
+                        exclusion.name = exclusion.name.as_deref().map(|name| map.restore(name));
+                        for element in &mut exclusion.elements {
+                            element.expression = map.restore_text(&element.expression);
+                        }
+                        if let Some(predicate) = &exclusion.predicate {
+                            exclusion.predicate = Some(map.restore_text(predicate));
+                        }
+                    }
+                    Constraint::ForeignKey(fk) => {
+                        fk.name = fk.name.as_deref().map(|name| map.restore(name));
+                        restore_all(&mut fk.columns);
+                        restore_all(&mut fk.referenced_columns);
+                        fk.referenced_schema = map.restore(&fk.referenced_schema);
+                        fk.referenced_table = map.restore(&fk.referenced_table);
+                    }
+                }
+            }
+            for index in &mut table.indexes {
+                index.name = map.restore(&index.name);
+                index.definition = map.restore_text(&index.definition);
+                if let Some(predicate) = &index.predicate {
+                    index.predicate = Some(map.restore_text(predicate));
+                }
+                for expression in &mut index.expressions {
+                    *expression = map.restore_text(expression);
+                }
+            }
+            for trigger in &mut table.triggers {
+                trigger.name = map.restore(&trigger.name);
+                trigger.function = trigger
+                    .function
+                    .as_deref()
+                    .map(|function| map.restore(function));
+            }
+            for rule in &mut table.rules {
+                rule.name = map.restore(&rule.name);
+            }
+            if let Some(definition) = &table.view_definition {
+                table.view_definition = Some(map.restore_text(definition));
+            }
+            for dependency in &mut table.depends_on {
+                dependency.schema = map.restore(&dependency.schema);
+                dependency.name = map.restore(&dependency.name);
+            }
+            if let Some(source) = &mut table.foreign_table {
+                source.server = map.restore(&source.server);
+                for value in source
+                    .options
+                    .values_mut()
+                    .chain(source.server_options.values_mut())
+                {
+                    *value = map.restore(value);
+                }
+            }
+            if let Some(privileges) = &mut table.privileges {
+                for role in privileges.select.iter_mut().chain(&mut privileges.insert) {
+                    *role = map.restore(role);
+                }
+            }
+        }
+    }
+    restored.current_role = schema.current_role.as_deref().map(|role| map.restore(role));
+    for failed in &mut restored.failed_tables {
+        failed.schema = map.restore(&failed.schema);
+        failed.table = map.restore(&failed.table);
+        failed.error = map.restore_text(&failed.error);
+    }
+    for external in &mut restored.external_tables {
+        external.schema = map.restore(&external.schema);
+        external.table = map.restore(&external.table);
+        for child in &mut external.referenced_by {
+            if let Some((schema, table)) = child.split_once('.') {
+                *child = format!("{}.{}", map.restore(schema), map.restore(table));
+            }
+        }
+    }
+    for enum_type in &mut restored.enums {
+        enum_type.schema = map.restore(&enum_type.schema);
+        enum_type.name = map.restore(&enum_type.name);
+    }
+    for domain in &mut restored.domains {
+        domain.schema = map.restore(&domain.schema);
+        domain.name = map.restore(&domain.name);
+        restore_column_type(&mut domain.base_type, map);
+        if let Some(default) = &domain.default {
+            domain.default = Some(map.restore_text(default));
+        }
+        for check in &mut domain.checks {
+            check.name = check.name.as_deref().map(|name| map.restore(name));
+            check.expression = map.restore_text(&check.expression);
+        }
+    }
+    for composite in &mut restored.composite_types {
+        composite.schema = map.restore(&composite.schema);
+        composite.name = map.restore(&composite.name);
+        for attribute in &mut composite.attributes {
+            attribute.name = map.restore(&attribute.name);
+            restore_column_type(&mut attribute.column_type, map);
+        }
+    }
+    for sequence in &mut restored.sequences {
+        sequence.schema = map.restore(&sequence.schema);
+        sequence.name = map.restore(&sequence.name);
+        if let Some(owner) = &mut sequence.owner {
+            owner.schema = map.restore(&owner.schema);
+            owner.table = map.restore(&owner.table);
+            owner.column = map.restore(&owner.column);
+        }
+    }
+    restored
+}
+
+fn restore_column_type(column_type: &mut ColumnType, map: &IdentifierMap) {
+    column_type.data_type = map.restore_text(&column_type.data_type);
+    column_type.udt_schema = map.restore(&column_type.udt_schema);
+    column_type.udt_name = match column_type.udt_name.strip_prefix('_') {
+        Some(element) if map.names.contains_key(element) => format!("_{}", map.restore(element)),
+        _ => map.restore(&column_type.udt_name),
+    };
+}
+
+/// Pseudonyms handed out so far, by kind and original identifier.
+#[derive(Default)]
+struct Names {
+    counters: BTreeMap<&'static str, usize>,
+    originals: BTreeMap<String, String>,
+    schemas: BTreeMap<String, String>,
+    tables: BTreeMap<(String, String), String>,
+    columns: BTreeMap<(String, String, String), String>,
+    types: BTreeMap<(String, String), String>,
+    sequences: BTreeMap<String, String>,
+    functions: BTreeMap<String, String>,
+    servers: BTreeMap<String, String>,
+    roles: BTreeMap<String, String>,
+    /// Original and pseudonym of the index whose definition is rewritten.
+    current_index: Option<(String, String)>,
+}
+
+impl Names {
+    fn fresh(&mut self, kind: &'static str, original: &str) -> String {
+        let counter = self.counters.entry(kind).or_default();
+        *counter += 1;
+        let pseudonym = format!("{kind}_{counter}");
+        self.originals
+            .insert(pseudonym.clone(), original.to_string());
+        pseudonym
+    }
+
+    fn schema(&mut self, name: &str) -> String {
+        if let Some(pseudonym) = self.schemas.get(name) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("schema", name);
+        self.schemas.insert(name.to_string(), pseudonym.clone());
+        pseudonym
+    }
+
+    fn table(&mut self, schema: &str, name: &str) -> String {
+        let key = (schema.to_string(), name.to_string());
+        if let Some(pseudonym) = self.tables.get(&key) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("table", name);
+        self.tables.insert(key, pseudonym.clone());
+        pseudonym
+    }
+
+    fn column(&mut self, schema: &str, table: &str, name: &str) -> String {
+        let key = (schema.to_string(), table.to_string(), name.to_string());
+        if let Some(pseudonym) = self.columns.get(&key) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("column", name);
+        self.columns.insert(key, pseudonym.clone());
+        pseudonym
+    }
+
+    /// Enum, domain or composite type; `kind` prefixes the pseudonym.
+    fn user_type(&mut self, kind: &'static str, schema: &str, name: &str) -> String {
+        let key = (schema.to_string(), name.to_string());
+        if let Some(pseudonym) = self.types.get(&key) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh(kind, name);
+        self.types.insert(key, pseudonym.clone());
+        pseudonym
+    }
+
+    fn sequence(&mut self, name: &str) -> String {
+        if let Some(pseudonym) = self.sequences.get(name) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("sequence", name);
+        self.sequences.insert(name.to_string(), pseudonym.clone());
+        pseudonym
+    }
+
+    /// Trigger function (`schema.function`), shared by its triggers.
+    fn function(&mut self, name: &str) -> String {
+        if let Some(pseudonym) = self.functions.get(name) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("function", name);
+        self.functions.insert(name.to_string(), pseudonym.clone());
+        pseudonym
+    }
+
+    /// Foreign server, shared by its foreign tables.
+    fn server(&mut self, name: &str) -> String {
+        if let Some(pseudonym) = self.servers.get(name) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("server", name);
+        self.servers.insert(name.to_string(), pseudonym.clone());
+        pseudonym
+    }
+
+    fn role(&mut self, name: &str) -> String {
+        if let Some(pseudonym) = self.roles.get(name) {
+            return pseudonym.clone();
+        }
+        let pseudonym = self.fresh("role", name);
+        self.roles.insert(name.to_string(), pseudonym.clone());
+        pseudonym
+    }
+
+    fn columns(&mut self, columns: &mut [String], (schema, table): (&str, &str)) {
+        for column in columns {
+            *column = self.column(schema, table, column);
+        }
+    }
+
+    /// User-defined types get their pseudonyms; built-in types stay.
+    fn column_type(&mut self, column_type: &mut ColumnType) {
+        let schema = column_type.udt_schema.clone();
+        let (array, element) = match column_type.udt_name.strip_prefix('_') {
+            Some(element) => (true, element.to_string()),
+            None => (false, column_type.udt_name.clone()),
+        };
+        let Some(pseudonym) = self.types.get(&(schema.clone(), element)).cloned() else {
+            return;
+        };
+        column_type.udt_name = if array {
+            format!("_{pseudonym}")
+        } else {
+            pseudonym
+        };
+        let types = self.types.clone();
+        let schemas = self.schemas.clone();
+        column_type.data_type = rewrite_sql(&column_type.data_type, &mut |token, _| {
+            types
+                .get(&(schema.clone(), token.to_string()))
+                .or_else(|| schemas.get(token))
+                .cloned()
+        });
+        column_type.udt_schema = self.schema(&schema);
+    }
+
+    /// Identifiers of an expression of table `scope`: its columns first,
+    /// then the current index, tables, user types and schemas; the name in
+    /// `nextval('...')` is a sequence.
+    fn expression(&mut self, text: &str, (schema, table): (&str, &str)) -> String {
+        rewrite_sql(text, &mut |token, in_nextval| {
+            if let Some(pseudonym) = self.schemas.get(token) {
+                if in_nextval {
+                    return Some(pseudonym.clone());
+                }
+            } else if in_nextval {
+                return Some(self.sequence(token));
+            }
+            let column = (schema.to_string(), table.to_string(), token.to_string());
+            let scoped = (schema.to_string(), token.to_string());
+            self.columns
+                .get(&column)
+                .or_else(|| {
+                    self.current_index
+                        .as_ref()
+                        .filter(|(original, _)| original == token)
+                        .map(|(_, pseudonym)| pseudonym)
+                })
+                .or_else(|| self.tables.get(&scoped))
+                .or_else(|| self.types.get(&scoped))
+                .or_else(|| {
+                    self.tables
+                        .iter()
+                        .chain(&self.types)
+                        .find(|((_, name), _)| name == token)
+                        .map(|(_, pseudonym)| pseudonym)
+                })
+                .or_else(|| self.schemas.get(token))
+                .cloned()
+        })
+    }
+
+    /// Query of a view: like [`Names::expression`], with the columns of the
+    /// relations it reads in scope too.
+    fn view_query(&mut self, text: &str, view: (&str, &str), reads: &[ViewDependency]) -> String {
+        let scopes: Vec<(&str, &str)> = std::iter::once(view)
+            .chain(
+                reads
+                    .iter()
+                    .map(|read| (read.schema.as_str(), read.name.as_str())),
+            )
+            .collect();
+        rewrite_sql(text, &mut |token, _| {
+            scopes
+                .iter()
+                .find_map(|(schema, table)| {
+                    self.columns
+                        .get(&(schema.to_string(), table.to_string(), token.to_string()))
+                })
+                .or_else(|| {
+                    self.tables
+                        .iter()
+                        .chain(&self.types)
+                        .find(|((_, name), _)| name == token)
+                        .map(|(_, pseudonym)| pseudonym)
+                })
+                .or_else(|| self.schemas.get(token))
+                .cloned()
+        })
+    }
+}
+
+/// Rewrite the identifiers of a SQL snippet with `rename(token, in_nextval)`.
+/// Quoted identifiers stay quoted and string literals are kept, except the
+/// dotted name inside `nextval('...')`, whose parts are renamed with
+/// `in_nextval` set.
+pub(crate) fn rewrite_sql(
+    text: &str,
+    rename: &mut dyn FnMut(&str, bool) -> Option<String>,
+) -> String {
+    rewrite_tokens(text, rename, false)
+}
+
+fn rewrite_tokens(
+    text: &str,
+    rename: &mut dyn FnMut(&str, bool) -> Option<String>,
+    in_nextval: bool,
+) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut idx = 0;
+    while idx < chars.len() {
+        let ch = chars[idx];
+        if ch == '\'' || ch == '"' {
+            let (content, end) = quoted(&chars, idx);
+            idx = end;
+            let after_nextval =
+                ch == '\'' && out.trim_end().to_ascii_lowercase().ends_with("nextval(");
+            out.push(ch);
+            if ch == '"' {
+                let name = content.replace("\"\"", "\"");
+                match rename(&name, in_nextval) {
+                    Some(pseudonym) => out.push_str(&pseudonym.replace('"', "\"\"")),
+                    None => out.push_str(&content),
+                }
+            } else if after_nextval {
+                out.push_str(&rewrite_tokens(&content, rename, true));
+            } else {
+                out.push_str(&content);
+            }
+            out.push(ch);
+        } else if ch.is_ascii_digit() {
+            while idx < chars.len()
+                && (chars[idx].is_ascii_alphanumeric() || matches!(chars[idx], '_' | '.'))
+            {
+                out.push(chars[idx]);
+                idx += 1;
+            }
+        } else if ch.is_alphabetic() || ch == '_' {
+            let start = idx;
+            while idx < chars.len()
+                && (chars[idx].is_alphanumeric() || matches!(chars[idx], '_' | '$'))
+            {
+                idx += 1;
+            }
+            let token: String = chars[start..idx].iter().collect();
+            match rename(&token, in_nextval) {
+                Some(pseudonym) => out.push_str(&pseudonym),
+                None => out.push_str(&token),
+            }
+        } else {
+            out.push(ch);
+            idx += 1;
+        }
+    }
+    out
+}
+
+/// Content of the quoted run starting at `start` (doubled quotes kept) and
+/// the index after its closing quote.
+fn quoted(chars: &[char], start: usize) -> (String, usize) {
+    let quote = chars[start];
+    let mut content = String::new();
+    let mut idx = start + 1;
+    while idx < chars.len() {
+        if chars[idx] == quote {
+            if chars.get(idx + 1) == Some(&quote) {
+                content.push(quote);
+                content.push(quote);
+                idx += 2;
+                continue;
+            }
+            return (content, idx + 1);
+        }
+        content.push(chars[idx]);
+        idx += 1;
+    }
+    (content, idx)
+}
@@ -0,0 +1,731 @@
+//! Fluent construction of [`DatabaseSchema`] values in code.
+//!
+//! Types are written as Postgres formats them (`integer`, `numeric(12,2)`,
+//! `character varying(255)`, `crm.status`); the underlying type metadata is
+//! filled in the way introspection would, and a type naming an enum, domain
+//! or composite type added with [`SchemaBuilder::enum_type`],
+//! [`SchemaBuilder::domain_type`] or [`SchemaBuilder::composite_type`] points
+//! at it. Constraints get the default
+//! Postgres names (`users_pkey`, `users_email_key`, `orders_user_id_fkey`),
+//! and primary keys and unique constraints get their backing indexes.
+//!
+//! ```
+//! use datalchemy_core::{FkAction, SchemaBuilder, TableBuilder};
+//!
+//! let schema = SchemaBuilder::new()
+//!     .table(
+//!         "public",
+//!         TableBuilder::new("users")
+//!             .column("id", "bigint")
+//!             .column("email", "character varying(255)")
+//!             .not_null()
+//!             .primary_key(["id"])
+//!             .unique(["email"]),
+//!     )
+//!     .table(
+//!         "public",
+//!         TableBuilder::new("orders")
+//!             .column("id", "bigint")
+//!             .column("user_id", "bigint")
+//!             .primary_key(["id"])
+//!             .foreign_key(["user_id"], "users", ["id"])
+//!             .on_delete(FkAction::Cascade),
+//!     )
+//!     .build()
+//!     .expect("valid schema");
+//! assert_eq!(schema.schemas[0].tables.len(), 2);
+//! ```
+
+use crate::SCHEMA_VERSION;
+use crate::constraints::{
+    CheckConstraint, Constraint, FkAction, FkMatchType, ForeignKey, Index, PrimaryKey,
+    UniqueConstraint,
+};
+use crate::error::{Error, Result};
+use crate::schema::{
+    Column, DatabaseSchema, Partition, PartitionKey, PartitionStrategy, Schema, Table, TableEvent,
+    TableKind, Trigger, TriggerTiming, ViewDependency,
+};
+use crate::types::{
+    ColumnType, CompositeAttribute, CompositeType, DomainType, EnumType, GeneratedExpression,
+    GeneratedKind, IdentityGeneration,
+};
+use crate::validation::validate_schema;
+
+/// Builder of a [`DatabaseSchema`]; schemas and tables keep insertion order.
+#[derive(Debug, Clone)]
+pub struct SchemaBuilder {
+    engine: String,
+    database: Option<String>,
+    tables: Vec<(String, TableBuilder)>,
+    enums: Vec<EnumType>,
+    domains: Vec<DomainType>,
+    composite_types: Vec<CompositeType>,
+}
+
+impl Default for SchemaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchemaBuilder {
+    /// Empty Postgres schema.
+    pub fn new() -> Self {
+        Self {
+            engine: "postgres".to_string(),
+            database: None,
+            tables: Vec::new(),
+            enums: Vec::new(),
+            domains: Vec::new(),
+            composite_types: Vec::new(),
+        }
+    }
+
+    pub fn engine(mut self, engine: impl Into<String>) -> Self {
+        self.engine = engine.into();
+        self
+    }
+
+    pub fn database(mut self, name: impl Into<String>) -> Self {
+        self.database = Some(name.into());
+        self
+    }
+
+    /// Enum type `schema.name`; columns typed `schema.name` (or `name`) use it.
+    pub fn enum_type<I, S>(mut self, schema: &str, name: &str, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.enums.push(EnumType {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            labels: labels.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Domain `schema.name` over `base_type`, with checks `<name>_check`
+    /// (numbered after the first) written on `VALUE`; the expressions may omit
+    /// the `CHECK (...)` wrapper.
+    pub fn domain_type<I, S>(mut self, schema: &str, name: &str, base_type: &str, checks: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let checks = checks
+            .into_iter()
+            .enumerate()
+            .map(|(idx, expression)| CheckConstraint {
+                name: Some(match idx {
+                    0 => format!("{name}_check"),
+                    idx => format!("{name}_check{idx}"),
+                }),
+                expression: check_expression(expression.as_ref()),
+            })
+            .collect();
+        self.domains.push(DomainType {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            base_type: column_type(base_type),
+            not_null: false,
+            default: None,
+            checks,
+        });
+        self
+    }
+
+    /// Composite type `schema.name` with `(attribute, type)` fields.
+    pub fn composite_type<I, S, T>(mut self, schema: &str, name: &str, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+        T: AsRef<str>,
+    {
+        self.composite_types.push(CompositeType {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            attributes: attributes
+                .into_iter()
+                .map(|(name, data_type)| CompositeAttribute {
+                    name: name.into(),
+                    column_type: column_type(data_type.as_ref()),
+                })
+                .collect(),
+        });
+        self
+    }
+
+    /// Add `table` to `schema` (created on first use).
+    pub fn table(mut self, schema: &str, table: TableBuilder) -> Self {
+        self.tables.push((schema.to_string(), table));
+        self
+    }
+
+    /// The schema, checked with [`validate_schema`]; misuse of a
+    /// [`TableBuilder`] (a column modifier before any column) is reported
+    /// here too.
+    pub fn build(mut self) -> Result<DatabaseSchema> {
+        let user_types: Vec<(String, String)> = self
+            .enums
+            .iter()
+            .map(|enum_type| (enum_type.schema.clone(), enum_type.name.clone()))
+            .chain(
+                self.domains
+                    .iter()
+                    .map(|domain| (domain.schema.clone(), domain.name.clone())),
+            )
+            .chain(
+                self.composite_types
+                    .iter()
+                    .map(|composite| (composite.schema.clone(), composite.name.clone())),
+            )
+            .collect();
+        for domain in &mut self.domains {
+            resolve_user_type(&mut domain.base_type, &domain.schema, &user_types);
+        }
+        for composite in &mut self.composite_types {
+            for attribute in &mut composite.attributes {
+                resolve_user_type(&mut attribute.column_type, &composite.schema, &user_types);
+            }
+        }
+        let mut schemas: Vec<Schema> = Vec::new();
+        for (schema_name, builder) in self.tables {
+            if let Some(error) = builder.errors.first() {
+                return Err(Error::InvalidSchema(format!(
+                    "{schema_name}.{}: {error}",
+                    builder.name
+                )));
+            }
+            let table = builder.finish(&schema_name, &user_types);
+            match schemas
+                .iter_mut()
+                .find(|db_schema| db_schema.name == schema_name)
+            {
+                Some(db_schema) => db_schema.tables.push(table),
+                None => schemas.push(Schema {
+                    name: schema_name,
+                    tables: vec![table],
+                }),
+            }
+        }
+        let schema = DatabaseSchema {
+            schema_version: SCHEMA_VERSION.to_string(),
+            engine: self.engine,
+            database: self.database,
+            schemas,
+            enums: self.enums,
+            domains: self.domains,
+            composite_types: self.composite_types,
+            sequences: Vec::new(),
+            current_role: None,
+            failed_tables: Vec::new(),
+            external_tables: Vec::new(),
+            schema_fingerprint: None,
+        };
+        validate_schema(&schema)?;
+        Ok(schema)
+    }
+}
+
+/// Builder of one [`Table`]. Column modifiers (`not_null`, `default_value`,
+/// ...) apply to the last added column and `on_delete`/`on_update` to the
+/// last foreign key.
+#[derive(Debug, Clone)]
+pub struct TableBuilder {
+    name: String,
+    kind: TableKind,
+    comment: Option<String>,
+    columns: Vec<Column>,
+    constraints: Vec<Constraint>,
+    indexes: Vec<(String, Vec<String>, bool)>,
+    partition_key: Option<PartitionKey>,
+    partitions: Vec<Partition>,
+    triggers: Vec<Trigger>,
+    view_definition: Option<String>,
+    depends_on: Vec<ViewDependency>,
+    errors: Vec<String>,
+}
+
+impl TableBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: TableKind::Table,
+            comment: None,
+            columns: Vec::new(),
+            constraints: Vec::new(),
+            indexes: Vec::new(),
+            partition_key: None,
+            partitions: Vec::new(),
+            triggers: Vec::new(),
+            view_definition: None,
+            depends_on: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn kind(mut self, kind: TableKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Nullable column of Postgres type `data_type`.
+    pub fn column(mut self, name: impl Into<String>, data_type: &str) -> Self {
+        self.columns.push(Column {
+            ordinal_position: self.columns.len() as i16 + 1,
+            name: name.into(),
+            column_type: column_type(data_type),
+            is_nullable: true,
+            default: None,
+            identity: None,
+            generated: None,
+            comment: None,
+            stats: None,
+        });
+        self
+    }
+
+    pub fn not_null(self) -> Self {
+        self.last_column("not_null", |column| column.is_nullable = false)
+    }
+
+    /// Default expression, as SQL (`now()`, `'draft'::text`).
+    pub fn default_value(self, expression: impl Into<String>) -> Self {
+        let expression = expression.into();
+        self.last_column("default_value", |column| column.default = Some(expression))
+    }
+
+    /// Identity column (implies NOT NULL).
+    pub fn identity(self, generation: IdentityGeneration) -> Self {
+        self.last_column("identity", |column| {
+            column.identity = Some(generation);
+            column.is_nullable = false;
+        })
+    }
+
+    /// Stored generated column computed from `expression`.
+    pub fn generated(self, expression: impl Into<String>) -> Self {
+        let expression = expression.into();
+        self.last_column("generated", |column| {
+            column.generated = Some(GeneratedExpression {
+                kind: GeneratedKind::Stored,
+                expression: Some(expression),
+            })
+        })
+    }
+
+    pub fn column_comment(self, comment: impl Into<String>) -> Self {
+        let comment = comment.into();
+        self.last_column("column_comment", |column| column.comment = Some(comment))
+    }
+
+    /// Primary key `<table>_pkey`; its columns become NOT NULL.
+    pub fn primary_key<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns = names(columns);
+        for column in &mut self.columns {
+            if columns.contains(&column.name) {
+                column.is_nullable = false;
+            }
+        }
+        let name = format!("{}_pkey", self.name);
+        self.indexes.push((name.clone(), columns.clone(), true));
+        self.constraints.push(Constraint::PrimaryKey(PrimaryKey {
+            name: Some(name),
+            columns,
+        }));
+        self
+    }
+
+    /// Unique constraint `<table>_<columns>_key`.
+    pub fn unique<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns = names(columns);
+        let name = format!("{}_{}_key", self.name, columns.join("_"));
+        self.indexes.push((name.clone(), columns.clone(), true));
+        self.constraints.push(Constraint::Unique(UniqueConstraint {
+            name: Some(name),
+            columns,
+            is_deferrable: false,
+            initially_deferred: false,
+        }));
+        self
+    }
+
+    /// Check constraint `<table>_check` (numbered after the first); the
+    /// expression may omit the `CHECK (...)` wrapper.
+    pub fn check(mut self, expression: &str) -> Self {
+        let count = self
+            .constraints
+            .iter()
+            .filter(|constraint| matches!(constraint, Constraint::Check(_)))
+            .count();
+        let name = match count {
+            0 => format!("{}_check", self.name),
+            count => format!("{}_check{count}", self.name),
+        };
+        self.constraints.push(Constraint::Check(CheckConstraint {
+            name: Some(name),
+            expression: check_expression(expression),
+        }));
+        self
+    }
+
+    /// Foreign key `<table>_<columns>_fkey` to `parent` (`table` in the same
+    /// schema, or `schema.table`).
+    pub fn foreign_key<I, S, J, T>(mut self, columns: I, parent: &str, parent_columns: J) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+        J: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let columns = names(columns);
+        let (referenced_schema, referenced_table) = match parent.split_once('.') {
+            Some((schema, table)) => (schema.to_string(), table.to_string()),
+            // Filled with the table's schema by `SchemaBuilder::build`.
+            None => (String::new(), parent.to_string()),
+        };
+        self.constraints.push(Constraint::ForeignKey(ForeignKey {
+            name: Some(format!("{}_{}_fkey", self.name, columns.join("_"))),
+            columns,
+            referenced_schema,
+            referenced_table,
+            referenced_columns: names(parent_columns),
+            on_update: FkAction::NoAction,
+            on_delete: FkAction::NoAction,
+            match_type: FkMatchType::Simple,
+            is_deferrable: false,
+            initially_deferred: false,
+        }));
+        self
+    }
+
+    pub fn on_delete(self, action: FkAction) -> Self {
+        self.last_foreign_key("on_delete", |fk| fk.on_delete = action)
+    }
+
+    pub fn on_update(self, action: FkAction) -> Self {
+        self.last_foreign_key("on_update", |fk| fk.on_update = action)
+    }
+
+    /// Btree index `<table>_<columns>_idx`.
+    pub fn index<I, S>(mut self, columns: I, unique: bool) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns = names(columns);
+        let name = format!("{}_{}_idx", self.name, columns.join("_"));
+        self.indexes.push((name, columns, unique));
+        self
+    }
+
+    /// Partitioned table (`PARTITION BY <strategy> (<columns>)`).
+    pub fn partition_by<I, S>(mut self, strategy: PartitionStrategy, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.kind = TableKind::PartitionedTable;
+        self.partition_key = Some(PartitionKey {
+            strategy,
+            columns: names(columns),
+        });
+        self
+    }
+
+    /// Child partition `table` (in the same schema, or `schema.table`) with
+    /// its bound, e.g. `FOR VALUES IN ('sp')`.
+    pub fn partition(mut self, table: &str, bound: &str) -> Self {
+        let (schema, name) = match table.split_once('.') {
+            Some((schema, name)) => (schema.to_string(), name.to_string()),
+            // Filled with the table's schema by `SchemaBuilder::build`.
+            None => (String::new(), table.to_string()),
+        };
+        self.partitions.push(Partition {
+            schema,
+            name,
+            bound: Some(bound.to_string()),
+        });
+        self
+    }
+
+    /// Query of a view or materialized view (set the kind with `kind`) and
+    /// the relations it reads (in the same schema, or `schema.table`).
+    pub fn view_definition<I, S>(mut self, definition: &str, depends_on: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.view_definition = Some(definition.to_string());
+        self.depends_on = depends_on
+            .into_iter()
+            .map(|relation| match relation.as_ref().split_once('.') {
+                Some((schema, name)) => ViewDependency {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                },
+                // Filled with the view's schema by `SchemaBuilder::build`.
+                None => ViewDependency {
+                    schema: String::new(),
+                    name: relation.as_ref().to_string(),
+                },
+            })
+            .collect();
+        self
+    }
+
+    /// Enabled `FOR EACH ROW` trigger executing `function`.
+    pub fn trigger<I>(
+        mut self,
+        name: impl Into<String>,
+        timing: TriggerTiming,
+        events: I,
+        function: &str,
+    ) -> Self
+    where
+        I: IntoIterator<Item = TableEvent>,
+    {
+        self.triggers.push(Trigger {
+            name: name.into(),
+            timing,
+            events: events.into_iter().collect(),
+            for_each_row: true,
+            function: Some(function.to_string()),
+            enabled: true,
+        });
+        self
+    }
+
+    fn last_column(mut self, method: &str, apply: impl FnOnce(&mut Column)) -> Self {
+        match self.columns.last_mut() {
+            Some(column) => apply(column),
+            None => self
+                .errors
+                .push(format!("{method} called before any column")),
+        }
+        self
+    }
+
+    fn last_foreign_key(mut self, method: &str, apply: impl FnOnce(&mut ForeignKey)) -> Self {
+        let last = self
+            .constraints
+            .iter_mut()
+            .rev()
+            .find_map(|constraint| match constraint {
+                Constraint::ForeignKey(fk) => Some(fk),
+                _ => None,
+            });
+        match last {
+            Some(fk) => apply(fk),
+            None => self
+                .errors
+                .push(format!("{method} called before any foreign key")),
+        }
+        self
+    }
+
+    fn finish(mut self, schema: &str, user_types: &[(String, String)]) -> Table {
+        for column in &mut self.columns {
+            resolve_user_type(&mut column.column_type, schema, user_types);
+        }
+        for constraint in &mut self.constraints {
+            if let Constraint::ForeignKey(fk) = constraint
+                && fk.referenced_schema.is_empty()
+            {
+                fk.referenced_schema = schema.to_string();
+            }
+        }
+        for partition in &mut self.partitions {
+            if partition.schema.is_empty() {
+                partition.schema = schema.to_string();
+            }
+        }
+        for dependency in &mut self.depends_on {
+            if dependency.schema.is_empty() {
+                dependency.schema = schema.to_string();
+            }
+        }
+        let primary = self
+            .constraints
+            .iter()
+            .find_map(|constraint| match constraint {
+                Constraint::PrimaryKey(pk) => pk.name.clone(),
+                _ => None,
+            });
+        let mut indexes: Vec<Index> = self
+            .indexes
+            .into_iter()
+            .map(|(name, columns, unique)| Index {
+                definition: format!(
+                    "CREATE {}INDEX {name} ON {schema}.{} USING btree ({})",
+                    if unique { "UNIQUE " } else { "" },
+                    self.name,
+                    columns.join(", ")
+                ),
+                is_primary: primary.as_deref() == Some(name.as_str()),
+                name,
+                is_unique: unique,
+                is_valid: true,
+                method: "btree".to_string(),
+                predicate: None,
+                expressions: Vec::new(),
+                shard_buckets: None,
+            })
+            .collect();
+        indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        Table {
+            name: self.name,
+            kind: self.kind,
+            comment: self.comment,
+            columns: self.columns,
+            constraints: self.constraints,
+            indexes,
+            partition_key: self.partition_key,
+            partitions: self.partitions,
+            row_estimate: None,
+            triggers: self.triggers,
+            rules: Vec::new(),
+            catalog_version: None,
+            hidden_columns: Vec::new(),
+            view_definition: self.view_definition,
+            depends_on: self.depends_on,
+            foreign_table: None,
+            privileges: None,
+        }
+    }
+}
+
+fn names<I, S>(columns: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    columns.into_iter().map(Into::into).collect()
+}
+
+/// Type metadata of a Postgres-formatted type, as introspection reports it.
+fn column_type(data_type: &str) -> ColumnType {
+    let data_type = data_type.trim();
+    let (element, array) = match data_type.strip_suffix("[]") {
+        Some(element) => (element.trim(), true),
+        None => (data_type, false),
+    };
+    let (base, args) = match element.split_once('(') {
+        Some((base, rest)) => {
+            let (args, tail) = rest.split_once(')').unwrap_or((rest, ""));
+            let args: Vec<i32> = args
+                .split(',')
+                .filter_map(|arg| arg.trim().parse().ok())
+                .collect();
+            (
+                format!("{} {}", base.trim(), tail.trim())
+                    .trim()
+                    .to_string(),
+                args,
+            )
+        }
+        None => (element.to_string(), Vec::new()),
+    };
+
+    let mut column_type = ColumnType {
+        data_type: data_type.to_string(),
+        udt_schema: "pg_catalog".to_string(),
+        udt_name: base.clone(),
+        character_max_length: None,
+        numeric_precision: None,
+        numeric_scale: None,
+        collation: None,
+    };
+    let (udt_name, precision) = match base.as_str() {
+        "smallint" | "int2" => ("int2", Some(16)),
+        "integer" | "int" | "int4" => ("int4", Some(32)),
+        "bigint" | "int8" => ("int8", Some(64)),
+        "boolean" | "bool" => ("bool", None),
+        "character varying" | "varchar" => ("varchar", None),
+        "character" | "char" | "bpchar" => ("bpchar", None),
+        "numeric" | "decimal" => ("numeric", None),
+        "real" | "float4" => ("float4", None),
+        "double precision" | "float8" => ("float8", None),
+        "timestamp without time zone" | "timestamp" => ("timestamp", None),
+        "timestamp with time zone" | "timestamptz" => ("timestamptz", None),
+        "time without time zone" | "time" => ("time", None),
+        "time with time zone" | "timetz" => ("timetz", None),
+        other => (other, None),
+    };
+    column_type.udt_name = udt_name.to_string();
+    match udt_name {
+        "int2" | "int4" | "int8" => {
+            column_type.numeric_precision = precision;
+            column_type.numeric_scale = Some(0);
+        }
+        "varchar" | "bpchar" => column_type.character_max_length = args.first().copied(),
+        "numeric" => {
+            column_type.numeric_precision = args.first().copied();
+            column_type.numeric_scale = args.get(1).copied();
+        }
+        _ => {}
+    }
+    if array {
+        column_type.udt_name = format!("_{}", column_type.udt_name);
+    }
+    column_type
+}
+
+/// `expression` wrapped in `CHECK (...)` unless it already is.
+fn check_expression(expression: &str) -> String {
+    if expression.trim_start().to_uppercase().starts_with("CHECK") {
+        expression.to_string()
+    } else {
+        format!("CHECK ({expression})")
+    }
+}
+
+/// Point a column typed `schema.type` (or `type`, in the table's schema) at
+/// the user type (enum, domain or composite) of that name.
+fn resolve_user_type(
+    column_type: &mut ColumnType,
+    table_schema: &str,
+    user_types: &[(String, String)],
+) {
+    let (element, array) = match column_type.udt_name.strip_prefix('_') {
+        Some(element) => (element.to_string(), true),
+        None => (column_type.udt_name.clone(), false),
+    };
+    let (schema, name) = match element.split_once('.') {
+        Some((schema, name)) => (schema.to_string(), name.to_string()),
+        None => (table_schema.to_string(), element),
+    };
+    let Some((type_schema, type_name)) = user_types
+        .iter()
+        .find(|(type_schema, type_name)| *type_schema == schema && *type_name == name)
+    else {
+        return;
+    };
+    column_type.udt_schema = type_schema.clone();
+    column_type.udt_name = if array {
+        format!("_{type_name}")
+    } else {
+        type_name.clone()
+    };
+    let qualified = format!("{type_schema}.{type_name}");
+    column_type.data_type = if array {
+        format!("{qualified}[]")
+    } else {
+        qualified
+    };
+}
@@ -24,6 +24,30 @@ pub struct CheckConstraint {
     pub expression: String,
 }
 
+/// Exclusion constraint (`EXCLUDE USING gist (room WITH =, during WITH &&)`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExclusionConstraint {
+    pub name: Option<String>,
+    /// Index access method (e.g. `gist`).
+    pub method: String,
+    /// Compared elements in declaration order.
+    pub elements: Vec<ExclusionElement>,
+    /// `WHERE` predicate of a partial exclusion constraint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicate: Option<String>,
+    pub is_deferrable: bool,
+    pub initially_deferred: bool,
+}
+
+/// Column or expression of an exclusion constraint and its operator.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExclusionElement {
+    /// Column name or expression text.
+    pub expression: String,
+    /// Operator two rows must not satisfy together (e.g. `&&`).
+    pub operator: String,
+}
+
 /// Foreign key action semantics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -70,6 +94,26 @@ pub struct Index {
     pub is_valid: bool,
     pub method: String,
     pub definition: String,
+    /// `WHERE` predicate of a partial index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicate: Option<String>,
+    /// Text of the key expressions (keys that are not plain columns).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expressions: Vec<String>,
+    /// Bucket count of a CockroachDB hash-sharded index (`USING HASH`); the
+    /// hidden shard column it keys on is not among the table columns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_buckets: Option<u32>,
+}
+
+impl Index {
+    /// Unique index the generator cannot enforce through constraints: partial
+    /// or over expressions.
+    pub fn is_unenforced_unique(&self) -> bool {
+        self.is_unique
+            && !self.is_primary
+            && (self.predicate.is_some() || !self.expressions.is_empty())
+    }
 }
 
 /// Table-level constraint definitions.
@@ -80,4 +124,5 @@ pub enum Constraint {
     ForeignKey(ForeignKey),
     Unique(UniqueConstraint),
     Check(CheckConstraint),
+    Exclusion(ExclusionConstraint),
 }
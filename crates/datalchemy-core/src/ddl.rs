@@ -0,0 +1,616 @@
+//! Postgres DDL reconstructed from a [`DatabaseSchema`] snapshot.
+//!
+//! The script creates schemas, enum types, domains, composite types,
+//! sequences referenced by defaults
+//! (with their start, increment and cache when captured), tables with inline
+//! primary key/unique/check constraints, foreign keys (added after every table
+//! exists), secondary indexes and comments, so it can be run against an empty
+//! database in a single pass. Views and materialized views with a captured
+//! query come last, each after the views it reads (materialized ones `WITH
+//! NO DATA`, to refresh after loading). The other views and foreign tables
+//! (whose server definition is not captured) are listed as comments only.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::constraints::{Constraint, FkAction, FkMatchType, ForeignKey};
+use crate::graph::view_order;
+use crate::schema::{Column, DatabaseSchema, Table, TableKind};
+use crate::types::{DomainType, GeneratedKind, IdentityGeneration};
+
+/// Render the snapshot as a Postgres DDL script.
+pub fn render_postgres_ddl(schema: &DatabaseSchema) -> String {
+    let mut out = String::new();
+    out.push_str("-- Schema reconstructed by datalchemy from schema.json.\n");
+    if let Some(database) = &schema.database {
+        let _ = writeln!(out, "-- Source database: {database}");
+    }
+
+    let namespaces: BTreeSet<&str> = schema
+        .schemas
+        .iter()
+        .map(|s| s.name.as_str())
+        .chain(schema.enums.iter().map(|e| e.schema.as_str()))
+        .chain(schema.domains.iter().map(|d| d.schema.as_str()))
+        .chain(schema.composite_types.iter().map(|c| c.schema.as_str()))
+        .collect();
+    if !namespaces.is_empty() {
+        out.push('\n');
+    }
+    for name in namespaces {
+        let _ = writeln!(out, "CREATE SCHEMA IF NOT EXISTS {};", quote_ident(name));
+    }
+
+    if !schema.enums.is_empty() {
+        out.push('\n');
+    }
+    for enum_type in &schema.enums {
+        let labels = enum_type
+            .labels
+            .iter()
+            .map(|label| quote_literal(label))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            out,
+            "CREATE TYPE {} AS ENUM ({});",
+            qualified(&enum_type.schema, &enum_type.name),
+            labels
+        );
+    }
+
+    if !schema.domains.is_empty() {
+        out.push('\n');
+    }
+    for domain in domains_in_dependency_order(schema) {
+        let _ = write!(
+            out,
+            "CREATE DOMAIN {} AS {}",
+            qualified(&domain.schema, &domain.name),
+            domain.base_type.data_type
+        );
+        if let Some(default) = &domain.default {
+            let _ = write!(out, " DEFAULT {default}");
+        }
+        if domain.not_null {
+            out.push_str(" NOT NULL");
+        }
+        for check in &domain.checks {
+            let _ = write!(
+                out,
+                " {}{}",
+                constraint_prefix(check.name.as_deref()),
+                check.expression
+            );
+        }
+        out.push_str(";\n");
+    }
+
+    if !schema.composite_types.is_empty() {
+        out.push('\n');
+    }
+    for composite in &schema.composite_types {
+        let attributes = composite
+            .attributes
+            .iter()
+            .map(|attribute| {
+                format!(
+                    "{} {}",
+                    quote_ident(&attribute.name),
+                    attribute.column_type.data_type
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            out,
+            "CREATE TYPE {} AS ({attributes});",
+            qualified(&composite.schema, &composite.name)
+        );
+    }
+
+    let tables: Vec<(&str, &Table)> = schema
+        .schemas
+        .iter()
+        .flat_map(|s| s.tables.iter().map(move |t| (s.name.as_str(), t)))
+        .collect();
+    let (stored, skipped): (Vec<_>, Vec<_>) = tables
+        .into_iter()
+        .partition(|(_, table)| is_stored(&table.kind));
+
+    let sequences: BTreeSet<&str> = stored
+        .iter()
+        .flat_map(|(_, table)| table.columns.iter())
+        .filter_map(|col| col.default.as_deref().and_then(sequence_name))
+        .collect();
+    if !sequences.is_empty() {
+        out.push('\n');
+    }
+    for sequence in sequences {
+        let options = schema
+            .sequences
+            .iter()
+            .find(|known| known.qualified_name() == sequence)
+            .map(|known| {
+                format!(
+                    " START WITH {} INCREMENT BY {} CACHE {}",
+                    known.start, known.increment, known.cache
+                )
+            })
+            .unwrap_or_default();
+        let _ = writeln!(out, "CREATE SEQUENCE IF NOT EXISTS {sequence}{options};");
+    }
+
+    for (schema_name, table) in &stored {
+        out.push('\n');
+        render_table(&mut out, schema_name, table);
+    }
+
+    let foreign_keys: Vec<(&str, &Table, &ForeignKey)> = stored
+        .iter()
+        .flat_map(|(schema_name, table)| {
+            table.constraints.iter().filter_map(move |c| match c {
+                Constraint::ForeignKey(fk) => Some((*schema_name, *table, fk)),
+                _ => None,
+            })
+        })
+        .collect();
+    if !foreign_keys.is_empty() {
+        out.push('\n');
+    }
+    for (schema_name, table, fk) in foreign_keys {
+        render_foreign_key(&mut out, schema_name, table, fk);
+    }
+
+    let mut indexes = Vec::new();
+    for (_, table) in &stored {
+        let constraint_names: BTreeSet<&str> = table
+            .constraints
+            .iter()
+            .filter_map(|c| match c {
+                Constraint::PrimaryKey(pk) => pk.name.as_deref(),
+                Constraint::Unique(unique) => unique.name.as_deref(),
+                Constraint::Exclusion(exclusion) => exclusion.name.as_deref(),
+                _ => None,
+            })
+            .collect();
+        indexes.extend(table.indexes.iter().filter(|index| {
+            index.is_valid && !index.is_primary && !constraint_names.contains(index.name.as_str())
+        }));
+    }
+    if !indexes.is_empty() {
+        out.push('\n');
+    }
+    for index in indexes {
+        let _ = writeln!(out, "{};", index.definition.trim_end_matches(';'));
+    }
+
+    let views: BTreeMap<String, (&str, &Table)> = skipped
+        .iter()
+        .filter(|(_, table)| table.kind.is_view())
+        .map(|(schema_name, table)| {
+            (
+                format!("{schema_name}.{}", table.name),
+                (*schema_name, *table),
+            )
+        })
+        .collect();
+    let mut rendered_views: BTreeSet<String> = BTreeSet::new();
+    let mut unrendered_views: Vec<String> = Vec::new();
+    for (key, _) in view_order(schema) {
+        let (schema_name, table) = views[&key];
+        let name = qualified(schema_name, &table.name);
+        let Some(definition) = &table.view_definition else {
+            unrendered_views.push(format!(
+                "-- {} {name} skipped: definition not captured in the snapshot",
+                kind_label(&table.kind)
+            ));
+            continue;
+        };
+        let missing = table.depends_on.iter().find(|dependency| {
+            let read = format!("{}.{}", dependency.schema, dependency.name);
+            views.contains_key(&read) && !rendered_views.contains(&read)
+        });
+        if let Some(missing) = missing {
+            unrendered_views.push(format!(
+                "-- {} {name} skipped: reads {}, which is not rendered",
+                kind_label(&table.kind),
+                qualified(&missing.schema, &missing.name)
+            ));
+            continue;
+        }
+        let definition = definition.trim().trim_end_matches(';');
+        let _ = match table.kind {
+            TableKind::MaterializedView => writeln!(
+                out,
+                "\nCREATE MATERIALIZED VIEW {name} AS\n{definition}\nWITH NO DATA;"
+            ),
+            _ => writeln!(out, "\nCREATE VIEW {name} AS\n{definition};"),
+        };
+        rendered_views.insert(key);
+    }
+
+    let mut comments = Vec::new();
+    for (schema_name, table) in &stored {
+        let table_name = qualified(schema_name, &table.name);
+        if let Some(comment) = &table.comment {
+            comments.push(format!(
+                "COMMENT ON TABLE {} IS {};",
+                table_name,
+                quote_literal(comment)
+            ));
+        }
+        for col in &table.columns {
+            if let Some(comment) = &col.comment {
+                comments.push(format!(
+                    "COMMENT ON COLUMN {}.{} IS {};",
+                    table_name,
+                    quote_ident(&col.name),
+                    quote_literal(comment)
+                ));
+            }
+        }
+    }
+    if !comments.is_empty() {
+        out.push('\n');
+    }
+    for comment in comments {
+        out.push_str(&comment);
+        out.push('\n');
+    }
+
+    let mut skipped_lines: Vec<String> = skipped
+        .iter()
+        .filter(|(_, table)| !table.kind.is_view())
+        .map(|(schema_name, table)| match &table.foreign_table {
+            Some(source) => format!(
+                "-- {} {} skipped: rows live in server {} ({})",
+                kind_label(&table.kind),
+                qualified(schema_name, &table.name),
+                quote_ident(&source.server),
+                source.wrapper
+            ),
+            None => format!(
+                "-- {} {} skipped: definition not captured in the snapshot",
+                kind_label(&table.kind),
+                qualified(schema_name, &table.name)
+            ),
+        })
+        .collect();
+    skipped_lines.extend(unrendered_views);
+    for (schema_name, table) in &stored {
+        let table_name = qualified(schema_name, &table.name);
+        for trigger in &table.triggers {
+            skipped_lines.push(format!(
+                "-- trigger {} on {table_name} skipped: function body not captured in the snapshot",
+                quote_ident(&trigger.name)
+            ));
+        }
+        for rule in &table.rules {
+            skipped_lines.push(format!(
+                "-- rule {} on {table_name} skipped: definition not captured in the snapshot",
+                quote_ident(&rule.name)
+            ));
+        }
+    }
+    if !skipped_lines.is_empty() {
+        out.push('\n');
+    }
+    for line in skipped_lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out
+}
+
+fn render_table(out: &mut String, schema_name: &str, table: &Table) {
+    let mut columns: Vec<&Column> = table.columns.iter().collect();
+    columns.sort_by_key(|col| col.ordinal_position);
+
+    let mut lines: Vec<String> = columns.iter().map(|col| column_definition(col)).collect();
+    for constraint in &table.constraints {
+        match constraint {
+            Constraint::PrimaryKey(pk) => lines.push(format!(
+                "{}PRIMARY KEY ({})",
+                constraint_prefix(pk.name.as_deref()),
+                ident_list(&pk.columns)
+            )),
+            Constraint::Unique(unique) => lines.push(format!(
+                "{}UNIQUE ({}){}",
+                constraint_prefix(unique.name.as_deref()),
+                ident_list(&unique.columns),
+                deferrable(unique.is_deferrable, unique.initially_deferred)
+            )),
+            Constraint::Check(check) => lines.push(format!(
+                "{}{}",
+                constraint_prefix(check.name.as_deref()),
+                check.expression
+            )),
+            Constraint::Exclusion(exclusion) => {
+                let elements: Vec<String> = exclusion
+                    .elements
+                    .iter()
+                    .map(|element| format!("{} WITH {}", element.expression, element.operator))
+                    .collect();
+                let predicate = exclusion
+                    .predicate
+                    .as_deref()
+                    .map(|predicate| format!(" WHERE ({predicate})"))
+                    .unwrap_or_default();
+                lines.push(format!(
+                    "{}EXCLUDE USING {} ({}){predicate}{}",
+                    constraint_prefix(exclusion.name.as_deref()),
+                    exclusion.method,
+                    elements.join(", "),
+                    deferrable(exclusion.is_deferrable, exclusion.initially_deferred)
+                ));
+            }
+            Constraint::ForeignKey(_) => {}
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "CREATE TABLE {} (",
+        qualified(schema_name, &table.name)
+    );
+    let _ = writeln!(out, "    {}", lines.join(",\n    "));
+    out.push_str(");\n");
+}
+
+fn column_definition(col: &Column) -> String {
+    let mut line = format!("{} {}", quote_ident(&col.name), col.column_type.data_type);
+    if let Some(collation) = &col.column_type.collation {
+        let _ = write!(line, " COLLATE {}", quote_ident(collation));
+    }
+    if let Some(generated) = &col.generated {
+        if let Some(expression) = &generated.expression {
+            let kind = match generated.kind {
+                GeneratedKind::Stored => "STORED",
+                GeneratedKind::Virtual => "VIRTUAL",
+            };
+            let _ = write!(line, " GENERATED ALWAYS AS ({expression}) {kind}");
+        }
+    } else if let Some(identity) = &col.identity {
+        line.push_str(match identity {
+            IdentityGeneration::Always => " GENERATED ALWAYS AS IDENTITY",
+            IdentityGeneration::ByDefault => " GENERATED BY DEFAULT AS IDENTITY",
+        });
+    } else if let Some(default) = &col.default {
+        let _ = write!(line, " DEFAULT {default}");
+    }
+    if !col.is_nullable {
+        line.push_str(" NOT NULL");
+    }
+    line
+}
+
+fn render_foreign_key(out: &mut String, schema_name: &str, table: &Table, fk: &ForeignKey) {
+    let mut statement = format!(
+        "ALTER TABLE {} ADD {}FOREIGN KEY ({}) REFERENCES {} ({})",
+        qualified(schema_name, &table.name),
+        constraint_prefix(fk.name.as_deref()),
+        ident_list(&fk.columns),
+        qualified(&fk.referenced_schema, &fk.referenced_table),
+        ident_list(&fk.referenced_columns)
+    );
+    match fk.match_type {
+        FkMatchType::Full => statement.push_str(" MATCH FULL"),
+        FkMatchType::Partial => statement.push_str(" MATCH PARTIAL"),
+        FkMatchType::Simple | FkMatchType::Unknown => {}
+    }
+    if let Some(action) = fk_action(&fk.on_update) {
+        let _ = write!(statement, " ON UPDATE {action}");
+    }
+    if let Some(action) = fk_action(&fk.on_delete) {
+        let _ = write!(statement, " ON DELETE {action}");
+    }
+    statement.push_str(&deferrable(fk.is_deferrable, fk.initially_deferred));
+    let _ = writeln!(out, "{statement};");
+}
+
+/// `NO ACTION` is the default and is omitted.
+fn fk_action(action: &FkAction) -> Option<&'static str> {
+    match action {
+        FkAction::Restrict => Some("RESTRICT"),
+        FkAction::Cascade => Some("CASCADE"),
+        FkAction::SetNull => Some("SET NULL"),
+        FkAction::SetDefault => Some("SET DEFAULT"),
+        FkAction::NoAction | FkAction::Unknown => None,
+    }
+}
+
+fn deferrable(is_deferrable: bool, initially_deferred: bool) -> String {
+    match (is_deferrable, initially_deferred) {
+        (false, _) => String::new(),
+        (true, false) => " DEFERRABLE".to_string(),
+        (true, true) => " DEFERRABLE INITIALLY DEFERRED".to_string(),
+    }
+}
+
+fn constraint_prefix(name: Option<&str>) -> String {
+    name.map(|name| format!("CONSTRAINT {} ", quote_ident(name)))
+        .unwrap_or_default()
+}
+
+fn is_stored(kind: &TableKind) -> bool {
+    matches!(kind, TableKind::Table | TableKind::PartitionedTable)
+}
+
+fn kind_label(kind: &TableKind) -> &str {
+    match kind {
+        TableKind::Table => "table",
+        TableKind::PartitionedTable => "partitioned table",
+        TableKind::View => "view",
+        TableKind::MaterializedView => "materialized view",
+        TableKind::ForeignTable => "foreign table",
+        TableKind::Other(other) => other,
+    }
+}
+
+/// Sequence referenced by a `nextval('name'::regclass)` default.
+/// Domains ordered so that a domain over another domain comes after it.
+fn domains_in_dependency_order(schema: &DatabaseSchema) -> Vec<&DomainType> {
+    let mut pending: Vec<&DomainType> = schema.domains.iter().collect();
+    let mut ordered: Vec<&DomainType> = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let before = pending.len();
+        pending.retain(|domain| {
+            let waits = schema.domain(&domain.base_type).is_some_and(|base| {
+                !std::ptr::eq(base, *domain)
+                    && !ordered.iter().any(|done| std::ptr::eq(*done, base))
+            });
+            if !waits {
+                ordered.push(domain);
+            }
+            waits
+        });
+        if pending.len() == before {
+            ordered.append(&mut pending);
+        }
+    }
+    ordered
+}
+
+pub(crate) fn sequence_name(default: &str) -> Option<&str> {
+    let rest = default.strip_prefix("nextval('")?;
+    let end = rest.find("'::regclass")?;
+    Some(&rest[..end])
+}
+
+fn qualified(schema_name: &str, name: &str) -> String {
+    format!("{}.{}", quote_ident(schema_name), quote_ident(name))
+}
+
+fn ident_list(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|col| quote_ident(col))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Quote an identifier when Postgres would not accept it bare.
+pub fn quote_ident(ident: &str) -> String {
+    let mut chars = ident.chars();
+    let simple = chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_lowercase() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_' || ch == '$');
+    if simple && !RESERVED_KEYWORDS.contains(&ident) {
+        ident.to_string()
+    } else {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+}
+
+/// Quote a string literal.
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Postgres reserved keywords (cannot be used as bare identifiers).
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "binary",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "similar",
+    "some",
+    "symmetric",
+    "system_user",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
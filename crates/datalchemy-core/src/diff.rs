@@ -0,0 +1,314 @@
+//! Structural diff between two schema snapshots.
+//!
+//! Tables are matched by `schema.table`, columns by name and constraints by
+//! [`constraint_signature`], so renaming a constraint is not a change. A
+//! column is modified when its type, nullability, default, identity or
+//! generated expression differs; comments and ordinal positions are ignored.
+//! Enums are matched by `schema.name` and modified when their labels (or the
+//! label order) differ.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constraints::Constraint;
+use crate::schema::{Column, DatabaseSchema, Table};
+use crate::types::EnumType;
+
+/// Tables and enums added, removed and modified from `before` to `after`, in
+/// `schema.table` / `schema.enum` order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    pub tables_added: Vec<String>,
+    pub tables_removed: Vec<String>,
+    pub tables_modified: Vec<TableDiff>,
+    #[serde(default)]
+    pub enums_added: Vec<String>,
+    #[serde(default)]
+    pub enums_removed: Vec<String>,
+    #[serde(default)]
+    pub enums_modified: Vec<EnumDiff>,
+}
+
+/// Column and constraint changes of a table present in both snapshots.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableDiff {
+    /// `schema.table`.
+    pub table: String,
+    pub columns_added: Vec<Column>,
+    pub columns_removed: Vec<Column>,
+    pub columns_modified: Vec<ColumnDiff>,
+    pub constraints_added: Vec<Constraint>,
+    pub constraints_removed: Vec<Constraint>,
+}
+
+/// An enum whose labels changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumDiff {
+    /// `schema.enum`.
+    pub name: String,
+    pub labels_added: Vec<String>,
+    pub labels_removed: Vec<String>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// A column whose definition changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnDiff {
+    pub name: String,
+    pub changes: Vec<ColumnChange>,
+    pub before: Column,
+    pub after: Column,
+}
+
+/// Aspect of a column definition that changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnChange {
+    Type,
+    Nullability,
+    Default,
+    Identity,
+    Generated,
+}
+
+impl ColumnChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Type => "type",
+            Self::Nullability => "nullability",
+            Self::Default => "default",
+            Self::Identity => "identity",
+            Self::Generated => "generated",
+        }
+    }
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.tables_added.is_empty()
+            && self.tables_removed.is_empty()
+            && self.tables_modified.is_empty()
+            && self.enums_added.is_empty()
+            && self.enums_removed.is_empty()
+            && self.enums_modified.is_empty()
+    }
+
+    /// Tables added, removed or modified, in `schema.table` order.
+    pub fn changed_tables(&self) -> BTreeSet<&str> {
+        self.tables_added
+            .iter()
+            .chain(&self.tables_removed)
+            .map(String::as_str)
+            .chain(
+                self.tables_modified
+                    .iter()
+                    .map(|table| table.table.as_str()),
+            )
+            .collect()
+    }
+
+    pub fn table(&self, name: &str) -> Option<&TableDiff> {
+        self.tables_modified
+            .iter()
+            .find(|table| table.table == name)
+    }
+}
+
+/// Compare two snapshots of a database schema.
+pub fn diff_schemas(before: &DatabaseSchema, after: &DatabaseSchema) -> SchemaDiff {
+    let mut diff = SchemaDiff::default();
+    diff_tables(before, after, &mut diff);
+    diff_enums(before, after, &mut diff);
+    diff
+}
+
+fn diff_tables(before: &DatabaseSchema, after: &DatabaseSchema, diff: &mut SchemaDiff) {
+    let before = tables_by_name(before);
+    let after = tables_by_name(after);
+    let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for name in names {
+        match (before.get(name), after.get(name)) {
+            (Some(_), None) => diff.tables_removed.push(name.clone()),
+            (None, Some(_)) => diff.tables_added.push(name.clone()),
+            (Some(old), Some(new)) => {
+                let table = table_diff(name, old, new);
+                if !table.is_empty() {
+                    diff.tables_modified.push(table);
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn diff_enums(before: &DatabaseSchema, after: &DatabaseSchema, diff: &mut SchemaDiff) {
+    let before = enums_by_name(before);
+    let after = enums_by_name(after);
+    let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for name in names {
+        match (before.get(name), after.get(name)) {
+            (Some(_), None) => diff.enums_removed.push(name.clone()),
+            (None, Some(_)) => diff.enums_added.push(name.clone()),
+            (Some(old), Some(new)) if old.labels != new.labels => {
+                diff.enums_modified.push(EnumDiff {
+                    name: name.clone(),
+                    labels_added: missing_from(&new.labels, &old.labels),
+                    labels_removed: missing_from(&old.labels, &new.labels),
+                    before: old.labels.clone(),
+                    after: new.labels.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Constraint without its name: kind, columns and, for foreign keys, the
+/// referenced table and columns.
+pub fn constraint_signature(constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::PrimaryKey(pk) => format!("pk ({})", pk.columns.join(", ")),
+        Constraint::ForeignKey(fk) => format!(
+            "fk ({}) -> {}.{} ({})",
+            fk.columns.join(", "),
+            fk.referenced_schema,
+            fk.referenced_table,
+            fk.referenced_columns.join(", ")
+        ),
+        Constraint::Unique(unique) => format!("unique ({})", unique.columns.join(", ")),
+        Constraint::Check(check) => format!("check {}", check.expression),
+        Constraint::Exclusion(exclusion) => {
+            let elements: Vec<String> = exclusion
+                .elements
+                .iter()
+                .map(|element| format!("{} with {}", element.expression, element.operator))
+                .collect();
+            let mut signature = format!(
+                "exclude using {} ({})",
+                exclusion.method,
+                elements.join(", ")
+            );
+            if let Some(predicate) = &exclusion.predicate {
+                signature.push_str(&format!(" where {predicate}"));
+            }
+            signature
+        }
+    }
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.columns_added.is_empty()
+            && self.columns_removed.is_empty()
+            && self.columns_modified.is_empty()
+            && self.constraints_added.is_empty()
+            && self.constraints_removed.is_empty()
+    }
+}
+
+fn tables_by_name(schema: &DatabaseSchema) -> BTreeMap<String, &Table> {
+    schema
+        .schemas
+        .iter()
+        .flat_map(|db_schema| {
+            db_schema
+                .tables
+                .iter()
+                .map(move |table| (format!("{}.{}", db_schema.name, table.name), table))
+        })
+        .collect()
+}
+
+fn enums_by_name(schema: &DatabaseSchema) -> BTreeMap<String, &EnumType> {
+    schema
+        .enums
+        .iter()
+        .map(|en| (format!("{}.{}", en.schema, en.name), en))
+        .collect()
+}
+
+/// Labels of `labels` not in `other`, in order.
+fn missing_from(labels: &[String], other: &[String]) -> Vec<String> {
+    labels
+        .iter()
+        .filter(|label| !other.contains(label))
+        .cloned()
+        .collect()
+}
+
+fn table_diff(name: &str, before: &Table, after: &Table) -> TableDiff {
+    let mut diff = TableDiff {
+        table: name.to_string(),
+        ..TableDiff::default()
+    };
+    for column in &before.columns {
+        match after.columns.iter().find(|col| col.name == column.name) {
+            None => diff.columns_removed.push(column.clone()),
+            Some(new) => {
+                let changes = column_changes(column, new);
+                if !changes.is_empty() {
+                    diff.columns_modified.push(ColumnDiff {
+                        name: column.name.clone(),
+                        changes,
+                        before: column.clone(),
+                        after: new.clone(),
+                    });
+                }
+            }
+        }
+    }
+    diff.columns_added = after
+        .columns
+        .iter()
+        .filter(|column| !before.columns.iter().any(|col| col.name == column.name))
+        .cloned()
+        .collect();
+
+    let old: BTreeSet<String> = before
+        .constraints
+        .iter()
+        .map(constraint_signature)
+        .collect();
+    let new: BTreeSet<String> = after.constraints.iter().map(constraint_signature).collect();
+    diff.constraints_removed = before
+        .constraints
+        .iter()
+        .filter(|constraint| !new.contains(&constraint_signature(constraint)))
+        .cloned()
+        .collect();
+    diff.constraints_added = after
+        .constraints
+        .iter()
+        .filter(|constraint| !old.contains(&constraint_signature(constraint)))
+        .cloned()
+        .collect();
+    diff
+}
+
+fn column_changes(before: &Column, after: &Column) -> Vec<ColumnChange> {
+    let mut changes = Vec::new();
+    if before.column_type.data_type != after.column_type.data_type {
+        changes.push(ColumnChange::Type);
+    }
+    if before.is_nullable != after.is_nullable {
+        changes.push(ColumnChange::Nullability);
+    }
+    if before.default != after.default {
+        changes.push(ColumnChange::Default);
+    }
+    if before.identity != after.identity {
+        changes.push(ColumnChange::Identity);
+    }
+    let generated = |column: &Column| {
+        column
+            .generated
+            .as_ref()
+            .map(|generated| generated.expression.clone())
+    };
+    if generated(before) != generated(after) {
+        changes.push(ColumnChange::Generated);
+    }
+    changes
+}
@@ -0,0 +1,93 @@
+//! Deterministic fingerprint of a schema snapshot.
+//!
+//! [`DatabaseSchema::compute_fingerprint`] hashes (SHA-256) a canonical JSON
+//! rendering of the snapshot (object keys sorted, no whitespace). It leaves
+//! out what varies between environments:
+//!
+//! - `schema_fingerprint` itself and `database`;
+//! - sampled statistics: table `row_estimate` and column `stats`;
+//! - table `catalog_version`;
+//! - privileges: `current_role` and table `privileges`;
+//! - `failed_tables` of a partial introspection.
+//!
+//! So the same structure gives the same fingerprint everywhere, and any
+//! structural change (tables, columns, types, constraints, indexes, enums...)
+//! gives a different one.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+use crate::schema::DatabaseSchema;
+
+impl DatabaseSchema {
+    /// Lowercase hex SHA-256 of the canonical JSON of the schema.
+    pub fn compute_fingerprint(&self) -> Result<String> {
+        let mut structure = self.clone();
+        structure.current_role = None;
+        structure.failed_tables.clear();
+        for table in structure
+            .schemas
+            .iter_mut()
+            .flat_map(|db_schema| &mut db_schema.tables)
+        {
+            table.row_estimate = None;
+            table.catalog_version = None;
+            table.privileges = None;
+            for column in &mut table.columns {
+                column.stats = None;
+            }
+        }
+        let mut value = serde_json::to_value(&structure)
+            .map_err(|err| Error::InvalidSchema(format!("schema is not serializable: {err}")))?;
+        if let Value::Object(fields) = &mut value {
+            fields.remove("schema_fingerprint");
+            fields.remove("database");
+        }
+        let mut canonical = String::new();
+        write_canonical(&value, &mut canonical);
+
+        Ok(Sha256::digest(canonical.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect())
+    }
+
+    /// Same schema with `schema_fingerprint` set to [`Self::compute_fingerprint`].
+    pub fn with_fingerprint(mut self) -> Result<DatabaseSchema> {
+        self.schema_fingerprint = Some(self.compute_fingerprint()?);
+        Ok(self)
+    }
+}
+
+/// Compact JSON with object keys in byte order, independent of the map
+/// implementation serde_json was built with.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (index, key) in keys.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&fields[key], out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
@@ -1,9 +1,17 @@
+//! FK dependency graph of a schema: load order, load levels and cycles.
+//!
+//! Nodes are `schema.table` keys and an edge goes from the referenced
+//! (parent) table to the referencing one, so parents are ordered first.
+//! Views, materialized views and foreign tables take no INSERTs (see
+//! [`TableKind::is_insertable`]) and are left out; the dependencies of views
+//! give the [`refresh_order`] of materialized views.
+
 use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 
 use crate::constraints::Constraint;
-use crate::schema::DatabaseSchema;
+use crate::schema::{DatabaseSchema, TableKind};
 
 /// Summary of FK graph structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +26,8 @@ pub struct FkGraphReport {
     pub summary: FkGraphSummary,
     pub topo_order: Option<Vec<String>>,
     pub cycle: Option<Vec<String>>,
+    /// Materialized views to refresh once the tables are loaded.
+    pub refresh_order: Vec<String>,
 }
 
 /// Build a deterministic FK dependency report for a database schema.
@@ -26,26 +36,272 @@ pub fn build_fk_graph_report(schema: &DatabaseSchema) -> FkGraphReport {
     let nodes = graph.len();
     let edges = graph.values().map(|targets| targets.len()).sum();
     let summary = FkGraphSummary { nodes, edges };
+    let refresh_order = refresh_order(schema);
 
     match toposort(&graph) {
         Ok(order) => FkGraphReport {
             summary,
             topo_order: Some(order),
             cycle: None,
+            refresh_order,
         },
         Err(cycle) => FkGraphReport {
             summary,
             topo_order: None,
             cycle: Some(cycle),
+            refresh_order,
         },
     }
 }
 
+/// Materialized views in an order where each comes after the materialized
+/// views its query reads, directly or through plain views, so refreshing
+/// them in turn after a load sees fresh data everywhere.
+pub fn refresh_order(schema: &DatabaseSchema) -> Vec<String> {
+    view_order(schema)
+        .into_iter()
+        .filter(|(_, kind)| *kind == TableKind::MaterializedView)
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Views and materialized views, each after the views it reads (ties broken
+/// by name).
+pub fn view_order(schema: &DatabaseSchema) -> Vec<(String, TableKind)> {
+    let mut kinds: BTreeMap<String, TableKind> = BTreeMap::new();
+    for db_schema in &schema.schemas {
+        for table in db_schema.tables.iter().filter(|table| table.kind.is_view()) {
+            kinds.insert(
+                format!("{}.{}", db_schema.name, table.name),
+                table.kind.clone(),
+            );
+        }
+    }
+
+    let mut graph: BTreeMap<String, BTreeSet<String>> = kinds
+        .keys()
+        .map(|key| (key.clone(), BTreeSet::new()))
+        .collect();
+    for db_schema in &schema.schemas {
+        for table in db_schema.tables.iter().filter(|table| table.kind.is_view()) {
+            let view = format!("{}.{}", db_schema.name, table.name);
+            for dependency in &table.depends_on {
+                let read = format!("{}.{}", dependency.schema, dependency.name);
+                if read != view && kinds.contains_key(&read) {
+                    graph.entry(read).or_default().insert(view.clone());
+                }
+            }
+        }
+    }
+
+    // Postgres rejects circular view definitions; a hand-edited snapshot
+    // with one falls back to name order.
+    let order = toposort(&graph).unwrap_or_else(|_| kinds.keys().cloned().collect());
+    order
+        .into_iter()
+        .map(|key| {
+            let kind = kinds[&key].clone();
+            (key, kind)
+        })
+        .collect()
+}
+
+/// Foreign key from a child table to the table it references, as
+/// `schema.table` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FkEdge {
+    pub child: String,
+    pub parent: String,
+    /// Constraint name, when the catalog has one.
+    pub constraint: Option<String>,
+    /// FK columns of the child table.
+    pub columns: Vec<String>,
+    /// Every FK column is nullable: the edge can be broken by inserting NULL
+    /// and updating the column once the parent rows exist.
+    pub nullable: bool,
+}
+
+/// Tables that depend on each other through foreign keys (a strongly
+/// connected component of the FK graph, or a self-referencing table).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FkCycle {
+    /// Tables of the cycle, sorted.
+    pub tables: Vec<String>,
+    /// FK edges between those tables.
+    pub edges: Vec<FkEdge>,
+}
+
+/// Every FK edge of `schema`, sorted by child, parent and constraint name.
+pub fn fk_edges(schema: &DatabaseSchema) -> Vec<FkEdge> {
+    let mut edges = Vec::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            for constraint in &table.constraints {
+                if let Constraint::ForeignKey(fk) = constraint {
+                    let nullable = fk.columns.iter().all(|name| {
+                        table
+                            .columns
+                            .iter()
+                            .any(|column| &column.name == name && column.is_nullable)
+                    });
+                    edges.push(FkEdge {
+                        child: format!("{}.{}", db_schema.name, table.name),
+                        parent: format!("{}.{}", fk.referenced_schema, fk.referenced_table),
+                        constraint: fk.name.clone(),
+                        columns: fk.columns.clone(),
+                        nullable,
+                    });
+                }
+            }
+        }
+    }
+    edges.sort_by(|a, b| {
+        (&a.child, &a.parent, &a.constraint).cmp(&(&b.child, &b.parent, &b.constraint))
+    });
+    edges
+}
+
+/// Tables in an order where every parent comes before its children
+/// (deterministic: ties are broken by name). Self-references count as
+/// cycles, as in [`build_fk_graph_report`]; the error lists every cycle.
+pub fn insert_order(schema: &DatabaseSchema) -> Result<Vec<String>, Vec<FkCycle>> {
+    toposort(&build_adjacency(schema)).map_err(|_| find_cycles(schema))
+}
+
+/// Tables grouped by load level: level 0 has no parents and each table sits
+/// one level after its deepest parent, so the tables of a level can be
+/// loaded in parallel once the previous levels are done.
+pub fn insert_levels(schema: &DatabaseSchema) -> Result<Vec<Vec<String>>, Vec<FkCycle>> {
+    let order = insert_order(schema)?;
+    let mut parents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let edges = fk_edges(schema);
+    for edge in &edges {
+        parents.entry(&edge.child).or_default().push(&edge.parent);
+    }
+
+    let mut level_of: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut levels: Vec<Vec<String>> = Vec::new();
+    for table in &order {
+        let level = parents
+            .get(table.as_str())
+            .into_iter()
+            .flatten()
+            .filter_map(|parent| level_of.get(parent))
+            .map(|level| level + 1)
+            .max()
+            .unwrap_or(0);
+        level_of.insert(table, level);
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+        levels[level].push(table.clone());
+    }
+    for level in &mut levels {
+        level.sort();
+    }
+    Ok(levels)
+}
+
+/// FK cycles of `schema` with the edges that close them, sorted by their
+/// first table; empty when the graph is acyclic.
+pub fn find_cycles(schema: &DatabaseSchema) -> Vec<FkCycle> {
+    let edges = fk_edges(schema);
+    let mut graph: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for edge in &edges {
+        graph.entry(&edge.parent).or_default();
+        graph.entry(&edge.child).or_default().insert(&edge.parent);
+    }
+
+    let mut cycles: Vec<FkCycle> = strongly_connected(&graph)
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1
+                || graph
+                    .get(component[0])
+                    .is_some_and(|targets| targets.contains(component[0]))
+        })
+        .map(|component| {
+            let tables: BTreeSet<&str> = component.into_iter().collect();
+            FkCycle {
+                tables: tables.iter().map(|table| table.to_string()).collect(),
+                edges: edges
+                    .iter()
+                    .filter(|edge| {
+                        tables.contains(edge.child.as_str())
+                            && tables.contains(edge.parent.as_str())
+                    })
+                    .cloned()
+                    .collect(),
+            }
+        })
+        .collect();
+    cycles.sort_by(|a, b| a.tables.cmp(&b.tables));
+    cycles
+}
+
+/// Strongly connected components (Tarjan), iterative so deep FK chains do
+/// not overflow the stack.
+fn strongly_connected<'a>(graph: &BTreeMap<&'a str, BTreeSet<&'a str>>) -> Vec<Vec<&'a str>> {
+    let mut index: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut low: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut on_stack: BTreeSet<&str> = BTreeSet::new();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut components = Vec::new();
+
+    for &root in graph.keys() {
+        if index.contains_key(root) {
+            continue;
+        }
+        // (node, iterator position over its targets)
+        let mut work: Vec<(&str, usize)> = vec![(root, 0)];
+        while let Some((node, position)) = work.pop() {
+            if position == 0 {
+                let next = index.len();
+                index.insert(node, next);
+                low.insert(node, next);
+                stack.push(node);
+                on_stack.insert(node);
+            }
+            let targets = &graph[node];
+            if let Some(&target) = targets.iter().nth(position) {
+                work.push((node, position + 1));
+                if !index.contains_key(target) {
+                    work.push((target, 0));
+                } else if on_stack.contains(target) {
+                    let low_node = low[node].min(index[target]);
+                    low.insert(node, low_node);
+                }
+                continue;
+            }
+            if let Some(&(parent, _)) = work.last() {
+                let low_parent = low[parent].min(low[node]);
+                low.insert(parent, low_parent);
+            }
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
 fn build_adjacency(schema: &DatabaseSchema) -> BTreeMap<String, BTreeSet<String>> {
     let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
 
     for db_schema in &schema.schemas {
-        for table in &db_schema.tables {
+        for table in db_schema
+            .tables
+            .iter()
+            .filter(|table| table.kind.is_insertable())
+        {
             let table_key = format!("{}.{}", db_schema.name, table.name);
             graph.entry(table_key.clone()).or_default();
 
@@ -145,6 +401,7 @@ mod tests {
             identity: None,
             generated: None,
             comment: None,
+            stats: None,
         }
     }
 
@@ -176,9 +433,26 @@ mod tests {
                     columns: vec![column("id")],
                     constraints: vec![Constraint::ForeignKey(fk)],
                     indexes: Vec::new(),
+                    partition_key: None,
+                    partitions: Vec::new(),
+                    row_estimate: None,
+                    triggers: Vec::new(),
+                    rules: Vec::new(),
+                    catalog_version: None,
+                    hidden_columns: Vec::new(),
+                    view_definition: None,
+                    depends_on: Vec::new(),
+                    foreign_table: None,
+                    privileges: None,
                 }],
             }],
             enums: Vec::new(),
+            domains: Vec::new(),
+            composite_types: Vec::new(),
+            sequences: Vec::new(),
+            current_role: None,
+            failed_tables: Vec::new(),
+            external_tables: Vec::new(),
             schema_fingerprint: None,
         };
 
@@ -222,6 +496,17 @@ mod tests {
                         columns: vec![column("id"), column("user_id")],
                         constraints: vec![Constraint::ForeignKey(fk)],
                         indexes: Vec::new(),
+                        partition_key: None,
+                        partitions: Vec::new(),
+                        row_estimate: None,
+                        triggers: Vec::new(),
+                        rules: Vec::new(),
+                        catalog_version: None,
+                        hidden_columns: Vec::new(),
+                        view_definition: None,
+                        depends_on: Vec::new(),
+                        foreign_table: None,
+                        privileges: None,
                     },
                     Table {
                         name: "users".to_string(),
@@ -230,10 +515,27 @@ mod tests {
                         columns: vec![column("id")],
                         constraints: Vec::new(),
                         indexes: Vec::new(),
+                        partition_key: None,
+                        partitions: Vec::new(),
+                        row_estimate: None,
+                        triggers: Vec::new(),
+                        rules: Vec::new(),
+                        catalog_version: None,
+                        hidden_columns: Vec::new(),
+                        view_definition: None,
+                        depends_on: Vec::new(),
+                        foreign_table: None,
+                        privileges: None,
                     },
                 ],
             }],
             enums: Vec::new(),
+            domains: Vec::new(),
+            composite_types: Vec::new(),
+            sequences: Vec::new(),
+            current_role: None,
+            failed_tables: Vec::new(),
+            external_tables: Vec::new(),
             schema_fingerprint: None,
         };
 
@@ -249,4 +551,176 @@ mod tests {
             .unwrap();
         assert!(users_idx < orders_idx);
     }
+
+    fn fk(column: &str, parent: &str) -> Constraint {
+        Constraint::ForeignKey(ForeignKey {
+            name: Some(format!("{column}_fkey")),
+            columns: vec![column.to_string()],
+            referenced_schema: "public".to_string(),
+            referenced_table: parent.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_update: crate::constraints::FkAction::NoAction,
+            on_delete: crate::constraints::FkAction::NoAction,
+            match_type: crate::constraints::FkMatchType::Simple,
+            is_deferrable: false,
+            initially_deferred: false,
+        })
+    }
+
+    /// `public` schema with `(table, [(fk column, parent)])` tables.
+    fn schema_of(tables: &[(&str, &[(&str, &str)])]) -> DatabaseSchema {
+        DatabaseSchema {
+            schema_version: "0.2".to_string(),
+            engine: "postgres".to_string(),
+            database: None,
+            schemas: vec![Schema {
+                name: "public".to_string(),
+                tables: tables
+                    .iter()
+                    .map(|(name, fks)| {
+                        let mut columns = vec![column("id")];
+                        columns.extend(fks.iter().map(|(col, _)| {
+                            let mut fk_column = column(col);
+                            fk_column.is_nullable = col.starts_with("opt_");
+                            fk_column
+                        }));
+                        Table {
+                            name: name.to_string(),
+                            kind: TableKind::Table,
+                            comment: None,
+                            columns,
+                            constraints: fks.iter().map(|(col, parent)| fk(col, parent)).collect(),
+                            indexes: Vec::new(),
+                            partition_key: None,
+                            partitions: Vec::new(),
+                            row_estimate: None,
+                            triggers: Vec::new(),
+                            rules: Vec::new(),
+                            catalog_version: None,
+                            hidden_columns: Vec::new(),
+                            view_definition: None,
+                            depends_on: Vec::new(),
+                            foreign_table: None,
+                            privileges: None,
+                        }
+                    })
+                    .collect(),
+            }],
+            enums: Vec::new(),
+            domains: Vec::new(),
+            composite_types: Vec::new(),
+            sequences: Vec::new(),
+            current_role: None,
+            failed_tables: Vec::new(),
+            external_tables: Vec::new(),
+            schema_fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn insert_levels_group_tables_by_depth() {
+        let schema = schema_of(&[
+            (
+                "order_items",
+                &[("order_id", "orders"), ("product_id", "products")],
+            ),
+            ("orders", &[("user_id", "users")]),
+            ("products", &[]),
+            ("users", &[]),
+        ]);
+
+        let order = insert_order(&schema).expect("acyclic");
+        let position = |table: &str| order.iter().position(|item| item == table).unwrap();
+        assert!(position("public.users") < position("public.orders"));
+        assert!(position("public.orders") < position("public.order_items"));
+        assert!(position("public.products") < position("public.order_items"));
+
+        let levels = insert_levels(&schema).expect("acyclic");
+        assert_eq!(
+            levels,
+            vec![
+                vec!["public.products".to_string(), "public.users".to_string()],
+                vec!["public.orders".to_string()],
+                vec!["public.order_items".to_string()],
+            ]
+        );
+        assert!(find_cycles(&schema).is_empty());
+    }
+
+    #[test]
+    fn cycles_list_their_tables_and_edges() {
+        let schema = schema_of(&[
+            ("accounts", &[("opt_owner_id", "people")]),
+            ("people", &[("account_id", "accounts")]),
+            ("employees", &[("opt_manager_id", "employees")]),
+            ("logins", &[("person_id", "people")]),
+        ]);
+
+        let cycles = insert_order(&schema).expect_err("cyclic");
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].tables, vec!["public.accounts", "public.people"]);
+        let edges: Vec<(&str, &str, bool)> = cycles[0]
+            .edges
+            .iter()
+            .map(|edge| (edge.child.as_str(), edge.parent.as_str(), edge.nullable))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("public.accounts", "public.people", true),
+                ("public.people", "public.accounts", false),
+            ]
+        );
+        // A self-reference is a cycle of one table; `logins` only depends on one.
+        assert_eq!(cycles[1].tables, vec!["public.employees"]);
+        assert_eq!(cycles[1].edges.len(), 1);
+        assert!(insert_levels(&schema).is_err());
+    }
+
+    #[test]
+    fn views_are_skipped_and_materialized_views_refresh_in_dependency_order() {
+        use crate::builder::{SchemaBuilder, TableBuilder};
+
+        let schema = SchemaBuilder::new()
+            .table(
+                "public",
+                TableBuilder::new("orders")
+                    .column("id", "integer")
+                    .primary_key(["id"]),
+            )
+            .table(
+                "public",
+                TableBuilder::new("totals")
+                    .kind(TableKind::MaterializedView)
+                    .column("total", "bigint")
+                    .view_definition("SELECT count(*) AS total FROM orders", ["orders"]),
+            )
+            .table(
+                "public",
+                TableBuilder::new("recent_totals")
+                    .kind(TableKind::View)
+                    .column("total", "bigint")
+                    .view_definition("SELECT total FROM totals", ["totals"]),
+            )
+            .table(
+                "public",
+                TableBuilder::new("a_report")
+                    .kind(TableKind::MaterializedView)
+                    .column("total", "bigint")
+                    .view_definition("SELECT total FROM recent_totals", ["recent_totals"]),
+            )
+            .build()
+            .expect("schema");
+
+        assert_eq!(
+            insert_order(&schema).expect("acyclic"),
+            vec!["public.orders"]
+        );
+        let report = build_fk_graph_report(&schema);
+        assert_eq!(report.summary.nodes, 1);
+        assert_eq!(
+            report.refresh_order,
+            vec!["public.totals".to_string(), "public.a_report".to_string()]
+        );
+    }
 }
@@ -0,0 +1,100 @@
+//! Annotations in table and column comments.
+//!
+//! A comment may carry `@key:value` tokens anywhere in its text, e.g.
+//! `COMMENT ON COLUMN users.doc IS 'CPF do titular @pii:cpf'` or
+//! `'@generator:semantic.br.cpf'`. Recognized keys are `pii` (repeatable:
+//! the kind of personal data, `email`, `cpf`, `phone`...) and `generator`
+//! (the generator id to use when the plan has no rule for the column; the
+//! first one wins). Other tokens are ignored.
+
+use crate::schema::{Column, Table};
+
+/// Hints extracted from a comment by [`comment_hints`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommentHints {
+    /// `@pii:<kind>` values, lowercase, in comment order.
+    pub pii: Vec<String>,
+    /// `@generator:<id>` value.
+    pub generator: Option<String>,
+}
+
+impl CommentHints {
+    pub fn is_empty(&self) -> bool {
+        self.pii.is_empty() && self.generator.is_none()
+    }
+
+    /// `pii.<kind>` tags, as generators record them.
+    pub fn pii_tags(&self) -> Vec<String> {
+        self.pii.iter().map(|kind| format!("pii.{kind}")).collect()
+    }
+}
+
+/// Hints of the `@pii:` and `@generator:` annotations in `comment`.
+pub fn comment_hints(comment: &str) -> CommentHints {
+    let mut hints = CommentHints::default();
+    for token in comment.split_whitespace() {
+        let Some((key, value)) = token
+            .strip_prefix('@')
+            .and_then(|rest| rest.split_once(':'))
+        else {
+            continue;
+        };
+        // Annotations often end a sentence or a list: `@pii:email,`.
+        let value = value.trim_end_matches([',', ';', '.', ')', '\'']);
+        if value.is_empty() {
+            continue;
+        }
+        match key.to_ascii_lowercase().as_str() {
+            "pii" => {
+                let kind = value.to_ascii_lowercase();
+                if !hints.pii.contains(&kind) {
+                    hints.pii.push(kind);
+                }
+            }
+            "generator" if hints.generator.is_none() => {
+                hints.generator = Some(value.to_string());
+            }
+            _ => {}
+        }
+    }
+    hints
+}
+
+impl Column {
+    /// Annotations of the column comment (empty without a comment).
+    pub fn hints(&self) -> CommentHints {
+        self.comment
+            .as_deref()
+            .map(comment_hints)
+            .unwrap_or_default()
+    }
+}
+
+impl Table {
+    /// Annotations of the table comment (empty without a comment).
+    pub fn hints(&self) -> CommentHints {
+        self.comment
+            .as_deref()
+            .map(comment_hints)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_pii_and_generator_annotations() {
+        let hints = comment_hints("Documento do titular @PII:CPF, @generator:semantic.br.cpf.");
+        assert_eq!(hints.pii, ["cpf"]);
+        assert_eq!(hints.generator.as_deref(), Some("semantic.br.cpf"));
+        assert_eq!(hints.pii_tags(), ["pii.cpf"]);
+
+        let hints = comment_hints("@pii:email @pii:email @generator:a @generator:b @owner:crm");
+        assert_eq!(hints.pii, ["email"]);
+        assert_eq!(hints.generator.as_deref(), Some("a"));
+
+        assert!(comment_hints("contato@empresa.com.br e @pii: sem valor").is_empty());
+    }
+}
@@ -3,23 +3,50 @@
 //! This crate defines the canonical schema types, validation helpers, and
 //! utilities shared across adapters and the CLI.
 
+pub mod anonymize;
+pub mod builder;
 pub mod constraints;
+pub mod ddl;
+pub mod diff;
 pub mod error;
+pub mod fingerprint;
 pub mod graph;
+pub mod hints;
+pub mod observer;
 pub mod redaction;
 pub mod schema;
+pub mod subset;
 pub mod types;
 pub mod validation;
 
+pub use anonymize::{IdentifierMap, anonymize_schema, restore_schema};
+pub use builder::{SchemaBuilder, TableBuilder};
 pub use constraints::{
-    CheckConstraint, Constraint, FkAction, FkMatchType, ForeignKey, Index, PrimaryKey,
-    UniqueConstraint,
+    CheckConstraint, Constraint, ExclusionConstraint, ExclusionElement, FkAction, FkMatchType,
+    ForeignKey, Index, PrimaryKey, UniqueConstraint,
+};
+pub use ddl::render_postgres_ddl;
+pub use diff::{
+    ColumnChange, ColumnDiff, EnumDiff, SchemaDiff, TableDiff, constraint_signature, diff_schemas,
 };
 pub use error::{Error, Result};
-pub use graph::{FkGraphReport, FkGraphSummary, build_fk_graph_report};
+pub use graph::{
+    FkCycle, FkEdge, FkGraphReport, FkGraphSummary, build_fk_graph_report, find_cycles, fk_edges,
+    insert_levels, insert_order, refresh_order, view_order,
+};
+pub use hints::{CommentHints, comment_hints};
+pub use observer::{EngineIssue, EngineObserver, IssueLevel, RunOutcome, RunSummary, Stage};
 pub use redaction::{RedactedConnection, redact_connection_string};
-pub use schema::{Column, DatabaseSchema, Schema, Table, TableKind};
-pub use types::{ColumnType, EnumType, GeneratedExpression, GeneratedKind, IdentityGeneration};
+pub use schema::{
+    Column, ColumnStats, DatabaseSchema, ExternalTable, FailedTable, ForeignTableSource, Partition,
+    PartitionKey, PartitionStrategy, RewriteRule, Schema, Table, TableEvent, TableKind,
+    TablePrivileges, Trigger, TriggerTiming, ViewDependency,
+};
+pub use subset::SubsetOptions;
+pub use types::{
+    ColumnType, CompositeAttribute, CompositeType, DomainType, EnumType, GeneratedExpression,
+    GeneratedKind, IdentityGeneration, Sequence, SequenceOwner,
+};
 pub use validation::validate_schema;
 
 /// Current schema contract version for `schema.json` artifacts.
@@ -0,0 +1,79 @@
+//! Pipeline events for embedding applications.
+//!
+//! The generation and evaluation engines accept an [`EngineObserver`]
+//! (`with_observer`) and call it from the running thread as tables start,
+//! rows are processed, issues are recorded and the run ends. Telemetry,
+//! metrics exporters (Prometheus counters) and audit logs hook in there
+//! without parsing the run artifacts. Every method has an empty default.
+
+use serde::{Deserialize, Serialize};
+
+/// Engine emitting an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Generate,
+    Evaluate,
+}
+
+/// Whether an issue is a warning or an error (evaluation violations,
+/// unsupported generation features).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueLevel {
+    Warning,
+    Error,
+}
+
+/// Warning or error recorded by an engine, as written to its report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineIssue {
+    pub stage: Stage,
+    pub level: IssueLevel,
+    pub code: String,
+    /// `schema.table.column` (or the report path) the issue is about.
+    pub path: Option<String>,
+    pub message: String,
+}
+
+/// How a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Totals of a finished run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub stage: Stage,
+    /// Generation run id, or the run id of the evaluated dataset (`unknown`
+    /// when it fails before it is known).
+    pub run_id: String,
+    pub outcome: RunOutcome,
+    pub tables: u64,
+    pub rows: u64,
+    pub warnings: u64,
+    pub errors: u64,
+    pub duration_ms: u64,
+}
+
+/// Receives the events of generation and evaluation runs.
+///
+/// Calls are synchronous; keep them cheap or hand the event to another thread.
+pub trait EngineObserver: Send + Sync {
+    /// A table is about to be generated or evaluated.
+    fn on_table_start(&self, _stage: Stage, _schema: &str, _table: &str) {}
+
+    /// `rows` more rows of the table were generated (once per table) or read
+    /// (once per table, or per chunk when streaming).
+    fn on_row_batch(&self, _stage: Stage, _schema: &str, _table: &str, _rows: u64) {}
+
+    fn on_issue(&self, _issue: &EngineIssue) {}
+
+    /// The run ended; called once per run. Runs rejected before the first
+    /// table (invalid plan, unreadable dataset directory) end without a call.
+    fn on_finish(&self, _summary: &RunSummary) {}
+}
@@ -1,8 +1,14 @@
+use std::collections::BTreeMap;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::constraints::{Constraint, Index};
-use crate::types::{ColumnType, EnumType, GeneratedExpression, IdentityGeneration};
+use crate::constraints::{CheckConstraint, Constraint, Index};
+use crate::ddl::quote_ident;
+use crate::types::{
+    ColumnType, CompositeType, DomainType, EnumType, GeneratedExpression, IdentityGeneration,
+    Sequence,
+};
 
 /// Top-level schema snapshot for a database.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -17,7 +23,29 @@ pub struct DatabaseSchema {
     pub schemas: Vec<Schema>,
     /// Enum types captured across schemas.
     pub enums: Vec<EnumType>,
-    /// Optional fingerprint of the schema for cache/validation purposes.
+    /// Domains captured across schemas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub domains: Vec<DomainType>,
+    /// Composite types captured across schemas (table row types excluded).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub composite_types: Vec<CompositeType>,
+    /// Sequences captured across schemas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sequences: Vec<Sequence>,
+    /// Role that ran the privileges pass (`current_user`), checked against
+    /// each table's `privileges`; absent when privileges were not captured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_role: Option<String>,
+    /// Tables left out of a partial introspection because their details
+    /// could not be read (timeout, deadline, lost connection).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_tables: Vec<FailedTable>,
+    /// Tables left out by the table filters but referenced by a foreign key
+    /// of an introspected table.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_tables: Vec<ExternalTable>,
+    /// SHA-256 of the canonical schema JSON, used to detect drift between
+    /// schema.json, plans and runs.
     pub schema_fingerprint: Option<String>,
 }
 
@@ -37,6 +65,183 @@ pub struct Table {
     pub columns: Vec<Column>,
     pub constraints: Vec<Constraint>,
     pub indexes: Vec<Index>,
+    /// Partition key of a partitioned table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_key: Option<PartitionKey>,
+    /// Direct child partitions of a partitioned table.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partitions: Vec<Partition>,
+    /// Planner row estimate (`pg_class.reltuples`), captured with
+    /// `sample_stats`; absent when the table was never analyzed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_estimate: Option<u64>,
+    /// Triggers on the table. Generation does not run them, but loading the
+    /// generated rows with INSERT does.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<Trigger>,
+    /// Rewrite rules on the table (`CREATE RULE`), same caveat as triggers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<RewriteRule>,
+    /// Digest of the catalog rows describing the table, set by incremental
+    /// introspection to tell which tables changed since this snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog_version: Option<String>,
+    /// Columns the database keeps out of `SELECT *` and fills itself
+    /// (CockroachDB `NOT VISIBLE` columns: the implicit `rowid` key, hash
+    /// shard columns); they are not listed in `columns` nor in constraints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_columns: Vec<String>,
+    /// Defining query of a view or materialized view (`pg_get_viewdef`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_definition: Option<String>,
+    /// Tables and views the query of a view or materialized view reads.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<ViewDependency>,
+    /// Server and options of a foreign table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreign_table: Option<ForeignTableSource>,
+    /// Roles allowed to read and write the table, captured by the optional
+    /// privileges pass.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privileges: Option<TablePrivileges>,
+}
+
+/// How a partitioned table splits its rows (`PARTITION BY ...`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct PartitionKey {
+    pub strategy: PartitionStrategy,
+    /// Key columns; expressions in the key are kept as written.
+    pub columns: Vec<String>,
+}
+
+/// Declarative partitioning strategy.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionStrategy {
+    Range,
+    List,
+    Hash,
+}
+
+/// A child partition of a partitioned table.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct Partition {
+    pub schema: String,
+    pub name: String,
+    /// Partition bound, e.g. `FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')`.
+    pub bound: Option<String>,
+}
+
+/// A relation read by the query of a view or materialized view.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ViewDependency {
+    pub schema: String,
+    pub name: String,
+}
+
+/// A table whose details a partial introspection could not read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct FailedTable {
+    pub schema: String,
+    pub table: String,
+    /// Error of the last attempt.
+    pub error: String,
+}
+
+/// A table outside the snapshot that foreign keys of the snapshot point to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ExternalTable {
+    pub schema: String,
+    pub table: String,
+    /// Introspected tables (`schema.table`) with a foreign key to it.
+    pub referenced_by: Vec<String>,
+}
+
+/// Where the rows of a foreign table live (`CREATE FOREIGN TABLE ... SERVER`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ForeignTableSource {
+    /// Foreign server name.
+    pub server: String,
+    /// Foreign-data wrapper of the server, e.g. `postgres_fdw`.
+    pub wrapper: String,
+    /// Options of the foreign table, e.g. `table_name`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+    /// Options of the server (`host`, `dbname`, ...); password-like options
+    /// are left out and user mappings, which hold the credentials, are not
+    /// read.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub server_options: BTreeMap<String, String>,
+}
+
+/// Roles holding SELECT and INSERT on a table (`has_table_privilege`), be it
+/// through a direct grant, `PUBLIC`, role membership, ownership or superuser.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct TablePrivileges {
+    pub select: Vec<String>,
+    pub insert: Vec<String>,
+}
+
+impl TablePrivileges {
+    /// `role` may insert rows into the table.
+    pub fn can_insert(&self, role: &str) -> bool {
+        self.insert.iter().any(|grantee| grantee == role)
+    }
+
+    /// `role` may read the table.
+    pub fn can_select(&self, role: &str) -> bool {
+        self.select.iter().any(|grantee| grantee == role)
+    }
+}
+
+/// A trigger (`CREATE TRIGGER`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct Trigger {
+    pub name: String,
+    pub timing: TriggerTiming,
+    pub events: Vec<TableEvent>,
+    /// `FOR EACH ROW`; `false` for `FOR EACH STATEMENT`.
+    pub for_each_row: bool,
+    /// Function executed (`schema.function`); absent when the body is inline
+    /// (SQLite).
+    pub function: Option<String>,
+    /// `false` when disabled (`ALTER TABLE ... DISABLE TRIGGER`).
+    pub enabled: bool,
+}
+
+impl Trigger {
+    /// Whether inserting rows fires the trigger.
+    pub fn fires_on_insert(&self) -> bool {
+        self.enabled && self.events.contains(&TableEvent::Insert)
+    }
+}
+
+/// When a trigger runs relative to its event.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerTiming {
+    Before,
+    After,
+    InsteadOf,
+}
+
+/// Statement kind a trigger or rule reacts to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TableEvent {
+    Insert,
+    Update,
+    Delete,
+    Truncate,
+}
+
+/// A rewrite rule (`CREATE RULE ... ON <event> TO <table>`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct RewriteRule {
+    pub name: String,
+    pub event: TableEvent,
+    /// `DO INSTEAD`: the original statement is replaced, not followed.
+    pub instead: bool,
 }
 
 /// Kind of table represented in the catalog.
@@ -51,6 +256,20 @@ pub enum TableKind {
     Other(String),
 }
 
+impl TableKind {
+    /// View or materialized view: its rows come from its query, so it
+    /// takes no INSERTs and is left out of the insert order.
+    pub fn is_view(&self) -> bool {
+        matches!(self, TableKind::View | TableKind::MaterializedView)
+    }
+
+    /// Generation can target it: not a view nor a foreign table, whose rows
+    /// live in another server.
+    pub fn is_insertable(&self) -> bool {
+        !self.is_view() && *self != TableKind::ForeignTable
+    }
+}
+
 /// Column metadata for a table-like object.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Column {
@@ -62,4 +281,169 @@ pub struct Column {
     pub identity: Option<IdentityGeneration>,
     pub generated: Option<GeneratedExpression>,
     pub comment: Option<String>,
+    /// Value distribution from `pg_stats`, captured with `sample_stats`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ColumnStats>,
+}
+
+/// Planner statistics of a column (`pg_stats`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ColumnStats {
+    /// Fraction of null values.
+    pub null_frac: f64,
+    /// Distinct values: a count when positive, minus the distinct/rows ratio
+    /// when negative (`-1` means all values are distinct).
+    pub n_distinct: f64,
+}
+
+impl ColumnStats {
+    /// Estimated distinct values for a table of `rows` rows.
+    pub fn distinct_values(&self, rows: u64) -> u64 {
+        if self.n_distinct < 0.0 {
+            (-self.n_distinct * rows as f64).round() as u64
+        } else {
+            self.n_distinct.round() as u64
+        }
+    }
+}
+
+impl DatabaseSchema {
+    /// Tables among `targets` (`(schema, table)`) that [`Self::current_role`]
+    /// may not insert into, as `schema.table`. Empty when privileges were not
+    /// captured; tables without `privileges` are assumed insertable.
+    pub fn tables_without_insert<'a>(
+        &self,
+        targets: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Vec<String> {
+        let Some(role) = &self.current_role else {
+            return Vec::new();
+        };
+        targets
+            .into_iter()
+            .filter(|(schema, table)| {
+                self.table(schema, table)
+                    .and_then(|table| table.privileges.as_ref())
+                    .is_some_and(|privileges| !privileges.can_insert(role))
+            })
+            .map(|(schema, table)| format!("{schema}.{table}"))
+            .collect()
+    }
+
+    /// Leaf partitions (`schema.table`) under `schema.table`, following
+    /// sub-partitioned children; empty when the table is not partitioned.
+    pub fn leaf_partitions(&self, schema: &str, table: &str) -> Vec<String> {
+        let Some(table) = self.table(schema, table) else {
+            return Vec::new();
+        };
+        let mut leaves = Vec::new();
+        for partition in &table.partitions {
+            let children = self.leaf_partitions(&partition.schema, &partition.name);
+            if children.is_empty() {
+                leaves.push(format!("{}.{}", partition.schema, partition.name));
+            } else {
+                leaves.extend(children);
+            }
+        }
+        leaves
+    }
+
+    /// Sequence feeding `schema.table.column`: the one the column owns, or
+    /// the one its `nextval('...')` default names.
+    pub fn column_sequence(&self, schema: &str, table: &str, column: &str) -> Option<&Sequence> {
+        let owned = self.sequences.iter().find(|sequence| {
+            sequence.owner.as_ref().is_some_and(|owner| {
+                owner.schema == schema && owner.table == table && owner.column == column
+            })
+        });
+        owned.or_else(|| {
+            let default = self
+                .table(schema, table)?
+                .columns
+                .iter()
+                .find(|candidate| candidate.name == column)?
+                .default
+                .as_deref()?;
+            let name = crate::ddl::sequence_name(default)?;
+            self.sequences.iter().find(|sequence| {
+                sequence.qualified_name() == name
+                    || (sequence.schema == schema && sequence.name == name)
+            })
+        })
+    }
+
+    /// Domain a column of type `column_type` is declared with.
+    pub fn domain(&self, column_type: &ColumnType) -> Option<&DomainType> {
+        self.domains.iter().find(|domain| {
+            domain.schema == column_type.udt_schema && domain.name == column_type.udt_name
+        })
+    }
+
+    /// Composite type a column of type `column_type` is declared with.
+    pub fn composite_type(&self, column_type: &ColumnType) -> Option<&CompositeType> {
+        self.composite_types.iter().find(|composite| {
+            composite.schema == column_type.udt_schema && composite.name == column_type.udt_name
+        })
+    }
+
+    /// Copy of the schema where domain-typed columns (and composite
+    /// attributes) carry the domain's base type, following domains over
+    /// domains. Columns also take the domain's NOT NULL, and its checks
+    /// become table checks on the column; domain defaults stay on the domain.
+    pub fn resolve_domains(&self) -> DatabaseSchema {
+        let mut resolved = self.clone();
+        for db_schema in &mut resolved.schemas {
+            for table in &mut db_schema.tables {
+                let mut checks = Vec::new();
+                for column in &mut table.columns {
+                    for domain in self.domain_chain(&column.column_type) {
+                        column.column_type = domain.base_type.clone();
+                        column.is_nullable &= !domain.not_null;
+                        checks.extend(domain.checks.iter().map(|check| CheckConstraint {
+                            name: check.name.clone(),
+                            expression: crate::anonymize::rewrite_sql(
+                                &check.expression,
+                                &mut |token, _| {
+                                    (token == "VALUE").then(|| quote_ident(&column.name))
+                                },
+                            ),
+                        }));
+                    }
+                }
+                table
+                    .constraints
+                    .extend(checks.into_iter().map(Constraint::Check));
+            }
+        }
+        for composite in &mut resolved.composite_types {
+            for attribute in &mut composite.attributes {
+                if let Some(domain) = self.domain_chain(&attribute.column_type).last() {
+                    attribute.column_type = domain.base_type.clone();
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Domains from `column_type` down to the one over a non-domain type.
+    fn domain_chain(&self, column_type: &ColumnType) -> Vec<&DomainType> {
+        let mut chain: Vec<&DomainType> = Vec::new();
+        let mut current = self.domain(column_type);
+        while let Some(domain) = current {
+            if chain.iter().any(|seen| std::ptr::eq(*seen, domain)) {
+                break;
+            }
+            chain.push(domain);
+            current = self.domain(&domain.base_type);
+        }
+        chain
+    }
+
+    fn table(&self, schema: &str, table: &str) -> Option<&Table> {
+        self.schemas
+            .iter()
+            .find(|db_schema| db_schema.name == schema)?
+            .tables
+            .iter()
+            .find(|candidate| candidate.name == table)
+    }
 }
@@ -0,0 +1,112 @@
+//! Focused slices of a schema: requested tables plus the FK tables they need.
+//!
+//! [`DatabaseSchema::subset`] keeps the requested tables and the transitive
+//! closure of their FK parents, so every foreign key in the slice points to a
+//! table in the slice. With [`SubsetOptions::include_children`] the tables
+//! that reference the requested ones (transitively) are kept too, together
+//! with their own parents.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::constraints::Constraint;
+use crate::error::{Error, Result};
+use crate::schema::DatabaseSchema;
+
+/// Options of [`DatabaseSchema::subset`].
+#[derive(Debug, Clone, Default)]
+pub struct SubsetOptions {
+    /// Also keep the tables that reference the requested ones.
+    pub include_children: bool,
+}
+
+impl DatabaseSchema {
+    /// Schema with only `tables` (`schema.table`, or `table` when the name is
+    /// unique) and the tables they depend on through foreign keys. Schemas
+    /// left without tables are dropped; enums are kept and a fingerprint, when
+    /// present, is recomputed for the slice.
+    pub fn subset<S: AsRef<str>>(
+        &self,
+        tables: &[S],
+        options: SubsetOptions,
+    ) -> Result<DatabaseSchema> {
+        let mut parents: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut children: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for db_schema in &self.schemas {
+            for table in &db_schema.tables {
+                let key = format!("{}.{}", db_schema.name, table.name);
+                parents.entry(key.clone()).or_default();
+                for constraint in &table.constraints {
+                    if let Constraint::ForeignKey(fk) = constraint {
+                        let parent = format!("{}.{}", fk.referenced_schema, fk.referenced_table);
+                        parents
+                            .entry(key.clone())
+                            .or_default()
+                            .insert(parent.clone());
+                        children.entry(parent).or_default().insert(key.clone());
+                    }
+                }
+            }
+        }
+
+        let requested = tables
+            .iter()
+            .map(|name| resolve_table(name.as_ref(), &parents))
+            .collect::<Result<Vec<String>>>()?;
+        let mut selected: BTreeSet<String> = requested.iter().cloned().collect();
+        if options.include_children {
+            selected = closure(requested, &children);
+        }
+        let selected = closure(selected.into_iter().collect(), &parents);
+
+        let mut subset = self.clone();
+        for db_schema in &mut subset.schemas {
+            let name = db_schema.name.clone();
+            db_schema
+                .tables
+                .retain(|table| selected.contains(&format!("{name}.{}", table.name)));
+        }
+        subset
+            .schemas
+            .retain(|db_schema| !db_schema.tables.is_empty());
+        if subset.schema_fingerprint.is_some() {
+            subset = subset.with_fingerprint()?;
+        }
+        Ok(subset)
+    }
+}
+
+/// `schema.table` of `name`, matching a bare table name when it is unique.
+fn resolve_table(name: &str, tables: &BTreeMap<String, BTreeSet<String>>) -> Result<String> {
+    if tables.contains_key(name) {
+        return Ok(name.to_string());
+    }
+    let matches: Vec<&String> = tables
+        .keys()
+        .filter(|key| key.rsplit_once('.').is_some_and(|(_, table)| table == name))
+        .collect();
+    match matches.as_slice() {
+        [key] => Ok((*key).clone()),
+        [] => Err(Error::InvalidSchema(format!("table not found: {name}"))),
+        _ => Err(Error::InvalidSchema(format!(
+            "ambiguous table '{name}' (use schema.table)"
+        ))),
+    }
+}
+
+/// `start` plus every table reachable through `edges`; edges to tables
+/// missing from the schema are ignored.
+fn closure(start: Vec<String>, edges: &BTreeMap<String, BTreeSet<String>>) -> BTreeSet<String> {
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    let mut stack = start;
+    while let Some(table) = stack.pop() {
+        if !seen.insert(table.clone()) {
+            continue;
+        }
+        for next in edges.get(&table).into_iter().flatten() {
+            if !seen.contains(next) {
+                stack.push(next.clone());
+            }
+        }
+    }
+    seen
+}
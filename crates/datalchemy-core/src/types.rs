@@ -1,6 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::constraints::CheckConstraint;
+
 /// Formatted and raw Postgres type metadata for a column.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ColumnType {
@@ -24,11 +26,13 @@ pub enum IdentityGeneration {
     ByDefault,
 }
 
-/// Kind of generated column supported by Postgres.
+/// Kind of generated column: `stored` (computed on write) or `virtual`
+/// (computed on read; Postgres 18+, SQLite, DuckDB).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum GeneratedKind {
     Stored,
+    Virtual,
 }
 
 /// Information about generated column expressions.
@@ -45,3 +49,68 @@ pub struct EnumType {
     pub name: String,
     pub labels: Vec<String>,
 }
+
+/// Representation of Postgres domains (`CREATE DOMAIN`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DomainType {
+    pub schema: String,
+    pub name: String,
+    /// Type the domain is declared over (possibly another domain).
+    pub base_type: ColumnType,
+    #[serde(default)]
+    pub not_null: bool,
+    pub default: Option<String>,
+    /// Domain checks; expressions refer to the value as `VALUE`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<CheckConstraint>,
+}
+
+/// Representation of Postgres composite types (`CREATE TYPE ... AS (...)`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeType {
+    pub schema: String,
+    pub name: String,
+    pub attributes: Vec<CompositeAttribute>,
+}
+
+/// One field of a composite type, in declaration order.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeAttribute {
+    pub name: String,
+    pub column_type: ColumnType,
+}
+
+/// A sequence, with the column that owns it (serial and identity columns).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct Sequence {
+    pub schema: String,
+    pub name: String,
+    pub owner: Option<SequenceOwner>,
+    pub start: i64,
+    pub increment: i64,
+    pub cache: i64,
+    /// Last value handed out; `None` before the first `nextval` or without
+    /// privileges to read it.
+    pub last_value: Option<i64>,
+}
+
+/// Column a sequence belongs to (`OWNED BY` or identity).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct SequenceOwner {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+}
+
+impl Sequence {
+    /// `schema.name`, as written in `nextval('...')` defaults.
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.schema, self.name)
+    }
+
+    /// Value `nextval` returns on its `index`-th call (0-based) from `start`.
+    pub fn value_at(&self, index: u64) -> i64 {
+        self.start
+            .saturating_add(self.increment.saturating_mul(index as i64))
+    }
+}
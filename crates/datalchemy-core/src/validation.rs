@@ -9,7 +9,9 @@ use crate::schema::DatabaseSchema;
 /// This checks:
 /// - duplicate schemas/tables/columns
 /// - primary key columns exist
-/// - foreign key columns and referenced targets exist
+/// - foreign key columns and referenced targets exist (a foreign key to one
+///   of the `failed_tables` of a partial introspection or to one of the
+///   `external_tables` left out by the table filters is not checked)
 pub fn validate_schema(schema: &DatabaseSchema) -> Result<()> {
     let mut catalog: BTreeMap<String, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
 
@@ -80,6 +82,16 @@ pub fn validate_schema(schema: &DatabaseSchema) -> Result<()> {
                             }
                         }
 
+                        if schema.failed_tables.iter().any(|failed| {
+                            failed.schema == fk.referenced_schema
+                                && failed.table == fk.referenced_table
+                        }) || schema.external_tables.iter().any(|external| {
+                            external.schema == fk.referenced_schema
+                                && external.table == fk.referenced_table
+                        }) {
+                            continue;
+                        }
+
                         let ref_columns = catalog
                             .get(&fk.referenced_schema)
                             .and_then(|tables| tables.get(&fk.referenced_table))
@@ -109,7 +121,7 @@ pub fn validate_schema(schema: &DatabaseSchema) -> Result<()> {
                             }
                         }
                     }
-                    Constraint::Check(_) => {}
+                    Constraint::Check(_) | Constraint::Exclusion(_) => {}
                 }
             }
         }
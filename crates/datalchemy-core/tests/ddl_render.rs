@@ -0,0 +1,354 @@
+use datalchemy_core::{
+    CheckConstraint, Column, ColumnType, Constraint, DatabaseSchema, EnumType, ExclusionConstraint,
+    ExclusionElement, FkAction, FkMatchType, ForeignKey, IdentityGeneration, Index, PrimaryKey,
+    Schema, SchemaBuilder, Table, TableBuilder, TableEvent, TableKind, Trigger, TriggerTiming,
+    render_postgres_ddl,
+};
+
+fn golden_schema() -> DatabaseSchema {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    serde_json::from_str(raw).expect("parse golden schema")
+}
+
+fn column(position: i16, name: &str, data_type: &str, nullable: bool) -> Column {
+    Column {
+        ordinal_position: position,
+        name: name.to_string(),
+        column_type: ColumnType {
+            data_type: data_type.to_string(),
+            udt_schema: "pg_catalog".to_string(),
+            udt_name: data_type.to_string(),
+            character_max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            collation: None,
+        },
+        is_nullable: nullable,
+        default: None,
+        identity: None,
+        generated: None,
+        comment: None,
+        stats: None,
+    }
+}
+
+#[test]
+fn renders_golden_schema_in_loadable_order() {
+    let ddl = render_postgres_ddl(&golden_schema());
+
+    assert!(ddl.contains("CREATE SCHEMA IF NOT EXISTS crm;"));
+    assert!(ddl.contains(
+        "CREATE TYPE crm.status_atividade AS ENUM ('pendente', 'concluida', 'cancelada');"
+    ));
+    assert!(
+        ddl.contains(
+            "CREATE TABLE crm.anotacoes (\n    id uuid DEFAULT gen_random_uuid() NOT NULL,"
+        )
+    );
+    assert!(ddl.contains("    CONSTRAINT anotacoes_pkey PRIMARY KEY (id),"));
+    assert!(ddl.contains(
+        "    CONSTRAINT atividades_periodo_chk CHECK (data_fim IS NULL OR data_fim >= data_inicio)"
+    ));
+    assert!(ddl.contains(
+        "ALTER TABLE crm.anotacoes ADD CONSTRAINT anotacoes_atividade_id_fkey \
+         FOREIGN KEY (atividade_id) REFERENCES crm.atividades (id) ON DELETE CASCADE;"
+    ));
+    // Indexes backing PK/UNIQUE constraints are created by the constraints themselves.
+    assert!(!ddl.contains("CREATE UNIQUE INDEX anotacoes_pkey"));
+    assert!(!ddl.contains("CREATE UNIQUE INDEX anotacoes_atividade_unique"));
+
+    let first_type = ddl.find("CREATE TYPE").expect("types");
+    let first_table = ddl.find("CREATE TABLE").expect("tables");
+    let first_fk = ddl.find("ALTER TABLE").expect("foreign keys");
+    let last_table = ddl.rfind("CREATE TABLE").expect("tables");
+    assert!(first_type < first_table);
+    assert!(last_table < first_fk);
+}
+
+#[test]
+fn renders_identity_sequences_indexes_and_comments() {
+    let mut id = column(1, "id", "bigint", false);
+    id.identity = Some(IdentityGeneration::Always);
+    let mut code = column(2, "Code", "integer", false);
+    code.default = Some("nextval('app.code_seq'::regclass)".to_string());
+    let mut order = column(3, "order", "text", true);
+    order.comment = Some("client's order".to_string());
+
+    let schema = DatabaseSchema {
+        schema_version: "0.2".to_string(),
+        engine: "postgres".to_string(),
+        database: None,
+        schemas: vec![Schema {
+            name: "app".to_string(),
+            tables: vec![
+                Table {
+                    name: "items".to_string(),
+                    kind: TableKind::Table,
+                    comment: Some("Items".to_string()),
+                    columns: vec![order, code, id],
+                    constraints: vec![
+                        Constraint::PrimaryKey(PrimaryKey {
+                            name: Some("items_pkey".to_string()),
+                            columns: vec!["id".to_string()],
+                        }),
+                        Constraint::Check(CheckConstraint {
+                            name: Some("items_code_chk".to_string()),
+                            expression: "CHECK (\"Code\" > 0)".to_string(),
+                        }),
+                        Constraint::ForeignKey(ForeignKey {
+                            name: Some("items_parent_fkey".to_string()),
+                            columns: vec!["Code".to_string()],
+                            referenced_schema: "app".to_string(),
+                            referenced_table: "items".to_string(),
+                            referenced_columns: vec!["id".to_string()],
+                            on_update: FkAction::Cascade,
+                            on_delete: FkAction::SetNull,
+                            match_type: FkMatchType::Full,
+                            is_deferrable: true,
+                            initially_deferred: true,
+                        }),
+                    ],
+                    indexes: vec![
+                        Index {
+                            name: "items_pkey".to_string(),
+                            is_unique: true,
+                            is_primary: true,
+                            is_valid: true,
+                            method: "btree".to_string(),
+                            definition:
+                                "CREATE UNIQUE INDEX items_pkey ON app.items USING btree (id)"
+                                    .to_string(),
+                            predicate: None,
+                            expressions: Vec::new(),
+                            shard_buckets: None,
+                        },
+                        Index {
+                            name: "items_order_idx".to_string(),
+                            is_unique: false,
+                            is_primary: false,
+                            is_valid: true,
+                            method: "btree".to_string(),
+                            definition:
+                                "CREATE INDEX items_order_idx ON app.items USING btree (\"order\")"
+                                    .to_string(),
+                            predicate: None,
+                            expressions: Vec::new(),
+                            shard_buckets: None,
+                        },
+                        Index {
+                            name: "items_broken_idx".to_string(),
+                            is_unique: false,
+                            is_primary: false,
+                            is_valid: false,
+                            method: "btree".to_string(),
+                            definition:
+                                "CREATE INDEX items_broken_idx ON app.items USING btree (id)"
+                                    .to_string(),
+                            predicate: None,
+                            expressions: Vec::new(),
+                            shard_buckets: None,
+                        },
+                    ],
+                    partition_key: None,
+                    partitions: Vec::new(),
+                    row_estimate: None,
+                    triggers: vec![Trigger {
+                        name: "items_audit".to_string(),
+                        timing: TriggerTiming::After,
+                        events: vec![TableEvent::Insert],
+                        for_each_row: true,
+                        function: Some("app.audit".to_string()),
+                        enabled: true,
+                    }],
+                    rules: Vec::new(),
+                    catalog_version: None,
+                    hidden_columns: Vec::new(),
+                    view_definition: None,
+                    depends_on: Vec::new(),
+                    foreign_table: None,
+                    privileges: None,
+                },
+                Table {
+                    name: "items_view".to_string(),
+                    kind: TableKind::View,
+                    comment: None,
+                    columns: vec![column(1, "id", "bigint", true)],
+                    constraints: Vec::new(),
+                    indexes: Vec::new(),
+                    partition_key: None,
+                    partitions: Vec::new(),
+                    row_estimate: None,
+                    triggers: Vec::new(),
+                    rules: Vec::new(),
+                    catalog_version: None,
+                    hidden_columns: Vec::new(),
+                    view_definition: None,
+                    depends_on: Vec::new(),
+                    foreign_table: None,
+                    privileges: None,
+                },
+            ],
+        }],
+        enums: vec![EnumType {
+            schema: "app".to_string(),
+            name: "mood".to_string(),
+            labels: vec!["it's ok".to_string()],
+        }],
+        schema_fingerprint: None,
+        sequences: Vec::new(),
+        current_role: None,
+        failed_tables: Vec::new(),
+        external_tables: Vec::new(),
+        domains: Vec::new(),
+        composite_types: Vec::new(),
+    };
+
+    let ddl = render_postgres_ddl(&schema);
+    let expected = "\
+-- Schema reconstructed by datalchemy from schema.json.
+
+CREATE SCHEMA IF NOT EXISTS app;
+
+CREATE TYPE app.mood AS ENUM ('it''s ok');
+
+CREATE SEQUENCE IF NOT EXISTS app.code_seq;
+
+CREATE TABLE app.items (
+    id bigint GENERATED ALWAYS AS IDENTITY NOT NULL,
+    \"Code\" integer DEFAULT nextval('app.code_seq'::regclass) NOT NULL,
+    \"order\" text,
+    CONSTRAINT items_pkey PRIMARY KEY (id),
+    CONSTRAINT items_code_chk CHECK (\"Code\" > 0)
+);
+
+ALTER TABLE app.items ADD CONSTRAINT items_parent_fkey FOREIGN KEY (\"Code\") REFERENCES app.items (id) MATCH FULL ON UPDATE CASCADE ON DELETE SET NULL DEFERRABLE INITIALLY DEFERRED;
+
+CREATE INDEX items_order_idx ON app.items USING btree (\"order\");
+
+COMMENT ON TABLE app.items IS 'Items';
+COMMENT ON COLUMN app.items.\"order\" IS 'client''s order';
+
+-- view app.items_view skipped: definition not captured in the snapshot
+-- trigger items_audit on app.items skipped: function body not captured in the snapshot
+";
+    assert_eq!(ddl, expected);
+}
+
+#[test]
+fn renders_exclusion_constraints_and_partial_indexes() {
+    let mut schema = SchemaBuilder::new()
+        .table(
+            "agenda",
+            TableBuilder::new("reservas")
+                .column("id", "integer")
+                .column("periodo", "tsrange")
+                .column("ativo", "boolean")
+                .primary_key(["id"]),
+        )
+        .build()
+        .expect("schema");
+    let table = &mut schema.schemas[0].tables[0];
+    table
+        .constraints
+        .push(Constraint::Exclusion(ExclusionConstraint {
+            name: Some("reservas_periodo_excl".to_string()),
+            method: "gist".to_string(),
+            elements: vec![ExclusionElement {
+                expression: "periodo".to_string(),
+                operator: "&&".to_string(),
+            }],
+            predicate: Some("ativo".to_string()),
+            is_deferrable: false,
+            initially_deferred: false,
+        }));
+    let index = |name: &str, is_unique: bool, method: &str, definition: &str| Index {
+        name: name.to_string(),
+        is_unique,
+        is_primary: false,
+        is_valid: true,
+        method: method.to_string(),
+        definition: definition.to_string(),
+        predicate: Some("ativo".to_string()),
+        expressions: Vec::new(),
+        shard_buckets: None,
+    };
+    table.indexes.push(index(
+        "reservas_periodo_excl",
+        false,
+        "gist",
+        "CREATE INDEX reservas_periodo_excl ON agenda.reservas USING gist (periodo) WHERE ativo",
+    ));
+    table.indexes.push(index(
+        "reservas_id_ativo",
+        true,
+        "btree",
+        "CREATE UNIQUE INDEX reservas_id_ativo ON agenda.reservas USING btree (id) WHERE ativo",
+    ));
+    let unenforced: Vec<&str> = table
+        .indexes
+        .iter()
+        .filter(|index| index.is_unenforced_unique())
+        .map(|index| index.name.as_str())
+        .collect();
+    assert_eq!(unenforced, ["reservas_id_ativo"]);
+
+    let ddl = render_postgres_ddl(&schema);
+    assert!(ddl.contains(
+        "    CONSTRAINT reservas_periodo_excl EXCLUDE USING gist (periodo WITH &&) WHERE (ativo)\n);"
+    ));
+    assert!(ddl.contains(
+        "CREATE UNIQUE INDEX reservas_id_ativo ON agenda.reservas USING btree (id) WHERE ativo;"
+    ));
+    // The exclusion constraint creates its own index.
+    assert!(!ddl.contains("CREATE INDEX reservas_periodo_excl"));
+}
+
+#[test]
+fn renders_views_after_the_relations_they_read() {
+    let schema = SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .column("total", "numeric")
+                .primary_key(["id"]),
+        )
+        .table(
+            "vendas",
+            TableBuilder::new("a_resumo")
+                .kind(TableKind::View)
+                .column("total", "numeric")
+                .view_definition(" SELECT soma AS total\n   FROM vendas.totais;", ["totais"]),
+        )
+        .table(
+            "vendas",
+            TableBuilder::new("totais")
+                .kind(TableKind::MaterializedView)
+                .column("soma", "numeric")
+                .view_definition(
+                    " SELECT sum(total) AS soma\n   FROM vendas.pedidos;",
+                    ["pedidos"],
+                ),
+        )
+        .table(
+            "vendas",
+            TableBuilder::new("legado")
+                .kind(TableKind::View)
+                .column("id", "integer"),
+        )
+        .build()
+        .expect("schema");
+
+    let ddl = render_postgres_ddl(&schema);
+    let materialized = ddl
+        .find(
+            "CREATE MATERIALIZED VIEW vendas.totais AS\nSELECT sum(total) AS soma\n   FROM vendas.pedidos\nWITH NO DATA;",
+        )
+        .expect("materialized view");
+    let view = ddl
+        .find("CREATE VIEW vendas.a_resumo AS\nSELECT soma AS total\n   FROM vendas.totais;")
+        .expect("view");
+    assert!(ddl.find("CREATE TABLE vendas.pedidos").expect("table") < materialized);
+    assert!(materialized < view);
+    assert!(ddl.contains("-- view vendas.legado skipped: definition not captured in the snapshot"));
+}
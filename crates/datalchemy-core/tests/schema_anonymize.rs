@@ -0,0 +1,257 @@
+use datalchemy_core::{
+    Constraint, DatabaseSchema, ForeignTableSource, IdentifierMap, RewriteRule, SchemaBuilder,
+    Sequence, SequenceOwner, TableEvent, TableKind, TablePrivileges, Trigger, TriggerTiming,
+    anonymize_schema, restore_schema, validate_schema,
+};
+
+fn golden_schema() -> DatabaseSchema {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    serde_json::from_str(raw).expect("parse golden schema")
+}
+
+#[test]
+fn hides_original_identifiers() {
+    let schema = golden_schema();
+    let (anonymized, map) = anonymize_schema(&schema);
+    let json = serde_json::to_string(&anonymized).expect("serialize");
+
+    for db_schema in &schema.schemas {
+        assert!(!json.contains(&format!("\"{}\"", db_schema.name)));
+        for table in &db_schema.tables {
+            assert!(!json.contains(&table.name), "table {} leaked", table.name);
+        }
+    }
+    for enum_type in &schema.enums {
+        assert!(
+            !json.contains(&enum_type.name),
+            "enum {} leaked",
+            enum_type.name
+        );
+    }
+    assert!(!json.contains("crm."));
+    assert_eq!(anonymized.schemas[0].name, "schema_1");
+    assert_eq!(anonymized.schemas[0].tables[0].name, "table_1");
+    assert_eq!(
+        map.original("table_1"),
+        Some(schema.schemas[0].tables[0].name.as_str())
+    );
+
+    // Foreign keys still point at tables of the anonymized schema.
+    let tables: Vec<&str> = anonymized.schemas[0]
+        .tables
+        .iter()
+        .map(|table| table.name.as_str())
+        .collect();
+    for table in &anonymized.schemas[0].tables {
+        for constraint in &table.constraints {
+            if let Constraint::ForeignKey(fk) = constraint {
+                assert_eq!(fk.referenced_schema, "schema_1");
+                assert!(tables.contains(&fk.referenced_table.as_str()));
+            }
+        }
+    }
+    validate_schema(&anonymized).expect("anonymized schema is valid");
+}
+
+#[test]
+fn restores_the_original_schema() {
+    let schema = golden_schema();
+    let (anonymized, map) = anonymize_schema(&schema);
+
+    let raw = serde_json::to_string(&map).expect("serialize map");
+    let map: IdentifierMap = serde_json::from_str(&raw).expect("parse map");
+    let restored = restore_schema(&anonymized, &map);
+    assert_eq!(
+        serde_json::to_value(&restored).expect("restored"),
+        serde_json::to_value(&schema).expect("original")
+    );
+    assert_eq!(
+        map.restore_text("column_1 of \"table_1\" must not be null"),
+        format!(
+            "{} of \"{}\" must not be null",
+            schema.schemas[0].tables[0].columns[0].name, schema.schemas[0].tables[0].name
+        )
+    );
+}
+
+#[test]
+fn pseudonyms_are_stable() {
+    let (first, first_map) = anonymize_schema(&golden_schema());
+    let (second, second_map) = anonymize_schema(&golden_schema());
+    assert_eq!(
+        serde_json::to_value(&first).expect("first"),
+        serde_json::to_value(&second).expect("second")
+    );
+    assert_eq!(first_map, second_map);
+}
+
+#[test]
+fn sequences_are_renamed_with_their_owner() {
+    let mut schema = golden_schema();
+    let table = &schema.schemas[0].tables[0];
+    schema.sequences.push(Sequence {
+        schema: schema.schemas[0].name.clone(),
+        name: format!("{}_seq", table.name),
+        owner: Some(SequenceOwner {
+            schema: schema.schemas[0].name.clone(),
+            table: table.name.clone(),
+            column: table.columns[0].name.clone(),
+        }),
+        start: 1,
+        increment: 1,
+        cache: 1,
+        last_value: Some(42),
+    });
+
+    let (anonymized, map) = anonymize_schema(&schema);
+    let sequence = &anonymized.sequences[0];
+    assert_eq!(sequence.schema, "schema_1");
+    assert!(!sequence.name.contains(&schema.schemas[0].tables[0].name));
+    let owner = sequence.owner.as_ref().expect("owner");
+    assert_eq!(
+        (owner.table.as_str(), owner.column.as_str()),
+        ("table_1", "column_1")
+    );
+    assert_eq!(
+        serde_json::to_value(restore_schema(&anonymized, &map)).expect("restored"),
+        serde_json::to_value(&schema).expect("original")
+    );
+}
+
+#[test]
+fn domains_and_composite_types_are_renamed() {
+    let mut schema = golden_schema();
+    let crm = schema.schemas[0].name.clone();
+    let typed = SchemaBuilder::new()
+        .domain_type(
+            &crm,
+            "cpf",
+            "character varying(11)",
+            ["VALUE ~ '^[0-9]{11}$'"],
+        )
+        .composite_type(&crm, "endereco", [("rua", "text"), ("documento", "cpf")])
+        .build()
+        .expect("build types");
+    schema.domains = typed.domains;
+    schema.composite_types = typed.composite_types;
+    schema.schemas[0].tables[0].columns[0].column_type =
+        schema.composite_types[0].attributes[1].column_type.clone();
+
+    let (anonymized, map) = anonymize_schema(&schema);
+    let json = serde_json::to_string(&anonymized).expect("serialize");
+    for name in ["cpf", "endereco", "documento", "rua"] {
+        assert!(!json.contains(&format!("\"{name}\"")), "{name} leaked");
+    }
+    let domain = &anonymized.domains[0];
+    assert!(domain.name.starts_with("domain_"));
+    assert_eq!(domain.checks[0].expression, "CHECK (VALUE ~ '^[0-9]{11}$')");
+    let column_type = &anonymized.schemas[0].tables[0].columns[0].column_type;
+    assert_eq!(column_type.udt_name, domain.name);
+    assert_eq!(
+        anonymized.composite_types[0].attributes[1]
+            .column_type
+            .udt_name,
+        domain.name
+    );
+    assert_eq!(
+        serde_json::to_value(restore_schema(&anonymized, &map)).expect("restored"),
+        serde_json::to_value(&schema).expect("original")
+    );
+}
+
+#[test]
+fn triggers_rules_and_trigger_functions_are_renamed() {
+    let mut schema = golden_schema();
+    let trigger = |name: &str| Trigger {
+        name: name.to_string(),
+        timing: TriggerTiming::After,
+        events: vec![TableEvent::Insert],
+        for_each_row: true,
+        function: Some("crm.auditar".to_string()),
+        enabled: true,
+    };
+    schema.schemas[0].tables[0].triggers = vec![trigger("empresas_auditoria")];
+    schema.schemas[0].tables[1].triggers = vec![trigger("contatos_auditoria")];
+    schema.schemas[0].tables[1].rules = vec![RewriteRule {
+        name: "contatos_arquivo".to_string(),
+        event: TableEvent::Insert,
+        instead: true,
+    }];
+
+    let (anonymized, map) = anonymize_schema(&schema);
+    let json = serde_json::to_string(&anonymized).expect("serialize");
+    for name in ["auditar", "empresas_auditoria", "contatos_arquivo"] {
+        assert!(!json.contains(name), "{name} leaked");
+    }
+    let tables = &anonymized.schemas[0].tables;
+    assert!(tables[0].triggers[0].name.starts_with("trigger_"));
+    assert!(tables[1].rules[0].name.starts_with("rule_"));
+    assert_eq!(
+        tables[0].triggers[0].function, tables[1].triggers[0].function,
+        "a shared function keeps one pseudonym"
+    );
+    assert_eq!(
+        serde_json::to_value(restore_schema(&anonymized, &map)).expect("restored"),
+        serde_json::to_value(&schema).expect("original")
+    );
+}
+
+#[test]
+fn foreign_servers_and_options_are_renamed() {
+    let mut schema = golden_schema();
+    let source = ForeignTableSource {
+        server: "erp_legado".to_string(),
+        wrapper: "postgres_fdw".to_string(),
+        options: [("table_name".to_string(), "clientes_erp".to_string())].into(),
+        server_options: [("host".to_string(), "erp.interno".to_string())].into(),
+    };
+    for table in &mut schema.schemas[0].tables[..2] {
+        table.kind = TableKind::ForeignTable;
+        table.foreign_table = Some(source.clone());
+    }
+
+    let (anonymized, map) = anonymize_schema(&schema);
+    let json = serde_json::to_string(&anonymized).expect("serialize");
+    for name in ["erp_legado", "clientes_erp", "erp.interno"] {
+        assert!(!json.contains(name), "{name} leaked");
+    }
+    let tables = &anonymized.schemas[0].tables;
+    let first = tables[0].foreign_table.as_ref().unwrap();
+    assert_eq!(
+        first.server,
+        tables[1].foreign_table.as_ref().unwrap().server,
+        "a shared server keeps one pseudonym"
+    );
+    assert_eq!(first.wrapper, "postgres_fdw");
+    assert!(first.options["table_name"].starts_with("option_"));
+    assert_eq!(
+        serde_json::to_value(restore_schema(&anonymized, &map)).expect("restored"),
+        serde_json::to_value(&schema).expect("original")
+    );
+}
+
+#[test]
+fn roles_are_renamed() {
+    let mut schema = golden_schema();
+    schema.current_role = Some("joana_app".to_string());
+    for table in &mut schema.schemas[0].tables {
+        table.privileges = Some(TablePrivileges {
+            select: vec!["joana_app".to_string(), "relatorios".to_string()],
+            insert: vec!["joana_app".to_string()],
+        });
+    }
+
+    let (anonymized, map) = anonymize_schema(&schema);
+    let json = serde_json::to_string(&anonymized).expect("serialize");
+    for name in ["joana_app", "relatorios"] {
+        assert!(!json.contains(name), "{name} leaked");
+    }
+    let role = anonymized.current_role.clone().expect("current role");
+    assert!(role.starts_with("role_"));
+    let privileges = anonymized.schemas[0].tables[0].privileges.as_ref().unwrap();
+    assert!(privileges.can_insert(&role), "a role keeps one pseudonym");
+    assert_eq!(
+        serde_json::to_value(restore_schema(&anonymized, &map)).expect("restored"),
+        serde_json::to_value(&schema).expect("original")
+    );
+}
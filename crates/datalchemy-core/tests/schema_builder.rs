@@ -0,0 +1,350 @@
+use datalchemy_core::{
+    Constraint, DatabaseSchema, FkAction, IdentityGeneration, PartitionKey, PartitionStrategy,
+    SchemaBuilder, Sequence, SequenceOwner, TableBuilder, TableKind, insert_order,
+    render_postgres_ddl,
+};
+
+fn golden_schema() -> DatabaseSchema {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    serde_json::from_str(raw).expect("parse golden schema")
+}
+
+fn shop() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .database("shop")
+        .enum_type("app", "order_status", ["open", "paid"])
+        .table(
+            "app",
+            TableBuilder::new("users")
+                .column("id", "bigint")
+                .identity(IdentityGeneration::Always)
+                .column("email", "character varying(255)")
+                .not_null()
+                .column("created_at", "timestamp with time zone")
+                .default_value("now()")
+                .primary_key(["id"])
+                .unique(["email"]),
+        )
+        .table(
+            "app",
+            TableBuilder::new("orders")
+                .column("id", "bigint")
+                .column("user_id", "bigint")
+                .not_null()
+                .column("status", "order_status")
+                .column("total", "numeric(12,2)")
+                .check("total >= 0")
+                .primary_key(["id"])
+                .foreign_key(["user_id"], "users", ["id"])
+                .on_delete(FkAction::Cascade)
+                .index(["user_id"], false),
+        )
+        .build()
+        .expect("build schema")
+}
+
+#[test]
+fn builds_tables_constraints_and_indexes() {
+    let schema = shop();
+    assert_eq!(schema.database.as_deref(), Some("shop"));
+    assert_eq!(schema.schemas.len(), 1);
+    let orders = &schema.schemas[0].tables[1];
+
+    let status = &orders.columns[2];
+    assert_eq!(status.ordinal_position, 3);
+    assert_eq!(status.column_type.data_type, "app.order_status");
+    assert_eq!(status.column_type.udt_schema, "app");
+    assert_eq!(status.column_type.udt_name, "order_status");
+    assert!(!orders.columns[0].is_nullable, "primary key is NOT NULL");
+    assert!(orders.columns[3].is_nullable);
+
+    let Some(Constraint::ForeignKey(fk)) = orders
+        .constraints
+        .iter()
+        .find(|constraint| matches!(constraint, Constraint::ForeignKey(_)))
+    else {
+        panic!("foreign key");
+    };
+    assert_eq!(fk.name.as_deref(), Some("orders_user_id_fkey"));
+    assert_eq!(
+        (fk.referenced_schema.as_str(), fk.referenced_table.as_str()),
+        ("app", "users")
+    );
+    assert_eq!(fk.on_delete, FkAction::Cascade);
+
+    let indexes: Vec<&str> = orders
+        .indexes
+        .iter()
+        .map(|index| index.definition.as_str())
+        .collect();
+    assert_eq!(
+        indexes,
+        vec![
+            "CREATE UNIQUE INDEX orders_pkey ON app.orders USING btree (id)",
+            "CREATE INDEX orders_user_id_idx ON app.orders USING btree (user_id)",
+        ]
+    );
+    assert!(orders.indexes[0].is_primary);
+
+    assert_eq!(
+        insert_order(&schema).expect("acyclic"),
+        vec!["app.users", "app.orders"]
+    );
+    let ddl = render_postgres_ddl(&schema);
+    assert!(ddl.contains("CONSTRAINT orders_check CHECK (total >= 0)"));
+    assert!(ddl.contains("CONSTRAINT users_email_key UNIQUE (email)"));
+}
+
+#[test]
+fn type_metadata_matches_introspection() {
+    let golden = golden_schema();
+    let golden_types: Vec<_> = golden.schemas[0]
+        .tables
+        .iter()
+        .flat_map(|table| &table.columns)
+        .map(|column| column.column_type.clone())
+        .collect();
+
+    let mut table = TableBuilder::new("types");
+    for (position, column_type) in golden_types.iter().enumerate() {
+        table = table.column(format!("c{position}"), &column_type.data_type);
+    }
+    let mut builder = SchemaBuilder::new().table("crm", table);
+    for enum_type in &golden.enums {
+        builder = builder.enum_type(&enum_type.schema, &enum_type.name, enum_type.labels.clone());
+    }
+    let schema = builder.build().expect("build schema");
+
+    for (column, expected) in schema.schemas[0].tables[0]
+        .columns
+        .iter()
+        .zip(&golden_types)
+    {
+        assert_eq!(
+            serde_json::to_value(&column.column_type).expect("built"),
+            serde_json::to_value(expected).expect("golden"),
+            "{}",
+            expected.data_type
+        );
+    }
+}
+
+#[test]
+fn reports_misuse_and_invalid_references() {
+    let err = SchemaBuilder::new()
+        .table("public", TableBuilder::new("t").not_null())
+        .build()
+        .expect_err("modifier without column");
+    assert!(
+        err.to_string()
+            .contains("public.t: not_null called before any column")
+    );
+
+    let err = SchemaBuilder::new()
+        .table(
+            "public",
+            TableBuilder::new("orders")
+                .column("user_id", "bigint")
+                .foreign_key(["user_id"], "users", ["id"]),
+        )
+        .build()
+        .expect_err("missing parent table");
+    assert!(err.to_string().contains("users"), "{err}");
+}
+
+#[test]
+fn partitioned_tables_resolve_their_leaves() {
+    let columns = |table: TableBuilder| {
+        table
+            .column("id", "bigint")
+            .column("uf", "text")
+            .column("criado_em", "date")
+    };
+    let schema = SchemaBuilder::new()
+        .table(
+            "vendas",
+            columns(TableBuilder::new("pedidos"))
+                .partition_by(PartitionStrategy::List, ["uf"])
+                .partition("pedidos_sp", "FOR VALUES IN ('SP')")
+                .partition("arquivo.pedidos_outros", "DEFAULT"),
+        )
+        .table("vendas", columns(TableBuilder::new("pedidos_sp")))
+        .table(
+            "arquivo",
+            columns(TableBuilder::new("pedidos_outros"))
+                .partition_by(PartitionStrategy::Range, ["criado_em"])
+                .partition(
+                    "pedidos_2023",
+                    "FOR VALUES FROM ('2023-01-01') TO ('2024-01-01')",
+                )
+                .partition(
+                    "pedidos_2024",
+                    "FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')",
+                ),
+        )
+        .table("arquivo", columns(TableBuilder::new("pedidos_2023")))
+        .table("arquivo", columns(TableBuilder::new("pedidos_2024")))
+        .build()
+        .expect("build schema");
+
+    let pedidos = &schema.schemas[0].tables[0];
+    assert_eq!(pedidos.kind, TableKind::PartitionedTable);
+    assert_eq!(
+        pedidos.partition_key,
+        Some(PartitionKey {
+            strategy: PartitionStrategy::List,
+            columns: vec!["uf".to_string()],
+        })
+    );
+    assert_eq!(pedidos.partitions[0].schema, "vendas");
+    assert_eq!(pedidos.partitions[1].schema, "arquivo");
+    assert_eq!(
+        schema.leaf_partitions("vendas", "pedidos"),
+        [
+            "vendas.pedidos_sp",
+            "arquivo.pedidos_2023",
+            "arquivo.pedidos_2024"
+        ]
+    );
+    assert!(schema.leaf_partitions("vendas", "pedidos_sp").is_empty());
+
+    let json = serde_json::to_value(&schema.schemas[0].tables[1]).expect("serialize");
+    assert!(json.get("partition_key").is_none());
+    assert!(json.get("partitions").is_none());
+}
+
+#[test]
+fn columns_resolve_their_sequences() {
+    let mut schema = SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("pedidos")
+                .column("id", "bigint")
+                .column("numero", "integer")
+                .default_value("nextval('vendas.pedidos_numero_seq'::regclass)")
+                .column("total", "numeric"),
+        )
+        .build()
+        .expect("build schema");
+    let sequence = |name: &str, owner: Option<&str>| Sequence {
+        schema: "vendas".to_string(),
+        name: name.to_string(),
+        owner: owner.map(|column| SequenceOwner {
+            schema: "vendas".to_string(),
+            table: "pedidos".to_string(),
+            column: column.to_string(),
+        }),
+        start: 100,
+        increment: -5,
+        cache: 1,
+        last_value: None,
+    };
+    schema.sequences = vec![
+        sequence("pedidos_id_seq", Some("id")),
+        sequence("pedidos_numero_seq", None),
+    ];
+
+    let name = |column: &str| {
+        schema
+            .column_sequence("vendas", "pedidos", column)
+            .map(|sequence| sequence.name.as_str())
+    };
+    assert_eq!(name("id"), Some("pedidos_id_seq"));
+    assert_eq!(
+        name("numero"),
+        Some("pedidos_numero_seq"),
+        "nextval default"
+    );
+    assert_eq!(name("total"), None);
+    assert_eq!(schema.sequences[0].value_at(3), 85);
+
+    let ddl = render_postgres_ddl(&schema);
+    assert!(
+        ddl.contains(
+            "CREATE SEQUENCE IF NOT EXISTS vendas.pedidos_numero_seq START WITH 100 INCREMENT BY -5 CACHE 1;"
+        ),
+        "{ddl}"
+    );
+}
+
+#[test]
+fn domains_resolve_to_their_base_type() {
+    let schema = SchemaBuilder::new()
+        .domain_type(
+            "app",
+            "cpf",
+            "character varying(11)",
+            ["VALUE ~ '^[0-9]{11}$'"],
+        )
+        .domain_type("app", "cpf_ativo", "cpf", ["length(VALUE) = 11"])
+        .composite_type("app", "endereco", [("rua", "text"), ("documento", "cpf")])
+        .table(
+            "app",
+            TableBuilder::new("clientes")
+                .column("id", "bigint")
+                .column("Documento", "app.cpf_ativo")
+                .column("endereco", "endereco"),
+        )
+        .build()
+        .expect("build schema");
+
+    let documento = &schema.schemas[0].tables[0].columns[1].column_type;
+    assert_eq!(
+        (documento.udt_schema.as_str(), documento.udt_name.as_str()),
+        ("app", "cpf_ativo")
+    );
+    assert_eq!(
+        schema.domain(documento).map(|domain| domain.name.as_str()),
+        Some("cpf_ativo")
+    );
+    assert!(
+        schema
+            .composite_type(&schema.schemas[0].tables[0].columns[2].column_type)
+            .is_some()
+    );
+
+    let resolved = schema.resolve_domains();
+    let clientes = &resolved.schemas[0].tables[0];
+    assert_eq!(
+        clientes.columns[1].column_type.data_type,
+        "character varying(11)"
+    );
+    assert_eq!(
+        clientes.columns[1].column_type.character_max_length,
+        Some(11)
+    );
+    let checks: Vec<&str> = clientes
+        .constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::Check(check) => Some(check.expression.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            "CHECK (length(\"Documento\") = 11)",
+            "CHECK (\"Documento\" ~ '^[0-9]{11}$')"
+        ]
+    );
+    assert_eq!(
+        resolved.composite_types[0].attributes[1]
+            .column_type
+            .udt_name,
+        "varchar"
+    );
+
+    let ddl = render_postgres_ddl(&schema);
+    let cpf = ddl
+        .find("CREATE DOMAIN app.cpf AS character varying(11) CONSTRAINT cpf_check CHECK (VALUE ~ '^[0-9]{11}$');")
+        .expect(&ddl);
+    let cpf_ativo = ddl
+        .find("CREATE DOMAIN app.cpf_ativo AS app.cpf CONSTRAINT cpf_ativo_check CHECK (length(VALUE) = 11);")
+        .expect(&ddl);
+    assert!(cpf < cpf_ativo, "base domain first");
+    assert!(
+        ddl.contains("CREATE TYPE app.endereco AS (rua text, documento app.cpf);"),
+        "{ddl}"
+    );
+}
@@ -0,0 +1,145 @@
+use datalchemy_core::{
+    ColumnChange, Constraint, DatabaseSchema, Table, UniqueConstraint, constraint_signature,
+    diff_schemas,
+};
+
+fn golden_schema() -> DatabaseSchema {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    serde_json::from_str(raw).expect("parse golden schema")
+}
+
+fn table_mut<'a>(schema: &'a mut DatabaseSchema, name: &str) -> &'a mut Table {
+    schema.schemas[0]
+        .tables
+        .iter_mut()
+        .find(|table| table.name == name)
+        .expect("table")
+}
+
+#[test]
+fn identical_schemas_have_no_diff() {
+    let diff = diff_schemas(&golden_schema(), &golden_schema());
+    assert!(diff.is_empty());
+    assert!(diff.changed_tables().is_empty());
+}
+
+#[test]
+fn reports_table_column_and_constraint_changes() {
+    let before = golden_schema();
+    let mut after = golden_schema();
+    after.schemas[0]
+        .tables
+        .retain(|table| table.name != "tarefas");
+    let mut extra = table_mut(&mut after, "funis").clone();
+    extra.name = "funis_arquivo".to_string();
+    after.schemas[0].tables.push(extra);
+
+    let usuarios = table_mut(&mut after, "usuarios");
+    usuarios.columns.retain(|column| column.name != "telefone");
+    let email = usuarios
+        .columns
+        .iter_mut()
+        .find(|column| column.name == "email")
+        .expect("email");
+    email.is_nullable = !email.is_nullable;
+    email.column_type.data_type = "character varying(320)".to_string();
+    let mut apelido = email.clone();
+    apelido.name = "apelido".to_string();
+    apelido.comment = Some("ignored".to_string());
+    usuarios.columns.push(apelido);
+    usuarios
+        .constraints
+        .retain(|constraint| !matches!(constraint, Constraint::Unique(_)));
+    usuarios
+        .constraints
+        .push(Constraint::Unique(UniqueConstraint {
+            name: Some("usuarios_nome_unique".to_string()),
+            columns: vec!["nome".to_string()],
+            is_deferrable: false,
+            initially_deferred: false,
+        }));
+    // A renamed constraint is not a change.
+    if let Some(Constraint::PrimaryKey(pk)) = usuarios.constraints.first_mut() {
+        pk.name = Some("usuarios_pk_renamed".to_string());
+    }
+
+    let diff = diff_schemas(&before, &after);
+    assert_eq!(diff.tables_added, vec!["crm.funis_arquivo"]);
+    assert_eq!(diff.tables_removed, vec!["crm.tarefas"]);
+    assert_eq!(diff.tables_modified.len(), 1);
+    let table = diff.table("crm.usuarios").expect("usuarios diff");
+    let names = |columns: &[datalchemy_core::Column]| {
+        columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&table.columns_added), vec!["apelido"]);
+    assert_eq!(names(&table.columns_removed), vec!["telefone"]);
+    assert_eq!(table.columns_modified.len(), 1);
+    assert_eq!(table.columns_modified[0].name, "email");
+    assert_eq!(
+        table.columns_modified[0].changes,
+        vec![ColumnChange::Type, ColumnChange::Nullability]
+    );
+    let signatures = |constraints: &[Constraint]| {
+        constraints
+            .iter()
+            .map(constraint_signature)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(signatures(&table.constraints_added), vec!["unique (nome)"]);
+    assert_eq!(
+        signatures(&table.constraints_removed),
+        vec!["unique (email)"]
+    );
+    assert_eq!(
+        diff.changed_tables().into_iter().collect::<Vec<_>>(),
+        vec!["crm.funis_arquivo", "crm.tarefas", "crm.usuarios"]
+    );
+}
+
+#[test]
+fn reports_enum_changes() {
+    let before = golden_schema();
+    let mut after = golden_schema();
+    let removed = after.enums.remove(0);
+    let mut renamed = removed.clone();
+    renamed.name = "status_arquivo".to_string();
+    after.enums.push(renamed);
+    let status_fatura = after
+        .enums
+        .iter_mut()
+        .find(|en| en.name == "status_fatura")
+        .expect("status_fatura");
+    let first = status_fatura.labels.remove(0);
+    status_fatura.labels.push("estornada".to_string());
+
+    let diff = diff_schemas(&before, &after);
+    assert!(diff.changed_tables().is_empty());
+    assert_eq!(diff.enums_added, vec!["crm.status_arquivo"]);
+    assert_eq!(diff.enums_removed, vec![format!("crm.{}", removed.name)]);
+    assert_eq!(diff.enums_modified.len(), 1);
+    let modified = &diff.enums_modified[0];
+    assert_eq!(modified.name, "crm.status_fatura");
+    assert_eq!(modified.labels_added, vec!["estornada"]);
+    assert_eq!(modified.labels_removed, vec![first]);
+}
+
+#[test]
+fn diff_round_trips_through_json() {
+    let before = golden_schema();
+    let mut after = golden_schema();
+    table_mut(&mut after, "produtos").columns[1].default = Some("'sku'".to_string());
+
+    let diff = diff_schemas(&before, &after);
+    let json = serde_json::to_value(&diff).expect("serialize diff");
+    assert_eq!(json["tables_modified"][0]["table"], "crm.produtos");
+    assert_eq!(
+        json["tables_modified"][0]["columns_modified"][0]["changes"],
+        serde_json::json!(["default"])
+    );
+    let parsed: datalchemy_core::SchemaDiff =
+        serde_json::from_value(json).expect("deserialize diff");
+    assert_eq!(parsed.tables_modified[0].columns_modified[0].name, "sku");
+}
@@ -1,4 +1,4 @@
-use datalchemy_core::{DatabaseSchema, Schema};
+use datalchemy_core::{ColumnStats, DatabaseSchema, Schema, TablePrivileges};
 
 #[test]
 fn serializes_schema_deterministically() {
@@ -11,6 +11,12 @@ fn serializes_schema_deterministically() {
             tables: Vec::new(),
         }],
         enums: Vec::new(),
+        domains: Vec::new(),
+        composite_types: Vec::new(),
+        sequences: Vec::new(),
+        current_role: None,
+        failed_tables: Vec::new(),
+        external_tables: Vec::new(),
         schema_fingerprint: None,
     };
 
@@ -30,3 +36,110 @@ fn serializes_schema_deterministically() {
 }"#;
     assert_eq!(json, expected);
 }
+
+#[test]
+fn fingerprint_is_canonical_and_tracks_structure() {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    let schema: DatabaseSchema = serde_json::from_str(raw).expect("parse golden schema");
+    let fingerprint = schema.compute_fingerprint().expect("fingerprint");
+    assert_eq!(fingerprint.len(), 64);
+    assert_eq!(
+        schema.schema_fingerprint.as_deref(),
+        Some(fingerprint.as_str())
+    );
+
+    let mut other_database = schema.clone();
+    other_database.database = Some("staging".to_string());
+    other_database.schema_fingerprint = None;
+    assert_eq!(
+        other_database.compute_fingerprint().expect("fingerprint"),
+        fingerprint,
+        "database name and stored fingerprint are ignored"
+    );
+
+    let mut sampled = schema.clone();
+    sampled.schemas[0].tables[0].row_estimate = Some(1200);
+    sampled.schemas[0].tables[0].columns[0].stats = Some(ColumnStats {
+        null_frac: 0.25,
+        n_distinct: -1.0,
+    });
+    assert_eq!(
+        sampled.compute_fingerprint().expect("fingerprint"),
+        fingerprint,
+        "sampled statistics are ignored"
+    );
+
+    let mut versioned = schema.clone();
+    versioned.schemas[0].tables[0].catalog_version = Some("5d41402abc4b2a76".to_string());
+    assert_eq!(
+        versioned.compute_fingerprint().expect("fingerprint"),
+        fingerprint,
+        "catalog versions are ignored"
+    );
+
+    let mut granted = schema.clone();
+    granted.current_role = Some("app".to_string());
+    granted.schemas[0].tables[0].privileges = Some(TablePrivileges {
+        select: vec!["app".to_string()],
+        insert: Vec::new(),
+    });
+    assert_eq!(
+        granted.compute_fingerprint().expect("fingerprint"),
+        fingerprint,
+        "privileges are ignored"
+    );
+
+    let mut changed = schema.clone();
+    changed.schemas[0].tables[0].columns[0].is_nullable ^= true;
+    assert_ne!(
+        changed.compute_fingerprint().expect("fingerprint"),
+        fingerprint
+    );
+}
+
+#[test]
+fn column_stats_estimate_distinct_values() {
+    let ratio = ColumnStats {
+        null_frac: 0.0,
+        n_distinct: -0.5,
+    };
+    assert_eq!(ratio.distinct_values(1000), 500);
+    let count = ColumnStats {
+        null_frac: 0.1,
+        n_distinct: 7.0,
+    };
+    assert_eq!(count.distinct_values(1000), 7);
+}
+
+#[test]
+fn tables_without_insert_follow_the_current_role() {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    let mut schema: DatabaseSchema = serde_json::from_str(raw).expect("parse golden schema");
+    let schema_name = schema.schemas[0].name.clone();
+    let first = schema.schemas[0].tables[0].name.clone();
+    let second = schema.schemas[0].tables[1].name.clone();
+    let targets = [
+        (schema_name.as_str(), first.as_str()),
+        (schema_name.as_str(), second.as_str()),
+    ];
+    schema.schemas[0].tables[0].privileges = Some(TablePrivileges {
+        select: vec!["leitor".to_string(), "app".to_string()],
+        insert: vec!["app".to_string()],
+    });
+    schema.schemas[0].tables[1].privileges = Some(TablePrivileges {
+        select: vec!["leitor".to_string(), "app".to_string()],
+        insert: Vec::new(),
+    });
+    assert!(
+        schema.tables_without_insert(targets).is_empty(),
+        "nothing to check without the privileges role"
+    );
+
+    schema.current_role = Some("app".to_string());
+    assert_eq!(
+        schema.tables_without_insert(targets),
+        vec![format!("{schema_name}.{second}")]
+    );
+    schema.current_role = Some("leitor".to_string());
+    assert_eq!(schema.tables_without_insert(targets).len(), 2);
+}
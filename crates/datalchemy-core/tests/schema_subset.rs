@@ -0,0 +1,98 @@
+use std::collections::BTreeSet;
+
+use datalchemy_core::{Constraint, DatabaseSchema, SubsetOptions, validate_schema};
+
+fn golden_schema() -> DatabaseSchema {
+    let raw = include_str!("../../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    serde_json::from_str(raw).expect("parse golden schema")
+}
+
+fn table_names(schema: &DatabaseSchema) -> BTreeSet<String> {
+    schema
+        .schemas
+        .iter()
+        .flat_map(|db_schema| {
+            db_schema
+                .tables
+                .iter()
+                .map(move |table| format!("{}.{}", db_schema.name, table.name))
+        })
+        .collect()
+}
+
+fn assert_fk_closed(schema: &DatabaseSchema) {
+    let names = table_names(schema);
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            for constraint in &table.constraints {
+                if let Constraint::ForeignKey(fk) = constraint {
+                    let parent = format!("{}.{}", fk.referenced_schema, fk.referenced_table);
+                    assert!(names.contains(&parent), "{} -> {parent}", table.name);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn keeps_transitive_fk_parents() {
+    let schema = golden_schema();
+    let subset = schema
+        .subset(&["crm.pagamentos"], SubsetOptions::default())
+        .expect("subset");
+
+    // pagamentos -> faturas -> cotacoes -> oportunidades -> ...
+    let names = table_names(&subset);
+    for table in [
+        "crm.pagamentos",
+        "crm.faturas",
+        "crm.cotacoes",
+        "crm.oportunidades",
+        "crm.usuarios",
+    ] {
+        assert!(names.contains(table), "{table} missing");
+    }
+    assert!(!names.contains("crm.itens_fatura"));
+    assert!(!names.contains("crm.tarefas"));
+    assert_eq!(
+        subset.schema_fingerprint,
+        Some(subset.compute_fingerprint().expect("fingerprint")),
+        "fingerprint describes the slice"
+    );
+    assert_ne!(subset.schema_fingerprint, schema.schema_fingerprint);
+    assert_fk_closed(&subset);
+    validate_schema(&subset).expect("subset is a valid schema");
+}
+
+#[test]
+fn includes_children_and_their_parents_on_request() {
+    let schema = golden_schema();
+    let parents_only = schema
+        .subset(&["faturas"], SubsetOptions::default())
+        .expect("subset");
+    let with_children = schema
+        .subset(
+            &["faturas"],
+            SubsetOptions {
+                include_children: true,
+            },
+        )
+        .expect("subset");
+
+    let names = table_names(&with_children);
+    assert!(!table_names(&parents_only).contains("crm.itens_fatura"));
+    assert!(names.contains("crm.itens_fatura"));
+    assert!(names.contains("crm.pagamentos"));
+    // itens_fatura also references produtos.
+    assert!(names.contains("crm.produtos"));
+    assert!(table_names(&parents_only).is_subset(&names));
+    assert_fk_closed(&with_children);
+}
+
+#[test]
+fn rejects_unknown_tables() {
+    let err = golden_schema()
+        .subset(&["crm.nope"], SubsetOptions::default())
+        .expect_err("unknown table");
+    assert!(err.to_string().contains("table not found: crm.nope"));
+}
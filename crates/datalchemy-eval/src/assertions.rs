@@ -0,0 +1,260 @@
+//! Plan assertions (see [`datalchemy_plan::assertion`]) evaluated over rows.
+//!
+//! Aggregates are accumulated row by row, so the same tally serves the
+//! in-memory and the streaming paths. `count(*)` is the number of rows in the
+//! file; the other aggregates cover the rows passed to
+//! [`AssertionTally::add_row`] (the sample, in sampled mode).
+
+use std::collections::HashSet;
+
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_plan::assertion::{Aggregate, AggregateFn};
+use datalchemy_plan::{Assertion, AssertionExpr};
+
+use crate::engine::value_key;
+use crate::metrics::{AggregateValue, AssertionResult, AssertionStatus, WarningItem};
+use crate::model::Violation;
+
+/// Aggregates of one assertion over the rows of its table.
+#[derive(Debug, Clone)]
+pub struct AssertionTally {
+    result: AssertionResult,
+    expr: Option<AssertionExpr>,
+    columns: Vec<Option<usize>>,
+    states: Vec<AggregateState>,
+    rows_checked: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AggregateState {
+    non_null: u64,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    distinct: HashSet<String>,
+}
+
+impl AssertionTally {
+    /// Bind `assertion` to a table whose rows hold `columns`, in order.
+    pub fn new(assertion: &Assertion, columns: &[&str]) -> Self {
+        let mut tally = Self {
+            result: pending_result(assertion),
+            expr: None,
+            columns: Vec::new(),
+            states: Vec::new(),
+            rows_checked: 0,
+        };
+        let expr = match AssertionExpr::parse(&assertion.expr) {
+            Ok(expr) => expr,
+            Err(err) => {
+                tally.fail_with(format!("invalid expression: {err}"));
+                return tally;
+            }
+        };
+        for aggregate in &expr.aggregates {
+            let idx = match &aggregate.column {
+                Some(column) => match columns
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(column))
+                {
+                    Some(idx) => Some(idx),
+                    None => {
+                        tally.fail_with(format!("column '{column}' not found in the dataset"));
+                        return tally;
+                    }
+                },
+                None => None,
+            };
+            tally.columns.push(idx);
+        }
+        tally.states = vec![AggregateState::default(); expr.aggregates.len()];
+        tally.expr = Some(expr);
+        tally
+    }
+
+    pub fn add_row(&mut self, row: &[GeneratedValue]) {
+        let Some(expr) = &self.expr else {
+            return;
+        };
+        self.rows_checked += 1;
+        let mut error = None;
+        for ((aggregate, idx), state) in expr
+            .aggregates
+            .iter()
+            .zip(&self.columns)
+            .zip(self.states.iter_mut())
+        {
+            let Some(value) = idx.and_then(|idx| row.get(idx)) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            state.non_null += 1;
+            match aggregate.function {
+                AggregateFn::CountDistinct | AggregateFn::DistinctRatio => {
+                    state.distinct.insert(value_key(value));
+                }
+                function if function.is_numeric() => {
+                    let Some(number) = numeric(value) else {
+                        error = Some(format!(
+                            "{aggregate}: non-numeric value '{}'",
+                            value_key(value)
+                        ));
+                        break;
+                    };
+                    state.sum += number;
+                    state.min = Some(state.min.map_or(number, |min| min.min(number)));
+                    state.max = Some(state.max.map_or(number, |max| max.max(number)));
+                }
+                _ => {}
+            }
+        }
+        if let Some(message) = error {
+            self.expr = None;
+            self.fail_with(message);
+        }
+    }
+
+    /// Evaluate the expression; `rows_found` is the table's `count(*)`.
+    pub fn finish(self, rows_found: u64) -> AssertionResult {
+        let mut result = self.result;
+        let Some(expr) = self.expr else {
+            return result;
+        };
+        let values: Vec<Option<f64>> = expr
+            .aggregates
+            .iter()
+            .zip(&self.states)
+            .map(|(aggregate, state)| {
+                aggregate_value(aggregate, state, rows_found, self.rows_checked)
+            })
+            .collect();
+        result.status = if expr.evaluate(&values) {
+            AssertionStatus::Passed
+        } else {
+            AssertionStatus::Failed
+        };
+        result.values = expr
+            .aggregates
+            .iter()
+            .zip(values)
+            .map(|(aggregate, value)| AggregateValue {
+                aggregate: aggregate.to_string(),
+                value,
+            })
+            .collect();
+        result
+    }
+
+    fn fail_with(&mut self, message: String) {
+        self.result.status = AssertionStatus::Error;
+        self.result.message = Some(message);
+    }
+}
+
+/// Result of an assertion whose table has no file in the dataset.
+pub fn missing_table(assertion: &Assertion) -> AssertionResult {
+    AssertionResult {
+        status: AssertionStatus::Error,
+        message: Some("table not found in the dataset".to_string()),
+        ..pending_result(assertion)
+    }
+}
+
+/// `assertion` violation of a failed assertion.
+pub fn assertion_violation(result: &AssertionResult) -> Option<Violation> {
+    if result.status != AssertionStatus::Failed {
+        return None;
+    }
+    Some(Violation {
+        code: "assertion".to_string(),
+        path: format!("{}.{}", result.schema, result.table),
+        message: format!(
+            "assertion '{}' failed: {} ({})",
+            result.name,
+            result.expr,
+            format_values(&result.values)
+        ),
+        row_index: None,
+        example: None,
+        samples: Vec::new(),
+    })
+}
+
+/// `assertion_not_evaluated` warning of an assertion that could not run.
+pub fn assertion_warning(result: &AssertionResult) -> Option<WarningItem> {
+    if result.status != AssertionStatus::Error {
+        return None;
+    }
+    Some(WarningItem {
+        code: "assertion_not_evaluated".to_string(),
+        path: format!("{}.{}", result.schema, result.table),
+        message: format!(
+            "assertion '{}' not evaluated: {}",
+            result.name,
+            result.message.as_deref().unwrap_or("unknown error")
+        ),
+        hint: Some("fix the assertion in the plan's `assertions` section".to_string()),
+    })
+}
+
+/// `avg(valor) = 12.5, count(*) = 10` form of measured aggregates.
+pub fn format_values(values: &[AggregateValue]) -> String {
+    values
+        .iter()
+        .map(|value| match value.value {
+            Some(number) => format!("{} = {}", value.aggregate, format_number(number)),
+            None => format!("{} = undefined", value.aggregate),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.4}")
+    }
+}
+
+fn pending_result(assertion: &Assertion) -> AssertionResult {
+    AssertionResult {
+        name: assertion.label().to_string(),
+        schema: assertion.schema.clone(),
+        table: assertion.table.clone(),
+        expr: assertion.expr.clone(),
+        status: AssertionStatus::Error,
+        values: Vec::new(),
+        message: None,
+    }
+}
+
+fn aggregate_value(
+    aggregate: &Aggregate,
+    state: &AggregateState,
+    rows_found: u64,
+    rows_checked: u64,
+) -> Option<f64> {
+    let ratio = |part: u64, total: u64| (total > 0).then(|| part as f64 / total as f64);
+    match aggregate.function {
+        AggregateFn::Count if aggregate.column.is_none() => Some(rows_found as f64),
+        AggregateFn::Count => Some(state.non_null as f64),
+        AggregateFn::CountDistinct => Some(state.distinct.len() as f64),
+        AggregateFn::Sum => Some(state.sum),
+        AggregateFn::Avg => (state.non_null > 0).then(|| state.sum / state.non_null as f64),
+        AggregateFn::Min => state.min,
+        AggregateFn::Max => state.max,
+        AggregateFn::NullRate => ratio(rows_checked - state.non_null, rows_checked),
+        AggregateFn::DistinctRatio => ratio(state.distinct.len() as u64, state.non_null),
+    }
+}
+
+fn numeric(value: &GeneratedValue) -> Option<f64> {
+    match value {
+        GeneratedValue::Int(value) => Some(*value as f64),
+        GeneratedValue::Float(value) => Some(*value),
+        _ => None,
+    }
+}
@@ -0,0 +1,281 @@
+//! Distribution comparison against a reference dataset.
+//!
+//! Numeric and temporal columns use the two-sample Kolmogorov-Smirnov test and
+//! a [`HISTOGRAM_BINS`]-bin histogram; categorical columns (text, booleans)
+//! use a chi-square test of homogeneity over their categories. UUID columns
+//! and categoricals with more than [`MAX_CATEGORIES`] values are skipped.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Timelike};
+use datalchemy_generate::generators::GeneratedValue;
+
+use crate::metrics::{ColumnDistribution, DistributionKind};
+
+/// Significance level below which a column is reported as diverging.
+pub const DIVERGENCE_ALPHA: f64 = 0.05;
+/// Equal-width bins of the numeric histogram overlap.
+pub const HISTOGRAM_BINS: usize = 10;
+/// Categoricals with more distinct values (both sides) are not compared.
+pub const MAX_CATEGORIES: usize = 50;
+
+/// Compare the values of a column with the reference values of the same column.
+///
+/// Returns `None` when the column cannot be compared (no values on a side,
+/// UUIDs, high-cardinality text or mixed types).
+pub fn compare_column<'a>(
+    schema: &str,
+    table: &str,
+    column: &str,
+    values: impl IntoIterator<Item = &'a GeneratedValue>,
+    reference: impl IntoIterator<Item = &'a GeneratedValue>,
+) -> Option<ColumnDistribution> {
+    let values: Vec<&GeneratedValue> = values.into_iter().filter(|v| !v.is_null()).collect();
+    let reference: Vec<&GeneratedValue> = reference.into_iter().filter(|v| !v.is_null()).collect();
+    if values.is_empty() || reference.is_empty() {
+        return None;
+    }
+    let kind = kind_of(&values)?;
+    if kind_of(&reference)? != kind {
+        return None;
+    }
+
+    let mut distribution = ColumnDistribution {
+        schema: schema.to_string(),
+        table: table.to_string(),
+        column: column.to_string(),
+        kind,
+        rows: values.len() as u64,
+        reference_rows: reference.len() as u64,
+        ks_statistic: None,
+        chi_square: None,
+        degrees_of_freedom: None,
+        p_value: 1.0,
+        histogram_overlap: 1.0,
+        diverges: false,
+    };
+    match kind {
+        DistributionKind::Numeric | DistributionKind::Temporal => {
+            let mut sample: Vec<f64> = values.iter().filter_map(|v| as_number(v)).collect();
+            let mut expected: Vec<f64> = reference.iter().filter_map(|v| as_number(v)).collect();
+            sample.sort_by(f64::total_cmp);
+            expected.sort_by(f64::total_cmp);
+            let statistic = ks_statistic(&sample, &expected);
+            distribution.ks_statistic = Some(statistic);
+            distribution.p_value = ks_p_value(statistic, sample.len(), expected.len());
+            distribution.histogram_overlap = numeric_overlap(&sample, &expected);
+        }
+        DistributionKind::Categorical => {
+            let mut counts: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+            for value in &values {
+                counts.entry(category(value)).or_default().0 += 1;
+            }
+            for value in &reference {
+                counts.entry(category(value)).or_default().1 += 1;
+            }
+            if counts.len() > MAX_CATEGORIES {
+                return None;
+            }
+            let observed: Vec<(u64, u64)> = counts.into_values().collect();
+            let (statistic, degrees) = chi_square(&observed);
+            distribution.chi_square = Some(statistic);
+            distribution.degrees_of_freedom = Some(degrees);
+            distribution.p_value = if degrees == 0 {
+                1.0
+            } else {
+                gamma_q(degrees as f64 / 2.0, statistic / 2.0)
+            };
+            let (total, reference_total) = (values.len() as f64, reference.len() as f64);
+            distribution.histogram_overlap = observed
+                .iter()
+                .map(|(a, b)| (*a as f64 / total).min(*b as f64 / reference_total))
+                .sum::<f64>()
+                .min(1.0);
+        }
+    }
+    distribution.diverges = distribution.p_value < DIVERGENCE_ALPHA;
+    Some(distribution)
+}
+
+fn kind_of(values: &[&GeneratedValue]) -> Option<DistributionKind> {
+    let kind_of_value = |value: &GeneratedValue| match value {
+        GeneratedValue::Int(_) | GeneratedValue::Float(_) => Some(DistributionKind::Numeric),
+        GeneratedValue::Date(_) | GeneratedValue::Time(_) | GeneratedValue::Timestamp(_) => {
+            Some(DistributionKind::Temporal)
+        }
+        GeneratedValue::Bool(_) | GeneratedValue::Text(_) => Some(DistributionKind::Categorical),
+        GeneratedValue::Uuid(_) | GeneratedValue::Null => None,
+    };
+    let kind = kind_of_value(values.first()?)?;
+    values
+        .iter()
+        .all(|value| kind_of_value(value) == Some(kind))
+        .then_some(kind)
+}
+
+fn as_number(value: &GeneratedValue) -> Option<f64> {
+    match value {
+        GeneratedValue::Int(value) => Some(*value as f64),
+        GeneratedValue::Float(value) => Some(*value),
+        GeneratedValue::Date(value) => Some(value.num_days_from_ce() as f64),
+        GeneratedValue::Time(value) => Some(value.num_seconds_from_midnight() as f64),
+        GeneratedValue::Timestamp(value) => Some(value.and_utc().timestamp() as f64),
+        _ => None,
+    }
+}
+
+fn category(value: &GeneratedValue) -> String {
+    match value {
+        GeneratedValue::Bool(value) => value.to_string(),
+        GeneratedValue::Text(value) => value.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Largest distance between the empirical CDFs of two sorted samples.
+fn ks_statistic(a: &[f64], b: &[f64]) -> f64 {
+    let (n, m) = (a.len() as f64, b.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut statistic = 0.0_f64;
+    while i < a.len() && j < b.len() {
+        let x = a[i].min(b[j]);
+        while i < a.len() && a[i] <= x {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= x {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+    }
+    statistic
+}
+
+/// Asymptotic p-value of the two-sample KS statistic (Stephens' correction).
+fn ks_p_value(statistic: f64, n: usize, m: usize) -> f64 {
+    let effective = ((n * m) as f64 / (n + m) as f64).sqrt();
+    let lambda = (effective + 0.12 + 0.11 / effective) * statistic;
+    let exponent = -2.0 * lambda * lambda;
+    let mut sign = 2.0;
+    let mut sum = 0.0;
+    let mut previous = 0.0_f64;
+    for k in 1..=100 {
+        let term = sign * (exponent * (k * k) as f64).exp();
+        sum += term;
+        if term.abs() <= 0.001 * previous || term.abs() <= 1e-8 * sum {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+        previous = term.abs();
+    }
+    // The series does not converge for tiny statistics: samples are alike.
+    1.0
+}
+
+fn numeric_overlap(a: &[f64], b: &[f64]) -> f64 {
+    let min = a[0].min(b[0]);
+    let max = a[a.len() - 1].max(b[b.len() - 1]);
+    if max <= min {
+        return 1.0;
+    }
+    let histogram = |values: &[f64]| {
+        let mut bins = [0.0_f64; HISTOGRAM_BINS];
+        for value in values {
+            let bin = (((value - min) / (max - min)) * HISTOGRAM_BINS as f64) as usize;
+            bins[bin.min(HISTOGRAM_BINS - 1)] += 1.0 / values.len() as f64;
+        }
+        bins
+    };
+    let (left, right) = (histogram(a), histogram(b));
+    left.iter()
+        .zip(&right)
+        .map(|(l, r)| l.min(*r))
+        .sum::<f64>()
+        .min(1.0)
+}
+
+/// Chi-square statistic and degrees of freedom of a 2 x k contingency table.
+fn chi_square(observed: &[(u64, u64)]) -> (f64, u64) {
+    let total_a: u64 = observed.iter().map(|(a, _)| a).sum();
+    let total_b: u64 = observed.iter().map(|(_, b)| b).sum();
+    let total = (total_a + total_b) as f64;
+    let statistic = observed
+        .iter()
+        .map(|(a, b)| {
+            let category = (a + b) as f64;
+            let expected_a = total_a as f64 * category / total;
+            let expected_b = total_b as f64 * category / total;
+            (*a as f64 - expected_a).powi(2) / expected_a
+                + (*b as f64 - expected_b).powi(2) / expected_b
+        })
+        .sum();
+    (statistic, observed.len().saturating_sub(1) as u64)
+}
+
+/// Regularized upper incomplete gamma function Q(a, x).
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < a + 1.0 {
+        // Series for P(a, x).
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..500 {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-14 {
+                break;
+            }
+        }
+        (1.0 - sum * (-x + a * x.ln() - ln_gamma(a)).exp()).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for Q(a, x) (modified Lentz).
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..500 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-14 {
+                break;
+            }
+        }
+        ((-x + a * x.ln() - ln_gamma(a)).exp() * h).clamp(0.0, 1.0)
+    }
+}
+
+/// Natural log of the gamma function (Lanczos approximation).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    let mut y = x;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
@@ -0,0 +1,95 @@
+//! Pearson/Spearman correlations between the numeric columns of a table.
+
+use datalchemy_generate::generators::GeneratedValue;
+
+use crate::metrics::ColumnCorrelation;
+
+/// Correlations of every pair of numeric columns, in column order.
+///
+/// `columns` are the column names and `rows` the table rows, with values in
+/// the same order. A column is numeric when all its non-null values are.
+pub fn correlate_columns(
+    schema: &str,
+    table: &str,
+    columns: &[&str],
+    rows: &[Vec<GeneratedValue>],
+) -> Vec<ColumnCorrelation> {
+    let numeric: Vec<usize> = (0..columns.len())
+        .filter(|idx| {
+            let mut values = rows
+                .iter()
+                .filter_map(|row| row.get(*idx))
+                .filter(|value| !value.is_null())
+                .peekable();
+            values.peek().is_some() && values.all(|value| as_number(value).is_some())
+        })
+        .collect();
+
+    let mut correlations = Vec::new();
+    for (position, &a) in numeric.iter().enumerate() {
+        for &b in &numeric[position + 1..] {
+            let (xs, ys): (Vec<f64>, Vec<f64>) = rows
+                .iter()
+                .filter_map(|row| Some((as_number(row.get(a)?)?, as_number(row.get(b)?)?)))
+                .unzip();
+            correlations.push(ColumnCorrelation {
+                schema: schema.to_string(),
+                table: table.to_string(),
+                column_a: columns[a].to_string(),
+                column_b: columns[b].to_string(),
+                rows: xs.len() as u64,
+                pearson: pearson(&xs, &ys),
+                spearman: pearson(&ranks(&xs), &ranks(&ys)),
+            });
+        }
+    }
+    correlations
+}
+
+fn as_number(value: &GeneratedValue) -> Option<f64> {
+    match value {
+        GeneratedValue::Int(value) => Some(*value as f64),
+        GeneratedValue::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() < 2 {
+        return None;
+    }
+    let count = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / count;
+    let mean_y = ys.iter().sum::<f64>() / count;
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        covariance += dx * dy;
+        variance_x += dx * dx;
+        variance_y += dy * dy;
+    }
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+    Some((covariance / (variance_x * variance_y).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// 1-based ranks, ties sharing their average rank.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end) as f64 / 2.0 + 1.0;
+        for idx in &order[start..=end] {
+            ranks[*idx] = rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
@@ -0,0 +1,269 @@
+//! Dataset files of a directory mapped to schema tables.
+//!
+//! Datalchemy runs write `<schema>.<table>.csv`; external datasets may use any
+//! layout, described by patterns with `{schema}` and `{table}` placeholders
+//! (e.g. `fixtures/{table}.parquet`). The extension selects the reader: `.csv`,
+//! `.jsonl`/`.ndjson` (one JSON object per line) or `.parquet` (the latter
+//! requires the `parquet` feature).
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use csv::StringRecord;
+
+use crate::errors::EvalError;
+
+/// Patterns tried, in order, when none is configured.
+pub const DEFAULT_FILE_PATTERNS: &[&str] = &[
+    "{schema}.{table}.csv",
+    "{schema}.{table}.parquet",
+    "{schema}.{table}.jsonl",
+    "{table}.csv",
+    "{table}.parquet",
+    "{table}.jsonl",
+];
+
+/// Rows of a dataset file, read lazily.
+pub type Records = Box<dyn Iterator<Item = Result<StringRecord, EvalError>>>;
+
+/// Reader used for a dataset file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetFormat {
+    Csv,
+    Parquet,
+    Jsonl,
+}
+
+impl DatasetFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "parquet" => Some(Self::Parquet),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// File holding the rows of one table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetFile {
+    pub path: PathBuf,
+    pub format: DatasetFormat,
+}
+
+/// Files of a dataset directory, matched case-insensitively against patterns.
+#[derive(Debug, Clone)]
+pub struct DatasetDir {
+    root: PathBuf,
+    patterns: Vec<String>,
+    /// Lowercased relative path (`/`-separated) to the file on disk.
+    files: HashMap<String, PathBuf>,
+}
+
+impl DatasetDir {
+    /// Index the files under `root`; empty `patterns` means [`DEFAULT_FILE_PATTERNS`].
+    pub fn open(root: &Path, patterns: &[String]) -> Result<Self, EvalError> {
+        if !root.is_dir() {
+            return Err(EvalError::InvalidDataset(format!(
+                "dataset directory not found: {}",
+                root.display()
+            )));
+        }
+        let mut files = HashMap::new();
+        collect_files(root, root, &mut files)?;
+        let patterns = if patterns.is_empty() {
+            DEFAULT_FILE_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect()
+        } else {
+            patterns.to_vec()
+        };
+        Ok(Self {
+            root: root.to_path_buf(),
+            patterns,
+            files,
+        })
+    }
+
+    /// First file matching a pattern for `schema.table`, if any.
+    pub fn table_file(&self, schema: &str, table: &str) -> Option<DatasetFile> {
+        self.patterns.iter().find_map(|pattern| {
+            let relative = pattern
+                .replace("{schema}", schema)
+                .replace("{table}", table);
+            let format = DatasetFormat::from_path(Path::new(&relative))?;
+            let path = self
+                .files
+                .get(&relative.replace('\\', "/").to_lowercase())
+                .cloned()?;
+            Some(DatasetFile { path, format })
+        })
+    }
+
+    /// Path shown in warnings for a table without a file.
+    pub fn expected_path(&self, schema: &str, table: &str) -> PathBuf {
+        let pattern = self.patterns.first().map(String::as_str).unwrap_or("");
+        self.root.join(
+            pattern
+                .replace("{schema}", schema)
+                .replace("{table}", table),
+        )
+    }
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut HashMap<String, PathBuf>,
+) -> Result<(), EvalError> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let key = relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy().to_lowercase())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(key, path);
+        }
+    }
+    Ok(())
+}
+
+/// Header and records of a dataset file, as text cells.
+pub fn read_records(file: &DatasetFile) -> Result<(Vec<String>, Records), EvalError> {
+    match file.format {
+        DatasetFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(true)
+                .from_path(&file.path)?;
+            let headers = reader.headers()?.iter().map(str::to_string).collect();
+            let records = reader
+                .into_records()
+                .map(|record| record.map_err(EvalError::from));
+            Ok((headers, Box::new(records)))
+        }
+        DatasetFormat::Parquet => read_parquet(&file.path),
+        DatasetFormat::Jsonl => read_jsonl(&file.path),
+    }
+}
+
+/// JSON Lines: the header is every key found in the file, so it is scanned
+/// once for keys before rows are streamed. Missing keys and
+/// `null` read as empty cells; arrays and objects keep their JSON text.
+fn read_jsonl(path: &Path) -> Result<(Vec<String>, Records), EvalError> {
+    let mut headers: Vec<String> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (index, line) in BufReader::new(std::fs::File::open(path)?)
+        .lines()
+        .enumerate()
+    {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        for key in jsonl_object(path, index, &line)?.keys() {
+            if !positions.contains_key(key) {
+                positions.insert(key.clone(), headers.len());
+                headers.push(key.clone());
+            }
+        }
+    }
+
+    let path = path.to_path_buf();
+    let width = headers.len();
+    let lines = BufReader::new(std::fs::File::open(&path)?)
+        .lines()
+        .enumerate();
+    let records = lines
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(move |(index, line)| {
+            let object = jsonl_object(&path, index, &line?)?;
+            let mut cells = vec![String::new(); width];
+            for (key, value) in object {
+                if let Some(&position) = positions.get(&key) {
+                    cells[position] = jsonl_cell(value);
+                }
+            }
+            Ok(cells.into_iter().collect::<StringRecord>())
+        });
+    Ok((headers, Box::new(records)))
+}
+
+fn jsonl_object(
+    path: &Path,
+    index: usize,
+    line: &str,
+) -> Result<serde_json::Map<String, serde_json::Value>, EvalError> {
+    match serde_json::from_str(line)? {
+        serde_json::Value::Object(object) => Ok(object),
+        _ => Err(EvalError::InvalidDataset(format!(
+            "{} line {}: expected a JSON object",
+            path.display(),
+            index + 1
+        ))),
+    }
+}
+
+fn jsonl_cell(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet(path: &Path) -> Result<(Vec<String>, Records), EvalError> {
+    use arrow_cast::display::{ArrayFormatter, FormatOptions};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(path)?)?;
+    let headers = builder
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect();
+    let reader = builder.build()?;
+    let options = FormatOptions::default().with_null("");
+    let records = reader.flat_map(move |batch| {
+        let rows: Vec<Result<StringRecord, EvalError>> = match batch {
+            Ok(batch) => {
+                let formatters = batch
+                    .columns()
+                    .iter()
+                    .map(|array| ArrayFormatter::try_new(array.as_ref(), &options))
+                    .collect::<Result<Vec<_>, _>>();
+                match formatters {
+                    Ok(formatters) => (0..batch.num_rows())
+                        .map(|row| {
+                            Ok(formatters
+                                .iter()
+                                .map(|formatter| formatter.value(row).to_string())
+                                .collect::<StringRecord>())
+                        })
+                        .collect(),
+                    Err(err) => vec![Err(EvalError::InvalidDataset(err.to_string()))],
+                }
+            }
+            Err(err) => vec![Err(EvalError::InvalidDataset(err.to_string()))],
+        };
+        rows
+    });
+    Ok((headers, Box::new(records)))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet(path: &Path) -> Result<(Vec<String>, Records), EvalError> {
+    Err(EvalError::InvalidDataset(format!(
+        "{} needs the `parquet` feature",
+        path.display()
+    )))
+}
@@ -0,0 +1,417 @@
+//! Drift between two evaluations, e.g. before and after a plan change.
+//!
+//! Compares the row counts, violation rates and column profiles of two
+//! `metrics.json` reports. Worse violation rates, failed thresholds and lost
+//! tables are regressions; better rates are improvements; profile moves beyond
+//! the tolerances below are drift, left for the reviewer to judge.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::{ColumnStats, ConstraintSummary, MetricsReport};
+
+/// Absolute change of a column's null rate reported as drift.
+pub const NULL_RATE_TOLERANCE: f64 = 0.05;
+/// Absolute change of a column's distinct/rows ratio reported as drift.
+pub const CARDINALITY_TOLERANCE: f64 = 0.1;
+/// Relative change of a numeric column's mean or median reported as drift.
+pub const MEAN_SHIFT_TOLERANCE: f64 = 0.1;
+
+/// Differences between a `before` and an `after` evaluation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsDiff {
+    pub before_run_id: String,
+    pub after_run_id: String,
+    pub tables: Vec<TableDiff>,
+    /// Violation rates that changed.
+    pub rates: Vec<RateDiff>,
+    /// Column profile metrics that moved beyond the tolerances.
+    pub columns: Vec<ColumnDrift>,
+    pub changes: Vec<DriftChange>,
+}
+
+impl MetricsDiff {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .count()
+    }
+}
+
+/// Row counts of a table on each side (`None` when absent).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableDiff {
+    pub schema: String,
+    pub table: String,
+    pub rows_before: Option<u64>,
+    pub rows_after: Option<u64>,
+}
+
+/// Violations over rows, for a constraint kind (`not_null`, `pk`, ...) or a
+/// single CHECK (`schema.table.constraint`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateDiff {
+    pub path: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+/// A column profile metric (`null_rate`, `cardinality_ratio`, `mean`, `p50`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnDrift {
+    /// `schema.table.column`.
+    pub path: String,
+    pub metric: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Regression,
+    Improvement,
+    Drift,
+}
+
+/// One line of the diff summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftChange {
+    pub kind: ChangeKind,
+    pub path: String,
+    pub message: String,
+}
+
+/// Compare two evaluation reports of the same schema.
+pub fn diff_metrics(before: &MetricsReport, after: &MetricsReport) -> MetricsDiff {
+    let mut changes = Vec::new();
+    let tables = diff_tables(before, after, &mut changes);
+    let rates = diff_rates(before, after, &mut changes);
+    diff_thresholds(before, after, &mut changes);
+    diff_pii(before, after, &mut changes);
+    let columns = diff_columns(&before.column_stats, &after.column_stats, &mut changes);
+
+    MetricsDiff {
+        before_run_id: before.run_id.clone(),
+        after_run_id: after.run_id.clone(),
+        tables,
+        rates,
+        columns,
+        changes,
+    }
+}
+
+fn diff_tables(
+    before: &MetricsReport,
+    after: &MetricsReport,
+    changes: &mut Vec<DriftChange>,
+) -> Vec<TableDiff> {
+    let mut rows: BTreeMap<(String, String), (Option<u64>, Option<u64>)> = BTreeMap::new();
+    for table in &before.tables {
+        rows.entry((table.schema.clone(), table.table.clone()))
+            .or_default()
+            .0 = Some(table.rows_found);
+    }
+    for table in &after.tables {
+        rows.entry((table.schema.clone(), table.table.clone()))
+            .or_default()
+            .1 = Some(table.rows_found);
+    }
+
+    let mut tables = Vec::new();
+    for ((schema, table), (rows_before, rows_after)) in rows {
+        let path = format!("{schema}.{table}");
+        match (rows_before, rows_after) {
+            (Some(_), None) => changes.push(DriftChange {
+                kind: ChangeKind::Regression,
+                path: path.clone(),
+                message: "table missing from the new evaluation".to_string(),
+            }),
+            (None, Some(_)) => changes.push(DriftChange {
+                kind: ChangeKind::Drift,
+                path: path.clone(),
+                message: "table added".to_string(),
+            }),
+            (Some(old), Some(new)) if old != new => changes.push(DriftChange {
+                kind: ChangeKind::Drift,
+                path: path.clone(),
+                message: format!("rows {old} -> {new}"),
+            }),
+            _ => {}
+        }
+        tables.push(TableDiff {
+            schema,
+            table,
+            rows_before,
+            rows_after,
+        });
+    }
+    tables
+}
+
+fn diff_rates(
+    before: &MetricsReport,
+    after: &MetricsReport,
+    changes: &mut Vec<DriftChange>,
+) -> Vec<RateDiff> {
+    let mut before_rates = constraint_rates(before);
+    let mut after_rates = constraint_rates(after);
+    before_rates.extend(check_rates(before));
+    after_rates.extend(check_rates(after));
+
+    let mut paths: Vec<&String> = before_rates.keys().chain(after_rates.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut rates = Vec::new();
+    for path in paths {
+        let old = before_rates.get(path).copied().unwrap_or(0.0);
+        let new = after_rates.get(path).copied().unwrap_or(0.0);
+        if (new - old).abs() < f64::EPSILON {
+            continue;
+        }
+        let (kind, verb) = if new > old {
+            (ChangeKind::Regression, "up")
+        } else {
+            (ChangeKind::Improvement, "down")
+        };
+        changes.push(DriftChange {
+            kind,
+            path: path.clone(),
+            message: format!("violation rate {verb}: {old:.4} -> {new:.4}"),
+        });
+        rates.push(RateDiff {
+            path: path.clone(),
+            before: old,
+            after: new,
+        });
+    }
+    rates
+}
+
+/// Violations per evaluated row of each constraint kind.
+fn constraint_rates(metrics: &MetricsReport) -> BTreeMap<String, f64> {
+    let rows: u64 = metrics.tables.iter().map(|table| table.rows_found).sum();
+    let rate = |violations: u64| {
+        if rows == 0 {
+            0.0
+        } else {
+            violations as f64 / rows as f64
+        }
+    };
+    let ConstraintSummary {
+        not_null,
+        pk,
+        unique,
+        fk,
+        check,
+    } = &metrics.constraints;
+    [
+        ("not_null", not_null.violations),
+        ("pk", pk.violations),
+        ("unique", unique.violations),
+        ("fk", fk.violations),
+        ("check", check.violations),
+    ]
+    .into_iter()
+    .map(|(kind, violations)| (kind.to_string(), rate(violations)))
+    .collect()
+}
+
+fn check_rates(metrics: &MetricsReport) -> BTreeMap<String, f64> {
+    metrics
+        .constraints
+        .check
+        .constraints
+        .iter()
+        .filter(|check| check.rows > 0)
+        .map(|check| {
+            let name = check.name.as_deref().unwrap_or(&check.expression);
+            (
+                format!("{}.{}.{}", check.schema, check.table, name),
+                check.failures as f64 / check.rows as f64,
+            )
+        })
+        .collect()
+}
+
+fn diff_thresholds(before: &MetricsReport, after: &MetricsReport, changes: &mut Vec<DriftChange>) {
+    for result in &after.thresholds {
+        let previous = before
+            .thresholds
+            .iter()
+            .find(|old| old.path == result.path && old.metric == result.metric);
+        let passed_before = previous.map(|old| old.passed).unwrap_or(true);
+        let kind = match (passed_before, result.passed) {
+            (true, false) => ChangeKind::Regression,
+            (false, true) => ChangeKind::Improvement,
+            _ => continue,
+        };
+        changes.push(DriftChange {
+            kind,
+            path: result.path.clone(),
+            message: format!(
+                "{} {} ({:.4}, limit {})",
+                result.metric,
+                if result.passed {
+                    "now passes"
+                } else {
+                    "now fails"
+                },
+                result.actual,
+                result.limit
+            ),
+        });
+    }
+}
+
+fn diff_pii(before: &MetricsReport, after: &MetricsReport, changes: &mut Vec<DriftChange>) {
+    let count = |metrics: &MetricsReport| -> u64 {
+        metrics
+            .pii_findings
+            .iter()
+            .map(|finding| finding.count)
+            .sum()
+    };
+    let (old, new) = (count(before), count(after));
+    if new != old {
+        changes.push(DriftChange {
+            kind: if new > old {
+                ChangeKind::Regression
+            } else {
+                ChangeKind::Improvement
+            },
+            path: "pii_scan".to_string(),
+            message: format!("real-looking PII values {old} -> {new}"),
+        });
+    }
+}
+
+fn diff_columns(
+    before: &[ColumnStats],
+    after: &[ColumnStats],
+    changes: &mut Vec<DriftChange>,
+) -> Vec<ColumnDrift> {
+    let previous: BTreeMap<(&str, &str, &str), &ColumnStats> = before
+        .iter()
+        .map(|stats| {
+            (
+                (
+                    stats.schema.as_str(),
+                    stats.table.as_str(),
+                    stats.column.as_str(),
+                ),
+                stats,
+            )
+        })
+        .collect();
+
+    let mut drifts = Vec::new();
+    for new in after {
+        let Some(old) =
+            previous.get(&(new.schema.as_str(), new.table.as_str(), new.column.as_str()))
+        else {
+            continue;
+        };
+        let path = format!("{}.{}.{}", new.schema, new.table, new.column);
+        let mut metrics = vec![
+            (
+                "null_rate",
+                old.null_rate,
+                new.null_rate,
+                (new.null_rate - old.null_rate).abs() > NULL_RATE_TOLERANCE,
+            ),
+            (
+                "cardinality_ratio",
+                old.cardinality_ratio,
+                new.cardinality_ratio,
+                (new.cardinality_ratio - old.cardinality_ratio).abs() > CARDINALITY_TOLERANCE,
+            ),
+        ];
+        if let (Some(old_mean), Some(new_mean)) = (old.mean, new.mean) {
+            metrics.push(("mean", old_mean, new_mean, shifted(old_mean, new_mean)));
+        }
+        if let (Some(old_q), Some(new_q)) = (old.quantiles, new.quantiles) {
+            metrics.push(("p50", old_q.p50, new_q.p50, shifted(old_q.p50, new_q.p50)));
+        }
+
+        for (metric, old_value, new_value, moved) in metrics {
+            if !moved {
+                continue;
+            }
+            changes.push(DriftChange {
+                kind: ChangeKind::Drift,
+                path: path.clone(),
+                message: format!("{metric} {old_value:.4} -> {new_value:.4}"),
+            });
+            drifts.push(ColumnDrift {
+                path: path.clone(),
+                metric: metric.to_string(),
+                before: old_value,
+                after: new_value,
+            });
+        }
+    }
+    drifts
+}
+
+fn shifted(before: f64, after: f64) -> bool {
+    let scale = before.abs().max(f64::EPSILON);
+    (after - before).abs() / scale > MEAN_SHIFT_TOLERANCE
+}
+
+/// Render the diff as markdown, regressions first.
+pub fn render_diff(diff: &MetricsDiff) -> String {
+    let mut lines = Vec::new();
+    lines.push("# Datalchemy Evaluation Diff".to_string());
+    lines.push(String::new());
+    lines.push(format!("- before: {}", diff.before_run_id));
+    lines.push(format!("- after: {}", diff.after_run_id));
+    lines.push(format!(
+        "- {} regression(s), {} improvement(s), {} drift(s)",
+        diff.count(ChangeKind::Regression),
+        diff.count(ChangeKind::Improvement),
+        diff.count(ChangeKind::Drift)
+    ));
+    lines.push(String::new());
+
+    for (kind, title) in [
+        (ChangeKind::Regression, "## Regressions"),
+        (ChangeKind::Improvement, "## Improvements"),
+        (ChangeKind::Drift, "## Drift"),
+    ] {
+        let matching: Vec<&DriftChange> = diff
+            .changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        lines.push(title.to_string());
+        for change in matching {
+            lines.push(format!("- {}: {}", change.path, change.message));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push("## Row counts".to_string());
+    lines.push("| table | before | after |".to_string());
+    lines.push("| --- | --- | --- |".to_string());
+    let cell = |rows: Option<u64>| {
+        rows.map(|rows| rows.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    for table in &diff.tables {
+        lines.push(format!(
+            "| {}.{} | {} | {} |",
+            table.schema,
+            table.table,
+            cell(table.rows_before),
+            cell(table.rows_after)
+        ));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,115 @@
+//! Duplicate rows and business keys (see [`DuplicateStats`]).
+//!
+//! A row is a full duplicate when every column equals an earlier row; a
+//! business key is duplicated when the columns of a UNIQUE constraint repeat.
+//! Keys with a null column never repeat, as in SQL. Full duplicates are
+//! reported as `duplicate_row` violations; repeated business keys already are
+//! `unique` violations, so they only get counts and samples here.
+
+use std::collections::HashMap;
+
+use crate::metrics::{DuplicateGroup, DuplicateKind, DuplicateStats};
+use crate::model::{Violation, ViolationSample};
+
+/// Builds [`DuplicateStats`] from repeated values, given in any order.
+#[derive(Debug)]
+pub struct DuplicateCounter {
+    stats: DuplicateStats,
+    limit: usize,
+}
+
+impl DuplicateCounter {
+    /// `limit` bounds the sample groups and the rows kept per group.
+    pub fn new(
+        schema: &str,
+        table: &str,
+        kind: DuplicateKind,
+        columns: Vec<String>,
+        rows_checked: u64,
+        limit: usize,
+    ) -> Self {
+        Self {
+            stats: DuplicateStats {
+                schema: schema.to_string(),
+                table: table.to_string(),
+                kind,
+                columns,
+                rows_checked,
+                duplicate_rows: 0,
+                duplicate_groups: 0,
+                samples: Vec::new(),
+            },
+            limit,
+        }
+    }
+
+    /// Record `value`, held by `rows` (ascending, at least two).
+    pub fn add(&mut self, value: String, mut rows: Vec<u64>) {
+        let count = rows.len() as u64;
+        self.stats.duplicate_rows += count.saturating_sub(1);
+        self.stats.duplicate_groups += 1;
+        if self.limit == 0 {
+            return;
+        }
+        rows.truncate(self.limit.max(2));
+        self.stats
+            .samples
+            .push(DuplicateGroup { value, rows, count });
+        if self.stats.samples.len() >= self.limit * 2 {
+            self.truncate();
+        }
+    }
+
+    pub fn finish(mut self) -> DuplicateStats {
+        self.truncate();
+        self.stats
+    }
+
+    fn truncate(&mut self) {
+        self.stats
+            .samples
+            .sort_by_key(|group| group.rows.first().copied().unwrap_or(0));
+        self.stats.samples.truncate(self.limit);
+    }
+}
+
+/// Values of `keys` (row number, value) found at more than one row, with
+/// their rows in ascending order.
+pub fn repeated_values(
+    keys: impl IntoIterator<Item = (u64, String)>,
+) -> impl Iterator<Item = (String, Vec<u64>)> {
+    let mut rows: HashMap<String, Vec<u64>> = HashMap::new();
+    for (row, key) in keys {
+        rows.entry(key).or_default().push(row);
+    }
+    rows.into_iter().filter(|(_, rows)| rows.len() > 1)
+}
+
+/// `duplicate_row` violation of a table with fully repeated rows.
+pub fn duplicate_row_violation(stats: &DuplicateStats) -> Option<Violation> {
+    if stats.kind != DuplicateKind::Row || stats.duplicate_rows == 0 {
+        return None;
+    }
+    let samples: Vec<ViolationSample> = stats
+        .samples
+        .iter()
+        .filter_map(|group| {
+            Some(ViolationSample {
+                row_index: *group.rows.get(1)?,
+                key: Some(format!("same as row {}", group.rows[0])),
+                value: group.value.clone(),
+            })
+        })
+        .collect();
+    Some(Violation {
+        code: "duplicate_row".to_string(),
+        path: format!("{}.{}", stats.schema, stats.table),
+        message: format!(
+            "{} row(s) repeat an earlier row in every column ({} distinct row(s) repeated)",
+            stats.duplicate_rows, stats.duplicate_groups
+        ),
+        row_index: samples.first().map(|sample| sample.row_index),
+        example: None,
+        samples,
+    })
+}
@@ -0,0 +1,2476 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use datalchemy_core::{
+    CheckConstraint, ColumnType, Constraint, DatabaseSchema, EngineIssue, EngineObserver,
+    ForeignKey, IssueLevel, RunOutcome, RunSummary, Stage,
+};
+use datalchemy_generate::checks::{CheckContext, CheckOutcome, evaluate_check};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::model::GenerationReport;
+use datalchemy_generate::output::value_formats;
+use datalchemy_plan::{
+    ConstraintKind, ConstraintMode, PLAN_VERSION, Plan, Rule, SchemaRef, ValueFormats,
+};
+use uuid::Uuid;
+
+mod streaming;
+
+use crate::assertions::{AssertionTally, assertion_violation, assertion_warning, missing_table};
+use crate::compare::compare_column;
+use crate::correlation::correlate_columns;
+use crate::dataset::{DatasetDir, DatasetFile, Records, read_records};
+use crate::duplicates::{DuplicateCounter, duplicate_row_violation, repeated_values};
+use crate::errors::EvalError;
+use crate::fanout::{FanOutCounter, fan_out_warnings};
+use crate::metrics::{
+    AssertionResult, CheckConstraintStats, CheckResult, CheckStatus, ColumnCorrelation,
+    ColumnDistribution, ColumnStats, ConstraintStats, ConstraintSummary, DuplicateKind,
+    DuplicateStats, FanOutStats, METRICS_VERSION, MetricsPlanRef, MetricsReport, MetricsSchemaRef,
+    PerformanceMetrics, PiiFinding, PiiKind, PrivacyMetrics, RealismMetrics, SampledTable,
+    SamplingMetrics, TableMetrics, TablePerformance, TableRealism, TemporalCheck, ThresholdResult,
+    WarningItem,
+};
+use crate::model::{EvaluateOptions, EvaluationResult, Violation, ViolationSample};
+use crate::pii::{scan_column, unprotected_pii_columns};
+use crate::privacy::{PrivacySpec, privacy_metrics, privacy_warnings};
+use crate::profile::{cardinality_warnings, profile_column};
+use crate::realism::{RealismTally, SourceSample, realism_metrics};
+use crate::report::{render_junit, render_report};
+use crate::sampling::{Reservoir, estimate, sampling_notes, table_seed};
+use crate::temporal::{TemporalTally, temporal_rules};
+use crate::thresholds::{RelationRate, evaluate_thresholds};
+
+/// Evaluate datasets against schema + plan constraints.
+#[derive(Clone)]
+pub struct EvaluationEngine {
+    options: EvaluateOptions,
+    cancel: Option<Arc<AtomicBool>>,
+    observer: Option<Arc<dyn EngineObserver>>,
+}
+
+impl fmt::Debug for EvaluationEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvaluationEngine")
+            .field("options", &self.options)
+            .field("cancel", &self.cancel)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl EvaluationEngine {
+    pub fn new(options: EvaluateOptions) -> Self {
+        Self {
+            options,
+            cancel: None,
+            observer: None,
+        }
+    }
+
+    /// Report evaluated tables, rows read, warnings, violations and the end of
+    /// every run to `observer`, from the running thread.
+    pub fn with_observer(mut self, observer: Arc<dyn EngineObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn observe(&self, event: impl FnOnce(&dyn EngineObserver)) {
+        if let Some(observer) = &self.observer {
+            event(observer.as_ref());
+        }
+    }
+
+    /// Stop runs with [`EvalError::Cancelled`] once `cancel` is set; the flag
+    /// is checked between tables and, when streaming, between chunks.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn check_cancel(&self) -> Result<(), EvalError> {
+        match &self.cancel {
+            Some(cancel) if cancel.load(Ordering::Relaxed) => Err(EvalError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn run(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        dataset_dir: &Path,
+    ) -> Result<EvaluationResult, EvalError> {
+        let dataset = DatasetDir::open(dataset_dir, &self.options.file_patterns)?;
+        let schema_index = SchemaIndex::new(schema);
+        let target_tables = collect_target_tables(schema, plan, &schema_index)?;
+        self.evaluate(schema, plan, dataset_dir, &dataset, target_tables)
+    }
+
+    /// Evaluate any directory of CSV/Parquet files against `schema`, without a plan.
+    ///
+    /// Files map to tables through `file_patterns`; schema tables without a file
+    /// are not evaluated (FKs pointing to them are reported as missing parents).
+    pub fn run_dataset(
+        &self,
+        schema: &DatabaseSchema,
+        dataset_dir: &Path,
+    ) -> Result<EvaluationResult, EvalError> {
+        let dataset = DatasetDir::open(dataset_dir, &self.options.file_patterns)?;
+        let target_tables: BTreeSet<String> = schema
+            .schemas
+            .iter()
+            .flat_map(|db_schema| {
+                db_schema
+                    .tables
+                    .iter()
+                    .map(move |table| (db_schema.name.as_str(), table.name.as_str()))
+            })
+            .filter(|(schema_name, table_name)| {
+                dataset.table_file(schema_name, table_name).is_some()
+            })
+            .map(|(schema_name, table_name)| table_key(schema_name, table_name))
+            .collect();
+        if target_tables.is_empty() {
+            return Err(EvalError::InvalidDataset(format!(
+                "no file in {} matches a schema table",
+                dataset_dir.display()
+            )));
+        }
+
+        let plan = Plan {
+            plan_version: PLAN_VERSION.to_string(),
+            seed: 0,
+            schema_ref: SchemaRef {
+                schema_version: schema.schema_version.clone(),
+                schema_fingerprint: schema.schema_fingerprint.clone(),
+                engine: schema.engine.clone(),
+            },
+            global: None,
+            targets: Vec::new(),
+            rules: Vec::new(),
+            rules_unsupported: Vec::new(),
+            assertions: Vec::new(),
+            options: None,
+        };
+        self.evaluate(schema, &plan, dataset_dir, &dataset, target_tables)
+    }
+
+    fn evaluate(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        dataset_dir: &Path,
+        dataset: &DatasetDir,
+        target_tables: BTreeSet<String>,
+    ) -> Result<EvaluationResult, EvalError> {
+        let start = Instant::now();
+        let result = if self.options.streaming {
+            self.evaluate_streaming(schema, plan, dataset_dir, dataset, target_tables)
+        } else {
+            self.evaluate_in_memory(schema, plan, dataset_dir, dataset, target_tables)
+        };
+        // `finish` reports completed runs and strict-mode violations.
+        if let Err(err) = &result
+            && !matches!(err, EvalError::Violations(_))
+        {
+            let outcome = match err {
+                EvalError::Cancelled => RunOutcome::Cancelled,
+                _ => RunOutcome::Failed,
+            };
+            self.observe(|observer| {
+                observer.on_finish(&RunSummary {
+                    stage: Stage::Evaluate,
+                    run_id: "unknown".to_string(),
+                    outcome,
+                    tables: 0,
+                    rows: 0,
+                    warnings: 0,
+                    errors: 0,
+                    duration_ms: start.elapsed().as_millis() as u64,
+                })
+            });
+        }
+        result
+    }
+
+    fn evaluate_in_memory(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        dataset_dir: &Path,
+        dataset: &DatasetDir,
+        target_tables: BTreeSet<String>,
+    ) -> Result<EvaluationResult, EvalError> {
+        let total_start = Instant::now();
+        let load_start = Instant::now();
+
+        let generation_report = load_generation_report(dataset_dir);
+        let run_id = detect_run_id(dataset_dir, generation_report.as_ref())
+            .unwrap_or_else(|| "unknown".to_string());
+        let plan_index = PlanIndex::new(plan);
+        let schema_index = SchemaIndex::new(schema);
+        let formats = dataset_value_formats(dataset_dir).unwrap_or_else(|| value_formats(plan));
+
+        let mut warnings = Vec::new();
+        if self.options.pii_scan
+            && let Some(report) = &generation_report
+        {
+            warnings.extend(unprotected_pii_columns(&self.options.pii_columns, report));
+        }
+        let tables = load_tables(
+            &schema_index,
+            &target_tables,
+            dataset,
+            &formats,
+            &self.options,
+            plan.seed,
+            &mut warnings,
+        )?;
+
+        let reference_tables = match &self.options.reference_dir {
+            Some(reference_dir) => Some(load_reference_tables(
+                &schema_index,
+                &target_tables,
+                reference_dir,
+                &self.options,
+                plan.seed,
+                &mut warnings,
+            )?),
+            None => None,
+        };
+        let source_sample = load_source_sample(&self.options)?;
+
+        let load_ms = load_start.elapsed().as_millis();
+        let validate_start = Instant::now();
+
+        let mut violations = Vec::new();
+        let mut column_stats = Vec::new();
+        let mut correlations = Vec::new();
+        let mut pii_findings = Vec::new();
+        let mut relations = Vec::new();
+        let mut duplicates = Vec::new();
+        let mut fan_out = Vec::new();
+        let mut temporal = Vec::new();
+        let mut assertion_results = BTreeMap::new();
+        let mut realism_tables = Vec::new();
+        let mut constraint_summary = empty_constraint_summary();
+
+        let mut table_metrics = build_table_metrics(plan, &target_tables, &tables);
+        table_metrics.sort_by(|a, b| {
+            (a.schema.clone(), a.table.clone()).cmp(&(b.schema.clone(), b.table.clone()))
+        });
+
+        let mut table_keys: Vec<String> = tables.keys().cloned().collect();
+        table_keys.sort();
+
+        for table_key in &table_keys {
+            self.check_cancel()?;
+            let data = match tables.get(table_key) {
+                Some(data) => data,
+                None => continue,
+            };
+            self.observe(|observer| {
+                observer.on_table_start(Stage::Evaluate, &data.schema, &data.table);
+                observer.on_row_batch(Stage::Evaluate, &data.schema, &data.table, data.rows_found);
+            });
+
+            let table = schema_index
+                .table(&data.schema, &data.table)
+                .ok_or_else(|| {
+                    EvalError::InvalidDataset(format!(
+                        "table '{}.{}' not found in schema",
+                        data.schema, data.table
+                    ))
+                })?;
+
+            let stats_start = column_stats.len();
+            collect_column_stats(data, &mut column_stats);
+            warnings.extend(cardinality_warnings(
+                table,
+                data.rows.len() as u64,
+                &column_stats[stats_start..],
+            ));
+            let column_names: Vec<&str> =
+                data.columns.iter().map(|col| col.name.as_str()).collect();
+            correlations.extend(correlate_columns(
+                &data.schema,
+                &data.table,
+                &column_names,
+                &data.rows,
+            ));
+            if self.options.pii_scan {
+                pii_findings.extend(scan_table_pii(
+                    data,
+                    generation_report.as_ref(),
+                    &self.options.allowed_email_domains,
+                ));
+            }
+            relations.extend(evaluate_table_constraints(
+                table,
+                data,
+                &tables,
+                &plan_index,
+                &self.options,
+                &mut warnings,
+                &mut violations,
+                &mut constraint_summary,
+            ));
+            duplicates.extend(evaluate_duplicates(
+                table,
+                data,
+                self.options.max_orphan_samples,
+                &mut violations,
+            ));
+            // Children of a sampled table are a sample: counts per parent
+            // would be scaled down.
+            if self.options.sample_rows.is_none() {
+                fan_out.extend(evaluate_fan_out(table, data, &tables, plan));
+            }
+            if self.options.temporal_checks {
+                let mut tallies =
+                    temporal_tallies(table, data, plan, self.options.max_orphan_samples);
+                add_temporal_rows(&mut tallies, data, 0);
+                finish_temporal(tallies, &mut temporal, &mut violations);
+            }
+            let mut tallies = assertion_tallies(plan, data);
+            add_assertion_rows(&mut tallies, data);
+            finish_assertions(tallies, data.rows_found, &mut assertion_results);
+            if let Some(mut tally) = realism_tally(source_sample.as_ref(), data) {
+                add_realism_rows(&mut tally, data);
+                realism_tables.push(tally.finish(data.rows_found));
+            }
+        }
+        let assertions =
+            collect_assertions(plan, assertion_results, &mut warnings, &mut violations);
+        let realism = collect_realism(source_sample.as_ref(), realism_tables, &mut warnings);
+
+        let distributions = reference_tables
+            .map(|reference| compare_distributions(&tables, &reference))
+            .unwrap_or_default();
+        let privacy = evaluate_privacy(&self.options.privacy, &tables, &mut warnings);
+        let sampling = self.options.sample_rows.map(|sample_rows| {
+            sampling_metrics(
+                sample_rows,
+                &tables,
+                &constraint_summary,
+                &relations,
+                !privacy.is_empty(),
+            )
+        });
+
+        self.finish(
+            schema,
+            plan,
+            dataset_dir,
+            run_id,
+            total_start,
+            EvalFindings {
+                tables: table_metrics,
+                column_stats,
+                constraints: constraint_summary,
+                correlations,
+                distributions,
+                privacy,
+                pii_findings,
+                relations,
+                duplicates,
+                fan_out,
+                temporal,
+                assertions,
+                realism,
+                sampling,
+                table_performance: table_performance(generation_report.as_ref()),
+                warnings,
+                violations,
+                load_ms,
+                validate_start,
+            },
+        )
+    }
+
+    /// Threshold checks, metrics assembly and artifact writing shared by the
+    /// in-memory and streaming paths.
+    fn finish(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        dataset_dir: &Path,
+        run_id: String,
+        total_start: Instant,
+        findings: EvalFindings,
+    ) -> Result<EvaluationResult, EvalError> {
+        self.check_cancel()?;
+        let EvalFindings {
+            tables: table_metrics,
+            mut column_stats,
+            constraints: constraint_summary,
+            correlations,
+            distributions,
+            privacy,
+            pii_findings,
+            relations,
+            duplicates,
+            fan_out,
+            temporal,
+            assertions,
+            realism,
+            sampling,
+            table_performance,
+            mut warnings,
+            mut violations,
+            load_ms,
+            validate_start,
+        } = findings;
+
+        violations.extend(pii_findings.iter().map(pii_violation));
+        warnings.extend(fan_out_warnings(&fan_out));
+        let (thresholds, threshold_warnings) =
+            evaluate_thresholds(&self.options.thresholds, &column_stats, &relations);
+        warnings.extend(threshold_warnings);
+        violations.extend(
+            thresholds
+                .iter()
+                .filter(|result| !result.passed)
+                .map(threshold_violation),
+        );
+
+        sort_warnings(&mut warnings);
+        sort_violations(&mut violations);
+        self.observe(|observer| {
+            for warning in &warnings {
+                observer.on_issue(&EngineIssue {
+                    stage: Stage::Evaluate,
+                    level: IssueLevel::Warning,
+                    code: warning.code.clone(),
+                    path: Some(warning.path.clone()),
+                    message: warning.message.clone(),
+                });
+            }
+            for violation in &violations {
+                observer.on_issue(&EngineIssue {
+                    stage: Stage::Evaluate,
+                    level: IssueLevel::Error,
+                    code: violation.code.clone(),
+                    path: Some(violation.path.clone()),
+                    message: violation.message.clone(),
+                });
+            }
+        });
+        column_stats.sort_by(|a, b| {
+            (a.schema.clone(), a.table.clone(), a.column.clone()).cmp(&(
+                b.schema.clone(),
+                b.table.clone(),
+                b.column.clone(),
+            ))
+        });
+
+        let validate_ms = validate_start.elapsed().as_millis();
+        let total_ms = total_start.elapsed().as_millis();
+
+        let metrics = MetricsReport {
+            metrics_version: METRICS_VERSION.to_string(),
+            run_id: run_id.clone(),
+            schema_ref: MetricsSchemaRef {
+                schema_version: schema.schema_version.clone(),
+                schema_fingerprint: schema.schema_fingerprint.clone(),
+            },
+            plan_ref: MetricsPlanRef {
+                plan_version: plan.plan_version.clone(),
+                seed: plan.seed,
+                plan_hash: None,
+            },
+            tables: table_metrics,
+            column_stats,
+            constraints: constraint_summary,
+            correlations,
+            distributions,
+            privacy,
+            pii_findings,
+            thresholds,
+            sampling,
+            duplicates,
+            fan_out,
+            temporal,
+            assertions,
+            realism,
+            warnings: warnings.clone(),
+            performance: PerformanceMetrics {
+                load_ms,
+                validate_ms,
+                total_ms,
+                tables: table_performance,
+            },
+        };
+
+        let report = render_report(
+            &metrics,
+            &violations,
+            self.options.max_examples,
+            self.options.top_slowest,
+        );
+        let out_dir = self
+            .options
+            .out_dir
+            .clone()
+            .unwrap_or_else(|| dataset_dir.to_path_buf());
+        std::fs::create_dir_all(&out_dir)?;
+
+        let metrics_path = out_dir.join("metrics.json");
+        std::fs::write(&metrics_path, serde_json::to_vec_pretty(&metrics)?)?;
+
+        let report_path = out_dir.join("report.md");
+        std::fs::write(&report_path, report.as_bytes())?;
+
+        let violations_path = if self.options.write_violations {
+            let path = out_dir.join("violations.json");
+            std::fs::write(&path, serde_json::to_vec_pretty(&violations)?)?;
+            Some(path)
+        } else {
+            None
+        };
+
+        let junit_path = if self.options.write_junit {
+            let path = out_dir.join("junit.xml");
+            let junit = render_junit(&metrics, &violations, self.options.max_examples);
+            std::fs::write(&path, junit.as_bytes())?;
+            Some(path)
+        } else {
+            None
+        };
+
+        let failed = self.options.strict && !violations.is_empty();
+        self.observe(|observer| {
+            observer.on_finish(&RunSummary {
+                stage: Stage::Evaluate,
+                run_id: run_id.clone(),
+                outcome: if failed {
+                    RunOutcome::Failed
+                } else {
+                    RunOutcome::Completed
+                },
+                tables: metrics.tables.len() as u64,
+                rows: metrics.tables.iter().map(|table| table.rows_found).sum(),
+                warnings: warnings.len() as u64,
+                errors: violations.len() as u64,
+                duration_ms: total_ms as u64,
+            })
+        });
+        if failed {
+            return Err(EvalError::Violations(violations.len() as u64));
+        }
+
+        Ok(EvaluationResult {
+            run_dir: out_dir,
+            metrics_path,
+            report_path,
+            violations_path,
+            junit_path,
+            metrics,
+            report,
+            violations,
+        })
+    }
+}
+
+/// Everything an evaluation pass found, before thresholds and reporting.
+struct EvalFindings {
+    tables: Vec<TableMetrics>,
+    column_stats: Vec<ColumnStats>,
+    constraints: ConstraintSummary,
+    correlations: Vec<ColumnCorrelation>,
+    distributions: Vec<ColumnDistribution>,
+    privacy: Vec<PrivacyMetrics>,
+    pii_findings: Vec<PiiFinding>,
+    relations: Vec<RelationRate>,
+    duplicates: Vec<DuplicateStats>,
+    fan_out: Vec<FanOutStats>,
+    temporal: Vec<TemporalCheck>,
+    assertions: Vec<AssertionResult>,
+    realism: Option<RealismMetrics>,
+    sampling: Option<SamplingMetrics>,
+    table_performance: Vec<TablePerformance>,
+    warnings: Vec<WarningItem>,
+    violations: Vec<Violation>,
+    load_ms: u128,
+    validate_start: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct ColumnInfo {
+    name: String,
+    is_nullable: bool,
+    column_type: ColumnType,
+}
+
+#[derive(Debug, Clone)]
+struct TableData {
+    schema: String,
+    table: String,
+    columns: Vec<ColumnInfo>,
+    column_lookup: HashMap<String, usize>,
+    rows: Vec<Vec<GeneratedValue>>,
+    rows_found: u64,
+    null_counts: Vec<u64>,
+    missing_columns: Vec<String>,
+    /// Complete key sets of referenced columns when `rows` is a sample.
+    key_sets: HashMap<Vec<usize>, HashSet<String>>,
+}
+
+impl TableData {
+    fn column_index(&self, column: &str) -> Option<usize> {
+        self.column_lookup.get(&column.to_lowercase()).copied()
+    }
+
+    fn has_missing_column(&self, column: &str) -> bool {
+        let column = column.to_lowercase();
+        self.missing_columns
+            .iter()
+            .any(|name| name.to_lowercase() == column)
+    }
+}
+
+struct SchemaIndex<'a> {
+    tables: HashMap<String, &'a datalchemy_core::Table>,
+}
+
+impl<'a> SchemaIndex<'a> {
+    fn new(schema: &'a DatabaseSchema) -> Self {
+        let mut tables = HashMap::new();
+        for db_schema in &schema.schemas {
+            for table in &db_schema.tables {
+                tables.insert(table_key(&db_schema.name, &table.name), table);
+            }
+        }
+        Self { tables }
+    }
+
+    fn table(&self, schema: &str, table: &str) -> Option<&'a datalchemy_core::Table> {
+        self.tables.get(&table_key(schema, table)).copied()
+    }
+}
+
+struct PlanIndex {
+    constraint_policies: HashMap<String, ConstraintMode>,
+}
+
+impl PlanIndex {
+    fn new(plan: &Plan) -> Self {
+        let mut constraint_policies = HashMap::new();
+
+        for rule in &plan.rules {
+            if let Rule::ConstraintPolicy(rule) = rule {
+                let key = constraint_key(&rule.schema, &rule.table, rule.constraint.clone());
+                constraint_policies.insert(key, rule.mode.clone());
+            }
+        }
+
+        Self {
+            constraint_policies,
+        }
+    }
+
+    fn constraint_mode(&self, schema: &str, table: &str, kind: ConstraintKind) -> ConstraintMode {
+        self.constraint_policies
+            .get(&constraint_key(schema, table, kind))
+            .cloned()
+            .unwrap_or(ConstraintMode::Enforce)
+    }
+}
+
+fn empty_constraint_summary() -> ConstraintSummary {
+    ConstraintSummary {
+        not_null: ConstraintStats {
+            checked: 0,
+            violations: 0,
+        },
+        pk: ConstraintStats {
+            checked: 0,
+            violations: 0,
+        },
+        unique: ConstraintStats {
+            checked: 0,
+            violations: 0,
+        },
+        fk: ConstraintStats {
+            checked: 0,
+            violations: 0,
+        },
+        check: CheckConstraintStats {
+            checked: 0,
+            violations: 0,
+            not_evaluated: 0,
+            constraints: Vec::new(),
+        },
+    }
+}
+
+fn collect_target_tables(
+    schema: &DatabaseSchema,
+    plan: &Plan,
+    schema_index: &SchemaIndex<'_>,
+) -> Result<BTreeSet<String>, EvalError> {
+    let mut targets = BTreeSet::new();
+
+    if plan.targets.is_empty() {
+        for db_schema in &schema.schemas {
+            for table in &db_schema.tables {
+                targets.insert(table_key(&db_schema.name, &table.name));
+            }
+        }
+        return Ok(targets);
+    }
+
+    for target in &plan.targets {
+        let target_key = table_key(&target.schema, &target.table);
+        targets.insert(target_key.clone());
+        if let Some(table) = schema_index.table(&target.schema, &target.table) {
+            for constraint in &table.constraints {
+                if let Constraint::ForeignKey(fk) = constraint {
+                    targets.insert(table_key(&fk.referenced_schema, &fk.referenced_table));
+                }
+            }
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Load the target tables, or a sample of each with `options.sample_rows`
+/// (drawn with `seed`).
+fn load_tables(
+    schema_index: &SchemaIndex<'_>,
+    target_tables: &BTreeSet<String>,
+    dataset: &DatasetDir,
+    formats: &ValueFormats,
+    options: &EvaluateOptions,
+    seed: u64,
+    warnings: &mut Vec<WarningItem>,
+) -> Result<BTreeMap<String, TableData>, EvalError> {
+    let referenced = match options.sample_rows {
+        Some(_) => referenced_columns(schema_index, target_tables),
+        None => HashMap::new(),
+    };
+    let mut tables = BTreeMap::new();
+    for (table_key, mut reader) in
+        open_tables(schema_index, target_tables, dataset, formats, warnings)?
+    {
+        let data = match options.sample_rows {
+            Some(sample_rows) => reader.sample(
+                sample_rows,
+                table_seed(seed, &reader.schema, &reader.table),
+                referenced
+                    .get(&table_key)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                options,
+                warnings,
+            )?,
+            None => reader.next_chunk(usize::MAX, options, warnings)?,
+        };
+        tables.insert(table_key, data);
+    }
+    Ok(tables)
+}
+
+/// Column sets referenced by the FKs of the target tables, per parent table.
+fn referenced_columns(
+    schema_index: &SchemaIndex<'_>,
+    target_tables: &BTreeSet<String>,
+) -> HashMap<String, Vec<Vec<String>>> {
+    let mut referenced: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+    for key in target_tables {
+        let Ok((schema_name, table_name)) = split_table_key(key) else {
+            continue;
+        };
+        let Some(table) = schema_index.table(schema_name, table_name) else {
+            continue;
+        };
+        for constraint in &table.constraints {
+            if let Constraint::ForeignKey(fk) = constraint {
+                let columns = referenced
+                    .entry(table_key(&fk.referenced_schema, &fk.referenced_table))
+                    .or_default();
+                if !columns.contains(&fk.referenced_columns) {
+                    columns.push(fk.referenced_columns.clone());
+                }
+            }
+        }
+    }
+    referenced
+}
+
+/// Open a reader for each target table found in the schema and the dataset;
+/// values are parsed with `formats`.
+fn open_tables(
+    schema_index: &SchemaIndex<'_>,
+    target_tables: &BTreeSet<String>,
+    dataset: &DatasetDir,
+    formats: &ValueFormats,
+    warnings: &mut Vec<WarningItem>,
+) -> Result<BTreeMap<String, TableReader>, EvalError> {
+    let mut readers = BTreeMap::new();
+
+    for table_key in target_tables {
+        let (schema_name, table_name) = split_table_key(table_key)?;
+        let table = match schema_index.table(schema_name, table_name) {
+            Some(table) => table,
+            None => {
+                warnings.push(WarningItem {
+                    code: "missing_schema_table".to_string(),
+                    path: table_key.clone(),
+                    message: format!("table '{table_key}' not found in schema"),
+                    hint: Some("check plan targets against schema.json".to_string()),
+                });
+                continue;
+            }
+        };
+
+        let Some(file) = dataset.table_file(schema_name, table_name) else {
+            warnings.push(WarningItem {
+                code: "missing_table".to_string(),
+                path: table_key.clone(),
+                message: format!(
+                    "dataset file not found: {}",
+                    dataset.expected_path(schema_name, table_name).display()
+                ),
+                hint: Some("ensure generation produced the CSV file".to_string()),
+            });
+            continue;
+        };
+
+        let reader = TableReader::open(schema_name, table_name, table, &file, formats, warnings)?;
+        readers.insert(table_key.clone(), reader);
+    }
+
+    Ok(readers)
+}
+
+/// Load the target tables of the reference dataset; its warnings are prefixed
+/// with `reference:` and missing tables are skipped. Values are parsed with
+/// the formats of its own `resolved_plan.json`, canonical without one.
+fn load_reference_tables(
+    schema_index: &SchemaIndex<'_>,
+    target_tables: &BTreeSet<String>,
+    reference_dir: &Path,
+    options: &EvaluateOptions,
+    seed: u64,
+    warnings: &mut Vec<WarningItem>,
+) -> Result<BTreeMap<String, TableData>, EvalError> {
+    let reference = DatasetDir::open(reference_dir, &options.file_patterns)?;
+    let formats = dataset_value_formats(reference_dir).unwrap_or_default();
+    let options = EvaluateOptions {
+        strict: false,
+        ..options.clone()
+    };
+    let mut reference_warnings = Vec::new();
+    let tables = load_tables(
+        schema_index,
+        target_tables,
+        &reference,
+        &formats,
+        &options,
+        seed,
+        &mut reference_warnings,
+    )?;
+    warnings.extend(reference_warnings.into_iter().map(|mut warning| {
+        warning.path = format!("reference:{}", warning.path);
+        if warning.code == "missing_table" {
+            warning.hint = Some("table is not compared with the reference".to_string());
+        }
+        warning
+    }));
+    Ok(tables)
+}
+
+fn compare_distributions(
+    tables: &BTreeMap<String, TableData>,
+    reference: &BTreeMap<String, TableData>,
+) -> Vec<ColumnDistribution> {
+    let mut distributions = Vec::new();
+    for (table_key, data) in tables {
+        let Some(reference) = reference.get(table_key) else {
+            continue;
+        };
+        for (idx, column) in data.columns.iter().enumerate() {
+            let Some(reference_idx) = reference.column_index(&column.name) else {
+                continue;
+            };
+            if data.has_missing_column(&column.name) || reference.has_missing_column(&column.name) {
+                continue;
+            }
+            distributions.extend(compare_column(
+                &data.schema,
+                &data.table,
+                &column.name,
+                data.rows.iter().filter_map(|row| row.get(idx)),
+                reference
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(reference_idx)),
+            ));
+        }
+    }
+    distributions
+}
+
+/// Rows of a dataset file parsed against the table columns, read in chunks.
+struct TableReader {
+    schema: String,
+    table: String,
+    columns: Vec<ColumnInfo>,
+    column_lookup: HashMap<String, usize>,
+    /// Position of each column in the file records, `None` when missing.
+    column_positions: Vec<Option<usize>>,
+    missing_columns: Vec<String>,
+    records: Records,
+    formats: ValueFormats,
+    rows_read: u64,
+}
+
+impl TableReader {
+    fn open(
+        schema: &str,
+        table: &str,
+        table_def: &datalchemy_core::Table,
+        file: &DatasetFile,
+        formats: &ValueFormats,
+        warnings: &mut Vec<WarningItem>,
+    ) -> Result<Self, EvalError> {
+        let (headers, records) = read_records(file).map_err(|err| err.in_table(schema, table))?;
+        let header_map = headers
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.to_lowercase(), idx))
+            .collect::<HashMap<_, _>>();
+
+        let mut columns = table_def.columns.clone();
+        columns.sort_by_key(|col| col.ordinal_position);
+
+        let column_infos = columns
+            .iter()
+            .map(|col| ColumnInfo {
+                name: col.name.clone(),
+                is_nullable: col.is_nullable,
+                column_type: col.column_type.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut column_positions = Vec::with_capacity(column_infos.len());
+        let mut column_lookup = HashMap::new();
+        let mut missing_columns = Vec::new();
+        // Generated columns the engine cannot compute are left out on purpose.
+        let mut unexpected_missing = Vec::new();
+
+        for (idx, col) in column_infos.iter().enumerate() {
+            column_lookup.insert(col.name.to_lowercase(), idx);
+            match header_map.get(&col.name.to_lowercase()) {
+                Some(position) => column_positions.push(Some(*position)),
+                None => {
+                    column_positions.push(None);
+                    missing_columns.push(col.name.clone());
+                    if columns[idx].generated.is_none() {
+                        unexpected_missing.push(col.name.clone());
+                    }
+                }
+            }
+        }
+
+        let mut extra_columns = Vec::new();
+        for header in &headers {
+            if !column_lookup.contains_key(&header.to_lowercase()) {
+                extra_columns.push(header.clone());
+            }
+        }
+
+        if !unexpected_missing.is_empty() {
+            warnings.push(WarningItem {
+                code: "missing_columns".to_string(),
+                path: format!("{}.{}", schema, table),
+                message: format!("missing columns: {}", unexpected_missing.join(", ")),
+                hint: Some("regenerate dataset to include all columns".to_string()),
+            });
+        }
+
+        if !extra_columns.is_empty() {
+            warnings.push(WarningItem {
+                code: "extra_columns".to_string(),
+                path: format!("{}.{}", schema, table),
+                message: format!("unexpected columns: {}", extra_columns.join(", ")),
+                hint: Some("remove extra columns or update schema".to_string()),
+            });
+        }
+
+        Ok(Self {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            columns: column_infos,
+            column_lookup,
+            column_positions,
+            missing_columns,
+            records,
+            formats: formats.clone(),
+            rows_read: 0,
+        })
+    }
+
+    /// Table data without rows, carrying the column layout; streaming adds
+    /// row and null counts to it as chunks are read.
+    fn shell(&self) -> TableData {
+        TableData {
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            columns: self.columns.clone(),
+            column_lookup: self.column_lookup.clone(),
+            rows: Vec::new(),
+            rows_found: 0,
+            null_counts: vec![0; self.columns.len()],
+            missing_columns: self.missing_columns.clone(),
+            key_sets: HashMap::new(),
+        }
+    }
+
+    /// Uniform sample of at most `capacity` rows, read in one pass.
+    ///
+    /// Row and null counts cover the whole file, and the complete key sets of
+    /// `key_columns` are kept so FK checks against this table stay exact.
+    fn sample(
+        &mut self,
+        capacity: usize,
+        seed: u64,
+        key_columns: &[Vec<String>],
+        options: &EvaluateOptions,
+        warnings: &mut Vec<WarningItem>,
+    ) -> Result<TableData, EvalError> {
+        let mut sample = self.shell();
+        for columns in key_columns {
+            let indices = columns
+                .iter()
+                .map(|column| self.column_lookup.get(&column.to_lowercase()).copied())
+                .collect::<Option<Vec<_>>>();
+            if let Some(indices) = indices {
+                sample.key_sets.entry(indices).or_default();
+            }
+        }
+
+        let mut reservoir = Reservoir::new(capacity, seed);
+        loop {
+            let chunk = self.next_chunk(options.chunk_rows.max(1), options, warnings)?;
+            if chunk.rows.is_empty() {
+                break;
+            }
+            for (total, nulls) in sample.null_counts.iter_mut().zip(&chunk.null_counts) {
+                *total += nulls;
+            }
+            for row in chunk.rows {
+                for (indices, keys) in sample.key_sets.iter_mut() {
+                    let values = indices
+                        .iter()
+                        .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+                        .collect::<Vec<_>>();
+                    if !values.iter().any(|value| value.is_null()) {
+                        keys.insert(tuple_key(&values));
+                    }
+                }
+                reservoir.push(row);
+            }
+        }
+
+        sample.rows_found = reservoir.seen();
+        sample.rows = reservoir.into_items();
+        Ok(sample)
+    }
+
+    /// Up to `limit` next rows; the chunk is empty once the file is exhausted.
+    ///
+    /// Warnings and errors use row numbers of the whole file.
+    fn next_chunk(
+        &mut self,
+        limit: usize,
+        options: &EvaluateOptions,
+        warnings: &mut Vec<WarningItem>,
+    ) -> Result<TableData, EvalError> {
+        let (schema, table) = (&self.schema, &self.table);
+        let mut rows = Vec::new();
+        let mut null_counts = vec![0u64; self.columns.len()];
+        while rows.len() < limit {
+            let Some(result) = self.records.next() else {
+                break;
+            };
+            let record = result.map_err(|err| err.in_table(schema, table))?;
+            self.rows_read += 1;
+            let row_number = self.rows_read;
+            let mut row = Vec::with_capacity(self.columns.len());
+            for (col_idx, col) in self.columns.iter().enumerate() {
+                let value = match self.column_positions[col_idx] {
+                    Some(pos) => record.get(pos).unwrap_or_default(),
+                    None => "",
+                };
+
+                match parse_value(col, value, &self.formats) {
+                    Ok(parsed) => {
+                        if parsed.is_null() {
+                            null_counts[col_idx] += 1;
+                        }
+                        row.push(parsed);
+                    }
+                    Err(message) => {
+                        warnings.push(WarningItem {
+                            code: "invalid_value".to_string(),
+                            path: format!("{}.{}.{}:{}", schema, table, col.name, row_number),
+                            message,
+                            hint: Some("check CSV serialization for this column".to_string()),
+                        });
+                        if options.strict {
+                            return Err(EvalError::InvalidDataset(format!(
+                                "invalid value at row {row_number}"
+                            ))
+                            .in_table(schema, table)
+                            .at_column(&col.name));
+                        }
+                        null_counts[col_idx] += 1;
+                        row.push(GeneratedValue::Null);
+                    }
+                }
+            }
+            rows.push(row);
+        }
+
+        let mut chunk = self.shell();
+        chunk.rows_found = rows.len() as u64;
+        chunk.rows = rows;
+        chunk.null_counts = null_counts;
+        Ok(chunk)
+    }
+}
+
+fn collect_column_stats(table: &TableData, stats: &mut Vec<ColumnStats>) {
+    for (idx, col) in table.columns.iter().enumerate() {
+        stats.push(profile_column(
+            &table.schema,
+            &table.table,
+            &col.name,
+            table.rows.iter().filter_map(|row| row.get(idx)),
+        ));
+    }
+}
+
+fn scan_table_pii(
+    data: &TableData,
+    report: Option<&GenerationReport>,
+    allowed_domains: &[String],
+) -> Vec<PiiFinding> {
+    let mut findings = Vec::new();
+    for (idx, column) in data.columns.iter().enumerate() {
+        if data.has_missing_column(&column.name) {
+            continue;
+        }
+        let tags = report
+            .and_then(|report| {
+                report
+                    .column_sources
+                    .get(&format!("{}.{}.{}", data.schema, data.table, column.name))
+            })
+            .map(|source| source.pii_tags.as_slice())
+            .unwrap_or_default();
+        findings.extend(scan_column(
+            &data.schema,
+            &data.table,
+            &column.name,
+            tags,
+            allowed_domains,
+            data.rows.iter().filter_map(|row| row.get(idx)),
+        ));
+    }
+    findings
+}
+
+fn threshold_violation(result: &ThresholdResult) -> Violation {
+    Violation {
+        code: "threshold".to_string(),
+        path: result.path.clone(),
+        message: format!(
+            "{:.4} breaks {} = {}",
+            result.actual, result.metric, result.limit
+        ),
+        row_index: None,
+        example: None,
+        samples: Vec::new(),
+    }
+}
+
+fn pii_violation(finding: &PiiFinding) -> Violation {
+    let (code, message) = match finding.kind {
+        PiiKind::Email => (
+            "pii_email",
+            format!(
+                "{} values look like real e-mails (domain not allowed)",
+                finding.count
+            ),
+        ),
+        PiiKind::Cpf => (
+            "pii_cpf",
+            format!(
+                "{} values look like valid CPFs in a column not tagged pii.cpf",
+                finding.count
+            ),
+        ),
+    };
+    Violation {
+        code: code.to_string(),
+        path: format!("{}.{}.{}", finding.schema, finding.table, finding.column),
+        message,
+        row_index: Some(finding.first_row),
+        example: Some(finding.example.clone()),
+        samples: Vec::new(),
+    }
+}
+
+fn evaluate_privacy(
+    specs: &[PrivacySpec],
+    tables: &BTreeMap<String, TableData>,
+    warnings: &mut Vec<WarningItem>,
+) -> Vec<PrivacyMetrics> {
+    let mut metrics = Vec::new();
+    for spec in specs {
+        let path = format!("{}.{}", spec.schema, spec.table);
+        let Some(data) = tables.get(&table_key(&spec.schema, &spec.table)) else {
+            warnings.push(WarningItem {
+                code: "privacy_table_missing".to_string(),
+                path,
+                message: "privacy spec table not found in dataset".to_string(),
+                hint: Some("include the table in the evaluated dataset".to_string()),
+            });
+            continue;
+        };
+        // Columns absent from the file are hidden so the spec reports them.
+        let columns: Vec<&str> = data
+            .columns
+            .iter()
+            .map(|col| {
+                if data.has_missing_column(&col.name) {
+                    ""
+                } else {
+                    col.name.as_str()
+                }
+            })
+            .collect();
+        match privacy_metrics(spec, &columns, &data.rows) {
+            Ok(table_metrics) => {
+                warnings.extend(privacy_warnings(&table_metrics));
+                metrics.push(table_metrics);
+            }
+            Err(column) => warnings.push(WarningItem {
+                code: "privacy_column_missing".to_string(),
+                path: format!("{path}.{column}"),
+                message: "privacy spec column not found in dataset".to_string(),
+                hint: Some("check quasi_identifiers and sensitive column names".to_string()),
+            }),
+        }
+    }
+    metrics
+}
+
+#[allow(clippy::too_many_arguments)]
+fn evaluate_table_constraints(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    tables: &BTreeMap<String, TableData>,
+    plan_index: &PlanIndex,
+    options: &EvaluateOptions,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) -> Vec<RelationRate> {
+    evaluate_not_null(data, violations, summary);
+    evaluate_unique(table, data, warnings, violations, summary);
+    let relations = evaluate_foreign_keys(
+        table,
+        data,
+        tables,
+        options.max_orphan_samples,
+        warnings,
+        violations,
+        summary,
+    );
+    evaluate_checks(table, data, plan_index, warnings, violations, summary);
+    relations
+}
+
+fn evaluate_not_null(
+    data: &TableData,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) {
+    for (idx, col) in data.columns.iter().enumerate() {
+        if col.is_nullable || data.has_missing_column(&col.name) {
+            continue;
+        }
+        summary.not_null.checked += 1;
+        let nulls = data.null_counts[idx];
+        if nulls > 0 {
+            summary.not_null.violations += nulls;
+            violations.push(Violation {
+                code: "not_null".to_string(),
+                path: format!("{}.{}.{}", data.schema, data.table, col.name),
+                message: format!("{} null value(s) found", nulls),
+                row_index: None,
+                example: None,
+                samples: Vec::new(),
+            });
+        }
+    }
+}
+
+fn evaluate_unique(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) {
+    for constraint in &table.constraints {
+        match constraint {
+            Constraint::PrimaryKey(pk) => {
+                summary.pk.checked += 1;
+                let count = check_unique_constraint(
+                    "primary_key",
+                    &data.schema,
+                    &data.table,
+                    &pk.columns,
+                    data,
+                    warnings,
+                    violations,
+                );
+                summary.pk.violations += count;
+            }
+            Constraint::Unique(unique) => {
+                summary.unique.checked += 1;
+                let count = check_unique_constraint(
+                    "unique",
+                    &data.schema,
+                    &data.table,
+                    &unique.columns,
+                    data,
+                    warnings,
+                    violations,
+                );
+                summary.unique.violations += count;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_unique_constraint(
+    kind: &str,
+    schema: &str,
+    table: &str,
+    columns: &[String],
+    data: &TableData,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+) -> u64 {
+    let Some(indices) = key_indices(schema, table, columns, data, warnings) else {
+        return 0;
+    };
+
+    let mut seen = HashSet::new();
+    let mut violations_count = 0u64;
+
+    for (row_idx, row) in data.rows.iter().enumerate() {
+        let values = indices
+            .iter()
+            .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+            .collect::<Vec<_>>();
+        let has_null = values.iter().any(|value| value.is_null());
+        if has_null {
+            if kind == "primary_key" {
+                violations_count += 1;
+                violations.push(Violation {
+                    code: "primary_key".to_string(),
+                    path: format!("{}.{}.{}", schema, table, columns.join(",")),
+                    message: "null value in primary key".to_string(),
+                    row_index: Some(row_idx as u64 + 1),
+                    example: None,
+                    samples: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        let key = tuple_key(&values);
+        if !seen.insert(key.clone()) {
+            violations_count += 1;
+            violations.push(Violation {
+                code: kind.to_string(),
+                path: format!("{}.{}.{}", schema, table, columns.join(",")),
+                message: "duplicate key detected".to_string(),
+                row_index: Some(row_idx as u64 + 1),
+                example: Some(key),
+                samples: Vec::new(),
+            });
+        }
+    }
+
+    violations_count
+}
+
+/// Full-row duplicates and repeated business keys (UNIQUE constraints) of a
+/// table. Keys with a missing column are skipped; the unique check already
+/// warned about them.
+fn evaluate_duplicates(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    max_samples: usize,
+    violations: &mut Vec<Violation>,
+) -> Vec<DuplicateStats> {
+    let columns = data.columns.iter().map(|col| col.name.clone()).collect();
+    let mut rows = DuplicateCounter::new(
+        &data.schema,
+        &data.table,
+        DuplicateKind::Row,
+        columns,
+        data.rows.len() as u64,
+        max_samples,
+    );
+    let row_keys = data
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(row_idx, row)| (row_idx as u64 + 1, tuple_key(row)));
+    for (value, found) in repeated_values(row_keys) {
+        rows.add(value, found);
+    }
+    let rows = rows.finish();
+    violations.extend(duplicate_row_violation(&rows));
+    let mut stats = vec![rows];
+
+    for constraint in &table.constraints {
+        let Constraint::Unique(unique) = constraint else {
+            continue;
+        };
+        let indices = unique
+            .columns
+            .iter()
+            .map(|column| {
+                if data.has_missing_column(column) {
+                    None
+                } else {
+                    data.column_index(column)
+                }
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(indices) = indices else {
+            continue;
+        };
+        let keys: Vec<(u64, String)> = data
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(row_idx, row)| {
+                let values = indices
+                    .iter()
+                    .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+                    .collect::<Vec<_>>();
+                if values.iter().any(|value| value.is_null()) {
+                    return None;
+                }
+                Some((row_idx as u64 + 1, tuple_key(&values)))
+            })
+            .collect();
+        let mut counter = DuplicateCounter::new(
+            &data.schema,
+            &data.table,
+            DuplicateKind::BusinessKey,
+            unique.columns.clone(),
+            keys.len() as u64,
+            max_samples,
+        );
+        for (value, found) in repeated_values(keys) {
+            counter.add(value, found);
+        }
+        stats.push(counter.finish());
+    }
+    stats
+}
+
+/// Temporal rules of a table with the indices of their columns; rules on a
+/// missing column are skipped.
+fn temporal_tallies(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    plan: &Plan,
+    max_samples: usize,
+) -> Vec<(Vec<usize>, TemporalTally)> {
+    temporal_rules(&data.schema, table, plan)
+        .into_iter()
+        .filter_map(|rule| {
+            let indices = rule
+                .columns()
+                .into_iter()
+                .map(|column| {
+                    if data.has_missing_column(column) {
+                        None
+                    } else {
+                        data.column_index(column)
+                    }
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((
+                indices,
+                TemporalTally::new(&data.schema, &data.table, rule, max_samples),
+            ))
+        })
+        .collect()
+}
+
+/// Check the rows of `data`, numbered from `offset + 1`.
+fn add_temporal_rows(tallies: &mut [(Vec<usize>, TemporalTally)], data: &TableData, offset: u64) {
+    for (row_idx, row) in data.rows.iter().enumerate() {
+        for (indices, tally) in tallies.iter_mut() {
+            let values = indices
+                .iter()
+                .map(|idx| row.get(*idx))
+                .collect::<Option<Vec<_>>>();
+            if let Some(values) = values {
+                tally.add(offset + row_idx as u64 + 1, &values);
+            }
+        }
+    }
+}
+
+fn finish_temporal(
+    tallies: Vec<(Vec<usize>, TemporalTally)>,
+    checks: &mut Vec<TemporalCheck>,
+    violations: &mut Vec<Violation>,
+) {
+    for (_, tally) in tallies {
+        let (check, violation) = tally.finish();
+        checks.push(check);
+        violations.extend(violation);
+    }
+}
+
+/// Assertions of `data`'s table, with their index in the plan.
+fn assertion_tallies(plan: &Plan, data: &TableData) -> Vec<(usize, AssertionTally)> {
+    // Columns missing from the file cannot be aggregated.
+    let columns: Vec<&str> = data
+        .columns
+        .iter()
+        .map(|column| {
+            if data.has_missing_column(&column.name) {
+                ""
+            } else {
+                column.name.as_str()
+            }
+        })
+        .collect();
+    plan.assertions
+        .iter()
+        .enumerate()
+        .filter(|(_, assertion)| assertion.schema == data.schema && assertion.table == data.table)
+        .map(|(idx, assertion)| (idx, AssertionTally::new(assertion, &columns)))
+        .collect()
+}
+
+fn add_assertion_rows(tallies: &mut [(usize, AssertionTally)], data: &TableData) {
+    for row in &data.rows {
+        for (_, tally) in tallies.iter_mut() {
+            tally.add_row(row);
+        }
+    }
+}
+
+fn finish_assertions(
+    tallies: Vec<(usize, AssertionTally)>,
+    rows_found: u64,
+    results: &mut BTreeMap<usize, AssertionResult>,
+) {
+    for (idx, tally) in tallies {
+        results.insert(idx, tally.finish(rows_found));
+    }
+}
+
+/// Results in plan order; assertions of tables that were not read are errors.
+fn collect_assertions(
+    plan: &Plan,
+    mut results: BTreeMap<usize, AssertionResult>,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+) -> Vec<AssertionResult> {
+    let results: Vec<AssertionResult> = plan
+        .assertions
+        .iter()
+        .enumerate()
+        .map(|(idx, assertion)| {
+            results
+                .remove(&idx)
+                .unwrap_or_else(|| missing_table(assertion))
+        })
+        .collect();
+    warnings.extend(results.iter().filter_map(assertion_warning));
+    violations.extend(results.iter().filter_map(assertion_violation));
+    results
+}
+
+/// Read `options.source_sample`, when set.
+fn load_source_sample(options: &EvaluateOptions) -> Result<Option<SourceSample>, EvalError> {
+    let Some(path) = &options.source_sample else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// Category counts of `data`'s table, when the source sample has it.
+fn realism_tally(sample: Option<&SourceSample>, data: &TableData) -> Option<RealismTally> {
+    let source = sample?.table(&data.schema, &data.table)?;
+    // Columns missing from the file are not compared.
+    let columns: Vec<&str> = data
+        .columns
+        .iter()
+        .map(|column| {
+            if data.has_missing_column(&column.name) {
+                ""
+            } else {
+                column.name.as_str()
+            }
+        })
+        .collect();
+    Some(RealismTally::new(source, &columns))
+}
+
+fn add_realism_rows(tally: &mut RealismTally, data: &TableData) {
+    for row in &data.rows {
+        tally.add_row(row);
+    }
+}
+
+/// Realism metrics of the compared tables; a sample sharing no table with the
+/// dataset is a warning.
+fn collect_realism(
+    sample: Option<&SourceSample>,
+    tables: Vec<TableRealism>,
+    warnings: &mut Vec<WarningItem>,
+) -> Option<RealismMetrics> {
+    let sample = sample?;
+    let realism = realism_metrics(sample, tables);
+    if realism.is_none() {
+        warnings.push(WarningItem {
+            code: "realism_not_scored".to_string(),
+            path: "source_sample".to_string(),
+            message: "no table of the source sample is in the dataset".to_string(),
+            hint: Some("sample the tables of the plan targets".to_string()),
+        });
+    }
+    realism
+}
+
+/// Column indices of a key, or `None` (with a warning) when a column is missing.
+fn key_indices(
+    schema: &str,
+    table: &str,
+    columns: &[String],
+    data: &TableData,
+    warnings: &mut Vec<WarningItem>,
+) -> Option<Vec<usize>> {
+    let mut indices = Vec::new();
+    for column in columns {
+        if data.has_missing_column(column) {
+            warnings.push(WarningItem {
+                code: "missing_column".to_string(),
+                path: format!("{}.{}.{}", schema, table, column),
+                message: "column missing in dataset".to_string(),
+                hint: Some("regenerate dataset with full headers".to_string()),
+            });
+            return None;
+        }
+        indices.push(data.column_index(column)?);
+    }
+    Some(indices)
+}
+
+fn evaluate_foreign_keys(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    tables: &BTreeMap<String, TableData>,
+    max_samples: usize,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) -> Vec<RelationRate> {
+    let mut relations = Vec::new();
+    let primary_key = table
+        .constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            Constraint::PrimaryKey(pk) => Some(pk.columns.as_slice()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    for constraint in &table.constraints {
+        if let Constraint::ForeignKey(fk) = constraint {
+            summary.fk.checked += 1;
+            let count = check_foreign_key(
+                data,
+                fk,
+                primary_key,
+                max_samples,
+                tables,
+                warnings,
+                violations,
+            );
+            if let Some(orphans) = count {
+                summary.fk.violations += orphans;
+                relations.push(RelationRate {
+                    path: fk_path(data, fk),
+                    table: table_key(&data.schema, &data.table),
+                    rows: data.rows.len() as u64,
+                    orphans,
+                });
+            }
+        }
+    }
+    relations
+}
+
+fn check_foreign_key(
+    data: &TableData,
+    fk: &ForeignKey,
+    primary_key: &[String],
+    max_samples: usize,
+    tables: &BTreeMap<String, TableData>,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+) -> Option<u64> {
+    let (parent, child_indices, parent_indices) = fk_indices(data, fk, tables, warnings)?;
+
+    // A sampled parent keeps the keys of all its rows.
+    let sampled_keys = parent.key_sets.get(&parent_indices);
+    let mut parent_keys = HashSet::new();
+    if sampled_keys.is_none() {
+        for row in &parent.rows {
+            let values = parent_indices
+                .iter()
+                .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+                .collect::<Vec<_>>();
+            if values.iter().any(|value| value.is_null()) {
+                continue;
+            }
+            parent_keys.insert(tuple_key(&values));
+        }
+    }
+    let parent_keys = sampled_keys.unwrap_or(&parent_keys);
+
+    let mut violations_count = 0u64;
+    let mut samples = Vec::new();
+    for (row_idx, row) in data.rows.iter().enumerate() {
+        let values = child_indices
+            .iter()
+            .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+            .collect::<Vec<_>>();
+        if values.iter().any(|value| value.is_null()) {
+            continue;
+        }
+        let key = tuple_key(&values);
+        if !parent_keys.contains(&key) {
+            violations_count += 1;
+            if samples.len() < max_samples {
+                samples.push(ViolationSample {
+                    row_index: row_idx as u64 + 1,
+                    key: row_key(data, primary_key, row),
+                    value: key,
+                });
+            }
+        }
+    }
+
+    if violations_count > 0 {
+        violations.push(orphan_violation(data, fk, violations_count, samples));
+    }
+
+    Some(violations_count)
+}
+
+/// Children-per-parent distribution of each FK of `table` whose parent was read.
+fn evaluate_fan_out(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    tables: &BTreeMap<String, TableData>,
+    plan: &Plan,
+) -> Vec<FanOutStats> {
+    let target_rows = |schema: &str, table: &str| {
+        plan.targets
+            .iter()
+            .find(|target| target.schema == schema && target.table == table)
+            .map(|target| target.rows)
+    };
+    let mut stats = Vec::new();
+    for constraint in &table.constraints {
+        let Constraint::ForeignKey(fk) = constraint else {
+            continue;
+        };
+        // Unreadable relations are already warned about by the FK check.
+        let Some((parent, child_indices, parent_indices)) =
+            fk_indices(data, fk, tables, &mut Vec::new())
+        else {
+            continue;
+        };
+        let key = |row: &[GeneratedValue], indices: &[usize]| {
+            let values = indices
+                .iter()
+                .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+                .collect::<Vec<_>>();
+            (!values.iter().any(|value| value.is_null())).then(|| tuple_key(&values))
+        };
+        let mut counter = FanOutCounter::default();
+        for row in &parent.rows {
+            if let Some(key) = key(row, &parent_indices) {
+                counter.add_parent(key);
+            }
+        }
+        for row in &data.rows {
+            if let Some(key) = key(row, &child_indices) {
+                counter.add_child(&key);
+            }
+        }
+        let expected_mean = match (
+            target_rows(&data.schema, &data.table),
+            target_rows(&fk.referenced_schema, &fk.referenced_table),
+        ) {
+            (Some(children), Some(parents)) if parents > 0 => {
+                Some(children as f64 / parents as f64)
+            }
+            _ => None,
+        };
+        stats.extend(counter.finish(
+            table_key(&data.schema, &data.table),
+            fk.columns.clone(),
+            table_key(&fk.referenced_schema, &fk.referenced_table),
+            expected_mean,
+        ));
+    }
+    stats
+}
+
+/// One violation per relation: the count plus sample orphan rows.
+fn orphan_violation(
+    data: &TableData,
+    fk: &ForeignKey,
+    count: u64,
+    samples: Vec<ViolationSample>,
+) -> Violation {
+    Violation {
+        code: "foreign_key".to_string(),
+        path: fk_path(data, fk),
+        message: format!("{count} broken foreign key reference(s)"),
+        row_index: samples.first().map(|sample| sample.row_index),
+        example: samples.first().map(|sample| sample.value.clone()),
+        samples,
+    }
+}
+
+/// Parent table and column indices of `fk`, or `None` (with a warning) when
+/// the relation cannot be checked.
+fn fk_indices<'a>(
+    data: &TableData,
+    fk: &ForeignKey,
+    tables: &'a BTreeMap<String, TableData>,
+    warnings: &mut Vec<WarningItem>,
+) -> Option<(&'a TableData, Vec<usize>, Vec<usize>)> {
+    if fk.columns.len() != fk.referenced_columns.len() {
+        warnings.push(WarningItem {
+            code: "fk_mismatch".to_string(),
+            path: format!("{}.{}", data.schema, data.table),
+            message: "foreign key column count mismatch".to_string(),
+            hint: Some("check schema.json for FK definition".to_string()),
+        });
+        return None;
+    }
+
+    for column in &fk.columns {
+        if data.has_missing_column(column) {
+            warnings.push(WarningItem {
+                code: "missing_column".to_string(),
+                path: format!("{}.{}.{}", data.schema, data.table, column),
+                message: "column missing in dataset".to_string(),
+                hint: Some("regenerate dataset with full headers".to_string()),
+            });
+            return None;
+        }
+    }
+
+    let parent_key = table_key(&fk.referenced_schema, &fk.referenced_table);
+    let parent = match tables.get(&parent_key) {
+        Some(table) => table,
+        None => {
+            warnings.push(WarningItem {
+                code: "missing_parent_table".to_string(),
+                path: format!("{}.{}", data.schema, data.table),
+                message: format!("parent table '{}' not found in dataset", parent_key),
+                hint: Some("include parent tables in generation targets".to_string()),
+            });
+            return None;
+        }
+    };
+
+    let child_indices = fk
+        .columns
+        .iter()
+        .filter_map(|col| data.column_index(col))
+        .collect::<Vec<_>>();
+    let parent_indices = fk
+        .referenced_columns
+        .iter()
+        .filter_map(|col| parent.column_index(col))
+        .collect::<Vec<_>>();
+
+    if child_indices.len() != fk.columns.len()
+        || parent_indices.len() != fk.referenced_columns.len()
+    {
+        warnings.push(WarningItem {
+            code: "missing_parent_column".to_string(),
+            path: format!("{}.{}", data.schema, data.table),
+            message: "foreign key columns missing in dataset".to_string(),
+            hint: Some("check CSV headers for FK columns".to_string()),
+        });
+        return None;
+    }
+
+    Some((parent, child_indices, parent_indices))
+}
+
+fn fk_path(data: &TableData, fk: &ForeignKey) -> String {
+    format!(
+        "{}.{}({}) -> {}.{}",
+        data.schema,
+        data.table,
+        fk.columns.join(","),
+        fk.referenced_schema,
+        fk.referenced_table
+    )
+}
+
+fn evaluate_checks(
+    table: &datalchemy_core::Table,
+    data: &TableData,
+    plan_index: &PlanIndex,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) {
+    let checks = table
+        .constraints
+        .iter()
+        .filter_map(|constraint| {
+            if let Constraint::Check(check) = constraint {
+                Some(check)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    summary.check.checked += checks.len() as u64;
+    if checks.is_empty() {
+        return;
+    }
+
+    for check in checks {
+        let evaluation = evaluate_check_constraint(check, data, 0, warnings, violations);
+        record_check_outcome(
+            check,
+            data,
+            data.rows.len() as u64,
+            evaluation,
+            plan_index,
+            warnings,
+            violations,
+            summary,
+        );
+    }
+}
+
+/// Add the outcome of one CHECK over `rows` rows of `data`'s table to the summary.
+#[allow(clippy::too_many_arguments)]
+fn record_check_outcome(
+    check: &CheckConstraint,
+    data: &TableData,
+    rows: u64,
+    evaluation: CheckEvaluation,
+    plan_index: &PlanIndex,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) {
+    let mode = plan_index.constraint_mode(&data.schema, &data.table, ConstraintKind::Check);
+    let (status, failures) = match evaluation {
+        CheckEvaluation::Passed => (CheckStatus::Passed, 0),
+        CheckEvaluation::Failed(count) => (CheckStatus::Failed, count),
+        CheckEvaluation::Unsupported => (CheckStatus::Unsupported, 0),
+    };
+    summary.check.constraints.push(CheckResult {
+        schema: data.schema.clone(),
+        table: data.table.clone(),
+        name: check.name.clone(),
+        expression: check.expression.clone(),
+        status,
+        rows,
+        failures,
+    });
+    match evaluation {
+        CheckEvaluation::Passed => {}
+        CheckEvaluation::Failed(count) => {
+            summary.check.violations += count;
+        }
+        CheckEvaluation::Unsupported => {
+            summary.check.not_evaluated += 1;
+            match mode {
+                ConstraintMode::Enforce => {
+                    summary.check.violations += 1;
+                    violations.push(Violation {
+                        code: "check".to_string(),
+                        path: format!("{}.{}", data.schema, data.table),
+                        message: format!("unsupported check expression: {}", check.expression),
+                        row_index: None,
+                        example: None,
+                        samples: Vec::new(),
+                    });
+                }
+                ConstraintMode::Warn => {
+                    warnings.push(WarningItem {
+                        code: "check_unsupported".to_string(),
+                        path: format!("{}.{}", data.schema, data.table),
+                        message: format!("check expression not evaluated: {}", check.expression),
+                        hint: Some("simplify the CHECK or switch policy".to_string()),
+                    });
+                }
+                ConstraintMode::Ignore => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CheckEvaluation {
+    Passed,
+    Failed(u64),
+    Unsupported,
+}
+
+/// Evaluate `check` on the rows of `data`, numbered from `row_offset + 1`.
+fn evaluate_check_constraint(
+    check: &CheckConstraint,
+    data: &TableData,
+    row_offset: u64,
+    warnings: &mut Vec<WarningItem>,
+    violations: &mut Vec<Violation>,
+) -> CheckEvaluation {
+    if data.rows.is_empty() {
+        return CheckEvaluation::Passed;
+    }
+
+    let base_date = match NaiveDate::from_ymd_opt(2024, 1, 1) {
+        Some(date) => date,
+        None => {
+            warnings.push(WarningItem {
+                code: "base_date_error".to_string(),
+                path: format!("{}.{}", data.schema, data.table),
+                message: "invalid base date for CHECK evaluation".to_string(),
+                hint: Some("verify evaluator base date".to_string()),
+            });
+            return CheckEvaluation::Unsupported;
+        }
+    };
+    let mut failures = 0u64;
+
+    for (row_idx, row) in data.rows.iter().enumerate() {
+        let mut values = HashMap::with_capacity(data.columns.len());
+        for (col_idx, col) in data.columns.iter().enumerate() {
+            values.insert(
+                col.name.to_lowercase(),
+                row.get(col_idx).cloned().unwrap_or(GeneratedValue::Null),
+            );
+        }
+
+        let ctx = CheckContext {
+            values: &values,
+            base_date,
+        };
+        match evaluate_check(&check.expression, &ctx) {
+            CheckOutcome::Passed => {}
+            CheckOutcome::Failed => {
+                failures += 1;
+                violations.push(Violation {
+                    code: "check".to_string(),
+                    path: format!("{}.{}", data.schema, data.table),
+                    message: "check constraint failed".to_string(),
+                    row_index: Some(row_offset + row_idx as u64 + 1),
+                    example: Some(check.expression.clone()),
+                    samples: Vec::new(),
+                });
+            }
+            CheckOutcome::Unsupported => {
+                return CheckEvaluation::Unsupported;
+            }
+        }
+    }
+
+    if failures == 0 {
+        CheckEvaluation::Passed
+    } else {
+        CheckEvaluation::Failed(failures)
+    }
+}
+
+/// Sample sizes per table and CHECK / FK failures extrapolated to the full tables.
+fn sampling_metrics(
+    sample_rows: usize,
+    tables: &BTreeMap<String, TableData>,
+    summary: &ConstraintSummary,
+    relations: &[RelationRate],
+    has_privacy: bool,
+) -> SamplingMetrics {
+    let rows_total = |key: &str| tables.get(key).map(|data| data.rows_found).unwrap_or(0);
+    let mut estimates = Vec::new();
+    for check in &summary.check.constraints {
+        if matches!(check.status, CheckStatus::Unsupported) {
+            continue;
+        }
+        let name = check.name.as_deref().unwrap_or(&check.expression);
+        estimates.push(estimate(
+            &format!("{}.{}.{}", check.schema, check.table, name),
+            "check",
+            check.rows,
+            check.failures,
+            rows_total(&table_key(&check.schema, &check.table)),
+        ));
+    }
+    for relation in relations {
+        estimates.push(estimate(
+            &relation.path,
+            "foreign_key",
+            relation.rows,
+            relation.orphans,
+            rows_total(&relation.table),
+        ));
+    }
+
+    SamplingMetrics {
+        sample_rows,
+        tables: tables
+            .values()
+            .map(|data| SampledTable {
+                schema: data.schema.clone(),
+                table: data.table.clone(),
+                rows_total: data.rows_found,
+                rows_sampled: data.rows.len() as u64,
+            })
+            .collect(),
+        estimates,
+        notes: sampling_notes(has_privacy),
+    }
+}
+
+fn build_table_metrics(
+    plan: &Plan,
+    target_tables: &BTreeSet<String>,
+    tables: &BTreeMap<String, TableData>,
+) -> Vec<TableMetrics> {
+    let mut expected = HashMap::new();
+    for target in &plan.targets {
+        expected.insert(table_key(&target.schema, &target.table), target.rows);
+    }
+
+    let mut metrics = Vec::new();
+    for table_key in target_tables {
+        let (schema, table) = match split_table_key(table_key) {
+            Ok((schema, table)) => (schema.to_string(), table.to_string()),
+            Err(_) => continue,
+        };
+        let rows_found = tables
+            .get(table_key)
+            .map(|data| data.rows_found)
+            .unwrap_or(0);
+        let rows_expected = expected.get(table_key).copied();
+        metrics.push(TableMetrics {
+            schema,
+            table,
+            rows_found,
+            rows_expected,
+        });
+    }
+
+    metrics
+}
+
+/// Parse a dataset value of `column`; text written with the plan's
+/// `formats` is read back, and canonical text is always accepted.
+fn parse_value(
+    column: &ColumnInfo,
+    value: &str,
+    formats: &ValueFormats,
+) -> Result<GeneratedValue, String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("null") {
+        return Ok(GeneratedValue::Null);
+    }
+
+    let normalized_type = normalize_type(&column.column_type);
+    match normalized_type.as_str() {
+        "uuid" => Uuid::parse_str(trimmed)
+            .map(|value| GeneratedValue::Uuid(value.to_string()))
+            .map_err(|_| format!("invalid uuid '{}'", trimmed)),
+        "smallint" | "integer" | "bigint" => trimmed
+            .parse::<i64>()
+            .map(GeneratedValue::Int)
+            .map_err(|_| format!("invalid integer '{}'", trimmed)),
+        "numeric" | "decimal" => {
+            let decimal = decimal_text(trimmed, formats);
+            let scale = column.column_type.numeric_scale.unwrap_or(0);
+            if scale > 0 {
+                decimal
+                    .parse::<f64>()
+                    .map(GeneratedValue::Float)
+                    .map_err(|_| format!("invalid numeric '{}'", trimmed))
+            } else if let Ok(value) = decimal.parse::<i64>() {
+                Ok(GeneratedValue::Int(value))
+            } else {
+                decimal
+                    .parse::<f64>()
+                    .map(GeneratedValue::Float)
+                    .map_err(|_| format!("invalid numeric '{}'", trimmed))
+            }
+        }
+        "real" | "double precision" => decimal_text(trimmed, formats)
+            .parse::<f64>()
+            .map(GeneratedValue::Float)
+            .map_err(|_| format!("invalid float '{}'", trimmed)),
+        "boolean" => parse_bool(trimmed)
+            .map(GeneratedValue::Bool)
+            .ok_or_else(|| format!("invalid boolean '{}'", trimmed)),
+        "date" => parse_date(trimmed, formats)
+            .map(GeneratedValue::Date)
+            .ok_or_else(|| format!("invalid date '{}'", trimmed)),
+        "timestamp with time zone" | "timestamp without time zone" => {
+            parse_timestamp(trimmed, formats)
+                .map(GeneratedValue::Timestamp)
+                .ok_or_else(|| format!("invalid timestamp '{}'", trimmed))
+        }
+        "time with time zone" | "time without time zone" => parse_time(trimmed, formats)
+            .map(GeneratedValue::Time)
+            .ok_or_else(|| format!("invalid time '{}'", trimmed)),
+        _ => Ok(GeneratedValue::Text(trimmed.to_string())),
+    }
+}
+
+/// `value` with the plan's decimal separator replaced by `.`.
+fn decimal_text<'a>(value: &'a str, formats: &ValueFormats) -> std::borrow::Cow<'a, str> {
+    match formats.decimal_separator {
+        Some(separator) if separator != '.' => value.replace(separator, ".").into(),
+        _ => value.into(),
+    }
+}
+
+/// Dates in the plan's `date_format`, or ISO.
+pub(crate) fn parse_date(value: &str, formats: &ValueFormats) -> Option<NaiveDate> {
+    formats
+        .date_format
+        .iter()
+        .map(String::as_str)
+        .chain(["%Y-%m-%d"])
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+fn parse_time(value: &str, formats: &ValueFormats) -> Option<NaiveTime> {
+    formats
+        .time_format
+        .iter()
+        .map(String::as_str)
+        .chain(["%H:%M:%S%.f"])
+        .find_map(|format| NaiveTime::parse_from_str(value, format).ok())
+}
+
+/// Timestamps in the plan's `timestamp_format` (with or without its
+/// `timezone_suffix`), or ISO with `T` or space separator and optional
+/// fractional seconds (hand-made fixtures and Parquet exports use both).
+pub(crate) fn parse_timestamp(value: &str, formats: &ValueFormats) -> Option<NaiveDateTime> {
+    let value = formats
+        .timezone_suffix
+        .as_deref()
+        .and_then(|suffix| value.strip_suffix(suffix))
+        .unwrap_or(value);
+    formats
+        .timestamp_format
+        .iter()
+        .map(String::as_str)
+        .chain(["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"])
+        .find_map(|format| {
+            NaiveDateTime::parse_from_str(value, format)
+                .ok()
+                // Patterns without a time of day read as midnight.
+                .or_else(|| {
+                    NaiveDate::parse_from_str(value, format)
+                        .ok()
+                        .map(|date| date.and_time(NaiveTime::MIN))
+                })
+        })
+}
+
+/// Accepts `true`/`false`, `t`/`f` (the `t_f` boolean format) and `1`/`0`.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "t" | "1" => Some(true),
+        "false" | "f" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn normalize_type(column_type: &ColumnType) -> String {
+    column_type
+        .data_type
+        .split('(')
+        .next()
+        .unwrap_or(&column_type.data_type)
+        .trim()
+        .to_lowercase()
+}
+
+/// Value formats of the plan that generated a Datalchemy run directory,
+/// from its `resolved_plan.json`, if readable.
+fn dataset_value_formats(dataset_dir: &Path) -> Option<ValueFormats> {
+    let contents = std::fs::read_to_string(dataset_dir.join("resolved_plan.json")).ok()?;
+    let plan: Plan = serde_json::from_str(&contents).ok()?;
+    Some(value_formats(&plan))
+}
+
+/// `generation_report.json` of a Datalchemy run directory, if readable.
+fn load_generation_report(dataset_dir: &Path) -> Option<GenerationReport> {
+    let contents = std::fs::read_to_string(dataset_dir.join("generation_report.json")).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Per-table generation cost recorded by the run, slowest first.
+fn table_performance(report: Option<&GenerationReport>) -> Vec<TablePerformance> {
+    let Some(report) = report else {
+        return Vec::new();
+    };
+    report
+        .slowest_tables(report.tables.len())
+        .into_iter()
+        .map(|table| TablePerformance {
+            schema: table.schema.clone(),
+            table: table.table.clone(),
+            rows: table.rows_generated,
+            retries: table.retries,
+            duration_ms: table.duration_ms,
+            rows_per_sec: table.rows_per_sec,
+            retries_per_sec: table.retries_per_sec,
+            peak_memory_bytes: table.peak_memory_bytes,
+        })
+        .collect()
+}
+
+fn detect_run_id(dataset_dir: &Path, report: Option<&GenerationReport>) -> Option<String> {
+    if let Some(report) = report {
+        return Some(report.run_id.clone());
+    }
+
+    let name = dataset_dir.file_name()?.to_string_lossy();
+    if let Some((_, run_part)) = name.split_once("__run_") {
+        return Some(run_part.to_string());
+    }
+
+    None
+}
+
+/// `column=value` pairs of the row's primary key, if the table has one.
+fn row_key(data: &TableData, primary_key: &[String], row: &[GeneratedValue]) -> Option<String> {
+    if primary_key.is_empty() {
+        return None;
+    }
+    let pairs = primary_key
+        .iter()
+        .map(|column| {
+            let value = data
+                .column_index(column)
+                .and_then(|idx| row.get(idx))
+                .map(value_key)?;
+            Some(format!("{column}={value}"))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(pairs.join(","))
+}
+
+pub(crate) fn tuple_key(values: &[GeneratedValue]) -> String {
+    values
+        .iter()
+        .map(|value| escape_key_component(&value_key(value)))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+pub(crate) fn value_key(value: &GeneratedValue) -> String {
+    match value {
+        GeneratedValue::Null => "null".to_string(),
+        GeneratedValue::Bool(value) => value.to_string(),
+        GeneratedValue::Int(value) => value.to_string(),
+        GeneratedValue::Float(value) => value.to_string(),
+        GeneratedValue::Text(value) | GeneratedValue::Uuid(value) => value.clone(),
+        GeneratedValue::Date(value) => value.format("%Y-%m-%d").to_string(),
+        GeneratedValue::Time(value) => value.format("%H:%M:%S").to_string(),
+        GeneratedValue::Timestamp(value) => value.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+}
+
+fn escape_key_component(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn table_key(schema: &str, table: &str) -> String {
+    format!("{schema}.{table}")
+}
+
+fn split_table_key(table_key: &str) -> Result<(&str, &str), EvalError> {
+    table_key
+        .split_once('.')
+        .ok_or_else(|| EvalError::InvalidDataset(format!("invalid table key: {table_key}")))
+}
+
+fn constraint_key(schema: &str, table: &str, kind: ConstraintKind) -> String {
+    format!("{}.{}.{}", schema, table, constraint_kind_key(kind))
+}
+
+fn constraint_kind_key(kind: ConstraintKind) -> &'static str {
+    match kind {
+        ConstraintKind::Check => "check",
+        ConstraintKind::Unique => "unique",
+        ConstraintKind::NotNull => "not_null",
+        ConstraintKind::PrimaryKey => "primary_key",
+        ConstraintKind::ForeignKey => "foreign_key",
+    }
+}
+
+fn sort_warnings(warnings: &mut [WarningItem]) {
+    warnings.sort_by(|a, b| (&a.path, &a.code).cmp(&(&b.path, &b.code)));
+}
+
+fn sort_violations(violations: &mut [Violation]) {
+    violations.sort_by(|a, b| {
+        (
+            a.path.clone(),
+            a.code.clone(),
+            a.row_index.unwrap_or_default(),
+        )
+            .cmp(&(
+                b.path.clone(),
+                b.code.clone(),
+                b.row_index.unwrap_or_default(),
+            ))
+    });
+}
@@ -0,0 +1,738 @@
+//! Chunked evaluation for datasets larger than memory.
+//!
+//! Each table file is read once, `chunk_rows` rows at a time. NOT NULL,
+//! CHECK, temporal and PII checks, plan assertions and the realism category
+//! counts run per chunk (the distinct values of
+//! `count_distinct`/`distinct_ratio` assertions stay in memory); PK, UNIQUE and FK keys, and whole rows for the duplicate check,
+//! are spilled to disk ([`crate::spill`]) and resolved once every table has
+//! been read. Column profiles, correlations, distributions, fan-out and
+//! privacy metrics need whole columns in memory and are skipped.
+//!
+//! Metrics counts match the in-memory path. Row-level violations (duplicate
+//! keys, null primary keys, failed CHECKs) are capped at `max_examples` per
+//! constraint, keeping the lowest rows.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use datalchemy_core::{CheckConstraint, Constraint, DatabaseSchema, ForeignKey, Stage};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::output::value_formats;
+use datalchemy_plan::Plan;
+use uuid::Uuid;
+
+use super::{
+    CheckEvaluation, EvalFindings, EvaluationEngine, PlanIndex, SchemaIndex, TableData,
+    add_assertion_rows, add_realism_rows, add_temporal_rows, assertion_tallies,
+    build_table_metrics, collect_assertions, collect_realism, dataset_value_formats, detect_run_id,
+    empty_constraint_summary, evaluate_check_constraint, evaluate_not_null, finish_assertions,
+    finish_temporal, fk_indices, fk_path, key_indices, load_generation_report, load_source_sample,
+    open_tables, orphan_violation, realism_tally, record_check_outcome, row_key, scan_table_pii,
+    table_key, table_performance, temporal_tallies, tuple_key,
+};
+use crate::dataset::DatasetDir;
+use crate::duplicates::{DuplicateCounter, duplicate_row_violation};
+use crate::errors::EvalError;
+use crate::metrics::{ConstraintSummary, DuplicateKind, DuplicateStats, PiiFinding, WarningItem};
+use crate::model::{EvaluationResult, Violation, ViolationSample};
+use crate::pii::unprotected_pii_columns;
+use crate::spill::{KeySpill, SpilledKey, SpilledKeys};
+use crate::thresholds::RelationRate;
+
+impl EvaluationEngine {
+    pub(super) fn evaluate_streaming(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        dataset_dir: &Path,
+        dataset: &DatasetDir,
+        target_tables: BTreeSet<String>,
+    ) -> Result<EvaluationResult, EvalError> {
+        let total_start = Instant::now();
+        let spill_root = self
+            .options
+            .spill_dir
+            .clone()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(format!("datalchemy_spill_{}", Uuid::new_v4()));
+        let streamed = self.stream_tables(
+            schema,
+            plan,
+            dataset_dir,
+            dataset,
+            &target_tables,
+            &spill_root,
+        );
+        let _ = std::fs::remove_dir_all(&spill_root);
+        let (run_id, findings) = streamed?;
+        self.finish(schema, plan, dataset_dir, run_id, total_start, findings)
+    }
+
+    fn stream_tables(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        dataset_dir: &Path,
+        dataset: &DatasetDir,
+        target_tables: &BTreeSet<String>,
+        spill_root: &Path,
+    ) -> Result<(String, EvalFindings), EvalError> {
+        let load_start = Instant::now();
+        let generation_report = load_generation_report(dataset_dir);
+        let run_id = detect_run_id(dataset_dir, generation_report.as_ref())
+            .unwrap_or_else(|| "unknown".to_string());
+        let plan_index = PlanIndex::new(plan);
+        let schema_index = SchemaIndex::new(schema);
+        let formats = dataset_value_formats(dataset_dir).unwrap_or_else(|| value_formats(plan));
+        let options = &self.options;
+
+        let mut warnings = vec![WarningItem {
+            code: "streaming_partial_metrics".to_string(),
+            path: "dataset".to_string(),
+            message: "streaming evaluation skips column profiles, correlations, \
+                      distributions, fan-out and privacy metrics"
+                .to_string(),
+            hint: Some("evaluate a sample in memory for full metrics".to_string()),
+        }];
+        if options.sample_rows.is_some() {
+            warnings.push(WarningItem {
+                code: "sampling_ignored".to_string(),
+                path: "dataset".to_string(),
+                message: "sample_rows is ignored in streaming mode".to_string(),
+                hint: Some("drop streaming for a quick sampled evaluation".to_string()),
+            });
+        }
+        if options.pii_scan
+            && let Some(report) = &generation_report
+        {
+            warnings.extend(unprotected_pii_columns(&options.pii_columns, report));
+        }
+        let mut violations = Vec::new();
+        let mut summary = empty_constraint_summary();
+        let mut pii_findings = Vec::new();
+        let mut temporal = Vec::new();
+        let mut assertion_results = BTreeMap::new();
+        let mut realism_tables = Vec::new();
+        let source_sample = load_source_sample(options)?;
+
+        let mut readers = open_tables(
+            &schema_index,
+            target_tables,
+            dataset,
+            &formats,
+            &mut warnings,
+        )?;
+        let mut shells: BTreeMap<String, TableData> = readers
+            .iter()
+            .map(|(key, reader)| (key.clone(), reader.shell()))
+            .collect();
+        let mut spills = SpillSet::new(spill_root);
+        let mut table_spills = plan_spills(
+            &schema_index,
+            &shells,
+            &mut spills,
+            &mut warnings,
+            &mut summary,
+        )?;
+
+        for (key, reader) in readers.iter_mut() {
+            let (Some(shell), Some(spills)) = (shells.get_mut(key), table_spills.get_mut(key))
+            else {
+                continue;
+            };
+            let checks = schema_index
+                .table(&shell.schema, &shell.table)
+                .map(|table| {
+                    table
+                        .constraints
+                        .iter()
+                        .filter_map(|constraint| match constraint {
+                            Constraint::Check(check) => Some(check),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let mut check_states = vec![CheckState::new(); checks.len()];
+            let mut tallies = match schema_index.table(&shell.schema, &shell.table) {
+                Some(table) if options.temporal_checks => {
+                    temporal_tallies(table, shell, plan, options.max_orphan_samples)
+                }
+                _ => Vec::new(),
+            };
+            let mut assertions = assertion_tallies(plan, shell);
+            let mut realism = realism_tally(source_sample.as_ref(), shell);
+            self.observe(|observer| {
+                observer.on_table_start(Stage::Evaluate, &shell.schema, &shell.table)
+            });
+
+            loop {
+                self.check_cancel()?;
+                let chunk = reader.next_chunk(options.chunk_rows.max(1), options, &mut warnings)?;
+                if chunk.rows.is_empty() {
+                    break;
+                }
+                self.observe(|observer| {
+                    observer.on_row_batch(
+                        Stage::Evaluate,
+                        &shell.schema,
+                        &shell.table,
+                        chunk.rows_found,
+                    )
+                });
+                let offset = shell.rows_found;
+                shell.rows_found += chunk.rows_found;
+                for (total, nulls) in shell.null_counts.iter_mut().zip(&chunk.null_counts) {
+                    *total += nulls;
+                }
+
+                for (check, state) in checks.iter().zip(check_states.iter_mut()) {
+                    state.add_chunk(check, &chunk, offset, options.max_examples, &mut warnings);
+                }
+                if options.pii_scan {
+                    let findings = scan_table_pii(
+                        &chunk,
+                        generation_report.as_ref(),
+                        &options.allowed_email_domains,
+                    );
+                    merge_pii_findings(&mut pii_findings, findings, offset);
+                }
+                add_temporal_rows(&mut tallies, &chunk, offset);
+                add_assertion_rows(&mut assertions, &chunk);
+                if let Some(realism) = &mut realism {
+                    add_realism_rows(realism, &chunk);
+                }
+                spills.add_chunk(&chunk, offset, options.max_examples)?;
+            }
+            finish_temporal(tallies, &mut temporal, &mut violations);
+            finish_assertions(assertions, shell.rows_found, &mut assertion_results);
+            if let Some(realism) = realism {
+                realism_tables.push(realism.finish(shell.rows_found));
+            }
+
+            summary.check.checked += checks.len() as u64;
+            for (check, state) in checks.into_iter().zip(check_states) {
+                violations.extend(state.violations);
+                record_check_outcome(
+                    check,
+                    shell,
+                    shell.rows_found,
+                    state.evaluation,
+                    &plan_index,
+                    &mut warnings,
+                    &mut violations,
+                    &mut summary,
+                );
+            }
+        }
+        drop(readers);
+        let assertions =
+            collect_assertions(plan, assertion_results, &mut warnings, &mut violations);
+        let realism = collect_realism(source_sample.as_ref(), realism_tables, &mut warnings);
+
+        let load_ms = load_start.elapsed().as_millis();
+        let validate_start = Instant::now();
+
+        let mut parents = HashMap::new();
+        let mut children = Vec::new();
+        let mut duplicates = Vec::new();
+        for (key, spills) in table_spills {
+            for (parent_key, parent) in spills.parents {
+                parents.insert(parent_key, parent.spill.finish()?);
+            }
+            let Some(shell) = shells.get(&key) else {
+                continue;
+            };
+            duplicates.push(resolve_duplicate_rows(
+                shell,
+                spills.rows,
+                options.max_orphan_samples,
+                &mut violations,
+            )?);
+            for unique in spills.unique {
+                duplicates.extend(resolve_unique(
+                    shell,
+                    unique,
+                    options.max_examples,
+                    options.max_orphan_samples,
+                    &mut violations,
+                    &mut summary,
+                )?);
+            }
+            children.extend(spills.foreign.into_iter().map(|fk| (key.clone(), fk)));
+        }
+
+        let mut relations = Vec::new();
+        for (key, child) in children {
+            let (Some(shell), Some(parent)) = (shells.get(&key), parents.get(&child.parent)) else {
+                continue;
+            };
+            let orphans = resolve_foreign_key(
+                shell,
+                child,
+                parent,
+                options.max_orphan_samples,
+                &mut violations,
+            )?;
+            summary.fk.violations += orphans.orphans;
+            relations.push(orphans);
+        }
+
+        for shell in shells.values() {
+            evaluate_not_null(shell, &mut violations, &mut summary);
+        }
+
+        let mut tables = build_table_metrics(plan, target_tables, &shells);
+        tables.sort_by(|a, b| {
+            (a.schema.clone(), a.table.clone()).cmp(&(b.schema.clone(), b.table.clone()))
+        });
+
+        Ok((
+            run_id,
+            EvalFindings {
+                tables,
+                column_stats: Vec::new(),
+                constraints: summary,
+                correlations: Vec::new(),
+                distributions: Vec::new(),
+                privacy: Vec::new(),
+                pii_findings,
+                relations,
+                duplicates,
+                fan_out: Vec::new(),
+                temporal,
+                assertions,
+                realism,
+                sampling: None,
+                table_performance: table_performance(generation_report.as_ref()),
+                warnings,
+                violations,
+                load_ms,
+                validate_start,
+            },
+        ))
+    }
+}
+
+/// Directories for the key sets of one evaluation.
+struct SpillSet {
+    root: PathBuf,
+    next: usize,
+}
+
+impl SpillSet {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            next: 0,
+        }
+    }
+
+    fn create(&mut self) -> Result<KeySpill, EvalError> {
+        self.next += 1;
+        Ok(KeySpill::create(
+            &self.root.join(format!("{:04}", self.next)),
+        )?)
+    }
+}
+
+/// Key sets written while a table is read.
+struct TableSpills<'a> {
+    primary_key: &'a [String],
+    /// Whole rows, for the duplicate row check.
+    rows: KeySpill,
+    unique: Vec<UniqueSpill>,
+    foreign: Vec<ForeignKeySpill<'a>>,
+    /// Referenced column sets of FKs pointing at this table, by parent key.
+    parents: BTreeMap<String, ParentSpill>,
+}
+
+struct UniqueSpill {
+    kind: &'static str,
+    columns: Vec<String>,
+    indices: Vec<usize>,
+    spill: KeySpill,
+    null_rows: u64,
+    null_violations: Vec<Violation>,
+}
+
+struct ForeignKeySpill<'a> {
+    fk: &'a ForeignKey,
+    indices: Vec<usize>,
+    parent: String,
+    spill: KeySpill,
+}
+
+struct ParentSpill {
+    indices: Vec<usize>,
+    spill: KeySpill,
+}
+
+impl TableSpills<'_> {
+    fn add_chunk(
+        &mut self,
+        chunk: &TableData,
+        offset: u64,
+        max_examples: usize,
+    ) -> Result<(), EvalError> {
+        for (row_idx, row) in chunk.rows.iter().enumerate() {
+            let row_number = offset + row_idx as u64 + 1;
+            self.rows.insert(&tuple_key(row), row_number, None)?;
+            for unique in &mut self.unique {
+                let values = key_values(row, &unique.indices);
+                if values.iter().any(GeneratedValue::is_null) {
+                    if unique.kind == "primary_key" {
+                        unique.null_rows += 1;
+                        if unique.null_violations.len() < max_examples {
+                            unique.null_violations.push(Violation {
+                                code: "primary_key".to_string(),
+                                path: format!(
+                                    "{}.{}.{}",
+                                    chunk.schema,
+                                    chunk.table,
+                                    unique.columns.join(",")
+                                ),
+                                message: "null value in primary key".to_string(),
+                                row_index: Some(row_number),
+                                example: None,
+                                samples: Vec::new(),
+                            });
+                        }
+                    }
+                    continue;
+                }
+                unique.spill.insert(&tuple_key(&values), row_number, None)?;
+            }
+            for foreign in &mut self.foreign {
+                let values = key_values(row, &foreign.indices);
+                if values.iter().any(GeneratedValue::is_null) {
+                    continue;
+                }
+                let label = row_key(chunk, self.primary_key, row);
+                foreign
+                    .spill
+                    .insert(&tuple_key(&values), row_number, label.as_deref())?;
+            }
+            for parent in self.parents.values_mut() {
+                let values = key_values(row, &parent.indices);
+                if values.iter().any(GeneratedValue::is_null) {
+                    continue;
+                }
+                parent.spill.insert(&tuple_key(&values), row_number, None)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Create the key sets of every table, emitting the same warnings as the
+/// in-memory checks for keys that cannot be checked.
+fn plan_spills<'a>(
+    schema_index: &SchemaIndex<'a>,
+    shells: &BTreeMap<String, TableData>,
+    spills: &mut SpillSet,
+    warnings: &mut Vec<WarningItem>,
+    summary: &mut ConstraintSummary,
+) -> Result<BTreeMap<String, TableSpills<'a>>, EvalError> {
+    let mut table_spills = BTreeMap::new();
+    let mut parent_columns = BTreeMap::new();
+
+    for (key, shell) in shells {
+        let Some(table) = schema_index.table(&shell.schema, &shell.table) else {
+            continue;
+        };
+        let mut current = TableSpills {
+            primary_key: &[],
+            rows: spills.create()?,
+            unique: Vec::new(),
+            foreign: Vec::new(),
+            parents: BTreeMap::new(),
+        };
+        for constraint in &table.constraints {
+            let (kind, columns) = match constraint {
+                Constraint::PrimaryKey(pk) => {
+                    summary.pk.checked += 1;
+                    current.primary_key = pk.columns.as_slice();
+                    ("primary_key", &pk.columns)
+                }
+                Constraint::Unique(unique) => {
+                    summary.unique.checked += 1;
+                    ("unique", &unique.columns)
+                }
+                _ => continue,
+            };
+            if let Some(indices) =
+                key_indices(&shell.schema, &shell.table, columns, shell, warnings)
+            {
+                current.unique.push(UniqueSpill {
+                    kind,
+                    columns: columns.clone(),
+                    indices,
+                    spill: spills.create()?,
+                    null_rows: 0,
+                    null_violations: Vec::new(),
+                });
+            }
+        }
+        for constraint in &table.constraints {
+            let Constraint::ForeignKey(fk) = constraint else {
+                continue;
+            };
+            summary.fk.checked += 1;
+            let Some((parent, indices, parent_indices)) = fk_indices(shell, fk, shells, warnings)
+            else {
+                continue;
+            };
+            let parent_key = format!(
+                "{}|{}",
+                table_key(&parent.schema, &parent.table),
+                fk.referenced_columns.join(",")
+            );
+            parent_columns
+                .entry(table_key(&parent.schema, &parent.table))
+                .or_insert_with(BTreeMap::new)
+                .insert(parent_key.clone(), parent_indices);
+            current.foreign.push(ForeignKeySpill {
+                fk,
+                indices,
+                parent: parent_key,
+                spill: spills.create()?,
+            });
+        }
+        table_spills.insert(key.clone(), current);
+    }
+
+    for (key, columns) in parent_columns {
+        let Some(current) = table_spills.get_mut(&key) else {
+            continue;
+        };
+        for (parent_key, indices) in columns {
+            current.parents.insert(
+                parent_key,
+                ParentSpill {
+                    indices,
+                    spill: spills.create()?,
+                },
+            );
+        }
+    }
+
+    Ok(table_spills)
+}
+
+fn resolve_duplicate_rows(
+    shell: &TableData,
+    rows: KeySpill,
+    max_samples: usize,
+    violations: &mut Vec<Violation>,
+) -> Result<DuplicateStats, EvalError> {
+    let spilled = rows.finish()?;
+    let mut counter = DuplicateCounter::new(
+        &shell.schema,
+        &shell.table,
+        DuplicateKind::Row,
+        shell.columns.iter().map(|col| col.name.clone()).collect(),
+        spilled.len(),
+        max_samples,
+    );
+    spilled.for_each_duplicate(|duplicate| counter.add(duplicate.key, duplicate.rows))?;
+    let stats = counter.finish();
+    violations.extend(duplicate_row_violation(&stats));
+    Ok(stats)
+}
+
+/// Resolve a PK or UNIQUE key set; UNIQUE ones also yield their business
+/// key duplicate stats.
+fn resolve_unique(
+    shell: &TableData,
+    unique: UniqueSpill,
+    max_examples: usize,
+    max_samples: usize,
+    violations: &mut Vec<Violation>,
+    summary: &mut ConstraintSummary,
+) -> Result<Option<DuplicateStats>, EvalError> {
+    let spilled = unique.spill.finish()?;
+    let mut duplicates = LowestRows::new(max_examples);
+    let mut business_key = (unique.kind == "unique").then(|| {
+        DuplicateCounter::new(
+            &shell.schema,
+            &shell.table,
+            DuplicateKind::BusinessKey,
+            unique.columns.clone(),
+            spilled.len(),
+            max_samples,
+        )
+    });
+    spilled.for_each_duplicate(|duplicate| {
+        for row in &duplicate.rows[1..] {
+            duplicates.push(SpilledKey {
+                row: *row,
+                key: duplicate.key.clone(),
+                label: None,
+            });
+        }
+        if let Some(counter) = business_key.as_mut() {
+            counter.add(duplicate.key, duplicate.rows);
+        }
+    })?;
+
+    let count = duplicates.seen + unique.null_rows;
+    if unique.kind == "primary_key" {
+        summary.pk.violations += count;
+    } else {
+        summary.unique.violations += count;
+    }
+    violations.extend(unique.null_violations);
+    let path = format!(
+        "{}.{}.{}",
+        shell.schema,
+        shell.table,
+        unique.columns.join(",")
+    );
+    violations.extend(duplicates.into_sorted().into_iter().map(|entry| Violation {
+        code: unique.kind.to_string(),
+        path: path.clone(),
+        message: "duplicate key detected".to_string(),
+        row_index: Some(entry.row),
+        example: Some(entry.key),
+        samples: Vec::new(),
+    }));
+    Ok(business_key.map(DuplicateCounter::finish))
+}
+
+fn resolve_foreign_key(
+    shell: &TableData,
+    child: ForeignKeySpill<'_>,
+    parent: &SpilledKeys,
+    max_samples: usize,
+    violations: &mut Vec<Violation>,
+) -> Result<RelationRate, EvalError> {
+    let spilled = child.spill.finish()?;
+    let mut orphans = LowestRows::new(max_samples);
+    spilled.for_each_missing(parent, |entry| orphans.push(entry))?;
+
+    let count = orphans.seen;
+    if count > 0 {
+        let samples = orphans
+            .into_sorted()
+            .into_iter()
+            .map(|entry| ViolationSample {
+                row_index: entry.row,
+                key: entry.label,
+                value: entry.key,
+            })
+            .collect();
+        violations.push(orphan_violation(shell, child.fk, count, samples));
+    }
+    Ok(RelationRate {
+        path: fk_path(shell, child.fk),
+        table: table_key(&shell.schema, &shell.table),
+        rows: shell.rows_found,
+        orphans: count,
+    })
+}
+
+/// Outcome of one CHECK accumulated over chunks.
+#[derive(Clone)]
+struct CheckState {
+    evaluation: CheckEvaluation,
+    violations: Vec<Violation>,
+}
+
+impl CheckState {
+    fn new() -> Self {
+        Self {
+            evaluation: CheckEvaluation::Passed,
+            violations: Vec::new(),
+        }
+    }
+
+    fn add_chunk(
+        &mut self,
+        check: &CheckConstraint,
+        chunk: &TableData,
+        offset: u64,
+        max_examples: usize,
+        warnings: &mut Vec<WarningItem>,
+    ) {
+        if matches!(self.evaluation, CheckEvaluation::Unsupported) {
+            return;
+        }
+        let mut found = Vec::new();
+        let evaluation = evaluate_check_constraint(check, chunk, offset, warnings, &mut found);
+        let room = max_examples.saturating_sub(self.violations.len());
+        self.violations.extend(found.into_iter().take(room));
+        self.evaluation = match (self.evaluation, evaluation) {
+            (_, CheckEvaluation::Unsupported) => CheckEvaluation::Unsupported,
+            (CheckEvaluation::Failed(total), CheckEvaluation::Failed(count)) => {
+                CheckEvaluation::Failed(total + count)
+            }
+            (CheckEvaluation::Failed(total), _) | (_, CheckEvaluation::Failed(total)) => {
+                CheckEvaluation::Failed(total)
+            }
+            _ => CheckEvaluation::Passed,
+        };
+    }
+}
+
+/// Counts spilled entries and keeps the `limit` with the lowest rows.
+struct LowestRows {
+    limit: usize,
+    seen: u64,
+    entries: Vec<SpilledKey>,
+}
+
+impl LowestRows {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            seen: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, entry: SpilledKey) {
+        self.seen += 1;
+        if self.limit == 0 {
+            return;
+        }
+        self.entries.push(entry);
+        if self.entries.len() >= self.limit * 2 {
+            self.truncate();
+        }
+    }
+
+    fn into_sorted(mut self) -> Vec<SpilledKey> {
+        self.truncate();
+        self.entries
+    }
+
+    fn truncate(&mut self) {
+        self.entries.sort_by_key(|entry| entry.row);
+        self.entries.truncate(self.limit);
+    }
+}
+
+/// Add chunk findings (rows relative to the chunk) to the table totals.
+fn merge_pii_findings(totals: &mut Vec<PiiFinding>, findings: Vec<PiiFinding>, offset: u64) {
+    for mut finding in findings {
+        finding.first_row += offset;
+        match totals.iter_mut().find(|total| {
+            total.schema == finding.schema
+                && total.table == finding.table
+                && total.column == finding.column
+                && total.kind == finding.kind
+        }) {
+            Some(total) => total.count += finding.count,
+            None => totals.push(finding),
+        }
+    }
+}
+
+fn key_values(row: &[GeneratedValue], indices: &[usize]) -> Vec<GeneratedValue> {
+    indices
+        .iter()
+        .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+        .collect()
+}
@@ -1,16 +1,109 @@
+use datalchemy_generate::{ErrorContext, ErrorInfo};
 use thiserror::Error;
 
 /// Errors emitted by the evaluation engine.
+///
+/// Errors raised while a table file is read come wrapped in
+/// [`EvalError::Context`]; [`EvalError::kind`] looks through it.
 #[derive(Debug, Error)]
 pub enum EvalError {
     #[error("invalid dataset: {0}")]
     InvalidDataset(String),
     #[error("validation failed with {0} violation(s)")]
     Violations(u64),
+    #[error("evaluation cancelled")]
+    Cancelled,
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
     #[error("csv error: {0}")]
     Csv(#[from] csv::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[cfg(feature = "parquet")]
+    #[error("parquet error: {0}")]
+    Parquet(Box<parquet::errors::ParquetError>),
+    /// An error located at a table or column.
+    #[error("{}: {}", .0.context, .0.source)]
+    Context(Box<ContextError>),
+}
+
+/// [`EvalError`] with the table or column it was raised for.
+#[derive(Debug)]
+pub struct ContextError {
+    pub context: ErrorContext,
+    pub source: EvalError,
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for EvalError {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        Self::Parquet(Box::new(err))
+    }
+}
+
+impl EvalError {
+    /// Stable code of the error kind (`datalchemy explain <code>`).
+    pub fn code(&self) -> &'static str {
+        match self.kind() {
+            Self::Violations(_) => "DL106",
+            _ => "DL105",
+        }
+    }
+
+    /// The error without its table/column context.
+    pub fn kind(&self) -> &EvalError {
+        match self {
+            Self::Context(err) => err.source.kind(),
+            _ => self,
+        }
+    }
+
+    /// Table or column the error was raised for, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context(err) => Some(&err.context),
+            _ => None,
+        }
+    }
+
+    /// Locate the error at `schema.table`; a column set by
+    /// [`at_column`](Self::at_column) is kept. Cancellation and violations
+    /// are not located.
+    pub fn in_table(self, schema: &str, table: &str) -> Self {
+        self.locate(|context| {
+            context.schema = Some(schema.to_string());
+            context.table = Some(table.to_string());
+        })
+    }
+
+    /// Locate the error at `column` of the table being read.
+    pub fn at_column(self, column: &str) -> Self {
+        self.locate(|context| {
+            context.column.get_or_insert_with(|| column.to_string());
+        })
+    }
+
+    fn locate(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        match self {
+            Self::Cancelled | Self::Violations(_) => self,
+            Self::Context(mut err) => {
+                update(&mut err.context);
+                Self::Context(err)
+            }
+            source => {
+                let mut context = ErrorContext::default();
+                update(&mut context);
+                Self::Context(Box::new(ContextError { context, source }))
+            }
+        }
+    }
+
+    /// Code, message (without the location) and location of the error.
+    pub fn info(&self) -> ErrorInfo {
+        ErrorInfo {
+            code: self.code().to_string(),
+            message: self.kind().to_string(),
+            context: self.context().cloned().unwrap_or_default(),
+        }
+    }
 }
@@ -0,0 +1,125 @@
+//! Children-per-parent (fan-out) distribution of foreign keys.
+//!
+//! Every parent row counts, childless ones included; child rows with a null
+//! or orphan key do not. The plan has no per-relation cardinality, so the
+//! expected mean is the ratio of the child and parent target rows. Generated
+//! parents are picked round-robin, which spreads children evenly: a relation
+//! whose counts differ by at most one, with [`UNIFORM_MIN_MEAN`] or more
+//! children per parent on average, is reported as uniform.
+
+use std::collections::HashMap;
+
+use crate::metrics::{FanOutStats, WarningItem};
+
+/// Mean fan-out from which an even spread is reported as uniform.
+pub const UNIFORM_MIN_MEAN: f64 = 2.0;
+/// Relative gap between the measured and the expected mean that is reported.
+pub const MEAN_TOLERANCE: f64 = 0.1;
+
+/// Children per parent key of one relation.
+#[derive(Debug, Clone, Default)]
+pub struct FanOutCounter {
+    counts: HashMap<String, u64>,
+}
+
+impl FanOutCounter {
+    /// Count a parent row with key `key`.
+    pub fn add_parent(&mut self, key: String) {
+        self.counts.entry(key).or_insert(0);
+    }
+
+    /// Count a child row referencing `key`; orphans are ignored.
+    pub fn add_child(&mut self, key: &str) {
+        if let Some(count) = self.counts.get_mut(key) {
+            *count += 1;
+        }
+    }
+
+    /// Distribution of the counts; `None` without parents.
+    pub fn finish(
+        self,
+        table: String,
+        columns: Vec<String>,
+        parent: String,
+        expected_mean: Option<f64>,
+    ) -> Option<FanOutStats> {
+        let mut counts: Vec<u64> = self.counts.into_values().collect();
+        if counts.is_empty() {
+            return None;
+        }
+        counts.sort_unstable();
+        let children: u64 = counts.iter().sum();
+        let parents = counts.len() as u64;
+        let mean = children as f64 / parents as f64;
+        let (min, max) = (counts[0], counts[counts.len() - 1]);
+        Some(FanOutStats {
+            table,
+            columns,
+            parent,
+            parents,
+            children,
+            childless_parents: counts.iter().take_while(|count| **count == 0).count() as u64,
+            min,
+            median: nearest_rank(&counts, 0.5),
+            p95: nearest_rank(&counts, 0.95),
+            max,
+            mean,
+            expected_mean,
+            uniform: max - min <= 1 && mean >= UNIFORM_MIN_MEAN,
+        })
+    }
+}
+
+impl FanOutStats {
+    /// The measured mean is off the plan's by more than [`MEAN_TOLERANCE`].
+    pub fn off_expected(&self) -> bool {
+        self.expected_mean.is_some_and(|expected| {
+            expected > 0.0 && (self.mean - expected).abs() / expected > MEAN_TOLERANCE
+        })
+    }
+}
+
+/// `uniform_fan_out` and `fan_out_mismatch` warnings.
+pub fn fan_out_warnings(stats: &[FanOutStats]) -> Vec<WarningItem> {
+    let mut warnings = Vec::new();
+    for stats in stats {
+        let path = format!(
+            "{}({}) -> {}",
+            stats.table,
+            stats.columns.join(","),
+            stats.parent
+        );
+        if stats.uniform {
+            warnings.push(WarningItem {
+                code: "uniform_fan_out".to_string(),
+                path: path.clone(),
+                message: format!(
+                    "every {} row has {}-{} children (mean {:.2}); real relations are skewed",
+                    stats.parent, stats.min, stats.max, stats.mean
+                ),
+                hint: Some(
+                    "generate the FK column with a skewed generator if queries depend on it"
+                        .to_string(),
+                ),
+            });
+        }
+        if stats.off_expected() {
+            warnings.push(WarningItem {
+                code: "fan_out_mismatch".to_string(),
+                path,
+                message: format!(
+                    "{:.2} children per parent, the plan targets {:.2}",
+                    stats.mean,
+                    stats.expected_mean.unwrap_or_default()
+                ),
+                hint: Some("check null/orphan FK values and the target row counts".to_string()),
+            });
+        }
+    }
+    warnings
+}
+
+fn nearest_rank(sorted: &[u64], quantile: f64) -> u64 {
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -0,0 +1,99 @@
+//! Evaluation history of a workspace.
+//!
+//! Each finished evaluation is appended as one [`HistoryEntry`] per line
+//! (NDJSON). The line is the full `metrics.json` plus the ids needed to find
+//! the evaluation again, so old entries can be diffed with
+//! [`MetricsReport::diff`] or plotted over time. Entries carry
+//! [`HISTORY_VERSION`]; fields added to the metrics later default when old
+//! lines are read back.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::EvalError;
+use crate::metrics::MetricsReport;
+
+/// History line contract version.
+pub const HISTORY_VERSION: &str = "0.1";
+/// File name of the history inside a workspace's eval directory.
+pub const HISTORY_FILE: &str = "history.ndjson";
+
+/// One evaluation of the history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub history_version: String,
+    pub eval_id: String,
+    /// Generation run the dataset came from, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_id: Option<String>,
+    /// RFC 3339 timestamp of the end of the evaluation.
+    pub recorded_at: String,
+    pub metrics: MetricsReport,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        eval_id: impl Into<String>,
+        out_id: Option<String>,
+        recorded_at: impl Into<String>,
+        metrics: MetricsReport,
+    ) -> Self {
+        Self {
+            history_version: HISTORY_VERSION.to_string(),
+            eval_id: eval_id.into(),
+            out_id,
+            recorded_at: recorded_at.into(),
+            metrics,
+        }
+    }
+
+    /// Single-line JSON, without the trailing newline.
+    pub fn to_line(&self) -> Result<String, EvalError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_line(line: &str) -> Result<Self, EvalError> {
+        Ok(serde_json::from_str(line)?)
+    }
+}
+
+/// Entries of a history file, in file order; empty when it does not exist.
+pub fn read_history(path: &Path) -> Result<Vec<HistoryEntry>, EvalError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    std::fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(HistoryEntry::from_line)
+        .collect()
+}
+
+/// Append `entry` to a history file, creating it when missing.
+pub fn append_history(path: &Path, entry: &HistoryEntry) -> Result<(), EvalError> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry.to_line()?)?;
+    Ok(())
+}
+
+/// Union of two histories, e.g. after importing a workspace.
+///
+/// Entries are keyed by `eval_id` (`ours` wins on conflict) and ordered by
+/// `recorded_at`, then `eval_id`.
+pub fn merge_history(ours: Vec<HistoryEntry>, theirs: Vec<HistoryEntry>) -> Vec<HistoryEntry> {
+    let mut entries = BTreeMap::new();
+    for entry in theirs.into_iter().chain(ours) {
+        entries.insert(entry.eval_id.clone(), entry);
+    }
+    let mut merged: Vec<HistoryEntry> = entries.into_values().collect();
+    merged.sort_by(|a, b| {
+        a.recorded_at
+            .cmp(&b.recorded_at)
+            .then_with(|| a.eval_id.cmp(&b.eval_id))
+    });
+    merged
+}
@@ -1,20 +1,47 @@
 //! Evaluation helpers for schema and dataset metrics.
 
+pub mod assertions;
+pub mod compare;
+pub mod correlation;
+pub mod dataset;
+pub mod diff;
+pub mod duplicates;
 pub mod engine;
 pub mod errors;
+pub mod fanout;
+pub mod history;
 pub mod metrics;
 pub mod model;
+pub mod pii;
+pub mod privacy;
+pub mod profile;
+pub mod realism;
 pub mod report;
+pub mod sampling;
 pub mod schema_metrics;
+pub mod spill;
+pub mod temporal;
+pub mod thresholds;
 
+pub use diff::{ChangeKind, MetricsDiff, diff_metrics, render_diff};
 pub use engine::EvaluationEngine;
-pub use errors::EvalError;
+pub use errors::{ContextError, EvalError};
+pub use history::{HistoryEntry, append_history, merge_history, read_history};
 pub use metrics::{
-    CheckConstraintStats, ColumnStats, ConstraintStats, ConstraintSummary, METRICS_VERSION,
-    MetricsPlanRef, MetricsReport, MetricsSchemaRef, PerformanceMetrics, TableMetrics, WarningItem,
+    AggregateValue, AssertionResult, AssertionStatus, CategoryRealism, CheckConstraintStats,
+    CheckResult, CheckStatus, ColumnCorrelation, ColumnDistribution, ColumnStats, ConstraintStats,
+    ConstraintSummary, DistributionKind, DuplicateGroup, DuplicateKind, DuplicateStats,
+    FanOutStats, METRICS_VERSION, MetricsPlanRef, MetricsReport, MetricsSchemaRef,
+    PerformanceMetrics, PiiFinding, PiiKind, PrivacyMetrics, Quantiles, RealismMetrics,
+    SampleEstimate, SampledTable, SamplingMetrics, SensitiveDiversity, TableMetrics,
+    TablePerformance, TableRealism, TemporalCheck, TemporalSource, ThresholdResult, ValueCount,
+    WarningItem,
 };
-pub use model::{EvaluateOptions, EvaluationResult, Violation};
+pub use model::{EvaluateOptions, EvaluationResult, Violation, ViolationSample};
+pub use privacy::PrivacySpec;
+pub use realism::SourceSample;
 pub use schema_metrics::{
-    ConstraintCounts, CoverageMetrics, FkGraphMetrics, SchemaCounts, SchemaMetrics,
-    collect_schema_metrics,
+    ConstraintCounts, CoverageMetrics, FkGraphMetrics, ForeignKeyCoverage, SchemaCounts,
+    SchemaMetrics, TableWidth, collect_schema_metrics,
 };
+pub use thresholds::{ColumnThreshold, EvalThresholds};
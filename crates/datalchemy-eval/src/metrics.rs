@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::diff::{MetricsDiff, diff_metrics};
+
 /// Metrics contract version for dataset evaluation.
-pub const METRICS_VERSION: &str = "0.1";
+pub const METRICS_VERSION: &str = "0.2";
 
 /// Machine-readable metrics for a dataset evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,14 +13,54 @@ pub struct MetricsReport {
     pub schema_ref: MetricsSchemaRef,
     pub plan_ref: MetricsPlanRef,
     pub tables: Vec<TableMetrics>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub column_stats: Vec<ColumnStats>,
     pub constraints: ConstraintSummary,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// Pairwise correlations between the numeric columns of each table.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub correlations: Vec<ColumnCorrelation>,
+    /// Column distributions compared with the reference dataset, when one is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub distributions: Vec<ColumnDistribution>,
+    /// k-anonymity / l-diversity of the tables with a privacy spec.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub privacy: Vec<PrivacyMetrics>,
+    /// Real-looking PII found in the dataset values.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pii_findings: Vec<PiiFinding>,
+    /// Outcome of each configured threshold.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<ThresholdResult>,
+    /// Sample sizes and extrapolated failures, when only a sample was checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingMetrics>,
+    /// Fully repeated rows and repeated business keys, per table.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateStats>,
+    /// Children-per-parent distribution of each foreign key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fan_out: Vec<FanOutStats>,
+    /// Date/timestamp ordering and plan window checks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub temporal: Vec<TemporalCheck>,
+    /// Plan assertions, in plan order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<AssertionResult>,
+    /// Comparison with a source database sample, when one is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realism: Option<RealismMetrics>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<WarningItem>,
     pub performance: PerformanceMetrics,
 }
 
+impl MetricsReport {
+    /// Changes from this evaluation (before) to `after`; see [`diff_metrics`].
+    pub fn diff(&self, after: &MetricsReport) -> MetricsDiff {
+        diff_metrics(self, after)
+    }
+}
+
 /// Reference metadata for schema inputs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsSchemaRef {
@@ -46,13 +88,109 @@ pub struct TableMetrics {
     pub rows_expected: Option<u64>,
 }
 
-/// Optional per-column statistics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Per-column profile: nulls, cardinality, range and frequent values.
+///
+/// `mean`, `stddev` and `quantiles` are only set for numeric columns; `min`
+/// and `max` use the column type's ordering and are rendered as text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ColumnStats {
     pub schema: String,
     pub table: String,
     pub column: String,
     pub null_count: u64,
+    /// Share of null rows (0 for an empty table).
+    #[serde(default)]
+    pub null_rate: f64,
+    /// Distinct non-null values.
+    #[serde(default)]
+    pub distinct_count: u64,
+    /// `distinct_count` over the table rows (0 for an empty table).
+    #[serde(default)]
+    pub cardinality_ratio: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+    /// Population standard deviation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stddev: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantiles: Option<Quantiles>,
+    /// Most frequent non-null values, by count then value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_values: Vec<ValueCount>,
+}
+
+/// Quantiles of a numeric column (linear interpolation between ranks).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quantiles {
+    pub p05: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+
+/// Occurrences of a value in a column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Correlation between two numeric columns of a table, over the rows where
+/// both are non-null. Unset when fewer than two rows or a column is constant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnCorrelation {
+    pub schema: String,
+    pub table: String,
+    pub column_a: String,
+    pub column_b: String,
+    pub rows: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pearson: Option<f64>,
+    /// Pearson correlation of the ranks (ties share their average rank).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spearman: Option<f64>,
+}
+
+/// How a column is compared with the reference dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributionKind {
+    /// Numbers, compared with the two-sample Kolmogorov-Smirnov test.
+    Numeric,
+    /// Dates, times and timestamps, compared like numbers.
+    Temporal,
+    /// Text and booleans, compared with a chi-square test of homogeneity.
+    Categorical,
+}
+
+/// Comparison of one column against the same column of the reference dataset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnDistribution {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub kind: DistributionKind,
+    /// Non-null values compared on each side.
+    pub rows: u64,
+    pub reference_rows: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ks_statistic: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chi_square: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degrees_of_freedom: Option<u64>,
+    /// Probability of a difference at least this large if both samples come
+    /// from the same distribution.
+    pub p_value: f64,
+    /// Shared mass of the two normalized histograms (1 = identical).
+    pub histogram_overlap: f64,
+    /// `p_value` is below [`DIVERGENCE_ALPHA`](crate::compare::DIVERGENCE_ALPHA).
+    pub diverges: bool,
 }
 
 /// Summary of constraint validation outcomes.
@@ -78,6 +216,134 @@ pub struct CheckConstraintStats {
     pub checked: u64,
     pub violations: u64,
     pub not_evaluated: u64,
+    /// Outcome of each CHECK constraint of the evaluated tables.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<CheckResult>,
+}
+
+/// Outcome of a CHECK constraint over every row of its table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// The expression is outside the supported subset; rows were not verified.
+    Unsupported,
+}
+
+/// Per-constraint CHECK evaluation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub schema: String,
+    pub table: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub expression: String,
+    pub status: CheckStatus,
+    pub rows: u64,
+    /// Rows failing the expression (0 unless `status` is `failed`).
+    pub failures: u64,
+}
+
+/// k-anonymity of a table over its quasi-identifiers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyMetrics {
+    pub schema: String,
+    pub table: String,
+    pub quasi_identifiers: Vec<String>,
+    pub rows: u64,
+    pub equivalence_classes: u64,
+    /// Size of the smallest equivalence class (0 for an empty table).
+    pub k_anonymity: u64,
+    pub k_threshold: u64,
+    pub classes_below_k: u64,
+    pub rows_below_k: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub l_diversity: Vec<SensitiveDiversity>,
+}
+
+/// l-diversity of a sensitive column within the equivalence classes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitiveDiversity {
+    pub column: String,
+    /// Fewest distinct non-null values found in a class.
+    pub l_diversity: u64,
+    pub l_threshold: u64,
+    pub classes_below_l: u64,
+}
+
+/// Kind of real-looking PII matched by the scanner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    /// E-mail on a domain outside the allowed ones.
+    Email,
+    /// CPF with valid check digits in a column not tagged `pii.cpf`.
+    Cpf,
+}
+
+/// Values of a column matching a PII pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiFinding {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub kind: PiiKind,
+    pub count: u64,
+    /// 1-based data row of the first hit.
+    pub first_row: u64,
+    /// Masked first hit.
+    pub example: String,
+}
+
+/// A threshold checked against the measured value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdResult {
+    /// Column (`schema.table.column`) or foreign key the limit applies to.
+    pub path: String,
+    /// Threshold name, e.g. `max_null_rate`.
+    pub metric: String,
+    pub limit: f64,
+    pub actual: f64,
+    pub passed: bool,
+}
+
+/// Sampled evaluation: rows checked per table and extrapolated failures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamplingMetrics {
+    /// Max rows sampled per table.
+    pub sample_rows: usize,
+    pub tables: Vec<SampledTable>,
+    pub estimates: Vec<SampleEstimate>,
+    /// Caveats to keep in mind when reading sampled metrics.
+    pub notes: Vec<String>,
+}
+
+/// Rows of a table and how many of them were sampled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampledTable {
+    pub schema: String,
+    pub table: String,
+    pub rows_total: u64,
+    pub rows_sampled: u64,
+}
+
+/// Failures of a CHECK or FK in the sample, extrapolated to the whole table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleEstimate {
+    /// CHECK (`schema.table.constraint`) or FK path, as in violations.
+    pub path: String,
+    /// `check` or `foreign_key`.
+    pub metric: String,
+    pub rows_sampled: u64,
+    pub failures: u64,
+    pub rate: f64,
+    /// 95% interval of the failure rate.
+    pub rate_low: f64,
+    pub rate_high: f64,
+    pub estimated_failures: u64,
+    pub estimated_low: u64,
+    pub estimated_high: u64,
 }
 
 /// Structured warning entry.
@@ -90,10 +356,197 @@ pub struct WarningItem {
     pub hint: Option<String>,
 }
 
+/// What a duplicate check compares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Every column of the row.
+    Row,
+    /// The columns of a UNIQUE constraint.
+    BusinessKey,
+}
+
+/// Rows of a table repeating an earlier row on `columns`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateStats {
+    pub schema: String,
+    pub table: String,
+    pub kind: DuplicateKind,
+    pub columns: Vec<String>,
+    /// Rows compared; business keys skip rows with a null key column.
+    pub rows_checked: u64,
+    /// Repeats, not counting the first occurrence of each value.
+    pub duplicate_rows: u64,
+    /// Distinct values found more than once.
+    pub duplicate_groups: u64,
+    /// Groups with the lowest first rows.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<DuplicateGroup>,
+}
+
+/// One repeated value and the rows holding it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub value: String,
+    /// Rows holding the value, ascending; the first is the original.
+    pub rows: Vec<u64>,
+    /// All rows holding the value (`rows` may be truncated).
+    pub count: u64,
+}
+
+/// Where a temporal rule was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporalSource {
+    /// Column name pair such as `created_at`/`updated_at`.
+    ColumnNames,
+    /// `derive.*_after_*` generator rule of the plan.
+    PlanDerive,
+    /// `min`/`max` params of a date or timestamp generator rule.
+    PlanWindow,
+}
+
+/// Outcome of one temporal rule over a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporalCheck {
+    pub schema: String,
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Rule as text, e.g. `updated_at >= created_at`.
+    pub rule: String,
+    pub source: TemporalSource,
+    /// Rows with non-null values in every column of the rule.
+    pub rows_checked: u64,
+    pub failures: u64,
+}
+
+/// Outcome of a plan assertion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssertionStatus {
+    Passed,
+    Failed,
+    /// The expression could not be evaluated (parse error, missing table or
+    /// column, non-numeric values).
+    Error,
+}
+
+/// Plan assertion evaluated against the dataset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionResult {
+    pub name: String,
+    pub schema: String,
+    pub table: String,
+    pub expr: String,
+    pub status: AssertionStatus,
+    /// Measured aggregates, e.g. `avg(valor_total)`; `None` when undefined.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<AggregateValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// One aggregate measured for an assertion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateValue {
+    pub aggregate: String,
+    pub value: Option<f64>,
+}
+
+/// Children per parent row of one foreign key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutStats {
+    /// `schema.table` of the child table.
+    pub table: String,
+    pub columns: Vec<String>,
+    /// `schema.table` of the parent table.
+    pub parent: String,
+    pub parents: u64,
+    /// Child rows referencing an existing parent.
+    pub children: u64,
+    pub childless_parents: u64,
+    pub min: u64,
+    pub median: u64,
+    pub p95: u64,
+    pub max: u64,
+    pub mean: f64,
+    /// Child target rows / parent target rows of the plan.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_mean: Option<f64>,
+    /// Every parent has the same number of children, give or take one.
+    pub uniform: bool,
+}
+
+/// Comparison of the dataset with a sample of the source database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealismMetrics {
+    /// Mean of `row_share_similarity` and every column similarity (1 =
+    /// identical); `None` when nothing could be compared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    /// Similarity of each table's share of the rows, with two or more tables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_share_similarity: Option<f64>,
+    /// Source connection, with secrets redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<String>,
+    /// Rows sampled per source table.
+    pub sample_rows: u64,
+    pub tables: Vec<TableRealism>,
+}
+
+/// One table compared with its source sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRealism {
+    pub schema: String,
+    pub table: String,
+    pub source_rows: u64,
+    pub rows: u64,
+    /// `rows / source_rows`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<CategoryRealism>,
+    /// Columns not read from the source because their name implies PII.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted_columns: Vec<String>,
+}
+
+/// Category frequencies of a column compared with the source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryRealism {
+    pub column: String,
+    /// Categories in the source sample.
+    pub categories: u64,
+    /// 1 minus the total variation distance of the frequencies.
+    pub similarity: f64,
+    /// Source categories absent from the dataset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_categories: Vec<String>,
+    /// Dataset values outside the source categories.
+    pub unseen_rows: u64,
+}
+
 /// Performance timings for the evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
     pub load_ms: u128,
     pub validate_ms: u128,
     pub total_ms: u128,
+    /// Generation cost per table, slowest first, from `generation_report.json`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<TablePerformance>,
+}
+
+/// How long a table took to generate and how much memory its rows held.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TablePerformance {
+    pub schema: String,
+    pub table: String,
+    pub rows: u64,
+    pub retries: u64,
+    pub duration_ms: u64,
+    pub rows_per_sec: f64,
+    pub retries_per_sec: f64,
+    pub peak_memory_bytes: u64,
 }
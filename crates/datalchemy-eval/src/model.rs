@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
 use crate::metrics::MetricsReport;
+use crate::privacy::PrivacySpec;
+use crate::thresholds::EvalThresholds;
 
 /// Options for dataset evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,11 +14,76 @@ pub struct EvaluateOptions {
     pub strict: bool,
     /// Limit the number of examples emitted in the report.
     pub max_examples: usize,
+    /// Offending rows kept per foreign key relation in its violation, and
+    /// repeated values kept per duplicate check.
+    #[serde(default = "default_max_orphan_samples")]
+    pub max_orphan_samples: usize,
     /// Emit violations.json with the full list of violations.
     pub write_violations: bool,
+    /// Emit junit.xml with one test case per check, for CI gates.
+    #[serde(default)]
+    pub write_junit: bool,
     /// Optional output directory override.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub out_dir: Option<PathBuf>,
+    /// File name patterns mapping tables to dataset files, with `{schema}` and
+    /// `{table}` placeholders. Empty uses [`DEFAULT_FILE_PATTERNS`].
+    ///
+    /// [`DEFAULT_FILE_PATTERNS`]: crate::dataset::DEFAULT_FILE_PATTERNS
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_patterns: Vec<String>,
+    /// Reference dataset (real sample or previous output) whose column
+    /// distributions are compared with the evaluated one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_dir: Option<PathBuf>,
+    /// `source_sample.json` read from the source database (see
+    /// [`SourceSample`](crate::realism::SourceSample)), compared with the
+    /// dataset for a realism score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_sample: Option<PathBuf>,
+    /// Tables checked for k-anonymity and l-diversity.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub privacy: Vec<PrivacySpec>,
+    /// Scan values for real-looking PII; hits are violations.
+    #[serde(default = "default_pii_scan")]
+    pub pii_scan: bool,
+    /// E-mail domains accepted by the PII scan. Empty uses
+    /// [`DEFAULT_ALLOWED_EMAIL_DOMAINS`].
+    ///
+    /// [`DEFAULT_ALLOWED_EMAIL_DOMAINS`]: crate::pii::DEFAULT_ALLOWED_EMAIL_DOMAINS
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_email_domains: Vec<String>,
+    /// PII tag of columns classified as PII, by `schema.table.column`; the
+    /// scan warns when the generation report shows one was filled by a
+    /// generator without that tag.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pii_columns: BTreeMap<String, String>,
+    /// Check date/timestamp ordering (column name pairs, `derive.*_after_*`
+    /// rules) and plan windows; failures are `temporal` violations.
+    #[serde(default = "default_temporal_checks")]
+    pub temporal_checks: bool,
+    /// Pass/fail limits; failed ones are reported as `threshold` violations.
+    #[serde(default, skip_serializing_if = "EvalThresholds::is_empty")]
+    pub thresholds: EvalThresholds,
+    /// Read tables in chunks and spill keys to disk instead of loading whole
+    /// files; for datasets larger than memory.
+    #[serde(default)]
+    pub streaming: bool,
+    /// Evaluate a uniform sample of at most this many rows per table, with
+    /// CHECK and FK failures extrapolated; for quick checks while iterating.
+    /// Ignored in streaming mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rows: Option<usize>,
+    /// Rows parsed at a time when streaming or sampling.
+    #[serde(default = "default_chunk_rows")]
+    pub chunk_rows: usize,
+    /// Where streaming evaluation writes its temporary key files; defaults to
+    /// the system temp directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spill_dir: Option<PathBuf>,
+    /// Slowest generated tables listed in the report's performance section.
+    #[serde(default = "default_top_slowest")]
+    pub top_slowest: usize,
 }
 
 impl Default for EvaluateOptions {
@@ -23,12 +91,48 @@ impl Default for EvaluateOptions {
         Self {
             strict: true,
             max_examples: 20,
+            max_orphan_samples: default_max_orphan_samples(),
             write_violations: false,
+            write_junit: false,
             out_dir: None,
+            file_patterns: Vec::new(),
+            reference_dir: None,
+            source_sample: None,
+            privacy: Vec::new(),
+            pii_scan: default_pii_scan(),
+            allowed_email_domains: Vec::new(),
+            pii_columns: BTreeMap::new(),
+            temporal_checks: default_temporal_checks(),
+            thresholds: EvalThresholds::default(),
+            streaming: false,
+            sample_rows: None,
+            chunk_rows: default_chunk_rows(),
+            spill_dir: None,
+            top_slowest: default_top_slowest(),
         }
     }
 }
 
+fn default_max_orphan_samples() -> usize {
+    10
+}
+
+fn default_pii_scan() -> bool {
+    true
+}
+
+fn default_temporal_checks() -> bool {
+    true
+}
+
+fn default_chunk_rows() -> usize {
+    50_000
+}
+
+fn default_top_slowest() -> usize {
+    5
+}
+
 /// Structured violation record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Violation {
@@ -39,6 +143,21 @@ pub struct Violation {
     pub row_index: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<String>,
+    /// Sample offending rows, for violations aggregated per relation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<ViolationSample>,
+}
+
+/// Offending row of an aggregated violation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViolationSample {
+    /// 1-based data row (line `row_index + 1` of a CSV with header).
+    pub row_index: u64,
+    /// Primary key of the row as `column=value`, when the table has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Offending value (foreign key tuple).
+    pub value: String,
 }
 
 /// Result of a dataset evaluation.
@@ -48,6 +167,7 @@ pub struct EvaluationResult {
     pub metrics_path: PathBuf,
     pub report_path: PathBuf,
     pub violations_path: Option<PathBuf>,
+    pub junit_path: Option<PathBuf>,
     pub metrics: MetricsReport,
     pub report: String,
     pub violations: Vec<Violation>,
@@ -0,0 +1,185 @@
+//! Scan of dataset values for real-looking PII.
+//!
+//! Generators write e-mails on reserved domains and record the PII tags of the
+//! columns they fill (`pii.cpf`, ...) in `generation_report.json`. Values that
+//! escape those guarantees suggest data copied from production:
+//! - e-mails whose domain is not an allowed domain (or a subdomain of one);
+//!   reserved TLDs (`.test`, `.example`, `.invalid`, `.localhost`) are allowed;
+//! - CPFs with valid check digits (`00000000000` or `000.000.000-00`) in
+//!   columns not tagged `pii.cpf`.
+//!
+//! Columns classified as PII (`classification.json`) are also checked against
+//! the generation report: one the report does not tag with its PII tag was
+//! filled by a generator that knows nothing about that kind of value.
+
+use std::collections::BTreeMap;
+
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::model::GenerationReport;
+
+use crate::metrics::{PiiFinding, PiiKind, WarningItem};
+
+/// Domains accepted in e-mails when none are configured.
+pub const DEFAULT_ALLOWED_EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+const RESERVED_TLDS: &[&str] = &["test", "example", "invalid", "localhost"];
+
+/// Findings of a column, one per PII kind with hits.
+///
+/// `tags` are the PII tags of the column (empty for external datasets) and
+/// `allowed_domains` the accepted e-mail domains (empty means
+/// [`DEFAULT_ALLOWED_EMAIL_DOMAINS`]).
+pub fn scan_column<'a>(
+    schema: &str,
+    table: &str,
+    column: &str,
+    tags: &[String],
+    allowed_domains: &[String],
+    values: impl IntoIterator<Item = &'a GeneratedValue>,
+) -> Vec<PiiFinding> {
+    let allowed_domains: Vec<String> = if allowed_domains.is_empty() {
+        DEFAULT_ALLOWED_EMAIL_DOMAINS
+            .iter()
+            .map(|domain| domain.to_string())
+            .collect()
+    } else {
+        allowed_domains
+            .iter()
+            .map(|domain| domain.to_lowercase())
+            .collect()
+    };
+    let cpf_tagged = tags.iter().any(|tag| tag == "pii.cpf");
+
+    let mut findings: Vec<PiiFinding> = Vec::new();
+    for (row_idx, value) in values.into_iter().enumerate() {
+        let GeneratedValue::Text(text) = value else {
+            continue;
+        };
+        let text = text.trim();
+        let kind = if email_domain(text)
+            .is_some_and(|domain| !domain_allowed(&domain, &allowed_domains))
+        {
+            PiiKind::Email
+        } else if !cpf_tagged && valid_cpf(text) {
+            PiiKind::Cpf
+        } else {
+            continue;
+        };
+        match findings.iter_mut().find(|finding| finding.kind == kind) {
+            Some(finding) => finding.count += 1,
+            None => findings.push(PiiFinding {
+                schema: schema.to_string(),
+                table: table.to_string(),
+                column: column.to_string(),
+                kind,
+                count: 1,
+                first_row: row_idx as u64 + 1,
+                example: mask(text, kind),
+            }),
+        }
+    }
+    findings
+}
+
+/// Warnings for `pii_columns` (`schema.table.column` -> PII tag) that the
+/// report does not tag with it, i.e. neither the generator nor the column
+/// name marked them as PII. Foreign key and database default columns are
+/// skipped.
+pub fn unprotected_pii_columns(
+    pii_columns: &BTreeMap<String, String>,
+    report: &GenerationReport,
+) -> Vec<WarningItem> {
+    let mut warnings = Vec::new();
+    for (path, tag) in pii_columns {
+        let Some(source) = report.column_sources.get(path) else {
+            continue;
+        };
+        if matches!(source.source.as_str(), "foreign_key" | "default")
+            || source.pii_tags.contains(tag)
+        {
+            continue;
+        }
+        warnings.push(WarningItem {
+            code: "pii_column_unprotected".to_string(),
+            path: path.clone(),
+            message: format!(
+                "column classified {tag} was filled by {} ({}), which does not produce {tag} values",
+                source.generator_id.as_deref().unwrap_or("no generator"),
+                source.source
+            ),
+            hint: Some(format!("assign a generator tagged {tag} in the plan")),
+        });
+    }
+    warnings
+}
+
+/// Lowercased domain of a single-address e-mail value.
+fn email_domain(value: &str) -> Option<String> {
+    let (local, domain) = value.split_once('@')?;
+    let valid = !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && domain.split('.').count() >= 2
+        && domain.split('.').all(|label| !label.is_empty());
+    valid.then(|| domain.to_lowercase())
+}
+
+fn domain_allowed(domain: &str, allowed: &[String]) -> bool {
+    let tld = domain.rsplit('.').next().unwrap_or_default();
+    RESERVED_TLDS.contains(&tld)
+        || allowed
+            .iter()
+            .any(|allowed| domain == allowed || domain.ends_with(&format!(".{allowed}")))
+}
+
+/// 11 digits (bare or `000.000.000-00`), not all equal, with valid check digits.
+fn valid_cpf(value: &str) -> bool {
+    let bare = value.len() == 11 && value.bytes().all(|byte| byte.is_ascii_digit());
+    let formatted = value.len() == 14
+        && value.bytes().enumerate().all(|(idx, byte)| match idx {
+            3 | 7 => byte == b'.',
+            11 => byte == b'-',
+            _ => byte.is_ascii_digit(),
+        });
+    if !bare && !formatted {
+        return false;
+    }
+    let digits: Vec<u32> = value.chars().filter_map(|ch| ch.to_digit(10)).collect();
+    if digits.iter().all(|digit| *digit == digits[0]) {
+        return false;
+    }
+    let check = |len: usize| {
+        let sum: u32 = digits[..len]
+            .iter()
+            .enumerate()
+            .map(|(idx, digit)| digit * (len as u32 + 1 - idx as u32))
+            .sum();
+        match sum % 11 {
+            0 | 1 => 0,
+            rest => 11 - rest,
+        }
+    };
+    check(9) == digits[9] && check(10) == digits[10]
+}
+
+/// Example shown in reports, without the identifying part of the value.
+fn mask(value: &str, kind: PiiKind) -> String {
+    match kind {
+        PiiKind::Email => {
+            let (local, domain) = value.split_once('@').unwrap_or((value, ""));
+            let visible: String = local.chars().take(2).collect();
+            format!("{visible}***@{domain}")
+        }
+        PiiKind::Cpf => {
+            let last: String = value
+                .chars()
+                .rev()
+                .take(2)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect();
+            format!("*********{last}")
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Privacy metrics over declared quasi-identifiers.
+//!
+//! Rows sharing the same quasi-identifier values form an equivalence class.
+//! A table is k-anonymous when every class has at least `k` rows, and
+//! l-diverse for a sensitive column when every class holds at least `l`
+//! distinct non-null values of it.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use datalchemy_generate::generators::GeneratedValue;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{tuple_key, value_key};
+use crate::metrics::{PrivacyMetrics, SensitiveDiversity, WarningItem};
+
+/// Minimum equivalence class size used when a spec sets none.
+pub const DEFAULT_K: u64 = 5;
+/// Minimum distinct sensitive values per class used when a spec sets none.
+pub const DEFAULT_L: u64 = 2;
+
+/// Quasi-identifier and sensitive columns declared for a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacySpec {
+    pub schema: String,
+    pub table: String,
+    pub quasi_identifiers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive: Vec<String>,
+    #[serde(default = "default_k")]
+    pub k: u64,
+    #[serde(default = "default_l")]
+    pub l: u64,
+}
+
+fn default_k() -> u64 {
+    DEFAULT_K
+}
+
+fn default_l() -> u64 {
+    DEFAULT_L
+}
+
+/// k-anonymity and l-diversity of a table for `spec`.
+///
+/// `columns` are the column names and `rows` the table rows, with values in
+/// the same order. Returns `Err` with the first declared column missing from
+/// the table.
+pub fn privacy_metrics(
+    spec: &PrivacySpec,
+    columns: &[&str],
+    rows: &[Vec<GeneratedValue>],
+) -> Result<PrivacyMetrics, String> {
+    let index = |name: &String| {
+        columns
+            .iter()
+            .position(|column| *column == name)
+            .ok_or_else(|| name.clone())
+    };
+    let quasi = spec
+        .quasi_identifiers
+        .iter()
+        .map(index)
+        .collect::<Result<Vec<_>, _>>()?;
+    let sensitive = spec
+        .sensitive
+        .iter()
+        .map(index)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Class key -> (rows, distinct values of each sensitive column).
+    let mut classes: BTreeMap<String, (u64, Vec<BTreeSet<String>>)> = BTreeMap::new();
+    for row in rows {
+        let key = tuple_key(
+            &quasi
+                .iter()
+                .map(|idx| row.get(*idx).cloned().unwrap_or(GeneratedValue::Null))
+                .collect::<Vec<_>>(),
+        );
+        let class = classes
+            .entry(key)
+            .or_insert_with(|| (0, vec![BTreeSet::new(); sensitive.len()]));
+        class.0 += 1;
+        for (position, idx) in sensitive.iter().enumerate() {
+            if let Some(value) = row.get(*idx).filter(|value| !value.is_null()) {
+                class.1[position].insert(value_key(value));
+            }
+        }
+    }
+
+    let below_k = classes.values().filter(|(size, _)| *size < spec.k);
+    let l_diversity = spec
+        .sensitive
+        .iter()
+        .enumerate()
+        .map(|(position, column)| {
+            let distinct = classes
+                .values()
+                .map(|(_, values)| values[position].len() as u64);
+            SensitiveDiversity {
+                column: column.clone(),
+                l_diversity: distinct.clone().min().unwrap_or(0),
+                l_threshold: spec.l,
+                classes_below_l: distinct.filter(|count| *count < spec.l).count() as u64,
+            }
+        })
+        .collect();
+
+    Ok(PrivacyMetrics {
+        schema: spec.schema.clone(),
+        table: spec.table.clone(),
+        quasi_identifiers: spec.quasi_identifiers.clone(),
+        rows: rows.len() as u64,
+        equivalence_classes: classes.len() as u64,
+        k_anonymity: classes.values().map(|(size, _)| *size).min().unwrap_or(0),
+        k_threshold: spec.k,
+        classes_below_k: below_k.clone().count() as u64,
+        rows_below_k: below_k.map(|(size, _)| size).sum(),
+        l_diversity,
+    })
+}
+
+/// Findings for classes below the k and l thresholds.
+pub fn privacy_warnings(metrics: &PrivacyMetrics) -> Vec<WarningItem> {
+    let mut warnings = Vec::new();
+    if metrics.classes_below_k > 0 {
+        warnings.push(WarningItem {
+            code: "k_anonymity_below_threshold".to_string(),
+            path: format!("{}.{}", metrics.schema, metrics.table),
+            message: format!(
+                "{} equivalence classes ({} rows) over ({}) have fewer than k={} rows",
+                metrics.classes_below_k,
+                metrics.rows_below_k,
+                metrics.quasi_identifiers.join(","),
+                metrics.k_threshold
+            ),
+            hint: Some("generalize quasi-identifiers or widen their generators".to_string()),
+        });
+    }
+    for diversity in &metrics.l_diversity {
+        if diversity.classes_below_l > 0 {
+            warnings.push(WarningItem {
+                code: "l_diversity_below_threshold".to_string(),
+                path: format!("{}.{}.{}", metrics.schema, metrics.table, diversity.column),
+                message: format!(
+                    "{} equivalence classes have fewer than l={} distinct values",
+                    diversity.classes_below_l, diversity.l_threshold
+                ),
+                hint: Some("spread sensitive values across quasi-identifier groups".to_string()),
+            });
+        }
+    }
+    warnings
+}
@@ -0,0 +1,224 @@
+//! Column profiles for `metrics.json` (see [`ColumnStats`]).
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use datalchemy_core::{Constraint, Table};
+use datalchemy_generate::generators::GeneratedValue;
+
+use crate::metrics::{ColumnStats, Quantiles, ValueCount, WarningItem};
+
+/// Number of most frequent values kept per column.
+pub const TOP_VALUES: usize = 5;
+/// Tables with fewer rows are not checked by [`cardinality_warnings`].
+pub const CARDINALITY_MIN_ROWS: u64 = 20;
+/// Ratio from which a column without unique constraint is near-unique.
+pub const NEAR_UNIQUE_RATIO: f64 = 0.99;
+/// Ratio below which a unique column is reported as low-cardinality.
+pub const LOW_CARDINALITY_RATIO: f64 = 0.5;
+
+/// Profile `values` (one per row, nulls included) of `schema.table.column`.
+pub fn profile_column<'a>(
+    schema: &str,
+    table: &str,
+    column: &str,
+    values: impl IntoIterator<Item = &'a GeneratedValue>,
+) -> ColumnStats {
+    let mut rows = 0_u64;
+    let mut null_count = 0_u64;
+    let mut min: Option<&GeneratedValue> = None;
+    let mut max: Option<&GeneratedValue> = None;
+    let mut numbers = Vec::new();
+    let mut counts: HashMap<String, u64> = HashMap::new();
+
+    for value in values {
+        rows += 1;
+        if value.is_null() {
+            null_count += 1;
+            continue;
+        }
+        if min.is_none_or(|current| compare_values(value, current) == Ordering::Less) {
+            min = Some(value);
+        }
+        if max.is_none_or(|current| compare_values(value, current) == Ordering::Greater) {
+            max = Some(value);
+        }
+        if let Some(number) = as_number(value) {
+            numbers.push(number);
+        }
+        *counts.entry(value_text(value)).or_default() += 1;
+    }
+
+    let non_null = rows - null_count;
+    // Numeric moments only when every non-null value is a number.
+    let numeric = !numbers.is_empty() && numbers.len() as u64 == non_null;
+    let (mean, stddev, quantiles) = if numeric {
+        let count = numbers.len() as f64;
+        let mean = numbers.iter().sum::<f64>() / count;
+        let variance = numbers
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        numbers.sort_by(f64::total_cmp);
+        let quantiles = Quantiles {
+            p05: quantile(&numbers, 0.05),
+            p25: quantile(&numbers, 0.25),
+            p50: quantile(&numbers, 0.50),
+            p75: quantile(&numbers, 0.75),
+            p95: quantile(&numbers, 0.95),
+        };
+        (Some(mean), Some(variance.sqrt()), Some(quantiles))
+    } else {
+        (None, None, None)
+    };
+
+    let distinct_count = counts.len() as u64;
+    let mut top_values: Vec<ValueCount> = counts
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect();
+    top_values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    top_values.truncate(TOP_VALUES);
+
+    ColumnStats {
+        schema: schema.to_string(),
+        table: table.to_string(),
+        column: column.to_string(),
+        null_count,
+        null_rate: if rows == 0 {
+            0.0
+        } else {
+            null_count as f64 / rows as f64
+        },
+        distinct_count,
+        cardinality_ratio: if rows == 0 {
+            0.0
+        } else {
+            distinct_count as f64 / rows as f64
+        },
+        min: min.map(value_text),
+        max: max.map(value_text),
+        mean,
+        stddev,
+        quantiles,
+        top_values,
+    }
+}
+
+/// Warn about cardinality that disagrees with the table's unique constraints:
+/// columns whose values are all distinct without a PK/UNIQUE constraint, and
+/// PK/UNIQUE columns (single-column) with few distinct values (mostly nulls).
+///
+/// Only tables with at least [`CARDINALITY_MIN_ROWS`] rows are checked; float,
+/// boolean and temporal columns are never flagged as near-unique.
+pub fn cardinality_warnings(table: &Table, rows: u64, stats: &[ColumnStats]) -> Vec<WarningItem> {
+    let unique_columns: HashSet<&str> = table
+        .constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::PrimaryKey(pk) if pk.columns.len() == 1 => Some(pk.columns[0].as_str()),
+            Constraint::Unique(unique) if unique.columns.len() == 1 => {
+                Some(unique.columns[0].as_str())
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    if rows < CARDINALITY_MIN_ROWS {
+        return warnings;
+    }
+    for stats in stats {
+        let path = format!("{}.{}.{}", stats.schema, stats.table, stats.column);
+        let constrained = unique_columns.contains(stats.column.as_str());
+        if constrained && stats.cardinality_ratio < LOW_CARDINALITY_RATIO {
+            warnings.push(WarningItem {
+                code: "low_cardinality_unique".to_string(),
+                path,
+                message: format!(
+                    "unique column has {} distinct values in {} rows (ratio {:.2})",
+                    stats.distinct_count, rows, stats.cardinality_ratio
+                ),
+                hint: Some("check null rate; unique constraints allow repeated nulls".to_string()),
+            });
+        } else if !constrained
+            && stats.cardinality_ratio >= NEAR_UNIQUE_RATIO
+            && near_unique_candidate(table, &stats.column)
+        {
+            warnings.push(WarningItem {
+                code: "near_unique_column".to_string(),
+                path,
+                message: format!(
+                    "{} distinct values in {} rows (ratio {:.2}) without a unique constraint",
+                    stats.distinct_count, rows, stats.cardinality_ratio
+                ),
+                hint: Some("add a UNIQUE constraint if the column is a natural key".to_string()),
+            });
+        }
+    }
+    warnings
+}
+
+/// Identifier-like column types (not float, boolean or temporal).
+fn near_unique_candidate(table: &Table, column: &str) -> bool {
+    let Some(column) = table.columns.iter().find(|col| col.name == column) else {
+        return false;
+    };
+    let data_type = column.column_type.data_type.to_lowercase();
+    ![
+        "real",
+        "double",
+        "numeric",
+        "decimal",
+        "boolean",
+        "date",
+        "time",
+        "timestamp",
+        "interval",
+    ]
+    .iter()
+    .any(|prefix| data_type.starts_with(prefix))
+}
+
+/// Quantile `q` of sorted `values`, interpolating between the closest ranks.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+fn as_number(value: &GeneratedValue) -> Option<f64> {
+    match value {
+        GeneratedValue::Int(value) => Some(*value as f64),
+        GeneratedValue::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn compare_values(a: &GeneratedValue, b: &GeneratedValue) -> Ordering {
+    match (a, b) {
+        (GeneratedValue::Bool(a), GeneratedValue::Bool(b)) => a.cmp(b),
+        (GeneratedValue::Date(a), GeneratedValue::Date(b)) => a.cmp(b),
+        (GeneratedValue::Time(a), GeneratedValue::Time(b)) => a.cmp(b),
+        (GeneratedValue::Timestamp(a), GeneratedValue::Timestamp(b)) => a.cmp(b),
+        _ => match (as_number(a), as_number(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => value_text(a).cmp(&value_text(b)),
+        },
+    }
+}
+
+fn value_text(value: &GeneratedValue) -> String {
+    match value {
+        GeneratedValue::Null => String::new(),
+        GeneratedValue::Bool(value) => value.to_string(),
+        GeneratedValue::Int(value) => value.to_string(),
+        GeneratedValue::Float(value) => value.to_string(),
+        GeneratedValue::Text(value) | GeneratedValue::Uuid(value) => value.clone(),
+        GeneratedValue::Date(value) => value.format("%Y-%m-%d").to_string(),
+        GeneratedValue::Time(value) => value.format("%H:%M:%S").to_string(),
+        GeneratedValue::Timestamp(value) => value.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+}
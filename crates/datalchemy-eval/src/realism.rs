@@ -0,0 +1,250 @@
+//! Realism score against a sample of the source database.
+//!
+//! A [`SourceSample`] (written by `datalchemy eval --live-sample`) holds the
+//! row count of each source table and the category frequencies of its
+//! low-cardinality columns over a random sample of rows. Columns whose name
+//! implies PII are never read from the source: they are listed as
+//! `redacted_columns` and not compared.
+//!
+//! The score is the mean of:
+//! - the row share similarity, 1 minus the total variation distance between
+//!   each table's share of the rows in the source and in the dataset (two or
+//!   more compared tables);
+//! - the similarity of each compared column, 1 minus the total variation
+//!   distance between the category frequencies, with dataset values outside
+//!   the source categories counted as one extra category.
+
+use std::collections::BTreeMap;
+
+use datalchemy_core::{Column, DatabaseSchema, RedactedConnection};
+use datalchemy_generate::engine::column_pii_tags;
+use datalchemy_generate::generators::GeneratedValue;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::value_key;
+use crate::metrics::{CategoryRealism, RealismMetrics, TableRealism};
+
+/// Columns below this similarity are reported as drifting from the source.
+pub const LOW_SIMILARITY: f64 = 0.8;
+
+/// Row counts and category frequencies read from the source database.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceSample {
+    /// Connection the sample was read from, with secrets redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<RedactedConnection>,
+    /// Rows sampled per table.
+    pub sample_rows: u64,
+    pub tables: Vec<SourceTable>,
+}
+
+/// One source table: total rows and the frequencies of its sampled columns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceTable {
+    pub schema: String,
+    pub table: String,
+    pub row_count: u64,
+    pub sampled_rows: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<SourceColumn>,
+    /// Columns not read because their name implies PII.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted_columns: Vec<String>,
+}
+
+/// Non-null value -> rows of one column in the sampled rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceColumn {
+    pub column: String,
+    pub frequencies: BTreeMap<String, u64>,
+}
+
+impl SourceSample {
+    pub fn table(&self, schema: &str, table: &str) -> Option<&SourceTable> {
+        self.tables
+            .iter()
+            .find(|source| source.schema == schema && source.table == table)
+    }
+}
+
+/// Whether a column must not be read from the source (its name implies PII).
+pub fn is_redacted(column: &str) -> bool {
+    !column_pii_tags(column).is_empty()
+}
+
+/// Whether a column's values are compared as categories: booleans, enums and
+/// text. Text columns with more than [`MAX_CATEGORIES`] values in the sample
+/// are left out by the sampler.
+///
+/// [`MAX_CATEGORIES`]: crate::compare::MAX_CATEGORIES
+pub fn is_categorical(schema: &DatabaseSchema, column: &Column) -> bool {
+    let column_type = &column.column_type;
+    matches!(
+        column_type.udt_name.as_str(),
+        "bool" | "text" | "varchar" | "bpchar" | "citext"
+    ) || schema.enums.iter().any(|enum_type| {
+        enum_type.schema == column_type.udt_schema && enum_type.name == column_type.udt_name
+    })
+}
+
+/// Category counts of one table's dataset rows, for the columns in its
+/// source sample.
+#[derive(Debug, Clone)]
+pub struct RealismTally {
+    schema: String,
+    table: String,
+    source_rows: u64,
+    redacted_columns: Vec<String>,
+    columns: Vec<CategoryTally>,
+}
+
+#[derive(Debug, Clone)]
+struct CategoryTally {
+    column: String,
+    idx: usize,
+    source: BTreeMap<String, u64>,
+    counts: BTreeMap<String, u64>,
+    unseen: u64,
+}
+
+impl RealismTally {
+    /// Bind `source` to a table whose rows hold `columns`, in order; source
+    /// columns missing from the dataset are not compared.
+    pub fn new(source: &SourceTable, columns: &[&str]) -> Self {
+        let columns = source
+            .columns
+            .iter()
+            .filter(|column| !column.frequencies.is_empty())
+            .filter_map(|column| {
+                let idx = columns
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(&column.column))?;
+                Some(CategoryTally {
+                    column: column.column.clone(),
+                    idx,
+                    source: column.frequencies.clone(),
+                    counts: column
+                        .frequencies
+                        .keys()
+                        .map(|key| (key.clone(), 0))
+                        .collect(),
+                    unseen: 0,
+                })
+            })
+            .collect();
+        Self {
+            schema: source.schema.clone(),
+            table: source.table.clone(),
+            source_rows: source.row_count,
+            redacted_columns: source.redacted_columns.clone(),
+            columns,
+        }
+    }
+
+    pub fn add_row(&mut self, row: &[GeneratedValue]) {
+        for column in &mut self.columns {
+            let Some(value) = row.get(column.idx) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            match column.counts.get_mut(&value_key(value)) {
+                Some(count) => *count += 1,
+                None => column.unseen += 1,
+            }
+        }
+    }
+
+    /// Compare the counted rows; `rows_found` is the table's row count.
+    pub fn finish(self, rows_found: u64) -> TableRealism {
+        TableRealism {
+            schema: self.schema,
+            table: self.table,
+            source_rows: self.source_rows,
+            rows: rows_found,
+            row_ratio: (self.source_rows > 0).then(|| rows_found as f64 / self.source_rows as f64),
+            columns: self
+                .columns
+                .into_iter()
+                .map(CategoryTally::finish)
+                .collect(),
+            redacted_columns: self.redacted_columns,
+        }
+    }
+}
+
+impl CategoryTally {
+    fn finish(self) -> CategoryRealism {
+        let source_total = self.source.values().sum::<u64>() as f64;
+        let total = self.counts.values().sum::<u64>() + self.unseen;
+        // A column with no values in the dataset shares nothing with the source.
+        let similarity = if total == 0 {
+            0.0
+        } else {
+            let distance = self
+                .source
+                .iter()
+                .map(|(key, count)| {
+                    let generated = self.counts.get(key).copied().unwrap_or(0);
+                    (*count as f64 / source_total - generated as f64 / total as f64).abs()
+                })
+                .sum::<f64>()
+                + self.unseen as f64 / total as f64;
+            1.0 - distance / 2.0
+        };
+        CategoryRealism {
+            column: self.column,
+            categories: self.source.len() as u64,
+            similarity,
+            missing_categories: self
+                .counts
+                .iter()
+                .filter(|(_, count)| **count == 0)
+                .map(|(key, _)| key.clone())
+                .collect(),
+            unseen_rows: self.unseen,
+        }
+    }
+}
+
+/// Score the compared tables; `None` when no table of the sample was read.
+pub fn realism_metrics(sample: &SourceSample, tables: Vec<TableRealism>) -> Option<RealismMetrics> {
+    if tables.is_empty() {
+        return None;
+    }
+    let row_share_similarity = (tables.len() > 1).then(|| {
+        let source_total = tables.iter().map(|table| table.source_rows).sum::<u64>();
+        let total = tables.iter().map(|table| table.rows).sum::<u64>();
+        let share = |part: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                part as f64 / total as f64
+            }
+        };
+        let distance = tables
+            .iter()
+            .map(|table| (share(table.source_rows, source_total) - share(table.rows, total)).abs())
+            .sum::<f64>();
+        1.0 - distance / 2.0
+    });
+    let parts: Vec<f64> = row_share_similarity
+        .into_iter()
+        .chain(
+            tables
+                .iter()
+                .flat_map(|table| table.columns.iter().map(|column| column.similarity)),
+        )
+        .collect();
+    Some(RealismMetrics {
+        score: (!parts.is_empty()).then(|| parts.iter().sum::<f64>() / parts.len() as f64),
+        row_share_similarity,
+        connection: sample
+            .connection
+            .as_ref()
+            .map(|connection| connection.redacted.clone()),
+        sample_rows: sample.sample_rows,
+        tables,
+    })
+}
@@ -1,11 +1,18 @@
-use crate::metrics::{ConstraintSummary, MetricsReport};
+use crate::assertions::format_values;
+use crate::compare::DIVERGENCE_ALPHA;
+use crate::metrics::{
+    AssertionStatus, CheckStatus, ConstraintSummary, DuplicateKind, MetricsReport, PiiKind,
+    TemporalSource,
+};
 use crate::model::Violation;
+use crate::realism::LOW_SIMILARITY;
 
 /// Render a deterministic markdown report from metrics and violations.
 pub fn render_report(
     metrics: &MetricsReport,
     violations: &[Violation],
     max_examples: usize,
+    top_slowest: usize,
 ) -> String {
     let mut lines = Vec::new();
 
@@ -51,6 +58,495 @@ pub fn render_report(
     );
     lines.push(String::new());
 
+    if !metrics.constraints.check.constraints.is_empty() {
+        lines.push("## Check constraints".to_string());
+        lines.push("| table | constraint | status | rows | failures |".to_string());
+        lines.push("| --- | --- | --- | --- | --- |".to_string());
+        for check in &metrics.constraints.check.constraints {
+            let status = match check.status {
+                CheckStatus::Passed => "passed",
+                CheckStatus::Failed => "failed",
+                CheckStatus::Unsupported => "unsupported",
+            };
+            lines.push(format!(
+                "| {}.{} | {} | {} | {} | {} |",
+                check.schema,
+                check.table,
+                table_cell(check.name.as_deref().unwrap_or(&check.expression)),
+                status,
+                check.rows,
+                check.failures
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.duplicates.is_empty() {
+        lines.push("## Duplicates".to_string());
+        let repeated: Vec<_> = metrics
+            .duplicates
+            .iter()
+            .filter(|stats| stats.duplicate_rows > 0)
+            .collect();
+        if repeated.is_empty() {
+            lines.push("- no duplicate rows or business keys".to_string());
+        } else {
+            lines.push(
+                "| table | key | rows | duplicate_rows | groups | first repeat |".to_string(),
+            );
+            lines.push("| --- | --- | --- | --- | --- | --- |".to_string());
+            for stats in repeated {
+                let key = match stats.kind {
+                    DuplicateKind::Row => "(all columns)".to_string(),
+                    DuplicateKind::BusinessKey => stats.columns.join(","),
+                };
+                let first = stats
+                    .samples
+                    .first()
+                    .map(|group| {
+                        format!(
+                            "rows {} ({}x): {}",
+                            group
+                                .rows
+                                .iter()
+                                .map(u64::to_string)
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            group.count,
+                            table_cell(&group.value)
+                        )
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                lines.push(format!(
+                    "| {}.{} | {} | {} | {} | {} | {} |",
+                    stats.schema,
+                    stats.table,
+                    table_cell(&key),
+                    stats.rows_checked,
+                    stats.duplicate_rows,
+                    stats.duplicate_groups,
+                    first
+                ));
+            }
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.fan_out.is_empty() {
+        lines.push("## Fan-out".to_string());
+        lines.push(
+            "| relation | parents | min | median | p95 | max | mean | expected | uniform |"
+                .to_string(),
+        );
+        lines.push("| --- | --- | --- | --- | --- | --- | --- | --- | --- |".to_string());
+        for stats in &metrics.fan_out {
+            let expected = stats
+                .expected_mean
+                .map(|mean| format!("{mean:.2}"))
+                .unwrap_or_else(|| "-".to_string());
+            lines.push(format!(
+                "| {}({}) -> {} | {} | {} | {} | {} | {} | {:.2} | {} | {} |",
+                stats.table,
+                stats.columns.join(","),
+                stats.parent,
+                stats.parents,
+                stats.min,
+                stats.median,
+                stats.p95,
+                stats.max,
+                stats.mean,
+                expected,
+                if stats.uniform { "yes" } else { "no" }
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.temporal.is_empty() {
+        lines.push("## Temporal checks".to_string());
+        lines.push("| table | rule | source | rows | failures |".to_string());
+        lines.push("| --- | --- | --- | --- | --- |".to_string());
+        for check in &metrics.temporal {
+            let source = match check.source {
+                TemporalSource::ColumnNames => "column names",
+                TemporalSource::PlanDerive => "plan derive",
+                TemporalSource::PlanWindow => "plan window",
+            };
+            lines.push(format!(
+                "| {}.{} | {} | {} | {} | {} |",
+                check.schema,
+                check.table,
+                table_cell(&check.rule),
+                source,
+                check.rows_checked,
+                check.failures
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.assertions.is_empty() {
+        lines.push("## Assertions".to_string());
+        lines.push("| assertion | table | status | measured |".to_string());
+        lines.push("| --- | --- | --- | --- |".to_string());
+        for assertion in &metrics.assertions {
+            let status = match assertion.status {
+                AssertionStatus::Passed => "passed",
+                AssertionStatus::Failed => "failed",
+                AssertionStatus::Error => "error",
+            };
+            let measured = match assertion.status {
+                AssertionStatus::Error => assertion.message.clone().unwrap_or_default(),
+                _ => format_values(&assertion.values),
+            };
+            lines.push(format!(
+                "| {} | {}.{} | {} | {} |",
+                table_cell(&assertion.name),
+                assertion.schema,
+                assertion.table,
+                status,
+                table_cell(&measured)
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if let Some(sampling) = &metrics.sampling {
+        lines.push("## Sampling".to_string());
+        lines.push(format!(
+            "- up to {} rows checked per table; failures extrapolated with a 95% interval.",
+            sampling.sample_rows
+        ));
+        lines.push("| table | rows_total | rows_sampled |".to_string());
+        lines.push("| --- | --- | --- |".to_string());
+        for table in &sampling.tables {
+            lines.push(format!(
+                "| {}.{} | {} | {} |",
+                table.schema, table.table, table.rows_total, table.rows_sampled
+            ));
+        }
+        if !sampling.estimates.is_empty() {
+            lines.push(String::new());
+            lines.push(
+                "| path | metric | sample failures | estimated failures | 95% interval |"
+                    .to_string(),
+            );
+            lines.push("| --- | --- | --- | --- | --- |".to_string());
+            for estimate in &sampling.estimates {
+                lines.push(format!(
+                    "| {} | {} | {}/{} | {} | {}..{} |",
+                    table_cell(&estimate.path),
+                    estimate.metric,
+                    estimate.failures,
+                    estimate.rows_sampled,
+                    estimate.estimated_failures,
+                    estimate.estimated_low,
+                    estimate.estimated_high
+                ));
+            }
+        }
+        lines.push(String::new());
+        for note in &sampling.notes {
+            lines.push(format!("- note: {note}"));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.column_stats.is_empty() {
+        lines.push("## Column profiles".to_string());
+        lines.push("| column | null_rate | distinct | min | max | mean | top |".to_string());
+        lines.push("| --- | --- | --- | --- | --- | --- | --- |".to_string());
+        for stats in &metrics.column_stats {
+            let top = stats
+                .top_values
+                .first()
+                .map(|top| format!("{} ({})", table_cell(&top.value), top.count))
+                .unwrap_or_else(|| "-".to_string());
+            lines.push(format!(
+                "| {}.{}.{} | {:.2} | {} | {} | {} | {} | {} |",
+                stats.schema,
+                stats.table,
+                stats.column,
+                stats.null_rate,
+                stats.distinct_count,
+                stats
+                    .min
+                    .as_deref()
+                    .map(table_cell)
+                    .unwrap_or("-".to_string()),
+                stats
+                    .max
+                    .as_deref()
+                    .map(table_cell)
+                    .unwrap_or("-".to_string()),
+                stats
+                    .mean
+                    .map(|mean| format!("{mean:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                top
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.correlations.is_empty() {
+        lines.push("## Correlations".to_string());
+        lines.push("| table | column_a | column_b | rows | pearson | spearman |".to_string());
+        lines.push("| --- | --- | --- | --- | --- | --- |".to_string());
+        let format_coefficient = |value: Option<f64>| {
+            value
+                .map(|value| format!("{value:.3}"))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        for correlation in &metrics.correlations {
+            lines.push(format!(
+                "| {}.{} | {} | {} | {} | {} | {} |",
+                correlation.schema,
+                correlation.table,
+                correlation.column_a,
+                correlation.column_b,
+                correlation.rows,
+                format_coefficient(correlation.pearson),
+                format_coefficient(correlation.spearman)
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.distributions.is_empty() {
+        let diverging = metrics
+            .distributions
+            .iter()
+            .filter(|distribution| distribution.diverges)
+            .count();
+        lines.push("## Distribution comparison".to_string());
+        lines.push(format!(
+            "- {} of {} compared columns diverge from the reference (p < {}).",
+            diverging,
+            metrics.distributions.len(),
+            DIVERGENCE_ALPHA
+        ));
+        lines.push(
+            "| column | kind | statistic | p_value | histogram_overlap | diverges |".to_string(),
+        );
+        lines.push("| --- | --- | --- | --- | --- | --- |".to_string());
+        for distribution in &metrics.distributions {
+            let statistic = match (distribution.ks_statistic, distribution.chi_square) {
+                (Some(ks), _) => format!("ks={ks:.3}"),
+                (None, Some(chi)) => format!(
+                    "chi2={chi:.3} (df={})",
+                    distribution.degrees_of_freedom.unwrap_or(0)
+                ),
+                (None, None) => "-".to_string(),
+            };
+            lines.push(format!(
+                "| {}.{}.{} | {:?} | {} | {:.4} | {:.2} | {} |",
+                distribution.schema,
+                distribution.table,
+                distribution.column,
+                distribution.kind,
+                statistic,
+                distribution.p_value,
+                distribution.histogram_overlap,
+                if distribution.diverges { "yes" } else { "no" }
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if let Some(realism) = &metrics.realism {
+        lines.push("## Realism".to_string());
+        lines.push(format!(
+            "- score: {} over {} table(s), {} sampled rows per source table{}.",
+            realism
+                .score
+                .map(|score| format!("{score:.3}"))
+                .unwrap_or_else(|| "-".to_string()),
+            realism.tables.len(),
+            realism.sample_rows,
+            realism
+                .connection
+                .as_ref()
+                .map(|connection| format!(" from {connection}"))
+                .unwrap_or_default()
+        ));
+        if let Some(similarity) = realism.row_share_similarity {
+            lines.push(format!("- row share similarity: {similarity:.3}."));
+        }
+        lines.push("| table | source_rows | rows | ratio | redacted columns |".to_string());
+        lines.push("| --- | --- | --- | --- | --- |".to_string());
+        for table in &realism.tables {
+            lines.push(format!(
+                "| {}.{} | {} | {} | {} | {} |",
+                table.schema,
+                table.table,
+                table.source_rows,
+                table.rows,
+                table
+                    .row_ratio
+                    .map(|ratio| format!("{ratio:.4}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                if table.redacted_columns.is_empty() {
+                    "-".to_string()
+                } else {
+                    table.redacted_columns.join(", ")
+                }
+            ));
+        }
+        if realism.tables.iter().any(|table| !table.columns.is_empty()) {
+            lines.push(String::new());
+            lines.push(
+                "| column | categories | similarity | missing categories | unseen rows |"
+                    .to_string(),
+            );
+            lines.push("| --- | --- | --- | --- | --- |".to_string());
+            for table in &realism.tables {
+                for column in &table.columns {
+                    lines.push(format!(
+                        "| {}.{}.{} | {} | {:.3}{} | {} | {} |",
+                        table.schema,
+                        table.table,
+                        column.column,
+                        column.categories,
+                        column.similarity,
+                        if column.similarity < LOW_SIMILARITY {
+                            " (low)"
+                        } else {
+                            ""
+                        },
+                        if column.missing_categories.is_empty() {
+                            "-".to_string()
+                        } else {
+                            column.missing_categories.join(", ")
+                        },
+                        column.unseen_rows
+                    ));
+                }
+            }
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.privacy.is_empty() {
+        lines.push("## Privacy".to_string());
+        lines.push(
+            "| table | quasi_identifiers | classes | k (min) | classes < k | rows < k | l (min) |"
+                .to_string(),
+        );
+        lines.push("| --- | --- | --- | --- | --- | --- | --- |".to_string());
+        for privacy in &metrics.privacy {
+            let diversity = if privacy.l_diversity.is_empty() {
+                "-".to_string()
+            } else {
+                privacy
+                    .l_diversity
+                    .iter()
+                    .map(|diversity| {
+                        format!(
+                            "{}={} (l={}, {} below)",
+                            diversity.column,
+                            diversity.l_diversity,
+                            diversity.l_threshold,
+                            diversity.classes_below_l
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            };
+            lines.push(format!(
+                "| {}.{} | {} | {} | {} (k={}) | {} | {} | {} |",
+                privacy.schema,
+                privacy.table,
+                privacy.quasi_identifiers.join(", "),
+                privacy.equivalence_classes,
+                privacy.k_anonymity,
+                privacy.k_threshold,
+                privacy.classes_below_k,
+                privacy.rows_below_k,
+                diversity
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.thresholds.is_empty() {
+        let failed = metrics
+            .thresholds
+            .iter()
+            .filter(|result| !result.passed)
+            .count();
+        lines.push("## Thresholds".to_string());
+        lines.push(format!(
+            "- {} of {} thresholds failed.",
+            failed,
+            metrics.thresholds.len()
+        ));
+        lines.push("| path | threshold | limit | actual | result |".to_string());
+        lines.push("| --- | --- | --- | --- | --- |".to_string());
+        for result in &metrics.thresholds {
+            lines.push(format!(
+                "| {} | {} | {} | {:.4} | {} |",
+                table_cell(&result.path),
+                result.metric,
+                result.limit,
+                result.actual,
+                if result.passed { "pass" } else { "fail" }
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    if !metrics.pii_findings.is_empty() {
+        lines.push("## PII scan".to_string());
+        lines.push("| column | kind | count | first_row | example |".to_string());
+        lines.push("| --- | --- | --- | --- | --- |".to_string());
+        for finding in &metrics.pii_findings {
+            let kind = match finding.kind {
+                PiiKind::Email => "email",
+                PiiKind::Cpf => "cpf",
+            };
+            lines.push(format!(
+                "| {}.{}.{} | {} | {} | {} | {} |",
+                finding.schema,
+                finding.table,
+                finding.column,
+                kind,
+                finding.count,
+                finding.first_row,
+                table_cell(&finding.example)
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push("## Performance".to_string());
+    lines.push(format!(
+        "- load_ms: {}, validate_ms: {}, total_ms: {}",
+        metrics.performance.load_ms, metrics.performance.validate_ms, metrics.performance.total_ms
+    ));
+    if !metrics.performance.tables.is_empty() && top_slowest > 0 {
+        lines.push(String::new());
+        lines.push(format!("Slowest tables to generate (top {top_slowest}):"));
+        lines.push(
+            "| table | duration_ms | rows | rows_per_sec | retries_per_sec | peak_memory_bytes |"
+                .to_string(),
+        );
+        lines.push("| --- | --- | --- | --- | --- | --- |".to_string());
+        for table in metrics.performance.tables.iter().take(top_slowest) {
+            lines.push(format!(
+                "| {}.{} | {} | {} | {:.1} | {:.1} | {} |",
+                table.schema,
+                table.table,
+                table.duration_ms,
+                table.rows,
+                table.rows_per_sec,
+                table.retries_per_sec,
+                table.peak_memory_bytes
+            ));
+        }
+    }
+    lines.push(String::new());
+
     if !metrics.warnings.is_empty() {
         lines.push("## Warnings".to_string());
         for warning in &metrics.warnings {
@@ -80,6 +576,17 @@ pub fn render_report(
                 "- {}{}: {}{}",
                 violation.path, row, violation.message, example
             ));
+            for sample in &violation.samples {
+                let key = sample
+                    .key
+                    .as_ref()
+                    .map(|key| format!(" ({key})"))
+                    .unwrap_or_default();
+                lines.push(format!(
+                    "  - row {}{}: {}",
+                    sample.row_index, key, sample.value
+                ));
+            }
         }
         lines.push(String::new());
     }
@@ -89,6 +596,138 @@ pub fn render_report(
     lines.join("\n")
 }
 
+/// Escape a value for a markdown table cell.
+/// Checks reported as JUnit test cases, with the violation codes they cover.
+const JUNIT_CHECKS: &[(&str, &[&str])] = &[
+    ("not_null", &["not_null"]),
+    ("primary_key", &["primary_key"]),
+    ("unique", &["unique"]),
+    ("foreign_key", &["foreign_key"]),
+    ("check", &["check"]),
+    ("pii_scan", &["pii_email", "pii_cpf"]),
+];
+
+/// Render a JUnit XML report: one test case per check, failed when the check
+/// has violations (the same ones that fail a strict evaluation).
+///
+/// Violation codes outside the known checks get a test case of their own.
+pub fn render_junit(
+    metrics: &MetricsReport,
+    violations: &[Violation],
+    max_examples: usize,
+) -> String {
+    let mut checks: Vec<(String, Vec<&Violation>)> = JUNIT_CHECKS
+        .iter()
+        .map(|(name, codes)| {
+            let matching = violations
+                .iter()
+                .filter(|violation| codes.contains(&violation.code.as_str()))
+                .collect();
+            (name.to_string(), matching)
+        })
+        .collect();
+    // Threshold breaches are violations too, but only reported when configured.
+    if !metrics.thresholds.is_empty() {
+        let matching = violations
+            .iter()
+            .filter(|violation| violation.code == "threshold")
+            .collect();
+        checks.push(("thresholds".to_string(), matching));
+    }
+    for violation in violations {
+        let known = JUNIT_CHECKS
+            .iter()
+            .any(|(_, codes)| codes.contains(&violation.code.as_str()));
+        if known || violation.code == "threshold" {
+            continue;
+        }
+        match checks.iter_mut().find(|(name, _)| *name == violation.code) {
+            Some((_, matching)) => matching.push(violation),
+            None => checks.push((violation.code.clone(), vec![violation])),
+        }
+    }
+
+    let failures = checks
+        .iter()
+        .filter(|(_, matching)| !matching.is_empty())
+        .count();
+    let suite = format!("datalchemy-eval.{}", metrics.run_id);
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(
+            r#"<testsuites name="datalchemy-eval" tests="{}" failures="{failures}">"#,
+            checks.len()
+        ),
+        format!(
+            r#"  <testsuite name="{}" tests="{}" failures="{failures}" errors="0" skipped="0">"#,
+            xml_escape(&suite),
+            checks.len()
+        ),
+        "    <properties>".to_string(),
+        format!(
+            r#"      <property name="run_id" value="{}"/>"#,
+            xml_escape(&metrics.run_id)
+        ),
+        format!(
+            r#"      <property name="metrics_version" value="{}"/>"#,
+            xml_escape(&metrics.metrics_version)
+        ),
+        format!(
+            r#"      <property name="warnings" value="{}"/>"#,
+            metrics.warnings.len()
+        ),
+        "    </properties>".to_string(),
+    ];
+    for (name, matching) in &checks {
+        let open = format!(
+            r#"    <testcase classname="{}" name="{}""#,
+            xml_escape(&suite),
+            xml_escape(name)
+        );
+        if matching.is_empty() {
+            lines.push(format!("{open}/>"));
+            continue;
+        }
+        lines.push(format!("{open}>"));
+        lines.push(format!(
+            r#"      <failure type="{}" message="{} violation(s)">"#,
+            xml_escape(name),
+            matching.len()
+        ));
+        for violation in matching.iter().take(max_examples) {
+            let row = violation
+                .row_index
+                .map(|row| format!(" (row {row})"))
+                .unwrap_or_default();
+            lines.push(xml_escape(&format!(
+                "{}{}: {}",
+                violation.path, row, violation.message
+            )));
+        }
+        if matching.len() > max_examples {
+            lines.push(format!("... {} more", matching.len() - max_examples));
+        }
+        lines.push("      </failure>".to_string());
+        lines.push("    </testcase>".to_string());
+    }
+    lines.push("  </testsuite>".to_string());
+    lines.push("</testsuites>".to_string());
+    lines.join("\n") + "\n"
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
 fn push_constraint_row(
     lines: &mut Vec<String>,
     name: &str,
@@ -129,9 +768,86 @@ fn recommendations(metrics: &MetricsReport, violations: &[Violation]) -> Vec<Str
     if metrics.constraints.fk.violations > 0 {
         lines.push("- ensure parent tables are generated before children.".to_string());
     }
+    if metrics
+        .duplicates
+        .iter()
+        .any(|stats| stats.kind == DuplicateKind::Row && stats.duplicate_rows > 0)
+    {
+        lines.push(
+            "- deduplicate repeated rows or add a unique generator to tell them apart.".to_string(),
+        );
+    }
+    if metrics.fan_out.iter().any(|stats| stats.uniform) {
+        lines.push(
+            "- skew FK generators of uniform relations so some parents have many children."
+                .to_string(),
+        );
+    }
+    if metrics.temporal.iter().any(|check| check.failures > 0) {
+        lines.push(
+            "- order dependent dates with derive.updated_after_created / derive.end_after_start."
+                .to_string(),
+        );
+    }
+    if metrics
+        .assertions
+        .iter()
+        .any(|assertion| assertion.status == AssertionStatus::Failed)
+    {
+        lines.push(
+            "- tune generators (or the declared intent) of tables with failed assertions."
+                .to_string(),
+        );
+    }
     if metrics.constraints.check.not_evaluated > 0 {
         lines.push("- simplify CHECK expressions or switch policy to warn/ignore.".to_string());
     }
+    if metrics
+        .distributions
+        .iter()
+        .any(|distribution| distribution.diverges)
+    {
+        lines.push(
+            "- tune generators of diverging columns (distributions, weights, ranges).".to_string(),
+        );
+    }
+    if metrics.realism.iter().any(|realism| {
+        realism.tables.iter().any(|table| {
+            table
+                .columns
+                .iter()
+                .any(|column| column.similarity < LOW_SIMILARITY)
+        })
+    }) {
+        lines.push(
+            "- weight categorical generators by the source frequencies of low-similarity columns."
+                .to_string(),
+        );
+    }
+    if metrics.privacy.iter().any(|privacy| {
+        privacy.classes_below_k > 0
+            || privacy
+                .l_diversity
+                .iter()
+                .any(|diversity| diversity.classes_below_l > 0)
+    }) {
+        lines.push(
+            "- generalize quasi-identifiers (ranges, prefixes) before sharing the dataset."
+                .to_string(),
+        );
+    }
+    if metrics.thresholds.iter().any(|result| !result.passed) {
+        lines.push("- fix the columns and relations breaking configured thresholds.".to_string());
+    }
+    if !metrics.pii_findings.is_empty() {
+        lines.push(
+            "- replace real-looking PII with semantic generators before sharing the dataset."
+                .to_string(),
+        );
+    }
+    if metrics.sampling.is_some() {
+        lines.push("- sampled evaluation: rerun on the full dataset before release.".to_string());
+    }
     if violations.is_empty() {
         lines.push("- no violations detected; compare metrics across runs for drift.".to_string());
     }
@@ -0,0 +1,149 @@
+//! Sampled evaluation for fast feedback while iterating on a plan.
+//!
+//! With `EvaluateOptions::sample_rows`, every table file is still read once,
+//! but only a uniform reservoir sample of its rows is profiled and checked.
+//! Row counts, NOT NULL counts and the parent keys of FK checks cover the
+//! whole file; CHECK and FK failures found in the sample are extrapolated to
+//! the table with a 95% Wilson interval.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::metrics::SampleEstimate;
+
+/// z-score of the 95% confidence intervals.
+pub const CONFIDENCE_Z: f64 = 1.96;
+
+/// Uniform sample of at most `capacity` items (algorithm R), reproducible for
+/// a given seed.
+#[derive(Debug, Clone)]
+pub struct Reservoir<T> {
+    capacity: usize,
+    seen: u64,
+    items: Vec<(u64, T)>,
+    state: u64,
+}
+
+impl<T> Reservoir<T> {
+    pub fn new(capacity: usize, seed: u64) -> Self {
+        Self {
+            capacity,
+            seen: 0,
+            items: Vec::with_capacity(capacity.min(1 << 16)),
+            state: seed,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        let position = self.seen;
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push((position, item));
+            return;
+        }
+        let slot = self.next_u64() % self.seen;
+        if (slot as usize) < self.capacity {
+            self.items[slot as usize] = (position, item);
+        }
+    }
+
+    /// Items pushed so far, sampled or not.
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Sampled items in the order they were pushed.
+    pub fn into_items(mut self) -> Vec<T> {
+        self.items.sort_by_key(|(position, _)| *position);
+        self.items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    // splitmix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+}
+
+/// Seed of a table's reservoir, derived from the plan seed.
+pub fn table_seed(seed: u64, schema: &str, table: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (seed, schema, table).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 95% Wilson score interval of a failure rate observed in `sampled` rows.
+pub fn wilson_interval(failures: u64, sampled: u64) -> (f64, f64) {
+    if sampled == 0 {
+        return (0.0, 1.0);
+    }
+    let n = sampled as f64;
+    let p = failures as f64 / n;
+    let z2 = CONFIDENCE_Z * CONFIDENCE_Z;
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let half = CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+    ((center - half).max(0.0), (center + half).min(1.0))
+}
+
+/// Extrapolate `failures` among `sampled` rows to a table of `total` rows.
+///
+/// A table read in full (`sampled == total`) gets an exact estimate.
+pub fn estimate(
+    path: &str,
+    metric: &str,
+    sampled: u64,
+    failures: u64,
+    total: u64,
+) -> SampleEstimate {
+    let rate = if sampled == 0 {
+        0.0
+    } else {
+        failures as f64 / sampled as f64
+    };
+    let (rate_low, rate_high) = if sampled >= total {
+        (rate, rate)
+    } else {
+        wilson_interval(failures, sampled)
+    };
+    let scale = |rate: f64| (rate * total as f64).round() as u64;
+    SampleEstimate {
+        path: path.to_string(),
+        metric: metric.to_string(),
+        rows_sampled: sampled,
+        failures,
+        rate,
+        rate_low,
+        rate_high,
+        estimated_failures: scale(rate),
+        estimated_low: scale(rate_low).max(failures),
+        estimated_high: scale(rate_high),
+    }
+}
+
+/// Caveats of a sampled evaluation, for the report.
+pub fn sampling_notes(has_privacy: bool) -> Vec<String> {
+    let mut notes = vec![
+        "row counts, NOT NULL counts and FK parent keys cover every row; CHECK and FK \
+         failures are extrapolated from the sample (95% Wilson interval)"
+            .to_string(),
+        "row numbers in violations count rows of the sample, not of the file".to_string(),
+        "duplicate keys found in the sample are real, but a clean sample does not prove \
+         uniqueness"
+            .to_string(),
+        "column profiles, correlations and distributions describe the sample".to_string(),
+        "plan assertions aggregate the sample, except count(*) (every row)".to_string(),
+        "FK fan-out is not measured: sampled children undercount every parent".to_string(),
+    ];
+    if has_privacy {
+        notes.push(
+            "k-anonymity and l-diversity are computed on the sample and understate the \
+             full table"
+                .to_string(),
+        );
+    }
+    notes
+}
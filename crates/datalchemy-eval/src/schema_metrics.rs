@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use datalchemy_core::{Constraint, DatabaseSchema, build_fk_graph_report};
+use datalchemy_core::{
+    Constraint, DatabaseSchema, ForeignKey, Table, TableEvent, build_fk_graph_report,
+};
+
+/// Tables with more columns than this are reported as wide.
+pub const WIDE_TABLE_COLUMNS: usize = 50;
+/// Tables listed in [`SchemaMetrics::widest_tables`].
+pub const WIDEST_TABLES: usize = 5;
 
 /// Top-level metrics report for a schema snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +17,15 @@ pub struct SchemaMetrics {
     pub counts: SchemaCounts,
     pub coverage: CoverageMetrics,
     pub fk_graph: FkGraphMetrics,
+    /// Index coverage and nullability of each foreign key.
+    #[serde(default)]
+    pub foreign_keys: Vec<ForeignKeyCoverage>,
+    /// `schema.table` of the tables without a primary key.
+    #[serde(default)]
+    pub tables_without_pk: Vec<String>,
+    /// The [`WIDEST_TABLES`] tables with the most columns, widest first.
+    #[serde(default)]
+    pub widest_tables: Vec<TableWidth>,
     pub warnings: Vec<String>,
 }
 
@@ -20,6 +36,12 @@ pub struct SchemaCounts {
     pub tables: usize,
     pub columns: usize,
     pub constraints: ConstraintCounts,
+    /// Triggers (`CREATE TRIGGER`), not run by generation.
+    #[serde(default)]
+    pub triggers: usize,
+    /// Rewrite rules (`CREATE RULE`), not run by generation.
+    #[serde(default)]
+    pub rules: usize,
 }
 
 /// Count summary for constraint types.
@@ -29,6 +51,9 @@ pub struct ConstraintCounts {
     pub foreign_keys: usize,
     pub unique: usize,
     pub checks: usize,
+    /// Exclusion constraints (`EXCLUDE USING ...`), not enforced by generation.
+    #[serde(default)]
+    pub exclusions: usize,
 }
 
 /// Coverage metrics for the schema.
@@ -37,6 +62,34 @@ pub struct CoverageMetrics {
     pub tables_with_pk_pct: f64,
     pub tables_with_fk_pct: f64,
     pub columns_not_null_pct: f64,
+    /// Foreign keys whose columns lead an index, PK or UNIQUE constraint.
+    #[serde(default)]
+    pub fks_with_index_pct: f64,
+    /// Foreign keys with at least one nullable column.
+    #[serde(default)]
+    pub nullable_fks_pct: f64,
+}
+
+/// One foreign key: whether lookups by its columns are indexed and whether
+/// child rows may leave it null.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForeignKeyCoverage {
+    /// `schema.table` of the child table.
+    pub table: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    /// `schema.table` of the parent table.
+    pub references: String,
+    pub indexed: bool,
+    pub nullable: bool,
+}
+
+/// Column count of a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableWidth {
+    pub table: String,
+    pub columns: usize,
 }
 
 /// FK graph metrics for the schema.
@@ -46,6 +99,9 @@ pub struct FkGraphMetrics {
     pub has_cycle: bool,
     pub cycle: Option<Vec<String>>,
     pub topo_order: Option<Vec<String>>,
+    /// Materialized views to refresh after loading, in dependency order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refresh_order: Vec<String>,
 }
 
 /// Collect metrics for a given schema snapshot.
@@ -59,18 +115,43 @@ pub fn collect_schema_metrics(schema: &DatabaseSchema) -> SchemaMetrics {
             foreign_keys: 0,
             unique: 0,
             checks: 0,
+            exclusions: 0,
         },
+        triggers: 0,
+        rules: 0,
     };
 
     let mut tables_with_pk = 0usize;
     let mut tables_with_fk = 0usize;
     let mut not_null_columns = 0usize;
+    let mut foreign_keys = Vec::new();
+    let mut tables_without_pk = Vec::new();
+    let mut widest_tables = Vec::new();
+    let mut insert_side_effects = Vec::new();
 
     for db_schema in &schema.schemas {
         counts.schemas += 1;
         for table in &db_schema.tables {
             counts.tables += 1;
             counts.columns += table.columns.len();
+            counts.triggers += table.triggers.len();
+            counts.rules += table.rules.len();
+            let fired: Vec<String> = table
+                .triggers
+                .iter()
+                .filter(|trigger| trigger.fires_on_insert())
+                .map(|trigger| format!("trigger {}", trigger.name))
+                .chain(
+                    table
+                        .rules
+                        .iter()
+                        .filter(|rule| rule.event == TableEvent::Insert)
+                        .map(|rule| format!("rule {}", rule.name)),
+                )
+                .collect();
+            if !fired.is_empty() {
+                insert_side_effects.push((format!("{}.{}", db_schema.name, table.name), fired));
+            }
             not_null_columns += table.columns.iter().filter(|col| !col.is_nullable).count();
 
             let mut has_pk = false;
@@ -82,9 +163,10 @@ pub fn collect_schema_metrics(schema: &DatabaseSchema) -> SchemaMetrics {
                         counts.constraints.primary_keys += 1;
                         has_pk = true;
                     }
-                    Constraint::ForeignKey(_) => {
+                    Constraint::ForeignKey(fk) => {
                         counts.constraints.foreign_keys += 1;
                         has_fk = true;
+                        foreign_keys.push(foreign_key_coverage(&db_schema.name, table, fk));
                     }
                     Constraint::Unique(_) => {
                         counts.constraints.unique += 1;
@@ -92,12 +174,21 @@ pub fn collect_schema_metrics(schema: &DatabaseSchema) -> SchemaMetrics {
                     Constraint::Check(_) => {
                         counts.constraints.checks += 1;
                     }
+                    Constraint::Exclusion(_) => {
+                        counts.constraints.exclusions += 1;
+                    }
                 }
             }
 
             if has_pk {
                 tables_with_pk += 1;
+            } else {
+                tables_without_pk.push(format!("{}.{}", db_schema.name, table.name));
             }
+            widest_tables.push(TableWidth {
+                table: format!("{}.{}", db_schema.name, table.name),
+                columns: table.columns.len(),
+            });
             if has_fk {
                 tables_with_fk += 1;
             }
@@ -106,6 +197,14 @@ pub fn collect_schema_metrics(schema: &DatabaseSchema) -> SchemaMetrics {
 
     let total_tables = counts.tables as f64;
     let total_columns = counts.columns as f64;
+    let total_fks = foreign_keys.len() as f64;
+    let ratio = |part: usize, total: f64| {
+        if total > 0.0 {
+            part as f64 / total
+        } else {
+            0.0
+        }
+    };
 
     let coverage = CoverageMetrics {
         tables_with_pk_pct: if total_tables > 0.0 {
@@ -123,14 +222,44 @@ pub fn collect_schema_metrics(schema: &DatabaseSchema) -> SchemaMetrics {
         } else {
             0.0
         },
+        fks_with_index_pct: ratio(
+            foreign_keys.iter().filter(|fk| fk.indexed).count(),
+            total_fks,
+        ),
+        nullable_fks_pct: ratio(
+            foreign_keys.iter().filter(|fk| fk.nullable).count(),
+            total_fks,
+        ),
     };
 
+    widest_tables.sort_by(|a, b| {
+        b.columns
+            .cmp(&a.columns)
+            .then_with(|| a.table.cmp(&b.table))
+    });
+    let mut warnings = schema_warnings(&foreign_keys, &tables_without_pk, &widest_tables);
+    for (table, fired) in insert_side_effects {
+        warnings.push(format!(
+            "table {table} runs {} on insert; loading the generated rows with INSERT may change \
+             or reject them",
+            fired.join(", ")
+        ));
+    }
+    for failed in &schema.failed_tables {
+        warnings.push(format!(
+            "table {}.{} could not be introspected ({}); it is missing from the schema",
+            failed.schema, failed.table, failed.error
+        ));
+    }
+    widest_tables.truncate(WIDEST_TABLES);
+
     let graph_report = build_fk_graph_report(schema);
     let fk_graph = FkGraphMetrics {
         edges: graph_report.summary.edges,
         has_cycle: graph_report.cycle.is_some(),
         cycle: graph_report.cycle,
         topo_order: graph_report.topo_order,
+        refresh_order: graph_report.refresh_order,
     };
 
     SchemaMetrics {
@@ -139,6 +268,124 @@ pub fn collect_schema_metrics(schema: &DatabaseSchema) -> SchemaMetrics {
         counts,
         coverage,
         fk_graph,
-        warnings: Vec::new(),
+        foreign_keys,
+        tables_without_pk,
+        widest_tables,
+        warnings,
+    }
+}
+
+/// Schema issues worth fixing before planning.
+fn schema_warnings(
+    foreign_keys: &[ForeignKeyCoverage],
+    tables_without_pk: &[String],
+    widest_tables: &[TableWidth],
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for table in tables_without_pk {
+        warnings.push(format!(
+            "table {table} has no primary key; generated rows cannot be told apart or referenced"
+        ));
+    }
+    for fk in foreign_keys.iter().filter(|fk| !fk.indexed) {
+        warnings.push(format!(
+            "foreign key {}({}) -> {} has no covering index; parent deletes and joins scan {}",
+            fk.table,
+            fk.columns.join(", "),
+            fk.references,
+            fk.table
+        ));
+    }
+    let nullable: Vec<String> = foreign_keys
+        .iter()
+        .filter(|fk| fk.nullable)
+        .map(|fk| format!("{}({})", fk.table, fk.columns.join(", ")))
+        .collect();
+    if !nullable.is_empty() {
+        warnings.push(format!(
+            "{} of {} foreign keys allow nulls ({}); set their null rate in the plan",
+            nullable.len(),
+            foreign_keys.len(),
+            nullable.join(", ")
+        ));
+    }
+    for table in widest_tables
+        .iter()
+        .filter(|table| table.columns > WIDE_TABLE_COLUMNS)
+    {
+        warnings.push(format!(
+            "table {} has {} columns (more than {WIDE_TABLE_COLUMNS}); consider splitting it",
+            table.table, table.columns
+        ));
+    }
+    warnings
+}
+
+fn foreign_key_coverage(schema: &str, table: &Table, fk: &ForeignKey) -> ForeignKeyCoverage {
+    let leads = |columns: &[String]| {
+        columns.len() >= fk.columns.len()
+            && fk
+                .columns
+                .iter()
+                .all(|column| columns[..fk.columns.len()].contains(column))
+    };
+    let indexed = table
+        .indexes
+        .iter()
+        .any(|index| leads(&index_columns(&index.definition)))
+        || table.constraints.iter().any(|constraint| match constraint {
+            Constraint::PrimaryKey(pk) => leads(&pk.columns),
+            Constraint::Unique(unique) => leads(&unique.columns),
+            _ => false,
+        });
+    let nullable = fk.columns.iter().any(|name| {
+        table
+            .columns
+            .iter()
+            .any(|column| &column.name == name && column.is_nullable)
+    });
+    ForeignKeyCoverage {
+        table: format!("{schema}.{}", table.name),
+        name: fk.name.clone(),
+        columns: fk.columns.clone(),
+        references: format!("{}.{}", fk.referenced_schema, fk.referenced_table),
+        indexed,
+        nullable,
+    }
+}
+
+/// Key columns of a `CREATE INDEX ... USING method (a, b DESC)` definition;
+/// expressions come back as written and match no column.
+fn index_columns(definition: &str) -> Vec<String> {
+    let Some(using) = definition.find(" USING ") else {
+        return Vec::new();
+    };
+    let rest = &definition[using..];
+    let Some(open) = rest.find('(') else {
+        return Vec::new();
+    };
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for ch in rest[open + 1..].chars() {
+        match ch {
+            '(' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' if depth == 0 => break,
+            ')' => {
+                depth -= 1;
+                current.push(ch);
+            }
+            ',' if depth == 0 => items.push(std::mem::take(&mut current)),
+            _ => current.push(ch),
+        }
     }
+    items.push(current);
+    items
+        .iter()
+        .filter_map(|item| item.split_whitespace().next())
+        .map(|column| column.trim_matches('"').to_string())
+        .collect()
 }
@@ -0,0 +1,234 @@
+//! Disk-backed key sets for streaming evaluation.
+//!
+//! Keys are hashed to 128 bits and appended to one of [`SPILL_PARTITIONS`]
+//! files, so duplicate and orphan checks only hold one partition in memory at
+//! a time. Two distinct keys sharing both hashes are treated as equal; at 128
+//! bits that is negligible even for billions of rows.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Number of partition files per key set.
+pub const SPILL_PARTITIONS: usize = 64;
+
+/// Buffered bytes per partition before they are appended to disk.
+const FLUSH_BYTES: usize = 256 * 1024;
+
+const NO_LABEL: u32 = u32::MAX;
+
+/// Key set being written: `(key, row, label)` entries in row order.
+#[derive(Debug)]
+pub struct KeySpill {
+    dir: PathBuf,
+    buffers: Vec<Vec<u8>>,
+    entries: u64,
+}
+
+impl KeySpill {
+    /// Start a key set whose partitions live in `dir` (created if needed).
+    pub fn create(dir: &Path) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        for partition in 0..SPILL_PARTITIONS {
+            File::create(partition_path(dir, partition))?;
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            buffers: vec![Vec::new(); SPILL_PARTITIONS],
+            entries: 0,
+        })
+    }
+
+    /// Append `key` found at `row`; `label` is an optional description of the
+    /// row (e.g. its primary key) kept for reporting.
+    pub fn insert(&mut self, key: &str, row: u64, label: Option<&str>) -> std::io::Result<()> {
+        let hash = key_hash(key);
+        let partition = (hash.0 % SPILL_PARTITIONS as u64) as usize;
+        let buffer = &mut self.buffers[partition];
+        buffer.extend_from_slice(&hash.0.to_le_bytes());
+        buffer.extend_from_slice(&hash.1.to_le_bytes());
+        buffer.extend_from_slice(&row.to_le_bytes());
+        write_str(buffer, Some(key));
+        write_str(buffer, label);
+        self.entries += 1;
+        if buffer.len() >= FLUSH_BYTES {
+            self.flush_partition(partition)?;
+        }
+        Ok(())
+    }
+
+    /// Flush pending entries and make the set readable.
+    pub fn finish(mut self) -> std::io::Result<SpilledKeys> {
+        for partition in 0..SPILL_PARTITIONS {
+            self.flush_partition(partition)?;
+        }
+        Ok(SpilledKeys {
+            dir: self.dir,
+            entries: self.entries,
+        })
+    }
+
+    fn flush_partition(&mut self, partition: usize) -> std::io::Result<()> {
+        let buffer = &mut self.buffers[partition];
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(partition_path(&self.dir, partition))?;
+        file.write_all(buffer)?;
+        buffer.clear();
+        Ok(())
+    }
+}
+
+/// One entry read back from a [`SpilledKeys`] partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpilledKey {
+    pub row: u64,
+    pub key: String,
+    pub label: Option<String>,
+}
+
+/// Key found at more than one row of a [`SpilledKeys`] set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKey {
+    pub key: String,
+    /// Every row holding the key, ascending; the first is the original.
+    pub rows: Vec<u64>,
+}
+
+/// Finished key set on disk.
+#[derive(Debug)]
+pub struct SpilledKeys {
+    dir: PathBuf,
+    entries: u64,
+}
+
+impl SpilledKeys {
+    pub fn len(&self) -> u64 {
+        self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries == 0
+    }
+
+    /// Call `on_duplicate` once for every key found at more than one row.
+    /// Partitions are visited in turn, so calls are not in row order across
+    /// partitions.
+    pub fn for_each_duplicate(
+        &self,
+        mut on_duplicate: impl FnMut(DuplicateKey),
+    ) -> std::io::Result<()> {
+        for partition in 0..SPILL_PARTITIONS {
+            let mut first_rows = HashMap::new();
+            let mut duplicates: HashMap<(u64, u64), DuplicateKey> = HashMap::new();
+            read_partition(&self.dir, partition, |hash, entry| {
+                match first_rows.get(&hash) {
+                    None => {
+                        first_rows.insert(hash, entry.row);
+                    }
+                    Some(&first_row) => duplicates
+                        .entry(hash)
+                        .or_insert_with(|| DuplicateKey {
+                            key: entry.key,
+                            rows: vec![first_row],
+                        })
+                        .rows
+                        .push(entry.row),
+                }
+            })?;
+            for (_, duplicate) in duplicates {
+                on_duplicate(duplicate);
+            }
+        }
+        Ok(())
+    }
+
+    /// Call `on_missing` for every entry whose key is absent from `parent`.
+    pub fn for_each_missing(
+        &self,
+        parent: &SpilledKeys,
+        mut on_missing: impl FnMut(SpilledKey),
+    ) -> std::io::Result<()> {
+        for partition in 0..SPILL_PARTITIONS {
+            let mut parent_keys = HashSet::new();
+            read_partition(&parent.dir, partition, |hash, _| {
+                parent_keys.insert(hash);
+            })?;
+            read_partition(&self.dir, partition, |hash, entry| {
+                if !parent_keys.contains(&hash) {
+                    on_missing(entry);
+                }
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn key_hash(key: &str) -> (u64, u64) {
+    let mut first = DefaultHasher::new();
+    key.hash(&mut first);
+    let mut second = DefaultHasher::new();
+    0x9e37_79b9_7f4a_7c15u64.hash(&mut second);
+    key.hash(&mut second);
+    (first.finish(), second.finish())
+}
+
+fn partition_path(dir: &Path, partition: usize) -> PathBuf {
+    dir.join(format!("part-{partition:03}.bin"))
+}
+
+fn write_str(buffer: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(value.as_bytes());
+        }
+        None => buffer.extend_from_slice(&NO_LABEL.to_le_bytes()),
+    }
+}
+
+fn read_partition(
+    dir: &Path,
+    partition: usize,
+    mut on_entry: impl FnMut((u64, u64), SpilledKey),
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(File::open(partition_path(dir, partition))?);
+    loop {
+        let mut head = [0u8; 24];
+        match reader.read_exact(&mut head) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        }
+        let word = |idx: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&head[idx * 8..idx * 8 + 8]);
+            u64::from_le_bytes(bytes)
+        };
+        let hash = (word(0), word(1));
+        let row = word(2);
+        let key = read_str(&mut reader)?.unwrap_or_default();
+        let label = read_str(&mut reader)?;
+        on_entry(hash, SpilledKey { row, key, label });
+    }
+}
+
+fn read_str(reader: &mut impl Read) -> std::io::Result<Option<String>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len);
+    if len == NO_LABEL {
+        return Ok(None);
+    }
+    let mut bytes = vec![0u8; len as usize];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
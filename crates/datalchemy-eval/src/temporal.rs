@@ -0,0 +1,310 @@
+//! Temporal invariants (see [`TemporalCheck`]).
+//!
+//! Rules come from three places:
+//! - column name pairs of the same table, e.g. `updated_at >= created_at`,
+//!   `data_fim >= data_inicio` (see [`NAME_PAIRS`]);
+//! - `derive.updated_after_created` / `derive.end_after_start` plan rules
+//!   (`column >= input_columns[0]`);
+//! - explicit `min`/`max` params of `primitive.date*` / `primitive.timestamp*`
+//!   plan rules, the window the plan generates the column in (ISO, or the
+//!   plan's `global.formats` patterns).
+//!
+//! Rows with a null in a rule's columns are not checked. Dates compare with
+//! timestamps as midnight; a date `max` bound covers the whole day.
+
+use chrono::{NaiveDateTime, NaiveTime};
+use datalchemy_core::{Column, Table};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::output::value_formats;
+use datalchemy_plan::{Plan, Rule, ValueFormats};
+
+use crate::engine::{parse_date, parse_timestamp};
+use crate::metrics::{TemporalCheck, TemporalSource};
+use crate::model::{Violation, ViolationSample};
+
+/// Name tokens of an earlier column and of the later column it pairs with.
+pub const NAME_PAIRS: &[(&str, &str)] = &[
+    ("created", "updated"),
+    ("created", "modified"),
+    ("criacao", "atualizacao"),
+    ("criado", "atualizado"),
+    ("start", "end"),
+    ("starts", "ends"),
+    ("started", "ended"),
+    ("begin", "end"),
+    ("inicio", "fim"),
+    ("from", "to"),
+    ("from", "until"),
+    ("issued", "due"),
+    ("emissao", "vencimento"),
+];
+
+const ORDER_GENERATORS: &[&str] = &["derive.updated_after_created", "derive.end_after_start"];
+const WINDOW_GENERATORS: &[&str] = &[
+    "primitive.date",
+    "primitive.date.range",
+    "primitive.timestamp",
+    "primitive.timestamp.range",
+];
+
+/// One temporal invariant of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemporalRule {
+    /// `later >= earlier`.
+    Order {
+        earlier: String,
+        later: String,
+        source: TemporalSource,
+    },
+    /// `min <= column <= max`.
+    Window {
+        column: String,
+        min: Option<NaiveDateTime>,
+        max: Option<NaiveDateTime>,
+    },
+}
+
+impl TemporalRule {
+    /// Columns whose values [`TemporalTally::add`] expects, in order.
+    pub fn columns(&self) -> Vec<&str> {
+        match self {
+            TemporalRule::Order { earlier, later, .. } => vec![earlier, later],
+            TemporalRule::Window { column, .. } => vec![column],
+        }
+    }
+
+    pub fn source(&self) -> TemporalSource {
+        match self {
+            TemporalRule::Order { source, .. } => *source,
+            TemporalRule::Window { .. } => TemporalSource::PlanWindow,
+        }
+    }
+
+    /// Human-readable form, e.g. `updated_at >= created_at`.
+    pub fn describe(&self) -> String {
+        match self {
+            TemporalRule::Order { earlier, later, .. } => format!("{later} >= {earlier}"),
+            TemporalRule::Window { column, min, max } => format!(
+                "{column} in [{}, {}]",
+                min.map(|value| value.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                max.map(|value| value.to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+        }
+    }
+}
+
+/// Temporal rules of `schema.table`, from its column names and the plan.
+pub fn temporal_rules(schema: &str, table: &Table, plan: &Plan) -> Vec<TemporalRule> {
+    let formats = value_formats(plan);
+    let mut rules = Vec::new();
+    for rule in &plan.rules {
+        let Rule::ColumnGenerator(rule) = rule else {
+            continue;
+        };
+        if rule.schema != schema || rule.table != table.name {
+            continue;
+        }
+        let generator = rule.generator_id();
+        let params = rule.generator_params();
+        if ORDER_GENERATORS.contains(&generator) {
+            let earlier = params
+                .and_then(|params| params.get("input_columns"))
+                .and_then(|value| value.as_array())
+                .and_then(|columns| columns.first())
+                .and_then(|value| value.as_str());
+            if let Some(earlier) = earlier.and_then(|name| find_column(table, name)) {
+                push_order(
+                    &mut rules,
+                    &earlier.name,
+                    &rule.column,
+                    TemporalSource::PlanDerive,
+                );
+            }
+        } else if WINDOW_GENERATORS.contains(&generator) {
+            let bound = |key: &str, end_of_day: bool| {
+                params
+                    .and_then(|params| params.get(key))
+                    .and_then(|value| value.as_str())
+                    .and_then(|value| parse_bound(value, end_of_day, &formats))
+            };
+            let (min, max) = (bound("min", false), bound("max", true));
+            if min.is_some() || max.is_some() {
+                rules.push(TemporalRule::Window {
+                    column: rule.column.clone(),
+                    min,
+                    max,
+                });
+            }
+        }
+    }
+
+    for column in table.columns.iter().filter(|column| is_temporal(column)) {
+        let name = column.name.to_lowercase();
+        let tokens: Vec<&str> = name.split('_').collect();
+        for (earlier, later) in NAME_PAIRS {
+            for (idx, token) in tokens.iter().enumerate() {
+                if token != earlier {
+                    continue;
+                }
+                let mut paired = tokens.clone();
+                paired[idx] = later;
+                let Some(other) = find_column(table, &paired.join("_")) else {
+                    continue;
+                };
+                if is_temporal(other) {
+                    push_order(
+                        &mut rules,
+                        &column.name,
+                        &other.name,
+                        TemporalSource::ColumnNames,
+                    );
+                }
+            }
+        }
+    }
+    rules
+}
+
+/// Outcome of one rule accumulated over rows (or chunks of rows).
+#[derive(Debug, Clone)]
+pub struct TemporalTally {
+    schema: String,
+    table: String,
+    rule: TemporalRule,
+    rows_checked: u64,
+    failures: u64,
+    samples: Vec<ViolationSample>,
+    limit: usize,
+}
+
+impl TemporalTally {
+    /// `limit` bounds the failing rows kept as violation samples.
+    pub fn new(schema: &str, table: &str, rule: TemporalRule, limit: usize) -> Self {
+        Self {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            rule,
+            rows_checked: 0,
+            failures: 0,
+            samples: Vec::new(),
+            limit,
+        }
+    }
+
+    pub fn rule(&self) -> &TemporalRule {
+        &self.rule
+    }
+
+    /// Check `row` given the values of [`TemporalRule::columns`].
+    pub fn add(&mut self, row: u64, values: &[&GeneratedValue]) {
+        let moments: Option<Vec<NaiveDateTime>> =
+            values.iter().map(|value| moment(value)).collect();
+        let Some(moments) = moments else {
+            return;
+        };
+        self.rows_checked += 1;
+        let failure = match (&self.rule, moments.as_slice()) {
+            (TemporalRule::Order { earlier, later, .. }, [start, end]) => {
+                (end < start).then(|| format!("{later} {end} < {earlier} {start}"))
+            }
+            (TemporalRule::Window { min, max, .. }, [value]) => {
+                let below = min.is_some_and(|min| *value < min);
+                let above = max.is_some_and(|max| *value > max);
+                (below || above).then(|| format!("{value} outside the plan window"))
+            }
+            _ => None,
+        };
+        if let Some(value) = failure {
+            self.failures += 1;
+            if self.samples.len() < self.limit {
+                self.samples.push(ViolationSample {
+                    row_index: row,
+                    key: None,
+                    value,
+                });
+            }
+        }
+    }
+
+    /// Metrics entry, and a `temporal` violation when some row failed.
+    pub fn finish(self) -> (TemporalCheck, Option<Violation>) {
+        let columns: Vec<String> = self
+            .rule
+            .columns()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let check = TemporalCheck {
+            schema: self.schema,
+            table: self.table,
+            columns,
+            rule: self.rule.describe(),
+            source: self.rule.source(),
+            rows_checked: self.rows_checked,
+            failures: self.failures,
+        };
+        let violation = (check.failures > 0).then(|| Violation {
+            code: "temporal".to_string(),
+            path: format!(
+                "{}.{}.{}",
+                check.schema,
+                check.table,
+                check.columns.join(",")
+            ),
+            message: format!("{} row(s) break {}", check.failures, check.rule),
+            row_index: self.samples.first().map(|sample| sample.row_index),
+            example: None,
+            samples: self.samples,
+        });
+        (check, violation)
+    }
+}
+
+fn push_order(rules: &mut Vec<TemporalRule>, earlier: &str, later: &str, source: TemporalSource) {
+    let exists = rules.iter().any(|rule| match rule {
+        TemporalRule::Order {
+            earlier: known_earlier,
+            later: known_later,
+            ..
+        } => known_earlier.eq_ignore_ascii_case(earlier) && known_later.eq_ignore_ascii_case(later),
+        TemporalRule::Window { .. } => false,
+    });
+    if !exists {
+        rules.push(TemporalRule::Order {
+            earlier: earlier.to_string(),
+            later: later.to_string(),
+            source,
+        });
+    }
+}
+
+fn find_column<'a>(table: &'a Table, name: &str) -> Option<&'a Column> {
+    table
+        .columns
+        .iter()
+        .find(|column| column.name.eq_ignore_ascii_case(name))
+}
+
+fn is_temporal(column: &Column) -> bool {
+    let data_type = column.column_type.data_type.to_lowercase();
+    data_type == "date" || data_type.starts_with("timestamp")
+}
+
+fn moment(value: &GeneratedValue) -> Option<NaiveDateTime> {
+    match value {
+        GeneratedValue::Timestamp(value) => Some(*value),
+        GeneratedValue::Date(value) => Some(value.and_time(NaiveTime::MIN)),
+        _ => None,
+    }
+}
+
+fn parse_bound(value: &str, end_of_day: bool, formats: &ValueFormats) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    match parse_date(value, formats) {
+        Some(date) if end_of_day => date.and_hms_milli_opt(23, 59, 59, 999),
+        Some(date) => Some(date.and_time(NaiveTime::MIN)),
+        None => parse_timestamp(value, formats),
+    }
+}
@@ -0,0 +1,122 @@
+//! Pass/fail thresholds on evaluation metrics.
+//!
+//! Each configured limit yields a [`ThresholdResult`]; failed ones become
+//! `threshold` violations, so they fail strict evaluations and CI gates.
+
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::{ColumnStats, ThresholdResult, WarningItem};
+
+/// Limits checked after the dataset is evaluated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvalThresholds {
+    /// Max share (0..=1) of child rows with a broken reference, per foreign key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fk_violation_rate: Option<f64>,
+    /// Limits on the profile of individual columns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnThreshold>,
+}
+
+impl EvalThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.max_fk_violation_rate.is_none() && self.columns.is_empty()
+    }
+}
+
+/// Limits of a column, or of every column of a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnThreshold {
+    /// `schema.table.column`, or `schema.table.*` for all columns of the table.
+    pub column: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_null_rate: Option<f64>,
+    /// Minimum distinct values / rows (the column's `cardinality_ratio`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_distinct_ratio: Option<f64>,
+}
+
+/// Rows and orphan rows of a checked foreign key.
+#[derive(Debug, Clone)]
+pub struct RelationRate {
+    /// `schema.table(columns) -> schema.table`, as in FK violations.
+    pub path: String,
+    /// Child table, `schema.table`.
+    pub table: String,
+    pub rows: u64,
+    pub orphans: u64,
+}
+
+/// Check `thresholds` against the column profiles and foreign keys.
+///
+/// Column thresholds matching no profiled column are reported as warnings.
+pub fn evaluate_thresholds(
+    thresholds: &EvalThresholds,
+    column_stats: &[ColumnStats],
+    relations: &[RelationRate],
+) -> (Vec<ThresholdResult>, Vec<WarningItem>) {
+    let mut results = Vec::new();
+    let mut warnings = Vec::new();
+
+    if let Some(limit) = thresholds.max_fk_violation_rate {
+        for relation in relations {
+            let actual = if relation.rows == 0 {
+                0.0
+            } else {
+                relation.orphans as f64 / relation.rows as f64
+            };
+            results.push(ThresholdResult {
+                path: relation.path.clone(),
+                metric: "max_fk_violation_rate".to_string(),
+                limit,
+                actual,
+                passed: actual <= limit,
+            });
+        }
+    }
+
+    for threshold in &thresholds.columns {
+        let matching: Vec<&ColumnStats> = column_stats
+            .iter()
+            .filter(|stats| column_matches(&threshold.column, stats))
+            .collect();
+        if matching.is_empty() {
+            warnings.push(WarningItem {
+                code: "threshold_column_missing".to_string(),
+                path: threshold.column.clone(),
+                message: "threshold column not found in dataset".to_string(),
+                hint: Some("use schema.table.column or schema.table.*".to_string()),
+            });
+            continue;
+        }
+        for stats in matching {
+            let path = format!("{}.{}.{}", stats.schema, stats.table, stats.column);
+            if let Some(limit) = threshold.max_null_rate {
+                results.push(ThresholdResult {
+                    path: path.clone(),
+                    metric: "max_null_rate".to_string(),
+                    limit,
+                    actual: stats.null_rate,
+                    passed: stats.null_rate <= limit,
+                });
+            }
+            if let Some(limit) = threshold.min_distinct_ratio {
+                results.push(ThresholdResult {
+                    path,
+                    metric: "min_distinct_ratio".to_string(),
+                    limit,
+                    actual: stats.cardinality_ratio,
+                    passed: stats.cardinality_ratio >= limit,
+                });
+            }
+        }
+    }
+    (results, warnings)
+}
+
+fn column_matches(pattern: &str, stats: &ColumnStats) -> bool {
+    match pattern.strip_suffix(".*") {
+        Some(table) => table == format!("{}.{}", stats.schema, stats.table),
+        None => pattern == format!("{}.{}.{}", stats.schema, stats.table, stats.column),
+    }
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{AssertionStatus, EvaluateOptions, EvaluationEngine, EvaluationResult};
+use datalchemy_plan::Plan;
+
+fn plan() -> Plan {
+    serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 7,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "crm", "table": "produtos", "rows": 4 }],
+        "rules": [],
+        "assertions": [
+            { "schema": "crm", "table": "produtos", "expr": "count(*) = 4" },
+            {
+                "name": "preco medio",
+                "schema": "crm",
+                "table": "produtos",
+                "expr": "avg(preco_base) between 10 and 20"
+            },
+            {
+                "schema": "crm",
+                "table": "produtos",
+                "expr": "null_rate(descricao) <= 0.5 and (max(preco_base) - min(preco_base)) / 10 = count(distinct ativo) + 1"
+            },
+            { "schema": "crm", "table": "produtos", "expr": "sum(nome) > 0" },
+            { "schema": "crm", "table": "usuarios", "expr": "count(*) > 0" }
+        ]
+    }))
+    .expect("parse plan")
+}
+
+fn evaluate(dataset_dir: &Path, streaming: bool) -> EvaluationResult {
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        streaming,
+        chunk_rows: 3,
+        spill_dir: Some(temp_dir("assertions_spill")),
+        out_dir: Some(temp_dir("assertions_eval")),
+        ..Default::default()
+    })
+    .run(&load_schema(), &plan(), dataset_dir)
+    .expect("evaluate dataset")
+}
+
+#[test]
+fn plan_assertions_are_evaluated() {
+    let produtos = [
+        "id,sku,nome,descricao,ativo,preco_base,data_criacao",
+        "6d4fbb48-7194-4247-95b3-0dc2c475d8bf,SKU-1,Caneta,azul,true,10.00,2024-01-01T00:00:00",
+        "6a23022b-f425-47d9-ad38-63caf15ece84,SKU-2,Lapis,,true,20.00,2024-01-01T00:00:00",
+        "1b0f3a4e-8f55-4c39-9d43-9f1b2f0f4a10,SKU-3,Borracha,branca,false,30.00,2024-01-01T00:00:00",
+        "9c7e2d1a-5b3f-4e8a-8d6c-2f1e0a9b8c7d,SKU-4,Caderno,,true,40.00,2024-01-01T00:00:00",
+    ]
+    .join("\n");
+    let dataset_dir = temp_dir("assertions");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.produtos.csv"), produtos).expect("write produtos");
+
+    let in_memory = evaluate(&dataset_dir, false);
+    let streamed = evaluate(&dataset_dir, true);
+
+    for result in [&in_memory, &streamed] {
+        let statuses: Vec<(&str, AssertionStatus)> = result
+            .metrics
+            .assertions
+            .iter()
+            .map(|assertion| (assertion.name.as_str(), assertion.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("count(*) = 4", AssertionStatus::Passed),
+                ("preco medio", AssertionStatus::Failed),
+                (
+                    "null_rate(descricao) <= 0.5 and (max(preco_base) - min(preco_base)) / 10 = count(distinct ativo) + 1",
+                    AssertionStatus::Passed
+                ),
+                ("sum(nome) > 0", AssertionStatus::Error),
+                ("count(*) > 0", AssertionStatus::Error),
+            ]
+        );
+
+        let failed: Vec<&str> = result
+            .violations
+            .iter()
+            .filter(|violation| violation.code == "assertion")
+            .map(|violation| violation.message.as_str())
+            .collect();
+        assert_eq!(
+            failed,
+            vec![
+                "assertion 'preco medio' failed: avg(preco_base) between 10 and 20 \
+                 (avg(preco_base) = 25)"
+            ]
+        );
+        let not_evaluated: Vec<&str> = result
+            .metrics
+            .warnings
+            .iter()
+            .filter(|warning| warning.code == "assertion_not_evaluated")
+            .map(|warning| warning.path.as_str())
+            .collect();
+        assert_eq!(not_evaluated, vec!["crm.produtos", "crm.usuarios"]);
+        assert!(result.report.contains("## Assertions"), "{}", result.report);
+    }
+    assert_eq!(
+        serde_json::to_value(&streamed.metrics.assertions).expect("serialize"),
+        serde_json::to_value(&in_memory.metrics.assertions).expect("serialize")
+    );
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine};
+
+#[test]
+fn cardinality_ratios_flag_missing_and_suspicious_unique_constraints() {
+    // `nome` is UNIQUE but only has 4 values; `descricao` has no constraint but
+    // every value is distinct.
+    let mut csv = String::from("id,nome,descricao,ativo,data_criacao\n");
+    for i in 0..40 {
+        csv.push_str(&format!(
+            "{},Funil {},Descricao {i},true,2024-01-01T00:00:{:02}\n",
+            uuid::Uuid::new_v4(),
+            i % 4,
+            i
+        ));
+    }
+    let dataset_dir = temp_dir("cardinality");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), csv).expect("write funis");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("cardinality_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    let ratio = |column: &str| {
+        result
+            .metrics
+            .column_stats
+            .iter()
+            .find(|stats| stats.column == column)
+            .map(|stats| stats.cardinality_ratio)
+            .expect("column stats")
+    };
+    assert_eq!(ratio("nome"), 0.1);
+    assert_eq!(ratio("descricao"), 1.0);
+    assert_eq!(ratio("ativo"), 0.025);
+
+    let flagged: Vec<(&str, &str)> = result
+        .metrics
+        .warnings
+        .iter()
+        .filter(|warning| warning.code.contains("unique"))
+        .map(|warning| (warning.code.as_str(), warning.path.as_str()))
+        .collect();
+    // `id` is the PK and `data_criacao` is temporal, so neither is flagged.
+    assert_eq!(
+        flagged,
+        vec![
+            ("near_unique_column", "crm.funis.descricao"),
+            ("low_cardinality_unique", "crm.funis.nome"),
+        ]
+    );
+}
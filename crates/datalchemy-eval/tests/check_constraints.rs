@@ -0,0 +1,62 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{CheckStatus, EvaluateOptions, EvaluationEngine};
+
+#[test]
+fn check_constraints_report_status_per_constraint() {
+    let funil_id = uuid::Uuid::new_v4();
+    let funis = format!(
+        "id,nome,descricao,ativo,data_criacao\n{funil_id},Vendas,,true,2024-01-01T00:00:00\n"
+    );
+    // `ordem > 0` fails on the last two rows; probabilidade stays in [0, 100].
+    let mut etapas = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    for (i, ordem) in [1, 2, 0, -1].iter().enumerate() {
+        etapas.push_str(&format!(
+            "{},{funil_id},Etapa {i},{ordem},{}.50\n",
+            uuid::Uuid::new_v4(),
+            i * 25
+        ));
+    }
+    let dataset_dir = temp_dir("check_constraints");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("check_constraints_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    let check = &result.metrics.constraints.check;
+    assert_eq!(check.checked, 2);
+    assert_eq!(check.violations, 2);
+    let outcomes: Vec<(Option<&str>, CheckStatus, u64, u64)> = check
+        .constraints
+        .iter()
+        .map(|result| {
+            (
+                result.name.as_deref(),
+                result.status,
+                result.rows,
+                result.failures,
+            )
+        })
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            (Some("etapas_funil_ordem_chk"), CheckStatus::Failed, 4, 2),
+            (Some("etapas_funil_prob_chk"), CheckStatus::Passed, 4, 0),
+        ]
+    );
+
+    let report = fs::read_to_string(&result.report_path).expect("read report");
+    assert!(report.contains("## Check constraints"));
+    assert!(report.contains("| crm.etapas_funil | etapas_funil_ordem_chk | failed | 4 | 2 |"));
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine, ValueCount};
+
+const FUNIS_CSV: &str = "\
+id,nome,descricao,ativo,data_criacao
+6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Vendas,,true,2024-01-01T00:00:00
+6a23022b-f425-47d9-ad38-63caf15ece84,Parcerias,canal indireto,true,2024-03-01T00:00:00
+1b0f3a4e-8f55-4c39-9d43-9f1b2f0f4a10,Suporte,,false,2023-12-31T23:59:59
+2c1e4b5f-9066-4d4a-8e54-0a2c3f1e5b21,Renovacao,,true,2024-02-01T00:00:00
+";
+
+const ETAPAS_CSV: &str = "\
+id,funil_id,nome,ordem,probabilidade
+7d5f6c70-a177-4e5b-9f65-1b3d4e2f6c32,6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Lead,1,10.00
+8e607d81-b288-4f6c-8076-2c4e5f307d43,6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Proposta,2,40.00
+9f718e92-c399-4071-9187-3d5f60418e54,6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Fechamento,3,90.00
+a0829fa3-d4aa-4182-a298-4e6071529f65,6a23022b-f425-47d9-ad38-63caf15ece84,Lead,4,20.00
+";
+
+#[test]
+fn metrics_include_column_profiles() {
+    let dataset_dir = temp_dir("profiles");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), FUNIS_CSV).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), ETAPAS_CSV).expect("write etapas");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(temp_dir("profiles_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+    let stats = |table: &str, column: &str| {
+        result
+            .metrics
+            .column_stats
+            .iter()
+            .find(|stats| stats.table == table && stats.column == column)
+            .unwrap_or_else(|| panic!("missing stats for {table}.{column}"))
+    };
+
+    let ordem = stats("etapas_funil", "ordem");
+    assert_eq!(ordem.distinct_count, 4);
+    assert_eq!(ordem.min.as_deref(), Some("1"));
+    assert_eq!(ordem.max.as_deref(), Some("4"));
+    assert_eq!(ordem.mean, Some(2.5));
+    assert!((ordem.stddev.expect("stddev") - 1.25_f64.sqrt()).abs() < 1e-9);
+    let quantiles = ordem.quantiles.expect("quantiles");
+    for (actual, expected) in [
+        (quantiles.p05, 1.15),
+        (quantiles.p25, 1.75),
+        (quantiles.p50, 2.5),
+        (quantiles.p75, 3.25),
+        (quantiles.p95, 3.85),
+    ] {
+        assert!((actual - expected).abs() < 1e-9, "{quantiles:?}");
+    }
+
+    let nome = stats("etapas_funil", "nome");
+    assert_eq!(nome.mean, None);
+    assert_eq!(
+        nome.top_values.first(),
+        Some(&ValueCount {
+            value: "Lead".to_string(),
+            count: 2,
+        })
+    );
+
+    let descricao = stats("funis", "descricao");
+    assert_eq!(descricao.null_count, 3);
+    assert_eq!(descricao.null_rate, 0.75);
+    assert_eq!(descricao.distinct_count, 1);
+
+    let criacao = stats("funis", "data_criacao");
+    assert_eq!(criacao.min.as_deref(), Some("2023-12-31T23:59:59"));
+    assert_eq!(criacao.max.as_deref(), Some("2024-03-01T00:00:00"));
+
+    assert!(result.report.contains("## Column profiles"));
+    let written: serde_json::Value =
+        serde_json::from_slice(&fs::read(&result.metrics_path).expect("read metrics"))
+            .expect("parse metrics");
+    assert_eq!(written["metrics_version"], "0.2");
+}
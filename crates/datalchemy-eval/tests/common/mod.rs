@@ -0,0 +1,28 @@
+//! Helpers shared by the integration tests of this crate.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use datalchemy_core::DatabaseSchema;
+
+pub fn load_json(path: &Path) -> serde_json::Value {
+    let contents =
+        fs::read_to_string(path).unwrap_or_else(|_| panic!("missing json at {}", path.display()));
+    serde_json::from_str(&contents).expect("parse json")
+}
+
+/// The golden Postgres schema of the minimal example plan.
+pub fn load_schema() -> DatabaseSchema {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+    serde_json::from_value(load_json(&path)).expect("parse schema")
+}
+
+/// Unique path under the system temp dir, not created yet.
+pub fn temp_dir(label: &str) -> PathBuf {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("datalchemy_{label}_{}", uuid::Uuid::new_v4()));
+    dir
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine};
+
+const ETAPAS_CSV: &str = "\
+id,funil_id,nome,ordem,probabilidade
+7d5f6c70-a177-4e5b-9f65-1b3d4e2f6c32,6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Lead,1,10.00
+8e607d81-b288-4f6c-8076-2c4e5f307d43,6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Proposta,2,40.00
+9f718e92-c399-4071-9187-3d5f60418e54,6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Fechamento,3,90.00
+a0829fa3-d4aa-4182-a298-4e6071529f65,6a23022b-f425-47d9-ad38-63caf15ece84,Retorno,4,20.00
+";
+
+#[test]
+fn metrics_include_pearson_and_spearman_correlations() {
+    let dataset_dir = temp_dir("correlations");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), ETAPAS_CSV).expect("write etapas");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("correlations_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    let correlations = &result.metrics.correlations;
+    assert_eq!(correlations.len(), 1, "{correlations:?}");
+    let correlation = &correlations[0];
+    assert_eq!(
+        (correlation.column_a.as_str(), correlation.column_b.as_str()),
+        ("ordem", "probabilidade")
+    );
+    assert_eq!(correlation.rows, 4);
+    // cov = 40, var(ordem) = 5, var(probabilidade) = 3800 (sums of squares).
+    let pearson = correlation.pearson.expect("pearson");
+    assert!((pearson - 40.0 / (5.0_f64 * 3800.0).sqrt()).abs() < 1e-9);
+    // Ranks of probabilidade are [1, 3, 4, 2].
+    let spearman = correlation.spearman.expect("spearman");
+    assert!((spearman - 0.4).abs() < 1e-9);
+    assert!(result.report.contains("## Correlations"));
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{
+    ChangeKind, EvaluateOptions, EvaluationEngine, MetricsReport, diff_metrics, render_diff,
+};
+
+/// Evaluate `etapas` rows, `orphans` of them pointing to a missing funil.
+fn evaluate(etapas: usize, orphans: usize) -> MetricsReport {
+    let funil_id = uuid::Uuid::new_v4();
+    let funis = format!(
+        "id,nome,descricao,ativo,data_criacao\n{funil_id},Vendas,,true,2024-01-01T00:00:00\n"
+    );
+    let mut rows = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    for i in 0..etapas {
+        let parent = if i < orphans {
+            uuid::Uuid::new_v4()
+        } else {
+            funil_id
+        };
+        rows.push_str(&format!(
+            "{},{parent},Etapa {i},{},{}.00\n",
+            uuid::Uuid::new_v4(),
+            i + 1,
+            i * 5 % 100
+        ));
+    }
+    let dataset_dir = temp_dir("diff");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), rows).expect("write etapas");
+
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("diff_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset")
+    .metrics
+}
+
+#[test]
+fn diff_flags_new_orphans_as_regression_and_row_changes_as_drift() {
+    let before = evaluate(10, 0);
+    let after = evaluate(20, 4);
+
+    let diff = diff_metrics(&before, &after);
+    let fk = diff
+        .changes
+        .iter()
+        .find(|change| change.path == "fk")
+        .expect("fk rate change");
+    assert_eq!(fk.kind, ChangeKind::Regression);
+    let fk_rate = diff
+        .rates
+        .iter()
+        .find(|rate| rate.path == "fk")
+        .expect("fk");
+    assert_eq!(fk_rate.before, 0.0);
+    assert!((fk_rate.after - 4.0 / 21.0).abs() < 1e-9);
+
+    assert!(diff.changes.iter().any(|change| {
+        change.kind == ChangeKind::Drift
+            && change.path == "crm.etapas_funil"
+            && change.message == "rows 10 -> 20"
+    }));
+    assert!(
+        diff.columns
+            .iter()
+            .any(|drift| drift.path == "crm.etapas_funil.ordem" && drift.metric == "mean")
+    );
+
+    let back = diff_metrics(&after, &before);
+    assert_eq!(back.count(ChangeKind::Regression), 0);
+    assert!(back.count(ChangeKind::Improvement) > 0);
+
+    let markdown = render_diff(&diff);
+    assert!(markdown.contains("## Regressions"));
+    assert!(markdown.contains("| crm.etapas_funil | 10 | 20 |"));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{DistributionKind, EvaluateOptions, EvaluationEngine, MetricsReport};
+
+/// `crm.funis` with 40 rows created daily from `day_offset`, `ativo` from `active`.
+fn write_funis(dir: &Path, day_offset: i64, active: impl Fn(usize) -> bool) {
+    fs::create_dir_all(dir).expect("create dir");
+    let base = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).expect("date");
+    let mut csv = String::from("id,nome,descricao,ativo,data_criacao\n");
+    for i in 0..40 {
+        let day = base + chrono::Duration::days(day_offset + i as i64);
+        csv.push_str(&format!(
+            "{},Funil {i},,{},{}T00:00:00\n",
+            uuid::Uuid::new_v4(),
+            active(i),
+            day.format("%Y-%m-%d")
+        ));
+    }
+    fs::write(dir.join("crm.funis.csv"), csv).expect("write funis");
+}
+
+fn evaluate(dataset: &Path, reference: &Path) -> MetricsReport {
+    EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(temp_dir("distribution_eval")),
+        reference_dir: Some(reference.to_path_buf()),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), dataset)
+    .expect("evaluate dataset")
+    .metrics
+}
+
+#[test]
+fn matching_distributions_do_not_diverge() {
+    let reference = temp_dir("distribution_reference");
+    let dataset = temp_dir("distribution_same");
+    write_funis(&reference, 0, |i| i % 2 == 0);
+    write_funis(&dataset, 0, |i| i % 2 == 1);
+
+    let metrics = evaluate(&dataset, &reference);
+    let columns: Vec<(&str, DistributionKind)> = metrics
+        .distributions
+        .iter()
+        .map(|distribution| (distribution.column.as_str(), distribution.kind))
+        .collect();
+    // `id` (uuid) is not compared; `descricao` is all null.
+    assert_eq!(
+        columns,
+        vec![
+            ("nome", DistributionKind::Categorical),
+            ("ativo", DistributionKind::Categorical),
+            ("data_criacao", DistributionKind::Temporal),
+        ]
+    );
+    for distribution in &metrics.distributions {
+        assert!(!distribution.diverges, "{distribution:?}");
+        assert!(
+            (distribution.histogram_overlap - 1.0).abs() < 1e-9,
+            "{distribution:?}"
+        );
+    }
+}
+
+#[test]
+fn shifted_distributions_are_reported() {
+    let reference = temp_dir("distribution_reference");
+    let dataset = temp_dir("distribution_shifted");
+    write_funis(&reference, 0, |i| i % 2 == 0);
+    write_funis(&dataset, 365, |_| true);
+
+    let metrics = evaluate(&dataset, &reference);
+    let find = |column: &str| {
+        metrics
+            .distributions
+            .iter()
+            .find(|distribution| distribution.column == column)
+            .unwrap_or_else(|| panic!("missing {column}"))
+    };
+
+    let created = find("data_criacao");
+    assert_eq!(created.ks_statistic, Some(1.0));
+    assert!(created.diverges && created.p_value < 1e-6, "{created:?}");
+    assert_eq!(created.histogram_overlap, 0.0);
+
+    let active = find("ativo");
+    assert_eq!(active.degrees_of_freedom, Some(1));
+    // 2x2 table [[40, 0], [20, 20]]: chi2 = 80 / 3.
+    assert!((active.chi_square.expect("chi2") - 80.0 / 3.0).abs() < 1e-9);
+    assert!(active.diverges && active.p_value < 1e-5, "{active:?}");
+    assert!((active.histogram_overlap - 0.5).abs() < 1e-9);
+}
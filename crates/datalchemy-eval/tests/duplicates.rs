@@ -0,0 +1,85 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{DuplicateKind, EvaluateOptions, EvaluationEngine, EvaluationResult};
+
+fn evaluate(dataset_dir: &Path, streaming: bool) -> EvaluationResult {
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        streaming,
+        chunk_rows: 2,
+        spill_dir: Some(temp_dir("duplicates_spill")),
+        out_dir: Some(temp_dir("duplicates_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), dataset_dir)
+    .expect("evaluate dataset")
+}
+
+#[test]
+fn repeated_rows_and_business_keys_are_reported() {
+    let vendas = "6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Vendas,,true,2024-01-01T00:00:00";
+    let suporte = "6a23022b-f425-47d9-ad38-63caf15ece84,Suporte,,true,2024-01-01T00:00:00";
+    let funis = [
+        "id,nome,descricao,ativo,data_criacao",
+        vendas,
+        "1b0f3a4e-8f55-4c39-9d43-9f1b2f0f4a10,Vendas,outro,false,2024-02-01T00:00:00",
+        vendas,
+        suporte,
+        vendas,
+        suporte,
+    ]
+    .join("\n");
+    let dataset_dir = temp_dir("duplicates");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+
+    let in_memory = evaluate(&dataset_dir, false);
+    let streamed = evaluate(&dataset_dir, true);
+
+    for result in [&in_memory, &streamed] {
+        let rows = result
+            .metrics
+            .duplicates
+            .iter()
+            .find(|stats| stats.kind == DuplicateKind::Row)
+            .expect("row duplicates");
+        assert_eq!(rows.rows_checked, 6);
+        assert_eq!(rows.duplicate_rows, 3);
+        assert_eq!(rows.duplicate_groups, 2);
+        assert_eq!(rows.samples[0].rows, vec![1, 3, 5]);
+        assert_eq!(rows.samples[1].rows, vec![4, 6]);
+
+        let nome = result
+            .metrics
+            .duplicates
+            .iter()
+            .find(|stats| stats.kind == DuplicateKind::BusinessKey)
+            .expect("business key duplicates");
+        assert_eq!(nome.columns, vec!["nome".to_string()]);
+        assert_eq!(nome.duplicate_rows, 4);
+        assert_eq!(nome.duplicate_groups, 2);
+        assert_eq!(nome.samples[0].rows, vec![1, 2, 3, 5]);
+
+        let violation = result
+            .violations
+            .iter()
+            .find(|violation| violation.code == "duplicate_row")
+            .expect("duplicate_row violation");
+        assert_eq!(violation.path, "crm.funis");
+        let repeats: Vec<u64> = violation
+            .samples
+            .iter()
+            .map(|sample| sample.row_index)
+            .collect();
+        assert_eq!(repeats, vec![3, 6]);
+        assert!(result.report.contains("## Duplicates"), "{}", result.report);
+    }
+    assert_eq!(
+        serde_json::to_value(&streamed.metrics.duplicates).expect("serialize"),
+        serde_json::to_value(&in_memory.metrics.duplicates).expect("serialize")
+    );
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvalError, EvaluateOptions, EvaluationEngine};
+
+const EMPRESAS_CSV: &str = "\
+id,razao_social,nome_fantasia,cnpj,email,telefone,site,ativo,data_criacao,data_atualizacao
+6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Acme LTDA,Acme,11222333000181,contato@acme.com,,,true,2024-01-01 10:00:00,2024-01-02 10:00:00
+6a23022b-f425-47d9-ad38-63caf15ece84,Beta SA,Beta,11222333000181,,,,false,2024-01-01T10:00:00.250,2024-01-03T10:00:00
+";
+
+const CONTATOS_CSV: &str = "\
+id,empresa_id,nome,sobrenome,email,telefone,cargo,data_nascimento,data_criacao
+1b0f3a4e-8f55-4c39-9d43-9f1b2f0f4a10,00000000-0000-0000-0000-000000000000,Ana,,ana@acme.com,,,,2024-01-05 08:00:00
+";
+
+#[test]
+fn external_fixtures_are_mapped_by_pattern_and_checked() {
+    let dataset_dir = temp_dir("external_dataset");
+    fs::create_dir_all(dataset_dir.join("fixtures")).expect("create fixtures");
+    fs::write(dataset_dir.join("fixtures/Empresas.csv"), EMPRESAS_CSV).expect("write empresas");
+    fs::write(dataset_dir.join("fixtures/contatos.csv"), CONTATOS_CSV).expect("write contatos");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("external_eval")),
+        file_patterns: vec!["fixtures/{table}.csv".to_string()],
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate external dataset");
+
+    let tables: Vec<(&str, u64)> = result
+        .metrics
+        .tables
+        .iter()
+        .map(|table| (table.table.as_str(), table.rows_found))
+        .collect();
+    assert_eq!(tables, vec![("contatos", 1), ("empresas", 2)]);
+    assert!(
+        result.metrics.warnings.is_empty(),
+        "{:?}",
+        result.metrics.warnings
+    );
+    assert!(
+        result
+            .violations
+            .iter()
+            .any(|violation| violation.code == "unique" && violation.path.ends_with("cnpj")),
+        "{:?}",
+        result.violations
+    );
+    assert!(
+        result
+            .violations
+            .iter()
+            .any(|violation| violation.code == "foreign_key"),
+        "{:?}",
+        result.violations
+    );
+    assert_eq!(result.metrics.constraints.fk.violations, 1);
+}
+
+#[test]
+fn external_dataset_without_matching_files_is_rejected() {
+    let dataset_dir = temp_dir("external_empty");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("notes.txt"), "nothing here").expect("write file");
+
+    let err = EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(temp_dir("external_empty_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect_err("no tables");
+    assert!(matches!(err, EvalError::InvalidDataset(_)), "{err}");
+}
+
+#[test]
+fn strict_invalid_value_error_is_located() {
+    let dataset_dir = temp_dir("external_invalid");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(
+        dataset_dir.join("crm.empresas.csv"),
+        EMPRESAS_CSV.replace(",true,", ",maybe,"),
+    )
+    .expect("write empresas");
+
+    let err = EvaluationEngine::new(EvaluateOptions {
+        strict: true,
+        out_dir: Some(temp_dir("external_invalid_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect_err("invalid boolean");
+    assert!(matches!(err.kind(), EvalError::InvalidDataset(_)), "{err}");
+    assert_eq!(err.code(), "DL105");
+    assert_eq!(
+        err.to_string(),
+        format!("crm.empresas.ativo: {}", err.kind())
+    );
+
+    let info = serde_json::to_value(err.info()).expect("serialize");
+    assert_eq!(info["code"], "DL105");
+    assert_eq!(info["table"], "empresas");
+    assert_eq!(info["column"], "ativo");
+    assert_eq!(info["message"], "invalid dataset: invalid value at row 1");
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn external_parquet_files_are_read() {
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray};
+    use parquet::arrow::ArrowWriter;
+
+    let strings =
+        |values: &[Option<&str>]| -> ArrayRef { Arc::new(StringArray::from(values.to_vec())) };
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "id",
+            strings(&[Some("6d4fbb48-7194-4247-95b3-0dc2c475d8bf")]),
+        ),
+        ("nome", strings(&[Some("Inbound")])),
+        ("descricao", strings(&[None])),
+        (
+            "ativo",
+            Arc::new(BooleanArray::from(vec![true])) as ArrayRef,
+        ),
+        ("data_criacao", strings(&[Some("2024-01-01T00:00:00")])),
+    ])
+    .expect("record batch");
+
+    let dataset_dir = temp_dir("external_parquet");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    let file = fs::File::create(dataset_dir.join("crm.fontes_lead.parquet")).expect("create file");
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None).expect("writer");
+    writer.write(&batch).expect("write batch");
+    writer.close().expect("close writer");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(temp_dir("external_parquet_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate parquet dataset");
+    assert_eq!(result.metrics.tables.len(), 1);
+    assert_eq!(result.metrics.tables[0].rows_found, 1);
+    assert!(result.violations.is_empty(), "{:?}", result.violations);
+}
+
+#[test]
+fn external_jsonl_files_are_read() {
+    let dataset_dir = temp_dir("external_jsonl");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    let empresas = "\
+{\"id\":\"6d4fbb48-7194-4247-95b3-0dc2c475d8bf\",\"razao_social\":\"Acme LTDA\",\"nome_fantasia\":\"Acme\",\"cnpj\":\"11222333000181\",\"email\":null,\"ativo\":true,\"data_criacao\":\"2024-01-01 10:00:00\",\"data_atualizacao\":\"2024-01-02 10:00:00\"}
+
+{\"id\":\"6a23022b-f425-47d9-ad38-63caf15ece84\",\"razao_social\":\"Beta SA\",\"cnpj\":\"11222333000181\",\"ativo\":false,\"data_criacao\":\"2024-01-01 10:00:00\",\"data_atualizacao\":\"2024-01-03 10:00:00\",\"nome_fantasia\":\"Beta\"}
+";
+    fs::write(dataset_dir.join("crm.empresas.jsonl"), empresas).expect("write empresas");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("external_jsonl_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate jsonl dataset");
+
+    assert_eq!(result.metrics.tables.len(), 1);
+    assert_eq!(result.metrics.tables[0].rows_found, 2);
+    assert!(
+        result
+            .violations
+            .iter()
+            .any(|violation| violation.code == "unique" && violation.path.ends_with("cnpj")),
+        "{:?}",
+        result.violations
+    );
+    assert_eq!(result.metrics.constraints.not_null.violations, 0);
+}
+
+#[test]
+fn jsonl_lines_must_be_objects() {
+    let dataset_dir = temp_dir("external_jsonl_invalid");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("empresas.jsonl"), "[1, 2]\n").expect("write empresas");
+
+    let err = EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(temp_dir("external_jsonl_invalid_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect_err("array line");
+    assert!(err.to_string().contains("expected a JSON object"), "{err}");
+}
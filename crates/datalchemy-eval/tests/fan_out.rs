@@ -0,0 +1,132 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine, EvaluationResult};
+use datalchemy_plan::Plan;
+
+fn plan(funis: u64, etapas: u64) -> Plan {
+    serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 7,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [
+            { "schema": "crm", "table": "funis", "rows": funis },
+            { "schema": "crm", "table": "etapas_funil", "rows": etapas }
+        ],
+        "rules": []
+    }))
+    .expect("parse plan")
+}
+
+/// `children[i]` etapas for funil `i`.
+fn write_dataset(children: &[usize]) -> PathBuf {
+    let mut funis = String::from("id,nome,descricao,ativo,data_criacao\n");
+    let mut etapas = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    for (idx, count) in children.iter().enumerate() {
+        let funil_id = uuid::Uuid::new_v4();
+        funis.push_str(&format!(
+            "{funil_id},Funil {idx},,true,2024-01-01T00:00:00\n"
+        ));
+        for ordem in 1..=*count {
+            etapas.push_str(&format!(
+                "{},{funil_id},Etapa {ordem},{ordem},10.00\n",
+                uuid::Uuid::new_v4()
+            ));
+        }
+    }
+    let dataset_dir = temp_dir("fan_out");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+    dataset_dir
+}
+
+fn evaluate(dataset_dir: &Path, plan: &Plan, options: EvaluateOptions) -> EvaluationResult {
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("fan_out_eval")),
+        ..options
+    })
+    .run(&load_schema(), plan, dataset_dir)
+    .expect("evaluate dataset")
+}
+
+fn has_warning(result: &EvaluationResult, code: &str) -> bool {
+    result
+        .metrics
+        .warnings
+        .iter()
+        .any(|warning| warning.code == code)
+}
+
+#[test]
+fn uniform_fan_out_is_flagged() {
+    let result = evaluate(
+        &write_dataset(&[3, 3, 4, 3]),
+        &plan(4, 13),
+        EvaluateOptions::default(),
+    );
+
+    assert_eq!(result.metrics.fan_out.len(), 1);
+    let stats = &result.metrics.fan_out[0];
+    assert_eq!(stats.table, "crm.etapas_funil");
+    assert_eq!(stats.columns, vec!["funil_id"]);
+    assert_eq!(stats.parent, "crm.funis");
+    assert_eq!((stats.parents, stats.children), (4, 13));
+    assert_eq!(
+        (stats.min, stats.median, stats.p95, stats.max),
+        (3, 3, 4, 4)
+    );
+    assert_eq!(stats.expected_mean, Some(3.25));
+    assert!(stats.uniform);
+    assert!(has_warning(&result, "uniform_fan_out"));
+    assert!(!has_warning(&result, "fan_out_mismatch"));
+    assert!(result.report.contains("## Fan-out"), "{}", result.report);
+    assert!(result.report.contains(
+        "| crm.etapas_funil(funil_id) -> crm.funis | 4 | 3 | 3 | 4 | 4 | 3.25 | 3.25 | yes |"
+    ));
+}
+
+#[test]
+fn skewed_fan_out_is_compared_with_plan_targets() {
+    let children = [0, 0, 1, 1, 1, 2, 2, 3, 5, 9];
+    let result = evaluate(
+        &write_dataset(&children),
+        &plan(10, 40),
+        EvaluateOptions::default(),
+    );
+
+    let stats = &result.metrics.fan_out[0];
+    assert_eq!(stats.childless_parents, 2);
+    assert_eq!(
+        (stats.min, stats.median, stats.p95, stats.max),
+        (0, 1, 9, 9)
+    );
+    assert!((stats.mean - 2.4).abs() < 1e-9);
+    assert!(!stats.uniform);
+    assert!(!has_warning(&result, "uniform_fan_out"));
+    assert!(has_warning(&result, "fan_out_mismatch"));
+}
+
+#[test]
+fn fan_out_is_skipped_when_streaming_or_sampling() {
+    let dataset_dir = write_dataset(&[3, 3, 3]);
+    for options in [
+        EvaluateOptions {
+            streaming: true,
+            spill_dir: Some(temp_dir("fan_out_spill")),
+            ..Default::default()
+        },
+        EvaluateOptions {
+            sample_rows: Some(2),
+            ..Default::default()
+        },
+    ] {
+        let result = evaluate(&dataset_dir, &plan(3, 9), options);
+        assert!(result.metrics.fan_out.is_empty());
+        assert!(!result.report.contains("## Fan-out"));
+    }
+}
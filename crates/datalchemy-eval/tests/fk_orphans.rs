@@ -0,0 +1,68 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine};
+
+#[test]
+fn foreign_key_violation_carries_orphan_row_samples() {
+    let funil_id = uuid::Uuid::new_v4();
+    let missing_id = uuid::Uuid::new_v4();
+    let funis = format!(
+        "id,nome,descricao,ativo,data_criacao\n{funil_id},Vendas,,true,2024-01-01T00:00:00\n"
+    );
+    // Rows 2, 4, 6 and 8 reference a funil that does not exist.
+    let mut etapas = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    let mut orphans = Vec::new();
+    for i in 1..=8 {
+        let id = uuid::Uuid::new_v4();
+        let parent = if i % 2 == 0 {
+            orphans.push(id);
+            missing_id
+        } else {
+            funil_id
+        };
+        etapas.push_str(&format!("{id},{parent},Etapa {i},{i},10.00\n"));
+    }
+    let dataset_dir = temp_dir("fk_orphans");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        max_orphan_samples: 3,
+        out_dir: Some(temp_dir("fk_orphans_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    assert_eq!(result.metrics.constraints.fk.violations, 4);
+    let fk_violations: Vec<_> = result
+        .violations
+        .iter()
+        .filter(|violation| violation.code == "foreign_key")
+        .collect();
+    assert_eq!(fk_violations.len(), 1, "one violation per relation");
+    let violation = fk_violations[0];
+    assert_eq!(violation.path, "crm.etapas_funil(funil_id) -> crm.funis");
+    assert_eq!(violation.message, "4 broken foreign key reference(s)");
+    assert_eq!(violation.row_index, Some(2));
+
+    let samples: Vec<(u64, Option<String>, String)> = violation
+        .samples
+        .iter()
+        .map(|sample| (sample.row_index, sample.key.clone(), sample.value.clone()))
+        .collect();
+    assert_eq!(
+        samples,
+        orphans
+            .iter()
+            .take(3)
+            .zip([2, 4, 6])
+            .map(|(id, row)| (row, Some(format!("id={id}")), missing_id.to_string()))
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::history::HISTORY_VERSION;
+use datalchemy_eval::{
+    ChangeKind, EvaluateOptions, EvaluationEngine, HistoryEntry, MetricsReport, append_history,
+    merge_history, read_history,
+};
+
+/// Evaluate `funis` rows, the last `blank` of them without a name.
+fn evaluate(funis: usize, blank: usize) -> MetricsReport {
+    let mut rows = String::from("id,nome,descricao,ativo,data_criacao\n");
+    for i in 0..funis {
+        let nome = if i + blank >= funis {
+            String::new()
+        } else {
+            format!("Funil {i}")
+        };
+        rows.push_str(&format!(
+            "{},{nome},,true,2024-01-01T00:00:00\n",
+            uuid::Uuid::new_v4()
+        ));
+    }
+    let dataset_dir = temp_dir("history");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), rows).expect("write funis");
+
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("history_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset")
+    .metrics
+}
+
+fn entry(eval_id: &str, recorded_at: &str, metrics: &MetricsReport) -> HistoryEntry {
+    HistoryEntry::new(eval_id, None, recorded_at, metrics.clone())
+}
+
+#[test]
+fn history_round_trips_and_diffs_entries() {
+    let before = evaluate(5, 0);
+    let after = evaluate(5, 2);
+    let path = temp_dir("history_file").with_extension("ndjson");
+    assert!(read_history(&path).expect("missing history").is_empty());
+
+    append_history(&path, &entry("eval_a", "2026-01-01T00:00:00Z", &before)).expect("append");
+    append_history(&path, &entry("eval_b", "2026-01-02T00:00:00Z", &after)).expect("append");
+    let contents = fs::read_to_string(&path).expect("read history");
+    assert_eq!(contents.lines().count(), 2);
+
+    let history = read_history(&path).expect("read history");
+    assert_eq!(history[0].history_version, HISTORY_VERSION);
+    assert_eq!(history[1].eval_id, "eval_b");
+    assert_eq!(
+        serde_json::to_value(&history[0].metrics).expect("serialize"),
+        serde_json::to_value(&before).expect("serialize")
+    );
+
+    let diff = history[0].metrics.diff(&history[1].metrics);
+    assert_eq!(diff.after_run_id, after.run_id);
+    assert!(
+        diff.changes
+            .iter()
+            .any(|change| change.kind == ChangeKind::Regression && change.path == "not_null")
+    );
+}
+
+#[test]
+fn metrics_without_optional_sections_are_read_back() {
+    let mut value = serde_json::to_value(evaluate(3, 0)).expect("serialize");
+    let object = value.as_object_mut().expect("object");
+    object.remove("column_stats");
+    object.remove("warnings");
+    let line = serde_json::json!({
+        "history_version": "0.1",
+        "eval_id": "eval_old",
+        "recorded_at": "2025-06-01T00:00:00Z",
+        "metrics": value
+    })
+    .to_string();
+
+    let entry = HistoryEntry::from_line(&line).expect("parse entry");
+    assert!(entry.metrics.column_stats.is_empty());
+    assert!(entry.metrics.warnings.is_empty());
+    assert_eq!(entry.out_id, None);
+}
+
+#[test]
+fn merged_histories_keep_ours_and_sort_by_time() {
+    let metrics = evaluate(2, 0);
+    let mut ours_shared = entry("eval_shared", "2026-01-02T00:00:00Z", &metrics);
+    ours_shared.out_id = Some("out_ours".to_string());
+    let ours = vec![
+        entry("eval_c", "2026-01-03T00:00:00Z", &metrics),
+        ours_shared,
+    ];
+    let theirs = vec![
+        entry("eval_shared", "2026-01-02T00:00:00Z", &metrics),
+        entry("eval_a", "2026-01-01T00:00:00Z", &metrics),
+    ];
+
+    let merged = merge_history(ours, theirs);
+    let ids: Vec<&str> = merged.iter().map(|entry| entry.eval_id.as_str()).collect();
+    assert_eq!(ids, vec!["eval_a", "eval_shared", "eval_c"]);
+    assert_eq!(merged[1].out_id.as_deref(), Some("out_ours"));
+}
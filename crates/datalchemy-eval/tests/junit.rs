@@ -0,0 +1,49 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvalError, EvaluateOptions, EvaluationEngine};
+
+#[test]
+fn junit_report_has_one_test_case_per_check() {
+    let funil_id = uuid::Uuid::new_v4();
+    let funis = format!(
+        "id,nome,descricao,ativo,data_criacao\n{funil_id},Vendas,,true,2024-01-01T00:00:00\n"
+    );
+    let etapas = format!(
+        "id,funil_id,nome,ordem,probabilidade\n\
+         {},{funil_id},Lead,1,10.00\n\
+         {},{},Proposta,2,50.00\n",
+        uuid::Uuid::new_v4(),
+        uuid::Uuid::new_v4(),
+        uuid::Uuid::new_v4()
+    );
+    let dataset_dir = temp_dir("junit");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+    let out_dir = temp_dir("junit_eval");
+
+    // Strict evaluation fails, but junit.xml is already written for CI.
+    let err = EvaluationEngine::new(EvaluateOptions {
+        write_junit: true,
+        out_dir: Some(out_dir.clone()),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect_err("orphan etapa fails strict eval");
+    assert!(matches!(err, EvalError::Violations(1)), "{err}");
+
+    let junit = fs::read_to_string(out_dir.join("junit.xml")).expect("read junit");
+    assert!(junit.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(junit.contains(r#"<testsuites name="datalchemy-eval" tests="6" failures="1">"#));
+    for check in ["not_null", "primary_key", "unique", "check", "pii_scan"] {
+        assert!(
+            junit.contains(&format!(r#"name="{check}"/>"#)),
+            "{check} passes: {junit}"
+        );
+    }
+    assert!(junit.contains(r#"<failure type="foreign_key" message="1 violation(s)">"#));
+    assert!(junit.contains("crm.etapas_funil(funil_id) -&gt; crm.funis (row 2)"));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine};
+use datalchemy_generate::{GenerationReport, TableReport};
+
+fn table_report(table: &str, duration_ms: u64) -> TableReport {
+    TableReport {
+        schema: "crm".to_string(),
+        table: table.to_string(),
+        rows_requested: 10,
+        rows_generated: 10,
+        retries: 2,
+        duration_ms,
+        rows_per_sec: 10_000.0 / duration_ms as f64,
+        retries_per_sec: 2_000.0 / duration_ms as f64,
+        peak_memory_bytes: 4096,
+    }
+}
+
+#[test]
+fn report_lists_slowest_generated_tables() {
+    let dataset_dir = temp_dir("performance");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(
+        dataset_dir.join("crm.funis.csv"),
+        "id,nome,descricao,ativo,data_criacao\n\
+         6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Vendas,,true,2024-01-01T00:00:00\n",
+    )
+    .expect("write funis");
+    let mut report = GenerationReport::new("perf".to_string());
+    report.tables = vec![
+        table_report("funis", 5),
+        table_report("etapas_funil", 250),
+        table_report("empresas", 40),
+    ];
+    fs::write(
+        dataset_dir.join("generation_report.json"),
+        serde_json::to_vec(&report).expect("serialize report"),
+    )
+    .expect("write report");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        top_slowest: 2,
+        out_dir: Some(temp_dir("performance_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    let order: Vec<&str> = result
+        .metrics
+        .performance
+        .tables
+        .iter()
+        .map(|table| table.table.as_str())
+        .collect();
+    assert_eq!(order, vec!["etapas_funil", "empresas", "funis"]);
+    assert!(
+        result
+            .report
+            .contains("| crm.etapas_funil | 250 | 10 | 40.0 | 8.0 | 4096 |"),
+        "{}",
+        result.report
+    );
+    assert!(result.report.contains("| crm.empresas | 40 |"));
+    assert!(!result.report.contains("| crm.funis | 5 |"));
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvalError, EvaluateOptions, EvaluationEngine, PiiKind};
+use datalchemy_generate::model::{ColumnSource, GenerationReport};
+
+// Emails on example.com/.test are safe; `cargo` carries valid CPFs.
+const USUARIOS_CSV: &str = "\
+id,nome,email,telefone,ativo,data_criacao,data_atualizacao
+7c9e6679-7425-40de-944b-e07fc1f90ae7,Ana,ana@example.com,,true,2024-01-01T00:00:00,2024-01-01T00:00:00
+16fd2706-8baf-433b-82eb-8c7fada847da,Bia,bia@mail.test,,true,2024-01-01T00:00:00,2024-01-01T00:00:00
+886313e1-3b8a-5372-9b90-0c9aee199e5d,Caio,caio.silva@gmail.com,,true,2024-01-01T00:00:00,2024-01-01T00:00:00
+a3bb189e-8bf9-3888-9912-ace4e6543002,Duda,duda@empresa.com.br,52998224725,true,2024-01-01T00:00:00,2024-01-01T00:00:00
+c2c0bd2d-4d46-4bd9-8f2c-3f4b0e9f8b3a,Eva,eva@sub.example.org,529.982.247-25,true,2024-01-01T00:00:00,2024-01-01T00:00:00
+";
+
+fn write_dataset(label: &str) -> PathBuf {
+    let dataset_dir = temp_dir(label);
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.usuarios.csv"), USUARIOS_CSV).expect("write usuarios");
+    dataset_dir
+}
+
+#[test]
+fn pii_scan_flags_real_looking_emails_and_cpfs() {
+    let dataset_dir = write_dataset("pii_scan");
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("pii_scan_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    let findings: Vec<(&str, PiiKind, u64, u64, &str)> = result
+        .metrics
+        .pii_findings
+        .iter()
+        .map(|finding| {
+            (
+                finding.column.as_str(),
+                finding.kind,
+                finding.count,
+                finding.first_row,
+                finding.example.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        findings,
+        vec![
+            ("email", PiiKind::Email, 2, 3, "ca***@gmail.com"),
+            ("telefone", PiiKind::Cpf, 2, 4, "*********25"),
+        ]
+    );
+    let codes: Vec<&str> = result
+        .violations
+        .iter()
+        .filter(|violation| violation.code.starts_with("pii_"))
+        .map(|violation| violation.code.as_str())
+        .collect();
+    assert_eq!(codes, vec!["pii_email", "pii_cpf"]);
+    let report = fs::read_to_string(&result.report_path).expect("read report");
+    assert!(report.contains("## PII scan"));
+    assert!(!report.contains("caio.silva@gmail.com"));
+}
+
+#[test]
+fn pii_hits_fail_strict_eval_unless_allowed() {
+    let dataset_dir = write_dataset("pii_strict");
+    let err = EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(temp_dir("pii_strict_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect_err("strict eval fails on PII");
+    assert!(matches!(err, EvalError::Violations(2)), "{err}");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("pii_allowed_eval")),
+        allowed_email_domains: vec![
+            "example.com".to_string(),
+            "gmail.com".to_string(),
+            "empresa.com.br".to_string(),
+        ],
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+    let kinds: Vec<PiiKind> = result
+        .metrics
+        .pii_findings
+        .iter()
+        .map(|finding| finding.kind)
+        .collect();
+    // example.org is no longer allowed once the list is overridden.
+    assert_eq!(kinds, vec![PiiKind::Email, PiiKind::Cpf]);
+    assert_eq!(result.metrics.pii_findings[0].count, 1);
+    assert_eq!(result.metrics.pii_findings[0].first_row, 5);
+}
+
+#[test]
+fn classified_pii_columns_filled_without_pii_generator_warn() {
+    let dataset_dir = write_dataset("pii_classified");
+    let mut report = GenerationReport::new("run_classified".to_string());
+    // `documento` is not a schema column: classified columns missing from
+    // the report are skipped.
+    for (column, source, generator_id, tags) in [
+        ("email", "plan", "semantic.br.email.safe", vec!["pii.email"]),
+        ("telefone", "heuristic", "faker.lorem.raw.Word", vec![]),
+        ("nome", "foreign_key", "primitive.text", vec![]),
+    ] {
+        report.column_sources.insert(
+            format!("crm.usuarios.{column}"),
+            ColumnSource {
+                source: source.to_string(),
+                generator_id: Some(generator_id.to_string()),
+                transforms: Vec::new(),
+                pii_tags: tags.into_iter().map(str::to_string).collect(),
+            },
+        );
+    }
+    fs::write(
+        dataset_dir.join("generation_report.json"),
+        serde_json::to_vec(&report).expect("serialize report"),
+    )
+    .expect("write report");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("pii_classified_eval")),
+        pii_columns: [
+            ("crm.usuarios.email", "pii.email"),
+            ("crm.usuarios.telefone", "pii.phone"),
+            ("crm.usuarios.nome", "pii.name"),
+            ("crm.usuarios.documento", "pii.cpf"),
+        ]
+        .into_iter()
+        .map(|(path, tag)| (path.to_string(), tag.to_string()))
+        .collect(),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    let unprotected: Vec<&str> = result
+        .metrics
+        .warnings
+        .iter()
+        .filter(|warning| warning.code == "pii_column_unprotected")
+        .map(|warning| warning.path.as_str())
+        .collect();
+    assert_eq!(unprotected, vec!["crm.usuarios.telefone"]);
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use std::fs;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine, PrivacySpec};
+
+#[test]
+fn privacy_metrics_report_k_anonymity_and_l_diversity() {
+    // Quasi-identifiers (cargo, data_nascimento): "Gerente" x 3 rows with two
+    // sobrenomes, "Analista" x 2 rows with a single one, "Diretor" x 1 row.
+    let rows = [
+        ("Gerente", "1990-01-01", "Silva"),
+        ("Gerente", "1990-01-01", "Souza"),
+        ("Gerente", "1990-01-01", "Silva"),
+        ("Analista", "1985-05-05", "Lima"),
+        ("Analista", "1985-05-05", "Lima"),
+        ("Diretor", "1970-07-07", "Costa"),
+    ];
+    let mut csv = String::from(
+        "id,empresa_id,nome,sobrenome,email,telefone,cargo,data_nascimento,data_criacao\n",
+    );
+    for (i, (cargo, nascimento, sobrenome)) in rows.iter().enumerate() {
+        csv.push_str(&format!(
+            "{},{},Contato {i},{sobrenome},c{i}@example.com,,{cargo},{nascimento},2024-01-01T00:00:00\n",
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4()
+        ));
+    }
+    let dataset_dir = temp_dir("privacy");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.contatos.csv"), csv).expect("write contatos");
+
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("privacy_eval")),
+        privacy: vec![
+            PrivacySpec {
+                schema: "crm".to_string(),
+                table: "contatos".to_string(),
+                quasi_identifiers: vec!["cargo".to_string(), "data_nascimento".to_string()],
+                sensitive: vec!["sobrenome".to_string()],
+                k: 2,
+                l: 2,
+            },
+            PrivacySpec {
+                schema: "crm".to_string(),
+                table: "contatos".to_string(),
+                quasi_identifiers: vec!["salario".to_string()],
+                sensitive: Vec::new(),
+                k: 2,
+                l: 2,
+            },
+        ],
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+
+    assert_eq!(result.metrics.privacy.len(), 1);
+    let privacy = &result.metrics.privacy[0];
+    assert_eq!(privacy.rows, 6);
+    assert_eq!(privacy.equivalence_classes, 3);
+    assert_eq!(privacy.k_anonymity, 1);
+    assert_eq!(privacy.classes_below_k, 1);
+    assert_eq!(privacy.rows_below_k, 1);
+    let diversity = &privacy.l_diversity[0];
+    assert_eq!(diversity.column, "sobrenome");
+    assert_eq!(diversity.l_diversity, 1);
+    assert_eq!(diversity.classes_below_l, 2);
+
+    let findings: Vec<(&str, &str)> = result
+        .metrics
+        .warnings
+        .iter()
+        .filter(|warning| {
+            warning.code.contains("anonymity")
+                || warning.code.starts_with("l_")
+                || warning.code.starts_with("privacy")
+        })
+        .map(|warning| (warning.code.as_str(), warning.path.as_str()))
+        .collect();
+    assert_eq!(
+        findings,
+        vec![
+            ("k_anonymity_below_threshold", "crm.contatos"),
+            ("privacy_column_missing", "crm.contatos.salario"),
+            ("l_diversity_below_threshold", "crm.contatos.sobrenome"),
+        ]
+    );
+
+    let report = fs::read_to_string(&result.report_path).expect("read report");
+    assert!(report.contains("## Privacy"));
+    assert!(report.contains("| crm.contatos | cargo, data_nascimento | 3 | 1 (k=2) | 1 | 1 |"));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::sampling::Reservoir;
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine, EvaluationResult};
+
+fn evaluate(dataset_dir: &Path) -> EvaluationResult {
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        sample_rows: Some(100),
+        out_dir: Some(temp_dir("sampling_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), dataset_dir)
+    .expect("evaluate dataset")
+}
+
+#[test]
+fn sampled_eval_extrapolates_failures_from_a_reproducible_sample() {
+    // 300 funis, so the parent table is sampled too; 1 etapa in 10 is an orphan.
+    let funil_ids: Vec<_> = (0..300).map(|_| uuid::Uuid::new_v4()).collect();
+    let mut funis = String::from("id,nome,descricao,ativo,data_criacao\n");
+    for (i, id) in funil_ids.iter().enumerate() {
+        funis.push_str(&format!("{id},Funil {i},,true,2024-01-01T00:00:00\n"));
+    }
+    let mut etapas = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    for i in 1..=2_000usize {
+        let parent = if i % 10 == 0 {
+            uuid::Uuid::new_v4()
+        } else {
+            funil_ids[i % funil_ids.len()]
+        };
+        let nome = if i == 1_500 {
+            String::new()
+        } else {
+            format!("Etapa {i}")
+        };
+        etapas.push_str(&format!(
+            "{},{parent},{nome},{i},10.00\n",
+            uuid::Uuid::new_v4()
+        ));
+    }
+    let dataset_dir = temp_dir("sampling");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+
+    let result = evaluate(&dataset_dir);
+    let sampling = result.metrics.sampling.as_ref().expect("sampling metrics");
+    let etapas_rows = sampling
+        .tables
+        .iter()
+        .find(|table| table.table == "etapas_funil")
+        .expect("etapas table");
+    assert_eq!(
+        (etapas_rows.rows_total, etapas_rows.rows_sampled),
+        (2_000, 100)
+    );
+    let rows_found: Vec<u64> = result.metrics.tables.iter().map(|t| t.rows_found).collect();
+    assert_eq!(rows_found, vec![2_000, 300]);
+    // NOT NULL counts cover the whole file, not just the sample.
+    assert_eq!(result.metrics.constraints.not_null.violations, 1);
+
+    let fk = sampling
+        .estimates
+        .iter()
+        .find(|estimate| estimate.metric == "foreign_key")
+        .expect("fk estimate");
+    assert_eq!(fk.rows_sampled, 100);
+    assert!(
+        fk.failures > 0 && fk.failures < 30,
+        "only true orphans: {fk:?}"
+    );
+    assert!(
+        fk.estimated_low <= 200 && 200 <= fk.estimated_high,
+        "interval covers the real orphan count: {fk:?}"
+    );
+    assert!(
+        sampling
+            .estimates
+            .iter()
+            .any(|estimate| estimate.metric == "check")
+    );
+    assert!(result.report.contains("## Sampling"));
+
+    let again = evaluate(&dataset_dir);
+    assert_eq!(
+        serde_json::to_value(&again.metrics.sampling).expect("serialize"),
+        serde_json::to_value(&result.metrics.sampling).expect("serialize")
+    );
+}
+
+#[test]
+fn reservoir_keeps_capacity_items_in_push_order() {
+    let mut reservoir = Reservoir::new(10, 42);
+    for item in 0..1_000 {
+        reservoir.push(item);
+    }
+    assert_eq!(reservoir.seen(), 1_000);
+    let items = reservoir.into_items();
+    assert_eq!(items.len(), 10);
+    assert!(items.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(
+        items.iter().any(|item| *item >= 10),
+        "later items are sampled"
+    );
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::load_schema;
+use datalchemy_core::{
+    Constraint, DatabaseSchema, FailedTable, RewriteRule, TableEvent, Trigger, TriggerTiming,
+};
+use datalchemy_eval::collect_schema_metrics;
+
+fn table_mut<'a>(schema: &'a mut DatabaseSchema, name: &str) -> &'a mut datalchemy_core::Table {
+    schema.schemas[0]
+        .tables
+        .iter_mut()
+        .find(|table| table.name == name)
+        .expect("table")
+}
+
+#[test]
+fn foreign_key_index_coverage_and_nullability() {
+    let metrics = collect_schema_metrics(&load_schema());
+
+    let coverage = |table: &str, column: &str| {
+        metrics
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.table == table && fk.columns == vec![column.to_string()])
+            .expect("foreign key")
+    };
+    // Covered by the UNIQUE index on atividade_id.
+    let anotacoes = coverage("crm.anotacoes", "atividade_id");
+    assert!(anotacoes.indexed);
+    assert_eq!(anotacoes.references, "crm.atividades");
+    // Only the primary key is indexed.
+    let atividades = coverage("crm.atividades", "contato_id");
+    assert!(!atividades.indexed);
+
+    let indexed = metrics.foreign_keys.iter().filter(|fk| fk.indexed).count();
+    assert_eq!(
+        metrics.coverage.fks_with_index_pct,
+        indexed as f64 / metrics.foreign_keys.len() as f64
+    );
+    assert!(metrics.warnings.iter().any(|warning| {
+        warning.starts_with("foreign key crm.atividades(contato_id) -> crm.contatos")
+    }));
+    assert!(
+        metrics
+            .warnings
+            .iter()
+            .all(|warning| !warning.starts_with("foreign key crm.anotacoes(atividade_id)"))
+    );
+
+    let nullable = metrics.foreign_keys.iter().filter(|fk| fk.nullable).count();
+    assert_eq!(
+        metrics.coverage.nullable_fks_pct,
+        nullable as f64 / metrics.foreign_keys.len() as f64
+    );
+    assert!(metrics.tables_without_pk.is_empty());
+    assert_eq!(metrics.widest_tables.len(), 5);
+    assert!(
+        metrics
+            .widest_tables
+            .windows(2)
+            .all(|pair| pair[0].columns >= pair[1].columns)
+    );
+}
+
+#[test]
+fn tables_without_pk_and_wide_tables_are_warned() {
+    let mut schema = load_schema();
+    let produtos = table_mut(&mut schema, "produtos");
+    produtos
+        .constraints
+        .retain(|constraint| !matches!(constraint, Constraint::PrimaryKey(_)));
+    let template = produtos.columns[0].clone();
+    for idx in produtos.columns.len()..60 {
+        let mut column = template.clone();
+        column.name = format!("extra_{idx}");
+        column.ordinal_position = idx as i16 + 1;
+        produtos.columns.push(column);
+    }
+
+    let metrics = collect_schema_metrics(&schema);
+
+    assert_eq!(metrics.tables_without_pk, vec!["crm.produtos".to_string()]);
+    assert_eq!(metrics.widest_tables[0].table, "crm.produtos");
+    assert_eq!(metrics.widest_tables[0].columns, 60);
+    assert!(metrics.warnings.iter().any(|warning| warning
+        == "table crm.produtos has no primary key; generated rows cannot be told apart or referenced"));
+    assert!(metrics.warnings.iter().any(|warning| {
+        warning == "table crm.produtos has 60 columns (more than 50); consider splitting it"
+    }));
+}
+
+#[test]
+fn insert_triggers_and_rules_are_warned() {
+    let mut schema = load_schema();
+    let trigger = |name: &str, events: Vec<TableEvent>| Trigger {
+        name: name.to_string(),
+        timing: TriggerTiming::Before,
+        events,
+        for_each_row: true,
+        function: Some("crm.carimbar".to_string()),
+        enabled: true,
+    };
+    let empresas = table_mut(&mut schema, "empresas");
+    empresas.triggers = vec![
+        trigger(
+            "empresas_carimbo",
+            vec![TableEvent::Insert, TableEvent::Update],
+        ),
+        trigger("empresas_limpeza", vec![TableEvent::Delete]),
+    ];
+    empresas.rules = vec![RewriteRule {
+        name: "empresas_arquivo".to_string(),
+        event: TableEvent::Insert,
+        instead: false,
+    }];
+
+    let metrics = collect_schema_metrics(&schema);
+
+    assert_eq!((metrics.counts.triggers, metrics.counts.rules), (2, 1));
+    assert!(metrics.warnings.iter().any(|warning| {
+        warning
+            == "table crm.empresas runs trigger empresas_carimbo, rule empresas_arquivo on insert; \
+                loading the generated rows with INSERT may change or reject them"
+    }));
+}
+
+#[test]
+fn failed_tables_are_warned() {
+    let mut schema = load_schema();
+    schema.failed_tables.push(FailedTable {
+        schema: "crm".to_string(),
+        table: "auditoria".to_string(),
+        error: "query timed out after 500 ms".to_string(),
+    });
+
+    let metrics = collect_schema_metrics(&schema);
+
+    assert!(metrics.warnings.iter().any(|warning| {
+        warning
+            == "table crm.auditoria could not be introspected (query timed out after 500 ms); \
+                it is missing from the schema"
+    }));
+}
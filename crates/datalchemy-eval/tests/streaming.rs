@@ -0,0 +1,213 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+use common::{load_schema, temp_dir};
+use datalchemy_core::{EngineIssue, EngineObserver, IssueLevel, RunOutcome, RunSummary, Stage};
+use datalchemy_eval::{EvalError, EvaluateOptions, EvaluationEngine, EvaluationResult};
+
+fn evaluate(dataset_dir: &Path, streaming: bool, spill_dir: &Path) -> EvaluationResult {
+    EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        streaming,
+        chunk_rows: 25,
+        spill_dir: Some(spill_dir.to_path_buf()),
+        out_dir: Some(temp_dir("streaming_eval")),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), dataset_dir)
+    .expect("evaluate dataset")
+}
+
+#[test]
+fn streaming_matches_in_memory_constraint_counts() {
+    let funil_id = uuid::Uuid::new_v4();
+    let missing_id = uuid::Uuid::new_v4();
+    let duplicate_id = uuid::Uuid::new_v4();
+    let funis = format!(
+        "id,nome,descricao,ativo,data_criacao\n{funil_id},Vendas,,true,2024-01-01T00:00:00\n"
+    );
+    let mut etapas = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    for i in 1..=120u32 {
+        let id = match i {
+            7 | 91 => duplicate_id,
+            _ => uuid::Uuid::new_v4(),
+        };
+        let parent = if i % 40 == 0 { missing_id } else { funil_id };
+        let nome = if i == 3 {
+            String::new()
+        } else {
+            format!("Etapa {i}")
+        };
+        let ordem = if i == 75 { 0 } else { i };
+        etapas.push_str(&format!("{id},{parent},{nome},{ordem},10.00\n"));
+    }
+    let dataset_dir = temp_dir("streaming");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+    let spill_dir = temp_dir("streaming_spill");
+
+    let in_memory = evaluate(&dataset_dir, false, &spill_dir);
+    let streamed = evaluate(&dataset_dir, true, &spill_dir);
+
+    assert_eq!(
+        serde_json::to_value(&streamed.metrics.constraints).expect("serialize"),
+        serde_json::to_value(&in_memory.metrics.constraints).expect("serialize")
+    );
+    assert_eq!(streamed.metrics.constraints.pk.violations, 1);
+    assert_eq!(streamed.metrics.constraints.fk.violations, 3);
+    assert_eq!(streamed.metrics.constraints.check.violations, 1);
+    assert_eq!(streamed.metrics.constraints.not_null.violations, 1);
+    assert_eq!(
+        serde_json::to_value(&streamed.metrics.tables).expect("serialize"),
+        serde_json::to_value(&in_memory.metrics.tables).expect("serialize")
+    );
+
+    let codes_and_rows = |result: &EvaluationResult| {
+        result
+            .violations
+            .iter()
+            .map(|violation| {
+                (
+                    violation.code.clone(),
+                    violation.path.clone(),
+                    violation.row_index,
+                    violation.samples.len(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(codes_and_rows(&streamed), codes_and_rows(&in_memory));
+    let fk = streamed
+        .violations
+        .iter()
+        .find(|violation| violation.code == "foreign_key")
+        .expect("fk violation");
+    let rows: Vec<u64> = fk.samples.iter().map(|sample| sample.row_index).collect();
+    assert_eq!(rows, vec![40, 80, 120]);
+
+    assert!(
+        streamed
+            .metrics
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "streaming_partial_metrics")
+    );
+    assert!(streamed.metrics.column_stats.is_empty());
+    let leftover = fs::read_dir(&spill_dir)
+        .map(|entries| entries.count())
+        .unwrap_or(0);
+    assert_eq!(leftover, 0, "spilled keys are removed");
+}
+
+#[test]
+fn cancelled_evaluation_writes_no_artifacts() {
+    let dataset_dir = temp_dir("streaming_cancel");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(
+        dataset_dir.join("crm.funis.csv"),
+        "id,nome,descricao,ativo,data_criacao\n\
+         7c9e6679-7425-40de-944b-e07fc1f90ae7,Vendas,,true,2024-01-01T00:00:00\n",
+    )
+    .expect("write funis");
+    let spill_dir = temp_dir("streaming_cancel_spill");
+
+    for streaming in [false, true] {
+        let out_dir = temp_dir("streaming_cancel_eval");
+        let err = EvaluationEngine::new(EvaluateOptions {
+            streaming,
+            spill_dir: Some(spill_dir.clone()),
+            out_dir: Some(out_dir.clone()),
+            ..Default::default()
+        })
+        .with_cancel(Arc::new(AtomicBool::new(true)))
+        .run_dataset(&load_schema(), &dataset_dir)
+        .expect_err("cancelled evaluation fails");
+        assert!(matches!(err, EvalError::Cancelled), "{err}");
+        assert!(!out_dir.exists(), "no report for a cancelled evaluation");
+    }
+    let leftover = fs::read_dir(&spill_dir)
+        .map(|entries| entries.count())
+        .unwrap_or(0);
+    assert_eq!(leftover, 0, "spilled keys are removed");
+}
+
+#[derive(Default)]
+struct Recorder {
+    rows: Mutex<Vec<(String, u64)>>,
+    issues: Mutex<Vec<EngineIssue>>,
+    summaries: Mutex<Vec<RunSummary>>,
+}
+
+impl EngineObserver for Recorder {
+    fn on_row_batch(&self, stage: Stage, schema: &str, table: &str, rows: u64) {
+        assert_eq!(stage, Stage::Evaluate);
+        let mut batches = self.rows.lock().expect("rows lock");
+        batches.push((format!("{schema}.{table}"), rows));
+    }
+
+    fn on_issue(&self, issue: &EngineIssue) {
+        self.issues.lock().expect("issues lock").push(issue.clone());
+    }
+
+    fn on_finish(&self, summary: &RunSummary) {
+        self.summaries
+            .lock()
+            .expect("summaries lock")
+            .push(summary.clone());
+    }
+}
+
+#[test]
+fn observer_sees_chunks_issues_and_summary() {
+    let dataset_dir = temp_dir("streaming_observer");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    let id = uuid::Uuid::new_v4();
+    let mut funis = "id,nome,descricao,ativo,data_criacao\n".to_string();
+    for _ in 0..3 {
+        funis.push_str(&format!("{id},Vendas,,true,2024-01-01T00:00:00\n"));
+    }
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    let spill_dir = temp_dir("streaming_observer_spill");
+
+    for streaming in [false, true] {
+        let recorder = Arc::new(Recorder::default());
+        let result = EvaluationEngine::new(EvaluateOptions {
+            strict: false,
+            streaming,
+            chunk_rows: 2,
+            spill_dir: Some(spill_dir.clone()),
+            out_dir: Some(temp_dir("streaming_observer_eval")),
+            ..Default::default()
+        })
+        .with_observer(recorder.clone())
+        .run_dataset(&load_schema(), &dataset_dir)
+        .expect("evaluate dataset");
+
+        let rows = recorder.rows.lock().expect("rows lock");
+        let expected: &[u64] = if streaming { &[2, 1] } else { &[3] };
+        assert_eq!(
+            rows.iter().map(|(_, rows)| *rows).collect::<Vec<_>>(),
+            expected
+        );
+        assert!(rows.iter().all(|(table, _)| table == "crm.funis"));
+
+        let issues = recorder.issues.lock().expect("issues lock");
+        let errors = issues
+            .iter()
+            .filter(|issue| issue.level == IssueLevel::Error)
+            .count();
+        assert_eq!(errors, result.violations.len());
+        assert!(errors > 0, "duplicate primary key is a violation");
+
+        let summaries = recorder.summaries.lock().expect("summaries lock");
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].outcome, RunOutcome::Completed);
+        assert_eq!((summaries[0].tables, summaries[0].rows), (1, 3));
+        assert_eq!(summaries[0].errors, result.violations.len() as u64);
+    }
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine, EvaluationResult, TemporalSource};
+use datalchemy_plan::Plan;
+
+fn plan() -> Plan {
+    serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 7,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "crm", "table": "listas_precos", "rows": 4 }],
+        "rules": [
+            {
+                "type": "column_generator",
+                "schema": "crm",
+                "table": "listas_precos",
+                "column": "data_inicio",
+                "generator": "primitive.date.range",
+                "params": { "min": "2024-01-01", "max": "2024-12-31" }
+            },
+            {
+                "type": "column_generator",
+                "schema": "crm",
+                "table": "listas_precos",
+                "column": "data_fim",
+                "generator": "derive.end_after_start",
+                "params": { "input_columns": ["data_inicio"], "max_days": 180 }
+            }
+        ]
+    }))
+    .expect("parse plan")
+}
+
+fn write_dataset() -> PathBuf {
+    let listas = [
+        "id,nome,moeda,ativo,data_inicio,data_fim",
+        "6d4fbb48-7194-4247-95b3-0dc2c475d8bf,Varejo,BRL,true,2024-01-01,2024-06-30",
+        "6a23022b-f425-47d9-ad38-63caf15ece84,Atacado,BRL,true,2024-02-01,2023-12-31",
+        "1b0f3a4e-8f55-4c39-9d43-9f1b2f0f4a10,Exportacao,USD,true,2025-03-01,",
+        "9c7e2d1a-5b3f-4e8a-8d6c-2f1e0a9b8c7d,Promocao,BRL,false,2024-12-31,2024-12-31",
+    ]
+    .join("\n");
+    let dataset_dir = temp_dir("temporal");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.listas_precos.csv"), listas).expect("write listas");
+    dataset_dir
+}
+
+fn options(streaming: bool) -> EvaluateOptions {
+    EvaluateOptions {
+        strict: false,
+        streaming,
+        chunk_rows: 2,
+        spill_dir: Some(temp_dir("temporal_spill")),
+        out_dir: Some(temp_dir("temporal_eval")),
+        ..Default::default()
+    }
+}
+
+fn evaluate(dataset_dir: &Path, streaming: bool) -> EvaluationResult {
+    EvaluationEngine::new(options(streaming))
+        .run(&load_schema(), &plan(), dataset_dir)
+        .expect("evaluate dataset")
+}
+
+#[test]
+fn plan_rules_check_order_and_window() {
+    let dataset_dir = write_dataset();
+    let in_memory = evaluate(&dataset_dir, false);
+    let streamed = evaluate(&dataset_dir, true);
+
+    for result in [&in_memory, &streamed] {
+        let temporal = &result.metrics.temporal;
+        assert_eq!(temporal.len(), 2, "{temporal:?}");
+
+        let window = &temporal[0];
+        assert_eq!(window.source, TemporalSource::PlanWindow);
+        assert_eq!(window.columns, vec!["data_inicio".to_string()]);
+        assert_eq!(window.rows_checked, 4);
+        assert_eq!(window.failures, 1);
+
+        // The derive rule takes over the data_inicio/data_fim name pair.
+        let order = &temporal[1];
+        assert_eq!(order.source, TemporalSource::PlanDerive);
+        assert_eq!(order.rule, "data_fim >= data_inicio");
+        assert_eq!(order.rows_checked, 3);
+        assert_eq!(order.failures, 1);
+
+        let failed: Vec<(&str, Option<u64>)> = result
+            .violations
+            .iter()
+            .filter(|violation| violation.code == "temporal")
+            .map(|violation| (violation.path.as_str(), violation.row_index))
+            .collect();
+        assert_eq!(
+            failed,
+            vec![
+                ("crm.listas_precos.data_inicio", Some(3)),
+                ("crm.listas_precos.data_inicio,data_fim", Some(2)),
+            ]
+        );
+        assert!(
+            result.report.contains("## Temporal checks"),
+            "{}",
+            result.report
+        );
+    }
+    assert_eq!(
+        serde_json::to_value(&streamed.metrics.temporal).expect("serialize"),
+        serde_json::to_value(&in_memory.metrics.temporal).expect("serialize")
+    );
+}
+
+#[test]
+fn column_name_pairs_are_checked_without_plan() {
+    let dataset_dir = write_dataset();
+    let result = EvaluationEngine::new(options(false))
+        .run_dataset(&load_schema(), &dataset_dir)
+        .expect("evaluate dataset");
+
+    let temporal = &result.metrics.temporal;
+    assert_eq!(temporal.len(), 1, "{temporal:?}");
+    assert_eq!(temporal[0].source, TemporalSource::ColumnNames);
+    assert_eq!(temporal[0].rule, "data_fim >= data_inicio");
+    assert_eq!(temporal[0].failures, 1);
+
+    let disabled = EvaluationEngine::new(EvaluateOptions {
+        temporal_checks: false,
+        ..options(false)
+    })
+    .run_dataset(&load_schema(), &dataset_dir)
+    .expect("evaluate dataset");
+    assert!(disabled.metrics.temporal.is_empty());
+    assert!(
+        disabled
+            .violations
+            .iter()
+            .all(|violation| violation.code != "temporal")
+    );
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use common::{load_schema, temp_dir};
+use datalchemy_eval::{
+    ColumnThreshold, EvalError, EvalThresholds, EvaluateOptions, EvaluationEngine,
+};
+
+/// Two funis (one without descricao) and four etapas, one of them orphan.
+fn write_dataset() -> PathBuf {
+    let (vendas, suporte) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let funis = format!(
+        "id,nome,descricao,ativo,data_criacao\n\
+         {vendas},Vendas,Funil de vendas,true,2024-01-01T00:00:00\n\
+         {suporte},Suporte,,true,2024-01-01T00:00:00\n"
+    );
+    let mut etapas = String::from("id,funil_id,nome,ordem,probabilidade\n");
+    for (i, funil) in [vendas, vendas, suporte, uuid::Uuid::new_v4()]
+        .iter()
+        .enumerate()
+    {
+        etapas.push_str(&format!(
+            "{},{funil},Etapa {i},{},50.00\n",
+            uuid::Uuid::new_v4(),
+            i + 1
+        ));
+    }
+    let dataset_dir = temp_dir("thresholds");
+    fs::create_dir_all(&dataset_dir).expect("create dir");
+    fs::write(dataset_dir.join("crm.funis.csv"), funis).expect("write funis");
+    fs::write(dataset_dir.join("crm.etapas_funil.csv"), etapas).expect("write etapas");
+    dataset_dir
+}
+
+fn thresholds() -> EvalThresholds {
+    EvalThresholds {
+        max_fk_violation_rate: Some(0.3),
+        columns: vec![
+            ColumnThreshold {
+                column: "crm.funis.descricao".to_string(),
+                max_null_rate: Some(0.25),
+                min_distinct_ratio: None,
+            },
+            ColumnThreshold {
+                column: "crm.funis.*".to_string(),
+                max_null_rate: None,
+                min_distinct_ratio: Some(0.6),
+            },
+            ColumnThreshold {
+                column: "crm.funis.apelido".to_string(),
+                max_null_rate: Some(0.0),
+                min_distinct_ratio: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn thresholds_turn_metrics_into_pass_fail_results() {
+    let result = EvaluationEngine::new(EvaluateOptions {
+        strict: false,
+        out_dir: Some(temp_dir("thresholds_eval")),
+        thresholds: thresholds(),
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &write_dataset())
+    .expect("evaluate dataset");
+
+    let outcomes: Vec<(&str, &str, f64, bool)> = result
+        .metrics
+        .thresholds
+        .iter()
+        .map(|result| {
+            (
+                result.path.as_str(),
+                result.metric.as_str(),
+                result.actual,
+                result.passed,
+            )
+        })
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            (
+                "crm.etapas_funil(funil_id) -> crm.funis",
+                "max_fk_violation_rate",
+                0.25,
+                true
+            ),
+            ("crm.funis.descricao", "max_null_rate", 0.5, false),
+            ("crm.funis.id", "min_distinct_ratio", 1.0, true),
+            ("crm.funis.nome", "min_distinct_ratio", 1.0, true),
+            ("crm.funis.descricao", "min_distinct_ratio", 0.5, false),
+            ("crm.funis.ativo", "min_distinct_ratio", 0.5, false),
+            ("crm.funis.data_criacao", "min_distinct_ratio", 0.5, false),
+        ]
+    );
+    let breaches: Vec<&str> = result
+        .violations
+        .iter()
+        .filter(|violation| violation.code == "threshold")
+        .map(|violation| violation.path.as_str())
+        .collect();
+    assert_eq!(breaches.len(), 4);
+    assert!(breaches.contains(&"crm.funis.descricao"));
+    assert!(
+        result
+            .metrics
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "threshold_column_missing"
+                && warning.path == "crm.funis.apelido")
+    );
+
+    let report = fs::read_to_string(&result.report_path).expect("read report");
+    assert!(report.contains("- 4 of 7 thresholds failed."));
+    assert!(report.contains("| crm.funis.descricao | max_null_rate | 0.25 | 0.5000 | fail |"));
+}
+
+#[test]
+fn failed_thresholds_fail_strict_evaluation_and_junit() {
+    let out_dir = temp_dir("thresholds_strict");
+    let err = EvaluationEngine::new(EvaluateOptions {
+        out_dir: Some(out_dir.clone()),
+        write_junit: true,
+        thresholds: EvalThresholds {
+            max_fk_violation_rate: Some(0.1),
+            columns: Vec::new(),
+        },
+        ..Default::default()
+    })
+    .run_dataset(&load_schema(), &write_dataset())
+    .expect_err("FK rate above the limit");
+    // The orphan etapa itself plus the breached FK rate threshold.
+    assert!(matches!(err, EvalError::Violations(2)), "{err}");
+
+    let junit = fs::read_to_string(out_dir.join("junit.xml")).expect("read junit");
+    assert!(junit.contains(r#"tests="7" failures="2""#), "{junit}");
+    assert!(junit.contains(r#"<failure type="thresholds" message="1 violation(s)">"#));
+}
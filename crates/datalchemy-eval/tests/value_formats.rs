@@ -0,0 +1,94 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{load_json, load_schema, temp_dir};
+use datalchemy_eval::{EvaluateOptions, EvaluationEngine};
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+use datalchemy_plan::{BooleanFormat, Plan, PlanGlobal, ValueFormats};
+
+/// The minimal example plan writing every non-default value format.
+fn plan() -> Plan {
+    let mut plan: Plan = serde_json::from_value(load_json(
+        &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json"),
+    ))
+    .expect("parse plan");
+    for target in &mut plan.targets {
+        target.rows = 20;
+    }
+    plan.global = Some(PlanGlobal {
+        locale: None,
+        formats: Some(ValueFormats {
+            date_format: Some("%d/%m/%Y".to_string()),
+            time_format: None,
+            timestamp_format: Some("%d/%m/%Y %H:%M:%S".to_string()),
+            timezone_suffix: Some("Z".to_string()),
+            boolean: Some(BooleanFormat::TF),
+            decimal_separator: Some(','),
+        }),
+    });
+    plan
+}
+
+fn generate(plan: &Plan) -> PathBuf {
+    GenerationEngine::new(GenerateOptions {
+        out_dir: temp_dir("value_formats_out"),
+        ..Default::default()
+    })
+    .run(&load_schema(), plan)
+    .expect("run generation")
+    .run_dir
+}
+
+fn options(streaming: bool) -> EvaluateOptions {
+    EvaluateOptions {
+        strict: true,
+        streaming,
+        chunk_rows: 7,
+        spill_dir: Some(temp_dir("value_formats_spill")),
+        out_dir: Some(temp_dir("value_formats_eval")),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn evaluation_reads_values_written_with_plan_formats() {
+    let plan = plan();
+    let run_dir = generate(&plan);
+
+    for streaming in [false, true] {
+        let result = EvaluationEngine::new(options(streaming))
+            .run(&load_schema(), &plan, &run_dir)
+            .expect("evaluate generated run");
+        let invalid: Vec<_> = result
+            .metrics
+            .warnings
+            .iter()
+            .filter(|warning| warning.code == "invalid_value")
+            .collect();
+        assert!(invalid.is_empty(), "streaming={streaming}: {invalid:?}");
+        assert_eq!(
+            result.metrics.constraints.check.violations, 0,
+            "streaming={streaming}: {:?}",
+            result.metrics.constraints.check
+        );
+    }
+}
+
+#[test]
+fn dataset_evaluation_uses_formats_of_resolved_plan() {
+    let run_dir = generate(&plan());
+
+    let result = EvaluationEngine::new(options(false))
+        .run_dataset(&load_schema(), &run_dir)
+        .expect("evaluate run without the plan");
+    assert!(
+        !result
+            .metrics
+            .warnings
+            .iter()
+            .any(|warning| warning.code == "invalid_value"),
+        "{:?}",
+        result.metrics.warnings
+    );
+}
@@ -0,0 +1,58 @@
+/*
+ * C API of datalchemy (libdatalchemy_ffi): generate rows of a schema.json +
+ * plan.json in-process. See docs/ffi.md.
+ *
+ * Strings returned by the library are owned by it: engine strings live until
+ * the next call on the engine, batch strings until dl_batch_free.
+ */
+#ifndef DATALCHEMY_H
+#define DATALCHEMY_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define DL_ABI_VERSION 1
+
+#define DL_OK 0
+#define DL_ERR_ARGUMENT 1
+#define DL_ERR_JSON 2
+#define DL_ERR_PLAN 3
+#define DL_ERR_GENERATION 4
+#define DL_ERR_STATE 5
+#define DL_ERR_PANIC 6
+
+typedef struct DlEngine DlEngine;
+typedef struct DlBatch DlBatch;
+
+uint32_t dl_abi_version(void);
+
+DlEngine *dl_engine_new(void);
+int32_t dl_engine_load_schema(DlEngine *engine, const char *schema_json);
+int32_t dl_engine_load_plan(DlEngine *engine, const char *plan_json);
+int32_t dl_engine_set_batch_size(DlEngine *engine, size_t batch_size);
+/* *out is NULL once every table was handed out. */
+int32_t dl_engine_next_batch(DlEngine *engine, DlBatch **out);
+/* NULL after a successful call. */
+const char *dl_engine_last_error(const DlEngine *engine);
+void dl_engine_free(DlEngine *engine);
+
+const char *dl_batch_schema(const DlBatch *batch);
+const char *dl_batch_table(const DlBatch *batch);
+uint64_t dl_batch_offset(const DlBatch *batch);
+size_t dl_batch_rows(const DlBatch *batch);
+size_t dl_batch_columns(const DlBatch *batch);
+const char *dl_batch_column_name(const DlBatch *batch, size_t column);
+/* NULL for SQL NULL. */
+const char *dl_batch_value(const DlBatch *batch, size_t row, size_t column);
+const char *dl_batch_json(const DlBatch *batch);
+void dl_batch_free(DlBatch *batch);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DATALCHEMY_H */
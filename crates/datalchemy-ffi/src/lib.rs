@@ -0,0 +1,663 @@
+//! C API for embedding the generator (`include/datalchemy.h`).
+//!
+//! JVM (JNA/Panama) and .NET (P/Invoke) test frameworks load the shared
+//! library and pull generated rows without spawning the CLI:
+//!
+//! 1. `dl_engine_new`, then `dl_engine_load_schema` and `dl_engine_load_plan`
+//!    with the schema.json / plan.json documents;
+//! 2. `dl_engine_next_batch` until it hands out `NULL`; every batch holds at
+//!    most `batch_size` rows of one table, tables in FK-safe insert order;
+//! 3. `dl_batch_free` for every batch and `dl_engine_free` at the end.
+//!
+//! Functions return a `DL_*` status; on failure `dl_engine_last_error` holds
+//! the message, starting with the CLI error code when there is one
+//! (`error[DL110]: ...`). Values are the canonical text of the CSV output, or
+//! `NULL` for SQL NULL. Strings handed out stay owned by the library.
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::thread::JoinHandle;
+
+use datalchemy_core::{Column, DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_plan::{Plan, ValidationReport, plan_json_schema, validate_plan};
+use serde_json::{Map, Number, Value, json};
+
+/// Version of the C API; bumped on incompatible changes.
+pub const DL_ABI_VERSION: u32 = 1;
+
+pub const DL_OK: i32 = 0;
+/// A NULL pointer, a string that is not UTF-8 or an out-of-range argument.
+pub const DL_ERR_ARGUMENT: i32 = 1;
+/// The schema or plan document is not valid JSON (or not a schema.json).
+pub const DL_ERR_JSON: i32 = 2;
+/// The plan does not validate against the loaded schema.
+pub const DL_ERR_PLAN: i32 = 3;
+/// Generation failed.
+pub const DL_ERR_GENERATION: i32 = 4;
+/// Call out of order (plan before schema, load after the first batch, ...).
+pub const DL_ERR_STATE: i32 = 5;
+/// A panic was caught at the boundary.
+pub const DL_ERR_PANIC: i32 = 6;
+
+const DEFAULT_BATCH_SIZE: usize = 10_000;
+/// Batches produced ahead of the caller.
+const STREAM_BUFFER: usize = 4;
+
+/// Generation engine: loaded documents and, once started, the running stream.
+pub struct DlEngine {
+    schema: Option<Arc<DatabaseSchema>>,
+    plan: Option<Plan>,
+    batch_size: usize,
+    stream: Option<Stream>,
+    last_error: Option<CString>,
+}
+
+/// Rows of one table, at most `batch_size` of them.
+pub struct DlBatch {
+    schema: CString,
+    table: CString,
+    offset: u64,
+    columns: Vec<Column>,
+    column_names: Vec<CString>,
+    rows: Vec<Vec<GeneratedValue>>,
+    text: OnceCell<Vec<Option<CString>>>,
+    json: OnceCell<CString>,
+}
+
+struct Stream {
+    rx: Option<Receiver<Message>>,
+    cancel: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+enum Message {
+    Batch(Box<DlBatch>),
+    Failed(String),
+}
+
+struct Failure {
+    status: i32,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: i32, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl DlEngine {
+    fn load_schema(&mut self, schema_json: &str) -> Result<(), Failure> {
+        self.ensure_not_started()?;
+        let schema: DatabaseSchema = serde_json::from_str(schema_json)
+            .map_err(|err| Failure::new(DL_ERR_JSON, format!("error[DL104]: json error: {err}")))?;
+        self.schema = Some(Arc::new(schema));
+        // A plan is only valid for the schema it was checked against.
+        self.plan = None;
+        Ok(())
+    }
+
+    fn load_plan(&mut self, plan_json: &str) -> Result<(), Failure> {
+        self.ensure_not_started()?;
+        let schema = self
+            .schema
+            .as_ref()
+            .ok_or_else(|| Failure::new(DL_ERR_STATE, "load the schema before the plan"))?;
+        let plan: Value = serde_json::from_str(plan_json)
+            .map_err(|err| Failure::new(DL_ERR_JSON, format!("error[DL104]: json error: {err}")))?;
+        let plan_schema = serde_json::to_value(plan_json_schema())
+            .map_err(|err| Failure::new(DL_ERR_JSON, format!("error[DL104]: json error: {err}")))?;
+        let validated = validate_plan(&plan, &plan_schema, schema).map_err(|report| {
+            Failure::new(
+                DL_ERR_PLAN,
+                format!("error[DL110]: invalid plan: {}", issue_summary(&report)),
+            )
+        })?;
+        self.plan = Some(validated.plan);
+        Ok(())
+    }
+
+    fn set_batch_size(&mut self, batch_size: usize) -> Result<(), Failure> {
+        self.ensure_not_started()?;
+        if batch_size == 0 {
+            return Err(Failure::new(DL_ERR_ARGUMENT, "batch_size must be positive"));
+        }
+        self.batch_size = batch_size;
+        Ok(())
+    }
+
+    fn ensure_not_started(&self) -> Result<(), Failure> {
+        match self.stream {
+            Some(_) => Err(Failure::new(
+                DL_ERR_STATE,
+                "generation already started; create a new engine",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// The next batch, `None` once every table was handed out.
+    fn next_batch(&mut self) -> Result<Option<Box<DlBatch>>, Failure> {
+        if self.stream.is_none() {
+            let (Some(schema), Some(plan)) = (&self.schema, &self.plan) else {
+                return Err(Failure::new(
+                    DL_ERR_STATE,
+                    "load the schema and the plan before pulling batches",
+                ));
+            };
+            self.stream = Some(Stream::start(
+                Arc::clone(schema),
+                plan.clone(),
+                self.batch_size,
+            ));
+        }
+        let Some(rx) = self.stream.as_ref().and_then(|stream| stream.rx.as_ref()) else {
+            return Ok(None);
+        };
+        match rx.recv() {
+            Ok(Message::Batch(batch)) => Ok(Some(batch)),
+            Ok(Message::Failed(message)) => {
+                self.finish();
+                Err(Failure::new(DL_ERR_GENERATION, message))
+            }
+            // The worker is done and dropped its sender.
+            Err(_) => {
+                self.finish();
+                Ok(None)
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Some(stream) = self.stream.as_mut() {
+            stream.stop();
+        }
+    }
+}
+
+impl Stream {
+    fn start(schema: Arc<DatabaseSchema>, plan: Plan, batch_size: usize) -> Self {
+        let (tx, rx) = sync_channel(STREAM_BUFFER);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let engine_cancel = Arc::clone(&cancel);
+        let worker = std::thread::spawn(move || {
+            let mut sink = ChannelSink {
+                tx: tx.clone(),
+                batch_size,
+            };
+            // The engine only writes its run directory when persisting; this
+            // out_dir is never created.
+            let engine = GenerationEngine::new(GenerateOptions {
+                out_dir: std::env::temp_dir(),
+                ..GenerateOptions::default()
+            })
+            .with_cancel(engine_cancel);
+            match engine.generate_to_sink(&schema, &plan, &mut sink) {
+                Ok(_) | Err(GenerationError::Cancelled) => {}
+                Err(err) => {
+                    let _ = tx.send(Message::Failed(format!("error[{}]: {err}", err.code())));
+                }
+            }
+        });
+        Self {
+            rx: Some(rx),
+            cancel,
+            worker: Some(worker),
+        }
+    }
+
+    /// Cancel the worker (if still running) and wait for it.
+    fn stop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        // Dropping the receiver unblocks a worker waiting on a full channel.
+        self.rx = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Sends every table to the engine's channel in batches.
+struct ChannelSink {
+    tx: SyncSender<Message>,
+    batch_size: usize,
+}
+
+impl TableSink for ChannelSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let mut columns = table.columns.clone();
+        columns.sort_by_key(|column| column.ordinal_position);
+        let output = TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: columns.iter().map(|column| column.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        };
+
+        // An empty table still yields one batch, so its columns are known.
+        let mut offset = 0;
+        loop {
+            let chunk = &rows[offset..rows.len().min(offset + self.batch_size)];
+            let batch = DlBatch {
+                schema: c_string(schema_name),
+                table: c_string(&table.name),
+                offset: offset as u64,
+                column_names: columns
+                    .iter()
+                    .map(|column| c_string(&column.name))
+                    .collect(),
+                rows: chunk
+                    .iter()
+                    .map(|row| {
+                        columns
+                            .iter()
+                            .map(|column| {
+                                row.get(&column.name.to_lowercase())
+                                    .cloned()
+                                    .unwrap_or(GeneratedValue::Null)
+                            })
+                            .collect()
+                    })
+                    .collect(),
+                columns: columns.clone(),
+                text: OnceCell::new(),
+                json: OnceCell::new(),
+            };
+            if self.tx.send(Message::Batch(Box::new(batch))).is_err() {
+                // The engine was freed.
+                return Err(GenerationError::Cancelled);
+            }
+            offset += chunk.len();
+            if offset == rows.len() {
+                return Ok(output);
+            }
+        }
+    }
+}
+
+impl DlBatch {
+    fn text(&self) -> &[Option<CString>] {
+        self.text.get_or_init(|| {
+            self.rows
+                .iter()
+                .flat_map(|row| {
+                    row.iter().zip(&self.columns).map(|(value, column)| {
+                        (!value.is_null()).then(|| c_string(&value.to_csv(column)))
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// `{"schema", "table", "offset", "columns", "rows": [{column: value}]}`.
+    fn json(&self) -> &CStr {
+        self.json.get_or_init(|| {
+            let rows: Vec<Value> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    let mut object = Map::with_capacity(self.columns.len());
+                    for (column, value) in self.columns.iter().zip(row) {
+                        let value = match value {
+                            GeneratedValue::Null => Value::Null,
+                            GeneratedValue::Bool(value) => Value::Bool(*value),
+                            GeneratedValue::Int(value) => Value::Number((*value).into()),
+                            GeneratedValue::Float(value) => {
+                                Number::from_f64(*value).map_or(Value::Null, Value::Number)
+                            }
+                            value => Value::String(value.to_csv(column)),
+                        };
+                        object.insert(column.name.clone(), value);
+                    }
+                    Value::Object(object)
+                })
+                .collect();
+            let document = json!({
+                "schema": self.schema.to_string_lossy(),
+                "table": self.table.to_string_lossy(),
+                "offset": self.offset,
+                "columns": self.columns.iter().map(|column| &column.name).collect::<Vec<_>>(),
+                "rows": rows,
+            });
+            c_string(&document.to_string())
+        })
+    }
+}
+
+fn issue_summary(report: &ValidationReport) -> String {
+    let mut summary = report
+        .errors
+        .iter()
+        .take(3)
+        .map(|issue| format!("{} {}: {}", issue.code, issue.path, issue.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    if report.errors.len() > 3 {
+        summary.push_str(&format!(" (+{} more)", report.errors.len() - 3));
+    }
+    summary
+}
+
+/// C string of `text`; interior NULs (never valid in a C string) are dropped.
+fn c_string(text: &str) -> CString {
+    CString::new(text.replace('\0', "")).unwrap_or_default()
+}
+
+/// # Safety
+///
+/// `text` is NULL or a NUL-terminated string.
+unsafe fn str_arg<'a>(text: *const c_char) -> Result<&'a str, Failure> {
+    if text.is_null() {
+        return Err(Failure::new(DL_ERR_ARGUMENT, "NULL string argument"));
+    }
+    // SAFETY: non-NULL and NUL-terminated per the caller's contract.
+    unsafe { CStr::from_ptr(text) }
+        .to_str()
+        .map_err(|err| Failure::new(DL_ERR_ARGUMENT, format!("string is not UTF-8: {err}")))
+}
+
+/// Run `body` on `engine`, recording its failure (or a panic) as the last error.
+///
+/// # Safety
+///
+/// `engine` is NULL or a live pointer from `dl_engine_new`.
+unsafe fn with_engine(
+    engine: *mut DlEngine,
+    body: impl FnOnce(&mut DlEngine) -> Result<(), Failure>,
+) -> i32 {
+    // SAFETY: NULL or live and not aliased per the caller's contract.
+    let Some(engine) = (unsafe { engine.as_mut() }) else {
+        return DL_ERR_ARGUMENT;
+    };
+    let result = catch_unwind(AssertUnwindSafe(|| body(engine)))
+        .unwrap_or_else(|_| Err(Failure::new(DL_ERR_PANIC, "internal panic")));
+    match result {
+        Ok(()) => {
+            engine.last_error = None;
+            DL_OK
+        }
+        Err(failure) => {
+            engine.last_error = Some(c_string(&failure.message));
+            failure.status
+        }
+    }
+}
+
+/// Read-only access to a batch; `fallback` when `batch` is NULL or on a panic.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live pointer from `dl_engine_next_batch`.
+unsafe fn with_batch<T>(batch: *const DlBatch, fallback: T, body: impl FnOnce(&DlBatch) -> T) -> T {
+    // SAFETY: NULL or live per the caller's contract.
+    let Some(batch) = (unsafe { batch.as_ref() }) else {
+        return fallback;
+    };
+    catch_unwind(AssertUnwindSafe(|| body(batch))).unwrap_or(fallback)
+}
+
+/// Version of the C API (`DL_ABI_VERSION`).
+#[unsafe(no_mangle)]
+pub extern "C" fn dl_abi_version() -> u32 {
+    DL_ABI_VERSION
+}
+
+/// New engine, released with `dl_engine_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn dl_engine_new() -> *mut DlEngine {
+    Box::into_raw(Box::new(DlEngine {
+        schema: None,
+        plan: None,
+        batch_size: DEFAULT_BATCH_SIZE,
+        stream: None,
+        last_error: None,
+    }))
+}
+
+/// Load a schema.json document; drops a previously loaded plan.
+///
+/// # Safety
+///
+/// `engine` comes from `dl_engine_new`; `schema_json` is NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_engine_load_schema(
+    engine: *mut DlEngine,
+    schema_json: *const c_char,
+) -> i32 {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_engine(engine, |engine| engine.load_schema(str_arg(schema_json)?)) }
+}
+
+/// Load a plan.json document, validated against the loaded schema.
+///
+/// # Safety
+///
+/// `engine` comes from `dl_engine_new`; `plan_json` is NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_engine_load_plan(
+    engine: *mut DlEngine,
+    plan_json: *const c_char,
+) -> i32 {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_engine(engine, |engine| engine.load_plan(str_arg(plan_json)?)) }
+}
+
+/// Maximum rows per batch (default 10000); set before the first batch.
+///
+/// # Safety
+///
+/// `engine` comes from `dl_engine_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_engine_set_batch_size(engine: *mut DlEngine, batch_size: usize) -> i32 {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_engine(engine, |engine| engine.set_batch_size(batch_size)) }
+}
+
+/// Store the next batch in `*out`, or `NULL` once generation is complete.
+/// The first call starts generation in a background thread.
+///
+/// # Safety
+///
+/// `engine` comes from `dl_engine_new`; `out` is a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_engine_next_batch(
+    engine: *mut DlEngine,
+    out: *mut *mut DlBatch,
+) -> i32 {
+    if out.is_null() {
+        return DL_ERR_ARGUMENT;
+    }
+    // SAFETY: `out` is valid per the caller's contract.
+    unsafe { *out = ptr::null_mut() };
+    // SAFETY: forwarded caller contract.
+    unsafe {
+        with_engine(engine, |engine| {
+            if let Some(batch) = engine.next_batch()? {
+                *out = Box::into_raw(batch);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Message of the last failed call on `engine`, `NULL` after a success. Valid
+/// until the next call on the engine.
+///
+/// # Safety
+///
+/// `engine` is NULL or comes from `dl_engine_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_engine_last_error(engine: *const DlEngine) -> *const c_char {
+    // SAFETY: NULL or live per the caller's contract.
+    unsafe { engine.as_ref() }
+        .and_then(|engine| engine.last_error.as_ref())
+        .map_or(ptr::null(), |message| message.as_ptr())
+}
+
+/// Stop generation and release the engine. Batches already handed out stay
+/// valid until their `dl_batch_free`.
+///
+/// # Safety
+///
+/// `engine` is NULL or comes from `dl_engine_new` and is not used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_engine_free(engine: *mut DlEngine) {
+    if !engine.is_null() {
+        // SAFETY: ownership goes back to Rust per the caller's contract.
+        let engine = unsafe { Box::from_raw(engine) };
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(engine)));
+    }
+}
+
+/// Schema name of the batch's table.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch; the string lives as long as the batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_schema(batch: *const DlBatch) -> *const c_char {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_batch(batch, ptr::null(), |batch| batch.schema.as_ptr()) }
+}
+
+/// Name of the batch's table.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch; the string lives as long as the batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_table(batch: *const DlBatch) -> *const c_char {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_batch(batch, ptr::null(), |batch| batch.table.as_ptr()) }
+}
+
+/// Index of the batch's first row within its table.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_offset(batch: *const DlBatch) -> u64 {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_batch(batch, 0, |batch| batch.offset) }
+}
+
+/// Number of rows in the batch (0 for an empty table).
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_rows(batch: *const DlBatch) -> usize {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_batch(batch, 0, |batch| batch.rows.len()) }
+}
+
+/// Number of columns, in table (ordinal) order.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_columns(batch: *const DlBatch) -> usize {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_batch(batch, 0, |batch| batch.columns.len()) }
+}
+
+/// Name of column `column`, `NULL` when out of range.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch; the string lives as long as the batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_column_name(
+    batch: *const DlBatch,
+    column: usize,
+) -> *const c_char {
+    // SAFETY: forwarded caller contract.
+    unsafe {
+        with_batch(batch, ptr::null(), |batch| {
+            batch
+                .column_names
+                .get(column)
+                .map_or(ptr::null(), |name| name.as_ptr())
+        })
+    }
+}
+
+/// Text of the value at `row`, `column`; `NULL` for SQL NULL or when out of
+/// range.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch; the string lives as long as the batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_value(
+    batch: *const DlBatch,
+    row: usize,
+    column: usize,
+) -> *const c_char {
+    // SAFETY: forwarded caller contract.
+    unsafe {
+        with_batch(batch, ptr::null(), |batch| {
+            if row >= batch.rows.len() || column >= batch.columns.len() {
+                return ptr::null();
+            }
+            batch.text()[row * batch.columns.len() + column]
+                .as_ref()
+                .map_or(ptr::null(), |value| value.as_ptr())
+        })
+    }
+}
+
+/// The batch as a JSON document:
+/// `{"schema", "table", "offset", "columns", "rows": [{column: value}]}`.
+///
+/// # Safety
+///
+/// `batch` is NULL or a live batch; the string lives as long as the batch.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_json(batch: *const DlBatch) -> *const c_char {
+    // SAFETY: forwarded caller contract.
+    unsafe { with_batch(batch, ptr::null(), |batch| batch.json().as_ptr()) }
+}
+
+/// Release a batch.
+///
+/// # Safety
+///
+/// `batch` is NULL or comes from `dl_engine_next_batch` and is not used
+/// afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dl_batch_free(batch: *mut DlBatch) {
+    if !batch.is_null() {
+        // SAFETY: ownership goes back to Rust per the caller's contract.
+        drop(unsafe { Box::from_raw(batch) });
+    }
+}
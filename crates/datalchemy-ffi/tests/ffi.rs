@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+use datalchemy_ffi::*;
+use serde_json::Value;
+
+fn c_json(path: &str) -> CString {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    CString::new(std::fs::read_to_string(path).expect("read json")).expect("no NUL")
+}
+
+fn schema_json() -> CString {
+    c_json("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json")
+}
+
+fn plan_json() -> CString {
+    c_json("../../plans/examples/minimal.plan.json")
+}
+
+fn text(value: *const c_char) -> Option<String> {
+    (!value.is_null()).then(|| {
+        unsafe { CStr::from_ptr(value) }
+            .to_str()
+            .expect("utf-8")
+            .to_string()
+    })
+}
+
+fn last_error(engine: *const DlEngine) -> String {
+    text(unsafe { dl_engine_last_error(engine) }).expect("last error")
+}
+
+#[test]
+fn pulls_every_table_in_batches() {
+    let (schema, plan) = (schema_json(), plan_json());
+    unsafe {
+        let engine = dl_engine_new();
+        assert_eq!(dl_engine_load_schema(engine, schema.as_ptr()), DL_OK);
+        assert_eq!(dl_engine_load_plan(engine, plan.as_ptr()), DL_OK);
+        assert_eq!(dl_engine_set_batch_size(engine, 64), DL_OK);
+        assert!(dl_engine_last_error(engine).is_null());
+
+        let mut rows: BTreeMap<String, usize> = BTreeMap::new();
+        let mut order = Vec::new();
+        loop {
+            let mut batch = ptr::null_mut();
+            assert_eq!(dl_engine_next_batch(engine, &mut batch), DL_OK);
+            if batch.is_null() {
+                break;
+            }
+            let table = text(dl_batch_table(batch)).expect("table");
+            assert_eq!(text(dl_batch_schema(batch)).as_deref(), Some("crm"));
+            let seen = rows.entry(table.clone()).or_default();
+            assert_eq!(dl_batch_offset(batch), *seen as u64);
+            assert!(dl_batch_rows(batch) <= 64);
+            *seen += dl_batch_rows(batch);
+            if order.last() != Some(&table) {
+                order.push(table.clone());
+            }
+
+            let columns = dl_batch_columns(batch);
+            assert!(dl_batch_column_name(batch, columns).is_null());
+            assert!(dl_batch_value(batch, dl_batch_rows(batch), 0).is_null());
+            if table == "usuarios" {
+                let email = (0..columns)
+                    .position(|i| text(dl_batch_column_name(batch, i)).as_deref() == Some("email"))
+                    .expect("email column");
+                let value = text(dl_batch_value(batch, 0, email)).expect("email value");
+                assert!(value.contains('@'));
+
+                let json: Value = serde_json::from_str(&text(dl_batch_json(batch)).expect("json"))
+                    .expect("parse");
+                assert_eq!(json["table"], "usuarios");
+                assert_eq!(json["rows"][0]["email"], Value::String(value));
+            }
+            dl_batch_free(batch);
+        }
+        // Missing FK parents are generated first.
+        assert_eq!(order.len(), rows.len(), "one run of batches per table");
+        let position = |name: &str| order.iter().position(|table| table == name).unwrap();
+        assert!(position("empresas") < position("contatos"));
+        assert!(position("usuarios") < position("oportunidades"));
+        assert_eq!(rows["usuarios"], 50);
+        assert_eq!(rows["contatos"], 200);
+        assert_eq!(rows["oportunidades"], 120);
+
+        let mut batch = ptr::null_mut();
+        assert_eq!(dl_engine_next_batch(engine, &mut batch), DL_OK);
+        assert!(batch.is_null(), "stays done");
+        dl_engine_free(engine);
+    }
+}
+
+#[test]
+fn reports_errors_with_codes() {
+    let (schema, plan) = (schema_json(), plan_json());
+    unsafe {
+        assert_eq!(dl_abi_version(), DL_ABI_VERSION);
+        assert_eq!(
+            dl_engine_load_schema(ptr::null_mut(), schema.as_ptr()),
+            DL_ERR_ARGUMENT
+        );
+
+        let engine = dl_engine_new();
+        assert_eq!(dl_engine_load_plan(engine, plan.as_ptr()), DL_ERR_STATE);
+        let bad = CString::new("{not json").unwrap();
+        assert_eq!(dl_engine_load_schema(engine, bad.as_ptr()), DL_ERR_JSON);
+        assert!(last_error(engine).starts_with("error[DL104]"));
+        assert_eq!(dl_engine_load_schema(engine, ptr::null()), DL_ERR_ARGUMENT);
+
+        assert_eq!(dl_engine_load_schema(engine, schema.as_ptr()), DL_OK);
+        let mut broken: Value = serde_json::from_str(plan.to_str().unwrap()).unwrap();
+        broken["targets"][0]["table"] = Value::from("nope");
+        let broken = CString::new(broken.to_string()).unwrap();
+        assert_eq!(dl_engine_load_plan(engine, broken.as_ptr()), DL_ERR_PLAN);
+        assert!(last_error(engine).starts_with("error[DL110]: invalid plan"));
+        assert_eq!(dl_engine_set_batch_size(engine, 0), DL_ERR_ARGUMENT);
+
+        let mut batch = ptr::null_mut();
+        assert_eq!(dl_engine_next_batch(engine, &mut batch), DL_ERR_STATE);
+        assert!(batch.is_null());
+        dl_engine_free(engine);
+    }
+}
+
+#[test]
+fn frees_engine_mid_stream() {
+    let (schema, plan) = (schema_json(), plan_json());
+    unsafe {
+        let engine = dl_engine_new();
+        assert_eq!(dl_engine_load_schema(engine, schema.as_ptr()), DL_OK);
+        assert_eq!(dl_engine_load_plan(engine, plan.as_ptr()), DL_OK);
+        assert_eq!(dl_engine_set_batch_size(engine, 1), DL_OK);
+        let mut batch = ptr::null_mut();
+        assert_eq!(dl_engine_next_batch(engine, &mut batch), DL_OK);
+        assert!(!batch.is_null());
+        assert_eq!(dl_engine_load_plan(engine, plan.as_ptr()), DL_ERR_STATE);
+
+        // The worker is blocked on a full channel; freeing cancels it.
+        dl_engine_free(engine);
+        assert_eq!(dl_batch_rows(batch), 1, "batch outlives its engine");
+        dl_batch_free(batch);
+    }
+}
@@ -2,7 +2,7 @@ use std::env;
 use std::path::PathBuf;
 
 use datalchemy_core::DatabaseSchema;
-use datalchemy_generate::{GenerateOptions, GenerationEngine};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
 use datalchemy_plan::Plan;
 use tracing_subscriber::EnvFilter;
 
@@ -14,12 +14,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut plan_path: Option<PathBuf> = None;
     let mut schema_path: Option<PathBuf> = None;
     let mut out_dir: Option<PathBuf> = None;
+    let mut format = OutputFormat::default();
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
             "--plan" => plan_path = args.next().map(PathBuf::from),
             "--schema" => schema_path = args.next().map(PathBuf::from),
             "--out" => out_dir = args.next().map(PathBuf::from),
+            "--format" => format = args.next().ok_or("missing --format value")?.parse()?,
             _ => {
                 if plan_path.is_none() {
                     plan_path = Some(PathBuf::from(arg));
@@ -38,7 +40,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let plan: Plan = serde_json::from_str(&plan_json)?;
     let schema: DatabaseSchema = serde_json::from_str(&schema_json)?;
 
-    let mut options = GenerateOptions::default();
+    let mut options = GenerateOptions {
+        format,
+        ..GenerateOptions::default()
+    };
     if let Some(out_dir) = out_dir {
         options.out_dir = out_dir;
     }
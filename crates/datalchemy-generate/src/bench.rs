@@ -0,0 +1,314 @@
+//! Generation throughput benchmark on a synthetic workload.
+//!
+//! The workload is a fixed two-table schema (`bench.accounts` and
+//! `bench.events`, one account per ten events) with type-driven generators,
+//! so results depend only on the engine and the output format. Each run
+//! splits `rows` events across `threads` independent shards, generated
+//! concurrently with their own seed and run directory; throughput is the total
+//! over the wall-clock time of the slowest shard.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use datalchemy_core::{
+    Column, ColumnType, Constraint, DatabaseSchema, FkAction, FkMatchType, ForeignKey, PrimaryKey,
+    SCHEMA_VERSION, Schema, Table, TableKind,
+};
+use datalchemy_plan::{PLAN_VERSION, Plan, SchemaRef, Target};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{GenerationEngine, per_sec};
+use crate::errors::GenerationError;
+use crate::model::{GenerateOptions, OutputFormat};
+
+/// Schema of the synthetic workload.
+pub const BENCH_SCHEMA: &str = "bench";
+
+/// What to measure.
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Events per run (accounts add one per ten events).
+    pub rows: u64,
+    /// Shard counts to measure; each is one run per format.
+    pub threads: Vec<usize>,
+    pub formats: Vec<OutputFormat>,
+    /// Directory for the shards' run directories.
+    pub out_dir: PathBuf,
+    pub seed: u64,
+    /// Keep the generated files instead of removing each run's output.
+    pub keep_output: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            rows: 100_000,
+            threads: vec![1],
+            formats: vec![OutputFormat::Csv],
+            out_dir: std::env::temp_dir().join("datalchemy_bench"),
+            seed: 42,
+            keep_output: false,
+        }
+    }
+}
+
+/// Throughput of one format at one thread count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub format: OutputFormat,
+    pub threads: usize,
+    /// Rows generated over every table and shard.
+    pub rows: u64,
+    pub bytes: u64,
+    pub duration_ms: u64,
+    pub rows_per_sec: f64,
+    pub bytes_per_sec: f64,
+}
+
+/// Results of [`run_bench`], comparable between releases.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// `datalchemy-generate` version that produced the numbers.
+    pub version: String,
+    pub rows: u64,
+    pub seed: u64,
+    pub results: Vec<BenchResult>,
+}
+
+/// Run every format at every thread count, in the given order.
+pub fn run_bench(options: &BenchOptions) -> Result<BenchReport, GenerationError> {
+    let schema = bench_schema();
+    let mut results = Vec::new();
+    for format in &options.formats {
+        for threads in &options.threads {
+            results.push(bench_run(&schema, options, *format, (*threads).max(1))?);
+        }
+    }
+    Ok(BenchReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        rows: options.rows,
+        seed: options.seed,
+        results,
+    })
+}
+
+fn bench_run(
+    schema: &DatabaseSchema,
+    options: &BenchOptions,
+    format: OutputFormat,
+    threads: usize,
+) -> Result<BenchResult, GenerationError> {
+    let out_dir = options
+        .out_dir
+        .join(format!("{}_{threads}t", format.as_str()));
+    let shards: Vec<u64> = (0..threads as u64)
+        .map(|shard| {
+            options.rows / threads as u64 + u64::from(shard < options.rows % threads as u64)
+        })
+        .filter(|rows| *rows > 0)
+        .collect();
+
+    let start = Instant::now();
+    let outcomes = std::thread::scope(|scope| {
+        let handles: Vec<_> = shards
+            .iter()
+            .enumerate()
+            .map(|(shard, rows)| {
+                let plan = bench_plan(schema, *rows, options.seed.wrapping_add(shard as u64));
+                let engine = GenerationEngine::new(GenerateOptions {
+                    out_dir: out_dir.join(format!("shard_{shard}")),
+                    format,
+                    ..GenerateOptions::default()
+                });
+                scope.spawn(move || engine.run(schema, &plan))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("bench shard panicked"))
+            .collect::<Vec<_>>()
+    });
+    let elapsed = start.elapsed();
+
+    let mut rows = 0;
+    let mut bytes = 0;
+    for outcome in outcomes {
+        let result = outcome?;
+        rows += result
+            .report
+            .tables
+            .iter()
+            .map(|table| table.rows_generated)
+            .sum::<u64>();
+        bytes += result.report.bytes_written;
+    }
+    if !options.keep_output {
+        let _ = std::fs::remove_dir_all(&out_dir);
+    }
+    Ok(BenchResult {
+        format,
+        threads,
+        rows,
+        bytes,
+        duration_ms: elapsed.as_millis() as u64,
+        rows_per_sec: per_sec(rows, elapsed),
+        bytes_per_sec: per_sec(bytes, elapsed),
+    })
+}
+
+/// Plan of one shard: `events` events and one account per ten of them.
+pub fn bench_plan(schema: &DatabaseSchema, events: u64, seed: u64) -> Plan {
+    let target = |table: &str, rows: u64| Target {
+        schema: BENCH_SCHEMA.to_string(),
+        table: table.to_string(),
+        rows,
+        strategy: None,
+    };
+    Plan {
+        plan_version: PLAN_VERSION.to_string(),
+        seed,
+        schema_ref: SchemaRef {
+            schema_version: schema.schema_version.clone(),
+            schema_fingerprint: None,
+            engine: schema.engine.clone(),
+        },
+        global: None,
+        targets: vec![
+            target("accounts", events.div_ceil(10).max(1)),
+            target("events", events),
+        ],
+        rules: Vec::new(),
+        rules_unsupported: Vec::new(),
+        assertions: Vec::new(),
+        options: None,
+    }
+}
+
+/// The synthetic workload's schema.
+pub fn bench_schema() -> DatabaseSchema {
+    let accounts = Table {
+        name: "accounts".to_string(),
+        kind: TableKind::Table,
+        comment: None,
+        columns: vec![
+            column(1, "id", "uuid", "uuid", false),
+            column(2, "name", "text", "text", false),
+            column(3, "email", "text", "text", true),
+            column(4, "active", "boolean", "bool", false),
+            column(
+                5,
+                "created_at",
+                "timestamp with time zone",
+                "timestamptz",
+                false,
+            ),
+        ],
+        constraints: vec![primary_key("accounts")],
+        indexes: Vec::new(),
+        partition_key: None,
+        partitions: Vec::new(),
+        row_estimate: None,
+        triggers: Vec::new(),
+        rules: Vec::new(),
+        catalog_version: None,
+        hidden_columns: Vec::new(),
+        view_definition: None,
+        depends_on: Vec::new(),
+        foreign_table: None,
+        privileges: None,
+    };
+    let events = Table {
+        name: "events".to_string(),
+        kind: TableKind::Table,
+        comment: None,
+        columns: vec![
+            column(1, "id", "uuid", "uuid", false),
+            column(2, "account_id", "uuid", "uuid", false),
+            column(3, "kind", "text", "text", false),
+            column(4, "quantity", "integer", "int4", false),
+            column(5, "amount", "numeric", "numeric", false),
+            column(6, "note", "text", "text", true),
+            column(7, "day", "date", "date", false),
+            column(
+                8,
+                "created_at",
+                "timestamp with time zone",
+                "timestamptz",
+                false,
+            ),
+        ],
+        constraints: vec![
+            primary_key("events"),
+            Constraint::ForeignKey(ForeignKey {
+                name: Some("events_account_id_fkey".to_string()),
+                columns: vec!["account_id".to_string()],
+                referenced_schema: BENCH_SCHEMA.to_string(),
+                referenced_table: "accounts".to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_update: FkAction::NoAction,
+                on_delete: FkAction::NoAction,
+                match_type: FkMatchType::Simple,
+                is_deferrable: false,
+                initially_deferred: false,
+            }),
+        ],
+        indexes: Vec::new(),
+        partition_key: None,
+        partitions: Vec::new(),
+        row_estimate: None,
+        triggers: Vec::new(),
+        rules: Vec::new(),
+        catalog_version: None,
+        hidden_columns: Vec::new(),
+        view_definition: None,
+        depends_on: Vec::new(),
+        foreign_table: None,
+        privileges: None,
+    };
+    DatabaseSchema {
+        schema_version: SCHEMA_VERSION.to_string(),
+        engine: "postgres".to_string(),
+        database: None,
+        schemas: vec![Schema {
+            name: BENCH_SCHEMA.to_string(),
+            tables: vec![accounts, events],
+        }],
+        enums: Vec::new(),
+        domains: Vec::new(),
+        composite_types: Vec::new(),
+        sequences: Vec::new(),
+        current_role: None,
+        failed_tables: Vec::new(),
+        external_tables: Vec::new(),
+        schema_fingerprint: None,
+    }
+}
+
+fn primary_key(table: &str) -> Constraint {
+    Constraint::PrimaryKey(PrimaryKey {
+        name: Some(format!("{table}_pkey")),
+        columns: vec!["id".to_string()],
+    })
+}
+
+fn column(ordinal: i16, name: &str, data_type: &str, udt_name: &str, nullable: bool) -> Column {
+    Column {
+        ordinal_position: ordinal,
+        name: name.to_string(),
+        column_type: ColumnType {
+            data_type: data_type.to_string(),
+            udt_schema: "pg_catalog".to_string(),
+            udt_name: udt_name.to_string(),
+            character_max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            collation: None,
+        },
+        is_nullable: nullable,
+        default: None,
+        identity: None,
+        generated: None,
+        comment: None,
+        stats: None,
+    }
+}
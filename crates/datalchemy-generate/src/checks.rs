@@ -77,7 +77,7 @@ pub fn evaluate_check(expression: &str, ctx: &CheckContext<'_>) -> CheckOutcome
 }
 
 fn normalize_expression(expression: &str) -> String {
-    let mut expr = expression.trim().to_string();
+    let mut expr = strip_casts(expression.trim());
     if expr.to_uppercase().starts_with("CHECK") {
         expr = expr[5..].trim().to_string();
     }
@@ -87,6 +87,20 @@ fn normalize_expression(expression: &str) -> String {
     expr
 }
 
+/// `expr` without the `::type` casts Postgres adds when it renders a check
+/// (`(VALUE)::numeric > (100)::numeric` -> `VALUE > 100`).
+pub(crate) fn strip_casts(expr: &str) -> String {
+    const TYPE: &str = r#"::\s*(?:"[^"]+"|[a-z_][a-z0-9_]*)(?:\.[a-z_][a-z0-9_]*)?(?:\s+(?:varying|precision|with(?:out)?\s+time\s+zone))?(?:\(\d+(?:\s*,\s*\d+)?\))?(?:\[\])?"#;
+    let Ok(wrapped) = Regex::new(&format!(r"(?i)\((-?[\w.]+|'[^']*')\){TYPE}")) else {
+        return expr.to_string();
+    };
+    let Ok(bare) = Regex::new(&format!("(?i){TYPE}")) else {
+        return expr.to_string();
+    };
+    let expr = wrapped.replace_all(expr, "$1");
+    bare.replace_all(&expr, "").into_owned()
+}
+
 fn split_and(expr: &str) -> Option<Vec<String>> {
     let lower = expr.to_lowercase();
     if !lower.contains(" and ") {
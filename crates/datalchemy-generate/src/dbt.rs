@@ -0,0 +1,205 @@
+//! Export of a CSV run into a dbt project's `seeds/` layout.
+//!
+//! Each table becomes `seeds/<schema>/<seed>.csv`, and `seeds/seeds.yml`
+//! declares column types and descriptions (column comment, or how the values
+//! were generated). Seeds are named after the table, prefixed with the schema
+//! when the same table name exists in several schemas. A `seeds.yml` not
+//! written by datalchemy is never overwritten.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use datalchemy_core::{Column, DatabaseSchema};
+
+use crate::dictionary::{DictionaryColumn, build_data_dictionary};
+use crate::errors::GenerationError;
+use crate::model::GenerationReport;
+
+pub const DBT_PROJECT_FILE: &str = "dbt_project.yml";
+pub const DBT_SEEDS_YML: &str = "seeds.yml";
+const GENERATED_HEADER: &str = "# Generated by datalchemy";
+
+/// Seed written into the project.
+#[derive(Debug, Clone)]
+pub struct DbtSeed {
+    pub name: String,
+    pub schema: String,
+    pub table: String,
+    /// CSV path relative to the project directory.
+    pub path: String,
+    pub rows: u64,
+}
+
+/// Copy the CSV files of `run_dir` into `project_dir/seeds` and write `seeds.yml`.
+pub fn export_dbt_seeds(
+    run_dir: &Path,
+    project_dir: &Path,
+    schema: &DatabaseSchema,
+    report: &GenerationReport,
+) -> Result<Vec<DbtSeed>, GenerationError> {
+    if !project_dir.join(DBT_PROJECT_FILE).is_file() {
+        return Err(GenerationError::InvalidPlan(format!(
+            "{} is not a dbt project (missing {DBT_PROJECT_FILE})",
+            project_dir.display()
+        )));
+    }
+    let seeds_dir = project_dir.join("seeds");
+    let yml_path = seeds_dir.join(DBT_SEEDS_YML);
+    if yml_path.exists() {
+        let existing = std::fs::read_to_string(&yml_path)?;
+        if !existing.starts_with(GENERATED_HEADER) {
+            return Err(GenerationError::Unsupported(format!(
+                "{} exists and was not generated by datalchemy",
+                yml_path.display()
+            )));
+        }
+    }
+
+    let dictionary = build_data_dictionary(schema, report);
+    let mut name_count: BTreeMap<&str, usize> = BTreeMap::new();
+    for table in &dictionary.tables {
+        *name_count.entry(table.table.as_str()).or_insert(0) += 1;
+    }
+
+    let mut seeds = Vec::new();
+    let mut yml = String::new();
+    let _ = writeln!(
+        yml,
+        "{GENERATED_HEADER} from run {}; re-export to update.",
+        report.run_id
+    );
+    yml.push_str("version: 2\n\nseeds:\n");
+
+    for table in &dictionary.tables {
+        let source = run_dir.join(format!("{}.{}.csv", table.schema, table.table));
+        if !source.is_file() {
+            return Err(GenerationError::Unsupported(format!(
+                "dbt seeds need csv output; {} not found",
+                source.display()
+            )));
+        }
+        let name = if name_count[table.table.as_str()] > 1 {
+            format!("{}_{}", table.schema, table.table)
+        } else {
+            table.table.clone()
+        };
+        let relative: PathBuf = ["seeds", table.schema.as_str(), &format!("{name}.csv")]
+            .iter()
+            .collect();
+        let target = project_dir.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&source, &target)?;
+
+        let columns = schema
+            .schemas
+            .iter()
+            .filter(|s| s.name == table.schema)
+            .flat_map(|s| s.tables.iter())
+            .find(|t| t.name == table.table)
+            .map(|t| t.columns.as_slice())
+            .unwrap_or_default();
+
+        let _ = writeln!(yml, "  - name: {}", yaml_string(&name));
+        let description = table.comment.clone().unwrap_or_else(|| {
+            format!(
+                "Synthetic data for {}.{} ({} rows).",
+                table.schema, table.table, table.rows
+            )
+        });
+        let _ = writeln!(yml, "    description: {}", yaml_string(&description));
+        yml.push_str("    config:\n");
+        let _ = writeln!(yml, "      schema: {}", yaml_string(&table.schema));
+        yml.push_str("      column_types:\n");
+        for column in &table.columns {
+            let data_type = columns
+                .iter()
+                .find(|col| col.name == column.name)
+                .map(seed_column_type)
+                .unwrap_or_else(|| column.data_type.clone());
+            let _ = writeln!(
+                yml,
+                "        {}: {}",
+                yaml_string(&column.name),
+                yaml_string(&data_type)
+            );
+        }
+        yml.push_str("    columns:\n");
+        for column in &table.columns {
+            let _ = writeln!(yml, "      - name: {}", yaml_string(&column.name));
+            let _ = writeln!(
+                yml,
+                "        description: {}",
+                yaml_string(&column_description(column))
+            );
+        }
+
+        seeds.push(DbtSeed {
+            name,
+            schema: table.schema.clone(),
+            table: table.table.clone(),
+            path: relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            rows: table.rows,
+        });
+    }
+
+    std::fs::create_dir_all(&seeds_dir)?;
+    std::fs::write(&yml_path, yml)?;
+    Ok(seeds)
+}
+
+/// Type given to dbt; user-defined types (enums, domains) do not exist in the
+/// seed target and load as `text`.
+fn seed_column_type(column: &Column) -> String {
+    if column.column_type.udt_schema == "pg_catalog" {
+        column.column_type.data_type.clone()
+    } else {
+        "text".to_string()
+    }
+}
+
+fn column_description(column: &DictionaryColumn) -> String {
+    if let Some(description) = &column.description {
+        return description.clone();
+    }
+    let mut text = format!("Synthetic ({}", column.source);
+    if let Some(generator) = &column.generator {
+        let _ = write!(text, ": {generator}");
+    }
+    text.push(')');
+    if !column.transforms.is_empty() {
+        let _ = write!(text, "; transforms: {}", column.transforms.join(" -> "));
+    }
+    if !column.pii_tags.is_empty() {
+        let _ = write!(text, "; PII: {}", column.pii_tags.join(", "));
+    }
+    text.push('.');
+    text
+}
+
+/// Double-quoted YAML scalar.
+fn yaml_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
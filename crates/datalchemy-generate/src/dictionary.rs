@@ -0,0 +1,369 @@
+//! Data dictionary of a run: `data_dictionary.md` and `data_dictionary.html`.
+//!
+//! Describes every generated table and column (type, keys, comment), how its
+//! values were produced (plan generator, column default, heuristic, fallback or
+//! foreign key), the transforms applied and the PII tags involved, so a
+//! synthetic dataset can be reviewed without reading the plan.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use datalchemy_core::{Column, Constraint, DatabaseSchema, Table};
+
+use crate::errors::GenerationError;
+use crate::model::GenerationReport;
+
+pub const DATA_DICTIONARY_MD: &str = "data_dictionary.md";
+pub const DATA_DICTIONARY_HTML: &str = "data_dictionary.html";
+
+/// Dictionary content shared by the Markdown and HTML renderings.
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDictionary {
+    pub run_id: String,
+    pub tables: Vec<DictionaryTable>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DictionaryTable {
+    pub schema: String,
+    pub table: String,
+    pub comment: Option<String>,
+    pub rows: u64,
+    pub columns: Vec<DictionaryColumn>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DictionaryColumn {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    /// `PK`, `UQ` and `FK -> schema.table.column` markers.
+    pub keys: Vec<String>,
+    /// How values were produced (see [`crate::model::ColumnSource`]); `-` when no row was generated.
+    pub source: String,
+    /// Generator id, default expression or referenced column, depending on the source.
+    pub generator: Option<String>,
+    pub transforms: Vec<String>,
+    pub pii_tags: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// Write both dictionary files into `run_dir`. Returns the bytes written.
+pub fn write_data_dictionary(
+    run_dir: &Path,
+    schema: &DatabaseSchema,
+    report: &GenerationReport,
+) -> Result<u64, GenerationError> {
+    let dictionary = build_data_dictionary(schema, report);
+    let markdown = render_markdown(&dictionary);
+    let html = render_html(&dictionary);
+    std::fs::write(run_dir.join(DATA_DICTIONARY_MD), &markdown)?;
+    std::fs::write(run_dir.join(DATA_DICTIONARY_HTML), &html)?;
+    Ok((markdown.len() + html.len()) as u64)
+}
+
+/// Collect the generated tables, in generation order, with their column sources.
+pub fn build_data_dictionary(schema: &DatabaseSchema, report: &GenerationReport) -> DataDictionary {
+    let mut tables = Vec::new();
+    for table_report in &report.tables {
+        let Some(table) = schema
+            .schemas
+            .iter()
+            .filter(|s| s.name == table_report.schema)
+            .flat_map(|s| s.tables.iter())
+            .find(|t| t.name == table_report.table)
+        else {
+            continue;
+        };
+
+        let mut columns: Vec<&Column> = table.columns.iter().collect();
+        columns.sort_by_key(|col| col.ordinal_position);
+        let columns = columns
+            .into_iter()
+            .map(|column| dictionary_column(&table_report.schema, table, column, report))
+            .collect();
+
+        tables.push(DictionaryTable {
+            schema: table_report.schema.clone(),
+            table: table.name.clone(),
+            comment: table.comment.clone(),
+            rows: table_report.rows_generated,
+            columns,
+        });
+    }
+
+    DataDictionary {
+        run_id: report.run_id.clone(),
+        tables,
+    }
+}
+
+fn dictionary_column(
+    schema_name: &str,
+    table: &Table,
+    column: &Column,
+    report: &GenerationReport,
+) -> DictionaryColumn {
+    let mut keys = Vec::new();
+    let mut reference = None;
+    for constraint in &table.constraints {
+        match constraint {
+            Constraint::PrimaryKey(pk) if pk.columns.contains(&column.name) => {
+                keys.push("PK".to_string());
+            }
+            Constraint::Unique(unique) if unique.columns.contains(&column.name) => {
+                keys.push("UQ".to_string());
+            }
+            Constraint::ForeignKey(fk) => {
+                if let Some(pos) = fk.columns.iter().position(|col| col == &column.name) {
+                    let target = format!(
+                        "{}.{}.{}",
+                        fk.referenced_schema,
+                        fk.referenced_table,
+                        fk.referenced_columns
+                            .get(pos)
+                            .map(String::as_str)
+                            .unwrap_or("?")
+                    );
+                    keys.push(format!("FK -> {target}"));
+                    reference.get_or_insert(target);
+                }
+            }
+            _ => {}
+        }
+    }
+    keys.dedup();
+
+    let key = format!("{}.{}.{}", schema_name, table.name, column.name);
+    let (source, generator, transforms, pii_tags) = match report.column_sources.get(&key) {
+        Some(source) => {
+            let generator = match source.source.as_str() {
+                "default" => column.default.clone(),
+                "foreign_key" => reference,
+                _ => source.generator_id.clone(),
+            };
+            (
+                source.source.clone(),
+                generator,
+                source.transforms.clone(),
+                source.pii_tags.clone(),
+            )
+        }
+        None => ("-".to_string(), None, Vec::new(), Vec::new()),
+    };
+
+    DictionaryColumn {
+        name: column.name.clone(),
+        data_type: column.column_type.data_type.clone(),
+        nullable: column.is_nullable,
+        keys,
+        source,
+        generator,
+        transforms,
+        pii_tags,
+        description: column.comment.clone(),
+    }
+}
+
+/// PII tag -> `schema.table.column` list.
+fn pii_summary(dictionary: &DataDictionary) -> BTreeMap<&str, BTreeSet<String>> {
+    let mut summary: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for table in &dictionary.tables {
+        for column in &table.columns {
+            for tag in &column.pii_tags {
+                summary
+                    .entry(tag.as_str())
+                    .or_default()
+                    .insert(format!("{}.{}.{}", table.schema, table.table, column.name));
+            }
+        }
+    }
+    summary
+}
+
+const COLUMN_HEADERS: [&str; 9] = [
+    "Column",
+    "Type",
+    "Nullable",
+    "Keys",
+    "Source",
+    "Generator",
+    "Transforms",
+    "PII",
+    "Description",
+];
+
+fn column_cells(column: &DictionaryColumn) -> [String; 9] {
+    [
+        column.name.clone(),
+        column.data_type.clone(),
+        if column.nullable { "yes" } else { "no" }.to_string(),
+        column.keys.join(", "),
+        column.source.clone(),
+        column.generator.clone().unwrap_or_default(),
+        column.transforms.join(" -> "),
+        column.pii_tags.join(", "),
+        column.description.clone().unwrap_or_default(),
+    ]
+}
+
+/// Markdown rendering.
+pub fn render_markdown(dictionary: &DataDictionary) -> String {
+    let mut out = String::new();
+    out.push_str("# Data dictionary\n\n");
+    let _ = writeln!(out, "Run: `{}`\n", dictionary.run_id);
+    out.push_str("All values are synthetic: each column lists how its values were generated.\n\n");
+
+    out.push_str("| Table | Rows |\n|---|---|\n");
+    for table in &dictionary.tables {
+        let _ = writeln!(
+            out,
+            "| {}.{} | {} |",
+            md_cell(&table.schema),
+            md_cell(&table.table),
+            table.rows
+        );
+    }
+
+    out.push_str("\n## PII summary\n\n");
+    let summary = pii_summary(dictionary);
+    if summary.is_empty() {
+        out.push_str("No PII tags.\n");
+    } else {
+        out.push_str("| Tag | Columns |\n|---|---|\n");
+        for (tag, columns) in &summary {
+            let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+            let _ = writeln!(
+                out,
+                "| {} | {} |",
+                md_cell(tag),
+                md_cell(&columns.join(", "))
+            );
+        }
+    }
+
+    for table in &dictionary.tables {
+        let _ = writeln!(out, "\n## {}.{}\n", table.schema, table.table);
+        if let Some(comment) = &table.comment {
+            let _ = writeln!(out, "{}\n", comment.trim());
+        }
+        let _ = writeln!(out, "Rows: {}\n", table.rows);
+        let _ = writeln!(out, "| {} |", COLUMN_HEADERS.join(" | "));
+        let _ = writeln!(out, "|{}", "---|".repeat(COLUMN_HEADERS.len()));
+        for column in &table.columns {
+            let cells: Vec<String> = column_cells(column)
+                .iter()
+                .map(|cell| md_cell(cell))
+                .collect();
+            let _ = writeln!(out, "| {} |", cells.join(" | "));
+        }
+    }
+    out
+}
+
+/// Self-contained HTML rendering.
+pub fn render_html(dictionary: &DataDictionary) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(
+        out,
+        "<title>Data dictionary {}</title>",
+        html_escape(&dictionary.run_id)
+    );
+    out.push_str(
+        "<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse;margin-bottom:1.5em}\
+         th,td{border:1px solid #ccc;padding:4px 8px;text-align:left;vertical-align:top}th{background:#f3f3f3}</style>\n",
+    );
+    out.push_str("</head>\n<body>\n<h1>Data dictionary</h1>\n");
+    let _ = writeln!(
+        out,
+        "<p>Run: <code>{}</code></p>",
+        html_escape(&dictionary.run_id)
+    );
+    out.push_str(
+        "<p>All values are synthetic: each column lists how its values were generated.</p>\n",
+    );
+
+    out.push_str("<table>\n<tr><th>Table</th><th>Rows</th></tr>\n");
+    for table in &dictionary.tables {
+        let _ = writeln!(
+            out,
+            "<tr><td><a href=\"#{0}.{1}\">{0}.{1}</a></td><td>{2}</td></tr>",
+            html_escape(&table.schema),
+            html_escape(&table.table),
+            table.rows
+        );
+    }
+    out.push_str("</table>\n");
+
+    out.push_str("<h2>PII summary</h2>\n");
+    let summary = pii_summary(dictionary);
+    if summary.is_empty() {
+        out.push_str("<p>No PII tags.</p>\n");
+    } else {
+        out.push_str("<table>\n<tr><th>Tag</th><th>Columns</th></tr>\n");
+        for (tag, columns) in &summary {
+            let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                html_escape(tag),
+                html_escape(&columns.join(", "))
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    for table in &dictionary.tables {
+        let _ = writeln!(
+            out,
+            "<h2 id=\"{0}.{1}\">{0}.{1}</h2>",
+            html_escape(&table.schema),
+            html_escape(&table.table)
+        );
+        if let Some(comment) = &table.comment {
+            let _ = writeln!(out, "<p>{}</p>", html_escape(comment.trim()));
+        }
+        let _ = writeln!(out, "<p>Rows: {}</p>", table.rows);
+        out.push_str("<table>\n<tr>");
+        for header in COLUMN_HEADERS {
+            let _ = write!(out, "<th>{header}</th>");
+        }
+        out.push_str("</tr>\n");
+        for column in &table.columns {
+            out.push_str("<tr>");
+            for cell in column_cells(column) {
+                let _ = write!(out, "<td>{}</td>", html_escape(&cell));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn md_cell(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", " ")
+        .replace('\n', " ")
+}
+
+fn html_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
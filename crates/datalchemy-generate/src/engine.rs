@@ -1,6 +1,9 @@
 use std::any::Any;
 use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::time::Instant;
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
@@ -11,21 +14,32 @@ use serde_json::Value;
 use tracing::{info, warn};
 
 use datalchemy_core::{
-    CheckConstraint, ColumnType, Constraint, DatabaseSchema, EnumType, ForeignKey, Table,
+    CheckConstraint, ColumnType, CompositeType, Constraint, DatabaseSchema, EngineObserver,
+    EnumType, ForeignKey, RunOutcome, Sequence, Stage, Table,
 };
 use datalchemy_plan::{
-    ConstraintKind, ConstraintMode, ForeignKeyMode, GeneratorRef, Plan, Rule, TransformRule,
+    ConstraintKind, ConstraintMode, EntityRef, ForeignKeyMode, GeneratorRef, Plan, Rule,
+    TransformRule,
 };
 
-use crate::checks::{CheckContext, CheckOutcome, evaluate_check};
-use crate::errors::GenerationError;
+use crate::checks::{CheckContext, CheckOutcome, evaluate_check, strip_casts};
+use crate::dictionary::write_data_dictionary;
+use crate::entity::{
+    EntityStage, entity_key, entity_rng, generate_entity_value, record_entity_usage,
+};
+use crate::errors::{ErrorContext, ErrorInfo, GenerationError};
 use crate::foreign::InMemoryForeignContext;
+use crate::generated;
 use crate::generators::{
     GeneratedValue, GeneratorContext, GeneratorRegistry, RowContext, TransformContext,
 };
-use crate::model::{GenerateOptions, GenerationIssue, GenerationReport, TableReport};
-use crate::output::csv::write_table_csv;
+use crate::manifest::write_files_manifest;
+use crate::model::{ColumnSource, GenerateOptions, GenerationIssue, GenerationReport, TableReport};
+use crate::output::stream::StreamSink;
+use crate::output::{TableSink, create_sink, value_formats, write_schema_sql};
 use crate::planner::plan_tables;
+use crate::profile::{TableProfile, ValueProfile};
+use crate::progress::{GenerationProgress, PlannedTable, RunHooks};
 
 /// Result of a generation run.
 #[derive(Debug, Clone)]
@@ -38,11 +52,47 @@ pub struct GenerationResult {
 #[derive(Debug, Clone)]
 pub struct GenerationEngine {
     options: GenerateOptions,
+    hooks: RunHooks,
+    profile: Option<ValueProfile>,
 }
 
 impl GenerationEngine {
     pub fn new(options: GenerateOptions) -> Self {
-        Self { options }
+        Self {
+            options,
+            hooks: RunHooks::default(),
+            profile: None,
+        }
+    }
+
+    /// Report the progress of every run to `progress`, from the running thread.
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(GenerationProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Report tables, generated rows, report issues and the end of every run
+    /// to `observer`, from the running thread.
+    pub fn with_observer(mut self, observer: Arc<dyn EngineObserver>) -> Self {
+        self.hooks.observer = Some(observer);
+        self
+    }
+
+    /// Stop runs with [`GenerationError::Cancelled`] once `cancel` is set; the
+    /// flag is checked between rows and the partial run directory is removed.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.hooks.cancel = Some(cancel);
+        self
+    }
+
+    /// Generate columns left to the engine's heuristics from the sampled
+    /// distributions of `profile` (see [`crate::profile`]).
+    pub fn with_profile(mut self, profile: ValueProfile) -> Self {
+        self.profile = Some(profile);
+        self
     }
 
     pub fn run(
@@ -50,6 +100,77 @@ impl GenerationEngine {
         schema: &DatabaseSchema,
         plan: &Plan,
     ) -> Result<GenerationResult, GenerationError> {
+        self.run_inner(schema, plan, None, true)
+    }
+
+    /// Run generation handing every table to `sink` instead of the configured format.
+    ///
+    /// The run directory still receives `resolved_plan.json` and
+    /// `generation_report.json`.
+    pub fn run_with_sink(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        sink: &mut dyn TableSink,
+    ) -> Result<GenerationResult, GenerationError> {
+        self.run_inner(schema, plan, Some(sink), true)
+    }
+
+    /// Run generation handing every table to `sink` only; no run directory is
+    /// created (for in-process consumers such as the gRPC server).
+    pub fn generate_to_sink(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        sink: &mut dyn TableSink,
+    ) -> Result<GenerationReport, GenerationError> {
+        Ok(self.run_inner(schema, plan, Some(sink), false)?.report)
+    }
+
+    /// Generate the whole plan and write only `schema_name.table_name` to `writer`.
+    ///
+    /// `format` is the configured one (`csv` or `pg_copy`). No run directory is
+    /// created; rows are identical to those of a full run with the same plan.
+    pub fn stream_table(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        schema_name: &str,
+        table_name: &str,
+        writer: &mut dyn Write,
+    ) -> Result<GenerationReport, GenerationError> {
+        let mut sink = StreamSink::new(
+            writer,
+            schema_name,
+            table_name,
+            self.options.format,
+            value_formats(plan),
+        )?;
+        let result = self.run_inner(schema, plan, Some(&mut sink), false)?;
+        if !sink.written() {
+            return Err(GenerationError::InvalidPlan(format!(
+                "table '{schema_name}.{table_name}' is not generated by the plan"
+            )));
+        }
+        Ok(result.report)
+    }
+
+    /// `persist` writes the run directory and its artifacts; without it only the
+    /// sink receives output.
+    fn run_inner(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        sink: Option<&mut dyn TableSink>,
+        persist: bool,
+    ) -> Result<GenerationResult, GenerationError> {
+        let resolved;
+        let schema = if schema.domains.is_empty() {
+            schema
+        } else {
+            resolved = schema.resolve_domains();
+            &resolved
+        };
         let start = Instant::now();
         let run_id = uuid::Uuid::new_v4().to_string();
         let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H-%M-%SZ").to_string();
@@ -57,7 +178,9 @@ impl GenerationEngine {
             .options
             .out_dir
             .join(format!("{timestamp}__run_{run_id}"));
-        std::fs::create_dir_all(&run_dir)?;
+        if persist {
+            std::fs::create_dir_all(&run_dir)?;
+        }
 
         let strict = plan
             .options
@@ -68,24 +191,49 @@ impl GenerationEngine {
         let plan_index = PlanIndex::new(&plan, strict)?;
         let tasks = plan_tables(schema, &plan, self.options.auto_generate_parents)?;
         let tasks_count = tasks.len();
+        self.hooks.emit(|| {
+            GenerationProgress::Planned(
+                tasks
+                    .iter()
+                    .map(|task| PlannedTable {
+                        schema: task.schema.clone(),
+                        table: task.table.clone(),
+                        rows: task.rows,
+                    })
+                    .collect(),
+            )
+        });
         let schema_index = SchemaIndex::new(schema);
         let enum_index = EnumIndex::new(schema);
         let registry = GeneratorRegistry::new();
         let mut foreign_context = InMemoryForeignContext::new();
         let base_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default();
 
-        let plan_path = run_dir.join("resolved_plan.json");
-        std::fs::write(&plan_path, serde_json::to_vec_pretty(&plan)?)?;
+        if persist {
+            let plan_path = run_dir.join("resolved_plan.json");
+            std::fs::write(&plan_path, serde_json::to_vec_pretty(&plan)?)?;
+        }
 
         let mut report = GenerationReport::new(run_id.clone());
         let mut bytes_written = 0_u64;
+        let mut outputs = Vec::new();
+        let mut owned_sink;
+        let sink: &mut dyn TableSink = match sink {
+            Some(sink) => sink,
+            None => {
+                owned_sink = create_sink(self.options.format, &run_dir, schema, &plan)?;
+                owned_sink.as_mut()
+            }
+        };
         let mut table_data: HashMap<String, TableData> = HashMap::new();
+        let mut issues_seen = (0, 0);
 
         info!(
             run_id = %run_id,
             tables = tasks_count,
             strict,
             seed = plan.seed,
+            format = self.options.format.as_str(),
             "generation started"
         );
 
@@ -105,10 +253,21 @@ impl GenerationEngine {
                         })?;
                     let table_key = table_key(&schema_name, &table_name);
 
-                    let table_ctx =
+                    let mut table_ctx =
                         TableContext::new(&schema_name, table, schema, &plan_index, base_date);
+                    table_ctx.profile = self
+                        .profile
+                        .as_ref()
+                        .and_then(|profile| profile.table(&schema_name, &table_name));
 
                     let table_seed = hash_seed(plan.seed, &table_key);
+                    self.hooks.emit(|| GenerationProgress::TableStarted {
+                        schema: schema_name.clone(),
+                        table: table_name.clone(),
+                    });
+                    self.hooks.observe(|observer| {
+                        observer.on_table_start(Stage::Generate, &schema_name, &table_name)
+                    });
                     info!(
                         schema = %schema_name,
                         table = %table_name,
@@ -127,21 +286,63 @@ impl GenerationEngine {
                         &self.options,
                         &mut table_data,
                         &mut report,
-                    )?;
-
-                    let csv_path = run_dir.join(format!("{}.{}.csv", schema_name, table_name));
-                    bytes_written += write_table_csv(&csv_path, table, &result.rows)?;
+                        &self.hooks,
+                    )
+                    .map_err(|err| err.in_table(&schema_name, &table_name))?;
+
+                    let output = sink
+                        .write_table(&schema_name, table, &result.rows)
+                        .map_err(|err| err.in_table(&schema_name, &table_name))?;
+                    self.hooks.observe(|observer| {
+                        observer.on_row_batch(
+                            Stage::Generate,
+                            &schema_name,
+                            &table_name,
+                            result.rows.len() as u64,
+                        )
+                    });
+                    bytes_written += output.bytes;
+                    outputs.push(output);
+                    for issue in sink.take_warnings() {
+                        record_warning(&mut report, issue);
+                    }
 
+                    let table_elapsed = table_start.elapsed();
+                    let peak_memory_bytes = estimate_rows_bytes(&result.rows);
                     report.tables.push(TableReport {
                         schema: schema_name.clone(),
                         table: table_name.clone(),
                         rows_requested: task.rows,
                         rows_generated: result.rows.len() as u64,
                         retries: result.retries,
+                        duration_ms: table_elapsed.as_millis() as u64,
+                        rows_per_sec: per_sec(result.rows.len() as u64, table_elapsed),
+                        retries_per_sec: per_sec(result.retries, table_elapsed),
+                        peak_memory_bytes,
                     });
                     report.retries_total += result.retries;
+                    report.peak_memory_bytes += peak_memory_bytes;
+                    self.hooks.emit(|| GenerationProgress::TableFinished {
+                        schema: schema_name.clone(),
+                        table: table_name.clone(),
+                        rows: result.rows.len() as u64,
+                        duration_ms: table_elapsed.as_millis() as u64,
+                    });
+                    self.hooks.observe_issues(&report, &mut issues_seen);
 
                     foreign_context.ingest_table(table_ctx.schema, table, &result.rows)?;
+                    // Rows routed to a leaf partition also belong to its
+                    // partitioned tables, which foreign keys reference.
+                    for (parent_schema, parent) in schema_index.partition_ancestors(&table_key) {
+                        let parent_data = table_data
+                            .entry(self::table_key(&parent_schema, &parent.name))
+                            .or_insert_with(|| TableData {
+                                rows: Vec::new(),
+                                retries: 0,
+                            });
+                        parent_data.rows.extend(result.rows.iter().cloned());
+                        foreign_context.ingest_table(&parent_schema, parent, &parent_data.rows)?;
+                    }
                     table_data.insert(table_key, result);
 
                     info!(
@@ -149,11 +350,17 @@ impl GenerationEngine {
                         table = %table_name,
                         rows_generated = report.tables.last().map(|t| t.rows_generated).unwrap_or(0),
                         retries = report.tables.last().map(|t| t.retries).unwrap_or(0),
-                        duration_ms = table_start.elapsed().as_millis() as u64,
+                        duration_ms = report.tables.last().map(|t| t.duration_ms).unwrap_or(0),
                         "table generated"
                     );
                 }
 
+                bytes_written += sink.finish(&outputs)?;
+                if persist {
+                    write_schema_sql(&run_dir, schema)?;
+                    write_data_dictionary(&run_dir, schema, &report)?;
+                    write_files_manifest(&run_dir, &outputs)?;
+                }
                 Ok(())
             },
         ));
@@ -161,20 +368,23 @@ impl GenerationEngine {
         let elapsed = start.elapsed();
         report.bytes_written = bytes_written;
         report.duration_ms = elapsed.as_millis() as u64;
-        report.throughput_bytes_per_sec = if elapsed.as_secs_f64() > 0.0 {
-            bytes_written as f64 / elapsed.as_secs_f64()
-        } else {
-            0.0
-        };
+        report.throughput_bytes_per_sec = per_sec(bytes_written, elapsed);
 
         let report_path = run_dir.join("generation_report.json");
         let write_report = |report: &GenerationReport| -> Result<(), GenerationError> {
-            std::fs::write(&report_path, serde_json::to_vec_pretty(report)?)?;
+            if persist {
+                std::fs::write(&report_path, serde_json::to_vec_pretty(report)?)?;
+            }
             Ok(())
         };
 
+        let mut finish = |report: &GenerationReport, outcome: RunOutcome| {
+            self.hooks.observe_issues(report, &mut issues_seen);
+            self.hooks.observe_finish(report, outcome);
+        };
         match outcome {
             Ok(Ok(())) => {
+                finish(&report, RunOutcome::Completed);
                 write_report(&report)?;
                 info!(
                     run_id = %run_id,
@@ -185,17 +395,35 @@ impl GenerationEngine {
                 );
                 Ok(GenerationResult { run_dir, report })
             }
+            // A cancelled run leaves no partial files behind.
+            Ok(Err(GenerationError::Cancelled)) => {
+                if persist {
+                    let _ = std::fs::remove_dir_all(&run_dir);
+                }
+                finish(&report, RunOutcome::Cancelled);
+                warn!(run_id = %run_id, "generation cancelled");
+                Err(GenerationError::Cancelled)
+            }
             Ok(Err(err)) => {
                 record_generation_failure(&mut report, err.to_string());
+                report.error = Some(err.info());
+                finish(&report, RunOutcome::Failed);
                 write_report(&report)?;
                 warn!(run_id = %run_id, error = %err, "generation failed");
                 Err(err)
             }
             Err(panic) => {
-                record_generation_failure(&mut report, panic_message(panic));
+                let message = panic_message(panic);
+                record_generation_failure(&mut report, message.clone());
+                report.error = Some(ErrorInfo {
+                    code: "DL213".to_string(),
+                    message,
+                    context: ErrorContext::default(),
+                });
+                finish(&report, RunOutcome::Failed);
                 write_report(&report)?;
                 warn!(run_id = %run_id, "generation panicked");
-                Err(GenerationError::Failed(report))
+                Err(GenerationError::Failed(Box::new(report)))
             }
         }
     }
@@ -206,22 +434,52 @@ struct TableData {
     retries: u64,
 }
 
-struct TableContext<'a> {
-    schema: &'a str,
-    table: &'a Table,
+pub(crate) fn per_sec(count: u64, elapsed: std::time::Duration) -> f64 {
+    if elapsed.as_secs_f64() > 0.0 {
+        count as f64 / elapsed.as_secs_f64()
+    } else {
+        0.0
+    }
+}
+
+/// Rough heap size of generated rows: map entries, column names and text values.
+pub(crate) fn estimate_rows_bytes(rows: &[HashMap<String, GeneratedValue>]) -> u64 {
+    let entry = (std::mem::size_of::<String>() + std::mem::size_of::<GeneratedValue>()) as u64;
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|(column, value)| {
+                    let text = match value {
+                        GeneratedValue::Text(text) | GeneratedValue::Uuid(text) => text.len(),
+                        _ => 0,
+                    };
+                    entry + (column.len() + text) as u64
+                })
+                .sum::<u64>()
+        })
+        .sum()
+}
+
+pub(crate) struct TableContext<'a> {
+    pub(crate) schema: &'a str,
+    pub(crate) table: &'a Table,
     primary_keys: Vec<Vec<String>>,
     unique_constraints: Vec<Vec<String>>,
     unique_columns: HashSet<String>,
     check_constraints: Vec<&'a CheckConstraint>,
-    foreign_keys: Vec<ForeignKey>,
+    pub(crate) foreign_keys: Vec<ForeignKey>,
     numeric_bounds: HashMap<String, NumericBounds>,
     current_date_columns: HashSet<String>,
     email_columns: HashSet<String>,
-    base_date: NaiveDate,
+    comment_generators: HashMap<String, String>,
+    sequences: HashMap<String, Sequence>,
+    composites: HashMap<String, CompositeType>,
+    profile: Option<&'a TableProfile>,
+    pub(crate) base_date: NaiveDate,
 }
 
 impl<'a> TableContext<'a> {
-    fn new(
+    pub(crate) fn new(
         schema_name: &'a str,
         table: &'a Table,
         schema: &'a DatabaseSchema,
@@ -252,14 +510,31 @@ impl<'a> TableContext<'a> {
                 Constraint::ForeignKey(fk) => {
                     foreign_keys.push(fk.clone());
                 }
+                Constraint::Exclusion(_) => {}
             }
         }
 
         let numeric_bounds = extract_numeric_bounds(schema_name, table, plan_index);
         let current_date_columns = extract_current_date_columns(table);
         let email_columns = extract_email_columns(table);
-
-        let _ = schema; // reserved for future schema-aware extensions
+        let comment_generators = extract_comment_generators(table);
+
+        let sequences = table
+            .columns
+            .iter()
+            .filter_map(|column| {
+                let sequence = schema.column_sequence(schema_name, &table.name, &column.name)?;
+                Some((column.name.to_lowercase(), sequence.clone()))
+            })
+            .collect();
+        let composites = table
+            .columns
+            .iter()
+            .filter_map(|column| {
+                let composite = schema.composite_type(&column.column_type)?;
+                Some((column.name.to_lowercase(), composite.clone()))
+            })
+            .collect();
 
         Self {
             schema: schema_name,
@@ -272,29 +547,36 @@ impl<'a> TableContext<'a> {
             numeric_bounds,
             current_date_columns,
             email_columns,
+            comment_generators,
+            sequences,
+            composites,
+            profile: None,
             base_date,
         }
     }
 }
 
-struct ColumnRule {
-    generator_id: String,
-    generator_locale: Option<String>,
-    params: Option<Value>,
-    transforms: Vec<TransformRule>,
+pub(crate) struct ColumnRule {
+    pub(crate) generator_id: String,
+    pub(crate) generator_locale: Option<String>,
+    pub(crate) params: Option<Value>,
+    pub(crate) transforms: Vec<TransformRule>,
     input_columns: Vec<String>,
+    pub(crate) entity: Option<EntityRef>,
 }
 
-struct PlanIndex {
+pub(crate) struct PlanIndex {
     column_rules: HashMap<String, ColumnRule>,
     constraint_policies: HashMap<String, ConstraintMode>,
     fk_strategies: HashMap<String, ForeignKeyMode>,
     allow_fk_disable: bool,
     global_locale: Option<String>,
     strict: bool,
+    /// Plan seed, for entity values.
+    pub(crate) seed: u64,
 }
 
-fn normalize_plan(plan: &Plan) -> Plan {
+pub(crate) fn normalize_plan(plan: &Plan) -> Plan {
     let mut plan = plan.clone();
     let mut rules = Vec::with_capacity(plan.rules.len());
     for rule in plan.rules {
@@ -314,7 +596,7 @@ fn normalize_plan(plan: &Plan) -> Plan {
 }
 
 impl PlanIndex {
-    fn new(plan: &Plan, strict: bool) -> Result<Self, GenerationError> {
+    pub(crate) fn new(plan: &Plan, strict: bool) -> Result<Self, GenerationError> {
         let mut column_rules = HashMap::new();
         let mut constraint_policies = HashMap::new();
         let mut fk_strategies = HashMap::new();
@@ -340,6 +622,7 @@ impl PlanIndex {
                             params: params.clone(),
                             transforms: rule.transforms.clone(),
                             input_columns: parse_input_columns_strict(&params)?,
+                            entity: rule.entity.clone(),
                         },
                     );
                 }
@@ -351,6 +634,8 @@ impl PlanIndex {
                     let key = table_key(&rule.schema, &rule.table);
                     fk_strategies.insert(key, rule.mode.clone());
                 }
+                // Output layout only; read by the output sinks.
+                Rule::FixedWidth(_) | Rule::OutputPartition(_) => {}
             }
         }
 
@@ -367,6 +652,7 @@ impl PlanIndex {
             allow_fk_disable,
             global_locale,
             strict,
+            seed: plan.seed,
         })
     }
 
@@ -384,7 +670,12 @@ impl PlanIndex {
             .unwrap_or(ForeignKeyMode::Respect)
     }
 
-    fn column_rule(&self, schema: &str, table: &str, column: &str) -> Option<&ColumnRule> {
+    pub(crate) fn column_rule(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+    ) -> Option<&ColumnRule> {
         self.column_rules.get(&column_key(schema, table, column))
     }
 }
@@ -413,32 +704,58 @@ fn parse_input_columns_strict(params: &Option<Value>) -> Result<Vec<String>, Gen
     Ok(columns)
 }
 
-struct SchemaIndex<'a> {
+pub(crate) struct SchemaIndex<'a> {
     tables: HashMap<String, &'a Table>,
+    /// Partitioned table of each partition, by `schema.table`.
+    partition_parents: HashMap<String, String>,
 }
 
 impl<'a> SchemaIndex<'a> {
-    fn new(schema: &'a DatabaseSchema) -> Self {
+    pub(crate) fn new(schema: &'a DatabaseSchema) -> Self {
         let mut tables = HashMap::new();
+        let mut partition_parents = HashMap::new();
         for db_schema in &schema.schemas {
             for table in &db_schema.tables {
-                tables.insert(table_key(&db_schema.name, &table.name), table);
+                let key = table_key(&db_schema.name, &table.name);
+                for partition in &table.partitions {
+                    partition_parents
+                        .insert(table_key(&partition.schema, &partition.name), key.clone());
+                }
+                tables.insert(key, table);
             }
         }
-        Self { tables }
+        Self {
+            tables,
+            partition_parents,
+        }
     }
 
-    fn table(&self, schema: &str, table: &str) -> Option<&'a Table> {
+    pub(crate) fn table(&self, schema: &str, table: &str) -> Option<&'a Table> {
         self.tables.get(&table_key(schema, table)).copied()
     }
+
+    /// Partitioned tables above `key`, nearest first.
+    fn partition_ancestors(&self, key: &str) -> Vec<(String, &'a Table)> {
+        let mut ancestors = Vec::new();
+        let mut current = key;
+        while let Some(parent) = self.partition_parents.get(current) {
+            if let (Some((schema, _)), Some(table)) =
+                (parent.split_once('.'), self.tables.get(parent))
+            {
+                ancestors.push((schema.to_string(), *table));
+            }
+            current = parent;
+        }
+        ancestors
+    }
 }
 
-struct EnumIndex {
+pub(crate) struct EnumIndex {
     enums: HashMap<String, EnumType>,
 }
 
 impl EnumIndex {
-    fn new(schema: &DatabaseSchema) -> Self {
+    pub(crate) fn new(schema: &DatabaseSchema) -> Self {
         let mut enums = HashMap::new();
         for enum_type in &schema.enums {
             enums.insert(
@@ -449,7 +766,7 @@ impl EnumIndex {
         Self { enums }
     }
 
-    fn values_for(&self, column: &datalchemy_core::Column) -> Option<&[String]> {
+    pub(crate) fn values_for(&self, column: &datalchemy_core::Column) -> Option<&[String]> {
         let key = enum_key(&column.column_type.udt_schema, &column.column_type.udt_name);
         self.enums
             .get(&key)
@@ -468,8 +785,10 @@ fn generate_table(
     options: &GenerateOptions,
     table_data: &mut HashMap<String, TableData>,
     report: &mut GenerationReport,
+    hooks: &RunHooks,
 ) -> Result<TableData, GenerationError> {
     let mut retries_total = 0;
+    record_generated_columns(ctx, report);
 
     for _ in 0..options.max_attempts_table {
         let mut rows_out = Vec::new();
@@ -477,6 +796,9 @@ fn generate_table(
         let mut failed = false;
 
         for row_index in 0..rows {
+            if hooks.cancelled() {
+                return Err(GenerationError::Cancelled);
+            }
             let mut row_attempts = 0;
             loop {
                 row_attempts += 1;
@@ -509,14 +831,24 @@ fn generate_table(
                 columns.sort_by_key(|col| col.ordinal_position);
 
                 let mut base_columns = Vec::new();
+                let mut entity_columns = Vec::new();
                 let mut derive_columns = Vec::new();
+                let mut generated_columns = Vec::new();
                 for column in columns {
+                    if column.generated.is_some() {
+                        // Computed by the database; see `generated`.
+                        generated_columns.push(column);
+                        continue;
+                    }
                     let rule = plan_index.column_rule(ctx.schema, &ctx.table.name, &column.name);
                     if rule
                         .map(|rule| is_derive_generator(&rule.generator_id))
                         .unwrap_or(false)
                     {
                         derive_columns.push(column);
+                    } else if rule.is_some_and(|rule| rule.entity.is_some()) {
+                        // After the base columns, which hold the entity keys.
+                        entity_columns.push(column);
                     } else {
                         base_columns.push(column);
                     }
@@ -524,24 +856,51 @@ fn generate_table(
 
                 let derive_order = resolve_derive_order(ctx, plan_index, &derive_columns)?;
 
-                for column in base_columns.iter().chain(derive_order.iter()) {
+                for column in base_columns
+                    .iter()
+                    .chain(entity_columns.iter())
+                    .chain(derive_order.iter())
+                {
                     let key = column.name.to_lowercase();
                     if row.contains_key(&key) {
+                        if row_index == 0 {
+                            record_column_source(
+                                report,
+                                ctx,
+                                column,
+                                "foreign_key",
+                                None,
+                                &[],
+                                &[],
+                            );
+                        }
                         continue;
                     }
 
-                    let value = generate_column_value(
-                        ctx,
-                        column,
-                        row_index,
-                        &row,
-                        registry,
-                        enum_index,
-                        plan_index,
-                        foreign_context,
-                        &mut rng,
-                        report,
-                    )?;
+                    let entity = plan_index
+                        .column_rule(ctx.schema, &ctx.table.name, &column.name)
+                        .and_then(|rule| Some((rule, entity_key(rule, &row)?)));
+                    let value = match entity {
+                        Some((rule, entity_key)) => generate_entity_value(
+                            ctx, column, rule, entity_key, plan_index, registry, enum_index,
+                        )
+                        .inspect(|_| {
+                            record_entity_usage(ctx, column, rule, row_index, registry, report)
+                        }),
+                        None => generate_column_value(
+                            ctx,
+                            column,
+                            row_index,
+                            &row,
+                            registry,
+                            enum_index,
+                            plan_index,
+                            foreign_context,
+                            &mut rng,
+                            report,
+                        ),
+                    }
+                    .map_err(|err| err.at_column(&column.name))?;
 
                     row.insert(key.clone(), value);
                 }
@@ -550,6 +909,12 @@ fn generate_table(
                     ctx, &mut row, row_index, registry, plan_index, &mut rng, report,
                 )?;
 
+                for column in &generated_columns {
+                    if let Some(value) = generated::compute(column, &row) {
+                        row.insert(column.name.to_lowercase(), value);
+                    }
+                }
+
                 if let Some(error) = enforce_not_null(ctx, &row) {
                     if row_attempts >= options.max_attempts_row {
                         if plan_index.strict {
@@ -603,6 +968,14 @@ fn generate_table(
                 }
 
                 rows_out.push(row);
+                let generated = rows_out.len() as u64;
+                if hooks.reports_rows(generated, rows) {
+                    hooks.emit(|| GenerationProgress::Rows {
+                        schema: ctx.schema.to_string(),
+                        table: ctx.table.name.clone(),
+                        rows: generated,
+                    });
+                }
                 break;
             }
 
@@ -799,6 +1172,21 @@ fn generate_column_value(
 
     let rule = plan_index.column_rule(ctx.schema, &ctx.table.name, &column.name);
     let mut value = if let Some(rule) = rule {
+        if row_index == 0 {
+            let tags = registry
+                .generator(&rule.generator_id)
+                .map(|generator| generator.pii_tags())
+                .unwrap_or(&[]);
+            record_column_source(
+                report,
+                ctx,
+                column,
+                "plan",
+                Some(&rule.generator_id),
+                tags,
+                &rule.transforms,
+            );
+        }
         if unique_hint && !is_derive_generator(&rule.generator_id) {
             generate_unique_from_rule(rule, column, row_index, ctx.base_date)
         } else {
@@ -816,8 +1204,55 @@ fn generate_column_value(
                 plan_index,
             )?
         }
+    } else if let Some(sequence) = ctx.sequences.get(&key) {
+        if row_index == 0 {
+            record_column_source(report, ctx, column, "sequence", None, &[], &[]);
+        }
+        GeneratedValue::Int(sequence.value_at(row_index))
+    } else if let Some(composite) = ctx.composites.get(&key) {
+        if row_index == 0 {
+            record_column_source(report, ctx, column, "composite", None, &[], &[]);
+        }
+        generate_composite(composite, ctx.base_date, rng)
+    } else if let Some((generator_id, value, tags)) = match ctx.comment_generators.get(&key) {
+        Some(generator_id) => generate_with_generator(
+            generator_id,
+            ctx,
+            column,
+            row_index,
+            row,
+            registry,
+            enum_index,
+            foreign_context,
+            rng,
+            plan_index.global_locale.as_deref(),
+        )?,
+        None => None,
+    } {
+        report.record_generator_usage(generator_id);
+        record_pii_tags(report, column, tags);
+        if row_index == 0 {
+            record_column_source(
+                report,
+                ctx,
+                column,
+                "comment",
+                Some(generator_id),
+                tags,
+                &[],
+            );
+        }
+        value
     } else if let Some(default) = generate_default(column, ctx.base_date, rng) {
+        if row_index == 0 {
+            record_column_source(report, ctx, column, "default", None, &[], &[]);
+        }
         default
+    } else if let Some(value) = generate_from_profile(ctx, column, unique_hint, rng) {
+        if row_index == 0 {
+            record_column_source(report, ctx, column, "profile", None, &[], &[]);
+        }
+        value
     } else if let Some((generator_id, value, tags)) = generate_from_default_generator(
         ctx,
         column,
@@ -831,8 +1266,22 @@ fn generate_column_value(
     )? {
         report.record_generator_usage(generator_id);
         record_pii_tags(report, column, tags);
+        if row_index == 0 {
+            record_column_source(
+                report,
+                ctx,
+                column,
+                "heuristic",
+                Some(generator_id),
+                tags,
+                &[],
+            );
+        }
         value
     } else if unique_hint {
+        if row_index == 0 {
+            record_column_source(report, ctx, column, "unique", None, &[], &[]);
+        }
         generate_unique_value(column, row_index, ctx.base_date)
     } else {
         generate_from_fallback(
@@ -872,6 +1321,19 @@ fn apply_row_transforms(
     let mut columns = ctx.table.columns.clone();
     columns.sort_by_key(|col| col.ordinal_position);
 
+    // Entity keys as generated, before their own columns are transformed.
+    let entity_rngs: HashMap<String, ChaCha8Rng> = columns
+        .iter()
+        .filter_map(|column| {
+            let rule = plan_index.column_rule(ctx.schema, &ctx.table.name, &column.name)?;
+            let key = entity_key(rule, row)?;
+            Some((
+                column.name.to_lowercase(),
+                entity_rng(plan_index.seed, rule, key, EntityStage::Transform),
+            ))
+        })
+        .collect();
+
     for column in &columns {
         let Some(rule) = plan_index.column_rule(ctx.schema, &ctx.table.name, &column.name) else {
             continue;
@@ -885,9 +1347,22 @@ fn apply_row_transforms(
             Some(value) => value,
             None => continue,
         };
-        let next = apply_transforms(
-            rule, value, ctx, column, row_index, registry, rng, report, plan_index,
-        )?;
+        let next = match entity_rngs.get(&key) {
+            Some(entity_rng) => apply_transforms(
+                rule,
+                value,
+                ctx,
+                column,
+                row_index,
+                registry,
+                &mut entity_rng.clone(),
+                report,
+                plan_index,
+            ),
+            None => apply_transforms(
+                rule, value, ctx, column, row_index, registry, rng, report, plan_index,
+            ),
+        }?;
         row.insert(key, next);
     }
 
@@ -997,16 +1472,30 @@ fn generate_from_fallback(
         record_fallback_warning(report, ctx, column, Some(generator_id));
         report.record_generator_usage(generator_id);
         record_pii_tags(report, column, tags);
+        if row_index == 0 {
+            record_column_source(
+                report,
+                ctx,
+                column,
+                "fallback",
+                Some(generator_id),
+                tags,
+                &[],
+            );
+        }
         return Ok(value);
     }
 
     record_fallback_warning(report, ctx, column, None);
     let value = fallback_for_type(column, ctx.base_date, rng);
     record_pii_tags(report, column, &[]);
+    if row_index == 0 {
+        record_column_source(report, ctx, column, "fallback", None, &[], &[]);
+    }
     Ok(value)
 }
 
-fn apply_transforms(
+pub(crate) fn apply_transforms(
     rule: &ColumnRule,
     mut value: GeneratedValue,
     ctx: &TableContext<'_>,
@@ -1082,6 +1571,26 @@ fn apply_transforms(
     Ok(value)
 }
 
+/// Value from the sampled profile of the column; unique and foreign key
+/// columns keep their own generation.
+fn generate_from_profile(
+    ctx: &TableContext<'_>,
+    column: &datalchemy_core::Column,
+    unique_hint: bool,
+    rng: &mut ChaCha8Rng,
+) -> Option<GeneratedValue> {
+    if unique_hint
+        || ctx.foreign_keys.iter().any(|fk| {
+            fk.columns
+                .iter()
+                .any(|fk_column| fk_column.eq_ignore_ascii_case(&column.name))
+        })
+    {
+        return None;
+    }
+    ctx.profile?.column(&column.name)?.generate(column, rng)
+}
+
 fn generate_default(
     column: &datalchemy_core::Column,
     base_date: NaiveDate,
@@ -1146,7 +1655,33 @@ fn generate_from_default_generator(
     rng: &mut ChaCha8Rng,
     locale: Option<&str>,
 ) -> Result<Option<(&'static str, GeneratedValue, &'static [&'static str])>, GenerationError> {
-    let generator_id = default_generator_id_for_column(ctx, column, enum_index);
+    generate_with_generator(
+        default_generator_id_for_column(ctx, column, enum_index),
+        ctx,
+        column,
+        row_index,
+        row,
+        registry,
+        enum_index,
+        foreign_context,
+        rng,
+        locale,
+    )
+}
+
+/// Value of `generator_id` without params, `None` when it is not registered.
+fn generate_with_generator(
+    generator_id: &str,
+    ctx: &TableContext<'_>,
+    column: &datalchemy_core::Column,
+    row_index: u64,
+    row: &RowContext,
+    registry: &GeneratorRegistry,
+    enum_index: &EnumIndex,
+    foreign_context: &mut InMemoryForeignContext,
+    rng: &mut ChaCha8Rng,
+    locale: Option<&str>,
+) -> Result<Option<(&'static str, GeneratedValue, &'static [&'static str])>, GenerationError> {
     let Some(generator) = registry.generator(generator_id) else {
         return Ok(None);
     };
@@ -1163,7 +1698,7 @@ fn generate_from_default_generator(
         generator_locale: locale,
     };
     let value = generator.generate(&mut generator_ctx, None, rng)?;
-    Ok(Some((generator_id, value, generator.pii_tags())))
+    Ok(Some((generator.id(), value, generator.pii_tags())))
 }
 
 fn default_generator_id_for_column(
@@ -1239,6 +1774,44 @@ fn fallback_for_type(
     }
 }
 
+/// Row literal (`(a,42,"b c")`) of `composite` with a fallback value per
+/// attribute.
+fn generate_composite(
+    composite: &CompositeType,
+    base_date: NaiveDate,
+    rng: &mut ChaCha8Rng,
+) -> GeneratedValue {
+    let fields: Vec<String> = composite
+        .attributes
+        .iter()
+        .enumerate()
+        .map(|(idx, attribute)| {
+            let column = datalchemy_core::Column {
+                ordinal_position: idx as i16 + 1,
+                name: attribute.name.clone(),
+                column_type: attribute.column_type.clone(),
+                is_nullable: true,
+                default: None,
+                identity: None,
+                generated: None,
+                comment: None,
+                stats: None,
+            };
+            let text = fallback_for_type(&column, base_date, rng).to_csv(&column);
+            let needs_quotes = text.is_empty()
+                || text
+                    .chars()
+                    .any(|ch| matches!(ch, '(' | ')' | ',' | '"' | '\\') || ch.is_whitespace());
+            if needs_quotes {
+                format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\"\""))
+            } else {
+                text
+            }
+        })
+        .collect();
+    GeneratedValue::Text(format!("({})", fields.join(",")))
+}
+
 fn normalize_type(column_type: &ColumnType) -> String {
     column_type
         .data_type
@@ -1261,24 +1834,56 @@ fn safe_time_from_seconds(seconds: u32) -> NaiveTime {
     NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).unwrap_or_default()
 }
 
-fn record_pii_tags(
+pub(crate) fn record_pii_tags(
     report: &mut GenerationReport,
     column: &datalchemy_core::Column,
     generator_tags: &'static [&'static str],
 ) {
+    for tag in pii_tag_set(column, generator_tags) {
+        report.record_pii(&tag);
+    }
+}
+
+fn pii_tag_set(
+    column: &datalchemy_core::Column,
+    generator_tags: &'static [&'static str],
+) -> BTreeSet<String> {
     let mut tags = BTreeSet::new();
     for tag in generator_tags {
-        tags.insert(*tag);
+        tags.insert(tag.to_string());
     }
     for tag in column_pii_tags(&column.name) {
-        tags.insert(tag);
-    }
-    for tag in tags {
-        report.record_pii(tag);
+        tags.insert(tag.to_string());
     }
+    tags.extend(column.hints().pii_tags());
+    tags
+}
+
+pub(crate) fn record_column_source(
+    report: &mut GenerationReport,
+    ctx: &TableContext<'_>,
+    column: &datalchemy_core::Column,
+    source: &str,
+    generator_id: Option<&str>,
+    generator_tags: &'static [&'static str],
+    transforms: &[TransformRule],
+) {
+    report.record_column_source(
+        format!("{}.{}.{}", ctx.schema, ctx.table.name, column.name),
+        ColumnSource {
+            source: source.to_string(),
+            generator_id: generator_id.map(str::to_string),
+            transforms: transforms
+                .iter()
+                .map(|transform| transform.transform.clone())
+                .collect(),
+            pii_tags: pii_tag_set(column, generator_tags).into_iter().collect(),
+        },
+    );
 }
 
-fn column_pii_tags(column_name: &str) -> Vec<&'static str> {
+/// PII tags implied by a column name (`email`, `cpf`, `telefone`, ...).
+pub fn column_pii_tags(column_name: &str) -> Vec<&'static str> {
     let name = column_name.to_lowercase();
     let mut tags = Vec::new();
     if name.contains("email") {
@@ -1331,7 +1936,7 @@ fn record_fallback_warning(
     record_warning(report, issue);
 }
 
-fn issue_for_column(
+pub(crate) fn issue_for_column(
     code: &str,
     message: String,
     ctx: &TableContext<'_>,
@@ -1350,7 +1955,43 @@ fn issue_for_column(
     }
 }
 
-fn record_warning(report: &mut GenerationReport, issue: GenerationIssue) {
+/// Record how the generated columns of the table are filled: computed from
+/// their expression, or omitted (with a warning) when it is not supported.
+fn record_generated_columns(ctx: &TableContext<'_>, report: &mut GenerationReport) {
+    for column in &ctx.table.columns {
+        let Some(expression) = &column.generated else {
+            continue;
+        };
+        if generated::is_computable(column) {
+            record_column_source(report, ctx, column, "generated_expression", None, &[], &[]);
+            continue;
+        }
+        record_warning(
+            report,
+            GenerationIssue {
+                level: "warning".to_string(),
+                code: "generated_column_omitted".to_string(),
+                message: format!(
+                    "generated column '{}.{}.{}' ({}) is computed by the database and left out of the output",
+                    ctx.schema,
+                    ctx.table.name,
+                    column.name,
+                    expression
+                        .expression
+                        .as_deref()
+                        .unwrap_or("unknown expression")
+                ),
+                path: None,
+                schema: Some(ctx.schema.to_string()),
+                table: Some(ctx.table.name.clone()),
+                column: Some(column.name.clone()),
+                generator_id: None,
+            },
+        );
+    }
+}
+
+pub(crate) fn record_warning(report: &mut GenerationReport, issue: GenerationIssue) {
     log_issue(&issue);
     report.record_warning(issue);
 }
@@ -1360,7 +2001,7 @@ fn record_unsupported(report: &mut GenerationReport, issue: GenerationIssue) {
     report.record_unsupported(issue);
 }
 
-fn record_generation_failure(report: &mut GenerationReport, message: String) {
+pub(crate) fn record_generation_failure(report: &mut GenerationReport, message: String) {
     let issue = GenerationIssue {
         level: "error".to_string(),
         code: "generation_failed".to_string(),
@@ -1400,7 +2041,7 @@ fn enforce_not_null(
     row: &HashMap<String, GeneratedValue>,
 ) -> Option<GenerationError> {
     for column in &ctx.table.columns {
-        if !column.is_nullable {
+        if !column.is_nullable && generated::is_written(column) {
             let key = column.name.to_lowercase();
             if row.get(&key).map(|value| value.is_null()).unwrap_or(true) {
                 return Some(GenerationError::Unsupported(format!(
@@ -1482,14 +2123,24 @@ fn evaluate_checks(
 }
 
 fn build_unique_sets(ctx: &TableContext<'_>) -> Vec<UniqueSet> {
-    let mut sets = Vec::new();
-    for pk in &ctx.primary_keys {
-        sets.push(UniqueSet::new(pk.clone()));
-    }
-    for unique in &ctx.unique_constraints {
-        sets.push(UniqueSet::new(unique.clone()));
-    }
-    sets
+    // Generated columns the engine leaves out have no value to compare.
+    let omitted: HashSet<String> = ctx
+        .table
+        .columns
+        .iter()
+        .filter(|column| !generated::is_written(column))
+        .map(|column| column.name.to_lowercase())
+        .collect();
+    ctx.primary_keys
+        .iter()
+        .chain(ctx.unique_constraints.iter())
+        .filter(|columns| {
+            !columns
+                .iter()
+                .any(|column| omitted.contains(&column.to_lowercase()))
+        })
+        .map(|columns| UniqueSet::new(columns.clone()))
+        .collect()
 }
 
 fn check_uniques(sets: &mut [UniqueSet], row: &HashMap<String, GeneratedValue>) -> bool {
@@ -1528,7 +2179,7 @@ impl UniqueSet {
     }
 }
 
-fn value_to_key(value: &GeneratedValue) -> String {
+pub(crate) fn value_to_key(value: &GeneratedValue) -> String {
     match value {
         GeneratedValue::Null => "<null>".to_string(),
         GeneratedValue::Bool(value) => value.to_string(),
@@ -1560,7 +2211,7 @@ fn extract_numeric_bounds(
     let mut bounds = HashMap::new();
     for constraint in &table.constraints {
         if let Constraint::Check(check) = constraint {
-            let expr = check.expression.to_lowercase();
+            let expr = strip_casts(&check.expression).to_lowercase();
             let expr = expr.replace("check", "");
             apply_numeric_constraints(&expr, &mut bounds);
         }
@@ -1569,7 +2220,13 @@ fn extract_numeric_bounds(
 }
 
 fn apply_numeric_constraints(expr: &str, bounds: &mut HashMap<String, NumericBounds>) {
+    let unwrapped = expr.trim().trim_start_matches('(').trim_end_matches(')');
+    if let Some((column, min, max)) = parse_between_bounds(unwrapped) {
+        update_bounds(bounds, &column, Some(min), Some(max));
+        return;
+    }
     for part in expr.split(" and ") {
+        let part = part.trim().trim_start_matches('(').trim_end_matches(')');
         if let Some((column, min, max)) = parse_between_bounds(part) {
             update_bounds(bounds, &column, Some(min), Some(max));
             continue;
@@ -1689,6 +2346,41 @@ fn extract_current_date_columns(table: &Table) -> HashSet<String> {
     columns
 }
 
+/// Generator of each column whose comment names one (`@generator:<id>`) or
+/// tags a kind of personal data with a semantic generator (`@pii:cpf`).
+fn extract_comment_generators(table: &Table) -> HashMap<String, String> {
+    table
+        .columns
+        .iter()
+        .filter_map(|column| {
+            let hints = column.hints();
+            let generator_id = hints.generator.or_else(|| {
+                hints
+                    .pii
+                    .iter()
+                    .find_map(|kind| pii_generator(kind))
+                    .map(str::to_string)
+            })?;
+            Some((column.name.to_lowercase(), generator_id))
+        })
+        .collect()
+}
+
+/// Semantic generator for a `@pii:<kind>` comment annotation.
+fn pii_generator(kind: &str) -> Option<&'static str> {
+    Some(match kind {
+        "email" => "semantic.person.email",
+        "phone" => "semantic.br.phone",
+        "name" => "semantic.br.name",
+        "cpf" => "semantic.br.cpf",
+        "cnpj" => "semantic.br.cnpj",
+        "rg" => "semantic.br.rg",
+        "address" => "semantic.br.address",
+        "network" => "semantic.br.ip",
+        _ => return None,
+    })
+}
+
 fn extract_email_columns(table: &Table) -> HashSet<String> {
     let mut columns = HashSet::new();
     let re_position = regex::Regex::new(
@@ -1853,7 +2545,7 @@ fn generate_unique_from_rule(
     }
 }
 
-fn hash_seed(seed: u64, key: &str) -> u64 {
+pub(crate) fn hash_seed(seed: u64, key: &str) -> u64 {
     let mut hash = seed ^ 0xcbf29ce484222325;
     for byte in key.as_bytes() {
         hash ^= *byte as u64;
@@ -1862,14 +2554,14 @@ fn hash_seed(seed: u64, key: &str) -> u64 {
     hash
 }
 
-fn hash_row_seed(table_seed: u64, row_index: u64, attempt: u32) -> u64 {
+pub(crate) fn hash_row_seed(table_seed: u64, row_index: u64, attempt: u32) -> u64 {
     let mut hash = table_seed ^ row_index.wrapping_mul(0x9e3779b97f4a7c15);
     hash ^= attempt as u64;
     hash = hash.wrapping_mul(0x100000001b3);
     hash
 }
 
-fn table_key(schema: &str, table: &str) -> String {
+pub(crate) fn table_key(schema: &str, table: &str) -> String {
     format!("{schema}.{table}")
 }
 
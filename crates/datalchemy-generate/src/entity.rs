@@ -0,0 +1,225 @@
+//! Stable values per logical entity (`entity` on column generator rules).
+//!
+//! A column bound to an entity is not generated from the row's random
+//! stream: its generator and transforms draw from a stream seeded by the plan
+//! seed, the entity name, the key value and the generator id. Customer #42
+//! thus gets the same name, e-mail or CPF in `clientes.nome` (keyed by `id`)
+//! and in `pedidos.cliente_nome` (keyed by `cliente_id`), in every run and
+//! incremental append of the same seed. Generators see row index 0 and a row
+//! holding only the key, so row-dependent generators (sequences, `derive.*`)
+//! do not vary per entity.
+//!
+//! [`EntityRegistry`] computes the same values without a run, so a test can
+//! refer to a fixture (`customer 42's e-mail`) by its key.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use datalchemy_core::DatabaseSchema;
+use datalchemy_plan::Plan;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::engine::{
+    ColumnRule, EnumIndex, PlanIndex, TableContext, apply_transforms, hash_seed, normalize_plan,
+    record_column_source, record_pii_tags, value_to_key,
+};
+use crate::errors::GenerationError;
+use crate::generators::{GeneratedValue, GeneratorContext, GeneratorRegistry, RowContext};
+use crate::model::GenerationReport;
+
+/// Random stream of an entity value.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum EntityStage {
+    Generate,
+    Transform,
+}
+
+/// Key of the entity `rule` binds its column to, when the row holds one.
+pub(crate) fn entity_key<'r>(rule: &ColumnRule, row: &'r RowContext) -> Option<&'r GeneratedValue> {
+    let entity = rule.entity.as_ref()?;
+    row.get(&entity.key.to_lowercase())
+        .filter(|value| !value.is_null())
+}
+
+pub(crate) fn entity_rng(
+    seed: u64,
+    rule: &ColumnRule,
+    key: &GeneratedValue,
+    stage: EntityStage,
+) -> ChaCha8Rng {
+    let name = rule
+        .entity
+        .as_ref()
+        .map(|entity| entity.name.as_str())
+        .unwrap_or_default();
+    let stage = match stage {
+        EntityStage::Generate => "generate",
+        EntityStage::Transform => "transform",
+    };
+    ChaCha8Rng::seed_from_u64(hash_seed(
+        seed,
+        &format!(
+            "entity:{name}:{}:{}:{stage}",
+            value_to_key(key),
+            rule.generator_id
+        ),
+    ))
+}
+
+/// Value of `column` for the entity `key` (before transforms).
+pub(crate) fn generate_entity_value(
+    ctx: &TableContext<'_>,
+    column: &datalchemy_core::Column,
+    rule: &ColumnRule,
+    key: &GeneratedValue,
+    plan_index: &PlanIndex,
+    registry: &GeneratorRegistry,
+    enum_index: &EnumIndex,
+) -> Result<GeneratedValue, GenerationError> {
+    let generator = registry.generator(&rule.generator_id).ok_or_else(|| {
+        GenerationError::InvalidPlan(format!("unknown generator id '{}'", rule.generator_id))
+    })?;
+    let key_column = rule
+        .entity
+        .as_ref()
+        .map(|entity| entity.key.to_lowercase())
+        .unwrap_or_default();
+    let row: RowContext = HashMap::from([(key_column, key.clone())]);
+    let mut generator_ctx = GeneratorContext {
+        schema: ctx.schema,
+        table: &ctx.table.name,
+        column,
+        foreign_keys: &ctx.foreign_keys,
+        base_date: ctx.base_date,
+        row_index: 0,
+        enum_values: enum_index.values_for(column),
+        row: &row,
+        foreign: None,
+        generator_locale: rule.generator_locale.as_deref(),
+    };
+    let mut rng = entity_rng(plan_index.seed, rule, key, EntityStage::Generate);
+    generator.generate(&mut generator_ctx, rule.params.as_ref(), &mut rng)
+}
+
+/// Report the column as an entity column once per table.
+pub(crate) fn record_entity_usage(
+    ctx: &TableContext<'_>,
+    column: &datalchemy_core::Column,
+    rule: &ColumnRule,
+    row_index: u64,
+    registry: &GeneratorRegistry,
+    report: &mut GenerationReport,
+) {
+    let tags = registry
+        .generator(&rule.generator_id)
+        .map(|generator| generator.pii_tags())
+        .unwrap_or(&[]);
+    report.record_generator_usage(&rule.generator_id);
+    record_pii_tags(report, column, tags);
+    if row_index == 0 {
+        record_column_source(
+            report,
+            ctx,
+            column,
+            "entity",
+            Some(&rule.generator_id),
+            tags,
+            &rule.transforms,
+        );
+    }
+}
+
+/// Entity column values of a plan, computed without a run.
+pub struct EntityRegistry<'a> {
+    /// With domains resolved, as in a run.
+    schema: Cow<'a, DatabaseSchema>,
+    enum_index: EnumIndex,
+    plan_index: PlanIndex,
+    registry: GeneratorRegistry,
+}
+
+impl<'a> EntityRegistry<'a> {
+    pub fn new(schema: &'a DatabaseSchema, plan: &Plan) -> Result<Self, GenerationError> {
+        let schema = if schema.domains.is_empty() {
+            Cow::Borrowed(schema)
+        } else {
+            Cow::Owned(schema.resolve_domains())
+        };
+        Ok(Self {
+            enum_index: EnumIndex::new(&schema),
+            schema,
+            plan_index: PlanIndex::new(&normalize_plan(plan), false)?,
+            registry: GeneratorRegistry::new(),
+        })
+    }
+
+    /// Value generated for `schema.table.column` in rows whose entity key is
+    /// `key`, transforms included; `None` when the column has no entity.
+    pub fn value(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+        key: &GeneratedValue,
+    ) -> Result<Option<GeneratedValue>, GenerationError> {
+        let Some(rule) = self
+            .plan_index
+            .column_rule(schema, table, column)
+            .filter(|rule| rule.entity.is_some())
+        else {
+            return Ok(None);
+        };
+        let table = self
+            .schema
+            .schemas
+            .iter()
+            .filter(|db_schema| db_schema.name == schema)
+            .flat_map(|db_schema| &db_schema.tables)
+            .find(|candidate| candidate.name == table)
+            .ok_or_else(|| {
+                GenerationError::InvalidPlan(format!(
+                    "table '{schema}.{table}' not found in schema"
+                ))
+            })?;
+        let column = table
+            .columns
+            .iter()
+            .find(|candidate| candidate.name == column)
+            .ok_or_else(|| {
+                GenerationError::InvalidPlan(format!(
+                    "column '{schema}.{}.{column}' not found in schema",
+                    table.name
+                ))
+            })?;
+        let base_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default();
+        let ctx = TableContext::new(schema, table, &self.schema, &self.plan_index, base_date);
+        let value = generate_entity_value(
+            &ctx,
+            column,
+            rule,
+            key,
+            &self.plan_index,
+            &self.registry,
+            &self.enum_index,
+        )?;
+        if rule.transforms.is_empty() {
+            return Ok(Some(value));
+        }
+        let mut report = GenerationReport::new(String::new());
+        let mut rng = entity_rng(self.plan_index.seed, rule, key, EntityStage::Transform);
+        apply_transforms(
+            rule,
+            value,
+            &ctx,
+            column,
+            0,
+            &self.registry,
+            &mut rng,
+            &mut report,
+            &self.plan_index,
+        )
+        .map(Some)
+    }
+}
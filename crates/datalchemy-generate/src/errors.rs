@@ -1,8 +1,15 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::model::GenerationReport;
 
 /// Errors emitted by the generation engine.
+///
+/// Variants stay small: reports and contexts are boxed. Errors raised while a
+/// table (or one of its columns) is generated come wrapped in
+/// [`GenerationError::Context`]; [`GenerationError::kind`] looks through it.
 #[derive(Debug, Error)]
 pub enum GenerationError {
     #[error("invalid plan: {0}")]
@@ -15,8 +22,177 @@ pub enum GenerationError {
     Json(#[from] serde_json::Error),
     #[error("csv error: {0}")]
     Csv(#[from] csv::Error),
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("xlsx error: {0}")]
+    Xlsx(#[from] rust_xlsxwriter::XlsxError),
+    #[cfg(feature = "arrow")]
+    #[error("arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+    #[cfg(feature = "duckdb")]
+    #[error("duckdb error: {0}")]
+    DuckDb(Box<duckdb::Error>),
+    #[cfg(feature = "object-store")]
+    #[error("object store error: {0}")]
+    ObjectStore(Box<object_store::Error>),
+    #[cfg(feature = "kafka")]
+    #[error("kafka error: {0}")]
+    Kafka(Box<rskafka::client::error::Error>),
     #[error("asset error: {0}")]
     Asset(String),
+    /// Reading real rows (masking) failed.
+    #[error("source error: {0}")]
+    Source(String),
     #[error("generation failed")]
-    Failed(GenerationReport),
+    Failed(Box<GenerationReport>),
+    #[error("generation cancelled")]
+    Cancelled,
+    /// An error located at a table or column.
+    #[error("{}: {}", .0.context, .0.source)]
+    Context(Box<ContextError>),
+}
+
+/// Table or column an error was raised for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<&str> = [&self.schema, &self.table, &self.column]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        f.write_str(&parts.join("."))
+    }
+}
+
+/// [`GenerationError`] with the table or column it was raised for.
+#[derive(Debug)]
+pub struct ContextError {
+    pub context: ErrorContext,
+    pub source: GenerationError,
+}
+
+/// Serializable form of an error, written to reports and JSON output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorInfo {
+    /// Stable code (`datalchemy explain <code>`).
+    pub code: String,
+    pub message: String,
+    #[serde(flatten)]
+    pub context: ErrorContext,
+}
+
+#[cfg(feature = "duckdb")]
+impl From<duckdb::Error> for GenerationError {
+    fn from(err: duckdb::Error) -> Self {
+        Self::DuckDb(Box::new(err))
+    }
+}
+
+#[cfg(feature = "object-store")]
+impl From<object_store::Error> for GenerationError {
+    fn from(err: object_store::Error) -> Self {
+        Self::ObjectStore(Box::new(err))
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl From<rskafka::client::error::Error> for GenerationError {
+    fn from(err: rskafka::client::error::Error) -> Self {
+        Self::Kafka(Box::new(err))
+    }
+}
+
+impl GenerationError {
+    /// Stable code of the error kind (`datalchemy explain <code>`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidPlan(_) => "DL201",
+            Self::Unsupported(_) => "DL202",
+            Self::Io(_) => "DL203",
+            Self::Json(_) => "DL204",
+            Self::Csv(_) => "DL205",
+            Self::Sqlite(_) => "DL206",
+            Self::Xlsx(_) => "DL207",
+            #[cfg(feature = "arrow")]
+            Self::Arrow(_) => "DL208",
+            #[cfg(feature = "duckdb")]
+            Self::DuckDb(_) => "DL209",
+            #[cfg(feature = "object-store")]
+            Self::ObjectStore(_) => "DL210",
+            #[cfg(feature = "kafka")]
+            Self::Kafka(_) => "DL211",
+            Self::Asset(_) => "DL212",
+            Self::Failed(_) => "DL213",
+            Self::Cancelled => "DL214",
+            Self::Source(_) => "DL215",
+            Self::Context(err) => err.source.code(),
+        }
+    }
+
+    /// The error without its table/column context.
+    pub fn kind(&self) -> &GenerationError {
+        match self {
+            Self::Context(err) => err.source.kind(),
+            _ => self,
+        }
+    }
+
+    /// Table or column the error was raised for, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context(err) => Some(&err.context),
+            _ => None,
+        }
+    }
+
+    /// Locate the error at `schema.table`; a column set by
+    /// [`at_column`](Self::at_column) is kept. Cancellation and run failures
+    /// are not located.
+    pub fn in_table(self, schema: &str, table: &str) -> Self {
+        self.locate(|context| {
+            context.schema = Some(schema.to_string());
+            context.table = Some(table.to_string());
+        })
+    }
+
+    /// Locate the error at `column` of the table being generated.
+    pub fn at_column(self, column: &str) -> Self {
+        self.locate(|context| {
+            context.column.get_or_insert_with(|| column.to_string());
+        })
+    }
+
+    fn locate(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        match self {
+            Self::Cancelled | Self::Failed(_) => self,
+            Self::Context(mut err) => {
+                update(&mut err.context);
+                Self::Context(err)
+            }
+            source => {
+                let mut context = ErrorContext::default();
+                update(&mut context);
+                Self::Context(Box::new(ContextError { context, source }))
+            }
+        }
+    }
+
+    /// Code, message (without the location) and location of the error.
+    pub fn info(&self) -> ErrorInfo {
+        ErrorInfo {
+            code: self.code().to_string(),
+            message: self.kind().to_string(),
+            context: self.context().cloned().unwrap_or_default(),
+        }
+    }
 }
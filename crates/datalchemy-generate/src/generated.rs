@@ -0,0 +1,352 @@
+//! Generated columns (`GENERATED ALWAYS AS (...)`).
+//!
+//! The database computes these columns, so the engine never draws them from a
+//! generator. Simple expressions (column references, literals, `+ - * /`,
+//! `||` and `concat(...)`) are computed from the other values of the row, so
+//! file outputs carry what the database would store; columns with any other
+//! expression are left out of every output. INSERT-style outputs skip
+//! generated columns either way ([`crate::output::pg_copy::copy_columns`]).
+
+use std::collections::HashMap;
+
+use datalchemy_core::Column;
+
+use crate::checks::strip_casts;
+use crate::generators::GeneratedValue;
+
+/// Whether the engine computes `column`: a generated column whose expression
+/// it can evaluate. Always `false` for ordinary columns.
+pub fn is_computable(column: &Column) -> bool {
+    column
+        .generated
+        .as_ref()
+        .and_then(|generated| generated.expression.as_deref())
+        .and_then(parse)
+        .is_some()
+}
+
+/// Whether `column` is written to file outputs: ordinary columns and the
+/// generated columns the engine computes.
+pub fn is_written(column: &Column) -> bool {
+    column.generated.is_none() || is_computable(column)
+}
+
+/// Value of the generated `column` for `row` (keys are lowercase column
+/// names); `None` when the expression is not supported or reads a column
+/// the row lacks.
+pub fn compute(column: &Column, row: &HashMap<String, GeneratedValue>) -> Option<GeneratedValue> {
+    let expression = column.generated.as_ref()?.expression.as_deref()?;
+    let value = parse(expression)?.eval(row)?;
+    Some(coerce(value, column))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Column(String),
+    Value(GeneratedValue),
+    Negate(Box<Expr>),
+    Binary(Box<Expr>, Op, Box<Expr>),
+    /// `concat(...)`, which skips NULL arguments.
+    Concat(Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// `||`, NULL when either side is NULL.
+    Concat,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Text(String),
+    Op(&'static str),
+    Open,
+    Close,
+    Comma,
+}
+
+fn parse(expression: &str) -> Option<Expr> {
+    let tokens = tokenize(&strip_casts(expression))?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.concat()?;
+    (parser.pos == parser.tokens.len()).then_some(expr)
+}
+
+fn tokenize(expression: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let ch = chars[idx];
+        match ch {
+            _ if ch.is_whitespace() => idx += 1,
+            '(' => {
+                tokens.push(Token::Open);
+                idx += 1;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                idx += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                idx += 1;
+            }
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Op(match ch {
+                    '+' => "+",
+                    '-' => "-",
+                    '*' => "*",
+                    _ => "/",
+                }));
+                idx += 1;
+            }
+            '|' if chars.get(idx + 1) == Some(&'|') => {
+                tokens.push(Token::Op("||"));
+                idx += 2;
+            }
+            '\'' => {
+                let mut text = String::new();
+                idx += 1;
+                loop {
+                    match (chars.get(idx), chars.get(idx + 1)) {
+                        (Some('\''), Some('\'')) => {
+                            text.push('\'');
+                            idx += 2;
+                        }
+                        (Some('\''), _) => break,
+                        (Some(&c), _) => {
+                            text.push(c);
+                            idx += 1;
+                        }
+                        (None, _) => return None,
+                    }
+                }
+                tokens.push(Token::Text(text));
+                idx += 1;
+            }
+            '"' | '`' | '[' => {
+                let close = if ch == '[' { ']' } else { ch };
+                let end = chars[idx + 1..].iter().position(|&c| c == close)? + idx + 1;
+                tokens.push(Token::Ident(chars[idx + 1..end].iter().collect()));
+                idx = end + 1;
+            }
+            _ if ch.is_ascii_digit() || ch == '.' => {
+                let start = idx;
+                while idx < chars.len() && (chars[idx].is_ascii_digit() || chars[idx] == '.') {
+                    idx += 1;
+                }
+                tokens.push(Token::Number(chars[start..idx].iter().collect()));
+            }
+            _ if ch.is_alphabetic() || ch == '_' => {
+                let start = idx;
+                while idx < chars.len() && (chars[idx].is_alphanumeric() || chars[idx] == '_') {
+                    idx += 1;
+                }
+                tokens.push(Token::Ident(chars[start..idx].iter().collect()));
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+/// Recursive descent with the Postgres precedence: `||` binds looser than
+/// `+ -`, which bind looser than `* /`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[(&str, Op)],
+        operand: fn(&mut Self) -> Option<Expr>,
+    ) -> Option<Expr> {
+        let mut left = operand(self)?;
+        while let Some(Token::Op(symbol)) = self.peek() {
+            let Some(&(_, op)) = ops.iter().find(|(candidate, _)| candidate == symbol) else {
+                break;
+            };
+            self.pos += 1;
+            left = Expr::Binary(Box::new(left), op, Box::new(operand(self)?));
+        }
+        Some(left)
+    }
+
+    fn concat(&mut self) -> Option<Expr> {
+        self.binary(&[("||", Op::Concat)], Self::additive)
+    }
+
+    fn additive(&mut self) -> Option<Expr> {
+        self.binary(&[("+", Op::Add), ("-", Op::Sub)], Self::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> Option<Expr> {
+        self.binary(&[("*", Op::Mul), ("/", Op::Div)], Self::unary)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        if self.peek() == Some(&Token::Op("-")) {
+            self.pos += 1;
+            return Some(Expr::Negate(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Option<Expr> {
+        match self.next()? {
+            Token::Open => {
+                let expr = self.concat()?;
+                (self.next()? == Token::Close).then_some(expr)
+            }
+            Token::Number(number) if number.contains('.') => number
+                .parse()
+                .ok()
+                .map(GeneratedValue::Float)
+                .map(Expr::Value),
+            Token::Number(number) => number
+                .parse()
+                .ok()
+                .map(GeneratedValue::Int)
+                .map(Expr::Value),
+            Token::Text(text) => Some(Expr::Value(GeneratedValue::Text(text))),
+            Token::Ident(name) if self.peek() == Some(&Token::Open) => {
+                if !name.eq_ignore_ascii_case("concat") {
+                    return None;
+                }
+                self.pos += 1;
+                let mut args = Vec::new();
+                if self.peek() == Some(&Token::Close) {
+                    self.pos += 1;
+                    return Some(Expr::Concat(args));
+                }
+                loop {
+                    args.push(self.concat()?);
+                    match self.next()? {
+                        Token::Comma => continue,
+                        Token::Close => return Some(Expr::Concat(args)),
+                        _ => return None,
+                    }
+                }
+            }
+            Token::Ident(name) if name.eq_ignore_ascii_case("null") => {
+                Some(Expr::Value(GeneratedValue::Null))
+            }
+            Token::Ident(name) => Some(Expr::Column(name.to_lowercase())),
+            _ => None,
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, row: &HashMap<String, GeneratedValue>) -> Option<GeneratedValue> {
+        match self {
+            Expr::Column(name) => row.get(name).cloned(),
+            Expr::Value(value) => Some(value.clone()),
+            Expr::Negate(inner) => match inner.eval(row)? {
+                GeneratedValue::Null => Some(GeneratedValue::Null),
+                GeneratedValue::Int(value) => value.checked_neg().map(GeneratedValue::Int),
+                GeneratedValue::Float(value) => Some(GeneratedValue::Float(-value)),
+                _ => None,
+            },
+            Expr::Binary(left, op, right) => {
+                let (left, right) = (left.eval(row)?, right.eval(row)?);
+                if left.is_null() || right.is_null() {
+                    return Some(GeneratedValue::Null);
+                }
+                match op {
+                    Op::Concat => Some(GeneratedValue::Text(
+                        plain_text(&left)? + &plain_text(&right)?,
+                    )),
+                    _ => arithmetic(&left, *op, &right),
+                }
+            }
+            Expr::Concat(args) => {
+                let mut text = String::new();
+                for arg in args {
+                    let value = arg.eval(row)?;
+                    if !value.is_null() {
+                        text.push_str(&plain_text(&value)?);
+                    }
+                }
+                Some(GeneratedValue::Text(text))
+            }
+        }
+    }
+}
+
+fn arithmetic(left: &GeneratedValue, op: Op, right: &GeneratedValue) -> Option<GeneratedValue> {
+    if let (GeneratedValue::Int(left), GeneratedValue::Int(right)) = (left, right) {
+        return match op {
+            Op::Add => left.checked_add(*right),
+            Op::Sub => left.checked_sub(*right),
+            Op::Mul => left.checked_mul(*right),
+            Op::Div => left.checked_div(*right),
+            Op::Concat => None,
+        }
+        .map(GeneratedValue::Int);
+    }
+    let (left, right) = (left.as_f64()?, right.as_f64()?);
+    let value = match op {
+        Op::Add => left + right,
+        Op::Sub => left - right,
+        Op::Mul => left * right,
+        Op::Div if right == 0.0 => return None,
+        Op::Div => left / right,
+        Op::Concat => return None,
+    };
+    Some(GeneratedValue::Float(value))
+}
+
+/// Text of a value as `||` sees it (the column's type output).
+fn plain_text(value: &GeneratedValue) -> Option<String> {
+    Some(match value {
+        GeneratedValue::Null => return None,
+        GeneratedValue::Bool(value) => value.to_string(),
+        GeneratedValue::Int(value) => value.to_string(),
+        GeneratedValue::Float(value) => value.to_string(),
+        GeneratedValue::Text(value) | GeneratedValue::Uuid(value) => value.clone(),
+        GeneratedValue::Date(value) => value.format("%Y-%m-%d").to_string(),
+        GeneratedValue::Time(value) => value.format("%H:%M:%S").to_string(),
+        GeneratedValue::Timestamp(value) => value.format("%Y-%m-%d %H:%M:%S").to_string(),
+    })
+}
+
+/// Store `value` as the type of `column` (integer, decimal or text).
+fn coerce(value: GeneratedValue, column: &Column) -> GeneratedValue {
+    let udt = column.column_type.udt_name.to_ascii_lowercase();
+    match value {
+        GeneratedValue::Float(value) if udt.starts_with("int") => {
+            GeneratedValue::Int(value.round() as i64)
+        }
+        GeneratedValue::Int(value)
+            if matches!(udt.as_str(), "numeric" | "float4" | "float8" | "real") =>
+        {
+            GeneratedValue::Float(value as f64)
+        }
+        GeneratedValue::Int(_) | GeneratedValue::Float(_)
+            if matches!(udt.as_str(), "text" | "varchar" | "bpchar") =>
+        {
+            plain_text(&value).map_or(value, GeneratedValue::Text)
+        }
+        value => value,
+    }
+}
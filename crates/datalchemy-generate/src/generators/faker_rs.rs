@@ -31,6 +31,10 @@ impl Generator for FakerAdapterGenerator {
         self.id
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        FAKER_TEXT_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
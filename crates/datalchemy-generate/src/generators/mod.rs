@@ -5,9 +5,11 @@ use rand::RngCore;
 use serde_json::Value;
 
 use datalchemy_core::{Column, ForeignKey};
+use datalchemy_plan::{BooleanFormat, ValueFormats};
 
 use crate::errors::GenerationError;
 use crate::foreign::ForeignContext;
+use crate::params::ParamSpec;
 
 pub mod derive;
 pub mod domain;
@@ -37,23 +39,58 @@ impl GeneratedValue {
         matches!(self, GeneratedValue::Null)
     }
 
+    /// Canonical text form, as read back by typed writers and loaders.
     pub fn to_csv(&self, column: &Column) -> String {
+        self.to_text(column, &ValueFormats::default())
+    }
+
+    /// Text form honoring the plan's `global.formats`.
+    pub fn to_text(&self, column: &Column, formats: &ValueFormats) -> String {
         match self {
             GeneratedValue::Null => String::new(),
-            GeneratedValue::Bool(value) => value.to_string(),
+            GeneratedValue::Bool(value) => match (formats.boolean, value) {
+                (Some(BooleanFormat::TF), true) => "t".to_string(),
+                (Some(BooleanFormat::TF), false) => "f".to_string(),
+                _ => value.to_string(),
+            },
             GeneratedValue::Int(value) => value.to_string(),
             GeneratedValue::Float(value) => {
-                if let Some(scale) = column.column_type.numeric_scale {
+                let text = if let Some(scale) = column.column_type.numeric_scale {
                     let scale = scale as usize;
                     format!("{value:.scale$}")
                 } else {
                     value.to_string()
+                };
+                match formats.decimal_separator {
+                    Some(separator) if separator != '.' => {
+                        text.replace('.', separator.encode_utf8(&mut [0; 4]))
+                    }
+                    _ => text,
                 }
             }
             GeneratedValue::Text(value) | GeneratedValue::Uuid(value) => value.clone(),
-            GeneratedValue::Date(value) => value.format("%Y-%m-%d").to_string(),
-            GeneratedValue::Time(value) => value.format("%H:%M:%S").to_string(),
-            GeneratedValue::Timestamp(value) => value.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            GeneratedValue::Date(value) => value
+                .format(formats.date_format.as_deref().unwrap_or("%Y-%m-%d"))
+                .to_string(),
+            GeneratedValue::Time(value) => value
+                .format(formats.time_format.as_deref().unwrap_or("%H:%M:%S"))
+                .to_string(),
+            GeneratedValue::Timestamp(value) => {
+                let mut text = value
+                    .format(
+                        formats
+                            .timestamp_format
+                            .as_deref()
+                            .unwrap_or("%Y-%m-%dT%H:%M:%S"),
+                    )
+                    .to_string();
+                if let Some(suffix) = &formats.timezone_suffix
+                    && column.column_type.udt_name == "timestamptz"
+                {
+                    text.push_str(suffix);
+                }
+                text
+            }
         }
     }
 
@@ -124,6 +161,12 @@ pub trait Generator: Send + Sync {
     fn pii_tags(&self) -> &'static [&'static str] {
         &[]
     }
+
+    /// Parameters checked by `validate_params`, for editors and docs; empty
+    /// when the generator takes none or reads them ad hoc.
+    fn params(&self) -> &'static [ParamSpec] {
+        &[]
+    }
 }
 
 /// Transform trait resolved by string identifiers.
@@ -176,4 +219,8 @@ impl GeneratorRegistry {
     pub fn transform(&self, id: &str) -> Option<&dyn Transform> {
         self.transforms.get(id).map(|transform| transform.as_ref())
     }
+
+    pub fn transform_ids(&self) -> Vec<&'static str> {
+        self.transforms.keys().copied().collect()
+    }
 }
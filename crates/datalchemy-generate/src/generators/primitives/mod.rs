@@ -145,6 +145,10 @@ impl Generator for IntRangeGenerator {
         self.id
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        INT_RANGE_PARAMS
+    }
+
     fn generate(
         &self,
         _ctx: &mut GeneratorContext<'_>,
@@ -171,6 +175,10 @@ impl Generator for IntSequenceHintGenerator {
         "primitive.int.sequence_hint"
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        INT_SEQUENCE_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -204,6 +212,10 @@ impl Generator for FloatRangeGenerator {
         self.id
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        FLOAT_RANGE_PARAMS
+    }
+
     fn generate(
         &self,
         _ctx: &mut GeneratorContext<'_>,
@@ -230,6 +242,10 @@ impl Generator for DecimalNumericGenerator {
         "primitive.decimal.numeric"
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        DECIMAL_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -272,6 +288,10 @@ impl Generator for TextGenerator {
         "primitive.text"
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        TEXT_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -349,6 +369,10 @@ impl Generator for TextPatternGenerator {
         "primitive.text.pattern"
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        TEXT_PATTERN_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -392,6 +416,10 @@ impl Generator for TextLoremGenerator {
         "primitive.text.lorem"
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        TEXT_LOREM_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -500,6 +528,10 @@ impl Generator for DateRangeGenerator {
         self.id
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        DATE_RANGE_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -524,7 +556,7 @@ impl Generator for DateRangeGenerator {
             )));
         }
         let span = (max - min).num_days().max(0);
-        let offset = rng.random_range(0..=span) as i64;
+        let offset: i64 = rng.random_range(0..=span);
         Ok(GeneratedValue::Date(min + chrono::Duration::days(offset)))
     }
 }
@@ -538,6 +570,10 @@ impl Generator for TimeRangeGenerator {
         self.id
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        TIME_RANGE_PARAMS
+    }
+
     fn generate(
         &self,
         _ctx: &mut GeneratorContext<'_>,
@@ -579,6 +615,10 @@ impl Generator for TimestampRangeGenerator {
         self.id
     }
 
+    fn params(&self) -> &'static [ParamSpec] {
+        TIMESTAMP_RANGE_PARAMS
+    }
+
     fn generate(
         &self,
         ctx: &mut GeneratorContext<'_>,
@@ -610,7 +650,7 @@ impl Generator for TimestampRangeGenerator {
             )));
         }
         let span = (max - min).num_seconds().max(0);
-        let offset = rng.random_range(0..=span) as i64;
+        let offset: i64 = rng.random_range(0..=span);
         Ok(GeneratedValue::Timestamp(
             min + chrono::Duration::seconds(offset),
         ))
@@ -13,6 +13,7 @@ pub fn register(registry: &mut GeneratorRegistry) {
     registry.register_transform(Box::new(CasingTransform));
     registry.register_transform(Box::new(WeightedChoiceTransform));
     registry.register_transform(Box::new(MaskTransform));
+    registry.register_transform(Box::new(DateShiftTransform));
 }
 
 struct NullRateTransform;
@@ -359,6 +360,70 @@ impl Transform for MaskTransform {
     }
 }
 
+/// Moves dates and timestamps by `days` (same shift for every value), or by a
+/// random shift in `-max_days..=max_days` per value.
+struct DateShiftTransform;
+
+impl Transform for DateShiftTransform {
+    fn id(&self) -> &'static str {
+        "transform.date_shift"
+    }
+
+    fn apply(
+        &self,
+        input: GeneratedValue,
+        _ctx: &TransformContext<'_>,
+        params: Option<&Value>,
+        rng: &mut dyn rand::RngCore,
+    ) -> Result<GeneratedValue, GenerationError> {
+        if matches!(input, GeneratedValue::Null) {
+            return Ok(input);
+        }
+        let days = params
+            .and_then(|params| params.get("days"))
+            .and_then(|value| value.as_i64());
+        let max_days = params
+            .and_then(|params| params.get("max_days"))
+            .and_then(|value| value.as_u64());
+        let days = match (days, max_days) {
+            (Some(days), None) => days,
+            (None, Some(max_days)) => {
+                let max_days = i64::try_from(max_days).map_err(|_| {
+                    GenerationError::InvalidPlan(
+                        "transform.date_shift max_days invalid".to_string(),
+                    )
+                })?;
+                rng.random_range(-max_days..=max_days)
+            }
+            _ => {
+                return Err(GenerationError::InvalidPlan(
+                    "transform.date_shift requires days or max_days".to_string(),
+                ));
+            }
+        };
+        let shift = chrono::Duration::try_days(days).ok_or_else(|| {
+            GenerationError::InvalidPlan("transform.date_shift days out of range".to_string())
+        })?;
+        let out_of_range =
+            || GenerationError::InvalidPlan("transform.date_shift result out of range".to_string());
+
+        match input {
+            GeneratedValue::Date(value) => value
+                .checked_add_signed(shift)
+                .map(GeneratedValue::Date)
+                .ok_or_else(out_of_range),
+            GeneratedValue::Timestamp(value) => value
+                .checked_add_signed(shift)
+                .map(GeneratedValue::Timestamp)
+                .ok_or_else(out_of_range),
+            other => Err(GenerationError::InvalidPlan(format!(
+                "transform.date_shift not supported for {}",
+                value_kind(&other)
+            ))),
+        }
+    }
+}
+
 fn value_to_string(value: &GeneratedValue) -> String {
     match value {
         GeneratedValue::Null => String::new(),
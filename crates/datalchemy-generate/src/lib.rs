@@ -3,24 +3,51 @@
 //! This crate consumes `schema.json` + `plan.json` to produce deterministic
 //! datasets (CSV) with constraint-aware generation.
 
-// Allow large error variants temporarily - see issue_task_20260128_refactor_errors
-#![allow(clippy::result_large_err)]
-#![allow(clippy::large_enum_variant)]
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::type_complexity)]
 
 pub mod assets;
+pub mod bench;
 pub mod checks;
+pub mod dbt;
+pub mod dictionary;
 pub mod engine;
+pub mod entity;
 pub mod errors;
 pub mod faker_rs;
 pub mod foreign;
+pub mod generated;
 pub mod generators;
+pub mod manifest;
+pub mod mask;
+pub mod mock_api;
 pub mod model;
 pub mod output;
 pub mod params;
 pub mod planner;
+pub mod profile;
+pub mod progress;
+#[cfg(feature = "streaming")]
+pub mod streaming;
+pub mod subset;
+#[cfg(feature = "object-store")]
+pub mod upload;
 
 pub use engine::{GenerationEngine, GenerationResult};
-pub use errors::GenerationError;
-pub use model::{GenerateOptions, GenerationReport, TableReport};
+pub use entity::EntityRegistry;
+pub use errors::{ContextError, ErrorContext, ErrorInfo, GenerationError};
+pub use mask::{MaskingEngine, RowSource, source_value};
+pub use model::{
+    GenerateOptions, GenerationReport, KafkaEncoding, KafkaTarget, ObjectStoreTarget, OutputFormat,
+    TableReport,
+};
+#[cfg(feature = "arrow")]
+pub use output::arrow::{RecordBatchSink, TableBatch};
+#[cfg(feature = "kafka")]
+pub use output::kafka::KafkaSink;
+pub use output::{TableOutput, TableSink};
+pub use profile::{ColumnProfile, TableProfile, ValueProfile};
+pub use progress::{GenerationProgress, PlannedTable};
+#[cfg(feature = "streaming")]
+pub use streaming::{RowBatch, RowStream};
+pub use subset::{RowSubset, RowSubsetOptions, SubsetSource};
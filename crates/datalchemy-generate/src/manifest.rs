@@ -0,0 +1,151 @@
+//! `files.json`: every file produced by a run with size, sha256, and table info.
+//!
+//! Downstream pipelines can check integrity before loading. Entries are sorted
+//! by path; paths are relative to the run directory and use `/` separators.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::GenerationError;
+use crate::output::TableOutput;
+
+pub const FILES_MANIFEST_NAME: &str = "files.json";
+
+/// Manifest of produced files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilesManifest {
+    pub files: Vec<FileEntry>,
+}
+
+/// One produced file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// Path relative to the run directory.
+    pub path: String,
+    pub bytes: u64,
+    pub sha256: String,
+    /// Tables stored in the file (several for single-file formats like sqlite).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<FileTable>,
+}
+
+/// Table content of a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTable {
+    pub schema: String,
+    pub table: String,
+    /// Row count; absent for one part of a partitioned table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<u64>,
+    pub columns: Vec<String>,
+}
+
+/// Hash every file under `run_dir` and write `files.json`. Returns its size.
+pub fn write_files_manifest(
+    run_dir: &Path,
+    outputs: &[TableOutput],
+) -> Result<u64, GenerationError> {
+    let manifest = build_files_manifest(run_dir, outputs)?;
+    let bytes = serde_json::to_vec_pretty(&manifest)?;
+    std::fs::write(run_dir.join(FILES_MANIFEST_NAME), &bytes)?;
+    Ok(bytes.len() as u64)
+}
+
+/// Build the manifest without writing it.
+pub fn build_files_manifest(
+    run_dir: &Path,
+    outputs: &[TableOutput],
+) -> Result<FilesManifest, GenerationError> {
+    let mut by_file: BTreeMap<&str, Vec<&TableOutput>> = BTreeMap::new();
+    for output in outputs {
+        by_file
+            .entry(output.file_name.as_str())
+            .or_default()
+            .push(output);
+    }
+
+    let mut paths = Vec::new();
+    collect_files(run_dir, &mut paths)?;
+    paths.sort();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let relative = relative_path(run_dir, &path)?;
+        if relative == FILES_MANIFEST_NAME {
+            continue;
+        }
+
+        let tables = match by_file.get(relative.as_str()) {
+            Some(outputs) => outputs
+                .iter()
+                .map(|output| file_table(output, Some(output.rows)))
+                .collect(),
+            // Partitioned tables: `<schema>.<table>/<col>=<value>/part-0.<ext>`.
+            None => relative
+                .split_once('/')
+                .and_then(|(dir, _)| by_file.get(dir))
+                .map(|outputs| {
+                    outputs
+                        .iter()
+                        .map(|output| file_table(output, None))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let (bytes, sha256) = hash_file(&path)?;
+        files.push(FileEntry {
+            path: relative,
+            bytes,
+            sha256,
+            tables,
+        });
+    }
+
+    Ok(FilesManifest { files })
+}
+
+/// Size and hex sha256 of a file.
+pub fn hash_file(path: &Path) -> Result<(u64, String), GenerationError> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let bytes = std::io::copy(&mut file, &mut hasher)?;
+    Ok((bytes, hex::encode(hasher.finalize())))
+}
+
+fn file_table(output: &TableOutput, rows: Option<u64>) -> FileTable {
+    FileTable {
+        schema: output.schema.clone(),
+        table: output.table.clone(),
+        rows,
+        columns: output.columns.clone(),
+    }
+}
+
+/// `path` relative to `root`, with `/` separators.
+pub(crate) fn relative_path(root: &Path, path: &Path) -> Result<String, GenerationError> {
+    let relative = path
+        .strip_prefix(root)
+        .map_err(|err| GenerationError::Asset(err.to_string()))?;
+    Ok(relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Files under `dir`, recursively (unsorted).
+pub(crate) fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), GenerationError> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
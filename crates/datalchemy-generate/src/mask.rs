@@ -0,0 +1,306 @@
+//! Masking of real rows (`datalchemy mask`).
+//!
+//! [`MaskingEngine`] reads the plan's target tables from a [`RowSource`] (at
+//! most `rows` rows each, FK parents first) instead of generating them,
+//! applies the transforms of the plan's `column_generator` rules to the real
+//! values and writes the result like a generation run: same run directory,
+//! output formats, `generation_report.json` and manifests. The generator of a
+//! rule is not used (`source` by convention). Columns without transforms are
+//! copied as read; those whose name implies PII are reported as
+//! `unmasked_pii_column` warnings.
+//!
+//! Transforms are seeded per table and row from the plan seed, so masking is
+//! reproducible. `transform.mask` (`hash`, `format_preserving`) and
+//! `transform.date_shift` with fixed `days` map equal inputs to equal outputs
+//! in every table, which keeps masked keys joinable.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use datalchemy_core::{Column, DatabaseSchema, Table};
+use datalchemy_plan::Plan;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::dictionary::write_data_dictionary;
+use crate::engine::{
+    GenerationResult, PlanIndex, SchemaIndex, TableContext, apply_transforms, column_pii_tags,
+    estimate_rows_bytes, hash_row_seed, hash_seed, issue_for_column, normalize_plan, per_sec,
+    record_generation_failure, record_warning, table_key,
+};
+use crate::errors::GenerationError;
+use crate::generators::{GeneratedValue, GeneratorRegistry};
+use crate::manifest::write_files_manifest;
+use crate::model::{ColumnSource, GenerateOptions, GenerationReport, TableReport};
+use crate::output::{TableOutput, create_sink, write_schema_sql};
+use crate::planner::plan_tables;
+
+/// Rows of real tables, e.g. a database connection.
+pub trait RowSource {
+    /// Up to `limit` rows of `schema.table`, keyed by lowercase column name
+    /// (see [`source_value`]).
+    fn read_table(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        limit: u64,
+    ) -> Result<Vec<HashMap<String, GeneratedValue>>, GenerationError>;
+}
+
+/// Entry point for masking real rows with a plan.
+#[derive(Debug, Clone)]
+pub struct MaskingEngine {
+    options: GenerateOptions,
+}
+
+impl MaskingEngine {
+    /// `options.format`, `out_dir`, `strict` and `auto_generate_parents`
+    /// (copy the FK parents of the targets) apply as in generation.
+    pub fn new(options: GenerateOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn run(
+        &self,
+        schema: &DatabaseSchema,
+        plan: &Plan,
+        source: &mut dyn RowSource,
+    ) -> Result<GenerationResult, GenerationError> {
+        let start = Instant::now();
+        let run_id = uuid::Uuid::new_v4().to_string();
+        let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H-%M-%SZ").to_string();
+        let run_dir = self
+            .options
+            .out_dir
+            .join(format!("{timestamp}__run_{run_id}"));
+        std::fs::create_dir_all(&run_dir)?;
+
+        let strict = plan
+            .options
+            .as_ref()
+            .and_then(|opts| opts.strict)
+            .unwrap_or(self.options.strict);
+        let plan = normalize_plan(plan);
+        let plan_index = PlanIndex::new(&plan, strict)?;
+        let tasks = plan_tables(schema, &plan, self.options.auto_generate_parents)?;
+        let schema_index = SchemaIndex::new(schema);
+        let registry = GeneratorRegistry::new();
+        let base_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default();
+        std::fs::write(
+            run_dir.join("resolved_plan.json"),
+            serde_json::to_vec_pretty(&plan)?,
+        )?;
+
+        let mut report = GenerationReport::new(run_id.clone());
+        let mut bytes_written = 0_u64;
+        info!(run_id = %run_id, tables = tasks.len(), strict, "masking started");
+
+        let mask_all = || -> Result<(), GenerationError> {
+            let mut sink = create_sink(self.options.format, &run_dir, schema, &plan)?;
+            let mut outputs: Vec<TableOutput> = Vec::new();
+            for task in tasks {
+                let table_start = Instant::now();
+                let table = schema_index
+                    .table(&task.schema, &task.table)
+                    .ok_or_else(|| {
+                        GenerationError::InvalidPlan(format!(
+                            "table '{}.{}' not found in schema",
+                            task.schema, task.table
+                        ))
+                    })?;
+                let ctx = TableContext::new(&task.schema, table, schema, &plan_index, base_date);
+                let mut rows = source
+                    .read_table(&task.schema, table, task.rows)
+                    .map_err(|err| err.in_table(&task.schema, &task.table))?;
+                let table_seed = hash_seed(plan.seed, &table_key(&task.schema, &task.table));
+                mask_rows(
+                    &ctx,
+                    &mut rows,
+                    table_seed,
+                    &registry,
+                    &plan_index,
+                    &mut report,
+                )
+                .map_err(|err| err.in_table(&task.schema, &task.table))?;
+
+                let output = sink
+                    .write_table(&task.schema, table, &rows)
+                    .map_err(|err| err.in_table(&task.schema, &task.table))?;
+                bytes_written += output.bytes;
+                outputs.push(output);
+                for issue in sink.take_warnings() {
+                    record_warning(&mut report, issue);
+                }
+
+                let elapsed = table_start.elapsed();
+                let peak_memory_bytes = estimate_rows_bytes(&rows);
+                report.tables.push(TableReport {
+                    schema: task.schema.clone(),
+                    table: task.table.clone(),
+                    rows_requested: task.rows,
+                    rows_generated: rows.len() as u64,
+                    retries: 0,
+                    duration_ms: elapsed.as_millis() as u64,
+                    rows_per_sec: per_sec(rows.len() as u64, elapsed),
+                    retries_per_sec: 0.0,
+                    peak_memory_bytes,
+                });
+                report.peak_memory_bytes = report.peak_memory_bytes.max(peak_memory_bytes);
+                info!(
+                    schema = %task.schema,
+                    table = %task.table,
+                    rows = rows.len(),
+                    "table masked"
+                );
+            }
+            bytes_written += sink.finish(&outputs)?;
+            write_schema_sql(&run_dir, schema)?;
+            write_data_dictionary(&run_dir, schema, &report)?;
+            write_files_manifest(&run_dir, &outputs)?;
+            Ok(())
+        };
+        let outcome = mask_all();
+
+        let elapsed = start.elapsed();
+        report.bytes_written = bytes_written;
+        report.duration_ms = elapsed.as_millis() as u64;
+        report.throughput_bytes_per_sec = per_sec(bytes_written, elapsed);
+        if let Err(err) = &outcome {
+            record_generation_failure(&mut report, err.to_string());
+            report.error = Some(err.info());
+            warn!(run_id = %run_id, error = %err, "masking failed");
+        }
+        std::fs::write(
+            run_dir.join("generation_report.json"),
+            serde_json::to_vec_pretty(&report)?,
+        )?;
+        outcome?;
+        info!(run_id = %run_id, tables = report.tables.len(), "masking completed");
+        Ok(GenerationResult { run_dir, report })
+    }
+}
+
+/// Apply the column transforms of the plan to the rows of one table.
+fn mask_rows(
+    ctx: &TableContext<'_>,
+    rows: &mut [HashMap<String, GeneratedValue>],
+    table_seed: u64,
+    registry: &GeneratorRegistry,
+    plan_index: &PlanIndex,
+    report: &mut GenerationReport,
+) -> Result<(), GenerationError> {
+    let mut masked = Vec::new();
+    for column in &ctx.table.columns {
+        let rule = plan_index
+            .column_rule(ctx.schema, &ctx.table.name, &column.name)
+            .filter(|rule| !rule.transforms.is_empty());
+        let transforms: Vec<String> = rule
+            .map(|rule| {
+                rule.transforms
+                    .iter()
+                    .map(|transform| transform.transform.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        report.record_column_source(
+            format!("{}.{}.{}", ctx.schema, ctx.table.name, column.name),
+            ColumnSource {
+                source: if rule.is_some() { "masked" } else { "source" }.to_string(),
+                generator_id: None,
+                transforms,
+                pii_tags: Vec::new(),
+            },
+        );
+        match rule {
+            Some(rule) => masked.push((column, rule)),
+            None if !column_pii_tags(&column.name).is_empty() => {
+                let issue = issue_for_column(
+                    "unmasked_pii_column",
+                    format!(
+                        "column '{}.{}.{}' looks like PII and is copied without transforms",
+                        ctx.schema, ctx.table.name, column.name
+                    ),
+                    ctx,
+                    column,
+                    None,
+                );
+                record_warning(report, issue);
+            }
+            None => {}
+        }
+    }
+
+    for (row_index, row) in rows.iter_mut().enumerate() {
+        let row_index = row_index as u64;
+        let mut rng = ChaCha8Rng::seed_from_u64(hash_row_seed(table_seed, row_index, 0));
+        for (column, rule) in &masked {
+            let key = column.name.to_lowercase();
+            let value = row.remove(&key).unwrap_or(GeneratedValue::Null);
+            let value = apply_transforms(
+                rule, value, ctx, column, row_index, registry, &mut rng, report, plan_index,
+            )
+            .map_err(|err| err.at_column(&column.name))?;
+            row.insert(key, value);
+        }
+    }
+    Ok(())
+}
+
+/// Value of `column` from its JSON form (`row_to_json` in Postgres): numbers,
+/// booleans, `uuid`, dates, times and timestamps (RFC 3339 offsets are
+/// converted to UTC) keep their type; anything else, or a value that does not
+/// parse as its type, becomes text.
+pub fn source_value(column: &Column, value: &Value) -> GeneratedValue {
+    let data_type = column
+        .column_type
+        .data_type
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .trim();
+    let text = || match value {
+        Value::String(text) => GeneratedValue::Text(text.clone()),
+        other => GeneratedValue::Text(other.to_string()),
+    };
+    let parsed = match (data_type, value) {
+        (_, Value::Null) => Some(GeneratedValue::Null),
+        ("smallint" | "integer" | "bigint", Value::Number(number)) => {
+            number.as_i64().map(GeneratedValue::Int)
+        }
+        ("numeric" | "real" | "double precision", Value::Number(number)) => {
+            match (
+                column.column_type.numeric_scale.unwrap_or(0),
+                number.as_i64(),
+            ) {
+                (0, Some(int)) if data_type == "numeric" => Some(GeneratedValue::Int(int)),
+                _ => number.as_f64().map(GeneratedValue::Float),
+            }
+        }
+        ("boolean", Value::Bool(flag)) => Some(GeneratedValue::Bool(*flag)),
+        ("uuid", Value::String(uuid)) => Some(GeneratedValue::Uuid(uuid.clone())),
+        ("date", Value::String(date)) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .ok()
+            .map(GeneratedValue::Date),
+        ("time without time zone" | "time with time zone", Value::String(time)) => {
+            let time = time.split(['+', '-', 'Z']).next().unwrap_or_default();
+            NaiveTime::parse_from_str(time, "%H:%M:%S%.f")
+                .ok()
+                .map(GeneratedValue::Time)
+        }
+        ("timestamp without time zone", Value::String(timestamp)) => {
+            NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(GeneratedValue::Timestamp)
+        }
+        ("timestamp with time zone", Value::String(timestamp)) => {
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|timestamp| GeneratedValue::Timestamp(timestamp.naive_utc()))
+        }
+        _ => None,
+    };
+    parsed.unwrap_or_else(text)
+}
@@ -0,0 +1,359 @@
+//! Read-only REST view over a CSV run, used by `datalchemy serve`.
+//!
+//! Routes (all `GET`, JSON bodies):
+//! - `/` lists the tables with their row counts and paths.
+//! - `/<schema>/<table>` returns a page of rows. `limit` (default
+//!   [`DEFAULT_PAGE_SIZE`], at most [`MAX_PAGE_SIZE`]) and `offset` page the
+//!   result, `sort=<column>` or `sort=-<column>` orders it, and any other
+//!   `<column>=<value>` parameter keeps rows whose value has that text form.
+//! - `/<schema>/<table>/<id>` returns one row by single-column primary key.
+//!
+//! Values are typed from the schema: integers, decimals and booleans become
+//! JSON numbers and booleans, empty cells of nullable columns become `null`.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::{Map, Number, Value, json};
+
+use datalchemy_core::{Column, Constraint, DatabaseSchema};
+
+use crate::errors::GenerationError;
+use crate::model::GenerationReport;
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Tables of a run loaded in memory.
+#[derive(Debug, Clone, Default)]
+pub struct MockDataset {
+    tables: BTreeMap<String, MockTable>,
+}
+
+#[derive(Debug, Clone)]
+struct MockTable {
+    schema: String,
+    table: String,
+    primary_key: Option<String>,
+    rows: Vec<Map<String, Value>>,
+}
+
+/// Response to a request: HTTP status, JSON body and, for lists, the match count.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub body: Value,
+    pub total: Option<usize>,
+}
+
+impl MockResponse {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body,
+            total: None,
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+            total: None,
+        }
+    }
+}
+
+impl MockDataset {
+    /// Load the `<schema>.<table>.csv` files of `run_dir` for every table in `report`.
+    pub fn load(
+        run_dir: &Path,
+        schema: &DatabaseSchema,
+        report: &GenerationReport,
+    ) -> Result<Self, GenerationError> {
+        let mut tables = BTreeMap::new();
+        for table_report in &report.tables {
+            let Some(table) = schema
+                .schemas
+                .iter()
+                .filter(|s| s.name == table_report.schema)
+                .flat_map(|s| s.tables.iter())
+                .find(|t| t.name == table_report.table)
+            else {
+                continue;
+            };
+            let path = run_dir.join(format!("{}.{}.csv", table_report.schema, table.name));
+            if !path.is_file() {
+                return Err(GenerationError::Unsupported(format!(
+                    "serve needs csv output; {} not found",
+                    path.display()
+                )));
+            }
+
+            let mut reader = csv::Reader::from_path(&path)?;
+            let headers = reader.headers()?.clone();
+            let columns: Vec<Option<&Column>> = headers
+                .iter()
+                .map(|name| table.columns.iter().find(|col| col.name == name))
+                .collect();
+            let mut rows = Vec::new();
+            for record in reader.records() {
+                let record = record?;
+                let mut row = Map::with_capacity(headers.len());
+                for ((name, column), cell) in headers.iter().zip(&columns).zip(record.iter()) {
+                    row.insert(name.to_string(), typed_value(*column, cell));
+                }
+                rows.push(row);
+            }
+
+            let primary_key = table
+                .constraints
+                .iter()
+                .find_map(|constraint| match constraint {
+                    Constraint::PrimaryKey(pk) if pk.columns.len() == 1 => {
+                        Some(pk.columns[0].clone())
+                    }
+                    _ => None,
+                });
+            tables.insert(
+                format!("{}/{}", table_report.schema, table.name),
+                MockTable {
+                    schema: table_report.schema.clone(),
+                    table: table.name.clone(),
+                    primary_key,
+                    rows,
+                },
+            );
+        }
+        Ok(Self { tables })
+    }
+
+    /// Answer a `GET` for `target` (path plus optional query string).
+    pub fn handle(&self, target: &str) -> MockResponse {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect();
+
+        match segments.as_slice() {
+            [] => MockResponse::ok(self.index()),
+            [schema, table] => match self.tables.get(&format!("{schema}/{table}")) {
+                Some(table) => list_rows(table, &parse_query(query)),
+                None => MockResponse::error(404, format!("table {schema}.{table} not found")),
+            },
+            [schema, table, id] => match self.tables.get(&format!("{schema}/{table}")) {
+                Some(table) => get_row(table, id),
+                None => MockResponse::error(404, format!("table {schema}.{table} not found")),
+            },
+            _ => MockResponse::error(404, "not found"),
+        }
+    }
+
+    fn index(&self) -> Value {
+        let tables: Vec<Value> = self
+            .tables
+            .iter()
+            .map(|(path, table)| {
+                json!({
+                    "schema": table.schema,
+                    "table": table.table,
+                    "rows": table.rows.len(),
+                    "path": format!("/{path}"),
+                    "primary_key": table.primary_key,
+                })
+            })
+            .collect();
+        json!({ "tables": tables })
+    }
+}
+
+fn list_rows(table: &MockTable, params: &[(String, String)]) -> MockResponse {
+    let mut limit = DEFAULT_PAGE_SIZE;
+    let mut offset = 0;
+    let mut sort = None;
+    let mut filters = Vec::new();
+    for (key, value) in params {
+        match key.as_str() {
+            "limit" => match value.parse::<usize>() {
+                Ok(value) => limit = value.min(MAX_PAGE_SIZE),
+                Err(_) => return MockResponse::error(400, format!("invalid limit '{value}'")),
+            },
+            "offset" => match value.parse::<usize>() {
+                Ok(value) => offset = value,
+                Err(_) => return MockResponse::error(400, format!("invalid offset '{value}'")),
+            },
+            "sort" => {
+                let (column, descending) = match value.strip_prefix('-') {
+                    Some(column) => (column, true),
+                    None => (value.as_str(), false),
+                };
+                sort = Some((column.to_string(), descending));
+            }
+            _ => filters.push((key.as_str(), value.as_str())),
+        }
+    }
+
+    let known = |column: &str| {
+        table
+            .rows
+            .first()
+            .is_none_or(|row| row.contains_key(column))
+    };
+    if let Some(column) = filters
+        .iter()
+        .map(|(column, _)| *column)
+        .chain(sort.as_ref().map(|(column, _)| column.as_str()))
+        .find(|column| !known(column))
+    {
+        return MockResponse::error(400, format!("unknown column '{column}'"));
+    }
+
+    let mut matched: Vec<&Map<String, Value>> = table
+        .rows
+        .iter()
+        .filter(|row| {
+            filters
+                .iter()
+                .all(|(column, value)| row.get(*column).is_some_and(|cell| text_of(cell) == *value))
+        })
+        .collect();
+    if let Some((column, descending)) = &sort {
+        matched.sort_by(|a, b| {
+            let ordering = compare_values(&a[column.as_str()], &b[column.as_str()]);
+            if *descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    let total = matched.len();
+    let data: Vec<Value> = matched
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|row| Value::Object(row.clone()))
+        .collect();
+    MockResponse {
+        status: 200,
+        body: json!({
+            "data": data,
+            "total": total,
+            "limit": limit,
+            "offset": offset,
+        }),
+        total: Some(total),
+    }
+}
+
+fn get_row(table: &MockTable, id: &str) -> MockResponse {
+    let Some(primary_key) = &table.primary_key else {
+        return MockResponse::error(
+            404,
+            format!(
+                "{}.{} has no single-column primary key",
+                table.schema, table.table
+            ),
+        );
+    };
+    table
+        .rows
+        .iter()
+        .find(|row| row.get(primary_key).is_some_and(|cell| text_of(cell) == id))
+        .map(|row| MockResponse::ok(Value::Object(row.clone())))
+        .unwrap_or_else(|| {
+            MockResponse::error(
+                404,
+                format!("{}.{} row '{id}' not found", table.schema, table.table),
+            )
+        })
+}
+
+/// JSON value for a CSV cell, typed by the column.
+fn typed_value(column: Option<&Column>, cell: &str) -> Value {
+    let Some(column) = column else {
+        return Value::String(cell.to_string());
+    };
+    if cell.is_empty() && column.is_nullable {
+        return Value::Null;
+    }
+    let typed = match column.column_type.udt_name.as_str() {
+        "int2" | "int4" | "int8" => cell.parse::<i64>().ok().map(Value::from),
+        "float4" | "float8" | "numeric" => cell
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number),
+        "bool" => match cell {
+            "true" | "t" => Some(Value::Bool(true)),
+            "false" | "f" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    typed.unwrap_or_else(|| Value::String(cell.to_string()))
+}
+
+/// Text form compared by filters and primary key lookups.
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (a, b) => text_of(a).cmp(&text_of(b)),
+    }
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` (query form encoding) into UTF-8 text.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'%' if idx + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[idx + 1..idx + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        idx += 3;
+                        continue;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            byte => out.push(byte),
+        }
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -3,6 +3,8 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{ErrorInfo, GenerationError};
+
 /// Options for the generation engine.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateOptions {
@@ -16,6 +18,9 @@ pub struct GenerateOptions {
     pub max_attempts_table: u32,
     /// Automatically generate missing parent tables for FKs.
     pub auto_generate_parents: bool,
+    /// File format used for generated tables.
+    #[serde(default)]
+    pub format: OutputFormat,
 }
 
 impl Default for GenerateOptions {
@@ -26,6 +31,197 @@ impl Default for GenerateOptions {
             max_attempts_row: 50,
             max_attempts_table: 5,
             auto_generate_parents: true,
+            format: OutputFormat::default(),
+        }
+    }
+}
+
+/// Output format for generated table files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    /// One CSV file per table with a header row.
+    #[default]
+    Csv,
+    /// Postgres `COPY ... FROM STDIN` text files plus a `load.sql` script.
+    PgCopy,
+    /// Postgres `COPY ... FROM STDIN (FORMAT binary)` files plus a `load.sql` script.
+    PgCopyBinary,
+    /// Self-contained `dataset.sqlite` file with translated DDL and rows.
+    Sqlite,
+    /// `dataset.duckdb` file (requires the `duckdb` feature).
+    #[serde(rename = "duckdb")]
+    DuckDb,
+    /// One Arrow IPC file per table (requires the `arrow` feature).
+    ArrowIpc,
+    /// Avro container file plus `.avsc` schema per table.
+    Avro,
+    /// `dataset.xlsx` workbook with one sheet per table (rows capped per sheet).
+    Xlsx,
+    /// Fixed-width `.txt` file per table, laid out by `fixed_width` plan rules.
+    FixedWidth,
+    /// Flyway versioned migration (`V*__seed_*.sql`) with `INSERT`s per table.
+    Flyway,
+    /// Liquibase formatted SQL changelog with one changeset per table.
+    Liquibase,
+}
+
+impl OutputFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::PgCopy => "pg_copy",
+            OutputFormat::PgCopyBinary => "pg_copy_binary",
+            OutputFormat::Sqlite => "sqlite",
+            OutputFormat::DuckDb => "duckdb",
+            OutputFormat::ArrowIpc => "arrow_ipc",
+            OutputFormat::Avro => "avro",
+            OutputFormat::Xlsx => "xlsx",
+            OutputFormat::FixedWidth => "fixed_width",
+            OutputFormat::Flyway => "flyway",
+            OutputFormat::Liquibase => "liquibase",
+        }
+    }
+
+    pub fn all() -> &'static [OutputFormat] {
+        &[
+            OutputFormat::Csv,
+            OutputFormat::PgCopy,
+            OutputFormat::PgCopyBinary,
+            OutputFormat::Sqlite,
+            OutputFormat::DuckDb,
+            OutputFormat::ArrowIpc,
+            OutputFormat::Avro,
+            OutputFormat::Xlsx,
+            OutputFormat::FixedWidth,
+            OutputFormat::Flyway,
+            OutputFormat::Liquibase,
+        ]
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        OutputFormat::all()
+            .iter()
+            .copied()
+            .find(|format| format.as_str() == value)
+            .ok_or_else(|| format!("unknown output format '{value}'"))
+    }
+}
+
+/// Object store destination for generated artifacts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectStoreTarget {
+    /// Destination URL (`s3://bucket/prefix`, `gs://bucket/prefix`, `az://container/prefix`).
+    pub url: String,
+    /// Custom endpoint for S3-compatible stores (ex.: MinIO) or Azure emulators.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Allow plain HTTP endpoints (local MinIO).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_http: bool,
+}
+
+impl ObjectStoreTarget {
+    /// Split the URL into `(scheme, bucket, prefix)`.
+    pub fn parts(&self) -> Result<(&str, &str, &str), GenerationError> {
+        let (scheme, rest) = self.url.split_once("://").ok_or_else(|| {
+            GenerationError::InvalidPlan(format!("invalid object store url '{}'", self.url))
+        })?;
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            return Err(GenerationError::InvalidPlan(format!(
+                "object store url '{}' has no bucket",
+                self.url
+            )));
+        }
+        Ok((scheme, bucket, prefix.trim_matches('/')))
+    }
+}
+
+/// Kafka destination for the Kafka sink (requires the `kafka` feature).
+///
+/// Each table is published to the topic `<topic_prefix><schema>.<table>`,
+/// created when missing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KafkaTarget {
+    /// Bootstrap brokers (`host:port`).
+    pub brokers: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub topic_prefix: String,
+    #[serde(default)]
+    pub encoding: KafkaEncoding,
+    /// Message key column per table (`schema.table` -> column). Tables not
+    /// listed are keyed by their primary key; tables without one get no key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub key_columns: BTreeMap<String, String>,
+    /// Maximum messages per second across all topics (unlimited when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_per_sec: Option<u32>,
+    /// Partitions of created topics; keyed messages are spread by key hash.
+    #[serde(default = "default_kafka_partitions")]
+    pub partitions: i32,
+}
+
+fn default_kafka_partitions() -> i32 {
+    1
+}
+
+impl Default for KafkaTarget {
+    fn default() -> Self {
+        Self {
+            brokers: Vec::new(),
+            topic_prefix: String::new(),
+            encoding: KafkaEncoding::default(),
+            key_columns: BTreeMap::new(),
+            rate_per_sec: None,
+            partitions: default_kafka_partitions(),
+        }
+    }
+}
+
+impl KafkaTarget {
+    /// Topic receiving the rows of `schema.table`.
+    pub fn topic(&self, schema: &str, table: &str) -> String {
+        format!("{}{}.{}", self.topic_prefix, schema, table)
+    }
+}
+
+/// Payload encoding of Kafka messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaEncoding {
+    /// One JSON object per row, keyed by column name.
+    #[default]
+    Json,
+    /// Avro single-object encoding (`C3 01`, schema fingerprint, binary record).
+    Avro,
+}
+
+impl KafkaEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KafkaEncoding::Json => "json",
+            KafkaEncoding::Avro => "avro",
+        }
+    }
+}
+
+impl std::str::FromStr for KafkaEncoding {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(KafkaEncoding::Json),
+            "avro" => Ok(KafkaEncoding::Avro),
+            other => Err(format!(
+                "unknown kafka encoding '{other}' (use json or avro)"
+            )),
         }
     }
 }
@@ -38,6 +234,30 @@ pub struct TableReport {
     pub rows_requested: u64,
     pub rows_generated: u64,
     pub retries: u64,
+    /// Time spent generating and writing the table.
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub rows_per_sec: f64,
+    #[serde(default)]
+    pub retries_per_sec: f64,
+    /// Estimated bytes of the generated rows, kept in memory for FK lookups
+    /// until the run ends.
+    #[serde(default)]
+    pub peak_memory_bytes: u64,
+}
+
+/// How the values of a column were produced, taken from the first generated row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnSource {
+    /// `plan`, `default`, `heuristic`, `unique`, `fallback` or `foreign_key`.
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transforms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pii_tags: Vec<String>,
 }
 
 /// Structured generation issue.
@@ -69,6 +289,9 @@ pub struct GenerationReport {
     pub heuristic_count: u64,
     pub unknown_generator_id_count: u64,
     pub pii_columns_touched: BTreeMap<String, u64>,
+    /// Source of each generated column, keyed by `schema.table.column`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_sources: BTreeMap<String, ColumnSource>,
     pub warnings_by_code: BTreeMap<String, u64>,
     #[serde(default)]
     pub duration_ms: u64,
@@ -76,8 +299,14 @@ pub struct GenerationReport {
     pub bytes_written: u64,
     #[serde(default)]
     pub throughput_bytes_per_sec: f64,
+    /// Estimated bytes of all generated rows held at the end of the run.
+    #[serde(default)]
+    pub peak_memory_bytes: u64,
     pub warnings: Vec<GenerationIssue>,
     pub unsupported: Vec<GenerationIssue>,
+    /// Error that stopped a failed run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorInfo>,
 }
 
 impl GenerationReport {
@@ -92,12 +321,15 @@ impl GenerationReport {
             heuristic_count: 0,
             unknown_generator_id_count: 0,
             pii_columns_touched: BTreeMap::new(),
+            column_sources: BTreeMap::new(),
             warnings_by_code: BTreeMap::new(),
             duration_ms: 0,
             bytes_written: 0,
             throughput_bytes_per_sec: 0.0,
+            peak_memory_bytes: 0,
             warnings: Vec::new(),
             unsupported: Vec::new(),
+            error: None,
         }
     }
 
@@ -125,6 +357,11 @@ impl GenerationReport {
         *self.pii_columns_touched.entry(tag.to_string()).or_insert(0) += 1;
     }
 
+    /// Keep the first source recorded for `key` (retries do not overwrite it).
+    pub fn record_column_source(&mut self, key: String, source: ColumnSource) {
+        self.column_sources.entry(key).or_insert(source);
+    }
+
     pub fn record_warning(&mut self, issue: GenerationIssue) {
         *self.warnings_by_code.entry(issue.code.clone()).or_insert(0) += 1;
         self.warnings.push(issue);
@@ -134,4 +371,15 @@ impl GenerationReport {
         *self.warnings_by_code.entry(issue.code.clone()).or_insert(0) += 1;
         self.unsupported.push(issue);
     }
+    /// Up to `count` tables, slowest first.
+    pub fn slowest_tables(&self, count: usize) -> Vec<&TableReport> {
+        let mut tables: Vec<&TableReport> = self.tables.iter().collect();
+        tables.sort_by(|a, b| {
+            b.duration_ms
+                .cmp(&a.duration_ms)
+                .then_with(|| (&a.schema, &a.table).cmp(&(&b.schema, &b.table)))
+        });
+        tables.truncate(count);
+        tables
+    }
 }
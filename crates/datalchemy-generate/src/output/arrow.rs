@@ -0,0 +1,357 @@
+//! Arrow record batches and Arrow IPC files (behind the `arrow` feature).
+//!
+//! `RecordBatchSink` keeps one `RecordBatch` per generated table in memory so
+//! library users can hand them to Polars/DataFusion without touching disk;
+//! `ArrowIpcSink` writes the same batches as `<schema>.<table>.arrow` files.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder, Float64Builder, Int16Builder,
+    Int32Builder, Int64Builder, StringBuilder, Time64MicrosecondBuilder,
+    TimestampMicrosecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchIterator};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+
+use datalchemy_core::{Column, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Record batch produced for one generated table.
+#[derive(Debug, Clone)]
+pub struct TableBatch {
+    pub schema: String,
+    pub table: String,
+    pub batch: RecordBatch,
+}
+
+impl TableBatch {
+    /// Stream view over the batch, as expected by Arrow consumers.
+    pub fn reader(
+        &self,
+    ) -> RecordBatchIterator<std::vec::IntoIter<Result<RecordBatch, ArrowError>>> {
+        RecordBatchIterator::new(
+            vec![Ok(self.batch.clone())].into_iter(),
+            self.batch.schema(),
+        )
+    }
+}
+
+/// Sink collecting one `RecordBatch` per table, in generation order.
+#[derive(Debug, Default)]
+pub struct RecordBatchSink {
+    batches: Vec<TableBatch>,
+}
+
+impl RecordBatchSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn batches(&self) -> &[TableBatch] {
+        &self.batches
+    }
+
+    pub fn into_batches(self) -> Vec<TableBatch> {
+        self.batches
+    }
+}
+
+impl TableSink for RecordBatchSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let batch = record_batch(table, rows)?;
+        self.batches.push(TableBatch {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            batch,
+        });
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: ordered_columns(table)
+                .into_iter()
+                .map(|col| col.name.clone())
+                .collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+/// Sink writing one Arrow IPC file per table.
+pub struct ArrowIpcSink {
+    run_dir: PathBuf,
+}
+
+impl ArrowIpcSink {
+    pub fn new(run_dir: PathBuf) -> Self {
+        Self { run_dir }
+    }
+}
+
+impl TableSink for ArrowIpcSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let batch = record_batch(table, rows)?;
+        let file_name = format!("{}.{}.arrow", schema_name, table.name);
+        let path = self.run_dir.join(&file_name);
+        let mut writer = FileWriter::try_new(File::create(&path)?, &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name,
+            columns: ordered_columns(table)
+                .into_iter()
+                .map(|col| col.name.clone())
+                .collect(),
+            bytes: std::fs::metadata(&path)?.len(),
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+/// Arrow schema for a table, columns in ordinal order.
+pub fn arrow_schema(table: &Table) -> SchemaRef {
+    let fields: Vec<Field> = ordered_columns(table)
+        .into_iter()
+        .map(|col| Field::new(&col.name, arrow_type(col), col.is_nullable))
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+/// Arrow data type for a Postgres column type (unknown types map to Utf8).
+pub fn arrow_type(column: &Column) -> DataType {
+    let ty = &column.column_type;
+    match ty.udt_name.as_str() {
+        "int2" => DataType::Int16,
+        "int4" => DataType::Int32,
+        "int8" => DataType::Int64,
+        "float4" => DataType::Float32,
+        "float8" => DataType::Float64,
+        "numeric" => match (ty.numeric_precision, ty.numeric_scale) {
+            (Some(precision), Some(scale)) if (1..=38).contains(&precision) && scale >= 0 => {
+                DataType::Decimal128(precision as u8, scale as i8)
+            }
+            _ => DataType::Float64,
+        },
+        "bool" => DataType::Boolean,
+        "date" => DataType::Date32,
+        "time" => DataType::Time64(TimeUnit::Microsecond),
+        "timestamp" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "timestamptz" => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        _ => DataType::Utf8,
+    }
+}
+
+/// Build a `RecordBatch` for generated rows of `table`.
+pub fn record_batch(
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> Result<RecordBatch, GenerationError> {
+    let schema = arrow_schema(table);
+    let columns = ordered_columns(table);
+    let arrays = columns
+        .iter()
+        .zip(schema.fields())
+        .map(|(col, field)| {
+            let values: Vec<Option<&GeneratedValue>> = rows
+                .iter()
+                .map(|row| {
+                    row.get(&col.name.to_lowercase())
+                        .filter(|value| !value.is_null())
+                })
+                .collect();
+            build_array(table, col, field.data_type(), &values)
+        })
+        .collect::<Result<Vec<ArrayRef>, GenerationError>>()?;
+
+    Ok(RecordBatch::try_new(schema, arrays)?)
+}
+
+fn build_array(
+    table: &Table,
+    column: &Column,
+    data_type: &DataType,
+    values: &[Option<&GeneratedValue>],
+) -> Result<ArrayRef, GenerationError> {
+    let invalid = |value: &GeneratedValue| {
+        GenerationError::InvalidPlan(format!(
+            "value '{}' is not valid for {}.{} ({})",
+            value.to_csv(column),
+            table.name,
+            column.name,
+            column.column_type.udt_name
+        ))
+    };
+    let parse = |value: &GeneratedValue| value.to_csv(column);
+
+    let array: ArrayRef = match data_type {
+        DataType::Int16 => {
+            let mut builder = Int16Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => builder
+                        .append_value(parse(value).parse::<i16>().map_err(|_| invalid(value))?),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int32 => {
+            let mut builder = Int32Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => builder
+                        .append_value(parse(value).parse::<i32>().map_err(|_| invalid(value))?),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => builder
+                        .append_value(parse(value).parse::<i64>().map_err(|_| invalid(value))?),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float32 => {
+            let mut builder = Float32Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => builder
+                        .append_value(parse(value).parse::<f32>().map_err(|_| invalid(value))?),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => builder
+                        .append_value(parse(value).parse::<f64>().map_err(|_| invalid(value))?),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Decimal128(precision, scale) => {
+            let mut builder = Decimal128Builder::with_capacity(values.len())
+                .with_precision_and_scale(*precision, *scale)?;
+            let factor = 10_f64.powi(i32::from(*scale));
+            for value in values {
+                match value {
+                    Some(value) => {
+                        let number = parse(value).parse::<f64>().map_err(|_| invalid(value))?;
+                        builder.append_value((number * factor).round() as i128);
+                    }
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(GeneratedValue::Bool(flag)) => builder.append_value(*flag),
+                    Some(value) => match parse(value).as_str() {
+                        "true" | "t" => builder.append_value(true),
+                        "false" | "f" => builder.append_value(false),
+                        _ => return Err(invalid(value)),
+                    },
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Date32 => {
+            let mut builder = Date32Builder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => {
+                        let date = NaiveDate::parse_from_str(&parse(value), "%Y-%m-%d")
+                            .map_err(|_| invalid(value))?;
+                        builder.append_value(date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE);
+                    }
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Time64(_) => {
+            let mut builder = Time64MicrosecondBuilder::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Some(value) => {
+                        let time = NaiveTime::parse_from_str(&parse(value), "%H:%M:%S")
+                            .map_err(|_| invalid(value))?;
+                        let micros = time
+                            .signed_duration_since(NaiveTime::MIN)
+                            .num_microseconds()
+                            .ok_or_else(|| invalid(value))?;
+                        builder.append_value(micros);
+                    }
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Timestamp(_, timezone) => {
+            let mut builder = TimestampMicrosecondBuilder::with_capacity(values.len())
+                .with_timezone_opt(timezone.clone());
+            for value in values {
+                match value {
+                    Some(value) => {
+                        let ts = NaiveDateTime::parse_from_str(&parse(value), "%Y-%m-%dT%H:%M:%S")
+                            .map_err(|_| invalid(value))?;
+                        builder.append_value(ts.and_utc().timestamp_micros());
+                    }
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::with_capacity(values.len(), values.len() * 16);
+            for value in values {
+                match value {
+                    Some(value) => builder.append_value(parse(value)),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    };
+
+    Ok(array)
+}
@@ -0,0 +1,378 @@
+//! Avro object container files plus `.avsc` schemas.
+//!
+//! Each table produces `<schema>.<table>.avro` (uncompressed, single block) and
+//! `<schema>.<table>.avsc`. The sync marker is derived from the table name so
+//! reruns with the same seed produce byte-identical files.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use datalchemy_core::{Column, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+const MAGIC: &[u8] = b"Obj\x01";
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Sink writing one Avro container file and schema per table.
+pub struct AvroSink {
+    run_dir: PathBuf,
+}
+
+impl AvroSink {
+    pub fn new(run_dir: PathBuf) -> Self {
+        Self { run_dir }
+    }
+}
+
+impl TableSink for AvroSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let avro_schema = avro_schema(schema_name, table);
+        let schema_json = serde_json::to_string_pretty(&avro_schema)?;
+        let base = format!("{}.{}", schema_name, table.name);
+        std::fs::write(self.run_dir.join(format!("{base}.avsc")), &schema_json)?;
+
+        let file_name = format!("{base}.avro");
+        let bytes = encode_container(schema_name, table, &avro_schema, rows)?;
+        std::fs::write(self.run_dir.join(&file_name), &bytes)?;
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name,
+            columns: ordered_columns(table)
+                .into_iter()
+                .map(|col| col.name.clone())
+                .collect(),
+            bytes: bytes.len() as u64 + schema_json.len() as u64,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+/// Avro record schema for a table. Nullable columns become `["null", T]` unions.
+pub fn avro_schema(schema_name: &str, table: &Table) -> Value {
+    let fields: Vec<Value> = ordered_columns(table)
+        .into_iter()
+        .map(|col| {
+            let ty = avro_type(col);
+            let mut field = json!({ "name": avro_name(&col.name) });
+            if col.is_nullable {
+                field["type"] = json!(["null", ty]);
+                field["default"] = Value::Null;
+            } else {
+                field["type"] = ty;
+            }
+            if avro_name(&col.name) != col.name {
+                field["aliases"] = json!([col.name]);
+            }
+            field
+        })
+        .collect();
+
+    json!({
+        "type": "record",
+        "name": avro_name(&table.name),
+        "namespace": avro_name(schema_name),
+        "fields": fields,
+    })
+}
+
+/// Avro type (with logical type when applicable) for a Postgres column type.
+pub fn avro_type(column: &Column) -> Value {
+    let ty = &column.column_type;
+    match ty.udt_name.as_str() {
+        "int2" | "int4" => json!("int"),
+        "int8" => json!("long"),
+        "float4" => json!("float"),
+        "float8" => json!("double"),
+        "numeric" => match (ty.numeric_precision, ty.numeric_scale) {
+            (Some(precision), Some(scale)) if precision > 0 && scale >= 0 => json!({
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": precision,
+                "scale": scale,
+            }),
+            _ => json!("double"),
+        },
+        "bool" => json!("boolean"),
+        "date" => json!({ "type": "int", "logicalType": "date" }),
+        "time" => json!({ "type": "long", "logicalType": "time-micros" }),
+        "timestamp" => json!({ "type": "long", "logicalType": "local-timestamp-micros" }),
+        "timestamptz" => json!({ "type": "long", "logicalType": "timestamp-micros" }),
+        "uuid" => json!({ "type": "string", "logicalType": "uuid" }),
+        "bytea" => json!("bytes"),
+        _ => json!("string"),
+    }
+}
+
+/// Sanitize an identifier into a valid Avro name (`[A-Za-z_][A-Za-z0-9_]*`).
+pub fn avro_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out.chars().next().is_none_or(|ch| ch.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn encode_container(
+    schema_name: &str,
+    table: &Table,
+    avro_schema: &Value,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> Result<Vec<u8>, GenerationError> {
+    let columns = ordered_columns(table);
+    let mut block = Vec::new();
+    for row in rows {
+        encode_record(&mut block, table, &columns, row)?;
+    }
+
+    let sync = sync_marker(schema_name, &table.name);
+    let mut out = Vec::with_capacity(block.len() + 256);
+    out.extend_from_slice(MAGIC);
+
+    // File metadata map: one block with two entries, then the terminating 0.
+    let schema_text = serde_json::to_string(avro_schema)?;
+    write_long(&mut out, 2);
+    write_bytes(&mut out, b"avro.codec");
+    write_bytes(&mut out, b"null");
+    write_bytes(&mut out, b"avro.schema");
+    write_bytes(&mut out, schema_text.as_bytes());
+    write_long(&mut out, 0);
+    out.extend_from_slice(&sync);
+
+    if !rows.is_empty() {
+        write_long(&mut out, rows.len() as i64);
+        write_long(&mut out, block.len() as i64);
+        out.extend_from_slice(&block);
+        out.extend_from_slice(&sync);
+    }
+
+    Ok(out)
+}
+
+/// Binary encoding of one row as a record of [`avro_schema`].
+pub(crate) fn encode_record(
+    out: &mut Vec<u8>,
+    table: &Table,
+    columns: &[&Column],
+    row: &HashMap<String, GeneratedValue>,
+) -> Result<(), GenerationError> {
+    for col in columns {
+        let value = row
+            .get(&col.name.to_lowercase())
+            .filter(|value| !value.is_null());
+        match (col.is_nullable, value) {
+            (true, None) => write_long(out, 0),
+            (true, Some(value)) => {
+                write_long(out, 1);
+                encode_value(out, table, col, value)?;
+            }
+            (false, Some(value)) => encode_value(out, table, col, value)?,
+            (false, None) => {
+                return Err(GenerationError::InvalidPlan(format!(
+                    "missing value for non-null column {}.{}",
+                    table.name, col.name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parsing Canonical Form of a schema built by [`avro_schema`]: full names,
+/// only `name`/`type`/`fields` attributes, logical types reduced to their base type.
+pub fn parsing_canonical_form(schema: &Value) -> String {
+    canonical(schema, "")
+}
+
+fn canonical(schema: &Value, namespace: &str) -> String {
+    match schema {
+        Value::Array(branches) => {
+            let branches: Vec<String> = branches
+                .iter()
+                .map(|branch| canonical(branch, namespace))
+                .collect();
+            format!("[{}]", branches.join(","))
+        }
+        Value::Object(map) if map.get("type") == Some(&json!("record")) => {
+            let name = map.get("name").and_then(Value::as_str).unwrap_or_default();
+            let namespace = map
+                .get("namespace")
+                .and_then(Value::as_str)
+                .unwrap_or(namespace);
+            let full_name = if name.contains('.') || namespace.is_empty() {
+                name.to_string()
+            } else {
+                format!("{namespace}.{name}")
+            };
+            let record_namespace = full_name.rsplit_once('.').map_or("", |(ns, _)| ns);
+            let fields: Vec<String> = map
+                .get("fields")
+                .and_then(Value::as_array)
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|field| {
+                            format!(
+                                "{{\"name\":{},\"type\":{}}}",
+                                field["name"],
+                                canonical(&field["type"], record_namespace)
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            format!(
+                "{{\"name\":{},\"type\":\"record\",\"fields\":[{}]}}",
+                Value::String(full_name),
+                fields.join(",")
+            )
+        }
+        Value::Object(map) => map
+            .get("type")
+            .map(|ty| canonical(ty, namespace))
+            .unwrap_or_default(),
+        other => other.to_string(),
+    }
+}
+
+/// CRC-64-AVRO (Rabin) fingerprint of a schema's canonical form.
+pub fn rabin_fingerprint(canonical_form: &str) -> u64 {
+    const EMPTY: u64 = 0xc15d_213a_a4d7_a795;
+    let mut table = [0_u64; 256];
+    for (i, slot) in table.iter_mut().enumerate() {
+        let mut fp = i as u64;
+        for _ in 0..8 {
+            fp = (fp >> 1) ^ (EMPTY & (fp & 1).wrapping_neg());
+        }
+        *slot = fp;
+    }
+    canonical_form.bytes().fold(EMPTY, |fp, byte| {
+        (fp >> 8) ^ table[((fp ^ byte as u64) & 0xff) as usize]
+    })
+}
+
+fn encode_value(
+    out: &mut Vec<u8>,
+    table: &Table,
+    column: &Column,
+    value: &GeneratedValue,
+) -> Result<(), GenerationError> {
+    let text = value.to_csv(column);
+    let invalid = || {
+        GenerationError::InvalidPlan(format!(
+            "value '{}' is not valid for {}.{} ({})",
+            text, table.name, column.name, column.column_type.udt_name
+        ))
+    };
+    let ty = &column.column_type;
+
+    match ty.udt_name.as_str() {
+        "int2" | "int4" => write_long(out, text.parse::<i32>().map_err(|_| invalid())? as i64),
+        "int8" => write_long(out, text.parse::<i64>().map_err(|_| invalid())?),
+        "float4" => {
+            out.extend_from_slice(&text.parse::<f32>().map_err(|_| invalid())?.to_le_bytes())
+        }
+        "float8" => {
+            out.extend_from_slice(&text.parse::<f64>().map_err(|_| invalid())?.to_le_bytes())
+        }
+        "numeric" => match (ty.numeric_precision, ty.numeric_scale) {
+            (Some(precision), Some(scale)) if precision > 0 && scale >= 0 => {
+                let number = text.parse::<f64>().map_err(|_| invalid())?;
+                let unscaled = (number * 10_f64.powi(scale)).round() as i128;
+                write_bytes(out, &twos_complement(unscaled));
+            }
+            _ => out.extend_from_slice(&text.parse::<f64>().map_err(|_| invalid())?.to_le_bytes()),
+        },
+        "bool" => match text.as_str() {
+            "true" | "t" => out.push(1),
+            "false" | "f" => out.push(0),
+            _ => return Err(invalid()),
+        },
+        "date" => {
+            let date = NaiveDate::parse_from_str(&text, "%Y-%m-%d").map_err(|_| invalid())?;
+            write_long(
+                out,
+                (date.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE) as i64,
+            );
+        }
+        "time" => {
+            let time = NaiveTime::parse_from_str(&text, "%H:%M:%S").map_err(|_| invalid())?;
+            let micros = time
+                .signed_duration_since(NaiveTime::MIN)
+                .num_microseconds()
+                .ok_or_else(invalid)?;
+            write_long(out, micros);
+        }
+        "timestamp" | "timestamptz" => {
+            let ts =
+                NaiveDateTime::parse_from_str(&text, "%Y-%m-%dT%H:%M:%S").map_err(|_| invalid())?;
+            write_long(out, ts.and_utc().timestamp_micros());
+        }
+        _ => write_bytes(out, text.as_bytes()),
+    }
+
+    Ok(())
+}
+
+/// Zig-zag varint encoding used for Avro `int` and `long`.
+fn write_long(out: &mut Vec<u8>, value: i64) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+    while n & !0x7f != 0 {
+        out.push(((n & 0x7f) | 0x80) as u8);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(out, bytes.len() as i64);
+    out.extend_from_slice(bytes);
+}
+
+/// Minimal big-endian two's-complement representation (Avro `decimal`).
+fn twos_complement(value: i128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut start = 0;
+    while start < bytes.len() - 1 {
+        let redundant = (bytes[start] == 0x00 && bytes[start + 1] & 0x80 == 0)
+            || (bytes[start] == 0xff && bytes[start + 1] & 0x80 != 0);
+        if !redundant {
+            break;
+        }
+        start += 1;
+    }
+    bytes[start..].to_vec()
+}
+
+fn sync_marker(schema_name: &str, table: &str) -> [u8; 16] {
+    let mut hasher = Sha256::new();
+    hasher.update(schema_name.as_bytes());
+    hasher.update(b".");
+    hasher.update(table.as_bytes());
+    let digest = hasher.finalize();
+    let mut sync = [0_u8; 16];
+    sync.copy_from_slice(&digest[..16]);
+    sync
+}
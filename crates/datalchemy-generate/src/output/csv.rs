@@ -4,23 +4,35 @@ use std::io::{BufWriter, Write};
 use std::path::Path;
 
 use datalchemy_core::Table;
+use datalchemy_plan::ValueFormats;
 
 use crate::generators::GeneratedValue;
+use crate::output::ordered_columns;
 
-/// Write a table as CSV with deterministic column ordering.
+/// Write a table as CSV with deterministic column ordering and the plan's value formats.
 pub fn write_table_csv(
     path: &Path,
     table: &Table,
     rows: &[HashMap<String, GeneratedValue>],
+    formats: &ValueFormats,
 ) -> Result<u64, csv::Error> {
     let writer = BufWriter::new(File::create(path).map_err(csv::Error::from)?);
+    write_csv(writer, table, rows, formats)
+}
+
+/// Write a table as CSV (header row first) into any writer. Returns bytes written.
+pub fn write_csv<W: Write>(
+    writer: W,
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+    formats: &ValueFormats,
+) -> Result<u64, csv::Error> {
     let counting = CountingWriter::new(writer);
     let mut writer = csv::WriterBuilder::new()
         .has_headers(false)
         .from_writer(counting);
 
-    let mut columns = table.columns.clone();
-    columns.sort_by_key(|col| col.ordinal_position);
+    let columns = ordered_columns(table);
 
     let header: Vec<String> = columns.iter().map(|col| col.name.clone()).collect();
     writer.write_record(&header)?;
@@ -30,7 +42,7 @@ pub fn write_table_csv(
             .iter()
             .map(|col| {
                 row.get(&col.name.to_lowercase())
-                    .map(|value| value.to_csv(col))
+                    .map(|value| value.to_text(col, formats))
                     .unwrap_or_default()
             })
             .collect();
@@ -0,0 +1,155 @@
+//! DuckDB database file sink (behind the `duckdb` feature).
+//!
+//! Creates `dataset.duckdb` with one DuckDB schema per source schema and
+//! bulk-loads rows through the appender API. Text values are cast by DuckDB
+//! into the declared column types (UUID, DATE, TIMESTAMP, ...).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ::duckdb::Connection;
+use ::duckdb::appender_params_from_iter;
+use ::duckdb::types::Value as DuckValue;
+
+use datalchemy_core::{Column, Constraint, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+pub const DUCKDB_FILE_NAME: &str = "dataset.duckdb";
+
+/// Sink writing every table into a single DuckDB database file.
+pub struct DuckDbSink {
+    conn: Connection,
+    path: PathBuf,
+}
+
+impl DuckDbSink {
+    pub fn create(run_dir: &Path) -> Result<Self, GenerationError> {
+        let path = run_dir.join(DUCKDB_FILE_NAME);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        let conn = Connection::open(&path)?;
+        Ok(Self { conn, path })
+    }
+}
+
+impl TableSink for DuckDbSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        self.conn.execute_batch(&format!(
+            "CREATE SCHEMA IF NOT EXISTS {};\n{}",
+            quote_ident(schema_name),
+            create_table_sql(schema_name, table)
+        ))?;
+
+        let columns = ordered_columns(table);
+        {
+            let mut appender = self.conn.appender_to_db(&table.name, schema_name)?;
+            for row in rows {
+                let values: Vec<DuckValue> = columns
+                    .iter()
+                    .map(|col| to_duck_value(col, row.get(&col.name.to_lowercase())))
+                    .collect();
+                appender.append_row(appender_params_from_iter(values))?;
+            }
+            appender.flush()?;
+        }
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: DUCKDB_FILE_NAME.to_string(),
+            columns: columns.iter().map(|col| col.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+
+    fn finish(&mut self, _outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        self.conn.execute_batch("CHECKPOINT;")?;
+        Ok(std::fs::metadata(&self.path)?.len())
+    }
+}
+
+/// Build a DuckDB `CREATE TABLE` statement (NOT NULL and primary key only).
+///
+/// Foreign keys are skipped: DuckDB checks them on append, which would force
+/// row-by-row loading, and analysts rarely need them enforced.
+pub fn create_table_sql(schema_name: &str, table: &Table) -> String {
+    let mut lines: Vec<String> = ordered_columns(table)
+        .into_iter()
+        .map(|col| {
+            let mut line = format!("  {} {}", quote_ident(&col.name), duckdb_type(col));
+            if !col.is_nullable {
+                line.push_str(" NOT NULL");
+            }
+            line
+        })
+        .collect();
+
+    for constraint in &table.constraints {
+        if let Constraint::PrimaryKey(pk) = constraint {
+            let columns = pk
+                .columns
+                .iter()
+                .map(|col| quote_ident(col))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("  PRIMARY KEY ({columns})"));
+        }
+    }
+
+    format!(
+        "CREATE TABLE {}.{} (\n{}\n);",
+        quote_ident(schema_name),
+        quote_ident(&table.name),
+        lines.join(",\n")
+    )
+}
+
+/// DuckDB type for a Postgres column type.
+pub fn duckdb_type(column: &Column) -> String {
+    let ty = &column.column_type;
+    match ty.udt_name.as_str() {
+        "int2" => "SMALLINT".to_string(),
+        "int4" => "INTEGER".to_string(),
+        "int8" => "BIGINT".to_string(),
+        "float4" => "REAL".to_string(),
+        "float8" => "DOUBLE".to_string(),
+        "numeric" => match (ty.numeric_precision, ty.numeric_scale) {
+            (Some(precision), Some(scale)) if precision <= 38 => {
+                format!("DECIMAL({precision},{scale})")
+            }
+            _ => "DOUBLE".to_string(),
+        },
+        "bool" => "BOOLEAN".to_string(),
+        "uuid" => "UUID".to_string(),
+        "date" => "DATE".to_string(),
+        "time" => "TIME".to_string(),
+        "timestamp" => "TIMESTAMP".to_string(),
+        "timestamptz" => "TIMESTAMPTZ".to_string(),
+        "bytea" => "BLOB".to_string(),
+        _ => "VARCHAR".to_string(),
+    }
+}
+
+fn to_duck_value(column: &Column, value: Option<&GeneratedValue>) -> DuckValue {
+    match value {
+        None | Some(GeneratedValue::Null) => DuckValue::Null,
+        Some(GeneratedValue::Bool(value)) => DuckValue::Boolean(*value),
+        Some(GeneratedValue::Int(value)) => DuckValue::BigInt(*value),
+        Some(GeneratedValue::Float(value)) => DuckValue::Double(*value),
+        Some(other) => DuckValue::Text(other.to_csv(column)),
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
@@ -0,0 +1,242 @@
+//! Fixed-width flat files for legacy batch interfaces.
+//!
+//! Each table produces `<schema>.<table>.txt` (one record per line) and a
+//! `<schema>.<table>.layout.json` describing field positions. Layouts come from
+//! `fixed_width` plan rules; tables without a rule get widths derived from the
+//! column types. Values longer than their field are truncated and reported as
+//! `fixed_width_truncated`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use datalchemy_core::{Column, Table};
+use datalchemy_plan::{FixedWidthAlign, FixedWidthColumn, Plan, Rule, ValueFormats};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::model::GenerationIssue;
+use crate::output::{TableOutput, TableSink, ordered_columns, value_formats};
+
+const DEFAULT_TEXT_WIDTH: u32 = 32;
+
+/// Resolved field of a fixed-width record.
+#[derive(Debug, Clone, Serialize)]
+pub struct FixedWidthField {
+    pub column: String,
+    /// 1-based start position in the record.
+    pub start: u32,
+    pub width: u32,
+    pub align: FixedWidthAlign,
+    pub pad: char,
+}
+
+/// Sink writing one fixed-width file per table.
+pub struct FixedWidthSink {
+    run_dir: PathBuf,
+    layouts: BTreeMap<String, Vec<FixedWidthColumn>>,
+    formats: ValueFormats,
+    warnings: Vec<GenerationIssue>,
+}
+
+impl FixedWidthSink {
+    pub fn new(run_dir: PathBuf, plan: &Plan) -> Self {
+        let mut layouts = BTreeMap::new();
+        for rule in &plan.rules {
+            if let Rule::FixedWidth(rule) = rule {
+                layouts.insert(
+                    format!("{}.{}", rule.schema, rule.table),
+                    rule.columns.clone(),
+                );
+            }
+        }
+        Self {
+            run_dir,
+            layouts,
+            formats: value_formats(plan),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Field layout for `table`, from the plan rule or derived from column types.
+    pub fn layout(
+        &self,
+        schema_name: &str,
+        table: &Table,
+    ) -> Result<Vec<FixedWidthField>, GenerationError> {
+        let mut fields = Vec::new();
+        let mut start = 1;
+        match self.layouts.get(&format!("{}.{}", schema_name, table.name)) {
+            Some(specs) => {
+                for spec in specs {
+                    let column = find_column(schema_name, table, &spec.column)?;
+                    fields.push(FixedWidthField {
+                        column: column.name.clone(),
+                        start,
+                        width: spec.width,
+                        align: spec.align.unwrap_or_else(|| default_align(column)),
+                        pad: spec.pad.unwrap_or(' '),
+                    });
+                    start += spec.width;
+                }
+            }
+            None => {
+                for column in ordered_columns(table) {
+                    let width = default_width(column);
+                    fields.push(FixedWidthField {
+                        column: column.name.clone(),
+                        start,
+                        width,
+                        align: default_align(column),
+                        pad: ' ',
+                    });
+                    start += width;
+                }
+            }
+        }
+        Ok(fields)
+    }
+}
+
+impl TableSink for FixedWidthSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let fields = self.layout(schema_name, table)?;
+        let columns = fields
+            .iter()
+            .map(|field| find_column(schema_name, table, &field.column))
+            .collect::<Result<Vec<_>, _>>()?;
+        let base = format!("{}.{}", schema_name, table.name);
+        let file_name = format!("{base}.txt");
+        let path = self.run_dir.join(&file_name);
+
+        let mut truncated: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        for row in rows {
+            let mut line = String::new();
+            for (field, column) in fields.iter().zip(&columns) {
+                let text = row
+                    .get(&field.column.to_lowercase())
+                    .map(|value| value.to_text(column, &self.formats))
+                    .unwrap_or_default();
+                let (cell, cut) = format_field(&text, field);
+                if cut {
+                    *truncated.entry(field.column.as_str()).or_insert(0) += 1;
+                }
+                line.push_str(&cell);
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()?;
+
+        for (column, count) in truncated {
+            self.warnings.push(GenerationIssue {
+                level: "warning".to_string(),
+                code: "fixed_width_truncated".to_string(),
+                message: format!("{count} values truncated to fit the field width"),
+                path: None,
+                schema: Some(schema_name.to_string()),
+                table: Some(table.name.clone()),
+                column: Some(column.to_string()),
+                generator_id: None,
+            });
+        }
+
+        let layout_json = serde_json::to_string_pretty(&fields)?;
+        std::fs::write(
+            self.run_dir.join(format!("{base}.layout.json")),
+            &layout_json,
+        )?;
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name,
+            columns: fields.iter().map(|field| field.column.clone()).collect(),
+            bytes: std::fs::metadata(&path)?.len() + layout_json.len() as u64,
+            rows: rows.len() as u64,
+        })
+    }
+
+    fn take_warnings(&mut self) -> Vec<GenerationIssue> {
+        std::mem::take(&mut self.warnings)
+    }
+}
+
+fn find_column<'a>(
+    schema_name: &str,
+    table: &'a Table,
+    name: &str,
+) -> Result<&'a Column, GenerationError> {
+    table
+        .columns
+        .iter()
+        .find(|col| col.name == name)
+        .ok_or_else(|| {
+            GenerationError::InvalidPlan(format!(
+                "fixed-width column {}.{}.{} not found",
+                schema_name, table.name, name
+            ))
+        })
+}
+
+/// Pad or truncate `text` to the field width. Returns the cell and whether it was cut.
+fn format_field(text: &str, field: &FixedWidthField) -> (String, bool) {
+    let width = field.width as usize;
+    let len = text.chars().count();
+    if len >= width {
+        let cell: String = match field.align {
+            FixedWidthAlign::Left => text.chars().take(width).collect(),
+            FixedWidthAlign::Right => text.chars().skip(len - width).collect(),
+        };
+        return (cell, len > width);
+    }
+
+    let padding: String = std::iter::repeat_n(field.pad, width - len).collect();
+    let cell = match field.align {
+        FixedWidthAlign::Left => format!("{text}{padding}"),
+        // Keep the sign in front of zero padding (`-0042`, not `00-42`).
+        FixedWidthAlign::Right if field.pad == '0' && text.starts_with('-') => {
+            format!("-{padding}{}", &text[1..])
+        }
+        FixedWidthAlign::Right => format!("{padding}{text}"),
+    };
+    (cell, false)
+}
+
+fn default_align(column: &Column) -> FixedWidthAlign {
+    match column.column_type.udt_name.as_str() {
+        "int2" | "int4" | "int8" | "float4" | "float8" | "numeric" => FixedWidthAlign::Right,
+        _ => FixedWidthAlign::Left,
+    }
+}
+
+fn default_width(column: &Column) -> u32 {
+    let ty = &column.column_type;
+    match ty.udt_name.as_str() {
+        "int2" => 6,
+        "int4" => 11,
+        "int8" => 20,
+        "float4" | "float8" => 24,
+        "numeric" => ty
+            .numeric_precision
+            .map(|precision| precision.max(1) as u32 + 2)
+            .unwrap_or(24),
+        "bool" => 5,
+        "date" => 10,
+        "time" => 8,
+        "timestamp" | "timestamptz" => 19,
+        "uuid" => 36,
+        _ => ty
+            .character_max_length
+            .map(|len| len.max(1) as u32)
+            .unwrap_or(DEFAULT_TEXT_WIDTH),
+    }
+}
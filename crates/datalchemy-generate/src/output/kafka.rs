@@ -0,0 +1,364 @@
+//! Kafka messages for generated rows; the producer sink is behind the `kafka` feature.
+//!
+//! Every row becomes one message on `<prefix><schema>.<table>`, encoded as a
+//! JSON object or as an Avro single-object datum (schema from
+//! [`avro_schema`](super::avro::avro_schema)). Tables arrive in FK-safe order,
+//! so a consumer reading topics in publication order never sees a child event
+//! before its parent. Keys are partitioned like the Java client's default
+//! partitioner (murmur2), keeping all events of a key in order.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Number, Value};
+
+use datalchemy_core::{Column, Constraint, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::model::{KafkaEncoding, KafkaTarget};
+use crate::output::avro::{avro_schema, encode_record, parsing_canonical_form, rabin_fingerprint};
+use crate::output::ordered_columns;
+
+/// Header holding `schema.table` on every message.
+pub const TABLE_HEADER: &str = "datalchemy.table";
+const AVRO_SINGLE_OBJECT_MAGIC: [u8; 2] = [0xc3, 0x01];
+
+/// Message produced for one row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KafkaMessage {
+    pub key: Option<Vec<u8>>,
+    pub value: Vec<u8>,
+}
+
+/// Encode the rows of a table as messages, in row order.
+pub fn encode_messages(
+    target: &KafkaTarget,
+    schema_name: &str,
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> Result<Vec<KafkaMessage>, GenerationError> {
+    let columns = ordered_columns(table);
+    let key_columns = key_columns(target, schema_name, table)?;
+    let avro_prefix = match target.encoding {
+        KafkaEncoding::Json => None,
+        KafkaEncoding::Avro => {
+            let canonical = parsing_canonical_form(&avro_schema(schema_name, table));
+            let mut prefix = AVRO_SINGLE_OBJECT_MAGIC.to_vec();
+            prefix.extend_from_slice(&rabin_fingerprint(&canonical).to_le_bytes());
+            Some(prefix)
+        }
+    };
+
+    let mut messages = Vec::with_capacity(rows.len());
+    for row in rows {
+        let value = match &avro_prefix {
+            None => serde_json::to_vec(&json_row(&columns, row))?,
+            Some(prefix) => {
+                let mut value = prefix.clone();
+                encode_record(&mut value, table, &columns, row)?;
+                value
+            }
+        };
+        let key = (!key_columns.is_empty()).then(|| {
+            key_columns
+                .iter()
+                .map(|col| {
+                    row.get(&col.name.to_lowercase())
+                        .map(|value| value.to_csv(col))
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join("|")
+                .into_bytes()
+        });
+        messages.push(KafkaMessage { key, value });
+    }
+    Ok(messages)
+}
+
+/// Key columns of a table: the configured column, else the primary key.
+fn key_columns<'a>(
+    target: &KafkaTarget,
+    schema_name: &str,
+    table: &'a Table,
+) -> Result<Vec<&'a Column>, GenerationError> {
+    let table_key = format!("{}.{}", schema_name, table.name);
+    if let Some(name) = target.key_columns.get(&table_key) {
+        let column = table
+            .columns
+            .iter()
+            .find(|col| &col.name == name)
+            .ok_or_else(|| {
+                GenerationError::InvalidPlan(format!(
+                    "kafka key column '{name}' not found in {table_key}"
+                ))
+            })?;
+        return Ok(vec![column]);
+    }
+    let primary_key = table
+        .constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            Constraint::PrimaryKey(pk) => Some(&pk.columns),
+            _ => None,
+        });
+    Ok(primary_key
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| table.columns.iter().find(|col| &col.name == name))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Row as a JSON object; dates and times use their canonical text form.
+fn json_row(columns: &[&Column], row: &HashMap<String, GeneratedValue>) -> Value {
+    let mut object = Map::with_capacity(columns.len());
+    for col in columns {
+        let value = match row.get(&col.name.to_lowercase()) {
+            None | Some(GeneratedValue::Null) => Value::Null,
+            Some(GeneratedValue::Bool(value)) => Value::Bool(*value),
+            Some(GeneratedValue::Int(value)) => Value::Number((*value).into()),
+            Some(GeneratedValue::Float(value)) => {
+                Number::from_f64(*value).map_or(Value::Null, Value::Number)
+            }
+            Some(value) => Value::String(value.to_csv(col)),
+        };
+        object.insert(col.name.clone(), value);
+    }
+    Value::Object(object)
+}
+
+/// Partition of a keyed message, as chosen by Kafka's default partitioner.
+pub fn partition_for_key(key: &[u8], partitions: i32) -> i32 {
+    if partitions <= 1 {
+        return 0;
+    }
+    ((murmur2(key) & 0x7fff_ffff) % partitions as u32) as i32
+}
+
+/// 32-bit murmur2 with Kafka's seed.
+fn murmur2(data: &[u8]) -> u32 {
+    const SEED: u32 = 0x9747_b28c;
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = SEED ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+    let rest = chunks.remainder();
+    if rest.len() >= 3 {
+        h ^= (rest[2] as u32) << 16;
+    }
+    if rest.len() >= 2 {
+        h ^= (rest[1] as u32) << 8;
+    }
+    if !rest.is_empty() {
+        h ^= rest[0] as u32;
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+#[cfg(feature = "kafka")]
+pub use self::producer::KafkaSink;
+
+#[cfg(feature = "kafka")]
+mod producer {
+    use std::collections::{BTreeMap, HashMap};
+    use std::time::{Duration, Instant};
+
+    use rskafka::client::error::{Error as ClientError, ProtocolError};
+    use rskafka::client::partition::{Compression, PartitionClient, UnknownTopicHandling};
+    use rskafka::client::{Client, ClientBuilder};
+    use rskafka::record::Record;
+    use tokio::runtime::Runtime;
+    use tracing::info;
+
+    use datalchemy_core::Table;
+
+    use super::{KafkaMessage, TABLE_HEADER, encode_messages, partition_for_key};
+    use crate::errors::GenerationError;
+    use crate::generators::GeneratedValue;
+    use crate::model::KafkaTarget;
+    use crate::output::{TableOutput, TableSink, ordered_columns};
+
+    const BATCH_SIZE: usize = 500;
+    const CREATE_TOPIC_TIMEOUT_MS: i32 = 5_000;
+
+    /// Sink publishing every generated row to Kafka, one topic per table.
+    ///
+    /// Use it with [`GenerationEngine::run_with_sink`](crate::GenerationEngine::run_with_sink).
+    /// The sink owns a single-threaded Tokio runtime, so it must not be used
+    /// from inside another runtime.
+    pub struct KafkaSink {
+        target: KafkaTarget,
+        runtime: Runtime,
+        client: Client,
+        partition_clients: HashMap<(String, i32), PartitionClient>,
+        started: Instant,
+        sent: u64,
+    }
+
+    impl KafkaSink {
+        /// Connect to the bootstrap brokers of `target`.
+        pub fn connect(target: KafkaTarget) -> Result<Self, GenerationError> {
+            if target.brokers.is_empty() {
+                return Err(GenerationError::InvalidPlan(
+                    "kafka target has no brokers".to_string(),
+                ));
+            }
+            if target.partitions < 1 {
+                return Err(GenerationError::InvalidPlan(format!(
+                    "kafka partitions must be at least 1, got {}",
+                    target.partitions
+                )));
+            }
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            let client = runtime.block_on(ClientBuilder::new(target.brokers.clone()).build())?;
+            Ok(Self {
+                target,
+                runtime,
+                client,
+                partition_clients: HashMap::new(),
+                started: Instant::now(),
+                sent: 0,
+            })
+        }
+
+        fn create_topic(&self, topic: &str) -> Result<(), GenerationError> {
+            let controller = self.client.controller_client()?;
+            let created = self.runtime.block_on(controller.create_topic(
+                topic,
+                self.target.partitions,
+                1,
+                CREATE_TOPIC_TIMEOUT_MS,
+            ));
+            match created {
+                Ok(()) => Ok(()),
+                Err(ClientError::ServerError {
+                    protocol_error: ProtocolError::TopicAlreadyExists,
+                    ..
+                }) => Ok(()),
+                Err(err) => Err(err.into()),
+            }
+        }
+
+        fn produce(
+            &mut self,
+            topic: &str,
+            partition: i32,
+            records: Vec<Record>,
+        ) -> Result<(), GenerationError> {
+            let key = (topic.to_string(), partition);
+            if !self.partition_clients.contains_key(&key) {
+                let client = self.runtime.block_on(self.client.partition_client(
+                    topic,
+                    partition,
+                    UnknownTopicHandling::Retry,
+                ))?;
+                self.partition_clients.insert(key.clone(), client);
+            }
+            let count = records.len() as u64;
+            let client = &self.partition_clients[&key];
+            self.runtime
+                .block_on(client.produce(records, Compression::NoCompression))?;
+            self.sent += count;
+            self.throttle();
+            Ok(())
+        }
+
+        /// Sleep until the configured rate allows the messages sent so far.
+        fn throttle(&self) {
+            let Some(rate) = self.target.rate_per_sec.filter(|rate| *rate > 0) else {
+                return;
+            };
+            let due = Duration::from_secs_f64(self.sent as f64 / rate as f64);
+            let elapsed = self.started.elapsed();
+            if due > elapsed {
+                std::thread::sleep(due - elapsed);
+            }
+        }
+
+        fn batch_size(&self) -> usize {
+            self.target
+                .rate_per_sec
+                .filter(|rate| *rate > 0)
+                .map_or(BATCH_SIZE, |rate| (rate as usize).clamp(1, BATCH_SIZE))
+        }
+    }
+
+    impl TableSink for KafkaSink {
+        fn write_table(
+            &mut self,
+            schema_name: &str,
+            table: &Table,
+            rows: &[HashMap<String, GeneratedValue>],
+        ) -> Result<TableOutput, GenerationError> {
+            let topic = self.target.topic(schema_name, &table.name);
+            self.create_topic(&topic)?;
+
+            let messages = encode_messages(&self.target, schema_name, table, rows)?;
+            let bytes = messages
+                .iter()
+                .map(|msg| (msg.value.len() + msg.key.as_ref().map_or(0, Vec::len)) as u64)
+                .sum();
+
+            let mut by_partition: BTreeMap<i32, Vec<KafkaMessage>> = BTreeMap::new();
+            for message in messages {
+                let partition = message
+                    .key
+                    .as_deref()
+                    .map_or(0, |key| partition_for_key(key, self.target.partitions));
+                by_partition.entry(partition).or_default().push(message);
+            }
+
+            let table_header = format!("{}.{}", schema_name, table.name).into_bytes();
+            let batch_size = self.batch_size();
+            for (partition, messages) in by_partition {
+                for chunk in messages.chunks(batch_size) {
+                    let timestamp = chrono::Utc::now();
+                    let records = chunk
+                        .iter()
+                        .map(|msg| Record {
+                            key: msg.key.clone(),
+                            value: Some(msg.value.clone()),
+                            headers: BTreeMap::from([(
+                                TABLE_HEADER.to_string(),
+                                table_header.clone(),
+                            )]),
+                            timestamp,
+                        })
+                        .collect();
+                    self.produce(&topic, partition, records)?;
+                }
+            }
+            info!(topic = %topic, messages = rows.len(), "table published");
+
+            Ok(TableOutput {
+                schema: schema_name.to_string(),
+                table: table.name.clone(),
+                file_name: topic,
+                columns: ordered_columns(table)
+                    .into_iter()
+                    .map(|col| col.name.clone())
+                    .collect(),
+                bytes,
+                rows: rows.len() as u64,
+            })
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Seed data as database migrations: Flyway versioned scripts or a Liquibase
+//! formatted SQL changelog.
+//!
+//! Both hold plain `INSERT` statements in FK-safe table order. Flyway files are
+//! named `V<timestamp>.<nnn>__seed_<schema>_<table>.sql`, so they sort after
+//! hand-written migrations and keep the table order; the Liquibase changelog
+//! (`changelog-seed.sql`) has one changeset per table. Generated columns are
+//! left to the database, and sequences fed by a column of the table (serial,
+//! identity or a `nextval` default) are moved past the inserted values with
+//! `setval`, so later inserts do not collide.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+use datalchemy_core::ddl::{quote_ident, quote_literal};
+use datalchemy_core::{DatabaseSchema, Sequence, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::output::pg_copy::copy_columns;
+use crate::output::{TableOutput, TableSink};
+
+/// Liquibase changelog written by [`LiquibaseSink`].
+pub const LIQUIBASE_CHANGELOG: &str = "changelog-seed.sql";
+/// Rows per multi-row `INSERT` statement.
+pub const INSERT_BATCH_ROWS: usize = 500;
+const CHANGESET_AUTHOR: &str = "datalchemy";
+
+/// Sink writing one Flyway versioned migration per table.
+pub struct FlywaySink {
+    run_dir: PathBuf,
+    version: String,
+    next: usize,
+    sequences: TableSequences,
+}
+
+impl FlywaySink {
+    /// `version` prefixes every file version (`V<version>.<nnn>__...`).
+    pub fn new(run_dir: PathBuf, version: String, schema: &DatabaseSchema) -> Self {
+        Self {
+            run_dir,
+            version,
+            next: 1,
+            sequences: table_sequences(schema),
+        }
+    }
+}
+
+impl TableSink for FlywaySink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let file_name = format!(
+            "V{}.{:03}__seed_{}_{}.sql",
+            self.version,
+            self.next,
+            file_part(schema_name),
+            file_part(&table.name)
+        );
+        self.next += 1;
+
+        let mut script = format!(
+            "-- Seed data for {}.{} ({} rows), generated by datalchemy.\n",
+            schema_name,
+            table.name,
+            rows.len()
+        );
+        script.push_str(&insert_statements(schema_name, table, rows));
+        script.push_str(&sequence_resets(
+            table,
+            rows,
+            self.sequences
+                .get(&(schema_name.to_string(), table.name.clone())),
+        ));
+        std::fs::write(self.run_dir.join(&file_name), &script)?;
+
+        Ok(table_output(
+            schema_name,
+            table,
+            file_name,
+            script.len(),
+            rows,
+        ))
+    }
+}
+
+/// Sink appending one changeset per table to [`LIQUIBASE_CHANGELOG`].
+pub struct LiquibaseSink {
+    file: File,
+    version: String,
+    next: usize,
+    sequences: TableSequences,
+}
+
+impl LiquibaseSink {
+    /// `version` makes changeset ids unique per run (`seed-<version>-<nnn>-<schema>.<table>`).
+    pub fn create(
+        run_dir: PathBuf,
+        version: String,
+        schema: &DatabaseSchema,
+    ) -> Result<Self, GenerationError> {
+        let mut file = File::create(run_dir.join(LIQUIBASE_CHANGELOG))?;
+        file.write_all(b"--liquibase formatted sql\n")?;
+        Ok(Self {
+            file,
+            version,
+            next: 1,
+            sequences: table_sequences(schema),
+        })
+    }
+}
+
+impl TableSink for LiquibaseSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let mut changeset = format!(
+            "\n--changeset {CHANGESET_AUTHOR}:seed-{}-{:03}-{}.{} context:seed\n",
+            self.version, self.next, schema_name, table.name
+        );
+        self.next += 1;
+        let _ = writeln!(
+            changeset,
+            "--comment: Seed data for {}.{} ({} rows)",
+            schema_name,
+            table.name,
+            rows.len()
+        );
+        changeset.push_str(&insert_statements(schema_name, table, rows));
+        changeset.push_str(&sequence_resets(
+            table,
+            rows,
+            self.sequences
+                .get(&(schema_name.to_string(), table.name.clone())),
+        ));
+        self.file.write_all(changeset.as_bytes())?;
+
+        Ok(table_output(
+            schema_name,
+            table,
+            LIQUIBASE_CHANGELOG.to_string(),
+            changeset.len(),
+            rows,
+        ))
+    }
+
+    fn finish(&mut self, _outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        self.file.flush()?;
+        Ok(0)
+    }
+}
+
+/// Multi-row `INSERT` statements for `rows`, [`INSERT_BATCH_ROWS`] rows each.
+///
+/// Values use their canonical text form as quoted literals, which Postgres
+/// coerces to the column type (enums and domains included).
+pub fn insert_statements(
+    schema_name: &str,
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> String {
+    let columns = copy_columns(table);
+    let column_list = columns
+        .iter()
+        .map(|col| quote_ident(&col.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let target = format!("{}.{}", quote_ident(schema_name), quote_ident(&table.name));
+
+    let mut out = String::new();
+    for batch in rows.chunks(INSERT_BATCH_ROWS) {
+        let _ = writeln!(out, "INSERT INTO {target} ({column_list}) VALUES");
+        for (idx, row) in batch.iter().enumerate() {
+            let values = columns
+                .iter()
+                .map(|col| match row.get(&col.name.to_lowercase()) {
+                    None | Some(GeneratedValue::Null) => "NULL".to_string(),
+                    Some(value) => quote_literal(&value.to_csv(col)),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let end = if idx + 1 == batch.len() { ";" } else { "," };
+            let _ = writeln!(out, "  ({values}){end}");
+        }
+    }
+    out
+}
+
+/// Sequences fed by a column, per `(schema, table)`.
+type TableSequences = HashMap<(String, String), Vec<(String, Sequence)>>;
+
+fn table_sequences(schema: &DatabaseSchema) -> TableSequences {
+    let mut sequences = TableSequences::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            for column in &table.columns {
+                if let Some(sequence) =
+                    schema.column_sequence(&db_schema.name, &table.name, &column.name)
+                {
+                    sequences
+                        .entry((db_schema.name.clone(), table.name.clone()))
+                        .or_default()
+                        .push((column.name.clone(), sequence.clone()));
+                }
+            }
+        }
+    }
+    sequences
+}
+
+/// `setval` of each sequence in `sequences` (column, sequence) to the last
+/// value `rows` used, in the direction of its increment.
+pub fn sequence_resets(
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+    sequences: Option<&Vec<(String, Sequence)>>,
+) -> String {
+    let mut out = String::new();
+    let inserted: Vec<&str> = copy_columns(table)
+        .into_iter()
+        .map(|col| col.name.as_str())
+        .collect();
+    for (column, sequence) in sequences.into_iter().flatten() {
+        if !inserted.contains(&column.as_str()) {
+            continue;
+        }
+        let values = rows
+            .iter()
+            .filter_map(|row| match row.get(&column.to_lowercase()) {
+                Some(GeneratedValue::Int(value)) => Some(*value),
+                _ => None,
+            });
+        let last = if sequence.increment < 0 {
+            values.min()
+        } else {
+            values.max()
+        };
+        if let Some(last) = last {
+            let name = format!(
+                "{}.{}",
+                quote_ident(&sequence.schema),
+                quote_ident(&sequence.name)
+            );
+            let _ = writeln!(
+                out,
+                "SELECT setval({}, {last}, true);",
+                quote_literal(&name)
+            );
+        }
+    }
+    out
+}
+
+fn table_output(
+    schema_name: &str,
+    table: &Table,
+    file_name: String,
+    bytes: usize,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> TableOutput {
+    TableOutput {
+        schema: schema_name.to_string(),
+        table: table.name.clone(),
+        file_name,
+        columns: copy_columns(table)
+            .into_iter()
+            .map(|col| col.name.clone())
+            .collect(),
+        bytes: bytes as u64,
+        rows: rows.len() as u64,
+    }
+}
+
+/// Identifier reduced to `[A-Za-z0-9_]` for migration file names.
+fn file_part(name: &str) -> String {
+    name.chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect()
+}
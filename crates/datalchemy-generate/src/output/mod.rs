@@ -1 +1,181 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod avro;
 pub mod csv;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+pub mod fixed_width;
+pub mod kafka;
+pub mod migrations;
+pub mod partition;
+pub mod pg_copy;
+pub mod sqlite;
+pub mod stream;
+pub mod xlsx;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use datalchemy_core::{DatabaseSchema, Table, render_postgres_ddl};
+use datalchemy_plan::{Plan, ValueFormats};
+
+use crate::errors::GenerationError;
+use crate::generated;
+use crate::generators::GeneratedValue;
+use crate::model::{GenerationIssue, OutputFormat};
+
+/// DDL script written next to the data so it can be loaded into an empty database.
+pub const SCHEMA_SQL_FILE_NAME: &str = "schema.sql";
+
+/// File written for a generated table.
+#[derive(Debug, Clone)]
+pub struct TableOutput {
+    pub schema: String,
+    pub table: String,
+    /// File name relative to the run directory.
+    pub file_name: String,
+    /// Columns written to the file, in order.
+    pub columns: Vec<String>,
+    pub bytes: u64,
+    /// Rows written for the table.
+    pub rows: u64,
+}
+
+/// Destination for generated tables, created once per run.
+///
+/// Tables arrive in generation (FK-safe insert) order.
+pub trait TableSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError>;
+
+    /// Warnings raised since the last call (e.g. truncated output), recorded in the report.
+    fn take_warnings(&mut self) -> Vec<GenerationIssue> {
+        Vec::new()
+    }
+
+    /// Write run-level artifacts once every table is written. Returns bytes written.
+    fn finish(&mut self, _outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        Ok(0)
+    }
+}
+
+/// Write [`SCHEMA_SQL_FILE_NAME`] for `schema` into `run_dir`. Returns its size.
+pub fn write_schema_sql(run_dir: &Path, schema: &DatabaseSchema) -> Result<u64, GenerationError> {
+    let ddl = render_postgres_ddl(schema);
+    std::fs::write(run_dir.join(SCHEMA_SQL_FILE_NAME), &ddl)?;
+    Ok(ddl.len() as u64)
+}
+
+/// Build the sink for `format`, writing into `run_dir`.
+///
+/// `plan` supplies per-table output layouts (`fixed_width`, `output_partition`)
+/// and the value formats used by the text writers (csv, fixed_width).
+pub fn create_sink(
+    format: OutputFormat,
+    run_dir: &Path,
+    schema: &DatabaseSchema,
+    plan: &Plan,
+) -> Result<Box<dyn TableSink>, GenerationError> {
+    let dir = run_dir;
+    let run_dir = run_dir.to_path_buf();
+    let sink: Box<dyn TableSink> = match format {
+        OutputFormat::Csv => Box::new(CsvSink {
+            run_dir,
+            formats: value_formats(plan),
+        }),
+        OutputFormat::PgCopy => Box::new(pg_copy::PgCopySink::new(run_dir, false)),
+        OutputFormat::PgCopyBinary => Box::new(pg_copy::PgCopySink::new(run_dir, true)),
+        OutputFormat::Sqlite => Box::new(sqlite::SqliteSink::create(&run_dir, schema)?),
+        OutputFormat::Avro => Box::new(avro::AvroSink::new(run_dir)),
+        OutputFormat::Xlsx => Box::new(xlsx::XlsxSink::new(run_dir)),
+        OutputFormat::FixedWidth => Box::new(fixed_width::FixedWidthSink::new(run_dir, plan)),
+        OutputFormat::Flyway => Box::new(migrations::FlywaySink::new(
+            run_dir,
+            migration_version(),
+            schema,
+        )),
+        OutputFormat::Liquibase => Box::new(migrations::LiquibaseSink::create(
+            run_dir,
+            migration_version(),
+            schema,
+        )?),
+        #[cfg(feature = "arrow")]
+        OutputFormat::ArrowIpc => Box::new(self::arrow::ArrowIpcSink::new(run_dir)),
+        #[cfg(not(feature = "arrow"))]
+        OutputFormat::ArrowIpc => {
+            return Err(GenerationError::Unsupported(
+                "arrow output requires datalchemy-generate built with the `arrow` feature"
+                    .to_string(),
+            ));
+        }
+        #[cfg(feature = "duckdb")]
+        OutputFormat::DuckDb => Box::new(self::duckdb::DuckDbSink::create(&run_dir)?),
+        #[cfg(not(feature = "duckdb"))]
+        OutputFormat::DuckDb => {
+            return Err(GenerationError::Unsupported(
+                "duckdb output requires datalchemy-generate built with the `duckdb` feature"
+                    .to_string(),
+            ));
+        }
+    };
+    partition::PartitionedSink::wrap(format, dir, plan, sink)
+}
+
+/// Version stamp of migration outputs (UTC `yyyymmddHHMMSS`), so seed
+/// migrations sort after the project's existing ones.
+fn migration_version() -> String {
+    chrono::Utc::now().format("%Y%m%d%H%M%S").to_string()
+}
+
+/// Value formats from the plan's `global.formats` (canonical when unset).
+pub fn value_formats(plan: &Plan) -> ValueFormats {
+    plan.global
+        .as_ref()
+        .and_then(|global| global.formats.clone())
+        .unwrap_or_default()
+}
+
+/// Columns written to file outputs, sorted by ordinal position (generated
+/// columns the engine cannot compute are left out).
+pub(crate) fn ordered_columns(table: &Table) -> Vec<&datalchemy_core::Column> {
+    let mut columns: Vec<_> = table
+        .columns
+        .iter()
+        .filter(|col| generated::is_written(col))
+        .collect();
+    columns.sort_by_key(|col| col.ordinal_position);
+    columns
+}
+
+struct CsvSink {
+    run_dir: PathBuf,
+    formats: ValueFormats,
+}
+
+impl TableSink for CsvSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let file_name = format!("{}.{}.csv", schema_name, table.name);
+        let bytes =
+            csv::write_table_csv(&self.run_dir.join(&file_name), table, rows, &self.formats)?;
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name,
+            columns: ordered_columns(table)
+                .into_iter()
+                .map(|col| col.name.clone())
+                .collect(),
+            bytes,
+            rows: rows.len() as u64,
+        })
+    }
+}
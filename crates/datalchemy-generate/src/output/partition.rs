@@ -0,0 +1,216 @@
+//! Hive-style partitioned layout driven by `output_partition` plan rules.
+//!
+//! A partitioned table is written as
+//! `<schema>.<table>/<column>=<value>/part-0.<ext>`, one directory per distinct
+//! value (NULL goes to `__HIVE_DEFAULT_PARTITION__`). The partition column is
+//! dropped from the data files, as Hive-aware readers restore it from the path.
+//! Other tables pass through to the regular sink unchanged.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use datalchemy_core::Table;
+use datalchemy_plan::{Plan, Rule};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::model::{GenerationIssue, OutputFormat};
+use crate::output::{TableOutput, TableSink};
+
+pub const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Sink splitting partitioned tables into per-value directories.
+pub struct PartitionedSink {
+    format: OutputFormat,
+    run_dir: PathBuf,
+    plan: Plan,
+    partitions: BTreeMap<String, String>,
+    inner: Box<dyn TableSink>,
+    warnings: Vec<GenerationIssue>,
+}
+
+impl PartitionedSink {
+    /// Wrap `inner` when the plan declares partitions; otherwise return it as is.
+    pub fn wrap(
+        format: OutputFormat,
+        run_dir: &Path,
+        plan: &Plan,
+        inner: Box<dyn TableSink>,
+    ) -> Result<Box<dyn TableSink>, GenerationError> {
+        let partitions: BTreeMap<String, String> = plan
+            .rules
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::OutputPartition(rule) => Some((
+                    format!("{}.{}", rule.schema, rule.table),
+                    rule.column.clone(),
+                )),
+                _ => None,
+            })
+            .collect();
+        if partitions.is_empty() {
+            return Ok(inner);
+        }
+        if !supports_partitions(format) {
+            return Err(GenerationError::Unsupported(format!(
+                "output_partition is not supported for {} output",
+                format.as_str()
+            )));
+        }
+        Ok(Box::new(Self {
+            format,
+            run_dir: run_dir.to_path_buf(),
+            plan: plan.clone(),
+            partitions,
+            inner,
+            warnings: Vec::new(),
+        }))
+    }
+}
+
+impl TableSink for PartitionedSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let Some(column_name) = self
+            .partitions
+            .get(&format!("{}.{}", schema_name, table.name))
+        else {
+            return self.inner.write_table(schema_name, table, rows);
+        };
+        let column = table
+            .columns
+            .iter()
+            .find(|col| &col.name == column_name)
+            .ok_or_else(|| {
+                GenerationError::InvalidPlan(format!(
+                    "partition column {}.{}.{} not found",
+                    schema_name, table.name, column_name
+                ))
+            })?;
+
+        let key = column.name.to_lowercase();
+        let mut groups: BTreeMap<String, Vec<HashMap<String, GeneratedValue>>> = BTreeMap::new();
+        for row in rows {
+            let value = match row.get(&key) {
+                None | Some(GeneratedValue::Null) => HIVE_DEFAULT_PARTITION.to_string(),
+                Some(value) => escape_partition_value(&value.to_csv(column)),
+            };
+            let mut row = row.clone();
+            row.remove(&key);
+            groups.entry(value).or_default().push(row);
+        }
+
+        let mut data_table = table.clone();
+        data_table.columns.retain(|col| col.name != column.name);
+
+        let table_dir_name = format!("{}.{}", schema_name, table.name);
+        let table_dir = self.run_dir.join(&table_dir_name);
+        std::fs::create_dir_all(&table_dir)?;
+
+        let mut bytes = 0;
+        let mut columns = Vec::new();
+        for (value, group) in &groups {
+            let dir = table_dir.join(format!("{}={}", column.name, value));
+            std::fs::create_dir_all(&dir)?;
+            let mut sink = partition_sink(self.format, &dir, &self.plan)?;
+            let output = sink.write_table(schema_name, &data_table, group)?;
+            self.warnings.extend(sink.take_warnings());
+            rename_part_files(&dir, &table_dir_name)?;
+            bytes += output.bytes;
+            columns = output.columns;
+        }
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: table_dir_name,
+            columns,
+            bytes,
+            rows: rows.len() as u64,
+        })
+    }
+
+    fn take_warnings(&mut self) -> Vec<GenerationIssue> {
+        let mut warnings = std::mem::take(&mut self.warnings);
+        warnings.extend(self.inner.take_warnings());
+        warnings
+    }
+
+    fn finish(&mut self, outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        self.inner.finish(outputs)
+    }
+}
+
+/// Formats writing self-contained files per table (no run-level artifacts).
+fn supports_partitions(format: OutputFormat) -> bool {
+    matches!(
+        format,
+        OutputFormat::Csv | OutputFormat::Avro | OutputFormat::ArrowIpc | OutputFormat::FixedWidth
+    )
+}
+
+fn partition_sink(
+    format: OutputFormat,
+    dir: &Path,
+    plan: &Plan,
+) -> Result<Box<dyn TableSink>, GenerationError> {
+    let dir = dir.to_path_buf();
+    let sink: Box<dyn TableSink> = match format {
+        OutputFormat::Csv => Box::new(super::CsvSink {
+            run_dir: dir,
+            formats: super::value_formats(plan),
+        }),
+        OutputFormat::Avro => Box::new(super::avro::AvroSink::new(dir)),
+        OutputFormat::FixedWidth => Box::new(super::fixed_width::FixedWidthSink::new(dir, plan)),
+        #[cfg(feature = "arrow")]
+        OutputFormat::ArrowIpc => Box::new(super::arrow::ArrowIpcSink::new(dir)),
+        other => {
+            return Err(GenerationError::Unsupported(format!(
+                "output_partition is not supported for {} output",
+                other.as_str()
+            )));
+        }
+    };
+    Ok(sink)
+}
+
+/// Rename `<schema>.<table>.<ext>` files written into a partition to `part-0.<ext>`.
+fn rename_part_files(dir: &Path, table_file_prefix: &str) -> Result<(), GenerationError> {
+    let prefix = format!("{table_file_prefix}.");
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+    for path in entries {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if let Some(ext) = name.strip_prefix(&prefix) {
+            std::fs::rename(&path, dir.join(format!("part-0.{ext}")))?;
+        }
+    }
+    Ok(())
+}
+
+/// Percent-encode characters that are not safe in a Hive partition path segment.
+pub fn escape_partition_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '"' | '#' | '%' | '\'' | '*' | '/' | ':' | '=' | '?' | '\\' | '{' | '[' | ']' | '^' => {
+                out.push_str(&format!("%{:02X}", ch as u32))
+            }
+            ch if ch.is_control() => out.push_str(&format!("%{:02X}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    if out.is_empty() {
+        HIVE_DEFAULT_PARTITION.to_string()
+    } else {
+        out
+    }
+}
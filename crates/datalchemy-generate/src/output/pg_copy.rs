@@ -0,0 +1,362 @@
+//! Postgres `COPY ... FROM STDIN` writers (text and binary) plus loader script.
+//!
+//! Text files follow the default COPY text conventions: tab separated, `\N`
+//! for NULL and backslash escapes for control characters. Binary files follow
+//! the `PGCOPY` layout documented for `COPY ... (FORMAT binary)`. Both keep
+//! the canonical value forms COPY parses; the plan's `global.formats` do not
+//! apply.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate, NaiveTime};
+
+use datalchemy_core::{Column, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const PG_EPOCH_DAYS_FROM_CE: i32 = 730_120;
+
+/// Sink writing one COPY file per table plus `load.sql`.
+pub struct PgCopySink {
+    run_dir: PathBuf,
+    binary: bool,
+}
+
+impl PgCopySink {
+    pub fn new(run_dir: PathBuf, binary: bool) -> Self {
+        Self { run_dir, binary }
+    }
+}
+
+impl TableSink for PgCopySink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let extension = if self.binary { "copy.bin" } else { "copy" };
+        let file_name = format!("{}.{}.{}", schema_name, table.name, extension);
+        let path = self.run_dir.join(&file_name);
+        let bytes = if self.binary {
+            write_table_copy_binary(&path, table, rows)?
+        } else {
+            write_table_copy_text(&path, table, rows)?
+        };
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name,
+            columns: copy_columns(table)
+                .into_iter()
+                .map(|col| col.name.clone())
+                .collect(),
+            bytes,
+            rows: rows.len() as u64,
+        })
+    }
+
+    fn finish(&mut self, outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        write_loader_script(&self.run_dir.join("load.sql"), outputs, self.binary)
+    }
+}
+
+/// Columns accepted by `COPY FROM`, in ordinal order (generated columns are skipped).
+pub fn copy_columns(table: &Table) -> Vec<&Column> {
+    ordered_columns(table)
+        .into_iter()
+        .filter(|col| col.generated.is_none())
+        .collect()
+}
+
+/// Write a table in COPY text format (no header, `\N` for NULL).
+pub fn write_table_copy_text(
+    path: &Path,
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> Result<u64, GenerationError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_copy_text(&mut writer, table, rows)
+}
+
+/// Write a table in COPY text format into any writer. Returns bytes written.
+pub fn write_copy_text<W: Write>(
+    writer: &mut W,
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> Result<u64, GenerationError> {
+    let columns = copy_columns(table);
+    let mut bytes = 0_u64;
+
+    for row in rows {
+        let mut line = String::new();
+        for (idx, col) in columns.iter().enumerate() {
+            if idx > 0 {
+                line.push('\t');
+            }
+            match row.get(&col.name.to_lowercase()) {
+                None | Some(GeneratedValue::Null) => line.push_str("\\N"),
+                Some(value) => escape_text(&value.to_csv(col), &mut line),
+            }
+        }
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+        bytes += line.len() as u64;
+    }
+
+    writer.flush()?;
+    Ok(bytes)
+}
+
+/// Write a table in COPY binary format.
+pub fn write_table_copy_binary(
+    path: &Path,
+    table: &Table,
+    rows: &[HashMap<String, GeneratedValue>],
+) -> Result<u64, GenerationError> {
+    let columns = copy_columns(table);
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(BINARY_SIGNATURE);
+    buffer.extend_from_slice(&0_i32.to_be_bytes());
+    buffer.extend_from_slice(&0_i32.to_be_bytes());
+
+    for row in rows {
+        buffer.extend_from_slice(&(columns.len() as i16).to_be_bytes());
+        for col in &columns {
+            match row.get(&col.name.to_lowercase()) {
+                None | Some(GeneratedValue::Null) => {
+                    buffer.extend_from_slice(&(-1_i32).to_be_bytes());
+                }
+                Some(value) => {
+                    let field = encode_binary(table, col, value)?;
+                    buffer.extend_from_slice(&(field.len() as i32).to_be_bytes());
+                    buffer.extend_from_slice(&field);
+                }
+            }
+        }
+    }
+    buffer.extend_from_slice(&(-1_i16).to_be_bytes());
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&buffer)?;
+    writer.flush()?;
+    Ok(buffer.len() as u64)
+}
+
+/// Write `load.sql`, a psql script that loads every COPY file in insert order.
+pub fn write_loader_script(
+    path: &Path,
+    outputs: &[TableOutput],
+    binary: bool,
+) -> Result<u64, GenerationError> {
+    let mut script = String::new();
+    script.push_str("-- Generated by datalchemy. Run from this directory: psql -f load.sql\n");
+    script.push_str("\\set ON_ERROR_STOP on\n");
+    script.push_str("BEGIN;\n");
+    for output in outputs {
+        let columns = output
+            .columns
+            .iter()
+            .map(|col| quote_ident(col))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let options = if binary { " WITH (FORMAT binary)" } else { "" };
+        script.push_str(&format!(
+            "\\copy {}.{} ({}) FROM '{}'{}\n",
+            quote_ident(&output.schema),
+            quote_ident(&output.table),
+            columns,
+            output.file_name.replace('\'', "''"),
+            options
+        ));
+    }
+    script.push_str("COMMIT;\n");
+
+    std::fs::write(path, script.as_bytes())?;
+    Ok(script.len() as u64)
+}
+
+fn escape_text(value: &str, out: &mut String) {
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\u{b}' => out.push_str("\\v"),
+            other => out.push(other),
+        }
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn encode_binary(
+    table: &Table,
+    column: &Column,
+    value: &GeneratedValue,
+) -> Result<Vec<u8>, GenerationError> {
+    let invalid = || {
+        GenerationError::InvalidPlan(format!(
+            "value '{}' is not valid for {}.{} ({})",
+            value.to_csv(column),
+            table.name,
+            column.name,
+            column.column_type.udt_name
+        ))
+    };
+    let pg_epoch = || {
+        NaiveDate::from_ymd_opt(2000, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(invalid)
+    };
+
+    let bytes = match (column.column_type.udt_name.as_str(), value) {
+        ("bool", GeneratedValue::Bool(flag)) => vec![u8::from(*flag)],
+        ("int2", GeneratedValue::Int(int)) => i16::try_from(*int)
+            .map_err(|_| invalid())?
+            .to_be_bytes()
+            .to_vec(),
+        ("int4", GeneratedValue::Int(int)) => i32::try_from(*int)
+            .map_err(|_| invalid())?
+            .to_be_bytes()
+            .to_vec(),
+        ("int8", GeneratedValue::Int(int)) => int.to_be_bytes().to_vec(),
+        ("float4", GeneratedValue::Int(_) | GeneratedValue::Float(_)) => {
+            (value.as_f64().ok_or_else(invalid)? as f32)
+                .to_be_bytes()
+                .to_vec()
+        }
+        ("float8", GeneratedValue::Int(_) | GeneratedValue::Float(_)) => {
+            value.as_f64().ok_or_else(invalid)?.to_be_bytes().to_vec()
+        }
+        ("numeric", GeneratedValue::Int(int)) => {
+            encode_numeric(&int.to_string()).ok_or_else(invalid)?
+        }
+        ("numeric", GeneratedValue::Float(float)) => {
+            let decimal = match column.column_type.numeric_scale {
+                Some(scale) => format!("{float:.*}", scale as usize),
+                None => float.to_string(),
+            };
+            encode_numeric(&decimal).ok_or_else(invalid)?
+        }
+        ("uuid", GeneratedValue::Uuid(text) | GeneratedValue::Text(text)) => {
+            uuid::Uuid::parse_str(text)
+                .map_err(|_| invalid())?
+                .as_bytes()
+                .to_vec()
+        }
+        ("date", GeneratedValue::Date(date)) => (date.num_days_from_ce() - PG_EPOCH_DAYS_FROM_CE)
+            .to_be_bytes()
+            .to_vec(),
+        ("time", GeneratedValue::Time(time)) => time
+            .signed_duration_since(NaiveTime::MIN)
+            .num_microseconds()
+            .ok_or_else(invalid)?
+            .to_be_bytes()
+            .to_vec(),
+        ("timestamp" | "timestamptz", GeneratedValue::Timestamp(ts)) => ts
+            .signed_duration_since(pg_epoch()?)
+            .num_microseconds()
+            .ok_or_else(invalid)?
+            .to_be_bytes()
+            .to_vec(),
+        ("jsonb", GeneratedValue::Text(text)) => {
+            let mut bytes = vec![1];
+            bytes.extend_from_slice(text.as_bytes());
+            bytes
+        }
+        ("text" | "varchar" | "bpchar" | "name" | "citext" | "json" | "xml", _) => {
+            value.to_csv(column).into_bytes()
+        }
+        // Enum labels use their text representation in binary COPY.
+        (_, GeneratedValue::Text(text)) if column.column_type.udt_schema != "pg_catalog" => {
+            text.clone().into_bytes()
+        }
+        (
+            "bool" | "int2" | "int4" | "int8" | "float4" | "float8" | "numeric" | "uuid" | "date"
+            | "time" | "timestamp" | "timestamptz" | "jsonb",
+            _,
+        ) => return Err(invalid()),
+        (other, _) => {
+            return Err(GenerationError::Unsupported(format!(
+                "binary COPY does not support type '{}' ({}.{})",
+                other, table.name, column.name
+            )));
+        }
+    };
+
+    Ok(bytes)
+}
+
+/// Encode a decimal string using the Postgres binary `numeric` layout.
+fn encode_numeric(text: &str) -> Option<Vec<u8>> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (int_part, frac_part) = match unsigned.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, frac_part),
+        None => (unsigned, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    if !int_part
+        .chars()
+        .chain(frac_part.chars())
+        .all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let dscale = frac_part.len() as u16;
+    let int_part = int_part.trim_start_matches('0');
+    let int_padded = format!("{}{}", "0".repeat((4 - int_part.len() % 4) % 4), int_part);
+    let frac_padded = format!("{}{}", frac_part, "0".repeat((4 - frac_part.len() % 4) % 4));
+
+    let mut digits: Vec<i16> = Vec::new();
+    for chunk in int_padded.as_bytes().chunks(4) {
+        digits.push(std::str::from_utf8(chunk).ok()?.parse().ok()?);
+    }
+    let mut weight = digits.len() as i16 - 1;
+    for chunk in frac_padded.as_bytes().chunks(4) {
+        digits.push(std::str::from_utf8(chunk).ok()?.parse().ok()?);
+    }
+
+    while digits.first() == Some(&0) {
+        digits.remove(0);
+        weight -= 1;
+    }
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        weight = 0;
+    }
+
+    let sign: u16 = if negative && !digits.is_empty() {
+        0x4000
+    } else {
+        0x0000
+    };
+    let mut bytes = Vec::with_capacity(8 + digits.len() * 2);
+    bytes.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    bytes.extend_from_slice(&weight.to_be_bytes());
+    bytes.extend_from_slice(&sign.to_be_bytes());
+    bytes.extend_from_slice(&dscale.to_be_bytes());
+    for digit in digits {
+        bytes.extend_from_slice(&digit.to_be_bytes());
+    }
+    Some(bytes)
+}
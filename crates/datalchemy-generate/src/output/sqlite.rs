@@ -0,0 +1,209 @@
+//! SQLite database file sink.
+//!
+//! Creates `dataset.sqlite` with DDL translated from the Postgres schema
+//! (type affinities, NOT NULL, PK/UNIQUE/FK) and inserts every generated row.
+//! CHECK expressions and defaults are not translated because they are not
+//! portable across engines. Values are stored typed, so the plan's
+//! `global.formats` do not apply.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use rusqlite::types::Value as SqlValue;
+
+use datalchemy_core::{Column, Constraint, DatabaseSchema, Table};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+pub const SQLITE_FILE_NAME: &str = "dataset.sqlite";
+
+/// Sink writing every table into a single SQLite database file.
+pub struct SqliteSink {
+    conn: Connection,
+    path: PathBuf,
+    table_names: BTreeMap<(String, String), String>,
+}
+
+impl SqliteSink {
+    pub fn create(run_dir: &Path, schema: &DatabaseSchema) -> Result<Self, GenerationError> {
+        let path = run_dir.join(SQLITE_FILE_NAME);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        let conn = Connection::open(&path)?;
+        Ok(Self {
+            conn,
+            path,
+            table_names: sqlite_table_names(schema),
+        })
+    }
+
+    fn table_name(&self, schema_name: &str, table: &str) -> String {
+        self.table_names
+            .get(&(schema_name.to_string(), table.to_string()))
+            .cloned()
+            .unwrap_or_else(|| table.to_string())
+    }
+}
+
+impl TableSink for SqliteSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let name = self.table_name(schema_name, &table.name);
+        let ddl = create_table_sql(&name, table, |schema, table| self.table_name(schema, table));
+        self.conn.execute_batch(&ddl)?;
+
+        let columns = ordered_columns(table);
+        let insert = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote_ident(&name),
+            columns
+                .iter()
+                .map(|col| quote_ident(&col.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            (1..=columns.len())
+                .map(|idx| format!("?{idx}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(&insert)?;
+            for row in rows {
+                let values: Vec<SqlValue> = columns
+                    .iter()
+                    .map(|col| to_sql_value(col, row.get(&col.name.to_lowercase())))
+                    .collect();
+                stmt.execute(rusqlite::params_from_iter(values))?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: SQLITE_FILE_NAME.to_string(),
+            columns: columns.iter().map(|col| col.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+
+    fn finish(&mut self, _outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        Ok(std::fs::metadata(&self.path)?.len())
+    }
+}
+
+/// Map `(schema, table)` to a SQLite table name.
+///
+/// SQLite has a single namespace, so names shared by several schemas are
+/// prefixed with `<schema>_`.
+pub fn sqlite_table_names(schema: &DatabaseSchema) -> BTreeMap<(String, String), String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            *counts.entry(table.name.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    let mut names = BTreeMap::new();
+    for db_schema in &schema.schemas {
+        for table in &db_schema.tables {
+            let name = if counts.get(table.name.as_str()).copied().unwrap_or(0) > 1 {
+                format!("{}_{}", db_schema.name, table.name)
+            } else {
+                table.name.clone()
+            };
+            names.insert((db_schema.name.clone(), table.name.clone()), name);
+        }
+    }
+    names
+}
+
+/// Build a SQLite `CREATE TABLE` statement for `table`.
+pub fn create_table_sql(
+    name: &str,
+    table: &Table,
+    resolve: impl Fn(&str, &str) -> String,
+) -> String {
+    let mut lines: Vec<String> = ordered_columns(table)
+        .into_iter()
+        .map(|col| {
+            let mut line = format!("  {} {}", quote_ident(&col.name), sqlite_affinity(col));
+            if !col.is_nullable {
+                line.push_str(" NOT NULL");
+            }
+            line
+        })
+        .collect();
+
+    for constraint in &table.constraints {
+        match constraint {
+            Constraint::PrimaryKey(pk) => {
+                lines.push(format!("  PRIMARY KEY ({})", quote_list(&pk.columns)));
+            }
+            Constraint::Unique(unique) => {
+                lines.push(format!("  UNIQUE ({})", quote_list(&unique.columns)));
+            }
+            Constraint::ForeignKey(fk) => {
+                lines.push(format!(
+                    "  FOREIGN KEY ({}) REFERENCES {} ({})",
+                    quote_list(&fk.columns),
+                    quote_ident(&resolve(&fk.referenced_schema, &fk.referenced_table)),
+                    quote_list(&fk.referenced_columns)
+                ));
+            }
+            Constraint::Check(_) | Constraint::Exclusion(_) => {}
+        }
+    }
+
+    format!(
+        "CREATE TABLE {} (\n{}\n);",
+        quote_ident(name),
+        lines.join(",\n")
+    )
+}
+
+/// SQLite type affinity for a Postgres column type.
+pub fn sqlite_affinity(column: &Column) -> &'static str {
+    match column.column_type.udt_name.as_str() {
+        "int2" | "int4" | "int8" | "bool" => "INTEGER",
+        "float4" | "float8" => "REAL",
+        "numeric" => "NUMERIC",
+        "bytea" => "BLOB",
+        _ => "TEXT",
+    }
+}
+
+fn to_sql_value(column: &Column, value: Option<&GeneratedValue>) -> SqlValue {
+    match value {
+        None | Some(GeneratedValue::Null) => SqlValue::Null,
+        Some(GeneratedValue::Bool(value)) => SqlValue::Integer(i64::from(*value)),
+        Some(GeneratedValue::Int(value)) => SqlValue::Integer(*value),
+        Some(GeneratedValue::Float(value)) if column.column_type.numeric_scale.is_none() => {
+            SqlValue::Real(*value)
+        }
+        Some(other) => SqlValue::Text(other.to_csv(column)),
+    }
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_list(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|col| quote_ident(col))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
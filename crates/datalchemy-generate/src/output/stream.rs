@@ -0,0 +1,92 @@
+//! Single-table output to a writer (e.g. stdout), used by
+//! [`GenerationEngine::stream_table`](crate::GenerationEngine::stream_table).
+//!
+//! Every table of the plan is still generated, so foreign keys and seeds match
+//! a full run; only the selected table is written.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use datalchemy_core::Table;
+use datalchemy_plan::ValueFormats;
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::model::OutputFormat;
+use crate::output::pg_copy::{copy_columns, write_copy_text};
+use crate::output::{TableOutput, TableSink, csv, ordered_columns};
+
+/// Sink writing the rows of one table to `writer`, ignoring the others.
+pub struct StreamSink<'w> {
+    writer: &'w mut dyn Write,
+    schema: String,
+    table: String,
+    format: OutputFormat,
+    formats: ValueFormats,
+    written: bool,
+}
+
+impl<'w> StreamSink<'w> {
+    /// `format` must be `csv` (header + rows) or `pg_copy` (COPY text rows).
+    pub fn new(
+        writer: &'w mut dyn Write,
+        schema: &str,
+        table: &str,
+        format: OutputFormat,
+        formats: ValueFormats,
+    ) -> Result<Self, GenerationError> {
+        if !matches!(format, OutputFormat::Csv | OutputFormat::PgCopy) {
+            return Err(GenerationError::Unsupported(format!(
+                "{} output cannot be streamed (use csv or pg_copy)",
+                format.as_str()
+            )));
+        }
+        Ok(Self {
+            writer,
+            schema: schema.to_string(),
+            table: table.to_string(),
+            format,
+            formats,
+            written: false,
+        })
+    }
+
+    /// Whether the selected table was generated and written.
+    pub fn written(&self) -> bool {
+        self.written
+    }
+}
+
+impl TableSink for StreamSink<'_> {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let selected = schema_name == self.schema && table.name == self.table;
+        let (bytes, columns) = match (selected, self.format) {
+            (false, _) => (0, Vec::new()),
+            (true, OutputFormat::PgCopy) => (
+                write_copy_text(&mut self.writer, table, rows)?,
+                copy_columns(table),
+            ),
+            (true, _) => (
+                csv::write_csv(&mut self.writer, table, rows, &self.formats)?,
+                ordered_columns(table),
+            ),
+        };
+        if selected {
+            self.writer.flush()?;
+            self.written = true;
+        }
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: columns.into_iter().map(|col| col.name.clone()).collect(),
+            bytes,
+            rows: if selected { rows.len() as u64 } else { 0 },
+        })
+    }
+}
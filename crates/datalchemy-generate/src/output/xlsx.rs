@@ -0,0 +1,172 @@
+//! Excel workbook sink: `dataset.xlsx` with one sheet per table.
+//!
+//! Sheets hold at most [`XLSX_MAX_ROWS`] data rows (the Excel limit minus the
+//! header); extra rows are dropped and reported as `xlsx_rows_truncated`.
+//! Cells keep typed values, so the plan's `global.formats` do not apply.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use rust_xlsxwriter::{Format, Workbook};
+
+use datalchemy_core::Table;
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::model::GenerationIssue;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+pub const XLSX_FILE_NAME: &str = "dataset.xlsx";
+/// Data rows per sheet (1,048,576 Excel rows minus the header).
+pub const XLSX_MAX_ROWS: usize = 1_048_575;
+const SHEET_NAME_MAX: usize = 31;
+
+/// Sink writing every table as a sheet of a single workbook.
+pub struct XlsxSink {
+    run_dir: PathBuf,
+    workbook: Workbook,
+    sheet_names: BTreeSet<String>,
+    max_rows: usize,
+    warnings: Vec<GenerationIssue>,
+}
+
+impl XlsxSink {
+    pub fn new(run_dir: PathBuf) -> Self {
+        Self::with_max_rows(run_dir, XLSX_MAX_ROWS)
+    }
+
+    pub fn with_max_rows(run_dir: PathBuf, max_rows: usize) -> Self {
+        Self {
+            run_dir,
+            workbook: Workbook::new(),
+            sheet_names: BTreeSet::new(),
+            max_rows: max_rows.min(XLSX_MAX_ROWS),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn unique_sheet_name(&mut self, schema_name: &str, table: &str) -> String {
+        let base = sheet_name(&format!("{schema_name}.{table}"));
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.sheet_names.contains(&name.to_lowercase()) {
+            let tag = format!("~{suffix}");
+            let keep = SHEET_NAME_MAX.saturating_sub(tag.len());
+            name = format!("{}{}", base.chars().take(keep).collect::<String>(), tag);
+            suffix += 1;
+        }
+        self.sheet_names.insert(name.to_lowercase());
+        name
+    }
+}
+
+impl TableSink for XlsxSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let name = self.unique_sheet_name(schema_name, &table.name);
+        let columns = ordered_columns(table);
+        let header = Format::new().set_bold();
+        let date_format = Format::new().set_num_format("yyyy-mm-dd");
+        let time_format = Format::new().set_num_format("hh:mm:ss");
+        let timestamp_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+
+        let sheet = self.workbook.add_worksheet();
+        sheet.set_name(&name)?;
+        for (col_idx, col) in columns.iter().enumerate() {
+            sheet.write_string_with_format(0, col_idx as u16, &col.name, &header)?;
+        }
+        sheet.set_freeze_panes(1, 0)?;
+
+        for (row_idx, row) in rows.iter().take(self.max_rows).enumerate() {
+            let excel_row = row_idx as u32 + 1;
+            for (col_idx, col) in columns.iter().enumerate() {
+                let col_idx = col_idx as u16;
+                let result = match row.get(&col.name.to_lowercase()) {
+                    None | Some(GeneratedValue::Null) => continue,
+                    Some(GeneratedValue::Bool(value)) => {
+                        sheet.write_boolean(excel_row, col_idx, *value)
+                    }
+                    Some(GeneratedValue::Int(value)) => {
+                        sheet.write_number(excel_row, col_idx, *value as f64)
+                    }
+                    Some(GeneratedValue::Float(value)) => {
+                        sheet.write_number(excel_row, col_idx, *value)
+                    }
+                    Some(GeneratedValue::Date(value)) => {
+                        sheet.write_datetime_with_format(excel_row, col_idx, value, &date_format)
+                    }
+                    Some(GeneratedValue::Time(value)) => {
+                        sheet.write_datetime_with_format(excel_row, col_idx, value, &time_format)
+                    }
+                    Some(GeneratedValue::Timestamp(value)) => sheet.write_datetime_with_format(
+                        excel_row,
+                        col_idx,
+                        value,
+                        &timestamp_format,
+                    ),
+                    Some(value) => sheet.write_string(excel_row, col_idx, value.to_csv(col)),
+                };
+                result?;
+            }
+        }
+
+        if rows.len() > self.max_rows {
+            self.warnings.push(GenerationIssue {
+                level: "warning".to_string(),
+                code: "xlsx_rows_truncated".to_string(),
+                message: format!(
+                    "sheet '{}' keeps {} of {} rows",
+                    name,
+                    self.max_rows,
+                    rows.len()
+                ),
+                path: None,
+                schema: Some(schema_name.to_string()),
+                table: Some(table.name.clone()),
+                column: None,
+                generator_id: None,
+            });
+        }
+
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: XLSX_FILE_NAME.to_string(),
+            columns: columns.iter().map(|col| col.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len().min(self.max_rows) as u64,
+        })
+    }
+
+    fn take_warnings(&mut self) -> Vec<GenerationIssue> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    fn finish(&mut self, _outputs: &[TableOutput]) -> Result<u64, GenerationError> {
+        let path = self.run_dir.join(XLSX_FILE_NAME);
+        self.workbook.save(&path)?;
+        Ok(std::fs::metadata(&path)?.len())
+    }
+}
+
+/// Sanitize a sheet name: Excel forbids `[]:*?/\` and limits names to 31 chars.
+pub fn sheet_name(raw: &str) -> String {
+    let cleaned: String = raw
+        .chars()
+        .map(|ch| match ch {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            other => other,
+        })
+        .take(SHEET_NAME_MAX)
+        .collect();
+    let trimmed = cleaned.trim_matches('\'');
+    if trimmed.is_empty() {
+        "sheet".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
@@ -15,6 +15,20 @@ pub enum ParamKind {
     Timestamp,
 }
 
+impl ParamKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParamKind::Bool => "bool",
+            ParamKind::Int => "int",
+            ParamKind::Float => "float",
+            ParamKind::String => "string",
+            ParamKind::Date => "date",
+            ParamKind::Time => "time",
+            ParamKind::Timestamp => "timestamp",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ParamSpec {
     pub key: &'static str,
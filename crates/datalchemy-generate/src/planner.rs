@@ -51,13 +51,33 @@ pub fn plan_tables(
         }
     }
 
-    let order = datalchemy_core::build_fk_graph_report(schema)
-        .topo_order
-        .ok_or_else(|| GenerationError::Unsupported("cyclic FK graph".to_string()))?;
+    let routed = route_to_leaf_partitions(schema, &mut rows_by_table);
 
+    let order = datalchemy_core::insert_order(schema).map_err(|cycles| {
+        let cycles: Vec<String> = cycles
+            .iter()
+            .map(|cycle| cycle.tables.join(" <-> "))
+            .collect();
+        GenerationError::Unsupported(format!("cyclic FK graph: {}", cycles.join("; ")))
+    })?;
+
+    // Leaves take the insert position of their partitioned root, so tables
+    // referencing the root come after every leaf.
     let mut tasks = Vec::new();
+    let leaves: BTreeSet<&String> = routed.values().flatten().collect();
+    let mut planned = BTreeSet::new();
     for key in order {
-        if let Some(rows) = rows_by_table.get(&key) {
+        if leaves.contains(&key) {
+            continue;
+        }
+        let keys = routed.get(&key).cloned().unwrap_or_else(|| vec![key]);
+        for key in keys {
+            let Some(rows) = rows_by_table.get(&key) else {
+                continue;
+            };
+            if !planned.insert(key.clone()) {
+                continue;
+            }
             let (schema_name, table_name) = split_key(&key)?;
             tasks.push(GenerationTask {
                 schema: schema_name.to_string(),
@@ -76,6 +96,39 @@ pub fn plan_tables(
     Ok(tasks)
 }
 
+/// Replace partitioned tables by their leaf partitions, splitting the rows
+/// evenly (the first leaves take the remainder). Returns the leaves per
+/// partitioned table.
+fn route_to_leaf_partitions(
+    schema: &DatabaseSchema,
+    rows_by_table: &mut HashMap<String, u64>,
+) -> HashMap<String, Vec<String>> {
+    let mut routed = HashMap::new();
+    let mut keys: Vec<String> = rows_by_table.keys().cloned().collect();
+    keys.sort();
+    for key in keys {
+        let Ok((schema_name, table_name)) = split_key(&key) else {
+            continue;
+        };
+        let leaves = schema.leaf_partitions(schema_name, table_name);
+        if leaves.is_empty() {
+            continue;
+        }
+        let rows = rows_by_table.remove(&key).unwrap_or_default();
+        let count = leaves.len() as u64;
+        for (index, leaf) in leaves.iter().enumerate() {
+            let share = rows / count + u64::from((index as u64) < rows % count);
+            if share == 0 {
+                continue;
+            }
+            let entry = rows_by_table.entry(leaf.clone()).or_insert(0);
+            *entry = (*entry).max(share);
+        }
+        routed.insert(key, leaves);
+    }
+    routed
+}
+
 fn build_parent_map(schema: &DatabaseSchema) -> HashMap<String, BTreeSet<String>> {
     let mut parents: HashMap<String, BTreeSet<String>> = HashMap::new();
 
@@ -86,6 +139,13 @@ fn build_parent_map(schema: &DatabaseSchema) -> HashMap<String, BTreeSet<String>
 
             for constraint in &table.constraints {
                 if let Constraint::ForeignKey(fk) = constraint {
+                    // Tables left out of the snapshot cannot be generated.
+                    if schema.external_tables.iter().any(|external| {
+                        external.schema == fk.referenced_schema
+                            && external.table == fk.referenced_table
+                    }) {
+                        continue;
+                    }
                     let parent_key = table_key(&fk.referenced_schema, &fk.referenced_table);
                     parents
                         .entry(child_key.clone())
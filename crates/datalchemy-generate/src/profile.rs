@@ -0,0 +1,264 @@
+//! Value profiles sampled from a source database (`profile.json`).
+//!
+//! A [`ValueProfile`] (written by `datalchemy introspect --profile`) holds,
+//! per column and over a random sample of rows, the null fraction, the
+//! smallest and largest values (numbers, dates, times and timestamps) and the
+//! most frequent values (none when no value repeats, e.g. keys and free
+//! text). Columns whose name implies PII are never read: they are listed as
+//! `redacted_columns`.
+//!
+//! With [`GenerationEngine::with_profile`](crate::GenerationEngine::with_profile),
+//! columns without a plan rule, default, foreign key or unique constraint are
+//! generated from their profile: NULL with the sampled null fraction, then
+//! categories with the sampled frequencies when the top values cover the
+//! whole sample, else uniformly between min and max.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use chrono::Duration;
+use datalchemy_core::{Column, RedactedConnection};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::engine::column_pii_tags;
+use crate::generators::GeneratedValue;
+use crate::mask::source_value;
+
+/// Most frequent values kept per column.
+pub const DEFAULT_TOP_VALUES: usize = 20;
+
+/// Sampled value distributions of the source tables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValueProfile {
+    /// Connection the sample was read from, with secrets redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<RedactedConnection>,
+    /// Rows sampled per table.
+    pub sample_rows: u64,
+    pub tables: Vec<TableProfile>,
+}
+
+/// Profiles of the sampled columns of one table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableProfile {
+    pub schema: String,
+    pub table: String,
+    pub sampled_rows: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnProfile>,
+    /// Columns not read because their name implies PII.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted_columns: Vec<String>,
+}
+
+/// Distribution of one column in the sampled rows. Values are kept in their
+/// JSON form (`row_to_json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    pub column: String,
+    /// Non-null values sampled.
+    pub values: u64,
+    pub null_frac: f64,
+    /// Distinct non-null values sampled.
+    pub distinct: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<Value>,
+    /// Most frequent values, by count then first seen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_values: Vec<ValueCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueCount {
+    pub value: Value,
+    pub count: u64,
+}
+
+impl ValueProfile {
+    pub fn table(&self, schema: &str, table: &str) -> Option<&TableProfile> {
+        self.tables
+            .iter()
+            .find(|profile| profile.schema == schema && profile.table == table)
+    }
+}
+
+impl TableProfile {
+    pub fn column(&self, column: &str) -> Option<&ColumnProfile> {
+        self.columns
+            .iter()
+            .find(|profile| profile.column.eq_ignore_ascii_case(column))
+    }
+}
+
+impl ColumnProfile {
+    /// Profile of `column` from its sampled values (`Value::Null` for NULL),
+    /// keeping the `top` most frequent ones.
+    pub fn from_values(column: &Column, values: &[Value], top: usize) -> ColumnProfile {
+        let mut counts: Vec<ValueCount> = Vec::new();
+        let mut index: BTreeMap<String, usize> = BTreeMap::new();
+        let mut min: Option<(GeneratedValue, &Value)> = None;
+        let mut max: Option<(GeneratedValue, &Value)> = None;
+        let mut non_null = 0;
+        for value in values.iter().filter(|value| !value.is_null()) {
+            non_null += 1;
+            let position = *index.entry(value.to_string()).or_insert_with(|| {
+                counts.push(ValueCount {
+                    value: value.clone(),
+                    count: 0,
+                });
+                counts.len() - 1
+            });
+            counts[position].count += 1;
+
+            let typed = source_value(column, value);
+            if !is_orderable(&typed) {
+                continue;
+            }
+            if min
+                .as_ref()
+                .is_none_or(|(low, _)| compare(&typed, low) == Some(Ordering::Less))
+            {
+                min = Some((typed.clone(), value));
+            }
+            if max
+                .as_ref()
+                .is_none_or(|(high, _)| compare(&typed, high) == Some(Ordering::Greater))
+            {
+                max = Some((typed, value));
+            }
+        }
+
+        let distinct = counts.len() as u64;
+        if distinct == non_null {
+            // No value repeats (keys, free text): nothing frequent to keep.
+            counts.clear();
+        }
+        // Stable: equal counts keep the order they were first seen in.
+        counts.sort_by_key(|value| std::cmp::Reverse(value.count));
+        counts.truncate(top);
+        ColumnProfile {
+            column: column.name.clone(),
+            values: non_null,
+            null_frac: if values.is_empty() {
+                0.0
+            } else {
+                let nulls = values.len() as u64 - non_null;
+                (nulls as f64 / values.len() as f64 * 10_000.0).round() / 10_000.0
+            },
+            distinct,
+            min: min.map(|(_, value)| value.clone()),
+            max: max.map(|(_, value)| value.clone()),
+            top_values: counts,
+        }
+    }
+
+    /// Whether the top values are every value sampled, so they can be drawn
+    /// as categories.
+    pub fn is_categorical(&self) -> bool {
+        !self.top_values.is_empty() && self.top_values.len() as u64 == self.distinct
+    }
+
+    /// A value for `column` following the profile: NULL with `null_frac`
+    /// (nullable columns), else a category weighted by its count or a value
+    /// between min and max. `None` when the profile has neither.
+    pub fn generate(&self, column: &Column, rng: &mut dyn rand::RngCore) -> Option<GeneratedValue> {
+        if column.is_nullable && self.null_frac > 0.0 && rng.random_bool(self.null_frac.min(1.0)) {
+            return Some(GeneratedValue::Null);
+        }
+        if self.is_categorical() {
+            let total: u64 = self.top_values.iter().map(|value| value.count).sum();
+            let mut pick = rng.random_range(0..total);
+            for value in &self.top_values {
+                if pick < value.count {
+                    return Some(source_value(column, &value.value));
+                }
+                pick -= value.count;
+            }
+            return None;
+        }
+        let low = source_value(column, self.min.as_ref()?);
+        let high = source_value(column, self.max.as_ref()?);
+        between(&low, &high, column, rng)
+    }
+}
+
+/// Whether a column is left out of profiles (its name implies PII).
+pub fn is_redacted(column: &str) -> bool {
+    !column_pii_tags(column).is_empty()
+}
+
+fn is_orderable(value: &GeneratedValue) -> bool {
+    matches!(
+        value,
+        GeneratedValue::Int(_)
+            | GeneratedValue::Float(_)
+            | GeneratedValue::Date(_)
+            | GeneratedValue::Time(_)
+            | GeneratedValue::Timestamp(_)
+    )
+}
+
+fn compare(left: &GeneratedValue, right: &GeneratedValue) -> Option<Ordering> {
+    match (left, right) {
+        (GeneratedValue::Int(left), GeneratedValue::Int(right)) => Some(left.cmp(right)),
+        (
+            GeneratedValue::Int(_) | GeneratedValue::Float(_),
+            GeneratedValue::Int(_) | GeneratedValue::Float(_),
+        ) => left.as_f64()?.partial_cmp(&right.as_f64()?),
+        (GeneratedValue::Date(left), GeneratedValue::Date(right)) => Some(left.cmp(right)),
+        (GeneratedValue::Time(left), GeneratedValue::Time(right)) => Some(left.cmp(right)),
+        (GeneratedValue::Timestamp(left), GeneratedValue::Timestamp(right)) => {
+            Some(left.cmp(right))
+        }
+        _ => None,
+    }
+}
+
+/// Uniform value in `low..=high`; floats keep the column's numeric scale.
+fn between(
+    low: &GeneratedValue,
+    high: &GeneratedValue,
+    column: &Column,
+    rng: &mut dyn rand::RngCore,
+) -> Option<GeneratedValue> {
+    if compare(low, high)? == Ordering::Greater {
+        return None;
+    }
+    Some(match (low, high) {
+        (GeneratedValue::Int(low), GeneratedValue::Int(high)) => {
+            GeneratedValue::Int(rng.random_range(*low..=*high))
+        }
+        (GeneratedValue::Date(low), GeneratedValue::Date(high)) => {
+            let days = (*high - *low).num_days();
+            GeneratedValue::Date(*low + Duration::days(rng.random_range(0..=days)))
+        }
+        (GeneratedValue::Time(low), GeneratedValue::Time(high)) => {
+            let seconds = (*high - *low).num_seconds();
+            GeneratedValue::Time(*low + Duration::seconds(rng.random_range(0..=seconds)))
+        }
+        (GeneratedValue::Timestamp(low), GeneratedValue::Timestamp(high)) => {
+            let seconds = (*high - *low).num_seconds();
+            GeneratedValue::Timestamp(*low + Duration::seconds(rng.random_range(0..=seconds)))
+        }
+        _ => {
+            let (low, high) = (low.as_f64()?, high.as_f64()?);
+            let value = if low < high {
+                rng.random_range(low..=high)
+            } else {
+                low
+            };
+            let value = match column.column_type.numeric_scale {
+                Some(scale) => {
+                    let factor = 10f64.powi(scale);
+                    (value * factor).round() / factor
+                }
+                None => value,
+            };
+            GeneratedValue::Float(value.clamp(low, high))
+        }
+    })
+}
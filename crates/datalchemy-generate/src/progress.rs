@@ -0,0 +1,145 @@
+//! Progress reporting and cancellation for long generation runs.
+//!
+//! [`GenerationEngine::with_progress`](crate::GenerationEngine::with_progress)
+//! receives a [`GenerationProgress`] per planned run, table start, batch of rows
+//! and table end; [`GenerationEngine::with_cancel`](crate::GenerationEngine::with_cancel)
+//! stops the run between rows with [`GenerationError::Cancelled`](crate::GenerationError::Cancelled).
+//! [`GenerationEngine::with_observer`](crate::GenerationEngine::with_observer)
+//! forwards tables, rows, report issues and the run summary to an
+//! [`EngineObserver`].
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use datalchemy_core::{EngineIssue, EngineObserver, IssueLevel, RunOutcome, RunSummary, Stage};
+
+use crate::model::{GenerationIssue, GenerationReport};
+
+/// Row progress is reported about this many times per table.
+const ROW_EVENTS_PER_TABLE: u64 = 100;
+
+/// A table planned for generation, in generation order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedTable {
+    pub schema: String,
+    pub table: String,
+    pub rows: u64,
+}
+
+/// Event reported while a run progresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationProgress {
+    /// Tables of the run (including auto-generated parents), before the first.
+    Planned(Vec<PlannedTable>),
+    TableStarted {
+        schema: String,
+        table: String,
+    },
+    /// Rows accepted so far for the table (restarts from zero on a table retry).
+    Rows {
+        schema: String,
+        table: String,
+        rows: u64,
+    },
+    TableFinished {
+        schema: String,
+        table: String,
+        rows: u64,
+        duration_ms: u64,
+    },
+}
+
+pub(crate) type ProgressFn = Arc<dyn Fn(GenerationProgress) + Send + Sync>;
+
+/// Progress callback, cancel flag and observer of an engine.
+#[derive(Clone, Default)]
+pub(crate) struct RunHooks {
+    pub(crate) progress: Option<ProgressFn>,
+    pub(crate) cancel: Option<Arc<AtomicBool>>,
+    pub(crate) observer: Option<Arc<dyn EngineObserver>>,
+}
+
+impl RunHooks {
+    pub(crate) fn emit(&self, event: impl FnOnce() -> GenerationProgress) {
+        if let Some(progress) = &self.progress {
+            progress(event());
+        }
+    }
+
+    pub(crate) fn observe(&self, event: impl FnOnce(&dyn EngineObserver)) {
+        if let Some(observer) = &self.observer {
+            event(observer.as_ref());
+        }
+    }
+
+    /// Hand the report issues recorded since the last call to the observer;
+    /// `seen` counts the warnings and unsupported entries already handed out.
+    pub(crate) fn observe_issues(&self, report: &GenerationReport, seen: &mut (usize, usize)) {
+        self.observe(|observer| {
+            let warnings = report.warnings.iter().skip(seen.0);
+            for issue in warnings.chain(report.unsupported.iter().skip(seen.1)) {
+                observer.on_issue(&engine_issue(issue));
+            }
+        });
+        *seen = (report.warnings.len(), report.unsupported.len());
+    }
+
+    pub(crate) fn observe_finish(&self, report: &GenerationReport, outcome: RunOutcome) {
+        self.observe(|observer| {
+            let issues = report.warnings.iter().chain(&report.unsupported);
+            let errors = issues
+                .filter(|issue| issue_level(issue) == IssueLevel::Error)
+                .count() as u64;
+            observer.on_finish(&RunSummary {
+                stage: Stage::Generate,
+                run_id: report.run_id.clone(),
+                outcome,
+                tables: report.tables.len() as u64,
+                rows: report.tables.iter().map(|table| table.rows_generated).sum(),
+                warnings: (report.warnings.len() + report.unsupported.len()) as u64 - errors,
+                errors,
+                duration_ms: report.duration_ms,
+            });
+        });
+    }
+
+    pub(crate) fn cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    /// Whether `rows` accepted rows of a `total` row table is worth an event.
+    pub(crate) fn reports_rows(&self, rows: u64, total: u64) -> bool {
+        self.progress.is_some()
+            && (rows == total || rows.is_multiple_of((total / ROW_EVENTS_PER_TABLE).max(1)))
+    }
+}
+
+impl fmt::Debug for RunHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunHooks")
+            .field("progress", &self.progress.is_some())
+            .field("cancel", &self.cancel)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+fn issue_level(issue: &GenerationIssue) -> IssueLevel {
+    match issue.level.as_str() {
+        "error" => IssueLevel::Error,
+        _ => IssueLevel::Warning,
+    }
+}
+
+fn engine_issue(issue: &GenerationIssue) -> EngineIssue {
+    EngineIssue {
+        stage: Stage::Generate,
+        level: issue_level(issue),
+        code: issue.code.clone(),
+        path: issue.path.clone(),
+        message: issue.message.clone(),
+    }
+}
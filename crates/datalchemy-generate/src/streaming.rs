@@ -0,0 +1,137 @@
+//! Async row stream (behind the `streaming` feature).
+//!
+//! [`GenerationEngine::stream`] runs generation on a background thread and
+//! yields one [`RowBatch`] per table, in FK-safe insert order, so library
+//! consumers can pipe rows into their own sinks without any file being
+//! written. The stream works with any executor; a table is produced ahead of
+//! the consumer at most.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use datalchemy_core::{Column, DatabaseSchema, Table};
+use datalchemy_plan::Plan;
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+
+use crate::engine::GenerationEngine;
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::model::GenerationReport;
+use crate::output::{TableOutput, TableSink, ordered_columns};
+
+/// Generated rows of one table; values follow `columns`.
+#[derive(Debug, Clone)]
+pub struct RowBatch {
+    pub schema: String,
+    pub table: String,
+    /// Columns in table (ordinal) order.
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<GeneratedValue>>,
+}
+
+/// Stream of the tables of a run, see [`GenerationEngine::stream`].
+///
+/// Dropping it stops generation at the next table.
+#[derive(Debug)]
+pub struct RowStream {
+    rx: mpsc::Receiver<Message>,
+    result: Option<Result<GenerationReport, GenerationError>>,
+}
+
+#[derive(Debug)]
+enum Message {
+    Batch(RowBatch),
+    Done(Box<Result<GenerationReport, GenerationError>>),
+}
+
+impl RowStream {
+    /// Outcome of the run once the stream has ended: the report, or the error
+    /// that cut the stream short. `None` before the end (and after the first
+    /// call).
+    pub fn take_result(&mut self) -> Option<Result<GenerationReport, GenerationError>> {
+        self.result.take()
+    }
+}
+
+impl Stream for RowStream {
+    type Item = RowBatch;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<RowBatch>> {
+        loop {
+            match ready!(self.rx.poll_recv(cx)) {
+                Some(Message::Batch(batch)) => return Poll::Ready(Some(batch)),
+                // The worker drops its sender right after.
+                Some(Message::Done(result)) => self.result = Some(*result),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl GenerationEngine {
+    /// Generate `plan` on a background thread, yielding every table as a
+    /// [`RowBatch`] as soon as it is ready. No run directory is created; rows
+    /// are identical to those of a full run with the same plan.
+    pub fn stream(&self, schema: &DatabaseSchema, plan: &Plan) -> RowStream {
+        let (tx, rx) = mpsc::channel(1);
+        let engine = self.clone();
+        let schema = schema.clone();
+        let plan = plan.clone();
+        std::thread::spawn(move || {
+            let mut sink = ChannelSink { tx: tx.clone() };
+            let result = engine.generate_to_sink(&schema, &plan, &mut sink);
+            let _ = tx.blocking_send(Message::Done(Box::new(result)));
+        });
+        RowStream { rx, result: None }
+    }
+}
+
+/// Sends every table to the stream.
+struct ChannelSink {
+    tx: mpsc::Sender<Message>,
+}
+
+impl TableSink for ChannelSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let columns: Vec<Column> = ordered_columns(table).into_iter().cloned().collect();
+        let output = TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: columns.iter().map(|column| column.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        };
+        let rows = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        row.get(&column.name.to_lowercase())
+                            .cloned()
+                            .unwrap_or(GeneratedValue::Null)
+                    })
+                    .collect()
+            })
+            .collect();
+        let batch = RowBatch {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            columns,
+            rows,
+        };
+        if self.tx.blocking_send(Message::Batch(batch)).is_err() {
+            // The stream was dropped.
+            return Err(GenerationError::Cancelled);
+        }
+        Ok(output)
+    }
+}
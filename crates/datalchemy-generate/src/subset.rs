@@ -0,0 +1,346 @@
+//! Referentially intact slices of real data (`datalchemy subset`).
+//!
+//! [`RowSubset::extract`] starts from seed rows of one table and follows
+//! foreign keys through a [`SubsetSource`]: up to every parent row the slice
+//! references, and (with `include_children`) down to the rows referencing
+//! the slice, at most `max_rows_per_table` per table. Parents are never
+//! capped, so every foreign key of the slice resolves. The tables are those of
+//! [`DatabaseSchema::subset`].
+//!
+//! A [`RowSubset`] is a [`RowSource`]: [`RowSubset::plan`] targets its tables
+//! with their row counts and [`MaskingEngine`](crate::MaskingEngine) writes
+//! it, masked with the transforms of a plan or copied as read.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use datalchemy_core::{Constraint, DatabaseSchema, ForeignKey, SubsetOptions, Table};
+use datalchemy_plan::{PLAN_VERSION, Plan, Rule, SchemaRef, Target};
+
+use crate::errors::GenerationError;
+use crate::generators::GeneratedValue;
+use crate::mask::RowSource;
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Real rows looked up by filter or key, e.g. a database connection.
+pub trait SubsetSource {
+    /// Up to `limit` rows of `schema.table` matching `filter` (a SQL
+    /// condition; every row when `None`).
+    fn seed_rows(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        filter: Option<&str>,
+        limit: u64,
+    ) -> Result<Vec<Row>, GenerationError>;
+
+    /// Rows of `schema.table` whose `columns`, as text, equal one of `keys`
+    /// (values in [`GeneratedValue::to_csv`] form); at most `limit` when set.
+    fn rows_by_key(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        columns: &[String],
+        keys: &[Vec<String>],
+        limit: Option<u64>,
+    ) -> Result<Vec<Row>, GenerationError>;
+}
+
+/// Where a subset starts and how far it grows.
+#[derive(Debug, Clone)]
+pub struct RowSubsetOptions {
+    /// Seed table, `schema.table` (or `table` when the name is unique).
+    pub seed: String,
+    /// SQL condition on the seed rows.
+    pub filter: Option<String>,
+    /// Seed rows to start from.
+    pub seed_rows: u64,
+    /// Cap of rows per table reached through child edges.
+    pub max_rows_per_table: u64,
+    /// Follow foreign keys down to the rows that reference the slice.
+    pub include_children: bool,
+}
+
+impl Default for RowSubsetOptions {
+    fn default() -> Self {
+        Self {
+            seed: String::new(),
+            filter: None,
+            seed_rows: 100,
+            max_rows_per_table: 1_000,
+            include_children: true,
+        }
+    }
+}
+
+/// Rows of a referentially intact slice, per `schema.table`.
+#[derive(Debug, Clone)]
+pub struct RowSubset {
+    /// The tables of the slice.
+    pub schema: DatabaseSchema,
+    pub tables: BTreeMap<String, Vec<Row>>,
+}
+
+impl RowSubset {
+    /// Read the slice of `schema` described by `options` from `source`.
+    pub fn extract(
+        schema: &DatabaseSchema,
+        options: &RowSubsetOptions,
+        source: &mut dyn SubsetSource,
+    ) -> Result<Self, GenerationError> {
+        let slice = schema
+            .subset(
+                &[options.seed.as_str()],
+                SubsetOptions {
+                    include_children: options.include_children,
+                },
+            )
+            .map_err(|err| GenerationError::InvalidPlan(err.to_string()))?;
+        let mut tables: HashMap<String, (&str, &Table)> = HashMap::new();
+        let mut foreign_keys: Vec<(String, &ForeignKey)> = Vec::new();
+        for db_schema in &slice.schemas {
+            for table in &db_schema.tables {
+                let key = format!("{}.{}", db_schema.name, table.name);
+                for constraint in &table.constraints {
+                    if let Constraint::ForeignKey(fk) = constraint {
+                        foreign_keys.push((key.clone(), fk));
+                    }
+                }
+                tables.insert(key, (db_schema.name.as_str(), table));
+            }
+        }
+        let seed = if tables.contains_key(&options.seed) {
+            options.seed.clone()
+        } else {
+            tables
+                .keys()
+                .find(|key| {
+                    key.rsplit_once('.')
+                        .is_some_and(|(_, name)| name == options.seed)
+                })
+                .cloned()
+                .unwrap_or_default()
+        };
+        let (seed_schema, seed_table) = tables[&seed];
+
+        let mut state = Collected::default();
+        let rows = source
+            .seed_rows(
+                seed_schema,
+                seed_table,
+                options.filter.as_deref(),
+                options.seed_rows,
+            )
+            .map_err(|err| err.in_table(seed_schema, &seed_table.name))?;
+        state.add(&seed, seed_table, rows, true);
+
+        while let Some((key, rows, follow_children)) = state.queue.pop_front() {
+            let (_, table) = tables[&key];
+            for (child, fk) in &foreign_keys {
+                let parent = format!("{}.{}", fk.referenced_schema, fk.referenced_table);
+                if *child == key {
+                    let (parent_schema, parent_table) = tables[&parent];
+                    let present = state.keys(&parent, parent_table, &fk.referenced_columns);
+                    let keys: Vec<Vec<String>> = key_values(table, &rows, &fk.columns)
+                        .into_iter()
+                        .filter(|values| !present.contains(values))
+                        .collect();
+                    if !keys.is_empty() {
+                        let found = source
+                            .rows_by_key(
+                                parent_schema,
+                                parent_table,
+                                &fk.referenced_columns,
+                                &keys,
+                                None,
+                            )
+                            .map_err(|err| err.in_table(parent_schema, &parent_table.name))?;
+                        state.add(&parent, parent_table, found, false);
+                    }
+                }
+                if follow_children && options.include_children && parent == key {
+                    let (child_schema, child_table) = tables[child];
+                    let room = options
+                        .max_rows_per_table
+                        .saturating_sub(state.rows(child) as u64);
+                    let keys = key_values(table, &rows, &fk.referenced_columns);
+                    if room > 0 && !keys.is_empty() {
+                        let found = source
+                            .rows_by_key(child_schema, child_table, &fk.columns, &keys, Some(room))
+                            .map_err(|err| err.in_table(child_schema, &child_table.name))?;
+                        state.add(child, child_table, found, true);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            schema: slice,
+            tables: state.tables,
+        })
+    }
+
+    /// Rows read for `schema.table`.
+    pub fn rows(&self, schema: &str, table: &str) -> &[Row] {
+        self.tables
+            .get(&format!("{schema}.{table}"))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Plan targeting every table of the slice with its row count. The seed,
+    /// global settings, options and column rules of `base` (rules of tables
+    /// outside the slice dropped) apply when given.
+    pub fn plan(&self, base: Option<&Plan>) -> Plan {
+        let targets = self
+            .schema
+            .schemas
+            .iter()
+            .flat_map(|db_schema| {
+                db_schema
+                    .tables
+                    .iter()
+                    .filter(|table| table.kind.is_insertable())
+                    .map(|table| Target {
+                        schema: db_schema.name.clone(),
+                        table: table.name.clone(),
+                        rows: self.rows(&db_schema.name, &table.name).len() as u64,
+                        strategy: None,
+                    })
+            })
+            .filter(|target| target.rows > 0)
+            .collect();
+        let rules = base
+            .map(|plan| {
+                plan.rules
+                    .iter()
+                    .filter(|rule| match rule {
+                        Rule::ColumnGenerator(rule) => self
+                            .tables
+                            .contains_key(&format!("{}.{}", rule.schema, rule.table)),
+                        _ => false,
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        Plan {
+            plan_version: PLAN_VERSION.to_string(),
+            seed: base.map(|plan| plan.seed).unwrap_or_default(),
+            schema_ref: SchemaRef {
+                schema_version: self.schema.schema_version.clone(),
+                schema_fingerprint: None,
+                engine: self.schema.engine.clone(),
+            },
+            global: base.and_then(|plan| plan.global.clone()),
+            targets,
+            rules,
+            rules_unsupported: Vec::new(),
+            assertions: Vec::new(),
+            options: base.and_then(|plan| plan.options.clone()),
+        }
+    }
+}
+
+impl RowSource for RowSubset {
+    fn read_table(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        limit: u64,
+    ) -> Result<Vec<Row>, GenerationError> {
+        Ok(self
+            .rows(schema, &table.name)
+            .iter()
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Rows collected so far and the batches still to follow.
+#[derive(Default)]
+struct Collected {
+    tables: BTreeMap<String, Vec<Row>>,
+    seen: HashMap<String, HashSet<String>>,
+    queue: VecDeque<(String, Vec<Row>, bool)>,
+}
+
+impl Collected {
+    /// Keep the rows of `key` not collected yet and queue them.
+    fn add(&mut self, key: &str, table: &Table, rows: Vec<Row>, follow_children: bool) {
+        let seen = self.seen.entry(key.to_string()).or_default();
+        let rows: Vec<Row> = rows
+            .into_iter()
+            .filter(|row| seen.insert(row_identity(table, row)))
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        self.tables
+            .entry(key.to_string())
+            .or_default()
+            .extend(rows.iter().cloned());
+        self.queue
+            .push_back((key.to_string(), rows, follow_children));
+    }
+
+    fn rows(&self, key: &str) -> usize {
+        self.tables.get(key).map(Vec::len).unwrap_or_default()
+    }
+
+    /// Values of `columns` in the collected rows of `key`.
+    fn keys(&self, key: &str, table: &Table, columns: &[String]) -> HashSet<Vec<String>> {
+        key_values(
+            table,
+            self.tables.get(key).map(Vec::as_slice).unwrap_or_default(),
+            columns,
+        )
+        .into_iter()
+        .collect()
+    }
+}
+
+/// Distinct values of `columns` in `rows`, skipping keys with a null.
+fn key_values(table: &Table, rows: &[Row], columns: &[String]) -> Vec<Vec<String>> {
+    let mut seen = HashSet::new();
+    rows.iter()
+        .filter_map(|row| {
+            columns
+                .iter()
+                .map(|name| {
+                    let value = row.get(&name.to_lowercase())?;
+                    if value.is_null() {
+                        return None;
+                    }
+                    let column = table.columns.iter().find(|column| &column.name == name)?;
+                    Some(value.to_csv(column))
+                })
+                .collect::<Option<Vec<String>>>()
+        })
+        .filter(|values| seen.insert(values.clone()))
+        .collect()
+}
+
+/// Primary key values of `row`, or every value without a primary key.
+fn row_identity(table: &Table, row: &Row) -> String {
+    let primary_key = table
+        .constraints
+        .iter()
+        .find_map(|constraint| match constraint {
+            Constraint::PrimaryKey(pk) => Some(pk.columns.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            table
+                .columns
+                .iter()
+                .map(|column| column.name.clone())
+                .collect()
+        });
+    primary_key
+        .iter()
+        .map(|column| format!("{:?}", row.get(&column.to_lowercase())))
+        .collect::<Vec<_>>()
+        .join("\u{1f}")
+}
@@ -0,0 +1,220 @@
+//! Upload of generated artifacts to object stores (behind the `object-store` feature).
+//!
+//! Targets are URLs such as `s3://bucket/prefix`, `gs://bucket/prefix` or
+//! `az://container/prefix`. Credentials come from the standard environment
+//! variables or an explicit map (see [`build_store`]) and are never written to
+//! artifacts or logs. Files larger than
+//! [`MULTIPART_THRESHOLD`] are sent with multipart upload, which is aborted
+//! when a part or its completion fails.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey};
+use object_store::azure::{AzureConfigKey, MicrosoftAzureBuilder};
+use object_store::gcp::{GoogleCloudStorageBuilder, GoogleConfigKey};
+use object_store::path::Path as ObjectPath;
+use object_store::{MultipartUpload, ObjectStore, PutPayload};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncReadExt;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+use crate::errors::GenerationError;
+use crate::manifest::{collect_files, relative_path};
+use crate::model::ObjectStoreTarget;
+
+/// Files at or above this size use multipart upload.
+pub const MULTIPART_THRESHOLD: u64 = 16 * 1024 * 1024;
+/// Part size for multipart uploads (S3 requires at least 5 MiB).
+pub const MULTIPART_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const MULTIPART_CONCURRENCY: usize = 4;
+
+/// Object written by an upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedObject {
+    /// Object key inside the bucket.
+    pub key: String,
+    pub bytes: u64,
+    pub multipart: bool,
+}
+
+/// Build the store client for `target`, selected by URL scheme.
+///
+/// `credentials` holds environment-style keys (`AWS_SECRET_ACCESS_KEY`,
+/// `GOOGLE_SERVICE_ACCOUNT_KEY`, `AZURE_STORAGE_ACCOUNT_KEY`, ...) resolved by the
+/// caller, e.g. from the secrets vault. They override the process environment;
+/// keys unknown to the selected backend are ignored.
+pub fn build_store(
+    target: &ObjectStoreTarget,
+    credentials: &BTreeMap<String, String>,
+) -> Result<Arc<dyn ObjectStore>, GenerationError> {
+    let (scheme, _, _) = target.parts()?;
+    let config = credentials
+        .iter()
+        .map(|(key, value)| (key.to_ascii_lowercase(), value));
+    match scheme {
+        "s3" | "s3a" => {
+            let mut builder = AmazonS3Builder::from_env()
+                .with_url(&target.url)
+                .with_allow_http(target.allow_http);
+            for (key, value) in config {
+                if let Ok(key) = key.parse::<AmazonS3ConfigKey>() {
+                    builder = builder.with_config(key, value);
+                }
+            }
+            if let Some(endpoint) = &target.endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+            if let Some(region) = &target.region {
+                builder = builder.with_region(region);
+            }
+            Ok(Arc::new(builder.build()?))
+        }
+        "gs" => {
+            let mut builder = GoogleCloudStorageBuilder::from_env().with_url(&target.url);
+            for (key, value) in config {
+                if let Ok(key) = key.parse::<GoogleConfigKey>() {
+                    builder = builder.with_config(key, value);
+                }
+            }
+            Ok(Arc::new(builder.build()?))
+        }
+        "az" | "azure" | "abfs" | "abfss" => {
+            let mut builder = MicrosoftAzureBuilder::from_env().with_url(&target.url);
+            for (key, value) in config {
+                if let Ok(key) = key.parse::<AzureConfigKey>() {
+                    builder = builder.with_config(key, value);
+                }
+            }
+            if let Some(endpoint) = &target.endpoint {
+                builder = builder.with_endpoint(endpoint.clone());
+            }
+            if target.allow_http {
+                builder = builder.with_allow_http(true);
+            }
+            Ok(Arc::new(builder.build()?))
+        }
+        other => Err(GenerationError::Unsupported(format!(
+            "object store scheme '{other}' is not supported (use s3://, gs:// or az://)"
+        ))),
+    }
+}
+
+/// Upload every file under `dir` to `target`, below `<prefix>/<name>/`.
+pub async fn upload_dir(
+    dir: &Path,
+    target: &ObjectStoreTarget,
+    credentials: &BTreeMap<String, String>,
+    name: &str,
+) -> Result<Vec<UploadedObject>, GenerationError> {
+    let store = build_store(target, credentials)?;
+    let (_, _, prefix) = target.parts()?;
+    let key_prefix = if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}/{name}")
+    };
+    upload_dir_to(store.as_ref(), dir, &key_prefix).await
+}
+
+/// Upload every file under `dir` into `store`, keys relative to `key_prefix`.
+pub async fn upload_dir_to(
+    store: &dyn ObjectStore,
+    dir: &Path,
+    key_prefix: &str,
+) -> Result<Vec<UploadedObject>, GenerationError> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+
+    let mut uploaded = Vec::with_capacity(files.len());
+    for file in files {
+        let relative = relative_path(dir, &file)?;
+        let key = if key_prefix.is_empty() {
+            relative
+        } else {
+            format!("{key_prefix}/{relative}")
+        };
+        let object = upload_file(store, &file, &key).await?;
+        info!(
+            key = %object.key,
+            bytes = object.bytes,
+            multipart = object.multipart,
+            "artifact uploaded"
+        );
+        uploaded.push(object);
+    }
+    Ok(uploaded)
+}
+
+async fn upload_file(
+    store: &dyn ObjectStore,
+    file: &Path,
+    key: &str,
+) -> Result<UploadedObject, GenerationError> {
+    let location = ObjectPath::from(key);
+    let bytes = tokio::fs::metadata(file).await?.len();
+
+    if bytes < MULTIPART_THRESHOLD {
+        let payload = PutPayload::from(tokio::fs::read(file).await?);
+        store.put(&location, payload).await?;
+        return Ok(UploadedObject {
+            key: key.to_string(),
+            bytes,
+            multipart: false,
+        });
+    }
+
+    let mut reader = tokio::fs::File::open(file).await?;
+    let mut upload = store.put_multipart(&location).await?;
+    if let Err(err) = upload_parts(upload.as_mut(), &mut reader).await {
+        // Drop the parts already stored; the upload error is the one reported.
+        if let Err(abort_err) = upload.abort().await {
+            warn!(key, error = %abort_err, "failed to abort multipart upload");
+        }
+        return Err(err);
+    }
+
+    Ok(UploadedObject {
+        key: key.to_string(),
+        bytes,
+        multipart: true,
+    })
+}
+
+/// Send `reader` in parts of [`MULTIPART_CHUNK_SIZE`] bytes, at most
+/// `MULTIPART_CONCURRENCY` at a time, and complete the upload.
+async fn upload_parts(
+    upload: &mut dyn MultipartUpload,
+    reader: &mut tokio::fs::File,
+) -> Result<(), GenerationError> {
+    let mut parts = JoinSet::new();
+    loop {
+        let mut chunk = Vec::with_capacity(MULTIPART_CHUNK_SIZE);
+        (&mut *reader)
+            .take(MULTIPART_CHUNK_SIZE as u64)
+            .read_to_end(&mut chunk)
+            .await?;
+        if chunk.is_empty() {
+            break;
+        }
+        while parts.len() >= MULTIPART_CONCURRENCY {
+            join_part(&mut parts).await?;
+        }
+        parts.spawn(upload.put_part(PutPayload::from(chunk)));
+    }
+    while !parts.is_empty() {
+        join_part(&mut parts).await?;
+    }
+    upload.complete().await?;
+    Ok(())
+}
+
+async fn join_part(parts: &mut JoinSet<object_store::Result<()>>) -> Result<(), GenerationError> {
+    if let Some(joined) = parts.join_next().await {
+        joined.map_err(std::io::Error::other)??;
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::temp_out_path;
+use datalchemy_core::validate_schema;
+use datalchemy_generate::OutputFormat;
+use datalchemy_generate::bench::{BenchOptions, bench_schema, run_bench};
+
+#[test]
+fn bench_reports_throughput_per_format_and_thread_count() {
+    validate_schema(&bench_schema()).expect("valid bench schema");
+    let out_dir = temp_out_path("bench");
+    let report = run_bench(&BenchOptions {
+        rows: 20,
+        threads: vec![1, 3],
+        formats: vec![OutputFormat::Csv, OutputFormat::PgCopy],
+        out_dir: out_dir.clone(),
+        ..Default::default()
+    })
+    .expect("run bench");
+
+    let runs: Vec<(OutputFormat, usize, u64)> = report
+        .results
+        .iter()
+        .map(|result| (result.format, result.threads, result.rows))
+        .collect();
+    // 20 events plus one account per ten events of each shard (7, 7, 6).
+    assert_eq!(
+        runs,
+        vec![
+            (OutputFormat::Csv, 1, 22),
+            (OutputFormat::Csv, 3, 23),
+            (OutputFormat::PgCopy, 1, 22),
+            (OutputFormat::PgCopy, 3, 23),
+        ]
+    );
+    for result in &report.results {
+        assert!(result.bytes > 0, "{result:?}");
+        assert!(result.rows_per_sec > 0.0, "{result:?}");
+    }
+    assert!(!out_dir.join("csv_1t").exists(), "output is removed");
+}
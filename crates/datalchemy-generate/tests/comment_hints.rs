@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Keeps the rows written per table.
+#[derive(Default)]
+struct CapturingSink {
+    tables: Vec<Vec<Row>>,
+}
+
+impl TableSink for CapturingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[Row],
+    ) -> Result<TableOutput, GenerationError> {
+        self.tables.push(rows.to_vec());
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .table(
+            "crm",
+            TableBuilder::new("clientes")
+                .column("id", "integer")
+                .column("documento", "text")
+                .column_comment("Documento do titular @generator:semantic.br.cpf")
+                .column("contato", "text")
+                .column_comment("Canal preferido @pii:email")
+                .column("observacao", "text")
+                .column_comment("Texto livre @pii:medical")
+                .primary_key(["id"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+#[test]
+fn comment_annotations_choose_generators_and_tag_pii() {
+    let plan: Plan = serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 11,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "crm", "table": "clientes", "rows": 20 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let mut sink = CapturingSink::default();
+    let report = GenerationEngine::new(GenerateOptions::default())
+        .generate_to_sink(&schema(), &plan, &mut sink)
+        .expect("generate");
+
+    for row in &sink.tables[0] {
+        match &row["documento"] {
+            GeneratedValue::Text(cpf) => {
+                let digits = cpf.chars().filter(char::is_ascii_digit).count();
+                assert_eq!(digits, 11, "{cpf}");
+            }
+            other => panic!("documento {other:?}"),
+        }
+        match &row["contato"] {
+            GeneratedValue::Text(email) => assert!(email.contains('@'), "{email}"),
+            other => panic!("contato {other:?}"),
+        }
+    }
+
+    let documento = &report.column_sources["crm.clientes.documento"];
+    assert_eq!(documento.source, "comment");
+    assert_eq!(documento.generator_id.as_deref(), Some("semantic.br.cpf"));
+    let contato = &report.column_sources["crm.clientes.contato"];
+    assert_eq!(contato.source, "comment");
+    assert_eq!(
+        contato.generator_id.as_deref(),
+        Some("semantic.person.email")
+    );
+    assert!(contato.pii_tags.contains(&"pii.email".to_string()));
+
+    let observacao = &report.column_sources["crm.clientes.observacao"];
+    assert_ne!(observacao.source, "comment", "no generator for this kind");
+    assert!(observacao.pii_tags.contains(&"pii.medical".to_string()));
+}
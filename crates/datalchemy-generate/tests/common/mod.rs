@@ -0,0 +1,54 @@
+//! Helpers shared by the integration tests of this crate.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use datalchemy_core::DatabaseSchema;
+use datalchemy_plan::Plan;
+
+pub fn load_json(path: &Path) -> serde_json::Value {
+    let contents =
+        fs::read_to_string(path).unwrap_or_else(|_| panic!("missing json at {}", path.display()));
+    serde_json::from_str(&contents).expect("parse json")
+}
+
+/// The minimal example plan and the golden Postgres schema it targets.
+pub fn load_plan_and_schema() -> (Plan, DatabaseSchema) {
+    let plan_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
+    let schema_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    let plan: Plan = serde_json::from_value(load_json(&plan_path)).expect("parse plan");
+    let schema: DatabaseSchema =
+        serde_json::from_value(load_json(&schema_path)).expect("parse schema");
+    (plan, schema)
+}
+
+/// [`load_plan_and_schema`] with every target set to `rows` rows.
+pub fn load_plan_and_schema_with_rows(rows: u64) -> (Plan, DatabaseSchema) {
+    let (mut plan, schema) = load_plan_and_schema();
+    for target in &mut plan.targets {
+        target.rows = rows;
+    }
+    (plan, schema)
+}
+
+/// Unique path under the system temp dir, not created yet.
+pub fn temp_out_path(label: &str) -> PathBuf {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!(
+        "datalchemy_generate_{label}_{}",
+        uuid::Uuid::new_v4()
+    ));
+    dir
+}
+
+/// New empty directory under the system temp dir.
+pub fn temp_out_dir(label: &str) -> PathBuf {
+    let dir = temp_out_path(label);
+    fs::create_dir_all(&dir).expect("create temp out dir");
+    dir
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::dictionary::{DATA_DICTIONARY_HTML, DATA_DICTIONARY_MD};
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+
+#[test]
+fn data_dictionary_describes_columns_sources_and_pii() {
+    let (mut plan, schema) = load_plan_and_schema();
+    for target in &mut plan.targets {
+        target.rows = 5;
+    }
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("data_dictionary"),
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let sources = &result.report.column_sources;
+    let email = sources
+        .get("crm.usuarios.email")
+        .expect("usuarios.email source");
+    assert_eq!(email.source, "plan");
+    assert_eq!(
+        email.generator_id.as_deref(),
+        Some("semantic.br.email.safe")
+    );
+    assert!(email.pii_tags.iter().any(|tag| tag == "pii.email"));
+    assert_eq!(sources["crm.contatos.empresa_id"].source, "foreign_key");
+    assert_eq!(sources["crm.oportunidades.status"].source, "default");
+
+    let markdown =
+        fs::read_to_string(result.run_dir.join(DATA_DICTIONARY_MD)).expect("read markdown");
+    assert!(markdown.contains("## crm.usuarios"));
+    assert!(markdown.contains("| pii.email |"));
+    assert!(markdown.contains(
+        "| empresa_id | uuid | no | FK -> crm.empresas.id | foreign_key | crm.empresas.id |"
+    ));
+    assert!(markdown.contains(
+        "| status | crm.status_oportunidade | no |  | default | 'aberta'::crm.status_oportunidade |"
+    ));
+
+    let html = fs::read_to_string(result.run_dir.join(DATA_DICTIONARY_HTML)).expect("read html");
+    assert!(html.contains("<h2 id=\"crm.usuarios\">crm.usuarios</h2>"));
+    assert!(html.contains("<td>FK -&gt; crm.empresas.id</td>"));
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    EntityRegistry, GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Keeps the rows written per table.
+#[derive(Default)]
+struct CapturingSink {
+    tables: HashMap<String, Vec<Row>>,
+}
+
+impl TableSink for CapturingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[Row],
+    ) -> Result<TableOutput, GenerationError> {
+        self.tables.insert(table.name.clone(), rows.to_vec());
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .table(
+            "crm",
+            TableBuilder::new("clientes")
+                .column("id", "integer")
+                .not_null()
+                .column("nome", "text")
+                .column("cpf", "text")
+                .primary_key(["id"]),
+        )
+        .table(
+            "crm",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .not_null()
+                .column("cliente_id", "integer")
+                .not_null()
+                .column("cliente_nome", "text")
+                .column("cliente_cpf", "text")
+                .primary_key(["id"])
+                .foreign_key(["cliente_id"], "clientes", ["id"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+fn entity_rule(table: &str, column: &str, generator: &str, key: &str) -> serde_json::Value {
+    json!({
+        "type": "column_generator",
+        "schema": "crm",
+        "table": table,
+        "column": column,
+        "generator": generator,
+        "entity": { "name": "cliente", "key": key }
+    })
+}
+
+fn plan(seed: u64, clientes: u64, pedidos: u64) -> Plan {
+    serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": seed,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [
+            { "schema": "crm", "table": "clientes", "rows": clientes },
+            { "schema": "crm", "table": "pedidos", "rows": pedidos }
+        ],
+        "rules": [
+            {
+                "type": "column_generator",
+                "schema": "crm",
+                "table": "clientes",
+                "column": "id",
+                "generator": { "id": "primitive.int.sequence", "params": { "start": 1, "step": 1 } }
+            },
+            entity_rule("clientes", "nome", "semantic.br.name", "id"),
+            entity_rule("clientes", "cpf", "semantic.br.cpf", "id"),
+            entity_rule("pedidos", "cliente_nome", "semantic.br.name", "cliente_id"),
+            entity_rule("pedidos", "cliente_cpf", "semantic.br.cpf", "cliente_id")
+        ]
+    }))
+    .expect("plan")
+}
+
+fn generate(plan: &Plan) -> HashMap<String, Vec<Row>> {
+    let mut sink = CapturingSink::default();
+    GenerationEngine::new(GenerateOptions::default())
+        .generate_to_sink(&schema(), plan, &mut sink)
+        .expect("generate");
+    sink.tables
+}
+
+fn by_id(rows: &[Row]) -> HashMap<i64, &Row> {
+    rows.iter()
+        .map(|row| (row["id"].as_i64().expect("id"), row))
+        .collect()
+}
+
+#[test]
+fn entity_columns_agree_across_tables() {
+    let tables = generate(&plan(42, 5, 30));
+    let clientes = by_id(&tables["clientes"]);
+    for pedido in &tables["pedidos"] {
+        let cliente = clientes[&pedido["cliente_id"].as_i64().expect("cliente_id")];
+        assert_eq!(pedido["cliente_nome"], cliente["nome"]);
+        assert_eq!(pedido["cliente_cpf"], cliente["cpf"]);
+    }
+    let names: std::collections::HashSet<_> = clientes
+        .values()
+        .map(|row| format!("{:?}", row["nome"]))
+        .collect();
+    assert!(names.len() > 1, "entities differ from each other");
+}
+
+#[test]
+fn entity_values_are_stable_across_runs_and_appends() {
+    let small = generate(&plan(42, 3, 1));
+    let large = generate(&plan(42, 10, 1));
+    let other_seed = generate(&plan(7, 3, 1));
+    let (small, large, other_seed) = (
+        by_id(&small["clientes"]),
+        by_id(&large["clientes"]),
+        by_id(&other_seed["clientes"]),
+    );
+    for id in 1..=3 {
+        assert_eq!(small[&id]["nome"], large[&id]["nome"]);
+        assert_eq!(small[&id]["cpf"], large[&id]["cpf"]);
+    }
+    assert_ne!(small[&1]["cpf"], other_seed[&1]["cpf"]);
+}
+
+#[test]
+fn registry_predicts_generated_entity_values() {
+    let schema = schema();
+    let plan = plan(42, 5, 0);
+    let clientes = generate(&plan).remove("clientes").expect("clientes");
+    let registry = EntityRegistry::new(&schema, &plan).expect("registry");
+
+    for row in &clientes {
+        let key = &row["id"];
+        assert_eq!(
+            registry
+                .value("crm", "clientes", "nome", key)
+                .expect("value"),
+            Some(row["nome"].clone())
+        );
+        assert_eq!(
+            registry
+                .value("crm", "pedidos", "cliente_cpf", key)
+                .expect("value"),
+            Some(row["cpf"].clone())
+        );
+    }
+    assert_eq!(
+        registry
+            .value("crm", "clientes", "id", &GeneratedValue::Int(1))
+            .expect("value"),
+        None,
+        "not an entity column"
+    );
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::dbt::{DBT_PROJECT_FILE, DBT_SEEDS_YML, export_dbt_seeds};
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+
+#[test]
+fn csv_output_exports_as_dbt_seeds() {
+    let (mut plan, schema) = load_plan_and_schema();
+    for target in &mut plan.targets {
+        target.rows = 5;
+    }
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("dbt_run"),
+        ..Default::default()
+    };
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let project = temp_out_dir("dbt_project");
+    assert!(export_dbt_seeds(&result.run_dir, &project, &schema, &result.report).is_err());
+
+    fs::write(project.join(DBT_PROJECT_FILE), "name: fixtures\n").expect("write project");
+    let seeds =
+        export_dbt_seeds(&result.run_dir, &project, &schema, &result.report).expect("export seeds");
+
+    let usuarios = seeds
+        .iter()
+        .find(|seed| seed.table == "usuarios")
+        .expect("usuarios seed");
+    assert_eq!(usuarios.name, "usuarios");
+    assert_eq!(usuarios.path, "seeds/crm/usuarios.csv");
+    assert_eq!(
+        fs::read(project.join(&usuarios.path)).expect("read seed"),
+        fs::read(result.run_dir.join("crm.usuarios.csv")).expect("read output")
+    );
+
+    let yml = fs::read_to_string(project.join("seeds").join(DBT_SEEDS_YML)).expect("seeds.yml");
+    assert!(yml.starts_with("# Generated by datalchemy"));
+    assert!(yml.contains("  - name: \"usuarios\"\n"));
+    assert!(yml.contains("      schema: \"crm\"\n"));
+    assert!(yml.contains("        \"ativo\": \"boolean\"\n"));
+    // Enum columns load as text.
+    assert!(yml.contains("        \"status\": \"text\"\n"));
+    assert!(yml.contains(
+        "        description: \"Synthetic (plan: semantic.br.email.safe); PII: pii.email.\"\n"
+    ));
+
+    // Re-exporting replaces our own seeds.yml, but never a hand-written one.
+    export_dbt_seeds(&result.run_dir, &project, &schema, &result.report).expect("re-export");
+    fs::write(project.join("seeds").join(DBT_SEEDS_YML), "version: 2\n").expect("write yml");
+    assert!(export_dbt_seeds(&result.run_dir, &project, &schema, &result.report).is_err());
+}
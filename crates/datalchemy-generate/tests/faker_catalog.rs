@@ -26,6 +26,7 @@ fn test_column(name: &str, data_type: &str) -> Column {
         identity: None,
         generated: None,
         comment: None,
+        stats: None,
     }
 }
 
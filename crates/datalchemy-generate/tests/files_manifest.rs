@@ -0,0 +1,64 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::manifest::{FILES_MANIFEST_NAME, FilesManifest, hash_file};
+use datalchemy_generate::output::SCHEMA_SQL_FILE_NAME;
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+
+#[test]
+fn files_manifest_lists_checksums_rows_and_headers() {
+    let (plan, schema) = load_plan_and_schema();
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("files_manifest"),
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let manifest: FilesManifest = serde_json::from_str(
+        &fs::read_to_string(result.run_dir.join(FILES_MANIFEST_NAME)).expect("read files.json"),
+    )
+    .expect("parse files.json");
+
+    let paths: Vec<&str> = manifest
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+    assert!(paths.contains(&"resolved_plan.json"));
+    assert!(paths.contains(&SCHEMA_SQL_FILE_NAME));
+    assert!(!paths.contains(&FILES_MANIFEST_NAME));
+
+    let usuarios = manifest
+        .files
+        .iter()
+        .find(|file| file.path == "crm.usuarios.csv")
+        .expect("usuarios entry");
+    let (bytes, sha256) = hash_file(&result.run_dir.join("crm.usuarios.csv")).expect("hash");
+    assert_eq!(usuarios.bytes, bytes);
+    assert_eq!(usuarios.sha256, sha256);
+    assert_eq!(usuarios.tables.len(), 1);
+    assert_eq!(usuarios.tables[0].rows, Some(50));
+
+    let contents = fs::read_to_string(result.run_dir.join("crm.usuarios.csv")).expect("read csv");
+    let header: Vec<&str> = contents
+        .lines()
+        .next()
+        .expect("header")
+        .split(',')
+        .collect();
+    assert_eq!(usuarios.tables[0].columns, header);
+
+    let ddl =
+        fs::read_to_string(result.run_dir.join(SCHEMA_SQL_FILE_NAME)).expect("read schema.sql");
+    assert!(ddl.contains("CREATE TYPE crm.status_lead AS ENUM"));
+    assert!(ddl.contains("CREATE TABLE crm.usuarios ("));
+    assert!(ddl.contains("ALTER TABLE crm.contatos ADD CONSTRAINT contatos_empresa_id_fkey"));
+}
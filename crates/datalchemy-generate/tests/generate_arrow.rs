@@ -0,0 +1,59 @@
+mod common;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+
+fn arrow_options(label: &str) -> GenerateOptions {
+    GenerateOptions {
+        out_dir: temp_out_dir(label),
+        format: OutputFormat::ArrowIpc,
+        ..Default::default()
+    }
+}
+
+#[cfg(not(feature = "arrow"))]
+#[test]
+fn arrow_without_feature_is_unsupported() {
+    let (plan, schema) = load_plan_and_schema();
+    let err = GenerationEngine::new(arrow_options("arrow_off"))
+        .run(&schema, &plan)
+        .expect_err("arrow sink is feature-gated");
+    assert!(matches!(
+        err,
+        datalchemy_generate::GenerationError::Unsupported(_)
+    ));
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn record_batch_sink_returns_one_batch_per_table() {
+    use datalchemy_generate::RecordBatchSink;
+
+    let (plan, schema) = load_plan_and_schema();
+    let mut sink = RecordBatchSink::new();
+    GenerationEngine::new(arrow_options("arrow_batches"))
+        .run_with_sink(&schema, &plan, &mut sink)
+        .expect("run generation");
+
+    let usuarios = sink
+        .batches()
+        .iter()
+        .find(|batch| batch.table == "usuarios")
+        .expect("usuarios batch");
+    assert_eq!(usuarios.batch.num_rows(), 50);
+    assert!(usuarios.batch.schema().field_with_name("email").is_ok());
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow_ipc_files_round_trip() {
+    let (plan, schema) = load_plan_and_schema();
+    let result = GenerationEngine::new(arrow_options("arrow_ipc"))
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let file = fs::File::open(result.run_dir.join("crm.usuarios.arrow")).expect("open arrow file");
+    let reader = arrow_ipc::reader::FileReader::try_new(file, None).expect("read arrow file");
+    let rows: usize = reader.map(|batch| batch.expect("batch").num_rows()).sum();
+    assert_eq!(rows, 50);
+}
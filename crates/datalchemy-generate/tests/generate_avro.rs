@@ -0,0 +1,35 @@
+mod common;
+
+use std::fs;
+
+use apache_avro::Reader;
+use apache_avro::types::Value as AvroValue;
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+
+#[test]
+fn avro_files_are_readable_with_schema() {
+    let (plan, schema) = load_plan_and_schema();
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("avro"),
+        format: OutputFormat::Avro,
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let avsc = fs::read_to_string(result.run_dir.join("crm.oportunidades.avsc")).expect("avsc");
+    let writer_schema = apache_avro::Schema::parse_str(&avsc).expect("parse avsc");
+
+    let file = fs::File::open(result.run_dir.join("crm.oportunidades.avro")).expect("open avro");
+    let reader = Reader::with_schema(&writer_schema, file).expect("avro reader");
+    let mut rows = 0;
+    for record in reader {
+        let record = record.expect("decode record");
+        assert!(matches!(record, AvroValue::Record(_)));
+        rows += 1;
+    }
+    assert_eq!(rows, 120);
+}
@@ -1,30 +1,10 @@
+mod common;
+
 use std::fs;
-use std::path::PathBuf;
 
-use datalchemy_core::DatabaseSchema;
+use common::{load_plan_and_schema, temp_out_dir};
 use datalchemy_generate::{GenerateOptions, GenerationEngine};
-use datalchemy_plan::Plan;
-
-fn load_json(path: &PathBuf) -> serde_json::Value {
-    let contents =
-        fs::read_to_string(path).unwrap_or_else(|_| panic!("missing json at {}", path.display()));
-    serde_json::from_str(&contents).expect("parse json")
-}
-
-fn load_plan_and_schema() -> (Plan, DatabaseSchema) {
-    let plan_path =
-        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
-    let schema_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
-
-    let plan_json = load_json(&plan_path);
-    let schema_json = load_json(&schema_path);
-
-    let plan: Plan = serde_json::from_value(plan_json).expect("parse plan");
-    let schema: DatabaseSchema = serde_json::from_value(schema_json).expect("parse schema");
-
-    (plan, schema)
-}
+use datalchemy_plan::{BooleanFormat, PlanGlobal, ValueFormats};
 
 #[test]
 fn generate_is_deterministic() {
@@ -99,12 +79,93 @@ fn generate_respects_row_counts() {
     assert_eq!(rows_generated, 50);
 }
 
-fn temp_out_dir(label: &str) -> PathBuf {
-    let mut dir = std::env::temp_dir();
-    dir.push(format!(
-        "datalchemy_generate_{label}_{}",
-        uuid::Uuid::new_v4()
-    ));
-    fs::create_dir_all(&dir).expect("create temp out dir");
-    dir
+#[test]
+fn csv_applies_plan_value_formats() {
+    let (mut plan, schema) = load_plan_and_schema();
+    for target in &mut plan.targets {
+        target.rows = 5;
+    }
+    plan.global = Some(PlanGlobal {
+        locale: None,
+        formats: Some(ValueFormats {
+            date_format: Some("%d/%m/%Y".to_string()),
+            time_format: None,
+            timestamp_format: Some("%Y-%m-%d %H:%M:%S".to_string()),
+            timezone_suffix: Some("Z".to_string()),
+            boolean: Some(BooleanFormat::TF),
+            decimal_separator: Some(','),
+        }),
+    });
+
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("run_formats"),
+        ..Default::default()
+    };
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let read = |file: &str| -> Vec<Vec<String>> {
+        let mut reader = csv::Reader::from_path(result.run_dir.join(file)).expect("open csv");
+        reader
+            .records()
+            .map(|record| record.expect("record").iter().map(str::to_string).collect())
+            .collect()
+    };
+
+    // usuarios: id, nome, email, telefone, ativo, data_criacao (timestamptz), ...
+    for row in read("crm.usuarios.csv") {
+        assert!(row[4] == "t" || row[4] == "f", "boolean {}", row[4]);
+        let created = &row[5];
+        assert_eq!(created.len(), 20, "timestamp {created}");
+        assert_eq!(&created[10..11], " ");
+        assert!(created.ends_with('Z'));
+    }
+    // contatos.data_nascimento (date)
+    for row in read("crm.contatos.csv") {
+        let born = &row[7];
+        assert!(born.is_empty() || (born.len() == 10 && &born[2..3] == "/" && &born[5..6] == "/"));
+    }
+    // etapas_funil.probabilidade (numeric(5,2))
+    for row in read("crm.etapas_funil.csv") {
+        assert!(row[4].contains(','), "decimal {}", row[4]);
+        assert!(!row[4].contains('.'));
+    }
+}
+
+#[test]
+fn report_breaks_down_performance_per_table() {
+    let (plan, schema) = load_plan_and_schema();
+
+    let engine = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_dir("run_performance"),
+        ..Default::default()
+    });
+    let result = engine.run(&schema, &plan).expect("run generation");
+    let report = &result.report;
+
+    assert!(!report.tables.is_empty());
+    for table in &report.tables {
+        assert!(table.peak_memory_bytes > 0, "{}", table.table);
+        if table.duration_ms > 0 {
+            assert!(table.rows_per_sec > 0.0, "{}", table.table);
+        }
+    }
+    assert_eq!(
+        report.peak_memory_bytes,
+        report
+            .tables
+            .iter()
+            .map(|table| table.peak_memory_bytes)
+            .sum::<u64>()
+    );
+
+    let slowest = report.slowest_tables(2);
+    assert_eq!(slowest.len(), 2.min(report.tables.len()));
+    assert!(
+        report
+            .tables
+            .iter()
+            .all(|table| table.duration_ms <= slowest[0].duration_ms)
+    );
 }
@@ -0,0 +1,41 @@
+mod common;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+
+fn duckdb_options(label: &str) -> GenerateOptions {
+    GenerateOptions {
+        out_dir: temp_out_dir(label),
+        format: OutputFormat::DuckDb,
+        ..Default::default()
+    }
+}
+
+#[cfg(not(feature = "duckdb"))]
+#[test]
+fn duckdb_without_feature_is_unsupported() {
+    let (plan, schema) = load_plan_and_schema();
+    let err = GenerationEngine::new(duckdb_options("duckdb_off"))
+        .run(&schema, &plan)
+        .expect_err("duckdb sink is feature-gated");
+    assert!(matches!(
+        err,
+        datalchemy_generate::GenerationError::Unsupported(_)
+    ));
+}
+
+#[cfg(feature = "duckdb")]
+#[test]
+fn duckdb_output_creates_queryable_database() {
+    let (plan, schema) = load_plan_and_schema();
+    let result = GenerationEngine::new(duckdb_options("duckdb"))
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let conn = duckdb::Connection::open(result.run_dir.join("dataset.duckdb"))
+        .expect("open dataset.duckdb");
+    let usuarios: i64 = conn
+        .query_row("SELECT count(*) FROM crm.usuarios", [], |row| row.get(0))
+        .expect("count usuarios");
+    assert_eq!(usuarios, 50);
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::load_plan_and_schema_with_rows;
+use datalchemy_core::Table;
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, GenerationReport, TableOutput, TableSink,
+};
+
+/// Fails when `table` is written.
+struct FailingSink {
+    table: &'static str,
+}
+
+impl TableSink for FailingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        if table.name == self.table {
+            return Err(std::io::Error::other("disk full").into());
+        }
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+#[test]
+fn sink_errors_carry_the_table_and_code() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let err = GenerationEngine::new(GenerateOptions::default())
+        .generate_to_sink(&schema, &plan, &mut FailingSink { table: "contatos" })
+        .expect_err("sink fails");
+
+    assert!(matches!(err.kind(), GenerationError::Io(_)), "{err}");
+    assert_eq!(err.code(), "DL203");
+    assert_eq!(err.to_string(), "crm.contatos: io error: disk full");
+    let context = err.context().expect("located");
+    assert_eq!(context.table.as_deref(), Some("contatos"));
+    assert_eq!(context.column, None);
+
+    let info = serde_json::to_value(err.info()).expect("serialize");
+    assert_eq!(
+        info,
+        serde_json::json!({
+            "code": "DL203",
+            "message": "io error: disk full",
+            "schema": "crm",
+            "table": "contatos",
+        })
+    );
+}
+
+#[test]
+fn cancellation_is_never_located() {
+    let err = GenerationError::Cancelled
+        .in_table("crm", "contatos")
+        .at_column("email");
+    assert!(matches!(err, GenerationError::Cancelled));
+    assert!(err.context().is_none());
+
+    let err = GenerationError::InvalidPlan("bad".to_string())
+        .at_column("email")
+        .in_table("crm", "contatos");
+    assert_eq!(err.to_string(), "crm.contatos.email: invalid plan: bad");
+    assert_eq!(err.code(), "DL201");
+}
+
+#[test]
+fn report_error_round_trips_and_is_omitted_when_absent() {
+    let mut report = GenerationReport::new("r1".to_string());
+    let value = serde_json::to_value(&report).expect("serialize");
+    assert!(value.get("error").is_none());
+
+    report.error = Some(
+        GenerationError::Unsupported("x".to_string())
+            .in_table("crm", "usuarios")
+            .info(),
+    );
+    let value = serde_json::to_value(&report).expect("serialize");
+    assert_eq!(value["error"]["code"], "DL202");
+    assert_eq!(value["error"]["table"], "usuarios");
+    let back: GenerationReport = serde_json::from_value(value).expect("deserialize");
+    assert_eq!(back.error, report.error);
+}
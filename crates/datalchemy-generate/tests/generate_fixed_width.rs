@@ -0,0 +1,57 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+
+#[test]
+fn fixed_width_records_follow_plan_layout() {
+    let (mut plan, schema) = load_plan_and_schema();
+    plan.rules.push(
+        serde_json::from_value(serde_json::json!({
+            "type": "fixed_width",
+            "schema": "crm",
+            "table": "usuarios",
+            "columns": [
+                { "column": "id", "width": 36 },
+                { "column": "nome", "width": 4 },
+                { "column": "ativo", "width": 6, "align": "right", "pad": "*" }
+            ]
+        }))
+        .expect("parse fixed_width rule"),
+    );
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("fixed_width"),
+        format: OutputFormat::FixedWidth,
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let contents =
+        fs::read_to_string(result.run_dir.join("crm.usuarios.txt")).expect("read fixed-width");
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 50);
+    for line in &lines {
+        assert_eq!(line.chars().count(), 46);
+        let flag = &line[40..];
+        assert!(
+            flag == "**true" || flag == "*false",
+            "unexpected flag field {flag}"
+        );
+    }
+
+    let layout: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(result.run_dir.join("crm.usuarios.layout.json")).expect("layout"),
+    )
+    .expect("parse layout");
+    assert_eq!(layout[2]["start"], 41);
+
+    assert!(result.run_dir.join("crm.oportunidades.txt").exists());
+    assert!(result.report.warnings.iter().any(|issue| {
+        issue.code == "fixed_width_truncated" && issue.column.as_deref() == Some("nome")
+    }));
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use std::collections::{BTreeMap, HashMap};
+
+use common::{load_plan_and_schema_with_rows, temp_out_path};
+use datalchemy_core::Table;
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::output::kafka::{KafkaMessage, encode_messages, partition_for_key};
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, KafkaEncoding, KafkaTarget, TableOutput,
+    TableSink,
+};
+
+/// Encodes messages the way `KafkaSink` does, without a broker.
+struct EncodingSink {
+    target: KafkaTarget,
+    messages: Vec<(String, Vec<KafkaMessage>)>,
+}
+
+impl TableSink for EncodingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let topic = self.target.topic(schema_name, &table.name);
+        let messages = encode_messages(&self.target, schema_name, table, rows)?;
+        self.messages.push((topic.clone(), messages));
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: topic,
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+fn encode_run(target: KafkaTarget, label: &str) -> Vec<(String, Vec<KafkaMessage>)> {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let mut sink = EncodingSink {
+        target,
+        messages: Vec::new(),
+    };
+    GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_path(label),
+        ..Default::default()
+    })
+    .run_with_sink(&schema, &plan, &mut sink)
+    .expect("run generation");
+    sink.messages
+}
+
+#[test]
+fn json_messages_are_keyed_by_primary_key_and_follow_fk_order() {
+    let messages = encode_run(
+        KafkaTarget {
+            brokers: vec!["localhost:9092".to_string()],
+            topic_prefix: "synthetic.".to_string(),
+            key_columns: BTreeMap::from([("crm.usuarios".to_string(), "email".to_string())]),
+            ..Default::default()
+        },
+        "kafka_json",
+    );
+
+    let topics: Vec<&str> = messages.iter().map(|(topic, _)| topic.as_str()).collect();
+    let position = |topic: &str| topics.iter().position(|t| *t == topic).expect(topic);
+    assert!(position("synthetic.crm.empresas") < position("synthetic.crm.contatos"));
+
+    let (_, empresas) = &messages[position("synthetic.crm.empresas")];
+    assert_eq!(empresas.len(), 5);
+    for message in empresas {
+        let value: serde_json::Value = serde_json::from_slice(&message.value).expect("json value");
+        let id = value["id"].as_str().expect("uuid id");
+        assert_eq!(message.key.as_deref(), Some(id.as_bytes()));
+    }
+
+    let (_, usuarios) = &messages[position("synthetic.crm.usuarios")];
+    for message in usuarios {
+        let value: serde_json::Value = serde_json::from_slice(&message.value).expect("json value");
+        let email = value["email"].as_str().expect("email");
+        assert_eq!(message.key.as_deref(), Some(email.as_bytes()));
+    }
+}
+
+#[test]
+fn avro_messages_use_single_object_encoding() {
+    let (_, schema) = load_plan_and_schema_with_rows(5);
+    let messages = encode_run(
+        KafkaTarget {
+            brokers: vec!["localhost:9092".to_string()],
+            encoding: KafkaEncoding::Avro,
+            ..Default::default()
+        },
+        "kafka_avro",
+    );
+
+    let table = schema
+        .schemas
+        .iter()
+        .find(|s| s.name == "crm")
+        .and_then(|s| s.tables.iter().find(|t| t.name == "empresas"))
+        .expect("empresas table");
+    let schema_json = datalchemy_generate::output::avro::avro_schema("crm", table);
+    let avro_schema = apache_avro::Schema::parse(&schema_json).expect("parse avro schema");
+    // apache-avro keeps logical types in its canonical form, so fingerprint the
+    // spec form (logical types reduced to their base type) parsed back.
+    let canonical = datalchemy_generate::output::avro::parsing_canonical_form(&schema_json);
+    assert!(canonical.starts_with(
+        r#"{"name":"crm.empresas","type":"record","fields":[{"name":"id","type":"string"}"#
+    ));
+    let fingerprint = apache_avro::Schema::parse_str(&canonical)
+        .expect("parse canonical form")
+        .fingerprint::<apache_avro::rabin::Rabin>()
+        .bytes;
+
+    let (_, empresas) = messages
+        .iter()
+        .find(|(topic, _)| topic == "crm.empresas")
+        .expect("empresas topic");
+    for message in empresas {
+        assert_eq!(&message.value[..2], &[0xc3, 0x01]);
+        assert_eq!(&message.value[2..10], fingerprint.as_slice());
+        let mut datum = &message.value[10..];
+        let value = apache_avro::from_avro_datum(&avro_schema, &mut datum, None)
+            .expect("decode avro datum");
+        assert!(datum.is_empty());
+        assert!(matches!(value, apache_avro::types::Value::Record(_)));
+    }
+}
+
+#[test]
+fn keyed_partitions_match_kafka_default_partitioner() {
+    assert_eq!(partition_for_key(b"21", 12), 0);
+    assert_eq!(partition_for_key(b"foobar", 12), 6);
+    assert_eq!(partition_for_key(b"abc", 12), 3);
+    assert_eq!(partition_for_key(b"abc", 1), 0);
+}
+
+#[test]
+fn unknown_key_column_is_rejected() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let mut sink = EncodingSink {
+        target: KafkaTarget {
+            key_columns: BTreeMap::from([("crm.empresas".to_string(), "missing".to_string())]),
+            ..Default::default()
+        },
+        messages: Vec::new(),
+    };
+    let err = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_path("kafka_bad_key"),
+        ..Default::default()
+    })
+    .run_with_sink(&schema, &plan, &mut sink)
+    .expect_err("missing key column");
+    assert!(
+        err.to_string().contains("kafka key column 'missing'"),
+        "{err}"
+    );
+}
+
+#[cfg(feature = "kafka")]
+#[test]
+fn kafka_sink_requires_brokers() {
+    let err = match datalchemy_generate::KafkaSink::connect(KafkaTarget::default()) {
+        Ok(_) => panic!("connect without brokers"),
+        Err(err) => err,
+    };
+    assert!(matches!(err, GenerationError::InvalidPlan(_)));
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use std::collections::HashMap;
+use std::fs;
+
+use common::{load_plan_and_schema_with_rows, temp_out_path};
+use datalchemy_core::{SchemaBuilder, Sequence, SequenceOwner, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::output::migrations::{LIQUIBASE_CHANGELOG, insert_statements};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+use datalchemy_plan::Plan;
+
+fn migration_options(label: &str, format: OutputFormat) -> GenerateOptions {
+    GenerateOptions {
+        out_dir: temp_out_path(label),
+        format,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn flyway_writes_one_versioned_migration_per_table_in_fk_order() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let result = GenerationEngine::new(migration_options("flyway", OutputFormat::Flyway))
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let mut files: Vec<String> = fs::read_dir(&result.run_dir)
+        .expect("read run dir")
+        .map(|entry| {
+            entry
+                .expect("entry")
+                .file_name()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .filter(|name| name.starts_with('V') && name.ends_with(".sql"))
+        .collect();
+    files.sort();
+    assert_eq!(files.len(), result.report.tables.len());
+
+    let pattern = regex::Regex::new(r"^V\d{14}\.\d{3}__seed_\w+\.sql$").expect("regex");
+    for (file, table) in files.iter().zip(&result.report.tables) {
+        assert!(pattern.is_match(file), "{file}");
+        assert!(
+            file.ends_with(&format!("__seed_{}_{}.sql", table.schema, table.table)),
+            "{file} out of generation order"
+        );
+    }
+
+    let empresas = files
+        .iter()
+        .find(|file| file.ends_with("__seed_crm_empresas.sql"))
+        .expect("empresas migration");
+    let script = fs::read_to_string(result.run_dir.join(empresas)).expect("read migration");
+    assert!(script.contains("INSERT INTO crm.empresas (id, razao_social,"));
+    assert_eq!(script.matches("\n  (").count(), 5);
+    assert!(script.trim_end().ends_with(");"));
+}
+
+#[test]
+fn liquibase_changelog_has_one_changeset_per_table() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let result = GenerationEngine::new(migration_options("liquibase", OutputFormat::Liquibase))
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let changelog =
+        fs::read_to_string(result.run_dir.join(LIQUIBASE_CHANGELOG)).expect("read changelog");
+    assert!(changelog.starts_with("--liquibase formatted sql\n"));
+    let changesets: Vec<&str> = changelog
+        .lines()
+        .filter(|line| line.starts_with("--changeset datalchemy:seed-"))
+        .collect();
+    assert_eq!(changesets.len(), result.report.tables.len());
+    assert!(changesets[0].ends_with(&format!(
+        "-001-{}.{} context:seed",
+        result.report.tables[0].schema, result.report.tables[0].table
+    )));
+}
+
+#[test]
+fn insert_literals_are_quoted_and_nulls_kept() {
+    let (_, schema) = load_plan_and_schema_with_rows(5);
+    let table = schema
+        .schemas
+        .iter()
+        .find(|s| s.name == "crm")
+        .and_then(|s| s.tables.iter().find(|t| t.name == "empresas"))
+        .expect("empresas table");
+
+    let mut row: HashMap<String, GeneratedValue> = HashMap::new();
+    for column in &table.columns {
+        row.insert(column.name.to_lowercase(), GeneratedValue::Null);
+    }
+    row.insert(
+        "razao_social".to_string(),
+        GeneratedValue::Text("D'Avila & Filhos".to_string()),
+    );
+    row.insert("ativo".to_string(), GeneratedValue::Bool(true));
+
+    let sql = insert_statements("crm", table, &[row]);
+    assert!(sql.contains("'D''Avila & Filhos'"), "{sql}");
+    assert!(sql.contains("'true'"), "{sql}");
+    assert!(sql.contains("NULL"), "{sql}");
+    assert_eq!(sql.matches("INSERT INTO").count(), 1);
+}
+
+#[test]
+fn sequence_ids_follow_the_sequence_and_are_reset_after_inserts() {
+    let mut schema = SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("pedidos")
+                .column("id", "bigint")
+                .not_null()
+                .column("total", "numeric")
+                .primary_key(["id"]),
+        )
+        .build()
+        .expect("schema");
+    schema.sequences.push(Sequence {
+        schema: "vendas".to_string(),
+        name: "pedidos_id_seq".to_string(),
+        owner: Some(SequenceOwner {
+            schema: "vendas".to_string(),
+            table: "pedidos".to_string(),
+            column: "id".to_string(),
+        }),
+        start: 100,
+        increment: 10,
+        cache: 1,
+        last_value: None,
+    });
+    let plan: Plan = serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 3,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "vendas", "table": "pedidos", "rows": 5 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let result = GenerationEngine::new(migration_options("flyway_seq", OutputFormat::Flyway))
+        .run(&schema, &plan)
+        .expect("run generation");
+    let migration = fs::read_dir(&result.run_dir)
+        .expect("read run dir")
+        .map(|entry| entry.expect("entry").path())
+        .find(|path| {
+            path.to_string_lossy()
+                .ends_with("__seed_vendas_pedidos.sql")
+        })
+        .expect("pedidos migration");
+    let script = fs::read_to_string(migration).expect("read migration");
+    for id in [100, 110, 120, 130, 140] {
+        assert!(script.contains(&format!("\n  ('{id}', ")), "{script}");
+    }
+    assert!(
+        script
+            .trim_end()
+            .ends_with("SELECT setval('vendas.pedidos_id_seq', 140, true);"),
+        "{script}"
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, GenerationError, OutputFormat};
+use datalchemy_plan::Plan;
+
+fn partition_usuarios_by_ativo(plan: &mut Plan) {
+    plan.rules.push(
+        serde_json::from_value(serde_json::json!({
+            "type": "output_partition",
+            "schema": "crm",
+            "table": "usuarios",
+            "column": "ativo"
+        }))
+        .expect("parse output_partition rule"),
+    );
+}
+
+#[test]
+fn partitioned_csv_uses_hive_directories() {
+    let (mut plan, schema) = load_plan_and_schema();
+    partition_usuarios_by_ativo(&mut plan);
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("partition"),
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let table_dir = result.run_dir.join("crm.usuarios");
+    let mut partitions: Vec<String> = fs::read_dir(&table_dir)
+        .expect("read partition dir")
+        .map(|entry| {
+            entry
+                .expect("entry")
+                .file_name()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    partitions.sort();
+    assert!(!partitions.is_empty());
+    assert!(
+        partitions
+            .iter()
+            .all(|name| name == "ativo=true" || name == "ativo=false")
+    );
+
+    let mut rows = 0;
+    for partition in &partitions {
+        let contents =
+            fs::read_to_string(table_dir.join(partition).join("part-0.csv")).expect("read part");
+        let mut lines = contents.lines();
+        let header = lines.next().expect("header");
+        assert!(!header.split(',').any(|column| column == "ativo"));
+        rows += lines.count();
+    }
+    assert_eq!(rows, 50);
+    assert!(result.run_dir.join("crm.oportunidades.csv").exists());
+}
+
+#[test]
+fn partitioning_single_file_formats_is_unsupported() {
+    let (mut plan, schema) = load_plan_and_schema();
+    partition_usuarios_by_ativo(&mut plan);
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("partition_sqlite"),
+        format: OutputFormat::Sqlite,
+        ..Default::default()
+    };
+
+    let err = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect_err("sqlite cannot be partitioned");
+    assert!(matches!(err, GenerationError::Unsupported(_)));
+}
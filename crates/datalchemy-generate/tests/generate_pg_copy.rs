@@ -0,0 +1,61 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+
+#[test]
+fn pg_copy_text_writes_rows_and_loader() {
+    let (plan, schema) = load_plan_and_schema();
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("copy_text"),
+        format: OutputFormat::PgCopy,
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let usuarios =
+        fs::read_to_string(result.run_dir.join("crm.usuarios.copy")).expect("read usuarios.copy");
+    assert_eq!(usuarios.lines().count(), 50, "no header, one line per row");
+    assert!(!result.run_dir.join("crm.usuarios.csv").exists());
+
+    let loader = fs::read_to_string(result.run_dir.join("load.sql")).expect("read load.sql");
+    let usuarios_pos = loader
+        .find("\\copy \"crm\".\"usuarios\"")
+        .expect("usuarios copy");
+    let oportunidades_pos = loader
+        .find("\\copy \"crm\".\"oportunidades\"")
+        .expect("oportunidades copy");
+    assert!(
+        usuarios_pos < oportunidades_pos,
+        "parents load before children"
+    );
+    assert!(loader.contains("FROM 'crm.usuarios.copy'"));
+    assert!(loader.trim_end().ends_with("COMMIT;"));
+}
+
+#[test]
+fn pg_copy_binary_writes_signature_and_trailer() {
+    let (plan, schema) = load_plan_and_schema();
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("copy_binary"),
+        format: OutputFormat::PgCopyBinary,
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let bytes =
+        fs::read(result.run_dir.join("crm.usuarios.copy.bin")).expect("read usuarios.copy.bin");
+    assert!(bytes.starts_with(b"PGCOPY\n\xff\r\n\0"));
+    assert!(bytes.ends_with(&(-1_i16).to_be_bytes()));
+
+    let loader = fs::read_to_string(result.run_dir.join("load.sql")).expect("read load.sql");
+    assert!(loader.contains("WITH (FORMAT binary)"));
+}
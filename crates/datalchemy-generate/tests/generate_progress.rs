@@ -0,0 +1,157 @@
+mod common;
+
+use std::fs;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_core::{EngineIssue, EngineObserver, RunOutcome, RunSummary, Stage};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, GenerationError, GenerationProgress};
+
+#[test]
+fn progress_reports_planned_tables_rows_and_completion() {
+    let (plan, schema) = load_plan_and_schema();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let result = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_dir("progress"),
+        ..Default::default()
+    })
+    .with_progress(move |event| sink.lock().expect("events lock").push(event))
+    .run(&schema, &plan)
+    .expect("run generation");
+
+    let events = events.lock().expect("events lock");
+    let GenerationProgress::Planned(planned) = &events[0] else {
+        panic!("first event is the plan: {:?}", events[0]);
+    };
+    assert_eq!(planned.len(), result.report.tables.len());
+
+    for table in &result.report.tables {
+        let last_rows = events.iter().rev().find_map(|event| match event {
+            GenerationProgress::Rows {
+                schema,
+                table: name,
+                rows,
+            } if *schema == table.schema && *name == table.table => Some(*rows),
+            _ => None,
+        });
+        assert_eq!(last_rows, Some(table.rows_generated));
+        assert!(events.contains(&GenerationProgress::TableFinished {
+            schema: table.schema.clone(),
+            table: table.table.clone(),
+            rows: table.rows_generated,
+            duration_ms: table.duration_ms,
+        }));
+    }
+}
+
+#[test]
+fn cancel_flag_stops_the_run() {
+    let (plan, schema) = load_plan_and_schema();
+    let out_dir = temp_out_dir("cancel");
+    let err = GenerationEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..Default::default()
+    })
+    .with_cancel(Arc::new(AtomicBool::new(true)))
+    .run(&schema, &plan)
+    .expect_err("cancelled run");
+    assert!(matches!(err, GenerationError::Cancelled));
+    assert_eq!(err.code(), "DL214");
+    let leftover = fs::read_dir(&out_dir).expect("read out dir").count();
+    assert_eq!(leftover, 0, "cancelled run directory is removed");
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<String>>,
+    summaries: Mutex<Vec<RunSummary>>,
+}
+
+impl EngineObserver for Recorder {
+    fn on_table_start(&self, _stage: Stage, schema: &str, table: &str) {
+        let mut events = self.events.lock().expect("events lock");
+        events.push(format!("start {schema}.{table}"));
+    }
+
+    fn on_row_batch(&self, _stage: Stage, schema: &str, table: &str, rows: u64) {
+        let mut events = self.events.lock().expect("events lock");
+        events.push(format!("rows {schema}.{table} {rows}"));
+    }
+
+    fn on_issue(&self, issue: &EngineIssue) {
+        let mut events = self.events.lock().expect("events lock");
+        events.push(format!("issue {}", issue.code));
+    }
+
+    fn on_finish(&self, summary: &RunSummary) {
+        self.summaries
+            .lock()
+            .expect("summaries lock")
+            .push(summary.clone());
+    }
+}
+
+#[test]
+fn observer_sees_tables_rows_and_summary() {
+    let (plan, schema) = load_plan_and_schema();
+    let recorder = Arc::new(Recorder::default());
+    let result = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_dir("observer"),
+        ..Default::default()
+    })
+    .with_observer(recorder.clone())
+    .run(&schema, &plan)
+    .expect("run generation");
+
+    let events = recorder.events.lock().expect("events lock");
+    for table in &result.report.tables {
+        let name = format!("{}.{}", table.schema, table.table);
+        let start = events
+            .iter()
+            .position(|event| *event == format!("start {name}"))
+            .expect("table start");
+        assert_eq!(
+            events[start + 1],
+            format!("rows {name} {}", table.rows_generated)
+        );
+    }
+    let issues = events
+        .iter()
+        .filter(|event| event.starts_with("issue "))
+        .count();
+    assert_eq!(
+        issues,
+        result.report.warnings.len() + result.report.unsupported.len()
+    );
+
+    let summaries = recorder.summaries.lock().expect("summaries lock");
+    assert_eq!(summaries.len(), 1);
+    let summary = &summaries[0];
+    assert_eq!(summary.stage, Stage::Generate);
+    assert_eq!(summary.run_id, result.report.run_id);
+    assert_eq!(summary.outcome, RunOutcome::Completed);
+    assert_eq!(summary.tables, result.report.tables.len() as u64);
+    assert_eq!(
+        summary.rows,
+        result
+            .report
+            .tables
+            .iter()
+            .map(|table| table.rows_generated)
+            .sum::<u64>()
+    );
+
+    let cancelled = Arc::new(Recorder::default());
+    GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_dir("observer_cancel"),
+        ..Default::default()
+    })
+    .with_cancel(Arc::new(AtomicBool::new(true)))
+    .with_observer(cancelled.clone())
+    .run(&schema, &plan)
+    .expect_err("cancelled run");
+    let summaries = cancelled.summaries.lock().expect("summaries lock");
+    assert_eq!(summaries[0].outcome, RunOutcome::Cancelled);
+}
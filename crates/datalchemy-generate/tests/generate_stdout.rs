@@ -0,0 +1,122 @@
+mod common;
+
+use std::collections::HashMap;
+use std::fs;
+
+use common::{load_plan_and_schema_with_rows, temp_out_path};
+use datalchemy_core::Table;
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, OutputFormat, TableOutput, TableSink,
+};
+
+#[test]
+fn stream_table_matches_full_run_without_run_dir() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let full_dir = temp_out_path("stream_full");
+    let result = GenerationEngine::new(GenerateOptions {
+        out_dir: full_dir,
+        ..Default::default()
+    })
+    .run(&schema, &plan)
+    .expect("run generation");
+    let expected = fs::read(result.run_dir.join("crm.empresas.csv")).expect("read generated csv");
+
+    let stream_dir = temp_out_path("stream_stdout");
+    let mut streamed = Vec::new();
+    let report = GenerationEngine::new(GenerateOptions {
+        out_dir: stream_dir.clone(),
+        ..Default::default()
+    })
+    .stream_table(&schema, &plan, "crm", "empresas", &mut streamed)
+    .expect("stream table");
+
+    assert_eq!(streamed, expected);
+    assert!(report.tables.iter().any(|table| table.table == "empresas"));
+    assert!(
+        !stream_dir.exists(),
+        "stream must not create a run directory"
+    );
+}
+
+#[test]
+fn stream_table_writes_copy_text_and_rejects_other_formats() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let mut streamed = Vec::new();
+    GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_path("stream_copy"),
+        format: OutputFormat::PgCopy,
+        ..Default::default()
+    })
+    .stream_table(&schema, &plan, "crm", "empresas", &mut streamed)
+    .expect("stream table");
+    let text = String::from_utf8(streamed).expect("utf8");
+    assert_eq!(text.lines().count(), 5);
+    assert!(text.lines().all(|line| line.contains('\t')));
+
+    let err = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_path("stream_avro"),
+        format: OutputFormat::Avro,
+        ..Default::default()
+    })
+    .stream_table(&schema, &plan, "crm", "empresas", &mut Vec::new())
+    .expect_err("avro cannot be streamed");
+    assert!(matches!(err, GenerationError::Unsupported(_)), "{err}");
+
+    let err = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_path("stream_missing"),
+        ..Default::default()
+    })
+    .stream_table(&schema, &plan, "crm", "missing", &mut Vec::new())
+    .expect_err("unknown table");
+    assert!(matches!(err, GenerationError::InvalidPlan(_)), "{err}");
+}
+
+/// Sink keeping the row count of every table it receives.
+#[derive(Default)]
+struct CountingSink {
+    tables: Vec<(String, usize)>,
+}
+
+impl TableSink for CountingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        self.tables
+            .push((format!("{schema_name}.{}", table.name), rows.len()));
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: table.columns.iter().map(|col| col.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+#[test]
+fn generate_to_sink_hands_every_table_without_run_dir() {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let out_dir = temp_out_path("to_sink");
+    let mut sink = CountingSink::default();
+    let report = GenerationEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..Default::default()
+    })
+    .generate_to_sink(&schema, &plan, &mut sink)
+    .expect("generate to sink");
+
+    assert_eq!(sink.tables.len(), report.tables.len());
+    for (table, rows) in report.tables.iter().zip(&sink.tables) {
+        assert_eq!(rows.0, format!("{}.{}", table.schema, table.table));
+        assert_eq!(rows.1 as u64, table.rows_generated);
+    }
+    assert!(
+        !out_dir.exists(),
+        "sink runs must not create a run directory"
+    );
+}
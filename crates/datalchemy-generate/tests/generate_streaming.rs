@@ -0,0 +1,83 @@
+#![cfg(feature = "streaming")]
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use common::{load_plan_and_schema_with_rows, temp_out_path};
+use datalchemy_core::DatabaseSchema;
+use datalchemy_generate::{GenerateOptions, GenerationEngine, RowBatch};
+use datalchemy_plan::Plan;
+use tokio_stream::StreamExt;
+
+fn csv_lines(batch: &RowBatch) -> Vec<String> {
+    batch
+        .rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(&batch.columns)
+                .map(|(value, column)| value.to_csv(column))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn streams_tables_in_insert_order_with_run_rows() {
+    let (plan, schema) = load_plan_and_schema_with_rows(10);
+    let out_dir = temp_out_path("streaming");
+    let engine = GenerationEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..Default::default()
+    });
+
+    let mut stream = engine.stream(&schema, &plan);
+    assert!(stream.take_result().is_none());
+    let mut batches = Vec::new();
+    while let Some(batch) = stream.next().await {
+        batches.push(batch);
+    }
+    let report = stream.take_result().expect("ended").expect("generation");
+    assert!(!out_dir.exists(), "no run directory");
+
+    let tables: Vec<&str> = batches.iter().map(|batch| batch.table.as_str()).collect();
+    let position = |name: &str| tables.iter().position(|table| *table == name).expect(name);
+    assert!(position("empresas") < position("contatos"));
+    assert!(position("usuarios") < position("oportunidades"));
+    assert_eq!(report.tables.len(), batches.len());
+
+    let usuarios = &batches[position("usuarios")];
+    assert_eq!(usuarios.schema, "crm");
+    assert_eq!(usuarios.rows.len(), 10);
+    assert!(
+        usuarios
+            .columns
+            .windows(2)
+            .all(|pair| pair[0].ordinal_position < pair[1].ordinal_position)
+    );
+
+    let result = engine.run(&schema, &plan).expect("run generation");
+    let csv = fs::read_to_string(result.run_dir.join("crm.usuarios.csv")).expect("read csv");
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let written: Vec<String> = reader
+        .records()
+        .map(|record| record.expect("record").iter().collect::<Vec<_>>().join(","))
+        .collect();
+    assert_eq!(csv_lines(usuarios), written);
+}
+
+#[tokio::test]
+async fn dropping_the_stream_stops_generation() {
+    let (plan, schema) = load_plan_and_schema_with_rows(10);
+    let engine = GenerationEngine::new(GenerateOptions::default());
+    let mut stream = engine.stream(&schema, &plan);
+    let first = stream.next().await.expect("first table");
+    drop(stream);
+
+    let mut again = engine.stream(&schema, &plan);
+    let replay = again.next().await.expect("first table");
+    assert_eq!(csv_lines(&first), csv_lines(&replay));
+}
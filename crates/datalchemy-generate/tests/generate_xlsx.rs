@@ -0,0 +1,59 @@
+mod common;
+
+use std::fs;
+
+use common::{load_plan_and_schema, temp_out_dir};
+use datalchemy_generate::output::xlsx::{XLSX_FILE_NAME, XlsxSink};
+use datalchemy_generate::{GenerateOptions, GenerationEngine, OutputFormat};
+
+#[test]
+fn xlsx_workbook_is_written() {
+    let (plan, schema) = load_plan_and_schema();
+    let options = GenerateOptions {
+        out_dir: temp_out_dir("xlsx"),
+        format: OutputFormat::Xlsx,
+        ..Default::default()
+    };
+
+    let result = GenerationEngine::new(options)
+        .run(&schema, &plan)
+        .expect("run generation");
+
+    let bytes = fs::read(result.run_dir.join(XLSX_FILE_NAME)).expect("read xlsx");
+    assert!(bytes.starts_with(b"PK"));
+    assert!(
+        !result
+            .report
+            .warnings_by_code
+            .contains_key("xlsx_rows_truncated")
+    );
+}
+
+#[test]
+fn xlsx_rows_over_cap_are_reported() {
+    let (plan, schema) = load_plan_and_schema();
+    let out_dir = temp_out_dir("xlsx_capped");
+    fs::create_dir_all(&out_dir).expect("create out dir");
+    let options = GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..Default::default()
+    };
+
+    let mut sink = XlsxSink::with_max_rows(out_dir.clone(), 10);
+    let result = GenerationEngine::new(options)
+        .run_with_sink(&schema, &plan, &mut sink)
+        .expect("run generation");
+
+    assert!(out_dir.join(XLSX_FILE_NAME).exists());
+    let truncated: Vec<_> = result
+        .report
+        .warnings
+        .iter()
+        .filter(|issue| issue.code == "xlsx_rows_truncated")
+        .collect();
+    assert!(
+        truncated
+            .iter()
+            .any(|issue| issue.table.as_deref() == Some("usuarios"))
+    );
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::temp_out_dir;
+use datalchemy_core::{Column, DatabaseSchema, SchemaBuilder, TableBuilder};
+use datalchemy_generate::generated::{compute, is_computable};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+/// Generated columns as Postgres renders them (with casts) and as written by
+/// hand, plus one the engine cannot compute.
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("itens")
+                .column("id", "integer")
+                .not_null()
+                .column("nome", "text")
+                .not_null()
+                .column("sobrenome", "text")
+                .column("preco", "numeric")
+                .not_null()
+                .column("quantidade", "integer")
+                .not_null()
+                .column("desconto", "numeric")
+                .column("total", "numeric")
+                .not_null()
+                .generated("((preco * (quantidade)::numeric) - (1)::numeric)")
+                .column("dobro", "integer")
+                .generated("-quantidade + quantidade * 2 / (1 + 1)")
+                .column("liquido", "numeric")
+                .generated("preco - desconto")
+                .column("nome_completo", "text")
+                .generated("((nome || ' '::text) || sobrenome)")
+                .column("rotulo", "text")
+                .generated("concat(nome, '-', desconto, quantidade)")
+                .column("nome_maiusculo", "text")
+                .not_null()
+                .generated("upper(nome)")
+                .primary_key(["id"])
+                .unique(["nome_maiusculo"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+fn column(name: &str) -> Column {
+    schema().schemas[0].tables[0]
+        .columns
+        .iter()
+        .find(|column| column.name == name)
+        .cloned()
+        .expect("column")
+}
+
+fn row() -> HashMap<String, GeneratedValue> {
+    HashMap::from([
+        ("nome".to_string(), GeneratedValue::Text("Ana".to_string())),
+        (
+            "sobrenome".to_string(),
+            GeneratedValue::Text("Lima".to_string()),
+        ),
+        ("preco".to_string(), GeneratedValue::Float(2.5)),
+        ("quantidade".to_string(), GeneratedValue::Int(4)),
+        ("desconto".to_string(), GeneratedValue::Null),
+    ])
+}
+
+#[test]
+fn computes_arithmetic_with_casts_and_precedence() {
+    assert_eq!(
+        compute(&column("total"), &row()),
+        Some(GeneratedValue::Float(9.0))
+    );
+    assert_eq!(
+        compute(&column("dobro"), &row()),
+        Some(GeneratedValue::Int(0))
+    );
+    assert_eq!(
+        compute(&column("liquido"), &row()),
+        Some(GeneratedValue::Null),
+        "null operands give null"
+    );
+}
+
+#[test]
+fn computes_concatenation() {
+    assert_eq!(
+        compute(&column("nome_completo"), &row()),
+        Some(GeneratedValue::Text("Ana Lima".to_string()))
+    );
+    assert_eq!(
+        compute(&column("rotulo"), &row()),
+        Some(GeneratedValue::Text("Ana-4".to_string())),
+        "concat() skips null arguments"
+    );
+
+    let mut row = row();
+    row.insert("sobrenome".to_string(), GeneratedValue::Null);
+    assert_eq!(
+        compute(&column("nome_completo"), &row),
+        Some(GeneratedValue::Null)
+    );
+}
+
+#[test]
+fn other_expressions_are_not_computable() {
+    assert!(!is_computable(&column("nome_maiusculo")));
+    assert!(!is_computable(&column("id")));
+    assert!(is_computable(&column("total")));
+
+    let mut row = row();
+    row.remove("quantidade");
+    assert_eq!(
+        compute(&column("total"), &row),
+        None,
+        "columns missing from the row cannot be read"
+    );
+}
+
+#[test]
+fn csv_output_computes_simple_expressions_and_omits_the_rest() {
+    let plan: Plan = serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 11,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "vendas", "table": "itens", "rows": 20 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let result = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_dir("generated_columns"),
+        ..Default::default()
+    })
+    .run(&schema(), &plan)
+    .expect("run generation");
+
+    let mut reader =
+        csv::Reader::from_path(result.run_dir.join("vendas.itens.csv")).expect("read csv");
+    let header = reader.headers().expect("header").clone();
+    assert!(header.iter().any(|column| column == "total"), "{header:?}");
+    assert!(
+        !header.iter().any(|column| column == "nome_maiusculo"),
+        "{header:?}"
+    );
+
+    let position = |name: &str| header.iter().position(|column| column == name).unwrap();
+    let mut rows = 0;
+    for record in reader.records() {
+        let record = record.expect("record");
+        let value = |name: &str| -> f64 { record[position(name)].parse().expect(name) };
+        let expected = value("preco") * value("quantidade") - 1.0;
+        assert!((value("total") - expected).abs() < 1e-6, "{record:?}");
+        rows += 1;
+    }
+    assert_eq!(rows, 20);
+
+    let report = &result.report;
+    assert_eq!(
+        report.column_sources["vendas.itens.total"].source,
+        "generated_expression"
+    );
+    assert_eq!(
+        report.warnings_by_code.get("generated_column_omitted"),
+        Some(&1)
+    );
+}
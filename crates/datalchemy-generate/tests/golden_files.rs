@@ -51,6 +51,7 @@ fn column(
         identity: None,
         generated: None,
         comment: None,
+        stats: None,
     }
 }
 
@@ -76,6 +77,17 @@ fn schema_fixture() -> DatabaseSchema {
             columns: vec!["id".to_string()],
         })],
         indexes: Vec::new(),
+        partition_key: None,
+        partitions: Vec::new(),
+        row_estimate: None,
+        triggers: Vec::new(),
+        rules: Vec::new(),
+        catalog_version: None,
+        hidden_columns: Vec::new(),
+        view_definition: None,
+        depends_on: Vec::new(),
+        foreign_table: None,
+        privileges: None,
     };
 
     let orders = Table {
@@ -124,6 +136,17 @@ fn schema_fixture() -> DatabaseSchema {
             }),
         ],
         indexes: Vec::new(),
+        partition_key: None,
+        partitions: Vec::new(),
+        row_estimate: None,
+        triggers: Vec::new(),
+        rules: Vec::new(),
+        catalog_version: None,
+        hidden_columns: Vec::new(),
+        view_definition: None,
+        depends_on: Vec::new(),
+        foreign_table: None,
+        privileges: None,
     };
 
     DatabaseSchema {
@@ -135,6 +158,12 @@ fn schema_fixture() -> DatabaseSchema {
             tables: vec![users, orders],
         }],
         enums: Vec::new(),
+        domains: Vec::new(),
+        composite_types: Vec::new(),
+        sequences: Vec::new(),
+        current_role: None,
+        failed_tables: Vec::new(),
+        external_tables: Vec::new(),
         schema_fingerprint: None,
     }
 }
@@ -153,6 +182,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("primitive.uuid.v4"),
         params: None,
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -161,6 +191,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("primitive.text.pattern"),
         params: Some(serde_json::json!({"pattern": "User-####"})),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -169,6 +200,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("derive.email_from_name"),
         params: Some(serde_json::json!({"input_columns": ["name"], "domain": "example.com"})),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -179,6 +211,7 @@ fn plan_fixture() -> Plan {
             serde_json::json!({"min": "2024-01-01T00:00:00", "max": "2024-01-10T23:59:59"}),
         ),
         transforms: Vec::new(),
+        entity: None,
     }));
 
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
@@ -188,6 +221,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("primitive.uuid.v4"),
         params: None,
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -196,6 +230,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("derive.fk"),
         params: None,
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -209,6 +244,7 @@ fn plan_fixture() -> Plan {
             "parent_column": "email"
         })),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -217,6 +253,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("primitive.float.range"),
         params: Some(serde_json::json!({"min": 10.0, "max": 120.0})),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -225,6 +262,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("primitive.int.range"),
         params: Some(serde_json::json!({"min": 1, "max": 5})),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -233,6 +271,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("primitive.float.range"),
         params: Some(serde_json::json!({"min": 0.0, "max": 5.0})),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -241,6 +280,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("derive.money_total"),
         params: Some(serde_json::json!({"input_columns": ["price", "qty", "discount"]})),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -251,6 +291,7 @@ fn plan_fixture() -> Plan {
             serde_json::json!({"min": "2024-01-01T00:00:00", "max": "2024-01-10T23:59:59"}),
         ),
         transforms: Vec::new(),
+        entity: None,
     }));
     rules.push(Rule::ColumnGenerator(ColumnGeneratorRule {
         schema: "public".to_string(),
@@ -259,6 +300,7 @@ fn plan_fixture() -> Plan {
         generator: gen_id("derive.updated_after_created"),
         params: Some(serde_json::json!({"input_columns": ["created_at"], "max_seconds": 86400})),
         transforms: Vec::new(),
+        entity: None,
     }));
 
     Plan {
@@ -286,6 +328,7 @@ fn plan_fixture() -> Plan {
         ],
         rules,
         rules_unsupported: Vec::new(),
+        assertions: Vec::new(),
         options: Some(PlanOptions {
             allow_fk_disable: None,
             strict: Some(true),
@@ -307,6 +350,7 @@ fn golden_files_are_stable() {
         max_attempts_row: 50,
         max_attempts_table: 3,
         auto_generate_parents: true,
+        ..Default::default()
     };
     let engine = GenerationEngine::new(options);
     let result = engine.run(&schema, &plan).expect("generation succeeds");
@@ -0,0 +1,313 @@
+mod common;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use common::temp_out_dir;
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationError, GenerationReport, MaskingEngine, RowSource, source_value,
+};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+/// Rows per `schema.table`; records the tables read.
+#[derive(Default)]
+struct MemoryRows {
+    tables: HashMap<String, Vec<HashMap<String, GeneratedValue>>>,
+    reads: Vec<String>,
+}
+
+impl RowSource for MemoryRows {
+    fn read_table(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        limit: u64,
+    ) -> Result<Vec<HashMap<String, GeneratedValue>>, GenerationError> {
+        let key = format!("{schema}.{}", table.name);
+        self.reads.push(key.clone());
+        let rows = self
+            .tables
+            .get(&key)
+            .ok_or_else(|| GenerationError::Source(format!("relation {key} does not exist")))?;
+        Ok(rows.iter().take(limit as usize).cloned().collect())
+    }
+}
+
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .table(
+            "crm",
+            TableBuilder::new("clientes")
+                .column("id", "integer")
+                .not_null()
+                .column("email", "text")
+                .column("cpf", "text")
+                .column("nascimento", "date")
+                .primary_key(["id"]),
+        )
+        .table(
+            "crm",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .not_null()
+                .column("cliente_id", "integer")
+                .column("email_contato", "text")
+                .primary_key(["id"])
+                .foreign_key(["cliente_id"], "clientes", ["id"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+fn source() -> MemoryRows {
+    let clientes = (1..=3)
+        .map(|id| {
+            HashMap::from([
+                ("id".to_string(), GeneratedValue::Int(id)),
+                (
+                    "email".to_string(),
+                    GeneratedValue::Text(format!("cliente{id}@example.com")),
+                ),
+                (
+                    "cpf".to_string(),
+                    GeneratedValue::Text(format!("123.456.789-0{id}")),
+                ),
+                (
+                    "nascimento".to_string(),
+                    GeneratedValue::Date(NaiveDate::from_ymd_opt(1990, 1, id as u32).unwrap()),
+                ),
+            ])
+        })
+        .collect();
+    let pedidos = (1..=4)
+        .map(|id| {
+            HashMap::from([
+                ("id".to_string(), GeneratedValue::Int(id)),
+                ("cliente_id".to_string(), GeneratedValue::Int(id % 3 + 1)),
+                (
+                    "email_contato".to_string(),
+                    GeneratedValue::Text(format!("contato{id}@example.com")),
+                ),
+            ])
+        })
+        .collect();
+    MemoryRows {
+        tables: HashMap::from([
+            ("crm.clientes".to_string(), clientes),
+            ("crm.pedidos".to_string(), pedidos),
+        ]),
+        reads: Vec::new(),
+    }
+}
+
+fn masking_rule(
+    table: &str,
+    column: &str,
+    transform: &str,
+    params: serde_json::Value,
+) -> serde_json::Value {
+    json!({
+        "type": "column_generator",
+        "schema": "crm",
+        "table": table,
+        "column": column,
+        "generator": "source",
+        "transforms": [{ "transform": transform, "params": params }]
+    })
+}
+
+fn plan(pedidos_rows: u64) -> Plan {
+    serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 7,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [
+            { "schema": "crm", "table": "clientes", "rows": 10 },
+            { "schema": "crm", "table": "pedidos", "rows": pedidos_rows }
+        ],
+        "rules": [
+            masking_rule("clientes", "id", "transform.mask", json!({ "mode": "hash" })),
+            masking_rule("clientes", "email", "transform.mask", json!({ "mode": "hash" })),
+            masking_rule("clientes", "cpf", "transform.mask", json!({ "mode": "format_preserving" })),
+            masking_rule("clientes", "nascimento", "transform.date_shift", json!({ "days": -30 })),
+            masking_rule("pedidos", "cliente_id", "transform.mask", json!({ "mode": "hash" })),
+        ]
+    }))
+    .expect("plan")
+}
+
+fn read_csv(path: &Path) -> Vec<HashMap<String, String>> {
+    let mut reader = csv::Reader::from_path(path).expect("open csv");
+    let headers = reader.headers().expect("headers").clone();
+    reader
+        .records()
+        .map(|record| {
+            let record = record.expect("record");
+            headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| (header.to_string(), value.to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn masks_real_rows_with_plan_transforms() {
+    let out_dir = temp_out_dir("mask");
+    let mut source = source();
+    let result = MaskingEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..GenerateOptions::default()
+    })
+    .run(&schema(), &plan(2), &mut source)
+    .expect("mask");
+
+    assert_eq!(source.reads, ["crm.clientes", "crm.pedidos"]);
+    let clientes = read_csv(&result.run_dir.join("crm.clientes.csv"));
+    let pedidos = read_csv(&result.run_dir.join("crm.pedidos.csv"));
+    assert_eq!(clientes.len(), 3);
+    assert_eq!(pedidos.len(), 2, "limited to the target rows");
+
+    let first = &clientes[0];
+    assert_eq!(first["email"].len(), 64, "sha-256 hex");
+    assert_ne!(first["email"], "cliente1@example.com");
+    assert_eq!(first["cpf"], "***.***.***-01");
+    assert_eq!(first["nascimento"], "1989-12-02");
+
+    // Hashed keys still join.
+    let masked_ids: Vec<&str> = clientes.iter().map(|row| row["id"].as_str()).collect();
+    for pedido in &pedidos {
+        assert!(masked_ids.contains(&pedido["cliente_id"].as_str()));
+    }
+    assert_eq!(pedidos[0]["email_contato"], "contato1@example.com");
+
+    let report: GenerationReport = serde_json::from_slice(
+        &fs::read(result.run_dir.join("generation_report.json")).expect("report"),
+    )
+    .expect("parse report");
+    let unmasked: Vec<&str> = report
+        .warnings
+        .iter()
+        .filter(|issue| issue.code == "unmasked_pii_column")
+        .filter_map(|issue| issue.column.as_deref())
+        .collect();
+    assert_eq!(unmasked, ["email_contato"]);
+    let sources = &report.column_sources;
+    assert_eq!(sources["crm.clientes.email"].source, "masked");
+    assert_eq!(sources["crm.pedidos.email_contato"].source, "source");
+    for artifact in [
+        "resolved_plan.json",
+        "schema.sql",
+        "files.json",
+        "data_dictionary.md",
+    ] {
+        assert!(result.run_dir.join(artifact).exists(), "{artifact}");
+    }
+
+    fs::remove_dir_all(out_dir).ok();
+}
+
+#[test]
+fn masking_is_reproducible() {
+    let out_dir = temp_out_dir("mask_repeat");
+    let engine = MaskingEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..GenerateOptions::default()
+    });
+    let mut plan = plan(4);
+    if let Some(datalchemy_plan::Rule::ColumnGenerator(rule)) = plan.rules.get_mut(3) {
+        rule.transforms[0].params = Some(json!({ "max_days": 365 }));
+    }
+    let first = engine.run(&schema(), &plan, &mut source()).expect("mask");
+    let second = engine.run(&schema(), &plan, &mut source()).expect("mask");
+    let read = |run_dir: &Path| fs::read_to_string(run_dir.join("crm.clientes.csv")).expect("csv");
+    assert_eq!(read(&first.run_dir), read(&second.run_dir));
+
+    fs::remove_dir_all(out_dir).ok();
+}
+
+#[test]
+fn source_errors_are_reported_with_the_table() {
+    let out_dir = temp_out_dir("mask_error");
+    let mut source = source();
+    source.tables.remove("crm.pedidos");
+    let err = MaskingEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..GenerateOptions::default()
+    })
+    .run(&schema(), &plan(2), &mut source)
+    .expect_err("missing table");
+
+    assert_eq!(err.code(), "DL215");
+    assert_eq!(
+        err.to_string(),
+        "crm.pedidos: source error: relation crm.pedidos does not exist"
+    );
+    let run_dir = fs::read_dir(&out_dir)
+        .expect("out dir")
+        .next()
+        .expect("run dir")
+        .expect("entry")
+        .path();
+    let report: GenerationReport =
+        serde_json::from_slice(&fs::read(run_dir.join("generation_report.json")).expect("report"))
+            .expect("parse report");
+    assert_eq!(report.error.expect("error").code, "DL215");
+
+    fs::remove_dir_all(out_dir).ok();
+}
+
+#[test]
+fn source_values_keep_their_column_type() {
+    let schema = SchemaBuilder::new()
+        .table(
+            "public",
+            TableBuilder::new("t")
+                .column("n", "bigint")
+                .column("d", "date")
+                .column("ts", "timestamp with time zone")
+                .column("j", "jsonb"),
+        )
+        .build()
+        .expect("schema");
+    let table = &schema.schemas[0].tables[0];
+    let column = |name: &str| {
+        table
+            .columns
+            .iter()
+            .find(|column| column.name == name)
+            .expect("column")
+    };
+
+    assert_eq!(
+        source_value(column("n"), &json!(42)),
+        GeneratedValue::Int(42)
+    );
+    assert_eq!(
+        source_value(column("d"), &json!("2024-02-29")),
+        GeneratedValue::Date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+    );
+    assert_eq!(
+        source_value(column("ts"), &json!("2024-01-01T03:00:00-03:00")),
+        GeneratedValue::Timestamp(
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(6, 0, 0)
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        source_value(column("j"), &json!({ "a": 1 })),
+        GeneratedValue::Text("{\"a\":1}".to_string())
+    );
+    assert_eq!(
+        source_value(column("d"), &json!(null)),
+        GeneratedValue::Null
+    );
+}
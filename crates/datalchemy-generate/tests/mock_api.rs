@@ -0,0 +1,68 @@
+mod common;
+
+use common::{load_plan_and_schema_with_rows, temp_out_path};
+use datalchemy_generate::mock_api::MockDataset;
+use datalchemy_generate::{GenerateOptions, GenerationEngine};
+
+fn load_dataset() -> MockDataset {
+    let (plan, schema) = load_plan_and_schema_with_rows(5);
+    let result = GenerationEngine::new(GenerateOptions {
+        out_dir: temp_out_path("mock_api"),
+        ..Default::default()
+    })
+    .run(&schema, &plan)
+    .expect("run generation");
+    MockDataset::load(&result.run_dir, &schema, &result.report).expect("load dataset")
+}
+
+#[test]
+fn mock_api_pages_filters_and_looks_up_rows() {
+    let dataset = load_dataset();
+
+    let index = dataset.handle("/");
+    assert_eq!(index.status, 200);
+    let tables = index.body["tables"].as_array().expect("tables");
+    assert!(
+        tables
+            .iter()
+            .any(|table| table["path"] == "/crm/empresas" && table["rows"] == 5)
+    );
+
+    let page = dataset.handle("/crm/empresas?limit=2&offset=1");
+    assert_eq!(page.status, 200);
+    assert_eq!(page.total, Some(5));
+    let data = page.body["data"].as_array().expect("data");
+    assert_eq!(data.len(), 2);
+    assert!(data[0]["ativo"].is_boolean(), "{}", data[0]);
+
+    let id = data[0]["id"].as_str().expect("id").to_string();
+    let row = dataset.handle(&format!("/crm/empresas/{id}"));
+    assert_eq!(row.status, 200);
+    assert_eq!(row.body["id"], id.as_str());
+
+    let filtered = dataset.handle(&format!("/crm/empresas?id={id}"));
+    assert_eq!(filtered.total, Some(1));
+
+    let sorted = dataset.handle("/crm/empresas?sort=-razao_social");
+    let names: Vec<&str> = sorted.body["data"]
+        .as_array()
+        .expect("data")
+        .iter()
+        .map(|row| row["razao_social"].as_str().expect("razao_social"))
+        .collect();
+    let mut expected = names.clone();
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn mock_api_reports_client_errors() {
+    let dataset = load_dataset();
+
+    assert_eq!(dataset.handle("/crm/missing").status, 404);
+    assert_eq!(dataset.handle("/crm/empresas/not-an-id").status, 404);
+    assert_eq!(dataset.handle("/crm/empresas?limit=abc").status, 400);
+    let unknown = dataset.handle("/crm/empresas?nope=1");
+    assert_eq!(unknown.status, 400);
+    assert_eq!(unknown.body["error"], "unknown column 'nope'");
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use datalchemy_core::{DatabaseSchema, PartitionStrategy, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Keeps the rows written per table, in write order.
+#[derive(Default)]
+struct CapturingSink {
+    tables: Vec<(String, Vec<Row>)>,
+}
+
+impl TableSink for CapturingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[Row],
+    ) -> Result<TableOutput, GenerationError> {
+        self.tables.push((table.name.clone(), rows.to_vec()));
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+/// `pedidos` listed by `uf` into two leaves; `itens` references the
+/// partitioned table and sorts before it by name.
+fn schema() -> DatabaseSchema {
+    let pedido = |name: &str| {
+        TableBuilder::new(name)
+            .column("id", "integer")
+            .column("uf", "text")
+            .primary_key(["id"])
+    };
+    SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("itens")
+                .column("id", "integer")
+                .column("pedido_id", "integer")
+                .not_null()
+                .primary_key(["id"])
+                .foreign_key(["pedido_id"], "pedidos", ["id"]),
+        )
+        .table(
+            "vendas",
+            pedido("pedidos")
+                .partition_by(PartitionStrategy::List, ["uf"])
+                .partition("pedidos_sp", "FOR VALUES IN ('SP')")
+                .partition("pedidos_rj", "FOR VALUES IN ('RJ')"),
+        )
+        .table("vendas", pedido("pedidos_sp"))
+        .table("vendas", pedido("pedidos_rj"))
+        .build()
+        .expect("schema")
+}
+
+fn uf_rule(table: &str, uf: &str) -> serde_json::Value {
+    json!({
+        "type": "column_generator",
+        "schema": "vendas",
+        "table": table,
+        "column": "uf",
+        "generator": "primitive.text.pattern",
+        "params": { "pattern": uf }
+    })
+}
+
+#[test]
+fn rows_are_routed_to_leaf_partitions() {
+    let plan: Plan = serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 11,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [
+            { "schema": "vendas", "table": "pedidos", "rows": 5 },
+            { "schema": "vendas", "table": "itens", "rows": 20 }
+        ],
+        "rules": [uf_rule("pedidos_sp", "SP"), uf_rule("pedidos_rj", "RJ")]
+    }))
+    .expect("plan");
+
+    let mut sink = CapturingSink::default();
+    GenerationEngine::new(GenerateOptions::default())
+        .generate_to_sink(&schema(), &plan, &mut sink)
+        .expect("generate");
+
+    let written: Vec<(&str, usize)> = sink
+        .tables
+        .iter()
+        .map(|(table, rows)| (table.as_str(), rows.len()))
+        .collect();
+    assert_eq!(
+        written,
+        [("pedidos_sp", 3), ("pedidos_rj", 2), ("itens", 20)],
+        "leaves replace the partitioned table, before the tables referencing it"
+    );
+
+    for (table, uf) in [(0, "SP"), (1, "RJ")] {
+        for row in &sink.tables[table].1 {
+            assert_eq!(row["uf"], GeneratedValue::Text(uf.to_string()));
+        }
+    }
+    let pedidos: Vec<&GeneratedValue> = sink.tables[..2]
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|row| &row["id"]))
+        .collect();
+    for item in &sink.tables[2].1 {
+        assert!(
+            pedidos.contains(&&item["pedido_id"]),
+            "{:?} references a leaf row",
+            item["pedido_id"]
+        );
+    }
+}
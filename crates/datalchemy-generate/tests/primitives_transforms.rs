@@ -27,6 +27,7 @@ fn test_column(name: &str, data_type: &str, is_nullable: bool) -> Column {
         identity: None,
         generated: None,
         comment: None,
+        stats: None,
     }
 }
 
@@ -84,3 +85,31 @@ fn null_rate_transform_rejects_not_null_column() {
     );
     assert!(matches!(result, Err(GenerationError::InvalidPlan(_))));
 }
+
+#[test]
+fn registry_exposes_param_specs_and_transform_ids() {
+    let registry = GeneratorRegistry::new();
+    let specs = registry
+        .generator("primitive.text.pattern")
+        .expect("generator exists")
+        .params();
+    assert!(
+        specs
+            .iter()
+            .any(|spec| spec.key == "pattern" && spec.required)
+    );
+    let specs = registry
+        .generator("primitive.int.range")
+        .expect("generator exists")
+        .params();
+    let keys: Vec<&str> = specs.iter().map(|spec| spec.key).collect();
+    assert_eq!(keys, vec!["min", "max"]);
+    assert!(
+        registry
+            .generator("primitive.uuid")
+            .expect("generator exists")
+            .params()
+            .is_empty()
+    );
+    assert!(registry.transform_ids().contains(&"transform.null_rate"));
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationError, MaskingEngine, RowSubset, RowSubsetOptions, SubsetSource,
+};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Rows per table, looked up like the Postgres source does.
+struct MemoryDatabase {
+    tables: HashMap<String, Vec<Row>>,
+}
+
+impl SubsetSource for MemoryDatabase {
+    fn seed_rows(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        _filter: Option<&str>,
+        limit: u64,
+    ) -> Result<Vec<Row>, GenerationError> {
+        let rows = &self.tables[&format!("{schema}.{}", table.name)];
+        Ok(rows.iter().take(limit as usize).cloned().collect())
+    }
+
+    fn rows_by_key(
+        &mut self,
+        schema: &str,
+        table: &Table,
+        columns: &[String],
+        keys: &[Vec<String>],
+        limit: Option<u64>,
+    ) -> Result<Vec<Row>, GenerationError> {
+        let rows = &self.tables[&format!("{schema}.{}", table.name)];
+        Ok(rows
+            .iter()
+            .filter(|row| {
+                let values: Vec<String> = columns
+                    .iter()
+                    .map(|name| {
+                        let column = table.columns.iter().find(|c| &c.name == name).unwrap();
+                        row[name].to_csv(column)
+                    })
+                    .collect();
+                keys.contains(&values)
+            })
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .cloned()
+            .collect())
+    }
+}
+
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .table(
+            "crm",
+            TableBuilder::new("empresas")
+                .column("id", "integer")
+                .not_null()
+                .primary_key(["id"]),
+        )
+        .table(
+            "crm",
+            TableBuilder::new("clientes")
+                .column("id", "integer")
+                .not_null()
+                .column("empresa_id", "integer")
+                .column("email", "text")
+                .primary_key(["id"])
+                .foreign_key(["empresa_id"], "empresas", ["id"]),
+        )
+        .table(
+            "crm",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .not_null()
+                .column("cliente_id", "integer")
+                .primary_key(["id"])
+                .foreign_key(["cliente_id"], "clientes", ["id"]),
+        )
+        .table(
+            "crm",
+            TableBuilder::new("avulsos")
+                .column("id", "integer")
+                .not_null()
+                .primary_key(["id"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+fn row(values: &[(&str, GeneratedValue)]) -> Row {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+/// 3 empresas, 6 clientes (empresa `id % 3 + 1`), 12 pedidos (cliente
+/// `id % 6 + 1`) and a table outside every foreign key.
+fn database() -> MemoryDatabase {
+    let int = GeneratedValue::Int;
+    let empresas = (1..=3).map(|id| row(&[("id", int(id))])).collect();
+    let clientes = (1..=6)
+        .map(|id| {
+            row(&[
+                ("id", int(id)),
+                ("empresa_id", int(id % 3 + 1)),
+                (
+                    "email",
+                    GeneratedValue::Text(format!("cliente{id}@example.com")),
+                ),
+            ])
+        })
+        .collect();
+    let pedidos = (1..=12)
+        .map(|id| row(&[("id", int(id)), ("cliente_id", int(id % 6 + 1))]))
+        .collect();
+    let avulsos = (1..=2).map(|id| row(&[("id", int(id))])).collect();
+    MemoryDatabase {
+        tables: HashMap::from([
+            ("crm.empresas".to_string(), empresas),
+            ("crm.clientes".to_string(), clientes),
+            ("crm.pedidos".to_string(), pedidos),
+            ("crm.avulsos".to_string(), avulsos),
+        ]),
+    }
+}
+
+fn ids(subset: &RowSubset, table: &str) -> Vec<i64> {
+    let mut ids: Vec<i64> = subset
+        .rows("crm", table)
+        .iter()
+        .filter_map(|row| row["id"].as_i64())
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+fn references(subset: &RowSubset, table: &str, column: &str) -> Vec<i64> {
+    subset
+        .rows("crm", table)
+        .iter()
+        .filter_map(|row| row[column].as_i64())
+        .collect()
+}
+
+fn options(seed_rows: u64) -> RowSubsetOptions {
+    RowSubsetOptions {
+        seed: "clientes".to_string(),
+        seed_rows,
+        ..RowSubsetOptions::default()
+    }
+}
+
+#[test]
+fn follows_foreign_keys_up_and_down() {
+    let subset = RowSubset::extract(&schema(), &options(2), &mut database()).expect("subset");
+
+    assert_eq!(ids(&subset, "clientes"), [1, 2]);
+    assert_eq!(ids(&subset, "empresas"), [2, 3], "parents of the seed rows");
+    assert_eq!(
+        ids(&subset, "pedidos"),
+        [1, 6, 7, 12],
+        "children of the seed rows"
+    );
+    for cliente in references(&subset, "pedidos", "cliente_id") {
+        assert!(ids(&subset, "clientes").contains(&cliente));
+    }
+    assert!(subset.rows("crm", "avulsos").is_empty());
+    let tables: Vec<&str> = subset.schema.schemas[0]
+        .tables
+        .iter()
+        .map(|table| table.name.as_str())
+        .collect();
+    assert_eq!(tables, ["empresas", "clientes", "pedidos"]);
+}
+
+#[test]
+fn caps_child_rows_but_keeps_their_parents() {
+    let subset = RowSubset::extract(
+        &schema(),
+        &RowSubsetOptions {
+            max_rows_per_table: 1,
+            ..options(6)
+        },
+        &mut database(),
+    )
+    .expect("subset");
+
+    assert_eq!(ids(&subset, "clientes").len(), 6, "seed rows");
+    assert_eq!(ids(&subset, "pedidos").len(), 1);
+    assert_eq!(ids(&subset, "empresas"), [1, 2, 3]);
+
+    let parents_only = RowSubset::extract(
+        &schema(),
+        &RowSubsetOptions {
+            include_children: false,
+            ..options(1)
+        },
+        &mut database(),
+    )
+    .expect("subset");
+    assert_eq!(ids(&parents_only, "clientes"), [1]);
+    assert_eq!(ids(&parents_only, "empresas"), [2]);
+    assert!(parents_only.rows("crm", "pedidos").is_empty());
+}
+
+#[test]
+fn masked_subset_is_written_like_a_run() {
+    let mut subset = RowSubset::extract(&schema(), &options(2), &mut database()).expect("subset");
+    let base: Plan = serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 3,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "crm", "table": "clientes", "rows": 1 }],
+        "rules": [{
+            "type": "column_generator",
+            "schema": "crm",
+            "table": "clientes",
+            "column": "email",
+            "generator": "source",
+            "transforms": [{ "transform": "transform.mask", "params": { "mode": "redact" } }]
+        }]
+    }))
+    .expect("plan");
+    let plan = subset.plan(Some(&base));
+    let targets: Vec<(&str, u64)> = plan
+        .targets
+        .iter()
+        .map(|target| (target.table.as_str(), target.rows))
+        .collect();
+    assert_eq!(targets, [("empresas", 2), ("clientes", 2), ("pedidos", 4)]);
+    assert_eq!(plan.seed, 3);
+
+    let mut out_dir = std::env::temp_dir();
+    out_dir.push(format!(
+        "datalchemy_generate_subset_{}",
+        uuid::Uuid::new_v4()
+    ));
+    let schema = subset.schema.clone();
+    let result = MaskingEngine::new(GenerateOptions {
+        out_dir: out_dir.clone(),
+        ..GenerateOptions::default()
+    })
+    .run(&schema, &plan, &mut subset)
+    .expect("write subset");
+
+    let clientes = fs::read_to_string(result.run_dir.join("crm.clientes.csv")).expect("csv");
+    assert!(clientes.contains("***"));
+    assert!(!clientes.contains("example.com"));
+    let pedidos = fs::read_to_string(result.run_dir.join("crm.pedidos.csv")).expect("csv");
+    assert_eq!(pedidos.lines().count(), 5, "header and 4 rows");
+    assert!(
+        !PathBuf::from(&result.run_dir)
+            .join("crm.avulsos.csv")
+            .exists()
+    );
+
+    fs::remove_dir_all(out_dir).ok();
+}
@@ -0,0 +1,230 @@
+#![cfg(feature = "object-store")]
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_trait::async_trait;
+use common::temp_out_path;
+use datalchemy_generate::upload::{MULTIPART_THRESHOLD, build_store, upload_dir_to};
+use datalchemy_generate::{GenerationError, ObjectStoreTarget};
+use futures_util::stream::BoxStream;
+use object_store::memory::InMemory;
+use object_store::path::Path as ObjectPath;
+use object_store::{
+    GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, ObjectStore,
+    PutMultipartOptions, PutOptions, PutPayload, PutResult, UploadPart,
+};
+
+#[tokio::test]
+async fn upload_dir_keeps_relative_layout_and_uses_multipart_for_large_files() {
+    let dir = temp_out_path("upload");
+    fs::create_dir_all(dir.join("crm.usuarios/ativo=true")).expect("create dirs");
+    fs::write(dir.join("crm.contatos.csv"), "id\n1\n").expect("write csv");
+    fs::write(dir.join("crm.usuarios/ativo=true/part-0.csv"), "id\n2\n").expect("write part");
+    fs::write(
+        dir.join("dataset.bin"),
+        vec![7_u8; MULTIPART_THRESHOLD as usize + 1],
+    )
+    .expect("write large file");
+
+    let store = InMemory::new();
+    let uploaded = upload_dir_to(&store, &dir, "datalchemy/out_1")
+        .await
+        .expect("upload");
+
+    let keys: Vec<&str> = uploaded.iter().map(|object| object.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        vec![
+            "datalchemy/out_1/crm.contatos.csv",
+            "datalchemy/out_1/crm.usuarios/ativo=true/part-0.csv",
+            "datalchemy/out_1/dataset.bin",
+        ]
+    );
+    assert!(uploaded[2].multipart);
+    assert!(!uploaded[0].multipart);
+
+    let large = store
+        .head(&ObjectPath::from("datalchemy/out_1/dataset.bin"))
+        .await
+        .expect("head large object");
+    assert_eq!(large.size, MULTIPART_THRESHOLD + 1);
+}
+
+#[tokio::test]
+async fn failed_multipart_upload_is_aborted() {
+    let dir = temp_out_path("upload_abort");
+    fs::create_dir_all(&dir).expect("create dir");
+    fs::write(
+        dir.join("dataset.bin"),
+        vec![7_u8; MULTIPART_THRESHOLD as usize + 1],
+    )
+    .expect("write large file");
+
+    let store = FailingCompleteStore::default();
+    let err = upload_dir_to(&store, &dir, "out")
+        .await
+        .expect_err("complete fails");
+    assert!(matches!(err, GenerationError::ObjectStore(_)), "{err}");
+    assert!(store.aborted.load(Ordering::SeqCst));
+}
+
+#[test]
+fn target_url_is_split_into_bucket_and_prefix() {
+    let target = ObjectStoreTarget {
+        url: "s3://datasets/team/qa/".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        target.parts().expect("parts"),
+        ("s3", "datasets", "team/qa")
+    );
+
+    let missing_bucket = ObjectStoreTarget {
+        url: "s3:///prefix".to_string(),
+        ..Default::default()
+    };
+    assert!(missing_bucket.parts().is_err());
+}
+
+#[test]
+fn backends_are_selected_by_url_scheme() {
+    let credentials = BTreeMap::from([
+        (
+            "AZURE_STORAGE_ACCOUNT_NAME".to_string(),
+            "devstore".to_string(),
+        ),
+        (
+            "AZURE_STORAGE_ACCOUNT_KEY".to_string(),
+            "ZGF0YWxjaGVteQ==".to_string(),
+        ),
+        ("AWS_ACCESS_KEY_ID".to_string(), "minio".to_string()),
+        ("AWS_SECRET_ACCESS_KEY".to_string(), "minio123".to_string()),
+    ]);
+
+    let azure = ObjectStoreTarget {
+        url: "az://datasets/qa".to_string(),
+        ..Default::default()
+    };
+    let store = build_store(&azure, &credentials).expect("azure store");
+    assert!(store.to_string().contains("Azure"));
+
+    let s3 = ObjectStoreTarget {
+        url: "s3://datasets/qa".to_string(),
+        endpoint: Some("http://localhost:9000".to_string()),
+        region: Some("us-east-1".to_string()),
+        allow_http: true,
+    };
+    let store = build_store(&s3, &credentials).expect("s3 store");
+    assert!(store.to_string().contains("AmazonS3"));
+
+    let ftp = ObjectStoreTarget {
+        url: "ftp://datasets/qa".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        build_store(&ftp, &credentials),
+        Err(GenerationError::Unsupported(_))
+    ));
+}
+
+/// In-memory store whose multipart uploads fail on `complete`.
+#[derive(Debug, Default)]
+struct FailingCompleteStore {
+    inner: InMemory,
+    aborted: Arc<AtomicBool>,
+}
+
+impl fmt::Display for FailingCompleteStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FailingCompleteStore")
+    }
+}
+
+#[derive(Debug)]
+struct FailingCompleteUpload {
+    inner: Box<dyn MultipartUpload>,
+    aborted: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl MultipartUpload for FailingCompleteUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        self.inner.put_part(data)
+    }
+
+    async fn complete(&mut self) -> object_store::Result<PutResult> {
+        Err(object_store::Error::NotImplemented)
+    }
+
+    async fn abort(&mut self) -> object_store::Result<()> {
+        self.aborted.store(true, Ordering::SeqCst);
+        self.inner.abort().await
+    }
+}
+
+#[async_trait]
+impl ObjectStore for FailingCompleteStore {
+    async fn put_opts(
+        &self,
+        location: &ObjectPath,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        self.inner.put_opts(location, payload, opts).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &ObjectPath,
+        opts: PutMultipartOptions,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        Ok(Box::new(FailingCompleteUpload {
+            inner: self.inner.put_multipart_opts(location, opts).await?,
+            aborted: Arc::clone(&self.aborted),
+        }))
+    }
+
+    async fn get_opts(
+        &self,
+        location: &ObjectPath,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        self.inner.get_opts(location, options).await
+    }
+
+    async fn delete(&self, location: &ObjectPath) -> object_store::Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&ObjectPath>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&ObjectPath>,
+    ) -> object_store::Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &ObjectPath, to: &ObjectPath) -> object_store::Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &ObjectPath,
+        to: &ObjectPath,
+    ) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+}
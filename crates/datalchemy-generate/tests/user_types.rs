@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_plan::Plan;
+use serde_json::json;
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Keeps the table (as handed to the sink) and its rows.
+#[derive(Default)]
+struct CapturingSink {
+    tables: Vec<(Table, Vec<Row>)>,
+}
+
+impl TableSink for CapturingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[Row],
+    ) -> Result<TableOutput, GenerationError> {
+        self.tables.push((table.clone(), rows.to_vec()));
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+/// Domains written by hand and as Postgres renders them (with casts), a
+/// domain over a domain and a composite type.
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .domain_type("vendas", "quantidade", "integer", ["VALUE BETWEEN 1 AND 5"])
+        .domain_type(
+            "vendas",
+            "preco",
+            "numeric(10,2)",
+            ["CHECK ((VALUE >= (10)::numeric))"],
+        )
+        .domain_type(
+            "vendas",
+            "preco_promocional",
+            "vendas.preco",
+            ["CHECK (((VALUE)::numeric <= (20)::numeric))"],
+        )
+        .composite_type(
+            "vendas",
+            "endereco",
+            [
+                ("rua", "text"),
+                ("numero", "integer"),
+                ("cep", "character varying(8)"),
+            ],
+        )
+        .table(
+            "vendas",
+            TableBuilder::new("itens")
+                .column("id", "integer")
+                .column("quantidade", "vendas.quantidade")
+                .column("preco", "preco_promocional")
+                .column("entrega", "vendas.endereco")
+                .primary_key(["id"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+#[test]
+fn domain_columns_follow_base_type_and_checks() {
+    let plan: Plan = serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 5,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "vendas", "table": "itens", "rows": 50 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let mut sink = CapturingSink::default();
+    let report = GenerationEngine::new(GenerateOptions::default())
+        .generate_to_sink(&schema(), &plan, &mut sink)
+        .expect("generate");
+    assert_eq!(report.tables[0].rows_generated, 50);
+
+    let (table, rows) = &sink.tables[0];
+    let udt = |column: &str| {
+        table
+            .columns
+            .iter()
+            .find(|candidate| candidate.name == column)
+            .map(|column| column.column_type.udt_name.as_str())
+    };
+    assert_eq!(udt("quantidade"), Some("int4"));
+    assert_eq!(udt("preco"), Some("numeric"), "domain over domain");
+    assert_eq!(udt("entrega"), Some("endereco"));
+
+    let row_literal = regex::Regex::new(r#"^\((\S+|"[^"]*"),-?\d+,(\S+|"[^"]*")\)$"#).unwrap();
+    for row in rows {
+        let quantidade = row["quantidade"].as_f64().expect("quantidade");
+        assert!((1.0..=5.0).contains(&quantidade), "{quantidade}");
+        let preco = row["preco"].as_f64().expect("preco");
+        assert!((10.0..=20.0).contains(&preco), "{preco}");
+        let entrega = row["entrega"].as_str().expect("entrega");
+        assert!(row_literal.is_match(entrega), "{entrega}");
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, Table, TableBuilder};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::profile::ColumnProfile;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableProfile, TableSink,
+    ValueProfile,
+};
+use datalchemy_plan::Plan;
+use serde_json::{Value, json};
+
+type Row = HashMap<String, GeneratedValue>;
+
+/// Keeps the rows written per table.
+#[derive(Default)]
+struct CapturingSink {
+    tables: Vec<Vec<Row>>,
+}
+
+impl TableSink for CapturingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[Row],
+    ) -> Result<TableOutput, GenerationError> {
+        self.tables.push(rows.to_vec());
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+fn schema() -> DatabaseSchema {
+    SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .column("status", "text")
+                .not_null()
+                .column("quantidade", "integer")
+                .column("criado_em", "date")
+                .not_null()
+                .column("observacao", "text")
+                .primary_key(["id"]),
+        )
+        .build()
+        .expect("schema")
+}
+
+fn column_profile(column: &str, values: &[Value]) -> ColumnProfile {
+    let schema = schema();
+    let table = &schema.schemas[0].tables[0];
+    let column = table
+        .columns
+        .iter()
+        .find(|candidate| candidate.name == column)
+        .expect("column");
+    ColumnProfile::from_values(column, values, 20)
+}
+
+#[test]
+fn column_profiles_summarize_sampled_values() {
+    let status = column_profile(
+        "status",
+        &[
+            json!("aberto"),
+            json!("fechado"),
+            json!("aberto"),
+            Value::Null,
+        ],
+    );
+    assert_eq!((status.values, status.distinct), (3, 2));
+    assert_eq!(status.null_frac, 0.25);
+    assert!(status.is_categorical());
+    assert_eq!(status.top_values[0].value, json!("aberto"));
+    assert_eq!(status.top_values[0].count, 2);
+    assert_eq!(status.min, None, "text is not ranged");
+
+    let quantidade = column_profile("quantidade", &[json!(7), json!(3), json!(12)]);
+    assert_eq!(
+        (&quantidade.min, &quantidade.max),
+        (&Some(json!(3)), &Some(json!(12)))
+    );
+    assert!(quantidade.top_values.is_empty(), "no value repeats");
+    assert!(!quantidade.is_categorical());
+
+    let criado_em = column_profile("criado_em", &[json!("2024-03-10"), json!("2023-12-01")]);
+    assert_eq!(criado_em.min, Some(json!("2023-12-01")));
+    assert_eq!(criado_em.max, Some(json!("2024-03-10")));
+}
+
+#[test]
+fn unruled_columns_follow_the_profile() {
+    let profile = ValueProfile {
+        connection: None,
+        sample_rows: 4,
+        tables: vec![TableProfile {
+            schema: "vendas".to_string(),
+            table: "pedidos".to_string(),
+            sampled_rows: 4,
+            columns: vec![
+                column_profile(
+                    "status",
+                    &[
+                        json!("aberto"),
+                        json!("fechado"),
+                        json!("aberto"),
+                        json!("aberto"),
+                    ],
+                ),
+                column_profile("quantidade", &[json!(7), json!(3), json!(12), json!(5)]),
+                column_profile(
+                    "criado_em",
+                    &[
+                        json!("2024-03-10"),
+                        json!("2023-12-01"),
+                        json!("2024-01-15"),
+                        json!("2024-02-02"),
+                    ],
+                ),
+                column_profile("observacao", &vec![Value::Null; 4]),
+            ],
+            redacted_columns: Vec::new(),
+        }],
+    };
+    let plan: Plan = serde_json::from_value(json!({
+        "plan_version": "0.2",
+        "seed": 3,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "vendas", "table": "pedidos", "rows": 200 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let mut sink = CapturingSink::default();
+    GenerationEngine::new(GenerateOptions::default())
+        .with_profile(profile)
+        .generate_to_sink(&schema(), &plan, &mut sink)
+        .expect("generate");
+
+    let rows = &sink.tables[0];
+    let first = NaiveDate::from_ymd_opt(2023, 12, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let mut abertos = 0;
+    for row in rows {
+        match &row["status"] {
+            GeneratedValue::Text(status) if status == "aberto" => abertos += 1,
+            GeneratedValue::Text(status) => assert_eq!(status, "fechado"),
+            other => panic!("status {other:?}"),
+        }
+        let quantidade = row["quantidade"].as_f64().expect("quantidade");
+        assert!((3.0..=12.0).contains(&quantidade), "{quantidade}");
+        match row["criado_em"] {
+            GeneratedValue::Date(date) => assert!(first <= date && date <= last, "{date}"),
+            ref other => panic!("criado_em {other:?}"),
+        }
+        assert_eq!(
+            row["observacao"],
+            GeneratedValue::Null,
+            "always null in the sample"
+        );
+    }
+    assert!(
+        (120..=180).contains(&abertos),
+        "{abertos} of 200 follow the 3:1 weight"
+    );
+}
@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use datalchemy_core::{DatabaseSchema, Result};
 
 use crate::options::IntrospectOptions;
+use crate::timings::IntrospectTimings;
 
 /// Trait implemented by database adapters that can introspect schemas.
 #[async_trait]
@@ -12,4 +13,16 @@ pub trait Adapter {
 
     /// Introspect the database and return a schema snapshot.
     async fn introspect(&self, opts: &IntrospectOptions) -> Result<DatabaseSchema>;
+
+    /// Introspect reusing the unchanged tables of `previous` and report the
+    /// time of each phase. By default `previous` is ignored and no phase is
+    /// timed.
+    async fn introspect_timed(
+        &self,
+        opts: &IntrospectOptions,
+        previous: Option<&DatabaseSchema>,
+    ) -> Result<(DatabaseSchema, IntrospectTimings)> {
+        let _ = previous;
+        Ok((self.introspect(opts).await?, IntrospectTimings::default()))
+    }
 }
@@ -0,0 +1,180 @@
+//! Map raw DuckDB catalog rows to datalchemy-core types.
+
+use datalchemy_core::types::ColumnType;
+use datalchemy_core::{
+    CheckConstraint, Column, Constraint, FkAction, FkMatchType, ForeignKey, Index, PrimaryKey,
+    Table, TableKind, UniqueConstraint,
+};
+
+use super::queries::{LIST_SEPARATOR, RawColumn, RawConstraint, RawIndex, RawTable};
+
+/// Build a [`Table`] (or view) from its raw row and its columns.
+pub fn map_table(raw: RawTable, raw_columns: Vec<RawColumn>) -> Table {
+    let columns = raw_columns.into_iter().map(map_column).collect();
+    let (kind, view_definition) = match raw.view_sql {
+        Some(sql) => (TableKind::View, Some(view_query(&sql))),
+        None => (TableKind::Table, None),
+    };
+
+    Table {
+        name: raw.name,
+        kind,
+        comment: raw.comment,
+        columns,
+        constraints: Vec::new(),
+        indexes: Vec::new(),
+        partition_key: None,
+        partitions: Vec::new(),
+        row_estimate: raw.estimated_size.map(|size| size.max(0) as u64),
+        triggers: Vec::new(),
+        rules: Vec::new(),
+        catalog_version: None,
+        hidden_columns: Vec::new(),
+        view_definition,
+        depends_on: Vec::new(),
+        foreign_table: None,
+        privileges: None,
+    }
+}
+
+fn map_column(raw: RawColumn) -> Column {
+    Column {
+        ordinal_position: raw.ordinal as i16,
+        name: raw.name,
+        column_type: map_column_type(
+            &raw.data_type,
+            raw.character_max_length,
+            raw.numeric_precision,
+            raw.numeric_scale,
+        ),
+        is_nullable: raw.is_nullable,
+        default: raw.default,
+        identity: None,
+        generated: None,
+        comment: raw.comment,
+        stats: None,
+    }
+}
+
+/// Map a DuckDB type name to a [`ColumnType`] whose `udt_name` uses the
+/// Postgres spelling, so generators pick the same defaults for both engines.
+fn map_column_type(
+    raw: &str,
+    character_max_length: Option<i32>,
+    numeric_precision: Option<i32>,
+    numeric_scale: Option<i32>,
+) -> ColumnType {
+    let upper = raw.to_ascii_uppercase();
+    let base = upper.split('(').next().unwrap_or_default().trim();
+    let udt_name = match base {
+        "TINYINT" | "SMALLINT" | "UTINYINT" => "int2",
+        "INTEGER" | "USMALLINT" => "int4",
+        "BIGINT" | "UINTEGER" | "HUGEINT" | "UBIGINT" => "int8",
+        "REAL" | "FLOAT" => "float4",
+        "DOUBLE" => "float8",
+        "DECIMAL" => "numeric",
+        "BOOLEAN" => "bool",
+        "UUID" => "uuid",
+        "DATE" => "date",
+        "TIME" => "time",
+        "TIMESTAMP" | "TIMESTAMP_NS" | "TIMESTAMP_MS" | "TIMESTAMP_S" => "timestamp",
+        "TIMESTAMP WITH TIME ZONE" | "TIMESTAMPTZ" => "timestamptz",
+        "INTERVAL" => "interval",
+        "BLOB" => "bytea",
+        "JSON" => "json",
+        "VARCHAR" if character_max_length.is_some() => "varchar",
+        "VARCHAR" => "text",
+        _ => base,
+    };
+    let is_numeric = udt_name == "numeric";
+
+    ColumnType {
+        data_type: raw.to_string(),
+        udt_schema: "pg_catalog".to_string(),
+        udt_name: udt_name.to_ascii_lowercase(),
+        character_max_length,
+        numeric_precision: numeric_precision.filter(|_| is_numeric),
+        numeric_scale: numeric_scale.filter(|_| is_numeric),
+        collation: None,
+    }
+}
+
+/// Keep only the query of a `CREATE VIEW ... AS <query>;` statement, which is
+/// how [`Table::view_definition`] stores it for every engine.
+fn view_query(sql: &str) -> String {
+    let upper = sql.to_ascii_uppercase();
+    let query = match upper.find(" AS ") {
+        Some(idx) => &sql[idx + 4..],
+        None => sql,
+    };
+    query.trim().trim_end_matches(';').trim_end().to_string()
+}
+
+fn split_list(raw: Option<&str>) -> Vec<String> {
+    raw.filter(|list| !list.is_empty())
+        .map(|list| list.split(LIST_SEPARATOR).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Map raw constraint rows of one table. DuckDB has no referential actions
+/// or deferrable constraints, so foreign keys always use `NO ACTION`.
+pub fn map_constraints(schema: &str, raw: Vec<RawConstraint>) -> Vec<Constraint> {
+    let mut constraints: Vec<Constraint> = Vec::new();
+    for row in raw {
+        let columns = split_list(row.columns.as_deref());
+        let constraint = match row.constraint_type.as_str() {
+            "PRIMARY KEY" => Constraint::PrimaryKey(PrimaryKey {
+                name: row.name,
+                columns,
+            }),
+            "UNIQUE" => Constraint::Unique(UniqueConstraint {
+                name: row.name,
+                columns,
+                is_deferrable: false,
+                initially_deferred: false,
+            }),
+            "CHECK" => match row.expression {
+                Some(expression) => Constraint::Check(CheckConstraint {
+                    name: row.name,
+                    expression,
+                }),
+                None => continue,
+            },
+            "FOREIGN KEY" => match row.referenced_table {
+                Some(referenced_table) => Constraint::ForeignKey(ForeignKey {
+                    name: row.name,
+                    columns,
+                    referenced_schema: schema.to_string(),
+                    referenced_table,
+                    referenced_columns: split_list(row.referenced_columns.as_deref()),
+                    on_update: FkAction::NoAction,
+                    on_delete: FkAction::NoAction,
+                    match_type: FkMatchType::Simple,
+                    is_deferrable: false,
+                    initially_deferred: false,
+                }),
+                None => continue,
+            },
+            _ => continue,
+        };
+        constraints.push(constraint);
+    }
+    constraints
+}
+
+/// Map explicitly created indexes; DuckDB only builds ART indexes.
+pub fn map_indexes(raw: Vec<RawIndex>) -> Vec<Index> {
+    raw.into_iter()
+        .map(|idx| Index {
+            name: idx.name,
+            is_unique: idx.is_unique,
+            is_primary: idx.is_primary,
+            is_valid: true,
+            method: "art".to_string(),
+            definition: idx.sql.unwrap_or_default(),
+            predicate: None,
+            expressions: Vec::new(),
+            shard_buckets: None,
+        })
+        .collect()
+}
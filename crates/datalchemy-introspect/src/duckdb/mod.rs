@@ -0,0 +1,171 @@
+//! DuckDB database introspection adapter (feature `duckdb`).
+
+mod mapper;
+mod queries;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use ::duckdb::{AccessMode, Config, Connection};
+
+use datalchemy_core::{DatabaseSchema, Result, SCHEMA_VERSION, Schema, Table};
+
+use crate::adapter::Adapter;
+use crate::filter;
+use crate::options::IntrospectOptions;
+use crate::registry::AdapterFactory;
+
+/// Schemas DuckDB creates for its own catalog.
+const SYSTEM_SCHEMAS: &[&str] = &["information_schema", "pg_catalog"];
+
+/// Adapter for DuckDB database files.
+///
+/// `duckdb::Connection` is not `Sync`, so the file is opened (read-only) on
+/// each introspection; the catalog calls are blocking.
+#[derive(Debug, Clone)]
+pub struct DuckDbAdapter {
+    path: PathBuf,
+}
+
+impl DuckDbAdapter {
+    /// Create a new adapter for the DuckDB file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl Adapter for DuckDbAdapter {
+    fn engine(&self) -> &'static str {
+        "duckdb"
+    }
+
+    async fn introspect(&self, opts: &IntrospectOptions) -> Result<DatabaseSchema> {
+        introspect_duckdb_with_options(&self.path, opts.clone())
+    }
+}
+
+/// Opens a [`DuckDbAdapter`] for `duckdb://<path>` connection strings.
+#[derive(Debug, Clone, Copy)]
+pub struct DuckDbFactory;
+
+#[async_trait::async_trait]
+impl AdapterFactory for DuckDbFactory {
+    fn engine(&self) -> &'static str {
+        "duckdb"
+    }
+
+    fn schemes(&self) -> &'static [&'static str] {
+        &["duckdb"]
+    }
+
+    async fn open(
+        &self,
+        conn: &str,
+        _opts: &IntrospectOptions,
+    ) -> Result<Box<dyn Adapter + Send + Sync>> {
+        let path = conn.split_once("://").map_or(conn, |(_, path)| path);
+        Ok(Box::new(DuckDbAdapter::new(path)))
+    }
+}
+
+/// Introspect a DuckDB file with default options.
+pub fn introspect_duckdb(path: &Path) -> Result<DatabaseSchema> {
+    introspect_duckdb_with_options(path, IntrospectOptions::default())
+}
+
+/// Introspect a DuckDB file with caller-provided options.
+pub fn introspect_duckdb_with_options(
+    path: &Path,
+    opts: IntrospectOptions,
+) -> Result<DatabaseSchema> {
+    let config = Config::default()
+        .access_mode(AccessMode::ReadOnly)
+        .map_err(|err| datalchemy_core::Error::Db(err.to_string()))?;
+    let conn = Connection::open_with_flags(path, config)
+        .map_err(|err| datalchemy_core::Error::Db(err.to_string()))?;
+    introspect(&conn, &opts)
+}
+
+/// Introspect an open DuckDB connection according to the provided options.
+fn introspect(conn: &Connection, opts: &IntrospectOptions) -> Result<DatabaseSchema> {
+    let database = queries::current_database(conn)?;
+
+    let mut columns: BTreeMap<(String, String), Vec<queries::RawColumn>> = BTreeMap::new();
+    for column in queries::list_columns(conn)? {
+        columns
+            .entry((column.schema.clone(), column.table.clone()))
+            .or_default()
+            .push(column);
+    }
+    let mut constraints: BTreeMap<(String, String), Vec<queries::RawConstraint>> = BTreeMap::new();
+    for constraint in queries::list_constraints(conn)? {
+        constraints
+            .entry((constraint.schema.clone(), constraint.table.clone()))
+            .or_default()
+            .push(constraint);
+    }
+    let mut indexes: BTreeMap<(String, String), Vec<queries::RawIndex>> = BTreeMap::new();
+    if opts.include_indexes {
+        for index in queries::list_indexes(conn)? {
+            indexes
+                .entry((index.schema.clone(), index.table.clone()))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut schemas: BTreeMap<String, Vec<Table>> = BTreeMap::new();
+    for raw in queries::list_tables(conn)? {
+        if !opts.include_system_schemas && SYSTEM_SCHEMAS.contains(&raw.schema.as_str()) {
+            continue;
+        }
+        if let Some(filter) = &opts.schemas
+            && !filter.contains(&raw.schema)
+        {
+            continue;
+        }
+        if raw.view_sql.is_some() && !opts.include_views {
+            continue;
+        }
+        if !opts.includes_table(&raw.schema, &raw.name) {
+            continue;
+        }
+
+        let key = (raw.schema.clone(), raw.name.clone());
+        let schema_name = raw.schema.clone();
+        let mut table = mapper::map_table(raw, columns.remove(&key).unwrap_or_default());
+        table.constraints =
+            mapper::map_constraints(&schema_name, constraints.remove(&key).unwrap_or_default());
+        table.indexes = mapper::map_indexes(indexes.remove(&key).unwrap_or_default());
+        if !opts.include_comments {
+            table.comment = None;
+            for column in &mut table.columns {
+                column.comment = None;
+            }
+        }
+        schemas.entry(schema_name).or_default().push(table);
+    }
+
+    let schemas: Vec<Schema> = schemas
+        .into_iter()
+        .map(|(name, tables)| Schema { name, tables })
+        .collect();
+    let external_tables = filter::external_tables(&schemas, opts);
+
+    DatabaseSchema {
+        schema_version: SCHEMA_VERSION.to_string(),
+        engine: "duckdb".to_string(),
+        database: Some(database),
+        schemas,
+        enums: Vec::new(),
+        domains: Vec::new(),
+        composite_types: Vec::new(),
+        sequences: Vec::new(),
+        current_role: None,
+        failed_tables: Vec::new(),
+        external_tables,
+        schema_fingerprint: None,
+    }
+    .with_fingerprint()
+}
@@ -0,0 +1,195 @@
+//! DuckDB catalog queries using the `duckdb_*()` metadata table functions.
+
+use ::duckdb::Connection;
+
+use datalchemy_core::Result;
+
+fn db_err(err: ::duckdb::Error) -> datalchemy_core::Error {
+    datalchemy_core::Error::Db(err.to_string())
+}
+
+/// Separator used to flatten DuckDB `VARCHAR[]` columns into one string.
+pub const LIST_SEPARATOR: char = '\u{1f}';
+
+/// Raw table or view from `duckdb_tables()` / `duckdb_views()`.
+pub struct RawTable {
+    pub schema: String,
+    pub name: String,
+    pub comment: Option<String>,
+    pub estimated_size: Option<i64>,
+    /// `CREATE VIEW` statement; `None` for base tables.
+    pub view_sql: Option<String>,
+}
+
+/// Raw column from `duckdb_columns()`.
+pub struct RawColumn {
+    pub schema: String,
+    pub table: String,
+    pub name: String,
+    pub ordinal: i32,
+    pub comment: Option<String>,
+    pub default: Option<String>,
+    pub is_nullable: bool,
+    pub data_type: String,
+    pub character_max_length: Option<i32>,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+}
+
+/// Raw constraint from `duckdb_constraints()`; list columns are joined with
+/// [`LIST_SEPARATOR`].
+pub struct RawConstraint {
+    pub schema: String,
+    pub table: String,
+    pub constraint_type: String,
+    pub name: Option<String>,
+    pub expression: Option<String>,
+    pub columns: Option<String>,
+    pub referenced_table: Option<String>,
+    pub referenced_columns: Option<String>,
+}
+
+/// Raw index from `duckdb_indexes()`.
+pub struct RawIndex {
+    pub schema: String,
+    pub table: String,
+    pub name: String,
+    pub is_unique: bool,
+    pub is_primary: bool,
+    pub sql: Option<String>,
+}
+
+/// Name of the attached database being introspected.
+pub fn current_database(conn: &Connection) -> Result<String> {
+    conn.query_row("SELECT current_database()", [], |row| row.get(0))
+        .map_err(db_err)
+}
+
+/// List user tables and views of the current database.
+pub fn list_tables(conn: &Connection) -> Result<Vec<RawTable>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT schema_name, table_name, comment, estimated_size, NULL AS sql
+             FROM duckdb_tables()
+             WHERE database_name = current_database() AND NOT internal AND NOT temporary
+             UNION ALL
+             SELECT schema_name, view_name, comment, NULL, sql
+             FROM duckdb_views()
+             WHERE database_name = current_database() AND NOT internal AND NOT temporary
+             ORDER BY 1, 2",
+        )
+        .map_err(db_err)?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RawTable {
+                schema: row.get(0)?,
+                name: row.get(1)?,
+                comment: row.get(2)?,
+                estimated_size: row.get(3)?,
+                view_sql: row.get(4)?,
+            })
+        })
+        .map_err(db_err)?;
+
+    rows.collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(db_err)
+}
+
+/// List columns of every table and view of the current database.
+pub fn list_columns(conn: &Connection) -> Result<Vec<RawColumn>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT schema_name, table_name, column_name, column_index, comment,
+                    column_default, is_nullable, data_type, character_maximum_length,
+                    numeric_precision, numeric_scale
+             FROM duckdb_columns()
+             WHERE database_name = current_database() AND NOT internal
+             ORDER BY schema_name, table_name, column_index",
+        )
+        .map_err(db_err)?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RawColumn {
+                schema: row.get(0)?,
+                table: row.get(1)?,
+                name: row.get(2)?,
+                ordinal: row.get(3)?,
+                comment: row.get(4)?,
+                default: row.get(5)?,
+                is_nullable: row.get(6)?,
+                data_type: row.get(7)?,
+                character_max_length: row.get(8)?,
+                numeric_precision: row.get(9)?,
+                numeric_scale: row.get(10)?,
+            })
+        })
+        .map_err(db_err)?;
+
+    rows.collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(db_err)
+}
+
+/// List PRIMARY KEY, UNIQUE, FOREIGN KEY and CHECK constraints.
+pub fn list_constraints(conn: &Connection) -> Result<Vec<RawConstraint>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT schema_name, table_name, constraint_type, constraint_name, expression,
+                    list_aggregate(constraint_column_names, 'string_agg', chr(31)),
+                    referenced_table,
+                    list_aggregate(referenced_column_names, 'string_agg', chr(31))
+             FROM duckdb_constraints()
+             WHERE database_name = current_database()
+               AND constraint_type IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY', 'CHECK')
+             ORDER BY schema_name, table_name, constraint_index",
+        )
+        .map_err(db_err)?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RawConstraint {
+                schema: row.get(0)?,
+                table: row.get(1)?,
+                constraint_type: row.get(2)?,
+                name: row.get(3)?,
+                expression: row.get(4)?,
+                columns: row.get(5)?,
+                referenced_table: row.get(6)?,
+                referenced_columns: row.get(7)?,
+            })
+        })
+        .map_err(db_err)?;
+
+    rows.collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(db_err)
+}
+
+/// List explicitly created indexes (ART indexes backing constraints are not
+/// reported by DuckDB).
+pub fn list_indexes(conn: &Connection) -> Result<Vec<RawIndex>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT schema_name, table_name, index_name, is_unique, is_primary, sql
+             FROM duckdb_indexes()
+             WHERE database_name = current_database()
+             ORDER BY schema_name, table_name, index_name",
+        )
+        .map_err(db_err)?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RawIndex {
+                schema: row.get(0)?,
+                table: row.get(1)?,
+                name: row.get(2)?,
+                is_unique: row.get(3)?,
+                is_primary: row.get(4)?,
+                sql: row.get(5)?,
+            })
+        })
+        .map_err(db_err)?;
+
+    rows.collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(db_err)
+}
@@ -0,0 +1,147 @@
+//! Table include/exclude patterns and the external tables they leave behind.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use datalchemy_core::{Constraint, Error, ExternalTable, Result, Schema};
+use regex::Regex;
+
+use crate::options::IntrospectOptions;
+
+/// Pattern selecting tables by name.
+///
+/// A glob (`*` any run of characters, `?` one character) is matched against
+/// `schema.table` when it contains a dot and against the bare table name
+/// otherwise, so `public.orders*` and `audit_*` both work. A pattern
+/// starting with `re:` is a regular expression searched in `schema.table`.
+#[derive(Debug, Clone)]
+pub enum TablePattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl TablePattern {
+    /// Whether the table `schema.table` matches the pattern.
+    pub fn matches(&self, schema: &str, table: &str) -> bool {
+        match self {
+            TablePattern::Glob(glob) if glob.contains('.') => {
+                glob_matches(glob, &format!("{schema}.{table}"))
+            }
+            TablePattern::Glob(glob) => glob_matches(glob, table),
+            TablePattern::Regex(regex) => regex.is_match(&format!("{schema}.{table}")),
+        }
+    }
+}
+
+impl FromStr for TablePattern {
+    type Err = Error;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        match pattern.strip_prefix("re:") {
+            Some(regex) => Regex::new(regex)
+                .map(TablePattern::Regex)
+                .map_err(|err| Error::Other(format!("invalid table pattern '{pattern}': {err}"))),
+            None => Ok(TablePattern::Glob(pattern.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for TablePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TablePattern::Glob(glob) => f.write_str(glob),
+            TablePattern::Regex(regex) => write!(f, "re:{}", regex.as_str()),
+        }
+    }
+}
+
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut g, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, t));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    g = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+/// Tables left out by the table filters that foreign keys of `schemas`
+/// point to, with the tables that point to them.
+pub(crate) fn external_tables(schemas: &[Schema], opts: &IntrospectOptions) -> Vec<ExternalTable> {
+    let mut external: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for db_schema in schemas {
+        for table in &db_schema.tables {
+            for constraint in &table.constraints {
+                let Constraint::ForeignKey(fk) = constraint else {
+                    continue;
+                };
+                if opts.includes_table(&fk.referenced_schema, &fk.referenced_table) {
+                    continue;
+                }
+                let children = external
+                    .entry((fk.referenced_schema.clone(), fk.referenced_table.clone()))
+                    .or_default();
+                let child = format!("{}.{}", db_schema.name, table.name);
+                if !children.contains(&child) {
+                    children.push(child);
+                }
+            }
+        }
+    }
+    external
+        .into_iter()
+        .map(|((schema, table), referenced_by)| ExternalTable {
+            schema,
+            table,
+            referenced_by,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(raw: &str) -> TablePattern {
+        raw.parse().expect("valid pattern")
+    }
+
+    #[test]
+    fn globs_match_bare_or_qualified_names() {
+        assert!(pattern("orders*").matches("public", "orders_2024"));
+        assert!(pattern("public.orders*").matches("public", "orders"));
+        assert!(!pattern("public.orders*").matches("sales", "orders"));
+        assert!(pattern("*_audit").matches("crm", "login_audit"));
+        assert!(pattern("log?").matches("crm", "log1"));
+        assert!(!pattern("log?").matches("crm", "log"));
+        assert!(pattern("*.*").matches("crm", "any"));
+    }
+
+    #[test]
+    fn regexes_search_the_qualified_name() {
+        let regex = pattern(r"re:^crm\.pedidos_\d+$");
+        assert!(regex.matches("crm", "pedidos_2024"));
+        assert!(!regex.matches("crm", "pedidos_itens"));
+        assert_eq!(regex.to_string(), r"re:^crm\.pedidos_\d+$");
+        assert!("re:(".parse::<TablePattern>().is_err());
+    }
+}
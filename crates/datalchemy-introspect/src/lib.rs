@@ -1,13 +1,28 @@
 //! Database introspection adapters.
 
 pub mod adapter;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+pub mod filter;
 pub mod options;
 pub mod postgres;
+pub mod registry;
+pub mod retry;
 pub mod sqlite;
+pub mod timings;
 
 pub use adapter::Adapter;
+#[cfg(feature = "duckdb")]
+pub use duckdb::{DuckDbAdapter, DuckDbFactory, introspect_duckdb, introspect_duckdb_with_options};
+pub use filter::TablePattern;
 pub use options::IntrospectOptions;
-pub use postgres::{PostgresAdapter, introspect_postgres, introspect_postgres_with_options};
-pub use sqlite::{SqliteAdapter, introspect_sqlite, introspect_sqlite_with_options};
+pub use postgres::{
+    Dialect, PostgresAdapter, PostgresFactory, introspect_incremental, introspect_postgres,
+    introspect_postgres_timed, introspect_postgres_with_options,
+};
+pub use registry::{AdapterFactory, AdapterRegistry};
+pub use retry::RetryPolicy;
+pub use sqlite::{SqliteAdapter, SqliteFactory, introspect_sqlite, introspect_sqlite_with_options};
+pub use timings::{IntrospectTimings, PhaseTiming};
 
 pub use datalchemy_core::DatabaseSchema;
@@ -1,3 +1,8 @@
+use std::time::Duration;
+
+use crate::filter::TablePattern;
+use crate::retry::RetryPolicy;
+
 /// Options that control how introspection behaves.
 #[derive(Debug, Clone)]
 pub struct IntrospectOptions {
@@ -8,6 +13,39 @@ pub struct IntrospectOptions {
     pub include_indexes: bool,
     pub include_comments: bool,
     pub schemas: Option<Vec<String>>,
+    /// Introspect only the tables matching one of these patterns; empty
+    /// keeps every table.
+    pub include_tables: Vec<TablePattern>,
+    /// Skip the tables matching one of these patterns. Skipped tables that
+    /// foreign keys point to go to `DatabaseSchema::external_tables`.
+    pub exclude_tables: Vec<TablePattern>,
+    /// Capture planner statistics (`pg_class.reltuples`, `pg_stats` null
+    /// fraction and distinct values) into `Table`/`Column`. Postgres only;
+    /// reflects the last `ANALYZE`.
+    pub sample_stats: bool,
+    /// Record which roles hold SELECT and INSERT on each table
+    /// (`Table::privileges`) and the introspecting role
+    /// (`DatabaseSchema::current_role`). Postgres only.
+    pub include_privileges: bool,
+    /// Tables whose columns, constraints and indexes are fetched at the same
+    /// time, each over its own pool connection; keep it at most the pool
+    /// size. Postgres only.
+    pub concurrency: usize,
+    /// How long to wait for a pool connection. The adapters take a ready
+    /// pool, so this is for the caller that builds it (the CLI does).
+    pub acquire_timeout: Duration,
+    /// Limit for each catalog query (for the per-table details, all queries
+    /// of one table); `None` waits as long as the database does. Postgres only.
+    pub query_timeout: Option<Duration>,
+    /// Limit for the whole introspection, counted from its start. Postgres
+    /// only.
+    pub deadline: Option<Duration>,
+    /// Retries of a catalog query that failed or timed out. Postgres only.
+    pub retry: RetryPolicy,
+    /// Record tables whose details still fail after the retries (or ran past
+    /// the deadline) in `DatabaseSchema::failed_tables` and go on, instead of
+    /// failing the whole introspection. Postgres only.
+    pub partial: bool,
 }
 
 impl Default for IntrospectOptions {
@@ -20,6 +58,31 @@ impl Default for IntrospectOptions {
             include_indexes: true,
             include_comments: true,
             schemas: None,
+            include_tables: Vec::new(),
+            exclude_tables: Vec::new(),
+            sample_stats: false,
+            include_privileges: false,
+            concurrency: 4,
+            acquire_timeout: Duration::from_secs(10),
+            query_timeout: None,
+            deadline: None,
+            retry: RetryPolicy::default(),
+            partial: false,
         }
     }
 }
+
+impl IntrospectOptions {
+    /// Whether `schema.table` passes `include_tables` and `exclude_tables`.
+    pub fn includes_table(&self, schema: &str, table: &str) -> bool {
+        (self.include_tables.is_empty()
+            || self
+                .include_tables
+                .iter()
+                .any(|pattern| pattern.matches(schema, table)))
+            && !self
+                .exclude_tables
+                .iter()
+                .any(|pattern| pattern.matches(schema, table))
+    }
+}
@@ -0,0 +1,54 @@
+/// Postgres-compatible engine behind a connection.
+///
+/// CockroachDB speaks the Postgres protocol and exposes `pg_catalog`, but
+/// without some of its objects and semantics; the adapter skips what a
+/// dialect lacks instead of failing on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    Cockroach,
+}
+
+impl Dialect {
+    /// Dialect of a server from its `version()` text.
+    pub fn detect(version: &str) -> Self {
+        if version.contains("CockroachDB") {
+            Self::Cockroach
+        } else {
+            Self::Postgres
+        }
+    }
+
+    /// `engine` of the snapshots introspected through this dialect.
+    pub fn engine(self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::Cockroach => "cockroachdb",
+        }
+    }
+
+    /// Catalog rows carry `xmin`, used for the `catalog_version` of
+    /// incremental introspection.
+    pub fn has_catalog_xmin(self) -> bool {
+        self == Self::Postgres
+    }
+
+    /// Domains, exclusion constraints, rewrite rules, triggers and planner
+    /// statistics (`pg_stats`): absent or not exposed through `pg_catalog`
+    /// by CockroachDB.
+    pub fn has_postgres_extras(self) -> bool {
+        self == Self::Postgres
+    }
+
+    /// `pg_sequence_last_value`; CockroachDB sequences have no session cache
+    /// to read it from.
+    pub fn has_sequence_last_value(self) -> bool {
+        self == Self::Postgres
+    }
+
+    /// `NOT VISIBLE` columns (the implicit `rowid` key, hash shard columns)
+    /// and hash-sharded indexes.
+    pub fn has_hidden_columns(self) -> bool {
+        self == Self::Cockroach
+    }
+}
@@ -1,22 +1,32 @@
+use std::collections::BTreeMap;
+
 use datalchemy_core::{
-    CheckConstraint, Column, ColumnType, Constraint, EnumType, ForeignKey, GeneratedExpression,
-    GeneratedKind, Index, PrimaryKey, Table, TableKind, UniqueConstraint,
+    CheckConstraint, Column, ColumnStats, ColumnType, CompositeAttribute, CompositeType,
+    Constraint, DomainType, EnumType, ExclusionConstraint, ExclusionElement, ForeignKey,
+    ForeignTableSource, GeneratedExpression, GeneratedKind, Index, Partition, PartitionKey,
+    PartitionStrategy, PrimaryKey, RewriteRule, Sequence, SequenceOwner, Table, TableKind,
+    TablePrivileges, Trigger, UniqueConstraint, ViewDependency,
 };
 
 use crate::options::IntrospectOptions;
 use crate::postgres::utils::{
     fk_action_from_code, fk_match_from_code, identity_from_text, relkind_to_table_kind,
+    rule_event_from_code, trigger_events_from_type, trigger_is_row_level, trigger_timing_from_type,
 };
 
 use super::queries::{
-    RawCheckConstraint, RawColumn, RawEnumType, RawForeignKey, RawIndex, RawPrimaryKey, RawTable,
-    RawUniqueConstraint,
+    RawCheckConstraint, RawColumn, RawColumnStats, RawCompositeAttribute, RawDomain, RawEnumType,
+    RawExclusionConstraint, RawForeignKey, RawForeignTable, RawIndex, RawPartition, RawPrimaryKey,
+    RawRule, RawSequence, RawShardedIndex, RawTable, RawTablePrivilege, RawTableStats, RawTrigger,
+    RawUniqueConstraint, RawViewDependency,
 };
 
 pub fn filter_schemas(raw: Vec<String>, opts: &IntrospectOptions) -> Vec<String> {
     raw.into_iter()
         .filter(|schema| {
-            let is_system = schema.starts_with("pg_") || schema == "information_schema";
+            let is_system = schema.starts_with("pg_")
+                || schema == "information_schema"
+                || schema == "crdb_internal";
             match &opts.schemas {
                 Some(list) => list.iter().any(|item| item == schema),
                 None => opts.include_system_schemas || !is_system,
@@ -46,6 +56,17 @@ pub fn map_tables(raw: Vec<RawTable>, opts: &IntrospectOptions) -> Vec<Table> {
                 columns: Vec::new(),
                 constraints: Vec::new(),
                 indexes: Vec::new(),
+                partition_key: None,
+                partitions: Vec::new(),
+                row_estimate: None,
+                triggers: Vec::new(),
+                rules: Vec::new(),
+                catalog_version: None,
+                hidden_columns: Vec::new(),
+                view_definition: None,
+                depends_on: Vec::new(),
+                foreign_table: None,
+                privileges: None,
             })
         })
         .collect()
@@ -77,19 +98,20 @@ pub fn map_columns(raw: Vec<RawColumn>, opts: &IntrospectOptions) -> Vec<Column>
             is_nullable: col.is_nullable,
             default: col.default,
             identity: identity_from_text(col.identity_generation),
-            generated: if col.is_generated {
-                Some(GeneratedExpression {
-                    kind: GeneratedKind::Stored,
-                    expression: col.generation_expression,
-                })
-            } else {
-                None
-            },
+            generated: col.generated_kind.map(|kind| GeneratedExpression {
+                kind: if kind == "VIRTUAL" {
+                    GeneratedKind::Virtual
+                } else {
+                    GeneratedKind::Stored
+                },
+                expression: col.generation_expression,
+            }),
             comment: if opts.include_comments {
                 col.comment
             } else {
                 None
             },
+            stats: None,
         })
         .collect()
 }
@@ -121,6 +143,27 @@ pub fn map_check_constraints(raw: Vec<RawCheckConstraint>) -> Vec<CheckConstrain
         .collect()
 }
 
+pub fn map_exclusion_constraints(raw: Vec<RawExclusionConstraint>) -> Vec<ExclusionConstraint> {
+    raw.into_iter()
+        .map(|ex| ExclusionConstraint {
+            name: Some(ex.name),
+            method: ex.method,
+            elements: ex
+                .expressions
+                .into_iter()
+                .zip(ex.operators)
+                .map(|(expression, operator)| ExclusionElement {
+                    expression,
+                    operator,
+                })
+                .collect(),
+            predicate: ex.predicate,
+            is_deferrable: ex.is_deferrable,
+            initially_deferred: ex.initially_deferred,
+        })
+        .collect()
+}
+
 pub fn map_foreign_keys(raw: Vec<RawForeignKey>) -> Vec<ForeignKey> {
     raw.into_iter()
         .map(|fk| ForeignKey {
@@ -138,6 +181,128 @@ pub fn map_foreign_keys(raw: Vec<RawForeignKey>) -> Vec<ForeignKey> {
         .collect()
 }
 
+pub fn map_triggers(raw: Vec<RawTrigger>) -> Vec<Trigger> {
+    raw.into_iter()
+        .map(|trigger| Trigger {
+            name: trigger.name,
+            timing: trigger_timing_from_type(trigger.tgtype),
+            events: trigger_events_from_type(trigger.tgtype),
+            for_each_row: trigger_is_row_level(trigger.tgtype),
+            function: Some(trigger.function),
+            // 'D' = disabled; 'O', 'R' and 'A' fire depending on the
+            // session replication role.
+            enabled: trigger.enabled_code as u8 != b'D',
+        })
+        .collect()
+}
+
+pub fn map_rules(raw: Vec<RawRule>) -> Vec<RewriteRule> {
+    raw.into_iter()
+        .filter_map(|rule| {
+            Some(RewriteRule {
+                name: rule.name,
+                event: rule_event_from_code(rule.event_code)?,
+                instead: rule.is_instead,
+            })
+        })
+        .collect()
+}
+
+/// Parse `pg_get_partkeydef` output, e.g. `RANGE (created_at)` or
+/// `LIST (lower(uf), pais)`.
+pub fn map_partition_key(raw: Option<String>) -> Option<PartitionKey> {
+    let raw = raw?;
+    let (strategy, columns) = raw.trim().split_once(' ')?;
+    let strategy = match strategy.to_ascii_uppercase().as_str() {
+        "RANGE" => PartitionStrategy::Range,
+        "LIST" => PartitionStrategy::List,
+        "HASH" => PartitionStrategy::Hash,
+        _ => return None,
+    };
+    let columns = columns.trim().strip_prefix('(')?.strip_suffix(')')?;
+    Some(PartitionKey {
+        strategy,
+        columns: split_top_level(columns),
+    })
+}
+
+/// Split on commas outside parentheses and quotes.
+fn split_top_level(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0_i32;
+    let mut quote: Option<char> = None;
+    for ch in list.chars() {
+        match ch {
+            '\'' | '"' if quote.is_none() => quote = Some(ch),
+            _ if quote == Some(ch) => quote = None,
+            _ if quote.is_some() => {}
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    if !current.trim().is_empty() {
+        items.push(current.trim().to_string());
+    }
+    items
+}
+
+pub fn map_partitions(raw: Vec<RawPartition>) -> Vec<Partition> {
+    raw.into_iter()
+        .map(|partition| Partition {
+            schema: partition.schema,
+            name: partition.name,
+            bound: partition.bound,
+        })
+        .collect()
+}
+
+pub fn map_view_dependencies(raw: Vec<RawViewDependency>) -> Vec<ViewDependency> {
+    raw.into_iter()
+        .map(|dependency| ViewDependency {
+            schema: dependency.schema,
+            name: dependency.name,
+        })
+        .collect()
+}
+
+pub fn map_foreign_table(raw: Option<RawForeignTable>) -> Option<ForeignTableSource> {
+    let raw = raw?;
+    Some(ForeignTableSource {
+        server: raw.server,
+        wrapper: raw.wrapper,
+        options: parse_options(raw.options),
+        server_options: parse_options(raw.server_options)
+            .into_iter()
+            .filter(|(key, _)| !is_secret_option(key))
+            .collect(),
+    })
+}
+
+/// `key=value` entries of a catalog options array.
+fn parse_options(raw: Vec<String>) -> BTreeMap<String, String> {
+    raw.into_iter()
+        .map(|option| match option.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (option, String::new()),
+        })
+        .collect()
+}
+
+fn is_secret_option(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    ["password", "passwd", "secret", "token", "key"]
+        .iter()
+        .any(|word| key.contains(word))
+}
+
 pub fn map_indexes(raw: Vec<RawIndex>) -> Vec<Index> {
     raw.into_iter()
         .map(|idx| Index {
@@ -147,6 +312,9 @@ pub fn map_indexes(raw: Vec<RawIndex>) -> Vec<Index> {
             is_valid: idx.is_valid,
             method: idx.method,
             definition: idx.definition,
+            predicate: idx.predicate,
+            expressions: idx.expressions,
+            shard_buckets: None,
         })
         .collect()
 }
@@ -165,6 +333,218 @@ pub fn map_enums(raw: Vec<RawEnumType>, opts: &IntrospectOptions) -> Vec<EnumTyp
         .collect()
 }
 
+pub fn map_domains(raw: Vec<RawDomain>, opts: &IntrospectOptions) -> Vec<DomainType> {
+    let allowed_schemas =
+        filter_schemas(raw.iter().map(|item| item.schema.clone()).collect(), opts);
+
+    raw.into_iter()
+        .filter(|domain| {
+            allowed_schemas
+                .iter()
+                .any(|schema| schema == &domain.schema)
+        })
+        .map(|domain| DomainType {
+            schema: domain.schema,
+            name: domain.name,
+            base_type: ColumnType {
+                data_type: domain.data_type,
+                udt_schema: domain.udt_schema,
+                udt_name: domain.udt_name,
+                character_max_length: domain.character_max_length,
+                numeric_precision: domain.numeric_precision,
+                numeric_scale: domain.numeric_scale,
+                collation: domain.collation,
+            },
+            not_null: domain.not_null,
+            default: domain.default,
+            checks: domain
+                .check_names
+                .into_iter()
+                .zip(domain.check_expressions)
+                .map(|(name, expression)| CheckConstraint {
+                    name: Some(name),
+                    expression,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn map_composite_types(
+    raw: Vec<RawCompositeAttribute>,
+    opts: &IntrospectOptions,
+) -> Vec<CompositeType> {
+    let allowed_schemas =
+        filter_schemas(raw.iter().map(|item| item.schema.clone()).collect(), opts);
+
+    let mut composites: Vec<CompositeType> = Vec::new();
+    for attr in raw {
+        if !allowed_schemas.iter().any(|schema| schema == &attr.schema) {
+            continue;
+        }
+        let attribute = CompositeAttribute {
+            name: attr.name,
+            column_type: ColumnType {
+                data_type: attr.data_type,
+                udt_schema: attr.udt_schema,
+                udt_name: attr.udt_name,
+                character_max_length: attr.character_max_length,
+                numeric_precision: attr.numeric_precision,
+                numeric_scale: attr.numeric_scale,
+                collation: attr.collation,
+            },
+        };
+        match composites.last_mut() {
+            Some(last) if last.schema == attr.schema && last.name == attr.type_name => {
+                last.attributes.push(attribute)
+            }
+            _ => composites.push(CompositeType {
+                schema: attr.schema,
+                name: attr.type_name,
+                attributes: vec![attribute],
+            }),
+        }
+    }
+    composites
+}
+
+pub fn map_sequences(raw: Vec<RawSequence>) -> Vec<Sequence> {
+    raw.into_iter()
+        .map(|seq| Sequence {
+            owner: match (seq.owner_schema, seq.owner_table, seq.owner_column) {
+                (Some(schema), Some(table), Some(column)) => Some(SequenceOwner {
+                    schema,
+                    table,
+                    column,
+                }),
+                _ => None,
+            },
+            schema: seq.schema,
+            name: seq.name,
+            start: seq.start,
+            increment: seq.increment,
+            cache: seq.cache,
+            last_value: seq.last_value,
+        })
+        .collect()
+}
+
+/// Fill `table` (name, kind and comment already mapped) with the structure
+/// of its unchanged snapshot `before`, without the statistics.
+pub fn reuse_table(table: &mut Table, before: &Table) {
+    table.columns = before
+        .columns
+        .iter()
+        .cloned()
+        .map(|column| Column {
+            stats: None,
+            ..column
+        })
+        .collect();
+    table.constraints = before.constraints.clone();
+    table.indexes = before.indexes.clone();
+    table.partition_key = before.partition_key.clone();
+    table.partitions = before.partitions.clone();
+    table.triggers = before.triggers.clone();
+    table.rules = before.rules.clone();
+    table.hidden_columns = before.hidden_columns.clone();
+    table.view_definition = before.view_definition.clone();
+    table.depends_on = before.depends_on.clone();
+    table.foreign_table = before.foreign_table.clone();
+}
+
+/// Moves CockroachDB `NOT VISIBLE` columns out of `table`: they leave
+/// `columns`, key constraints (a key left without columns, such as the
+/// implicit `rowid` primary key, is dropped) and the checks that mention
+/// them, and are listed in `hidden_columns`. Hash-sharded indexes get their
+/// bucket count.
+pub fn apply_hidden_columns(table: &mut Table, hidden: Vec<String>, sharded: Vec<RawShardedIndex>) {
+    for index in &mut table.indexes {
+        index.shard_buckets = sharded
+            .iter()
+            .find(|raw| raw.name == index.name)
+            .map(|raw| raw.buckets as u32);
+    }
+    if hidden.is_empty() {
+        return;
+    }
+    let is_hidden = |column: &String| hidden.contains(column);
+    table.columns.retain(|column| !is_hidden(&column.name));
+    table.constraints.retain_mut(|constraint| match constraint {
+        Constraint::PrimaryKey(pk) => {
+            pk.columns.retain(|column| !is_hidden(column));
+            !pk.columns.is_empty()
+        }
+        Constraint::Unique(unique) => {
+            unique.columns.retain(|column| !is_hidden(column));
+            !unique.columns.is_empty()
+        }
+        Constraint::Check(check) => !hidden
+            .iter()
+            .any(|column| check.expression.contains(column.as_str())),
+        Constraint::ForeignKey(fk) => !fk.columns.iter().any(is_hidden),
+        Constraint::Exclusion(_) => true,
+    });
+    table.hidden_columns = hidden;
+}
+
+/// Fills `row_estimate` and column `stats`; tables never analyzed
+/// (`reltuples` -1) and columns without `pg_stats` rows stay empty.
+pub fn apply_stats(
+    tables: &mut [Table],
+    raw_tables: Vec<RawTableStats>,
+    raw_columns: Vec<RawColumnStats>,
+) {
+    for raw in raw_tables {
+        if let Some(table) = tables.iter_mut().find(|table| table.name == raw.table)
+            && raw.reltuples >= 0.0
+        {
+            table.row_estimate = Some(raw.reltuples.round() as u64);
+        }
+    }
+    for raw in raw_columns {
+        let column = tables
+            .iter_mut()
+            .filter(|table| table.name == raw.table)
+            .flat_map(|table| &mut table.columns)
+            .find(|column| column.name == raw.column);
+        if let Some(column) = column {
+            column.stats = Some(ColumnStats {
+                null_frac: round_stat(raw.null_frac),
+                n_distinct: round_stat(raw.n_distinct),
+            });
+        }
+    }
+}
+
+/// Fills `privileges` of every table with the roles allowed to read and
+/// insert into it.
+pub fn apply_privileges(tables: &mut [Table], raw: Vec<RawTablePrivilege>) {
+    for table in tables.iter_mut() {
+        table.privileges = Some(TablePrivileges::default());
+    }
+    for raw in raw {
+        let Some(privileges) = tables
+            .iter_mut()
+            .find(|table| table.name == raw.table)
+            .and_then(|table| table.privileges.as_mut())
+        else {
+            continue;
+        };
+        if raw.can_select {
+            privileges.select.push(raw.grantee.clone());
+        }
+        if raw.can_insert {
+            privileges.insert.push(raw.grantee);
+        }
+    }
+}
+
+/// `real` statistics at 4 decimals, so the JSON does not carry float4 noise.
+fn round_stat(value: f32) -> f64 {
+    (f64::from(value) * 10_000.0).round() / 10_000.0
+}
+
 pub fn sort_constraints(constraints: &mut [Constraint]) {
     constraints.sort_by_key(constraint_key);
 }
@@ -187,5 +567,10 @@ fn constraint_key(constraint: &Constraint) -> (u8, String, String) {
         Constraint::ForeignKey(fk) => {
             (3, fk.name.clone().unwrap_or_default(), fk.columns.join("|"))
         }
+        Constraint::Exclusion(exclusion) => (
+            4,
+            exclusion.name.clone().unwrap_or_default(),
+            exclusion.method.clone(),
+        ),
     }
 }
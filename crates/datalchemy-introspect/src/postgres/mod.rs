@@ -1,14 +1,27 @@
+use std::time::Instant;
+
+use futures_util::{StreamExt, TryStreamExt, stream};
 use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
 
-use datalchemy_core::{DatabaseSchema, Result, SCHEMA_VERSION, Schema};
+use datalchemy_core::{
+    DatabaseSchema, FailedTable, Result, SCHEMA_VERSION, Schema, Table, TableKind,
+};
 
 use crate::adapter::Adapter;
+use crate::filter;
 use crate::options::IntrospectOptions;
+use crate::registry::AdapterFactory;
+use crate::retry;
+use crate::timings::IntrospectTimings;
 
+mod dialect;
 mod mapper;
 mod queries;
 mod utils;
 
+pub use dialect::Dialect;
+
 /// Adapter for PostgreSQL databases.
 #[derive(Debug, Clone)]
 pub struct PostgresAdapter {
@@ -31,6 +44,44 @@ impl Adapter for PostgresAdapter {
     async fn introspect(&self, opts: &IntrospectOptions) -> Result<DatabaseSchema> {
         introspect(&self.pool, opts).await
     }
+
+    async fn introspect_timed(
+        &self,
+        opts: &IntrospectOptions,
+        previous: Option<&DatabaseSchema>,
+    ) -> Result<(DatabaseSchema, IntrospectTimings)> {
+        introspect_postgres_timed(&self.pool, opts, previous).await
+    }
+}
+
+/// Opens a [`PostgresAdapter`] for `postgres://` and `postgresql://`
+/// connection strings, with a pool sized for `concurrency`.
+#[derive(Debug, Clone, Copy)]
+pub struct PostgresFactory;
+
+#[async_trait::async_trait]
+impl AdapterFactory for PostgresFactory {
+    fn engine(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn schemes(&self) -> &'static [&'static str] {
+        &["postgres", "postgresql"]
+    }
+
+    async fn open(
+        &self,
+        conn: &str,
+        opts: &IntrospectOptions,
+    ) -> Result<Box<dyn Adapter + Send + Sync>> {
+        let pool = PgPoolOptions::new()
+            .max_connections((opts.concurrency as u32).max(5))
+            .acquire_timeout(opts.acquire_timeout)
+            .connect(conn)
+            .await
+            .map_err(|err| datalchemy_core::Error::Db(err.to_string()))?;
+        Ok(Box::new(PostgresAdapter::new(pool)))
+    }
 }
 
 /// Introspect Postgres with default options.
@@ -46,55 +97,202 @@ pub async fn introspect_postgres_with_options(
     introspect(pool, &opts).await
 }
 
+/// Re-introspect Postgres starting from `previous`, a snapshot of the same
+/// database taken with the same options: tables whose catalog rows are
+/// unchanged since `previous` (same `catalog_version`) are reused as they
+/// were, the others are queried again; user types and sequences are always
+/// queried. Tables get their `catalog_version`, so the result can be the
+/// `previous` of the next run; a snapshot without versions (from
+/// [`introspect`]) is re-queried in full. Statistics follow `opts`.
+pub async fn introspect_incremental(
+    pool: &PgPool,
+    previous: &DatabaseSchema,
+    opts: &IntrospectOptions,
+) -> Result<DatabaseSchema> {
+    Ok(introspect_postgres_timed(pool, opts, Some(previous))
+        .await?
+        .0)
+}
+
 /// Introspect a Postgres database according to the provided options.
+///
+/// CockroachDB servers are detected from `version()`: the snapshot gets
+/// `engine` `cockroachdb`, its `NOT VISIBLE` columns go to `hidden_columns`,
+/// hash-sharded indexes record `shard_buckets`, and the catalogs CockroachDB
+/// does not provide (see [`Dialect`]) are skipped.
 pub async fn introspect(pool: &PgPool, opts: &IntrospectOptions) -> Result<DatabaseSchema> {
-    let database = queries::fetch_database_name(pool).await?;
-    let schemas = mapper::filter_schemas(queries::list_schemas(pool).await?, opts);
-    let mut enums = mapper::map_enums(queries::list_enums(pool).await?, opts);
+    Ok(introspect_postgres_timed(pool, opts, None).await?.0)
+}
+
+/// [`introspect`] (or [`introspect_incremental`] with `previous`), also
+/// returning the time spent in each phase: `catalog` (database, schemas and
+/// user types), `sequences`, `tables` (relations and catalog versions),
+/// `details` (columns, constraints, partitions, indexes, triggers and rules,
+/// fetched for `opts.concurrency` tables at a time), `stats` and `privileges`.
+///
+/// Every catalog query runs under `opts.query_timeout`, `opts.deadline` and
+/// `opts.retry` (the details of a table are retried as a whole); with
+/// `opts.partial`, tables whose details still fail go to `failed_tables`
+/// instead of failing the introspection.
+pub async fn introspect_postgres_timed(
+    pool: &PgPool,
+    opts: &IntrospectOptions,
+    previous: Option<&DatabaseSchema>,
+) -> Result<(DatabaseSchema, IntrospectTimings)> {
+    let mut timings = IntrospectTimings::default();
+
+    let started = Instant::now();
+    let deadline = opts.deadline.map(|deadline| started + deadline);
+    let dialect =
+        Dialect::detect(&retry::run(opts, deadline, || queries::fetch_server_version(pool)).await?);
+    let database = retry::run(opts, deadline, || queries::fetch_database_name(pool)).await?;
+    let schemas = mapper::filter_schemas(
+        retry::run(opts, deadline, || queries::list_schemas(pool)).await?,
+        opts,
+    );
+    let mut enums = mapper::map_enums(
+        retry::run(opts, deadline, || queries::list_enums(pool)).await?,
+        opts,
+    );
+    let domains = if dialect.has_postgres_extras() {
+        mapper::map_domains(
+            retry::run(opts, deadline, || queries::list_domains(pool)).await?,
+            opts,
+        )
+    } else {
+        Vec::new()
+    };
+    let composite_types = mapper::map_composite_types(
+        retry::run(opts, deadline, || queries::list_composite_attributes(pool)).await?,
+        opts,
+    );
+    timings.record("catalog", started.elapsed());
 
     let mut schema_items = Vec::new();
+    let mut sequences = Vec::new();
+    let mut failed_tables = Vec::new();
 
     for schema_name in schemas {
-        let raw_tables = queries::list_tables_in_schema(pool, &schema_name).await?;
+        let started = Instant::now();
+        sequences.extend(mapper::map_sequences(
+            retry::run(opts, deadline, || {
+                queries::list_sequences(pool, &schema_name, dialect)
+            })
+            .await?,
+        ));
+        timings.record("sequences", started.elapsed());
+
+        let started = Instant::now();
+        let raw_tables = retry::run(opts, deadline, || {
+            queries::list_tables_in_schema(pool, &schema_name)
+        })
+        .await?;
         let mut tables = mapper::map_tables(raw_tables, opts);
+        tables.retain(|table| opts.includes_table(&schema_name, &table.name));
+        let versions = match previous {
+            Some(_) if dialect.has_catalog_xmin() => {
+                retry::run(opts, deadline, || {
+                    queries::list_catalog_versions(pool, &schema_name)
+                })
+                .await?
+            }
+            _ => Vec::new(),
+        };
+        timings.record("tables", started.elapsed());
 
+        let started = Instant::now();
+        let previous_tables = previous
+            .and_then(|previous| {
+                previous
+                    .schemas
+                    .iter()
+                    .find(|db_schema| db_schema.name == schema_name)
+            })
+            .map(|db_schema| db_schema.tables.as_slice())
+            .unwrap_or_default();
+        let mut changed = Vec::new();
         for table in &mut tables {
-            let raw_columns = queries::list_columns(pool, &schema_name, &table.name).await?;
-            table.columns = mapper::map_columns(raw_columns, opts);
-
-            let raw_pk = queries::get_primary_key(pool, &schema_name, &table.name).await?;
-            let raw_uniques =
-                queries::list_unique_constraints(pool, &schema_name, &table.name).await?;
-            let raw_checks =
-                queries::list_check_constraints(pool, &schema_name, &table.name).await?;
-            let raw_fks = queries::list_foreign_keys(pool, &schema_name, &table.name).await?;
-
-            let mut constraints = Vec::new();
-            if let Some(pk) = mapper::map_primary_key(raw_pk) {
-                constraints.push(datalchemy_core::Constraint::PrimaryKey(pk));
+            if previous.is_some() {
+                table.catalog_version = versions
+                    .iter()
+                    .find(|raw| raw.table == table.name)
+                    .map(|raw| raw.version.clone());
             }
-            constraints.extend(
-                mapper::map_unique_constraints(raw_uniques)
-                    .into_iter()
-                    .map(datalchemy_core::Constraint::Unique),
-            );
-            constraints.extend(
-                mapper::map_check_constraints(raw_checks)
-                    .into_iter()
-                    .map(datalchemy_core::Constraint::Check),
-            );
-            constraints.extend(
-                mapper::map_foreign_keys(raw_fks)
-                    .into_iter()
-                    .map(datalchemy_core::Constraint::ForeignKey),
+            let unchanged = previous_tables.iter().find(|before| {
+                before.name == table.name
+                    && before.kind == table.kind
+                    && before.catalog_version.is_some()
+                    && before.catalog_version == table.catalog_version
+            });
+            match unchanged {
+                Some(before) => mapper::reuse_table(table, before),
+                None => changed.push(table),
+            }
+        }
+        let schema_ref = schema_name.as_str();
+        let details: Vec<_> = changed
+            .into_iter()
+            .map(|table| async move {
+                let listed = table.clone();
+                let fetched = retry::run(opts, deadline, || {
+                    let mut fresh = listed.clone();
+                    async move {
+                        introspect_table(pool, dialect, schema_ref, &mut fresh, opts).await?;
+                        Ok(fresh)
+                    }
+                })
+                .await;
+                match fetched {
+                    Ok(fresh) => {
+                        *table = fresh;
+                        Ok(None)
+                    }
+                    Err(err) if opts.partial => Ok(Some(FailedTable {
+                        schema: schema_ref.to_string(),
+                        table: table.name.clone(),
+                        error: err.to_string(),
+                    })),
+                    Err(err) => Err(err),
+                }
+            })
+            .collect();
+        let failed: Vec<FailedTable> = stream::iter(details)
+            .buffer_unordered(opts.concurrency.max(1))
+            .try_collect::<Vec<Option<FailedTable>>>()
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+        tables.retain(|table| !failed.iter().any(|failed| failed.table == table.name));
+        failed_tables.extend(failed);
+        timings.record("details", started.elapsed());
+
+        if opts.sample_stats && dialect.has_postgres_extras() {
+            let started = Instant::now();
+            mapper::apply_stats(
+                &mut tables,
+                retry::run(opts, deadline, || {
+                    queries::list_table_stats(pool, &schema_name)
+                })
+                .await?,
+                retry::run(opts, deadline, || {
+                    queries::list_column_stats(pool, &schema_name)
+                })
+                .await?,
             );
-            mapper::sort_constraints(&mut constraints);
-            table.constraints = constraints;
+            timings.record("stats", started.elapsed());
+        }
 
-            if opts.include_indexes {
-                let raw_indexes = queries::list_indexes(pool, &schema_name, &table.name).await?;
-                table.indexes = mapper::map_indexes(raw_indexes);
-            }
+        if opts.include_privileges {
+            let started = Instant::now();
+            mapper::apply_privileges(
+                &mut tables,
+                retry::run(opts, deadline, || {
+                    queries::list_table_privileges(pool, &schema_name)
+                })
+                .await?,
+            );
+            timings.record("privileges", started.elapsed());
         }
 
         tables.sort_by(|left, right| left.name.cmp(&right.name));
@@ -111,12 +309,121 @@ pub async fn introspect(pool: &PgPool, opts: &IntrospectOptions) -> Result<Datab
             .then_with(|| left.name.cmp(&right.name))
     });
 
-    Ok(DatabaseSchema {
+    let external_tables = filter::external_tables(&schema_items, opts);
+    let current_role = if opts.include_privileges {
+        Some(retry::run(opts, deadline, || queries::fetch_current_user(pool)).await?)
+    } else {
+        None
+    };
+
+    let schema = DatabaseSchema {
         schema_version: SCHEMA_VERSION.to_string(),
-        engine: "postgres".to_string(),
+        engine: dialect.engine().to_string(),
         database: Some(database),
         schemas: schema_items,
         enums,
+        domains,
+        composite_types,
+        sequences,
+        current_role,
+        failed_tables,
+        external_tables,
         schema_fingerprint: None,
-    })
+    }
+    .with_fingerprint()?;
+    Ok((schema, timings))
+}
+
+/// Columns, constraints, partitioning, indexes, triggers and rules of `table`.
+async fn introspect_table(
+    pool: &PgPool,
+    dialect: Dialect,
+    schema_name: &str,
+    table: &mut Table,
+    opts: &IntrospectOptions,
+) -> Result<()> {
+    let raw_columns = queries::list_columns(pool, schema_name, &table.name).await?;
+    table.columns = mapper::map_columns(raw_columns, opts);
+
+    let raw_pk = queries::get_primary_key(pool, schema_name, &table.name).await?;
+    let raw_uniques = queries::list_unique_constraints(pool, schema_name, &table.name).await?;
+    let raw_checks = queries::list_check_constraints(pool, schema_name, &table.name).await?;
+    let raw_fks = queries::list_foreign_keys(pool, schema_name, &table.name).await?;
+    let raw_exclusions = if dialect.has_postgres_extras() {
+        queries::list_exclusion_constraints(pool, schema_name, &table.name).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut constraints = Vec::new();
+    if let Some(pk) = mapper::map_primary_key(raw_pk) {
+        constraints.push(datalchemy_core::Constraint::PrimaryKey(pk));
+    }
+    constraints.extend(
+        mapper::map_unique_constraints(raw_uniques)
+            .into_iter()
+            .map(datalchemy_core::Constraint::Unique),
+    );
+    constraints.extend(
+        mapper::map_check_constraints(raw_checks)
+            .into_iter()
+            .map(datalchemy_core::Constraint::Check),
+    );
+    constraints.extend(
+        mapper::map_foreign_keys(raw_fks)
+            .into_iter()
+            .map(datalchemy_core::Constraint::ForeignKey),
+    );
+    constraints.extend(
+        mapper::map_exclusion_constraints(raw_exclusions)
+            .into_iter()
+            .map(datalchemy_core::Constraint::Exclusion),
+    );
+    mapper::sort_constraints(&mut constraints);
+    table.constraints = constraints;
+
+    if table.kind == TableKind::PartitionedTable {
+        let raw_key = queries::get_partition_key(pool, schema_name, &table.name).await?;
+        table.partition_key = mapper::map_partition_key(raw_key);
+        let raw_partitions = queries::list_partitions(pool, schema_name, &table.name).await?;
+        table.partitions = mapper::map_partitions(raw_partitions);
+    }
+
+    if table.kind.is_view() {
+        table.view_definition =
+            queries::get_view_definition(pool, schema_name, &table.name).await?;
+        if dialect.has_postgres_extras() {
+            table.depends_on = mapper::map_view_dependencies(
+                queries::list_view_dependencies(pool, schema_name, &table.name).await?,
+            );
+        }
+    }
+
+    if table.kind == TableKind::ForeignTable {
+        table.foreign_table = mapper::map_foreign_table(
+            queries::get_foreign_table(pool, schema_name, &table.name).await?,
+        );
+    }
+
+    if opts.include_indexes {
+        let raw_indexes = queries::list_indexes(pool, schema_name, &table.name).await?;
+        table.indexes = mapper::map_indexes(raw_indexes);
+    }
+
+    if dialect.has_postgres_extras() {
+        table.triggers =
+            mapper::map_triggers(queries::list_triggers(pool, schema_name, &table.name).await?);
+        table.rules = mapper::map_rules(queries::list_rules(pool, schema_name, &table.name).await?);
+    }
+
+    if dialect.has_hidden_columns() {
+        let hidden = queries::list_hidden_columns(pool, schema_name, &table.name).await?;
+        let sharded = if opts.include_indexes {
+            queries::list_sharded_indexes(pool, schema_name, &table.name).await?
+        } else {
+            Vec::new()
+        };
+        mapper::apply_hidden_columns(table, hidden, sharded);
+    }
+    Ok(())
 }
@@ -2,6 +2,8 @@ use sqlx::{PgPool, Row};
 
 use datalchemy_core::Result;
 
+use super::dialect::Dialect;
+
 fn db_err(err: sqlx::Error) -> datalchemy_core::Error {
     datalchemy_core::Error::Db(err.to_string())
 }
@@ -14,6 +16,20 @@ pub async fn fetch_database_name(pool: &PgPool) -> Result<String> {
     Ok(name)
 }
 
+pub async fn fetch_current_user(pool: &PgPool) -> Result<String> {
+    sqlx::query_scalar::<_, String>("select current_user::text")
+        .fetch_one(pool)
+        .await
+        .map_err(db_err)
+}
+
+pub async fn fetch_server_version(pool: &PgPool) -> Result<String> {
+    sqlx::query_scalar::<_, String>("select version()")
+        .fetch_one(pool)
+        .await
+        .map_err(db_err)
+}
+
 pub async fn list_schemas(pool: &PgPool) -> Result<Vec<String>> {
     let rows = sqlx::query(
         r#"
@@ -26,11 +42,10 @@ pub async fn list_schemas(pool: &PgPool) -> Result<Vec<String>> {
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| row.try_get::<String, _>("name"))
         .collect::<std::result::Result<Vec<_>, _>>()
-        .map_err(db_err)?)
+        .map_err(db_err)
 }
 
 pub struct RawTable {
@@ -58,8 +73,7 @@ pub async fn list_tables_in_schema(pool: &PgPool, schema: &str) -> Result<Vec<Ra
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawTable {
                 name: row.try_get::<String, _>("name").map_err(db_err)?,
@@ -69,7 +83,7 @@ pub async fn list_tables_in_schema(pool: &PgPool, schema: &str) -> Result<Vec<Ra
                     .map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
 }
 
 pub struct RawColumn {
@@ -81,7 +95,8 @@ pub struct RawColumn {
     pub is_nullable: bool,
     pub default: Option<String>,
     pub identity_generation: Option<String>,
-    pub is_generated: bool,
+    /// `STORED` or `VIRTUAL` for generated columns.
+    pub generated_kind: Option<String>,
     pub generation_expression: Option<String>,
     pub character_max_length: Option<i32>,
     pub numeric_precision: Option<i32>,
@@ -100,14 +115,21 @@ pub async fn list_columns(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
           tn.nspname as "udt_schema",
           t.typname as "udt_name",
           (not a.attnotnull) as "is_nullable",
-          pg_get_expr(ad.adbin, ad.adrelid) as "default",
+          case
+            when a.attgenerated = '' then pg_get_expr(ad.adbin, ad.adrelid)
+            else null
+          end as "default",
           case
             when a.attidentity = '' then null
             when a.attidentity = 'a' then 'ALWAYS'
             when a.attidentity = 'd' then 'BY DEFAULT'
             else null
           end as "identity_generation",
-          (a.attgenerated <> '') as "is_generated",
+          case a.attgenerated
+            when 's' then 'STORED'
+            when 'v' then 'VIRTUAL'
+            else null
+          end as "generated_kind",
           case
             when a.attgenerated <> '' then pg_get_expr(ad.adbin, ad.adrelid)
             else null
@@ -138,8 +160,7 @@ pub async fn list_columns(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawColumn {
                 ordinal_position: row.try_get::<i16, _>("ordinal_position").map_err(db_err)?,
@@ -154,7 +175,9 @@ pub async fn list_columns(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
                 identity_generation: row
                     .try_get::<Option<String>, _>("identity_generation")
                     .map_err(db_err)?,
-                is_generated: row.try_get::<bool, _>("is_generated").map_err(db_err)?,
+                generated_kind: row
+                    .try_get::<Option<String>, _>("generated_kind")
+                    .map_err(db_err)?,
                 generation_expression: row
                     .try_get::<Option<String>, _>("generation_expression")
                     .map_err(db_err)?,
@@ -175,7 +198,7 @@ pub async fn list_columns(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
                     .map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
 }
 
 pub struct RawPrimaryKey {
@@ -216,6 +239,200 @@ pub async fn get_primary_key(
     }))
 }
 
+pub async fn get_partition_key(pool: &PgPool, schema: &str, table: &str) -> Result<Option<String>> {
+    let row = sqlx::query(
+        r#"
+        select pg_get_partkeydef(c.oid) as "definition"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        where n.nspname = $1
+          and c.relname = $2
+          and c.relkind = 'p'
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_err)?;
+
+    Ok(row.and_then(|row| {
+        row.try_get::<Option<String>, _>("definition")
+            .ok()
+            .flatten()
+    }))
+}
+
+/// Query of a view or materialized view, as `pg_get_viewdef` prints it.
+pub async fn get_view_definition(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+) -> Result<Option<String>> {
+    let row = sqlx::query(
+        r#"
+        select pg_get_viewdef(c.oid, true) as "definition"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        where n.nspname = $1
+          and c.relname = $2
+          and c.relkind in ('v','m')
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_err)?;
+
+    Ok(row.and_then(|row| {
+        row.try_get::<Option<String>, _>("definition")
+            .ok()
+            .flatten()
+    }))
+}
+
+pub struct RawForeignTable {
+    pub server: String,
+    pub wrapper: String,
+    pub options: Vec<String>,
+    pub server_options: Vec<String>,
+}
+
+/// Server, wrapper and `key=value` options of a foreign table.
+pub async fn get_foreign_table(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+) -> Result<Option<RawForeignTable>> {
+    let row = sqlx::query(
+        r#"
+        select
+          s.srvname as "server",
+          w.fdwname as "wrapper",
+          coalesce(ft.ftoptions, '{}'::text[]) as "options",
+          coalesce(s.srvoptions, '{}'::text[]) as "server_options"
+        from pg_foreign_table ft
+        join pg_class c on c.oid = ft.ftrelid
+        join pg_namespace n on n.oid = c.relnamespace
+        join pg_foreign_server s on s.oid = ft.ftserver
+        join pg_foreign_data_wrapper w on w.oid = s.srvfdw
+        where n.nspname = $1
+          and c.relname = $2
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_err)?;
+
+    row.map(|row| {
+        Ok(RawForeignTable {
+            server: row.try_get::<String, _>("server").map_err(db_err)?,
+            wrapper: row.try_get::<String, _>("wrapper").map_err(db_err)?,
+            options: row.try_get::<Vec<String>, _>("options").map_err(db_err)?,
+            server_options: row
+                .try_get::<Vec<String>, _>("server_options")
+                .map_err(db_err)?,
+        })
+    })
+    .transpose()
+}
+
+pub struct RawViewDependency {
+    pub schema: String,
+    pub name: String,
+}
+
+/// Relations read by a view or materialized view: the `pg_depend` entries
+/// of its `_RETURN` rule.
+pub async fn list_view_dependencies(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<RawViewDependency>> {
+    let rows = sqlx::query(
+        r#"
+        select distinct
+          ref_nsp.nspname as "schema",
+          ref.relname as "name"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        join pg_rewrite r on r.ev_class = c.oid and r.rulename = '_RETURN'
+        join pg_depend d
+          on d.classid = 'pg_rewrite'::regclass
+         and d.objid = r.oid
+         and d.refclassid = 'pg_class'::regclass
+        join pg_class ref on ref.oid = d.refobjid
+        join pg_namespace ref_nsp on ref_nsp.oid = ref.relnamespace
+        where n.nspname = $1
+          and c.relname = $2
+          and ref.oid <> c.oid
+        order by 1, 2
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawViewDependency {
+                schema: row.try_get::<String, _>("schema").map_err(db_err)?,
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawPartition {
+    pub schema: String,
+    pub name: String,
+    pub bound: Option<String>,
+}
+
+pub async fn list_partitions(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<RawPartition>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          cn.nspname as "schema",
+          c.relname as "name",
+          pg_get_expr(c.relpartbound, c.oid) as "bound"
+        from pg_inherits i
+        join pg_class p on p.oid = i.inhparent
+        join pg_namespace pn on pn.oid = p.relnamespace
+        join pg_class c on c.oid = i.inhrelid
+        join pg_namespace cn on cn.oid = c.relnamespace
+        where pn.nspname = $1
+          and p.relname = $2
+          and c.relispartition
+        order by cn.nspname, c.relname
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawPartition {
+                schema: row.try_get::<String, _>("schema").map_err(db_err)?,
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                bound: row.try_get::<Option<String>, _>("bound").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
 pub struct RawUniqueConstraint {
     pub name: String,
     pub columns: Vec<String>,
@@ -253,8 +470,7 @@ pub async fn list_unique_constraints(
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawUniqueConstraint {
                 name: row.try_get::<String, _>("name").map_err(db_err)?,
@@ -265,7 +481,7 @@ pub async fn list_unique_constraints(
                     .map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
 }
 
 pub struct RawCheckConstraint {
@@ -298,15 +514,168 @@ pub async fn list_check_constraints(
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawCheckConstraint {
                 name: row.try_get::<String, _>("name").map_err(db_err)?,
                 expression: row.try_get::<String, _>("expression").map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawExclusionConstraint {
+    pub name: String,
+    pub method: String,
+    pub expressions: Vec<String>,
+    pub operators: Vec<String>,
+    pub predicate: Option<String>,
+    pub is_deferrable: bool,
+    pub initially_deferred: bool,
+}
+
+pub async fn list_exclusion_constraints(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<RawExclusionConstraint>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          con.conname as "name",
+          am.amname as "method",
+          array_agg(pg_get_indexdef(con.conindid, op.ord::int, true) order by op.ord)
+            as "expressions",
+          array_agg(opr.oprname::text order by op.ord) as "operators",
+          pg_get_expr(i.indpred, i.indrelid, true) as "predicate",
+          con.condeferrable as "is_deferrable",
+          con.condeferred as "initially_deferred"
+        from pg_constraint con
+        join pg_class rel on rel.oid = con.conrelid
+        join pg_namespace nsp on nsp.oid = rel.relnamespace
+        join pg_index i on i.indexrelid = con.conindid
+        join pg_class idx on idx.oid = con.conindid
+        join pg_am am on am.oid = idx.relam
+        join unnest(con.conexclop) with ordinality as op(oid, ord) on true
+        join pg_operator opr on opr.oid = op.oid
+        where nsp.nspname = $1
+          and rel.relname = $2
+          and con.contype = 'x'
+        group by
+          con.conname, am.amname, i.indpred, i.indrelid,
+          con.condeferrable, con.condeferred
+        order by con.conname
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawExclusionConstraint {
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                method: row.try_get::<String, _>("method").map_err(db_err)?,
+                expressions: row
+                    .try_get::<Vec<String>, _>("expressions")
+                    .map_err(db_err)?,
+                operators: row.try_get::<Vec<String>, _>("operators").map_err(db_err)?,
+                predicate: row
+                    .try_get::<Option<String>, _>("predicate")
+                    .map_err(db_err)?,
+                is_deferrable: row.try_get::<bool, _>("is_deferrable").map_err(db_err)?,
+                initially_deferred: row
+                    .try_get::<bool, _>("initially_deferred")
+                    .map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawTrigger {
+    pub name: String,
+    pub tgtype: i16,
+    pub function: String,
+    pub enabled_code: i8,
+}
+
+pub async fn list_triggers(pool: &PgPool, schema: &str, table: &str) -> Result<Vec<RawTrigger>> {
+    // Internal triggers implement foreign keys and deferred constraints.
+    let rows = sqlx::query(
+        r#"
+        select
+          t.tgname as "name",
+          t.tgtype as "tgtype",
+          fn_nsp.nspname || '.' || p.proname as "function",
+          t.tgenabled as "enabled_code"
+        from pg_trigger t
+        join pg_class rel on rel.oid = t.tgrelid
+        join pg_namespace nsp on nsp.oid = rel.relnamespace
+        join pg_proc p on p.oid = t.tgfoid
+        join pg_namespace fn_nsp on fn_nsp.oid = p.pronamespace
+        where nsp.nspname = $1
+          and rel.relname = $2
+          and not t.tgisinternal
+        order by t.tgname
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawTrigger {
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                tgtype: row.try_get::<i16, _>("tgtype").map_err(db_err)?,
+                function: row.try_get::<String, _>("function").map_err(db_err)?,
+                enabled_code: row.try_get::<i8, _>("enabled_code").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawRule {
+    pub name: String,
+    pub event_code: i8,
+    pub is_instead: bool,
+}
+
+pub async fn list_rules(pool: &PgPool, schema: &str, table: &str) -> Result<Vec<RawRule>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          r.rulename as "name",
+          r.ev_type as "event_code",
+          r.is_instead as "is_instead"
+        from pg_rewrite r
+        join pg_class rel on rel.oid = r.ev_class
+        join pg_namespace nsp on nsp.oid = rel.relnamespace
+        where nsp.nspname = $1
+          and rel.relname = $2
+          and r.rulename <> '_RETURN'
+        order by r.rulename
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawRule {
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                event_code: row.try_get::<i8, _>("event_code").map_err(db_err)?,
+                is_instead: row.try_get::<bool, _>("is_instead").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
 }
 
 pub struct RawForeignKey {
@@ -366,8 +735,7 @@ pub async fn list_foreign_keys(
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawForeignKey {
                 name: row.try_get::<String, _>("name").map_err(db_err)?,
@@ -390,7 +758,7 @@ pub async fn list_foreign_keys(
                     .map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
 }
 
 pub struct RawIndex {
@@ -400,6 +768,8 @@ pub struct RawIndex {
     pub is_valid: bool,
     pub method: String,
     pub definition: String,
+    pub predicate: Option<String>,
+    pub expressions: Vec<String>,
 }
 
 pub async fn list_indexes(pool: &PgPool, schema: &str, table: &str) -> Result<Vec<RawIndex>> {
@@ -411,7 +781,16 @@ pub async fn list_indexes(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
           i.indisprimary as "is_primary",
           i.indisvalid as "is_valid",
           am.amname as "method",
-          pg_get_indexdef(i.indexrelid) as "definition"
+          pg_get_indexdef(i.indexrelid) as "definition",
+          pg_get_expr(i.indpred, i.indrelid, true) as "predicate",
+          coalesce(
+            (
+              select array_agg(pg_get_indexdef(i.indexrelid, k.ord::int, true) order by k.ord)
+              from unnest(i.indkey::int2[]) with ordinality as k(attnum, ord)
+              where k.attnum = 0
+            ),
+            '{}'::text[]
+          ) as "expressions"
         from pg_index i
         join pg_class tbl on tbl.oid = i.indrelid
         join pg_namespace nsp on nsp.oid = tbl.relnamespace
@@ -428,8 +807,7 @@ pub async fn list_indexes(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawIndex {
                 name: row.try_get::<String, _>("name").map_err(db_err)?,
@@ -438,9 +816,15 @@ pub async fn list_indexes(pool: &PgPool, schema: &str, table: &str) -> Result<Ve
                 is_valid: row.try_get::<bool, _>("is_valid").map_err(db_err)?,
                 method: row.try_get::<String, _>("method").map_err(db_err)?,
                 definition: row.try_get::<String, _>("definition").map_err(db_err)?,
+                predicate: row
+                    .try_get::<Option<String>, _>("predicate")
+                    .map_err(db_err)?,
+                expressions: row
+                    .try_get::<Vec<String>, _>("expressions")
+                    .map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
 }
 
 pub struct RawEnumType {
@@ -467,8 +851,7 @@ pub async fn list_enums(pool: &PgPool) -> Result<Vec<RawEnumType>> {
     .await
     .map_err(db_err)?;
 
-    Ok(rows
-        .into_iter()
+    rows.into_iter()
         .map(|row| {
             Ok(RawEnumType {
                 schema: row.try_get::<String, _>("schema").map_err(db_err)?,
@@ -476,5 +859,491 @@ pub async fn list_enums(pool: &PgPool) -> Result<Vec<RawEnumType>> {
                 labels: row.try_get::<Vec<String>, _>("labels").map_err(db_err)?,
             })
         })
-        .collect::<Result<Vec<_>>>()?)
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawDomain {
+    pub schema: String,
+    pub name: String,
+    pub data_type: String,
+    pub udt_schema: String,
+    pub udt_name: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+    pub character_max_length: Option<i32>,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+    pub collation: Option<String>,
+    pub check_names: Vec<String>,
+    pub check_expressions: Vec<String>,
+}
+
+pub async fn list_domains(pool: &PgPool) -> Result<Vec<RawDomain>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          n.nspname as "schema",
+          t.typname as "name",
+          pg_catalog.format_type(t.typbasetype, t.typtypmod) as "data_type",
+          bn.nspname as "udt_schema",
+          bt.typname as "udt_name",
+          t.typnotnull as "not_null",
+          t.typdefault as "default",
+          d.character_maximum_length as "character_max_length",
+          d.numeric_precision as "numeric_precision",
+          d.numeric_scale as "numeric_scale",
+          d.collation_name as "collation",
+          coalesce(
+            array_agg(c.conname::text order by c.conname) filter (where c.oid is not null),
+            '{}'
+          ) as "check_names",
+          coalesce(
+            array_agg(pg_get_constraintdef(c.oid) order by c.conname)
+              filter (where c.oid is not null),
+            '{}'
+          ) as "check_expressions"
+        from pg_type t
+        join pg_namespace n on n.oid = t.typnamespace
+        join pg_type bt on bt.oid = t.typbasetype
+        join pg_namespace bn on bn.oid = bt.typnamespace
+        left join information_schema.domains d
+          on d.domain_schema = n.nspname and d.domain_name = t.typname
+        left join pg_constraint c on c.contypid = t.oid and c.contype = 'c'
+        where t.typtype = 'd'
+        group by
+          n.nspname, t.typname, t.typbasetype, t.typtypmod, bn.nspname, bt.typname,
+          t.typnotnull, t.typdefault, d.character_maximum_length, d.numeric_precision,
+          d.numeric_scale, d.collation_name
+        order by n.nspname, t.typname
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawDomain {
+                schema: row.try_get::<String, _>("schema").map_err(db_err)?,
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                data_type: row.try_get::<String, _>("data_type").map_err(db_err)?,
+                udt_schema: row.try_get::<String, _>("udt_schema").map_err(db_err)?,
+                udt_name: row.try_get::<String, _>("udt_name").map_err(db_err)?,
+                not_null: row.try_get::<bool, _>("not_null").map_err(db_err)?,
+                default: row
+                    .try_get::<Option<String>, _>("default")
+                    .map_err(db_err)?,
+                character_max_length: row
+                    .try_get::<Option<i32>, _>("character_max_length")
+                    .map_err(db_err)?,
+                numeric_precision: row
+                    .try_get::<Option<i32>, _>("numeric_precision")
+                    .map_err(db_err)?,
+                numeric_scale: row
+                    .try_get::<Option<i32>, _>("numeric_scale")
+                    .map_err(db_err)?,
+                collation: row
+                    .try_get::<Option<String>, _>("collation")
+                    .map_err(db_err)?,
+                check_names: row
+                    .try_get::<Vec<String>, _>("check_names")
+                    .map_err(db_err)?,
+                check_expressions: row
+                    .try_get::<Vec<String>, _>("check_expressions")
+                    .map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+/// One attribute of a composite type; rows come ordered by type and position.
+pub struct RawCompositeAttribute {
+    pub schema: String,
+    pub type_name: String,
+    pub name: String,
+    pub data_type: String,
+    pub udt_schema: String,
+    pub udt_name: String,
+    pub character_max_length: Option<i32>,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+    pub collation: Option<String>,
+}
+
+pub async fn list_composite_attributes(pool: &PgPool) -> Result<Vec<RawCompositeAttribute>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          n.nspname as "schema",
+          t.typname as "type_name",
+          a.attname as "name",
+          pg_catalog.format_type(a.atttypid, a.atttypmod) as "data_type",
+          an.nspname as "udt_schema",
+          at.typname as "udt_name",
+          ia.character_maximum_length as "character_max_length",
+          ia.numeric_precision as "numeric_precision",
+          ia.numeric_scale as "numeric_scale",
+          ia.collation_name as "collation"
+        from pg_type t
+        join pg_namespace n on n.oid = t.typnamespace
+        join pg_class c on c.oid = t.typrelid and c.relkind = 'c'
+        join pg_attribute a on a.attrelid = c.oid and a.attnum > 0 and not a.attisdropped
+        join pg_type at on at.oid = a.atttypid
+        join pg_namespace an on an.oid = at.typnamespace
+        left join information_schema.attributes ia
+          on ia.udt_schema = n.nspname and ia.udt_name = t.typname
+          and ia.attribute_name = a.attname
+        where t.typtype = 'c'
+        order by n.nspname, t.typname, a.attnum
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawCompositeAttribute {
+                schema: row.try_get::<String, _>("schema").map_err(db_err)?,
+                type_name: row.try_get::<String, _>("type_name").map_err(db_err)?,
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                data_type: row.try_get::<String, _>("data_type").map_err(db_err)?,
+                udt_schema: row.try_get::<String, _>("udt_schema").map_err(db_err)?,
+                udt_name: row.try_get::<String, _>("udt_name").map_err(db_err)?,
+                character_max_length: row
+                    .try_get::<Option<i32>, _>("character_max_length")
+                    .map_err(db_err)?,
+                numeric_precision: row
+                    .try_get::<Option<i32>, _>("numeric_precision")
+                    .map_err(db_err)?,
+                numeric_scale: row
+                    .try_get::<Option<i32>, _>("numeric_scale")
+                    .map_err(db_err)?,
+                collation: row
+                    .try_get::<Option<String>, _>("collation")
+                    .map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawSequence {
+    pub schema: String,
+    pub name: String,
+    pub start: i64,
+    pub increment: i64,
+    pub cache: i64,
+    pub last_value: Option<i64>,
+    pub owner_schema: Option<String>,
+    pub owner_table: Option<String>,
+    pub owner_column: Option<String>,
+}
+
+pub async fn list_sequences(
+    pool: &PgPool,
+    schema: &str,
+    dialect: Dialect,
+) -> Result<Vec<RawSequence>> {
+    let last_value = if dialect.has_sequence_last_value() {
+        "case
+            when has_sequence_privilege(c.oid, 'SELECT') then pg_sequence_last_value(c.oid)
+            else null
+          end"
+    } else {
+        "null::int8"
+    };
+    let sql = format!(
+        r#"
+        select
+          n.nspname as "schema",
+          c.relname as "name",
+          s.seqstart as "start",
+          s.seqincrement as "increment",
+          s.seqcache as "cache",
+          {last_value} as "last_value",
+          tn.nspname as "owner_schema",
+          t.relname as "owner_table",
+          a.attname as "owner_column"
+        from pg_sequence s
+        join pg_class c on c.oid = s.seqrelid
+        join pg_namespace n on n.oid = c.relnamespace
+        left join pg_depend d
+          on d.classid = 'pg_class'::regclass
+          and d.objid = c.oid
+          and d.refclassid = 'pg_class'::regclass
+          and d.deptype in ('a', 'i')
+        left join pg_class t on t.oid = d.refobjid
+        left join pg_namespace tn on tn.oid = t.relnamespace
+        left join pg_attribute a on a.attrelid = d.refobjid and a.attnum = d.refobjsubid
+        where n.nspname = $1
+        order by c.relname
+        "#
+    );
+    let rows = sqlx::query(&sql)
+        .bind(schema)
+        .fetch_all(pool)
+        .await
+        .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawSequence {
+                schema: row.try_get::<String, _>("schema").map_err(db_err)?,
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                start: row.try_get::<i64, _>("start").map_err(db_err)?,
+                increment: row.try_get::<i64, _>("increment").map_err(db_err)?,
+                cache: row.try_get::<i64, _>("cache").map_err(db_err)?,
+                last_value: row
+                    .try_get::<Option<i64>, _>("last_value")
+                    .map_err(db_err)?,
+                owner_schema: row
+                    .try_get::<Option<String>, _>("owner_schema")
+                    .map_err(db_err)?,
+                owner_table: row
+                    .try_get::<Option<String>, _>("owner_table")
+                    .map_err(db_err)?,
+                owner_column: row
+                    .try_get::<Option<String>, _>("owner_column")
+                    .map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawCatalogVersion {
+    pub table: String,
+    pub version: String,
+}
+
+/// Digest of the `xmin` of every catalog row describing each relation of
+/// `schema`: `xmin` changes when DDL rewrites the row, while `ANALYZE` and
+/// `VACUUM` update `pg_class` in place and leave it alone.
+pub async fn list_catalog_versions(pool: &PgPool, schema: &str) -> Result<Vec<RawCatalogVersion>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          c.relname as "table",
+          md5(concat_ws('|',
+            c.xmin::text,
+            (select string_agg(a.xmin::text, ',' order by a.attnum)
+               from pg_attribute a where a.attrelid = c.oid),
+            (select string_agg(ad.xmin::text, ',' order by ad.adnum)
+               from pg_attrdef ad where ad.adrelid = c.oid),
+            (select string_agg(con.xmin::text, ',' order by con.oid)
+               from pg_constraint con where con.conrelid = c.oid),
+            (select string_agg(i.xmin::text || ':' || ic.xmin::text, ',' order by i.indexrelid)
+               from pg_index i join pg_class ic on ic.oid = i.indexrelid
+               where i.indrelid = c.oid),
+            (select string_agg(t.xmin::text, ',' order by t.oid)
+               from pg_trigger t where t.tgrelid = c.oid),
+            (select string_agg(r.xmin::text, ',' order by r.oid)
+               from pg_rewrite r where r.ev_class = c.oid),
+            (select string_agg(d.xmin::text, ',' order by d.objsubid)
+               from pg_description d
+               where d.objoid = c.oid and d.classoid = 'pg_class'::regclass),
+            (select string_agg(inh.xmin::text, ',' order by inh.inhrelid)
+               from pg_inherits inh where inh.inhparent = c.oid),
+            (select pt.xmin::text from pg_partitioned_table pt where pt.partrelid = c.oid)
+          )) as "version"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        where n.nspname = $1
+          and c.relkind in ('r','p','v','m','f')
+        order by c.relname
+        "#,
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawCatalogVersion {
+                table: row.try_get::<String, _>("table").map_err(db_err)?,
+                version: row.try_get::<String, _>("version").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+/// CockroachDB `NOT VISIBLE` columns of `table` (implicit `rowid`, hash
+/// shard columns).
+pub async fn list_hidden_columns(pool: &PgPool, schema: &str, table: &str) -> Result<Vec<String>> {
+    let rows = sqlx::query(
+        r#"
+        select column_name as "name"
+        from information_schema.columns
+        where table_schema = $1
+          and table_name = $2
+          and is_hidden = 'YES'
+        order by ordinal_position
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| row.try_get::<String, _>("name"))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(db_err)
+}
+
+pub struct RawShardedIndex {
+    pub name: String,
+    pub buckets: i64,
+}
+
+/// CockroachDB hash-sharded indexes of `table` with their bucket count.
+pub async fn list_sharded_indexes(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<RawShardedIndex>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          i.index_name as "name",
+          i.shard_bucket_count::int8 as "buckets"
+        from crdb_internal.table_indexes i
+        join crdb_internal.tables t on t.table_id = i.descriptor_id
+        where t.database_name = current_database()
+          and t.schema_name = $1
+          and t.name = $2
+          and i.is_sharded
+        order by i.index_name
+        "#,
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawShardedIndex {
+                name: row.try_get::<String, _>("name").map_err(db_err)?,
+                buckets: row.try_get::<i64, _>("buckets").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawTableStats {
+    pub table: String,
+    pub reltuples: f32,
+}
+
+pub async fn list_table_stats(pool: &PgPool, schema: &str) -> Result<Vec<RawTableStats>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          c.relname as "table",
+          c.reltuples as "reltuples"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        where n.nspname = $1
+          and c.relkind in ('r','p','m','f')
+        order by c.relname
+        "#,
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawTableStats {
+                table: row.try_get::<String, _>("table").map_err(db_err)?,
+                reltuples: row.try_get::<f32, _>("reltuples").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawTablePrivilege {
+    pub table: String,
+    pub grantee: String,
+    pub can_select: bool,
+    pub can_insert: bool,
+}
+
+/// SELECT/INSERT of every non-system role on the tables of `schema`;
+/// `has_table_privilege` follows `PUBLIC`, role membership and ownership.
+pub async fn list_table_privileges(pool: &PgPool, schema: &str) -> Result<Vec<RawTablePrivilege>> {
+    let rows = sqlx::query(
+        r#"
+        select
+          c.relname as "table",
+          r.rolname as "grantee",
+          has_table_privilege(r.oid, c.oid, 'SELECT') as "can_select",
+          has_table_privilege(r.oid, c.oid, 'INSERT') as "can_insert"
+        from pg_class c
+        join pg_namespace n on n.oid = c.relnamespace
+        cross join pg_roles r
+        where n.nspname = $1
+          and c.relkind in ('r','p','v','m','f')
+          and r.rolname !~ '^pg_'
+        order by c.relname, r.rolname
+        "#,
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawTablePrivilege {
+                table: row.try_get::<String, _>("table").map_err(db_err)?,
+                grantee: row.try_get::<String, _>("grantee").map_err(db_err)?,
+                can_select: row.try_get::<bool, _>("can_select").map_err(db_err)?,
+                can_insert: row.try_get::<bool, _>("can_insert").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+}
+
+pub struct RawColumnStats {
+    pub table: String,
+    pub column: String,
+    pub null_frac: f32,
+    pub n_distinct: f32,
+}
+
+pub async fn list_column_stats(pool: &PgPool, schema: &str) -> Result<Vec<RawColumnStats>> {
+    // Partitioned tables only have inherited statistics (the whole tree);
+    // other tables prefer their own.
+    let rows = sqlx::query(
+        r#"
+        select distinct on (s.tablename, s.attname)
+          s.tablename::text as "table",
+          s.attname::text as "column",
+          s.null_frac as "null_frac",
+          s.n_distinct as "n_distinct"
+        from pg_stats s
+        where s.schemaname = $1
+        order by s.tablename, s.attname, s.inherited
+        "#,
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(RawColumnStats {
+                table: row.try_get::<String, _>("table").map_err(db_err)?,
+                column: row.try_get::<String, _>("column").map_err(db_err)?,
+                null_frac: row.try_get::<f32, _>("null_frac").map_err(db_err)?,
+                n_distinct: row.try_get::<f32, _>("n_distinct").map_err(db_err)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
 }
@@ -1,4 +1,6 @@
-use datalchemy_core::{FkAction, FkMatchType, IdentityGeneration, TableKind};
+use datalchemy_core::{
+    FkAction, FkMatchType, IdentityGeneration, TableEvent, TableKind, TriggerTiming,
+};
 
 /// Convert Postgres `relkind` code to a typed table kind.
 pub fn relkind_to_table_kind(code: i8) -> TableKind {
@@ -42,3 +44,53 @@ pub fn identity_from_text(identity: Option<String>) -> Option<IdentityGeneration
         _ => None,
     })
 }
+
+// `pg_trigger.tgtype` bits (see `TRIGGER_TYPE_*` in `pg_trigger.h`).
+const TRIGGER_TYPE_ROW: i16 = 1 << 0;
+const TRIGGER_TYPE_BEFORE: i16 = 1 << 1;
+const TRIGGER_TYPE_INSERT: i16 = 1 << 2;
+const TRIGGER_TYPE_DELETE: i16 = 1 << 3;
+const TRIGGER_TYPE_UPDATE: i16 = 1 << 4;
+const TRIGGER_TYPE_TRUNCATE: i16 = 1 << 5;
+const TRIGGER_TYPE_INSTEAD: i16 = 1 << 6;
+
+/// Timing encoded in `pg_trigger.tgtype`.
+pub fn trigger_timing_from_type(tgtype: i16) -> TriggerTiming {
+    if tgtype & TRIGGER_TYPE_INSTEAD != 0 {
+        TriggerTiming::InsteadOf
+    } else if tgtype & TRIGGER_TYPE_BEFORE != 0 {
+        TriggerTiming::Before
+    } else {
+        TriggerTiming::After
+    }
+}
+
+/// Events encoded in `pg_trigger.tgtype`, in statement order.
+pub fn trigger_events_from_type(tgtype: i16) -> Vec<TableEvent> {
+    [
+        (TRIGGER_TYPE_INSERT, TableEvent::Insert),
+        (TRIGGER_TYPE_UPDATE, TableEvent::Update),
+        (TRIGGER_TYPE_DELETE, TableEvent::Delete),
+        (TRIGGER_TYPE_TRUNCATE, TableEvent::Truncate),
+    ]
+    .into_iter()
+    .filter(|(bit, _)| tgtype & bit != 0)
+    .map(|(_, event)| event)
+    .collect()
+}
+
+/// Whether `pg_trigger.tgtype` is a `FOR EACH ROW` trigger.
+pub fn trigger_is_row_level(tgtype: i16) -> bool {
+    tgtype & TRIGGER_TYPE_ROW != 0
+}
+
+/// Convert `pg_rewrite.ev_type` to the event of a rule; `None` for
+/// `SELECT` (view `_RETURN` rules).
+pub fn rule_event_from_code(code: i8) -> Option<TableEvent> {
+    match code as u8 as char {
+        '2' => Some(TableEvent::Update),
+        '3' => Some(TableEvent::Insert),
+        '4' => Some(TableEvent::Delete),
+        _ => None,
+    }
+}
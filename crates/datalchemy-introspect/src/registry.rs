@@ -0,0 +1,189 @@
+//! Adapters resolved from the scheme of a connection string.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use datalchemy_core::{Error, Result};
+
+use crate::adapter::Adapter;
+use crate::options::IntrospectOptions;
+
+/// Opens adapters for the connection strings of one engine.
+///
+/// Adapter crates implement it and register it in an [`AdapterRegistry`],
+/// which hands it the connection strings using one of its URL schemes.
+#[async_trait]
+pub trait AdapterFactory: Send + Sync {
+    /// Engine identifier of the opened adapters (e.g. `postgres`).
+    fn engine(&self) -> &'static str;
+
+    /// URL schemes accepted, without `://` (e.g. `postgres`, `postgresql`).
+    fn schemes(&self) -> &'static [&'static str];
+
+    /// Open an adapter for `conn`, whose scheme is one of [`Self::schemes`].
+    async fn open(
+        &self,
+        conn: &str,
+        opts: &IntrospectOptions,
+    ) -> Result<Box<dyn Adapter + Send + Sync>>;
+}
+
+/// Adapter factories by URL scheme.
+///
+/// [`AdapterRegistry::builtin`] holds the adapters of this crate (`postgres`,
+/// `sqlite` and, with the `duckdb` feature, `duckdb`); other crates add theirs
+/// with [`AdapterRegistry::register`].
+#[derive(Clone, Default)]
+pub struct AdapterRegistry {
+    factories: BTreeMap<String, Arc<dyn AdapterFactory>>,
+}
+
+impl AdapterRegistry {
+    /// Registry without adapters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the adapters of this crate.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register(crate::postgres::PostgresFactory)
+            .register(crate::sqlite::SqliteFactory);
+        #[cfg(feature = "duckdb")]
+        registry.register(crate::duckdb::DuckDbFactory);
+        registry
+    }
+
+    /// Register `factory` under each of its schemes, replacing the factory
+    /// previously registered for them.
+    pub fn register(&mut self, factory: impl AdapterFactory + 'static) -> &mut Self {
+        let factory: Arc<dyn AdapterFactory> = Arc::new(factory);
+        for scheme in factory.schemes() {
+            self.factories
+                .insert(scheme.to_ascii_lowercase(), Arc::clone(&factory));
+        }
+        self
+    }
+
+    /// Registered schemes, sorted.
+    pub fn schemes(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Factory registered for the scheme of `conn`.
+    pub fn resolve(&self, conn: &str) -> Option<&dyn AdapterFactory> {
+        let (scheme, _) = conn.split_once("://")?;
+        self.factories
+            .get(&scheme.to_ascii_lowercase())
+            .map(|factory| factory.as_ref())
+    }
+
+    /// Engine of the adapter registered for the scheme of `conn`.
+    pub fn engine_for(&self, conn: &str) -> Option<&'static str> {
+        self.resolve(conn).map(|factory| factory.engine())
+    }
+
+    /// Open the adapter registered for the scheme of `conn`.
+    pub async fn open(
+        &self,
+        conn: &str,
+        opts: &IntrospectOptions,
+    ) -> Result<Box<dyn Adapter + Send + Sync>> {
+        match self.resolve(conn) {
+            Some(factory) => factory.open(conn, opts).await,
+            None => Err(Error::Unsupported(format!(
+                "no adapter registered for the connection scheme (known: {})",
+                self.schemes().collect::<Vec<_>>().join(", ")
+            ))),
+        }
+    }
+}
+
+impl fmt::Debug for AdapterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.factories
+                    .iter()
+                    .map(|(scheme, factory)| (scheme, factory.engine())),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datalchemy_core::DatabaseSchema;
+
+    use super::*;
+
+    struct StubAdapter;
+
+    #[async_trait]
+    impl Adapter for StubAdapter {
+        fn engine(&self) -> &'static str {
+            "stub"
+        }
+
+        async fn introspect(&self, _opts: &IntrospectOptions) -> Result<DatabaseSchema> {
+            Err(Error::Unsupported("stub".to_string()))
+        }
+    }
+
+    struct StubFactory;
+
+    #[async_trait]
+    impl AdapterFactory for StubFactory {
+        fn engine(&self) -> &'static str {
+            "stub"
+        }
+
+        fn schemes(&self) -> &'static [&'static str] {
+            &["stub", "postgres"]
+        }
+
+        async fn open(
+            &self,
+            _conn: &str,
+            _opts: &IntrospectOptions,
+        ) -> Result<Box<dyn Adapter + Send + Sync>> {
+            Ok(Box::new(StubAdapter))
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_adapters_by_scheme() {
+        let mut registry = AdapterRegistry::builtin();
+        assert_eq!(
+            registry.engine_for("postgresql://user@db/crm"),
+            Some("postgres")
+        );
+        assert_eq!(registry.engine_for("SQLITE://crm.db"), Some("sqlite"));
+        assert_eq!(registry.engine_for("stub://anything"), None);
+        assert_eq!(registry.engine_for("crm.db"), None);
+
+        registry.register(StubFactory);
+        assert_eq!(registry.engine_for("stub://anything"), Some("stub"));
+        assert_eq!(
+            registry.engine_for("postgres://user@db/crm"),
+            Some("stub"),
+            "later registrations replace the scheme"
+        );
+        let adapter = registry
+            .open("stub://anything", &IntrospectOptions::default())
+            .await
+            .expect("open stub");
+        assert_eq!(adapter.engine(), "stub");
+
+        let err = AdapterRegistry::new()
+            .open("mysql://db", &IntrospectOptions::default())
+            .await
+            .err()
+            .expect("no adapter");
+        assert!(err.to_string().contains("no adapter registered"), "{err}");
+    }
+}
@@ -0,0 +1,147 @@
+//! Timeouts, deadline and retries around catalog queries.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use datalchemy_core::{Error, Result};
+
+use crate::options::IntrospectOptions;
+
+/// How failed catalog queries are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per query, the first one included; `1` disables retries.
+    pub max_attempts: u32,
+    /// Wait before the second attempt; doubled after each further failure.
+    pub initial_backoff: Duration,
+    /// Upper bound of the wait between attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after the failed attempt number `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Run the query built by `query` under `opts.query_timeout` and the time
+/// left until `deadline`, retrying any failure as `opts.retry` says. Gives up
+/// with the last error, or right away once the deadline has passed.
+pub(crate) async fn run<T, F, Fut>(
+    opts: &IntrospectOptions,
+    deadline: Option<Instant>,
+    mut query: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if remaining == Some(Duration::ZERO) {
+            return Err(Error::Db("introspection deadline exceeded".to_string()));
+        }
+        let limit = match (opts.query_timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        };
+        let result = match limit {
+            Some(limit) => tokio::time::timeout(limit, query())
+                .await
+                .unwrap_or_else(|_| {
+                    Err(Error::Db(format!(
+                        "query timed out after {} ms",
+                        limit.as_millis()
+                    )))
+                }),
+            None => query().await,
+        };
+        match result {
+            Err(_) if attempt < opts.retry.max_attempts => {
+                let mut wait = opts.retry.backoff(attempt);
+                if let Some(remaining) = remaining {
+                    wait = wait.min(remaining);
+                }
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[tokio::test]
+    async fn retries_until_success_and_times_out_slow_queries() {
+        let opts = IntrospectOptions {
+            query_timeout: Some(Duration::from_millis(20)),
+            retry: RetryPolicy {
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+            },
+            ..IntrospectOptions::default()
+        };
+
+        let mut calls = 0;
+        let value = run(&opts, None, || {
+            calls += 1;
+            let call = calls;
+            async move {
+                if call < 3 {
+                    Err(Error::Db("connection reset".to_string()))
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await
+        .expect("third attempt succeeds");
+        assert_eq!(value, 3);
+
+        let err = run(&opts, None, || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await
+        .expect_err("every attempt times out");
+        assert!(err.to_string().contains("timed out after 20 ms"), "{err}");
+
+        let past = Instant::now() - Duration::from_millis(1);
+        let err = run(&opts, Some(past), || async { Ok(()) })
+            .await
+            .expect_err("deadline already passed");
+        assert!(err.to_string().contains("deadline exceeded"), "{err}");
+    }
+}
@@ -2,18 +2,21 @@
 
 use std::collections::BTreeMap;
 
+use regex::Regex;
 use sqlx::SqlitePool;
 
 use datalchemy_core::types::ColumnType;
 use datalchemy_core::{
-    CheckConstraint, Column, Constraint, FkAction, FkMatchType, ForeignKey, Index, PrimaryKey,
-    Table, TableKind, UniqueConstraint,
+    Column, Constraint, FkAction, FkMatchType, ForeignKey, GeneratedExpression, GeneratedKind,
+    Index, PrimaryKey, Table, TableEvent, TableKind, Trigger, TriggerTiming,
 };
 
-use super::queries::{RawColumn, RawForeignKey, RawIndex};
+use super::queries::{RawColumn, RawForeignKey, RawIndex, RawTrigger};
 
 /// Build a [`Table`] from raw column info, including PK and CHECK extraction.
-pub fn map_table(name: &str, raw_columns: Vec<RawColumn>) -> Table {
+/// Generated column expressions are read from `create_sql`.
+pub fn map_table(name: &str, raw_columns: Vec<RawColumn>, create_sql: Option<&str>) -> Table {
+    let expressions = create_sql.map(generated_expressions).unwrap_or_default();
     let pk_columns: Vec<String> = raw_columns
         .iter()
         .filter(|c| c.pk)
@@ -22,6 +25,7 @@ pub fn map_table(name: &str, raw_columns: Vec<RawColumn>) -> Table {
 
     let columns: Vec<Column> = raw_columns
         .iter()
+        .filter(|raw| raw.hidden != 1)
         .enumerate()
         .map(|(i, raw)| Column {
             ordinal_position: (i + 1) as i16,
@@ -30,8 +34,19 @@ pub fn map_table(name: &str, raw_columns: Vec<RawColumn>) -> Table {
             is_nullable: !raw.notnull && !raw.pk,
             default: raw.dflt_value.clone(),
             identity: None,
-            generated: None,
+            generated: match raw.hidden {
+                2 | 3 => Some(GeneratedExpression {
+                    kind: if raw.hidden == 2 {
+                        GeneratedKind::Virtual
+                    } else {
+                        GeneratedKind::Stored
+                    },
+                    expression: expressions.get(&raw.name.to_lowercase()).cloned(),
+                }),
+                _ => None,
+            },
             comment: None,
+            stats: None,
         })
         .collect();
 
@@ -50,9 +65,82 @@ pub fn map_table(name: &str, raw_columns: Vec<RawColumn>) -> Table {
         columns,
         constraints,
         indexes: Vec::new(),
+        partition_key: None,
+        partitions: Vec::new(),
+        row_estimate: None,
+        triggers: Vec::new(),
+        rules: Vec::new(),
+        catalog_version: None,
+        hidden_columns: Vec::new(),
+        view_definition: None,
+        depends_on: Vec::new(),
+        foreign_table: None,
+        privileges: None,
     }
 }
 
+/// Expressions of the generated columns (`<name> ... AS (<expr>)`) of a
+/// `CREATE TABLE` statement, keyed by lowercase column name.
+fn generated_expressions(create_sql: &str) -> BTreeMap<String, String> {
+    let mut expressions = BTreeMap::new();
+    let Some(start) = create_sql.find('(') else {
+        return expressions;
+    };
+    let Ok(generated) = Regex::new(r"(?i)\bAS\s*\(") else {
+        return expressions;
+    };
+    for definition in split_definitions(&create_sql[start + 1..]) {
+        let definition = definition.trim();
+        let name_end = match definition.chars().next() {
+            Some(quote @ ('"' | '`' | '[')) => {
+                let close = if quote == '[' { ']' } else { quote };
+                definition[1..]
+                    .find(close)
+                    .map_or(definition.len(), |end| end + 2)
+            }
+            _ => definition
+                .find(char::is_whitespace)
+                .unwrap_or(definition.len()),
+        };
+        let name = definition[..name_end].trim_matches(['"', '`', '[', ']']);
+        let Some(found) = generated.find(&definition[name_end..]) else {
+            continue;
+        };
+        let body = &definition[name_end + found.end()..];
+        if let Some(expression) = split_definitions(body).first() {
+            expressions.insert(name.to_lowercase(), expression.trim().to_string());
+        }
+    }
+    expressions
+}
+
+/// Split `sql` at top-level commas, up to the parenthesis closing the list.
+fn split_definitions(sql: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    let mut quote: Option<char> = None;
+    for (idx, ch) in sql.char_indices() {
+        match (quote, ch) {
+            (Some(open), _) if ch == open || (open == '[' && ch == ']') => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`' | '[') => quote = Some(ch),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => {
+                parts.push(&sql[start..idx]);
+                return parts;
+            }
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(&sql[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&sql[start..]);
+    parts
+}
+
 /// Map a SQLite type string to a [`ColumnType`].
 fn map_column_type(raw: &str) -> ColumnType {
     let upper = raw.to_uppercase();
@@ -165,7 +253,64 @@ pub async fn map_indexes(raw: Vec<RawIndex>, pool: &SqlitePool, table: &str) ->
             is_valid: true,
             method: "btree".to_string(),
             definition,
+            predicate: None,
+            expressions: Vec::new(),
+            shard_buckets: None,
         });
     }
     indexes
 }
+
+/// Read timing and event from the header of each `CREATE TRIGGER` statement
+/// (`... [BEFORE|AFTER|INSTEAD OF] {INSERT|UPDATE [OF ...]|DELETE} ON ...`).
+/// SQLite triggers are always row-level and run an inline body.
+pub fn map_triggers(raw: Vec<RawTrigger>) -> Vec<Trigger> {
+    raw.into_iter()
+        .map(|trigger| {
+            let header = trigger.sql.to_ascii_uppercase();
+            let mut timing = TriggerTiming::Before;
+            let mut events = Vec::new();
+            for token in header.split_whitespace().take_while(|token| *token != "ON") {
+                match token {
+                    "AFTER" => timing = TriggerTiming::After,
+                    "INSTEAD" => timing = TriggerTiming::InsteadOf,
+                    "INSERT" => events = vec![TableEvent::Insert],
+                    "UPDATE" => events = vec![TableEvent::Update],
+                    "DELETE" => events = vec![TableEvent::Delete],
+                    _ => {}
+                }
+            }
+            Trigger {
+                name: trigger.name,
+                timing,
+                events,
+                for_each_row: true,
+                function: None,
+                enabled: true,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_generated_column_expressions() {
+        let sql = "CREATE TABLE itens (
+            id INTEGER PRIMARY KEY,
+            preco REAL NOT NULL CHECK (preco >= 0),
+            \"qtd\" INTEGER DEFAULT (1),
+            total REAL GENERATED ALWAYS AS (preco * \"qtd\") STORED,
+            [rotulo] TEXT AS ('item ' || id || ', ' || upper(substr('x', 1))) VIRTUAL
+        )";
+        let expressions = generated_expressions(sql);
+        assert_eq!(expressions.len(), 2);
+        assert_eq!(expressions["total"], "preco * \"qtd\"");
+        assert_eq!(
+            expressions["rotulo"],
+            "'item ' || id || ', ' || upper(substr('x', 1))"
+        );
+    }
+}
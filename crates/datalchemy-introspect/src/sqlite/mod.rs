@@ -4,11 +4,14 @@ mod mapper;
 mod queries;
 
 use sqlx::SqlitePool;
+use sqlx::sqlite::SqlitePoolOptions;
 
 use datalchemy_core::{DatabaseSchema, Result, SCHEMA_VERSION, Schema};
 
 use crate::adapter::Adapter;
+use crate::filter;
 use crate::options::IntrospectOptions;
+use crate::registry::AdapterFactory;
 
 /// Adapter for SQLite databases.
 #[derive(Debug, Clone)]
@@ -34,6 +37,35 @@ impl Adapter for SqliteAdapter {
     }
 }
 
+/// Opens a [`SqliteAdapter`] for `sqlite://` connection strings.
+#[derive(Debug, Clone, Copy)]
+pub struct SqliteFactory;
+
+#[async_trait::async_trait]
+impl AdapterFactory for SqliteFactory {
+    fn engine(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn schemes(&self) -> &'static [&'static str] {
+        &["sqlite"]
+    }
+
+    async fn open(
+        &self,
+        conn: &str,
+        opts: &IntrospectOptions,
+    ) -> Result<Box<dyn Adapter + Send + Sync>> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .acquire_timeout(opts.acquire_timeout)
+            .connect(conn)
+            .await
+            .map_err(|err| datalchemy_core::Error::Db(err.to_string()))?;
+        Ok(Box::new(SqliteAdapter::new(pool)))
+    }
+}
+
 /// Introspect SQLite with default options.
 pub async fn introspect_sqlite(pool: &SqlitePool) -> Result<DatabaseSchema> {
     introspect_sqlite_with_options(pool, IntrospectOptions::default()).await
@@ -54,11 +86,15 @@ async fn introspect(pool: &SqlitePool, opts: &IntrospectOptions) -> Result<Datab
     let mut tables = Vec::new();
 
     for table_name in table_names {
+        if !opts.includes_table("main", &table_name) {
+            continue;
+        }
         let raw_columns = queries::list_columns(pool, &table_name).await?;
         let raw_fks = queries::list_foreign_keys(pool, &table_name).await?;
         let raw_indexes = queries::list_indexes(pool, &table_name).await?;
 
-        let mut table = mapper::map_table(&table_name, raw_columns);
+        let create_sql = queries::table_sql(pool, &table_name).await?;
+        let mut table = mapper::map_table(&table_name, raw_columns, create_sql.as_deref());
         table.constraints.extend(
             mapper::map_foreign_keys(raw_fks)
                 .into_iter()
@@ -68,23 +104,32 @@ async fn introspect(pool: &SqlitePool, opts: &IntrospectOptions) -> Result<Datab
         if opts.include_indexes {
             table.indexes = mapper::map_indexes(raw_indexes, pool, &table_name).await;
         }
+        table.triggers = mapper::map_triggers(queries::list_triggers(pool, &table_name).await?);
 
         tables.push(table);
     }
 
     tables.sort_by(|a, b| a.name.cmp(&b.name));
 
-    let schema = Schema {
+    let schemas = vec![Schema {
         name: "main".to_string(),
         tables,
-    };
+    }];
+    let external_tables = filter::external_tables(&schemas, opts);
 
-    Ok(DatabaseSchema {
+    DatabaseSchema {
         schema_version: SCHEMA_VERSION.to_string(),
         engine: "sqlite".to_string(),
         database: None,
-        schemas: vec![schema],
+        schemas,
         enums: Vec::new(),
+        domains: Vec::new(),
+        composite_types: Vec::new(),
+        sequences: Vec::new(),
+        current_role: None,
+        failed_tables: Vec::new(),
+        external_tables,
         schema_fingerprint: None,
-    })
+    }
+    .with_fingerprint()
 }
@@ -8,14 +8,15 @@ fn db_err(err: sqlx::Error) -> datalchemy_core::Error {
     datalchemy_core::Error::Db(err.to_string())
 }
 
-/// Raw column info from `PRAGMA table_info`.
+/// Raw column info from `PRAGMA table_xinfo`.
 pub struct RawColumn {
-    pub cid: i64,
     pub name: String,
     pub col_type: String,
     pub notnull: bool,
     pub dflt_value: Option<String>,
     pub pk: bool,
+    /// `2` for VIRTUAL and `3` for STORED generated columns, `0` otherwise.
+    pub hidden: i64,
 }
 
 /// Raw foreign key info from `PRAGMA foreign_key_list`.
@@ -37,6 +38,12 @@ pub struct RawIndex {
     pub origin: String,
 }
 
+/// Raw trigger from `sqlite_master`.
+pub struct RawTrigger {
+    pub name: String,
+    pub sql: String,
+}
+
 /// List all user tables (excluding internal SQLite tables).
 pub async fn list_tables(pool: &SqlitePool) -> Result<Vec<String>> {
     let rows = sqlx::query(
@@ -57,15 +64,15 @@ pub async fn list_tables(pool: &SqlitePool) -> Result<Vec<String>> {
     Ok(names)
 }
 
-/// List columns for a table via `PRAGMA table_info`.
+/// List columns for a table via `PRAGMA table_xinfo`, which unlike
+/// `table_info` includes generated columns.
 pub async fn list_columns(pool: &SqlitePool, table: &str) -> Result<Vec<RawColumn>> {
-    let query = format!("PRAGMA table_info(\"{}\")", table);
+    let query = format!("PRAGMA table_xinfo(\"{}\")", table);
     let rows = sqlx::query(&query).fetch_all(pool).await.map_err(db_err)?;
 
     let mut columns = Vec::new();
     for row in rows {
         columns.push(RawColumn {
-            cid: row.try_get::<i64, _>("cid").map_err(db_err)?,
             name: row.try_get::<String, _>("name").map_err(db_err)?,
             col_type: row.try_get::<String, _>("type").map_err(db_err)?,
             notnull: row.try_get::<bool, _>("notnull").map_err(db_err)?,
@@ -73,11 +80,23 @@ pub async fn list_columns(pool: &SqlitePool, table: &str) -> Result<Vec<RawColum
                 .try_get::<Option<String>, _>("dflt_value")
                 .map_err(db_err)?,
             pk: row.try_get::<bool, _>("pk").map_err(db_err)?,
+            hidden: row.try_get::<i64, _>("hidden").map_err(db_err)?,
         });
     }
     Ok(columns)
 }
 
+/// `CREATE TABLE` statement of a table, where generated column expressions
+/// live.
+pub async fn table_sql(pool: &SqlitePool, table: &str) -> Result<Option<String>> {
+    sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(table)
+        .fetch_optional(pool)
+        .await
+        .map(Option::flatten)
+        .map_err(db_err)
+}
+
 /// List foreign keys for a table via `PRAGMA foreign_key_list`.
 pub async fn list_foreign_keys(pool: &SqlitePool, table: &str) -> Result<Vec<RawForeignKey>> {
     let query = format!("PRAGMA foreign_key_list(\"{}\")", table);
@@ -127,3 +146,26 @@ pub async fn list_index_columns(pool: &SqlitePool, index_name: &str) -> Result<V
     }
     Ok(cols)
 }
+
+/// List triggers on a table with their `CREATE TRIGGER` statement.
+pub async fn list_triggers(pool: &SqlitePool, table: &str) -> Result<Vec<RawTrigger>> {
+    let rows = sqlx::query(
+        "SELECT name, sql FROM sqlite_master
+         WHERE type = 'trigger'
+         AND tbl_name = ?
+         ORDER BY name",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    let mut triggers = Vec::new();
+    for row in rows {
+        triggers.push(RawTrigger {
+            name: row.try_get::<String, _>("name").map_err(db_err)?,
+            sql: row.try_get::<String, _>("sql").map_err(db_err)?,
+        });
+    }
+    Ok(triggers)
+}
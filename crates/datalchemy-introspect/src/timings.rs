@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// Wall-clock time spent in each introspection phase, summed over schemas.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntrospectTimings {
+    /// Phases in the order they first ran.
+    pub phases: Vec<PhaseTiming>,
+}
+
+/// Time spent in one phase (`catalog`, `sequences`, `tables`, `details`,
+/// `stats`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    pub phase: &'static str,
+    pub duration: Duration,
+}
+
+impl IntrospectTimings {
+    /// Add `duration` to `phase`, appending the phase on first use.
+    pub fn record(&mut self, phase: &'static str, duration: Duration) {
+        match self.phases.iter_mut().find(|timing| timing.phase == phase) {
+            Some(timing) => timing.duration += duration,
+            None => self.phases.push(PhaseTiming { phase, duration }),
+        }
+    }
+
+    /// Time spent in `phase`, zero when it did not run.
+    pub fn get(&self, phase: &str) -> Duration {
+        self.phases
+            .iter()
+            .find(|timing| timing.phase == phase)
+            .map(|timing| timing.duration)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "duckdb")]
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use datalchemy_core::{Constraint, TableKind};
+use datalchemy_introspect::{Adapter, DuckDbAdapter, IntrospectOptions, introspect_duckdb};
+
+fn database_path(label: &str) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "datalchemy_introspect_{label}_{}.duckdb",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn create_database(label: &str) -> Result<PathBuf> {
+    let path = database_path(label);
+    let conn = duckdb::Connection::open(&path).context("create duckdb file")?;
+    conn.execute_batch(
+        "CREATE SCHEMA crm;
+         CREATE TABLE crm.usuarios (
+             id INTEGER PRIMARY KEY,
+             email VARCHAR(120) NOT NULL UNIQUE,
+             saldo DECIMAL(12, 2) CHECK (saldo >= 0),
+             criado_em TIMESTAMP
+         );
+         COMMENT ON TABLE crm.usuarios IS 'usuarios do crm';
+         CREATE TABLE crm.pedidos (
+             id BIGINT PRIMARY KEY,
+             usuario_id INTEGER NOT NULL REFERENCES crm.usuarios (id),
+             total DOUBLE
+         );
+         CREATE INDEX pedidos_total_idx ON crm.pedidos (total);
+         CREATE VIEW crm.pedidos_por_usuario AS
+             SELECT usuario_id, count(*) AS pedidos FROM crm.pedidos GROUP BY usuario_id;",
+    )
+    .context("create fixture tables")?;
+    Ok(path)
+}
+
+#[test]
+fn introspects_tables_constraints_and_views() -> Result<()> {
+    let path = create_database("tables")?;
+    let schema = introspect_duckdb(&path)?;
+
+    assert_eq!(schema.engine, "duckdb");
+    let crm = schema
+        .schemas
+        .iter()
+        .find(|schema| schema.name == "crm")
+        .context("crm schema")?;
+
+    let usuarios = crm
+        .tables
+        .iter()
+        .find(|t| t.name == "usuarios")
+        .context("usuarios")?;
+    assert_eq!(usuarios.comment.as_deref(), Some("usuarios do crm"));
+    let email = usuarios
+        .columns
+        .iter()
+        .find(|c| c.name == "email")
+        .context("email")?;
+    assert_eq!(email.column_type.udt_name, "varchar");
+    assert!(!email.is_nullable);
+    let saldo = usuarios
+        .columns
+        .iter()
+        .find(|c| c.name == "saldo")
+        .context("saldo")?;
+    assert_eq!(saldo.column_type.udt_name, "numeric");
+    assert_eq!(saldo.column_type.numeric_scale, Some(2));
+    assert!(
+        usuarios
+            .constraints
+            .iter()
+            .any(|c| matches!(c, Constraint::PrimaryKey(pk) if pk.columns == ["id"]))
+    );
+    assert!(
+        usuarios
+            .constraints
+            .iter()
+            .any(|c| matches!(c, Constraint::Unique(u) if u.columns == ["email"]))
+    );
+    assert!(
+        usuarios
+            .constraints
+            .iter()
+            .any(|c| matches!(c, Constraint::Check(_)))
+    );
+
+    let pedidos = crm
+        .tables
+        .iter()
+        .find(|t| t.name == "pedidos")
+        .context("pedidos")?;
+    let fk = pedidos
+        .constraints
+        .iter()
+        .find_map(|c| match c {
+            Constraint::ForeignKey(fk) => Some(fk),
+            _ => None,
+        })
+        .context("pedidos fk")?;
+    assert_eq!(fk.columns, ["usuario_id"]);
+    assert_eq!(fk.referenced_schema, "crm");
+    assert_eq!(fk.referenced_table, "usuarios");
+    assert_eq!(fk.referenced_columns, ["id"]);
+    assert!(
+        pedidos
+            .indexes
+            .iter()
+            .any(|i| i.name == "pedidos_total_idx")
+    );
+
+    let view = crm
+        .tables
+        .iter()
+        .find(|t| t.name == "pedidos_por_usuario")
+        .context("view")?;
+    assert_eq!(view.kind, TableKind::View);
+    let definition = view.view_definition.as_deref().context("view definition")?;
+    assert!(definition.to_ascii_uppercase().starts_with("SELECT"));
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[tokio::test]
+async fn adapter_honours_options() -> Result<()> {
+    let path = create_database("options")?;
+    let adapter = DuckDbAdapter::new(&path);
+    assert_eq!(adapter.engine(), "duckdb");
+
+    let schema = adapter
+        .introspect(&IntrospectOptions {
+            include_views: false,
+            include_indexes: false,
+            include_comments: false,
+            ..IntrospectOptions::default()
+        })
+        .await?;
+    let tables: Vec<_> = schema.schemas.iter().flat_map(|s| &s.tables).collect();
+    assert!(tables.iter().all(|t| t.kind == TableKind::Table));
+    assert!(
+        tables
+            .iter()
+            .all(|t| t.indexes.is_empty() && t.comment.is_none())
+    );
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
@@ -1,9 +1,16 @@
 use anyhow::{Context, Result, anyhow};
-use datalchemy_core::{Constraint, FkAction, FkMatchType, TableKind};
-use datalchemy_introspect::{IntrospectOptions, introspect_postgres_with_options};
+use datalchemy_core::{
+    Constraint, FkAction, FkMatchType, GeneratedKind, Table, TableEvent, TableKind, TriggerTiming,
+    insert_order, refresh_order, validate_schema,
+};
+use datalchemy_introspect::{
+    Dialect, IntrospectOptions, RetryPolicy, introspect_incremental, introspect_postgres_timed,
+    introspect_postgres_with_options,
+};
 use jsonschema::{Draft, JSONSchema};
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use std::{env, fs};
 
 fn database_url() -> Result<String> {
@@ -74,6 +81,22 @@ async fn introspects_schema_with_constraints_and_indexes() -> Result<()> {
 
     let snapshot = introspect_postgres_with_options(&pool, IntrospectOptions::default()).await?;
 
+    assert_eq!(snapshot.engine, "postgres");
+    assert_eq!(
+        Dialect::detect(
+            "CockroachDB CCL v23.1.11 (x86_64-pc-linux-gnu, built 2023/09/27 01:53:43, go1.19.10)"
+        ),
+        Dialect::Cockroach
+    );
+    assert_eq!(Dialect::Cockroach.engine(), "cockroachdb");
+    assert!(
+        snapshot
+            .schemas
+            .iter()
+            .flat_map(|schema| &schema.tables)
+            .all(|table| table.hidden_columns.is_empty())
+    );
+
     assert!(
         !snapshot
             .schemas
@@ -215,5 +238,352 @@ async fn introspects_schema_with_constraints_and_indexes() -> Result<()> {
     let rendered = serde_json::to_string_pretty(&snapshot)?;
     assert_eq!(rendered, golden_text.trim_end());
 
+    sqlx::query("analyze crm.usuarios").execute(&pool).await?;
+    let sampled = introspect_postgres_with_options(
+        &pool,
+        IntrospectOptions {
+            schemas: Some(vec!["crm".to_string()]),
+            sample_stats: true,
+            ..IntrospectOptions::default()
+        },
+    )
+    .await?;
+    let usuarios = sampled.schemas[0]
+        .tables
+        .iter()
+        .find(|table| table.name == "usuarios")
+        .ok_or_else(|| anyhow!("expected usuarios table"))?;
+    let rows: i64 = sqlx::query_scalar("select count(*) from crm.usuarios")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(usuarios.row_estimate, Some(rows as u64));
+    let email = usuarios
+        .columns
+        .iter()
+        .find(|column| column.name == "email")
+        .and_then(|column| column.stats)
+        .ok_or_else(|| anyhow!("expected email stats"))?;
+    assert_eq!(email.null_frac, 0.0);
+    assert_eq!(email.n_distinct, -1.0, "email is unique");
+
+    let unsampled = introspect_postgres_with_options(
+        &pool,
+        IntrospectOptions {
+            schemas: Some(vec!["crm".to_string()]),
+            ..IntrospectOptions::default()
+        },
+    )
+    .await?;
+    assert_eq!(
+        sampled.schema_fingerprint, unsampled.schema_fingerprint,
+        "statistics do not change the fingerprint"
+    );
+
+    sqlx::raw_sql(
+        r#"
+        do $$
+        begin
+          if not exists (select 1 from pg_roles where rolname = 'datalchemy_leitor') then
+            create role datalchemy_leitor nologin;
+          end if;
+        end
+        $$;
+        revoke all on all tables in schema crm from datalchemy_leitor;
+        grant usage on schema crm to datalchemy_leitor;
+        grant select on crm.usuarios to datalchemy_leitor;
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    let granted = introspect_postgres_with_options(
+        &pool,
+        IntrospectOptions {
+            schemas: Some(vec!["crm".to_string()]),
+            include_privileges: true,
+            ..IntrospectOptions::default()
+        },
+    )
+    .await?;
+    let role: String = sqlx::query_scalar("select current_user::text")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(granted.current_role.as_deref(), Some(role.as_str()));
+    let privileges = granted.schemas[0]
+        .tables
+        .iter()
+        .find(|table| table.name == "usuarios")
+        .and_then(|table| table.privileges.as_ref())
+        .ok_or_else(|| anyhow!("expected usuarios privileges"))?;
+    assert!(privileges.can_insert(&role) && privileges.can_select(&role));
+    assert!(privileges.can_select("datalchemy_leitor"));
+    assert!(!privileges.can_insert("datalchemy_leitor"));
+    assert!(
+        granted
+            .tables_without_insert([("crm", "usuarios")])
+            .is_empty()
+    );
+    let mut as_reader = granted.clone();
+    as_reader.current_role = Some("datalchemy_leitor".to_string());
+    assert_eq!(
+        as_reader.tables_without_insert([("crm", "usuarios")]),
+        ["crm.usuarios"]
+    );
+    assert_eq!(
+        granted.schema_fingerprint, unsampled.schema_fingerprint,
+        "privileges do not change the fingerprint"
+    );
+
+    sqlx::raw_sql(
+        r#"
+        drop schema if exists gatilhos cascade;
+        create schema gatilhos;
+        create table gatilhos.pedidos (
+          id integer primary key,
+          total numeric,
+          total_com_frete numeric generated always as (total + 10) stored
+        );
+        create function gatilhos.carimbar() returns trigger language plpgsql as $$
+          begin
+            return new;
+          end
+        $$;
+        create trigger pedidos_carimbo before insert or update on gatilhos.pedidos
+          for each row execute function gatilhos.carimbar();
+        create trigger pedidos_resumo after delete on gatilhos.pedidos
+          for each statement execute function gatilhos.carimbar();
+        alter table gatilhos.pedidos disable trigger pedidos_resumo;
+        create rule pedidos_sem_negativos as on insert to gatilhos.pedidos
+          where new.total < 0 do instead nothing;
+        create materialized view gatilhos.totais as
+          select count(*) as pedidos, sum(total) as total from gatilhos.pedidos;
+        create view gatilhos.resumo as select total from gatilhos.totais;
+        drop server if exists gatilhos_remoto cascade;
+        drop foreign data wrapper if exists gatilhos_fdw cascade;
+        create foreign data wrapper gatilhos_fdw;
+        create server gatilhos_remoto foreign data wrapper gatilhos_fdw
+          options (host 'erp.interno', password 'segredo');
+        create foreign table gatilhos.cotacoes (moeda text)
+          server gatilhos_remoto options (table_name 'cotacoes_erp');
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    let gatilhos = introspect_postgres_with_options(
+        &pool,
+        IntrospectOptions {
+            schemas: Some(vec!["gatilhos".to_string()]),
+            ..IntrospectOptions::default()
+        },
+    )
+    .await;
+    sqlx::raw_sql("drop schema gatilhos cascade; drop foreign data wrapper gatilhos_fdw cascade;")
+        .execute(&pool)
+        .await?;
+    let gatilhos = gatilhos?;
+    let tables = &gatilhos.schemas[0].tables;
+    let pedidos = tables.iter().find(|table| table.name == "pedidos").unwrap();
+    let carimbo = &pedidos.triggers[0];
+    assert_eq!(carimbo.name, "pedidos_carimbo");
+    assert_eq!(carimbo.timing, TriggerTiming::Before);
+    assert_eq!(carimbo.events, [TableEvent::Insert, TableEvent::Update]);
+    assert!(carimbo.for_each_row && carimbo.enabled);
+    assert_eq!(carimbo.function.as_deref(), Some("gatilhos.carimbar"));
+    let resumo = &pedidos.triggers[1];
+    assert_eq!(resumo.timing, TriggerTiming::After);
+    assert_eq!(resumo.events, [TableEvent::Delete]);
+    assert!(!resumo.for_each_row && !resumo.enabled);
+    assert_eq!(pedidos.rules.len(), 1);
+    assert_eq!(pedidos.rules[0].name, "pedidos_sem_negativos");
+    assert_eq!(pedidos.rules[0].event, TableEvent::Insert);
+    assert!(pedidos.rules[0].instead);
+    assert!(pedidos.view_definition.is_none());
+    let frete = pedidos
+        .columns
+        .iter()
+        .find(|column| column.name == "total_com_frete")
+        .unwrap();
+    let generated = frete.generated.as_ref().expect("generated column");
+    assert_eq!(generated.kind, GeneratedKind::Stored);
+    assert_eq!(
+        generated.expression.as_deref(),
+        Some("(total + (10)::numeric)")
+    );
+    assert!(frete.default.is_none());
+    let totais = tables.iter().find(|table| table.name == "totais").unwrap();
+    assert_eq!(totais.kind, TableKind::MaterializedView);
+    assert!(
+        totais
+            .view_definition
+            .as_deref()
+            .is_some_and(|query| query.contains("FROM gatilhos.pedidos"))
+    );
+    let reads = |table: &Table| -> Vec<String> {
+        table
+            .depends_on
+            .iter()
+            .map(|dependency| format!("{}.{}", dependency.schema, dependency.name))
+            .collect()
+    };
+    assert_eq!(reads(totais), ["gatilhos.pedidos"]);
+    let resumo_view = tables.iter().find(|table| table.name == "resumo").unwrap();
+    assert_eq!(reads(resumo_view), ["gatilhos.totais"]);
+    assert_eq!(
+        insert_order(&gatilhos).expect("acyclic"),
+        ["gatilhos.pedidos"]
+    );
+    let cotacoes = tables
+        .iter()
+        .find(|table| table.name == "cotacoes")
+        .unwrap();
+    let source = cotacoes.foreign_table.as_ref().expect("foreign table");
+    assert_eq!(source.server, "gatilhos_remoto");
+    assert_eq!(source.wrapper, "gatilhos_fdw");
+    assert_eq!(source.options["table_name"], "cotacoes_erp");
+    assert_eq!(source.server_options["host"], "erp.interno");
+    assert!(!source.server_options.contains_key("password"));
+    assert!(pedidos.foreign_table.is_none());
+    assert_eq!(refresh_order(&gatilhos), ["gatilhos.totais"]);
+
+    let crm_only = IntrospectOptions {
+        schemas: Some(vec!["crm".to_string()]),
+        ..IntrospectOptions::default()
+    };
+    let versioned = introspect_incremental(&pool, &unsampled, &crm_only).await?;
+    assert!(
+        versioned.schemas[0]
+            .tables
+            .iter()
+            .all(|table| table.catalog_version.is_some())
+    );
+    assert_eq!(
+        versioned.schema_fingerprint, unsampled.schema_fingerprint,
+        "catalog versions do not change the fingerprint"
+    );
+
+    // Unchanged tables come from the previous snapshot, not the catalog.
+    let mut previous = versioned.clone();
+    let produtos = previous.schemas[0]
+        .tables
+        .iter_mut()
+        .find(|table| table.name == "produtos")
+        .ok_or_else(|| anyhow!("expected produtos table"))?;
+    produtos.columns[0].comment = Some("from the previous snapshot".to_string());
+    sqlx::query("alter table crm.empresas add column apelido text")
+        .execute(&pool)
+        .await?;
+    let incremental = introspect_incremental(&pool, &previous, &crm_only).await;
+    let full = introspect_postgres_with_options(&pool, crm_only.clone()).await;
+    sqlx::query("alter table crm.empresas drop column apelido")
+        .execute(&pool)
+        .await?;
+    let (incremental, full) = (incremental?, full?);
+
+    let table = |schema: &datalchemy_core::DatabaseSchema, name: &str| {
+        schema.schemas[0]
+            .tables
+            .iter()
+            .find(|table| table.name == name)
+            .cloned()
+            .ok_or_else(|| anyhow!("expected {name} table"))
+    };
+    assert_eq!(
+        table(&incremental, "produtos")?.columns[0]
+            .comment
+            .as_deref(),
+        Some("from the previous snapshot")
+    );
+    let empresas = table(&incremental, "empresas")?;
+    assert!(
+        empresas
+            .columns
+            .iter()
+            .any(|column| column.name == "apelido")
+    );
+    assert_ne!(
+        empresas.catalog_version,
+        table(&versioned, "empresas")?.catalog_version
+    );
+    let produtos_comment = table(&full, "produtos")?.columns[0].comment.clone();
+    let mut merged = incremental.clone();
+    for merged_table in &mut merged.schemas[0].tables {
+        merged_table.catalog_version = None;
+        if merged_table.name == "produtos" {
+            merged_table.columns[0].comment = produtos_comment.clone();
+        }
+    }
+    assert_eq!(
+        serde_json::to_value(merged.with_fingerprint()?)?,
+        serde_json::to_value(&full)?,
+        "the merge matches a full introspection"
+    );
+
+    let serial = IntrospectOptions {
+        concurrency: 1,
+        ..crm_only.clone()
+    };
+    let (timed, timings) = introspect_postgres_timed(&pool, &serial, None).await?;
+    assert_eq!(
+        serde_json::to_value(&timed)?,
+        serde_json::to_value(&unsampled)?,
+        "fetching tables one at a time gives the same snapshot"
+    );
+    let phases: Vec<_> = timings.phases.iter().map(|timing| timing.phase).collect();
+    assert_eq!(phases, ["catalog", "sequences", "tables", "details"]);
+
+    let resilient = IntrospectOptions {
+        query_timeout: Some(Duration::from_secs(30)),
+        deadline: Some(Duration::from_secs(120)),
+        retry: RetryPolicy {
+            max_attempts: 3,
+            ..RetryPolicy::default()
+        },
+        partial: true,
+        ..crm_only.clone()
+    };
+    let (retried, _) = introspect_postgres_timed(&pool, &resilient, None).await?;
+    assert!(retried.failed_tables.is_empty());
+    assert_eq!(
+        serde_json::to_value(&retried)?,
+        serde_json::to_value(&unsampled)?,
+        "timeouts and retries do not change a healthy snapshot"
+    );
+
+    let expired = IntrospectOptions {
+        deadline: Some(Duration::ZERO),
+        ..crm_only.clone()
+    };
+    let err = introspect_postgres_timed(&pool, &expired, None)
+        .await
+        .expect_err("deadline already passed");
+    assert!(err.to_string().contains("deadline exceeded"), "{err}");
+
+    let filtered = IntrospectOptions {
+        include_tables: vec!["crm.contatos".parse()?, "oportunidade?".parse()?],
+        exclude_tables: vec![r"re:\.contatos$".parse()?],
+        ..crm_only.clone()
+    };
+    let subset = introspect_postgres_with_options(&pool, filtered).await?;
+    let names: Vec<_> = subset.schemas[0]
+        .tables
+        .iter()
+        .map(|table| table.name.as_str())
+        .collect();
+    assert_eq!(names, ["oportunidades"]);
+    let external: Vec<_> = subset
+        .external_tables
+        .iter()
+        .map(|external| (external.table.as_str(), external.referenced_by.clone()))
+        .collect();
+    assert_eq!(
+        external,
+        [
+            ("contatos", vec!["crm.oportunidades".to_string()]),
+            ("empresas", vec!["crm.oportunidades".to_string()]),
+            ("etapas_funil", vec!["crm.oportunidades".to_string()]),
+            ("usuarios", vec!["crm.oportunidades".to_string()]),
+        ]
+    );
+    validate_schema(&subset)?;
+
     Ok(())
 }
@@ -0,0 +1,471 @@
+//! Expression language of plan [`Assertion`](crate::Assertion)s.
+//!
+//! An expression is a boolean combination (`and`, `or`, parentheses) of
+//! comparisons (`<`, `<=`, `>`, `>=`, `=`, `!=`, `between .. and ..`) between
+//! arithmetic terms (`+ - * /`) of numbers and aggregates of one table:
+//!
+//! - `count(*)`: rows of the table;
+//! - `count(col)`: non-null values; `count_distinct(col)` (or
+//!   `count(distinct col)`): distinct non-null values;
+//! - `sum(col)`, `avg(col)`, `min(col)`, `max(col)`: over non-null numeric values;
+//! - `null_rate(col)`: null values / rows; `distinct_ratio(col)`: distinct
+//!   non-null values / non-null values.
+//!
+//! Keywords and function names are case-insensitive; column names may be
+//! double-quoted. An aggregate without values (e.g. `avg` of an all-null
+//! column) or a division by zero is undefined, and a comparison with an
+//! undefined term does not hold.
+
+use std::fmt;
+
+/// Aggregate function of an [`Aggregate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFn {
+    Count,
+    CountDistinct,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    NullRate,
+    DistinctRatio,
+}
+
+impl AggregateFn {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "count" => AggregateFn::Count,
+            "count_distinct" => AggregateFn::CountDistinct,
+            "sum" => AggregateFn::Sum,
+            "avg" => AggregateFn::Avg,
+            "min" => AggregateFn::Min,
+            "max" => AggregateFn::Max,
+            "null_rate" => AggregateFn::NullRate,
+            "distinct_ratio" => AggregateFn::DistinctRatio,
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AggregateFn::Count => "count",
+            AggregateFn::CountDistinct => "count_distinct",
+            AggregateFn::Sum => "sum",
+            AggregateFn::Avg => "avg",
+            AggregateFn::Min => "min",
+            AggregateFn::Max => "max",
+            AggregateFn::NullRate => "null_rate",
+            AggregateFn::DistinctRatio => "distinct_ratio",
+        }
+    }
+
+    /// Whether the function needs numeric values.
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            AggregateFn::Sum | AggregateFn::Avg | AggregateFn::Min | AggregateFn::Max
+        )
+    }
+}
+
+/// One aggregate of an expression; `column` is `None` only for `count(*)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    pub function: AggregateFn,
+    pub column: Option<String>,
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({})",
+            self.function.as_str(),
+            self.column.as_deref().unwrap_or("*")
+        )
+    }
+}
+
+/// Arithmetic operator of a [`Term`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Comparison operator of a [`Condition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// Numeric term; aggregates refer to [`AssertionExpr::aggregates`] by index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Number(f64),
+    Aggregate(usize),
+    Negate(Box<Term>),
+    Binary(Box<Term>, ArithOp, Box<Term>),
+}
+
+/// Boolean part of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Compare(Term, CompareOp, Term),
+    Between(Term, Term, Term),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+/// Parsed assertion expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionExpr {
+    pub condition: Condition,
+    /// Distinct aggregates of the expression, in order of appearance.
+    pub aggregates: Vec<Aggregate>,
+}
+
+impl AssertionExpr {
+    /// Parse `expr`; the error describes the first problem found.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            aggregates: Vec::new(),
+        };
+        let condition = parser.condition()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {token} after the expression"));
+        }
+        Ok(Self {
+            condition,
+            aggregates: parser.aggregates,
+        })
+    }
+
+    /// Whether the expression holds, given one value per aggregate (`None`
+    /// when undefined).
+    pub fn evaluate(&self, values: &[Option<f64>]) -> bool {
+        eval_condition(&self.condition, values)
+    }
+}
+
+fn eval_condition(condition: &Condition, values: &[Option<f64>]) -> bool {
+    match condition {
+        Condition::Compare(left, op, right) => {
+            match (eval_term(left, values), eval_term(right, values)) {
+                (Some(left), Some(right)) => match op {
+                    CompareOp::Lt => left < right,
+                    CompareOp::Le => left <= right,
+                    CompareOp::Gt => left > right,
+                    CompareOp::Ge => left >= right,
+                    CompareOp::Eq => left == right,
+                    CompareOp::Ne => left != right,
+                },
+                _ => false,
+            }
+        }
+        Condition::Between(value, low, high) => match (
+            eval_term(value, values),
+            eval_term(low, values),
+            eval_term(high, values),
+        ) {
+            (Some(value), Some(low), Some(high)) => low <= value && value <= high,
+            _ => false,
+        },
+        Condition::And(left, right) => {
+            eval_condition(left, values) && eval_condition(right, values)
+        }
+        Condition::Or(left, right) => eval_condition(left, values) || eval_condition(right, values),
+    }
+}
+
+fn eval_term(term: &Term, values: &[Option<f64>]) -> Option<f64> {
+    match term {
+        Term::Number(value) => Some(*value),
+        Term::Aggregate(idx) => values.get(*idx).copied().flatten(),
+        Term::Negate(term) => eval_term(term, values).map(|value| -value),
+        Term::Binary(left, op, right) => {
+            let (left, right) = (eval_term(left, values)?, eval_term(right, values)?);
+            match op {
+                ArithOp::Add => Some(left + right),
+                ArithOp::Sub => Some(left - right),
+                ArithOp::Mul => Some(left * right),
+                ArithOp::Div => (right != 0.0).then(|| left / right),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Quoted(String),
+    LParen,
+    RParen,
+    Star,
+    Plus,
+    Minus,
+    Slash,
+    Compare(CompareOp),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "'{value}'"),
+            Token::Ident(value) => write!(f, "'{value}'"),
+            Token::Quoted(value) => write!(f, "'\"{value}\"'"),
+            Token::LParen => f.write_str("'('"),
+            Token::RParen => f.write_str("')'"),
+            Token::Star => f.write_str("'*'"),
+            Token::Plus => f.write_str("'+'"),
+            Token::Minus => f.write_str("'-'"),
+            Token::Slash => f.write_str("'/'"),
+            Token::Compare(op) => f.write_str(match op {
+                CompareOp::Lt => "'<'",
+                CompareOp::Le => "'<='",
+                CompareOp::Gt => "'>'",
+                CompareOp::Ge => "'>='",
+                CompareOp::Eq => "'='",
+                CompareOp::Ne => "'!='",
+            }),
+        }
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let ch = chars[idx];
+        let next = chars.get(idx + 1).copied();
+        let (token, len) = match ch {
+            ch if ch.is_whitespace() => {
+                idx += 1;
+                continue;
+            }
+            '(' => (Token::LParen, 1),
+            ')' => (Token::RParen, 1),
+            '*' => (Token::Star, 1),
+            '+' => (Token::Plus, 1),
+            '-' => (Token::Minus, 1),
+            '/' => (Token::Slash, 1),
+            '<' if next == Some('=') => (Token::Compare(CompareOp::Le), 2),
+            '<' if next == Some('>') => (Token::Compare(CompareOp::Ne), 2),
+            '<' => (Token::Compare(CompareOp::Lt), 1),
+            '>' if next == Some('=') => (Token::Compare(CompareOp::Ge), 2),
+            '>' => (Token::Compare(CompareOp::Gt), 1),
+            '=' if next == Some('=') => (Token::Compare(CompareOp::Eq), 2),
+            '=' => (Token::Compare(CompareOp::Eq), 1),
+            '!' if next == Some('=') => (Token::Compare(CompareOp::Ne), 2),
+            '"' => {
+                let end = chars[idx + 1..]
+                    .iter()
+                    .position(|ch| *ch == '"')
+                    .ok_or_else(|| "unterminated quoted column name".to_string())?;
+                let name: String = chars[idx + 1..idx + 1 + end].iter().collect();
+                (Token::Quoted(name), end + 2)
+            }
+            ch if ch.is_ascii_digit() || ch == '.' => {
+                let len = chars[idx..]
+                    .iter()
+                    .take_while(|ch| ch.is_ascii_digit() || **ch == '.')
+                    .count();
+                let text: String = chars[idx..idx + len].iter().collect();
+                let value = text
+                    .parse()
+                    .map_err(|_| format!("invalid number '{text}'"))?;
+                (Token::Number(value), len)
+            }
+            ch if ch.is_alphabetic() || ch == '_' => {
+                let len = chars[idx..]
+                    .iter()
+                    .take_while(|ch| ch.is_alphanumeric() || **ch == '_')
+                    .count();
+                (Token::Ident(chars[idx..idx + len].iter().collect()), len)
+            }
+            other => return Err(format!("unexpected character '{other}'")),
+        };
+        tokens.push(token);
+        idx += len;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    aggregates: Vec<Aggregate>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {expected}, found {token}")),
+            None => Err(format!("expected {expected} at end of expression")),
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition, String> {
+        let mut left = self.conjunction()?;
+        while self.keyword("or") {
+            let right = self.conjunction()?;
+            left = Condition::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn conjunction(&mut self) -> Result<Condition, String> {
+        let mut left = self.predicate()?;
+        while self.keyword("and") {
+            let right = self.predicate()?;
+            left = Condition::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn predicate(&mut self) -> Result<Condition, String> {
+        // `(` opens either a nested condition or an arithmetic term.
+        if self.peek() == Some(&Token::LParen) {
+            let start = (self.pos, self.aggregates.len());
+            self.pos += 1;
+            if let Ok(condition) = self.condition()
+                && self.next() == Some(Token::RParen)
+            {
+                return Ok(condition);
+            }
+            self.pos = start.0;
+            self.aggregates.truncate(start.1);
+        }
+        let left = self.term()?;
+        if self.keyword("between") {
+            let low = self.term()?;
+            if !self.keyword("and") {
+                return Err("expected 'and' in 'between'".to_string());
+            }
+            let high = self.term()?;
+            return Ok(Condition::Between(left, low, high));
+        }
+        match self.next() {
+            Some(Token::Compare(op)) => Ok(Condition::Compare(left, op, self.term()?)),
+            Some(token) => Err(format!("expected a comparison, found {token}")),
+            None => Err("expected a comparison at end of expression".to_string()),
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, String> {
+        let mut left = self.product()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithOp::Add,
+                Some(Token::Minus) => ArithOp::Sub,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            left = Term::Binary(Box::new(left), op, Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Term, String> {
+        let mut left = self.factor()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => ArithOp::Mul,
+                Some(Token::Slash) => ArithOp::Div,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            left = Term::Binary(Box::new(left), op, Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Term, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Term::Number(value)),
+            Some(Token::Minus) => Ok(Term::Negate(Box::new(self.factor()?))),
+            Some(Token::LParen) => {
+                let term = self.term()?;
+                self.expect(Token::RParen)?;
+                Ok(term)
+            }
+            Some(Token::Ident(name)) => {
+                let function = AggregateFn::parse(&name)
+                    .ok_or_else(|| format!("unknown aggregate '{name}'"))?;
+                self.expect(Token::LParen)?;
+                let aggregate = self.aggregate(function)?;
+                self.expect(Token::RParen)?;
+                let idx = match self.aggregates.iter().position(|known| *known == aggregate) {
+                    Some(idx) => idx,
+                    None => {
+                        self.aggregates.push(aggregate);
+                        self.aggregates.len() - 1
+                    }
+                };
+                Ok(Term::Aggregate(idx))
+            }
+            Some(token) => Err(format!("expected a number or aggregate, found {token}")),
+            None => Err("expected a number or aggregate at end of expression".to_string()),
+        }
+    }
+
+    fn aggregate(&mut self, mut function: AggregateFn) -> Result<Aggregate, String> {
+        if function == AggregateFn::Count {
+            if self.peek() == Some(&Token::Star) {
+                self.pos += 1;
+                return Ok(Aggregate {
+                    function,
+                    column: None,
+                });
+            }
+            if self.keyword("distinct") {
+                function = AggregateFn::CountDistinct;
+            }
+        }
+        match self.next() {
+            Some(Token::Ident(column) | Token::Quoted(column)) => Ok(Aggregate {
+                function,
+                column: Some(column),
+            }),
+            Some(token) => Err(format!(
+                "expected a column in {}(), found {token}",
+                function.as_str()
+            )),
+            None => Err(format!(
+                "expected a column in {}() at end of expression",
+                function.as_str()
+            )),
+        }
+    }
+}
@@ -1,19 +1,22 @@
+use serde::Serialize;
 use thiserror::Error;
 
 /// Severity level for validation issues.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum IssueSeverity {
     Error,
     Warning,
 }
 
 /// Structured validation issue with location and hint.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ValidationIssue {
     pub severity: IssueSeverity,
     pub code: String,
     pub path: String,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hint: Option<String>,
 }
 
@@ -37,7 +40,7 @@ impl ValidationIssue {
 }
 
 /// Aggregated validation report with errors and warnings.
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct ValidationReport {
     pub errors: Vec<ValidationIssue>,
     pub warnings: Vec<ValidationIssue>,
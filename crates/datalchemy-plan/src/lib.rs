@@ -3,21 +3,27 @@
 //! This crate defines the canonical `plan.json` structure, its JSON Schema,
 //! and validation helpers (structural + schema-aware).
 
+pub mod assertion;
 pub mod errors;
 pub mod model;
 pub mod schema;
 pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+pub use assertion::AssertionExpr;
 pub use errors::{IssueSeverity, PlanError, ValidationIssue, ValidationReport};
 pub use model::{
-    ColumnGeneratorRule, ConstraintKind, ConstraintMode, ConstraintPolicyRule, ForeignKeyMode,
-    ForeignKeyStrategyRule, GeneratorRef, GeneratorSpec, InsertOrder, Plan, PlanGlobal,
-    PlanOptions, Rule, RuleReference, SchemaRef, Target, TargetStrategy, TransformRule,
-    UnsupportedRule,
+    Assertion, BooleanFormat, ColumnGeneratorRule, ConstraintKind, ConstraintMode,
+    ConstraintPolicyRule, EntityRef, FixedWidthAlign, FixedWidthColumn, FixedWidthRule,
+    ForeignKeyMode, ForeignKeyStrategyRule, GeneratorRef, GeneratorSpec, InsertOrder,
+    OutputPartitionRule, Plan, PlanGlobal, PlanOptions, Rule, RuleReference, SchemaRef, Target,
+    TargetStrategy, TransformRule, UnsupportedRule, ValueFormats,
 };
 pub use schema::plan_json_schema;
 pub use validate::{
-    ValidatedPlan, validate_plan, validate_plan_against_schema, validate_plan_json,
+    ValidatedPlan, unsupported_rule_candidates, validate_plan, validate_plan_against_schema,
+    validate_plan_json,
 };
 
 /// Current plan contract version for `plan.json` artifacts.
@@ -56,6 +56,10 @@ pub enum Rule {
     ConstraintPolicy(ConstraintPolicyRule),
     /// Configure how foreign keys are handled per table.
     ForeignKeyStrategy(ForeignKeyStrategyRule),
+    /// Column layout for fixed-width output of a table.
+    FixedWidth(FixedWidthRule),
+    /// Split a table's output into Hive-style `column=value/` directories.
+    OutputPartition(OutputPartitionRule),
 }
 
 /// Column generator rule.
@@ -71,6 +75,21 @@ pub struct ColumnGeneratorRule {
     /// Optional transforms applied after generation.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub transforms: Vec<TransformRule>,
+    /// Generate the value per logical entity instead of per row.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityRef>,
+}
+
+/// Logical entity a column belongs to (e.g. `customer` keyed by `id`).
+///
+/// Under the same plan seed, rows whose `key` column holds the same value get
+/// the same value from the same generator, in every table, run and append.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EntityRef {
+    /// Entity name shared by the columns that describe it.
+    pub name: String,
+    /// Column of the same table holding the entity key (e.g. `id` or `cliente_id`).
+    pub key: String,
 }
 
 /// Generator reference; accepts legacy string id or full spec.
@@ -194,6 +213,68 @@ pub enum ForeignKeyMode {
     Disable,
 }
 
+/// Fixed-width layout rule; columns are written in the listed order.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FixedWidthRule {
+    pub schema: String,
+    pub table: String,
+    pub columns: Vec<FixedWidthColumn>,
+}
+
+/// Width, alignment and padding of one fixed-width field.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FixedWidthColumn {
+    pub column: String,
+    /// Field width in characters.
+    pub width: u32,
+    /// Alignment inside the field (defaults: numbers right, others left).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align: Option<FixedWidthAlign>,
+    /// Padding character (default: space).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pad: Option<char>,
+}
+
+/// Alignment of a value inside a fixed-width field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FixedWidthAlign {
+    Left,
+    Right,
+}
+
+/// Hive-style partitioning of a table's output files by one column.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OutputPartitionRule {
+    pub schema: String,
+    pub table: String,
+    /// Column whose values name the partition directories.
+    pub column: String,
+}
+
+/// Expected aggregate property of a table's data, checked by `datalchemy eval`.
+///
+/// `expr` compares aggregates of the table, e.g. `count(*) >= 100`,
+/// `avg(valor_total) between 100 and 5000`, `null_rate(email) <= 0.05 and
+/// count_distinct(status) = 4` (see [`crate::assertion`]).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Assertion {
+    /// Short label for reports (default: the expression).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub schema: String,
+    pub table: String,
+    /// Boolean expression over aggregates of the table's columns.
+    pub expr: String,
+}
+
+impl Assertion {
+    /// `name`, or the expression when unnamed.
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.expr)
+    }
+}
+
 /// Unsupported rule placeholder for future features.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UnsupportedRule {
@@ -232,6 +313,46 @@ pub struct PlanGlobal {
     /// Default locale for generators.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub locale: Option<String>,
+    /// Value formatting for text outputs (csv, fixed_width).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formats: Option<ValueFormats>,
+}
+
+/// Per-type value formatting applied by text writers.
+///
+/// Unset fields keep the canonical formats (`%Y-%m-%d`, `%H:%M:%S`,
+/// `%Y-%m-%dT%H:%M:%S`, `true`/`false`, `.`). Patterns use strftime syntax.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValueFormats {
+    /// Pattern for `date` values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /// Pattern for `time` values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+    /// Pattern for `timestamp`/`timestamptz` values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_format: Option<String>,
+    /// Suffix appended to `timestamp with time zone` values (e.g. `Z`, `+00:00`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone_suffix: Option<String>,
+    /// Boolean spelling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boolean: Option<BooleanFormat>,
+    /// Decimal separator for floating-point and numeric values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<char>,
+}
+
+/// Boolean spelling in text outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BooleanFormat {
+    /// `true` / `false`.
+    TrueFalse,
+    /// `t` / `f` (Postgres text form).
+    #[serde(rename = "t_f")]
+    TF,
 }
 
 /// Canonical plan definition for generation.
@@ -253,6 +374,9 @@ pub struct Plan {
     /// Unsupported rules recorded for future evolution.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules_unsupported: Vec<UnsupportedRule>,
+    /// Aggregate checks the evaluator runs against the generated dataset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<Assertion>,
     /// Optional plan-level options.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub options: Option<PlanOptions>,
@@ -1,13 +1,16 @@
 use std::collections::{HashMap, HashSet};
 
-use datalchemy_core::{Constraint, DatabaseSchema};
+use chrono::format::{Item, StrftimeItems};
+use datalchemy_core::{Constraint, DatabaseSchema, TableEvent, TableKind, TriggerTiming};
 use jsonschema::JSONSchema;
 use serde_json::Value;
 
+use crate::assertion::AssertionExpr;
 use crate::errors::{IssueSeverity, PlanError, ValidationIssue, ValidationReport};
 use crate::model::{
-    ConstraintKind, ConstraintMode, ConstraintPolicyRule, ForeignKeyMode, ForeignKeyStrategyRule,
-    Plan, Rule, Target, UnsupportedRule,
+    Assertion, ConstraintKind, ConstraintMode, ConstraintPolicyRule, FixedWidthRule,
+    ForeignKeyMode, ForeignKeyStrategyRule, OutputPartitionRule, Plan, Rule, RuleReference, Target,
+    UnsupportedRule,
 };
 
 /// Validated plan with accumulated warnings.
@@ -48,11 +51,14 @@ pub fn validate_plan_against_schema(plan: &Plan, schema: &DatabaseSchema) -> Val
     let mut report = ValidationReport::default();
 
     validate_schema_ref(plan, schema, &mut report);
+    validate_value_formats(plan, &mut report);
 
     let schema_index = build_schema_index(schema);
     validate_targets(&plan.targets, &schema_index, &mut report);
     validate_rules(plan, &schema_index, &mut report);
     validate_unsupported(&plan.rules_unsupported, &schema_index, &mut report);
+    validate_unenforced_constraints(plan, schema, &mut report);
+    validate_assertions(&plan.assertions, &schema_index, &mut report);
 
     report
 }
@@ -214,8 +220,8 @@ fn validate_targets(targets: &[Target], schema_index: &SchemaIndex, report: &mut
                 ));
                 continue;
             }
-            Some(schema_tables) => {
-                if !schema_tables.tables.contains_key(table_name) {
+            Some(schema_tables) => match schema_tables.tables.get(table_name) {
+                None => {
                     report.push_error(ValidationIssue::new(
                         IssueSeverity::Error,
                         "unknown_table",
@@ -227,7 +233,43 @@ fn validate_targets(targets: &[Target], schema_index: &SchemaIndex, report: &mut
                         None,
                     ));
                 }
-            }
+                Some(info) if info.kind.is_view() => {
+                    let hint = match info.kind {
+                        TableKind::MaterializedView => {
+                            "target the tables it reads and refresh it after loading"
+                        }
+                        _ => "target the tables it reads",
+                    };
+                    report.push_error(ValidationIssue::new(
+                        IssueSeverity::Error,
+                        "target_is_view",
+                        format!("{base_path}/table"),
+                        format!(
+                            "'{}.{}' is a view; its rows come from its query",
+                            schema_name, table_name
+                        ),
+                        Some(hint.to_string()),
+                    ));
+                }
+                Some(info) if !info.kind.is_insertable() => {
+                    let server = info
+                        .foreign_server
+                        .as_deref()
+                        .map(|server| format!(" of server '{server}'"))
+                        .unwrap_or_default();
+                    report.push_error(ValidationIssue::new(
+                        IssueSeverity::Error,
+                        "target_not_insertable",
+                        format!("{base_path}/table"),
+                        format!(
+                            "'{}.{}' is a foreign table{server}; its rows live in that server",
+                            schema_name, table_name
+                        ),
+                        Some("generate the data for the remote table instead".to_string()),
+                    ));
+                }
+                Some(_) => {}
+            },
         }
 
         let target_key = format!("{schema_name}.{table_name}");
@@ -247,6 +289,8 @@ fn validate_rules(plan: &Plan, schema_index: &SchemaIndex, report: &mut Validati
     let mut column_generators: HashMap<String, String> = HashMap::new();
     let mut constraint_policies: HashMap<String, ConstraintMode> = HashMap::new();
     let mut fk_policies: HashMap<String, ForeignKeyMode> = HashMap::new();
+    let mut fixed_width_layouts: HashSet<String> = HashSet::new();
+    let mut output_partitions: HashSet<String> = HashSet::new();
 
     for (idx, rule) in plan.rules.iter().enumerate() {
         let base_path = format!("/rules/{idx}");
@@ -279,6 +323,24 @@ fn validate_rules(plan: &Plan, schema_index: &SchemaIndex, report: &mut Validati
                     plan.options.as_ref(),
                 );
             }
+            Rule::FixedWidth(rule) => {
+                validate_fixed_width_rule(
+                    rule,
+                    &base_path,
+                    schema_index,
+                    &mut fixed_width_layouts,
+                    report,
+                );
+            }
+            Rule::OutputPartition(rule) => {
+                validate_output_partition_rule(
+                    rule,
+                    &base_path,
+                    schema_index,
+                    &mut output_partitions,
+                    report,
+                );
+            }
         }
     }
 }
@@ -346,6 +408,167 @@ fn validate_unsupported(
     }
 }
 
+/// Exclusion constraints and partial or expression unique indexes of the
+/// target tables, which generation does not enforce, and their triggers and
+/// rules firing on INSERT, which loading the rows runs: candidates for
+/// `rules_unsupported`.
+pub fn unsupported_rule_candidates(plan: &Plan, schema: &DatabaseSchema) -> Vec<UnsupportedRule> {
+    candidates_with_codes(plan, schema)
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// [`unsupported_rule_candidates`] with the warning code of each.
+fn candidates_with_codes(
+    plan: &Plan,
+    schema: &DatabaseSchema,
+) -> Vec<(&'static str, UnsupportedRule)> {
+    let mut candidates = Vec::new();
+    for target in &plan.targets {
+        let Some(table) = schema
+            .schemas
+            .iter()
+            .filter(|db_schema| db_schema.name == target.schema)
+            .flat_map(|db_schema| &db_schema.tables)
+            .find(|table| table.name == target.table)
+        else {
+            continue;
+        };
+        let reference = || {
+            Some(RuleReference {
+                schema: target.schema.clone(),
+                table: target.table.clone(),
+                column: None,
+            })
+        };
+        for constraint in &table.constraints {
+            if let Constraint::Exclusion(exclusion) = constraint {
+                candidates.push((
+                    "unenforced_constraint",
+                    UnsupportedRule {
+                        description: format!(
+                            "exclusion constraint {}",
+                            exclusion.name.as_deref().unwrap_or("(unnamed)")
+                        ),
+                        reason: "exclusion constraints are not enforced during generation"
+                            .to_string(),
+                        reference: reference(),
+                    },
+                ));
+            }
+        }
+        for index in &table.indexes {
+            if index.is_unenforced_unique() {
+                candidates.push((
+                    "unenforced_constraint",
+                    UnsupportedRule {
+                        description: format!("unique index {}", index.name),
+                        reason: "partial and expression unique indexes are not enforced during \
+                                 generation"
+                            .to_string(),
+                        reference: reference(),
+                    },
+                ));
+            }
+        }
+        for trigger in table
+            .triggers
+            .iter()
+            .filter(|trigger| trigger.fires_on_insert())
+        {
+            candidates.push((
+                "insert_side_effect",
+                UnsupportedRule {
+                    description: format!("trigger {}", trigger.name),
+                    reason: format!(
+                        "fires {} insert{}; loading the generated rows may change or reject \
+                         them",
+                        timing_label(trigger.timing),
+                        trigger
+                            .function
+                            .as_deref()
+                            .map(|function| format!(" ({function})"))
+                            .unwrap_or_default()
+                    ),
+                    reference: reference(),
+                },
+            ));
+        }
+        for rule in table
+            .rules
+            .iter()
+            .filter(|rule| rule.event == TableEvent::Insert)
+        {
+            candidates.push((
+                "insert_side_effect",
+                UnsupportedRule {
+                    description: format!("rule {}", rule.name),
+                    reason: format!(
+                        "rewrites inserts ({}); loading the generated rows may not store them \
+                         as generated",
+                        if rule.instead {
+                            "do instead"
+                        } else {
+                            "do also"
+                        }
+                    ),
+                    reference: reference(),
+                },
+            ));
+        }
+    }
+    candidates
+}
+
+fn timing_label(timing: TriggerTiming) -> &'static str {
+    match timing {
+        TriggerTiming::Before => "before",
+        TriggerTiming::After => "after",
+        TriggerTiming::InsteadOf => "instead of",
+    }
+}
+
+/// Warns about each [`unsupported_rule_candidates`] entry the plan does not
+/// record in `rules_unsupported` (same description and table).
+fn validate_unenforced_constraints(
+    plan: &Plan,
+    schema: &DatabaseSchema,
+    report: &mut ValidationReport,
+) {
+    let recorded = |candidate: &UnsupportedRule| {
+        plan.rules_unsupported.iter().any(|rule| {
+            rule.description == candidate.description
+                && match (&rule.reference, &candidate.reference) {
+                    (Some(left), Some(right)) => {
+                        left.schema == right.schema && left.table == right.table
+                    }
+                    _ => false,
+                }
+        })
+    };
+    for (code, candidate) in candidates_with_codes(plan, schema) {
+        if recorded(&candidate) {
+            continue;
+        }
+        let table = candidate
+            .reference
+            .as_ref()
+            .map(|reference| format!("{}.{}", reference.schema, reference.table))
+            .unwrap_or_default();
+        report.push_warning(ValidationIssue::new(
+            IssueSeverity::Warning,
+            code,
+            "/rules_unsupported",
+            format!(
+                "{} on '{table}': {}",
+                candidate.description, candidate.reason
+            ),
+            Some("record it in rules_unsupported or check the generated rows".to_string()),
+        ));
+    }
+}
+
 fn validate_column_generator_rule(
     rule: &crate::model::ColumnGeneratorRule,
     base_path: &str,
@@ -397,6 +620,7 @@ fn validate_column_generator_rule(
 
     validate_input_columns(rule, base_path, table, report);
     validate_parent_reference(rule, base_path, schema_index, report);
+    validate_entity(rule, base_path, table, report);
 
     let generator_id = rule.generator_id().trim();
     if generator_id.is_empty() {
@@ -468,6 +692,46 @@ fn validate_column_generator_rule(
     }
 }
 
+fn validate_entity(
+    rule: &crate::model::ColumnGeneratorRule,
+    base_path: &str,
+    table: &TableInfo,
+    report: &mut ValidationReport,
+) {
+    let Some(entity) = &rule.entity else {
+        return;
+    };
+    if entity.name.trim().is_empty() {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "invalid_entity",
+            format!("{base_path}/entity/name"),
+            "entity name must be a non-empty string".to_string(),
+            None,
+        ));
+    }
+    if entity.key == rule.column {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "invalid_entity",
+            format!("{base_path}/entity/key"),
+            "entity key must be another column of the table".to_string(),
+            None,
+        ));
+    } else if !table.columns.contains_key(entity.key.as_str()) {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "invalid_entity",
+            format!("{base_path}/entity/key"),
+            format!(
+                "entity key column '{}.{}.{}' not found",
+                rule.schema, rule.table, entity.key
+            ),
+            Some("use a column of the same table, e.g. its primary key".to_string()),
+        ));
+    }
+}
+
 fn validate_input_columns(
     rule: &crate::model::ColumnGeneratorRule,
     base_path: &str,
@@ -775,6 +1039,257 @@ fn validate_foreign_key_strategy_rule(
     }
 }
 
+fn validate_fixed_width_rule(
+    rule: &FixedWidthRule,
+    base_path: &str,
+    schema_index: &SchemaIndex,
+    layouts: &mut HashSet<String>,
+    report: &mut ValidationReport,
+) {
+    let schema_name = rule.schema.as_str();
+    let table_name = rule.table.as_str();
+
+    let table = match schema_index
+        .schemas
+        .get(schema_name)
+        .and_then(|schema_tables| schema_tables.tables.get(table_name))
+    {
+        Some(table) => table,
+        None => {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "unknown_fixed_width_target",
+                format!("{base_path}/table"),
+                format!(
+                    "table '{}.{}' not found for fixed-width layout",
+                    schema_name, table_name
+                ),
+                None,
+            ));
+            return;
+        }
+    };
+
+    if !layouts.insert(format!("{schema_name}.{table_name}")) {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "duplicate_fixed_width_layout",
+            base_path.to_string(),
+            "multiple fixed-width layouts for the same table".to_string(),
+            Some("keep only one fixed_width rule per table".to_string()),
+        ));
+        return;
+    }
+
+    if rule.columns.is_empty() {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "empty_fixed_width_layout",
+            format!("{base_path}/columns"),
+            "fixed-width layout must list at least one column".to_string(),
+            None,
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for (idx, field) in rule.columns.iter().enumerate() {
+        let field_path = format!("{base_path}/columns/{idx}");
+        if !table.columns.contains_key(&field.column) {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "unknown_fixed_width_column",
+                format!("{field_path}/column"),
+                format!(
+                    "column '{}.{}.{}' not found for fixed-width layout",
+                    schema_name, table_name, field.column
+                ),
+                None,
+            ));
+        } else if !seen.insert(field.column.as_str()) {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "duplicate_fixed_width_column",
+                format!("{field_path}/column"),
+                format!("column '{}' listed more than once", field.column),
+                None,
+            ));
+        }
+        if field.width == 0 {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "invalid_fixed_width",
+                format!("{field_path}/width"),
+                "fixed-width field width must be greater than zero".to_string(),
+                None,
+            ));
+        }
+    }
+}
+
+fn validate_output_partition_rule(
+    rule: &OutputPartitionRule,
+    base_path: &str,
+    schema_index: &SchemaIndex,
+    partitions: &mut HashSet<String>,
+    report: &mut ValidationReport,
+) {
+    let schema_name = rule.schema.as_str();
+    let table_name = rule.table.as_str();
+
+    let table = match schema_index
+        .schemas
+        .get(schema_name)
+        .and_then(|schema_tables| schema_tables.tables.get(table_name))
+    {
+        Some(table) => table,
+        None => {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "unknown_partition_target",
+                format!("{base_path}/table"),
+                format!(
+                    "table '{}.{}' not found for output partition",
+                    schema_name, table_name
+                ),
+                None,
+            ));
+            return;
+        }
+    };
+
+    if !partitions.insert(format!("{schema_name}.{table_name}")) {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "duplicate_output_partition",
+            base_path.to_string(),
+            "multiple output partitions for the same table".to_string(),
+            Some("keep only one output_partition rule per table".to_string()),
+        ));
+        return;
+    }
+
+    if !table.columns.contains_key(&rule.column) {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "unknown_partition_column",
+            format!("{base_path}/column"),
+            format!(
+                "column '{}.{}.{}' not found for output partition",
+                schema_name, table_name, rule.column
+            ),
+            None,
+        ));
+    }
+}
+
+fn validate_assertions(
+    assertions: &[Assertion],
+    schema_index: &SchemaIndex,
+    report: &mut ValidationReport,
+) {
+    for (idx, assertion) in assertions.iter().enumerate() {
+        let base_path = format!("/assertions/{idx}");
+        let Some(table) = schema_index
+            .schemas
+            .get(assertion.schema.as_str())
+            .and_then(|schema_tables| schema_tables.tables.get(assertion.table.as_str()))
+        else {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "unknown_assertion_table",
+                format!("{base_path}/table"),
+                format!(
+                    "table '{}.{}' not found for assertion",
+                    assertion.schema, assertion.table
+                ),
+                None,
+            ));
+            continue;
+        };
+
+        let expr = match AssertionExpr::parse(&assertion.expr) {
+            Ok(expr) => expr,
+            Err(err) => {
+                report.push_error(ValidationIssue::new(
+                    IssueSeverity::Error,
+                    "invalid_assertion_expr",
+                    format!("{base_path}/expr"),
+                    format!("invalid assertion '{}': {err}", assertion.expr),
+                    Some("e.g. count(*) >= 100 and null_rate(email) <= 0.05".to_string()),
+                ));
+                continue;
+            }
+        };
+        for column in expr
+            .aggregates
+            .iter()
+            .filter_map(|aggregate| aggregate.column.as_deref())
+        {
+            if !table.columns.contains_key(column) {
+                report.push_error(ValidationIssue::new(
+                    IssueSeverity::Error,
+                    "unknown_assertion_column",
+                    format!("{base_path}/expr"),
+                    format!(
+                        "column '{}.{}.{}' not found for assertion",
+                        assertion.schema, assertion.table, column
+                    ),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+fn validate_value_formats(plan: &Plan, report: &mut ValidationReport) {
+    let Some(formats) = plan
+        .global
+        .as_ref()
+        .and_then(|global| global.formats.as_ref())
+    else {
+        return;
+    };
+
+    let patterns = [
+        ("date_format", &formats.date_format),
+        ("time_format", &formats.time_format),
+        ("timestamp_format", &formats.timestamp_format),
+    ];
+    for (field, pattern) in patterns {
+        let Some(pattern) = pattern else {
+            continue;
+        };
+        let invalid = pattern.is_empty()
+            || StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error));
+        if invalid {
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "invalid_value_format",
+                format!("/global/formats/{field}"),
+                format!("'{pattern}' is not a valid strftime pattern"),
+                Some("use chrono strftime specifiers such as %d/%m/%Y".to_string()),
+            ));
+        }
+    }
+
+    if let Some(separator) = formats.decimal_separator
+        && (separator.is_ascii_digit()
+            || matches!(separator, '-' | '+' | 'e' | 'E')
+            || separator.is_control())
+    {
+        report.push_error(ValidationIssue::new(
+            IssueSeverity::Error,
+            "invalid_decimal_separator",
+            "/global/formats/decimal_separator",
+            format!(
+                "'{}' cannot be used as decimal separator",
+                separator.escape_default()
+            ),
+            Some("use '.' or ','".to_string()),
+        ));
+    }
+}
+
 fn table_has_foreign_keys(table: &TableInfo) -> bool {
     table
         .constraints
@@ -822,6 +1337,11 @@ fn build_schema_index(schema: &DatabaseSchema) -> SchemaIndex {
             tables.insert(
                 table.name.clone(),
                 TableInfo {
+                    kind: table.kind.clone(),
+                    foreign_server: table
+                        .foreign_table
+                        .as_ref()
+                        .map(|source| source.server.clone()),
                     columns,
                     constraints: table.constraints.clone(),
                 },
@@ -850,6 +1370,8 @@ struct SchemaTables {
 }
 
 struct TableInfo {
+    kind: TableKind,
+    foreign_server: Option<String>,
     columns: HashMap<String, ColumnInfo>,
     constraints: Vec<Constraint>,
 }
@@ -0,0 +1,79 @@
+//! Browser exports (behind the `wasm` feature).
+//!
+//! A web UI validates plan.json edits with the same rules as the CLI.
+//! Documents go in and reports come out as JSON strings; a report is
+//! `{"ok": bool, "errors": [...], "warnings": [...]}` with the issues of
+//! `datalchemy plan validate`.
+
+use serde_json::{Value, json};
+use wasm_bindgen::prelude::*;
+
+use crate::errors::{IssueSeverity, ValidationIssue, ValidationReport};
+use crate::schema::plan_json_schema;
+
+/// JSON Schema of plan.json, for editor completion.
+#[wasm_bindgen(js_name = planJsonSchema)]
+pub fn plan_json_schema_string() -> String {
+    serde_json::to_string(&plan_json_schema()).unwrap_or_default()
+}
+
+/// Structural validation of a plan.json document against the plan JSON Schema.
+#[wasm_bindgen(js_name = validatePlanJson)]
+pub fn validate_plan_json(plan_json: &str) -> String {
+    let report = parse("plan", plan_json).and_then(|plan| {
+        crate::validate::validate_plan_json(&plan, &schema_value()?)
+            .map_err(|err| invalid("schema_validation_error", err.to_string()))
+    });
+    report_json(report)
+}
+
+/// Structural validation, then the plan against a schema.json document.
+#[wasm_bindgen(js_name = validatePlan)]
+pub fn validate_plan(plan_json: &str, schema_json: &str) -> String {
+    let report = parse("plan", plan_json).and_then(|plan| {
+        let schema = serde_json::from_value(parse("schema", schema_json)?)
+            .map_err(|err| invalid("invalid_schema_json", err.to_string()))?;
+        Ok(
+            match crate::validate::validate_plan(&plan, &schema_value()?, &schema) {
+                Ok(validated) => ValidationReport {
+                    errors: Vec::new(),
+                    warnings: validated.warnings,
+                },
+                Err(report) => report,
+            },
+        )
+    });
+    report_json(report)
+}
+
+fn parse(what: &str, text: &str) -> Result<Value, ValidationReport> {
+    serde_json::from_str(text)
+        .map_err(|err| invalid(&format!("invalid_{what}_json"), err.to_string()))
+}
+
+fn schema_value() -> Result<Value, ValidationReport> {
+    serde_json::to_value(plan_json_schema())
+        .map_err(|err| invalid("schema_validation_error", err.to_string()))
+}
+
+fn invalid(code: &str, message: String) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    report.push_error(ValidationIssue::new(
+        IssueSeverity::Error,
+        code,
+        "/",
+        message,
+        None,
+    ));
+    report
+}
+
+fn report_json(report: Result<ValidationReport, ValidationReport>) -> String {
+    let report = report.unwrap_or_else(|report| report);
+    json!({
+        "ok": report.is_ok(),
+        "errors": report.errors,
+        "warnings": report.warnings,
+    })
+    .to_string()
+}
@@ -1,5 +1,11 @@
-use datalchemy_core::DatabaseSchema;
-use datalchemy_plan::{validate_plan, validate_plan_json};
+use datalchemy_core::{
+    Constraint, DatabaseSchema, ExclusionConstraint, ExclusionElement, ForeignTableSource,
+    RewriteRule, SchemaBuilder, TableBuilder, TableEvent, TableKind, Trigger, TriggerTiming,
+};
+use datalchemy_plan::{
+    Plan, unsupported_rule_candidates, validate_plan, validate_plan_against_schema,
+    validate_plan_json,
+};
 use std::fs;
 use std::path::Path;
 
@@ -31,3 +37,405 @@ fn minimal_plan_validates_against_schema() {
         .expect("plan validation should succeed");
     assert!(validated.warnings.is_empty(), "unexpected warnings");
 }
+
+#[test]
+fn fixed_width_rule_is_validated_against_schema() {
+    let plan_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    let mut plan: Plan = serde_json::from_value(load_json(&plan_path)).expect("parse plan");
+    let schema: DatabaseSchema =
+        serde_json::from_value(load_json(&schema_path)).expect("parse schema.json");
+    plan.rules.push(
+        serde_json::from_value(serde_json::json!({
+            "type": "fixed_width",
+            "schema": "crm",
+            "table": "usuarios",
+            "columns": [
+                { "column": "id", "width": 36 },
+                { "column": "nome", "width": 0 },
+                { "column": "missing", "width": 10, "align": "right", "pad": "0" }
+            ]
+        }))
+        .expect("parse fixed_width rule"),
+    );
+
+    let report = validate_plan_against_schema(&plan, &schema);
+    let codes: Vec<&str> = report
+        .errors
+        .iter()
+        .map(|issue| issue.code.as_str())
+        .collect();
+    assert_eq!(
+        codes,
+        vec!["invalid_fixed_width", "unknown_fixed_width_column"]
+    );
+}
+
+#[test]
+fn output_partition_rule_requires_known_column() {
+    let plan_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    let mut plan: Plan = serde_json::from_value(load_json(&plan_path)).expect("parse plan");
+    let schema: DatabaseSchema =
+        serde_json::from_value(load_json(&schema_path)).expect("parse schema.json");
+    plan.rules.push(
+        serde_json::from_value(serde_json::json!({
+            "type": "output_partition",
+            "schema": "crm",
+            "table": "usuarios",
+            "column": "dt"
+        }))
+        .expect("parse output_partition rule"),
+    );
+
+    let report = validate_plan_against_schema(&plan, &schema);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].code, "unknown_partition_column");
+}
+
+#[test]
+fn entity_key_must_be_a_column_of_the_table() {
+    let plan_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    let mut plan: Plan = serde_json::from_value(load_json(&plan_path)).expect("parse plan");
+    let schema: DatabaseSchema =
+        serde_json::from_value(load_json(&schema_path)).expect("parse schema.json");
+    for (column, key) in [
+        ("nome", "id"),
+        ("telefone", "cliente_id"),
+        ("sobrenome", "sobrenome"),
+    ] {
+        plan.rules.push(
+            serde_json::from_value(serde_json::json!({
+                "type": "column_generator",
+                "schema": "crm",
+                "table": "contatos",
+                "column": column,
+                "generator": "semantic.br.name",
+                "entity": { "name": "contato", "key": key }
+            }))
+            .expect("parse column_generator rule"),
+        );
+    }
+
+    let report = validate_plan_against_schema(&plan, &schema);
+    let paths: Vec<(&str, &str)> = report
+        .errors
+        .iter()
+        .map(|issue| (issue.code.as_str(), issue.path.as_str()))
+        .collect();
+    let base = plan.rules.len() - 2;
+    assert_eq!(
+        paths,
+        [
+            (
+                "invalid_entity",
+                format!("/rules/{base}/entity/key").as_str()
+            ),
+            (
+                "invalid_entity",
+                format!("/rules/{}/entity/key", base + 1).as_str()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn value_formats_are_validated() {
+    let plan_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    let mut plan_json = load_json(&plan_path);
+    plan_json["global"] = serde_json::json!({
+        "formats": {
+            "date_format": "%d/%m/%Y",
+            "timestamp_format": "%Y-%m-%d %Q",
+            "boolean": "t_f",
+            "decimal_separator": "5"
+        }
+    });
+    let plan: Plan = serde_json::from_value(plan_json).expect("parse plan");
+    let schema: DatabaseSchema =
+        serde_json::from_value(load_json(&schema_path)).expect("parse schema.json");
+
+    let report = validate_plan_against_schema(&plan, &schema);
+    let paths: Vec<&str> = report
+        .errors
+        .iter()
+        .map(|issue| issue.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "/global/formats/timestamp_format",
+            "/global/formats/decimal_separator"
+        ]
+    );
+}
+
+#[test]
+fn assertions_are_parsed_and_checked_against_schema() {
+    let plan_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../plans/examples/minimal.plan.json");
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    let mut plan: Plan = serde_json::from_value(load_json(&plan_path)).expect("parse plan");
+    let schema: DatabaseSchema =
+        serde_json::from_value(load_json(&schema_path)).expect("parse schema.json");
+    plan.assertions = serde_json::from_value(serde_json::json!([
+        {
+            "schema": "crm",
+            "table": "usuarios",
+            "expr": "count(*) >= 10 and (null_rate(telefone) <= 0.2 or count(distinct email) = count(*))"
+        },
+        { "schema": "crm", "table": "missing", "expr": "count(*) > 0" },
+        { "schema": "crm", "table": "usuarios", "expr": "avg(salario) > 0" },
+        { "schema": "crm", "table": "usuarios", "expr": "count(*) >" }
+    ]))
+    .expect("parse assertions");
+
+    let report = validate_plan_against_schema(&plan, &schema);
+    let codes: Vec<(&str, &str)> = report
+        .errors
+        .iter()
+        .map(|issue| (issue.code.as_str(), issue.path.as_str()))
+        .collect();
+    assert_eq!(
+        codes,
+        vec![
+            ("unknown_assertion_table", "/assertions/1/table"),
+            ("unknown_assertion_column", "/assertions/2/expr"),
+            ("invalid_assertion_expr", "/assertions/3/expr"),
+        ]
+    );
+}
+
+#[test]
+fn unenforced_constraints_are_reported_until_recorded() {
+    let mut schema = SchemaBuilder::new()
+        .table(
+            "agenda",
+            TableBuilder::new("reservas")
+                .column("id", "integer")
+                .column("sala", "integer")
+                .column("periodo", "tsrange")
+                .primary_key(["id"])
+                .index(["sala"], true),
+        )
+        .build()
+        .expect("schema");
+    let table = &mut schema.schemas[0].tables[0];
+    table
+        .constraints
+        .push(Constraint::Exclusion(ExclusionConstraint {
+            name: Some("reservas_sala_periodo_excl".to_string()),
+            method: "gist".to_string(),
+            elements: vec![
+                ExclusionElement {
+                    expression: "sala".to_string(),
+                    operator: "=".to_string(),
+                },
+                ExclusionElement {
+                    expression: "periodo".to_string(),
+                    operator: "&&".to_string(),
+                },
+            ],
+            predicate: None,
+            is_deferrable: false,
+            initially_deferred: false,
+        }));
+    let unique_index = table
+        .indexes
+        .iter_mut()
+        .find(|index| !index.is_primary)
+        .expect("unique index");
+    unique_index.predicate = Some("sala IS NOT NULL".to_string());
+    let unique_index = unique_index.name.clone();
+
+    let mut plan: Plan = serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 1,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "agenda", "table": "reservas", "rows": 10 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let candidates = unsupported_rule_candidates(&plan, &schema);
+    let descriptions: Vec<&str> = candidates
+        .iter()
+        .map(|rule| rule.description.as_str())
+        .collect();
+    assert_eq!(
+        descriptions,
+        [
+            "exclusion constraint reservas_sala_periodo_excl".to_string(),
+            format!("unique index {unique_index}"),
+        ]
+    );
+
+    let warnings = |plan: &Plan| {
+        validate_plan_against_schema(plan, &schema)
+            .warnings
+            .into_iter()
+            .filter(|issue| issue.code == "unenforced_constraint")
+            .count()
+    };
+    assert_eq!(warnings(&plan), 2);
+    plan.rules_unsupported = candidates[..1].to_vec();
+    assert_eq!(warnings(&plan), 1);
+    plan.rules_unsupported = candidates;
+    assert_eq!(warnings(&plan), 0);
+}
+
+#[test]
+fn insert_triggers_and_rules_are_reported() {
+    let mut schema = SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .primary_key(["id"])
+                .trigger(
+                    "pedidos_auditoria",
+                    TriggerTiming::After,
+                    [TableEvent::Insert, TableEvent::Update],
+                    "vendas.auditar",
+                )
+                .trigger(
+                    "pedidos_exclusao",
+                    TriggerTiming::Before,
+                    [TableEvent::Delete],
+                    "vendas.bloquear",
+                ),
+        )
+        .build()
+        .expect("schema");
+    let table = &mut schema.schemas[0].tables[0];
+    table.rules.push(RewriteRule {
+        name: "pedidos_arquivo".to_string(),
+        event: TableEvent::Insert,
+        instead: true,
+    });
+    table.triggers.push(Trigger {
+        name: "pedidos_desligado".to_string(),
+        enabled: false,
+        ..table.triggers[0].clone()
+    });
+
+    let mut plan: Plan = serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 1,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [{ "schema": "vendas", "table": "pedidos", "rows": 10 }],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let candidates = unsupported_rule_candidates(&plan, &schema);
+    let descriptions: Vec<&str> = candidates
+        .iter()
+        .map(|rule| rule.description.as_str())
+        .collect();
+    assert_eq!(
+        descriptions,
+        ["trigger pedidos_auditoria", "rule pedidos_arquivo"],
+        "delete-only and disabled triggers do not fire on insert"
+    );
+    assert!(
+        candidates[0]
+            .reason
+            .contains("after insert (vendas.auditar)")
+    );
+
+    let warnings = |plan: &Plan| {
+        validate_plan_against_schema(plan, &schema)
+            .warnings
+            .into_iter()
+            .filter(|issue| issue.code == "insert_side_effect")
+            .count()
+    };
+    assert_eq!(warnings(&plan), 2);
+    plan.rules_unsupported = candidates;
+    assert_eq!(warnings(&plan), 0);
+}
+
+#[test]
+fn views_and_foreign_tables_are_not_generation_targets() {
+    let mut schema = SchemaBuilder::new()
+        .table(
+            "vendas",
+            TableBuilder::new("pedidos")
+                .column("id", "integer")
+                .primary_key(["id"]),
+        )
+        .table(
+            "vendas",
+            TableBuilder::new("totais")
+                .kind(TableKind::MaterializedView)
+                .column("pedidos", "bigint")
+                .view_definition(
+                    "SELECT count(*) AS pedidos FROM vendas.pedidos",
+                    ["pedidos"],
+                ),
+        )
+        .table(
+            "vendas",
+            TableBuilder::new("cotacoes")
+                .kind(TableKind::ForeignTable)
+                .column("moeda", "text"),
+        )
+        .build()
+        .expect("schema");
+    schema.schemas[0].tables[2].foreign_table = Some(ForeignTableSource {
+        server: "mercado".to_string(),
+        wrapper: "postgres_fdw".to_string(),
+        options: Default::default(),
+        server_options: Default::default(),
+    });
+    let plan: Plan = serde_json::from_value(serde_json::json!({
+        "plan_version": "0.2",
+        "seed": 1,
+        "schema_ref": { "schema_version": "0.2", "engine": "postgres" },
+        "targets": [
+            { "schema": "vendas", "table": "pedidos", "rows": 10 },
+            { "schema": "vendas", "table": "totais", "rows": 10 },
+            { "schema": "vendas", "table": "cotacoes", "rows": 10 }
+        ],
+        "rules": []
+    }))
+    .expect("plan");
+
+    let report = validate_plan_against_schema(&plan, &schema);
+    let errors: Vec<(&str, &str)> = report
+        .errors
+        .iter()
+        .map(|issue| (issue.code.as_str(), issue.path.as_str()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("target_is_view", "/targets/1/table"),
+            ("target_not_insertable", "/targets/2/table")
+        ]
+    );
+    assert!(
+        report.errors[0]
+            .hint
+            .as_deref()
+            .is_some_and(|hint| hint.contains("refresh"))
+    );
+    assert!(report.errors[1].message.contains("server 'mercado'"));
+}
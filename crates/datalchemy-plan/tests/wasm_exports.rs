@@ -0,0 +1,35 @@
+#![cfg(feature = "wasm")]
+
+use std::fs;
+use std::path::Path;
+
+use datalchemy_plan::wasm::{plan_json_schema_string, validate_plan, validate_plan_json};
+use serde_json::Value;
+
+fn read(path: &str) -> String {
+    fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(path)).expect("read json")
+}
+
+fn report(json: String) -> Value {
+    serde_json::from_str(&json).expect("report json")
+}
+
+#[test]
+fn validates_documents_as_strings() {
+    let plan = read("../../plans/examples/minimal.plan.json");
+    let schema = read("../datalchemy-introspect/tests/golden/postgres_minimal.schema.json");
+
+    assert_eq!(report(validate_plan_json(&plan))["ok"], true);
+    assert_eq!(report(validate_plan(&plan, &schema))["ok"], true);
+
+    let unknown_table = plan.replacen("\"usuarios\"", "\"nope\"", 1);
+    let result = report(validate_plan(&unknown_table, &schema));
+    assert_eq!(result["ok"], false);
+    assert!(!result["errors"].as_array().expect("errors").is_empty());
+
+    let result = report(validate_plan_json("{"));
+    assert_eq!(result["errors"][0]["code"], "invalid_plan_json");
+
+    let schema: Value = serde_json::from_str(&plan_json_schema_string()).expect("schema json");
+    assert_eq!(schema["title"], "Plan");
+}
@@ -0,0 +1,181 @@
+//! The operations behind the Python functions, on plain Rust types so they
+//! can be tested without an interpreter.
+
+use std::collections::HashMap;
+
+use datalchemy_core::{Column, DatabaseSchema, Table};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_introspect::{IntrospectOptions, introspect_sqlite_with_options};
+use datalchemy_plan::{
+    IssueSeverity, ValidationIssue, ValidationReport, plan_json_schema, validate_plan,
+};
+use serde_json::Value;
+use sqlx::sqlite::SqlitePoolOptions;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Core(#[from] datalchemy_core::Error),
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid plan: {}", issue_summary(.0))]
+    InvalidPlan(ValidationReport),
+    #[error(transparent)]
+    Generation(Box<GenerationError>),
+    #[error("runtime error: {0}")]
+    Runtime(String),
+}
+
+impl From<GenerationError> for Error {
+    fn from(err: GenerationError) -> Self {
+        Self::Generation(Box::new(err))
+    }
+}
+
+impl Error {
+    /// Code shared with the CLI (`datalchemy explain <code>`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Core(_) => "DL102",
+            Self::Database(_) => "DL103",
+            Self::Json(_) => "DL104",
+            Self::InvalidPlan(_) => "DL110",
+            Self::Generation(err) => err.code(),
+            Self::Runtime(_) => "DL111",
+        }
+    }
+}
+
+fn issue_summary(report: &ValidationReport) -> String {
+    let mut summary = report
+        .errors
+        .iter()
+        .take(3)
+        .map(|issue| format!("{} {}: {}", issue.code, issue.path, issue.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    if report.errors.len() > 3 {
+        summary.push_str(&format!(" (+{} more)", report.errors.len() - 3));
+    }
+    summary
+}
+
+/// Schema of SQLite DDL, applied to an in-memory database and introspected.
+pub fn introspect_ddl(ddl: &str, include_indexes: bool) -> Result<DatabaseSchema, Error> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| Error::Runtime(err.to_string()))?;
+    runtime.block_on(async {
+        // One connection: every `:memory:` connection is a separate database.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await?;
+        sqlx::raw_sql(ddl).execute(&pool).await?;
+        let options = IntrospectOptions {
+            include_indexes,
+            ..IntrospectOptions::default()
+        };
+        Ok(introspect_sqlite_with_options(&pool, options).await?)
+    })
+}
+
+/// Structural and schema validation of `plan_json`: errors and warnings.
+pub fn validation_report(plan_json: &Value, schema: &DatabaseSchema) -> ValidationReport {
+    let plan_schema = match serde_json::to_value(plan_json_schema()) {
+        Ok(plan_schema) => plan_schema,
+        Err(err) => {
+            let mut report = ValidationReport::default();
+            report.push_error(ValidationIssue::new(
+                IssueSeverity::Error,
+                "schema_validation_error",
+                "/",
+                err.to_string(),
+                None,
+            ));
+            return report;
+        }
+    };
+    match validate_plan(plan_json, &plan_schema, schema) {
+        Ok(validated) => ValidationReport {
+            errors: Vec::new(),
+            warnings: validated.warnings,
+        },
+        Err(report) => report,
+    }
+}
+
+/// Rows generated for one table; values follow `columns`.
+#[derive(Debug, Clone)]
+pub struct GeneratedTable {
+    pub schema: String,
+    pub table: String,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<GeneratedValue>>,
+}
+
+/// Every table of the plan, in generation (FK-safe insert) order. Nothing
+/// is written to disk.
+pub fn generate_tables(
+    schema: &DatabaseSchema,
+    plan_json: &Value,
+) -> Result<Vec<GeneratedTable>, Error> {
+    let plan_schema = serde_json::to_value(plan_json_schema())?;
+    let plan = validate_plan(plan_json, &plan_schema, schema)
+        .map_err(Error::InvalidPlan)?
+        .plan;
+    let mut sink = MemorySink::default();
+    GenerationEngine::new(GenerateOptions::default()).generate_to_sink(schema, &plan, &mut sink)?;
+    Ok(sink.tables)
+}
+
+#[derive(Default)]
+struct MemorySink {
+    tables: Vec<GeneratedTable>,
+}
+
+impl TableSink for MemorySink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        let mut columns = table.columns.clone();
+        columns.sort_by_key(|column| column.ordinal_position);
+        let rows: Vec<Vec<GeneratedValue>> = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        row.get(&column.name.to_lowercase())
+                            .cloned()
+                            .unwrap_or(GeneratedValue::Null)
+                    })
+                    .collect()
+            })
+            .collect();
+        let output = TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: columns.iter().map(|column| column.name.clone()).collect(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        };
+        self.tables.push(GeneratedTable {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            columns,
+            rows,
+        });
+        Ok(output)
+    }
+}
@@ -0,0 +1,184 @@
+//! Python bindings: the `datalchemy` module.
+//!
+//! Schemas and plans cross the boundary as JSON documents (a `dict` or a JSON
+//! `str`), so they are the same files the CLI reads and writes:
+//!
+//! - `introspect_ddl(ddl, include_indexes=True)`: schema of SQLite DDL,
+//!   introspected from an in-memory database (no server needed).
+//! - `validate_plan(plan, schema)`: `{"ok", "errors", "warnings"}`.
+//! - `generate(schema, plan)`: `{"schema.table": [row dict, ...]}`.
+//! - `generate_batches(schema, plan, batch_size=10000)`: column-oriented
+//!   chunks, `pandas.DataFrame(batch["columns"])` per batch.
+//!
+//! Dates and timestamps come back as `datetime` values; failures raise
+//! `DatalchemyError` with the CLI error code (`error[DL110]: ...`).
+
+pub mod api;
+
+use datalchemy_core::DatabaseSchema;
+use datalchemy_generate::generators::GeneratedValue;
+use pyo3::IntoPyObjectExt;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api::Error;
+
+create_exception!(
+    datalchemy,
+    DatalchemyError,
+    PyException,
+    "Error raised by datalchemy; the message starts with its error code."
+);
+
+fn py_err(err: Error) -> PyErr {
+    DatalchemyError::new_err(format!("error[{}]: {err}", err.code()))
+}
+
+/// JSON document from a `dict`/`list` or a JSON string.
+fn to_json(document: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let text = match document.downcast::<PyString>() {
+        Ok(text) => text.to_str()?.to_string(),
+        Err(_) => document
+            .py()
+            .import("json")?
+            .call_method1("dumps", (document,))?
+            .extract()?,
+    };
+    serde_json::from_str(&text).map_err(|err| py_err(err.into()))
+}
+
+fn to_schema(document: &Bound<'_, PyAny>) -> PyResult<DatabaseSchema> {
+    serde_json::from_value(to_json(document)?).map_err(|err| py_err(err.into()))
+}
+
+fn to_python(py: Python<'_>, value: &impl Serialize) -> PyResult<PyObject> {
+    let text = serde_json::to_string(value).map_err(|err| py_err(err.into()))?;
+    Ok(py.import("json")?.call_method1("loads", (text,))?.unbind())
+}
+
+fn value_to_python<'py>(py: Python<'py>, value: &GeneratedValue) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        GeneratedValue::Null => Ok(py.None().into_bound(py)),
+        GeneratedValue::Bool(value) => value.into_bound_py_any(py),
+        GeneratedValue::Int(value) => value.into_bound_py_any(py),
+        GeneratedValue::Float(value) => value.into_bound_py_any(py),
+        GeneratedValue::Text(value) | GeneratedValue::Uuid(value) => value.into_bound_py_any(py),
+        GeneratedValue::Date(value) => value.into_bound_py_any(py),
+        GeneratedValue::Time(value) => value.into_bound_py_any(py),
+        GeneratedValue::Timestamp(value) => value.into_bound_py_any(py),
+    }
+}
+
+/// Schema (as a dict) of SQLite DDL, applied to an in-memory database.
+#[pyfunction]
+#[pyo3(signature = (ddl, include_indexes = true))]
+fn introspect_ddl(py: Python<'_>, ddl: &str, include_indexes: bool) -> PyResult<PyObject> {
+    let schema = py
+        .allow_threads(|| api::introspect_ddl(ddl, include_indexes))
+        .map_err(py_err)?;
+    to_python(py, &schema)
+}
+
+/// Validation of `plan` against `schema`: `{"ok", "errors", "warnings"}`.
+#[pyfunction]
+fn validate_plan(
+    py: Python<'_>,
+    plan: &Bound<'_, PyAny>,
+    schema: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    let plan = to_json(plan)?;
+    let schema = to_schema(schema)?;
+    let report = py.allow_threads(|| api::validation_report(&plan, &schema));
+    let result = to_python(py, &report)?;
+    result.bind(py).set_item("ok", report.is_ok())?;
+    Ok(result)
+}
+
+/// Rows of every generated table, keyed by `schema.table`, as dicts.
+#[pyfunction]
+fn generate<'py>(
+    py: Python<'py>,
+    schema: &Bound<'py, PyAny>,
+    plan: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let plan = to_json(plan)?;
+    let schema = to_schema(schema)?;
+    let tables = py
+        .allow_threads(|| api::generate_tables(&schema, &plan))
+        .map_err(py_err)?;
+    let result = PyDict::new(py);
+    for table in &tables {
+        let rows = PyList::empty(py);
+        for row in &table.rows {
+            let dict = PyDict::new(py);
+            for (column, value) in table.columns.iter().zip(row) {
+                dict.set_item(&column.name, value_to_python(py, value)?)?;
+            }
+            rows.append(dict)?;
+        }
+        result.set_item(format!("{}.{}", table.schema, table.table), rows)?;
+    }
+    Ok(result)
+}
+
+/// Generated rows in column-oriented chunks of at most `batch_size` rows:
+/// `[{"schema", "table", "offset", "columns": {name: [values]}}]`, in
+/// generation order.
+#[pyfunction]
+#[pyo3(signature = (schema, plan, batch_size = 10_000))]
+fn generate_batches<'py>(
+    py: Python<'py>,
+    schema: &Bound<'py, PyAny>,
+    plan: &Bound<'py, PyAny>,
+    batch_size: usize,
+) -> PyResult<Bound<'py, PyList>> {
+    if batch_size == 0 {
+        return Err(DatalchemyError::new_err("batch_size must be positive"));
+    }
+    let plan = to_json(plan)?;
+    let schema = to_schema(schema)?;
+    let tables = py
+        .allow_threads(|| api::generate_tables(&schema, &plan))
+        .map_err(py_err)?;
+    let batches = PyList::empty(py);
+    for table in &tables {
+        // An empty table still yields one batch, so its columns are known.
+        let chunks: Vec<&[Vec<GeneratedValue>]> = if table.rows.is_empty() {
+            vec![&[]]
+        } else {
+            table.rows.chunks(batch_size).collect()
+        };
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let columns = PyDict::new(py);
+            for (position, column) in table.columns.iter().enumerate() {
+                let values = PyList::empty(py);
+                for row in chunk {
+                    values.append(value_to_python(py, &row[position])?)?;
+                }
+                columns.set_item(&column.name, values)?;
+            }
+            let batch = PyDict::new(py);
+            batch.set_item("schema", &table.schema)?;
+            batch.set_item("table", &table.table)?;
+            batch.set_item("offset", index * batch_size)?;
+            batch.set_item("columns", columns)?;
+            batches.append(batch)?;
+        }
+    }
+    Ok(batches)
+}
+
+#[pymodule]
+#[pyo3(name = "datalchemy")]
+fn datalchemy_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("DatalchemyError", module.py().get_type::<DatalchemyError>())?;
+    module.add_function(wrap_pyfunction!(introspect_ddl, module)?)?;
+    module.add_function(wrap_pyfunction!(validate_plan, module)?)?;
+    module.add_function(wrap_pyfunction!(generate, module)?)?;
+    module.add_function(wrap_pyfunction!(generate_batches, module)?)?;
+    Ok(())
+}
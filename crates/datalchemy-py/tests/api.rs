@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use datalchemy_core::{Constraint, DatabaseSchema};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_py::api::{Error, generate_tables, introspect_ddl, validation_report};
+use serde_json::{Value, json};
+
+fn load_json(path: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path);
+    serde_json::from_str(&std::fs::read_to_string(&path).expect("read json")).expect("parse json")
+}
+
+fn golden_schema() -> DatabaseSchema {
+    serde_json::from_value(load_json(
+        "../datalchemy-introspect/tests/golden/postgres_minimal.schema.json",
+    ))
+    .expect("parse golden schema")
+}
+
+#[test]
+fn introspects_sqlite_ddl_offline() {
+    let schema = introspect_ddl(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE);
+         CREATE TABLE orders (
+             id INTEGER PRIMARY KEY,
+             user_id INTEGER NOT NULL REFERENCES users(id),
+             total REAL
+         );",
+        true,
+    )
+    .expect("introspect ddl");
+
+    assert_eq!(schema.engine, "sqlite");
+    let tables = &schema.schemas[0].tables;
+    assert_eq!(
+        tables
+            .iter()
+            .map(|table| table.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["orders", "users"]
+    );
+    assert!(
+        tables[0]
+            .constraints
+            .iter()
+            .any(|constraint| matches!(constraint, Constraint::ForeignKey(fk) if fk.referenced_table == "users"))
+    );
+
+    let err = introspect_ddl("CREATE TABLE broken (", true).expect_err("bad ddl");
+    assert_eq!(err.code(), "DL103");
+}
+
+#[test]
+fn reports_plan_issues() {
+    let schema = golden_schema();
+    let plan = load_json("../../plans/examples/minimal.plan.json");
+    assert!(validation_report(&plan, &schema).is_ok());
+
+    let mut broken = plan.clone();
+    broken["targets"][0]["table"] = json!("nope");
+    let report = validation_report(&broken, &schema);
+    assert!(!report.is_ok());
+
+    let err = generate_tables(&schema, &broken).expect_err("invalid plan");
+    assert!(matches!(err, Error::InvalidPlan(_)));
+    assert_eq!(err.code(), "DL110");
+}
+
+#[test]
+fn generates_rows_in_memory() {
+    let schema = golden_schema();
+    let plan = load_json("../../plans/examples/minimal.plan.json");
+    let tables = generate_tables(&schema, &plan).expect("generate");
+
+    let usuarios = tables
+        .iter()
+        .find(|table| table.table == "usuarios")
+        .expect("usuarios generated");
+    assert_eq!(usuarios.rows.len(), 50);
+    let email = usuarios
+        .columns
+        .iter()
+        .position(|column| column.name == "email")
+        .expect("email column");
+    assert!(
+        usuarios
+            .rows
+            .iter()
+            .all(|row| matches!(&row[email], GeneratedValue::Text(value) if value.contains('@')))
+    );
+    assert!(
+        usuarios
+            .rows
+            .iter()
+            .all(|row| row.len() == usuarios.columns.len())
+    );
+}
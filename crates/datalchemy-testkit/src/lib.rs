@@ -0,0 +1,31 @@
+//! Test support for code built on the Datalchemy contracts.
+//!
+//! Adapter authors and plugin writers fuzz their integrations with the
+//! [proptest] strategies of this crate: [`database_schema`] yields random
+//! schemas that pass [`validate_schema`](datalchemy_core::validate_schema)
+//! (tables spread over schemas, primary keys, unique columns, acyclic foreign
+//! keys, enum columns), and [`plan_for`] random plans that pass
+//! [`validate_plan`](datalchemy_plan::validate_plan) against a schema. The
+//! [`roundtrip`] checks assert that a value survives its JSON contract.
+//!
+//! ```
+//! use datalchemy_testkit::{check_json_roundtrip, check_plan, schema_and_plan};
+//! use proptest::test_runner::{Config, TestRunner};
+//!
+//! // In a test suite: `proptest! { #[test] fn ..((schema, plan) in schema_and_plan()) { .. } }`.
+//! let mut runner = TestRunner::new(Config::with_cases(8));
+//! runner
+//!     .run(&schema_and_plan(), |(schema, plan)| {
+//!         check_plan(&schema, &plan)?;
+//!         check_json_roundtrip(&plan)
+//!     })
+//!     .expect("plans keep their contract");
+//! ```
+
+pub mod plan;
+pub mod roundtrip;
+pub mod schema;
+
+pub use plan::{PlanConfig, plan_for, plan_for_with, schema_and_plan};
+pub use roundtrip::{check_json_roundtrip, check_plan, check_schema, json_roundtrip};
+pub use schema::{SchemaConfig, database_schema, database_schema_with};
@@ -0,0 +1,157 @@
+//! Strategies for [`Plan`] values valid against a schema.
+//!
+//! Plans target a non-empty subset of the schema's tables (in schema order)
+//! with a random seed and row counts, and may enforce the primary key policy
+//! and the `respect` foreign key strategy of their targets. They never carry
+//! column generators, so any generator registry accepts them.
+
+use datalchemy_core::{Constraint, DatabaseSchema};
+use datalchemy_plan::{
+    ConstraintKind, ConstraintMode, ConstraintPolicyRule, ForeignKeyMode, ForeignKeyStrategyRule,
+    PLAN_VERSION, Plan, PlanGlobal, Rule, SchemaRef, Target,
+};
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+use crate::schema::{SchemaConfig, database_schema_with};
+
+/// Limits of the generated plans.
+#[derive(Debug, Clone)]
+pub struct PlanConfig {
+    /// Rows per target, at least 1.
+    pub max_rows: u64,
+    /// Add constraint policy and foreign key strategy rules.
+    pub rules: bool,
+}
+
+impl Default for PlanConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: 50,
+            rules: true,
+        }
+    }
+}
+
+/// `(schema, table, has foreign keys)` of every table.
+type TableRef = (String, String, bool);
+
+/// Valid plans for `schema` with the [default](PlanConfig::default) limits.
+///
+/// # Panics
+///
+/// When `schema` has no table.
+pub fn plan_for(schema: &DatabaseSchema) -> impl Strategy<Value = Plan> + use<> {
+    plan_for_with(schema, PlanConfig::default())
+}
+
+/// Valid plans for `schema` within `config`.
+///
+/// # Panics
+///
+/// When `schema` has no table.
+pub fn plan_for_with(
+    schema: &DatabaseSchema,
+    config: PlanConfig,
+) -> impl Strategy<Value = Plan> + use<> {
+    let tables: Vec<TableRef> = schema
+        .schemas
+        .iter()
+        .flat_map(|db_schema| {
+            db_schema.tables.iter().map(|table| {
+                let has_foreign_keys = table
+                    .constraints
+                    .iter()
+                    .any(|constraint| matches!(constraint, Constraint::ForeignKey(_)));
+                (db_schema.name.clone(), table.name.clone(), has_foreign_keys)
+            })
+        })
+        .collect();
+    assert!(!tables.is_empty(), "plans need a schema with tables");
+    let schema_ref = SchemaRef {
+        schema_version: schema.schema_version.clone(),
+        schema_fingerprint: schema.schema_fingerprint.clone(),
+        engine: schema.engine.clone(),
+    };
+    let count = tables.len();
+    let targets = subsequence(tables, 1..=count).prop_flat_map(move |targets| {
+        let rows = proptest::collection::vec(1..=config.max_rows.max(1), targets.len());
+        let rules =
+            proptest::collection::vec(any::<bool>(), targets.len()).prop_map(move |flags| {
+                if config.rules {
+                    flags
+                } else {
+                    vec![false; flags.len()]
+                }
+            });
+        (Just(targets), rows, rules)
+    });
+    (
+        any::<u64>(),
+        proptest::option::of(select(&["pt_BR", "en_US"][..])),
+        targets,
+    )
+        .prop_map(move |(seed, locale, (targets, rows, rules))| {
+            build_plan(schema_ref.clone(), seed, locale, &targets, &rows, &rules)
+        })
+}
+
+/// A valid schema and a valid plan for it.
+pub fn schema_and_plan() -> impl Strategy<Value = (DatabaseSchema, Plan)> {
+    database_schema_with(SchemaConfig::default()).prop_flat_map(|schema| {
+        let plan = plan_for(&schema);
+        (Just(schema), plan)
+    })
+}
+
+fn build_plan(
+    schema_ref: SchemaRef,
+    seed: u64,
+    locale: Option<&str>,
+    targets: &[TableRef],
+    rows: &[u64],
+    rules: &[bool],
+) -> Plan {
+    let mut plan_rules = Vec::new();
+    for ((schema, table, has_foreign_keys), with_rules) in targets.iter().zip(rules) {
+        if !with_rules {
+            continue;
+        }
+        plan_rules.push(Rule::ConstraintPolicy(ConstraintPolicyRule {
+            schema: schema.clone(),
+            table: table.clone(),
+            constraint: ConstraintKind::PrimaryKey,
+            mode: ConstraintMode::Enforce,
+        }));
+        if *has_foreign_keys {
+            plan_rules.push(Rule::ForeignKeyStrategy(ForeignKeyStrategyRule {
+                schema: schema.clone(),
+                table: table.clone(),
+                mode: ForeignKeyMode::Respect,
+            }));
+        }
+    }
+    Plan {
+        plan_version: PLAN_VERSION.to_string(),
+        seed,
+        schema_ref,
+        global: locale.map(|locale| PlanGlobal {
+            locale: Some(locale.to_string()),
+            formats: None,
+        }),
+        targets: targets
+            .iter()
+            .zip(rows)
+            .map(|((schema, table, _), rows)| Target {
+                schema: schema.clone(),
+                table: table.clone(),
+                rows: *rows,
+                strategy: None,
+            })
+            .collect(),
+        rules: plan_rules,
+        rules_unsupported: Vec::new(),
+        assertions: Vec::new(),
+        options: None,
+    }
+}
@@ -0,0 +1,63 @@
+//! Contract checks for use inside `proptest!` bodies (`check_*(..)?`).
+//!
+//! Failures are [`TestCaseError`]s, so proptest shrinks the input that
+//! broke the contract and reports it.
+
+use datalchemy_core::{DatabaseSchema, validate_schema};
+use datalchemy_plan::{Plan, plan_json_schema, validate_plan};
+use proptest::test_runner::TestCaseError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// `value` written to JSON and read back.
+pub fn json_roundtrip<T>(value: &T) -> Result<T, serde_json::Error>
+where
+    T: Serialize + DeserializeOwned,
+{
+    serde_json::from_value(serde_json::to_value(value)?)
+}
+
+/// Fail unless `value` reads back from its JSON into the same JSON.
+pub fn check_json_roundtrip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let json = to_json(value)?;
+    let back = json_roundtrip(value)
+        .map_err(|err| TestCaseError::fail(format!("json does not read back: {err}")))?;
+    let again = to_json(&back)?;
+    if json != again {
+        return Err(TestCaseError::fail(format!(
+            "json changed on round trip:\n before: {json}\n after:  {again}"
+        )));
+    }
+    Ok(())
+}
+
+/// Fail unless `schema` passes [`validate_schema`].
+pub fn check_schema(schema: &DatabaseSchema) -> Result<(), TestCaseError> {
+    validate_schema(schema).map_err(|err| TestCaseError::fail(format!("invalid schema: {err}")))
+}
+
+/// Fail unless `plan`, as JSON, passes [`validate_plan`] (plan JSON Schema
+/// and checks against `schema`).
+pub fn check_plan(schema: &DatabaseSchema, plan: &Plan) -> Result<(), TestCaseError> {
+    let plan_schema = serde_json::to_value(plan_json_schema())
+        .map_err(|err| TestCaseError::fail(format!("plan json schema: {err}")))?;
+    validate_plan(&to_json(plan)?, &plan_schema, schema)
+        .map(|_| ())
+        .map_err(|report| {
+            let issues: Vec<String> = report
+                .errors
+                .iter()
+                .map(|issue| format!("{} at {}: {}", issue.code, issue.path, issue.message))
+                .collect();
+            TestCaseError::fail(format!("invalid plan: {}", issues.join("; ")))
+        })
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value, TestCaseError> {
+    serde_json::to_value(value)
+        .map_err(|err| TestCaseError::fail(format!("json serialization: {err}")))
+}
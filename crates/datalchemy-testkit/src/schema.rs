@@ -0,0 +1,177 @@
+//! Strategies for [`DatabaseSchema`] values.
+//!
+//! Schemas are assembled with [`SchemaBuilder`], so their constraints, indexes
+//! and type metadata look like introspected ones. Identifiers carry a prefix
+//! (`s_` schemas, `t_` tables, `c_` columns) and never clash with SQL
+//! keywords; every table has an `id` primary key and a foreign key column is
+//! named `<parent>_id`. Shrinking drops tables and columns.
+
+use std::collections::BTreeMap;
+
+use datalchemy_core::{DatabaseSchema, SchemaBuilder, TableBuilder};
+use proptest::collection::{btree_map, btree_set, vec};
+use proptest::prelude::*;
+use proptest::sample::{Index, select};
+
+/// Labels of the `mood` enum created in every schema.
+const MOOD_LABELS: [&str; 3] = ["low", "mid", "high"];
+
+/// Column types drawn for non-key columns; `mood` is the schema's enum.
+const COLUMN_TYPES: [&str; 14] = [
+    "smallint",
+    "integer",
+    "bigint",
+    "numeric(12,2)",
+    "real",
+    "double precision",
+    "boolean",
+    "text",
+    "character varying(64)",
+    "date",
+    "time without time zone",
+    "timestamp without time zone",
+    "timestamp with time zone",
+    "mood",
+];
+
+/// Types a unique constraint is put on: wide enough for any row count.
+const UNIQUE_TYPES: [&str; 4] = ["integer", "bigint", "text", "character varying(64)"];
+
+/// Types of the `id` primary keys.
+const KEY_TYPES: [&str; 3] = ["integer", "bigint", "uuid"];
+
+/// Size limits of the generated schemas.
+#[derive(Debug, Clone)]
+pub struct SchemaConfig {
+    /// Schemas (namespaces) the tables are spread over, at least 1.
+    pub max_schemas: usize,
+    /// Tables, at least 1.
+    pub max_tables: usize,
+    /// Columns besides `id` and the foreign key column.
+    pub max_columns: usize,
+    /// Give tables a foreign key to an earlier table.
+    pub foreign_keys: bool,
+}
+
+impl Default for SchemaConfig {
+    fn default() -> Self {
+        Self {
+            max_schemas: 2,
+            max_tables: 6,
+            max_columns: 6,
+            foreign_keys: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TableSpec {
+    schema: Index,
+    key_type: &'static str,
+    columns: BTreeMap<String, (&'static str, bool)>,
+    unique: Option<Index>,
+    parent: Option<Index>,
+}
+
+/// Valid schemas with the [default](SchemaConfig::default) limits.
+pub fn database_schema() -> impl Strategy<Value = DatabaseSchema> {
+    database_schema_with(SchemaConfig::default())
+}
+
+/// Valid schemas within `config`.
+pub fn database_schema_with(config: SchemaConfig) -> impl Strategy<Value = DatabaseSchema> {
+    let schemas = btree_set("s_[a-z0-9]{1,6}", 1..=config.max_schemas.max(1));
+    let tables = btree_set("t_[a-z0-9]{1,8}", 1..=config.max_tables.max(1));
+    (schemas, tables)
+        .prop_flat_map(move |(schemas, tables)| {
+            let specs = vec(table_spec(config.max_columns), tables.len());
+            (
+                Just(schemas.into_iter().collect::<Vec<_>>()),
+                Just(tables.into_iter().collect::<Vec<_>>()),
+                specs,
+                Just(config.foreign_keys),
+            )
+        })
+        .prop_map(|(schemas, tables, specs, foreign_keys)| {
+            build_schema(&schemas, &tables, &specs, foreign_keys)
+        })
+}
+
+fn table_spec(max_columns: usize) -> impl Strategy<Value = TableSpec> {
+    (
+        any::<Index>(),
+        select(&KEY_TYPES[..]),
+        btree_map(
+            "c_[a-z0-9]{1,8}",
+            (select(&COLUMN_TYPES[..]), any::<bool>()),
+            0..=max_columns,
+        ),
+        proptest::option::of(any::<Index>()),
+        proptest::option::of(any::<Index>()),
+    )
+        .prop_map(|(schema, key_type, columns, unique, parent)| TableSpec {
+            schema,
+            key_type,
+            columns,
+            unique,
+            parent,
+        })
+}
+
+fn build_schema(
+    schemas: &[String],
+    tables: &[String],
+    specs: &[TableSpec],
+    foreign_keys: bool,
+) -> DatabaseSchema {
+    let mut builder = SchemaBuilder::new();
+    for schema in schemas {
+        builder = builder.enum_type(schema, "mood", MOOD_LABELS);
+    }
+    for (idx, (name, spec)) in tables.iter().zip(specs).enumerate() {
+        let schema = spec.schema.get(schemas);
+        let mut table = TableBuilder::new(name)
+            .column("id", spec.key_type)
+            .not_null();
+        for (column, (data_type, not_null)) in &spec.columns {
+            let data_type = match *data_type {
+                "mood" => format!("{schema}.mood"),
+                other => other.to_string(),
+            };
+            table = table.column(column, &data_type);
+            if *not_null {
+                table = table.not_null();
+            }
+        }
+        if foreign_keys
+            && idx > 0
+            && let Some(parent) = &spec.parent
+        {
+            let parent_idx = parent.index(idx);
+            let parent_name = &tables[parent_idx];
+            let parent_spec = &specs[parent_idx];
+            let column = format!("{parent_name}_id");
+            table = table.column(&column, parent_spec.key_type).foreign_key(
+                [column.as_str()],
+                &format!("{}.{parent_name}", parent_spec.schema.get(schemas)),
+                ["id"],
+            );
+        }
+        table = table.primary_key(["id"]);
+        let unique_columns: Vec<&String> = spec
+            .columns
+            .iter()
+            .filter(|(_, (data_type, _))| UNIQUE_TYPES.contains(data_type))
+            .map(|(column, _)| column)
+            .collect();
+        if let Some(unique) = &spec.unique
+            && !unique_columns.is_empty()
+        {
+            table = table.unique([unique.get(&unique_columns).as_str()]);
+        }
+        builder = builder.table(schema, table);
+    }
+    builder
+        .build()
+        .expect("generated schemas are valid by construction")
+}
@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, HashMap};
+
+use datalchemy_core::{Constraint, Table, insert_order};
+use datalchemy_generate::generators::GeneratedValue;
+use datalchemy_generate::{
+    GenerateOptions, GenerationEngine, GenerationError, TableOutput, TableSink,
+};
+use datalchemy_testkit::{
+    PlanConfig, SchemaConfig, check_json_roundtrip, check_plan, check_schema, database_schema,
+    database_schema_with, plan_for_with, schema_and_plan,
+};
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+
+/// Rows written per `schema.table`.
+#[derive(Default)]
+struct CountingSink {
+    rows: BTreeMap<String, u64>,
+}
+
+impl TableSink for CountingSink {
+    fn write_table(
+        &mut self,
+        schema_name: &str,
+        table: &Table,
+        rows: &[HashMap<String, GeneratedValue>],
+    ) -> Result<TableOutput, GenerationError> {
+        self.rows
+            .insert(format!("{schema_name}.{}", table.name), rows.len() as u64);
+        Ok(TableOutput {
+            schema: schema_name.to_string(),
+            table: table.name.clone(),
+            file_name: String::new(),
+            columns: Vec::new(),
+            bytes: 0,
+            rows: rows.len() as u64,
+        })
+    }
+}
+
+proptest! {
+    #[test]
+    fn schemas_are_valid_and_round_trip(schema in database_schema()) {
+        check_schema(&schema)?;
+        check_json_roundtrip(&schema)?;
+        let tables: usize = schema.schemas.iter().map(|db_schema| db_schema.tables.len()).sum();
+        prop_assert_eq!(
+            insert_order(&schema).map(|order| order.len()).ok(),
+            Some(tables),
+            "foreign keys are acyclic"
+        );
+    }
+
+    #[test]
+    fn plans_are_valid_and_round_trip((schema, plan) in schema_and_plan()) {
+        check_plan(&schema, &plan)?;
+        check_json_roundtrip(&plan)?;
+        prop_assert!(!plan.targets.is_empty());
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn generated_plans_generate_their_targets(
+        (schema, plan) in database_schema_with(SchemaConfig {
+            max_tables: 4,
+            ..Default::default()
+        })
+        .prop_flat_map(|schema| {
+            let plan = plan_for_with(&schema, PlanConfig { max_rows: 5, rules: true });
+            (Just(schema), plan)
+        })
+    ) {
+        let mut sink = CountingSink::default();
+        let report = GenerationEngine::new(GenerateOptions::default())
+            .generate_to_sink(&schema, &plan, &mut sink)
+            .map_err(|err| TestCaseError::fail(err.to_string()))?;
+        for target in &plan.targets {
+            let key = format!("{}.{}", target.schema, target.table);
+            prop_assert_eq!(sink.rows.get(&key), Some(&target.rows), "{}", key);
+        }
+        prop_assert_eq!(report.tables.len(), sink.rows.len());
+    }
+}
+
+#[test]
+fn schemas_without_foreign_keys_stay_flat() {
+    let mut runner = proptest::test_runner::TestRunner::deterministic();
+    let strategy = database_schema_with(SchemaConfig {
+        foreign_keys: false,
+        ..Default::default()
+    });
+    for _ in 0..32 {
+        let schema = strategy.new_tree(&mut runner).expect("schema").current();
+        let has_fk = schema
+            .schemas
+            .iter()
+            .flat_map(|db_schema| &db_schema.tables)
+            .flat_map(|table| &table.constraints)
+            .any(|constraint| matches!(constraint, Constraint::ForeignKey(_)));
+        assert!(!has_fk);
+    }
+}